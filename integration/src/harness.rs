@@ -65,10 +65,40 @@ pub fn endpoint_url(port: u16, path: &str) -> UAString {
     format!("opc.tcp://{}:{}{}", hostname(), port, path).into()
 }
 
-fn v1_node_id() -> NodeId {
+pub fn websocket_port(port: u16) -> u16 {
+    // The opc.wss port of a server is well clear of the opc.tcp ports handed out to tests
+    port + 1000
+}
+
+pub fn websocket_endpoint_url(port: u16, path: &str) -> UAString {
+    format!("opc.wss://{}:{}{}", hostname(), websocket_port(port), path).into()
+}
+
+pub fn https_port(port: u16) -> u16 {
+    // The https port of a server is clear of the opc.tcp and opc.wss ports handed out to tests
+    port + 2000
+}
+
+pub fn https_endpoint_url(port: u16, path: &str) -> UAString {
+    format!("https://{}:{}{}", hostname(), https_port(port), path).into()
+}
+
+pub fn v1_node_id() -> NodeId {
     NodeId::new(2, "v1")
 }
 
+pub fn slow_node_id() -> NodeId {
+    NodeId::new(2, "slow")
+}
+
+pub fn heartbeat_node_id() -> NodeId {
+    NodeId::new(2, "heartbeat")
+}
+
+pub fn stress_folder_id() -> NodeId {
+    NodeId::new(2, "Stress")
+}
+
 pub fn stress_node_id(idx: usize) -> NodeId {
     NodeId::new(2, format!("v{:04}", idx))
 }
@@ -114,7 +144,7 @@ pub fn new_server(port: u16) -> Server {
     ];
 
     // Create an OPC UA server with sample configuration and default node set
-    let server = ServerBuilder::new()
+    let mut server = ServerBuilder::new()
         .application_name("integration_server")
         .application_uri("urn:integration_server")
         .discovery_urls(vec![endpoint_url(port, endpoint_path).to_string()])
@@ -124,6 +154,7 @@ pub fn new_server(port: u16) -> Server {
         .host_and_port(hostname(), port)
         .user_token(sample_user_id, server_user_token())
         .user_token(x509_user_id, server_x509_token())
+        .clients_can_modify_address_space()
         .endpoints(
             [
                 (
@@ -251,13 +282,25 @@ pub fn new_server(port: u16) -> Server {
             v.set_value_getter(Arc::new(Mutex::new(getter)));
         }
 
+        // A variable whose value comes from a slow device
+        VariableBuilder::new(&slow_node_id(), "slow", "slow")
+            .data_type(DataTypeId::Int32)
+            .value(0i32)
+            .value_getter(Arc::new(Mutex::new(SlowDevice)))
+            .organized_by(&sample_folder_id)
+            .insert(&mut address_space);
+
         // Add a bunch of sequential vars too, similar to demo-server
         let node_ids = (0..1000)
             .map(|i| stress_node_id(i))
             .collect::<Vec<NodeId>>();
-        let folder_id = address_space
-            .add_folder("Stress", "Stress", &NodeId::objects_folder_id())
-            .unwrap();
+        let folder_id = stress_folder_id();
+        address_space.add_folder_with_id(
+            &folder_id,
+            "Stress",
+            "Stress",
+            &NodeId::objects_folder_id(),
+        );
 
         node_ids.iter().enumerate().for_each(|(i, node_id)| {
             let name = format!("stress node v{:04}", i);
@@ -272,6 +315,7 @@ pub fn new_server(port: u16) -> Server {
         let functions_object_id = functions_object_id();
         ObjectBuilder::new(&functions_object_id, "Functions", "Functions")
             .event_notifier(EventNotifier::SUBSCRIBE_TO_EVENTS)
+            .write_mask(WriteMask::DISPLAY_NAME)
             .organized_by(ObjectId::ObjectsFolder)
             .insert(&mut address_space);
 
@@ -286,9 +330,53 @@ pub fn new_server(port: u16) -> Server {
             .insert(&mut address_space);
     }
 
+    // A heartbeat that clients can watch
+    server.add_heartbeat(
+        &heartbeat_node_id(),
+        "Heartbeat",
+        &NodeId::objects_folder_id(),
+        HeartbeatKind::Counter,
+        100,
+    );
+
     server
 }
 
+/// Supplies its value from another thread a while after it is asked for it, like a field device
+/// behind a gateway would
+struct SlowDevice;
+
+impl callbacks::AttributeGetter for SlowDevice {
+    fn get(
+        &mut self,
+        _node_id: &NodeId,
+        _timestamps_to_return: TimestampsToReturn,
+        _attribute_id: AttributeId,
+        _index_range: NumericRange,
+        _data_encoding: &QualifiedName,
+        _max_age: f64,
+    ) -> Result<Option<DataValue>, StatusCode> {
+        Err(StatusCode::BadWaitingForInitialData)
+    }
+
+    fn get_deferred(
+        &mut self,
+        _node_id: &NodeId,
+        _timestamps_to_return: TimestampsToReturn,
+        _attribute_id: AttributeId,
+        _index_range: NumericRange,
+        _data_encoding: &QualifiedName,
+        _max_age: f64,
+    ) -> DeferredValue {
+        let (pending_value, completer) = PendingValue::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            completer.complete(DataValue::new_now(42i32));
+        });
+        DeferredValue::Pending(pending_value)
+    }
+}
+
 struct HelloX;
 
 impl callbacks::Method for HelloX {
@@ -335,6 +423,11 @@ impl callbacks::Method for HelloX {
 }
 
 fn new_client(port: u16, quick_timeout: bool) -> Client {
+    client_builder(port, quick_timeout).client().unwrap()
+}
+
+/// Creates a builder for the client of the tests, to be customized further
+pub fn client_builder(port: u16, quick_timeout: bool) -> ClientBuilder {
     let builder = ClientBuilder::new()
         .application_name("integration_client")
         .application_uri("x")
@@ -343,12 +436,39 @@ fn new_client(port: u16, quick_timeout: bool) -> Client {
         .trust_server_certs(true)
         .session_retry_initial(Duration::from_millis(200));
 
-    let builder = if quick_timeout {
+    if quick_timeout {
         builder.session_retry_limit(1)
     } else {
         builder
-    };
-    builder.client().unwrap()
+    }
+}
+
+/// Creates a server that also listens for opc.wss connections on the websocket port
+pub fn new_websocket_server(port: u16) -> Server {
+    let server = new_server(port);
+    {
+        let server_state = server.server_state();
+        let server_state = server_state.read();
+        let mut config = server_state.config.write();
+        config.websocket_config = Some(WebSocketConfig {
+            port: websocket_port(port),
+        });
+    }
+    server
+}
+
+/// Creates a server that also listens for https connections on the https port
+pub fn new_https_server(port: u16) -> Server {
+    let server = new_server(port);
+    {
+        let server_state = server.server_state();
+        let server_state = server_state.read();
+        let mut config = server_state.config.write();
+        config.https_config = Some(HttpsConfig {
+            port: https_port(port),
+        });
+    }
+    server
 }
 
 pub fn new_client_server(port: u16, quick_timeout: bool) -> (Client, Server) {
@@ -707,3 +827,43 @@ pub async fn connect_with(
         false
     ).await;
 }
+
+pub async fn connect_with_websocket(
+    port: u16,
+    client_endpoint: EndpointDescription,
+    identity_token: IdentityToken,
+) {
+    let (client, server) = (new_client(port, false), new_websocket_server(port));
+    perform_test(
+        client,
+        server,
+        Some(
+            move |rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, client: Client| async move {
+                regular_client_test(client_endpoint, identity_token, rx_client_command, client)
+                    .await;
+            },
+        ),
+        regular_server_test,
+    )
+    .await;
+}
+
+pub async fn connect_with_https(
+    port: u16,
+    client_endpoint: EndpointDescription,
+    identity_token: IdentityToken,
+) {
+    let (client, server) = (new_client(port, false), new_https_server(port));
+    perform_test(
+        client,
+        server,
+        Some(
+            move |rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, client: Client| async move {
+                regular_client_test(client_endpoint, identity_token, rx_client_command, client)
+                    .await;
+            },
+        ),
+        regular_server_test,
+    )
+    .await;
+}
@@ -1,9 +1,14 @@
-use std::{sync::Arc, thread};
+use std::{str::FromStr, sync::Arc, thread};
 
 use chrono::Utc;
+use futures::StreamExt;
 use log::*;
 
-use opcua::client::{Client, DataChangeCallback, IdentityToken};
+use opcua::client::{
+    Client, ClientEndpoint, DataChangeCallback, EventCallback, IdentityToken, SessionConnectMode,
+    SessionPollResult, SubscriptionStateCallback, SubscriptionStateEvent, Watchdog,
+};
+use opcua::server::comms::transport::Transport;
 use opcua::server::prelude::*;
 use opcua::sync::*;
 
@@ -253,6 +258,26 @@ async fn connect_none() {
     connect_with(port, endpoint_none(port), IdentityToken::Anonymous).await;
 }
 
+/// Connect to the server over a WebSocket, i.e. an opc.wss endpoint, using no encryption of its
+/// own on top of the TLS session
+#[tokio::test]
+async fn connect_websocket() {
+    let port = next_port();
+    let mut endpoint = endpoint_none(port);
+    endpoint.endpoint_url = websocket_endpoint_url(port, "/");
+    connect_with_websocket(port, endpoint, IdentityToken::Anonymous).await;
+}
+
+/// Connect to the server over HTTPS, i.e. an https endpoint, where each request is sent in a POST
+/// and the TLS session is the only security
+#[tokio::test]
+async fn connect_https() {
+    let port = next_port();
+    let mut endpoint = endpoint_none(port);
+    endpoint.endpoint_url = https_endpoint_url(port, "/");
+    connect_with_https(port, endpoint, IdentityToken::Anonymous).await;
+}
+
 /// Connect to the server using Basic128Rsa15 + Sign
 #[tokio::test]
 async fn connect_basic128rsa15_sign() {
@@ -468,6 +493,285 @@ async fn read_write_read() {
     ).await;
 }
 
+/// Read, write and browse more nodes than the operation limits of the server allow in one call,
+/// and check that the client splits the calls and returns the results in order
+#[tokio::test]
+async fn read_write_browse_over_operation_limits() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let count = 3 * opcua::server::constants::MAX_NODES_PER_READ + 1;
+            let node_ids = (0..count).map(stress_node_id).collect::<Vec<_>>();
+
+            let nodes_to_write = node_ids
+                .iter()
+                .enumerate()
+                .map(|(i, node_id)| WriteValue {
+                    node_id: node_id.clone(),
+                    attribute_id: AttributeId::Value as u32,
+                    index_range: UAString::null(),
+                    value: Variant::Int32(i as i32).into(),
+                })
+                .collect::<Vec<_>>();
+            let results = session.write(&nodes_to_write).await.unwrap();
+            assert_eq!(results, vec![StatusCode::Good; count]);
+
+            // A node that does not exist fails on its own
+            let mut nodes_to_read = node_ids
+                .iter()
+                .map(|node_id| node_id.clone().into())
+                .collect::<Vec<ReadValueId>>();
+            nodes_to_read.push(NodeId::new(2, "does not exist").into());
+            let results = session
+                .read(&nodes_to_read, TimestampsToReturn::Neither, 0.0)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), count + 1);
+            for (i, value) in results[..count].iter().enumerate() {
+                assert_eq!(*value.value.as_ref().unwrap(), Variant::Int32(i as i32));
+            }
+            assert_eq!(results[count].status, Some(StatusCode::BadNodeIdUnknown));
+
+            let nodes_to_browse = node_ids
+                .iter()
+                .map(|node_id| BrowseDescription {
+                    node_id: node_id.clone(),
+                    browse_direction: BrowseDirection::Inverse,
+                    reference_type_id: ReferenceTypeId::Organizes.into(),
+                    include_subtypes: true,
+                    node_class_mask: 0,
+                    result_mask: BrowseDescriptionResultMask::all().bits(),
+                })
+                .collect::<Vec<_>>();
+            let results = session.browse(&nodes_to_browse).await.unwrap().unwrap();
+            assert_eq!(results.len(), count);
+            for result in results {
+                assert!(result.status_code.is_good());
+                let references = result.references.unwrap();
+                assert_eq!(references.len(), 1);
+                assert_eq!(references[0].node_id.node_id, stress_folder_id());
+            }
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Connect with small limits on the size of messages and check that a request or a response that
+/// exceeds them fails on its own, without taking the connection down
+#[tokio::test]
+async fn message_size_limits() {
+    let port = next_port();
+    let server = new_server(port);
+    {
+        let server_state = server.server_state();
+        let server_state = server_state.read();
+        let mut config = server_state.config.write();
+        config.limits.max_chunk_count = 1;
+    }
+    let client = client_builder(port, false)
+        .max_chunk_size(16384)
+        .max_incoming_chunk_size(16384)
+        .max_chunk_count(3)
+        .client()
+        .unwrap();
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(
+                endpoint_basic256sha256_sign_encrypt(port),
+                IdentityToken::Anonymous,
+            )
+            .await
+            .unwrap();
+        let handle = event_loop.spawn();
+        session.wait_for_connection().await;
+
+        // A request that takes more chunks than the server accepts
+        let result = session
+            .write(&[WriteValue {
+                node_id: v1_node_id(),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                value: Variant::from(ByteString::from(vec![0u8; 100000])).into(),
+            }])
+            .await;
+        assert_eq!(result.unwrap_err().status(), StatusCode::BadRequestTooLarge);
+
+        // A response that takes more chunks than the client accepts
+        let stress_folder = BrowseDescription {
+            node_id: stress_folder_id(),
+            browse_direction: BrowseDirection::Forward,
+            reference_type_id: ReferenceTypeId::Organizes.into(),
+            include_subtypes: true,
+            node_class_mask: 0,
+            result_mask: BrowseDescriptionResultMask::all().bits(),
+        };
+        let result = session.browse(&vec![stress_folder; 4]).await;
+        assert_eq!(
+            result.unwrap_err().status(),
+            StatusCode::BadResponseTooLarge
+        );
+
+        // The session carries on
+        let results = session
+            .read(&[stress_node_id(1).into()], TimestampsToReturn::Both, 1.0)
+            .await
+            .unwrap();
+        assert_eq!(*results[0].value.as_ref().unwrap(), Variant::Int32(0));
+
+        session.disconnect().await.unwrap();
+        handle.await.unwrap();
+    };
+    perform_test(client, server, Some(client_test), regular_server_test).await;
+}
+
+/// Connect to endpoints with a pinned certificate and check that the session is refused unless the
+/// server presents it, even though the client trusts the server
+#[tokio::test]
+async fn certificate_pinning() {
+    let port = next_port();
+    let server = new_server(port);
+    let server_cert = {
+        let server_state = server.server_state();
+        let server_state = server_state.read();
+        server_state.server_certificate.clone().unwrap()
+    };
+    // Certificates can only be pinned on endpoints with security
+    let pinned_endpoint = |pin: String| ClientEndpoint {
+        security_policy: SecurityPolicy::Basic256Sha256.to_str().into(),
+        security_mode: MessageSecurityMode::SignAndEncrypt.into(),
+        pinned_certificates: vec![pin],
+        ..ClientEndpoint::new(endpoint_url(port, "/").as_ref())
+    };
+    let client = client_builder(port, true)
+        .endpoint("wrong_pin", pinned_endpoint("00".repeat(20)))
+        .endpoint(
+            "thumbprint",
+            pinned_endpoint(server_cert.thumbprint().as_hex_string()),
+        )
+        .endpoint(
+            "public_key_hash",
+            pinned_endpoint(server_cert.public_key_hash().unwrap()),
+        )
+        .client()
+        .unwrap();
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let endpoints = client
+            .get_server_endpoints_from_url(endpoint_url(port, "/"))
+            .await
+            .unwrap();
+
+        let (_, event_loop) = client.new_session_from_id("wrong_pin", &endpoints).unwrap();
+        assert_eq!(
+            event_loop.spawn().await.unwrap(),
+            StatusCode::BadCertificateInvalid
+        );
+
+        for endpoint_id in ["thumbprint", "public_key_hash"] {
+            let (session, event_loop) =
+                client.new_session_from_id(endpoint_id, &endpoints).unwrap();
+            let handle = event_loop.spawn();
+            assert!(session.wait_for_connection().await);
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        }
+    };
+    perform_test(client, server, Some(client_test), regular_server_test).await;
+}
+
+/// Connect more sessions than the server allows and check that the extra one is refused
+#[tokio::test]
+async fn max_sessions() {
+    let port = next_port();
+    let server = new_server(port);
+    {
+        let server_state = server.server_state();
+        let server_state = server_state.read();
+        let mut config = server_state.config.write();
+        config.limits.max_sessions = 1;
+    }
+    let client = client_builder(port, true).client().unwrap();
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(endpoint_none(port), IdentityToken::Anonymous)
+            .await
+            .unwrap();
+        let handle = event_loop.spawn();
+        assert!(session.wait_for_connection().await);
+
+        let (_, event_loop) = client
+            .new_session_from_endpoint(endpoint_none(port), IdentityToken::Anonymous)
+            .await
+            .unwrap();
+        assert_eq!(
+            event_loop.spawn().await.unwrap(),
+            StatusCode::BadTooManySessions
+        );
+
+        session.disconnect().await.unwrap();
+        handle.await.unwrap();
+    };
+    perform_test(client, server, Some(client_test), regular_server_test).await;
+}
+
+/// Use the blocking one-shot functions to read, write and browse
+#[tokio::test]
+async fn oneshot_read_write_browse() {
+    let port = next_port();
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, _client: Client| async move {
+            let url = endpoint_url(port, "/").to_string();
+            let node_id = stress_node_id(1).to_string();
+            tokio::task::spawn_blocking(move || {
+                let results = opcua::client::read(&url, &[&node_id]).unwrap();
+                assert_eq!(*results[0].value.as_ref().unwrap(), Variant::Int32(0));
+
+                let results =
+                    opcua::client::write(&url, &[(&node_id, Variant::Int32(1))]).unwrap();
+                assert_eq!(results[0], StatusCode::Good);
+
+                let results = opcua::client::read(&url, &[&node_id]).unwrap();
+                assert_eq!(*results[0].value.as_ref().unwrap(), Variant::Int32(1));
+
+                assert_eq!(
+                    opcua::client::read(&url, &["not a node id"]).unwrap_err(),
+                    StatusCode::BadNodeIdInvalid
+                );
+
+                let references = opcua::client::browse(&url, "i=84").unwrap();
+                assert!(references
+                    .iter()
+                    .any(|r| r.browse_name == QualifiedName::new(0, "Objects")));
+
+                // The same calls as an authenticated user
+                let client = opcua::client::SimpleClient::new().identity_token(client_user_token());
+                let results = client.read(&url, &[&node_id]).unwrap();
+                assert_eq!(*results[0].value.as_ref().unwrap(), Variant::Int32(1));
+            })
+            .await
+            .unwrap();
+        },
+        false,
+    )
+    .await;
+}
+
 /// Connect with the server and attempt to subscribe and monitor 1000 variables
 #[tokio::test]
 async fn subscribe_1000() {
@@ -538,10 +842,10 @@ async fn subscribe_1000() {
             results.iter().enumerate().for_each(|(i, result)| {
                 if i == 999 {
                     // Last idx var does not exist so expect it to fail
-                    error!("Checkout {}", result.status_code);
-                    assert!(result.status_code.is_bad());
+                    error!("Checkout {}", result.result.status_code);
+                    assert!(result.result.status_code.is_bad());
                 } else {
-                    assert!(result.status_code.is_good());
+                    assert!(result.result.status_code.is_good());
                 }
             });
 
@@ -552,19 +856,126 @@ async fn subscribe_1000() {
     ).await;
 }
 
+/// Observe subscriptions and monitored items being added, modified and deleted
 #[tokio::test]
-async fn method_call() {
-    // Call a method on the server, one exercising some parameters in and out
+async fn subscription_state_events() {
     let port = next_port();
     let client_endpoint = endpoint_none(port);
 
     connect_with_client_test(
         port,
         move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
-            info!(
-                "Client will try to connect to endpoint {:?}",
-                client_endpoint
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let listener_id = {
+                let events = events.clone();
+                session
+                    .subscription_state
+                    .lock()
+                    .add_listener(SubscriptionStateCallback::new(move |event, state| {
+                        // The state already reflects the change
+                        if let SubscriptionStateEvent::SubscriptionAdded { subscription_id } = event {
+                            assert!(state.subscription_exists(*subscription_id));
+                        }
+                        events.lock().push(event.clone());
+                    }))
+            };
+
+            let subscription_id = session
+                .create_subscription(
+                    std::time::Duration::from_secs(1),
+                    100,
+                    100,
+                    0,
+                    0,
+                    true,
+                    DataChangeCallback::new(|_, _| {}),
+                )
+                .await
+                .unwrap();
+
+            let items_to_create = (1..=2)
+                .map(|i| MonitoredItemCreateRequest {
+                    item_to_monitor: stress_node_id(i).into(),
+                    monitoring_mode: MonitoringMode::Reporting,
+                    requested_parameters: MonitoringParameters {
+                        client_handle: i as u32,
+                        sampling_interval: 1000.0f64,
+                        filter: ExtensionObject::null(),
+                        queue_size: 1,
+                        discard_oldest: true,
+                    },
+                })
+                .collect::<Vec<_>>();
+            let monitored_item_ids = session
+                .create_monitored_items(subscription_id, TimestampsToReturn::Both, items_to_create)
+                .await
+                .unwrap()
+                .iter()
+                .map(|r| r.result.monitored_item_id)
+                .collect::<Vec<_>>();
+
+            session
+                .set_monitoring_mode(
+                    subscription_id,
+                    MonitoringMode::Sampling,
+                    &monitored_item_ids[1..],
+                )
+                .await
+                .unwrap();
+            session
+                .delete_monitored_items(subscription_id, &monitored_item_ids[..1])
+                .await
+                .unwrap();
+            session.delete_subscription(subscription_id).await.unwrap();
+
+            assert_eq!(
+                *events.lock(),
+                vec![
+                    SubscriptionStateEvent::SubscriptionAdded { subscription_id },
+                    SubscriptionStateEvent::MonitoredItemsAdded {
+                        subscription_id,
+                        monitored_item_ids: monitored_item_ids.clone(),
+                    },
+                    SubscriptionStateEvent::MonitoredItemsModified {
+                        subscription_id,
+                        monitored_item_ids: monitored_item_ids[1..].to_vec(),
+                    },
+                    SubscriptionStateEvent::MonitoredItemsDeleted {
+                        subscription_id,
+                        monitored_item_ids: monitored_item_ids[..1].to_vec(),
+                    },
+                    SubscriptionStateEvent::SubscriptionDeleted { subscription_id },
+                ]
             );
+
+            // Listeners can be removed, once
+            assert!(session.subscription_state.lock().remove_listener(listener_id));
+            assert!(!session.subscription_state.lock().remove_listener(listener_id));
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Adding a node makes the server raise a model change event about it
+#[tokio::test]
+async fn model_change_events() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
             let (session, event_loop) = client
                 .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
                 .await
@@ -573,21 +984,221 @@ async fn method_call() {
             let handle = event_loop.spawn();
             session.wait_for_connection().await;
 
-            // Call the method
-            let input_arguments = Some(vec![Variant::from("Foo")]);
-            let method = CallMethodRequest {
-                object_id: functions_object_id(),
-                method_id: hellox_method_id(),
-                input_arguments,
+            let (tx, mut rx) = unbounded_channel();
+            let subscription_id = session
+                .create_subscription(
+                    std::time::Duration::from_millis(100),
+                    100,
+                    100,
+                    0,
+                    0,
+                    true,
+                    EventCallback::new(move |event, _| {
+                        let _ = tx.send(event.unwrap_or_default());
+                    }),
+                )
+                .await
+                .unwrap();
+
+            let select_clauses = [
+                (ObjectTypeId::BaseEventType, "EventType"),
+                (ObjectTypeId::GeneralModelChangeEventType, "Changes"),
+            ]
+                .iter()
+                .map(|(type_definition_id, s)| SimpleAttributeOperand {
+                    type_definition_id: type_definition_id.into(),
+                    browse_path: Some(vec![QualifiedName::from(*s)]),
+                    attribute_id: AttributeId::Value as u32,
+                    index_range: UAString::null(),
+                })
+                .collect();
+            let event_filter = EventFilter {
+                where_clause: ContentFilter { elements: None },
+                select_clauses: Some(select_clauses),
             };
-            let result = session.call(method).await.unwrap();
+            let mut item_to_create: MonitoredItemCreateRequest = NodeId::from(&ObjectId::Server).into();
+            item_to_create.item_to_monitor.attribute_id = AttributeId::EventNotifier as u32;
+            item_to_create.requested_parameters.sampling_interval = 100.0;
+            item_to_create.requested_parameters.queue_size = 10;
+            item_to_create.requested_parameters.filter = ExtensionObject::from_encodable(
+                ObjectId::EventFilter_Encoding_DefaultBinary,
+                &event_filter,
+            );
+            let results = session
+                .create_monitored_items(subscription_id, TimestampsToReturn::Neither, vec![item_to_create])
+                .await
+                .unwrap();
+            assert!(results[0].result.status_code.is_good());
 
-            // Result should say "Hello Foo"
-            assert!(result.status_code.is_good());
-            let output_args = result.output_arguments.unwrap();
-            assert_eq!(output_args.len(), 1);
-            let msg = output_args.get(0).unwrap();
-            assert_eq!(msg.to_string(), "Hello Foo!");
+            let node_id = NodeId::new(2, "added");
+            let results = session
+                .add_nodes(&[AddNodesItem {
+                    parent_node_id: NodeId::objects_folder_id().into(),
+                    reference_type_id: ReferenceTypeId::Organizes.into(),
+                    requested_new_node_id: node_id.clone().into(),
+                    browse_name: "added".into(),
+                    node_class: NodeClass::Object,
+                    node_attributes: ExtensionObject::from_encodable(
+                        ObjectId::ObjectAttributes_Encoding_DefaultBinary,
+                        &ObjectAttributes {
+                            specified_attributes: (AttributesMask::DISPLAY_NAME
+                                | AttributesMask::DESCRIPTION
+                                | AttributesMask::WRITE_MASK
+                                | AttributesMask::USER_WRITE_MASK
+                                | AttributesMask::EVENT_NOTIFIER)
+                                .bits(),
+                            display_name: "added".into(),
+                            description: LocalizedText::null(),
+                            write_mask: 0,
+                            user_write_mask: 0,
+                            event_notifier: 0,
+                        },
+                    ),
+                    type_definition: ObjectTypeId::FolderType.into(),
+                }])
+                .await
+                .unwrap();
+            assert_eq!(results[0].status_code, StatusCode::Good);
+
+            // The server reports the changes of AddNodes before it responds, so the event is
+            // on its way and only the publishing interval delays it. Changes that the server
+            // itself made earlier may come first.
+            let changes = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+                loop {
+                    let fields = rx.recv().await.unwrap();
+                    assert_eq!(
+                        fields[0],
+                        Variant::from(NodeId::from(&ObjectTypeId::GeneralModelChangeEventType))
+                    );
+                    let Variant::Array(array) = &fields[1] else {
+                        panic!("Changes is not an array");
+                    };
+                    let changes = array
+                        .values
+                        .iter()
+                        .map(|v| match v {
+                            Variant::ExtensionObject(v) => v
+                                .decode_inner::<ModelChangeStructureDataType>(&DecodingOptions::default())
+                                .unwrap(),
+                            _ => panic!("Change is not an extension object"),
+                        })
+                        .collect::<Vec<_>>();
+                    if changes.iter().any(|c| c.affected == node_id) {
+                        break changes;
+                    }
+                }
+            })
+            .await
+            .expect("No model change event for the added node");
+            let added = changes.iter().find(|c| c.affected == node_id).unwrap();
+            assert_eq!(added.affected_type, ObjectTypeId::FolderType.into());
+            assert_ne!(added.verb & ModelChangeStructureVerbMask::NodeAdded as u8, 0);
+            assert!(changes.contains(&ModelChangeStructureDataType {
+                affected: NodeId::objects_folder_id(),
+                affected_type: ObjectTypeId::FolderType.into(),
+                verb: ModelChangeStructureVerbMask::ReferenceAdded as u8,
+            }));
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Delete every monitored item of a subscription in batches, including one the server has already deleted
+#[tokio::test]
+async fn delete_all_monitored_items() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let subscription_id = session
+                .create_subscription(
+                    std::time::Duration::from_secs(1),
+                    100,
+                    100,
+                    0,
+                    0,
+                    true,
+                    DataChangeCallback::new(|_, _| {}),
+                )
+                .await
+                .unwrap();
+
+            // More items than the server's default MaxMonitoredItemsPerCall of 10
+            let items_to_create = (1..=25)
+                .map(|i| MonitoredItemCreateRequest {
+                    item_to_monitor: stress_node_id(i).into(),
+                    monitoring_mode: MonitoringMode::Reporting,
+                    requested_parameters: MonitoringParameters {
+                        client_handle: i as u32,
+                        sampling_interval: 1000.0f64,
+                        filter: ExtensionObject::null(),
+                        queue_size: 1,
+                        discard_oldest: true,
+                    },
+                })
+                .collect::<Vec<_>>();
+            let mut monitored_item_ids = session
+                .create_monitored_items(subscription_id, TimestampsToReturn::Both, items_to_create)
+                .await
+                .unwrap()
+                .iter()
+                .map(|r| r.result.monitored_item_id)
+                .collect::<Vec<_>>();
+            monitored_item_ids.sort_unstable();
+
+            // Delete one item behind the back of the subscription state
+            let deleted_id = monitored_item_ids[12];
+            session
+                .send_request(DeleteMonitoredItemsRequest {
+                    request_header: session.request_header(std::time::Duration::from_secs(5)),
+                    subscription_id,
+                    monitored_item_ids: Some(vec![deleted_id]),
+                })
+                .await
+                .unwrap();
+
+            let results = session
+                .delete_all_monitored_items(subscription_id)
+                .await
+                .unwrap();
+            assert_eq!(
+                results.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+                monitored_item_ids
+            );
+            for (id, status_code) in results {
+                if id == deleted_id {
+                    assert_eq!(status_code, StatusCode::BadMonitoredItemIdInvalid);
+                } else {
+                    assert_eq!(status_code, StatusCode::Good);
+                }
+            }
+            assert!(session
+                .subscription_state
+                .lock()
+                .get(subscription_id)
+                .unwrap()
+                .monitored_items()
+                .is_empty());
+
+            // Nothing left to delete
+            assert!(session
+                .delete_all_monitored_items(subscription_id)
+                .await
+                .unwrap()
+                .is_empty());
 
             session.disconnect().await.unwrap();
             handle.await.unwrap();
@@ -595,3 +1206,884 @@ async fn method_call() {
         false
     ).await;
 }
+
+/// A service call the server faults returns an error holding the response header of the fault
+#[tokio::test]
+async fn service_error_from_fault() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let err = session.republish(1000, 1).await.unwrap_err();
+            assert_eq!(err.status(), StatusCode::BadSubscriptionIdInvalid);
+            let response_header = err.response_header().unwrap();
+            assert_eq!(
+                response_header.service_result,
+                StatusCode::BadSubscriptionIdInvalid
+            );
+            assert_ne!(response_header.request_handle, 0);
+
+            // An error made by the client has no response header
+            let err = session
+                .read(&[], TimestampsToReturn::Both, 0.0)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status(), StatusCode::BadNothingToDo);
+            assert!(err.response_header().is_none());
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Enumerate the monitored items of a subscription and have the server resend their values
+#[tokio::test]
+async fn get_monitored_items_and_resend_data() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let (tx_values, mut rx_values) = unbounded_channel();
+            let subscription_id = session
+                .create_subscription(
+                    std::time::Duration::from_millis(100),
+                    100,
+                    10,
+                    0,
+                    0,
+                    true,
+                    DataChangeCallback::new(move |value, _| {
+                        let _ = tx_values.send(value.value);
+                    }),
+                )
+                .await
+                .unwrap();
+
+            let created = session
+                .create_monitored_items(
+                    subscription_id,
+                    TimestampsToReturn::Both,
+                    vec![MonitoredItemCreateRequest::new(
+                        v1_node_id().into(),
+                        MonitoringMode::Reporting,
+                        MonitoringParameters {
+                            sampling_interval: 100.0,
+                            ..Default::default()
+                        },
+                    )],
+                )
+                .await
+                .unwrap();
+            let monitored_item_id = created[0].result.monitored_item_id;
+            let client_handle = created[0].client_handle;
+
+            let (server_handles, client_handles) = session
+                .call_get_monitored_items(subscription_id)
+                .await
+                .unwrap();
+            assert_eq!(server_handles, vec![monitored_item_id]);
+            assert_eq!(client_handles, vec![client_handle]);
+
+            // The value never changes, so it is only reported again when it is resent
+            assert_eq!(rx_values.recv().await.unwrap(), Some(Variant::Int32(100)));
+            session.call_resend_data(subscription_id).await.unwrap();
+            assert_eq!(rx_values.recv().await.unwrap(), Some(Variant::Int32(100)));
+
+            assert_eq!(
+                session.call_get_monitored_items(subscription_id + 1).await,
+                Err(StatusCode::BadSubscriptionIdInvalid)
+            );
+            assert_eq!(
+                session.call_resend_data(subscription_id + 1).await,
+                Err(StatusCode::BadSubscriptionIdInvalid)
+            );
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Watch the server heartbeat and a variable that never changes
+#[tokio::test]
+async fn heartbeat_watchdog() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let heartbeat_stalls = Arc::new(Mutex::new(0));
+            let heartbeat_watchdog = {
+                let heartbeat_stalls = heartbeat_stalls.clone();
+                Watchdog::start(
+                    session.clone(),
+                    heartbeat_node_id(),
+                    std::time::Duration::from_millis(1000),
+                    move || *heartbeat_stalls.lock() += 1,
+                )
+                .await
+                .unwrap()
+            };
+            let static_stalls = Arc::new(Mutex::new(0));
+            let static_watchdog = {
+                let static_stalls = static_stalls.clone();
+                Watchdog::start(
+                    session.clone(),
+                    stress_node_id(0),
+                    std::time::Duration::from_millis(1000),
+                    move || *static_stalls.lock() += 1,
+                )
+                .await
+                .unwrap()
+            };
+
+            // A variable that does not exist cannot be watched
+            assert!(Watchdog::start(
+                session.clone(),
+                NodeId::new(2, "missing"),
+                std::time::Duration::from_millis(1000),
+                || {},
+            )
+            .await
+            .is_err());
+
+            tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+
+            // The heartbeat keeps changing, the other variable stalls once
+            assert_eq!(*heartbeat_stalls.lock(), 0);
+            assert_eq!(*static_stalls.lock(), 1);
+
+            heartbeat_watchdog.stop();
+            static_watchdog.stop();
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn method_call() {
+    // Call a method on the server, one exercising some parameters in and out
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            info!(
+                "Client will try to connect to endpoint {:?}",
+                client_endpoint
+            );
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            // Call the method
+            let input_arguments = Some(vec![Variant::from("Foo")]);
+            let method = CallMethodRequest {
+                object_id: functions_object_id(),
+                method_id: hellox_method_id(),
+                input_arguments,
+            };
+            let result = session.call(method).await.unwrap();
+
+            // Result should say "Hello Foo"
+            assert!(result.status_code.is_good());
+            let output_args = result.output_arguments.unwrap();
+            assert_eq!(output_args.len(), 1);
+            let msg = output_args.get(0).unwrap();
+            assert_eq!(msg.to_string(), "Hello Foo!");
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn namespace_index() {
+    // Resolve the index of a namespace from the server's namespace array
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let namespaces = session.read_namespace_array().await.unwrap();
+            assert_eq!(namespaces[0], "http://opcfoundation.org/UA/");
+
+            // The server registers its application uri as a namespace
+            let ns = session
+                .namespace_index("urn:integration_server")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(namespaces[ns as usize], "urn:integration_server");
+            assert_eq!(session.namespace_index("urn:unknown").await.unwrap(), None);
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn resolve_expanded_node_id() {
+    // Resolve expanded node ids from the server's namespace and server arrays
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let ns = session
+                .namespace_index("urn:integration_server")
+                .await
+                .unwrap()
+                .unwrap();
+            let node_id = ExpandedNodeId::from_str("nsu=urn:integration_server;s=v1").unwrap();
+            assert_eq!(
+                session.resolve_expanded_node_id(&node_id).await.unwrap(),
+                NodeId::new(ns, "v1")
+            );
+            let node_id = ExpandedNodeId::from_str("nsu=urn:unknown;s=v1").unwrap();
+            assert_eq!(
+                session.resolve_expanded_node_id(&node_id).await.unwrap_err(),
+                StatusCode::BadNodeIdUnknown
+            );
+
+            // The first server is the server itself, other server indexes are not registered
+            let servers = session.read_server_array().await.unwrap();
+            let node_id = ExpandedNodeId::from_str("s=v1").unwrap();
+            assert_eq!(session.server_uri(&node_id).await.unwrap(), servers[0]);
+            let node_id = ExpandedNodeId::from_str("svr=5;s=v1").unwrap();
+            assert_eq!(
+                session.server_uri(&node_id).await.unwrap_err(),
+                StatusCode::BadServerIndexInvalid
+            );
+            assert_eq!(
+                session.resolve_expanded_node_id(&node_id).await.unwrap_err(),
+                StatusCode::BadReferenceLocalOnly
+            );
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn read_by_path() {
+    // Read the value of a variable from its path
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let objects_folder = NodeId::objects_folder_id();
+            let value = session.read_by_path(&objects_folder, "Sample/v1").await.unwrap();
+            assert_eq!(value, Variant::Int32(100));
+            let value = session
+                .read_by_path_as::<f64>(&objects_folder, "/0:Sample/0:v1")
+                .await
+                .unwrap();
+            assert_eq!(value, 100.0);
+
+            // A value that cannot be converted
+            let err = session
+                .read_by_path_as::<bool>(&NodeId::root_folder_id(), "Objects/Server/ServerStatus")
+                .await
+                .unwrap_err();
+            assert_eq!(err, StatusCode::BadTypeMismatch);
+
+            // A path with no node at its end
+            let err = session
+                .read_by_path(&objects_folder, "Sample/v2")
+                .await
+                .unwrap_err();
+            assert_eq!(err, StatusCode::BadNoMatch);
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn read_deferred_value() {
+    // Read a value that the server has to wait for, along with one it has at hand
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let results = session
+                .read(
+                    &[slow_node_id().into(), v1_node_id().into()],
+                    TimestampsToReturn::Both,
+                    0.0,
+                )
+                .await
+                .unwrap();
+            assert_eq!(results[0].value, Some(Variant::Int32(42)));
+            assert_eq!(results[1].value, Some(Variant::Int32(100)));
+
+            // The session carries on with other requests while a read is parked
+            let slow_nodes = [slow_node_id().into()];
+            let fast_nodes = [v1_node_id().into()];
+            let (slow, fast) = tokio::join!(
+                session.read(&slow_nodes, TimestampsToReturn::Neither, 0.0),
+                session.read(&fast_nodes, TimestampsToReturn::Neither, 0.0),
+            );
+            assert_eq!(slow.unwrap()[0].value, Some(Variant::Int32(42)));
+            assert_eq!(fast.unwrap()[0].value, Some(Variant::Int32(100)));
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn read_node() {
+    // Read every attribute of a variable and an object
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let snapshot = session.read_node(&v1_node_id()).await.unwrap();
+            assert_eq!(snapshot.node_class, NodeClass::Variable);
+            assert_eq!(snapshot.browse_name, QualifiedName::from("v1"));
+            assert_eq!(snapshot.value.and_then(|v| v.value), Some(Variant::Int32(100)));
+            assert_eq!(snapshot.data_type, Some(DataTypeId::Int32.into()));
+            assert!(snapshot.user_access_level.is_some());
+            assert!(snapshot.is_abstract.is_none());
+
+            let snapshot = session.read_node(&functions_object_id()).await.unwrap();
+            assert_eq!(snapshot.node_class, NodeClass::Object);
+            assert_eq!(snapshot.write_mask, Some(WriteMask::DISPLAY_NAME));
+            assert!(snapshot.event_notifier.is_some());
+            assert!(snapshot.value.is_none());
+
+            let err = session
+                .read_node(&NodeId::new(2, "Missing"))
+                .await
+                .unwrap_err();
+            assert_eq!(err, StatusCode::BadNodeIdUnknown);
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+#[tokio::test]
+async fn write_attribute() {
+    // Rename a node whose write mask allows it, and fail to write an attribute it doesn't
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    connect_with_client_test(
+        port,
+        move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>, mut client: Client| async move {
+            let (session, event_loop) = client
+                .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+                .await
+                .unwrap();
+
+            let handle = event_loop.spawn();
+            session.wait_for_connection().await;
+
+            let node_id = functions_object_id();
+            session
+                .write_attribute(&node_id, AttributeId::DisplayName, LocalizedText::new("en", "Renamed"))
+                .await
+                .unwrap();
+            let read_node = ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: AttributeId::DisplayName as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            };
+            let value = session
+                .read(&[read_node], TimestampsToReturn::Neither, 0.0)
+                .await
+                .unwrap()
+                .pop()
+                .unwrap();
+            assert_eq!(value.value, Some(Variant::from(LocalizedText::new("en", "Renamed"))));
+
+            let err = session
+                .write_attribute(&node_id, AttributeId::Description, LocalizedText::new("en", "Functions"))
+                .await
+                .unwrap_err();
+            assert_eq!(err, StatusCode::BadNotWritable);
+
+            session.disconnect().await.unwrap();
+            handle.await.unwrap();
+        },
+        false
+    ).await;
+}
+
+/// Connect to a server, have the server forcibly close the secure channel and expect the client
+/// to reconnect.
+#[tokio::test]
+async fn server_closes_secure_channel() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+            .await
+            .unwrap();
+
+        // Drive the event loop, forwarding its events so the test can observe the reconnect
+        let (tx_events, mut rx_events) = unbounded_channel();
+        let handle = tokio::task::spawn(async move {
+            let stream = event_loop.enter();
+            tokio::pin!(stream);
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(event) => {
+                        let _ = tx_events.send(event);
+                    }
+                    Err(err) => return err,
+                }
+            }
+            StatusCode::Good
+        });
+        session.wait_for_connection().await;
+
+        // Wait for the server to close the channel, then for the client to reconnect
+        let mut closed = false;
+        while let Some(event) = rx_events.recv().await {
+            match event {
+                SessionPollResult::ConnectionLost(_) => closed = true,
+                SessionPollResult::Reconnected(_) if closed => break,
+                _ => (),
+            }
+        }
+        assert!(closed);
+        assert!(session
+            .read(&[v1_node_id().into()], TimestampsToReturn::Both, 1.0)
+            .await
+            .is_ok());
+
+        session.disconnect().await.unwrap();
+        handle.await.unwrap();
+    };
+
+    let server_test = |mut rx_server_command: mpsc::UnboundedReceiver<ServerCommand>,
+                       server: Server| async move {
+        let server = Arc::new(RwLock::new(server));
+        let server_fut = Server::new_server_task(server.clone());
+        tokio::pin!(server_fut);
+
+        let mut closed = false;
+        let mut timer = tokio::time::interval(std::time::Duration::from_millis(100));
+        loop {
+            tokio::select! {
+                _ = rx_server_command.recv() => {
+                    server.write().abort();
+                    server_fut.await;
+                    break;
+                }
+                _ = &mut server_fut => {
+                    break;
+                }
+                _ = timer.tick(), if !closed => {
+                    let server = server.read();
+                    let channel = server
+                        .secure_channels()
+                        .into_iter()
+                        .find(|c| c.secure_channel_id != 0 && c.session_count > 0);
+                    if let Some(channel) = channel {
+                        assert!(channel.bytes_received > 0);
+                        assert!(channel.bytes_sent > 0);
+                        assert_eq!(channel.security_policy, SecurityPolicy::None);
+                        assert_eq!(
+                            server.close_secure_channel(u32::MAX),
+                            Err(StatusCode::BadSecureChannelIdInvalid)
+                        );
+                        server
+                            .close_secure_channel(channel.secure_channel_id)
+                            .unwrap();
+                        closed = true;
+                    }
+                }
+            }
+        }
+        assert!(closed);
+    };
+
+    let (client, server) = new_client_server(port, false);
+    perform_test(client, server, Some(client_test), server_test).await;
+}
+
+/// Ask the server to shut down and expect connected clients to see the countdown before their
+/// sessions are closed.
+#[tokio::test]
+async fn server_shutdown_countdown() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+    let (tx_watching, mut rx_watching) = unbounded_channel();
+
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+            .await
+            .unwrap();
+        let handle = event_loop.spawn();
+        session.wait_for_connection().await;
+
+        let (tx_values, mut rx_values) = unbounded_channel();
+        let subscription_id = session
+            .create_subscription(
+                std::time::Duration::from_millis(100),
+                100,
+                10,
+                0,
+                0,
+                true,
+                DataChangeCallback::new(move |value, item| {
+                    let _ = tx_values.send((item.item_to_monitor().node_id.clone(), value.value));
+                }),
+            )
+            .await
+            .unwrap();
+        let items_to_create = [
+            VariableId::Server_ServerStatus_State,
+            VariableId::Server_ServerStatus_SecondsTillShutdown,
+        ]
+        .into_iter()
+        .map(|id| {
+            let node_id: NodeId = id.into();
+            MonitoredItemCreateRequest::new(
+                node_id.into(),
+                MonitoringMode::Reporting,
+                MonitoringParameters {
+                    sampling_interval: 100.0,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+        session
+            .create_monitored_items(subscription_id, TimestampsToReturn::Both, items_to_create)
+            .await
+            .unwrap();
+        tx_watching.send(()).unwrap();
+
+        // Wait to see the server is shutting down, with time to go
+        let state_id: NodeId = VariableId::Server_ServerStatus_State.into();
+        let seconds_id: NodeId = VariableId::Server_ServerStatus_SecondsTillShutdown.into();
+        let (mut shutting_down, mut countdown) = (false, false);
+        while !(shutting_down && countdown) {
+            let (node_id, value) = rx_values.recv().await.unwrap();
+            if node_id == state_id {
+                shutting_down = value == Some(Variant::from(ServerState::Shutdown as i32));
+            } else if node_id == seconds_id {
+                countdown = matches!(value, Some(Variant::UInt32(seconds)) if seconds > 0);
+            }
+        }
+
+        // Then for the server to close the session
+        while session
+            .read(&[state_id.clone().into()], TimestampsToReturn::Both, 0.0)
+            .await
+            .is_ok()
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        }
+
+        handle.abort();
+    };
+
+    let server_test = |mut rx_server_command: mpsc::UnboundedReceiver<ServerCommand>,
+                       server: Server| async move {
+        let connections = server.connections();
+        let server = Arc::new(RwLock::new(server));
+        let server_fut = Server::new_server_task(server.clone());
+        tokio::pin!(server_fut);
+
+        let mut session_manager = None;
+        loop {
+            tokio::select! {
+                _ = rx_server_command.recv() => {
+                    server.write().abort();
+                    server_fut.await;
+                    break;
+                }
+                _ = &mut server_fut => {
+                    break;
+                }
+                _ = rx_watching.recv(), if session_manager.is_none() => {
+                    // Shut down once the client is watching
+                    session_manager = connections
+                        .read()
+                        .first()
+                        .map(|transport| transport.read().session_manager());
+                    server
+                        .write()
+                        .shutdown(2, LocalizedText::new("", "Maintenance"));
+                }
+            }
+        }
+        assert!(session_manager.unwrap().read().sessions.is_empty());
+    };
+
+    let (client, server) = new_client_server(port, false);
+    perform_test(client, server, Some(client_test), server_test).await;
+}
+
+/// Drop a connected session without disconnecting and expect the client to close the session on
+/// the server before its event loop ends
+#[tokio::test]
+async fn drop_session_closes_it() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+    let (tx_watching, mut rx_watching) = unbounded_channel::<tokio::sync::oneshot::Sender<()>>();
+
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+            .await
+            .unwrap();
+        let handle = event_loop.spawn();
+        assert!(session.wait_for_connection().await);
+
+        // Let the server find the session before it is dropped
+        let (tx_found, rx_found) = tokio::sync::oneshot::channel();
+        tx_watching.send(tx_found).unwrap();
+        rx_found.await.unwrap();
+
+        drop(session);
+        assert_eq!(handle.await.unwrap(), StatusCode::Good);
+    };
+
+    let server_test = |mut rx_server_command: mpsc::UnboundedReceiver<ServerCommand>,
+                       server: Server| async move {
+        let connections = server.connections();
+        let server = Arc::new(RwLock::new(server));
+        let server_fut = Server::new_server_task(server.clone());
+        tokio::pin!(server_fut);
+
+        let mut session_manager = None;
+        loop {
+            tokio::select! {
+                _ = rx_server_command.recv() => {
+                    server.write().abort();
+                    server_fut.await;
+                    break;
+                }
+                _ = &mut server_fut => {
+                    break;
+                }
+                Some(tx_found) = rx_watching.recv() => {
+                    session_manager = connections
+                        .read()
+                        .first()
+                        .map(|transport| transport.read().session_manager());
+                    assert_eq!(session_manager.as_ref().unwrap().read().sessions.len(), 1);
+                    tx_found.send(()).unwrap();
+                }
+            }
+        }
+        assert!(session_manager.unwrap().read().sessions.is_empty());
+    };
+
+    let (client, server) = new_client_server(port, false);
+    perform_test(client, server, Some(client_test), server_test).await;
+}
+
+/// Connect to a server, have the server forget the session and expect the client to reconnect
+/// with a new session when the server faults its requests with `BadSessionIdInvalid`.
+#[tokio::test]
+async fn reconnect_on_invalid_session() {
+    let port = next_port();
+    let client_endpoint = endpoint_none(port);
+
+    let client_test = move |_rx_client_command: mpsc::UnboundedReceiver<ClientCommand>,
+                            mut client: Client| async move {
+        let (session, event_loop) = client
+            .new_session_from_endpoint(client_endpoint, IdentityToken::Anonymous)
+            .await
+            .unwrap();
+
+        let (tx_events, mut rx_events) = unbounded_channel();
+        let handle = tokio::task::spawn(async move {
+            let stream = event_loop.enter();
+            tokio::pin!(stream);
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(event) => {
+                        let _ = tx_events.send(event);
+                    }
+                    Err(err) => return err,
+                }
+            }
+            StatusCode::Good
+        });
+        session.wait_for_connection().await;
+
+        // Keep reading until the server faults the session and the client reconnects
+        let mut lost = None;
+        loop {
+            tokio::select! {
+                event = rx_events.recv() => match event.unwrap() {
+                    SessionPollResult::ConnectionLost(code) => lost = Some(code),
+                    SessionPollResult::Reconnected(mode) if lost.is_some() => {
+                        assert!(matches!(mode, SessionConnectMode::NewSession(_)));
+                        break;
+                    }
+                    _ => (),
+                },
+                _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {
+                    let _ = session
+                        .read(&[v1_node_id().into()], TimestampsToReturn::Both, 1.0)
+                        .await;
+                }
+            }
+        }
+        assert_eq!(lost, Some(StatusCode::BadSessionIdInvalid));
+        assert!(session
+            .read(&[v1_node_id().into()], TimestampsToReturn::Both, 1.0)
+            .await
+            .is_ok());
+
+        session.disconnect().await.unwrap();
+        handle.await.unwrap();
+    };
+
+    let server_test = |mut rx_server_command: mpsc::UnboundedReceiver<ServerCommand>,
+                       server: Server| async move {
+        let connections = server.connections();
+        let server = Arc::new(RwLock::new(server));
+        let server_fut = Server::new_server_task(server.clone());
+        tokio::pin!(server_fut);
+
+        let mut forgotten = false;
+        let mut timer = tokio::time::interval(std::time::Duration::from_millis(100));
+        loop {
+            tokio::select! {
+                _ = rx_server_command.recv() => {
+                    server.write().abort();
+                    server_fut.await;
+                    break;
+                }
+                _ = &mut server_fut => {
+                    break;
+                }
+                _ = timer.tick(), if !forgotten => {
+                    // Drop the sessions of the connection so the server no longer recognises them
+                    let connections = connections.read();
+                    for transport in connections.iter() {
+                        let session_manager = transport.read().session_manager();
+                        let mut session_manager = session_manager.write();
+                        if !session_manager.sessions.is_empty() {
+                            session_manager.sessions.clear();
+                            forgotten = true;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(forgotten);
+    };
+
+    let (client, server) = new_client_server(port, false);
+    perform_test(client, server, Some(client_test), server_test).await;
+}
@@ -1,6 +1,6 @@
 use std::{path::PathBuf, time::Duration};
 
-use crate::server::prelude::Config;
+use crate::core::config::{Config, ConfigOverrideError};
 
 use super::{Client, ClientConfig, ClientEndpoint, ClientUserToken, ANONYMOUS_USER_TOKEN_ID};
 
@@ -22,6 +22,14 @@ impl ClientBuilder {
         })
     }
 
+    /// Overrides the values set so far with those of environment variables, e.g.
+    /// `OPCUA_CLIENT_DEFAULT_ENDPOINT`. Values set on the builder afterwards override them in turn.
+    pub fn with_env_overrides(self) -> Result<Self, ConfigOverrideError> {
+        Ok(ClientBuilder {
+            config: self.config.with_env_overrides()?,
+        })
+    }
+
     /// Yields a [`Client`] from the values set by the builder. If the builder is not in a valid state
     /// it will return `None`.
     ///
@@ -195,6 +203,37 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the urls to try in order when the url of the session's endpoint cannot be reached,
+    /// e.g. redundant network paths to the same server. Each round of connection attempts tries
+    /// the endpoint url and then every fallback, and the session retry settings apply to the
+    /// rounds.
+    pub fn fallback_endpoint_urls<T>(mut self, fallback_endpoint_urls: Vec<T>) -> Self
+    where
+        T: Into<String>,
+    {
+        self.config.fallback_endpoint_urls = fallback_endpoint_urls
+            .into_iter()
+            .map(|url| url.into())
+            .collect();
+        self
+    }
+
+    /// Sets a file that the traffic of every connection is captured to, for debugging.
+    pub fn capture_file<T>(mut self, capture_file: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        self.config.capture_file = Some(capture_file.into());
+        self
+    }
+
+    /// Sets whether the decoded messages of every connection are captured to the capture file as
+    /// well as its chunks. Off by default.
+    pub fn capture_messages(mut self, capture_messages: bool) -> Self {
+        self.config.capture_messages = capture_messages;
+        self
+    }
+
     /// Sets the session retry limit.
     ///
     /// # Panics
@@ -258,6 +297,25 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets whether the server should delete the subscriptions of the session when the
+    /// session is closed, either by [`Session::disconnect`] or by dropping the session.
+    /// The default is `true`.
+    ///
+    /// [`Session::disconnect`]: crate::client::Session::disconnect
+    pub fn delete_subscriptions_on_close(mut self, delete_subscriptions_on_close: bool) -> Self {
+        self.config.delete_subscriptions_on_close = delete_subscriptions_on_close;
+        self
+    }
+
+    /// Set the timeout on closing the session and the secure channel when a connected session
+    /// is dropped without calling [`Session::disconnect`].
+    ///
+    /// [`Session::disconnect`]: crate::client::Session::disconnect
+    pub fn close_timeout(mut self, close_timeout: Duration) -> Self {
+        self.config.close_timeout = close_timeout;
+        self
+    }
+
     /// Sets whether the client should ignore clock skew so the client can make a successful
     /// connection to the server, even when the client and server clocks are out of sync.
     pub fn ignore_clock_skew(mut self) -> Self {
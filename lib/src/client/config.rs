@@ -13,8 +13,8 @@ use std::{
 };
 
 use crate::{
-    core::config::Config,
-    crypto::SecurityPolicy,
+    core::{comms::url::is_valid_opc_ua_url, config::Config},
+    crypto::{SecurityPolicy, X509},
     types::{ApplicationType, MessageSecurityMode, UAString},
 };
 
@@ -110,6 +110,12 @@ pub struct ClientEndpoint {
     /// User id to use with the endpoint
     #[serde(default = "ClientEndpoint::anonymous_id")]
     pub user_token_id: String,
+    /// Certificates the server must present on this endpoint, each one as the hex string of the
+    /// thumbprint of the cert or of the SHA-256 hash of its public key. If any are set, the client
+    /// refuses a server cert that matches none of them, whatever the trust store holds. Pins
+    /// cannot be set on an endpoint without a security policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_certificates: Vec<String>,
 }
 
 impl ClientEndpoint {
@@ -123,9 +129,40 @@ impl ClientEndpoint {
             security_policy: SecurityPolicy::None.to_str().into(),
             security_mode: MessageSecurityMode::None.into(),
             user_token_id: Self::anonymous_id(),
+            pinned_certificates: Vec::new(),
         }
     }
 
+    /// Sets the security policy and message security mode of the endpoint
+    pub fn security(
+        mut self,
+        security_policy: SecurityPolicy,
+        security_mode: MessageSecurityMode,
+    ) -> Self {
+        self.security_policy = security_policy.to_str().into();
+        self.security_mode = security_mode.into();
+        self
+    }
+
+    /// Sets the id of the user token to use with the endpoint
+    pub fn user_token_id<T>(mut self, user_token_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.user_token_id = user_token_id.into();
+        self
+    }
+
+    /// Adds a certificate that the server may present on the endpoint, as the hex string of the
+    /// thumbprint of the cert or of the SHA-256 hash of its public key
+    pub fn pinned_certificate<T>(mut self, pinned_certificate: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.pinned_certificates.push(pinned_certificate.into());
+        self
+    }
+
     fn anonymous_id() -> String {
         ANONYMOUS_USER_TOKEN_ID.to_string()
     }
@@ -197,6 +234,21 @@ pub struct ClientConfig {
     pub(crate) user_tokens: BTreeMap<String, ClientUserToken>,
     /// List of end points
     pub(crate) endpoints: BTreeMap<String, ClientEndpoint>,
+    /// Urls to try in order when the url of a session's endpoint cannot be reached, e.g.
+    /// redundant network paths to the same server.
+    #[serde(default)]
+    pub(crate) fallback_endpoint_urls: Vec<String>,
+    /// File that the chunks of every connection are captured to, for debugging problems with the
+    /// stacks of servers. The capture can be replayed with
+    /// `opcua::core::comms::capture::replay_capture()`. A capture is sensitive, the chunks of a
+    /// channel without security hold user names and passwords in the clear.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) capture_file: Option<PathBuf>,
+    /// Also capture the decoded messages of every connection to the capture file, which shows
+    /// the messages of secured channels too. Identity tokens, user token signatures and nonces
+    /// are blanked out.
+    #[serde(default)]
+    pub(crate) capture_messages: bool,
     /// Decoding options used for serialization / deserialization
     pub(crate) decoding_options: DecodingOptions,
     /// Maximum number of times to attempt to reconnect to the server before giving up.
@@ -223,6 +275,13 @@ pub struct ClientConfig {
 
     /// Requested session timeout in milliseconds
     pub(crate) session_timeout: u32,
+    /// Whether the server should delete the subscriptions of the session when the session
+    /// is closed.
+    #[serde(default = "ClientConfig::default_delete_subscriptions_on_close")]
+    pub(crate) delete_subscriptions_on_close: bool,
+    /// Timeout for closing the session when it is dropped without being disconnected.
+    #[serde(default = "ClientConfig::default_close_timeout")]
+    pub(crate) close_timeout: Duration,
 
     /// Client performance settings
     pub(crate) performance: Performance,
@@ -231,6 +290,8 @@ pub struct ClientConfig {
 }
 
 impl Config for ClientConfig {
+    const ENV_PREFIX: &'static str = "OPCUA_CLIENT_";
+
     /// Test if the config is valid, which requires at the least that
     fn is_valid(&self) -> bool {
         let mut valid = true;
@@ -296,8 +357,39 @@ impl Config for ClientConfig {
                     );
                     valid = false;
                 }
+                if e.user_token_id != ANONYMOUS_USER_TOKEN_ID
+                    && !self.user_tokens.contains_key(&e.user_token_id)
+                {
+                    error!(
+                        "Endpoint {} user token id {} does not exist in list of user tokens",
+                        id, e.user_token_id
+                    );
+                    valid = false;
+                }
+                e.pinned_certificates.iter().for_each(|pin| {
+                    if !X509::is_valid_pin(pin) {
+                        error!("Endpoint {} pinned certificate {} is invalid", id, pin);
+                        valid = false;
+                    }
+                });
+                // Without security the server never proves it holds the key of its certificate
+                if !e.pinned_certificates.is_empty()
+                    && SecurityPolicy::from_str(&e.security_policy).unwrap() == SecurityPolicy::None
+                {
+                    error!(
+                        "Endpoint {} pins certificates but has no security policy",
+                        id
+                    );
+                    valid = false;
+                }
             });
         }
+        self.fallback_endpoint_urls.iter().for_each(|url| {
+            if !is_valid_opc_ua_url(url) {
+                error!("Fallback endpoint url {} is invalid", url);
+                valid = false;
+            }
+        });
         if self.session_retry_limit < 0 && self.session_retry_limit != -1 {
             error!("Session retry limit of {} is invalid - must be -1 (infinite), 0 (never) or a positive value", self.session_retry_limit);
             valid = false;
@@ -351,6 +443,9 @@ impl ClientConfig {
             default_endpoint: String::new(),
             user_tokens: BTreeMap::new(),
             endpoints: BTreeMap::new(),
+            fallback_endpoint_urls: Vec::new(),
+            capture_file: None,
+            capture_messages: false,
             session_retry_limit: SessionRetryPolicy::DEFAULT_RETRY_LIMIT as i32,
             session_retry_initial: Duration::from_secs(1),
             session_retry_max: Duration::from_secs(30),
@@ -360,6 +455,8 @@ impl ClientConfig {
             publish_timeout: Duration::from_secs(60),
             max_inflight_publish: 2,
             session_timeout: 0,
+            delete_subscriptions_on_close: true,
+            close_timeout: Self::default_close_timeout(),
             decoding_options: DecodingOptions {
                 max_array_length: decoding_options.max_array_length,
                 max_string_length: decoding_options.max_string_length,
@@ -377,14 +474,22 @@ impl ClientConfig {
             session_name: "Rust OPC UA Client".into(),
         }
     }
+
+    fn default_delete_subscriptions_on_close() -> bool {
+        true
+    }
+
+    fn default_close_timeout() -> Duration {
+        Duration::from_secs(2)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{self, collections::BTreeMap, path::PathBuf};
+    use std::{self, collections::BTreeMap, path::PathBuf, time::Duration};
 
     use crate::client::ClientBuilder;
-    use crate::core::config::Config;
+    use crate::core::config::{Config, ConfigOverrideError};
     use crate::crypto::SecurityPolicy;
     use crate::types::*;
 
@@ -413,6 +518,7 @@ mod tests {
                         security_policy: String::from(SecurityPolicy::None.to_str()),
                         security_mode: String::from(MessageSecurityMode::None),
                         user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                        pinned_certificates: Vec::new(),
                     },
                 ),
                 (
@@ -422,6 +528,7 @@ mod tests {
                         security_policy: String::from(SecurityPolicy::Basic128Rsa15.to_str()),
                         security_mode: String::from(MessageSecurityMode::SignAndEncrypt),
                         user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                        pinned_certificates: Vec::new(),
                     },
                 ),
                 (
@@ -431,6 +538,7 @@ mod tests {
                         security_policy: String::from(SecurityPolicy::Basic256.to_str()),
                         security_mode: String::from(MessageSecurityMode::SignAndEncrypt),
                         user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                        pinned_certificates: Vec::new(),
                     },
                 ),
                 (
@@ -440,6 +548,7 @@ mod tests {
                         security_policy: String::from(SecurityPolicy::Basic256Sha256.to_str()),
                         security_mode: String::from(MessageSecurityMode::SignAndEncrypt),
                         user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                        pinned_certificates: Vec::new(),
                     },
                 ),
             ])
@@ -490,6 +599,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn client_builder_config() {
+        // A config made entirely in code, with no config file
+        let config = ClientBuilder::new()
+            .application_name("Embedded Client")
+            .application_uri("urn:EmbeddedClient")
+            .product_uri("urn:EmbeddedClient")
+            .certificate_path("own/embedded.der")
+            .private_key_path("private/embedded.pem")
+            .verify_server_certs(true)
+            .endpoint(
+                "secure",
+                ClientEndpoint::new("opc.tcp://127.0.0.1:4855/")
+                    .security(
+                        SecurityPolicy::Basic256Sha256,
+                        MessageSecurityMode::SignAndEncrypt,
+                    )
+                    .user_token_id("operator")
+                    .pinned_certificate(
+                        "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08",
+                    ),
+            )
+            .default_endpoint("secure")
+            .user_token("operator", ClientUserToken::user_pass("op", "op1"))
+            .session_retry_limit(-1)
+            .session_retry_initial(Duration::from_secs(1))
+            .session_retry_max(Duration::from_secs(10))
+            .config();
+        assert!(config.is_valid());
+
+        let endpoint = &config.endpoints["secure"];
+        assert_eq!(endpoint.security_policy(), SecurityPolicy::Basic256Sha256);
+        assert_eq!(
+            MessageSecurityMode::from(endpoint.security_mode.as_ref()),
+            MessageSecurityMode::SignAndEncrypt
+        );
+        assert_eq!(endpoint.user_token_id, "operator");
+        assert_eq!(endpoint.pinned_certificates.len(), 1);
+        assert_eq!(config.session_retry_limit, -1);
+        assert_eq!(config.session_retry_initial, Duration::from_secs(1));
+        assert_eq!(config.session_retry_max, Duration::from_secs(10));
+
+        // The endpoint's user token must exist
+        let config = ClientBuilder::new()
+            .application_name("Embedded Client")
+            .application_uri("urn:EmbeddedClient")
+            .endpoint(
+                "secure",
+                ClientEndpoint::new("opc.tcp://127.0.0.1:4855/").user_token_id("nobody"),
+            )
+            .config();
+        assert!(!config.is_valid());
+    }
+
+    #[test]
+    fn client_config_overrides() {
+        let config = default_sample_config()
+            .with_overrides(vec![
+                ("OPCUA_CLIENT_DEFAULT_ENDPOINT", "sample_basic256"),
+                ("OPCUA_CLIENT_SESSION_RETRY_LIMIT", "-1"),
+                (
+                    "OPCUA_CLIENT_ENDPOINTS__SAMPLE_NONE__URL",
+                    "opc.tcp://plant:4855/",
+                ),
+                ("OPCUA_CLIENT_SESSION_RETRY_MAX__SECS", "5"),
+                ("OPCUA_CERT_PATH", "own/client.der"),
+                ("OPCUA_SERVER_PORT", "1234"),
+            ])
+            .unwrap();
+        assert!(config.is_valid());
+        assert_eq!(config.default_endpoint, "sample_basic256");
+        assert_eq!(config.session_retry_limit, -1);
+        assert_eq!(config.endpoints["sample_none"].url, "opc.tcp://plant:4855/");
+        assert_eq!(config.session_retry_max, Duration::from_secs(5));
+        assert_eq!(
+            config.certificate_path,
+            Some(PathBuf::from("own/client.der"))
+        );
+
+        // The error names the variable with the bad value, not one applied after it
+        let err = default_sample_config()
+            .with_overrides(vec![
+                ("OPCUA_CLIENT_SESSION_RETRY_LIMIT", "many"),
+                ("OPCUA_CLIENT_SESSION_NAME", "plant"),
+            ])
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            ConfigOverrideError::InvalidValue { name, value, .. }
+                if name == "OPCUA_CLIENT_SESSION_RETRY_LIMIT" && value == "many"
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("Invalid value \"many\" for OPCUA_CLIENT_SESSION_RETRY_LIMIT"));
+    }
+
     #[test]
     fn client_invalid_security_policy_config() {
         let mut config = default_sample_config();
@@ -502,6 +707,7 @@ mod tests {
                 security_policy: String::from("http://blah"),
                 security_mode: String::from(MessageSecurityMode::None),
                 user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                pinned_certificates: Vec::new(),
             },
         );
         assert!(!config.is_valid());
@@ -519,11 +725,34 @@ mod tests {
                 security_policy: String::from(SecurityPolicy::Basic128Rsa15.to_uri()),
                 security_mode: String::from("SingAndEncrypt"),
                 user_token_id: ANONYMOUS_USER_TOKEN_ID.to_string(),
+                pinned_certificates: Vec::new(),
             },
         );
         assert!(!config.is_valid());
     }
 
+    #[test]
+    fn client_invalid_pinned_certificate_config() {
+        let mut config = default_sample_config();
+        let pinned_certificates = vec![
+            "3b:5a:2f:10:1c:5a:2f:10:1c:5a:2f:10:1c:5a:2f:10:1c:5a:2f:10".to_string(),
+            "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08".to_string(),
+        ];
+        let endpoint = config.endpoints.get_mut("sample_basic256sha256").unwrap();
+        endpoint.pinned_certificates = pinned_certificates.clone();
+        assert!(config.is_valid());
+        // Neither a thumbprint nor a public key hash
+        let endpoint = config.endpoints.get_mut("sample_basic256sha256").unwrap();
+        endpoint.pinned_certificates.push("abcdef".to_string());
+        assert!(!config.is_valid());
+
+        // Pins on an endpoint without security are refused
+        let mut config = default_sample_config();
+        let endpoint = config.endpoints.get_mut("sample_none").unwrap();
+        endpoint.pinned_certificates = pinned_certificates;
+        assert!(!config.is_valid());
+    }
+
     #[test]
     fn client_anonymous_user_tokens_id() {
         let mut config = default_sample_config();
@@ -540,4 +769,20 @@ mod tests {
         );
         assert!(!config.is_valid());
     }
+
+    #[test]
+    fn client_fallback_endpoint_urls_config() {
+        let mut config = default_sample_config();
+        config.fallback_endpoint_urls = vec![String::from("opc.tcp://192.168.1.2:4855")];
+        assert!(config.is_valid());
+        // Fallbacks may use any transport the client is built with
+        config
+            .fallback_endpoint_urls
+            .push(String::from("opc.wss://192.168.1.3:4855"));
+        assert_eq!(config.is_valid(), cfg!(feature = "websocket"));
+        config.fallback_endpoint_urls[1] = String::from("https://192.168.1.3:4855");
+        assert_eq!(config.is_valid(), cfg!(feature = "https"));
+        config.fallback_endpoint_urls[1] = String::from("http://192.168.1.3:4855");
+        assert!(!config.is_valid());
+    }
 }
@@ -0,0 +1,219 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+use crate::types::{DataValue, DateTime, JsonEncoder, JsonEncoding, NodeId, StatusCode, Variant};
+
+use super::ValueSerializer;
+
+/// Serializes values as Avro binary framed for a Confluent compatible schema registry, i.e. a zero
+/// magic byte, the 4 byte big endian schema id and then the Avro encoded record.
+///
+/// The record always conforms to [`AvroSerializer::SCHEMA`]. The schema must be registered with the
+/// registry under the subject of the topic before exporting and the id it was given supplied to
+/// [`AvroSerializer::new`].
+///
+/// Scalar numbers, booleans, strings and byte strings map onto the equivalent Avro types. Any other
+/// value, including arrays, is written as a string using its OPC UA JSON encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct AvroSerializer {
+    schema_id: u32,
+}
+
+// Indices into the value union of the schema
+const VALUE_NULL: i64 = 0;
+const VALUE_BOOLEAN: i64 = 1;
+const VALUE_LONG: i64 = 2;
+const VALUE_DOUBLE: i64 = 3;
+const VALUE_STRING: i64 = 4;
+const VALUE_BYTES: i64 = 5;
+
+impl AvroSerializer {
+    /// The Avro schema of the records produced by this serializer.
+    pub const SCHEMA: &'static str = r#"{"type":"record","name":"DataValue","namespace":"org.opcfoundation.ua","fields":[{"name":"nodeId","type":"string"},{"name":"value","type":["null","boolean","long","double","string","bytes"]},{"name":"status","type":"long"},{"name":"sourceTimestamp","type":["null",{"type":"long","logicalType":"timestamp-micros"}]},{"name":"serverTimestamp","type":["null",{"type":"long","logicalType":"timestamp-micros"}]}]}"#;
+
+    /// Create a serializer that writes records tagged with the supplied schema id.
+    pub fn new(schema_id: u32) -> Self {
+        Self { schema_id }
+    }
+
+    /// The schema id written into the header of each record.
+    pub fn schema_id(&self) -> u32 {
+        self.schema_id
+    }
+
+    fn write_long(buf: &mut Vec<u8>, v: i64) {
+        // Zig-zag encoding followed by a variable length quantity
+        let mut v = ((v << 1) ^ (v >> 63)) as u64;
+        while v & !0x7f != 0 {
+            buf.push(((v & 0x7f) | 0x80) as u8);
+            v >>= 7;
+        }
+        buf.push(v as u8);
+    }
+
+    fn write_bytes(buf: &mut Vec<u8>, v: &[u8]) {
+        Self::write_long(buf, v.len() as i64);
+        buf.extend_from_slice(v);
+    }
+
+    fn write_timestamp(buf: &mut Vec<u8>, v: Option<&DateTime>) {
+        match v {
+            Some(v) => {
+                Self::write_long(buf, 1);
+                Self::write_long(buf, v.as_chrono().timestamp_micros());
+            }
+            None => Self::write_long(buf, 0),
+        }
+    }
+
+    fn write_value(buf: &mut Vec<u8>, v: Option<&Variant>) {
+        let Some(v) = v else {
+            Self::write_long(buf, VALUE_NULL);
+            return;
+        };
+        match v {
+            Variant::Empty => Self::write_long(buf, VALUE_NULL),
+            Variant::Boolean(v) => {
+                Self::write_long(buf, VALUE_BOOLEAN);
+                buf.push(u8::from(*v));
+            }
+            Variant::SByte(v) => Self::write_union_long(buf, *v as i64),
+            Variant::Byte(v) => Self::write_union_long(buf, *v as i64),
+            Variant::Int16(v) => Self::write_union_long(buf, *v as i64),
+            Variant::UInt16(v) => Self::write_union_long(buf, *v as i64),
+            Variant::Int32(v) => Self::write_union_long(buf, *v as i64),
+            Variant::UInt32(v) => Self::write_union_long(buf, *v as i64),
+            Variant::Int64(v) => Self::write_union_long(buf, *v),
+            // A UInt64 beyond the range of a long cannot be represented as one
+            Variant::UInt64(v) if *v <= i64::MAX as u64 => Self::write_union_long(buf, *v as i64),
+            Variant::Float(v) => Self::write_union_double(buf, *v as f64),
+            Variant::Double(v) => Self::write_union_double(buf, *v),
+            Variant::String(v) => {
+                Self::write_long(buf, VALUE_STRING);
+                Self::write_bytes(buf, v.as_ref().as_bytes());
+            }
+            Variant::ByteString(v) => {
+                Self::write_long(buf, VALUE_BYTES);
+                Self::write_bytes(buf, v.as_ref());
+            }
+            v => {
+                let json = v.encode_json(JsonEncoding::Reversible).to_string();
+                Self::write_long(buf, VALUE_STRING);
+                Self::write_bytes(buf, json.as_bytes());
+            }
+        }
+    }
+
+    fn write_union_long(buf: &mut Vec<u8>, v: i64) {
+        Self::write_long(buf, VALUE_LONG);
+        Self::write_long(buf, v);
+    }
+
+    fn write_union_double(buf: &mut Vec<u8>, v: f64) {
+        Self::write_long(buf, VALUE_DOUBLE);
+        buf.extend_from_slice(&v.to_le_bytes());
+    }
+}
+
+impl ValueSerializer for AvroSerializer {
+    fn serialize(&self, node_id: &NodeId, value: &DataValue) -> Result<Vec<u8>, StatusCode> {
+        let mut buf = Vec::with_capacity(64);
+        buf.push(0);
+        buf.extend_from_slice(&self.schema_id.to_be_bytes());
+        Self::write_bytes(&mut buf, node_id.to_string().as_bytes());
+        Self::write_value(&mut buf, value.value.as_ref());
+        Self::write_long(&mut buf, value.status().bits() as i64);
+        Self::write_timestamp(&mut buf, value.source_timestamp.as_ref());
+        Self::write_timestamp(&mut buf, value.server_timestamp.as_ref());
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::AvroSerializer;
+    use crate::client::export::ValueSerializer;
+    use crate::types::{ByteString, DataValue, DateTime, NodeId, StatusCode, Variant};
+
+    fn long(v: i64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        AvroSerializer::write_long(&mut buf, v);
+        buf
+    }
+
+    fn value(v: Option<Variant>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        AvroSerializer::write_value(&mut buf, v.as_ref());
+        buf
+    }
+
+    #[test]
+    fn zig_zag_longs() {
+        assert_eq!(long(0), [0x00]);
+        assert_eq!(long(-1), [0x01]);
+        assert_eq!(long(1), [0x02]);
+        assert_eq!(long(-64), [0x7f]);
+        assert_eq!(long(64), [0x80, 0x01]);
+        assert_eq!(long(1_000_000), [0x80, 0x89, 0x7a]);
+        assert_eq!(
+            long(i64::MAX),
+            [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+        );
+        assert_eq!(
+            long(i64::MIN),
+            [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+        );
+    }
+
+    #[test]
+    fn union_branches() {
+        // The branch index is written as a long before the value
+        assert_eq!(value(None), [0x00]);
+        assert_eq!(value(Some(Variant::Empty)), [0x00]);
+        assert_eq!(value(Some(Variant::Boolean(true))), [0x02, 0x01]);
+        assert_eq!(value(Some(Variant::Int32(5))), [0x04, 0x0a]);
+        assert_eq!(value(Some(Variant::UInt64(u64::MAX >> 1))), {
+            let mut expected = vec![0x04];
+            AvroSerializer::write_long(&mut expected, i64::MAX);
+            expected
+        });
+        // Beyond a long, so written as a string
+        assert_eq!(value(Some(Variant::UInt64(u64::MAX)))[0], 0x08);
+        assert_eq!(
+            value(Some(Variant::Double(1.5))),
+            [0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0x3f]
+        );
+        assert_eq!(value(Some(Variant::from("ab"))), [0x08, 0x04, b'a', b'b']);
+        assert_eq!(
+            value(Some(Variant::ByteString(ByteString::from(vec![1u8, 2, 3])))),
+            [0x0a, 0x06, 0x01, 0x02, 0x03]
+        );
+        // Arrays are written as strings of their JSON encoding
+        let json = br#"{"Body":[1,2],"Type":6}"#;
+        let mut expected = vec![0x08];
+        AvroSerializer::write_bytes(&mut expected, json);
+        assert_eq!(value(Some(Variant::from(vec![1i32, 2]))), expected);
+    }
+
+    #[test]
+    fn framed_record() {
+        let mut data_value = DataValue::value_only(5i32);
+        data_value.status = Some(StatusCode::Good);
+        data_value.source_timestamp = Some(DateTime::from(Utc.timestamp_opt(1, 0).unwrap()));
+        let record = AvroSerializer::new(0x01020304)
+            .serialize(&NodeId::new(2, "v1"), &data_value)
+            .unwrap();
+
+        let mut expected = vec![0x00, 0x01, 0x02, 0x03, 0x04];
+        expected.push(0x12);
+        expected.extend_from_slice(b"ns=2;s=v1");
+        expected.extend_from_slice(&[0x04, 0x0a]);
+        expected.push(0x00);
+        expected.extend_from_slice(&[0x02, 0x80, 0x89, 0x7a]);
+        expected.push(0x00);
+        assert_eq!(record, expected);
+    }
+}
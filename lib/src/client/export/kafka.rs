@@ -0,0 +1,121 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+use std::sync::Arc;
+
+use crate::{
+    client::{MonitoredItem, OnSubscriptionNotification},
+    types::{DataValue, StatusCode},
+};
+
+use super::{ExportKey, JsonSerializer, ValueSerializer};
+
+/// A record to be published to a Kafka topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaRecord {
+    /// The topic the record is published to.
+    pub topic: String,
+    /// The key of the record, used by Kafka for partitioning.
+    pub key: Option<Vec<u8>>,
+    /// The serialized value.
+    pub payload: Vec<u8>,
+}
+
+/// The sink that records are published to. Implement this over the Kafka client used by the
+/// application. Implementations should not block, e.g. by enqueuing onto an asynchronous producer,
+/// since they are called from the subscription notification callback.
+pub trait KafkaProducer: Send + Sync {
+    /// Publish the record.
+    fn send(&self, record: KafkaRecord) -> Result<(), StatusCode>;
+}
+
+impl<F> KafkaProducer for F
+where
+    F: Fn(KafkaRecord) -> Result<(), StatusCode> + Send + Sync,
+{
+    fn send(&self, record: KafkaRecord) -> Result<(), StatusCode> {
+        self(record)
+    }
+}
+
+/// Exports data changes of monitored items to a Kafka topic. Pass this as the callback when
+/// creating a subscription.
+///
+/// ```no_run
+/// use opcua::client::export::{AvroSerializer, ExportKey, KafkaExporter, KafkaRecord};
+/// use opcua::types::StatusCode;
+///
+/// let exporter = KafkaExporter::new("opcua-values", |record: KafkaRecord| {
+///     // Hand the record to the Kafka producer here
+///     Ok::<(), StatusCode>(())
+/// })
+/// .key(ExportKey::NodeId)
+/// .serializer(AvroSerializer::new(1));
+/// ```
+pub struct KafkaExporter {
+    topic: String,
+    key: ExportKey,
+    serializer: Box<dyn ValueSerializer>,
+    producer: Arc<dyn KafkaProducer>,
+}
+
+impl KafkaExporter {
+    /// Create an exporter publishing to the supplied topic. Records are keyed by node id and values
+    /// serialized as OPC UA JSON unless configured otherwise.
+    pub fn new(topic: impl Into<String>, producer: impl KafkaProducer + 'static) -> Self {
+        Self::new_shared(topic, Arc::new(producer))
+    }
+
+    /// Create an exporter publishing through a producer which is shared, e.g. with other exporters.
+    pub fn new_shared(topic: impl Into<String>, producer: Arc<dyn KafkaProducer>) -> Self {
+        Self {
+            topic: topic.into(),
+            key: ExportKey::default(),
+            serializer: Box::new(JsonSerializer),
+            producer,
+        }
+    }
+
+    /// Set how the key of each record is derived.
+    pub fn key(mut self, key: ExportKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Set the serializer used for the value of each record.
+    pub fn serializer(mut self, serializer: impl ValueSerializer + 'static) -> Self {
+        self.serializer = Box::new(serializer);
+        self
+    }
+
+    /// Serialize a data value into a record without publishing it.
+    pub fn record(
+        &self,
+        value: &DataValue,
+        item: &MonitoredItem,
+    ) -> Result<KafkaRecord, StatusCode> {
+        let node_id = &item.item_to_monitor().node_id;
+        Ok(KafkaRecord {
+            topic: self.topic.clone(),
+            key: self.key.key(node_id, item.client_handle()),
+            payload: self.serializer.serialize(node_id, value)?,
+        })
+    }
+}
+
+impl OnSubscriptionNotification for KafkaExporter {
+    fn on_data_value(&mut self, notification: DataValue, item: &MonitoredItem) {
+        let result = self
+            .record(&notification, item)
+            .and_then(|record| self.producer.send(record));
+        if let Err(err) = result {
+            error!(
+                "Failed to export value of {} to topic {}, {}",
+                item.item_to_monitor().node_id,
+                self.topic,
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Exporters that forward monitored item changes received by a client to some external system.
+//!
+//! An exporter implements [`OnSubscriptionNotification`] so it can be passed straight to
+//! [`Session::create_subscription`]. Each data change is turned into a keyed record, the value
+//! serialized by a pluggable [`ValueSerializer`], and handed to a sink supplied by the caller.
+//!
+//! The crate does not link against a Kafka client. Instead the [`KafkaProducer`] trait is
+//! implemented over whatever producer the application already uses, e.g. `rdkafka`.
+//!
+//! [`OnSubscriptionNotification`]: crate::client::OnSubscriptionNotification
+//! [`Session::create_subscription`]: crate::client::Session::create_subscription
+
+mod avro;
+mod kafka;
+
+pub use avro::AvroSerializer;
+pub use kafka::{KafkaExporter, KafkaProducer, KafkaRecord};
+
+use crate::types::{DataValue, NodeId, StatusCode};
+
+/// Determines the key attached to each exported record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportKey {
+    /// The node id of the monitored item in its string form, e.g. `ns=2;s=v1`.
+    #[default]
+    NodeId,
+    /// The client handle of the monitored item as a decimal string.
+    ClientHandle,
+    /// Records are sent without a key.
+    None,
+}
+
+impl ExportKey {
+    pub(crate) fn key(&self, node_id: &NodeId, client_handle: u32) -> Option<Vec<u8>> {
+        match self {
+            ExportKey::NodeId => Some(node_id.to_string().into_bytes()),
+            ExportKey::ClientHandle => Some(client_handle.to_string().into_bytes()),
+            ExportKey::None => None,
+        }
+    }
+}
+
+/// Serializes a data value received for a monitored item into the payload of an exported record.
+pub trait ValueSerializer: Send + Sync {
+    /// Serialize the value of the node into bytes.
+    fn serialize(&self, node_id: &NodeId, value: &DataValue) -> Result<Vec<u8>, StatusCode>;
+}
+
+/// Serializes values using the reversible OPC UA JSON encoding described in Part 6 5.4.
+///
+/// The payload is an object with a `NodeId` and a `Value` field holding the JSON forms of the node id
+/// and the data value respectively.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSerializer;
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonRecord<'a> {
+    node_id: &'a NodeId,
+    value: &'a DataValue,
+}
+
+impl ValueSerializer for JsonSerializer {
+    fn serialize(&self, node_id: &NodeId, value: &DataValue) -> Result<Vec<u8>, StatusCode> {
+        serde_json::to_vec(&JsonRecord { node_id, value }).map_err(|err| {
+            error!("Cannot serialize value of {} to JSON, {}", node_id, err);
+            StatusCode::BadEncodingError
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportKey;
+    use crate::types::NodeId;
+
+    #[test]
+    fn export_keys() {
+        let node_id = NodeId::new(2, "v1");
+        assert_eq!(
+            ExportKey::NodeId.key(&node_id, 42),
+            Some(b"ns=2;s=v1".to_vec())
+        );
+        assert_eq!(
+            ExportKey::ClientHandle.key(&node_id, 42),
+            Some(b"42".to_vec())
+        );
+        assert_eq!(ExportKey::None.key(&node_id, 42), None);
+        assert_eq!(ExportKey::default(), ExportKey::NodeId);
+    }
+}
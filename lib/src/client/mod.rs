@@ -29,6 +29,10 @@
 //!
 //! Data change and event notifications are via asynchronous callbacks.
 //!
+//! Scripts that only need to read, write or browse a few nodes can use the blocking functions in
+//! [`oneshot`] such as [`read`], which connect, make a single call and disconnect again. A
+//! [`SimpleClient`] makes the same calls as another user or over a secure channel.
+//!
 //! # Example
 //!
 //! Here is a complete example of a client that connects to the `samples/simple-server`, subscribes
@@ -112,20 +116,26 @@
 
 mod builder;
 mod config;
+pub mod export;
+pub mod oneshot;
 mod retry;
 mod session;
 mod transport;
+pub mod watchdog;
 
 use std::path::PathBuf;
 
 pub use builder::ClientBuilder;
 pub use config::{ClientConfig, ClientEndpoint, ClientUserToken, ANONYMOUS_USER_TOKEN_ID};
+pub use oneshot::{browse, read, write, SimpleClient};
 pub use session::{
-    Client, DataChangeCallback, EventCallback, MonitoredItem, OnSubscriptionNotification, Session,
-    SessionActivity, SessionConnectMode, SessionEventLoop, SessionPollResult, Subscription,
-    SubscriptionCallbacks,
+    Client, CreatedMonitoredItem, DataChangeCallback, Downsampling, EventCallback, MonitoredItem,
+    OnSubscriptionNotification, OnSubscriptionStateChange, ServiceError, Session, SessionActivity,
+    SessionConnectMode, SessionEventLoop, SessionPollResult, Subscription, SubscriptionCallbacks,
+    SubscriptionHandle, SubscriptionState, SubscriptionStateCallback, SubscriptionStateEvent,
 };
 pub use transport::AsyncSecureChannel;
+pub use watchdog::Watchdog;
 
 #[derive(Debug, Clone)]
 pub enum IdentityToken {
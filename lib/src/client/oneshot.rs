@@ -0,0 +1,339 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Blocking functions that connect to a server, perform a single service call and disconnect
+//! again. They are meant for quick scripts and examples that do not need the full [`Session`]
+//! machinery.
+//!
+//! Each call connects anonymously to the endpoint with security policy `None`, using a default
+//! client configuration that keeps its PKI directory in the system's temporary directory.
+//!
+//! ```no_run
+//! let values = opcua::client::read("opc.tcp://localhost:4855", &["ns=2;s=v1"]).unwrap();
+//! println!("v1 = {:?}", values[0].value);
+//! ```
+//!
+//! A [`SimpleClient`] makes the same calls with another identity or security policy.
+//!
+//! ```no_run
+//! use opcua::{client::{IdentityToken, SimpleClient}, crypto::SecurityPolicy, types::MessageSecurityMode};
+//!
+//! let client = SimpleClient::new()
+//!     .security(SecurityPolicy::Basic256Sha256, MessageSecurityMode::SignAndEncrypt)
+//!     .identity_token(IdentityToken::UserName("sample1".into(), "sample1pwd".into()))
+//!     .trust_server_certs(true);
+//! let values = client.read("opc.tcp://localhost:4855", &["ns=2;s=v1"]).unwrap();
+//! ```
+//!
+//! These functions run their own runtime and block until they are done, so they must not be called
+//! from within an asynchronous runtime such as tokio. Use a [`Session`] there instead.
+//!
+//! [`Session`]: super::Session
+
+use std::{future::Future, path::PathBuf, str::FromStr, sync::Arc};
+
+use crate::{
+    crypto::SecurityPolicy,
+    types::{
+        AttributeId, BrowseDescription, BrowseDescriptionResultMask, BrowseDirection, DataValue,
+        MessageSecurityMode, NodeClassMask, NodeId, ReadValueId, ReferenceDescription,
+        ReferenceTypeId, StatusCode, TimestampsToReturn, UAString, UserTokenPolicy, Variant,
+        WriteValue,
+    },
+};
+
+use super::{ClientBuilder, IdentityToken, Session};
+
+/// Reads the values of the nodes from the server at `endpoint_url`. See [`SimpleClient::read`].
+///
+/// # Panics
+///
+/// Panics if called from within an asynchronous runtime.
+pub fn read(endpoint_url: &str, node_ids: &[&str]) -> Result<Vec<DataValue>, StatusCode> {
+    SimpleClient::new().read(endpoint_url, node_ids)
+}
+
+/// Writes values to the nodes of the server at `endpoint_url`. See [`SimpleClient::write`].
+///
+/// # Panics
+///
+/// Panics if called from within an asynchronous runtime.
+pub fn write(
+    endpoint_url: &str,
+    values: &[(&str, Variant)],
+) -> Result<Vec<StatusCode>, StatusCode> {
+    SimpleClient::new().write(endpoint_url, values)
+}
+
+/// Browses the forward hierarchical references of a node on the server at `endpoint_url`. See
+/// [`SimpleClient::browse`].
+///
+/// # Panics
+///
+/// Panics if called from within an asynchronous runtime.
+pub fn browse(endpoint_url: &str, node_id: &str) -> Result<Vec<ReferenceDescription>, StatusCode> {
+    SimpleClient::new().browse(endpoint_url, node_id)
+}
+
+/// Makes the same one-shot calls as the functions of this module, with the identity, security
+/// and PKI directory it is configured with.
+#[derive(Debug, Clone)]
+pub struct SimpleClient {
+    security_policy: SecurityPolicy,
+    security_mode: MessageSecurityMode,
+    identity_token: IdentityToken,
+    pki_dir: PathBuf,
+    trust_server_certs: bool,
+}
+
+impl Default for SimpleClient {
+    fn default() -> Self {
+        Self {
+            security_policy: SecurityPolicy::None,
+            security_mode: MessageSecurityMode::None,
+            identity_token: IdentityToken::Anonymous,
+            pki_dir: std::env::temp_dir().join("opcua-oneshot-client"),
+            trust_server_certs: false,
+        }
+    }
+}
+
+impl SimpleClient {
+    /// Creates a client that connects anonymously with security policy `None`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the security policy and mode of the endpoint to connect to. A keypair for the client
+    /// is created in the PKI directory if it does not have one yet.
+    pub fn security(
+        mut self,
+        security_policy: SecurityPolicy,
+        security_mode: MessageSecurityMode,
+    ) -> Self {
+        self.security_policy = security_policy;
+        self.security_mode = security_mode;
+        self
+    }
+
+    /// Sets the identity that the session is activated with.
+    pub fn identity_token(mut self, identity_token: IdentityToken) -> Self {
+        self.identity_token = identity_token;
+        self
+    }
+
+    /// Sets the PKI directory of the client, which defaults to one in the system's temporary
+    /// directory.
+    pub fn pki_dir(mut self, pki_dir: impl Into<PathBuf>) -> Self {
+        self.pki_dir = pki_dir.into();
+        self
+    }
+
+    /// Sets whether server certificates are trusted without being in the PKI directory.
+    pub fn trust_server_certs(mut self, trust_server_certs: bool) -> Self {
+        self.trust_server_certs = trust_server_certs;
+        self
+    }
+
+    /// Reads the values of the nodes from the server at `endpoint_url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_url` - The url of the endpoint, e.g. `opc.tcp://localhost:4855`.
+    /// * `node_ids` - The nodes to read, in the string format of a [`NodeId`], e.g. `ns=2;s=v1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<DataValue>)` - The value of each node, in the order they were supplied.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime.
+    pub fn read(
+        &self,
+        endpoint_url: &str,
+        node_ids: &[&str],
+    ) -> Result<Vec<DataValue>, StatusCode> {
+        let nodes_to_read = parse_node_ids(node_ids)?
+            .into_iter()
+            .map(ReadValueId::from)
+            .collect::<Vec<_>>();
+        self.run(endpoint_url, |session| async move {
+            Ok(session
+                .read(&nodes_to_read, TimestampsToReturn::Both, 0.0)
+                .await?)
+        })
+    }
+
+    /// Writes values to the nodes of the server at `endpoint_url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_url` - The url of the endpoint, e.g. `opc.tcp://localhost:4855`.
+    /// * `values` - Pairs of the node to write, in the string format of a [`NodeId`], and the value
+    ///   to write to it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<StatusCode>)` - The result of each write, in the order they were supplied.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime.
+    pub fn write(
+        &self,
+        endpoint_url: &str,
+        values: &[(&str, Variant)],
+    ) -> Result<Vec<StatusCode>, StatusCode> {
+        let node_ids = parse_node_ids(&values.iter().map(|(n, _)| *n).collect::<Vec<_>>())?;
+        let nodes_to_write = node_ids
+            .into_iter()
+            .zip(values.iter())
+            .map(|(node_id, (_, value))| WriteValue {
+                node_id,
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                value: value.clone().into(),
+            })
+            .collect::<Vec<_>>();
+        self.run(endpoint_url, |session| async move {
+            Ok(session.write(&nodes_to_write).await?)
+        })
+    }
+
+    /// Browses the forward hierarchical references of a node on the server at `endpoint_url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_url` - The url of the endpoint, e.g. `opc.tcp://localhost:4855`.
+    /// * `node_id` - The node to browse, in the string format of a [`NodeId`], e.g. `i=85`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ReferenceDescription>)` - The references from the node.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime.
+    pub fn browse(
+        &self,
+        endpoint_url: &str,
+        node_id: &str,
+    ) -> Result<Vec<ReferenceDescription>, StatusCode> {
+        let node_id = parse_node_ids(&[node_id])?.remove(0);
+        let browse_description = BrowseDescription {
+            node_id,
+            browse_direction: BrowseDirection::Forward,
+            reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+            include_subtypes: true,
+            node_class_mask: NodeClassMask::all().bits(),
+            result_mask: BrowseDescriptionResultMask::all().bits(),
+        };
+        self.run(endpoint_url, |session| async move {
+            let mut results = session
+                .browse(&[browse_description])
+                .await?
+                .unwrap_or_default();
+            if results.is_empty() {
+                return Err(StatusCode::BadUnexpectedError);
+            }
+            let result = results.remove(0);
+            if result.status_code.is_bad() {
+                Err(result.status_code)
+            } else {
+                // The references are returned in full unless the server limits them, in which case
+                // the remainder are left behind the continuation point.
+                let mut references = result.references.unwrap_or_default();
+                let mut continuation_point = result.continuation_point;
+                while !continuation_point.is_null() {
+                    let Some(mut results) = session
+                        .browse_next(false, &[continuation_point.clone()])
+                        .await?
+                    else {
+                        break;
+                    };
+                    let Some(result) = results.pop() else {
+                        break;
+                    };
+                    references.extend(result.references.unwrap_or_default());
+                    continuation_point = result.continuation_point;
+                }
+                Ok(references)
+            }
+        })
+    }
+
+    /// Connects to the endpoint, calls `f` with the session and disconnects afterwards.
+    fn run<T, F, Fut>(&self, endpoint_url: &str, f: F) -> Result<T, StatusCode>
+    where
+        F: FnOnce(Arc<Session>) -> Fut,
+        Fut: Future<Output = Result<T, StatusCode>>,
+    {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| {
+                error!("Cannot create a runtime for the client, error = {}", err);
+                StatusCode::BadInternalError
+            })?;
+        runtime.block_on(async move {
+            let mut client = ClientBuilder::new()
+                .application_name("OPC UA One-shot Client")
+                .application_uri("urn:OneShotClient")
+                .product_uri("urn:OneShotClient")
+                .pki_dir(self.pki_dir.clone())
+                .create_sample_keypair(self.security_policy != SecurityPolicy::None)
+                .trust_server_certs(self.trust_server_certs)
+                .session_retry_limit(0)
+                .client()
+                .ok_or(StatusCode::BadConfigurationError)?;
+
+            let (session, event_loop) = client
+                .new_session_from_endpoint(
+                    (
+                        endpoint_url,
+                        self.security_policy.to_str(),
+                        self.security_mode,
+                        UserTokenPolicy::anonymous(),
+                    ),
+                    self.identity_token.clone(),
+                )
+                .await?;
+            let mut handle = event_loop.spawn();
+
+            // The event loop ends if the connection cannot be made
+            tokio::select! {
+                _ = session.wait_for_connection() => {}
+                status_code = &mut handle => {
+                    let status_code = status_code.unwrap_or(StatusCode::BadUnexpectedError);
+                    return Err(if status_code.is_good() {
+                        StatusCode::BadNotConnected
+                    } else {
+                        status_code
+                    });
+                }
+            }
+
+            let result = f(session.clone()).await;
+            let _ = session.disconnect().await;
+            let _ = handle.await;
+            result
+        })
+    }
+}
+
+fn parse_node_ids(node_ids: &[&str]) -> Result<Vec<NodeId>, StatusCode> {
+    node_ids
+        .iter()
+        .map(|node_id| {
+            NodeId::from_str(node_id).map_err(|_| {
+                error!("Cannot parse node id \"{}\"", node_id);
+                StatusCode::BadNodeIdInvalid
+            })
+        })
+        .collect()
+}
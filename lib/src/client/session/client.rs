@@ -10,9 +10,12 @@ use crate::{
         AsyncSecureChannel, ClientConfig, ClientEndpoint, IdentityToken, ANONYMOUS_USER_TOKEN_ID,
     },
     core::{
-        comms::url::{
-            hostname_from_url, is_opc_ua_binary_url, is_valid_opc_ua_url,
-            server_url_from_endpoint_url, url_matches_except_host, url_with_replaced_hostname,
+        comms::{
+            capture::MessageCapture,
+            url::{
+                hostname_from_url, is_valid_opc_ua_url, server_url_from_endpoint_url,
+                url_matches_except_host, url_with_replaced_hostname,
+            },
         },
         config::Config,
         supported_message::SupportedMessage,
@@ -37,6 +40,8 @@ pub struct Client {
     certificate_store: Arc<RwLock<CertificateStore>>,
     /// The session retry policy for new sessions
     session_retry_policy: SessionRetryPolicy,
+    /// Capture file that the traffic of connections is recorded to
+    message_capture: Option<MessageCapture>,
 }
 
 impl Client {
@@ -85,10 +90,19 @@ impl Client {
             config.session_retry_initial,
         );
 
+        let message_capture = config.capture_file.as_ref().and_then(|path| {
+            MessageCapture::open(path, config.capture_messages)
+                .map_err(|err| {
+                    error!("Cannot open capture file {}, {}", path.display(), err);
+                })
+                .ok()
+        });
+
         Self {
             config,
             session_retry_policy,
             certificate_store: Arc::new(RwLock::new(certificate_store)),
+            message_capture,
         }
     }
 
@@ -193,6 +207,7 @@ impl Client {
                 endpoint: server_endpoint,
                 user_identity_token,
                 preferred_locales: Vec::new(),
+                pinned_certificates: Vec::new(),
             })
             .unwrap())
     }
@@ -219,7 +234,7 @@ impl Client {
         session_info: impl Into<SessionInfo>,
     ) -> Result<(Arc<Session>, SessionEventLoop), String> {
         let session_info = session_info.into();
-        if !is_opc_ua_binary_url(session_info.endpoint.endpoint_url.as_ref()) {
+        if !is_valid_opc_ua_url(session_info.endpoint.endpoint_url.as_ref()) {
             Err(format!(
                 "Endpoint url {}, is not a valid / supported url",
                 session_info.endpoint.endpoint_url
@@ -233,6 +248,7 @@ impl Client {
                 self.session_retry_policy.clone(),
                 self.decoding_options(),
                 &self.config,
+                self.message_capture.clone(),
             ))
         }
     }
@@ -329,6 +345,7 @@ impl Client {
                         endpoint: endpoint.unwrap(),
                         user_identity_token,
                         preferred_locales,
+                        pinned_certificates: client_endpoint.pinned_certificates.clone(),
                     })
                 } else {
                     Err(format!(
@@ -363,12 +380,15 @@ impl Client {
             self.config.performance.ignore_clock_skew,
             Arc::default(),
             TransportConfiguration {
-                max_pending_incoming: 5,
+                max_pending_incoming: self.config.decoding_options.max_chunk_count,
                 max_inflight: self.config.performance.max_inflight_messages,
                 send_buffer_size: self.config.decoding_options.max_chunk_size,
                 recv_buffer_size: self.config.decoding_options.max_incoming_chunk_size,
                 max_message_size: self.config.decoding_options.max_message_size,
                 max_chunk_count: self.config.decoding_options.max_chunk_count,
+                // Temporary channels talk to one particular server, so never fall back
+                fallback_endpoint_urls: Vec::new(),
+                capture: self.message_capture.clone(),
             },
         )
     }
@@ -397,6 +417,11 @@ impl Client {
         }
     }
 
+    /// Returns the `CertificateStore` for the client.
+    pub fn certificate_store(&self) -> Arc<RwLock<CertificateStore>> {
+        self.certificate_store.clone()
+    }
+
     /// Gets the [`ClientEndpoint`] information for the default endpoint, as defined
     /// by the configuration. If there is no default endpoint, this function will return an error.
     ///
@@ -474,7 +499,7 @@ impl Client {
                 Some(endpoints) => Ok(endpoints),
             }
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -493,7 +518,7 @@ impl Client {
         server_url: impl Into<String>,
     ) -> Result<Vec<EndpointDescription>, StatusCode> {
         let server_url = server_url.into();
-        if !is_opc_ua_binary_url(&server_url) {
+        if !is_valid_opc_ua_url(&server_url) {
             Err(StatusCode::BadTcpEndpointUrlInvalid)
         } else {
             let preferred_locales = Vec::new();
@@ -503,6 +528,7 @@ impl Client {
                 endpoint: endpoint.clone(),
                 user_identity_token: IdentityToken::Anonymous,
                 preferred_locales,
+                pinned_certificates: Vec::new(),
             };
             let channel = self.channel_from_session_info(session_info);
 
@@ -556,7 +582,7 @@ impl Client {
             };
             Ok(servers)
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -582,6 +608,7 @@ impl Client {
             endpoint: endpoint.clone(),
             user_identity_token: IdentityToken::Anonymous,
             preferred_locales: Vec::new(),
+            pinned_certificates: Vec::new(),
         };
         let channel = self.channel_from_session_info(session_info);
 
@@ -687,7 +714,7 @@ impl Client {
             process_service_result(&response.response_header)?;
             Ok(())
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -747,6 +774,7 @@ impl Client {
             endpoint: endpoint.clone(),
             user_identity_token: IdentityToken::Anonymous,
             preferred_locales: Vec::new(),
+            pinned_certificates: Vec::new(),
         };
         let channel = self.channel_from_session_info(session_info);
 
@@ -1,5 +1,5 @@
 use std::{
-    sync::Arc,
+    sync::{Arc, Weak},
     time::{Duration, Instant},
 };
 
@@ -11,6 +11,7 @@ use crate::{
         session::{session_error, session_warn},
         transport::{SecureChannelEventLoop, TransportPollResult},
     },
+    core::runtime,
     types::{AttributeId, QualifiedName, ReadValueId, StatusCode, TimestampsToReturn, VariableId},
 };
 
@@ -54,12 +55,19 @@ enum SessionEventLoopState {
 }
 
 /// The session event loop drives the client. It must be polled for anything to happen at all.
+///
+/// Once connected, the event loop does not keep the session alive. When every handle to the
+/// session is dropped, the event loop waits for the session to be closed on the server, then ends.
 #[must_use = "The session event loop must be started for the session to work"]
 pub struct SessionEventLoop {
-    inner: Arc<Session>,
+    inner: Weak<Session>,
+    /// Keeps the session alive until it first connects.
+    session: Option<Arc<Session>>,
     trigger_publish_recv: tokio::sync::watch::Receiver<Instant>,
+    reconnect_recv: tokio::sync::watch::Receiver<StatusCode>,
     retry: SessionRetryPolicy,
     keep_alive_interval: Duration,
+    close_timeout: Duration,
 }
 
 impl SessionEventLoop {
@@ -67,35 +75,57 @@ impl SessionEventLoop {
         inner: Arc<Session>,
         retry: SessionRetryPolicy,
         trigger_publish_recv: tokio::sync::watch::Receiver<Instant>,
+        reconnect_recv: tokio::sync::watch::Receiver<StatusCode>,
         keep_alive_interval: Duration,
+        close_timeout: Duration,
     ) -> Self {
         Self {
-            inner,
+            inner: Arc::downgrade(&inner),
+            session: Some(inner),
             retry,
             trigger_publish_recv,
+            reconnect_recv,
             keep_alive_interval,
+            close_timeout,
         }
     }
 
     /// Convenience method for running the session event loop until completion,
-    /// this method will return once the session is closed manually, or
+    /// this method will return once the session is closed manually or dropped, or
     /// after it fails to reconnect.
     ///
+    /// A panic inside the event loop ends it with `BadInternalError`, and the session is left
+    /// disconnected.
+    ///
     /// # Returns
     ///
     /// * `StatusCode` - [Status code](StatusCode) indicating how the session terminated.
     pub async fn run(self) -> StatusCode {
-        let stream = self.enter();
-        tokio::pin!(stream);
-        loop {
-            let r = stream.try_next().await;
-
-            match r {
-                Ok(None) => break StatusCode::Good,
-                Err(e) => break e,
-                _ => (),
+        let session = self.inner.clone();
+        let result = runtime::catch_panic("session event loop", async move {
+            let stream = self.enter();
+            tokio::pin!(stream);
+            loop {
+                let r = stream.try_next().await;
+
+                match r {
+                    Ok(None) => break StatusCode::Good,
+                    Err(e) => break e,
+                    _ => (),
+                }
             }
-        }
+        })
+        .await;
+        result.unwrap_or_else(|status_code| {
+            if let Some(session) = session.upgrade() {
+                session_error!(
+                    session,
+                    "Session event loop panicked, the session is closed"
+                );
+                let _ = session.state_watch_tx.send(SessionState::Disconnected);
+            }
+            status_code
+        })
     }
 
     /// Convenience method for running the session event loop until completion on a tokio task.
@@ -118,14 +148,17 @@ impl SessionEventLoop {
     pub fn enter(self) -> impl Stream<Item = Result<SessionPollResult, StatusCode>> {
         futures::stream::try_unfold(
             (self, SessionEventLoopState::Disconnected),
-            |(slf, state)| async move {
+            |(mut slf, state)| async move {
                 let (res, state) = match state {
                     SessionEventLoopState::Connected(mut c, mut activity, mut subscriptions) => {
                         tokio::select! {
                             r = c.poll() => {
                                 if let TransportPollResult::Closed(code) = r {
-                                    session_warn!(slf.inner, "Transport disconnected: {code}");
-                                    let _ = slf.inner.state_watch_tx.send(SessionState::Disconnected);
+                                    let Some(inner) = slf.inner.upgrade() else {
+                                        return Ok(None);
+                                    };
+                                    session_warn!(inner, "Transport disconnected: {code}");
+                                    let _ = inner.state_watch_tx.send(SessionState::Disconnected);
 
                                     if code.is_good() {
                                         return Ok(None);
@@ -143,9 +176,13 @@ impl SessionEventLoop {
                                 }
                             }
                             r = activity.next() => {
-                                // Should never be null, fail out
+                                // Only ends once the session is dropped, otherwise fail out
                                 let Some(r) = r else {
-                                    session_error!(slf.inner, "Session activity loop ended unexpectedly");
+                                    let Some(inner) = slf.inner.upgrade() else {
+                                        Self::wait_for_close(c, slf.close_timeout).await;
+                                        return Ok(None);
+                                    };
+                                    session_error!(inner, "Session activity loop ended unexpectedly");
                                     return Err(StatusCode::BadUnexpectedError);
                                 };
 
@@ -154,10 +191,33 @@ impl SessionEventLoop {
                                     SessionEventLoopState::Connected(c, activity, subscriptions),
                                 ))
                             }
+                            r = slf.reconnect_recv.changed() => {
+                                // The sender is dropped along with the session.
+                                let (Ok(_), Some(inner)) = (r, slf.inner.upgrade()) else {
+                                    Self::wait_for_close(c, slf.close_timeout).await;
+                                    return Ok(None);
+                                };
+                                // The server has told us the session or the secure channel is
+                                // no longer valid, so drop the connection and reconnect.
+                                let code = *slf.reconnect_recv.borrow_and_update();
+                                session_warn!(inner, "Reconnecting after server returned {code}");
+                                inner.channel.close_channel().await;
+                                while !matches!(c.poll().await, TransportPollResult::Closed(_)) {}
+                                let _ = inner.state_watch_tx.send(SessionState::Disconnected);
+
+                                Ok((
+                                    SessionPollResult::ConnectionLost(code),
+                                    SessionEventLoopState::Disconnected,
+                                ))
+                            }
                             r = subscriptions.next() => {
-                                // Should never be null, fail out
+                                // Only ends once the session is dropped, otherwise fail out
                                 let Some(r) = r else {
-                                    session_error!(slf.inner, "Subscription event loop ended unexpectedly");
+                                    let Some(inner) = slf.inner.upgrade() else {
+                                        Self::wait_for_close(c, slf.close_timeout).await;
+                                        return Ok(None);
+                                    };
+                                    session_error!(inner, "Subscription event loop ended unexpectedly");
                                     return Err(StatusCode::BadUnexpectedError);
                                 };
 
@@ -169,9 +229,12 @@ impl SessionEventLoop {
                         }
                     }
                     SessionEventLoopState::Disconnected => {
-                        let connector = SessionConnector::new(slf.inner.clone());
+                        let Some(inner) = slf.session.take().or_else(|| slf.inner.upgrade()) else {
+                            return Ok(None);
+                        };
+                        let connector = SessionConnector::new(inner.clone());
 
-                        let _ = slf.inner.state_watch_tx.send(SessionState::Connecting);
+                        let _ = inner.state_watch_tx.send(SessionState::Connecting);
 
                         Ok((
                             SessionPollResult::BeginConnect,
@@ -187,19 +250,21 @@ impl SessionEventLoop {
 
                         match connector.try_connect().await {
                             Ok((channel, result)) => {
-                                let _ = slf.inner.state_watch_tx.send(SessionState::Connected);
+                                let Some(inner) = slf.inner.upgrade() else {
+                                    return Ok(None);
+                                };
+                                // Faults seen while connecting were dealt with by the connector
+                                slf.reconnect_recv.mark_unchanged();
+                                let _ = inner.state_watch_tx.send(SessionState::Connected);
                                 Ok((
                                     SessionPollResult::Reconnected(result),
                                     SessionEventLoopState::Connected(
                                         channel,
-                                        SessionActivityLoop::new(
-                                            slf.inner.clone(),
-                                            slf.keep_alive_interval,
-                                        )
-                                        .run()
-                                        .boxed(),
+                                        SessionActivityLoop::new(&inner, slf.keep_alive_interval)
+                                            .run()
+                                            .boxed(),
                                         SubscriptionEventLoop::new(
-                                            slf.inner.clone(),
+                                            &inner,
                                             slf.trigger_publish_recv.clone(),
                                         )
                                         .run()
@@ -229,6 +294,20 @@ impl SessionEventLoop {
             },
         )
     }
+
+    /// Poll the transport of a dropped session until the connection is closed, giving the
+    /// session time to be closed on the server. Gives up after the time allowed for both
+    /// closing the session and closing the secure channel.
+    async fn wait_for_close(mut channel: SecureChannelEventLoop, close_timeout: Duration) {
+        let closed =
+            async { while !matches!(channel.poll().await, TransportPollResult::Closed(_)) {} };
+        if tokio::time::timeout(close_timeout * 2, closed)
+            .await
+            .is_err()
+        {
+            warn!("Timed out waiting for the connection of a dropped session to close");
+        }
+    }
 }
 
 /// Periodic activity performed by the session.
@@ -264,14 +343,14 @@ impl SessionIntervals {
 }
 
 struct SessionActivityLoop {
-    inner: Arc<Session>,
+    inner: Weak<Session>,
     tick_gen: SessionIntervals,
 }
 
 impl SessionActivityLoop {
-    pub fn new(inner: Arc<Session>, keep_alive_interval: Duration) -> Self {
+    pub fn new(inner: &Arc<Session>, keep_alive_interval: Duration) -> Self {
         Self {
-            inner,
+            inner: Arc::downgrade(inner),
             tick_gen: SessionIntervals::new(keep_alive_interval),
         }
     }
@@ -280,8 +359,9 @@ impl SessionActivityLoop {
         futures::stream::unfold(self, |mut slf| async move {
             match slf.tick_gen.next().await {
                 SessionTickEvent::KeepAlive => {
-                    let res = slf
-                        .inner
+                    // The stream ends once the session is dropped
+                    let inner = slf.inner.upgrade()?;
+                    let res = inner
                         .read(
                             &[ReadValueId {
                                 node_id: VariableId::Server_ServerStatus_State.into(),
@@ -293,6 +373,7 @@ impl SessionActivityLoop {
                             1f64,
                         )
                         .await;
+                    drop(inner);
 
                     let value = match res.map(|r| r.into_iter().next()) {
                         Ok(Some(dv)) => dv,
@@ -304,7 +385,7 @@ impl SessionActivityLoop {
                                 slf,
                             ))
                         }
-                        Err(e) => return Some((SessionActivity::KeepAliveFailed(e), slf)),
+                        Err(e) => return Some((SessionActivity::KeepAliveFailed(e.into()), slf)),
                     };
 
                     let Some(status): Option<u8> = value.value.and_then(|v| v.try_into().ok())
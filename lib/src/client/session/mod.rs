@@ -14,6 +14,22 @@ pub struct SessionInfo {
     pub user_identity_token: IdentityToken,
     /// Preferred language locales
     pub preferred_locales: Vec<String>,
+    /// Certificates the server must present, see [`ClientEndpoint::pinned_certificates`]
+    ///
+    /// [`ClientEndpoint::pinned_certificates`]: crate::client::ClientEndpoint::pinned_certificates
+    pub pinned_certificates: Vec<String>,
+}
+
+impl SessionInfo {
+    /// Tests if the certificate matches one of the pinned certificates, or if there are none
+    pub(crate) fn is_pinned_certificate(&self, certificate: &ByteString) -> bool {
+        self.pinned_certificates.is_empty()
+            || X509::from_byte_string(certificate).is_ok_and(|cert| {
+                self.pinned_certificates
+                    .iter()
+                    .any(|pin| cert.matches_pin(pin))
+            })
+    }
 }
 
 impl From<EndpointDescription> for SessionInfo {
@@ -22,6 +38,7 @@ impl From<EndpointDescription> for SessionInfo {
             endpoint: value,
             user_identity_token: IdentityToken::Anonymous,
             preferred_locales: Vec::new(),
+            pinned_certificates: Vec::new(),
         }
     }
 }
@@ -32,6 +49,7 @@ impl From<(EndpointDescription, IdentityToken)> for SessionInfo {
             endpoint: value.0,
             user_identity_token: value.1,
             preferred_locales: Vec::new(),
+            pinned_certificates: Vec::new(),
         }
     }
 }
@@ -40,8 +58,12 @@ pub use client::Client;
 pub use connect::SessionConnectMode;
 pub use event_loop::{SessionActivity, SessionEventLoop, SessionPollResult};
 pub use services::subscriptions::{
-    DataChangeCallback, EventCallback, MonitoredItem, OnSubscriptionNotification, Subscription,
-    SubscriptionCallbacks,
+    state::{
+        OnSubscriptionStateChange, SubscriptionState, SubscriptionStateCallback,
+        SubscriptionStateEvent,
+    },
+    CreatedMonitoredItem, DataChangeCallback, Downsampling, EventCallback, MonitoredItem,
+    OnSubscriptionNotification, Subscription, SubscriptionCallbacks, SubscriptionHandle,
 };
 pub use session::Session;
 
@@ -81,39 +103,123 @@ macro_rules! session_trace {
 #[allow(unused)]
 pub(crate) use session_trace;
 
+use std::fmt;
+
 use crate::{
     core::supported_message::SupportedMessage,
-    types::{EndpointDescription, ResponseHeader, StatusCode},
+    crypto::X509,
+    types::{
+        ByteString, DiagnosticInfo, EndpointDescription, ResponseHeader, StatusCode, UAString,
+    },
 };
 
 use super::IdentityToken;
 
 /// Process the service result, i.e. where the request "succeeded" but the response
 /// contains a failure status code.
-pub(crate) fn process_service_result(response_header: &ResponseHeader) -> Result<(), StatusCode> {
+pub(crate) fn process_service_result(response_header: &ResponseHeader) -> Result<(), ServiceError> {
     if response_header.service_result.is_bad() {
         info!(
             "Received a bad service result {} from the request",
             response_header.service_result
         );
-        Err(response_header.service_result)
+        Err(ServiceError::from_response_header(response_header.clone()))
     } else {
         Ok(())
     }
 }
 
-pub(crate) fn process_unexpected_response(response: SupportedMessage) -> StatusCode {
+/// An error returned from a request to the server by the service calls of [`Session`] and by
+/// [`Session::send_request`]. Where the server responded with a `ServiceFault` or a bad service
+/// result, the response header is retained so the diagnostics the server supplied can be inspected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceError {
+    status: StatusCode,
+    response_header: Option<Box<ResponseHeader>>,
+}
+
+impl ServiceError {
+    /// Create an error from a response header returned by the server.
+    pub fn from_response_header(response_header: ResponseHeader) -> Self {
+        Self {
+            status: response_header.service_result,
+            response_header: Some(Box::new(response_header)),
+        }
+    }
+
+    /// The status code of the error.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The response header returned by the server, if the error came from the server rather than
+    /// from the client, e.g. a timeout or a lost connection.
+    pub fn response_header(&self) -> Option<&ResponseHeader> {
+        self.response_header.as_deref()
+    }
+
+    /// The diagnostics the server returned for the service, if any were requested.
+    pub fn diagnostic_info(&self) -> Option<&DiagnosticInfo> {
+        self.response_header().map(|h| &h.service_diagnostics)
+    }
+
+    /// The string table referenced by the diagnostics, if any.
+    pub fn string_table(&self) -> &[UAString] {
+        self.response_header()
+            .and_then(|h| h.string_table.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Test if the error means the session or secure channel is no longer valid on the server,
+    /// in which case the session reconnects.
+    pub fn requires_reconnect(&self) -> bool {
+        requires_reconnect(self.status)
+    }
+}
+
+impl From<StatusCode> for ServiceError {
+    fn from(status: StatusCode) -> Self {
+        Self {
+            status,
+            response_header: None,
+        }
+    }
+}
+
+impl From<ServiceError> for StatusCode {
+    fn from(value: ServiceError) -> Self {
+        value.status
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status)
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+/// Test if the status code returned by the server means the session has to be reconnected.
+pub(crate) fn requires_reconnect(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BadSessionIdInvalid | StatusCode::BadSecureChannelIdInvalid
+    )
+}
+
+pub(crate) fn process_unexpected_response(response: SupportedMessage) -> ServiceError {
     match response {
         SupportedMessage::ServiceFault(service_fault) => {
             error!(
                 "Received a service fault of {} for the request",
                 service_fault.response_header.service_result
             );
-            service_fault.response_header.service_result
+            ServiceError::from_response_header(service_fault.response_header)
         }
         _ => {
             error!("Received an unexpected response to the request");
-            StatusCode::BadUnknownResponse
+            StatusCode::BadUnknownResponse.into()
         }
     }
 }
@@ -2,17 +2,20 @@ use crate::{
     client::{
         session::{
             process_service_result, process_unexpected_response, session_debug, session_error,
+            ServiceError,
         },
         Session,
     },
     core::supported_message::SupportedMessage,
     types::{
-        DataValue, DeleteAtTimeDetails, DeleteEventDetails, DeleteRawModifiedDetails,
+        AttributeId, BrowsePath, DataTypeDefinition, DataTypeDefinitions, DataValue,
+        DeleteAtTimeDetails, DeleteEventDetails, DeleteRawModifiedDetails, ExpandedNodeId,
         ExtensionObject, HistoryReadRequest, HistoryReadResult, HistoryReadValueId,
-        HistoryUpdateRequest, HistoryUpdateResult, ObjectId, ReadAtTimeDetails, ReadEventDetails,
-        ReadProcessedDetails, ReadRawModifiedDetails, ReadRequest, ReadValueId, StatusCode,
-        TimestampsToReturn, UpdateDataDetails, UpdateEventDetails, UpdateStructureDataDetails,
-        WriteRequest, WriteValue,
+        HistoryUpdateRequest, HistoryUpdateResult, NodeId, NodeSnapshot, ObjectId,
+        ReadAtTimeDetails, ReadEventDetails, ReadProcessedDetails, ReadRawModifiedDetails,
+        ReadRequest, ReadValueId, RelativePath, RelativePathElement, StatusCode,
+        TimestampsToReturn, UAString, UpdateDataDetails, UpdateEventDetails,
+        UpdateStructureDataDetails, VariableId, Variant, WriteRequest, WriteValue,
     },
 };
 
@@ -80,7 +83,9 @@ impl From<&HistoryUpdateAction> for ExtensionObject {
 }
 
 impl Session {
-    /// Reads the value of nodes by sending a [`ReadRequest`] to the server.
+    /// Reads the value of nodes by sending a [`ReadRequest`] to the server. If there are more
+    /// nodes than the server's `MaxNodesPerRead` operation limit, they are read with as many
+    /// requests as it takes.
     ///
     /// See OPC UA Part 4 - Services 5.10.2 for complete description of the service and error responses.
     ///
@@ -95,41 +100,66 @@ impl Session {
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<DataValue>)` - A list of [`DataValue`] corresponding to each read operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Ok(Vec<DataValue>)` - A list of [`DataValue`] corresponding to each read operation. When the
+    ///   nodes are split over several requests, the values of a request that failed have the reason
+    ///   it failed as their status.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn read(
         &self,
         nodes_to_read: &[ReadValueId],
         timestamps_to_return: TimestampsToReturn,
         max_age: f64,
-    ) -> Result<Vec<DataValue>, StatusCode> {
+    ) -> Result<Vec<DataValue>, ServiceError> {
         if nodes_to_read.is_empty() {
             // No subscriptions
             session_error!(self, "read(), was not supplied with any nodes to read");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
-            session_debug!(self, "read() requested to read nodes {:?}", nodes_to_read);
-            let request = ReadRequest {
-                request_header: self.make_request_header(),
-                max_age,
-                timestamps_to_return,
-                nodes_to_read: Some(nodes_to_read.to_vec()),
-            };
-            let response = self.send(request).await?;
-            if let SupportedMessage::ReadResponse(response) = response {
-                session_debug!(self, "read(), success");
-                process_service_result(&response.response_header)?;
-                let results = if let Some(results) = response.results {
-                    results
-                } else {
-                    Vec::new()
-                };
-                Ok(results)
+            self.send_in_batches(
+                "read()",
+                nodes_to_read,
+                (
+                    &self.max_nodes_per_read,
+                    VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerRead,
+                ),
+                |batch| self.read_batch(batch, timestamps_to_return, max_age),
+                |status| DataValue {
+                    status: Some(status),
+                    ..Default::default()
+                },
+            )
+            .await
+        }
+    }
+
+    /// Sends a single [`ReadRequest`] for the nodes.
+    pub(crate) async fn read_batch(
+        &self,
+        nodes_to_read: &[ReadValueId],
+        timestamps_to_return: TimestampsToReturn,
+        max_age: f64,
+    ) -> Result<Vec<DataValue>, ServiceError> {
+        session_debug!(self, "read() requested to read nodes {:?}", nodes_to_read);
+        let request = ReadRequest {
+            request_header: self.make_request_header(),
+            max_age,
+            timestamps_to_return,
+            nodes_to_read: Some(nodes_to_read.to_vec()),
+        };
+        let response = self.send(request).await?;
+        if let SupportedMessage::ReadResponse(response) = response {
+            session_debug!(self, "read(), success");
+            process_service_result(&response.response_header)?;
+            let results = if let Some(results) = response.results {
+                results
             } else {
-                session_error!(self, "read() value failed");
-                Err(process_unexpected_response(response))
-            }
+                Vec::new()
+            };
+            Ok(results)
+        } else {
+            session_error!(self, "read() value failed");
+            Err(process_unexpected_response(response))
         }
     }
 
@@ -153,7 +183,7 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<HistoryReadResult>)` - A list of [`HistoryReadResult`] results corresponding to history read operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn history_read(
         &self,
@@ -161,7 +191,7 @@ impl Session {
         timestamps_to_return: TimestampsToReturn,
         release_continuation_points: bool,
         nodes_to_read: &[HistoryReadValueId],
-    ) -> Result<Vec<HistoryReadResult>, StatusCode> {
+    ) -> Result<Vec<HistoryReadResult>, ServiceError> {
         // Turn the enum into an extension object
         let history_read_details = ExtensionObject::from(history_read_details);
         let request = HistoryReadRequest {
@@ -197,7 +227,8 @@ impl Session {
     }
 
     /// Writes values to nodes by sending a [`WriteRequest`] to the server. Note that some servers may reject DataValues
-    /// containing source or server timestamps.
+    /// containing source or server timestamps. If there are more nodes than the server's `MaxNodesPerWrite`
+    /// operation limit, they are written with as many requests as it takes.
     ///
     /// See OPC UA Part 4 - Services 5.10.4 for complete description of the service and error responses.
     ///
@@ -207,31 +238,92 @@ impl Session {
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<StatusCode>)` - A list of [`StatusCode`] results corresponding to each write operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Ok(Vec<StatusCode>)` - A list of [`StatusCode`] results corresponding to each write operation. When the
+    ///   nodes are split over several requests, the results of a request that failed are the reason it failed.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn write(
         &self,
         nodes_to_write: &[WriteValue],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if nodes_to_write.is_empty() {
             // No subscriptions
             session_error!(self, "write() was not supplied with any nodes to write");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
-            let request = WriteRequest {
-                request_header: self.make_request_header(),
-                nodes_to_write: Some(nodes_to_write.to_vec()),
-            };
-            let response = self.send(request).await?;
-            if let SupportedMessage::WriteResponse(response) = response {
-                session_debug!(self, "write(), success");
-                process_service_result(&response.response_header)?;
-                Ok(response.results.unwrap_or_default())
-            } else {
-                session_error!(self, "write() failed {:?}", response);
-                Err(process_unexpected_response(response))
-            }
+            self.send_in_batches(
+                "write()",
+                nodes_to_write,
+                (
+                    &self.max_nodes_per_write,
+                    VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerWrite,
+                ),
+                |batch| self.write_batch(batch),
+                |status| status,
+            )
+            .await
+        }
+    }
+
+    /// Sends a single [`WriteRequest`] for the nodes.
+    async fn write_batch(
+        &self,
+        nodes_to_write: &[WriteValue],
+    ) -> Result<Vec<StatusCode>, ServiceError> {
+        let request = WriteRequest {
+            request_header: self.make_request_header(),
+            nodes_to_write: Some(nodes_to_write.to_vec()),
+        };
+        let response = self.send(request).await?;
+        if let SupportedMessage::WriteResponse(response) = response {
+            session_debug!(self, "write(), success");
+            process_service_result(&response.response_header)?;
+            Ok(response.results.unwrap_or_default())
+        } else {
+            session_error!(self, "write() failed {:?}", response);
+            Err(process_unexpected_response(response))
+        }
+    }
+
+    /// Writes a single attribute of a node, e.g. its `DisplayName` or `Description`, or the
+    /// `Value` of a variable. The server decides if the attribute may be written from the
+    /// `WriteMask` and `UserWriteMask` of the node, or the access levels of a variable's value.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The node to write to.
+    /// * `attribute_id` - The attribute of the node to write.
+    /// * `value` - The value of the attribute, which must be of the attribute's type, e.g. a
+    ///   `LocalizedText` for the `DisplayName`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The attribute was written.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure,
+    ///   e.g. `BadNotWritable` if the node does not allow the attribute to be written or
+    ///   `BadUserAccessDenied` if the user may not write it.
+    ///
+    pub async fn write_attribute(
+        &self,
+        node_id: &NodeId,
+        attribute_id: AttributeId,
+        value: impl Into<Variant>,
+    ) -> Result<(), StatusCode> {
+        let node_to_write = WriteValue {
+            node_id: node_id.clone(),
+            attribute_id: attribute_id as u32,
+            index_range: UAString::null(),
+            value: DataValue::value_only(value.into()),
+        };
+        let status_code = self
+            .write(&[node_to_write])
+            .await?
+            .pop()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        if status_code.is_bad() {
+            Err(status_code)
+        } else {
+            Ok(())
         }
     }
 
@@ -254,19 +346,19 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<ClientHistoryUpdateResult>)` - A list of [`ClientHistoryUpdateResult`] results corresponding to history update operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn history_update(
         &self,
         history_update_details: &[HistoryUpdateAction],
-    ) -> Result<Vec<HistoryUpdateResult>, StatusCode> {
+    ) -> Result<Vec<HistoryUpdateResult>, ServiceError> {
         if history_update_details.is_empty() {
             // No subscriptions
             session_error!(
                 self,
                 "history_update(), was not supplied with any detail to update"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             // Turn the enums into ExtensionObjects
             let history_update_details = history_update_details
@@ -294,4 +386,355 @@ impl Session {
             }
         }
     }
+
+    /// Reads the namespace array of the server, i.e. the value of `Server/NamespaceArray`. The
+    /// index of a namespace uri in the array is the namespace index used in node ids.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The namespace uris of the server in index order.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    pub async fn read_namespace_array(&self) -> Result<Vec<String>, StatusCode> {
+        self.read_string_array(VariableId::Server_NamespaceArray)
+            .await
+    }
+
+    /// Reads the server array of the server, i.e. the value of `Server/ServerArray`. The index of
+    /// a server uri in the array is the server index used in expanded node ids, where index 0 is
+    /// the server itself.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The server uris in index order.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    pub async fn read_server_array(&self) -> Result<Vec<String>, StatusCode> {
+        self.read_string_array(VariableId::Server_ServerArray).await
+    }
+
+    async fn read_string_array(&self, variable_id: VariableId) -> Result<Vec<String>, StatusCode> {
+        let node_id: NodeId = variable_id.into();
+        let data_value = self
+            .read(&[node_id.into()], TimestampsToReturn::Neither, 0.0)
+            .await?
+            .pop()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        let status_code = data_value.status();
+        if status_code.is_bad() {
+            return Err(status_code);
+        }
+        match data_value.value {
+            Some(Variant::Array(array)) => array
+                .values
+                .into_iter()
+                .map(|v| match v {
+                    Variant::String(s) => Ok(s.to_string()),
+                    _ => Err(StatusCode::BadTypeMismatch),
+                })
+                .collect(),
+            _ => {
+                session_error!(self, "read_string_array(), value is not an array");
+                Err(StatusCode::BadTypeMismatch)
+            }
+        }
+    }
+
+    /// Finds the uri of the server that holds the node of an expanded node id, e.g. the target
+    /// of a reference returned by browse that points to a node on another server. The server index
+    /// of the node id is looked up in the server array of this server.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The uri of the server that holds the node.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///   `BadServerIndexInvalid` means the server index is not in the server array.
+    ///
+    pub async fn server_uri(&self, node_id: &ExpandedNodeId) -> Result<String, StatusCode> {
+        let mut servers = self.read_server_array().await?;
+        let server_index = node_id.server_index as usize;
+        if server_index < servers.len() {
+            Ok(servers.swap_remove(server_index))
+        } else {
+            session_error!(
+                self,
+                "server_uri(), server index {} is not in the server array",
+                server_index
+            );
+            Err(StatusCode::BadServerIndexInvalid)
+        }
+    }
+
+    /// Resolves an expanded node id to a node id of this server, replacing a namespace uri with
+    /// its index in the namespace array. The namespace array is only read if the node id has a
+    /// namespace uri.
+    ///
+    /// A node held by another server can't be resolved through this session. Find its server
+    /// with [`Session::server_uri`], connect to it and resolve the node id with a server index
+    /// of 0 there.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node id on this server.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///   `BadNodeIdUnknown` means the namespace uri is not in the namespace array and
+    ///   `BadReferenceLocalOnly` means the node is held by another server.
+    ///
+    pub async fn resolve_expanded_node_id(
+        &self,
+        node_id: &ExpandedNodeId,
+    ) -> Result<NodeId, StatusCode> {
+        if node_id.server_index != 0 {
+            Err(StatusCode::BadReferenceLocalOnly)
+        } else if node_id.namespace_uri.is_empty() {
+            Ok(node_id.node_id.clone())
+        } else {
+            let namespaces = self.read_namespace_array().await?;
+            node_id.to_node_id(&namespaces).ok_or_else(|| {
+                session_error!(
+                    self,
+                    "resolve_expanded_node_id(), namespace {} is unknown",
+                    node_id.namespace_uri
+                );
+                StatusCode::BadNodeIdUnknown
+            })
+        }
+    }
+
+    /// Finds the index of a namespace uri in the namespace array of the server. The index of a
+    /// namespace can differ between servers and between restarts of a server, so clients should
+    /// resolve it rather than hard code it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(u16))` - The namespace index of the uri.
+    /// * `Ok(None)` - The server does not have the namespace.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///
+    pub async fn namespace_index(&self, namespace: &str) -> Result<Option<u16>, StatusCode> {
+        let namespaces = self.read_namespace_array().await?;
+        Ok(namespaces
+            .iter()
+            .position(|ns| ns == namespace)
+            .map(|i| i as u16))
+    }
+
+    /// Reads the value of the variable at a relative path from a node by translating the path to
+    /// a node id and reading its value, which makes a one-off fetch of a value a single call. The
+    /// path uses the syntax of OPC UA Part 4 Annex A, e.g. `"3:Device/2:Temperature"`, where
+    /// each browse name follows a hierarchical reference from the node before it. Use
+    /// [`Session::read_by_path_as`] to convert the value to a Rust type.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_node` - The node that the path starts from, e.g. the Objects folder.
+    /// * `path` - The relative path to the variable.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Variant)` - The value of the variable, empty if it has none.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure,
+    ///   e.g. `BadBrowseNameInvalid` if the path cannot be parsed or `BadNoMatch` if no node is
+    ///   at the path.
+    ///
+    pub async fn read_by_path(
+        &self,
+        start_node: &NodeId,
+        path: &str,
+    ) -> Result<Variant, StatusCode> {
+        // A path that starts with a browse name follows hierarchical references from the start
+        let path = if path.starts_with(['/', '.', '<']) {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        };
+        let relative_path =
+            RelativePath::from_str(&path, &RelativePathElement::default_node_resolver).map_err(
+                |_| {
+                    session_error!(self, "read_by_path(), invalid path {}", path);
+                    StatusCode::BadBrowseNameInvalid
+                },
+            )?;
+        let browse_path = BrowsePath {
+            starting_node: start_node.clone(),
+            relative_path,
+        };
+        let result = self
+            .translate_browse_paths_to_node_ids(&[browse_path])
+            .await?
+            .pop()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        if result.status_code.is_bad() {
+            session_debug!(
+                self,
+                "read_by_path(), {} not found, {}",
+                path,
+                result.status_code
+            );
+            return Err(result.status_code);
+        }
+        // Only a target that the whole path was followed to is the variable
+        let node_id = result
+            .targets
+            .iter()
+            .flatten()
+            .find(|t| t.remaining_path_index == u32::MAX && t.target_id.server_index == 0)
+            .map(|t| t.target_id.node_id.clone())
+            .ok_or(StatusCode::BadNoMatch)?;
+        let data_value = self
+            .read(&[node_id.into()], TimestampsToReturn::Neither, 0.0)
+            .await?
+            .pop()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        let status_code = data_value.status();
+        if status_code.is_bad() {
+            Err(status_code)
+        } else {
+            Ok(data_value.value.unwrap_or(Variant::Empty))
+        }
+    }
+
+    /// Reads the value of the variable at a relative path from a node like
+    /// [`Session::read_by_path`] and converts it to a Rust type, e.g. `f64`, `i32` or `bool`.
+    /// Numeric values are cast to the type.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The value of the variable.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure,
+    ///   `BadTypeMismatch` if the value cannot be converted to the type.
+    ///
+    pub async fn read_by_path_as<T>(&self, start_node: &NodeId, path: &str) -> Result<T, StatusCode>
+    where
+        T: TryFrom<Variant>,
+    {
+        let value = self.read_by_path(start_node, path).await?;
+        T::try_from(value).map_err(|_| StatusCode::BadTypeMismatch)
+    }
+
+    /// Reads every attribute of a node in one request into a [`NodeSnapshot`], with the
+    /// attributes that the node's class does not define, or that the node does not have, left
+    /// as `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The node to read.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeSnapshot)` - The attributes of the node.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure,
+    ///   e.g. `BadNodeIdUnknown` if there is no such node.
+    ///
+    pub async fn read_node(&self, node_id: &NodeId) -> Result<NodeSnapshot, StatusCode> {
+        let attribute_ids = (AttributeId::NodeId as u32..=AttributeId::AccessLevelEx as u32)
+            .filter_map(|attribute_id| AttributeId::from_u32(attribute_id).ok())
+            .collect::<Vec<_>>();
+        let nodes_to_read = attribute_ids
+            .iter()
+            .map(|attribute_id| ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: *attribute_id as u32,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let results = self
+            .read(&nodes_to_read, TimestampsToReturn::Both, 0.0)
+            .await?;
+        if results.len() != attribute_ids.len() {
+            return Err(StatusCode::BadUnexpectedError);
+        }
+        let decoding_options = {
+            let secure_channel = trace_read_lock!(self.channel.secure_channel);
+            secure_channel.decoding_options()
+        };
+        NodeSnapshot::from_attributes(attribute_ids.into_iter().zip(results), &decoding_options)
+            .inspect_err(|status_code| {
+                session_debug!(
+                    self,
+                    "read_node(), cannot read {}, {}",
+                    node_id,
+                    status_code
+                );
+            })
+    }
+
+    /// Reads the `DataTypeDefinition` attribute of custom data types into a
+    /// [`DataTypeDefinitions`] that can decode and encode values of those types. Custom structures
+    /// and enumerations that are the types of fields of the structures are read too.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type_ids` - The node ids of the data types to read.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DataTypeDefinitions)` - The definitions of the data types and their field types.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure,
+    ///   e.g. if a data type has no definition.
+    ///
+    pub async fn read_data_type_definitions(
+        &self,
+        data_type_ids: &[NodeId],
+    ) -> Result<DataTypeDefinitions, StatusCode> {
+        let decoding_options = {
+            let secure_channel = trace_read_lock!(self.channel.secure_channel);
+            secure_channel.decoding_options()
+        };
+        let mut definitions = DataTypeDefinitions::new();
+        let mut to_read = data_type_ids.to_vec();
+        while !to_read.is_empty() {
+            let nodes_to_read = to_read
+                .iter()
+                .map(|node_id| ReadValueId {
+                    node_id: node_id.clone(),
+                    attribute_id: AttributeId::DataTypeDefinition as u32,
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            let results = self
+                .read(&nodes_to_read, TimestampsToReturn::Neither, 0.0)
+                .await?;
+            if results.len() != to_read.len() {
+                return Err(StatusCode::BadUnexpectedError);
+            }
+            let mut field_types = Vec::new();
+            for (data_type_id, data_value) in to_read.drain(..).zip(results) {
+                let status_code = data_value.status();
+                if status_code.is_bad() {
+                    session_error!(
+                        self,
+                        "read_data_type_definitions(), data type {} has no definition, {}",
+                        data_type_id,
+                        status_code
+                    );
+                    return Err(status_code);
+                }
+                let definition = match data_value.value {
+                    Some(Variant::ExtensionObject(v)) => {
+                        DataTypeDefinition::from_extension_object(&v, &decoding_options)?
+                    }
+                    _ => return Err(StatusCode::BadTypeMismatch),
+                };
+                if let DataTypeDefinition::Structure(ref definition) = definition {
+                    // Types in namespace 0 are either built-in or have no definition to read
+                    field_types.extend(
+                        definition
+                            .fields
+                            .iter()
+                            .flatten()
+                            .map(|f| f.data_type.clone())
+                            .filter(|id| id.namespace != 0),
+                    );
+                }
+                definitions.add_definition(data_type_id, definition);
+            }
+            for data_type_id in field_types {
+                if !definitions.contains(&data_type_id) && !to_read.contains(&data_type_id) {
+                    to_read.push(data_type_id);
+                }
+            }
+        }
+        Ok(definitions)
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     client::{
-        session::{process_unexpected_response, session_debug, session_error},
+        session::{process_unexpected_response, session_debug, session_error, ServiceError},
         Session,
     },
     core::supported_message::SupportedMessage,
@@ -24,12 +24,12 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(CallMethodResult)` - A [`CallMethodResult`] for the Method call.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn call(
         &self,
         method: impl Into<CallMethodRequest>,
-    ) -> Result<CallMethodResult, StatusCode> {
+    ) -> Result<CallMethodResult, ServiceError> {
         session_debug!(self, "call()");
         let methods_to_call = Some(vec![method.into()]);
         let request = CallRequest {
@@ -45,7 +45,7 @@ impl Session {
                         "call(), expecting a result from the call to the server, got {} results",
                         results.len()
                     );
-                    Err(StatusCode::BadUnexpectedError)
+                    Err(StatusCode::BadUnexpectedError.into())
                 } else {
                     Ok(results.remove(0))
                 }
@@ -54,7 +54,7 @@ impl Session {
                     self,
                     "call(), expecting a result from the call to the server, got nothing"
                 );
-                Err(StatusCode::BadUnexpectedError)
+                Err(StatusCode::BadUnexpectedError.into())
             }
         } else {
             Err(process_unexpected_response(response))
@@ -70,7 +70,8 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok((Vec<u32>, Vec<u32>))` - Result for call, consisting a list of (monitored_item_id, client_handle)
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure, e.g.
+    ///   `BadSubscriptionIdInvalid` if the session has no such subscription.
     ///
     pub async fn call_get_monitored_items(
         &self,
@@ -81,6 +82,14 @@ impl Session {
         let method_id: NodeId = MethodId::Server_GetMonitoredItems.into();
         let request: CallMethodRequest = (object_id, method_id, args).into();
         let response = self.call(request).await?;
+        if response.status_code.is_bad() {
+            session_error!(
+                self,
+                "call_get_monitored_items(), failed {}",
+                response.status_code
+            );
+            return Err(response.status_code);
+        }
         if let Some(mut result) = response.output_arguments {
             if result.len() == 2 {
                 let server_handles = <Vec<u32>>::try_from(&result.remove(0))
@@ -97,4 +106,33 @@ impl Session {
             Err(StatusCode::BadUnexpectedError)
         }
     }
+
+    /// Calls ResendData via call_method(), asking the server to send the current value of every
+    /// monitored item of the subscription in its next publish response, e.g. to refresh the
+    /// values held by the client after it has recovered a subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription_id` - Server allocated identifier for the subscription to resend the data of.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The server will resend the data.
+    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure, e.g.
+    ///   `BadSubscriptionIdInvalid` if the session has no such subscription.
+    ///
+    pub async fn call_resend_data(&self, subscription_id: u32) -> Result<(), StatusCode> {
+        let args = Some(vec![Variant::from(subscription_id)]);
+        let object_id: NodeId = ObjectId::Server.into();
+        let method_id: NodeId = MethodId::Server_ResendData.into();
+        let request: CallMethodRequest = (object_id, method_id, args).into();
+        let response = self.call(request).await?;
+        if response.status_code.is_bad() {
+            session_error!(self, "call_resend_data(), failed {}", response.status_code);
+            Err(response.status_code)
+        } else {
+            session_debug!(self, "call_resend_data(), success");
+            Ok(())
+        }
+    }
 }
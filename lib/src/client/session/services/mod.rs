@@ -1,6 +1,9 @@
 pub mod attributes;
 pub mod method;
+#[cfg(feature = "node-management-services")]
 pub mod node_management;
+#[cfg(feature = "query-services")]
+pub mod query;
 pub mod session;
 pub mod subscriptions;
 pub mod view;
@@ -1,6 +1,8 @@
 use crate::{
     client::{
-        session::{process_service_result, process_unexpected_response, session_error},
+        session::{
+            process_service_result, process_unexpected_response, session_error, ServiceError,
+        },
         Session,
     },
     core::supported_message::SupportedMessage,
@@ -23,15 +25,15 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<AddNodesResult>)` - A list of [`AddNodesResult`] corresponding to each add node operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn add_nodes(
         &self,
         nodes_to_add: &[AddNodesItem],
-    ) -> Result<Vec<AddNodesResult>, StatusCode> {
+    ) -> Result<Vec<AddNodesResult>, ServiceError> {
         if nodes_to_add.is_empty() {
             session_error!(self, "add_nodes, called with no nodes to add");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = AddNodesRequest {
                 request_header: self.make_request_header(),
@@ -57,15 +59,15 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<StatusCode>)` - A list of `StatusCode` corresponding to each add reference operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn add_references(
         &self,
         references_to_add: &[AddReferencesItem],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if references_to_add.is_empty() {
             session_error!(self, "add_references, called with no references to add");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = AddReferencesRequest {
                 request_header: self.make_request_header(),
@@ -92,15 +94,15 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<StatusCode>)` - A list of `StatusCode` corresponding to each delete node operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn delete_nodes(
         &self,
         nodes_to_delete: &[DeleteNodesItem],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if nodes_to_delete.is_empty() {
             session_error!(self, "delete_nodes, called with no nodes to delete");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = DeleteNodesRequest {
                 request_header: self.make_request_header(),
@@ -126,18 +128,18 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<StatusCode>)` - A list of `StatusCode` corresponding to each delete node operation.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn delete_references(
         &self,
         references_to_delete: &[DeleteReferencesItem],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if references_to_delete.is_empty() {
             session_error!(
                 self,
                 "delete_references, called with no references to delete"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = DeleteReferencesRequest {
                 request_header: self.make_request_header(),
@@ -0,0 +1,116 @@
+use crate::{
+    client::{
+        session::{
+            process_service_result, process_unexpected_response, session_debug, session_error,
+            ServiceError,
+        },
+        Session,
+    },
+    core::supported_message::SupportedMessage,
+    types::{
+        ByteString, ContentFilter, DateTime, NodeId, NodeTypeDescription, QueryDataSet,
+        QueryFirstRequest, QueryFirstResponse, QueryNextRequest, StatusCode, ViewDescription,
+    },
+};
+
+impl Session {
+    /// Finds nodes of the specified types that match a filter by sending a [`QueryFirstRequest`]
+    /// to the server. Use [`QueryDataSet::data`] to pair the values of each data set with the
+    /// [`QueryDataDescription`] they were returned for.
+    ///
+    /// See OPC UA Part 4 - Services 5.9.3 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_types` - A list of [`NodeTypeDescription`] describing the types of node to find and the data to return for them.
+    /// * `filter` - A [`ContentFilter`] the nodes must match.
+    /// * `max_data_sets_to_return` - The maximum number of data sets to return, 0 for no limit.
+    /// * `max_references_to_return` - The maximum number of references to return in each data set, 0 for no limit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QueryFirstResponse)` - The [`QueryFirstResponse`] holding the data sets found. It may contain a
+    ///   continuation point, for use with `query_next()`.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
+    ///
+    /// [`QueryDataDescription`]: crate::types::QueryDataDescription
+    ///
+    pub async fn query_first(
+        &self,
+        node_types: &[NodeTypeDescription],
+        filter: ContentFilter,
+        max_data_sets_to_return: u32,
+        max_references_to_return: u32,
+    ) -> Result<QueryFirstResponse, ServiceError> {
+        if node_types.is_empty() {
+            session_error!(self, "query_first, was not supplied with any node types");
+            Err(StatusCode::BadNothingToDo.into())
+        } else {
+            let request = QueryFirstRequest {
+                request_header: self.make_request_header(),
+                view: ViewDescription {
+                    view_id: NodeId::null(),
+                    timestamp: DateTime::null(),
+                    view_version: 0,
+                },
+                node_types: Some(node_types.to_vec()),
+                filter,
+                max_data_sets_to_return,
+                max_references_to_return,
+            };
+            let response = self.send(request).await?;
+            if let SupportedMessage::QueryFirstResponse(response) = response {
+                session_debug!(self, "query_first, success");
+                process_service_result(&response.response_header)?;
+                Ok(*response)
+            } else {
+                session_error!(self, "query_first failed {:?}", response);
+                Err(process_unexpected_response(response))
+            }
+        }
+    }
+
+    /// Continues a query by sending the continuation point from a previous call in a [`QueryNextRequest`]
+    /// to the server. This function may have to be called repeatedly until no continuation point is returned.
+    ///
+    /// See OPC UA Part 4 - Services 5.9.4 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `release_continuation_point` - Flag indicating if the continuation point should be released by the server
+    /// * `continuation_point` - The continuation point from `query_first()` or a previous `query_next()`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Option<Vec<QueryDataSet>>, ByteString))` - The next [`QueryDataSet`] results and the revised
+    ///   continuation point, which is null when the query is complete.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
+    ///
+    pub async fn query_next(
+        &self,
+        release_continuation_point: bool,
+        continuation_point: ByteString,
+    ) -> Result<(Option<Vec<QueryDataSet>>, ByteString), ServiceError> {
+        if continuation_point.is_null() {
+            Err(StatusCode::BadNothingToDo.into())
+        } else {
+            let request = QueryNextRequest {
+                request_header: self.make_request_header(),
+                release_continuation_point,
+                continuation_point,
+            };
+            let response = self.send(request).await?;
+            if let SupportedMessage::QueryNextResponse(response) = response {
+                session_debug!(self, "query_next, success");
+                process_service_result(&response.response_header)?;
+                Ok((
+                    response.query_data_sets,
+                    response.revised_continuation_point,
+                ))
+            } else {
+                session_error!(self, "query_next failed {:?}", response);
+                Err(process_unexpected_response(response))
+            }
+        }
+    }
+}
@@ -4,7 +4,9 @@ use crypto::{certificate_store::CertificateStore, user_identity::make_user_name_
 
 use crate::{
     client::{
-        session::{process_service_result, process_unexpected_response},
+        session::{
+            process_service_result, process_unexpected_response, session_error, ServiceError,
+        },
         IdentityToken, Session,
     },
     core::{
@@ -44,20 +46,18 @@ impl Session {
             certificate_store.read_own_cert_and_pkey_optional()
         };
 
-        let client_certificate = if let Some(ref client_certificate) = client_certificate {
-            client_certificate.as_byte_string()
-        } else {
-            ByteString::null()
-        };
-
         let request = CreateSessionRequest {
             request_header: self.make_request_header(),
             client_description: self.application_description.clone(),
             server_uri,
             endpoint_url,
             session_name,
-            client_nonce,
-            client_certificate,
+            client_nonce: client_nonce.clone(),
+            client_certificate: if let Some(ref client_certificate) = client_certificate {
+                client_certificate.as_byte_string()
+            } else {
+                ByteString::null()
+            },
             requested_session_timeout: self.session_timeout,
             max_response_message_size: 0,
         };
@@ -67,20 +67,45 @@ impl Session {
         if let SupportedMessage::CreateSessionResponse(response) = response {
             process_service_result(&response.response_header)?;
 
-            let session_id = {
-                self.session_id.store(Arc::new(response.session_id.clone()));
-                response.session_id.clone()
-            };
-            self.auth_token
-                .store(Arc::new(response.authentication_token));
-
-            self.channel.update_from_created_session(
-                &response.server_nonce,
-                &response.server_certificate,
-            )?;
-
             let security_policy = self.channel.security_policy();
 
+            // The server certificate is checked before the session is kept, so that a session
+            // the client refuses is not activated on a retry. The endpoint certificate of a
+            // pinned server was checked against the pins before the channel was opened, so the
+            // server must present that same certificate and prove it holds its key by signing
+            // the client certificate and nonce, whatever the trust store holds.
+            if !self.session_info.pinned_certificates.is_empty() {
+                let is_pinned = response.server_certificate
+                    == self.session_info.endpoint.server_certificate
+                    && self
+                        .session_info
+                        .is_pinned_certificate(&response.server_certificate);
+                if !is_pinned {
+                    session_error!(
+                        self,
+                        "Server certificate does not match the pinned endpoint certificate"
+                    );
+                    return Err(StatusCode::BadCertificateInvalid);
+                }
+                let (Ok(server_certificate), Some(client_certificate)) = (
+                    crypto::X509::from_byte_string(&response.server_certificate),
+                    client_certificate.as_ref(),
+                ) else {
+                    return Err(StatusCode::BadCertificateInvalid);
+                };
+                let result = crypto::verify_signature_data(
+                    &response.server_signature,
+                    security_policy,
+                    &server_certificate,
+                    client_certificate,
+                    client_nonce.as_ref(),
+                );
+                if result.is_bad() {
+                    session_error!(self, "Server signature of the pinned server is invalid");
+                    return Err(StatusCode::BadApplicationSignatureInvalid);
+                }
+            }
+
             if security_policy != SecurityPolicy::None {
                 if let Ok(server_certificate) =
                     crypto::X509::from_byte_string(&response.server_certificate)
@@ -107,9 +132,21 @@ impl Session {
                 }
             }
 
+            let session_id = {
+                self.session_id.store(Arc::new(response.session_id.clone()));
+                response.session_id.clone()
+            };
+            self.auth_token
+                .store(Arc::new(response.authentication_token));
+
+            self.channel.update_from_created_session(
+                &response.server_nonce,
+                &response.server_certificate,
+            )?;
+
             Ok(session_id)
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -193,7 +230,7 @@ impl Session {
             process_service_result(&response.response_header)?;
             Ok(())
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -329,7 +366,7 @@ impl Session {
     /// This is not accessible by users, they must instead call `disconnect` to properly close the session.
     pub(crate) async fn close_session(&self) -> Result<(), StatusCode> {
         let request = CloseSessionRequest {
-            delete_subscriptions: true,
+            delete_subscriptions: self.delete_subscriptions_on_close,
             request_header: self.make_request_header(),
         };
         let response = self.send(request).await?;
@@ -337,7 +374,7 @@ impl Session {
             Ok(())
         } else {
             error!("close_session failed {:?}", response);
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
@@ -352,9 +389,9 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(u32)` - Success, number of cancelled requests
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
-    pub async fn cancel(&self, request_handle: IntegerId) -> Result<u32, StatusCode> {
+    pub async fn cancel(&self, request_handle: IntegerId) -> Result<u32, ServiceError> {
         let request = CancelRequest {
             request_header: self.make_request_header(),
             request_handle,
@@ -0,0 +1,150 @@
+use std::time::{Duration, Instant};
+
+use crate::types::{DataValue, StatusCode, Variant};
+
+/// How the values of a monitored item are downsampled on the client before they are passed to
+/// the callback of the subscription. This protects a slow consumer from a server that reports
+/// faster than it can keep up with, without changing how the server samples the item.
+///
+/// Values are gathered for an interval that starts with the first value received. Once the
+/// interval is over, the value for it is delivered when the next notification or keep-alive of
+/// the subscription arrives.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Downsampling {
+    /// Delivers the last value received in each interval.
+    Last(Duration),
+    /// Delivers the average of the good numeric values received in each interval, as a `Double`
+    /// with the timestamps of the last value. An interval with no good numeric values delivers
+    /// its last value as it is.
+    Average(Duration),
+}
+
+impl Downsampling {
+    fn interval(&self) -> Duration {
+        match self {
+            Downsampling::Last(interval) | Downsampling::Average(interval) => *interval,
+        }
+    }
+}
+
+/// The values of a monitored item gathered for the current interval.
+pub(crate) struct Downsampler {
+    downsampling: Downsampling,
+    /// End of the current interval, `None` until a value is received
+    interval_end: Option<Instant>,
+    last: Option<DataValue>,
+    sum: f64,
+    count: usize,
+}
+
+impl Downsampler {
+    pub fn new(downsampling: Downsampling) -> Downsampler {
+        Downsampler {
+            downsampling,
+            interval_end: None,
+            last: None,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn downsampling(&self) -> Downsampling {
+        self.downsampling
+    }
+
+    /// Adds a value received at `now`, returning the value of the previous interval if it is over.
+    pub fn push(&mut self, value: DataValue, now: Instant) -> Option<DataValue> {
+        let previous = self.take_due(now);
+        if self.interval_end.is_none() {
+            self.interval_end = Some(now + self.downsampling.interval());
+        }
+        if let Downsampling::Average(_) = self.downsampling {
+            let good = value.status.is_none_or(|s| s.is_good());
+            if let Some(v) = value
+                .value
+                .as_ref()
+                .filter(|_| good)
+                .and_then(Variant::as_f64)
+            {
+                self.sum += v;
+                self.count += 1;
+            }
+        }
+        self.last = Some(value);
+        previous
+    }
+
+    /// Returns the value of the current interval if it is over at `now`.
+    pub fn take_due(&mut self, now: Instant) -> Option<DataValue> {
+        if self.interval_end.is_some_and(|end| end <= now) {
+            self.take()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the current interval, if any values were received in it, and starts
+    /// a new one.
+    pub fn take(&mut self) -> Option<DataValue> {
+        self.interval_end = None;
+        let mut value = self.last.take()?;
+        if self.count > 0 {
+            value.value = Some(Variant::Double(self.sum / self.count as f64));
+            value.status = Some(StatusCode::Good);
+        }
+        self.sum = 0.0;
+        self.count = 0;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{Downsampler, Downsampling};
+    use crate::types::{DataValue, StatusCode, Variant};
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn keep_last() {
+        let start = Instant::now();
+        let mut downsampler = Downsampler::new(Downsampling::Last(INTERVAL));
+        assert!(downsampler.push(DataValue::new_now(1), start).is_none());
+        assert!(downsampler
+            .push(DataValue::new_now(2), start + INTERVAL / 2)
+            .is_none());
+        assert!(downsampler.take_due(start + INTERVAL / 2).is_none());
+
+        // The first value after the interval delivers the last value of it
+        let value = downsampler
+            .push(DataValue::new_now(3), start + INTERVAL)
+            .unwrap();
+        assert_eq!(value.value, Some(Variant::Int32(2)));
+        let value = downsampler.take_due(start + INTERVAL * 2).unwrap();
+        assert_eq!(value.value, Some(Variant::Int32(3)));
+        assert!(downsampler.take_due(start + INTERVAL * 3).is_none());
+    }
+
+    #[test]
+    fn average() {
+        let start = Instant::now();
+        let mut downsampler = Downsampler::new(Downsampling::Average(INTERVAL));
+        downsampler.push(DataValue::new_now(1), start);
+        downsampler.push(DataValue::new_now(2.5f64), start);
+        let mut bad = DataValue::new_now(100);
+        bad.status = Some(StatusCode::BadSensorFailure);
+        downsampler.push(bad, start);
+        downsampler.push(DataValue::new_now(3u8), start);
+        let value = downsampler.take_due(start + INTERVAL).unwrap();
+        assert_eq!(value.value, Some(Variant::Double(6.5 / 3.0)));
+        assert_eq!(value.status, Some(StatusCode::Good));
+
+        // Values that can't be averaged are delivered as they are
+        downsampler.push(DataValue::new_now("hello"), start + INTERVAL);
+        let value = downsampler.take().unwrap();
+        assert_eq!(value.value, Some(Variant::from("hello")));
+        assert!(downsampler.take().is_none());
+    }
+}
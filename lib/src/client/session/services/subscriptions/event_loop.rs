@@ -1,4 +1,7 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::{Arc, Weak},
+    time::Instant,
+};
 
 use futures::{future::Either, stream::FuturesUnordered, Future, Stream, StreamExt};
 
@@ -25,7 +28,7 @@ pub enum SubscriptionActivity {
 /// This handles publshing on a fixed interval, republishing failed requests,
 /// and subscription keep-alive.
 pub struct SubscriptionEventLoop {
-    session: Arc<Session>,
+    session: Weak<Session>,
     trigger_publish_recv: tokio::sync::watch::Receiver<Instant>,
     max_inflight_publish: usize,
     last_external_trigger: Instant,
@@ -35,7 +38,8 @@ pub struct SubscriptionEventLoop {
 }
 
 impl SubscriptionEventLoop {
-    /// Create a new subscription event loop for `session`
+    /// Create a new subscription event loop for `session`. The event loop only keeps a weak
+    /// reference to the session, and ends once the session is dropped.
     ///
     /// # Arguments
     ///
@@ -44,7 +48,7 @@ impl SubscriptionEventLoop {
     ///  This is used to trigger publish outside of the normal schedule, for example when
     ///  a new subscription is created.
     pub fn new(
-        session: Arc<Session>,
+        session: &Arc<Session>,
         trigger_publish_recv: tokio::sync::watch::Receiver<Instant>,
     ) -> Self {
         let last_external_trigger = trigger_publish_recv.borrow().clone();
@@ -52,7 +56,7 @@ impl SubscriptionEventLoop {
             max_inflight_publish: session.max_inflight_publish,
             last_external_trigger,
             trigger_publish_recv,
-            session,
+            session: Arc::downgrade(session),
             is_waiting_for_response: false,
         }
     }
@@ -63,8 +67,10 @@ impl SubscriptionEventLoop {
         futures::stream::unfold(
             (self, FuturesUnordered::new()),
             |(mut slf, mut futures)| async move {
+                // The stream ends once the session is dropped.
+                slf.session.upgrade()?;
                 // Store the next publish time, or None if there are no active subscriptions.
-                let mut next = slf.session.next_publish_time(false);
+                let mut next = slf.next_publish_time(false);
                 let mut recv: tokio::sync::watch::Receiver<Instant> =
                     slf.trigger_publish_recv.clone();
 
@@ -92,13 +98,13 @@ impl SubscriptionEventLoop {
                     tokio::select! {
                         // Both internal ticks and external triggers result in publish requests.
                         v = recv.wait_for(|i| i > &slf.last_external_trigger) => {
-                            if let Ok(v) = v {
-                                debug!("Sending publish due to external trigger");
-                                // On an external trigger, we always publish.
-                                futures.push(slf.static_publish());
-                                next = slf.session.next_publish_time(true);
-                                slf.last_external_trigger = v.clone();
-                            }
+                            // The sender is dropped along with the session, ending the stream.
+                            let v = v.ok()?;
+                            debug!("Sending publish due to external trigger");
+                            // On an external trigger, we always publish.
+                            futures.push(slf.static_publish());
+                            next = slf.next_publish_time(true);
+                            slf.last_external_trigger = v.clone();
                         }
                         _ = next_tick_fut => {
                             // Avoid publishing if there are too many inflight publish requests.
@@ -106,7 +112,7 @@ impl SubscriptionEventLoop {
                                 debug!("Sending publish due to internal tick");
                                 futures.push(slf.static_publish());
                             }
-                            next = slf.session.next_publish_time(true);
+                            next = slf.next_publish_time(true);
                         }
                         res = next_publish_fut => {
                             match res {
@@ -117,34 +123,38 @@ impl SubscriptionEventLoop {
                                         // We do this to avoid a buildup of publish requests
                                         // if exhausting the queue takes more time than
                                         // a single publishing interval.
-                                        slf.session.next_publish_time(true);
+                                        slf.next_publish_time(true);
                                     }
                                     slf.is_waiting_for_response = false;
 
                                     break SubscriptionActivity::Publish
                                 }
                                 Some(Err(e)) => {
+                                    // The stream ends on the next poll if the session has been dropped.
+                                    let Some(session) = slf.session.upgrade() else {
+                                        break SubscriptionActivity::PublishFailed(e)
+                                    };
                                     match e {
                                         StatusCode::BadTimeout => {
-                                            session_debug!(slf.session, "Publish request timed out, sending another");
+                                            session_debug!(session, "Publish request timed out, sending another");
                                             if futures.len() < slf.max_inflight_publish {
                                                 futures.push(slf.static_publish());
                                             }
                                         }
                                         StatusCode::BadTooManyPublishRequests => {
-                                            session_debug!(slf.session, "Server returned BadTooManyPublishRequests, backing off");
+                                            session_debug!(session, "Server returned BadTooManyPublishRequests, backing off");
                                             slf.is_waiting_for_response = true;
                                         }
                                         StatusCode::BadSessionClosed
                                         | StatusCode::BadSessionIdInvalid => {
-                                            // TODO: Do something here?
-                                            session_error!(slf.session, "Publish response indicates session is dead");
+                                            // The session event loop reconnects when the session is invalid
+                                            session_error!(session, "Publish response indicates session is dead");
                                         }
                                         StatusCode::BadNoSubscription
                                         | StatusCode::BadSubscriptionIdInvalid => {
                                             // TODO: Maybe do something here? This could happen when subscriptions are
                                             // in the process of being recreated. Make sure to avoid race conditions.
-                                            session_error!(slf.session, "Publish response indicates subscription is dead");
+                                            session_error!(session, "Publish response indicates subscription is dead");
                                         }
                                         _ => ()
                                     }
@@ -164,6 +174,19 @@ impl SubscriptionEventLoop {
 
     fn static_publish(&self) -> impl Future<Output = Result<bool, StatusCode>> + 'static {
         let inner_session = self.session.clone();
-        async move { inner_session.publish().await }
+        async move {
+            let Some(inner_session) = inner_session.upgrade() else {
+                return Err(StatusCode::BadSessionClosed);
+            };
+            inner_session.publish().await
+        }
+    }
+
+    /// The next time to publish, or `None` if there are no active subscriptions or the session
+    /// has been dropped.
+    fn next_publish_time(&self, set_last_publish: bool) -> Option<Instant> {
+        self.session
+            .upgrade()
+            .and_then(|s| s.next_publish_time(set_last_publish))
     }
 }
@@ -0,0 +1,182 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use futures::Stream;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use crate::{
+    client::{ServiceError, Session},
+    sync::Mutex,
+    types::{DataValue, MonitoredItemCreateRequest, NodeId, StatusCode, TimestampsToReturn},
+};
+
+use super::{MonitoredItem, OnSubscriptionNotification};
+
+type ValueCallback = Box<dyn FnMut(DataValue) + Send + Sync>;
+
+/// Passes the values of a subscription to the callback of the item they belong to, and to every
+/// stream made by [`SubscriptionHandle::changes`].
+#[derive(Default)]
+pub(crate) struct ItemRouter {
+    /// Callbacks keyed by the client handle of their monitored item
+    callbacks: HashMap<u32, ValueCallback>,
+    /// Senders of the open change streams
+    changes: Vec<UnboundedSender<(NodeId, DataValue)>>,
+}
+
+impl ItemRouter {
+    pub(crate) fn insert_callback(&mut self, client_handle: u32, callback: ValueCallback) {
+        self.callbacks.insert(client_handle, callback);
+    }
+
+    pub(crate) fn remove_callback(&mut self, client_handle: u32) {
+        self.callbacks.remove(&client_handle);
+    }
+
+    pub(crate) fn add_changes(&mut self, sender: UnboundedSender<(NodeId, DataValue)>) {
+        self.changes.push(sender);
+    }
+}
+
+/// The callback of a subscription made by [`Session::subscribe`], which hands every value to
+/// the shared [`ItemRouter`].
+pub(crate) struct RouterCallback(pub(crate) Arc<Mutex<ItemRouter>>);
+
+impl OnSubscriptionNotification for RouterCallback {
+    fn on_data_value(&mut self, notification: DataValue, item: &MonitoredItem) {
+        let mut router = trace_lock!(self.0);
+        // Streams that have been dropped are forgotten
+        let node_id = &item.item_to_monitor().node_id;
+        router
+            .changes
+            .retain(|sender| sender.send((node_id.clone(), notification.clone())).is_ok());
+        if let Some(callback) = router.callbacks.get_mut(&item.client_handle()) {
+            callback(notification);
+        }
+    }
+}
+
+/// A subscription that delivers the values of each of its monitored items to a callback of
+/// that item, instead of a single callback for the whole subscription.
+///
+/// It is made by [`Session::subscribe`]. The subscription stays on the server until it is deleted
+/// with [`SubscriptionHandle::delete`] or the session is closed.
+pub struct SubscriptionHandle {
+    session: Arc<Session>,
+    subscription_id: u32,
+    router: Arc<Mutex<ItemRouter>>,
+}
+
+impl SubscriptionHandle {
+    /// Server assigned ID of the subscription.
+    pub fn subscription_id(&self) -> u32 {
+        self.subscription_id
+    }
+
+    /// Creates a monitored item for the value of a node on the subscription. Each value the server
+    /// reports for the item is passed to `callback`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The node whose value attribute is monitored.
+    /// * `callback` - Called for each received data value of the item.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u32)` - Server assigned ID of the monitored item.
+    /// * `Err(ServiceError)` - Request failed or the server did not create the item, the error
+    ///   holds the reason for failure.
+    ///
+    pub async fn monitor_value(
+        &self,
+        node_id: impl Into<NodeId>,
+        callback: impl FnMut(DataValue) + Send + Sync + 'static,
+    ) -> Result<u32, ServiceError> {
+        // The callback is in place before the item is created so its first value is not missed
+        let client_handle = self.session.monitored_item_handle.next();
+        {
+            let mut router = trace_lock!(self.router);
+            router.insert_callback(client_handle, Box::new(callback));
+        }
+        let mut item: MonitoredItemCreateRequest = node_id.into().into();
+        item.requested_parameters.client_handle = client_handle;
+        let result = self
+            .session
+            .create_monitored_items(self.subscription_id, TimestampsToReturn::Both, vec![item])
+            .await
+            .and_then(|created| {
+                let status_code = created[0].result.status_code;
+                if status_code.is_good() {
+                    Ok(created[0].result.monitored_item_id)
+                } else {
+                    Err(status_code.into())
+                }
+            });
+        if result.is_err() {
+            let mut router = trace_lock!(self.router);
+            router.remove_callback(client_handle);
+        }
+        result
+    }
+
+    /// Returns a stream of the values of every monitored item on the subscription, together with
+    /// the node they belong to. The stream ends once the subscription is deleted. It is `Unpin`, so
+    /// `next()` can be called on it without pinning it first.
+    pub fn changes(&self) -> impl Stream<Item = (NodeId, DataValue)> + Unpin {
+        let (sender, mut receiver) = unbounded_channel();
+        {
+            let mut router = trace_lock!(self.router);
+            router.add_changes(sender);
+        }
+        futures::stream::poll_fn(move |cx| receiver.poll_recv(cx))
+    }
+
+    /// Deletes the subscription on the server, along with its monitored items.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatusCode)` - Service return code for the delete action, `Good` or `BadSubscriptionIdInvalid`
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
+    ///
+    pub async fn delete(self) -> Result<StatusCode, ServiceError> {
+        self.session.delete_subscription(self.subscription_id).await
+    }
+}
+
+impl Session {
+    /// Creates a subscription whose values are delivered per monitored item, returning a
+    /// [`SubscriptionHandle`] to add items to it with. The subscription uses a lifetime count of
+    /// 30 and a keep-alive count of 10, has no limit of notifications per publish and is enabled.
+    /// Use [`Session::create_subscription`] for full control.
+    ///
+    /// # Arguments
+    ///
+    /// * `publishing_interval` - The requested publishing interval of the subscription.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SubscriptionHandle)` - The new subscription.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
+    ///
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        publishing_interval: Duration,
+    ) -> Result<SubscriptionHandle, ServiceError> {
+        let router = Arc::new(Mutex::new(ItemRouter::default()));
+        let subscription_id = self
+            .create_subscription(
+                publishing_interval,
+                30,
+                10,
+                0,
+                0,
+                true,
+                RouterCallback(router.clone()),
+            )
+            .await?;
+        Ok(SubscriptionHandle {
+            session: self.clone(),
+            subscription_id,
+            router,
+        })
+    }
+}
@@ -1,16 +1,23 @@
+mod downsample;
 pub mod event_loop;
+mod handle;
 mod service;
 pub mod state;
 
 use std::{
-    collections::{BTreeSet, HashMap},
-    time::Duration,
+    collections::{BTreeSet, HashMap, HashSet},
+    time::{Duration, Instant},
 };
 
+pub use downsample::Downsampling;
+pub use handle::SubscriptionHandle;
+
+use downsample::Downsampler;
+
 use crate::types::{
     DataChangeNotification, DataValue, DecodingOptions, EventNotificationList, ExtensionObject,
-    Identifier, MonitoringMode, NotificationMessage, ObjectId, ReadValueId,
-    StatusChangeNotification, Variant,
+    Identifier, MonitoredItemCreateRequest, MonitoredItemCreateResult, MonitoringMode,
+    NotificationMessage, ObjectId, ReadValueId, StatusChangeNotification, Variant,
 };
 
 pub(crate) struct CreateMonitoredItem {
@@ -24,6 +31,16 @@ pub(crate) struct CreateMonitoredItem {
     pub filter: ExtensionObject,
 }
 
+/// The result of creating a monitored item, together with the client handle that notifications
+/// for the item are delivered with.
+#[derive(Debug, Clone)]
+pub struct CreatedMonitoredItem {
+    /// Client handle of the item, either the one requested or one assigned by the session.
+    pub client_handle: u32,
+    /// Result returned by the server for the item.
+    pub result: MonitoredItemCreateResult,
+}
+
 pub(crate) struct ModifyMonitoredItem {
     pub id: u32,
     pub sampling_interval: f64,
@@ -160,6 +177,8 @@ pub struct MonitoredItem {
     discard_oldest: bool,
     /// Active filter
     filter: ExtensionObject,
+    /// Downsampling of values on the client
+    downsampler: Option<Downsampler>,
 }
 
 impl MonitoredItem {
@@ -174,6 +193,7 @@ impl MonitoredItem {
             triggered_items: BTreeSet::new(),
             discard_oldest: true,
             filter: ExtensionObject::null(),
+            downsampler: None,
         }
     }
 
@@ -207,6 +227,11 @@ impl MonitoredItem {
         self.discard_oldest
     }
 
+    /// Monitoring mode of the item, i.e. whether it is disabled, sampling or reporting.
+    pub fn monitoring_mode(&self) -> MonitoringMode {
+        self.monitoring_mode
+    }
+
     pub(crate) fn set_sampling_interval(&mut self, value: f64) {
         self.sampling_interval = value;
     }
@@ -228,9 +253,15 @@ impl MonitoredItem {
         });
     }
 
-    pub(crate) fn triggered_items(&self) -> &BTreeSet<u32> {
+    /// IDs of the monitored items that are reported when this item is triggered.
+    pub fn triggered_items(&self) -> &BTreeSet<u32> {
         &self.triggered_items
     }
+
+    /// How the values of the item are downsampled on the client, if they are.
+    pub fn downsampling(&self) -> Option<Downsampling> {
+        self.downsampler.as_ref().map(|d| d.downsampling())
+    }
 }
 
 pub struct Subscription {
@@ -253,6 +284,8 @@ pub struct Subscription {
     monitored_items: HashMap<u32, MonitoredItem>,
     /// A map of client handle to monitored item id
     client_handles: HashMap<u32, u32>,
+    /// Sequence number of the last notification message received, or 0 if none has been
+    last_sequence_number: u32,
 
     callback: Box<dyn OnSubscriptionNotification>,
 }
@@ -279,6 +312,7 @@ impl Subscription {
             priority,
             monitored_items: HashMap::new(),
             client_handles: HashMap::new(),
+            last_sequence_number: 0,
             callback: status_change_callback,
         }
     }
@@ -315,6 +349,16 @@ impl Subscription {
         self.publishing_enabled
     }
 
+    /// Sequence number of the last notification message received on the subscription, or 0 if
+    /// none has been received yet.
+    pub fn last_sequence_number(&self) -> u32 {
+        self.last_sequence_number
+    }
+
+    pub(crate) fn set_last_sequence_number(&mut self, last_sequence_number: u32) {
+        self.last_sequence_number = last_sequence_number;
+    }
+
     pub(crate) fn set_publishing_interval(&mut self, publishing_interval: Duration) {
         self.publishing_interval = publishing_interval;
     }
@@ -351,6 +395,7 @@ impl Subscription {
                 triggered_items: BTreeSet::new(),
                 discard_oldest: i.discard_oldest,
                 filter: i.filter,
+                downsampler: None,
             };
 
             let client_handle = monitored_item.client_handle();
@@ -379,6 +424,47 @@ impl Subscription {
         })
     }
 
+    /// Find the ID of the monitored item with the supplied client handle.
+    pub(crate) fn monitored_item_id(&self, client_handle: u32) -> Option<u32> {
+        self.client_handles.get(&client_handle).copied()
+    }
+
+    /// Assigns a client handle from `next_handle` to each item requested with a handle of 0,
+    /// skipping handles that are already in use on the subscription or in `items`. If a handle
+    /// supplied by the caller is already in use, it is returned as the error.
+    pub(crate) fn assign_client_handles(
+        &self,
+        items: &mut [MonitoredItemCreateRequest],
+        mut next_handle: impl FnMut() -> u32,
+    ) -> Result<(), u32> {
+        let mut in_use = HashSet::with_capacity(items.len());
+        for item in items.iter() {
+            let client_handle = item.requested_parameters.client_handle;
+            if client_handle != 0
+                && (self.client_handles.contains_key(&client_handle)
+                    || !in_use.insert(client_handle))
+            {
+                return Err(client_handle);
+            }
+        }
+        for item in items.iter_mut() {
+            if item.requested_parameters.client_handle == 0 {
+                let client_handle = loop {
+                    let client_handle = next_handle();
+                    if client_handle != 0
+                        && !self.client_handles.contains_key(&client_handle)
+                        && !in_use.contains(&client_handle)
+                    {
+                        break client_handle;
+                    }
+                };
+                in_use.insert(client_handle);
+                item.requested_parameters.client_handle = client_handle;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn set_triggering(
         &mut self,
         triggering_item_id: u32,
@@ -390,11 +476,38 @@ impl Subscription {
         }
     }
 
+    /// Sets the downsampling of a monitored item. A value still held back by the previous
+    /// downsampling is delivered first.
+    pub(crate) fn set_downsampling(
+        &mut self,
+        monitored_item_id: u32,
+        downsampling: Option<Downsampling>,
+    ) {
+        let Some(item) = self.monitored_items.get_mut(&monitored_item_id) else {
+            return;
+        };
+        let pending = item.downsampler.as_mut().and_then(|d| d.take());
+        item.downsampler = downsampling.map(Downsampler::new);
+        if let Some(value) = pending {
+            self.callback.on_data_value(value, item);
+        }
+    }
+
+    /// Delivers the downsampled values of the intervals that are over at `now`.
+    pub(crate) fn deliver_downsampled(&mut self, now: Instant) {
+        for item in self.monitored_items.values_mut() {
+            if let Some(value) = item.downsampler.as_mut().and_then(|d| d.take_due(now)) {
+                self.callback.on_data_value(value, item);
+            }
+        }
+    }
+
     pub(crate) fn on_notification(
         &mut self,
         notification: NotificationMessage,
         decoding_options: &DecodingOptions,
     ) {
+        let now = Instant::now();
         let Some(notifications) = notification.notification_data else {
             return;
         };
@@ -415,10 +528,16 @@ impl Subscription {
                             let item = self
                                 .client_handles
                                 .get(&notif.client_handle)
-                                .and_then(|handle| self.monitored_items.get(handle));
+                                .and_then(|handle| self.monitored_items.get_mut(handle));
 
                             if let Some(item) = item {
-                                self.callback.on_data_value(notif.value, item);
+                                let value = match item.downsampler.as_mut() {
+                                    Some(downsampler) => downsampler.push(notif.value, now),
+                                    None => Some(notif.value),
+                                };
+                                if let Some(value) = value {
+                                    self.callback.on_data_value(value, item);
+                                }
                             }
                         }
                     }
@@ -449,3 +568,172 @@ impl Subscription {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::sync::Arc;
+
+    use super::{
+        handle::{ItemRouter, RouterCallback},
+        CreateMonitoredItem, DataChangeCallback, Subscription,
+    };
+    use crate::{
+        sync::Mutex,
+        types::{
+            DataValue, DateTime, DecodingOptions, ExtensionObject, MonitoredItemCreateRequest,
+            MonitoredItemNotification, MonitoringMode, MonitoringParameters, NodeId,
+            NotificationMessage, ReadValueId,
+        },
+    };
+
+    fn subscription_with_handles(client_handles: &[u32]) -> Subscription {
+        let mut subscription = Subscription::new(
+            1,
+            Duration::from_millis(100),
+            100,
+            10,
+            0,
+            0,
+            true,
+            Box::new(DataChangeCallback::new(|_, _| {})),
+        );
+        subscription.insert_monitored_items(
+            client_handles
+                .iter()
+                .enumerate()
+                .map(|(i, client_handle)| CreateMonitoredItem {
+                    id: i as u32 + 1,
+                    client_handle: *client_handle,
+                    item_to_monitor: ReadValueId::default(),
+                    monitoring_mode: MonitoringMode::Reporting,
+                    queue_size: 1,
+                    discard_oldest: true,
+                    sampling_interval: 100.0,
+                    filter: ExtensionObject::null(),
+                })
+                .collect(),
+        );
+        subscription
+    }
+
+    fn item(client_handle: u32) -> MonitoredItemCreateRequest {
+        MonitoredItemCreateRequest {
+            item_to_monitor: ReadValueId::default(),
+            monitoring_mode: MonitoringMode::Reporting,
+            requested_parameters: MonitoringParameters {
+                client_handle,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn client_handles(items: &[MonitoredItemCreateRequest]) -> Vec<u32> {
+        items
+            .iter()
+            .map(|i| i.requested_parameters.client_handle)
+            .collect()
+    }
+
+    #[test]
+    fn assign_client_handles() {
+        let subscription = subscription_with_handles(&[1000, 1002]);
+
+        // Handles in use on the subscription or in the request are skipped
+        let mut next = 1000..;
+        let mut items = vec![item(0), item(1003), item(0), item(5)];
+        subscription
+            .assign_client_handles(&mut items, || next.next().unwrap())
+            .unwrap();
+        assert_eq!(client_handles(&items), vec![1001, 1003, 1004, 5]);
+    }
+
+    #[test]
+    fn client_handle_collisions() {
+        let subscription = subscription_with_handles(&[1000]);
+
+        // Already in use on the subscription
+        let mut items = vec![item(0), item(1000)];
+        assert_eq!(
+            subscription.assign_client_handles(&mut items, || 2000),
+            Err(1000)
+        );
+        assert_eq!(client_handles(&items), vec![0, 1000]);
+
+        // Repeated within the request
+        let mut items = vec![item(7), item(0), item(7)];
+        assert_eq!(
+            subscription.assign_client_handles(&mut items, || 2000),
+            Err(7)
+        );
+    }
+
+    #[test]
+    fn route_values_to_items() {
+        let router = Arc::new(Mutex::new(ItemRouter::default()));
+        let mut subscription = Subscription::new(
+            1,
+            Duration::from_millis(100),
+            100,
+            10,
+            0,
+            0,
+            true,
+            Box::new(RouterCallback(router.clone())),
+        );
+        subscription.insert_monitored_items(
+            [(1, "v1"), (2, "v2")]
+                .into_iter()
+                .map(|(client_handle, node)| CreateMonitoredItem {
+                    id: client_handle,
+                    client_handle,
+                    item_to_monitor: NodeId::new(2, node).into(),
+                    monitoring_mode: MonitoringMode::Reporting,
+                    queue_size: 1,
+                    discard_oldest: true,
+                    sampling_interval: 100.0,
+                    filter: ExtensionObject::null(),
+                })
+                .collect(),
+        );
+
+        // Only the first item has a callback, but the stream gets the values of both
+        let values = Arc::new(Mutex::new(Vec::new()));
+        {
+            let values = values.clone();
+            let mut router = router.lock();
+            router.insert_callback(1, Box::new(move |v| values.lock().push(v)));
+        }
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        router.lock().add_changes(sender);
+
+        let notification = NotificationMessage::data_change(
+            1,
+            DateTime::now(),
+            vec![
+                MonitoredItemNotification {
+                    client_handle: 1,
+                    value: DataValue::new_now(10),
+                },
+                MonitoredItemNotification {
+                    client_handle: 2,
+                    value: DataValue::new_now(20),
+                },
+            ],
+            vec![],
+        );
+        subscription.on_notification(notification, &DecodingOptions::test());
+
+        let values = values.lock();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].value, Some(10.into()));
+        let (node_id, value) = receiver.try_recv().unwrap();
+        assert_eq!(node_id, NodeId::new(2, "v1"));
+        assert_eq!(value.value, Some(10.into()));
+        let (node_id, value) = receiver.try_recv().unwrap();
+        assert_eq!(node_id, NodeId::new(2, "v2"));
+        assert_eq!(value.value, Some(20.into()));
+        assert!(receiver.try_recv().is_err());
+    }
+}
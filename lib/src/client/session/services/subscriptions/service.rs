@@ -1,5 +1,6 @@
 use std::{
     collections::HashSet,
+    sync::{atomic::Ordering, Arc},
     time::{Duration, Instant},
 };
 
@@ -7,19 +8,23 @@ use crate::{
     client::{
         session::{
             process_service_result, process_unexpected_response,
-            services::subscriptions::{CreateMonitoredItem, ModifyMonitoredItem, Subscription},
-            session_debug, session_error, session_trace, session_warn,
+            services::subscriptions::{
+                CreateMonitoredItem, CreatedMonitoredItem, Downsampling, ModifyMonitoredItem,
+                Subscription,
+            },
+            session_debug, session_error, session_trace, session_warn, ServiceError,
         },
         Session,
     },
     core::supported_message::SupportedMessage,
     types::{
-        CreateMonitoredItemsRequest, CreateSubscriptionRequest, DeleteMonitoredItemsRequest,
-        DeleteSubscriptionsRequest, ModifyMonitoredItemsRequest, ModifySubscriptionRequest,
-        MonitoredItemCreateRequest, MonitoredItemCreateResult, MonitoredItemModifyRequest,
-        MonitoredItemModifyResult, MonitoringMode, MonitoringParameters, PublishRequest,
-        SetMonitoringModeRequest, SetPublishingModeRequest, SetTriggeringRequest, StatusCode,
-        TimestampsToReturn, TransferResult, TransferSubscriptionsRequest,
+        CreateMonitoredItemsRequest, CreateSubscriptionRequest, DecodingOptions,
+        DeleteMonitoredItemsRequest, DeleteSubscriptionsRequest, ModifyMonitoredItemsRequest,
+        ModifySubscriptionRequest, MonitoredItemCreateRequest, MonitoredItemModifyRequest,
+        MonitoredItemModifyResult, MonitoringMode, MonitoringParameters, NodeId,
+        NotificationMessage, PublishRequest, RepublishRequest, SetMonitoringModeRequest,
+        SetPublishingModeRequest, SetTriggeringRequest, StatusCode, TimestampsToReturn,
+        TransferResult, TransferSubscriptionsRequest, VariableId, Variant,
     },
 };
 
@@ -35,7 +40,7 @@ impl Session {
         publishing_enabled: bool,
         priority: u8,
         callback: Box<dyn OnSubscriptionNotification>,
-    ) -> Result<u32, StatusCode> {
+    ) -> Result<u32, ServiceError> {
         let request = CreateSubscriptionRequest {
             request_header: self.make_request_header(),
             requested_publishing_interval: publishing_interval.as_secs_f64(),
@@ -119,7 +124,7 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(u32)` - identifier for new subscription
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn create_subscription(
         &self,
@@ -130,7 +135,7 @@ impl Session {
         priority: u8,
         publishing_enabled: bool,
         callback: impl OnSubscriptionNotification + Send + Sync + 'static,
-    ) -> Result<u32, StatusCode> {
+    ) -> Result<u32, ServiceError> {
         self.create_subscription_inner(
             publishing_interval,
             lifetime_count,
@@ -184,7 +189,7 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(())` - Success
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn modify_subscription(
         &self,
@@ -194,13 +199,13 @@ impl Session {
         max_keep_alive_count: u32,
         max_notifications_per_publish: u32,
         priority: u8,
-    ) -> Result<(), StatusCode> {
+    ) -> Result<(), ServiceError> {
         if subscription_id == 0 {
             session_error!(self, "modify_subscription, subscription id must be non-zero, or the subscription is considered invalid");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if !self.subscription_exists(subscription_id) {
             session_error!(self, "modify_subscription, subscription id does not exist");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else {
             let request = ModifySubscriptionRequest {
                 request_header: self.make_request_header(),
@@ -244,16 +249,20 @@ impl Session {
     /// * `publishing_enabled` - A boolean parameter with the following values - `true` publishing
     ///   is enabled for the Subscriptions, `false`, publishing is disabled for the Subscriptions.
     ///
+    /// While publishing is disabled the server keeps sampling the monitored items and queues their
+    /// notifications, which are delivered once publishing is enabled again. Keep-alive messages
+    /// continue to be sent in the meantime.
+    ///
     /// # Returns
     ///
     /// * `Ok(Vec<StatusCode>)` - Service return code for the action for each id, `Good` or `BadSubscriptionIdInvalid`
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn set_publishing_mode(
         &self,
         subscription_ids: &[u32],
         publishing_enabled: bool,
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         session_debug!(
             self,
             "set_publishing_mode, for subscriptions {:?}, publishing enabled {}",
@@ -266,7 +275,7 @@ impl Session {
                 self,
                 "set_publishing_mode, no subscription ids were provided"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = SetPublishingModeRequest {
                 request_header: self.make_request_header(),
@@ -276,13 +285,24 @@ impl Session {
             let response = self.send(request).await?;
             if let SupportedMessage::SetPublishingModeResponse(response) = response {
                 process_service_result(&response.response_header)?;
+                let results = response.results.unwrap_or_default();
                 {
-                    // Clear out all subscriptions, assuming the delete worked
+                    // Only update the subscriptions the server accepted the change for
+                    let changed = subscription_ids
+                        .iter()
+                        .zip(results.iter())
+                        .filter(|(_, r)| r.is_good())
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<u32>>();
                     let mut subscription_state = trace_lock!(self.subscription_state);
-                    subscription_state.set_publishing_mode(subscription_ids, publishing_enabled);
+                    subscription_state.set_publishing_mode(&changed, publishing_enabled);
+                }
+                if publishing_enabled {
+                    // Fetch anything the server buffered while publishing was disabled
+                    let _ = self.trigger_publish_tx.send(Instant::now());
                 }
                 session_debug!(self, "set_publishing_mode success");
-                Ok(response.results.unwrap())
+                Ok(results)
             } else {
                 session_error!(self, "set_publishing_mode failed {:?}", response);
                 Err(process_unexpected_response(response))
@@ -306,20 +326,20 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<TransferResult>)` - The [`TransferResult`] for each transfer subscription.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn transfer_subscriptions(
         &self,
         subscription_ids: &[u32],
         send_initial_values: bool,
-    ) -> Result<Vec<TransferResult>, StatusCode> {
+    ) -> Result<Vec<TransferResult>, ServiceError> {
         if subscription_ids.is_empty() {
             // No subscriptions
             session_error!(
                 self,
                 "set_publishing_mode, no subscription ids were provided"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = TransferSubscriptionsRequest {
                 request_header: self.make_request_header(),
@@ -349,22 +369,22 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(StatusCode)` - Service return code for the delete action, `Good` or `BadSubscriptionIdInvalid`
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn delete_subscription(
         &self,
         subscription_id: u32,
-    ) -> Result<StatusCode, StatusCode> {
+    ) -> Result<StatusCode, ServiceError> {
         if subscription_id == 0 {
             session_error!(self, "delete_subscription, subscription id 0 is invalid");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if !self.subscription_exists(subscription_id) {
             session_error!(
                 self,
                 "delete_subscription, subscription id {} does not exist",
                 subscription_id
             );
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else {
             let result = self.delete_subscriptions(&[subscription_id][..]).await?;
             Ok(result[0])
@@ -384,16 +404,16 @@ impl Session {
     ///
     /// * `Ok(Vec<StatusCode>)` - List of result for delete action on each id, `Good` or `BadSubscriptionIdInvalid`
     ///   The size and order of the list matches the size and order of the input.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn delete_subscriptions(
         &self,
         subscription_ids: &[u32],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if subscription_ids.is_empty() {
             // No subscriptions
             session_trace!(self, "delete_subscriptions with no subscriptions");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             // Send a delete request holding all the subscription ides that we wish to delete
             let request = DeleteSubscriptionsRequest {
@@ -428,19 +448,23 @@ impl Session {
     /// * `subscription_id` - The Server-assigned identifier for the Subscription that will report Notifications for this MonitoredItem
     /// * `timestamps_to_return` - An enumeration that specifies the timestamp Attributes to be transmitted for each MonitoredItem.
     /// * `items_to_create` - A list of [`MonitoredItemCreateRequest`] to be created and assigned to the specified Subscription.
+    ///   Items with a client handle of 0 are assigned a handle that is unique on the subscription.
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<MonitoredItemCreateResult>)` - A list of [`MonitoredItemCreateResult`] corresponding to the items to create.
-    ///    The size and order of the list matches the size and order of the `items_to_create` request parameter.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Ok(Vec<CreatedMonitoredItem>)` - A list of [`CreatedMonitoredItem`] corresponding to the items to create, holding the
+    ///   client handle of each item and its [`MonitoredItemCreateResult`](crate::types::MonitoredItemCreateResult).
+    ///   The size and order of the list matches the size and order of the `items_to_create` request parameter.
+    /// * `Err(StatusCode::BadInvalidArgument)` - A client handle in `items_to_create` is already in use on the subscription,
+    ///   or is repeated in `items_to_create`.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn create_monitored_items(
         &self,
         subscription_id: u32,
         timestamps_to_return: TimestampsToReturn,
         items_to_create: Vec<MonitoredItemCreateRequest>,
-    ) -> Result<Vec<MonitoredItemCreateResult>, StatusCode> {
+    ) -> Result<Vec<CreatedMonitoredItem>, ServiceError> {
         session_debug!(
             self,
             "create_monitored_items, for subscription {}, {} items",
@@ -449,44 +473,52 @@ impl Session {
         );
         if subscription_id == 0 {
             session_error!(self, "create_monitored_items, subscription id 0 is invalid");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if !self.subscription_exists(subscription_id) {
             session_error!(
                 self,
                 "create_monitored_items, subscription id {} does not exist",
                 subscription_id
             );
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if items_to_create.is_empty() {
             session_error!(
                 self,
                 "create_monitored_items, called with no items to create"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
-            let mut final_items_to_create = Vec::new();
-            let mut created_items = Vec::new();
-
-            for mut req in items_to_create {
-                if req.requested_parameters.client_handle == 0 {
-                    req.requested_parameters.client_handle = self.monitored_item_handle.next();
+            let mut created_items = items_to_create;
+            {
+                let subscription_state = trace_lock!(self.subscription_state);
+                let Some(subscription) = subscription_state.get(subscription_id) else {
+                    return Err(StatusCode::BadInvalidArgument.into());
+                };
+                if let Err(client_handle) = subscription
+                    .assign_client_handles(&mut created_items, || self.monitored_item_handle.next())
+                {
+                    session_error!(
+                        self,
+                        "create_monitored_items, client handle {} is already in use on subscription {}",
+                        client_handle,
+                        subscription_id
+                    );
+                    return Err(StatusCode::BadInvalidArgument.into());
                 }
-
-                final_items_to_create.push(req.clone());
-                created_items.push(req);
             }
 
             let request = CreateMonitoredItemsRequest {
                 request_header: self.make_request_header(),
                 subscription_id,
                 timestamps_to_return,
-                items_to_create: Some(final_items_to_create.clone()),
+                items_to_create: Some(created_items.clone()),
             };
             let response = self.send(request).await?;
 
             if let SupportedMessage::CreateMonitoredItemsResponse(response) = response {
                 process_service_result(&response.response_header)?;
-                if let Some(ref results) = response.results {
+                let results = response.results.unwrap_or_default();
+                if !results.is_empty() {
                     session_debug!(
                         self,
                         "create_monitored_items, {} items created",
@@ -494,8 +526,8 @@ impl Session {
                     );
                     // Set the items in our internal state
                     let items_to_create = created_items
-                        .into_iter()
-                        .zip(results)
+                        .iter()
+                        .zip(&results)
                         .map(|(i, r)| CreateMonitoredItem {
                             id: r.monitored_item_id,
                             client_handle: i.requested_parameters.client_handle,
@@ -504,7 +536,7 @@ impl Session {
                             monitoring_mode: i.monitoring_mode,
                             queue_size: r.revised_queue_size,
                             sampling_interval: r.revised_sampling_interval,
-                            filter: i.requested_parameters.filter,
+                            filter: i.requested_parameters.filter.clone(),
                         })
                         .collect::<Vec<CreateMonitoredItem>>();
                     {
@@ -517,7 +549,14 @@ impl Session {
                         "create_monitored_items, success but no monitored items were created"
                     );
                 }
-                Ok(response.results.unwrap())
+                Ok(created_items
+                    .into_iter()
+                    .zip(results)
+                    .map(|(i, result)| CreatedMonitoredItem {
+                        client_handle: i.requested_parameters.client_handle,
+                        result,
+                    })
+                    .collect())
             } else {
                 session_error!(self, "create_monitored_items failed {:?}", response);
                 Err(process_unexpected_response(response))
@@ -539,14 +578,14 @@ impl Session {
     ///
     /// * `Ok(Vec<MonitoredItemModifyResult>)` - A list of [`MonitoredItemModifyResult`] corresponding to the MonitoredItems to modify.
     ///    The size and order of the list matches the size and order of the `items_to_modify` request parameter.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn modify_monitored_items(
         &self,
         subscription_id: u32,
         timestamps_to_return: TimestampsToReturn,
         items_to_modify: &[MonitoredItemModifyRequest],
-    ) -> Result<Vec<MonitoredItemModifyResult>, StatusCode> {
+    ) -> Result<Vec<MonitoredItemModifyResult>, ServiceError> {
         session_debug!(
             self,
             "modify_monitored_items, for subscription {}, {} items",
@@ -555,20 +594,20 @@ impl Session {
         );
         if subscription_id == 0 {
             session_error!(self, "modify_monitored_items, subscription id 0 is invalid");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if !self.subscription_exists(subscription_id) {
             session_error!(
                 self,
                 "modify_monitored_items, subscription id {} does not exist",
                 subscription_id
             );
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if items_to_modify.is_empty() {
             session_error!(
                 self,
                 "modify_monitored_items, called with no items to modify"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let monitored_item_ids = items_to_modify
                 .iter()
@@ -623,17 +662,17 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Vec<StatusCode>)` - Individual result for each monitored item.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn set_monitoring_mode(
         &self,
         subscription_id: u32,
         monitoring_mode: MonitoringMode,
         monitored_item_ids: &[u32],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         if monitored_item_ids.is_empty() {
             session_error!(self, "set_monitoring_mode, called with nothing to do");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = {
                 let monitored_item_ids = Some(monitored_item_ids.to_vec());
@@ -645,17 +684,25 @@ impl Session {
                 }
             };
             let response = self.send(request).await?;
-
-            {
-                let mut subscription_state = trace_lock!(self.subscription_state);
-                subscription_state.set_monitoring_mode(
-                    subscription_id,
-                    monitored_item_ids,
-                    monitoring_mode,
-                );
-            }
             if let SupportedMessage::SetMonitoringModeResponse(response) = response {
-                Ok(response.results.unwrap())
+                process_service_result(&response.response_header)?;
+                let results = response.results.unwrap_or_default();
+                {
+                    // Only update the items the server accepted the change for
+                    let changed = monitored_item_ids
+                        .iter()
+                        .zip(results.iter())
+                        .filter(|(_, r)| r.is_good())
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<u32>>();
+                    let mut subscription_state = trace_lock!(self.subscription_state);
+                    subscription_state.set_monitoring_mode(
+                        subscription_id,
+                        &changed,
+                        monitoring_mode,
+                    );
+                }
+                Ok(results)
             } else {
                 session_error!(self, "set_monitoring_mode failed {:?}", response);
                 Err(process_unexpected_response(response))
@@ -679,7 +726,7 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok((Option<Vec<StatusCode>>, Option<Vec<StatusCode>>))` - Individual result for each item added / removed for the SetTriggering call.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn set_triggering(
         &self,
@@ -687,10 +734,10 @@ impl Session {
         triggering_item_id: u32,
         links_to_add: &[u32],
         links_to_remove: &[u32],
-    ) -> Result<(Option<Vec<StatusCode>>, Option<Vec<StatusCode>>), StatusCode> {
+    ) -> Result<(Option<Vec<StatusCode>>, Option<Vec<StatusCode>>), ServiceError> {
         if links_to_add.is_empty() && links_to_remove.is_empty() {
             session_error!(self, "set_triggering, called with nothing to add or remove");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = {
                 let links_to_add = if links_to_add.is_empty() {
@@ -713,13 +760,24 @@ impl Session {
             };
             let response = self.send(request).await?;
             if let SupportedMessage::SetTriggeringResponse(response) = response {
-                // Update client side state
+                process_service_result(&response.response_header)?;
+                // Update client side state with the links the server accepted
+                let accepted = |links: &[u32], results: &Option<Vec<StatusCode>>| {
+                    links
+                        .iter()
+                        .zip(results.iter().flatten())
+                        .filter(|(_, r)| r.is_good())
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<u32>>()
+                };
+                let added = accepted(links_to_add, &response.add_results);
+                let removed = accepted(links_to_remove, &response.remove_results);
                 let mut subscription_state = trace_lock!(self.subscription_state);
                 subscription_state.set_triggering(
                     subscription_id,
                     triggering_item_id,
-                    links_to_add,
-                    links_to_remove,
+                    &added,
+                    &removed,
                 );
                 Ok((response.add_results, response.remove_results))
             } else {
@@ -729,7 +787,53 @@ impl Session {
         }
     }
 
+    /// Sets how the values of monitored items are downsampled on the client before they are
+    /// passed to the callback of the subscription, or turns downsampling off with `None`. Nothing
+    /// is sent to the server, which goes on sampling the items as before.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription_id` - the subscription identifier containing the monitored items.
+    /// * `monitored_item_ids` - the monitored items to downsample.
+    /// * `downsampling` - how the values are downsampled.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Success
+    /// * `Err(StatusCode)` - The subscription or one of the monitored items does not exist.
+    ///
+    pub fn set_downsampling(
+        &self,
+        subscription_id: u32,
+        monitored_item_ids: &[u32],
+        downsampling: Option<Downsampling>,
+    ) -> Result<(), StatusCode> {
+        let mut subscription_state = trace_lock!(self.subscription_state);
+        let Some(subscription) = subscription_state.get(subscription_id) else {
+            session_error!(
+                self,
+                "set_downsampling, subscription id {} does not exist",
+                subscription_id
+            );
+            return Err(StatusCode::BadSubscriptionIdInvalid);
+        };
+        if let Some(id) = monitored_item_ids
+            .iter()
+            .find(|id| !subscription.monitored_items().contains_key(id))
+        {
+            session_error!(
+                self,
+                "set_downsampling, monitored item id {} does not exist",
+                id
+            );
+            return Err(StatusCode::BadMonitoredItemIdInvalid);
+        }
+        subscription_state.set_downsampling(subscription_id, monitored_item_ids, downsampling);
+        Ok(())
+    }
+
     /// Deletes monitored items from a subscription by sending a [`DeleteMonitoredItemsRequest`] to the server.
+    /// The items are sent in batches of no more than the server's `MaxMonitoredItemsPerCall` operation limit.
     ///
     /// See OPC UA Part 4 - Services 5.12.6 for complete description of the service and error responses.
     ///
@@ -742,13 +846,14 @@ impl Session {
     ///
     /// * `Ok(Vec<StatusCode>)` - List of StatusCodes for the MonitoredItems to delete. The size and
     ///   order of the list matches the size and order of the `items_to_delete` request parameter.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure. Items in
+    ///   batches that were sent before the failure are deleted.
     ///
     pub async fn delete_monitored_items(
         &self,
         subscription_id: u32,
         items_to_delete: &[u32],
-    ) -> Result<Vec<StatusCode>, StatusCode> {
+    ) -> Result<Vec<StatusCode>, ServiceError> {
         session_debug!(
             self,
             "delete_monitored_items, subscription {} for {} items",
@@ -757,40 +862,170 @@ impl Session {
         );
         if subscription_id == 0 {
             session_error!(self, "delete_monitored_items, subscription id 0 is invalid");
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if !self.subscription_exists(subscription_id) {
             session_error!(
                 self,
                 "delete_monitored_items, subscription id {} does not exist",
                 subscription_id
             );
-            Err(StatusCode::BadInvalidArgument)
+            Err(StatusCode::BadInvalidArgument.into())
         } else if items_to_delete.is_empty() {
             session_error!(
                 self,
                 "delete_monitored_items, called with no items to delete"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
-            let request = DeleteMonitoredItemsRequest {
-                request_header: self.make_request_header(),
-                subscription_id,
-                monitored_item_ids: Some(items_to_delete.to_vec()),
+            let monitored_items_per_call = self.monitored_items_per_call().await;
+            let mut results = Vec::with_capacity(items_to_delete.len());
+            for batch in items_to_delete.chunks(monitored_items_per_call) {
+                results.extend(
+                    self.delete_monitored_items_batch(subscription_id, batch)
+                        .await?,
+                );
+            }
+            session_debug!(self, "delete_monitored_items, success");
+            Ok(results)
+        }
+    }
+
+    /// Deletes every monitored item of a subscription, in batches of no more than the server's
+    /// `MaxMonitoredItemsPerCall` operation limit. A batch that fails does not stop the batches
+    /// after it, and its items are kept in the subscription state.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription_id` - The Server-assigned identifier for the Subscription whose MonitoredItems are deleted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(u32, StatusCode)>)` - The id of each MonitoredItem of the subscription, in ascending order,
+    ///   with the result of deleting it. Items in a batch that failed have the reason the batch failed.
+    /// * `Err(ServiceError)` - The subscription does not exist.
+    ///
+    pub async fn delete_all_monitored_items(
+        &self,
+        subscription_id: u32,
+    ) -> Result<Vec<(u32, StatusCode)>, ServiceError> {
+        let mut items_to_delete = {
+            let subscription_state = trace_lock!(self.subscription_state);
+            let Some(subscription) = subscription_state.get(subscription_id) else {
+                session_error!(
+                    self,
+                    "delete_all_monitored_items, subscription id {} does not exist",
+                    subscription_id
+                );
+                return Err(StatusCode::BadInvalidArgument.into());
             };
-            let response = self.send(request).await?;
-            if let SupportedMessage::DeleteMonitoredItemsResponse(response) = response {
-                process_service_result(&response.response_header)?;
-                if response.results.is_some() {
-                    let mut subscription_state = trace_lock!(self.subscription_state);
-                    subscription_state.delete_monitored_items(subscription_id, items_to_delete);
+            subscription
+                .monitored_items()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        items_to_delete.sort_unstable();
+
+        let mut results = Vec::with_capacity(items_to_delete.len());
+        if items_to_delete.is_empty() {
+            return Ok(results);
+        }
+        let monitored_items_per_call = self.monitored_items_per_call().await;
+        for batch in items_to_delete.chunks(monitored_items_per_call) {
+            match self
+                .delete_monitored_items_batch(subscription_id, batch)
+                .await
+            {
+                Ok(batch_results) => {
+                    results.extend(batch.iter().copied().zip(batch_results));
+                }
+                Err(err) => {
+                    session_warn!(
+                        self,
+                        "delete_all_monitored_items, a batch of {} items failed with {}",
+                        batch.len(),
+                        err
+                    );
+                    results.extend(batch.iter().map(|id| (*id, err.status())));
                 }
-                session_debug!(self, "delete_monitored_items, success");
-                Ok(response.results.unwrap())
-            } else {
-                session_error!(self, "delete_monitored_items failed {:?}", response);
-                Err(process_unexpected_response(response))
             }
         }
+        Ok(results)
+    }
+
+    /// Sends a single [`DeleteMonitoredItemsRequest`] and removes the items that the server no
+    /// longer has from the subscription state.
+    async fn delete_monitored_items_batch(
+        &self,
+        subscription_id: u32,
+        items_to_delete: &[u32],
+    ) -> Result<Vec<StatusCode>, ServiceError> {
+        let request = DeleteMonitoredItemsRequest {
+            request_header: self.make_request_header(),
+            subscription_id,
+            monitored_item_ids: Some(items_to_delete.to_vec()),
+        };
+        let response = self.send(request).await?;
+        if let SupportedMessage::DeleteMonitoredItemsResponse(response) = response {
+            process_service_result(&response.response_header)?;
+            let results = response.results.unwrap_or_default();
+            if results.len() != items_to_delete.len() {
+                session_error!(
+                    self,
+                    "delete_monitored_items, expected {} results, got {}",
+                    items_to_delete.len(),
+                    results.len()
+                );
+                return Err(StatusCode::BadUnexpectedError.into());
+            }
+            // An item the server does not know has gone all the same
+            let deleted = items_to_delete
+                .iter()
+                .zip(&results)
+                .filter(|(_, r)| r.is_good() || **r == StatusCode::BadMonitoredItemIdInvalid)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            {
+                let mut subscription_state = trace_lock!(self.subscription_state);
+                subscription_state.delete_monitored_items(subscription_id, &deleted);
+            }
+            Ok(results)
+        } else {
+            session_error!(self, "delete_monitored_items failed {:?}", response);
+            Err(process_unexpected_response(response))
+        }
+    }
+
+    /// Returns the most monitored items to send to the server in one call. This is the server's
+    /// `MaxMonitoredItemsPerCall` operation limit, or the configured chunk size for recreating
+    /// monitored items if the server has no limit or it cannot be read.
+    async fn monitored_items_per_call(&self) -> usize {
+        let monitored_items_per_call = self.monitored_items_per_call.load(Ordering::Relaxed);
+        if monitored_items_per_call > 0 {
+            return monitored_items_per_call as usize;
+        }
+        let node_id: NodeId =
+            VariableId::Server_ServerCapabilities_OperationLimits_MaxMonitoredItemsPerCall.into();
+        let limit = match self
+            .read(&[node_id.into()], TimestampsToReturn::Neither, 0.0)
+            .await
+        {
+            Ok(mut values) => match values.pop().and_then(|v| v.value) {
+                Some(Variant::UInt32(limit)) if limit > 0 => limit,
+                _ => self.recreate_monitored_items_chunk.max(1) as u32,
+            },
+            Err(status_code) => {
+                session_warn!(
+                    self,
+                    "Cannot read the monitored items per call limit of the server, {}",
+                    status_code
+                );
+                return self.recreate_monitored_items_chunk.max(1);
+            }
+        };
+        self.monitored_items_per_call
+            .store(limit, Ordering::Relaxed);
+        limit as usize
     }
 
     pub(crate) fn next_publish_time(&self, set_last_publish: bool) -> Option<Instant> {
@@ -803,7 +1038,7 @@ impl Session {
 
     /// Send a publish request, returning `true` if the session should send a new request
     /// immediately.
-    pub(crate) async fn publish(&self) -> Result<bool, StatusCode> {
+    pub(crate) async fn publish(self: &Arc<Self>) -> Result<bool, StatusCode> {
         let acks = {
             let mut subscription_state = trace_lock!(self.subscription_state);
             let acks = subscription_state.take_acknowledgements();
@@ -832,6 +1067,9 @@ impl Session {
         };
 
         let response = self.channel.send(request, self.publish_timeout).await;
+        if let Ok(response) = &response {
+            self.check_service_fault(response);
+        }
 
         let err_status = match response {
             Ok(SupportedMessage::PublishResponse(r)) => {
@@ -842,12 +1080,21 @@ impl Session {
                     secure_channel.decoding_options()
                 };
 
-                {
+                let recover = {
                     let mut subscription_state = trace_lock!(self.subscription_state);
-                    subscription_state.handle_notification(
+                    subscription_state.receive_notification(
                         r.subscription_id,
                         r.notification_message,
+                        r.available_sequence_numbers,
                         &decoding_options,
+                    )
+                };
+                if recover {
+                    // Notifications missed before this one are republished by a task of its own
+                    // so the publish loop is not held up by the round trips
+                    tokio::spawn(
+                        self.clone()
+                            .recover_notifications(r.subscription_id, decoding_options),
                     );
                 }
 
@@ -856,7 +1103,7 @@ impl Session {
             Err(e) => e,
             Ok(r) => {
                 session_error!(self, "publish failed {:?}", r);
-                process_unexpected_response(r)
+                process_unexpected_response(r).into()
             }
         };
 
@@ -868,6 +1115,111 @@ impl Session {
         Err(err_status)
     }
 
+    /// Asks the server to republish a notification message from its retransmission queue by
+    /// sending a [`RepublishRequest`] to the server. Notification messages are retained by the
+    /// server until they are acknowledged, so this may be used to recover messages that were lost.
+    ///
+    /// The session calls this automatically when it detects a gap in the sequence numbers of
+    /// received notifications, replaying the missed notifications to the subscription callback.
+    /// It does so in the background, holding back later notifications of the subscription until
+    /// the missed ones have been delivered.
+    ///
+    /// See OPC UA Part 4 - Services 5.13.6 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription_id` - The Server-assigned identifier for the subscription.
+    /// * `retransmit_sequence_number` - The sequence number of the notification message to republish.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NotificationMessage)` - The requested [`NotificationMessage`].
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure,
+    ///   e.g. `BadMessageNotAvailable` if the server no longer holds the message.
+    ///
+    pub async fn republish(
+        &self,
+        subscription_id: u32,
+        retransmit_sequence_number: u32,
+    ) -> Result<NotificationMessage, ServiceError> {
+        session_debug!(
+            self,
+            "republish, subscription {}, sequence number {}",
+            subscription_id,
+            retransmit_sequence_number
+        );
+        let request = RepublishRequest {
+            request_header: self.make_request_header(),
+            subscription_id,
+            retransmit_sequence_number,
+        };
+        let response = self.send(request).await?;
+        if let SupportedMessage::RepublishResponse(response) = response {
+            process_service_result(&response.response_header)?;
+            Ok(response.notification_message)
+        } else {
+            session_error!(self, "republish failed {:?}", response);
+            Err(process_unexpected_response(response))
+        }
+    }
+
+    /// Republish the notifications missed on a subscription and handle them, then the ones held
+    /// back while they were recovered, in the order that they were sent.
+    async fn recover_notifications(
+        self: Arc<Self>,
+        subscription_id: u32,
+        decoding_options: DecodingOptions,
+    ) {
+        loop {
+            let next = {
+                let mut subscription_state = trace_lock!(self.subscription_state);
+                subscription_state.next_recovered_notification(subscription_id)
+            };
+            let Some((notification, missing)) = next else {
+                break;
+            };
+            self.republish_notifications(subscription_id, missing, &decoding_options)
+                .await;
+            let mut subscription_state = trace_lock!(self.subscription_state);
+            subscription_state.handle_notification(
+                subscription_id,
+                notification,
+                &decoding_options,
+            );
+        }
+    }
+
+    /// Republish the given notifications of a subscription and handle them in order, as if they
+    /// had arrived in publish responses.
+    async fn republish_notifications(
+        &self,
+        subscription_id: u32,
+        sequence_numbers: Vec<u32>,
+        decoding_options: &DecodingOptions,
+    ) {
+        for sequence_number in sequence_numbers {
+            match self.republish(subscription_id, sequence_number).await {
+                Ok(notification_message) => {
+                    let mut subscription_state = trace_lock!(self.subscription_state);
+                    subscription_state.handle_notification(
+                        subscription_id,
+                        notification_message,
+                        decoding_options,
+                    );
+                }
+                Err(e) => {
+                    session_warn!(
+                        self,
+                        "Could not republish notification {} on subscription {}: {}",
+                        sequence_number,
+                        subscription_id,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
     /// This code attempts to take the existing subscriptions created by a previous session and
     /// either transfer them to this session, or construct them from scratch.
     pub(crate) async fn transfer_subscriptions_from_old_session(&self) {
@@ -889,12 +1241,27 @@ impl Session {
             subscription_ids.iter().copied().collect::<HashSet<u32>>();
         if let Ok(transfer_results) = self.transfer_subscriptions(&subscription_ids, true).await {
             session_debug!(self, "transfer_results = {:?}", transfer_results);
-            transfer_results.iter().enumerate().for_each(|(i, r)| {
+            let decoding_options = {
+                let secure_channel = trace_read_lock!(self.channel.secure_channel);
+                secure_channel.decoding_options()
+            };
+            for (subscription_id, r) in subscription_ids.iter().zip(transfer_results) {
                 if r.status_code.is_good() {
                     // Subscription was transferred so it does not need to be recreated
-                    subscription_ids_to_recreate.remove(&subscription_ids[i]);
+                    subscription_ids_to_recreate.remove(subscription_id);
+
+                    // Recover notifications the old session never delivered
+                    let unreceived = {
+                        let subscription_state = trace_lock!(self.subscription_state);
+                        subscription_state.unreceived_sequence_numbers(
+                            *subscription_id,
+                            r.available_sequence_numbers.as_deref().unwrap_or_default(),
+                        )
+                    };
+                    self.republish_notifications(*subscription_id, unreceived, &decoding_options)
+                        .await;
                 }
-            });
+            }
         }
 
         // But if it didn't work, then some or all subscriptions have to be remade.
@@ -970,14 +1337,50 @@ impl Session {
                     .await;
             }
 
-            for item in subscription.monitored_items.values() {
-                let triggered_items = item.triggered_items();
-                if !triggered_items.is_empty() {
-                    let links_to_add = triggered_items.iter().copied().collect::<Vec<u32>>();
-                    let _ = self
-                        .set_triggering(subscription_id, item.id(), links_to_add.as_slice(), &[])
-                        .await;
+            // The recreated items have new IDs, so the triggering links and downsampling are
+            // restored by finding each item again through its client handle.
+            let triggering = {
+                let mut subscription_state = trace_lock!(self.subscription_state);
+                let Some(recreated) = subscription_state.get(subscription_id) else {
+                    continue;
+                };
+                let new_id = |old_id: &u32| {
+                    subscription
+                        .monitored_items
+                        .get(old_id)
+                        .and_then(|item| recreated.monitored_item_id(item.client_handle()))
+                };
+                let downsampling = subscription
+                    .monitored_items
+                    .values()
+                    .filter_map(|item| Some((new_id(&item.id())?, item.downsampling()?)))
+                    .collect::<Vec<_>>();
+                let triggering = subscription
+                    .monitored_items
+                    .values()
+                    .filter(|item| !item.triggered_items().is_empty())
+                    .filter_map(|item| {
+                        let links_to_add = item
+                            .triggered_items()
+                            .iter()
+                            .filter_map(new_id)
+                            .collect::<Vec<u32>>();
+                        Some((new_id(&item.id())?, links_to_add))
+                    })
+                    .collect::<Vec<_>>();
+                for (monitored_item_id, downsampling) in downsampling {
+                    subscription_state.set_downsampling(
+                        subscription_id,
+                        &[monitored_item_id],
+                        Some(downsampling),
+                    );
                 }
+                triggering
+            };
+            for (triggering_item_id, links_to_add) in triggering {
+                let _ = self
+                    .set_triggering(subscription_id, triggering_item_id, &links_to_add, &[])
+                    .await;
             }
         }
     }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     time::{Duration, Instant},
 };
 
@@ -7,7 +7,83 @@ use crate::types::{
     DecodingOptions, MonitoringMode, NotificationMessage, SubscriptionAcknowledgement,
 };
 
-use super::{CreateMonitoredItem, ModifyMonitoredItem, Subscription};
+use super::{CreateMonitoredItem, Downsampling, ModifyMonitoredItem, Subscription};
+
+/// A change to the subscriptions or monitored items held in a [SubscriptionState].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionStateEvent {
+    /// A subscription was created.
+    SubscriptionAdded { subscription_id: u32 },
+    /// The parameters or publishing mode of a subscription changed.
+    SubscriptionModified { subscription_id: u32 },
+    /// A subscription was deleted, along with its monitored items.
+    SubscriptionDeleted { subscription_id: u32 },
+    /// Monitored items were created on a subscription.
+    MonitoredItemsAdded {
+        subscription_id: u32,
+        monitored_item_ids: Vec<u32>,
+    },
+    /// The parameters, monitoring mode or triggered items of monitored items changed.
+    MonitoredItemsModified {
+        subscription_id: u32,
+        monitored_item_ids: Vec<u32>,
+    },
+    /// Monitored items were deleted from a subscription.
+    MonitoredItemsDeleted {
+        subscription_id: u32,
+        monitored_item_ids: Vec<u32>,
+    },
+}
+
+/// A listener for changes to a [SubscriptionState].
+/// You may implement this on your own struct, or simply use [SubscriptionStateCallback]
+/// for a closure.
+///
+/// Listeners are called with the subscription state locked, after the change has been applied,
+/// so they must not try to lock the subscription state of the session themselves.
+pub trait OnSubscriptionStateChange: Send + Sync {
+    /// Called for each change to the subscription state.
+    fn on_subscription_state_change(
+        &mut self,
+        event: &SubscriptionStateEvent,
+        state: &SubscriptionState,
+    );
+}
+
+type StateChangeFn = dyn FnMut(&SubscriptionStateEvent, &SubscriptionState) + Send + Sync;
+
+/// A held back notification and the sequence numbers the server had available when it arrived.
+type HeldNotification = (NotificationMessage, Option<Vec<u32>>);
+
+/// A wrapper around a closure that implements [OnSubscriptionStateChange]
+pub struct SubscriptionStateCallback {
+    callback: Box<StateChangeFn>,
+}
+
+impl SubscriptionStateCallback {
+    /// Create a new subscription state callback wrapper.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Called for each change to the subscription state.
+    pub fn new(
+        callback: impl FnMut(&SubscriptionStateEvent, &SubscriptionState) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            callback: Box::new(callback) as Box<StateChangeFn>,
+        }
+    }
+}
+
+impl OnSubscriptionStateChange for SubscriptionStateCallback {
+    fn on_subscription_state_change(
+        &mut self,
+        event: &SubscriptionStateEvent,
+        state: &SubscriptionState,
+    ) {
+        (self.callback)(event, state);
+    }
+}
 
 /// State containing all known subscriptions in the session.
 pub struct SubscriptionState {
@@ -16,6 +92,11 @@ pub struct SubscriptionState {
     acknowledgements: Vec<SubscriptionAcknowledgement>,
     keep_alive_timeout: Option<Duration>,
     min_publish_interval: Duration,
+    listeners: Vec<(u32, Box<dyn OnSubscriptionStateChange>)>,
+    next_listener_id: u32,
+    /// Notifications held back on subscriptions whose missed notifications are being republished,
+    /// each with the sequence numbers the server had available when it arrived
+    recovering: HashMap<u32, VecDeque<HeldNotification>>,
 }
 
 impl SubscriptionState {
@@ -32,9 +113,40 @@ impl SubscriptionState {
             acknowledgements: Vec::new(),
             keep_alive_timeout: None,
             min_publish_interval,
+            listeners: Vec::new(),
+            next_listener_id: 1,
+            recovering: HashMap::new(),
         }
     }
 
+    /// Register a listener that is called whenever subscriptions or monitored items are added,
+    /// modified or deleted. Returns an id that can be passed to [SubscriptionState::remove_listener].
+    pub fn add_listener(&mut self, listener: impl OnSubscriptionStateChange + 'static) -> u32 {
+        let listener_id = self.next_listener_id;
+        self.next_listener_id += 1;
+        self.listeners.push((listener_id, Box::new(listener)));
+        listener_id
+    }
+
+    /// Remove a listener added with [SubscriptionState::add_listener]. Returns `false` if there
+    /// was no such listener.
+    pub fn remove_listener(&mut self, listener_id: u32) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|(id, _)| *id != listener_id);
+        self.listeners.len() != len
+    }
+
+    fn notify(&mut self, event: SubscriptionStateEvent) {
+        if self.listeners.is_empty() {
+            return;
+        }
+        let mut listeners = std::mem::take(&mut self.listeners);
+        for (_, listener) in listeners.iter_mut() {
+            listener.on_subscription_state_change(&event, self);
+        }
+        self.listeners = listeners;
+    }
+
     pub(crate) fn next_publish_time(&self) -> Option<Instant> {
         if self.subscriptions.is_empty() {
             return None;
@@ -91,9 +203,10 @@ impl SubscriptionState {
     }
 
     pub(crate) fn add_subscription(&mut self, subscription: Subscription) {
-        self.subscriptions
-            .insert(subscription.subscription_id(), subscription);
+        let subscription_id = subscription.subscription_id();
+        self.subscriptions.insert(subscription_id, subscription);
         self.set_keep_alive_timeout();
+        self.notify(SubscriptionStateEvent::SubscriptionAdded { subscription_id });
     }
 
     pub(crate) fn modify_subscription(
@@ -112,12 +225,17 @@ impl SubscriptionState {
             subscription.set_max_notifications_per_publish(max_notifications_per_publish);
             subscription.set_priority(priority);
             self.set_keep_alive_timeout();
+            self.notify(SubscriptionStateEvent::SubscriptionModified { subscription_id });
         }
     }
 
     pub(crate) fn delete_subscription(&mut self, subscription_id: u32) -> Option<Subscription> {
         let subscription = self.subscriptions.remove(&subscription_id);
+        self.recovering.remove(&subscription_id);
         self.set_keep_alive_timeout();
+        if subscription.is_some() {
+            self.notify(SubscriptionStateEvent::SubscriptionDeleted { subscription_id });
+        }
         subscription
     }
 
@@ -129,6 +247,9 @@ impl SubscriptionState {
         subscription_ids.iter().for_each(|subscription_id| {
             if let Some(ref mut subscription) = self.subscriptions.get_mut(subscription_id) {
                 subscription.set_publishing_enabled(publishing_enabled);
+                self.notify(SubscriptionStateEvent::SubscriptionModified {
+                    subscription_id: *subscription_id,
+                });
             }
         });
     }
@@ -139,7 +260,12 @@ impl SubscriptionState {
         items_to_create: Vec<CreateMonitoredItem>,
     ) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            let monitored_item_ids = items_to_create.iter().map(|i| i.id).collect();
             subscription.insert_monitored_items(items_to_create);
+            self.notify(SubscriptionStateEvent::MonitoredItemsAdded {
+                subscription_id,
+                monitored_item_ids,
+            });
         }
     }
 
@@ -150,12 +276,30 @@ impl SubscriptionState {
     ) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
             subscription.modify_monitored_items(items_to_modify);
+            let monitored_item_ids = items_to_modify
+                .iter()
+                .map(|i| i.id)
+                .filter(|id| subscription.monitored_items.contains_key(id))
+                .collect();
+            self.notify(SubscriptionStateEvent::MonitoredItemsModified {
+                subscription_id,
+                monitored_item_ids,
+            });
         }
     }
 
     pub(crate) fn delete_monitored_items(&mut self, subscription_id: u32, items_to_delete: &[u32]) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            let monitored_item_ids = items_to_delete
+                .iter()
+                .copied()
+                .filter(|id| subscription.monitored_items.contains_key(id))
+                .collect();
             subscription.delete_monitored_items(items_to_delete);
+            self.notify(SubscriptionStateEvent::MonitoredItemsDeleted {
+                subscription_id,
+                monitored_item_ids,
+            });
         }
     }
 
@@ -167,7 +311,16 @@ impl SubscriptionState {
         links_to_remove: &[u32],
     ) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
-            subscription.set_triggering(triggering_item_id, links_to_add, links_to_remove);
+            if subscription
+                .monitored_items
+                .contains_key(&triggering_item_id)
+            {
+                subscription.set_triggering(triggering_item_id, links_to_add, links_to_remove);
+                self.notify(SubscriptionStateEvent::MonitoredItemsModified {
+                    subscription_id,
+                    monitored_item_ids: vec![triggering_item_id],
+                });
+            }
         }
     }
 
@@ -178,23 +331,198 @@ impl SubscriptionState {
         monitoring_mode: MonitoringMode,
     ) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            let mut monitored_item_ids = Vec::with_capacity(montiored_item_ids.len());
             for id in montiored_item_ids {
                 if let Some(item) = subscription.monitored_items.get_mut(id) {
                     item.set_monitoring_mode(monitoring_mode);
+                    monitored_item_ids.push(*id);
                 }
             }
+            self.notify(SubscriptionStateEvent::MonitoredItemsModified {
+                subscription_id,
+                monitored_item_ids,
+            });
         }
     }
 
+    pub(crate) fn set_downsampling(
+        &mut self,
+        subscription_id: u32,
+        monitored_item_ids: &[u32],
+        downsampling: Option<Downsampling>,
+    ) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            for id in monitored_item_ids {
+                subscription.set_downsampling(*id, downsampling);
+            }
+            self.notify(SubscriptionStateEvent::MonitoredItemsModified {
+                subscription_id,
+                monitored_item_ids: monitored_item_ids.to_vec(),
+            });
+        }
+    }
+
+    /// Returns the sequence numbers of notification messages that were missed on the subscription
+    /// before the supplied one, in the order they were sent. Only sequence numbers that the server
+    /// reports as still available for retransmission are returned.
+    pub(crate) fn missing_sequence_numbers(
+        &self,
+        subscription_id: u32,
+        notification: &NotificationMessage,
+        available_sequence_numbers: Option<&[u32]>,
+    ) -> Vec<u32> {
+        let Some(sub) = self.subscriptions.get(&subscription_id) else {
+            return Vec::new();
+        };
+        let last = sub.last_sequence_number();
+        // A keep-alive holds the sequence number of the next notification, so in both cases any
+        // sequence number between the last one received and this one has been missed.
+        if last == 0 || !is_after(notification.sequence_number, last) {
+            return Vec::new();
+        }
+        let gap = distance(notification.sequence_number, last) - 1;
+        if gap == 0 {
+            return Vec::new();
+        }
+
+        let mut missing = available_sequence_numbers
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|n| is_after(*n, last) && is_after(notification.sequence_number, *n))
+            .collect::<Vec<_>>();
+        missing.sort_by_key(|n| distance(*n, last));
+        if (missing.len() as u32) < gap {
+            warn!(
+                "Subscription {} missed {} notification(s) after sequence number {} that are no longer available from the server",
+                subscription_id,
+                gap - missing.len() as u32,
+                last
+            );
+        }
+        missing
+    }
+
+    /// Handles a notification from a publish response, unless notifications were missed before it
+    /// or earlier ones are still being recovered. Then it is held back, and `true` is returned if
+    /// the caller must start recovering the subscription with
+    /// [`SubscriptionState::next_recovered_notification`].
+    pub(crate) fn receive_notification(
+        &mut self,
+        subscription_id: u32,
+        notification: NotificationMessage,
+        available_sequence_numbers: Option<Vec<u32>>,
+        decoding_options: &DecodingOptions,
+    ) -> bool {
+        if let Some(held) = self.recovering.get_mut(&subscription_id) {
+            held.push_back((notification, available_sequence_numbers));
+            false
+        } else if self.follows_gap(subscription_id, &notification) {
+            self.recovering.insert(
+                subscription_id,
+                VecDeque::from([(notification, available_sequence_numbers)]),
+            );
+            true
+        } else {
+            self.handle_notification(subscription_id, notification, decoding_options);
+            false
+        }
+    }
+
+    /// Takes the next notification held back on a recovering subscription, along with the
+    /// sequence numbers missed before it, which must be republished and handled first. Recovery
+    /// of the subscription ends once no notification is left.
+    pub(crate) fn next_recovered_notification(
+        &mut self,
+        subscription_id: u32,
+    ) -> Option<(NotificationMessage, Vec<u32>)> {
+        let held = self.recovering.get_mut(&subscription_id)?;
+        let Some((notification, available_sequence_numbers)) = held.pop_front() else {
+            self.recovering.remove(&subscription_id);
+            return None;
+        };
+        let missing = self.missing_sequence_numbers(
+            subscription_id,
+            &notification,
+            available_sequence_numbers.as_deref(),
+        );
+        Some((notification, missing))
+    }
+
+    /// Test if sequence numbers are missing between the last notification received on the
+    /// subscription and the supplied one
+    fn follows_gap(&self, subscription_id: u32, notification: &NotificationMessage) -> bool {
+        self.subscriptions
+            .get(&subscription_id)
+            .map(|sub| sub.last_sequence_number())
+            .is_some_and(|last| {
+                last != 0
+                    && is_after(notification.sequence_number, last)
+                    && distance(notification.sequence_number, last) > 1
+            })
+    }
+
+    /// Returns the sequence numbers out of `available_sequence_numbers` that come after the last
+    /// notification received on the subscription, oldest first. After a subscription is
+    /// transferred to a new session these are the notifications the client has yet to see.
+    pub(crate) fn unreceived_sequence_numbers(
+        &self,
+        subscription_id: u32,
+        available_sequence_numbers: &[u32],
+    ) -> Vec<u32> {
+        let Some(sub) = self.subscriptions.get(&subscription_id) else {
+            return Vec::new();
+        };
+        let last = sub.last_sequence_number();
+        let mut unreceived = available_sequence_numbers
+            .iter()
+            .copied()
+            .filter(|n| last == 0 || is_after(*n, last))
+            .collect::<Vec<_>>();
+        unreceived.sort_by_key(|n| distance(*n, last));
+        unreceived
+    }
+
     pub(crate) fn handle_notification(
         &mut self,
         subscription_id: u32,
         notification: NotificationMessage,
         decoding_options: &DecodingOptions,
     ) {
-        self.add_acknowledgement(subscription_id, notification.sequence_number);
-        if let Some(sub) = self.subscriptions.get_mut(&subscription_id) {
+        let is_keep_alive = notification
+            .notification_data
+            .as_ref()
+            .is_none_or(|d| d.is_empty());
+        let Some(sub) = self.subscriptions.get_mut(&subscription_id) else {
+            // Acknowledge it anyway so the server can release it
+            if !is_keep_alive {
+                self.add_acknowledgement(subscription_id, notification.sequence_number);
+            }
+            return;
+        };
+        // Values held back by downsampling go out before any newer ones
+        sub.deliver_downsampled(Instant::now());
+        let last = sub.last_sequence_number();
+        if is_keep_alive {
+            // The keep-alive holds the next sequence number so every one before it has been sent
+            let previous = match notification.sequence_number.wrapping_sub(1) {
+                0 if last != 0 => u32::MAX,
+                previous => previous,
+            };
+            if previous != 0 && (last == 0 || is_after(previous, last)) {
+                sub.set_last_sequence_number(previous);
+            }
+        } else if last == 0 || is_after(notification.sequence_number, last) {
+            let sequence_number = notification.sequence_number;
+            sub.set_last_sequence_number(sequence_number);
             sub.on_notification(notification, decoding_options);
+            self.add_acknowledgement(subscription_id, sequence_number);
+        } else {
+            // Already received, e.g. through a republish while this response was in flight
+            debug!(
+                "Ignoring duplicate notification with sequence number {} on subscription {}",
+                notification.sequence_number, subscription_id
+            );
         }
     }
 
@@ -206,3 +534,223 @@ impl SubscriptionState {
             .min()
     }
 }
+
+/// Test if sequence number `a` was sent after `b`, allowing for sequence numbers wrapping around.
+fn is_after(a: u32, b: u32) -> bool {
+    let distance = distance(a, b);
+    distance != 0 && distance < u32::MAX / 2
+}
+
+/// The number of sequence numbers from `b` to `a`. Sequence numbers wrap around from `u32::MAX`
+/// to 1, as 0 is never used.
+fn distance(a: u32, b: u32) -> u32 {
+    let distance = a.wrapping_sub(b);
+    if a < b {
+        distance.wrapping_sub(1)
+    } else {
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::{distance, is_after, SubscriptionState};
+    use crate::{
+        client::session::services::subscriptions::{
+            CreateMonitoredItem, DataChangeCallback, Subscription,
+        },
+        sync::Mutex,
+        types::{
+            DataValue, DateTime, DecodingOptions, ExtensionObject, MonitoredItemNotification,
+            MonitoringMode, NotificationMessage, ReadValueId, Variant,
+        },
+    };
+
+    const SUBSCRIPTION_ID: u32 = 1;
+
+    /// Makes a state with one subscription whose values are recorded in the returned list
+    fn state_with_subscription() -> (SubscriptionState, Arc<Mutex<Vec<Variant>>>) {
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let callback = {
+            let values = values.clone();
+            DataChangeCallback::new(move |value, _| values.lock().push(value.value.unwrap()))
+        };
+        let mut subscription = Subscription::new(
+            SUBSCRIPTION_ID,
+            Duration::from_millis(100),
+            100,
+            10,
+            0,
+            0,
+            true,
+            Box::new(callback),
+        );
+        subscription.insert_monitored_items(vec![CreateMonitoredItem {
+            id: 1,
+            client_handle: 1,
+            item_to_monitor: ReadValueId::default(),
+            monitoring_mode: MonitoringMode::Reporting,
+            queue_size: 1,
+            discard_oldest: true,
+            sampling_interval: 100.0,
+            filter: ExtensionObject::null(),
+        }]);
+        let mut state = SubscriptionState::new(Duration::from_millis(100));
+        state.add_subscription(subscription);
+        (state, values)
+    }
+
+    /// A notification whose value is its sequence number
+    fn notification(sequence_number: u32) -> NotificationMessage {
+        NotificationMessage::data_change(
+            sequence_number,
+            DateTime::now(),
+            vec![MonitoredItemNotification {
+                client_handle: 1,
+                value: DataValue::value_only(sequence_number),
+            }],
+            vec![],
+        )
+    }
+
+    fn handle(state: &mut SubscriptionState, sequence_number: u32) {
+        state.handle_notification(
+            SUBSCRIPTION_ID,
+            notification(sequence_number),
+            &DecodingOptions::test(),
+        );
+    }
+
+    fn acknowledged(state: &mut SubscriptionState) -> Vec<u32> {
+        state
+            .take_acknowledgements()
+            .into_iter()
+            .map(|ack| ack.sequence_number)
+            .collect()
+    }
+
+    fn delivered(values: &Mutex<Vec<Variant>>) -> Vec<u32> {
+        std::mem::take(&mut *values.lock())
+            .into_iter()
+            .map(|v| match v {
+                Variant::UInt32(v) => v,
+                v => panic!("Unexpected value {:?}", v),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sequence_number_order() {
+        assert!(is_after(2, 1));
+        assert!(!is_after(1, 2));
+        assert!(!is_after(1, 1));
+        // 0 is skipped when sequence numbers wrap around
+        assert!(is_after(1, u32::MAX));
+        assert_eq!(distance(1, u32::MAX), 1);
+        assert_eq!(distance(2, u32::MAX - 1), 3);
+        assert!(!is_after(u32::MAX, 1));
+    }
+
+    #[test]
+    fn gap_is_recovered_in_order() {
+        let (mut state, values) = state_with_subscription();
+        let decoding_options = DecodingOptions::test();
+        handle(&mut state, 5);
+        assert_eq!(delivered(&values), vec![5]);
+
+        // 6 and 7 were missed, and 3 was acknowledged long ago
+        assert!(state.receive_notification(
+            SUBSCRIPTION_ID,
+            notification(8),
+            Some(vec![7, 3, 6]),
+            &decoding_options,
+        ));
+        // Held back behind the recovery
+        assert!(!state.receive_notification(
+            SUBSCRIPTION_ID,
+            notification(9),
+            None,
+            &decoding_options,
+        ));
+        assert!(delivered(&values).is_empty());
+
+        let (held, missing) = state.next_recovered_notification(SUBSCRIPTION_ID).unwrap();
+        assert_eq!(held.sequence_number, 8);
+        assert_eq!(missing, vec![6, 7]);
+        handle(&mut state, 6);
+        handle(&mut state, 7);
+        state.handle_notification(SUBSCRIPTION_ID, held, &decoding_options);
+
+        let (held, missing) = state.next_recovered_notification(SUBSCRIPTION_ID).unwrap();
+        assert_eq!(held.sequence_number, 9);
+        assert!(missing.is_empty());
+        state.handle_notification(SUBSCRIPTION_ID, held, &decoding_options);
+        assert!(state.next_recovered_notification(SUBSCRIPTION_ID).is_none());
+
+        // Recovery is over so the next notification is handled straight away
+        assert!(!state.receive_notification(
+            SUBSCRIPTION_ID,
+            notification(10),
+            None,
+            &decoding_options,
+        ));
+        assert_eq!(delivered(&values), vec![6, 7, 8, 9, 10]);
+        assert_eq!(acknowledged(&mut state), vec![5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn gap_across_wraparound() {
+        let (mut state, values) = state_with_subscription();
+        handle(&mut state, u32::MAX - 1);
+
+        // The sequence number after u32::MAX is 1, so only u32::MAX was missed
+        let missing = state.missing_sequence_numbers(
+            SUBSCRIPTION_ID,
+            &notification(1),
+            Some(&[1, u32::MAX, u32::MAX - 1]),
+        );
+        assert_eq!(missing, vec![u32::MAX]);
+        handle(&mut state, u32::MAX);
+
+        // Nothing is missed from u32::MAX to 1
+        assert!(state
+            .missing_sequence_numbers(SUBSCRIPTION_ID, &notification(1), Some(&[u32::MAX]))
+            .is_empty());
+        assert!(!state.receive_notification(
+            SUBSCRIPTION_ID,
+            notification(1),
+            None,
+            &DecodingOptions::test(),
+        ));
+        assert_eq!(delivered(&values), vec![u32::MAX - 1, u32::MAX, 1]);
+
+        // A keep-alive that holds 1 means u32::MAX was the last one sent
+        let (mut state, _) = state_with_subscription();
+        handle(&mut state, u32::MAX - 1);
+        state.handle_notification(
+            SUBSCRIPTION_ID,
+            NotificationMessage::keep_alive(1, DateTime::now()),
+            &DecodingOptions::test(),
+        );
+        assert!(state
+            .missing_sequence_numbers(SUBSCRIPTION_ID, &notification(2), Some(&[1]))
+            .contains(&1));
+    }
+
+    #[test]
+    fn duplicate_notification_is_ignored() {
+        let (mut state, values) = state_with_subscription();
+        handle(&mut state, 1);
+        handle(&mut state, 2);
+        // Received again, e.g. through a republish while the publish response was in flight
+        handle(&mut state, 2);
+        handle(&mut state, 1);
+        assert!(state
+            .missing_sequence_numbers(SUBSCRIPTION_ID, &notification(2), Some(&[1, 2]))
+            .is_empty());
+        assert_eq!(delivered(&values), vec![1, 2]);
+        assert_eq!(acknowledged(&mut state), vec![1, 2]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     client::{
         session::{
             process_service_result, process_unexpected_response, session_debug, session_error,
+            ServiceError,
         },
         Session,
     },
@@ -9,12 +10,14 @@ use crate::{
     types::{
         BrowseDescription, BrowseNextRequest, BrowsePath, BrowsePathResult, BrowseRequest,
         BrowseResult, ByteString, DateTime, NodeId, RegisterNodesRequest, StatusCode,
-        TranslateBrowsePathsToNodeIdsRequest, UnregisterNodesRequest, ViewDescription,
+        TranslateBrowsePathsToNodeIdsRequest, UnregisterNodesRequest, VariableId, ViewDescription,
     },
 };
 
 impl Session {
-    /// Discover the references to the specified nodes by sending a [`BrowseRequest`] to the server.
+    /// Discover the references to the specified nodes by sending a [`BrowseRequest`] to the server. If there are
+    /// more nodes than the server's `MaxNodesPerBrowse` operation limit, they are browsed with as many requests
+    /// as it takes.
     ///
     /// See OPC UA Part 4 - Services 5.8.2 for complete description of the service and error responses.
     ///
@@ -25,36 +28,60 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(Option<Vec<BrowseResult>)` - A list [`BrowseResult`] corresponding to each node to browse. A browse result
-    ///                                    may contain a continuation point, for use with `browse_next()`.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    ///   may contain a continuation point, for use with `browse_next()`. When the nodes are split over several
+    ///   requests, the results of a request that failed have the reason it failed as their status code.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn browse(
         &self,
         nodes_to_browse: &[BrowseDescription],
-    ) -> Result<Option<Vec<BrowseResult>>, StatusCode> {
+    ) -> Result<Option<Vec<BrowseResult>>, ServiceError> {
         if nodes_to_browse.is_empty() {
             session_error!(self, "browse, was not supplied with any nodes to browse");
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
-            let request = BrowseRequest {
-                request_header: self.make_request_header(),
-                view: ViewDescription {
-                    view_id: NodeId::null(),
-                    timestamp: DateTime::null(),
-                    view_version: 0,
+            self.send_in_batches(
+                "browse",
+                nodes_to_browse,
+                (
+                    &self.max_nodes_per_browse,
+                    VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerBrowse,
+                ),
+                |batch| async move { Ok(self.browse_batch(batch).await?.unwrap_or_default()) },
+                |status_code| BrowseResult {
+                    status_code,
+                    continuation_point: ByteString::null(),
+                    references: None,
                 },
-                requested_max_references_per_node: 1000,
-                nodes_to_browse: Some(nodes_to_browse.to_vec()),
-            };
-            let response = self.send(request).await?;
-            if let SupportedMessage::BrowseResponse(response) = response {
-                session_debug!(self, "browse, success");
-                process_service_result(&response.response_header)?;
-                Ok(response.results)
-            } else {
-                session_error!(self, "browse failed {:?}", response);
-                Err(process_unexpected_response(response))
-            }
+            )
+            .await
+            .map(Some)
+        }
+    }
+
+    /// Sends a single [`BrowseRequest`] for the nodes.
+    async fn browse_batch(
+        &self,
+        nodes_to_browse: &[BrowseDescription],
+    ) -> Result<Option<Vec<BrowseResult>>, ServiceError> {
+        let request = BrowseRequest {
+            request_header: self.make_request_header(),
+            view: ViewDescription {
+                view_id: NodeId::null(),
+                timestamp: DateTime::null(),
+                view_version: 0,
+            },
+            requested_max_references_per_node: 1000,
+            nodes_to_browse: Some(nodes_to_browse.to_vec()),
+        };
+        let response = self.send(request).await?;
+        if let SupportedMessage::BrowseResponse(response) = response {
+            session_debug!(self, "browse, success");
+            process_service_result(&response.response_header)?;
+            Ok(response.results)
+        } else {
+            session_error!(self, "browse failed {:?}", response);
+            Err(process_unexpected_response(response))
         }
     }
 
@@ -72,15 +99,15 @@ impl Session {
     ///
     /// * `Ok(Option<Vec<BrowseResult>)` - A list [`BrowseResult`] corresponding to each node to browse. A browse result
     ///                                    may contain a continuation point, for use with `browse_next()`.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn browse_next(
         &self,
         release_continuation_points: bool,
         continuation_points: &[ByteString],
-    ) -> Result<Option<Vec<BrowseResult>>, StatusCode> {
+    ) -> Result<Option<Vec<BrowseResult>>, ServiceError> {
         if continuation_points.is_empty() {
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = BrowseNextRequest {
                 request_header: self.make_request_header(),
@@ -115,18 +142,18 @@ impl Session {
     /// * `Ok(Vec<BrowsePathResult>>)` - List of [`BrowsePathResult`] for the list of browse
     ///                       paths. The size and order of the list matches the size and order of the `browse_paths`
     ///                       parameter.
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn translate_browse_paths_to_node_ids(
         &self,
         browse_paths: &[BrowsePath],
-    ) -> Result<Vec<BrowsePathResult>, StatusCode> {
+    ) -> Result<Vec<BrowsePathResult>, ServiceError> {
         if browse_paths.is_empty() {
             session_error!(
                 self,
                 "translate_browse_paths_to_node_ids, was not supplied with any browse paths"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = TranslateBrowsePathsToNodeIdsRequest {
                 request_header: self.make_request_header(),
@@ -162,18 +189,18 @@ impl Session {
     ///
     /// * `Ok(Vec<NodeId>)` - A list of [`NodeId`] corresponding to size and order of the input. The
     ///                       server may return an alias for the input `NodeId`
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
     pub async fn register_nodes(
         &self,
         nodes_to_register: &[NodeId],
-    ) -> Result<Vec<NodeId>, StatusCode> {
+    ) -> Result<Vec<NodeId>, ServiceError> {
         if nodes_to_register.is_empty() {
             session_error!(
                 self,
                 "register_nodes, was not supplied with any nodes to register"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = RegisterNodesRequest {
                 request_header: self.make_request_header(),
@@ -204,15 +231,18 @@ impl Session {
     /// # Returns
     ///
     /// * `Ok(())` - Request succeeded, server ignores invalid nodes
-    /// * `Err(StatusCode)` - Request failed, [Status code](StatusCode) is the reason for failure.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
     ///
-    pub async fn unregister_nodes(&self, nodes_to_unregister: &[NodeId]) -> Result<(), StatusCode> {
+    pub async fn unregister_nodes(
+        &self,
+        nodes_to_unregister: &[NodeId],
+    ) -> Result<(), ServiceError> {
         if nodes_to_unregister.is_empty() {
             session_error!(
                 self,
                 "unregister_nodes, was not supplied with any nodes to unregister"
             );
-            Err(StatusCode::BadNothingToDo)
+            Err(StatusCode::BadNothingToDo.into())
         } else {
             let request = UnregisterNodesRequest {
                 request_header: self.make_request_header(),
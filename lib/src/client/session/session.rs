@@ -1,4 +1,5 @@
 use std::{
+    future::Future,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
@@ -13,13 +14,21 @@ use crate::{
         retry::SessionRetryPolicy, transport::tcp::TransportConfiguration, AsyncSecureChannel,
         ClientConfig,
     },
-    core::{handle::AtomicHandle, supported_message::SupportedMessage},
+    core::{
+        comms::capture::MessageCapture, handle::AtomicHandle, supported_message::SupportedMessage,
+    },
     crypto::CertificateStore,
     sync::{Mutex, RwLock},
-    types::{ApplicationDescription, DecodingOptions, NodeId, RequestHeader, StatusCode, UAString},
+    types::{
+        ApplicationDescription, CloseSessionRequest, DecodingOptions, NodeId, RequestHeader,
+        StatusCode, TimestampsToReturn, UAString, VariableId, Variant,
+    },
 };
 
-use super::{services::subscriptions::state::SubscriptionState, SessionEventLoop, SessionInfo};
+use super::{
+    requires_reconnect, services::subscriptions::state::SubscriptionState, session_debug,
+    session_error, session_warn, ServiceError, SessionEventLoop, SessionInfo,
+};
 
 #[derive(Clone, Copy)]
 pub enum SessionState {
@@ -52,14 +61,26 @@ pub struct Session {
     pub(super) request_timeout: Duration,
     pub(super) publish_timeout: Duration,
     pub(super) recreate_monitored_items_chunk: usize,
+    /// Most monitored items the server accepts per call, read from its operation limits, or 0
+    /// until it has been read.
+    pub(super) monitored_items_per_call: AtomicU32,
+    /// Most nodes the server accepts per read, write and browse call, read from its operation
+    /// limits, or 0 until they have been read.
+    pub(super) max_nodes_per_read: AtomicU32,
+    pub(super) max_nodes_per_write: AtomicU32,
+    pub(super) max_nodes_per_browse: AtomicU32,
     pub(super) session_timeout: f64,
+    pub(super) delete_subscriptions_on_close: bool,
+    pub(super) close_timeout: Duration,
     pub(super) max_inflight_publish: usize,
     pub subscription_state: Mutex<SubscriptionState>,
     pub(super) monitored_item_handle: AtomicHandle,
     pub(super) trigger_publish_tx: tokio::sync::watch::Sender<Instant>,
+    pub(super) reconnect_tx: tokio::sync::watch::Sender<StatusCode>,
 }
 
 impl Session {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         certificate_store: Arc<RwLock<CertificateStore>>,
         session_info: SessionInfo,
@@ -68,11 +89,13 @@ impl Session {
         session_retry_policy: SessionRetryPolicy,
         decoding_options: DecodingOptions,
         config: &ClientConfig,
+        message_capture: Option<MessageCapture>,
     ) -> (Arc<Self>, SessionEventLoop) {
         let auth_token: Arc<ArcSwap<NodeId>> = Default::default();
         let (state_watch_tx, state_watch_rx) =
             tokio::sync::watch::channel(SessionState::Disconnected);
         let (trigger_publish_tx, trigger_publish_rx) = tokio::sync::watch::channel(Instant::now());
+        let (reconnect_tx, reconnect_rx) = tokio::sync::watch::channel(StatusCode::Good);
 
         let session = Arc::new(Session {
             channel: AsyncSecureChannel::new(
@@ -83,12 +106,14 @@ impl Session {
                 config.performance.ignore_clock_skew,
                 auth_token.clone(),
                 TransportConfiguration {
-                    max_pending_incoming: 5,
+                    max_pending_incoming: config.decoding_options.max_chunk_count,
                     max_inflight: config.performance.max_inflight_messages,
                     send_buffer_size: config.decoding_options.max_chunk_size,
                     recv_buffer_size: config.decoding_options.max_incoming_chunk_size,
                     max_message_size: config.decoding_options.max_message_size,
                     max_chunk_count: config.decoding_options.max_chunk_count,
+                    fallback_endpoint_urls: config.fallback_endpoint_urls.clone(),
+                    capture: message_capture,
                 },
             ),
             internal_session_id: AtomicU32::new(NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed)),
@@ -102,12 +127,19 @@ impl Session {
             certificate_store,
             request_timeout: config.request_timeout,
             session_timeout: config.session_timeout as f64,
+            delete_subscriptions_on_close: config.delete_subscriptions_on_close,
+            close_timeout: config.close_timeout,
             publish_timeout: config.publish_timeout,
             max_inflight_publish: config.max_inflight_publish,
             recreate_monitored_items_chunk: config.performance.recreate_monitored_items_chunk,
+            monitored_items_per_call: AtomicU32::new(0),
+            max_nodes_per_read: AtomicU32::new(0),
+            max_nodes_per_write: AtomicU32::new(0),
+            max_nodes_per_browse: AtomicU32::new(0),
             subscription_state: Mutex::new(SubscriptionState::new(config.min_publish_interval)),
             monitored_item_handle: AtomicHandle::new(1000),
             trigger_publish_tx,
+            reconnect_tx,
         });
 
         (
@@ -116,7 +148,9 @@ impl Session {
                 session,
                 session_retry_policy,
                 trigger_publish_rx,
+                reconnect_rx,
                 config.keep_alive_interval,
+                config.close_timeout,
             ),
         )
     }
@@ -128,7 +162,159 @@ impl Session {
         &self,
         request: impl Into<SupportedMessage>,
     ) -> Result<SupportedMessage, StatusCode> {
-        self.channel.send(request, self.request_timeout).await
+        let response = self.channel.send(request, self.request_timeout).await?;
+        self.check_service_fault(&response);
+        Ok(response)
+    }
+
+    /// Send a request to the server and wait for the response, using the default configured
+    /// timeout. This may be used to call services that have no dedicated function on the session.
+    ///
+    /// A `ServiceFault` or a response with a bad service result is returned as a [`ServiceError`]
+    /// holding the response header, so the diagnostics supplied by the server can be inspected.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SupportedMessage)` - The response to the request.
+    /// * `Err(ServiceError)` - Request failed, the error holds the reason for failure.
+    ///
+    pub async fn send_request(
+        &self,
+        request: impl Into<SupportedMessage>,
+    ) -> Result<SupportedMessage, ServiceError> {
+        let response = self.send(request).await?;
+        if matches!(response, SupportedMessage::ServiceFault(_))
+            || (response.is_response() && response.response_header().service_result.is_bad())
+        {
+            Err(ServiceError::from_response_header(
+                response.response_header().clone(),
+            ))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Inspect a response for a `ServiceFault` telling the client that its session or secure
+    /// channel is no longer valid. If it does, the session is marked for reconnection.
+    pub(super) fn check_service_fault(&self, response: &SupportedMessage) {
+        if let SupportedMessage::ServiceFault(fault) = response {
+            let status = fault.response_header.service_result;
+            if requires_reconnect(status) {
+                session_warn!(
+                    self,
+                    "Server returned {}, the session will reconnect",
+                    status
+                );
+                let _ = self.reconnect_tx.send(status);
+            }
+        }
+    }
+
+    /// Sends the items of a service call in batches of no more than the server's operation limit
+    /// for the service, and merges the results of the batches in order. The limit is read from
+    /// the server the first time that it is needed.
+    ///
+    /// If the items fit in one call, its failure fails the call. Otherwise a batch that fails
+    /// gives each of its items the result that `failed` makes from the reason, and the call
+    /// only fails if every batch fails.
+    pub(super) async fn send_in_batches<'a, T, R, F, Fut>(
+        &self,
+        service: &str,
+        items: &'a [T],
+        limit: (&AtomicU32, VariableId),
+        send_batch: F,
+        failed: impl Fn(StatusCode) -> R,
+    ) -> Result<Vec<R>, ServiceError>
+    where
+        F: Fn(&'a [T]) -> Fut,
+        Fut: Future<Output = Result<Vec<R>, ServiceError>>,
+    {
+        let nodes_per_call = if items.len() > 1 {
+            self.nodes_per_call(limit.0, limit.1).await
+        } else {
+            1
+        };
+        if items.len() <= nodes_per_call {
+            return send_batch(items).await;
+        }
+
+        session_debug!(
+            self,
+            "{}, splitting {} items into batches of {}",
+            service,
+            items.len(),
+            nodes_per_call
+        );
+        let mut results = Vec::with_capacity(items.len());
+        let mut first_error = None;
+        let mut succeeded = false;
+        for batch in items.chunks(nodes_per_call) {
+            let batch_results = match send_batch(batch).await {
+                Ok(batch_results) if batch_results.len() == batch.len() => Ok(batch_results),
+                Ok(batch_results) => {
+                    session_error!(
+                        self,
+                        "{}, expected {} results, got {}",
+                        service,
+                        batch.len(),
+                        batch_results.len()
+                    );
+                    Err(StatusCode::BadUnexpectedError.into())
+                }
+                Err(err) => Err(err),
+            };
+            match batch_results {
+                Ok(batch_results) => {
+                    succeeded = true;
+                    results.extend(batch_results);
+                }
+                Err(err) => {
+                    session_warn!(
+                        self,
+                        "{}, a batch of {} items failed with {}",
+                        service,
+                        batch.len(),
+                        err
+                    );
+                    results.extend(batch.iter().map(|_| failed(err.status())));
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) if !succeeded => Err(err),
+            _ => Ok(results),
+        }
+    }
+
+    /// Returns the most nodes to send to the server in one call of a service, i.e. its operation
+    /// limit for the service, or `usize::MAX` if the server has no limit or it cannot be read.
+    async fn nodes_per_call(&self, cached: &AtomicU32, limit_id: VariableId) -> usize {
+        let nodes_per_call = cached.load(Ordering::Relaxed);
+        if nodes_per_call > 0 {
+            return nodes_per_call as usize;
+        }
+        let node_id: NodeId = limit_id.into();
+        let limit = match self
+            .read_batch(&[node_id.into()], TimestampsToReturn::Neither, 0.0)
+            .await
+        {
+            Ok(mut values) => match values.pop().and_then(|v| v.value) {
+                Some(Variant::UInt32(limit)) if limit > 0 => limit,
+                _ => u32::MAX,
+            },
+            Err(status_code) => {
+                session_warn!(
+                    self,
+                    "Cannot read the operation limit {:?} of the server, {}",
+                    limit_id,
+                    status_code
+                );
+                return usize::MAX;
+            }
+        };
+        cached.store(limit, Ordering::Relaxed);
+        limit as usize
     }
 
     /// Create a request header with the default timeout.
@@ -136,6 +322,13 @@ impl Session {
         self.channel.make_request_header(self.request_timeout)
     }
 
+    /// Create a request header for a request sent with [`Session::send_request`] that times out
+    /// after `timeout` instead of the default request timeout. The timeout is also sent to the
+    /// server as the timeout hint of the request.
+    pub fn request_header(&self, timeout: Duration) -> RequestHeader {
+        self.channel.make_request_header(timeout)
+    }
+
     /// Reset the session after a hard disconnect, clearing the session ID and incrementing the internal
     /// session counter.
     pub(crate) fn reset(&self) {
@@ -177,12 +370,40 @@ impl Session {
     }
 
     /// Disconnect from the server and wait until disconnected.
+    ///
+    /// The secure channel is closed even if closing the session fails, in which case the
+    /// error from closing the session is returned.
     pub async fn disconnect(&self) -> Result<(), StatusCode> {
-        self.close_session().await?;
+        let res = self.close_session().await;
         self.channel.close_channel().await;
 
         self.wait_for_state(false).await;
 
-        Ok(())
+        res
+    }
+}
+
+impl Drop for Session {
+    /// Make a best-effort attempt to close the session and the secure channel on the server
+    /// if the session is dropped while still connected, so short-lived programs that never
+    /// call [`Session::disconnect`] do not leave sessions behind on the server.
+    fn drop(&mut self) {
+        if !matches!(*self.state_watch_rx.borrow(), SessionState::Connected) {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            session_warn!(
+                self,
+                "Session dropped outside a tokio runtime, it is not closed on the server"
+            );
+            return;
+        };
+        let request = CloseSessionRequest {
+            delete_subscriptions: self.delete_subscriptions_on_close,
+            request_header: self.channel.make_request_header(self.close_timeout),
+        };
+        if let Some(close) = self.channel.close_detached(request, self.close_timeout) {
+            handle.spawn(close);
+        }
     }
 }
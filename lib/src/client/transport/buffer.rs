@@ -3,6 +3,7 @@ use std::{
     io::{BufRead, Cursor},
 };
 
+use bytes::BytesMut;
 use tokio::io::AsyncWriteExt;
 
 use crate::{
@@ -13,6 +14,9 @@ use crate::{
     types::StatusCode,
 };
 
+/// The number of buffers of sent chunks that are kept for the chunks of later messages
+const MAX_SPARE_CHUNKS: usize = 4;
+
 #[derive(Copy, Clone, Debug)]
 enum SendBufferState {
     Reading(usize),
@@ -24,6 +28,12 @@ pub struct SendBuffer {
     buffer: Cursor<Vec<u8>>,
     /// Queued chunks
     chunks: VecDeque<MessageChunk>,
+    /// Buffers of chunks that were sent, which queued chunks are assembled in
+    spare_chunks: Vec<BytesMut>,
+    /// Scratch buffer for the headers of a chunk
+    header_buffer: Vec<u8>,
+    /// Scratch buffer that a message is encoded into before it is split into chunks
+    message_buffer: Vec<u8>,
     /// The last request id
     last_request_id: u32,
     /// Last sent sequence number
@@ -48,6 +58,9 @@ impl SendBuffer {
         Self {
             buffer: Cursor::new(vec![0u8; buffer_size + 1024]),
             chunks: VecDeque::with_capacity(max_chunk_count),
+            spare_chunks: Vec::with_capacity(MAX_SPARE_CHUNKS),
+            header_buffer: Vec::with_capacity(1024),
+            message_buffer: Vec::with_capacity(buffer_size),
             last_request_id: 1000,
             last_sent_sequence_number: 0,
             max_message_size,
@@ -69,10 +82,21 @@ impl SendBuffer {
         trace!("Sending chunk {:?}", next_chunk);
         let size = secure_channel.apply_security(&next_chunk, self.buffer.get_mut())?;
         self.state = SendBufferState::Reading(size);
+        if self.spare_chunks.len() < MAX_SPARE_CHUNKS {
+            self.spare_chunks.push(next_chunk.data);
+        }
 
         Ok(())
     }
 
+    /// Returns the chunk that was last encoded, while it is still to be sent
+    pub fn encoded_chunk(&self) -> &[u8] {
+        match self.state {
+            SendBufferState::Reading(size) => &self.buffer.get_ref()[..size],
+            SendBufferState::Writing => &[],
+        }
+    }
+
     pub fn write(
         &mut self,
         request_id: u32,
@@ -87,28 +111,47 @@ impl SendBuffer {
         }
 
         // Turn message to chunk(s)
-        let chunks = Chunker::encode(
-            self.last_sent_sequence_number + 1,
-            request_id,
+        let message_type = Chunker::encode_message(
             self.max_message_size,
-            self.send_buffer_size,
             secure_channel,
             &message,
+            &mut self.message_buffer,
+        )?;
+        let bodies = Chunker::chunk_bodies(
+            message_type,
+            self.send_buffer_size,
+            secure_channel,
+            &self.message_buffer,
         )?;
 
-        if self.max_chunk_count > 0 && chunks.len() > self.max_chunk_count {
+        if self.max_chunk_count > 0 && bodies.len() > self.max_chunk_count {
             error!(
                 "Cannot write message since {} chunks exceeds {} chunk limit",
-                chunks.len(),
+                bodies.len(),
                 self.max_chunk_count
             );
-            Err(StatusCode::BadCommunicationError)
+            Err(Chunker::too_large_status(secure_channel))
         } else {
             // Sequence number monotonically increases per chunk
-            self.last_sent_sequence_number += chunks.len() as u32;
-
-            // Send chunks
-            self.chunks.extend(chunks.into_iter());
+            let sequence_number = self.last_sent_sequence_number + 1;
+            self.last_sent_sequence_number += bodies.len() as u32;
+
+            // Queue chunks
+            let last = bodies.len().saturating_sub(1);
+            for (i, body) in bodies.into_iter().enumerate() {
+                MessageChunk::encode_header(
+                    sequence_number + i as u32,
+                    request_id,
+                    message_type,
+                    Chunker::is_final(i, last),
+                    secure_channel,
+                    body.len(),
+                    &mut self.header_buffer,
+                )?;
+                let buffer = self.spare_chunks.pop().unwrap_or_default();
+                self.chunks
+                    .push_back(MessageChunk::assemble(&self.header_buffer, body, buffer));
+            }
             Ok(request_id)
         }
     }
@@ -306,7 +349,7 @@ mod tests {
         let (mut buffer, channel) = get_buffer_and_channel();
 
         let err = buffer.write(1, message.into(), &channel).unwrap_err();
-        assert_eq!(err, StatusCode::BadCommunicationError);
+        assert_eq!(err, StatusCode::BadRequestTooLarge);
     }
 
     #[tokio::test]
@@ -1,4 +1,4 @@
-use std::{str::FromStr, sync::Arc, time::Duration};
+use std::{future::Future, str::FromStr, sync::Arc, time::Duration};
 
 use crate::{
     client::{session::SessionInfo, transport::core::TransportPollResult},
@@ -76,11 +76,18 @@ impl AsyncSecureChannel {
         }
     }
 
+    /// Sends a request and waits for its response. The request times out with `BadTimeout` after
+    /// the timeout hint in its request header, or after `timeout` if the request has no hint.
     pub async fn send(
         &self,
         request: impl Into<SupportedMessage>,
         timeout: Duration,
     ) -> Result<SupportedMessage, StatusCode> {
+        let request = request.into();
+        let timeout = match request.request_header().timeout_hint {
+            0 => timeout,
+            timeout_hint => Duration::from_millis(timeout_hint as u64),
+        };
         let sender = self.request_send.load().as_deref().cloned();
         let Some(send) = sender else {
             return Err(StatusCode::BadNotConnected);
@@ -214,39 +221,56 @@ impl AsyncSecureChannel {
                 self.session_info.endpoint.security_policy_uri.as_ref()
             );
             return Err(StatusCode::BadSecurityPolicyRejected);
-        } else {
-            let (cert, key) = {
-                let certificate_store = trace_write_lock!(self.certificate_store);
-                certificate_store.read_own_cert_and_pkey_optional()
-            };
-
+        }
+        if !self.session_info.pinned_certificates.is_empty() {
+            // A pinned server is checked before the channel is opened, so nothing is sent to a
+            // server that does not hold the pinned certificate
+            if security_policy == SecurityPolicy::None {
+                error!("connect, certificates cannot be pinned without a security policy");
+                return Err(StatusCode::BadSecurityPolicyRejected);
+            }
+            if !self
+                .session_info
+                .is_pinned_certificate(&self.session_info.endpoint.server_certificate)
             {
-                let mut secure_channel = trace_write_lock!(self.secure_channel);
-                secure_channel.set_private_key(key);
-                secure_channel.set_cert(cert);
-                secure_channel.set_security_policy(security_policy);
-                secure_channel.set_security_mode(self.session_info.endpoint.security_mode);
-                let _ = secure_channel.set_remote_cert_from_byte_string(
-                    &self.session_info.endpoint.server_certificate,
-                );
-                info!("Security policy = {:?}", security_policy);
-                info!(
-                    "Security mode = {:?}",
-                    self.session_info.endpoint.security_mode
+                error!(
+                    "connect, endpoint certificate does not match any of the pinned certificates"
                 );
+                return Err(StatusCode::BadCertificateInvalid);
             }
+        }
 
-            let (send, recv) = tokio::sync::mpsc::channel(self.transport_config.max_inflight);
-            let transport = TcpTransport::connect(
-                self.secure_channel.clone(),
-                recv,
-                self.transport_config.clone(),
-                endpoint_url.as_ref(),
-            )
-            .await?;
+        let (cert, key) = {
+            let certificate_store = trace_write_lock!(self.certificate_store);
+            certificate_store.read_own_cert_and_pkey_optional()
+        };
 
-            Ok((transport, send))
+        {
+            let mut secure_channel = trace_write_lock!(self.secure_channel);
+            secure_channel.set_private_key(key);
+            secure_channel.set_cert(cert);
+            secure_channel.set_security_policy(security_policy);
+            secure_channel.set_security_mode(self.session_info.endpoint.security_mode);
+            let _ = secure_channel
+                .set_remote_cert_from_byte_string(&self.session_info.endpoint.server_certificate);
+            info!("Security policy = {:?}", security_policy);
+            info!(
+                "Security mode = {:?}",
+                self.session_info.endpoint.security_mode
+            );
         }
+
+        let (send, recv) = tokio::sync::mpsc::channel(self.transport_config.max_inflight);
+        let transport = TcpTransport::connect(
+            self.secure_channel.clone(),
+            self.certificate_store.clone(),
+            recv,
+            self.transport_config.clone(),
+            endpoint_url.as_ref(),
+        )
+        .await?;
+
+        Ok((transport, send))
     }
 
     /// Close the secure channel, optionally wait for the channel to close.
@@ -266,4 +290,32 @@ impl AsyncSecureChannel {
             }
         }
     }
+
+    /// Create a future that sends `request` and waits for the response, then closes the secure
+    /// channel, each step giving up after `timeout`. The future does not borrow the channel, so
+    /// it can run after the channel is dropped. Returns `None` if the channel is not open.
+    pub(crate) fn close_detached(
+        &self,
+        request: impl Into<SupportedMessage>,
+        timeout: Duration,
+    ) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let sender = self.request_send.load().as_deref().cloned()?;
+        let request = Request::new(request, sender.clone(), timeout);
+        let close = Request::new(
+            CloseSecureChannelRequest {
+                request_header: self.state.make_request_header(timeout),
+            },
+            sender,
+            timeout,
+        );
+
+        Some(async move {
+            if let Err(e) = request.send().await {
+                warn!("Request sent before closing the channel failed: {e}");
+            }
+            if let Err(e) = close.send_no_response().await {
+                error!("Failed to send disconnect message: {e}");
+            }
+        })
+    }
 }
@@ -2,13 +2,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
+use bytes::BytesMut;
 use futures::future::Either;
 use parking_lot::RwLock;
 
 use crate::core::comms::message_chunk::MessageIsFinalType;
 use crate::core::comms::{
-    chunker::Chunker, message_chunk::MessageChunk, message_chunk_info::ChunkInfo,
-    secure_channel::SecureChannel, tcp_codec::Message,
+    capture::{Direction, MessageCapture},
+    chunker::Chunker,
+    message_chunk::MessageChunk,
+    message_chunk_info::ChunkInfo,
+    secure_channel::SecureChannel,
+    tcp_codec::Message,
 };
 use crate::core::supported_message::SupportedMessage;
 use crate::types::StatusCode;
@@ -18,7 +23,7 @@ use super::buffer::SendBuffer;
 #[derive(Debug)]
 struct MessageChunkWithChunkInfo {
     header: ChunkInfo,
-    data_with_header: Vec<u8>,
+    data_with_header: BytesMut,
 }
 
 pub(crate) struct MessageState {
@@ -40,6 +45,8 @@ pub(super) struct TransportState {
     max_pending_incoming: usize,
     /// Last decoded sequence number
     last_received_sequence_number: u32,
+    /// Capture of the connection's traffic, if the client captures to a file
+    pub(super) capture: Option<MessageCapture>,
 }
 
 #[derive(Debug)]
@@ -62,6 +69,7 @@ impl TransportState {
         outgoing_recv: tokio::sync::mpsc::Receiver<OutgoingMessage>,
         max_pending_incoming: usize,
         max_inflight: usize,
+        capture: Option<MessageCapture>,
     ) -> Self {
         Self {
             secure_channel,
@@ -70,6 +78,7 @@ impl TransportState {
             max_inflight,
             max_pending_incoming,
             last_received_sequence_number: 0,
+            capture,
         }
     }
 
@@ -113,6 +122,13 @@ impl TransportState {
         }
     }
 
+    /// Fails a message that could not be sent, without waiting for its response.
+    pub fn fail_message(&mut self, request_id: u32, status: StatusCode) {
+        if let Some(message_state) = self.message_states.remove(&request_id) {
+            let _ = message_state.callback.send(Err(status));
+        }
+    }
+
     /// Store incoming messages in the message state.
     pub fn handle_incoming_message(&mut self, message: Message) -> Result<(), StatusCode> {
         let status = match message {
@@ -120,7 +136,12 @@ impl TransportState {
                 debug!("Reader got an unexpected ack {:?}", ack);
                 StatusCode::BadUnexpectedError
             }
-            Message::Chunk(chunk) => self.process_chunk(chunk).err().unwrap_or(StatusCode::Good),
+            Message::Chunk(chunk) => {
+                if let Some(ref capture) = self.capture {
+                    capture.chunks(Direction::Received, &chunk.data);
+                }
+                self.process_chunk(chunk).err().unwrap_or(StatusCode::Good)
+            }
             Message::Error(error) => {
                 if let Some(status_code) = StatusCode::from_u32(error.error) {
                     status_code
@@ -167,7 +188,7 @@ impl TransportState {
 
     fn process_chunk(&mut self, chunk: MessageChunk) -> Result<(), StatusCode> {
         let mut secure_channel = trace_write_lock!(self.secure_channel);
-        let chunk = secure_channel.verify_and_remove_security(&chunk.data)?;
+        let chunk = secure_channel.remove_security(chunk)?;
 
         let chunk_info = chunk.chunk_info(&secure_channel)?;
         drop(secure_channel);
@@ -191,7 +212,8 @@ impl TransportState {
                     data_with_header: chunk.data,
                 });
                 let chunks_len: usize = message_state.chunks.len();
-                if self.max_pending_incoming > 0 && chunks_len > self.max_pending_incoming {
+                // The final chunk is still to come
+                if self.max_pending_incoming > 0 && chunks_len >= self.max_pending_incoming {
                     error!(
                         "too many pending incoming messages {} > {}",
                         chunks_len, self.max_pending_incoming
@@ -199,7 +221,7 @@ impl TransportState {
                     let message_state = self.message_states.remove(&req_id).unwrap();
                     let _ = message_state
                         .callback
-                        .send(Err(StatusCode::BadEncodingLimitsExceeded));
+                        .send(Err(StatusCode::BadResponseTooLarge));
                 }
             }
             MessageIsFinalType::FinalError => {
@@ -217,6 +239,9 @@ impl TransportState {
                 let message_state = self.message_states.remove(&req_id).unwrap();
                 let in_chunks = Self::merge_chunks(message_state.chunks)?;
                 let message = self.turn_received_chunks_into_message(&in_chunks)?;
+                if let Some(ref capture) = self.capture {
+                    capture.message(Direction::Received, req_id, &message);
+                }
 
                 let _ = message_state.callback.send(Ok(message));
             }
@@ -0,0 +1,219 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Connects to https endpoints. There is no secure channel over HTTPS, so a bridge plays the part
+//! of the server for the transport, answering its Hello and OpenSecureChannel itself, and sends
+//! every other request in an HTTPS POST, turning the response back into chunks. Requests are sent
+//! on a pool of connections so a publish that the server holds on to doesn't hold up the others.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use tokio::{
+    io::{self, DuplexStream},
+    net::TcpStream,
+    sync::mpsc::unbounded_channel,
+};
+use tokio_rustls::client::TlsStream;
+
+use super::tls::TlsClient;
+use crate::core::comms::{
+    https::*,
+    secure_channel::{Role, SecureChannel},
+    tcp_codec::Message,
+    tcp_types::{AcknowledgeMessage, MessageHeader, MessageType},
+};
+use crate::core::supported_message::SupportedMessage;
+use crate::crypto::CertificateStore;
+use crate::sync::*;
+use crate::types::{
+    encoding::{BinaryEncoder, DecodingOptions},
+    service_types::{
+        ChannelSecurityToken, MessageSecurityMode, OpenSecureChannelRequest,
+        OpenSecureChannelResponse, ServiceFault,
+    },
+    status_code::StatusCode,
+    ByteString, DateTime, ResponseHeader,
+};
+
+/// Sends requests to the server of an https endpoint.
+struct HttpsConnections {
+    tls_client: TlsClient,
+    /// Address of the server
+    address: SocketAddr,
+    /// Open connections that aren't carrying a request
+    idle: Mutex<Vec<TlsStream<TcpStream>>>,
+    decoding_options: DecodingOptions,
+}
+
+impl HttpsConnections {
+    /// Sends the request in a POST on an idle connection, or a new one if there is none, and
+    /// returns the response.
+    async fn call(&self, request: &SupportedMessage) -> Result<SupportedMessage, StatusCode> {
+        let body = encode_https_body(request)?;
+        let idle = trace_lock!(self.idle).pop();
+        let response = match idle {
+            Some(stream) => match self.post(stream, &body).await {
+                // The server may have closed an idle connection, so try again on a new one
+                Err(StatusCode::BadConnectionClosed) => {
+                    self.post(self.connect().await?, &body).await
+                }
+                response => response,
+            },
+            None => self.post(self.connect().await?, &body).await,
+        }?;
+        decode_https_body(&response, &self.decoding_options)
+    }
+
+    async fn connect(&self) -> Result<TlsStream<TcpStream>, StatusCode> {
+        let socket = TcpStream::connect(self.address).await.map_err(|err| {
+            error!("Could not connect to host {}, {:?}", self.address, err);
+            StatusCode::BadCommunicationError
+        })?;
+        self.tls_client.connect(socket).await
+    }
+
+    async fn post(
+        &self,
+        mut stream: TlsStream<TcpStream>,
+        body: &[u8],
+    ) -> Result<Vec<u8>, StatusCode> {
+        let response = https_post(
+            &mut stream,
+            &self.tls_client.host,
+            &self.tls_client.path,
+            body,
+            self.decoding_options.max_message_size,
+        )
+        .await?;
+        if response.keep_alive {
+            trace_lock!(self.idle).push(stream);
+        }
+        Ok(response.body)
+    }
+}
+
+/// Opens a TLS session over the socket and returns the stream the transport runs on. The session
+/// is the first connection of the pool that requests are sent on.
+pub(crate) async fn connect(
+    socket: TcpStream,
+    endpoint_url: &str,
+    certificate_store: Arc<RwLock<CertificateStore>>,
+    decoding_options: DecodingOptions,
+    max_chunk_size: usize,
+) -> Result<DuplexStream, StatusCode> {
+    let address = socket.peer_addr().map_err(|err| {
+        error!("Socket has no peer address, {:?}", err);
+        StatusCode::BadCommunicationError
+    })?;
+    let tls_client = TlsClient::new(endpoint_url, certificate_store)?;
+    let stream = tls_client.connect(socket).await?;
+    let connections = Arc::new(HttpsConnections {
+        tls_client,
+        address,
+        idle: Mutex::new(vec![stream]),
+        decoding_options,
+    });
+
+    let (local, remote) = io::duplex(max_chunk_size);
+    tokio::spawn(async move {
+        if let Err(status_code) = https_bridge(remote, connections).await {
+            debug!("HTTPS bridge closed with status {}", status_code);
+        }
+    });
+    Ok(local)
+}
+
+/// Answers the Hello and OpenSecureChannel of the transport, and sends its other requests to
+/// the server, until the transport closes the stream or the channel.
+async fn https_bridge(
+    stream: DuplexStream,
+    connections: Arc<HttpsConnections>,
+) -> Result<(), StatusCode> {
+    let mut stream = ChunkStream::new(stream, Role::Server, connections.decoding_options.clone());
+    let hello = match stream.read_raw().await? {
+        Some(Message::Hello(hello)) => hello,
+        other => {
+            error!("Expected a hello from the transport, got {:?}", other);
+            return Err(StatusCode::BadCommunicationError);
+        }
+    };
+    // There's no server to revise the sizes, so the transport gets what it asked for
+    let mut acknowledge = AcknowledgeMessage {
+        message_header: MessageHeader::new(MessageType::Acknowledge),
+        protocol_version: 0,
+        receive_buffer_size: hello.send_buffer_size,
+        send_buffer_size: hello.receive_buffer_size,
+        max_message_size: hello.max_message_size,
+        max_chunk_count: hello.max_chunk_count,
+    };
+    acknowledge.message_header.message_size = acknowledge.byte_len() as u32;
+    stream.write_raw(&acknowledge.encode_to_vec()).await?;
+    stream.set_max_chunk_size(hello.receive_buffer_size as usize);
+
+    let (response_tx, mut response_rx) = unbounded_channel();
+    loop {
+        let (request_id, response) = tokio::select! {
+            message = stream.read_message() => {
+                let Some((request_id, request)) = message? else {
+                    return Ok(());
+                };
+                match request {
+                    SupportedMessage::OpenSecureChannelRequest(request) => {
+                        (request_id, open_secure_channel(stream.secure_channel_mut(), &request))
+                    }
+                    SupportedMessage::CloseSecureChannelRequest(_) => {
+                        debug!("Transport closed the channel so the HTTPS bridge closes");
+                        return Ok(());
+                    }
+                    request => {
+                        let connections = connections.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let response = connections.call(&request).await.unwrap_or_else(
+                                |status_code| {
+                                    error!("HTTPS request failed, {}", status_code);
+                                    ServiceFault::new(request.request_header(), status_code).into()
+                                },
+                            );
+                            let _ = response_tx.send((request_id, response));
+                        });
+                        continue;
+                    }
+                }
+            }
+            Some(response) = response_rx.recv() => response,
+        };
+        stream.write_message(request_id, &response).await?;
+    }
+}
+
+/// Answers an OpenSecureChannel from the transport. The channel only exists between the transport
+/// and the bridge so its security token is made up here.
+fn open_secure_channel(
+    secure_channel: &mut SecureChannel,
+    request: &OpenSecureChannelRequest,
+) -> SupportedMessage {
+    if request.security_mode != MessageSecurityMode::None {
+        error!(
+            "HTTPS only supports the None security mode, not {:?}",
+            request.security_mode
+        );
+        return ServiceFault::new(&request.request_header, StatusCode::BadSecurityModeRejected)
+            .into();
+    }
+    let security_token = ChannelSecurityToken {
+        channel_id: 1,
+        token_id: secure_channel.token_id() + 1,
+        created_at: DateTime::now(),
+        revised_lifetime: request.requested_lifetime,
+    };
+    secure_channel.set_security_token(security_token.clone());
+    OpenSecureChannelResponse {
+        response_header: ResponseHeader::new_good(&request.request_header),
+        server_protocol_version: 0,
+        security_token,
+        server_nonce: ByteString::null(),
+    }
+    .into()
+}
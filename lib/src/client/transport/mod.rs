@@ -1,8 +1,14 @@
 mod buffer;
 mod channel;
 mod core;
+#[cfg(feature = "https")]
+mod https;
 mod state;
 pub mod tcp;
+#[cfg(any(feature = "websocket", feature = "https"))]
+mod tls;
+#[cfg(feature = "websocket")]
+mod websocket;
 
 pub use channel::{AsyncSecureChannel, SecureChannelEventLoop};
 pub(crate) use core::OutgoingMessage;
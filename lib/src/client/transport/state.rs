@@ -196,7 +196,7 @@ impl SecureChannelState {
             }
             Ok(())
         } else {
-            Err(process_unexpected_response(response))
+            Err(process_unexpected_response(response).into())
         }
     }
 
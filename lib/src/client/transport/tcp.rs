@@ -1,21 +1,37 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
 
 use super::buffer::SendBuffer;
 use super::core::{OutgoingMessage, TransportPollResult, TransportState};
 use crate::core::comms::{
+    capture::{Direction, MessageCapture},
     secure_channel::SecureChannel,
     tcp_codec::{Message, TcpCodec},
-    tcp_types::HelloMessage,
-    url::hostname_port_from_url,
+    tcp_types::{AcknowledgeMessage, HelloMessage},
+    url::{
+        hostname_port_from_url, is_opc_ua_https_url, is_opc_ua_websocket_url, is_valid_opc_ua_url,
+    },
 };
 use crate::core::supported_message::SupportedMessage;
-use crate::types::{encoding::BinaryEncoder, StatusCode};
+use crate::crypto::CertificateStore;
+use crate::types::{
+    encoding::{BinaryEncoder, DecodingOptions},
+    StatusCode,
+};
 use futures::StreamExt;
 use parking_lot::RwLock;
-use tokio::io::{AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio_util::codec::FramedRead;
 
+/// The reading half of the stream the transport runs on, a TCP socket, a WebSocket or an HTTPS
+/// bridge
+type TransportReader = Box<dyn AsyncRead + Send + Unpin>;
+/// The writing half of the stream the transport runs on
+type TransportWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
 #[derive(Debug, Clone, Copy)]
 enum TransportCloseState {
     Open,
@@ -25,8 +41,8 @@ enum TransportCloseState {
 
 pub(crate) struct TcpTransport {
     state: TransportState,
-    read: FramedRead<ReadHalf<TcpStream>, TcpCodec>,
-    write: WriteHalf<TcpStream>,
+    read: FramedRead<TransportReader, TcpCodec>,
+    write: TransportWriter,
     send_buffer: SendBuffer,
     should_close: bool,
     closed: TransportCloseState,
@@ -40,23 +56,47 @@ pub struct TransportConfiguration {
     pub recv_buffer_size: usize,
     pub max_message_size: usize,
     pub max_chunk_count: usize,
+    /// Urls that are tried in order if the endpoint url cannot be connected to
+    pub fallback_endpoint_urls: Vec<String>,
+    /// Capture file that the traffic of the connection is recorded to
+    pub capture: Option<MessageCapture>,
 }
 
+/// Numbers the connections of the client so that captured connections to the same url are
+/// told apart
+static NEXT_CONNECTION_ID: AtomicU32 = AtomicU32::new(1);
+
 impl TcpTransport {
-    /// Attempt to establish a connection to the OPC UA endpoint given by `endpoint_url`.
+    /// Attempt to establish a connection to the OPC UA endpoint given by `endpoint_url`, or failing
+    /// that, to each of the fallback urls of the configuration in turn.
     /// Note that on success, this returns a `TcpTransport`. The caller is responsible for
     /// calling `run` on the returned transport in order to actually send and receive messages.
     pub async fn connect(
         secure_channel: Arc<RwLock<SecureChannel>>,
+        certificate_store: Arc<RwLock<CertificateStore>>,
         outgoing_recv: tokio::sync::mpsc::Receiver<OutgoingMessage>,
         config: TransportConfiguration,
         endpoint_url: &str,
     ) -> Result<Self, StatusCode> {
-        let (framed_read, writer) =
-            match Self::connect_inner(&secure_channel, &config, endpoint_url).await {
-                Ok(k) => k,
-                Err(status) => return Err(status),
+        let mut url = endpoint_url;
+        let mut result =
+            Self::connect_inner(&secure_channel, &certificate_store, &config, url).await;
+        for fallback_endpoint_url in &config.fallback_endpoint_urls {
+            let Err(status) = result else {
+                break;
             };
+            warn!(
+                "Cannot connect to {}, error = {}, trying fallback endpoint {}",
+                url, status, fallback_endpoint_url
+            );
+            url = fallback_endpoint_url;
+            result = Self::connect_inner(&secure_channel, &certificate_store, &config, url).await;
+        }
+        let (framed_read, writer, ack) = result?;
+        let capture = config.capture.as_ref().map(|capture| {
+            let connection_id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+            capture.for_connection(format!("{} ({})", url, connection_id))
+        });
 
         Ok(Self {
             state: TransportState::new(
@@ -64,13 +104,17 @@ impl TcpTransport {
                 outgoing_recv,
                 config.max_pending_incoming,
                 config.max_inflight,
+                capture,
             ),
             read: framed_read,
             write: writer,
+            // Requests are chunked and limited according to what the server accepts
             send_buffer: SendBuffer::new(
-                config.send_buffer_size,
-                config.max_message_size,
-                config.max_chunk_count,
+                config
+                    .send_buffer_size
+                    .min(ack.receive_buffer_size as usize),
+                ack.max_message_size as usize,
+                ack.max_chunk_count as usize,
             ),
             should_close: false,
             closed: TransportCloseState::Open,
@@ -79,15 +123,24 @@ impl TcpTransport {
 
     async fn connect_inner(
         secure_channel: &RwLock<SecureChannel>,
+        certificate_store: &Arc<RwLock<CertificateStore>>,
         config: &TransportConfiguration,
         endpoint_url: &str,
     ) -> Result<
         (
-            FramedRead<ReadHalf<TcpStream>, TcpCodec>,
-            WriteHalf<TcpStream>,
+            FramedRead<TransportReader, TcpCodec>,
+            TransportWriter,
+            AcknowledgeMessage,
         ),
         StatusCode,
     > {
+        if !is_valid_opc_ua_url(endpoint_url) {
+            error!(
+                "Cannot connect to {}, it is not a supported url",
+                endpoint_url
+            );
+            return Err(StatusCode::BadTcpEndpointUrlInvalid);
+        }
         let (host, port) = hostname_port_from_url(
             endpoint_url,
             crate::core::constants::DEFAULT_OPC_UA_SERVER_PORT,
@@ -121,7 +174,33 @@ impl TcpTransport {
             StatusCode::BadCommunicationError
         })?;
 
-        let (reader, mut writer) = tokio::io::split(socket);
+        let (reader, mut writer): (TransportReader, TransportWriter) =
+            if is_opc_ua_websocket_url(endpoint_url) {
+                let stream = Self::connect_websocket(
+                    socket,
+                    endpoint_url,
+                    certificate_store,
+                    usize::max(config.send_buffer_size, config.recv_buffer_size),
+                )
+                .await?;
+                let (reader, writer) = tokio::io::split(stream);
+                (Box::new(reader), Box::new(writer))
+            } else if is_opc_ua_https_url(endpoint_url) {
+                let decoding_options = trace_read_lock!(secure_channel).decoding_options();
+                let stream = Self::connect_https(
+                    socket,
+                    endpoint_url,
+                    certificate_store,
+                    decoding_options,
+                    usize::max(config.send_buffer_size, config.recv_buffer_size),
+                )
+                .await?;
+                let (reader, writer) = tokio::io::split(stream);
+                (Box::new(reader), Box::new(writer))
+            } else {
+                let (reader, writer) = socket.into_split();
+                (Box::new(reader), Box::new(writer))
+            };
 
         let hello = HelloMessage::new(
             endpoint_url,
@@ -142,10 +221,10 @@ impl TcpTransport {
                 error!("Cannot send hello to server, err = {:?}", err);
                 StatusCode::BadCommunicationError
             })?;
-        match framed_read.next().await {
+        let ack = match framed_read.next().await {
             Some(Ok(Message::Acknowledge(ack))) => {
-                // TODO revise our sizes and other things according to the ACK
                 log::trace!("Received acknowledgement: {:?}", ack);
+                ack
             }
             other => {
                 error!(
@@ -154,9 +233,72 @@ impl TcpTransport {
                 );
                 return Err(StatusCode::BadConnectionClosed);
             }
-        }
+        };
+
+        Ok((framed_read, writer, ack))
+    }
+
+    #[cfg(feature = "websocket")]
+    async fn connect_websocket(
+        socket: TcpStream,
+        endpoint_url: &str,
+        certificate_store: &Arc<RwLock<CertificateStore>>,
+        max_chunk_size: usize,
+    ) -> Result<tokio::io::DuplexStream, StatusCode> {
+        super::websocket::connect(
+            socket,
+            endpoint_url,
+            certificate_store.clone(),
+            max_chunk_size,
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "websocket"))]
+    async fn connect_websocket(
+        _socket: TcpStream,
+        endpoint_url: &str,
+        _certificate_store: &Arc<RwLock<CertificateStore>>,
+        _max_chunk_size: usize,
+    ) -> Result<tokio::io::DuplexStream, StatusCode> {
+        error!(
+            "Cannot connect to {} because the websocket feature is disabled",
+            endpoint_url
+        );
+        Err(StatusCode::BadTcpEndpointUrlInvalid)
+    }
 
-        Ok((framed_read, writer))
+    #[cfg(feature = "https")]
+    async fn connect_https(
+        socket: TcpStream,
+        endpoint_url: &str,
+        certificate_store: &Arc<RwLock<CertificateStore>>,
+        decoding_options: DecodingOptions,
+        max_chunk_size: usize,
+    ) -> Result<tokio::io::DuplexStream, StatusCode> {
+        super::https::connect(
+            socket,
+            endpoint_url,
+            certificate_store.clone(),
+            decoding_options,
+            max_chunk_size,
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "https"))]
+    async fn connect_https(
+        _socket: TcpStream,
+        endpoint_url: &str,
+        _certificate_store: &Arc<RwLock<CertificateStore>>,
+        _decoding_options: DecodingOptions,
+        _max_chunk_size: usize,
+    ) -> Result<tokio::io::DuplexStream, StatusCode> {
+        error!(
+            "Cannot connect to {} because the https feature is disabled",
+            endpoint_url
+        );
+        Err(StatusCode::BadTcpEndpointUrlInvalid)
     }
 
     fn handle_incoming_message(
@@ -194,6 +336,9 @@ impl TcpTransport {
             if let Err(e) = self.send_buffer.encode_next_chunk(&secure_channel) {
                 return TransportPollResult::Closed(e);
             }
+            if let Some(ref capture) = self.state.capture {
+                capture.chunks(Direction::Sent, self.send_buffer.encoded_chunk());
+            }
         }
 
         // If there is something in the send buffer, write to the stream.
@@ -228,11 +373,21 @@ impl TcpTransport {
                         self.should_close = true;
                         debug!("Writer is about to send a CloseSecureChannelRequest which means it should close in a moment");
                     }
-                    let secure_channel = trace_read_lock!(self.state.secure_channel);
-                    if let Err(e) = self.send_buffer.write(request_id, outgoing, &secure_channel) {
-                        TransportPollResult::Closed(e)
-                    } else {
-                        TransportPollResult::OutgoingMessage
+                    if let Some(ref capture) = self.state.capture {
+                        capture.message(Direction::Sent, request_id, &outgoing);
+                    }
+                    let result = {
+                        let secure_channel = trace_read_lock!(self.state.secure_channel);
+                        self.send_buffer.write(request_id, outgoing, &secure_channel)
+                    };
+                    match result {
+                        // A request too large for the server fails, the connection carries on
+                        Err(StatusCode::BadRequestTooLarge) => {
+                            self.state.fail_message(request_id, StatusCode::BadRequestTooLarge);
+                            TransportPollResult::OutgoingMessage
+                        }
+                        Err(e) => TransportPollResult::Closed(e),
+                        Ok(_) => TransportPollResult::OutgoingMessage,
                     }
                 }
                 incoming = self.read.next() => {
@@ -275,3 +430,116 @@ impl TcpTransport {
         r
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, path::Path, sync::Arc};
+
+    use parking_lot::RwLock;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::{TcpTransport, TransportConfiguration};
+    use crate::core::comms::{
+        secure_channel::SecureChannel,
+        tcp_types::{AcknowledgeMessage, HelloMessage, MessageHeader, MessageType},
+    };
+    use crate::crypto::CertificateStore;
+    use crate::types::encoding::{BinaryEncoder, DecodingOptions};
+
+    /// Accepts one connection, reads its hello and acknowledges it, returning the endpoint url of
+    /// the hello
+    async fn acknowledge_hello(listener: TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut header = [0u8; 8];
+        socket.read_exact(&mut header).await.unwrap();
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let mut hello = header.to_vec();
+        hello.resize(size, 0);
+        socket.read_exact(&mut hello[8..]).await.unwrap();
+        let hello =
+            HelloMessage::decode(&mut Cursor::new(hello), &DecodingOptions::test()).unwrap();
+
+        let mut ack = AcknowledgeMessage {
+            message_header: MessageHeader::new(MessageType::Acknowledge),
+            protocol_version: 0,
+            receive_buffer_size: 65535,
+            send_buffer_size: 65535,
+            max_message_size: 0,
+            max_chunk_count: 0,
+        };
+        ack.message_header.message_size = ack.byte_len() as u32;
+        socket.write_all(&ack.encode_to_vec()).await.unwrap();
+        hello.endpoint_url.as_ref().to_string()
+    }
+
+    #[tokio::test]
+    async fn connect_falls_back_when_refused() {
+        // A port that nothing listens on refuses the connection
+        let refused = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let refused_url = format!("opc.tcp://{}/", refused.local_addr().unwrap());
+        drop(refused);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let fallback_url = format!("opc.tcp://{}/", listener.local_addr().unwrap());
+        let server = tokio::spawn(acknowledge_hello(listener));
+
+        let (_, outgoing_recv) = tokio::sync::mpsc::channel(1);
+        let config = TransportConfiguration {
+            max_pending_incoming: 5,
+            max_inflight: 5,
+            send_buffer_size: 65535,
+            recv_buffer_size: 65535,
+            max_message_size: 0,
+            max_chunk_count: 0,
+            fallback_endpoint_urls: vec![fallback_url.clone()],
+            capture: None,
+        };
+        let transport = TcpTransport::connect(
+            Arc::new(RwLock::new(SecureChannel::new_no_certificate_store())),
+            Arc::new(RwLock::new(CertificateStore::new(Path::new("./pki")))),
+            outgoing_recv,
+            config,
+            &refused_url,
+        )
+        .await;
+        assert!(transport.is_ok());
+        assert_eq!(server.await.unwrap(), fallback_url);
+    }
+
+    #[tokio::test]
+    async fn connect_skips_unsupported_fallback() {
+        let refused = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let refused_url = format!("opc.tcp://{}/", refused.local_addr().unwrap());
+        drop(refused);
+
+        // A fallback with a scheme the client has no transport for is not connected to even
+        // though something listens on its port
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let unsupported_url = format!("http://{}/", listener.local_addr().unwrap());
+        let fallback_url = format!("opc.tcp://{}/", listener.local_addr().unwrap());
+        let server = tokio::spawn(acknowledge_hello(listener));
+
+        let (_, outgoing_recv) = tokio::sync::mpsc::channel(1);
+        let config = TransportConfiguration {
+            max_pending_incoming: 5,
+            max_inflight: 5,
+            send_buffer_size: 65535,
+            recv_buffer_size: 65535,
+            max_message_size: 0,
+            max_chunk_count: 0,
+            fallback_endpoint_urls: vec![unsupported_url, fallback_url.clone()],
+            capture: None,
+        };
+        let transport = TcpTransport::connect(
+            Arc::new(RwLock::new(SecureChannel::new_no_certificate_store())),
+            Arc::new(RwLock::new(CertificateStore::new(Path::new("./pki")))),
+            outgoing_recv,
+            config,
+            &refused_url,
+        )
+        .await;
+        assert!(transport.is_ok());
+        assert_eq!(server.await.unwrap(), fallback_url);
+    }
+}
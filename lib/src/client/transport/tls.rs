@@ -0,0 +1,126 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Opens the TLS sessions of the opc.wss and https transports. The TLS certificate of an OPC UA
+//! server is its application instance certificate, so it is checked against the certificate store
+//! of the client, the same as the certificate in an endpoint description, rather than against root
+//! certificates.
+
+use std::{sync::Arc, time::SystemTime};
+
+use parking_lot::RwLock;
+use tokio::net::TcpStream;
+use tokio_rustls::{
+    client::TlsStream,
+    rustls::{
+        self,
+        client::{ServerCertVerified, ServerCertVerifier},
+        Certificate, ServerName,
+    },
+    TlsConnector,
+};
+
+use crate::crypto::{CertificateStore, SecurityPolicy, X509};
+use crate::types::status_code::StatusCode;
+
+/// Verifies the TLS certificate of the server with the certificate store of the client.
+struct CertificateStoreVerifier {
+    certificate_store: Arc<RwLock<CertificateStore>>,
+    hostname: String,
+}
+
+impl ServerCertVerifier for CertificateStoreVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let cert = X509::from_der(&end_entity.0).map_err(|_| {
+            rustls::Error::InvalidCertificate(rustls::CertificateError::BadEncoding)
+        })?;
+        // The key length of the certificate is checked against a policy in current use, since
+        // the policy of the secure channel inside the TLS session isn't known yet
+        let certificate_store = trace_read_lock!(self.certificate_store);
+        let status_code = certificate_store.validate_or_reject_application_instance_cert(
+            &cert,
+            SecurityPolicy::Basic256Sha256,
+            Some(self.hostname.as_str()),
+            None,
+        );
+        if status_code.is_good() {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            error!(
+                "TLS certificate of the server is not trusted, {}",
+                status_code
+            );
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+}
+
+/// Opens TLS sessions with the server of an endpoint url.
+#[derive(Clone)]
+pub(crate) struct TlsClient {
+    connector: TlsConnector,
+    server_name: ServerName,
+    /// The host and port of the server, as sent in the `Host` header
+    pub host: String,
+    /// The path of the endpoint url
+    pub path: String,
+}
+
+impl TlsClient {
+    pub fn new(
+        endpoint_url: &str,
+        certificate_store: Arc<RwLock<CertificateStore>>,
+    ) -> Result<TlsClient, StatusCode> {
+        let url =
+            url::Url::parse(endpoint_url).map_err(|_| StatusCode::BadTcpEndpointUrlInvalid)?;
+        let hostname = url
+            .host_str()
+            .ok_or(StatusCode::BadTcpEndpointUrlInvalid)?
+            .to_string();
+        let server_name = ServerName::try_from(hostname.trim_matches(|c| c == '[' || c == ']'))
+            .map_err(|_| {
+                error!("Host {} cannot be used as a TLS server name", hostname);
+                StatusCode::BadTcpEndpointUrlInvalid
+            })?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", hostname, port),
+            None => hostname.clone(),
+        };
+
+        let config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(CertificateStoreVerifier {
+                certificate_store,
+                hostname,
+            }))
+            .with_no_client_auth();
+        Ok(TlsClient {
+            connector: TlsConnector::from(Arc::new(config)),
+            server_name,
+            host,
+            path: url.path().to_string(),
+        })
+    }
+
+    /// Opens a TLS session over the socket.
+    pub async fn connect(&self, socket: TcpStream) -> Result<TlsStream<TcpStream>, StatusCode> {
+        self.connector
+            .connect(self.server_name.clone(), socket)
+            .await
+            .map_err(|err| {
+                error!("TLS handshake with {} failed, {}", self.host, err);
+                StatusCode::BadSecureChannelClosed
+            })
+    }
+}
@@ -0,0 +1,29 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Connects to opc.wss endpoints.
+
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use tokio::{io::DuplexStream, net::TcpStream};
+
+use super::tls::TlsClient;
+use crate::core::comms::{secure_channel::Role, websocket::*};
+use crate::crypto::CertificateStore;
+use crate::types::status_code::StatusCode;
+
+/// Opens a TLS session over the socket and upgrades it to a WebSocket, returning the stream that
+/// carries the chunks of the connection.
+pub(crate) async fn connect(
+    socket: TcpStream,
+    endpoint_url: &str,
+    certificate_store: Arc<RwLock<CertificateStore>>,
+    max_chunk_size: usize,
+) -> Result<DuplexStream, StatusCode> {
+    let tls_client = TlsClient::new(endpoint_url, certificate_store)?;
+    let mut stream = tls_client.connect(socket).await?;
+    websocket_client_handshake(&mut stream, &tls_client.host, &tls_client.path).await?;
+    Ok(websocket_bridge(stream, Role::Client, max_chunk_size))
+}
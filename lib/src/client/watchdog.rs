@@ -0,0 +1,105 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Watches a heartbeat variable on a server and reports when it stops changing.
+//!
+//! Servers often maintain a heartbeat (or watchdog) variable, such as a counter or a toggling
+//! boolean that changes at a fixed rate, so that the machines they talk to can tell the server is
+//! still alive. A [`Watchdog`] reads such a variable periodically and calls back when its value
+//! has not changed for longer than a timeout, e.g. because the server or the device behind the
+//! variable has hung while the connection itself stays up.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::{task::JoinHandle, time::Instant};
+
+use crate::types::{NodeId, ReadValueId, StatusCode, TimestampsToReturn, Variant};
+
+use super::Session;
+
+/// Watches a heartbeat variable on a server, calling back if its value stops changing. The
+/// variable is read a few times per timeout. A read that fails counts as no change, so the
+/// watchdog also calls back if the server stops responding. Dropping the watchdog stops it.
+pub struct Watchdog {
+    task: JoinHandle<()>,
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Watchdog {
+    /// Starts watching the variable. The `on_stalled` callback is called when the value has not
+    /// changed for `timeout`. It is called once for each stall, i.e. not again until the value
+    /// has changed and stopped changing once more. The timeout should be a few times the rate at
+    /// which the variable changes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Watchdog)` - The watchdog, which keeps watching until it is stopped or dropped.
+    /// * `Err(StatusCode)` - The variable could not be read, [Status code](StatusCode) is the
+    ///   reason for failure.
+    pub async fn start<F>(
+        session: Arc<Session>,
+        node_id: NodeId,
+        timeout: Duration,
+        mut on_stalled: F,
+    ) -> Result<Watchdog, StatusCode>
+    where
+        F: FnMut() + Send + Sync + 'static,
+    {
+        let mut value = Self::read_value(&session, &node_id).await?;
+        let mut last_change = Instant::now();
+        let mut stalled = false;
+
+        // The variable is read a few times per timeout so that a change is seen well before the
+        // timeout expires
+        let interval = timeout / 4;
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Ok(v) = Self::read_value(&session, &node_id).await {
+                    if v != value {
+                        value = v;
+                        last_change = Instant::now();
+                        stalled = false;
+                    }
+                }
+                if !stalled && last_change.elapsed() >= timeout {
+                    stalled = true;
+                    on_stalled();
+                }
+            }
+        });
+
+        Ok(Watchdog { task })
+    }
+
+    /// Stops watching the variable
+    pub fn stop(self) {
+        self.task.abort();
+    }
+
+    /// Reads the value of the variable, failing if the read fails or returns a bad status
+    async fn read_value(
+        session: &Session,
+        node_id: &NodeId,
+    ) -> Result<Option<Variant>, StatusCode> {
+        let nodes_to_read = [ReadValueId::from(node_id.clone())];
+        let data_value = session
+            .read(&nodes_to_read, TimestampsToReturn::Neither, 0.0)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        let status_code = data_value.status();
+        if status_code.is_bad() {
+            Err(status_code)
+        } else {
+            Ok(data_value.value)
+        }
+    }
+}
@@ -0,0 +1,330 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Capture of the traffic of connections to a file, and replay of a capture through the decoder.
+//!
+//! A capture file holds one JSON record per line. Each record has a timestamp, the connection it
+//! belongs to and its direction, and is either a chunk as it was on the wire, base64 encoded, or
+//! a service message as it was decoded, in its debug format. Chunks of secured channels are
+//! captured encrypted, so their decoded messages can be captured too, but only when that is
+//! asked for.
+//!
+//! A capture is sensitive. The chunks of a channel without security carry everything in the
+//! clear, including user names and passwords. Decoded messages have their identity tokens, user
+//! token signatures and nonces blanked out, but hold everything else that was sent, whatever the
+//! security of the channel.
+
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+
+use crate::core::{
+    comms::{
+        chunker::Chunker,
+        message_chunk::{MessageChunk, MessageIsFinalType},
+        secure_channel::{Role, SecureChannel},
+        security_header::SecurityHeader,
+        tcp_types::{CHUNK_MESSAGE, CLOSE_SECURE_CHANNEL_MESSAGE, OPEN_SECURE_CHANNEL_MESSAGE},
+    },
+    supported_message::SupportedMessage,
+};
+use crate::crypto::SecurityPolicy;
+use crate::sync::*;
+use crate::types::{
+    encoding::DecodingOptions, status_code::StatusCode, ByteString, ExtensionObject, SignatureData,
+};
+
+/// The direction of captured traffic, relative to the side that captured it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Received,
+    Sent,
+}
+
+/// What a capture record holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CaptureContent {
+    /// A chunk as it was on the wire, base64 encoded
+    Chunk { data: String },
+    /// A service message after it was decoded, or before it was encoded
+    Message {
+        request_id: u32,
+        name: String,
+        message: String,
+    },
+}
+
+/// A line of a capture file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Identifies the connection, e.g. the id of a server transport or a client's endpoint url
+    pub connection: String,
+    pub direction: Direction,
+    #[serde(flatten)]
+    pub content: CaptureContent,
+}
+
+/// Records the traffic of connections to a capture file. A capture is cheap to clone, the clones
+/// for each connection share the file.
+#[derive(Debug, Clone)]
+pub struct MessageCapture {
+    connection: String,
+    file: Arc<Mutex<File>>,
+    /// Set if decoded messages are captured as well as chunks
+    capture_messages: bool,
+}
+
+impl MessageCapture {
+    /// Opens the capture file at the path, appending to it if it exists. Decoded messages are
+    /// only captured if `capture_messages` is set, otherwise just the chunks are.
+    pub fn open(path: &Path, capture_messages: bool) -> io::Result<MessageCapture> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        warn!(
+            "Capturing {} to {}, the capture is sensitive",
+            if capture_messages {
+                "chunks and decoded messages"
+            } else {
+                "chunks"
+            },
+            path.display()
+        );
+        Ok(MessageCapture {
+            connection: String::new(),
+            file: Arc::new(Mutex::new(file)),
+            capture_messages,
+        })
+    }
+
+    /// Returns a capture of the same file for the records of a connection.
+    pub fn for_connection<T>(&self, connection: T) -> MessageCapture
+    where
+        T: Into<String>,
+    {
+        MessageCapture {
+            connection: connection.into(),
+            file: self.file.clone(),
+            capture_messages: self.capture_messages,
+        }
+    }
+
+    /// Records the chunks in bytes that were read from or written to a stream. The bytes hold
+    /// whole chunks, e.g. those of a message. Hello, acknowledge and error messages are left out.
+    pub fn chunks(&self, direction: Direction, mut data: &[u8]) {
+        while data.len() >= 8 {
+            let message_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+            let (chunk, rest) = data.split_at(message_size.clamp(8, data.len()));
+            if matches!(
+                &chunk[..3],
+                CHUNK_MESSAGE | OPEN_SECURE_CHANNEL_MESSAGE | CLOSE_SECURE_CHANNEL_MESSAGE
+            ) {
+                self.write(
+                    direction,
+                    CaptureContent::Chunk {
+                        data: STANDARD.encode(chunk),
+                    },
+                );
+            }
+            data = rest;
+        }
+    }
+
+    /// Records a service message, if decoded messages are captured. Its identity tokens, user
+    /// token signatures and nonces are blanked out.
+    pub fn message(&self, direction: Direction, request_id: u32, message: &SupportedMessage) {
+        if !self.capture_messages {
+            return;
+        }
+        let redacted = redacted(message);
+        self.write(
+            direction,
+            CaptureContent::Message {
+                request_id,
+                name: message.name().to_string(),
+                message: format!("{:?}", redacted.as_ref().unwrap_or(message)),
+            },
+        );
+    }
+
+    fn write(&self, direction: Direction, content: CaptureContent) {
+        let record = CaptureRecord {
+            timestamp: Utc::now(),
+            connection: self.connection.clone(),
+            direction,
+            content,
+        };
+        match serde_json::to_string(&record) {
+            Ok(line) => {
+                let mut file = trace_lock!(self.file);
+                if let Err(err) = writeln!(file, "{}", line) {
+                    error!("Cannot write to capture file, {}", err);
+                }
+            }
+            Err(err) => error!("Cannot serialize capture record, {}", err),
+        }
+    }
+}
+
+/// Returns a copy of the message with its secrets blanked out, or `None` if it holds none
+fn redacted(message: &SupportedMessage) -> Option<SupportedMessage> {
+    let mut message = match message {
+        SupportedMessage::OpenSecureChannelRequest(_)
+        | SupportedMessage::OpenSecureChannelResponse(_)
+        | SupportedMessage::CreateSessionRequest(_)
+        | SupportedMessage::CreateSessionResponse(_)
+        | SupportedMessage::ActivateSessionRequest(_)
+        | SupportedMessage::ActivateSessionResponse(_) => message.clone(),
+        _ => return None,
+    };
+    match message {
+        SupportedMessage::OpenSecureChannelRequest(ref mut request) => {
+            request.client_nonce = ByteString::null();
+        }
+        SupportedMessage::OpenSecureChannelResponse(ref mut response) => {
+            response.server_nonce = ByteString::null();
+        }
+        SupportedMessage::CreateSessionRequest(ref mut request) => {
+            request.client_nonce = ByteString::null();
+        }
+        SupportedMessage::CreateSessionResponse(ref mut response) => {
+            response.server_nonce = ByteString::null();
+        }
+        SupportedMessage::ActivateSessionRequest(ref mut request) => {
+            request.user_identity_token = ExtensionObject::null();
+            request.user_token_signature = SignatureData::null();
+        }
+        SupportedMessage::ActivateSessionResponse(ref mut response) => {
+            response.server_nonce = ByteString::null();
+        }
+        _ => {}
+    }
+    Some(message)
+}
+
+/// A message that was decoded from the captured chunks of a connection
+#[derive(Debug)]
+pub struct ReplayedMessage {
+    /// The timestamp of the message's final chunk
+    pub timestamp: DateTime<Utc>,
+    pub connection: String,
+    pub direction: Direction,
+    /// The decoded message, or the reason it could not be decoded
+    pub message: Result<SupportedMessage, StatusCode>,
+}
+
+/// The chunks of one direction of a connection that are being replayed
+struct ReplayStream {
+    secure_channel: SecureChannel,
+    /// Set once the stream opens a secure channel with a security policy
+    secured: bool,
+    pending_chunks: Vec<MessageChunk>,
+}
+
+/// Reads the records of a capture file, returning an error if the file cannot be read or holds
+/// a line that is not a record. A line that is not a record is an error of kind `InvalidData`.
+pub fn read_capture(path: &Path) -> io::Result<Vec<CaptureRecord>> {
+    let file = File::open(path)?;
+    BufReader::new(file)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| {
+            let line = line?;
+            serde_json::from_str(&line)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        })
+        .collect()
+}
+
+/// Feeds the captured chunks of a file back through the decoder, returning the messages of each
+/// connection in the order that they were captured. Only the traffic of channels that use the
+/// `None` security policy can be decoded, the messages of a secured channel are replayed as
+/// `BadSecurityPolicyRejected`. Their decoded messages are in the capture as message records.
+pub fn replay_capture(
+    path: &Path,
+    decoding_options: &DecodingOptions,
+) -> io::Result<Vec<ReplayedMessage>> {
+    let mut streams: HashMap<(String, Direction), ReplayStream> = HashMap::new();
+    let mut replayed = Vec::new();
+    for record in read_capture(path)? {
+        let CaptureContent::Chunk { data } = record.content else {
+            continue;
+        };
+        let stream = streams
+            .entry((record.connection.clone(), record.direction))
+            .or_insert_with(|| ReplayStream {
+                secure_channel: SecureChannel::new_no_security(
+                    Role::Unknown,
+                    decoding_options.clone(),
+                ),
+                secured: false,
+                pending_chunks: Vec::new(),
+            });
+        if let Some(message) = replay_chunk(stream, &data, decoding_options) {
+            replayed.push(ReplayedMessage {
+                timestamp: record.timestamp,
+                connection: record.connection,
+                direction: record.direction,
+                message,
+            });
+        }
+    }
+    Ok(replayed)
+}
+
+/// Adds a chunk to the stream, returning the message that it completes
+fn replay_chunk(
+    stream: &mut ReplayStream,
+    data: &str,
+    decoding_options: &DecodingOptions,
+) -> Option<Result<SupportedMessage, StatusCode>> {
+    let chunk = match STANDARD
+        .decode(data)
+        .map_err(|_| StatusCode::BadDecodingError)
+        .and_then(|data| MessageChunk::from_bytes(BytesMut::from(&data[..]), decoding_options))
+    {
+        Ok(chunk) => chunk,
+        Err(err) => return Some(Err(err)),
+    };
+    let message_header = match chunk.message_header(decoding_options) {
+        Ok(message_header) => message_header,
+        Err(err) => return Some(Err(err)),
+    };
+    if let Ok(SecurityHeader::Asymmetric(security_header)) = chunk.security_header(decoding_options)
+    {
+        stream.secured = SecurityPolicy::from_uri(security_header.security_policy_uri.as_ref())
+            != SecurityPolicy::None;
+    }
+
+    match message_header.is_final {
+        MessageIsFinalType::FinalError => {
+            stream.pending_chunks.clear();
+            None
+        }
+        _ if stream.secured => (message_header.is_final == MessageIsFinalType::Final)
+            .then_some(Err(StatusCode::BadSecurityPolicyRejected)),
+        is_final => {
+            let chunk = match stream.secure_channel.remove_security(chunk) {
+                Ok(chunk) => chunk,
+                Err(err) => return Some(Err(err)),
+            };
+            stream.pending_chunks.push(chunk);
+            if is_final == MessageIsFinalType::Final {
+                let chunks = std::mem::take(&mut stream.pending_chunks);
+                Some(Chunker::decode(&chunks, &stream.secure_channel, None))
+            } else {
+                None
+            }
+        }
+    }
+}
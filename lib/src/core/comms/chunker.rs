@@ -4,7 +4,7 @@
 
 //! Contains code for turning messages into chunks and chunks into messages.
 
-use std::io::Cursor;
+use std::io::{self, Cursor, Read};
 
 use crate::{
     core::{
@@ -16,13 +16,36 @@ use crate::{
     },
     crypto::SecurityPolicy,
     types::{
-        encoding::BinaryEncoder, node_id::NodeId, node_ids::ObjectId, status_code::StatusCode,
+        encoding::{BinaryEncoder, DecodingOptions},
+        node_id::NodeId,
+        node_ids::ObjectId,
+        status_code::StatusCode,
     },
 };
 
 /// The Chunker is responsible for turning messages to chunks and chunks into messages.
 pub struct Chunker;
 
+/// Reads the bodies of a message's chunks one after another, so a message that spans several
+/// chunks is decoded without copying them into one buffer first.
+struct ChunkBodies<'a> {
+    bodies: Vec<&'a [u8]>,
+    current: usize,
+}
+
+impl Read for ChunkBodies<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while let Some(body) = self.bodies.get_mut(self.current) {
+            let read = body.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            self.current += 1;
+        }
+        Ok(0)
+    }
+}
+
 impl Chunker {
     /// Tests what kind of chunk type is used for the supported message.
     fn message_type(message: &SupportedMessage) -> MessageChunkType {
@@ -112,6 +135,40 @@ impl Chunker {
         secure_channel: &SecureChannel,
         supported_message: &SupportedMessage,
     ) -> std::result::Result<Vec<MessageChunk>, StatusCode> {
+        let mut data = Vec::new();
+        let message_type = Self::encode_message(
+            max_message_size,
+            secure_channel,
+            supported_message,
+            &mut data,
+        )?;
+        let bodies = Self::chunk_bodies(message_type, max_chunk_size, secure_channel, &data)?;
+        let last = bodies.len().saturating_sub(1);
+        bodies
+            .into_iter()
+            .enumerate()
+            .map(|(i, body)| {
+                MessageChunk::new(
+                    sequence_number + i as u32,
+                    request_id,
+                    message_type,
+                    Self::is_final(i, last),
+                    secure_channel,
+                    body,
+                )
+            })
+            .collect()
+    }
+
+    /// Encodes the node id and body of a message into a buffer, replacing its contents, and
+    /// returns the type of chunk the message is sent in. The buffer can be reused between
+    /// messages so that encoding does not allocate once it has grown to fit.
+    pub fn encode_message(
+        max_message_size: usize,
+        secure_channel: &SecureChannel,
+        supported_message: &SupportedMessage,
+        data: &mut Vec<u8>,
+    ) -> std::result::Result<MessageChunkType, StatusCode> {
         let security_policy = secure_channel.security_policy();
         if security_policy == SecurityPolicy::Unknown {
             panic!("Security policy cannot be unknown");
@@ -125,72 +182,68 @@ impl Chunker {
                 "Max message size is {} and message {} exceeds that",
                 max_message_size, message_size
             );
-            // Client stack should report a BadRequestTooLarge, server BadResponseTooLarge
-            Err(if secure_channel.is_client_role() {
-                StatusCode::BadRequestTooLarge
-            } else {
-                StatusCode::BadResponseTooLarge
-            })
+            Err(Self::too_large_status(secure_channel))
         } else {
             let node_id = supported_message.node_id();
             message_size += node_id.byte_len();
 
-            let message_type = Chunker::message_type(supported_message);
-            let mut stream = Cursor::new(vec![0u8; message_size]);
+            data.clear();
+            data.reserve(message_size);
+            let mut stream = Cursor::new(data);
 
             trace!("Encoding node id {:?}", node_id);
             let _ = node_id.encode(&mut stream);
             let _ = supported_message.encode(&mut stream)?;
-            let data = stream.into_inner();
+            Ok(Chunker::message_type(supported_message))
+        }
+    }
 
-            let result = if max_chunk_size > 0 {
-                let max_body_per_chunk = MessageChunk::body_size_from_message_size(
-                    message_type,
-                    secure_channel,
-                    max_chunk_size,
-                )
-                .map_err(|_| {
-                    error!(
-                        "body_size_from_message_size error for max_chunk_size = {}",
-                        max_chunk_size
-                    );
-                    StatusCode::BadTcpInternalError
-                })?;
+    /// The status of a message that exceeds the limits of the other end. The client stack
+    /// reports a `BadRequestTooLarge`, the server `BadResponseTooLarge`.
+    pub fn too_large_status(secure_channel: &SecureChannel) -> StatusCode {
+        if secure_channel.is_client_role() {
+            StatusCode::BadRequestTooLarge
+        } else {
+            StatusCode::BadResponseTooLarge
+        }
+    }
 
-                // Multiple chunks means breaking the data up into sections. Fortunately
-                // Rust has a nice function to do just that.
-                let data_chunks = data.chunks(max_body_per_chunk);
-                let data_chunks_len = data_chunks.len();
-                let mut chunks = Vec::with_capacity(data_chunks_len);
-                for (i, data_chunk) in data_chunks.enumerate() {
-                    let is_final = if i == data_chunks_len - 1 {
-                        MessageIsFinalType::Final
-                    } else {
-                        MessageIsFinalType::Intermediate
-                    };
-                    let chunk = MessageChunk::new(
-                        sequence_number + i as u32,
-                        request_id,
-                        message_type,
-                        is_final,
-                        secure_channel,
-                        data_chunk,
-                    )?;
-                    chunks.push(chunk);
-                }
-                chunks
-            } else {
-                let chunk = MessageChunk::new(
-                    sequence_number,
-                    request_id,
-                    message_type,
-                    MessageIsFinalType::Final,
-                    secure_channel,
-                    &data,
-                )?;
-                vec![chunk]
-            };
-            Ok(result)
+    /// Splits an encoded message into the bodies of the chunks it is sent in. A `max_chunk_size`
+    /// of 0 means the message is sent in a single chunk.
+    pub fn chunk_bodies<'a>(
+        message_type: MessageChunkType,
+        max_chunk_size: usize,
+        secure_channel: &SecureChannel,
+        data: &'a [u8],
+    ) -> std::result::Result<Vec<&'a [u8]>, StatusCode> {
+        if max_chunk_size > 0 {
+            let max_body_per_chunk = MessageChunk::body_size_from_message_size(
+                message_type,
+                secure_channel,
+                max_chunk_size,
+            )
+            .map_err(|_| {
+                error!(
+                    "body_size_from_message_size error for max_chunk_size = {}",
+                    max_chunk_size
+                );
+                StatusCode::BadTcpInternalError
+            })?;
+
+            // Multiple chunks means breaking the data up into sections. Fortunately
+            // Rust has a nice function to do just that.
+            Ok(data.chunks(max_body_per_chunk).collect())
+        } else {
+            Ok(vec![data])
+        }
+    }
+
+    /// The last most chunk of a message is final, the rest intermediate
+    pub fn is_final(index: usize, last: usize) -> MessageIsFinalType {
+        if index == last {
+            MessageIsFinalType::Final
+        } else {
+            MessageIsFinalType::Intermediate
         }
     }
 
@@ -201,8 +254,7 @@ impl Chunker {
         secure_channel: &SecureChannel,
         expected_node_id: Option<NodeId>,
     ) -> std::result::Result<SupportedMessage, StatusCode> {
-        // Calculate the size of data held in all chunks
-        let mut data_size: usize = 0;
+        let mut bodies = Vec::with_capacity(chunks.len());
         for (i, chunk) in chunks.iter().enumerate() {
             let chunk_info = chunk.chunk_info(secure_channel)?;
             // The last most chunk is expected to be final, the rest intermediate
@@ -214,39 +266,44 @@ impl Chunker {
             if chunk_info.message_header.is_final != expected_is_final {
                 return Err(StatusCode::BadDecodingError);
             }
-            // Calculate how much space data is in the chunk
+            // The assumption is the data is decrypted / verified by now
             let body_start = chunk_info.body_offset;
             let body_end = body_start + chunk_info.body_length;
-            data_size += chunk.data[body_start..body_end].len();
+            bodies.push(&chunk.data[body_start..body_end]);
         }
 
-        // Read the data into a contiguous buffer. The assumption is the data is decrypted / verified by now
-        // TODO this buffer should be externalized so it is not allocated each time
-        let mut data = Vec::with_capacity(data_size);
-        for chunk in chunks.iter() {
-            let chunk_info = chunk.chunk_info(secure_channel)?;
-
-            let body_start = chunk_info.body_offset;
-            let body_end = body_start + chunk_info.body_length;
-            let body_data = &chunk.data[body_start..body_end];
-            data.extend_from_slice(body_data);
-        }
+        Self::decode_stream(
+            &mut ChunkBodies { bodies, current: 0 },
+            &secure_channel.decoding_options(),
+            expected_node_id,
+        )
+    }
 
-        // Make a stream around the data
-        let mut data = Cursor::new(data);
+    /// Decodes a message from the data of the chunks it was sent in, i.e. its node id followed by
+    /// its body. The message must be of a `SupportedMessage` type otherwise an error will occur.
+    pub fn decode_message(
+        data: &[u8],
+        decoding_options: &DecodingOptions,
+        expected_node_id: Option<NodeId>,
+    ) -> std::result::Result<SupportedMessage, StatusCode> {
+        Self::decode_stream(&mut Cursor::new(data), decoding_options, expected_node_id)
+    }
 
+    fn decode_stream<S: Read>(
+        data: &mut S,
+        decoding_options: &DecodingOptions,
+        expected_node_id: Option<NodeId>,
+    ) -> std::result::Result<SupportedMessage, StatusCode> {
         // The extension object prefix is just the node id. A point the spec rather unhelpfully doesn't
         // elaborate on. Probably because people enjoy debugging why the stream pos is out by 1 byte
         // for hours.
 
-        let decoding_options = secure_channel.decoding_options();
-
         // Read node id from stream
-        let node_id = NodeId::decode(&mut data, &decoding_options)?;
+        let node_id = NodeId::decode(data, decoding_options)?;
         let object_id = Self::object_id_from_node_id(node_id, expected_node_id)?;
 
         // Now decode the payload using the node id.
-        match SupportedMessage::decode_by_object_id(&mut data, object_id, &decoding_options) {
+        match SupportedMessage::decode_by_object_id(data, object_id, decoding_options) {
             Ok(decoded_message) => {
                 if let SupportedMessage::Invalid(_) = decoded_message {
                     debug!("Message {:?} is unsupported", object_id);
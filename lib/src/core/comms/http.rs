@@ -0,0 +1,68 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the small part of HTTP/1.1 that the transports built on it need, i.e. reading and
+//! writing the head of a request or response and finding its headers.
+
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::types::status_code::StatusCode;
+
+/// Largest request or response head that is read
+const MAX_HTTP_HEAD_LEN: usize = 8192;
+
+pub(crate) fn io_error(err: io::Error) -> StatusCode {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        StatusCode::BadConnectionClosed
+    } else {
+        error!("HTTP connection IO error {:?}", err);
+        StatusCode::BadCommunicationError
+    }
+}
+
+/// Reads the head of an HTTP request or response, i.e. everything up to the empty line. The
+/// stream is read a byte at a time so nothing after the head is consumed.
+pub(crate) async fn read_http_head<R>(reader: &mut R) -> Result<String, StatusCode>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut head = Vec::with_capacity(512);
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HTTP_HEAD_LEN {
+            error!("HTTP head is longer than {} bytes", MAX_HTTP_HEAD_LEN);
+            return Err(StatusCode::BadConnectionRejected);
+        }
+        head.push(reader.read_u8().await.map_err(io_error)?);
+    }
+    String::from_utf8(head).map_err(|_| {
+        error!("HTTP head is not valid UTF-8");
+        StatusCode::BadConnectionRejected
+    })
+}
+
+/// Finds the value of a header in an HTTP head. Header names are case insensitive.
+pub(crate) fn http_header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.split("\r\n").skip(1).find_map(|line| {
+        let (header, value) = line.split_once(':')?;
+        if header.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Tests if a comma separated header value contains the token, ignoring case
+#[cfg(feature = "websocket")]
+pub(crate) fn has_token(value: Option<&str>, token: &str) -> bool {
+    value.is_some_and(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)))
+}
+
+pub(crate) async fn write_http_head<W>(writer: &mut W, head: &str) -> Result<(), StatusCode>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.write_all(head.as_bytes()).await.map_err(io_error)?;
+    writer.flush().await.map_err(io_error)
+}
@@ -0,0 +1,335 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the HTTPS mapping of the binary encoding, i.e. the `https` transport.
+//!
+//! Each request is sent in the body of an HTTP POST and its response comes back in the body of the
+//! HTTP response. A body holds the message the same way the chunks of a secure channel do, its node
+//! id followed by the message. There is no secure channel, TLS protects the messages, so the only
+//! security policy is None and sessions are identified by their authentication token.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use tokio::io::{
+    self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream, ReadHalf, WriteHalf,
+};
+use tokio_util::codec::FramedRead;
+
+use crate::core::{
+    comms::{
+        chunker::Chunker,
+        http::{http_header, io_error, read_http_head, write_http_head},
+        message_chunk::{MessageChunk, MessageIsFinalType},
+        secure_channel::{Role, SecureChannel},
+        tcp_codec::{Message, TcpCodec},
+    },
+    supported_message::SupportedMessage,
+};
+use crate::crypto::SecurityPolicy;
+use crate::types::{
+    encoding::{BinaryEncoder, DecodingOptions},
+    status_code::StatusCode,
+};
+
+/// The content type of a binary encoded body
+pub const HTTPS_CONTENT_TYPE_UABINARY: &str = "application/octet-stream";
+
+/// The header a client names the security policy of its request in
+pub const HTTPS_SECURITY_POLICY_HEADER: &str = "OPCUA-SecurityPolicy";
+
+/// The body of an HTTP request or response, and whether the connection stays open after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpsMessage {
+    pub body: Vec<u8>,
+    pub keep_alive: bool,
+}
+
+/// Encodes a message into the body of an HTTP request or response.
+pub fn encode_https_body(message: &SupportedMessage) -> Result<Vec<u8>, StatusCode> {
+    let node_id = message.node_id();
+    let mut body = Vec::with_capacity(node_id.byte_len() + message.byte_len());
+    node_id.encode(&mut body)?;
+    message.encode(&mut body)?;
+    Ok(body)
+}
+
+/// Decodes a message from the body of an HTTP request or response.
+pub fn decode_https_body(
+    body: &[u8],
+    decoding_options: &DecodingOptions,
+) -> Result<SupportedMessage, StatusCode> {
+    Chunker::decode_message(body, decoding_options, None)
+}
+
+/// Reads the body that follows an HTTP head. Only bodies with a `Content-Length` are supported
+/// and those longer than `max_body_len` bytes are rejected, unless it is 0.
+async fn read_body<R>(
+    reader: &mut R,
+    head: &str,
+    max_body_len: usize,
+) -> Result<Vec<u8>, (StatusCode, &'static str)>
+where
+    R: AsyncRead + Unpin,
+{
+    let Some(content_length) = http_header(head, "Content-Length") else {
+        error!("HTTP message has no Content-Length");
+        return Err((StatusCode::BadCommunicationError, "411 Length Required"));
+    };
+    let Ok(content_length) = content_length.parse::<usize>() else {
+        error!(
+            "HTTP message has an invalid Content-Length {}",
+            content_length
+        );
+        return Err((StatusCode::BadCommunicationError, "400 Bad Request"));
+    };
+    if max_body_len > 0 && content_length > max_body_len {
+        error!(
+            "HTTP message of {} bytes exceeds the limit of {} bytes",
+            content_length, max_body_len
+        );
+        return Err((StatusCode::BadTcpMessageTooLarge, "413 Payload Too Large"));
+    }
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|err| (io_error(err), "400 Bad Request"))?;
+    Ok(body)
+}
+
+/// Tests if the HTTP/1.1 connection stays open after the message with this head
+fn is_keep_alive(head: &str) -> bool {
+    !http_header(head, "Connection").is_some_and(|v| v.eq_ignore_ascii_case("close"))
+}
+
+/// Reads a request from an HTTPS connection. A request that isn't a binary encoded POST with the
+/// None security policy is answered with an error and the connection should be closed.
+pub async fn read_https_request<S>(
+    stream: &mut S,
+    max_body_len: usize,
+) -> Result<HttpsMessage, StatusCode>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let head = read_http_head(stream).await?;
+    let request_line = head.split("\r\n").next().unwrap_or_default();
+    let content_type = http_header(&head, "Content-Type");
+    let security_policy = http_header(&head, HTTPS_SECURITY_POLICY_HEADER);
+    let result = if !request_line.starts_with("POST ") {
+        error!("HTTPS request is not a POST, {}", request_line);
+        Err((StatusCode::BadCommunicationError, "405 Method Not Allowed"))
+    } else if content_type.is_some_and(|v| !v.starts_with(HTTPS_CONTENT_TYPE_UABINARY)) {
+        error!(
+            "HTTPS request has an unsupported content type {:?}",
+            content_type
+        );
+        Err((
+            StatusCode::BadCommunicationError,
+            "415 Unsupported Media Type",
+        ))
+    } else if security_policy.is_some_and(|v| v != SecurityPolicy::None.to_uri()) {
+        error!(
+            "HTTPS request has an unsupported security policy {:?}",
+            security_policy
+        );
+        Err((StatusCode::BadSecurityPolicyRejected, "400 Bad Request"))
+    } else {
+        read_body(stream, &head, max_body_len).await
+    };
+    match result {
+        Ok(body) => Ok(HttpsMessage {
+            body,
+            keep_alive: is_keep_alive(&head),
+        }),
+        Err((status_code, status)) => {
+            write_https_error(stream, status).await?;
+            Err(status_code)
+        }
+    }
+}
+
+/// Answers a request on an HTTPS connection with an error status, e.g. `"400 Bad Request"`,
+/// after which the connection is closed.
+pub async fn write_https_error<W>(writer: &mut W, status: &str) -> Result<(), StatusCode>
+where
+    W: AsyncWrite + Unpin,
+{
+    let head = format!(
+        "HTTP/1.1 {}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+        status
+    );
+    write_http_head(writer, &head).await
+}
+
+/// Writes the response to a request on an HTTPS connection.
+pub async fn write_https_response<W>(
+    writer: &mut W,
+    body: &[u8],
+    keep_alive: bool,
+) -> Result<(), StatusCode>
+where
+    W: AsyncWrite + Unpin,
+{
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: {}\r\n\r\n",
+        HTTPS_CONTENT_TYPE_UABINARY,
+        body.len(),
+        if keep_alive { "keep-alive" } else { "close" }
+    );
+    writer.write_all(head.as_bytes()).await.map_err(io_error)?;
+    writer.write_all(body).await.map_err(io_error)?;
+    writer.flush().await.map_err(io_error)
+}
+
+/// Sends a request in a POST to `path` on the server at `host` and reads its response. A server
+/// that doesn't answer with a binary encoded body fails the request.
+pub async fn https_post<S>(
+    stream: &mut S,
+    host: &str,
+    path: &str,
+    body: &[u8],
+    max_body_len: usize,
+) -> Result<HttpsMessage, StatusCode>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let head = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}: {}\r\n\r\n",
+        if path.is_empty() { "/" } else { path },
+        host,
+        HTTPS_CONTENT_TYPE_UABINARY,
+        body.len(),
+        HTTPS_SECURITY_POLICY_HEADER,
+        SecurityPolicy::None.to_uri()
+    );
+    stream.write_all(head.as_bytes()).await.map_err(io_error)?;
+    stream.write_all(body).await.map_err(io_error)?;
+    stream.flush().await.map_err(io_error)?;
+
+    let head = read_http_head(stream).await?;
+    let status_line = head.split("\r\n").next().unwrap_or_default();
+    if status_line.split(' ').nth(1) != Some("200") {
+        error!("Server did not accept the HTTPS request, {}", status_line);
+        return Err(StatusCode::BadCommunicationError);
+    }
+    let body = read_body(stream, &head, max_body_len)
+        .await
+        .map_err(|(status_code, _)| status_code)?;
+    Ok(HttpsMessage {
+        body,
+        keep_alive: is_keep_alive(&head),
+    })
+}
+
+/// The end of a stream that a transport sends chunks on, which gathers the chunks into whole
+/// messages and sends messages back as chunks. A bridge between a transport and HTTPS uses it to
+/// play the part of the other end of the secure channel, which has no security.
+pub struct ChunkStream {
+    reader: FramedRead<ReadHalf<DuplexStream>, TcpCodec>,
+    writer: WriteHalf<DuplexStream>,
+    secure_channel: SecureChannel,
+    /// Largest chunk the other end receives, or 0 for no limit
+    max_chunk_size: usize,
+    last_sequence_number: u32,
+    /// Chunks of messages that haven't had their final chunk yet, by request id
+    pending_chunks: HashMap<u32, Vec<MessageChunk>>,
+}
+
+impl ChunkStream {
+    /// Creates the end of the stream. The role is the part this end plays in the channel.
+    pub fn new(stream: DuplexStream, role: Role, decoding_options: DecodingOptions) -> ChunkStream {
+        let (reader, writer) = io::split(stream);
+        ChunkStream {
+            reader: FramedRead::new(reader, TcpCodec::new(decoding_options.clone())),
+            writer,
+            secure_channel: SecureChannel::new_no_security(role, decoding_options),
+            max_chunk_size: 0,
+            last_sequence_number: 0,
+            pending_chunks: HashMap::new(),
+        }
+    }
+
+    pub fn secure_channel_mut(&mut self) -> &mut SecureChannel {
+        &mut self.secure_channel
+    }
+
+    pub fn set_max_chunk_size(&mut self, max_chunk_size: usize) {
+        self.max_chunk_size = max_chunk_size;
+    }
+
+    /// Reads the next message of the transport, such as its Hello. Returns `None` once the
+    /// stream is closed.
+    pub async fn read_raw(&mut self) -> Result<Option<Message>, StatusCode> {
+        match self.reader.next().await {
+            Some(Ok(message)) => Ok(Some(message)),
+            Some(Err(err)) => {
+                error!("Cannot read from the transport, {:?}", err);
+                Err(StatusCode::BadCommunicationError)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn write_raw(&mut self, data: &[u8]) -> Result<(), StatusCode> {
+        self.writer.write_all(data).await.map_err(io_error)
+    }
+
+    /// Reads chunks until one of them completes a message, returning the request id and the
+    /// message. Returns `None` once the stream is closed. This is cancel safe, the chunks of a
+    /// message that are read before it is cancelled are kept.
+    pub async fn read_message(&mut self) -> Result<Option<(u32, SupportedMessage)>, StatusCode> {
+        loop {
+            let chunk = match self.read_raw().await? {
+                Some(Message::Chunk(chunk)) => chunk,
+                Some(message) => {
+                    error!("Expected a chunk from the transport, got {:?}", message);
+                    return Err(StatusCode::BadCommunicationError);
+                }
+                None => return Ok(None),
+            };
+            let chunk_info = chunk.chunk_info(&self.secure_channel)?;
+            let request_id = chunk_info.sequence_header.request_id;
+            match chunk_info.message_header.is_final {
+                MessageIsFinalType::Intermediate => {
+                    self.pending_chunks
+                        .entry(request_id)
+                        .or_default()
+                        .push(chunk);
+                }
+                MessageIsFinalType::FinalError => {
+                    debug!("Discarding chunks of request {} after an abort", request_id);
+                    self.pending_chunks.remove(&request_id);
+                }
+                MessageIsFinalType::Final => {
+                    let mut chunks = self.pending_chunks.remove(&request_id).unwrap_or_default();
+                    chunks.push(chunk);
+                    let message = Chunker::decode(&chunks, &self.secure_channel, None)?;
+                    return Ok(Some((request_id, message)));
+                }
+            }
+        }
+    }
+
+    /// Sends a message to the transport in as many chunks as it needs.
+    pub async fn write_message(
+        &mut self,
+        request_id: u32,
+        message: &SupportedMessage,
+    ) -> Result<(), StatusCode> {
+        let chunks = Chunker::encode(
+            self.last_sequence_number + 1,
+            request_id,
+            0,
+            self.max_chunk_size,
+            &self.secure_channel,
+            message,
+        )?;
+        self.last_sequence_number += chunks.len() as u32;
+        for chunk in chunks {
+            self.write_raw(&chunk.data).await?;
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@
 
 use std::io::{Cursor, Read, Write};
 
+use bytes::{BufMut, BytesMut};
+
 use crate::types::{status_code::StatusCode, *};
 
 use super::{
@@ -130,8 +132,9 @@ impl MessageChunkHeader {}
 /// to be available in sequence so they can be formed back into the message.
 #[derive(Debug)]
 pub struct MessageChunk {
-    /// All of the chunk's data including headers, payload, padding, signature
-    pub data: Vec<u8>,
+    /// All of the chunk's data including headers, payload, padding, signature. A chunk read from a
+    /// stream shares the read buffer rather than holding a copy.
+    pub data: BytesMut,
 }
 
 impl BinaryEncoder<MessageChunk> for MessageChunk {
@@ -164,15 +167,12 @@ impl BinaryEncoder<MessageChunk> for MessageChunk {
             Err(StatusCode::BadTcpMessageTooLarge)
         } else {
             // Now make a buffer to write the header and message into
-            let data = vec![0u8; message_size];
-            let mut stream = Cursor::new(data);
+            let mut data = BytesMut::with_capacity(message_size);
 
             // Write header to a buffer
-            let chunk_header_size = chunk_header.encode(&mut stream)?;
+            let chunk_header_size = chunk_header.encode(&mut (&mut data).writer())?;
             assert_eq!(chunk_header_size, MESSAGE_CHUNK_HEADER_SIZE);
-
-            // Get the data (with header written to it)
-            let mut data = stream.into_inner();
+            data.resize(message_size, 0);
 
             // Read remainder of stream into slice after the header
             let _ = in_stream.read_exact(&mut data[chunk_header_size..]);
@@ -183,6 +183,35 @@ impl BinaryEncoder<MessageChunk> for MessageChunk {
 }
 
 impl MessageChunk {
+    /// Makes a chunk out of bytes that hold a whole chunk, e.g. split off the buffer of a stream,
+    /// without copying them.
+    pub fn from_bytes(
+        data: BytesMut,
+        decoding_options: &DecodingOptions,
+    ) -> Result<MessageChunk, StatusCode> {
+        let chunk_header =
+            MessageChunkHeader::decode(&mut Cursor::new(&data[..]), decoding_options).map_err(
+                |err| {
+                    error!("Cannot decode chunk header {:?}", err);
+                    StatusCode::BadCommunicationError
+                },
+            )?;
+        let message_size = chunk_header.message_size as usize;
+        if decoding_options.max_message_size > 0 && message_size > decoding_options.max_message_size
+        {
+            Err(StatusCode::BadTcpMessageTooLarge)
+        } else if message_size != data.len() {
+            error!(
+                "Chunk has a message size of {} but holds {} bytes",
+                message_size,
+                data.len()
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(MessageChunk { data })
+        }
+    }
+
     pub fn new(
         sequence_number: u32,
         request_id: u32,
@@ -191,6 +220,43 @@ impl MessageChunk {
         secure_channel: &SecureChannel,
         data: &[u8],
     ) -> Result<MessageChunk, StatusCode> {
+        let mut header = Vec::new();
+        Self::encode_header(
+            sequence_number,
+            request_id,
+            message_type,
+            is_final,
+            secure_channel,
+            data.len(),
+            &mut header,
+        )?;
+        Ok(Self::assemble(&header, data, BytesMut::new()))
+    }
+
+    /// Assembles a chunk from headers made by `MessageChunk::encode_header()` and a body, in a
+    /// buffer that is handed in so its allocation can be reused, e.g. that of a chunk which has
+    /// been sent. Whatever the buffer held is replaced.
+    pub fn assemble(header: &[u8], body: &[u8], mut buffer: BytesMut) -> MessageChunk {
+        buffer.clear();
+        buffer.reserve(header.len() + body.len());
+        buffer.extend_from_slice(header);
+        buffer.extend_from_slice(body);
+        MessageChunk { data: buffer }
+    }
+
+    /// Encodes the chunk header, security header and sequence header of a chunk with a body of
+    /// `body_size` bytes, replacing the contents of the buffer. Writing the body straight after
+    /// the headers produces the same bytes as `MessageChunk::new()`, without the chunk being
+    /// assembled in memory first.
+    pub fn encode_header(
+        sequence_number: u32,
+        request_id: u32,
+        message_type: MessageChunkType,
+        is_final: MessageIsFinalType,
+        secure_channel: &SecureChannel,
+        body_size: usize,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), StatusCode> {
         // security header depends on message type
         let security_header = secure_channel.make_security_header(message_type);
         let sequence_header = SequenceHeader {
@@ -202,7 +268,7 @@ impl MessageChunk {
         let mut message_size = MESSAGE_CHUNK_HEADER_SIZE;
         message_size += security_header.byte_len();
         message_size += sequence_header.byte_len();
-        message_size += data.len();
+        message_size += body_size;
 
         trace!(
             "Creating a chunk with a size of {}, data excluding padding & signature",
@@ -216,19 +282,16 @@ impl MessageChunk {
             secure_channel_id,
         };
 
-        let mut stream = Cursor::new(vec![0u8; message_size]);
+        buffer.clear();
+        buffer.reserve(message_size);
+        let mut stream = Cursor::new(buffer);
         // write chunk header
-        let _ = chunk_header.encode(&mut stream);
+        chunk_header.encode(&mut stream)?;
         // write security header
-        let _ = security_header.encode(&mut stream);
+        security_header.encode(&mut stream)?;
         // write sequence header
-        let _ = sequence_header.encode(&mut stream);
-        // write message
-        let _ = stream.write(data);
-
-        Ok(MessageChunk {
-            data: stream.into_inner(),
-        })
+        sequence_header.encode(&mut stream)?;
+        Ok(())
     }
 
     /// Calculates the body size that fit inside of a message chunk of a particular size.
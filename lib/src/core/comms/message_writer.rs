@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
-use std::io::{Cursor, Write};
+use std::io::{Cursor, IoSlice, Write};
+
+use bytes::BytesMut;
 
 use crate::types::{status_code::StatusCode, BinaryEncoder, EncodingResult};
 
-use super::{chunker::Chunker, secure_channel::SecureChannel, tcp_types::AcknowledgeMessage};
+use super::{
+    chunker::Chunker, message_chunk::MessageChunk, secure_channel::SecureChannel,
+    tcp_types::AcknowledgeMessage,
+};
 
 use crate::core::supported_message::SupportedMessage;
 
@@ -15,34 +20,80 @@ const DEFAULT_SENT_SEQUENCE_NUMBER: u32 = 0;
 
 /// SocketWriter is a wrapper around the writable half of a tokio stream and a buffer which
 /// will be dumped into that stream.
+///
+/// The buffers the writer needs are allocated up front and reused for every message, so writing
+/// messages at a high rate does not allocate. A chunk that is secured is still copied once into
+/// the buffer that security is applied to, and `SecureChannel::apply_security()` pads it in a copy
+/// of its own, before the result is copied to the send buffer.
 pub struct MessageWriter {
     /// The send buffer
     buffer: Cursor<Vec<u8>>,
+    /// A send buffer that was yielded by `bytes_to_write()` and handed back by `recycle()`, to be
+    /// swapped in next time
+    spare_buffer: Vec<u8>,
+    /// Scratch buffer that a message is encoded into before it is split into chunks
+    message_buffer: Vec<u8>,
+    /// Scratch buffer for the headers of a chunk
+    header_buffer: Vec<u8>,
+    /// Scratch buffer that a chunk is assembled in before security is applied to it
+    chunk_buffer: BytesMut,
+    /// Scratch buffer that a chunk is signed / encrypted into
+    security_buffer: Vec<u8>,
     /// The last request id
     last_request_id: u32,
     /// Last sent sequence number
     last_sent_sequence_number: u32,
+    /// Maximum size of a chunk. Use 0 for no limit
+    max_chunk_size: usize,
     /// Maximum size of a message, total. Use 0 for no limit
     max_message_size: usize,
-    /// Maximum size of a chunk. Use 0 for no limit
+    /// Maximum number of chunks in a message. Use 0 for no limit
     max_chunk_count: usize,
 }
 
 impl MessageWriter {
+    /// Creates a writer that splits messages into chunks of at most `buffer_size` bytes.
     pub fn new(
         buffer_size: usize,
         max_message_size: usize,
         max_chunk_count: usize,
     ) -> MessageWriter {
         MessageWriter {
-            buffer: Cursor::new(vec![0u8; buffer_size]),
+            buffer: Cursor::new(Vec::with_capacity(buffer_size)),
+            spare_buffer: Vec::with_capacity(buffer_size),
+            message_buffer: Vec::with_capacity(buffer_size),
+            header_buffer: Vec::with_capacity(1024),
+            chunk_buffer: BytesMut::with_capacity(buffer_size),
+            security_buffer: vec![0u8; Self::security_buffer_size(buffer_size)],
             last_request_id: DEFAULT_REQUEST_ID,
             last_sent_sequence_number: DEFAULT_SENT_SEQUENCE_NUMBER,
+            max_chunk_size: buffer_size,
             max_message_size,
             max_chunk_count,
         }
     }
 
+    /// Sets the limits that the other end negotiated for the messages it receives, i.e. the size
+    /// of a chunk and the size and number of chunks of a message. 0 means no limit.
+    pub fn set_limits(
+        &mut self,
+        max_chunk_size: usize,
+        max_message_size: usize,
+        max_chunk_count: usize,
+    ) {
+        self.security_buffer
+            .resize(Self::security_buffer_size(max_chunk_size), 0);
+        self.max_chunk_size = max_chunk_size;
+        self.max_message_size = max_message_size;
+        self.max_chunk_count = max_chunk_count;
+    }
+
+    fn security_buffer_size(max_chunk_size: usize) -> usize {
+        // This allows the message to be encoded to a chunk with header + encoding which is just
+        // slightly larger in size (up to 1024 bytes).
+        max_chunk_size + 1024
+    }
+
     pub fn write_ack(&mut self, ack: &AcknowledgeMessage) -> EncodingResult<usize> {
         ack.encode(&mut self.buffer)
     }
@@ -57,64 +108,122 @@ impl MessageWriter {
     ) -> Result<u32, StatusCode> {
         trace!("Writing request to buffer");
         // Turn message to chunk(s)
-        let chunks = Chunker::encode(
-            self.last_sent_sequence_number + 1,
-            request_id,
+        let message_type = Chunker::encode_message(
             self.max_message_size,
-            0,
             secure_channel,
             &message,
+            &mut self.message_buffer,
+        )?;
+        let bodies = Chunker::chunk_bodies(
+            message_type,
+            self.max_chunk_size,
+            secure_channel,
+            &self.message_buffer,
         )?;
 
-        if self.max_chunk_count > 0 && chunks.len() > self.max_chunk_count {
+        if self.max_chunk_count > 0 && bodies.len() > self.max_chunk_count {
             error!(
                 "Cannot write message since {} chunks exceeds {} chunk limit",
-                chunks.len(),
+                bodies.len(),
                 self.max_chunk_count
             );
-            Err(StatusCode::BadCommunicationError)
+            Err(Chunker::too_large_status(secure_channel))
         } else {
             // Sequence number monotonically increases per chunk
-            self.last_sent_sequence_number += chunks.len() as u32;
+            let sequence_number = self.last_sent_sequence_number + 1;
+            self.last_sent_sequence_number += bodies.len() as u32;
 
             // Send chunks
-
-            // This max chunk size allows the message to be encoded to a chunk with header + encoding
-            // which is just slightly larger in size (up to 1024 bytes).
-            let data_buffer_size = self.buffer.get_ref().len() + 1024;
-            let mut data = vec![0u8; data_buffer_size];
-            for chunk in chunks {
-                trace!("Sending chunk {:?}", chunk);
-                let size = secure_channel.apply_security(&chunk, &mut data)?;
-                self.buffer.write(&data[..size]).map_err(|error| {
-                    error!(
-                        "Error while writing bytes to stream, connection broken, check error {:?}",
-                        error
+            let last = bodies.len().saturating_sub(1);
+            for (i, body) in bodies.into_iter().enumerate() {
+                let sequence_number = sequence_number + i as u32;
+                let is_final = Chunker::is_final(i, last);
+                MessageChunk::encode_header(
+                    sequence_number,
+                    request_id,
+                    message_type,
+                    is_final,
+                    secure_channel,
+                    body.len(),
+                    &mut self.header_buffer,
+                )?;
+                if secure_channel.should_apply_security() {
+                    let chunk = MessageChunk::assemble(
+                        &self.header_buffer,
+                        body,
+                        std::mem::take(&mut self.chunk_buffer),
                     );
-                    StatusCode::BadCommunicationError
-                })?;
+                    trace!("Sending chunk {:?}", chunk);
+                    let result = secure_channel.apply_security(&chunk, &mut self.security_buffer);
+                    // The chunk's buffer is kept for the next one
+                    self.chunk_buffer = chunk.data;
+                    let size = result?;
+                    Self::write_slices(
+                        &mut self.buffer,
+                        &[IoSlice::new(&self.security_buffer[..size])],
+                    )?;
+                } else {
+                    // Without security the chunk is just its headers followed by the body, so they
+                    // are written straight to the buffer without assembling the chunk first
+                    let size = self.header_buffer.len() + body.len();
+                    if size > self.security_buffer.len() {
+                        error!("The size of the message chunk {} exceeds the size of the destination buffer {}", size, self.security_buffer.len());
+                        return Err(StatusCode::BadEncodingLimitsExceeded);
+                    }
+                    trace!("Sending chunk of {} bytes", size);
+                    Self::write_slices(
+                        &mut self.buffer,
+                        &[IoSlice::new(&self.header_buffer), IoSlice::new(body)],
+                    )?;
+                }
             }
             trace!("Message written");
             Ok(request_id)
         }
     }
 
+    /// Writes all of the slices to the buffer in a single vectored write
+    fn write_slices(
+        buffer: &mut Cursor<Vec<u8>>,
+        slices: &[IoSlice<'_>],
+    ) -> Result<(), StatusCode> {
+        let size: usize = slices.iter().map(|s| s.len()).sum();
+        match buffer.write_vectored(slices) {
+            Ok(written) if written == size => Ok(()),
+            result => {
+                error!(
+                    "Error while writing bytes to stream, connection broken, check error {:?}",
+                    result
+                );
+                Err(StatusCode::BadCommunicationError)
+            }
+        }
+    }
+
     pub fn next_request_id(&mut self) -> u32 {
         self.last_request_id += 1;
         self.last_request_id
     }
 
-    /// Clears the buffer
-    fn clear(&mut self) {
-        self.buffer.set_position(0);
-    }
-
-    /// Yields any results to write, resetting the buffer back afterwards
+    /// Yields any results to write, resetting the buffer back afterwards. The bytes should be
+    /// handed back with `recycle()` once they are written so their allocation is reused.
     pub fn bytes_to_write(&mut self) -> Vec<u8> {
         let pos = self.buffer.position() as usize;
-        let result = (self.buffer.get_ref())[0..pos].to_vec();
+        // Swap in the spare buffer rather than copying out of the send buffer
+        let mut result = std::mem::take(self.buffer.get_mut());
+        result.truncate(pos);
+        *self.buffer.get_mut() = std::mem::take(&mut self.spare_buffer);
         // Buffer MUST be cleared here, otherwise races are possible
-        self.clear();
+        self.buffer.set_position(0);
         result
     }
+
+    /// Hands back bytes yielded by `bytes_to_write()` after they are written, so the next call
+    /// can reuse their allocation
+    pub fn recycle(&mut self, mut bytes: Vec<u8>) {
+        if bytes.capacity() > self.spare_buffer.capacity() {
+            bytes.clear();
+            self.spare_buffer = bytes;
+        }
+    }
 }
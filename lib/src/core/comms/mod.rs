@@ -5,7 +5,12 @@
 //! Contains all code related to sending / receiving messages from a transport
 //! and turning those messages into and out of chunks.
 
+pub mod capture;
 pub mod chunker;
+#[cfg(any(feature = "websocket", feature = "https"))]
+pub(crate) mod http;
+#[cfg(feature = "https")]
+pub mod https;
 pub mod message_chunk;
 pub mod message_chunk_info;
 pub mod message_writer;
@@ -14,13 +19,19 @@ pub mod security_header;
 pub mod tcp_codec;
 pub mod tcp_types;
 pub mod url;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 pub mod prelude {
     pub use super::chunker::*;
+    #[cfg(feature = "https")]
+    pub use super::https::*;
     pub use super::message_chunk::*;
     pub use super::secure_channel::*;
     pub use super::security_header::*;
     pub use super::tcp_codec::*;
     pub use super::tcp_types::*;
     pub use super::url::*;
+    #[cfg(feature = "websocket")]
+    pub use super::websocket::*;
 }
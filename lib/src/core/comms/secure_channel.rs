@@ -8,6 +8,7 @@ use std::{
     sync::Arc,
 };
 
+use bytes::BytesMut;
 use chrono::{Duration, TimeDelta};
 
 use crate::crypto::{
@@ -68,14 +69,23 @@ pub struct SecureChannel {
     local_keys: Option<(Vec<u8>, AesKey, Vec<u8>)>,
     /// Decoding options
     decoding_options: DecodingOptions,
+    /// Memory that decrypted chunks are taken from. It is reclaimed rather than allocated anew
+    /// once the chunks taken from it are dropped.
+    receive_buffer: BytesMut,
 }
 
 impl SecureChannel {
     /// For testing purposes only
     #[cfg(test)]
     pub fn new_no_certificate_store() -> SecureChannel {
+        Self::new_no_security(Role::Unknown, DecodingOptions::default())
+    }
+
+    /// Creates a channel without security or certificates, for carrying messages that are
+    /// protected by something else, e.g. the TLS session of an HTTPS connection.
+    pub fn new_no_security(role: Role, decoding_options: DecodingOptions) -> SecureChannel {
         SecureChannel {
-            role: Role::Unknown,
+            role,
             security_policy: SecurityPolicy::None,
             security_mode: MessageSecurityMode::None,
             secure_channel_id: 0,
@@ -89,7 +99,8 @@ impl SecureChannel {
             remote_cert: None,
             local_keys: None,
             remote_keys: None,
-            decoding_options: DecodingOptions::default(),
+            decoding_options,
+            receive_buffer: BytesMut::new(),
         }
     }
 
@@ -123,6 +134,7 @@ impl SecureChannel {
             local_keys: None,
             remote_keys: None,
             decoding_options,
+            receive_buffer: BytesMut::new(),
         }
     }
 
@@ -166,6 +178,14 @@ impl SecureChannel {
         self.security_policy = security_policy;
     }
 
+    /// Tests if chunks are signed, and possibly encrypted, by `apply_security()`. When they are not,
+    /// a chunk is sent exactly as it was encoded.
+    pub fn should_apply_security(&self) -> bool {
+        self.security_policy != SecurityPolicy::None
+            && (self.security_mode == MessageSecurityMode::Sign
+                || self.security_mode == MessageSecurityMode::SignAndEncrypt)
+    }
+
     pub fn clear_security_token(&mut self) {
         self.secure_channel_id = 0;
         self.token_id = 0;
@@ -226,8 +246,8 @@ impl SecureChannel {
             false
         } else {
             // Check if secure channel 75% close to expiration in which case send a renew
-            let renew_lifetime = (self.token_lifetime() * 3) / 4;
-            let renew_lifetime = TimeDelta::try_milliseconds(renew_lifetime as i64).unwrap();
+            let renew_lifetime = (self.token_lifetime() as i64 * 3) / 4;
+            let renew_lifetime = TimeDelta::milliseconds(renew_lifetime);
             // Renew the token?
             DateTime::now() - self.token_created_at() > renew_lifetime
         }
@@ -373,8 +393,8 @@ impl SecureChannel {
     /// Test if the token has expired yet
     pub fn token_has_expired(&self) -> bool {
         let token_created_at = self.token_created_at;
-        let token_expires =
-            token_created_at + TimeDelta::try_seconds(self.token_lifetime as i64).unwrap();
+        // The lifetime is in milliseconds
+        let token_expires = token_created_at + TimeDelta::milliseconds(self.token_lifetime as i64);
         DateTime::now().ge(&token_expires)
     }
 
@@ -555,10 +575,7 @@ impl SecureChannel {
         message_chunk: &MessageChunk,
         dst: &mut [u8],
     ) -> Result<usize, StatusCode> {
-        let size = if self.security_policy != SecurityPolicy::None
-            && (self.security_mode == MessageSecurityMode::Sign
-                || self.security_mode == MessageSecurityMode::SignAndEncrypt)
-        {
+        let size = if self.should_apply_security() {
             let chunk_info = message_chunk.chunk_info(self)?;
 
             // S - Message Header
@@ -600,6 +617,47 @@ impl SecureChannel {
         Ok(size)
     }
 
+    /// Decrypts and verifies the chunk if the mode / policy requires it. Unlike
+    /// `verify_and_remove_security()` a chunk that has no security to remove is handed back as it
+    /// is rather than copied.
+    pub fn remove_security(&mut self, chunk: MessageChunk) -> Result<MessageChunk, StatusCode> {
+        let message_header = chunk.message_header(&self.decoding_options)?;
+        if message_header.message_type.is_open_secure_channel()
+            || message_header.message_size as usize != chunk.data.len()
+            || self.should_verify_chunks()
+        {
+            self.verify_and_remove_security(&chunk.data)
+        } else {
+            Ok(chunk)
+        }
+    }
+
+    /// Tests if the symmetric chunks of the channel are signed and maybe encrypted
+    fn should_verify_chunks(&self) -> bool {
+        self.security_policy != SecurityPolicy::None
+            && (self.security_mode == MessageSecurityMode::Sign
+                || self.security_mode == MessageSecurityMode::SignAndEncrypt)
+    }
+
+    /// Takes a zeroed buffer of `size` bytes for a decrypted chunk out of the receive buffer
+    fn take_receive_buffer(&mut self, size: usize) -> BytesMut {
+        // Gets back the memory of earlier chunks if they have all been dropped
+        self.receive_buffer.reserve(size);
+        self.receive_buffer.resize(size, 0);
+        self.receive_buffer.split_to(size)
+    }
+
+    /// Writes the size of a decrypted chunk into its header and truncates it to that size
+    fn truncate_decrypted(
+        mut data: BytesMut,
+        message_size: usize,
+        decoding_options: &DecodingOptions,
+    ) -> Result<BytesMut, StatusCode> {
+        Self::update_message_size(&mut data[..], message_size, decoding_options)?;
+        data.truncate(message_size);
+        Ok(data)
+    }
+
     /// Decrypts and verifies the body data if the mode / policy requires it
     pub fn verify_and_remove_security(&mut self, src: &[u8]) -> Result<MessageChunk, StatusCode> {
         self.verify_and_remove_security_forensic(src, None)
@@ -673,7 +731,9 @@ impl SecureChannel {
                 }
                 SecurityPolicy::None => {
                     // Nothing to do
-                    return Ok(MessageChunk { data: src.to_vec() });
+                    return Ok(MessageChunk {
+                        data: BytesMut::from(src),
+                    });
                 }
                 _ => {}
             }
@@ -709,7 +769,7 @@ impl SecureChannel {
             let receiver_thumbprint = security_header.receiver_certificate_thumbprint;
             trace!("Receiver thumbprint = {:?}", receiver_thumbprint);
 
-            let mut decrypted_data = vec![0u8; message_size];
+            let mut decrypted_data = self.take_receive_buffer(message_size);
             let decrypted_size = self.asymmetric_decrypt_and_verify(
                 security_policy,
                 &verification_key,
@@ -720,15 +780,8 @@ impl SecureChannel {
                 &mut decrypted_data,
             )?;
 
-            Self::update_message_size_and_truncate(
-                decrypted_data,
-                decrypted_size,
-                &self.decoding_options,
-            )?
-        } else if self.security_policy != SecurityPolicy::None
-            && (self.security_mode == MessageSecurityMode::Sign
-                || self.security_mode == MessageSecurityMode::SignAndEncrypt)
-        {
+            Self::truncate_decrypted(decrypted_data, decrypted_size, &self.decoding_options)?
+        } else if self.should_verify_chunks() {
             // Symmetric decrypt and verify
             let signature_size = self.security_policy.symmetric_signature_size();
             let encrypted_range = encrypted_data_offset..message_size;
@@ -739,7 +792,7 @@ impl SecureChannel {
                 encrypted_range
             );
 
-            let mut decrypted_data = vec![0u8; message_size];
+            let mut decrypted_data = self.take_receive_buffer(message_size);
             let decrypted_size = self.symmetric_decrypt_and_verify(
                 src,
                 signed_range,
@@ -748,13 +801,13 @@ impl SecureChannel {
             )?;
 
             // Now we need to strip off signature
-            Self::update_message_size_and_truncate(
+            Self::truncate_decrypted(
                 decrypted_data,
                 decrypted_size - signature_size,
                 &self.decoding_options,
             )?
         } else {
-            src.to_vec()
+            BytesMut::from(src)
         };
 
         Ok(MessageChunk { data })
@@ -38,8 +38,8 @@ pub enum Message {
 }
 
 /// Implements a tokio codec that as close as possible, allows incoming data to be transformed into
-/// OPC UA message chunks with no intermediate buffers. Chunks are subsequently transformed into
-/// messages so there is still some buffers within message chunks, but not at the raw socket level.
+/// OPC UA message chunks with no intermediate buffers. Each chunk holds the bytes it was split off
+/// the read buffer with, and chunks are subsequently decoded into messages where they are.
 pub struct TcpCodec {
     decoding_options: DecodingOptions,
 }
@@ -63,14 +63,14 @@ impl Decoder for TcpCodec {
             // whole message to be extracted.
             let message_size = message_header.message_size as usize;
             if buf.len() >= message_size {
-                // Extract the message bytes from the buffer & decode them into a message
-                let mut buf = buf.split_to(message_size);
-                let message =
-                    Self::decode_message(message_header, &mut buf, &self.decoding_options)
-                        .map_err(|e| {
-                            error!("Codec got an error {} while decoding a message", e);
-                            io::Error::from(e)
-                        })?;
+                // Extract the message bytes from the buffer & decode them into a message. A chunk
+                // keeps the bytes it was split from rather than copying them.
+                let buf = buf.split_to(message_size);
+                let message = Self::decode_message(message_header, buf, &self.decoding_options)
+                    .map_err(|e| {
+                        error!("Codec got an error {} while decoding a message", e);
+                        io::Error::from(e)
+                    })?;
                 Ok(Some(message))
             } else {
                 // Not enough bytes
@@ -117,25 +117,25 @@ impl TcpCodec {
     /// Reads a message out of the buffer, which is assumed by now to be the proper length
     fn decode_message(
         message_header: MessageHeader,
-        buf: &mut BytesMut,
+        buf: BytesMut,
         decoding_options: &DecodingOptions,
     ) -> Result<Message, StatusCode> {
-        let mut buf = io::Cursor::new(&buf[..]);
+        let mut stream = io::Cursor::new(&buf[..]);
         match message_header.message_type {
             MessageType::Acknowledge => Ok(Message::Acknowledge(AcknowledgeMessage::decode(
-                &mut buf,
+                &mut stream,
                 decoding_options,
             )?)),
             MessageType::Hello => Ok(Message::Hello(HelloMessage::decode(
-                &mut buf,
+                &mut stream,
                 decoding_options,
             )?)),
             MessageType::Error => Ok(Message::Error(ErrorMessage::decode(
-                &mut buf,
+                &mut stream,
                 decoding_options,
             )?)),
-            MessageType::Chunk => Ok(Message::Chunk(MessageChunk::decode(
-                &mut buf,
+            MessageType::Chunk => Ok(Message::Chunk(MessageChunk::from_bytes(
+                buf,
                 decoding_options,
             )?)),
             MessageType::Invalid => {
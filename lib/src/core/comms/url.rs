@@ -9,13 +9,16 @@ use url::Url;
 use crate::types::status_code::StatusCode;
 
 pub const OPC_TCP_SCHEME: &str = "opc.tcp";
+pub const OPC_WSS_SCHEME: &str = "opc.wss";
+pub const OPC_HTTPS_SCHEME: &str = "https";
 
 /// Creates a `Url` from the input string, supplying a default port if necessary.
 fn opc_url_from_str(s: &str) -> Result<Url, ()> {
     Url::parse(s)
         .map(|mut url| {
-            if url.port().is_none() {
-                // If no port is supplied, then treat it as the default port 4840
+            if url.port_or_known_default().is_none() {
+                // If no port is supplied, then treat it as the default port 4840. An https url
+                // keeps its well known port of 443.
                 let _ = url.set_port(Some(crate::core::constants::DEFAULT_OPC_UA_SERVER_PORT));
             }
             url
@@ -65,8 +68,12 @@ pub fn server_url_from_endpoint_url(endpoint_url: &str) -> std::result::Result<S
     })
 }
 
+/// Tests if the url is one the client and server can connect through, i.e. an `opc.tcp` url or,
+/// with the `websocket` feature, an `opc.wss` url or, with the `https` feature, an `https` url.
 pub fn is_valid_opc_ua_url(url: &str) -> bool {
     is_opc_ua_binary_url(url)
+        || (cfg!(feature = "websocket") && is_opc_ua_websocket_url(url))
+        || (cfg!(feature = "https") && is_opc_ua_https_url(url))
 }
 
 pub fn is_opc_ua_binary_url(url: &str) -> bool {
@@ -77,6 +84,22 @@ pub fn is_opc_ua_binary_url(url: &str) -> bool {
     }
 }
 
+pub fn is_opc_ua_websocket_url(url: &str) -> bool {
+    if let Ok(url) = opc_url_from_str(url) {
+        url.scheme() == OPC_WSS_SCHEME
+    } else {
+        false
+    }
+}
+
+pub fn is_opc_ua_https_url(url: &str) -> bool {
+    if let Ok(url) = opc_url_from_str(url) {
+        url.scheme() == OPC_HTTPS_SCHEME
+    } else {
+        false
+    }
+}
+
 pub fn hostname_from_url(url: &str) -> Result<String, ()> {
     // Validate and split out the endpoint we have
     if let Ok(url) = Url::parse(url) {
@@ -94,11 +117,13 @@ pub fn hostname_port_from_url(url: &str, default_port: u16) -> Result<(String, u
     // Validate and split out the endpoint we have
     let url = Url::parse(url).map_err(|_| StatusCode::BadTcpEndpointUrlInvalid)?;
 
-    if url.scheme() != OPC_TCP_SCHEME || !url.has_host() {
+    if ![OPC_TCP_SCHEME, OPC_WSS_SCHEME, OPC_HTTPS_SCHEME].contains(&url.scheme())
+        || !url.has_host()
+    {
         Err(StatusCode::BadTcpEndpointUrlInvalid)
     } else {
         let host = url.host_str().unwrap();
-        let port = url.port().unwrap_or(default_port);
+        let port = url.port_or_known_default().unwrap_or(default_port);
         Ok((host.to_string(), port))
     }
 }
@@ -114,6 +139,19 @@ mod tests {
             "opc.tcp://[FEDC:BA98:7654:3210:FEDC:BA98:7654:3210]:80/xyz"
         ));
         assert!(!is_opc_ua_binary_url("http://foo/xyz"));
+        assert!(!is_opc_ua_binary_url("opc.wss://foo/xyz"));
+        assert!(is_opc_ua_websocket_url("opc.wss://foo/xyz"));
+        assert_eq!(
+            hostname_port_from_url("opc.wss://foo:4843/xyz", 4840).unwrap(),
+            ("foo".to_string(), 4843)
+        );
+        assert!(!is_opc_ua_binary_url("https://foo/xyz"));
+        assert!(is_opc_ua_https_url("https://foo/xyz"));
+        assert!(!is_opc_ua_https_url("http://foo/xyz"));
+        assert_eq!(
+            hostname_port_from_url("https://foo/xyz", 4840).unwrap(),
+            ("foo".to_string(), 443)
+        );
     }
 
     #[test]
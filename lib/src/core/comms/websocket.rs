@@ -0,0 +1,393 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the WebSocket mapping of the binary protocol, i.e. the `opc.wss` transport.
+//!
+//! The connection is upgraded from HTTP with the `opcua+uacp` protocol, after which every UACP
+//! chunk travels in a binary WebSocket message. The [`websocket_bridge`] function turns the frames
+//! back into a plain byte stream so the secure channel runs over a WebSocket exactly as it does over TCP.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tokio::io::{
+    self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream, ReadHalf, WriteHalf,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::core::comms::{
+    http::{has_token, http_header, io_error, read_http_head, write_http_head},
+    secure_channel::Role,
+    tcp_types::MESSAGE_HEADER_LEN,
+};
+use crate::crypto::random;
+use crate::types::status_code::StatusCode;
+
+/// The WebSocket sub protocol for the binary encoding
+pub const WEBSOCKET_PROTOCOL_UACP: &str = "opcua+uacp";
+
+/// Appended to the key of the client to produce the accept value of the server (RFC 6455)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The opcode of a WebSocket frame.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WebSocketOpCode {
+    Continuation = 0x0,
+    Text = 0x1,
+    Binary = 0x2,
+    Close = 0x8,
+    Ping = 0x9,
+    Pong = 0xa,
+}
+
+impl WebSocketOpCode {
+    fn from_u8(value: u8) -> Result<WebSocketOpCode, StatusCode> {
+        match value {
+            0x0 => Ok(WebSocketOpCode::Continuation),
+            0x1 => Ok(WebSocketOpCode::Text),
+            0x2 => Ok(WebSocketOpCode::Binary),
+            0x8 => Ok(WebSocketOpCode::Close),
+            0x9 => Ok(WebSocketOpCode::Ping),
+            0xa => Ok(WebSocketOpCode::Pong),
+            _ => {
+                error!("WebSocket frame has an unknown opcode {}", value);
+                Err(StatusCode::BadCommunicationError)
+            }
+        }
+    }
+
+    fn is_control(self) -> bool {
+        matches!(
+            self,
+            WebSocketOpCode::Close | WebSocketOpCode::Ping | WebSocketOpCode::Pong
+        )
+    }
+}
+
+/// A single WebSocket frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebSocketFrame {
+    /// Set on the final frame of a message
+    pub fin: bool,
+    pub opcode: WebSocketOpCode,
+    pub payload: Vec<u8>,
+}
+
+impl WebSocketFrame {
+    /// Creates a frame that is a whole message.
+    pub fn new(opcode: WebSocketOpCode, payload: Vec<u8>) -> WebSocketFrame {
+        WebSocketFrame {
+            fin: true,
+            opcode,
+            payload,
+        }
+    }
+
+    /// Encodes the frame, masking the payload with the key if one is supplied. A client must mask
+    /// every frame it sends and a server must not mask any.
+    pub fn encode(&self, mask: Option<[u8; 4]>) -> Vec<u8> {
+        let len = self.payload.len();
+        let mut data = Vec::with_capacity(len + 14);
+        data.push(if self.fin { 0x80 } else { 0 } | self.opcode as u8);
+        let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+        if len < 126 {
+            data.push(mask_bit | len as u8);
+        } else if len <= u16::MAX as usize {
+            data.push(mask_bit | 126);
+            data.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            data.push(mask_bit | 127);
+            data.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+        if let Some(mask) = mask {
+            data.extend_from_slice(&mask);
+            data.extend(
+                self.payload
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| b ^ mask[i % 4]),
+            );
+        } else {
+            data.extend_from_slice(&self.payload);
+        }
+        data
+    }
+
+    /// Reads the next frame from the stream. Frames from a client must be masked and frames from
+    /// a server must not be, so `masked` says which is expected. Payloads larger than
+    /// `max_payload_len` are rejected.
+    pub async fn read<R>(
+        reader: &mut R,
+        masked: bool,
+        max_payload_len: usize,
+    ) -> Result<WebSocketFrame, StatusCode>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut header = [0u8; 2];
+        reader.read_exact(&mut header).await.map_err(io_error)?;
+        if header[0] & 0x70 != 0 {
+            error!("WebSocket frame has reserved bits set but no extension was negotiated");
+            return Err(StatusCode::BadCommunicationError);
+        }
+        let fin = header[0] & 0x80 != 0;
+        let opcode = WebSocketOpCode::from_u8(header[0] & 0x0f)?;
+        if (header[1] & 0x80 != 0) != masked {
+            error!(
+                "WebSocket frame is {}masked when it should {}be",
+                if masked { "not " } else { "" },
+                if masked { "" } else { "not " }
+            );
+            return Err(StatusCode::BadCommunicationError);
+        }
+        let len = match header[1] & 0x7f {
+            126 => u64::from(reader.read_u16().await.map_err(io_error)?),
+            127 => reader.read_u64().await.map_err(io_error)?,
+            len => u64::from(len),
+        };
+        if opcode.is_control() && (!fin || len > 125) {
+            error!("WebSocket control frame is fragmented or too long");
+            return Err(StatusCode::BadCommunicationError);
+        }
+        if len > max_payload_len as u64 {
+            error!(
+                "WebSocket frame of {} bytes exceeds the limit of {} bytes",
+                len, max_payload_len
+            );
+            return Err(StatusCode::BadTcpMessageTooLarge);
+        }
+        let mut mask = [0u8; 4];
+        if masked {
+            reader.read_exact(&mut mask).await.map_err(io_error)?;
+        }
+        let mut payload = vec![0u8; len as usize];
+        reader.read_exact(&mut payload).await.map_err(io_error)?;
+        if masked {
+            payload
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, b)| *b ^= mask[i % 4]);
+        }
+        Ok(WebSocketFrame {
+            fin,
+            opcode,
+            payload,
+        })
+    }
+}
+
+/// Returns the `Sec-WebSocket-Accept` value a server answers the `Sec-WebSocket-Key` of a
+/// client with.
+pub fn websocket_accept_key(key: &str) -> String {
+    let digest = openssl::sha::sha1(format!("{}{}", key.trim(), WEBSOCKET_GUID).as_bytes());
+    STANDARD.encode(digest)
+}
+
+/// Performs the server side of the handshake, reading the upgrade request of the client and
+/// answering it. A request that isn't a WebSocket upgrade for the binary protocol is answered
+/// with an error and the connection is rejected.
+pub async fn websocket_server_handshake<S>(stream: &mut S) -> Result<(), StatusCode>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let head = read_http_head(stream).await?;
+    let key = {
+        let request_line = head.split("\r\n").next().unwrap_or_default();
+        let protocol = http_header(&head, "Sec-WebSocket-Protocol");
+        if !request_line.starts_with("GET ") {
+            Err("request is not a GET")
+        } else if !has_token(http_header(&head, "Upgrade"), "websocket")
+            || !has_token(http_header(&head, "Connection"), "upgrade")
+        {
+            Err("request is not a WebSocket upgrade")
+        } else if http_header(&head, "Sec-WebSocket-Version") != Some("13") {
+            Err("WebSocket version is not supported")
+        } else if protocol.is_some() && !has_token(protocol, WEBSOCKET_PROTOCOL_UACP) {
+            Err("request does not offer the opcua+uacp protocol")
+        } else {
+            http_header(&head, "Sec-WebSocket-Key").ok_or("request has no key")
+        }
+    };
+    match key {
+        Ok(key) => {
+            let response = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\nSec-WebSocket-Protocol: {}\r\n\r\n",
+                websocket_accept_key(key),
+                WEBSOCKET_PROTOCOL_UACP
+            );
+            write_http_head(stream, &response).await
+        }
+        Err(reason) => {
+            error!("WebSocket handshake was rejected, {}", reason);
+            let response =
+                "HTTP/1.1 400 Bad Request\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+            write_http_head(stream, response).await?;
+            Err(StatusCode::BadConnectionRejected)
+        }
+    }
+}
+
+/// Performs the client side of the handshake, asking the server at `host` to upgrade the
+/// request for `path` to a WebSocket with the binary protocol.
+pub async fn websocket_client_handshake<S>(
+    stream: &mut S,
+    host: &str,
+    path: &str,
+) -> Result<(), StatusCode>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let key = STANDARD.encode(random::byte_string(16).as_ref());
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Protocol: {}\r\n\r\n",
+        if path.is_empty() { "/" } else { path },
+        host,
+        key,
+        WEBSOCKET_PROTOCOL_UACP
+    );
+    write_http_head(stream, &request).await?;
+
+    let head = read_http_head(stream).await?;
+    let status_line = head.split("\r\n").next().unwrap_or_default();
+    let protocol = http_header(&head, "Sec-WebSocket-Protocol");
+    if status_line.split(' ').nth(1) != Some("101") {
+        error!("Server did not upgrade to a WebSocket, {}", status_line);
+        Err(StatusCode::BadConnectionRejected)
+    } else if http_header(&head, "Sec-WebSocket-Accept") != Some(&websocket_accept_key(&key)) {
+        error!("Server answered the WebSocket handshake with the wrong accept value");
+        Err(StatusCode::BadConnectionRejected)
+    } else if protocol.is_some_and(|p| !p.eq_ignore_ascii_case(WEBSOCKET_PROTOCOL_UACP)) {
+        error!(
+            "Server chose an unsupported WebSocket protocol {:?}",
+            protocol
+        );
+        Err(StatusCode::BadConnectionRejected)
+    } else {
+        Ok(())
+    }
+}
+
+/// Turns a WebSocket that has completed its handshake into a byte stream. Spawns a task that
+/// unwraps the payloads of incoming frames into the returned stream and sends each UACP chunk
+/// written to the stream as one binary message. Pings are answered and a close from the other
+/// end closes the stream. Dropping the returned stream closes the WebSocket.
+///
+/// The role is the end of the connection this is, which decides if frames are masked. Chunks
+/// and frames are limited to `max_chunk_size` bytes.
+pub fn websocket_bridge<S>(stream: S, role: Role, max_chunk_size: usize) -> DuplexStream
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (local, remote) = io::duplex(max_chunk_size.max(MESSAGE_HEADER_LEN));
+    tokio::spawn(async move {
+        let (ws_read, ws_write) = io::split(stream);
+        let (remote_read, remote_write) = io::split(remote);
+        let (control_tx, control_rx) = unbounded_channel();
+        let is_client = role == Role::Client;
+        let result = tokio::select! {
+            result = read_frames(ws_read, remote_write, control_tx, !is_client, max_chunk_size) => result,
+            result = write_frames(remote_read, ws_write, control_rx, is_client, max_chunk_size) => result,
+        };
+        if let Err(status_code) = result {
+            debug!("WebSocket closed with status {}", status_code);
+        }
+    });
+    local
+}
+
+/// Reads frames from the WebSocket and writes their payloads to the stream, passing control
+/// frames that need an answer to the writer.
+async fn read_frames<S>(
+    mut reader: ReadHalf<S>,
+    mut writer: WriteHalf<DuplexStream>,
+    control_tx: UnboundedSender<WebSocketFrame>,
+    masked: bool,
+    max_payload_len: usize,
+) -> Result<(), StatusCode>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    loop {
+        let frame = WebSocketFrame::read(&mut reader, masked, max_payload_len).await?;
+        match frame.opcode {
+            WebSocketOpCode::Binary | WebSocketOpCode::Continuation => {
+                writer.write_all(&frame.payload).await.map_err(io_error)?;
+            }
+            WebSocketOpCode::Text => {
+                error!("WebSocket text messages are not supported by the binary protocol");
+                let close =
+                    WebSocketFrame::new(WebSocketOpCode::Close, 1003u16.to_be_bytes().to_vec());
+                let _ = control_tx.send(close);
+                std::future::pending::<()>().await;
+            }
+            WebSocketOpCode::Ping => {
+                let _ = control_tx.send(WebSocketFrame::new(WebSocketOpCode::Pong, frame.payload));
+            }
+            WebSocketOpCode::Pong => {}
+            WebSocketOpCode::Close => {
+                // Echo the close, the writer ends once it is sent
+                let _ = control_tx.send(WebSocketFrame::new(WebSocketOpCode::Close, frame.payload));
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+/// Reads UACP chunks from the stream and sends each in a binary message, along with any control
+/// frames from the reader. Sends a close when the stream ends.
+async fn write_frames<S>(
+    mut reader: ReadHalf<DuplexStream>,
+    mut writer: WriteHalf<S>,
+    mut control_rx: UnboundedReceiver<WebSocketFrame>,
+    masked: bool,
+    max_chunk_size: usize,
+) -> Result<(), StatusCode>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let mask = || {
+        let mut mask = [0u8; 4];
+        random::bytes(&mut mask);
+        mask
+    };
+    let mut buffer = Vec::with_capacity(max_chunk_size);
+    loop {
+        let frame = tokio::select! {
+            frame = control_rx.recv() => frame,
+            read = reader.read_buf(&mut buffer) => {
+                if read.map_err(io_error)? == 0 {
+                    Some(WebSocketFrame::new(WebSocketOpCode::Close, 1000u16.to_be_bytes().to_vec()))
+                } else {
+                    None
+                }
+            }
+        };
+        if let Some(frame) = frame {
+            let data = frame.encode(masked.then(mask));
+            writer.write_all(&data).await.map_err(io_error)?;
+            writer.flush().await.map_err(io_error)?;
+            if frame.opcode == WebSocketOpCode::Close {
+                let _ = writer.shutdown().await;
+                return Ok(());
+            }
+        }
+        // Send every whole chunk that has been written
+        while buffer.len() >= MESSAGE_HEADER_LEN {
+            let chunk_len = u32::from_le_bytes(buffer[4..8].try_into().unwrap()) as usize;
+            if chunk_len < MESSAGE_HEADER_LEN || chunk_len > max_chunk_size {
+                error!(
+                    "Chunk of {} bytes cannot be sent in a WebSocket message",
+                    chunk_len
+                );
+                return Err(StatusCode::BadTcpMessageTooLarge);
+            }
+            if buffer.len() < chunk_len {
+                break;
+            }
+            let chunk = buffer.drain(..chunk_len).collect();
+            let data =
+                WebSocketFrame::new(WebSocketOpCode::Binary, chunk).encode(masked.then(mask));
+            writer.write_all(&data).await.map_err(io_error)?;
+            writer.flush().await.map_err(io_error)?;
+        }
+    }
+}
@@ -2,19 +2,85 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
+use std::error::Error;
+use std::fmt;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
 use std::result::Result;
 
 use serde;
-use serde_yaml;
+use serde_yaml::{self, Value};
 
 use crate::types::{
     service_types::{ApplicationDescription, ApplicationType},
     LocalizedText, UAString,
 };
 
+/// Environment variables that override the same value of both client and server configs
+const SHARED_ENV_ALIASES: &[(&str, &str)] = &[
+    ("OPCUA_CERT_PATH", "CERTIFICATE_PATH"),
+    ("OPCUA_PRIVATE_KEY_PATH", "PRIVATE_KEY_PATH"),
+    ("OPCUA_PKI_DIR", "PKI_DIR"),
+];
+
+/// The reason that the overrides of [`Config::with_overrides`] could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigOverrideError {
+    /// The config could not be serialized to apply the overrides to it
+    Serialize(String),
+    /// The value of a variable does not fit the value of the config that it overrides
+    InvalidValue {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ConfigOverrideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialize(reason) => write!(f, "Cannot serialize configuration, {}", reason),
+            Self::InvalidValue {
+                name,
+                value,
+                reason,
+            } => write!(f, "Invalid value \"{}\" for {}, {}", value, name, reason),
+        }
+    }
+}
+
+impl Error for ConfigOverrideError {}
+
+/// Sets the value at a path like `LIMITS__MAX_SESSIONS` of a serialized config. Names are matched
+/// regardless of case, and values that are missing, e.g. optional ones, are added in lower case.
+fn set_override(config: &mut Value, path: &str, value: &str) {
+    let mut target = config;
+    for name in path.split("__") {
+        if target.is_null() {
+            *target = Value::Mapping(Default::default());
+        }
+        let Value::Mapping(mapping) = target else {
+            warn!("Configuration value {} cannot be overridden", path);
+            return;
+        };
+        let key = mapping
+            .keys()
+            .find(|k| k.as_str().is_some_and(|k| k.eq_ignore_ascii_case(name)))
+            .cloned()
+            .unwrap_or_else(|| {
+                warn!("Configuration has no value {}, adding it", path);
+                Value::String(name.to_lowercase())
+            });
+        target = mapping.entry(key).or_insert(Value::Null);
+    }
+    *target = if target.is_string() {
+        Value::String(value.to_string())
+    } else {
+        serde_yaml::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()))
+    };
+}
+
 /// A trait that handles the loading / saving and validity of configuration information for a
 /// client and/or server.
 pub trait Config: serde::Serialize {
@@ -64,6 +130,95 @@ pub trait Config: serde::Serialize {
         }
     }
 
+    /// Prefix of the environment variables that override values of the config, e.g.
+    /// `OPCUA_SERVER_`. The rest of the name is the path to the value in upper case, with `__`
+    /// between the names of nested values, e.g. `OPCUA_SERVER_LIMITS__MAX_SESSIONS`.
+    const ENV_PREFIX: &'static str;
+
+    /// Shorter names for values that are often overridden, e.g. `HOST` for `TCP_CONFIG__HOST`.
+    /// They follow the `ENV_PREFIX` too.
+    const ENV_ALIASES: &'static [(&'static str, &'static str)] = &[];
+
+    /// Overrides values of the config with those of the process's environment variables. See
+    /// [`Config::with_overrides`].
+    fn with_env_overrides(self) -> Result<Self, ConfigOverrideError>
+    where
+        for<'de> Self: Sized + serde::Deserialize<'de>,
+    {
+        self.with_overrides(std::env::vars())
+    }
+
+    /// Overrides values of the config with variables named like environment variables. Variables
+    /// that are shared by client and server, i.e. `OPCUA_CERT_PATH`, `OPCUA_PRIVATE_KEY_PATH` and
+    /// `OPCUA_PKI_DIR`, are applied first, then the variables that begin with `ENV_PREFIX`, so the
+    /// latter win. Each value is read as YAML, e.g. `4855`, `true` or `[a, b]`, unless it replaces
+    /// a string. Variables that don't begin with the prefix are ignored. The error names the
+    /// variable whose value does not fit the config.
+    fn with_overrides<I, K, V>(self, vars: I) -> Result<Self, ConfigOverrideError>
+    where
+        for<'de> Self: Sized + serde::Deserialize<'de>,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut shared = Vec::new();
+        let mut prefixed = Vec::new();
+        for (name, value) in vars {
+            let name = name.as_ref();
+            if let Some((_, path)) = SHARED_ENV_ALIASES.iter().find(|(alias, _)| *alias == name) {
+                shared.push((
+                    name.to_string(),
+                    path.to_string(),
+                    value.as_ref().to_string(),
+                ));
+            } else if let Some(key) = name.strip_prefix(Self::ENV_PREFIX) {
+                let path = Self::ENV_ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == key)
+                    .map(|(_, path)| path.to_string())
+                    .unwrap_or_else(|| key.to_string());
+                prefixed.push((name.to_string(), path, value.as_ref().to_string()));
+            }
+        }
+        if shared.is_empty() && prefixed.is_empty() {
+            return Ok(self);
+        }
+        prefixed.sort();
+
+        let base = serde_yaml::to_value(&self)
+            .map_err(|err| ConfigOverrideError::Serialize(err.to_string()))?;
+        let overrides = shared.into_iter().chain(prefixed).collect::<Vec<_>>();
+        let mut config = base.clone();
+        for (name, path, value) in &overrides {
+            info!("Configuration value {} is overridden by {}", path, name);
+            set_override(&mut config, path, value);
+        }
+        serde_yaml::from_value(config).map_err(|err| {
+            // Blame the first variable that does not fit the config by itself. Variables that
+            // only fit together, e.g. the fields of a new user token, are all applied at once
+            // above, so the last one is blamed when no single variable is to blame.
+            let (name, value, reason) = overrides
+                .iter()
+                .find_map(|(name, path, value)| {
+                    let mut config = base.clone();
+                    set_override(&mut config, path, value);
+                    serde_yaml::from_value::<Self>(config)
+                        .err()
+                        .map(|err| (name, value, err))
+                })
+                .or_else(|| {
+                    let (name, _, value) = overrides.last()?;
+                    Some((name, value, err))
+                })
+                .unwrap();
+            ConfigOverrideError::InvalidValue {
+                name: name.clone(),
+                value: value.clone(),
+                reason: reason.to_string(),
+            }
+        })
+    }
+
     fn is_valid(&self) -> bool;
 
     fn application_name(&self) -> UAString;
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
-use std::{collections::BTreeSet, sync::Arc};
+use std::{any::Any, collections::BTreeSet, future::Future, panic::AssertUnwindSafe, sync::Arc};
+
+use futures::FutureExt;
 
 use crate::sync::*;
 use crate::trace_lock;
+use crate::types::status_code::StatusCode;
 
 /// The `Runtime` is for debugging / diagnostics purposes and tracks which substantial system objects
 /// components are in existence. It can be used to detect if something has shutdown or not.
@@ -52,3 +55,34 @@ impl Runtime {
         }
     }
 }
+
+/// Runs a background task to completion, catching a panic inside it. A panic is logged and turned
+/// into a `BadInternalError` so the owner of the task can treat it like any other failure instead
+/// of the task silently disappearing.
+pub async fn catch_panic<F>(task_name: &str, task: F) -> Result<F::Output, StatusCode>
+where
+    F: Future,
+{
+    AssertUnwindSafe(task)
+        .catch_unwind()
+        .await
+        .map_err(|payload| {
+            error!(
+                "Task \"{}\" panicked: {}",
+                task_name,
+                panic_message(payload.as_ref())
+            );
+            StatusCode::BadInternalError
+        })
+}
+
+/// Extracts the message from the payload of a panic, if it has one.
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    } else {
+        "unknown panic"
+    }
+}
@@ -19,8 +19,8 @@ pub use crate::core::comms::tcp_types::AcknowledgeMessage;
 /// The first form just handles the trailing comma after the last entry to save some pointless
 /// editing when new messages are added to the list.
 macro_rules! supported_messages_enum {
-    [ $( $x:ident, ) * ] => (supported_messages_enum![ $( $x ),* ];);
-    [ $( $x:ident ), * ] => {
+    [ $( $(#[$m:meta])* $x:ident, ) * ] => (supported_messages_enum![ $( $(#[$m])* $x ),* ];);
+    [ $( $(#[$m:meta])* $x:ident ), * ] => {
         #[derive(Debug, PartialEq, Clone)]
         pub enum SupportedMessage {
             /// An invalid request / response of some form
@@ -28,7 +28,7 @@ macro_rules! supported_messages_enum {
             /// Acknowledge message
             AcknowledgeMessage(Box<AcknowledgeMessage>),
             /// Other messages
-            $( $x(Box<$x>), )*
+            $( $(#[$m])* $x(Box<$x>), )*
         }
 
         impl BinaryEncoder <SupportedMessage> for SupportedMessage {
@@ -38,7 +38,7 @@ macro_rules! supported_messages_enum {
                         panic!("Unsupported message byte_len {:?}", object_id);
                     },
                     SupportedMessage::AcknowledgeMessage(value) => value.byte_len(),
-                    $( SupportedMessage::$x(value) => value.byte_len(), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.byte_len(), )*
                 }
             }
 
@@ -48,7 +48,7 @@ macro_rules! supported_messages_enum {
                         panic!("Unsupported message encode {:?}", object_id);
                     },
                     SupportedMessage::AcknowledgeMessage(value) => value.encode(stream),
-                    $( SupportedMessage::$x(value) => value.encode(stream), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.encode(stream), )*
                 }
             }
 
@@ -63,6 +63,7 @@ macro_rules! supported_messages_enum {
         }
 
         $(
+        $(#[$m])*
         impl Into<SupportedMessage> for $x {
             fn into(self) -> SupportedMessage { SupportedMessage::$x(Box::new(self)) }
         }
@@ -77,7 +78,16 @@ macro_rules! supported_messages_enum {
                     SupportedMessage::AcknowledgeMessage(value) => {
                         panic!("Unsupported message node_id {:?}", value);
                     },
-                    $( SupportedMessage::$x(value) => value.object_id().into(), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.object_id().into(), )*
+                }
+            }
+
+            /// The name of the message's type, e.g. `ReadRequest`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    SupportedMessage::Invalid(_) => "Invalid",
+                    SupportedMessage::AcknowledgeMessage(_) => "AcknowledgeMessage",
+                    $( $(#[$m])* SupportedMessage::$x(_) => stringify!($x), )*
                 }
             }
         }
@@ -107,9 +117,13 @@ impl SupportedMessage {
             SupportedMessage::CloseSessionRequest(_) => true,
             SupportedMessage::CancelRequest(_) => true,
             SupportedMessage::ActivateSessionRequest(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddNodesRequest(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddReferencesRequest(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteNodesRequest(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteReferencesRequest(_) => true,
             SupportedMessage::CreateMonitoredItemsRequest(_) => true,
             SupportedMessage::ModifyMonitoredItemsRequest(_) => true,
@@ -121,7 +135,9 @@ impl SupportedMessage {
             SupportedMessage::DeleteSubscriptionsRequest(_) => true,
             SupportedMessage::TransferSubscriptionsRequest(_) => true,
             SupportedMessage::SetPublishingModeRequest(_) => true,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryFirstRequest(_) => true,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryNextRequest(_) => true,
             SupportedMessage::BrowseRequest(_) => true,
             SupportedMessage::BrowseNextRequest(_) => true,
@@ -151,9 +167,13 @@ impl SupportedMessage {
             SupportedMessage::CloseSessionRequest(r) => &r.request_header,
             SupportedMessage::CancelRequest(r) => &r.request_header,
             SupportedMessage::ActivateSessionRequest(r) => &r.request_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddNodesRequest(r) => &r.request_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddReferencesRequest(r) => &r.request_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteNodesRequest(r) => &r.request_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteReferencesRequest(r) => &r.request_header,
             SupportedMessage::CreateMonitoredItemsRequest(r) => &r.request_header,
             SupportedMessage::ModifyMonitoredItemsRequest(r) => &r.request_header,
@@ -165,7 +185,9 @@ impl SupportedMessage {
             SupportedMessage::DeleteSubscriptionsRequest(r) => &r.request_header,
             SupportedMessage::TransferSubscriptionsRequest(r) => &r.request_header,
             SupportedMessage::SetPublishingModeRequest(r) => &r.request_header,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryFirstRequest(r) => &r.request_header,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryNextRequest(r) => &r.request_header,
             SupportedMessage::BrowseRequest(r) => &r.request_header,
             SupportedMessage::BrowseNextRequest(r) => &r.request_header,
@@ -196,9 +218,13 @@ impl SupportedMessage {
             SupportedMessage::CloseSessionResponse(_) => true,
             SupportedMessage::CancelResponse(_) => true,
             SupportedMessage::ActivateSessionResponse(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddNodesResponse(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddReferencesResponse(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteNodesResponse(_) => true,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteReferencesResponse(_) => true,
             SupportedMessage::CreateMonitoredItemsResponse(_) => true,
             SupportedMessage::ModifyMonitoredItemsResponse(_) => true,
@@ -210,7 +236,9 @@ impl SupportedMessage {
             SupportedMessage::DeleteSubscriptionsResponse(_) => true,
             SupportedMessage::TransferSubscriptionsResponse(_) => true,
             SupportedMessage::SetPublishingModeResponse(_) => true,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryFirstResponse(_) => true,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryNextResponse(_) => true,
             SupportedMessage::BrowseResponse(_) => true,
             SupportedMessage::BrowseNextResponse(_) => true,
@@ -241,9 +269,13 @@ impl SupportedMessage {
             SupportedMessage::CloseSessionResponse(r) => &r.response_header,
             SupportedMessage::CancelResponse(r) => &r.response_header,
             SupportedMessage::ActivateSessionResponse(r) => &r.response_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddNodesResponse(r) => &r.response_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddReferencesResponse(r) => &r.response_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteNodesResponse(r) => &r.response_header,
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteReferencesResponse(r) => &r.response_header,
             SupportedMessage::CreateMonitoredItemsResponse(r) => &r.response_header,
             SupportedMessage::ModifyMonitoredItemsResponse(r) => &r.response_header,
@@ -255,7 +287,9 @@ impl SupportedMessage {
             SupportedMessage::DeleteSubscriptionsResponse(r) => &r.response_header,
             SupportedMessage::TransferSubscriptionsResponse(r) => &r.response_header,
             SupportedMessage::SetPublishingModeResponse(r) => &r.response_header,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryFirstResponse(r) => &r.response_header,
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryNextResponse(r) => &r.response_header,
             SupportedMessage::BrowseResponse(r) => &r.response_header,
             SupportedMessage::BrowseNextResponse(r) => &r.response_header,
@@ -343,27 +377,35 @@ impl SupportedMessage {
             ObjectId::ActivateSessionResponse_Encoding_DefaultBinary => {
                 ActivateSessionResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::AddNodesRequest_Encoding_DefaultBinary => {
                 AddNodesRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::AddNodesResponse_Encoding_DefaultBinary => {
                 AddNodesResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::AddReferencesRequest_Encoding_DefaultBinary => {
                 AddReferencesRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::AddReferencesResponse_Encoding_DefaultBinary => {
                 AddReferencesResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::DeleteNodesRequest_Encoding_DefaultBinary => {
                 DeleteNodesRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::DeleteNodesResponse_Encoding_DefaultBinary => {
                 DeleteNodesResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::DeleteReferencesRequest_Encoding_DefaultBinary => {
                 DeleteReferencesRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "node-management-services")]
             ObjectId::DeleteReferencesResponse_Encoding_DefaultBinary => {
                 DeleteReferencesResponse::decode(stream, decoding_options)?.into()
             }
@@ -427,15 +469,19 @@ impl SupportedMessage {
             ObjectId::SetPublishingModeResponse_Encoding_DefaultBinary => {
                 SetPublishingModeResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "query-services")]
             ObjectId::QueryFirstRequest_Encoding_DefaultBinary => {
                 QueryFirstRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "query-services")]
             ObjectId::QueryFirstResponse_Encoding_DefaultBinary => {
                 QueryFirstResponse::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "query-services")]
             ObjectId::QueryNextRequest_Encoding_DefaultBinary => {
                 QueryNextRequest::decode(stream, decoding_options)?.into()
             }
+            #[cfg(feature = "query-services")]
             ObjectId::QueryNextResponse_Encoding_DefaultBinary => {
                 QueryNextResponse::decode(stream, decoding_options)?.into()
             }
@@ -543,13 +589,21 @@ supported_messages_enum![
     CancelResponse,
     ActivateSessionRequest,
     ActivateSessionResponse,
+    #[cfg(feature = "node-management-services")]
     AddNodesRequest,
+    #[cfg(feature = "node-management-services")]
     AddNodesResponse,
+    #[cfg(feature = "node-management-services")]
     AddReferencesRequest,
+    #[cfg(feature = "node-management-services")]
     AddReferencesResponse,
+    #[cfg(feature = "node-management-services")]
     DeleteNodesRequest,
+    #[cfg(feature = "node-management-services")]
     DeleteNodesResponse,
+    #[cfg(feature = "node-management-services")]
     DeleteReferencesRequest,
+    #[cfg(feature = "node-management-services")]
     DeleteReferencesResponse,
     CreateMonitoredItemsRequest,
     CreateMonitoredItemsResponse,
@@ -571,9 +625,13 @@ supported_messages_enum![
     TransferSubscriptionsResponse,
     SetPublishingModeRequest,
     SetPublishingModeResponse,
+    #[cfg(feature = "query-services")]
     QueryFirstRequest,
+    #[cfg(feature = "query-services")]
     QueryFirstResponse,
+    #[cfg(feature = "query-services")]
     QueryNextRequest,
+    #[cfg(feature = "query-services")]
     QueryNextResponse,
     BrowseRequest,
     BrowseResponse,
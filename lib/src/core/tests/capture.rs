@@ -0,0 +1,156 @@
+use std::io::Write;
+
+use tempdir::TempDir;
+
+use crate::{
+    core::{
+        comms::{
+            capture::*, chunker::Chunker, secure_channel::SecureChannel, tcp_types::MIN_CHUNK_SIZE,
+        },
+        supported_message::SupportedMessage,
+        tests::*,
+    },
+    types::DecodingOptions,
+};
+
+fn make_read_response() -> SupportedMessage {
+    let results = (0..1000).map(|i| DataValue::new_now(i as u32)).collect();
+    ReadResponse {
+        response_header: ResponseHeader::null(),
+        results: Some(results),
+        diagnostic_infos: None,
+    }
+    .into()
+}
+
+/// Encodes a message into the bytes that a stream would carry
+fn message_bytes(request_id: u32, message: &SupportedMessage) -> Vec<u8> {
+    let secure_channel = SecureChannel::new_no_certificate_store();
+    let chunks =
+        Chunker::encode(1, request_id, 0, MIN_CHUNK_SIZE, &secure_channel, message).unwrap();
+    assert!(chunks.len() > 1);
+    let mut bytes = Vec::new();
+    for chunk in chunks {
+        let mut data = vec![0u8; chunk.data.len()];
+        let size = secure_channel.apply_security(&chunk, &mut data).unwrap();
+        bytes.extend_from_slice(&data[..size]);
+    }
+    bytes
+}
+
+/// Capture the chunks and the message of a connection, and replay the capture to get the
+/// message back.
+#[test]
+fn capture_and_replay() {
+    let _ = Test::setup();
+
+    let tmp_dir = TempDir::new("capture").unwrap();
+    let path = tmp_dir.path().join("capture.jsonl");
+
+    let message = make_read_response();
+    let bytes = message_bytes(7, &message);
+    let capture = MessageCapture::open(&path, true).unwrap();
+    let connection = capture.for_connection("1");
+    connection.chunks(Direction::Sent, &bytes);
+    connection.message(Direction::Sent, 7, &message);
+    // A connection with a chunk that is not valid
+    capture
+        .for_connection("2")
+        .chunks(Direction::Received, &[b'M', b'S', b'G', b'F', 8, 0, 0, 0]);
+
+    let records = read_capture(&path).unwrap();
+    let chunks = records
+        .iter()
+        .filter(|r| matches!(r.content, CaptureContent::Chunk { .. }))
+        .count();
+    assert_eq!(chunks, records.len() - 1);
+    let last = records.iter().rfind(|r| r.connection == "1").unwrap();
+    assert_eq!(last.direction, Direction::Sent);
+    let CaptureContent::Message {
+        request_id, name, ..
+    } = &last.content
+    else {
+        panic!("Expected a message record");
+    };
+    assert_eq!(*request_id, 7);
+    assert_eq!(name, "ReadResponse");
+
+    let replayed = replay_capture(&path, &DecodingOptions::default()).unwrap();
+    assert_eq!(replayed.len(), 2);
+    assert_eq!(replayed[0].connection, "1");
+    assert_eq!(replayed[0].direction, Direction::Sent);
+    assert_eq!(replayed[0].message.as_ref().unwrap(), &message);
+    assert_eq!(replayed[1].connection, "2");
+    assert!(replayed[1].message.is_err());
+}
+
+/// Decoded messages are only captured when asked for, and then without their secrets
+#[test]
+fn capture_messages_redacted() {
+    let _ = Test::setup();
+
+    let tmp_dir = TempDir::new("capture").unwrap();
+    let message: SupportedMessage = ActivateSessionRequest {
+        request_header: RequestHeader::dummy(),
+        client_signature: SignatureData::null(),
+        client_software_certificates: None,
+        locale_ids: None,
+        user_identity_token: ExtensionObject::from_encodable(
+            ObjectId::UserNameIdentityToken_Encoding_DefaultBinary,
+            &UserNameIdentityToken {
+                policy_id: UAString::from("userpass"),
+                user_name: UAString::from("sample1"),
+                password: ByteString::from(b"sample1pwd"),
+                encryption_algorithm: UAString::null(),
+            },
+        ),
+        user_token_signature: SignatureData {
+            algorithm: UAString::from("http://www.w3.org/2000/09/xmldsig#rsa-sha1"),
+            signature: ByteString::from(b"signature"),
+        },
+    }
+    .into();
+
+    let path = tmp_dir.path().join("chunks.jsonl");
+    let capture = MessageCapture::open(&path, false).unwrap();
+    capture
+        .for_connection("1")
+        .message(Direction::Sent, 1, &message);
+    assert!(read_capture(&path).unwrap().is_empty());
+
+    let path = tmp_dir.path().join("messages.jsonl");
+    let capture = MessageCapture::open(&path, true).unwrap();
+    capture
+        .for_connection("1")
+        .message(Direction::Sent, 1, &message);
+    let records = read_capture(&path).unwrap();
+    let CaptureContent::Message { name, message, .. } = &records[0].content else {
+        panic!("Expected a message record");
+    };
+    assert_eq!(name, "ActivateSessionRequest");
+    // The bytes of the password and signature are nowhere in the message
+    for secret in [&b"sample1pwd"[..], &b"signature"[..]] {
+        let secret = format!("{:?}", secret);
+        assert!(!message.contains(secret.trim_matches(|c| c == '[' || c == ']')));
+    }
+}
+
+/// Replaying a file that is not a capture is an error
+#[test]
+fn replay_invalid_capture() {
+    let _ = Test::setup();
+
+    let tmp_dir = TempDir::new("capture").unwrap();
+    let path = tmp_dir.path().join("capture.jsonl");
+    writeln!(std::fs::File::create(&path).unwrap(), "not a record").unwrap();
+    assert_eq!(
+        read_capture(&path).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+    assert!(replay_capture(&path, &DecodingOptions::default()).is_err());
+    assert!(replay_capture(
+        &tmp_dir.path().join("missing.jsonl"),
+        &DecodingOptions::default()
+    )
+    .is_err());
+}
@@ -1,8 +1,18 @@
 use std::io::{Cursor, Write};
 
+use bytes::BytesMut;
+use tokio_util::codec::Decoder;
+
 use crate::{
     core::{
-        comms::{chunker::*, message_chunk::*, secure_channel::*, tcp_types::MIN_CHUNK_SIZE},
+        comms::{
+            chunker::*,
+            message_chunk::*,
+            message_writer::MessageWriter,
+            secure_channel::*,
+            tcp_codec::{Message, TcpCodec},
+            tcp_types::MIN_CHUNK_SIZE,
+        },
         supported_message::SupportedMessage,
         tests::*,
     },
@@ -130,6 +140,185 @@ fn chunk_multi_encode_decode() {
     assert_eq!(response, new_response);
 }
 
+/// Read the chunks of a large message off a stream buffer and ensure that neither the codec nor
+/// removing security copies them, and that the message decodes from them where they are.
+#[test]
+fn chunks_are_read_without_copying() {
+    let _ = Test::setup();
+
+    let decoding_options = DecodingOptions {
+        max_chunk_count: 0,
+        max_string_length: 65535,
+        max_byte_string_length: 65535,
+        max_array_length: 20000,
+        ..Default::default()
+    };
+    let mut secure_channel = SecureChannel::new_no_certificate_store();
+    secure_channel.set_decoding_options(decoding_options.clone());
+
+    let response = make_large_read_response();
+    let chunks = Chunker::encode(1, 1, 0, MIN_CHUNK_SIZE, &secure_channel, &response).unwrap();
+    assert!(chunks.len() > 1);
+
+    let mut buf = BytesMut::new();
+    for chunk in &chunks {
+        buf.extend_from_slice(&chunk.data);
+    }
+    let received_bytes = buf.as_ptr_range();
+
+    let mut codec = TcpCodec::new(decoding_options);
+    let mut received = Vec::new();
+    while let Some(message) = codec.decode(&mut buf).unwrap() {
+        let Message::Chunk(chunk) = message else {
+            panic!("Expected a chunk, got {:?}", message);
+        };
+        let chunk = secure_channel.remove_security(chunk).unwrap();
+        assert!(received_bytes.contains(&chunk.data.as_ptr()));
+        received.push(chunk);
+    }
+    assert_eq!(received.len(), chunks.len());
+
+    let new_response = Chunker::decode(&received, &secure_channel, None).unwrap();
+    assert_eq!(response, new_response);
+}
+
+/// Write messages with a message writer and ensure it produces the same bytes as encoding the
+/// chunks and applying security to them, while reusing the buffer it yields.
+#[test]
+fn message_writer_matches_chunker() {
+    let _ = Test::setup();
+
+    let secure_channel = SecureChannel::new_no_certificate_store();
+    assert!(!secure_channel.should_apply_security());
+
+    let message = make_sample_message();
+    let request_id = 100;
+    let expected = |sequence_number: u32| {
+        let chunks =
+            Chunker::encode(sequence_number, request_id, 0, 0, &secure_channel, &message).unwrap();
+        let mut expected = Vec::new();
+        for chunk in chunks {
+            let mut data = vec![0u8; chunk.data.len()];
+            let size = secure_channel.apply_security(&chunk, &mut data).unwrap();
+            expected.extend_from_slice(&data[..size]);
+        }
+        expected
+    };
+
+    let mut writer = MessageWriter::new(65535, 0, 0);
+    writer
+        .write(request_id, message.clone(), &secure_channel)
+        .unwrap();
+    let bytes = writer.bytes_to_write();
+    assert_eq!(bytes, expected(1));
+    let ptr = bytes.as_ptr();
+    writer.recycle(bytes);
+
+    // The next message is written to a new buffer and the one after it to the recycled buffer
+    writer
+        .write(request_id, message.clone(), &secure_channel)
+        .unwrap();
+    let bytes = writer.bytes_to_write();
+    assert_eq!(bytes, expected(2));
+    writer.recycle(bytes);
+
+    writer
+        .write(request_id, message.clone(), &secure_channel)
+        .unwrap();
+    let bytes = writer.bytes_to_write();
+    assert_eq!(bytes, expected(3));
+    assert_eq!(bytes.as_ptr(), ptr);
+}
+
+/// Write messages over a secured channel and ensure the writer produces the same bytes as encoding
+/// the chunks and applying security to them, while it reuses the buffer it assembles chunks in.
+#[test]
+fn message_writer_matches_chunker_secured() {
+    let _ = Test::setup();
+
+    let (secure_channel, _) = make_secure_channels(
+        MessageSecurityMode::SignAndEncrypt,
+        SecurityPolicy::Basic256Sha256,
+    );
+    assert!(secure_channel.should_apply_security());
+
+    let message = make_large_read_response();
+    let request_id = 100;
+    let expected = |sequence_number: u32| {
+        let chunks = Chunker::encode(
+            sequence_number,
+            request_id,
+            0,
+            MIN_CHUNK_SIZE,
+            &secure_channel,
+            &message,
+        )
+        .unwrap();
+        assert!(chunks.len() > 2);
+        let mut expected = Vec::new();
+        for chunk in &chunks {
+            let mut data = vec![0u8; MIN_CHUNK_SIZE + 1024];
+            let size = secure_channel.apply_security(chunk, &mut data).unwrap();
+            expected.extend_from_slice(&data[..size]);
+        }
+        (chunks.len() as u32, expected)
+    };
+
+    let mut writer = MessageWriter::new(65535, 0, 0);
+    writer.set_limits(MIN_CHUNK_SIZE, 0, 0);
+    let mut sequence_number = 1;
+    for _ in 0..2 {
+        writer
+            .write(request_id, message.clone(), &secure_channel)
+            .unwrap();
+        let (chunk_count, expected) = expected(sequence_number);
+        assert_eq!(writer.bytes_to_write(), expected);
+        sequence_number += chunk_count;
+    }
+}
+
+/// Write a large message with the limits negotiated by the other end and ensure that it is split
+/// into chunks of the negotiated size, or refused if it needs too many chunks.
+#[test]
+fn message_writer_negotiated_limits() {
+    let _ = Test::setup();
+
+    let decoding_options = DecodingOptions {
+        max_chunk_count: 0,
+        max_string_length: 65535,
+        max_byte_string_length: 65535,
+        max_array_length: 20000,
+        ..Default::default()
+    };
+    let mut secure_channel = SecureChannel::new_no_certificate_store();
+    secure_channel.set_decoding_options(decoding_options.clone());
+
+    let response = make_large_read_response();
+    let mut writer = MessageWriter::new(65535, 0, 0);
+    writer.set_limits(MIN_CHUNK_SIZE, 0, 0);
+    writer.write(1, response.clone(), &secure_channel).unwrap();
+    let mut buf = BytesMut::from(&writer.bytes_to_write()[..]);
+
+    let mut codec = TcpCodec::new(decoding_options);
+    let mut received = Vec::new();
+    while let Some(message) = codec.decode(&mut buf).unwrap() {
+        let Message::Chunk(chunk) = message else {
+            panic!("Expected a chunk, got {:?}", message);
+        };
+        assert!(chunk.data.len() <= MIN_CHUNK_SIZE);
+        received.push(secure_channel.remove_security(chunk).unwrap());
+    }
+    assert!(received.len() > 2);
+    let new_response = Chunker::decode(&received, &secure_channel, None).unwrap();
+    assert_eq!(response, new_response);
+
+    // A message that needs more chunks than the other end accepts is not written at all
+    writer.set_limits(MIN_CHUNK_SIZE, 0, 2);
+    let err = writer.write(2, response, &secure_channel).unwrap_err();
+    assert_eq!(err, StatusCode::BadResponseTooLarge);
+    assert!(writer.bytes_to_write().is_empty());
+}
+
 /// Encode a large message with multiple chunks. Ensure all but the last chunk is marked intermediate
 /// and the last is marked final.
 #[test]
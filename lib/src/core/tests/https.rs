@@ -0,0 +1,134 @@
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+
+use crate::core::comms::{https::*, secure_channel::Role, tcp_types::MIN_CHUNK_SIZE};
+use crate::core::supported_message::SupportedMessage;
+use crate::types::{
+    encoding::DecodingOptions,
+    service_types::{GetEndpointsRequest, ReadRequest, ServiceFault, TimestampsToReturn},
+    status_code::StatusCode,
+    RequestHeader, UAString,
+};
+
+fn get_endpoints_request() -> SupportedMessage {
+    GetEndpointsRequest {
+        request_header: RequestHeader::dummy(),
+        endpoint_url: UAString::from("https://localhost:4443/"),
+        locale_ids: None,
+        profile_uris: None,
+    }
+    .into()
+}
+
+#[test]
+fn body_round_trip() {
+    let decoding_options = DecodingOptions::default();
+    let request = get_endpoints_request();
+    let body = encode_https_body(&request).unwrap();
+    assert_eq!(
+        decode_https_body(&body, &decoding_options).unwrap(),
+        request
+    );
+
+    let response: SupportedMessage =
+        ServiceFault::new(&RequestHeader::dummy(), StatusCode::BadServiceUnsupported).into();
+    let body = encode_https_body(&response).unwrap();
+    assert_eq!(
+        decode_https_body(&body, &decoding_options).unwrap(),
+        response
+    );
+
+    // A body that isn't a message
+    assert!(decode_https_body(&[1, 2, 3], &decoding_options).is_err());
+}
+
+#[tokio::test]
+async fn post_and_respond() {
+    let (mut client, mut server) = io::duplex(4096);
+    let request = encode_https_body(&get_endpoints_request()).unwrap();
+    let client_task =
+        async { https_post(&mut client, "localhost:4443", "/opcua", &request, 0).await };
+    let server_task = async {
+        let message = read_https_request(&mut server, 0).await.unwrap();
+        write_https_response(&mut server, &message.body, message.keep_alive)
+            .await
+            .unwrap();
+        message
+    };
+    let (response, message) = tokio::join!(client_task, server_task);
+    assert_eq!(
+        message,
+        HttpsMessage {
+            body: request.clone(),
+            keep_alive: true
+        }
+    );
+    assert_eq!(
+        response.unwrap(),
+        HttpsMessage {
+            body: request,
+            keep_alive: true
+        }
+    );
+}
+
+#[tokio::test]
+async fn request_rejected() {
+    // Not a POST
+    let (mut client, mut server) = io::duplex(4096);
+    client
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        read_https_request(&mut server, 0).await.unwrap_err(),
+        StatusCode::BadCommunicationError
+    );
+    let mut response = vec![0u8; 12];
+    client.read_exact(&mut response).await.unwrap();
+    assert_eq!(response, b"HTTP/1.1 405");
+
+    // A security policy other than None
+    let (mut client, mut server) = io::duplex(4096);
+    client
+        .write_all(
+            b"POST / HTTP/1.1\r\nOPCUA-SecurityPolicy: http://opcfoundation.org/UA/SecurityPolicy#Basic256Sha256\r\nContent-Length: 0\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        read_https_request(&mut server, 0).await.unwrap_err(),
+        StatusCode::BadSecurityPolicyRejected
+    );
+
+    // A body that is too big
+    let (mut client, mut server) = io::duplex(4096);
+    client
+        .write_all(b"POST / HTTP/1.1\r\nContent-Length: 100\r\n\r\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        read_https_request(&mut server, 10).await.unwrap_err(),
+        StatusCode::BadTcpMessageTooLarge
+    );
+}
+
+#[tokio::test]
+async fn chunk_stream_round_trip() {
+    let (client, server) = io::duplex(65536);
+    let mut client = ChunkStream::new(client, Role::Client, DecodingOptions::default());
+    let mut server = ChunkStream::new(server, Role::Server, DecodingOptions::default());
+    // A small chunk size so the message needs more than one chunk
+    client.set_max_chunk_size(MIN_CHUNK_SIZE);
+    let request: SupportedMessage = ReadRequest {
+        request_header: RequestHeader::dummy(),
+        max_age: 0.0,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: Some(vec![Default::default(); 1000]),
+    }
+    .into();
+    client.write_message(5, &request).await.unwrap();
+    assert_eq!(server.read_message().await.unwrap(), Some((5, request)));
+
+    drop(client);
+    assert_eq!(server.read_message().await.unwrap(), None);
+}
@@ -169,9 +169,15 @@ impl Test {
     }
 }
 
+mod capture;
 mod chunk;
 mod comms;
 mod hello;
+#[cfg(feature = "https")]
+mod https;
+mod runtime;
 mod secure_channel;
 mod services;
 mod supported_message;
+#[cfg(feature = "websocket")]
+mod websocket;
@@ -0,0 +1,29 @@
+use futures::executor::block_on;
+
+use crate::core::runtime::*;
+use crate::types::status_code::StatusCode;
+
+#[test]
+fn catch_panic_passes_result() {
+    assert_eq!(block_on(catch_panic("ok", async { 5 })), Ok(5));
+}
+
+#[test]
+fn catch_panic_converts_panic() {
+    let result = block_on(catch_panic("panics", async {
+        panic!("Something went wrong")
+    }));
+    assert_eq!(result, Err(StatusCode::BadInternalError));
+}
+
+#[test]
+fn panic_messages() {
+    let payload = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "static message");
+
+    let payload = std::panic::catch_unwind(|| panic!("formatted {}", 1)).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "formatted 1");
+
+    let payload = std::panic::catch_unwind(|| std::panic::panic_any(1)).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "unknown panic");
+}
@@ -43,6 +43,32 @@ fn test_symmetric_encrypt_decrypt(
     assert_eq!(message, message2);
 }
 
+/// Once a decrypted chunk is dropped, the next chunk is decrypted into the same memory
+#[test]
+fn decrypted_chunks_reuse_memory() {
+    let _ = Test::setup();
+    let (secure_channel1, mut secure_channel2) = make_secure_channels(
+        MessageSecurityMode::SignAndEncrypt,
+        SecurityPolicy::Basic256Sha256,
+    );
+    let chunks = Chunker::encode(1, 1, 0, 0, &secure_channel1, &make_sample_message()).unwrap();
+    let mut encrypted_data = vec![0u8; chunks[0].data.len() + 4096];
+    let encrypted_size = secure_channel1
+        .apply_security(&chunks[0], &mut encrypted_data[..])
+        .unwrap();
+
+    let chunk = secure_channel2
+        .verify_and_remove_security(&encrypted_data[..encrypted_size])
+        .unwrap();
+    let first = chunk.data.as_ptr();
+    drop(chunk);
+    let chunk = secure_channel2
+        .verify_and_remove_security(&encrypted_data[..encrypted_size])
+        .unwrap();
+    assert_eq!(chunk.data.as_ptr(), first);
+    assert_eq!(&chunks[0].data[12..], &chunk.data[12..chunks[0].data.len()]);
+}
+
 fn test_asymmetric_encrypt_decrypt(
     message: SupportedMessage,
     security_mode: MessageSecurityMode,
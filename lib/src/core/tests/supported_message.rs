@@ -8,3 +8,24 @@ fn size() {
     println!("SupportedMessage size = {}", size);
     assert!(size <= 16);
 }
+
+#[test]
+fn name() {
+    use crate::types::{
+        service_types::{ReadRequest, TimestampsToReturn},
+        ObjectId, RequestHeader,
+    };
+
+    let message: SupportedMessage = ReadRequest {
+        request_header: RequestHeader::dummy(),
+        max_age: 0.0,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: None,
+    }
+    .into();
+    assert_eq!(message.name(), "ReadRequest");
+    assert_eq!(
+        SupportedMessage::Invalid(ObjectId::ReadRequest_Encoding_DefaultBinary).name(),
+        "Invalid"
+    );
+}
@@ -0,0 +1,167 @@
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+
+use crate::core::comms::{secure_channel::Role, websocket::*};
+use crate::types::status_code::StatusCode;
+
+/// Makes a fake chunk of the given length, with the length in the header as a real chunk has it
+fn chunk(len: usize, fill: u8) -> Vec<u8> {
+    let mut chunk = vec![fill; len];
+    chunk[..4].copy_from_slice(b"MSGF");
+    chunk[4..8].copy_from_slice(&(len as u32).to_le_bytes());
+    chunk
+}
+
+#[test]
+fn accept_key() {
+    // The example from RFC 6455
+    assert_eq!(
+        websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+    );
+}
+
+#[tokio::test]
+async fn frame_round_trip() {
+    for len in [0, 125, 126, 65535, 65536] {
+        let frame = WebSocketFrame::new(WebSocketOpCode::Binary, vec![7u8; len]);
+        for mask in [None, Some([1, 2, 3, 4])] {
+            let data = frame.encode(mask);
+            let decoded = WebSocketFrame::read(&mut data.as_slice(), mask.is_some(), 65536)
+                .await
+                .unwrap();
+            assert_eq!(decoded, frame);
+        }
+    }
+
+    // Frames from a client must be masked, those from a server must not
+    let data = WebSocketFrame::new(WebSocketOpCode::Binary, vec![1, 2, 3]).encode(None);
+    assert_eq!(
+        WebSocketFrame::read(&mut data.as_slice(), true, 100)
+            .await
+            .unwrap_err(),
+        StatusCode::BadCommunicationError
+    );
+    // Payload that is too big
+    assert_eq!(
+        WebSocketFrame::read(&mut data.as_slice(), false, 2)
+            .await
+            .unwrap_err(),
+        StatusCode::BadTcpMessageTooLarge
+    );
+    // Control frames can't be fragmented
+    let mut ping = WebSocketFrame::new(WebSocketOpCode::Ping, vec![]);
+    ping.fin = false;
+    assert_eq!(
+        WebSocketFrame::read(&mut ping.encode(None).as_slice(), false, 100)
+            .await
+            .unwrap_err(),
+        StatusCode::BadCommunicationError
+    );
+}
+
+#[tokio::test]
+async fn handshake() {
+    let (mut client, mut server) = io::duplex(4096);
+    let (client_result, server_result) = tokio::join!(
+        websocket_client_handshake(&mut client, "localhost:4843", "/opcua"),
+        websocket_server_handshake(&mut server)
+    );
+    assert_eq!(client_result, Ok(()));
+    assert_eq!(server_result, Ok(()));
+}
+
+#[tokio::test]
+async fn handshake_rejected() {
+    let (mut client, mut server) = io::duplex(4096);
+    client
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        websocket_server_handshake(&mut server).await.unwrap_err(),
+        StatusCode::BadConnectionRejected
+    );
+    let mut response = vec![0u8; 12];
+    client.read_exact(&mut response).await.unwrap();
+    assert_eq!(response, b"HTTP/1.1 400");
+
+    // A server that answers with another protocol
+    let (mut client, mut server) = io::duplex(4096);
+    let server = async move {
+        let mut request = vec![0u8; 1024];
+        let _ = server.read(&mut request).await.unwrap();
+        server
+            .write_all(
+                b"HTTP/1.1 101 Switching Protocols\r\nSec-WebSocket-Protocol: opcua+uajson\r\n\r\n",
+            )
+            .await
+            .unwrap();
+    };
+    let (result, _) = tokio::join!(
+        websocket_client_handshake(&mut client, "localhost", "/"),
+        server
+    );
+    assert_eq!(result.unwrap_err(), StatusCode::BadConnectionRejected);
+}
+
+#[tokio::test]
+async fn bridge_sends_a_message_per_chunk() {
+    let (client, mut server) = io::duplex(65536);
+    let mut stream = websocket_bridge(client, Role::Client, 8192);
+
+    // Two chunks written in pieces that don't line up with the chunks
+    let chunks = [chunk(100, 1), chunk(3000, 2)];
+    let data = chunks.concat();
+    stream.write_all(&data[..50]).await.unwrap();
+    stream.write_all(&data[50..1000]).await.unwrap();
+    stream.write_all(&data[1000..]).await.unwrap();
+    for chunk in &chunks {
+        let frame = WebSocketFrame::read(&mut server, true, 8192).await.unwrap();
+        assert_eq!(frame.opcode, WebSocketOpCode::Binary);
+        assert_eq!(&frame.payload, chunk);
+    }
+
+    // Pings are answered and payloads of incoming frames are read from the stream
+    server
+        .write_all(&WebSocketFrame::new(WebSocketOpCode::Ping, vec![9]).encode(None))
+        .await
+        .unwrap();
+    let pong = WebSocketFrame::read(&mut server, true, 8192).await.unwrap();
+    assert_eq!(pong, WebSocketFrame::new(WebSocketOpCode::Pong, vec![9]));
+    server
+        .write_all(&WebSocketFrame::new(WebSocketOpCode::Binary, chunk(20, 3)).encode(None))
+        .await
+        .unwrap();
+    let mut received = vec![0u8; 20];
+    stream.read_exact(&mut received).await.unwrap();
+    assert_eq!(received, chunk(20, 3));
+
+    // Dropping the stream closes the WebSocket
+    drop(stream);
+    let close = WebSocketFrame::read(&mut server, true, 8192).await.unwrap();
+    assert_eq!(close.opcode, WebSocketOpCode::Close);
+}
+
+#[tokio::test]
+async fn bridge_to_bridge() {
+    let (client, server) = io::duplex(65536);
+    let mut client = websocket_bridge(client, Role::Client, 8192);
+    let mut server = websocket_bridge(server, Role::Server, 8192);
+
+    let request = chunk(5000, 4);
+    client.write_all(&request).await.unwrap();
+    let mut received = vec![0u8; request.len()];
+    server.read_exact(&mut received).await.unwrap();
+    assert_eq!(received, request);
+
+    let response = chunk(8192, 5);
+    server.write_all(&response).await.unwrap();
+    let mut received = vec![0u8; response.len()];
+    client.read_exact(&mut received).await.unwrap();
+    assert_eq!(received, response);
+
+    // A chunk larger than the limit ends the connection, which may happen before it is all written
+    let _ = client.write_all(&chunk(8193, 6)).await;
+    let mut received = Vec::new();
+    assert_eq!(client.read_to_end(&mut received).await.unwrap(), 0);
+}
@@ -9,6 +9,7 @@ use std::fs::{metadata, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
 use openssl::{pkey, x509};
 
 use crate::types::status_code::StatusCode;
@@ -16,6 +17,7 @@ use crate::types::status_code::StatusCode;
 use super::{
     pkey::PrivateKey,
     security_policy::SecurityPolicy,
+    thumbprint::Thumbprint,
     x509::{X509Data, X509},
 };
 
@@ -28,6 +30,25 @@ const TRUSTED_CERTS_DIR: &str = "trusted";
 /// The directory holding rejected certificates
 const REJECTED_CERTS_DIR: &str = "rejected";
 
+/// A certificate in the rejected directory, with the details an administrator needs to decide
+/// whether to trust it.
+#[derive(Debug, Clone)]
+pub struct RejectedCertificate {
+    /// Path of the certificate file
+    pub path: PathBuf,
+    /// Subject name of the certificate
+    pub subject_name: String,
+    /// Thumbprint of the certificate, which identifies it to `trust_rejected_cert()` and
+    /// `purge_rejected_cert()`
+    pub thumbprint: Thumbprint,
+    /// Start of the validity period of the certificate, if it can be parsed
+    pub not_before: Option<DateTime<Utc>>,
+    /// End of the validity period of the certificate, if it can be parsed
+    pub not_after: Option<DateTime<Utc>>,
+    /// The certificate
+    pub cert: X509,
+}
+
 /// The certificate store manages the storage of a server/client's own certificate & private key
 /// and the trust / rejection of certificates from the other end.
 pub struct CertificateStore {
@@ -507,6 +528,83 @@ impl CertificateStore {
         Ok(cert_path)
     }
 
+    /// Lists the certificates in the rejected directory. Files that cannot be read as certificates
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// A string description of any failure
+    ///
+    pub fn rejected_certs(&self) -> Result<Vec<RejectedCertificate>, String> {
+        let rejected_certs_dir = self.rejected_certs_dir();
+        let entries = std::fs::read_dir(&rejected_certs_dir).map_err(|_| {
+            format!(
+                "Cannot read the rejected certificates in {}",
+                rejected_certs_dir.display()
+            )
+        })?;
+        let mut rejected_certs = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match CertificateStore::read_cert(&path) {
+                Ok(cert) => rejected_certs.push(RejectedCertificate {
+                    subject_name: cert.subject_name(),
+                    thumbprint: cert.thumbprint(),
+                    not_before: cert.not_before().ok(),
+                    not_after: cert.not_after().ok(),
+                    path,
+                    cert,
+                }),
+                Err(err) => warn!("Skipping rejected certificate file, {}", err),
+            }
+        }
+        Ok(rejected_certs)
+    }
+
+    /// Trusts a rejected certificate by moving it from the rejected directory to the trusted
+    /// directory. If the move succeeds, the function returns the path of the trusted file.
+    ///
+    /// # Errors
+    ///
+    /// A string description of any failure
+    ///
+    pub fn trust_rejected_cert(&self, thumbprint: &Thumbprint) -> Result<PathBuf, String> {
+        let rejected = self.find_rejected_cert(thumbprint)?;
+        let trusted_path = self.store_trusted_cert(&rejected.cert)?;
+        Self::remove_file(&rejected.path)?;
+        info!(
+            "Rejected certificate {} is now trusted",
+            rejected.path.display()
+        );
+        Ok(trusted_path)
+    }
+
+    /// Purges a rejected certificate by deleting it from the rejected directory. The next time
+    /// the other end presents it, it is rejected again.
+    ///
+    /// # Errors
+    ///
+    /// A string description of any failure
+    ///
+    pub fn purge_rejected_cert(&self, thumbprint: &Thumbprint) -> Result<(), String> {
+        let rejected = self.find_rejected_cert(thumbprint)?;
+        Self::remove_file(&rejected.path)?;
+        info!("Rejected certificate {} is purged", rejected.path.display());
+        Ok(())
+    }
+
+    fn find_rejected_cert(&self, thumbprint: &Thumbprint) -> Result<RejectedCertificate, String> {
+        self.rejected_certs()?
+            .into_iter()
+            .find(|rejected| rejected.thumbprint == *thumbprint)
+            .ok_or_else(|| {
+                format!(
+                    "There is no rejected certificate with thumbprint {}",
+                    thumbprint.as_hex_string()
+                )
+            })
+    }
+
     /// Writes a cert to the trusted directory. If the write succeeds, the function
     /// returns a path to the written file.
     ///
@@ -572,6 +670,12 @@ impl CertificateStore {
         Ok(X509::from(cert.unwrap()))
     }
 
+    /// Removes a file, or returns an error reason for failure.
+    fn remove_file(file_path: &Path) -> Result<(), String> {
+        std::fs::remove_file(file_path)
+            .map_err(|_| format!("Could not remove file {}", file_path.display()))
+    }
+
     /// Writes bytes to file and returns the size written, or an error reason for failure.
     ///
     /// # Errors
@@ -134,6 +134,13 @@ impl PrivateKey {
         })
     }
 
+    pub fn private_key_to_der(&self) -> Result<Vec<u8>, PKeyError> {
+        self.value.private_key_to_pkcs8().map_err(|_| {
+            error!("Cannot turn private key to DER");
+            PKeyError
+        })
+    }
+
     /// Creates a message digest from the specified block of data and then signs it to return a signature
     fn sign(
         &self,
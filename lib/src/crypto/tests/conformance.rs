@@ -0,0 +1,237 @@
+//! Known-answer tests for every supported security policy. The expected values in
+//! `test_data/vectors.txt` were computed with Python's `hmac` module and `cryptography` package
+//! from the algorithms that Part 7 names for each policy, so a change to key derivation, signing,
+//! encryption or padding fails here even if the crate still agrees with itself. They are not the
+//! OPC Foundation's sample vectors, which are not part of this tree.
+
+use std::collections::HashMap;
+
+use openssl::{hash::MessageDigest, rsa::Padding, sign};
+
+use crate::{
+    core::comms::{
+        secure_channel::SecureChannel,
+        security_header::{SecurityHeader, SymmetricSecurityHeader},
+    },
+    crypto::{
+        aeskey::AesKey,
+        pkey::{KeySize, PrivateKey},
+        x509::X509,
+        SecurityPolicy,
+    },
+    from_hex,
+    types::MessageSecurityMode,
+};
+
+const POLICIES: [SecurityPolicy; 5] = [
+    SecurityPolicy::Basic128Rsa15,
+    SecurityPolicy::Basic256,
+    SecurityPolicy::Basic256Sha256,
+    SecurityPolicy::Aes128Sha256RsaOaep,
+    SecurityPolicy::Aes256Sha256RsaPss,
+];
+
+/// Parses the vectors file into sections of `name = value` pairs. Values outside of a section are
+/// shared by every policy and stored under the empty section name.
+fn vectors() -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut section = String::new();
+    for line in include_str!("test_data/vectors.txt").lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+/// Runs a test against the vectors of each security policy. The closure receives a lookup for
+/// hex encoded values in the policy's section, which falls back to the shared values.
+fn for_each_policy<F>(f: F)
+where
+    F: Fn(SecurityPolicy, &dyn Fn(&str) -> Vec<u8>),
+{
+    let vectors = vectors();
+    let shared = &vectors[""];
+    for policy in POLICIES {
+        let section = &vectors[policy.to_str()];
+        let lookup = |key: &str| {
+            let value = section
+                .get(key)
+                .or_else(|| shared.get(key))
+                .unwrap_or_else(|| panic!("No vector {} for {}", key, policy));
+            from_hex(value)
+        };
+        f(policy, &lookup);
+    }
+}
+
+fn key_pair() -> (X509, PrivateKey) {
+    let cert = X509::from_der(include_bytes!("test_data/cert.der")).unwrap();
+    let key = PrivateKey::from_pem(include_bytes!("test_data/private.pem")).unwrap();
+    (cert, key)
+}
+
+#[test]
+fn key_derivation() {
+    for_each_policy(|policy, v| {
+        let client_nonce = v("client_nonce");
+        let server_nonce = v("server_nonce");
+
+        let (signing_key, encrypting_key, iv) =
+            policy.make_secure_channel_keys(&server_nonce, &client_nonce);
+        assert_eq!(signing_key, v("client_signing_key"), "{}", policy);
+        assert_eq!(
+            encrypting_key.value(),
+            v("client_encrypting_key"),
+            "{}",
+            policy
+        );
+        assert_eq!(iv, v("client_iv"), "{}", policy);
+
+        let (signing_key, encrypting_key, iv) =
+            policy.make_secure_channel_keys(&client_nonce, &server_nonce);
+        assert_eq!(signing_key, v("server_signing_key"), "{}", policy);
+        assert_eq!(
+            encrypting_key.value(),
+            v("server_encrypting_key"),
+            "{}",
+            policy
+        );
+        assert_eq!(iv, v("server_iv"), "{}", policy);
+    });
+}
+
+#[test]
+fn symmetric_signature() {
+    for_each_policy(|policy, v| {
+        let key = v("client_signing_key");
+        let message = v("message");
+        let expected = v("symmetric_signature");
+
+        let mut signature = vec![0u8; policy.symmetric_signature_size()];
+        policy
+            .symmetric_sign(&key, &message, &mut signature)
+            .unwrap();
+        assert_eq!(signature, expected, "{}", policy);
+        assert!(policy
+            .symmetric_verify_signature(&key, &message, &expected)
+            .is_ok());
+        assert!(policy
+            .symmetric_verify_signature(&key, &message[1..], &expected)
+            .is_err());
+    });
+}
+
+#[test]
+fn symmetric_encryption() {
+    for_each_policy(|policy, v| {
+        let key = AesKey::new(policy, &v("client_encrypting_key"));
+        let iv = v("client_iv");
+        let message = v("message");
+        let expected = v("symmetric_cipher_text");
+
+        let mut cipher_text = vec![0u8; message.len() + 16];
+        let size = policy
+            .symmetric_encrypt(&key, &iv, &message, &mut cipher_text)
+            .unwrap();
+        assert_eq!(&cipher_text[..size], &expected[..], "{}", policy);
+
+        let mut plain_text = vec![0u8; expected.len() + 16];
+        let size = policy
+            .symmetric_decrypt(&key, &iv, &expected, &mut plain_text)
+            .unwrap();
+        assert_eq!(&plain_text[..size], &message[..], "{}", policy);
+    });
+}
+
+#[test]
+fn asymmetric_signature() {
+    let (cert, key) = key_pair();
+    let public_key = cert.public_key().unwrap();
+    for_each_policy(|policy, v| {
+        let message = v("message");
+        let expected = v("asymmetric_signature");
+
+        let mut signature = vec![0u8; key.size()];
+        let size = policy
+            .asymmetric_sign(&key, &message, &mut signature)
+            .unwrap();
+        if policy == SecurityPolicy::Aes256Sha256RsaPss {
+            // PSS signatures are salted so they differ from the vector every time. Check the
+            // signature with OpenSSL directly instead, with the SHA-256 digest, MGF1 with SHA-256
+            // and 32 byte salt of RSA-PSS-SHA2-256.
+            let public_key = openssl::x509::X509::from_der(include_bytes!("test_data/cert.der"))
+                .unwrap()
+                .public_key()
+                .unwrap();
+            let mut verifier = sign::Verifier::new(MessageDigest::sha256(), &public_key).unwrap();
+            verifier.set_rsa_padding(Padding::PKCS1_PSS).unwrap();
+            verifier.set_rsa_mgf1_md(MessageDigest::sha256()).unwrap();
+            verifier
+                .set_rsa_pss_saltlen(sign::RsaPssSaltlen::custom(32))
+                .unwrap();
+            verifier.update(&message).unwrap();
+            assert!(verifier.verify(&signature[..size]).unwrap(), "{}", policy);
+        } else {
+            assert_eq!(&signature[..size], &expected[..], "{}", policy);
+        }
+        assert!(policy
+            .asymmetric_verify_signature(&public_key, &message, &expected, None)
+            .is_ok());
+        assert!(policy
+            .asymmetric_verify_signature(&public_key, &message[1..], &expected, None)
+            .is_err());
+    });
+}
+
+#[test]
+fn asymmetric_encryption_padding() {
+    let (_, key) = key_pair();
+    for_each_policy(|policy, v| {
+        // Encryption is randomized, so check a cipher text produced elsewhere decrypts with the
+        // padding the policy specifies
+        let cipher_text = v("asymmetric_cipher_text");
+        let mut plain_text = vec![0u8; cipher_text.len()];
+        let size = policy
+            .asymmetric_decrypt(&key, &cipher_text, &mut plain_text)
+            .unwrap();
+        assert_eq!(&plain_text[..size], &v("message")[..], "{}", policy);
+    });
+}
+
+#[test]
+fn symmetric_padding() {
+    let vectors = vectors();
+    for policy in POLICIES {
+        let mut secure_channel = SecureChannel::new_no_certificate_store();
+        secure_channel.set_security_mode(MessageSecurityMode::SignAndEncrypt);
+        secure_channel.set_security_policy(policy);
+        let security_header = SecurityHeader::Symmetric(SymmetricSecurityHeader { token_id: 1 });
+        let signature_size = policy.symmetric_signature_size();
+
+        for pair in vectors[policy.to_str()]["symmetric_padding"].split_whitespace() {
+            let (body_size, expected) = pair.split_once(':').unwrap();
+            let (padding_size, _) = secure_channel.padding_size(
+                &security_header,
+                body_size.parse().unwrap(),
+                signature_size,
+            );
+            assert_eq!(
+                padding_size,
+                expected.parse::<usize>().unwrap(),
+                "{} body size {}",
+                policy,
+                body_size
+            );
+        }
+    }
+}
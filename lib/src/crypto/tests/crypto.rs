@@ -168,6 +168,87 @@ fn test_and_trust_application_instance_cert() {
     drop(tmp_dir);
 }
 
+#[test]
+fn trust_and_purge_rejected_certs() {
+    let (tmp_dir, cert_store) = make_certificate_store();
+
+    // Make two unrecognized certs, which end up in the rejected folder
+    let (cert, _) = make_test_cert_1024();
+    let (cert2, _) = make_test_cert_1024();
+    for cert in [&cert, &cert2] {
+        let result = cert_store.validate_or_reject_application_instance_cert(
+            cert,
+            SecurityPolicy::Basic128Rsa15,
+            None,
+            None,
+        );
+        assert!(result.is_bad());
+    }
+
+    let rejected = cert_store.rejected_certs().unwrap();
+    assert_eq!(rejected.len(), 2);
+    let rejected_cert = rejected
+        .iter()
+        .find(|r| r.thumbprint == cert.thumbprint())
+        .unwrap();
+    assert_eq!(rejected_cert.subject_name, cert.subject_name());
+    assert_eq!(rejected_cert.not_after, cert.not_after().ok());
+
+    // Trust the first cert, which is then valid
+    let trusted_path = cert_store.trust_rejected_cert(&cert.thumbprint()).unwrap();
+    assert!(trusted_path.exists());
+    assert!(!rejected_cert.path.exists());
+    let result = cert_store.validate_or_reject_application_instance_cert(
+        &cert,
+        SecurityPolicy::Basic128Rsa15,
+        None,
+        None,
+    );
+    assert!(result.is_good());
+
+    // Purge the second cert
+    cert_store.purge_rejected_cert(&cert2.thumbprint()).unwrap();
+    assert!(cert_store.rejected_certs().unwrap().is_empty());
+    assert!(cert_store.purge_rejected_cert(&cert2.thumbprint()).is_err());
+
+    drop(tmp_dir);
+}
+
+#[test]
+fn certificate_pins() {
+    let (cert, key) = make_test_cert_1024();
+    let (cert2, _) = make_test_cert_1024();
+    // A cert renewed with the same key keeps its public key hash but not its thumbprint
+    let renewed = X509::from_pkey(&key, &X509Data::sample_cert()).unwrap();
+
+    let thumbprint = cert.thumbprint().as_hex_string();
+    let public_key_hash = cert.public_key_hash().unwrap();
+    assert_eq!(public_key_hash.len(), 64);
+    assert_eq!(renewed.public_key_hash().unwrap(), public_key_hash);
+    assert_ne!(cert2.public_key_hash().unwrap(), public_key_hash);
+
+    assert!(cert.matches_pin(&thumbprint));
+    assert!(cert.matches_pin(&thumbprint.to_uppercase()));
+    assert!(cert.matches_pin(&public_key_hash));
+    assert!(!renewed.matches_pin(&thumbprint));
+    assert!(renewed.matches_pin(&public_key_hash));
+    assert!(!cert2.matches_pin(&thumbprint));
+    assert!(!cert2.matches_pin(&public_key_hash));
+
+    // Pins may be written with separators between the bytes
+    let separated = thumbprint
+        .as_bytes()
+        .chunks(2)
+        .map(|b| std::str::from_utf8(b).unwrap())
+        .collect::<Vec<_>>()
+        .join(":");
+    assert!(X509::is_valid_pin(&separated));
+    assert!(cert.matches_pin(&separated));
+    assert!(X509::is_valid_pin(&public_key_hash));
+    assert!(!X509::is_valid_pin("abcdef"));
+    assert!(!X509::is_valid_pin(&format!("{}g", &public_key_hash[1..])));
+}
+
 #[test]
 fn test_and_reject_thumbprint_mismatch() {
     let (tmp_dir, cert_store) = make_certificate_store();
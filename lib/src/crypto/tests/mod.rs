@@ -48,5 +48,6 @@ fn make_test_cert_2048() -> (X509, PrivateKey) {
 }
 
 mod authentication;
+mod conformance;
 mod crypto;
 mod security_policy;
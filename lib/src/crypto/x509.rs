@@ -563,6 +563,34 @@ impl X509 {
         Thumbprint::new(&digest)
     }
 
+    /// Returns the SHA-256 digest of the DER form of the subject public key info of the cert as a
+    /// hex string. Unlike the thumbprint it stays the same when a cert is renewed with the same key.
+    pub fn public_key_hash(&self) -> Result<String, X509Error> {
+        use openssl::hash::{hash, MessageDigest};
+        let der = self
+            .value
+            .public_key()
+            .and_then(|pkey| pkey.public_key_to_der())
+            .map_err(|_| X509Error)?;
+        let digest = hash(MessageDigest::sha256(), &der).map_err(|_| X509Error)?;
+        Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Tests if the cert matches a pin, which is either the hex string of its thumbprint or of
+    /// its public key hash. Case and `:` separators in the pin are ignored.
+    pub fn matches_pin(&self, pin: &str) -> bool {
+        let pin = pin.replace(':', "").to_lowercase();
+        pin == self.thumbprint().as_hex_string()
+            || self.public_key_hash().is_ok_and(|hash| pin == hash)
+    }
+
+    /// Tests if a pin has the form of a thumbprint or a public key hash.
+    pub fn is_valid_pin(pin: &str) -> bool {
+        let pin = pin.replace(':', "");
+        (pin.len() == Thumbprint::THUMBPRINT_SIZE * 2 || pin.len() == 64)
+            && pin.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
     /// Turn the Asn1 values into useful portable types
     pub fn not_before(&self) -> Result<DateTime<Utc>, X509Error> {
         let date = self.value.not_before().to_string();
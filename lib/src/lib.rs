@@ -14,6 +14,8 @@ extern crate actix_web;
 extern crate serde_json;
 #[macro_use]
 extern crate derivative;
+// Lets code generated by the derive macros, which refers to `::opcua`, be used within the crate
+extern crate self as opcua;
 
 // Synchronization structs. This is a wrapper mod around `parking_lot` types so opcua users don't have
 // to reference that other crate.
@@ -71,6 +73,8 @@ pub mod client;
 pub mod console_logging;
 pub mod core;
 pub mod crypto;
+#[cfg(feature = "pubsub")]
+pub mod pubsub;
 #[cfg(feature = "server")]
 pub mod server;
 pub mod types;
@@ -0,0 +1,297 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the decoding of data set messages on the subscriber side. The metadata of a data set
+//! says the names, types and configuration version of its fields, which is what turns the
+//! values in a data set message into named, typed fields.
+
+use std::{collections::BTreeMap, io::Cursor};
+
+use crate::types::{status_code::StatusCode, *};
+
+use super::{
+    data_set_message::{raw_data, DataSetMessage, DataSetMessagePayload, FieldEncoding},
+    json_message::{JsonDataSetMessage, JsonDataSetMessageType},
+};
+
+/// The fields of a data set message, decoded with the metadata of the data set
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetFields {
+    pub sequence_number: Option<u16>,
+    pub timestamp: Option<DateTime>,
+    pub status: Option<StatusCode>,
+    /// True when the message was a key frame, i.e. it has every field of the data set. A delta
+    /// frame only has the fields that changed and a keep alive has none.
+    pub key_frame: bool,
+    /// The values of the fields by name. A value that doesn't have the type of its field in the
+    /// metadata and cannot be converted to it has a `BadTypeMismatch` status.
+    pub fields: BTreeMap<String, DataValue>,
+}
+
+impl DataSetFields {
+    /// Gets the value of a field
+    pub fn get(&self, name: &str) -> Option<&DataValue> {
+        self.fields.get(name)
+    }
+}
+
+/// Decodes the data set messages of one data set writer using the metadata of its data set
+#[derive(Debug, Clone)]
+pub struct DataSetDecoder {
+    meta_data: DataSetMetaDataType,
+    decoding_options: DecodingOptions,
+}
+
+impl DataSetDecoder {
+    pub fn new(meta_data: DataSetMetaDataType) -> Self {
+        DataSetDecoder {
+            meta_data,
+            decoding_options: DecodingOptions::default(),
+        }
+    }
+
+    /// Sets the limits used when decoding raw data fields
+    pub fn decoding_options(mut self, decoding_options: DecodingOptions) -> Self {
+        self.decoding_options = decoding_options;
+        self
+    }
+
+    pub fn meta_data(&self) -> &DataSetMetaDataType {
+        &self.meta_data
+    }
+
+    /// Replaces the metadata, e.g. after the publisher announces that the data set has changed
+    pub fn set_meta_data(&mut self, meta_data: DataSetMetaDataType) {
+        self.meta_data = meta_data;
+    }
+
+    /// Decodes the fields of the message.
+    ///
+    /// A message whose major version is not the major version of the metadata cannot be decoded
+    /// and fails with `BadConfigurationError`, which means the subscriber should get the new
+    /// metadata from the publisher. A different minor version only means that fields were added
+    /// to the end of the data set, so the fields that are in the metadata are decoded and any
+    /// others are ignored.
+    pub fn decode(&self, message: &DataSetMessage) -> Result<DataSetFields, StatusCode> {
+        let same_minor_version =
+            self.check_version(message.major_version, message.minor_version)?;
+        let field_meta_data = self.meta_data.fields.as_deref().unwrap_or_default();
+        let (key_frame, values) = match message.payload {
+            DataSetMessagePayload::KeyFrame(ref values) => {
+                Self::check_field_count(field_meta_data, values.len(), same_minor_version)?;
+                (true, values.iter().cloned().enumerate().collect::<Vec<_>>())
+            }
+            DataSetMessagePayload::RawKeyFrame {
+                field_count,
+                ref data,
+            } => {
+                let field_count = field_count as usize;
+                Self::check_field_count(field_meta_data, field_count, same_minor_version)?;
+                let values = self.decode_raw_fields(field_meta_data, field_count, data)?;
+                (true, values.into_iter().enumerate().collect())
+            }
+            DataSetMessagePayload::DeltaFrame(ref values) => {
+                if let Some((idx, _)) = values
+                    .iter()
+                    .find(|(idx, _)| *idx as usize >= field_meta_data.len())
+                {
+                    if same_minor_version {
+                        error!(
+                            "Delta frame for {} has field index {} but the data set has {} fields",
+                            self.meta_data.name,
+                            idx,
+                            field_meta_data.len()
+                        );
+                        return Err(StatusCode::BadDecodingError);
+                    }
+                }
+                (
+                    false,
+                    values
+                        .iter()
+                        .map(|(idx, value)| (*idx as usize, value.clone()))
+                        .collect(),
+                )
+            }
+            DataSetMessagePayload::KeepAlive => (false, Vec::new()),
+        };
+
+        let fields = values
+            .into_iter()
+            .filter_map(|(idx, value)| {
+                field_meta_data.get(idx).map(|field| {
+                    (
+                        field.name.to_string(),
+                        Self::check_type(field, value, false),
+                    )
+                })
+            })
+            .collect();
+        Ok(DataSetFields {
+            sequence_number: message.sequence_number,
+            timestamp: message.timestamp,
+            status: message.status,
+            key_frame,
+            fields,
+        })
+    }
+
+    /// Decodes the fields of a JSON message. The fields are named so they are matched to the
+    /// metadata by name, with the same handling of versions as `decode()`.
+    pub fn decode_json(&self, message: &JsonDataSetMessage) -> Result<DataSetFields, StatusCode> {
+        let same_minor_version = self.check_version(
+            message.meta_data_version.as_ref().map(|v| v.major_version),
+            message.meta_data_version.as_ref().map(|v| v.minor_version),
+        )?;
+        let field_meta_data = self.meta_data.fields.as_deref().unwrap_or_default();
+        if message.message_type == JsonDataSetMessageType::KeyFrame {
+            Self::check_field_count(field_meta_data, message.payload.len(), same_minor_version)?;
+        }
+        // The types of raw data fields were guessed from their JSON, so they are cast
+        let cast = message.field_encoding == FieldEncoding::RawData;
+        let mut fields = BTreeMap::new();
+        for (name, value) in message.payload.iter() {
+            match field_meta_data
+                .iter()
+                .find(|field| field.name.as_ref() == name)
+            {
+                Some(field) => {
+                    fields.insert(name.clone(), Self::check_type(field, value.clone(), cast));
+                }
+                None if same_minor_version => {
+                    error!(
+                        "Message for {} has field {} that is not in the data set",
+                        self.meta_data.name, name
+                    );
+                    return Err(StatusCode::BadDecodingError);
+                }
+                None => {}
+            }
+        }
+        Ok(DataSetFields {
+            sequence_number: message.sequence_number.map(|v| v as u16),
+            timestamp: message.timestamp,
+            status: message.status,
+            key_frame: message.message_type == JsonDataSetMessageType::KeyFrame,
+            fields,
+        })
+    }
+
+    /// Checks the version of a message against the metadata, returning whether the minor
+    /// version is the same
+    fn check_version(
+        &self,
+        major_version: Option<u32>,
+        minor_version: Option<u32>,
+    ) -> Result<bool, StatusCode> {
+        let version = &self.meta_data.configuration_version;
+        if major_version.is_some_and(|v| v != version.major_version) {
+            warn!(
+                "Data set message for {} has major version {:?} but the metadata has {}",
+                self.meta_data.name, major_version, version.major_version
+            );
+            Err(StatusCode::BadConfigurationError)
+        } else {
+            Ok(minor_version.is_none_or(|v| v == version.minor_version))
+        }
+    }
+
+    /// Checks the number of fields of a key frame against the metadata. A message with a newer
+    /// minor version may have more fields, and one with an older minor version fewer.
+    fn check_field_count(
+        field_meta_data: &[FieldMetaData],
+        field_count: usize,
+        same_minor_version: bool,
+    ) -> Result<(), StatusCode> {
+        if same_minor_version && field_count != field_meta_data.len() {
+            error!(
+                "Key frame has {} fields but the data set has {} fields",
+                field_count,
+                field_meta_data.len()
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reads raw data fields using their types in the metadata. Fields after the ones in the
+    /// metadata cannot be read because their types are unknown, so they are ignored.
+    fn decode_raw_fields(
+        &self,
+        field_meta_data: &[FieldMetaData],
+        field_count: usize,
+        data: &[u8],
+    ) -> Result<Vec<DataValue>, StatusCode> {
+        let mut stream = Cursor::new(data);
+        field_meta_data
+            .iter()
+            .take(field_count)
+            .map(|field| {
+                raw_data::decode(
+                    &mut stream,
+                    field.built_in_type,
+                    field.value_rank,
+                    &self.decoding_options,
+                )
+                .map(DataValue::value_only)
+                .map_err(|err| {
+                    error!(
+                        "Cannot decode raw data field {} of {} - {}",
+                        field.name, self.meta_data.name, err
+                    );
+                    StatusCode::BadDecodingError
+                })
+            })
+            .collect()
+    }
+
+    /// Makes the value the type of the field, converting it implicitly if it is another type, or
+    /// explicitly when `cast` is true
+    fn check_type(field: &FieldMetaData, mut value: DataValue, cast: bool) -> DataValue {
+        let expected = match VariantTypeId::from_encoding_mask(field.built_in_type) {
+            Ok(VariantTypeId::Empty) | Ok(VariantTypeId::Variant) | Err(_) => return value,
+            Ok(expected) => expected,
+        };
+        // The value rank says if the field is a scalar (-1), an array (0 or more), or either
+        let scalar_allowed = field.value_rank < 0;
+        let array_allowed = field.value_rank != -1;
+        let converted = match value.value.take() {
+            None | Some(Variant::Empty) => return value,
+            Some(Variant::Array(array)) => {
+                if array_allowed && array.value_type == expected {
+                    Some(Variant::Array(array))
+                } else {
+                    None
+                }
+            }
+            Some(v) if scalar_allowed => {
+                let converted = if cast {
+                    v.cast(expected)
+                } else {
+                    v.convert(expected)
+                };
+                Some(converted).filter(|v| *v != Variant::Empty)
+            }
+            Some(_) => None,
+        };
+        match converted {
+            Some(converted) => DataValue {
+                value: Some(converted),
+                ..value
+            },
+            None => {
+                debug!(
+                    "Field {} does not have the type {:?} of its metadata",
+                    field.name, expected
+                );
+                DataValue {
+                    value: None,
+                    status: Some(StatusCode::BadTypeMismatch),
+                    ..value
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,475 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UADP `DataSetMessage` described in Part 14 7.2.2.3, i.e. the values of the fields
+//! of a data set that a data set writer sends in the payload of a network message.
+
+use std::io::{Read, Write};
+
+use crate::types::{status_code::StatusCode, *};
+
+// DataSetFlags1
+const DATA_SET_MESSAGE_VALID: u8 = 0x01;
+const FIELD_ENCODING_MASK: u8 = 0x06;
+const FIELD_ENCODING_SHIFT: u8 = 1;
+const SEQUENCE_NUMBER_ENABLED: u8 = 0x08;
+const STATUS_ENABLED: u8 = 0x10;
+const MAJOR_VERSION_ENABLED: u8 = 0x20;
+const MINOR_VERSION_ENABLED: u8 = 0x40;
+const DATA_SET_FLAGS_2_ENABLED: u8 = 0x80;
+
+// DataSetFlags2
+const MESSAGE_TYPE_MASK: u8 = 0x0f;
+const TIMESTAMP_ENABLED: u8 = 0x10;
+const PICO_SECONDS_ENABLED: u8 = 0x20;
+
+const MESSAGE_TYPE_KEY_FRAME: u8 = 0;
+const MESSAGE_TYPE_DELTA_FRAME: u8 = 1;
+const MESSAGE_TYPE_KEEP_ALIVE: u8 = 3;
+
+/// How the fields of a data set message are encoded
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FieldEncoding {
+    /// Each field is a `Variant`
+    Variant,
+    /// Each field is the value alone, without any type information. The metadata of the data
+    /// set is needed to decode the fields.
+    RawData,
+    /// Each field is a `DataValue`
+    DataValue,
+}
+
+impl From<DataSetFieldContentMask> for FieldEncoding {
+    /// The field encoding that a data set writer uses for its content mask
+    fn from(mask: DataSetFieldContentMask) -> Self {
+        if mask.contains(DataSetFieldContentMask::RawData) {
+            FieldEncoding::RawData
+        } else if mask.is_empty() {
+            FieldEncoding::Variant
+        } else {
+            FieldEncoding::DataValue
+        }
+    }
+}
+
+impl FieldEncoding {
+    fn bits(&self) -> u8 {
+        match self {
+            FieldEncoding::Variant => 0,
+            FieldEncoding::RawData => 1,
+            FieldEncoding::DataValue => 2,
+        }
+    }
+
+    fn from_bits(bits: u8) -> EncodingResult<Self> {
+        match bits {
+            0 => Ok(FieldEncoding::Variant),
+            1 => Ok(FieldEncoding::RawData),
+            2 => Ok(FieldEncoding::DataValue),
+            _ => {
+                error!("Invalid field encoding {}", bits);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    fn byte_len_field(&self, value: &DataValue) -> usize {
+        match self {
+            FieldEncoding::Variant => value.value.as_ref().map_or(1, |v| v.byte_len()),
+            FieldEncoding::RawData => value.value.as_ref().map_or(0, raw_data::byte_len),
+            FieldEncoding::DataValue => value.byte_len(),
+        }
+    }
+
+    fn encode_field<S: Write>(&self, stream: &mut S, value: &DataValue) -> EncodingResult<usize> {
+        match self {
+            FieldEncoding::Variant => match value.value {
+                Some(ref v) => v.encode(stream),
+                None => Variant::Empty.encode(stream),
+            },
+            FieldEncoding::RawData => match value.value {
+                Some(ref v) => raw_data::encode(stream, v),
+                None => Ok(0),
+            },
+            FieldEncoding::DataValue => value.encode(stream),
+        }
+    }
+
+    fn decode_field<S: Read>(
+        &self,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<DataValue> {
+        match self {
+            FieldEncoding::Variant => Ok(DataValue::value_only(Variant::decode(
+                stream,
+                decoding_options,
+            )?)),
+            FieldEncoding::DataValue => DataValue::decode(stream, decoding_options),
+            FieldEncoding::RawData => {
+                error!("Raw data fields cannot be decoded without the metadata of the data set");
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Encoding of a field value in the raw data field encoding, which is the value without its
+/// variant encoding mask. Arrays are written as their length followed by their elements.
+pub(crate) mod raw_data {
+    use std::io::{Read, Write};
+
+    use crate::types::{status_code::StatusCode, *};
+
+    pub(crate) fn byte_len(value: &Variant) -> usize {
+        match value {
+            Variant::Array(array) => {
+                4 + array
+                    .values
+                    .iter()
+                    .map(Variant::byte_len_variant_value)
+                    .sum::<usize>()
+            }
+            value => Variant::byte_len_variant_value(value),
+        }
+    }
+
+    pub(crate) fn encode<S: Write>(stream: &mut S, value: &Variant) -> EncodingResult<usize> {
+        match value {
+            Variant::Array(array) => {
+                if array.dimensions.as_ref().is_some_and(|d| d.len() > 1) {
+                    error!("Multi-dimensional arrays cannot be written as raw data");
+                    return Err(StatusCode::BadEncodingError);
+                }
+                let mut size = write_i32(stream, array.values.len() as i32)?;
+                for value in array.values.iter() {
+                    size += Variant::encode_variant_value(stream, value)?;
+                }
+                Ok(size)
+            }
+            value => Variant::encode_variant_value(stream, value),
+        }
+    }
+
+    /// Reads a value of the built in type, which is an array when the value rank is not scalar
+    pub(crate) fn decode<S: Read>(
+        stream: &mut S,
+        built_in_type: u8,
+        value_rank: i32,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Variant> {
+        let value_type = VariantTypeId::from_encoding_mask(built_in_type)?;
+        if value_type == VariantTypeId::Empty {
+            error!("Raw data fields need a built in type to be decoded");
+            return Err(StatusCode::BadDecodingError);
+        }
+        if value_rank < 0 && value_rank != -3 {
+            // A field of any type is a whole variant
+            return if value_type == VariantTypeId::Variant {
+                Variant::decode(stream, decoding_options)
+            } else {
+                Variant::decode_variant_value(stream, built_in_type, decoding_options)
+            };
+        }
+        if value_rank > 1 {
+            error!("Multi-dimensional arrays cannot be read as raw data");
+            return Err(StatusCode::BadDecodingError);
+        }
+        let len = read_i32(stream)?;
+        if len < 0 {
+            return Ok(Variant::from(Array::new(value_type, Vec::new())?));
+        }
+        if len as usize > decoding_options.max_array_length {
+            error!(
+                "Array length {} exceeds decoding limit {}",
+                len, decoding_options.max_array_length
+            );
+            return Err(StatusCode::BadDecodingError);
+        }
+        let values = (0..len)
+            .map(|_| Variant::decode_variant_value(stream, built_in_type, decoding_options))
+            .collect::<EncodingResult<Vec<_>>>()?;
+        Ok(Variant::from(Array::new(value_type, values)?))
+    }
+}
+
+/// The content of a data set message
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataSetMessagePayload {
+    /// The values of all of the fields of the data set
+    KeyFrame(Vec<DataValue>),
+    /// The values of the fields that changed since the last message, by field index
+    DeltaFrame(Vec<(u16, DataValue)>),
+    /// No values, sent to say that the data set writer is still alive when nothing has changed
+    KeepAlive,
+    /// A key frame with raw data fields that has not been decoded yet, holding the number of
+    /// fields and their encoded bytes
+    RawKeyFrame { field_count: u16, data: Vec<u8> },
+}
+
+/// A data set message, i.e. the content of a data set from one data set writer
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetMessage {
+    pub field_encoding: FieldEncoding,
+    pub sequence_number: Option<u16>,
+    pub timestamp: Option<DateTime>,
+    pub pico_seconds: Option<u16>,
+    /// The status of the data set. Only the severity and sub code of the status code are sent.
+    pub status: Option<StatusCode>,
+    /// The major version of the configuration of the data set
+    pub major_version: Option<u32>,
+    /// The minor version of the configuration of the data set
+    pub minor_version: Option<u32>,
+    pub payload: DataSetMessagePayload,
+}
+
+impl BinaryEncoder<DataSetMessage> for DataSetMessage {
+    fn byte_len(&self) -> usize {
+        let mut size = 1;
+        if self.data_set_flags_2() != 0 {
+            size += 1;
+        }
+        size += self.sequence_number.map_or(0, |_| 2);
+        size += self.timestamp.as_ref().map_or(0, |v| v.byte_len());
+        size += self.pico_seconds.map_or(0, |_| 2);
+        size += self.status.map_or(0, |_| 2);
+        size += self.major_version.map_or(0, |_| 4);
+        size += self.minor_version.map_or(0, |_| 4);
+        size += match self.payload {
+            DataSetMessagePayload::KeyFrame(ref fields) => {
+                2 + fields
+                    .iter()
+                    .map(|v| self.field_encoding.byte_len_field(v))
+                    .sum::<usize>()
+            }
+            DataSetMessagePayload::DeltaFrame(ref fields) => {
+                2 + fields
+                    .iter()
+                    .map(|(_, v)| 2 + self.field_encoding.byte_len_field(v))
+                    .sum::<usize>()
+            }
+            DataSetMessagePayload::KeepAlive => 0,
+            DataSetMessagePayload::RawKeyFrame { ref data, .. } => 2 + data.len(),
+        };
+        size
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let data_set_flags_2 = self.data_set_flags_2();
+        let mut data_set_flags_1 =
+            DATA_SET_MESSAGE_VALID | (self.field_encoding.bits() << FIELD_ENCODING_SHIFT);
+        if self.sequence_number.is_some() {
+            data_set_flags_1 |= SEQUENCE_NUMBER_ENABLED;
+        }
+        if self.status.is_some() {
+            data_set_flags_1 |= STATUS_ENABLED;
+        }
+        if self.major_version.is_some() {
+            data_set_flags_1 |= MAJOR_VERSION_ENABLED;
+        }
+        if self.minor_version.is_some() {
+            data_set_flags_1 |= MINOR_VERSION_ENABLED;
+        }
+        if data_set_flags_2 != 0 {
+            data_set_flags_1 |= DATA_SET_FLAGS_2_ENABLED;
+        }
+
+        let mut size = write_u8(stream, data_set_flags_1)?;
+        if data_set_flags_2 != 0 {
+            size += write_u8(stream, data_set_flags_2)?;
+        }
+        if let Some(sequence_number) = self.sequence_number {
+            size += write_u16(stream, sequence_number)?;
+        }
+        if let Some(ref timestamp) = self.timestamp {
+            size += timestamp.encode(stream)?;
+        }
+        if let Some(pico_seconds) = self.pico_seconds {
+            size += write_u16(stream, pico_seconds)?;
+        }
+        if let Some(status) = self.status {
+            size += write_u16(stream, (status.bits() >> 16) as u16)?;
+        }
+        if let Some(major_version) = self.major_version {
+            size += write_u32(stream, major_version)?;
+        }
+        if let Some(minor_version) = self.minor_version {
+            size += write_u32(stream, minor_version)?;
+        }
+        match self.payload {
+            DataSetMessagePayload::KeyFrame(ref fields) => {
+                size += write_u16(stream, Self::field_count(fields.len())?)?;
+                for field in fields {
+                    size += self.field_encoding.encode_field(stream, field)?;
+                }
+            }
+            DataSetMessagePayload::DeltaFrame(ref fields) => {
+                size += write_u16(stream, Self::field_count(fields.len())?)?;
+                for (field_index, field) in fields {
+                    size += write_u16(stream, *field_index)?;
+                    size += self.field_encoding.encode_field(stream, field)?;
+                }
+            }
+            DataSetMessagePayload::KeepAlive => {}
+            DataSetMessagePayload::RawKeyFrame {
+                field_count,
+                ref data,
+            } => {
+                size += write_u16(stream, field_count)?;
+                size += process_encode_io_result(stream.write(data))?;
+            }
+        }
+        Ok(size)
+    }
+
+    /// Decodes the message from a stream that holds it and nothing else, since a key frame with
+    /// raw data fields is read to the end of the stream.
+    fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let data_set_flags_1 = read_u8(stream)?;
+        if data_set_flags_1 & DATA_SET_MESSAGE_VALID == 0 {
+            error!("Data set message is not valid");
+            return Err(StatusCode::BadDecodingError);
+        }
+        let field_encoding = FieldEncoding::from_bits(
+            (data_set_flags_1 & FIELD_ENCODING_MASK) >> FIELD_ENCODING_SHIFT,
+        )?;
+        let data_set_flags_2 = if data_set_flags_1 & DATA_SET_FLAGS_2_ENABLED != 0 {
+            read_u8(stream)?
+        } else {
+            0
+        };
+        let sequence_number = if data_set_flags_1 & SEQUENCE_NUMBER_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        let timestamp = if data_set_flags_2 & TIMESTAMP_ENABLED != 0 {
+            Some(DateTime::decode(stream, decoding_options)?)
+        } else {
+            None
+        };
+        let pico_seconds = if data_set_flags_2 & PICO_SECONDS_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        let status = if data_set_flags_1 & STATUS_ENABLED != 0 {
+            Some(StatusCode::from_bits_truncate(
+                (read_u16(stream)? as u32) << 16,
+            ))
+        } else {
+            None
+        };
+        let major_version = if data_set_flags_1 & MAJOR_VERSION_ENABLED != 0 {
+            Some(read_u32(stream)?)
+        } else {
+            None
+        };
+        let minor_version = if data_set_flags_1 & MINOR_VERSION_ENABLED != 0 {
+            Some(read_u32(stream)?)
+        } else {
+            None
+        };
+
+        let payload = match data_set_flags_2 & MESSAGE_TYPE_MASK {
+            MESSAGE_TYPE_KEY_FRAME => {
+                let field_count = read_u16(stream)?;
+                if field_encoding == FieldEncoding::RawData {
+                    let mut data = Vec::new();
+                    process_decode_io_result(stream.read_to_end(&mut data))?;
+                    DataSetMessagePayload::RawKeyFrame { field_count, data }
+                } else {
+                    Self::check_field_count(field_count, decoding_options)?;
+                    let fields = (0..field_count)
+                        .map(|_| field_encoding.decode_field(stream, decoding_options))
+                        .collect::<EncodingResult<Vec<_>>>()?;
+                    DataSetMessagePayload::KeyFrame(fields)
+                }
+            }
+            MESSAGE_TYPE_DELTA_FRAME => {
+                let field_count = read_u16(stream)?;
+                Self::check_field_count(field_count, decoding_options)?;
+                let fields = (0..field_count)
+                    .map(|_| {
+                        let field_index = read_u16(stream)?;
+                        let field = field_encoding.decode_field(stream, decoding_options)?;
+                        Ok((field_index, field))
+                    })
+                    .collect::<EncodingResult<Vec<_>>>()?;
+                DataSetMessagePayload::DeltaFrame(fields)
+            }
+            MESSAGE_TYPE_KEEP_ALIVE => DataSetMessagePayload::KeepAlive,
+            message_type => {
+                error!("Unsupported data set message type {}", message_type);
+                return Err(StatusCode::BadNotSupported);
+            }
+        };
+
+        Ok(DataSetMessage {
+            field_encoding,
+            sequence_number,
+            timestamp,
+            pico_seconds,
+            status,
+            major_version,
+            minor_version,
+            payload,
+        })
+    }
+}
+
+impl DataSetMessage {
+    /// Creates a message with the payload and no optional header fields
+    pub fn new(field_encoding: FieldEncoding, payload: DataSetMessagePayload) -> DataSetMessage {
+        DataSetMessage {
+            field_encoding,
+            sequence_number: None,
+            timestamp: None,
+            pico_seconds: None,
+            status: None,
+            major_version: None,
+            minor_version: None,
+            payload,
+        }
+    }
+
+    fn data_set_flags_2(&self) -> u8 {
+        let mut flags = match self.payload {
+            DataSetMessagePayload::KeyFrame(_) | DataSetMessagePayload::RawKeyFrame { .. } => {
+                MESSAGE_TYPE_KEY_FRAME
+            }
+            DataSetMessagePayload::DeltaFrame(_) => MESSAGE_TYPE_DELTA_FRAME,
+            DataSetMessagePayload::KeepAlive => MESSAGE_TYPE_KEEP_ALIVE,
+        };
+        if self.timestamp.is_some() {
+            flags |= TIMESTAMP_ENABLED;
+        }
+        if self.pico_seconds.is_some() {
+            flags |= PICO_SECONDS_ENABLED;
+        }
+        flags
+    }
+
+    fn field_count(count: usize) -> EncodingResult<u16> {
+        u16::try_from(count).map_err(|_| {
+            error!("Too many fields, {}, in data set message", count);
+            StatusCode::BadEncodingLimitsExceeded
+        })
+    }
+
+    fn check_field_count(
+        field_count: u16,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<()> {
+        if field_count as usize > decoding_options.max_array_length {
+            error!(
+                "Field count {} exceeds decoding limit {}",
+                field_count, decoding_options.max_array_length
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(())
+        }
+    }
+}
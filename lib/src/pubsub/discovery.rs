@@ -0,0 +1,499 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UADP discovery messages described in Part 14 7.2.2.4. A subscriber sends a
+//! discovery request to ask publishers for their endpoints, the metadata of their data sets or the
+//! configuration of their data set writers, and publishers answer with discovery responses. A
+//! publisher may also send the responses unsolicited, as announcements, e.g. when the metadata of
+//! a data set changes.
+//!
+//! This allows a subscriber to bootstrap the metadata it needs to decode data set messages without
+//! configuring it out of band.
+
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+use crate::types::{status_code::StatusCode, *};
+
+/// The type of a discovery request, of which only one is defined
+const REQUEST_TYPE_PUBLISHER_INFORMATION: u8 = 1;
+
+/// The information that a discovery request asks for, and that a discovery response holds
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InformationType {
+    /// The endpoints of the server of the publisher
+    PublisherEndpoints = 1,
+    /// The metadata of the data sets that data set writers publish
+    DataSetMetaData = 2,
+    /// The configuration of data set writers and their writer groups
+    DataSetWriterConfiguration = 3,
+}
+
+impl InformationType {
+    fn decode<S: Read>(stream: &mut S) -> EncodingResult<Self> {
+        match read_u8(stream)? {
+            1 => Ok(InformationType::PublisherEndpoints),
+            2 => Ok(InformationType::DataSetMetaData),
+            3 => Ok(InformationType::DataSetWriterConfiguration),
+            v => {
+                error!("Invalid discovery information type {}", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// A request from a subscriber for information about publishers
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryRequest {
+    pub information_type: InformationType,
+    /// The data set writers the request is for. This is ignored for the publisher endpoints and
+    /// a null or empty array means all of the data set writers of a publisher.
+    pub data_set_writer_ids: Option<Vec<u16>>,
+}
+
+impl BinaryEncoder<DiscoveryRequest> for DiscoveryRequest {
+    fn byte_len(&self) -> usize {
+        2 + byte_len_array(&self.data_set_writer_ids)
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut size = write_u8(stream, REQUEST_TYPE_PUBLISHER_INFORMATION)?;
+        size += write_u8(stream, self.information_type as u8)?;
+        size += write_array(stream, &self.data_set_writer_ids)?;
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let request_type = read_u8(stream)?;
+        if request_type != REQUEST_TYPE_PUBLISHER_INFORMATION {
+            error!("Invalid discovery request type {}", request_type);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let information_type = InformationType::decode(stream)?;
+        let data_set_writer_ids = read_array(stream, decoding_options)?;
+        Ok(DiscoveryRequest {
+            information_type,
+            data_set_writer_ids,
+        })
+    }
+}
+
+impl DiscoveryRequest {
+    /// Creates a request for the given information about the data set writers
+    pub fn new(information_type: InformationType, data_set_writer_ids: &[u16]) -> Self {
+        DiscoveryRequest {
+            information_type,
+            data_set_writer_ids: Some(data_set_writer_ids.to_vec()),
+        }
+    }
+
+    /// Tests if the request is for the data set writer, i.e. if it names the writer or names none
+    fn includes(&self, data_set_writer_id: u16) -> bool {
+        match self.data_set_writer_ids {
+            Some(ref ids) if !ids.is_empty() => ids.contains(&data_set_writer_id),
+            _ => true,
+        }
+    }
+}
+
+/// The information held by a discovery response
+#[derive(Debug, Clone, PartialEq)]
+pub enum Announcement {
+    PublisherEndpoints {
+        endpoints: Option<Vec<EndpointDescription>>,
+        status_code: StatusCode,
+    },
+    DataSetMetaData {
+        data_set_writer_id: u16,
+        meta_data: DataSetMetaDataType,
+        status_code: StatusCode,
+    },
+    DataSetWriterConfiguration {
+        data_set_writer_ids: Option<Vec<u16>>,
+        /// The writer group holding the configuration of the data set writers
+        writer_group: WriterGroupDataType,
+        /// The status of each data set writer
+        status_codes: Option<Vec<StatusCode>>,
+    },
+}
+
+impl Announcement {
+    /// The type of information in the announcement
+    pub fn information_type(&self) -> InformationType {
+        match self {
+            Announcement::PublisherEndpoints { .. } => InformationType::PublisherEndpoints,
+            Announcement::DataSetMetaData { .. } => InformationType::DataSetMetaData,
+            Announcement::DataSetWriterConfiguration { .. } => {
+                InformationType::DataSetWriterConfiguration
+            }
+        }
+    }
+}
+
+/// A response from a publisher to a discovery request, or an unsolicited announcement of the same
+/// information
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryResponse {
+    /// Incremented by the publisher for each response it sends
+    pub sequence_number: u16,
+    pub announcement: Announcement,
+}
+
+impl BinaryEncoder<DiscoveryResponse> for DiscoveryResponse {
+    fn byte_len(&self) -> usize {
+        let mut size = 3;
+        size += match self.announcement {
+            Announcement::PublisherEndpoints {
+                ref endpoints,
+                ref status_code,
+            } => byte_len_array(endpoints) + status_code.byte_len(),
+            Announcement::DataSetMetaData {
+                ref meta_data,
+                ref status_code,
+                ..
+            } => 2 + meta_data.byte_len() + status_code.byte_len(),
+            Announcement::DataSetWriterConfiguration {
+                ref data_set_writer_ids,
+                ref writer_group,
+                ref status_codes,
+            } => {
+                byte_len_array(data_set_writer_ids)
+                    + writer_group.byte_len()
+                    + byte_len_array(status_codes)
+            }
+        };
+        size
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut size = write_u8(stream, self.announcement.information_type() as u8)?;
+        size += write_u16(stream, self.sequence_number)?;
+        size += match self.announcement {
+            Announcement::PublisherEndpoints {
+                ref endpoints,
+                ref status_code,
+            } => write_array(stream, endpoints)? + status_code.encode(stream)?,
+            Announcement::DataSetMetaData {
+                data_set_writer_id,
+                ref meta_data,
+                ref status_code,
+            } => {
+                write_u16(stream, data_set_writer_id)?
+                    + meta_data.encode(stream)?
+                    + status_code.encode(stream)?
+            }
+            Announcement::DataSetWriterConfiguration {
+                ref data_set_writer_ids,
+                ref writer_group,
+                ref status_codes,
+            } => {
+                write_array(stream, data_set_writer_ids)?
+                    + writer_group.encode(stream)?
+                    + write_array(stream, status_codes)?
+            }
+        };
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let information_type = InformationType::decode(stream)?;
+        let sequence_number = read_u16(stream)?;
+        let announcement = match information_type {
+            InformationType::PublisherEndpoints => Announcement::PublisherEndpoints {
+                endpoints: read_array(stream, decoding_options)?,
+                status_code: StatusCode::decode(stream, decoding_options)?,
+            },
+            InformationType::DataSetMetaData => Announcement::DataSetMetaData {
+                data_set_writer_id: read_u16(stream)?,
+                meta_data: DataSetMetaDataType::decode(stream, decoding_options)?,
+                status_code: StatusCode::decode(stream, decoding_options)?,
+            },
+            InformationType::DataSetWriterConfiguration => {
+                Announcement::DataSetWriterConfiguration {
+                    data_set_writer_ids: read_array(stream, decoding_options)?,
+                    writer_group: WriterGroupDataType::decode(stream, decoding_options)?,
+                    status_codes: read_array(stream, decoding_options)?,
+                }
+            }
+        };
+        Ok(DiscoveryResponse {
+            sequence_number,
+            announcement,
+        })
+    }
+}
+
+/// The information about a publisher that can be discovered. A publisher fills it in from its
+/// configuration to answer discovery requests, and a subscriber builds it up from the responses
+/// and announcements it receives.
+#[derive(Debug, Clone, Default)]
+pub struct PublisherInformation {
+    /// The endpoints of the server of the publisher
+    pub endpoints: Vec<EndpointDescription>,
+    /// The metadata of the data set published by each data set writer, by data set writer id
+    pub meta_data: BTreeMap<u16, DataSetMetaDataType>,
+    /// The writer groups of the publisher and their data set writers
+    pub writer_groups: Vec<WriterGroupDataType>,
+    /// The sequence number of the last response
+    sequence_number: u16,
+}
+
+impl PublisherInformation {
+    /// Answers a discovery request with the responses that a publisher sends back. A data set
+    /// writer that is requested but which the publisher doesn't have is answered with
+    /// `BadNotFound`.
+    pub fn respond(&mut self, request: &DiscoveryRequest) -> Vec<DiscoveryResponse> {
+        let announcements = match request.information_type {
+            InformationType::PublisherEndpoints => vec![Announcement::PublisherEndpoints {
+                endpoints: Some(self.endpoints.clone()),
+                status_code: StatusCode::Good,
+            }],
+            InformationType::DataSetMetaData => self.meta_data_announcements(request),
+            InformationType::DataSetWriterConfiguration => {
+                self.writer_configuration_announcements(request)
+            }
+        };
+        announcements
+            .into_iter()
+            .map(|announcement| self.announce(announcement))
+            .collect()
+    }
+
+    /// Makes a response to announce the information unsolicited, e.g. after the metadata of a
+    /// data set has changed
+    pub fn announce(&mut self, announcement: Announcement) -> DiscoveryResponse {
+        self.sequence_number = self.sequence_number.wrapping_add(1);
+        DiscoveryResponse {
+            sequence_number: self.sequence_number,
+            announcement,
+        }
+    }
+
+    /// Updates the information from a response or announcement that was received from the
+    /// publisher. Information with a bad status is ignored.
+    pub fn update(&mut self, response: &DiscoveryResponse) {
+        match response.announcement {
+            Announcement::PublisherEndpoints {
+                ref endpoints,
+                status_code,
+            } => {
+                if status_code.is_good() {
+                    self.endpoints = endpoints.clone().unwrap_or_default();
+                }
+            }
+            Announcement::DataSetMetaData {
+                data_set_writer_id,
+                ref meta_data,
+                status_code,
+            } => {
+                if status_code.is_good() {
+                    self.meta_data.insert(data_set_writer_id, meta_data.clone());
+                }
+            }
+            Announcement::DataSetWriterConfiguration {
+                ref data_set_writer_ids,
+                ref writer_group,
+                ref status_codes,
+            } => {
+                let data_set_writer_ids = data_set_writer_ids.as_deref().unwrap_or_default();
+                let status_codes = status_codes.as_deref().unwrap_or_default();
+                let data_set_writers = writer_group
+                    .data_set_writers
+                    .iter()
+                    .flatten()
+                    .filter(|writer| {
+                        data_set_writer_ids
+                            .iter()
+                            .position(|id| *id == writer.data_set_writer_id)
+                            .is_some_and(|idx| status_codes.get(idx).is_some_and(|s| s.is_good()))
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !data_set_writers.is_empty() {
+                    self.update_writer_group(writer_group, data_set_writers);
+                }
+            }
+        }
+    }
+
+    /// Finds the data set writer with the id and the writer group it belongs to
+    pub fn find_data_set_writer(
+        &self,
+        data_set_writer_id: u16,
+    ) -> Option<(&WriterGroupDataType, &DataSetWriterDataType)> {
+        self.writer_groups.iter().find_map(|writer_group| {
+            writer_group
+                .data_set_writers
+                .iter()
+                .flatten()
+                .find(|writer| writer.data_set_writer_id == data_set_writer_id)
+                .map(|writer| (writer_group, writer))
+        })
+    }
+
+    fn meta_data_announcements(&self, request: &DiscoveryRequest) -> Vec<Announcement> {
+        match request.data_set_writer_ids {
+            Some(ref ids) if !ids.is_empty() => ids
+                .iter()
+                .map(
+                    |data_set_writer_id| match self.meta_data.get(data_set_writer_id) {
+                        Some(meta_data) => Announcement::DataSetMetaData {
+                            data_set_writer_id: *data_set_writer_id,
+                            meta_data: meta_data.clone(),
+                            status_code: StatusCode::Good,
+                        },
+                        None => Announcement::DataSetMetaData {
+                            data_set_writer_id: *data_set_writer_id,
+                            meta_data: Self::empty_meta_data(),
+                            status_code: StatusCode::BadNotFound,
+                        },
+                    },
+                )
+                .collect(),
+            _ => self
+                .meta_data
+                .iter()
+                .map(
+                    |(data_set_writer_id, meta_data)| Announcement::DataSetMetaData {
+                        data_set_writer_id: *data_set_writer_id,
+                        meta_data: meta_data.clone(),
+                        status_code: StatusCode::Good,
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    /// Makes an announcement for each writer group with the requested data set writers in it,
+    /// plus one for the requested writers that are not found
+    fn writer_configuration_announcements(&self, request: &DiscoveryRequest) -> Vec<Announcement> {
+        let mut announcements = self
+            .writer_groups
+            .iter()
+            .filter_map(|writer_group| {
+                let data_set_writers = writer_group
+                    .data_set_writers
+                    .iter()
+                    .flatten()
+                    .filter(|writer| request.includes(writer.data_set_writer_id))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if data_set_writers.is_empty() {
+                    None
+                } else {
+                    let data_set_writer_ids = data_set_writers
+                        .iter()
+                        .map(|writer| writer.data_set_writer_id)
+                        .collect::<Vec<_>>();
+                    let status_codes = vec![StatusCode::Good; data_set_writer_ids.len()];
+                    let writer_group = WriterGroupDataType {
+                        data_set_writers: Some(data_set_writers),
+                        ..writer_group.clone()
+                    };
+                    Some(Announcement::DataSetWriterConfiguration {
+                        data_set_writer_ids: Some(data_set_writer_ids),
+                        writer_group,
+                        status_codes: Some(status_codes),
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let not_found = request
+            .data_set_writer_ids
+            .iter()
+            .flatten()
+            .filter(|id| self.find_data_set_writer(**id).is_none())
+            .copied()
+            .collect::<Vec<_>>();
+        if !not_found.is_empty() {
+            let status_codes = vec![StatusCode::BadNotFound; not_found.len()];
+            announcements.push(Announcement::DataSetWriterConfiguration {
+                data_set_writer_ids: Some(not_found),
+                writer_group: Self::empty_writer_group(),
+                status_codes: Some(status_codes),
+            });
+        }
+        announcements
+    }
+
+    /// Merges data set writers from an announcement into the writer group with the same id,
+    /// replacing any writers with the same ids, or adds the writer group if it is new
+    fn update_writer_group(
+        &mut self,
+        writer_group: &WriterGroupDataType,
+        data_set_writers: Vec<DataSetWriterDataType>,
+    ) {
+        // A data set writer can only belong to one writer group
+        self.writer_groups.iter_mut().for_each(|writer_group| {
+            if let Some(ref mut writers) = writer_group.data_set_writers {
+                writers.retain(|writer| {
+                    !data_set_writers
+                        .iter()
+                        .any(|w| w.data_set_writer_id == writer.data_set_writer_id)
+                });
+            }
+        });
+        if let Some(existing) = self
+            .writer_groups
+            .iter_mut()
+            .find(|existing| existing.writer_group_id == writer_group.writer_group_id)
+        {
+            let mut writers = existing.data_set_writers.take().unwrap_or_default();
+            writers.extend(data_set_writers);
+            *existing = WriterGroupDataType {
+                data_set_writers: Some(writers),
+                ..writer_group.clone()
+            };
+        } else {
+            self.writer_groups.push(WriterGroupDataType {
+                data_set_writers: Some(data_set_writers),
+                ..writer_group.clone()
+            });
+        }
+    }
+
+    /// The metadata sent with a bad status
+    fn empty_meta_data() -> DataSetMetaDataType {
+        DataSetMetaDataType {
+            namespaces: None,
+            structure_data_types: None,
+            enum_data_types: None,
+            simple_data_types: None,
+            name: UAString::null(),
+            description: LocalizedText::null(),
+            fields: None,
+            data_set_class_id: Guid::null(),
+            configuration_version: ConfigurationVersionDataType {
+                major_version: 0,
+                minor_version: 0,
+            },
+        }
+    }
+
+    /// The writer group sent for data set writers that are not found
+    fn empty_writer_group() -> WriterGroupDataType {
+        WriterGroupDataType {
+            name: UAString::null(),
+            enabled: false,
+            security_mode: MessageSecurityMode::Invalid,
+            security_group_id: UAString::null(),
+            security_key_services: None,
+            max_network_message_size: 0,
+            group_properties: None,
+            writer_group_id: 0,
+            publishing_interval: 0f64,
+            keep_alive_time: 0f64,
+            priority: 0,
+            locale_ids: None,
+            header_layout_uri: UAString::null(),
+            transport_settings: ExtensionObject::null(),
+            message_settings: ExtensionObject::null(),
+            data_set_writers: None,
+        }
+    }
+}
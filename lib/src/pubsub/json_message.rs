@@ -0,0 +1,432 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the JSON message mapping described in Part 14 7.2.5, which is what publishers send
+//! through brokers such as MQTT. Unlike UADP, the fields of a JSON data set message are named so
+//! they can be read without the metadata of the data set.
+
+use crate::types::{status_code::StatusCode, *};
+
+use super::{
+    data_set_decoder::DataSetFields,
+    data_set_message::{DataSetMessage, DataSetMessagePayload, FieldEncoding},
+};
+
+/// The message type of a network message holding data set messages
+pub const MESSAGE_TYPE_DATA: &str = "ua-data";
+/// The message type of a message holding the metadata of a data set
+pub const MESSAGE_TYPE_META_DATA: &str = "ua-metadata";
+
+const MESSAGE_TYPE_KEY_FRAME: &str = "ua-keyframe";
+const MESSAGE_TYPE_DELTA_FRAME: &str = "ua-deltaframe";
+const MESSAGE_TYPE_KEEP_ALIVE: &str = "ua-keepalive";
+
+/// The type of a JSON data set message
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum JsonDataSetMessageType {
+    KeyFrame,
+    DeltaFrame,
+    KeepAlive,
+}
+
+impl JsonDataSetMessageType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JsonDataSetMessageType::KeyFrame => MESSAGE_TYPE_KEY_FRAME,
+            JsonDataSetMessageType::DeltaFrame => MESSAGE_TYPE_DELTA_FRAME,
+            JsonDataSetMessageType::KeepAlive => MESSAGE_TYPE_KEEP_ALIVE,
+        }
+    }
+
+    fn from_str(message_type: Option<&str>) -> EncodingResult<Self> {
+        match message_type {
+            // Key frame is the default when the type is left out
+            None | Some(MESSAGE_TYPE_KEY_FRAME) => Ok(JsonDataSetMessageType::KeyFrame),
+            Some(MESSAGE_TYPE_DELTA_FRAME) => Ok(JsonDataSetMessageType::DeltaFrame),
+            Some(MESSAGE_TYPE_KEEP_ALIVE) => Ok(JsonDataSetMessageType::KeepAlive),
+            Some(message_type) => {
+                error!("Unsupported data set message type {}", message_type);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// A JSON data set message, i.e. the fields of a data set from one data set writer
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDataSetMessage {
+    pub data_set_writer_id: u16,
+    pub sequence_number: Option<u32>,
+    /// The configuration version of the data set
+    pub meta_data_version: Option<ConfigurationVersionDataType>,
+    pub timestamp: Option<DateTime>,
+    pub status: Option<StatusCode>,
+    pub message_type: JsonDataSetMessageType,
+    /// How the fields are written. Variant and data value fields use the requested encoding and
+    /// raw data fields are always written non-reversibly, i.e. as the value alone.
+    pub field_encoding: FieldEncoding,
+    /// The fields by name. Decoded fields are in the order of their names.
+    pub payload: Vec<(String, DataValue)>,
+}
+
+impl JsonEncoder for JsonDataSetMessage {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "DataSetWriterId",
+            &self.data_set_writer_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SequenceNumber",
+            &self.sequence_number,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MetaDataVersion",
+            &self.meta_data_version,
+            encoding,
+        );
+        write_json_field(&mut object, "Timestamp", &self.timestamp, encoding);
+        write_json_field(&mut object, "Status", &self.status, encoding);
+        object.insert(
+            "MessageType".to_string(),
+            JsonValue::from(self.message_type.as_str()),
+        );
+        let field_encoding = if self.field_encoding == FieldEncoding::RawData {
+            JsonEncoding::NonReversible
+        } else {
+            encoding
+        };
+        let payload = self
+            .payload
+            .iter()
+            .map(|(name, value)| {
+                let value = match self.field_encoding {
+                    FieldEncoding::DataValue => value.encode_json(field_encoding),
+                    FieldEncoding::Variant | FieldEncoding::RawData => {
+                        value.value.encode_json(field_encoding)
+                    }
+                };
+                (name.clone(), value)
+            })
+            .collect::<JsonObject>();
+        object.insert("Payload".to_string(), JsonValue::Object(payload));
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        let message_type =
+            JsonDataSetMessageType::from_str(value.get("MessageType").and_then(|v| v.as_str()))?;
+        let mut field_encoding = None;
+        let payload = match value.get("Payload") {
+            None | Some(JsonValue::Null) => Vec::new(),
+            Some(JsonValue::Object(fields)) => fields
+                .iter()
+                .map(|(name, value)| {
+                    let (encoding, value) = Self::decode_field(value, decoding_options)?;
+                    field_encoding.get_or_insert(encoding);
+                    Ok((name.clone(), value))
+                })
+                .collect::<EncodingResult<Vec<_>>>()?,
+            Some(payload) => {
+                error!("Expected a JSON object for the payload, got {}", payload);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(JsonDataSetMessage {
+            data_set_writer_id: read_json_field(value, "DataSetWriterId", decoding_options)?,
+            sequence_number: read_json_field(value, "SequenceNumber", decoding_options)?,
+            meta_data_version: read_json_field(value, "MetaDataVersion", decoding_options)?,
+            timestamp: read_json_field(value, "Timestamp", decoding_options)?,
+            status: read_json_field(value, "Status", decoding_options)?,
+            message_type,
+            field_encoding: field_encoding.unwrap_or(FieldEncoding::Variant),
+            payload,
+        })
+    }
+}
+
+impl From<JsonDataSetMessage> for DataSetFields {
+    /// Takes the fields of the message as they are, for when there is no metadata to check them
+    fn from(message: JsonDataSetMessage) -> Self {
+        DataSetFields {
+            sequence_number: message.sequence_number.map(|v| v as u16),
+            timestamp: message.timestamp,
+            status: message.status,
+            key_frame: message.message_type == JsonDataSetMessageType::KeyFrame,
+            fields: message.payload.into_iter().collect(),
+        }
+    }
+}
+
+impl JsonDataSetMessage {
+    /// Makes the JSON form of a data set message, naming its fields with the names of the fields
+    /// of the data set
+    pub fn from_data_set_message<T>(
+        data_set_writer_id: u16,
+        message: DataSetMessage,
+        field_names: &[T],
+    ) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let name = |idx: usize| field_names.get(idx).map(|v| v.as_ref().to_string());
+        let (message_type, payload) = match message.payload {
+            DataSetMessagePayload::KeyFrame(values) => (
+                JsonDataSetMessageType::KeyFrame,
+                values
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(idx, value)| name(idx).map(|name| (name, value)))
+                    .collect(),
+            ),
+            DataSetMessagePayload::DeltaFrame(values) => (
+                JsonDataSetMessageType::DeltaFrame,
+                values
+                    .into_iter()
+                    .filter_map(|(idx, value)| name(idx as usize).map(|name| (name, value)))
+                    .collect(),
+            ),
+            DataSetMessagePayload::KeepAlive | DataSetMessagePayload::RawKeyFrame { .. } => {
+                (JsonDataSetMessageType::KeepAlive, Vec::new())
+            }
+        };
+        let meta_data_version = match (message.major_version, message.minor_version) {
+            (None, None) => None,
+            (major_version, minor_version) => Some(ConfigurationVersionDataType {
+                major_version: major_version.unwrap_or_default(),
+                minor_version: minor_version.unwrap_or_default(),
+            }),
+        };
+        JsonDataSetMessage {
+            data_set_writer_id,
+            sequence_number: message.sequence_number.map(u32::from),
+            meta_data_version,
+            timestamp: message.timestamp,
+            status: message.status,
+            message_type,
+            field_encoding: message.field_encoding,
+            payload,
+        }
+    }
+
+    /// Reads a field, telling how it was written from its JSON. A reversible variant is an object
+    /// with a type, a data value is any other object, and anything else is a non-reversible
+    /// value whose type is guessed.
+    fn decode_field(
+        value: &JsonValue,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<(FieldEncoding, DataValue)> {
+        match value {
+            JsonValue::Null => Ok((FieldEncoding::Variant, DataValue::null())),
+            JsonValue::Object(object) if object.contains_key("Type") => Ok((
+                FieldEncoding::Variant,
+                DataValue::value_only(Variant::decode_json(value, decoding_options)?),
+            )),
+            JsonValue::Object(_) => Ok((
+                FieldEncoding::DataValue,
+                DataValue::decode_json(value, decoding_options)?,
+            )),
+            JsonValue::Bool(v) => Ok((FieldEncoding::RawData, DataValue::value_only(*v))),
+            JsonValue::Number(v) => {
+                let value = match v.as_i64() {
+                    Some(v) => Variant::from(v),
+                    None => Variant::from(v.as_f64().unwrap_or(f64::NAN)),
+                };
+                Ok((FieldEncoding::RawData, DataValue::value_only(value)))
+            }
+            JsonValue::String(v) => Ok((
+                FieldEncoding::RawData,
+                DataValue::value_only(UAString::from(v.as_str())),
+            )),
+            JsonValue::Array(_) => {
+                error!("Non-reversible arrays cannot be decoded");
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// A JSON network message, i.e. the data set messages that a writer group sends at once
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonNetworkMessage {
+    /// A unique id for the message
+    pub message_id: UAString,
+    pub publisher_id: Option<UAString>,
+    pub writer_group_name: Option<UAString>,
+    pub data_set_class_id: Option<Guid>,
+    pub messages: Vec<JsonDataSetMessage>,
+}
+
+impl JsonEncoder for JsonNetworkMessage {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MessageId", &self.message_id, encoding);
+        object.insert(
+            "MessageType".to_string(),
+            JsonValue::from(MESSAGE_TYPE_DATA),
+        );
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, encoding);
+        write_json_field(
+            &mut object,
+            "WriterGroupName",
+            &self.writer_group_name,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetClassId",
+            &self.data_set_class_id,
+            encoding,
+        );
+        object.insert(
+            "Messages".to_string(),
+            JsonValue::Array(
+                self.messages
+                    .iter()
+                    .map(|v| v.encode_json(encoding))
+                    .collect(),
+            ),
+        );
+        JsonValue::Object(object)
+    }
+
+    /// Decodes a network message. A data set message sent on its own, without a network
+    /// message header, is decoded as a network message holding just that message.
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        let messages = match value.get("Messages") {
+            Some(JsonValue::Array(messages)) => {
+                if messages.len() > decoding_options.max_array_length {
+                    error!(
+                        "Array length {} exceeds decoding limit {}",
+                        messages.len(),
+                        decoding_options.max_array_length
+                    );
+                    return Err(StatusCode::BadDecodingError);
+                }
+                messages
+                    .iter()
+                    .map(|v| JsonDataSetMessage::decode_json(v, decoding_options))
+                    .collect::<EncodingResult<Vec<_>>>()?
+            }
+            Some(JsonValue::Object(_)) => vec![JsonDataSetMessage::decode_json(
+                &value["Messages"],
+                decoding_options,
+            )?],
+            None if value.get("Payload").is_some() => {
+                return Ok(JsonNetworkMessage {
+                    message_id: UAString::null(),
+                    publisher_id: None,
+                    writer_group_name: None,
+                    data_set_class_id: None,
+                    messages: vec![JsonDataSetMessage::decode_json(value, decoding_options)?],
+                });
+            }
+            _ => {
+                error!("Network message has no data set messages");
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(JsonNetworkMessage {
+            message_id: read_json_field(value, "MessageId", decoding_options)?,
+            publisher_id: read_json_field(value, "PublisherId", decoding_options)?,
+            writer_group_name: read_json_field(value, "WriterGroupName", decoding_options)?,
+            data_set_class_id: read_json_field(value, "DataSetClassId", decoding_options)?,
+            messages,
+        })
+    }
+}
+
+/// A JSON metadata message, which a publisher sends to say what the fields of the data set of a
+/// data set writer are
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonMetaDataMessage {
+    /// A unique id for the message
+    pub message_id: UAString,
+    pub publisher_id: UAString,
+    pub data_set_writer_id: u16,
+    pub meta_data: DataSetMetaDataType,
+    pub timestamp: Option<DateTime>,
+}
+
+impl JsonEncoder for JsonMetaDataMessage {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MessageId", &self.message_id, encoding);
+        object.insert(
+            "MessageType".to_string(),
+            JsonValue::from(MESSAGE_TYPE_META_DATA),
+        );
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetWriterId",
+            &self.data_set_writer_id,
+            encoding,
+        );
+        write_json_field(&mut object, "MetaData", &self.meta_data, encoding);
+        write_json_field(&mut object, "Timestamp", &self.timestamp, encoding);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(JsonMetaDataMessage {
+            message_id: read_json_field(value, "MessageId", decoding_options)?,
+            publisher_id: read_json_field(value, "PublisherId", decoding_options)?,
+            data_set_writer_id: read_json_field(value, "DataSetWriterId", decoding_options)?,
+            meta_data: read_json_field(value, "MetaData", decoding_options)?,
+            timestamp: read_json_field(value, "Timestamp", decoding_options)?,
+        })
+    }
+}
+
+/// A message received from a broker, which is either data or metadata
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonMessage {
+    Data(JsonNetworkMessage),
+    MetaData(JsonMetaDataMessage),
+}
+
+impl JsonEncoder for JsonMessage {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        match self {
+            JsonMessage::Data(v) => v.encode_json(encoding),
+            JsonMessage::MetaData(v) => v.encode_json(encoding),
+        }
+    }
+
+    /// Decodes a message by its message type. A message without a type is data, as is a data
+    /// set message sent on its own.
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        match value.get("MessageType").and_then(|v| v.as_str()) {
+            Some(MESSAGE_TYPE_META_DATA) => Ok(JsonMessage::MetaData(
+                JsonMetaDataMessage::decode_json(value, decoding_options)?,
+            )),
+            Some(MESSAGE_TYPE_DATA)
+            | Some(MESSAGE_TYPE_KEY_FRAME)
+            | Some(MESSAGE_TYPE_DELTA_FRAME)
+            | Some(MESSAGE_TYPE_KEEP_ALIVE)
+            | None => Ok(JsonMessage::Data(JsonNetworkMessage::decode_json(
+                value,
+                decoding_options,
+            )?)),
+            Some(message_type) => {
+                error!("Unsupported message type {}", message_type);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Makes a unique id for a message
+pub(crate) fn message_id() -> UAString {
+    UAString::from(Guid::new().to_string())
+}
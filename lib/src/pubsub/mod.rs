@@ -0,0 +1,47 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! The OPC UA PubSub module, described in Part 14. PubSub lets publishers send data to any number
+//! of subscribers over a datagram or broker based transport rather than through client / server
+//! sessions.
+//!
+//! Messages are encoded with the UADP mapping, i.e. as binary `NetworkMessage`s, and sent over
+//! UDP. A `Publisher` sends the values of variables in a server's address space and a
+//! `Subscriber` decodes the data set messages it receives into named fields with a
+//! `DataSetDecoder`.
+//!
+//! With the `pubsub-mqtt` feature, publishers and subscribers can instead use an MQTT broker,
+//! sending data set messages and metadata as JSON.
+//!
+//! UADP messages can be signed and encrypted with the keys of a security group, which are handed
+//! out by a security key service.
+
+pub mod data_set_decoder;
+pub mod data_set_message;
+pub mod discovery;
+pub mod json_message;
+#[cfg(feature = "pubsub-mqtt")]
+pub mod mqtt;
+pub mod network_message;
+#[cfg(feature = "server")]
+pub mod publisher;
+pub mod security;
+pub mod sks;
+pub mod subscriber;
+pub mod udp;
+
+#[cfg(test)]
+mod tests;
+
+pub mod prelude {
+    #[cfg(feature = "pubsub-mqtt")]
+    pub use super::mqtt::*;
+    #[cfg(feature = "server")]
+    pub use super::publisher::*;
+    pub use super::{
+        data_set_decoder::*, data_set_message::*, discovery::*, json_message::*,
+        network_message::*, security::*, sks::*, subscriber::*, udp::*,
+    };
+    pub use crate::types::{status_code::StatusCode, *};
+}
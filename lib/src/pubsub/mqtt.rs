@@ -0,0 +1,415 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the MQTT transport described in Part 14 7.3.5. Publishers send JSON network messages
+//! to topics of a broker at an `mqtt://` or `mqtts://` url and subscribers receive them by
+//! subscribing to those topics. The metadata of each data set writer is sent as a retained
+//! message, so a subscriber gets it from the broker as soon as it subscribes.
+
+use std::sync::Arc;
+
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, Transport};
+use tokio::time::{sleep, timeout, Duration};
+
+pub use rumqttc::QoS;
+
+use crate::sync::*;
+use crate::types::{status_code::StatusCode, *};
+
+use super::{json_message::JsonMessage, subscriber::Subscriber};
+
+#[cfg(feature = "server")]
+use super::publisher::Publisher;
+#[cfg(feature = "server")]
+use crate::server::address_space::AddressSpace;
+#[cfg(feature = "server")]
+use tokio::time::{interval_at, Instant};
+
+/// The scheme of MQTT urls
+pub const MQTT_SCHEME: &str = "mqtt";
+
+/// The scheme of MQTT over TLS urls
+pub const MQTTS_SCHEME: &str = "mqtts";
+
+/// The port that is used when an `mqtt://` url doesn't have one
+pub const DEFAULT_MQTT_PORT: u16 = 1883;
+
+/// The port that is used when an `mqtts://` url doesn't have one
+pub const DEFAULT_MQTTS_PORT: u16 = 8883;
+
+/// The prefix of the default topics of data messages
+pub const DATA_TOPIC_PREFIX: &str = "opcua/json/data";
+
+/// The prefix of the default topics of metadata messages
+pub const META_DATA_TOPIC_PREFIX: &str = "opcua/json/metadata";
+
+/// The largest message that can be sent or received
+const MAX_PACKET_SIZE: usize = 1024 * 1024;
+
+/// The number of requests that can be queued for the event loop of the client
+const REQUEST_CHANNEL_CAPACITY: usize = 64;
+
+/// How often the event loop checks if it has aborted while it waits for the broker
+const ABORT_POLL_INTERVAL_MS: u64 = 500;
+
+/// How long to wait before connecting again after the connection to the broker fails
+const RECONNECT_DELAY_MS: u64 = 1000;
+
+/// The settings of the MQTT transport of a publisher or subscriber
+#[derive(Debug, Clone)]
+pub struct MqttSettings {
+    client_id: String,
+    qos: QoS,
+    topic: Option<String>,
+    meta_data_topic: Option<String>,
+    credentials: Option<(String, String)>,
+    keep_alive: Duration,
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        MqttSettings {
+            client_id: format!("opcua-{}", Guid::new()),
+            qos: QoS::AtLeastOnce,
+            topic: None,
+            meta_data_topic: None,
+            credentials: None,
+            keep_alive: Duration::from_secs(30),
+        }
+    }
+}
+
+impl MqttSettings {
+    /// Sets the id that the client connects to the broker with. The default is a unique id.
+    pub fn client_id<T>(mut self, client_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.client_id = client_id.into();
+        self
+    }
+
+    /// Sets the quality of service that messages are sent and subscribed with
+    pub fn qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    /// Sets the topic of data messages. A publisher sends the messages of every writer group to
+    /// it and a subscriber subscribes to it, so it may be a filter for a subscriber. The
+    /// default is a topic per writer group for a publisher and every data topic for a subscriber.
+    pub fn topic<T>(mut self, topic: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    /// Sets the topic of metadata messages, in the same way as `topic()`
+    pub fn meta_data_topic<T>(mut self, meta_data_topic: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.meta_data_topic = Some(meta_data_topic.into());
+        self
+    }
+
+    /// Sets the user name and password to connect to the broker with
+    pub fn credentials<T, S>(mut self, user_name: T, password: S) -> Self
+    where
+        T: Into<String>,
+        S: Into<String>,
+    {
+        self.credentials = Some((user_name.into(), password.into()));
+        self
+    }
+
+    /// Sets how often the client pings the broker when it has nothing else to send
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// The topic that the data messages of the writer group are sent to
+    pub fn data_topic(&self, publisher_id: &str, writer_group_name: &str) -> String {
+        self.topic
+            .clone()
+            .unwrap_or_else(|| format!("{DATA_TOPIC_PREFIX}/{publisher_id}/{writer_group_name}"))
+    }
+
+    /// The topic that the metadata of the data set writer is sent to
+    pub fn meta_data_topic_of(
+        &self,
+        publisher_id: &str,
+        writer_group_name: &str,
+        data_set_writer_name: &str,
+    ) -> String {
+        self.meta_data_topic.clone().unwrap_or_else(|| {
+            format!(
+                "{META_DATA_TOPIC_PREFIX}/{publisher_id}/{writer_group_name}/{data_set_writer_name}"
+            )
+        })
+    }
+
+    /// The topic filters that a subscriber subscribes to
+    pub fn subscriber_topics(&self) -> [String; 2] {
+        [
+            self.topic
+                .clone()
+                .unwrap_or_else(|| format!("{DATA_TOPIC_PREFIX}/#")),
+            self.meta_data_topic
+                .clone()
+                .unwrap_or_else(|| format!("{META_DATA_TOPIC_PREFIX}/#")),
+        ]
+    }
+}
+
+/// Tests if the url is the url of an MQTT broker
+pub fn is_mqtt_url(url: &str) -> bool {
+    url.starts_with("mqtt://") || url.starts_with("mqtts://")
+}
+
+/// Makes the options to connect to the broker at an `mqtt://host:port` or `mqtts://host:port`
+/// url. The default TLS configuration is used for `mqtts://`.
+pub fn mqtt_options(url: &str, settings: &MqttSettings) -> Result<MqttOptions, StatusCode> {
+    let (host_port, tls) = if let Some(v) = url.strip_prefix("mqtt://") {
+        (v, false)
+    } else if let Some(v) = url.strip_prefix("mqtts://") {
+        (v, true)
+    } else {
+        error!(
+            "Url {} is not an {} or {} url",
+            url, MQTT_SCHEME, MQTTS_SCHEME
+        );
+        return Err(StatusCode::BadInvalidArgument);
+    };
+    let host_port = host_port.trim_end_matches('/');
+    let default_port = if tls {
+        DEFAULT_MQTTS_PORT
+    } else {
+        DEFAULT_MQTT_PORT
+    };
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse::<u16>().map_err(|_| {
+                error!("Url {} has an invalid port", url);
+                StatusCode::BadInvalidArgument
+            })?;
+            (host, port)
+        }
+        None => (host_port, default_port),
+    };
+    if host.is_empty() {
+        error!("Url {} has no host", url);
+        return Err(StatusCode::BadInvalidArgument);
+    }
+
+    let mut options = MqttOptions::new(settings.client_id.clone(), host, port);
+    options
+        .set_keep_alive(settings.keep_alive)
+        .set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+    if let Some((ref user_name, ref password)) = settings.credentials {
+        options.set_credentials(user_name.clone(), password.clone());
+    }
+    if tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    Ok(options)
+}
+
+/// Polls the event loop of a client, which is what sends and receives its packets, until `abort`
+/// says to stop. Each event is passed to the handler.
+async fn poll_events<A, H>(mut event_loop: EventLoop, abort: A, mut handler: H)
+where
+    A: Fn() -> bool,
+    H: FnMut(Event),
+{
+    while !abort() {
+        match timeout(
+            Duration::from_millis(ABORT_POLL_INTERVAL_MS),
+            event_loop.poll(),
+        )
+        .await
+        {
+            Ok(Ok(event)) => handler(event),
+            Ok(Err(err)) => {
+                // The next poll connects again
+                error!("Connection to the MQTT broker failed - {}", err);
+                sleep(Duration::from_millis(RECONNECT_DELAY_MS)).await;
+            }
+            // Timed out, check for abort again
+            Err(_) => {}
+        }
+    }
+}
+
+/// Runs a publisher that sends to a broker. The metadata of the data set writers is sent once
+/// as retained messages and the data messages of every writer group on its own interval.
+#[cfg(feature = "server")]
+pub(crate) async fn run_publisher(
+    publisher: Arc<RwLock<Publisher>>,
+    address_space: Arc<RwLock<AddressSpace>>,
+) -> Result<(), StatusCode> {
+    let (options, settings, writer_groups, meta_data_messages) = {
+        let mut publisher = trace_write_lock!(publisher);
+        {
+            let address_space = trace_read_lock!(address_space);
+            publisher.update_information(&address_space);
+        }
+        let settings = publisher.mqtt_settings().clone();
+        let options = mqtt_options(publisher.url(), &settings)?;
+        let publisher_id = publisher.publisher_id().to_string();
+        let writer_groups = publisher
+            .writer_groups()
+            .iter()
+            .map(|writer_group| {
+                (
+                    writer_group.writer_group_id,
+                    writer_group.publishing_interval,
+                    settings.data_topic(&publisher_id, writer_group.name.as_ref()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let meta_data_messages = publisher
+            .meta_data_messages()
+            .into_iter()
+            .filter_map(|message| {
+                publisher.writer_groups().iter().find_map(|writer_group| {
+                    writer_group
+                        .data_set_writers
+                        .iter()
+                        .find(|writer| writer.data_set_writer_id == message.data_set_writer_id)
+                        .map(|writer| {
+                            let topic = settings.meta_data_topic_of(
+                                &publisher_id,
+                                writer_group.name.as_ref(),
+                                writer.name.as_ref(),
+                            );
+                            (topic, message.to_json(JsonEncoding::Reversible))
+                        })
+                })
+            })
+            .collect::<Vec<_>>();
+        (options, settings, writer_groups, meta_data_messages)
+    };
+    info!(
+        "Publisher is sending to MQTT broker {:?}",
+        options.broker_address()
+    );
+
+    let (client, event_loop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+    let mut tasks = Vec::with_capacity(writer_groups.len() + 1);
+    {
+        let publisher = publisher.clone();
+        tasks.push(tokio::spawn(poll_events(
+            event_loop,
+            move || trace_read_lock!(publisher).is_abort(),
+            |_| {},
+        )));
+    }
+    for (topic, message) in meta_data_messages {
+        if let Err(err) = client.publish(topic, settings.qos, true, message).await {
+            error!("Cannot publish metadata - {}", err);
+        }
+    }
+    for (writer_group_id, publishing_interval, topic) in writer_groups {
+        tasks.push(tokio::spawn(publish_task(
+            publisher.clone(),
+            address_space.clone(),
+            client.clone(),
+            settings.qos,
+            topic,
+            writer_group_id,
+            publishing_interval,
+        )));
+    }
+    futures::future::join_all(tasks).await;
+    let _ = client.try_disconnect();
+    info!("Publisher has stopped");
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+async fn publish_task(
+    publisher: Arc<RwLock<Publisher>>,
+    address_space: Arc<RwLock<AddressSpace>>,
+    client: AsyncClient,
+    qos: QoS,
+    topic: String,
+    writer_group_id: u16,
+    publishing_interval: f64,
+) {
+    let publishing_interval =
+        Duration::from_micros((publishing_interval * 1000f64) as u64).max(Duration::from_millis(1));
+    let mut timer = interval_at(Instant::now(), publishing_interval);
+    loop {
+        timer.tick().await;
+        let message = {
+            let mut publisher = trace_write_lock!(publisher);
+            if publisher.is_abort() {
+                break;
+            }
+            let address_space = trace_read_lock!(address_space);
+            publisher.publish_json(writer_group_id, &address_space, &chrono::Utc::now())
+        };
+        if let Some(message) = message {
+            let payload = message.to_json(JsonEncoding::Reversible);
+            if let Err(err) = client.publish(topic.as_str(), qos, false, payload).await {
+                error!("Cannot publish to {} - {}", topic, err);
+            }
+        }
+    }
+    debug!(
+        "Publishing task for writer group {} is finished",
+        writer_group_id
+    );
+}
+
+/// Runs a subscriber that receives from a broker. The subscriber subscribes to its topics each
+/// time it connects and hands the messages it receives to its data set readers.
+pub(crate) async fn run_subscriber(subscriber: Arc<RwLock<Subscriber>>) -> Result<(), StatusCode> {
+    let (options, settings) = {
+        let subscriber = trace_read_lock!(subscriber);
+        let settings = subscriber.mqtt_settings().clone();
+        (mqtt_options(subscriber.url(), &settings)?, settings)
+    };
+    info!(
+        "Subscriber is receiving from MQTT broker {:?}",
+        options.broker_address()
+    );
+
+    let (client, event_loop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+    let decoding_options = DecodingOptions::default();
+    let abort = {
+        let subscriber = subscriber.clone();
+        move || trace_read_lock!(subscriber).is_abort()
+    };
+    poll_events(event_loop, abort, |event| match event {
+        Event::Incoming(Packet::ConnAck(_)) => {
+            for topic in settings.subscriber_topics() {
+                if let Err(err) = client.try_subscribe(topic.as_str(), settings.qos) {
+                    error!("Cannot subscribe to {} - {}", topic, err);
+                }
+            }
+        }
+        Event::Incoming(Packet::Publish(publish)) => {
+            let message = std::str::from_utf8(&publish.payload)
+                .map_err(|_| StatusCode::BadDecodingError)
+                .and_then(|payload| JsonMessage::from_json(payload, &decoding_options));
+            match message {
+                Ok(message) => trace_write_lock!(subscriber).handle_json_message(&message),
+                Err(err) => debug!(
+                    "Ignoring a message on {} that cannot be decoded - {}",
+                    publish.topic, err
+                ),
+            }
+        }
+        _ => {}
+    })
+    .await;
+    let _ = client.try_disconnect();
+    info!("Subscriber has stopped");
+    Ok(())
+}
@@ -0,0 +1,647 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UADP `NetworkMessage` described in Part 14 7.2.2.2, i.e. the header that every
+//! message sent by a publisher starts with, followed by its payload.
+
+use std::{
+    fmt,
+    io::{Cursor, Read, Write},
+};
+
+use crate::types::{status_code::StatusCode, *};
+
+use super::{
+    data_set_message::DataSetMessage,
+    discovery::{DiscoveryRequest, DiscoveryResponse},
+    security::SecurityHeader,
+};
+
+/// The version of the UADP message mapping
+pub const UADP_VERSION: u8 = 1;
+
+// UADPFlags
+const UADP_VERSION_MASK: u8 = 0x0f;
+const PUBLISHER_ID_ENABLED: u8 = 0x10;
+const GROUP_HEADER_ENABLED: u8 = 0x20;
+const PAYLOAD_HEADER_ENABLED: u8 = 0x40;
+const EXTENDED_FLAGS_1_ENABLED: u8 = 0x80;
+
+// ExtendedFlags1
+const PUBLISHER_ID_TYPE_MASK: u8 = 0x07;
+const DATA_SET_CLASS_ID_ENABLED: u8 = 0x08;
+const SECURITY_ENABLED: u8 = 0x10;
+const TIMESTAMP_ENABLED: u8 = 0x20;
+const PICO_SECONDS_ENABLED: u8 = 0x40;
+const EXTENDED_FLAGS_2_ENABLED: u8 = 0x80;
+
+// ExtendedFlags2
+const CHUNK_MESSAGE: u8 = 0x01;
+const PROMOTED_FIELDS_ENABLED: u8 = 0x02;
+const NETWORK_MESSAGE_TYPE_MASK: u8 = 0x1c;
+const NETWORK_MESSAGE_TYPE_SHIFT: u8 = 2;
+
+// GroupFlags
+const WRITER_GROUP_ID_ENABLED: u8 = 0x01;
+const GROUP_VERSION_ENABLED: u8 = 0x02;
+const NETWORK_MESSAGE_NUMBER_ENABLED: u8 = 0x04;
+const SEQUENCE_NUMBER_ENABLED: u8 = 0x08;
+
+/// The identifier of a publisher. It must be unique within the networks the publisher sends
+/// messages to and can be one of several types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublisherId {
+    Byte(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    String(UAString),
+}
+
+impl PublisherId {
+    /// The value of the publisher id type bits in ExtendedFlags1
+    fn type_bits(&self) -> u8 {
+        match self {
+            PublisherId::Byte(_) => 0,
+            PublisherId::UInt16(_) => 1,
+            PublisherId::UInt32(_) => 2,
+            PublisherId::UInt64(_) => 3,
+            PublisherId::String(_) => 4,
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        match self {
+            PublisherId::Byte(_) => 1,
+            PublisherId::UInt16(_) => 2,
+            PublisherId::UInt32(_) => 4,
+            PublisherId::UInt64(_) => 8,
+            PublisherId::String(v) => v.byte_len(),
+        }
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        match self {
+            PublisherId::Byte(v) => write_u8(stream, *v),
+            PublisherId::UInt16(v) => write_u16(stream, *v),
+            PublisherId::UInt32(v) => write_u32(stream, *v),
+            PublisherId::UInt64(v) => write_u64(stream, *v),
+            PublisherId::String(v) => v.encode(stream),
+        }
+    }
+
+    fn decode<S: Read>(
+        type_bits: u8,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Self> {
+        match type_bits {
+            0 => Ok(PublisherId::Byte(read_u8(stream)?)),
+            1 => Ok(PublisherId::UInt16(read_u16(stream)?)),
+            2 => Ok(PublisherId::UInt32(read_u32(stream)?)),
+            3 => Ok(PublisherId::UInt64(read_u64(stream)?)),
+            4 => Ok(PublisherId::String(UAString::decode(
+                stream,
+                decoding_options,
+            )?)),
+            _ => {
+                error!("Invalid publisher id type {}", type_bits);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PublisherId {
+    /// Writes the publisher id as it appears in JSON messages and MQTT topics
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PublisherId::Byte(v) => write!(f, "{}", v),
+            PublisherId::UInt16(v) => write!(f, "{}", v),
+            PublisherId::UInt32(v) => write!(f, "{}", v),
+            PublisherId::UInt64(v) => write!(f, "{}", v),
+            PublisherId::String(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// The group header of a network message identifies the writer group that sent it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupHeader {
+    pub writer_group_id: Option<u16>,
+    pub group_version: Option<u32>,
+    pub network_message_number: Option<u16>,
+    pub sequence_number: Option<u16>,
+}
+
+impl BinaryEncoder<GroupHeader> for GroupHeader {
+    fn byte_len(&self) -> usize {
+        let mut size = 1;
+        size += self.writer_group_id.map_or(0, |_| 2);
+        size += self.group_version.map_or(0, |_| 4);
+        size += self.network_message_number.map_or(0, |_| 2);
+        size += self.sequence_number.map_or(0, |_| 2);
+        size
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut group_flags = 0;
+        if self.writer_group_id.is_some() {
+            group_flags |= WRITER_GROUP_ID_ENABLED;
+        }
+        if self.group_version.is_some() {
+            group_flags |= GROUP_VERSION_ENABLED;
+        }
+        if self.network_message_number.is_some() {
+            group_flags |= NETWORK_MESSAGE_NUMBER_ENABLED;
+        }
+        if self.sequence_number.is_some() {
+            group_flags |= SEQUENCE_NUMBER_ENABLED;
+        }
+        let mut size = write_u8(stream, group_flags)?;
+        if let Some(writer_group_id) = self.writer_group_id {
+            size += write_u16(stream, writer_group_id)?;
+        }
+        if let Some(group_version) = self.group_version {
+            size += write_u32(stream, group_version)?;
+        }
+        if let Some(network_message_number) = self.network_message_number {
+            size += write_u16(stream, network_message_number)?;
+        }
+        if let Some(sequence_number) = self.sequence_number {
+            size += write_u16(stream, sequence_number)?;
+        }
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, _: &DecodingOptions) -> EncodingResult<Self> {
+        let group_flags = read_u8(stream)?;
+        let writer_group_id = if group_flags & WRITER_GROUP_ID_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        let group_version = if group_flags & GROUP_VERSION_ENABLED != 0 {
+            Some(read_u32(stream)?)
+        } else {
+            None
+        };
+        let network_message_number = if group_flags & NETWORK_MESSAGE_NUMBER_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        let sequence_number = if group_flags & SEQUENCE_NUMBER_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        Ok(GroupHeader {
+            writer_group_id,
+            group_version,
+            network_message_number,
+            sequence_number,
+        })
+    }
+}
+
+/// The type of a network message, which is held in the ExtendedFlags2 of the header
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NetworkMessageType {
+    /// The payload contains data set messages
+    DataSetMessage,
+    /// The payload is a discovery request from a subscriber
+    DiscoveryRequest,
+    /// The payload is a discovery response, or announcement, from a publisher
+    DiscoveryResponse,
+}
+
+impl NetworkMessageType {
+    fn bits(&self) -> u8 {
+        match self {
+            NetworkMessageType::DataSetMessage => 0,
+            NetworkMessageType::DiscoveryRequest => 1,
+            NetworkMessageType::DiscoveryResponse => 2,
+        }
+    }
+
+    fn from_bits(bits: u8) -> EncodingResult<Self> {
+        match bits {
+            0 => Ok(NetworkMessageType::DataSetMessage),
+            1 => Ok(NetworkMessageType::DiscoveryRequest),
+            2 => Ok(NetworkMessageType::DiscoveryResponse),
+            _ => {
+                error!("Invalid network message type {}", bits);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// The header of a UADP network message. Optional fields are only written when they are set and
+/// the flags that say which fields are present are derived from them.
+///
+/// A message with a security header can only be encoded and decoded with the keys of its security
+/// group, see `SecurityKeys`. Chunked messages and promoted fields are not supported.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NetworkMessageHeader {
+    pub publisher_id: Option<PublisherId>,
+    pub data_set_class_id: Option<Guid>,
+    pub group_header: Option<GroupHeader>,
+    /// The ids of the data set writers whose messages are in the payload
+    pub data_set_writer_ids: Option<Vec<u16>>,
+    pub timestamp: Option<DateTime>,
+    pub pico_seconds: Option<u16>,
+    /// Says how the message is signed and encrypted
+    pub security: Option<SecurityHeader>,
+}
+
+impl NetworkMessageHeader {
+    /// Creates a header with just a publisher id
+    pub fn new(publisher_id: PublisherId) -> NetworkMessageHeader {
+        NetworkMessageHeader {
+            publisher_id: Some(publisher_id),
+            ..Default::default()
+        }
+    }
+
+    fn extended_flags_1(&self, message_type: NetworkMessageType) -> u8 {
+        let mut flags = self.publisher_id.as_ref().map_or(0, |v| v.type_bits());
+        if self.data_set_class_id.is_some() {
+            flags |= DATA_SET_CLASS_ID_ENABLED;
+        }
+        if self.timestamp.is_some() {
+            flags |= TIMESTAMP_ENABLED;
+        }
+        if self.pico_seconds.is_some() {
+            flags |= PICO_SECONDS_ENABLED;
+        }
+        if self.security.is_some() {
+            flags |= SECURITY_ENABLED;
+        }
+        if message_type != NetworkMessageType::DataSetMessage {
+            flags |= EXTENDED_FLAGS_2_ENABLED;
+        }
+        flags
+    }
+
+    fn byte_len(&self, message_type: NetworkMessageType) -> usize {
+        let mut size = 1;
+        let extended_flags_1 = self.extended_flags_1(message_type);
+        if extended_flags_1 != 0 {
+            size += 1;
+        }
+        if extended_flags_1 & EXTENDED_FLAGS_2_ENABLED != 0 {
+            size += 1;
+        }
+        size += self.publisher_id.as_ref().map_or(0, |v| v.byte_len());
+        size += self.data_set_class_id.as_ref().map_or(0, |v| v.byte_len());
+        size += self.group_header.as_ref().map_or(0, |v| v.byte_len());
+        size += self
+            .data_set_writer_ids
+            .as_ref()
+            .map_or(0, |v| 1 + v.len() * 2);
+        size += self.timestamp.as_ref().map_or(0, |v| v.byte_len());
+        size += self.pico_seconds.map_or(0, |_| 2);
+        size += self.security.as_ref().map_or(0, |v| v.byte_len());
+        size
+    }
+
+    pub(crate) fn encode<S: Write>(
+        &self,
+        message_type: NetworkMessageType,
+        stream: &mut S,
+    ) -> EncodingResult<usize> {
+        let extended_flags_1 = self.extended_flags_1(message_type);
+        let mut flags = UADP_VERSION;
+        if self.publisher_id.is_some() {
+            flags |= PUBLISHER_ID_ENABLED;
+        }
+        if self.group_header.is_some() {
+            flags |= GROUP_HEADER_ENABLED;
+        }
+        if self.data_set_writer_ids.is_some() {
+            flags |= PAYLOAD_HEADER_ENABLED;
+        }
+        if extended_flags_1 != 0 {
+            flags |= EXTENDED_FLAGS_1_ENABLED;
+        }
+
+        let mut size = write_u8(stream, flags)?;
+        if extended_flags_1 != 0 {
+            size += write_u8(stream, extended_flags_1)?;
+        }
+        if extended_flags_1 & EXTENDED_FLAGS_2_ENABLED != 0 {
+            size += write_u8(stream, message_type.bits() << NETWORK_MESSAGE_TYPE_SHIFT)?;
+        }
+        if let Some(ref publisher_id) = self.publisher_id {
+            size += publisher_id.encode(stream)?;
+        }
+        if let Some(ref data_set_class_id) = self.data_set_class_id {
+            size += data_set_class_id.encode(stream)?;
+        }
+        if let Some(ref group_header) = self.group_header {
+            size += group_header.encode(stream)?;
+        }
+        if let Some(ref data_set_writer_ids) = self.data_set_writer_ids {
+            // The payload header has a byte count rather than the usual array length
+            let count = u8::try_from(data_set_writer_ids.len()).map_err(|_| {
+                error!(
+                    "Too many data set messages, {}, for one network message",
+                    data_set_writer_ids.len()
+                );
+                StatusCode::BadEncodingLimitsExceeded
+            })?;
+            size += write_u8(stream, count)?;
+            for data_set_writer_id in data_set_writer_ids {
+                size += write_u16(stream, *data_set_writer_id)?;
+            }
+        }
+        if let Some(ref timestamp) = self.timestamp {
+            size += timestamp.encode(stream)?;
+        }
+        if let Some(pico_seconds) = self.pico_seconds {
+            size += write_u16(stream, pico_seconds)?;
+        }
+        if let Some(ref security) = self.security {
+            size += security.encode(stream)?;
+        }
+        Ok(size)
+    }
+
+    pub(crate) fn decode<S: Read>(
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<(Self, NetworkMessageType)> {
+        let flags = read_u8(stream)?;
+        if flags & UADP_VERSION_MASK != UADP_VERSION {
+            error!("Unsupported UADP version {}", flags & UADP_VERSION_MASK);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let extended_flags_1 = if flags & EXTENDED_FLAGS_1_ENABLED != 0 {
+            read_u8(stream)?
+        } else {
+            0
+        };
+        let extended_flags_2 = if extended_flags_1 & EXTENDED_FLAGS_2_ENABLED != 0 {
+            read_u8(stream)?
+        } else {
+            0
+        };
+        if extended_flags_2 & (CHUNK_MESSAGE | PROMOTED_FIELDS_ENABLED) != 0 {
+            error!("Network message is chunked or has promoted fields, which is not supported");
+            return Err(StatusCode::BadNotSupported);
+        }
+        let message_type = NetworkMessageType::from_bits(
+            (extended_flags_2 & NETWORK_MESSAGE_TYPE_MASK) >> NETWORK_MESSAGE_TYPE_SHIFT,
+        )?;
+
+        let publisher_id = if flags & PUBLISHER_ID_ENABLED != 0 {
+            Some(PublisherId::decode(
+                extended_flags_1 & PUBLISHER_ID_TYPE_MASK,
+                stream,
+                decoding_options,
+            )?)
+        } else {
+            None
+        };
+        let data_set_class_id = if extended_flags_1 & DATA_SET_CLASS_ID_ENABLED != 0 {
+            Some(Guid::decode(stream, decoding_options)?)
+        } else {
+            None
+        };
+        let group_header = if flags & GROUP_HEADER_ENABLED != 0 {
+            Some(GroupHeader::decode(stream, decoding_options)?)
+        } else {
+            None
+        };
+        let data_set_writer_ids = if flags & PAYLOAD_HEADER_ENABLED != 0 {
+            let count = read_u8(stream)?;
+            let data_set_writer_ids = (0..count)
+                .map(|_| read_u16(stream))
+                .collect::<EncodingResult<Vec<u16>>>()?;
+            Some(data_set_writer_ids)
+        } else {
+            None
+        };
+        let timestamp = if extended_flags_1 & TIMESTAMP_ENABLED != 0 {
+            Some(DateTime::decode(stream, decoding_options)?)
+        } else {
+            None
+        };
+        let pico_seconds = if extended_flags_1 & PICO_SECONDS_ENABLED != 0 {
+            Some(read_u16(stream)?)
+        } else {
+            None
+        };
+        let security = if extended_flags_1 & SECURITY_ENABLED != 0 {
+            Some(SecurityHeader::decode(stream, decoding_options)?)
+        } else {
+            None
+        };
+
+        let header = NetworkMessageHeader {
+            publisher_id,
+            data_set_class_id,
+            group_header,
+            data_set_writer_ids,
+            timestamp,
+            pico_seconds,
+            security,
+        };
+        Ok((header, message_type))
+    }
+}
+
+/// The payload of a network message
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkMessagePayload {
+    /// The data set messages, in the same order as the data set writer ids in the header
+    DataSetMessages(Vec<DataSetMessage>),
+    DiscoveryRequest(DiscoveryRequest),
+    DiscoveryResponse(Box<DiscoveryResponse>),
+}
+
+impl NetworkMessagePayload {
+    pub(crate) fn message_type(&self) -> NetworkMessageType {
+        match self {
+            NetworkMessagePayload::DataSetMessages(_) => NetworkMessageType::DataSetMessage,
+            NetworkMessagePayload::DiscoveryRequest(_) => NetworkMessageType::DiscoveryRequest,
+            NetworkMessagePayload::DiscoveryResponse(_) => NetworkMessageType::DiscoveryResponse,
+        }
+    }
+}
+
+/// A UADP network message, i.e. the unit of data sent by a publisher or subscriber over a
+/// datagram transport
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkMessage {
+    pub header: NetworkMessageHeader,
+    pub payload: NetworkMessagePayload,
+}
+
+impl BinaryEncoder<NetworkMessage> for NetworkMessage {
+    fn byte_len(&self) -> usize {
+        let mut size = self.header.byte_len(self.payload.message_type());
+        size += match self.payload {
+            NetworkMessagePayload::DataSetMessages(ref messages) => {
+                // The size of each message precedes them when there is more than one
+                let sizes_len = if messages.len() > 1 {
+                    messages.len() * 2
+                } else {
+                    0
+                };
+                sizes_len + messages.iter().map(|v| v.byte_len()).sum::<usize>()
+            }
+            NetworkMessagePayload::DiscoveryRequest(ref v) => v.byte_len(),
+            NetworkMessagePayload::DiscoveryResponse(ref v) => v.byte_len(),
+        };
+        size
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        if self.header.security.is_some() {
+            error!("Network message has a security header so it must be encoded with its keys");
+            return Err(StatusCode::BadEncodingError);
+        }
+        let size = self.header.encode(self.payload.message_type(), stream)?;
+        Ok(size + self.encode_payload(stream)?)
+    }
+
+    fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let (header, message_type) = NetworkMessageHeader::decode(stream, decoding_options)?;
+        if header.security.is_some() {
+            error!("Network message is secured so it must be decoded with its keys");
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        Self::decode_payload(header, message_type, stream, decoding_options)
+    }
+}
+
+impl NetworkMessage {
+    /// Writes the payload, which follows the header
+    pub(crate) fn encode_payload<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        match self.payload {
+            NetworkMessagePayload::DataSetMessages(ref messages) => {
+                self.encode_data_set_messages(stream, messages)
+            }
+            NetworkMessagePayload::DiscoveryRequest(ref v) => v.encode(stream),
+            NetworkMessagePayload::DiscoveryResponse(ref v) => v.encode(stream),
+        }
+    }
+
+    /// Reads the payload of a message whose header has been read
+    pub(crate) fn decode_payload<S: Read>(
+        header: NetworkMessageHeader,
+        message_type: NetworkMessageType,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Self> {
+        let payload = match message_type {
+            NetworkMessageType::DiscoveryRequest => NetworkMessagePayload::DiscoveryRequest(
+                DiscoveryRequest::decode(stream, decoding_options)?,
+            ),
+            NetworkMessageType::DiscoveryResponse => NetworkMessagePayload::DiscoveryResponse(
+                Box::new(DiscoveryResponse::decode(stream, decoding_options)?),
+            ),
+            NetworkMessageType::DataSetMessage => NetworkMessagePayload::DataSetMessages(
+                Self::decode_data_set_messages(stream, &header, decoding_options)?,
+            ),
+        };
+        Ok(NetworkMessage { header, payload })
+    }
+
+    /// Creates a network message holding data set messages. The header must have the ids of the
+    /// data set writers of the messages unless there is only one message.
+    pub fn data_set_messages(
+        header: NetworkMessageHeader,
+        messages: Vec<DataSetMessage>,
+    ) -> NetworkMessage {
+        NetworkMessage {
+            header,
+            payload: NetworkMessagePayload::DataSetMessages(messages),
+        }
+    }
+
+    /// Creates a network message holding a discovery request
+    pub fn discovery_request(
+        header: NetworkMessageHeader,
+        request: DiscoveryRequest,
+    ) -> NetworkMessage {
+        NetworkMessage {
+            header,
+            payload: NetworkMessagePayload::DiscoveryRequest(request),
+        }
+    }
+
+    /// Creates a network message holding a discovery response
+    pub fn discovery_response(
+        header: NetworkMessageHeader,
+        response: DiscoveryResponse,
+    ) -> NetworkMessage {
+        NetworkMessage {
+            header,
+            payload: NetworkMessagePayload::DiscoveryResponse(Box::new(response)),
+        }
+    }
+
+    fn encode_data_set_messages<S: Write>(
+        &self,
+        stream: &mut S,
+        messages: &[DataSetMessage],
+    ) -> EncodingResult<usize> {
+        let data_set_writer_count = self.header.data_set_writer_ids.as_ref().map(|v| v.len());
+        if data_set_writer_count.map_or(messages.len() != 1, |count| count != messages.len()) {
+            error!(
+                "Network message has {} data set messages but the header has {:?} data set writers",
+                messages.len(),
+                data_set_writer_count
+            );
+            return Err(StatusCode::BadEncodingError);
+        }
+        let mut size = 0;
+        if messages.len() > 1 {
+            for message in messages {
+                let message_size = u16::try_from(message.byte_len()).map_err(|_| {
+                    error!("Data set message is too large for a network message");
+                    StatusCode::BadEncodingLimitsExceeded
+                })?;
+                size += write_u16(stream, message_size)?;
+            }
+        }
+        for message in messages {
+            size += message.encode(stream)?;
+        }
+        Ok(size)
+    }
+
+    fn decode_data_set_messages<S: Read>(
+        stream: &mut S,
+        header: &NetworkMessageHeader,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Vec<DataSetMessage>> {
+        let count = header.data_set_writer_ids.as_ref().map_or(1, |v| v.len());
+        if count == 1 {
+            // A single message takes up the rest of the network message
+            let mut data = Vec::new();
+            process_decode_io_result(stream.read_to_end(&mut data))?;
+            Ok(vec![DataSetMessage::decode(
+                &mut Cursor::new(data),
+                decoding_options,
+            )?])
+        } else {
+            let sizes = (0..count)
+                .map(|_| read_u16(stream))
+                .collect::<EncodingResult<Vec<u16>>>()?;
+            sizes
+                .into_iter()
+                .map(|size| {
+                    let mut data = vec![0u8; size as usize];
+                    process_decode_io_result(stream.read_exact(&mut data))?;
+                    DataSetMessage::decode(&mut Cursor::new(data), decoding_options)
+                })
+                .collect()
+        }
+    }
+}
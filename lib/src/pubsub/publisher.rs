@@ -0,0 +1,976 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UADP publisher. A publisher samples the variables of its published data sets
+//! from a server's address space and sends them as network messages over UDP, one network message
+//! per writer group every publishing interval. Publishers with an MQTT url send JSON network
+//! messages to a broker instead.
+
+use std::{collections::BTreeMap, io::Cursor, net::SocketAddr, sync::Arc};
+
+use tokio::{
+    net::UdpSocket,
+    time::{interval_at, timeout, Duration, Instant},
+};
+
+use crate::server::address_space::AddressSpace;
+use crate::sync::*;
+use crate::types::{status_code::StatusCode, *};
+
+use super::{
+    data_set_message::*,
+    discovery::*,
+    json_message::{self, JsonDataSetMessage, JsonMetaDataMessage, JsonNetworkMessage},
+    network_message::*,
+    security::SecurityKeys,
+    udp::{self, MAX_DATAGRAM_SIZE},
+};
+
+#[cfg(feature = "pubsub-mqtt")]
+use super::mqtt::{self, MqttSettings};
+
+/// The number of publishing intervals between keep alive messages of a writer group that
+/// doesn't set its own keep alive time
+const DEFAULT_KEEP_ALIVE_COUNT: f64 = 10f64;
+
+/// How often the discovery task checks if the publisher has aborted while it waits for requests
+const ABORT_POLL_INTERVAL_MS: u64 = 500;
+
+/// A field of a published data set
+#[derive(Debug, Clone)]
+pub struct PublishedField {
+    /// The name of the field in the metadata of the data set
+    pub name: UAString,
+    /// The node and attribute that the value of the field is read from
+    pub variable: PublishedVariableDataType,
+    /// The id of the field in the metadata of the data set
+    pub data_set_field_id: Guid,
+}
+
+/// A published data set, i.e. a named list of fields whose values are read from the address
+/// space each time the data set is published
+#[derive(Debug, Clone)]
+pub struct PublishedDataSet {
+    pub name: UAString,
+    pub fields: Vec<PublishedField>,
+    /// The version of the configuration of the data set. Subscribers use it to check that the
+    /// metadata they have matches the messages they receive.
+    pub configuration_version: ConfigurationVersionDataType,
+}
+
+impl PublishedDataSet {
+    /// Creates an empty data set
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<UAString>,
+    {
+        let version = version_time();
+        PublishedDataSet {
+            name: name.into(),
+            fields: Vec::new(),
+            configuration_version: ConfigurationVersionDataType {
+                major_version: version,
+                minor_version: version,
+            },
+        }
+    }
+
+    /// Adds a field whose value is the value of the variable
+    pub fn add_variable<T, N>(self, field_name: T, node_id: N) -> Self
+    where
+        T: Into<UAString>,
+        N: Into<NodeId>,
+    {
+        self.add_field(
+            field_name,
+            PublishedVariableDataType {
+                published_variable: node_id.into(),
+                attribute_id: AttributeId::Value as u32,
+                sampling_interval_hint: -1f64,
+                deadband_type: 0,
+                deadband_value: 0f64,
+                index_range: UAString::null(),
+                substitute_value: Variant::Empty,
+                meta_data_properties: None,
+            },
+        )
+    }
+
+    /// Adds a field whose value is read as described by the published variable
+    pub fn add_field<T>(mut self, field_name: T, variable: PublishedVariableDataType) -> Self
+    where
+        T: Into<UAString>,
+    {
+        self.fields.push(PublishedField {
+            name: field_name.into(),
+            variable,
+            data_set_field_id: Guid::new(),
+        });
+        self
+    }
+
+    /// Makes the metadata of the data set from the fields and the nodes they are read from
+    pub fn meta_data(&self, address_space: &AddressSpace) -> DataSetMetaDataType {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| Self::field_meta_data(field, address_space))
+            .collect();
+        DataSetMetaDataType {
+            namespaces: None,
+            structure_data_types: None,
+            enum_data_types: None,
+            simple_data_types: None,
+            name: self.name.clone(),
+            description: LocalizedText::null(),
+            fields: Some(fields),
+            data_set_class_id: Guid::null(),
+            configuration_version: self.configuration_version.clone(),
+        }
+    }
+
+    /// Reads the values of the fields from the address space. A field whose node or attribute
+    /// cannot be read has its substitute value if it has one, or a bad status otherwise.
+    pub fn sample(&self, address_space: &AddressSpace) -> Vec<DataValue> {
+        self.fields
+            .iter()
+            .map(|field| Self::read_field(field, address_space))
+            .collect()
+    }
+
+    fn read_field(field: &PublishedField, address_space: &AddressSpace) -> DataValue {
+        let variable = &field.variable;
+        let index_range = if variable.index_range.is_null() {
+            Ok(NumericRange::None)
+        } else {
+            variable
+                .index_range
+                .as_ref()
+                .parse::<NumericRange>()
+                .map_err(|_| StatusCode::BadIndexRangeInvalid)
+        };
+        let value = AttributeId::from_u32(variable.attribute_id)
+            .map_err(|_| StatusCode::BadAttributeIdInvalid)
+            .and_then(|attribute_id| index_range.map(|index_range| (attribute_id, index_range)))
+            .and_then(|(attribute_id, index_range)| {
+                address_space
+                    .find_node(&variable.published_variable)
+                    .ok_or(StatusCode::BadNodeIdUnknown)?
+                    .as_node()
+                    .get_attribute(
+                        TimestampsToReturn::Both,
+                        attribute_id,
+                        index_range,
+                        &QualifiedName::null(),
+                    )
+                    .ok_or(StatusCode::BadAttributeIdInvalid)
+            });
+        let status = match value {
+            Ok(value) if value.status.is_none_or(|s| !s.is_bad()) => return value,
+            Ok(value) => value.status.unwrap_or(StatusCode::BadInternalError),
+            Err(status) => status,
+        };
+        if variable.substitute_value == Variant::Empty {
+            DataValue {
+                status: Some(status),
+                ..DataValue::null()
+            }
+        } else {
+            DataValue {
+                value: Some(variable.substitute_value.clone()),
+                status: Some(StatusCode::UncertainSubstituteValue),
+                ..DataValue::null()
+            }
+        }
+    }
+
+    fn field_meta_data(field: &PublishedField, address_space: &AddressSpace) -> FieldMetaData {
+        // The type of the value attribute comes from the variable, anything else from the value
+        let variable = address_space
+            .find_variable_by_ref(&field.variable.published_variable)
+            .filter(|_| field.variable.attribute_id == AttributeId::Value as u32);
+        let value = Self::read_field(field, address_space).value;
+        let (data_type, value_rank, array_dimensions) = match variable {
+            Some(variable) => (
+                variable.data_type(),
+                variable.value_rank(),
+                variable.array_dimensions(),
+            ),
+            None => {
+                let value_rank = match value {
+                    Some(Variant::Array(ref array)) => array
+                        .dimensions
+                        .as_ref()
+                        .map_or(1, |dimensions| dimensions.len() as i32),
+                    _ => -1,
+                };
+                (DataTypeId::BaseDataType.into(), value_rank, None)
+            }
+        };
+        FieldMetaData {
+            name: field.name.clone(),
+            description: LocalizedText::null(),
+            field_flags: DataSetFieldFlags::empty(),
+            built_in_type: built_in_type(&data_type, value.as_ref()),
+            data_type,
+            value_rank,
+            array_dimensions,
+            max_string_length: 0,
+            data_set_field_id: field.data_set_field_id.clone(),
+            properties: None,
+        }
+    }
+}
+
+/// The built in type of a field, which is the data type when that is a built in type, or else the
+/// type of the current value
+fn built_in_type(data_type: &NodeId, value: Option<&Variant>) -> u8 {
+    match data_type.identifier {
+        Identifier::Numeric(id) if data_type.namespace == 0 && (1..=25).contains(&id) => id as u8,
+        _ => match value {
+            Some(Variant::Array(array)) => array.value_type.encoding_mask(),
+            Some(value) => value.type_id().encoding_mask(),
+            None => 0,
+        },
+    }
+}
+
+/// The number of seconds since 2000, which is what the versions of configurations are made from
+fn version_time() -> u32 {
+    let seconds =
+        (DateTime::now().as_chrono() - DateTime::ymd(2000, 1, 1).as_chrono()).num_seconds();
+    seconds.clamp(0, u32::MAX as i64) as u32
+}
+
+/// A data set writer, which turns the values of a published data set into data set messages
+#[derive(Debug, Clone)]
+pub struct DataSetWriter {
+    pub data_set_writer_id: u16,
+    pub name: UAString,
+    /// The name of the published data set that the writer sends
+    pub data_set_name: UAString,
+    /// Which parts of the values are sent. Fields are sent as variants when it is empty, as raw
+    /// data when it has `RawData`, and as data values otherwise.
+    pub field_content_mask: DataSetFieldContentMask,
+    /// The number of publishing intervals between key frames. Delta frames with only the values
+    /// that changed are sent in between. Raw data is always sent as key frames.
+    pub key_frame_count: u32,
+    sequence_number: u16,
+    intervals_since_key_frame: u32,
+    last_values: Option<Vec<DataValue>>,
+    last_message_time: Option<DateTimeUtc>,
+}
+
+impl DataSetWriter {
+    /// Creates a writer that sends every field as a variant in a key frame every interval
+    pub fn new<T, S>(data_set_writer_id: u16, name: T, data_set_name: S) -> Self
+    where
+        T: Into<UAString>,
+        S: Into<UAString>,
+    {
+        DataSetWriter {
+            data_set_writer_id,
+            name: name.into(),
+            data_set_name: data_set_name.into(),
+            field_content_mask: DataSetFieldContentMask::None,
+            key_frame_count: 1,
+            sequence_number: 0,
+            intervals_since_key_frame: 0,
+            last_values: None,
+            last_message_time: None,
+        }
+    }
+
+    /// Sets which parts of the values are sent
+    pub fn field_content_mask(mut self, field_content_mask: DataSetFieldContentMask) -> Self {
+        self.field_content_mask = field_content_mask;
+        self
+    }
+
+    /// Sets the number of publishing intervals between key frames
+    pub fn key_frame_count(mut self, key_frame_count: u32) -> Self {
+        self.key_frame_count = key_frame_count;
+        self
+    }
+
+    /// The configuration of the writer, as it is sent in discovery responses
+    pub fn data_type(&self) -> DataSetWriterDataType {
+        DataSetWriterDataType {
+            name: self.name.clone(),
+            enabled: true,
+            data_set_writer_id: self.data_set_writer_id,
+            data_set_field_content_mask: self.field_content_mask,
+            key_frame_count: self.key_frame_count,
+            data_set_name: self.data_set_name.clone(),
+            data_set_writer_properties: None,
+            transport_settings: ExtensionObject::null(),
+            message_settings: ExtensionObject::null(),
+        }
+    }
+
+    /// Makes the message for the values sampled this publishing interval, or nothing when no
+    /// value changed and it is not yet time for a keep alive
+    fn next_message(
+        &mut self,
+        values: Vec<DataValue>,
+        configuration_version: &ConfigurationVersionDataType,
+        keep_alive_time: f64,
+        now: &DateTimeUtc,
+    ) -> Option<DataSetMessage> {
+        let field_encoding = FieldEncoding::from(self.field_content_mask);
+        let values = values
+            .into_iter()
+            .map(|value| self.apply_field_content_mask(value, field_encoding))
+            .collect::<Vec<_>>();
+
+        let key_frame = field_encoding == FieldEncoding::RawData
+            || self.intervals_since_key_frame + 1 >= self.key_frame_count
+            || self
+                .last_values
+                .as_ref()
+                .is_none_or(|last_values| last_values.len() != values.len());
+        let payload = if key_frame {
+            self.intervals_since_key_frame = 0;
+            DataSetMessagePayload::KeyFrame(values.clone())
+        } else {
+            self.intervals_since_key_frame += 1;
+            let last_values = self.last_values.as_deref().unwrap_or_default();
+            let changes = values
+                .iter()
+                .zip(last_values)
+                .enumerate()
+                .filter(|(_, (value, last_value))| {
+                    value.value != last_value.value || value.status != last_value.status
+                })
+                .map(|(idx, (value, _))| (idx as u16, value.clone()))
+                .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                DataSetMessagePayload::DeltaFrame(changes)
+            } else if self
+                .last_message_time
+                .is_none_or(|last| (*now - last).num_milliseconds() as f64 >= keep_alive_time)
+            {
+                DataSetMessagePayload::KeepAlive
+            } else {
+                return None;
+            }
+        };
+
+        if payload != DataSetMessagePayload::KeepAlive {
+            self.last_values = Some(values);
+        }
+        self.last_message_time = Some(*now);
+        self.sequence_number = self.sequence_number.wrapping_add(1);
+
+        let mut message = DataSetMessage::new(field_encoding, payload);
+        message.sequence_number = Some(self.sequence_number);
+        message.timestamp = Some(DateTime::from(*now));
+        message.major_version = Some(configuration_version.major_version);
+        message.minor_version = Some(configuration_version.minor_version);
+        Some(message)
+    }
+
+    /// Removes the parts of a value that the field content mask doesn't include
+    fn apply_field_content_mask(
+        &self,
+        value: DataValue,
+        field_encoding: FieldEncoding,
+    ) -> DataValue {
+        let mask = self.field_content_mask;
+        match field_encoding {
+            // A bad value is sent as its status code when fields are variants
+            FieldEncoding::Variant => match value.status {
+                Some(status) if status.is_bad() => DataValue::value_only(status),
+                _ => DataValue::value_only(value.value.unwrap_or_default()),
+            },
+            FieldEncoding::RawData => DataValue::value_only(value.value.unwrap_or_default()),
+            FieldEncoding::DataValue => DataValue {
+                value: value.value,
+                status: value
+                    .status
+                    .filter(|_| mask.contains(DataSetFieldContentMask::StatusCode)),
+                source_timestamp: value
+                    .source_timestamp
+                    .filter(|_| mask.contains(DataSetFieldContentMask::SourceTimestamp)),
+                source_picoseconds: value
+                    .source_picoseconds
+                    .filter(|_| mask.contains(DataSetFieldContentMask::SourcePicoSeconds)),
+                server_timestamp: value
+                    .server_timestamp
+                    .filter(|_| mask.contains(DataSetFieldContentMask::ServerTimestamp)),
+                server_picoseconds: value
+                    .server_picoseconds
+                    .filter(|_| mask.contains(DataSetFieldContentMask::ServerPicoSeconds)),
+            },
+        }
+    }
+}
+
+/// A writer group, i.e. the data set writers whose messages are sent together in one network
+/// message every publishing interval
+#[derive(Debug, Clone)]
+pub struct WriterGroup {
+    pub writer_group_id: u16,
+    pub name: UAString,
+    /// The publishing interval in milliseconds
+    pub publishing_interval: f64,
+    /// The time in milliseconds after which a writer sends a keep alive if none of its values
+    /// have changed
+    pub keep_alive_time: f64,
+    /// Whether the network messages of the group are signed or signed and encrypted
+    pub security_mode: MessageSecurityMode,
+    /// The security group whose keys secure the network messages of the group
+    pub security_group_id: UAString,
+    pub data_set_writers: Vec<DataSetWriter>,
+    sequence_number: u16,
+}
+
+impl WriterGroup {
+    /// Creates a writer group with no data set writers
+    pub fn new<T>(writer_group_id: u16, name: T, publishing_interval: f64) -> Self
+    where
+        T: Into<UAString>,
+    {
+        WriterGroup {
+            writer_group_id,
+            name: name.into(),
+            publishing_interval,
+            keep_alive_time: publishing_interval * DEFAULT_KEEP_ALIVE_COUNT,
+            security_mode: MessageSecurityMode::None,
+            security_group_id: UAString::null(),
+            data_set_writers: Vec::new(),
+            sequence_number: 0,
+        }
+    }
+
+    /// Sets the time in milliseconds after which a writer sends a keep alive
+    pub fn keep_alive_time(mut self, keep_alive_time: f64) -> Self {
+        self.keep_alive_time = keep_alive_time;
+        self
+    }
+
+    /// Secures the network messages of the group with the keys of the security group. The
+    /// publisher must be given the keys with `Publisher::set_security_keys()`.
+    pub fn security<T>(mut self, security_mode: MessageSecurityMode, security_group_id: T) -> Self
+    where
+        T: Into<UAString>,
+    {
+        self.security_mode = security_mode;
+        self.security_group_id = security_group_id.into();
+        self
+    }
+
+    /// Adds a data set writer to the group
+    pub fn add_data_set_writer(mut self, data_set_writer: DataSetWriter) -> Self {
+        self.data_set_writers.push(data_set_writer);
+        self
+    }
+
+    /// The configuration of the group, as it is sent in discovery responses
+    pub fn data_type(&self) -> WriterGroupDataType {
+        WriterGroupDataType {
+            name: self.name.clone(),
+            enabled: true,
+            security_mode: self.security_mode,
+            security_group_id: self.security_group_id.clone(),
+            security_key_services: None,
+            max_network_message_size: MAX_DATAGRAM_SIZE as u32,
+            group_properties: None,
+            writer_group_id: self.writer_group_id,
+            publishing_interval: self.publishing_interval,
+            keep_alive_time: self.keep_alive_time,
+            priority: 0,
+            locale_ids: None,
+            header_layout_uri: UAString::null(),
+            transport_settings: ExtensionObject::null(),
+            message_settings: ExtensionObject::null(),
+            data_set_writers: Some(
+                self.data_set_writers
+                    .iter()
+                    .map(|writer| writer.data_type())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A data set message with the id of its data set writer and the index of its published data set
+type WrittenDataSet = (u16, usize, DataSetMessage);
+
+/// A publisher that sends the published data sets of its writer groups to an `opc.udp://`
+/// address, or to an MQTT broker
+pub struct Publisher {
+    publisher_id: PublisherId,
+    url: String,
+    published_data_sets: Vec<PublishedDataSet>,
+    writer_groups: Vec<WriterGroup>,
+    information: PublisherInformation,
+    /// The keys of each security group, by the security group id
+    security_keys: BTreeMap<String, SecurityKeys>,
+    #[cfg(feature = "pubsub-mqtt")]
+    mqtt_settings: MqttSettings,
+    abort: bool,
+}
+
+impl Publisher {
+    /// Creates a publisher that sends to the url
+    pub fn new<T>(publisher_id: PublisherId, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Publisher {
+            publisher_id,
+            url: url.into(),
+            published_data_sets: Vec::new(),
+            writer_groups: Vec::new(),
+            information: PublisherInformation::default(),
+            security_keys: BTreeMap::new(),
+            #[cfg(feature = "pubsub-mqtt")]
+            mqtt_settings: MqttSettings::default(),
+            abort: false,
+        }
+    }
+
+    pub fn publisher_id(&self) -> &PublisherId {
+        &self.publisher_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sets the settings used when the url is an `mqtt://` or `mqtts://` broker
+    #[cfg(feature = "pubsub-mqtt")]
+    pub fn set_mqtt_settings(&mut self, mqtt_settings: MqttSettings) {
+        self.mqtt_settings = mqtt_settings;
+    }
+
+    #[cfg(feature = "pubsub-mqtt")]
+    pub fn mqtt_settings(&self) -> &MqttSettings {
+        &self.mqtt_settings
+    }
+
+    /// Sets the keys of a security group, e.g. from a call to GetSecurityKeys. Writer groups
+    /// with the security group id are secured with them.
+    pub fn set_security_keys<T>(&mut self, security_group_id: T, security_keys: SecurityKeys)
+    where
+        T: Into<String>,
+    {
+        self.security_keys
+            .insert(security_group_id.into(), security_keys);
+    }
+
+    pub fn security_keys(&self, security_group_id: &str) -> Option<&SecurityKeys> {
+        self.security_keys.get(security_group_id)
+    }
+
+    pub fn security_keys_mut(&mut self, security_group_id: &str) -> Option<&mut SecurityKeys> {
+        self.security_keys.get_mut(security_group_id)
+    }
+
+    /// Adds a published data set
+    pub fn add_published_data_set(&mut self, published_data_set: PublishedDataSet) {
+        self.published_data_sets.push(published_data_set);
+    }
+
+    /// Adds a writer group. The data set writers of the group send the published data sets
+    /// with the same names.
+    pub fn add_writer_group(&mut self, writer_group: WriterGroup) {
+        self.writer_groups.push(writer_group);
+    }
+
+    pub fn published_data_sets(&self) -> &[PublishedDataSet] {
+        &self.published_data_sets
+    }
+
+    pub fn writer_groups(&self) -> &[WriterGroup] {
+        &self.writer_groups
+    }
+
+    /// The information that answers discovery requests
+    pub fn information(&self) -> &PublisherInformation {
+        &self.information
+    }
+
+    /// The information that answers discovery requests, e.g. to set the endpoints
+    pub fn information_mut(&mut self) -> &mut PublisherInformation {
+        &mut self.information
+    }
+
+    /// Updates the metadata and writer groups in the information that answers discovery
+    /// requests from the configuration of the publisher
+    pub fn update_information(&mut self, address_space: &AddressSpace) {
+        self.information.meta_data = self
+            .writer_groups
+            .iter()
+            .flat_map(|writer_group| writer_group.data_set_writers.iter())
+            .filter_map(|writer| {
+                self.published_data_sets
+                    .iter()
+                    .find(|data_set| data_set.name == writer.data_set_name)
+                    .map(|data_set| (writer.data_set_writer_id, data_set.meta_data(address_space)))
+            })
+            .collect();
+        self.information.writer_groups = self
+            .writer_groups
+            .iter()
+            .map(|writer_group| writer_group.data_type())
+            .collect();
+    }
+
+    /// Samples the data sets of the writer group and makes the network message to send for this
+    /// publishing interval. There is no message if the group doesn't exist or none of its
+    /// writers have anything to send.
+    pub fn publish(
+        &mut self,
+        writer_group_id: u16,
+        address_space: &AddressSpace,
+        now: &DateTimeUtc,
+    ) -> Option<NetworkMessage> {
+        let (sequence_number, messages) =
+            self.write_data_sets(writer_group_id, address_space, now)?;
+        let mut header = NetworkMessageHeader::new(self.publisher_id.clone());
+        header.group_header = Some(GroupHeader {
+            writer_group_id: Some(writer_group_id),
+            group_version: None,
+            network_message_number: Some(1),
+            sequence_number: Some(sequence_number),
+        });
+        let (data_set_writer_ids, messages) = messages
+            .into_iter()
+            .map(|(data_set_writer_id, _, message)| (data_set_writer_id, message))
+            .unzip();
+        header.data_set_writer_ids = Some(data_set_writer_ids);
+        Some(NetworkMessage::data_set_messages(header, messages))
+    }
+
+    /// Encodes a network message of the writer group. The message is secured with the keys of
+    /// the group's security group when the group has a security mode.
+    pub fn encode(
+        &mut self,
+        writer_group_id: u16,
+        message: &NetworkMessage,
+    ) -> Result<Vec<u8>, StatusCode> {
+        let security = self
+            .writer_groups
+            .iter()
+            .find(|writer_group| writer_group.writer_group_id == writer_group_id)
+            .filter(|writer_group| {
+                matches!(
+                    writer_group.security_mode,
+                    MessageSecurityMode::Sign | MessageSecurityMode::SignAndEncrypt
+                )
+            })
+            .map(|writer_group| {
+                (
+                    writer_group.security_mode,
+                    writer_group.security_group_id.as_ref().to_string(),
+                )
+            });
+        match security {
+            None => encode_message(message),
+            Some((security_mode, security_group_id)) => {
+                match self.security_keys.get_mut(&security_group_id) {
+                    Some(security_keys) => security_keys.encode(message, security_mode),
+                    None => {
+                        error!(
+                            "Writer group {} has no keys for security group {}",
+                            writer_group_id, security_group_id
+                        );
+                        Err(StatusCode::BadSecurityChecksFailed)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Samples the data sets of the writer group and makes the JSON network message to send for
+    /// this publishing interval, like `publish()` does for UADP
+    pub fn publish_json(
+        &mut self,
+        writer_group_id: u16,
+        address_space: &AddressSpace,
+        now: &DateTimeUtc,
+    ) -> Option<JsonNetworkMessage> {
+        let (_, messages) = self.write_data_sets(writer_group_id, address_space, now)?;
+        let messages = messages
+            .into_iter()
+            .map(|(data_set_writer_id, data_set_idx, message)| {
+                let field_names = self.published_data_sets[data_set_idx]
+                    .fields
+                    .iter()
+                    .map(|field| field.name.as_ref())
+                    .collect::<Vec<_>>();
+                JsonDataSetMessage::from_data_set_message(data_set_writer_id, message, &field_names)
+            })
+            .collect();
+        Some(JsonNetworkMessage {
+            message_id: json_message::message_id(),
+            publisher_id: Some(UAString::from(self.publisher_id.to_string())),
+            writer_group_name: self
+                .writer_groups
+                .iter()
+                .find(|writer_group| writer_group.writer_group_id == writer_group_id)
+                .map(|writer_group| writer_group.name.clone()),
+            data_set_class_id: None,
+            messages,
+        })
+    }
+
+    /// The JSON metadata messages for the data sets of the data set writers, made from the
+    /// information set by `update_information()`
+    pub fn meta_data_messages(&self) -> Vec<JsonMetaDataMessage> {
+        let timestamp = DateTime::now();
+        self.information
+            .meta_data
+            .iter()
+            .map(|(data_set_writer_id, meta_data)| JsonMetaDataMessage {
+                message_id: json_message::message_id(),
+                publisher_id: UAString::from(self.publisher_id.to_string()),
+                data_set_writer_id: *data_set_writer_id,
+                meta_data: meta_data.clone(),
+                timestamp: Some(timestamp),
+            })
+            .collect()
+    }
+
+    /// Samples the data sets of the writer group and makes the messages of its data set writers.
+    /// The result is the sequence number of the group and each message with the id of its writer
+    /// and the index of its published data set.
+    fn write_data_sets(
+        &mut self,
+        writer_group_id: u16,
+        address_space: &AddressSpace,
+        now: &DateTimeUtc,
+    ) -> Option<(u16, Vec<WrittenDataSet>)> {
+        let Publisher {
+            ref published_data_sets,
+            ref mut writer_groups,
+            ..
+        } = self;
+        let writer_group = writer_groups
+            .iter_mut()
+            .find(|writer_group| writer_group.writer_group_id == writer_group_id)?;
+        let keep_alive_time = writer_group.keep_alive_time;
+
+        let mut messages = Vec::new();
+        for writer in writer_group.data_set_writers.iter_mut() {
+            let Some(data_set_idx) = published_data_sets
+                .iter()
+                .position(|data_set| data_set.name == writer.data_set_name)
+            else {
+                debug!(
+                    "Data set writer {} has no published data set named {}",
+                    writer.data_set_writer_id, writer.data_set_name
+                );
+                continue;
+            };
+            let data_set = &published_data_sets[data_set_idx];
+            let values = data_set.sample(address_space);
+            if let Some(message) = writer.next_message(
+                values,
+                &data_set.configuration_version,
+                keep_alive_time,
+                now,
+            ) {
+                messages.push((writer.data_set_writer_id, data_set_idx, message));
+            }
+        }
+        if messages.is_empty() {
+            return None;
+        }
+        writer_group.sequence_number = writer_group.sequence_number.wrapping_add(1);
+        Some((writer_group.sequence_number, messages))
+    }
+
+    /// Answers a network message holding a discovery request. Other messages are not answered.
+    pub fn handle_discovery_request(&mut self, message: &NetworkMessage) -> Vec<NetworkMessage> {
+        match message.payload {
+            NetworkMessagePayload::DiscoveryRequest(ref request) => self
+                .information
+                .respond(request)
+                .into_iter()
+                .map(|response| {
+                    NetworkMessage::discovery_response(
+                        NetworkMessageHeader::new(self.publisher_id.clone()),
+                        response,
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Tells the publisher to stop. Each task stops the next time it wakes up.
+    pub fn abort(&mut self) {
+        info!("Publisher has been told to abort");
+        self.abort = true;
+    }
+
+    pub fn is_abort(&self) -> bool {
+        self.abort
+    }
+
+    /// Runs the publisher until it is aborted. Every writer group publishes on its own interval.
+    /// When the url is a multicast address, discovery requests sent to it are answered too.
+    ///
+    /// When the url is an `mqtt://` or `mqtts://` broker, JSON messages are sent to it instead.
+    pub async fn run(
+        publisher: Arc<RwLock<Publisher>>,
+        address_space: Arc<RwLock<AddressSpace>>,
+    ) -> Result<(), StatusCode> {
+        #[cfg(feature = "pubsub-mqtt")]
+        if mqtt::is_mqtt_url(trace_read_lock!(publisher).url()) {
+            return mqtt::run_publisher(publisher, address_space).await;
+        }
+        let (address, writer_groups) = {
+            let mut publisher = trace_write_lock!(publisher);
+            {
+                let address_space = trace_read_lock!(address_space);
+                publisher.update_information(&address_space);
+            }
+            let address = udp::udp_address(&publisher.url)?;
+            let writer_groups = publisher
+                .writer_groups
+                .iter()
+                .map(|writer_group| {
+                    (
+                        writer_group.writer_group_id,
+                        writer_group.publishing_interval,
+                    )
+                })
+                .collect::<Vec<_>>();
+            (address, writer_groups)
+        };
+        info!("Publisher is sending to {}", address);
+
+        let sender = Arc::new(udp::bind_sender(&address).await?);
+        let mut tasks = writer_groups
+            .into_iter()
+            .map(|(writer_group_id, publishing_interval)| {
+                tokio::spawn(Self::publish_task(
+                    publisher.clone(),
+                    address_space.clone(),
+                    sender.clone(),
+                    address,
+                    writer_group_id,
+                    publishing_interval,
+                ))
+            })
+            .collect::<Vec<_>>();
+        if address.ip().is_multicast() {
+            let receiver = udp::bind_receiver(&address)?;
+            tasks.push(tokio::spawn(Self::discovery_task(
+                publisher.clone(),
+                receiver,
+                sender,
+                address,
+            )));
+        }
+        futures::future::join_all(tasks).await;
+        info!("Publisher has stopped");
+        Ok(())
+    }
+
+    async fn publish_task(
+        publisher: Arc<RwLock<Publisher>>,
+        address_space: Arc<RwLock<AddressSpace>>,
+        sender: Arc<UdpSocket>,
+        address: SocketAddr,
+        writer_group_id: u16,
+        publishing_interval: f64,
+    ) {
+        let publishing_interval = Duration::from_micros((publishing_interval * 1000f64) as u64)
+            .max(Duration::from_millis(1));
+        let mut timer = interval_at(Instant::now(), publishing_interval);
+        loop {
+            timer.tick().await;
+            let data = {
+                let mut publisher = trace_write_lock!(publisher);
+                if publisher.is_abort() {
+                    break;
+                }
+                let address_space = trace_read_lock!(address_space);
+                publisher
+                    .publish(writer_group_id, &address_space, &chrono::Utc::now())
+                    .map(|message| publisher.encode(writer_group_id, &message))
+            };
+            match data {
+                Some(Ok(data)) => Self::send(&sender, &address, &data).await,
+                Some(Err(err)) => error!("Cannot encode a network message - {}", err),
+                None => {}
+            }
+        }
+        debug!(
+            "Publishing task for writer group {} is finished",
+            writer_group_id
+        );
+    }
+
+    async fn discovery_task(
+        publisher: Arc<RwLock<Publisher>>,
+        receiver: UdpSocket,
+        sender: Arc<UdpSocket>,
+        address: SocketAddr,
+    ) {
+        let decoding_options = DecodingOptions::default();
+        let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+        loop {
+            if trace_read_lock!(publisher).is_abort() {
+                break;
+            }
+            let received = timeout(
+                Duration::from_millis(ABORT_POLL_INTERVAL_MS),
+                receiver.recv_from(&mut buffer),
+            )
+            .await;
+            let size = match received {
+                Ok(Ok((size, _))) => size,
+                Ok(Err(err)) => {
+                    error!("Cannot receive from {} - {}", address, err);
+                    break;
+                }
+                // Timed out, check for abort again
+                Err(_) => continue,
+            };
+            // Anything that isn't a discovery request, including messages from this and other
+            // publishers, is ignored
+            let responses = match NetworkMessage::decode(
+                &mut Cursor::new(&buffer[..size]),
+                &decoding_options,
+            ) {
+                Ok(message) => trace_write_lock!(publisher).handle_discovery_request(&message),
+                Err(err) => {
+                    debug!("Ignoring a datagram that cannot be decoded - {}", err);
+                    continue;
+                }
+            };
+            for response in responses {
+                match encode_message(&response) {
+                    Ok(data) => Self::send(&sender, &address, &data).await,
+                    Err(err) => error!("Cannot encode a network message - {}", err),
+                }
+            }
+        }
+        debug!("Discovery task is finished");
+    }
+
+    async fn send(sender: &UdpSocket, address: &SocketAddr, data: &[u8]) {
+        if data.len() > MAX_DATAGRAM_SIZE {
+            error!(
+                "Network message of {} bytes is too large to send in a datagram",
+                data.len()
+            );
+        } else if let Err(err) = sender.send_to(data, address).await {
+            error!("Cannot send a network message to {} - {}", address, err);
+        }
+    }
+}
+
+/// Encodes a network message that isn't secured
+fn encode_message(message: &NetworkMessage) -> Result<Vec<u8>, StatusCode> {
+    let mut stream = Cursor::new(Vec::with_capacity(message.byte_len()));
+    message.encode(&mut stream)?;
+    Ok(stream.into_inner())
+}
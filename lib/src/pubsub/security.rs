@@ -0,0 +1,486 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the security of UADP network messages described in Part 14 7.2.2.2.3 and 8. A
+//! secured message is signed, and optionally encrypted, with symmetric keys that every publisher
+//! and subscriber of a security group gets from a security key service (SKS). The keys have
+//! token ids and are replaced by the next key after a key lifetime, so each message says the
+//! token of the key that secured it.
+
+use std::{
+    fmt,
+    io::{Cursor, Read, Write},
+    time::{Duration, Instant},
+};
+
+use openssl::symm::{self, Cipher};
+
+use crate::crypto::{hmac_sha256, random, verify_hmac_sha256, SHA256_SIZE};
+use crate::types::{status_code::StatusCode, *};
+
+use super::network_message::{NetworkMessage, NetworkMessageHeader};
+
+/// The uri of the security policy that encrypts with AES-128 in counter mode
+pub const SECURITY_POLICY_AES128_CTR: &str =
+    "http://opcfoundation.org/UA/SecurityPolicy#PubSub-Aes128-CTR";
+
+/// The uri of the security policy that encrypts with AES-256 in counter mode
+pub const SECURITY_POLICY_AES256_CTR: &str =
+    "http://opcfoundation.org/UA/SecurityPolicy#PubSub-Aes256-CTR";
+
+// SecurityFlags of the security header
+const NETWORK_MESSAGE_SIGNED: u8 = 0x01;
+const NETWORK_MESSAGE_ENCRYPTED: u8 = 0x02;
+const SECURITY_FOOTER_ENABLED: u8 = 0x04;
+const FORCE_KEY_RESET: u8 = 0x08;
+
+/// Both policies sign with HMAC-SHA256 using a 256 bit key
+const SIGNING_KEY_LENGTH: usize = 32;
+const SIGNATURE_LENGTH: usize = SHA256_SIZE;
+const KEY_NONCE_LENGTH: usize = 4;
+const MESSAGE_NONCE_LENGTH: usize = 8;
+
+/// A security policy of PubSub messages. Messages are signed with HMAC-SHA256 and encrypted with
+/// AES in counter mode.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PubSubSecurityPolicy {
+    Aes128Ctr,
+    Aes256Ctr,
+}
+
+impl PubSubSecurityPolicy {
+    pub fn from_uri(uri: &str) -> Result<Self, StatusCode> {
+        match uri {
+            SECURITY_POLICY_AES128_CTR => Ok(PubSubSecurityPolicy::Aes128Ctr),
+            SECURITY_POLICY_AES256_CTR => Ok(PubSubSecurityPolicy::Aes256Ctr),
+            _ => {
+                error!("Unsupported PubSub security policy {}", uri);
+                Err(StatusCode::BadSecurityPolicyRejected)
+            }
+        }
+    }
+
+    pub fn to_uri(&self) -> &'static str {
+        match self {
+            PubSubSecurityPolicy::Aes128Ctr => SECURITY_POLICY_AES128_CTR,
+            PubSubSecurityPolicy::Aes256Ctr => SECURITY_POLICY_AES256_CTR,
+        }
+    }
+
+    fn encrypting_key_length(&self) -> usize {
+        match self {
+            PubSubSecurityPolicy::Aes128Ctr => 16,
+            PubSubSecurityPolicy::Aes256Ctr => 32,
+        }
+    }
+
+    /// The length of a key from a security key service, which is the signing key, the
+    /// encrypting key and the key nonce one after the other
+    pub fn key_length(&self) -> usize {
+        SIGNING_KEY_LENGTH + self.encrypting_key_length() + KEY_NONCE_LENGTH
+    }
+
+    fn cipher(&self) -> Cipher {
+        match self {
+            PubSubSecurityPolicy::Aes128Ctr => Cipher::aes_128_ctr(),
+            PubSubSecurityPolicy::Aes256Ctr => Cipher::aes_256_ctr(),
+        }
+    }
+}
+
+/// The security header of a network message, which says how the message is secured and with
+/// which key
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityHeader {
+    pub signed: bool,
+    pub encrypted: bool,
+    /// Tells subscribers to get new keys from the security key service
+    pub force_key_reset: bool,
+    /// The token id of the key that secured the message
+    pub security_token_id: u32,
+    /// Makes the counter blocks of each message unique when it is encrypted
+    pub message_nonce: Vec<u8>,
+}
+
+impl SecurityHeader {
+    /// The security mode that the message was secured with
+    pub fn security_mode(&self) -> MessageSecurityMode {
+        if self.encrypted {
+            MessageSecurityMode::SignAndEncrypt
+        } else if self.signed {
+            MessageSecurityMode::Sign
+        } else {
+            MessageSecurityMode::None
+        }
+    }
+
+    pub(crate) fn byte_len(&self) -> usize {
+        1 + 4 + 1 + self.message_nonce.len()
+    }
+
+    pub(crate) fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut flags = 0;
+        if self.signed {
+            flags |= NETWORK_MESSAGE_SIGNED;
+        }
+        if self.encrypted {
+            flags |= NETWORK_MESSAGE_ENCRYPTED;
+        }
+        if self.force_key_reset {
+            flags |= FORCE_KEY_RESET;
+        }
+        let nonce_length = u8::try_from(self.message_nonce.len()).map_err(|_| {
+            error!("Message nonce is too long");
+            StatusCode::BadEncodingLimitsExceeded
+        })?;
+        let mut size = write_u8(stream, flags)?;
+        size += write_u32(stream, self.security_token_id)?;
+        size += write_u8(stream, nonce_length)?;
+        size += process_encode_io_result(stream.write(&self.message_nonce))?;
+        Ok(size)
+    }
+
+    pub(crate) fn decode<S: Read>(stream: &mut S, _: &DecodingOptions) -> EncodingResult<Self> {
+        let flags = read_u8(stream)?;
+        if flags & SECURITY_FOOTER_ENABLED != 0 {
+            error!("Network message has a security footer, which is not supported");
+            return Err(StatusCode::BadNotSupported);
+        }
+        let security_token_id = read_u32(stream)?;
+        let nonce_length = read_u8(stream)?;
+        let mut message_nonce = vec![0u8; nonce_length as usize];
+        process_decode_io_result(stream.read_exact(&mut message_nonce))?;
+        Ok(SecurityHeader {
+            signed: flags & NETWORK_MESSAGE_SIGNED != 0,
+            encrypted: flags & NETWORK_MESSAGE_ENCRYPTED != 0,
+            force_key_reset: flags & FORCE_KEY_RESET != 0,
+            security_token_id,
+            message_nonce,
+        })
+    }
+}
+
+/// A key of a security group, made of the keys that sign and encrypt messages and a nonce that
+/// is part of the counter blocks
+#[derive(Clone, PartialEq)]
+pub struct SecurityKey {
+    token_id: u32,
+    signing_key: Vec<u8>,
+    encrypting_key: Vec<u8>,
+    key_nonce: Vec<u8>,
+}
+
+impl fmt::Debug for SecurityKey {
+    // The key material is left out so it doesn't end up in logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecurityKey")
+            .field("token_id", &self.token_id)
+            .finish()
+    }
+}
+
+impl SecurityKey {
+    /// Splits a key from a security key service into its parts
+    pub fn new(
+        policy: PubSubSecurityPolicy,
+        token_id: u32,
+        key: &[u8],
+    ) -> Result<SecurityKey, StatusCode> {
+        if key.len() != policy.key_length() {
+            error!(
+                "Key {} has {} bytes but {} needs {}",
+                token_id,
+                key.len(),
+                policy.to_uri(),
+                policy.key_length()
+            );
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let (signing_key, rest) = key.split_at(SIGNING_KEY_LENGTH);
+        let (encrypting_key, key_nonce) = rest.split_at(policy.encrypting_key_length());
+        Ok(SecurityKey {
+            token_id,
+            signing_key: signing_key.to_vec(),
+            encrypting_key: encrypting_key.to_vec(),
+            key_nonce: key_nonce.to_vec(),
+        })
+    }
+
+    /// Makes a random key, which is what a security key service hands out
+    pub fn generate(policy: PubSubSecurityPolicy, token_id: u32) -> SecurityKey {
+        let mut key = vec![0u8; policy.key_length()];
+        random::bytes(&mut key);
+        Self::new(policy, token_id, &key).unwrap()
+    }
+
+    pub fn token_id(&self) -> u32 {
+        self.token_id
+    }
+
+    /// The key as it is sent by a security key service
+    pub fn to_byte_string(&self) -> ByteString {
+        let mut key = self.signing_key.clone();
+        key.extend_from_slice(&self.encrypting_key);
+        key.extend_from_slice(&self.key_nonce);
+        ByteString::from(key)
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<[u8; SIGNATURE_LENGTH], StatusCode> {
+        let mut signature = [0u8; SIGNATURE_LENGTH];
+        hmac_sha256(&self.signing_key, data, &mut signature)?;
+        Ok(signature)
+    }
+
+    /// Encrypts or decrypts, which are the same in counter mode. The first counter block is the
+    /// key nonce, the message nonce and a block counter of 1.
+    fn crypt(
+        &self,
+        policy: PubSubSecurityPolicy,
+        message_nonce: &[u8],
+        data: &[u8],
+    ) -> Result<Vec<u8>, StatusCode> {
+        if message_nonce.len() != MESSAGE_NONCE_LENGTH {
+            error!(
+                "Message nonce has {} bytes but should have {}",
+                message_nonce.len(),
+                MESSAGE_NONCE_LENGTH
+            );
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let mut counter_block = Vec::with_capacity(16);
+        counter_block.extend_from_slice(&self.key_nonce);
+        counter_block.extend_from_slice(message_nonce);
+        counter_block.extend_from_slice(&1u32.to_be_bytes());
+        symm::encrypt(
+            policy.cipher(),
+            &self.encrypting_key,
+            Some(&counter_block),
+            data,
+        )
+        .map_err(|err| {
+            error!("Cannot encrypt or decrypt a network message - {}", err);
+            StatusCode::BadSecurityChecksFailed
+        })
+    }
+}
+
+/// The keys of a security group that a publisher or subscriber has got from a security key
+/// service. The first key is current when the keys are received and the next key becomes
+/// current after the time to the next key, then each following key after the key lifetime.
+///
+/// The previous key is kept after the current key changes so messages secured with it just
+/// before the change can still be decoded.
+#[derive(Debug, Clone)]
+pub struct SecurityKeys {
+    policy: PubSubSecurityPolicy,
+    keys: Vec<SecurityKey>,
+    current: usize,
+    next_key_at: Instant,
+    key_lifetime: Duration,
+    nonce_sequence: u32,
+}
+
+impl SecurityKeys {
+    /// Creates the keys from the result of a GetSecurityKeys call. The first key must be the
+    /// current key, which is what a security key service returns for a starting token id of 0.
+    /// The times are in milliseconds.
+    pub fn new(
+        security_policy_uri: &str,
+        first_token_id: u32,
+        keys: &[ByteString],
+        time_to_next_key: f64,
+        key_lifetime: f64,
+    ) -> Result<SecurityKeys, StatusCode> {
+        let policy = PubSubSecurityPolicy::from_uri(security_policy_uri)?;
+        if keys.is_empty() {
+            error!("There are no security keys");
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let keys = keys
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| {
+                SecurityKey::new(policy, next_token_id(first_token_id, idx), key.as_ref())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SecurityKeys {
+            policy,
+            keys,
+            current: 0,
+            next_key_at: Instant::now() + duration_from_ms(time_to_next_key),
+            key_lifetime: duration_from_ms(key_lifetime),
+            nonce_sequence: 0,
+        })
+    }
+
+    pub fn policy(&self) -> PubSubSecurityPolicy {
+        self.policy
+    }
+
+    /// The key that messages are secured with
+    pub fn current_key(&self) -> &SecurityKey {
+        &self.keys[self.current]
+    }
+
+    /// Finds a key by its token id
+    pub fn find_key(&self, token_id: u32) -> Option<&SecurityKey> {
+        self.keys.iter().find(|key| key.token_id == token_id)
+    }
+
+    /// Moves to the key that is current at the time. The last key stays current once it
+    /// expires, and `needs_keys()` says that it is time to get more.
+    pub fn rotate(&mut self, now: Instant) {
+        while now >= self.next_key_at && self.current + 1 < self.keys.len() {
+            self.current += 1;
+            self.next_key_at += self.key_lifetime;
+            debug!(
+                "Security key {} is now current",
+                self.keys[self.current].token_id
+            );
+        }
+        // Drop all but the previous key
+        if self.current > 1 {
+            self.keys.drain(..self.current - 1);
+            self.current = 1;
+        }
+    }
+
+    /// Tests if the current key is the last one, so new keys should be got from the security
+    /// key service before it expires
+    pub fn needs_keys(&self) -> bool {
+        self.current + 1 >= self.keys.len()
+    }
+
+    /// Replaces the keys with newer keys from the security key service, keeping the current
+    /// key as the previous key if the new keys don't have it
+    pub fn update(&mut self, mut keys: SecurityKeys) {
+        let current = self.current_key().clone();
+        if keys.find_key(current.token_id).is_none() {
+            keys.keys.insert(0, current);
+            keys.current += 1;
+        }
+        keys.nonce_sequence = self.nonce_sequence;
+        *self = keys;
+    }
+
+    /// Encodes a network message secured with the current key. The message is signed, and
+    /// encrypted too when the security mode is `SignAndEncrypt`.
+    pub fn encode(
+        &mut self,
+        message: &NetworkMessage,
+        security_mode: MessageSecurityMode,
+    ) -> Result<Vec<u8>, StatusCode> {
+        let encrypted = match security_mode {
+            MessageSecurityMode::Sign => false,
+            MessageSecurityMode::SignAndEncrypt => true,
+            _ => {
+                error!(
+                    "Security mode {:?} cannot secure a network message",
+                    security_mode
+                );
+                return Err(StatusCode::BadSecurityModeRejected);
+            }
+        };
+        self.rotate(Instant::now());
+
+        // The nonce is random with a sequence number so it is never the same for one key
+        let mut message_nonce = vec![0u8; MESSAGE_NONCE_LENGTH];
+        random::bytes(&mut message_nonce[..4]);
+        message_nonce[4..].copy_from_slice(&self.nonce_sequence.to_le_bytes());
+        self.nonce_sequence = self.nonce_sequence.wrapping_add(1);
+
+        let key = &self.keys[self.current];
+        let header = NetworkMessageHeader {
+            security: Some(SecurityHeader {
+                signed: true,
+                encrypted,
+                force_key_reset: false,
+                security_token_id: key.token_id,
+                message_nonce,
+            }),
+            ..message.header.clone()
+        };
+        let mut stream = Cursor::new(Vec::with_capacity(message.byte_len() + 64));
+        let header_len = header.encode(message.payload.message_type(), &mut stream)?;
+        message.encode_payload(&mut stream)?;
+        let mut data = stream.into_inner();
+        if let Some(ref security) = header.security {
+            if encrypted {
+                let payload =
+                    key.crypt(self.policy, &security.message_nonce, &data[header_len..])?;
+                data.truncate(header_len);
+                data.extend_from_slice(&payload);
+            }
+        }
+        let signature = key.sign(&data)?;
+        data.extend_from_slice(&signature);
+        Ok(data)
+    }
+
+    /// Decodes a network message, checking its signature and decrypting it if it is secured.
+    /// The security header of the result says how it was secured. A message that isn't secured
+    /// is decoded as it is.
+    pub fn decode(
+        &self,
+        data: &[u8],
+        decoding_options: &DecodingOptions,
+    ) -> Result<NetworkMessage, StatusCode> {
+        let mut stream = Cursor::new(data);
+        let (header, message_type) = NetworkMessageHeader::decode(&mut stream, decoding_options)?;
+        let Some(ref security) = header.security else {
+            return NetworkMessage::decode_payload(
+                header,
+                message_type,
+                &mut stream,
+                decoding_options,
+            );
+        };
+        if !security.signed {
+            error!("Network message is secured but not signed");
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let Some(key) = self.find_key(security.security_token_id) else {
+            error!(
+                "Network message is secured with key {}, which is unknown",
+                security.security_token_id
+            );
+            return Err(StatusCode::BadSecurityChecksFailed);
+        };
+        let header_len = stream.position() as usize;
+        if data.len() < header_len + SIGNATURE_LENGTH {
+            error!("Network message is too short to have a signature");
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let (signed, signature) = data.split_at(data.len() - SIGNATURE_LENGTH);
+        if !verify_hmac_sha256(&key.signing_key, signed, signature) {
+            error!("Network message has an invalid signature");
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let payload = if security.encrypted {
+            key.crypt(self.policy, &security.message_nonce, &signed[header_len..])?
+        } else {
+            signed[header_len..].to_vec()
+        };
+        NetworkMessage::decode_payload(
+            header,
+            message_type,
+            &mut Cursor::new(payload),
+            decoding_options,
+        )
+    }
+}
+
+/// The token id that follows another by a number of keys. Token ids wrap around and skip 0,
+/// which is not a valid token id.
+pub(crate) fn next_token_id(token_id: u32, count: usize) -> u32 {
+    let mut token_id = token_id;
+    for _ in 0..count {
+        token_id = token_id.wrapping_add(1).max(1);
+    }
+    token_id
+}
+
+pub(crate) fn duration_from_ms(ms: f64) -> Duration {
+    Duration::from_micros((ms.max(0f64) * 1000f64) as u64)
+}
@@ -0,0 +1,382 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the security key service (SKS) described in Part 14 8.3. A server that is an SKS
+//! holds the keys of its security groups, replaces them with new keys after each key lifetime,
+//! and hands them to publishers and subscribers through the `GetSecurityKeys` method of the
+//! `PublishSubscribe` object.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Instant,
+};
+
+#[cfg(any(feature = "server", feature = "client"))]
+use crate::types::service_types::CallMethodResult;
+use crate::types::{status_code::StatusCode, *};
+
+use super::security::{
+    duration_from_ms, next_token_id, PubSubSecurityPolicy, SecurityKey, SecurityKeys,
+};
+
+#[cfg(feature = "server")]
+use std::sync::Arc;
+
+#[cfg(feature = "server")]
+use crate::{
+    server::{callbacks::Method, session::SessionManager},
+    sync::*,
+    types::service_types::CallMethodRequest,
+};
+
+/// The number of keys after the current key that a security group has ready by default
+const DEFAULT_MAX_FUTURE_KEY_COUNT: usize = 2;
+
+/// The number of keys before the current key that a security group keeps by default
+const DEFAULT_MAX_PAST_KEY_COUNT: usize = 1;
+
+/// The result of the `GetSecurityKeys` method
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetSecurityKeysResult {
+    pub security_policy_uri: UAString,
+    /// The token id of the first key
+    pub first_token_id: u32,
+    /// The keys, one after the other by token id
+    pub keys: Vec<ByteString>,
+    /// The milliseconds until the first key is replaced by the next
+    pub time_to_next_key: f64,
+    /// The milliseconds that each key after the first is current for
+    pub key_lifetime: f64,
+}
+
+impl GetSecurityKeysResult {
+    /// Makes the keys that a publisher or subscriber secures messages with
+    pub fn security_keys(&self) -> Result<SecurityKeys, StatusCode> {
+        SecurityKeys::new(
+            self.security_policy_uri.as_ref(),
+            self.first_token_id,
+            &self.keys,
+            self.time_to_next_key,
+            self.key_lifetime,
+        )
+    }
+
+    #[cfg(feature = "server")]
+    fn output_arguments(&self) -> Vec<Variant> {
+        let keys: Vec<Variant> = self.keys.iter().cloned().map(Variant::from).collect();
+        vec![
+            Variant::from(self.security_policy_uri.clone()),
+            Variant::from(self.first_token_id),
+            Variant::from((VariantTypeId::ByteString, keys)),
+            Variant::from(self.time_to_next_key),
+            Variant::from(self.key_lifetime),
+        ]
+    }
+
+    #[cfg(feature = "client")]
+    fn from_output_arguments(output_arguments: &[Variant]) -> Result<Self, StatusCode> {
+        let [Variant::String(security_policy_uri), Variant::UInt32(first_token_id), keys, Variant::Double(time_to_next_key), Variant::Double(key_lifetime)] =
+            output_arguments
+        else {
+            error!("GetSecurityKeys returned unexpected output arguments");
+            return Err(StatusCode::BadUnexpectedError);
+        };
+        let keys = match keys {
+            Variant::Array(array) => array
+                .values
+                .iter()
+                .map(|v| match v {
+                    Variant::ByteString(v) => Ok(v.clone()),
+                    _ => Err(StatusCode::BadUnexpectedError),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Variant::Empty => Vec::new(),
+            _ => return Err(StatusCode::BadUnexpectedError),
+        };
+        Ok(GetSecurityKeysResult {
+            security_policy_uri: security_policy_uri.clone(),
+            first_token_id: *first_token_id,
+            keys,
+            time_to_next_key: *time_to_next_key,
+            key_lifetime: *key_lifetime,
+        })
+    }
+}
+
+/// A security group, i.e. the keys shared by the publishers and subscribers that secure their
+/// messages the same way. A new key becomes current after each key lifetime.
+#[derive(Debug)]
+pub struct SecurityGroup {
+    security_group_id: String,
+    policy: PubSubSecurityPolicy,
+    key_lifetime: f64,
+    max_future_key_count: usize,
+    max_past_key_count: usize,
+    keys: VecDeque<SecurityKey>,
+    current: usize,
+    current_since: Instant,
+}
+
+impl SecurityGroup {
+    /// Creates a security group whose keys are current for the key lifetime in milliseconds
+    pub fn new<T>(security_group_id: T, policy: PubSubSecurityPolicy, key_lifetime: f64) -> Self
+    where
+        T: Into<String>,
+    {
+        let mut security_group = SecurityGroup {
+            security_group_id: security_group_id.into(),
+            policy,
+            key_lifetime,
+            max_future_key_count: DEFAULT_MAX_FUTURE_KEY_COUNT,
+            max_past_key_count: DEFAULT_MAX_PAST_KEY_COUNT,
+            keys: VecDeque::from([SecurityKey::generate(policy, 1)]),
+            current: 0,
+            current_since: Instant::now(),
+        };
+        security_group.generate_future_keys();
+        security_group
+    }
+
+    /// Sets the number of keys after the current key that are handed out
+    pub fn max_future_key_count(mut self, max_future_key_count: usize) -> Self {
+        self.max_future_key_count = max_future_key_count;
+        self.generate_future_keys();
+        self
+    }
+
+    /// Sets the number of keys before the current key that are kept for subscribers that ask
+    /// for them
+    pub fn max_past_key_count(mut self, max_past_key_count: usize) -> Self {
+        self.max_past_key_count = max_past_key_count;
+        self
+    }
+
+    pub fn security_group_id(&self) -> &str {
+        &self.security_group_id
+    }
+
+    pub fn policy(&self) -> PubSubSecurityPolicy {
+        self.policy
+    }
+
+    /// Gets keys starting with the key with the token id, or with the current key when the token
+    /// id is 0 or unknown. Up to the requested number of keys are returned, or all of them when
+    /// the count is 0.
+    pub fn get_security_keys(
+        &mut self,
+        starting_token_id: u32,
+        requested_key_count: u32,
+        now: Instant,
+    ) -> GetSecurityKeysResult {
+        self.rotate(now);
+        let start = match starting_token_id {
+            0 => self.current,
+            token_id => self
+                .keys
+                .iter()
+                .position(|key| key.token_id() == token_id)
+                .unwrap_or(self.current),
+        };
+        let count = match requested_key_count as usize {
+            0 => self.keys.len() - start,
+            count => count.min(self.keys.len() - start),
+        };
+        let key_lifetime = duration_from_ms(self.key_lifetime);
+        let time_to_next_key = key_lifetime.saturating_sub(now.duration_since(self.current_since));
+        GetSecurityKeysResult {
+            security_policy_uri: UAString::from(self.policy.to_uri()),
+            first_token_id: self.keys[start].token_id(),
+            keys: self
+                .keys
+                .iter()
+                .skip(start)
+                .take(count)
+                .map(|key| key.to_byte_string())
+                .collect(),
+            time_to_next_key: time_to_next_key.as_secs_f64() * 1000f64,
+            key_lifetime: self.key_lifetime,
+        }
+    }
+
+    /// Makes the next key current for every key lifetime that has passed
+    fn rotate(&mut self, now: Instant) {
+        let key_lifetime = duration_from_ms(self.key_lifetime);
+        if key_lifetime.is_zero() {
+            return;
+        }
+        while now.duration_since(self.current_since) >= key_lifetime {
+            self.current_since += key_lifetime;
+            self.current += 1;
+            if self.current == self.keys.len() {
+                self.push_key();
+            }
+            debug!(
+                "Security group {} has a new current key {}",
+                self.security_group_id,
+                self.keys[self.current].token_id()
+            );
+        }
+        while self.current > self.max_past_key_count {
+            self.keys.pop_front();
+            self.current -= 1;
+        }
+        self.generate_future_keys();
+    }
+
+    fn generate_future_keys(&mut self) {
+        while self.keys.len() - self.current - 1 < self.max_future_key_count {
+            self.push_key();
+        }
+    }
+
+    fn push_key(&mut self) {
+        let token_id = next_token_id(self.keys.back().map_or(0, |key| key.token_id()), 1);
+        self.keys
+            .push_back(SecurityKey::generate(self.policy, token_id));
+    }
+}
+
+/// A security key service, which holds the security groups of a server
+#[derive(Debug, Default)]
+pub struct SecurityKeyService {
+    security_groups: BTreeMap<String, SecurityGroup>,
+}
+
+impl SecurityKeyService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a security group, replacing any with the same id
+    pub fn add_security_group(&mut self, security_group: SecurityGroup) {
+        self.security_groups
+            .insert(security_group.security_group_id.clone(), security_group);
+    }
+
+    pub fn find_security_group(&self, security_group_id: &str) -> Option<&SecurityGroup> {
+        self.security_groups.get(security_group_id)
+    }
+
+    /// Gets the keys of a security group, see `SecurityGroup::get_security_keys()`
+    pub fn get_security_keys(
+        &mut self,
+        security_group_id: &str,
+        starting_token_id: u32,
+        requested_key_count: u32,
+    ) -> Result<GetSecurityKeysResult, StatusCode> {
+        match self.security_groups.get_mut(security_group_id) {
+            Some(security_group) => Ok(security_group.get_security_keys(
+                starting_token_id,
+                requested_key_count,
+                Instant::now(),
+            )),
+            None => {
+                error!("Security group {} does not exist", security_group_id);
+                Err(StatusCode::BadNotFound)
+            }
+        }
+    }
+}
+
+/// This is the handler for the PublishSubscribe.GetSecurityKeys method call. Register it with
+/// `AddressSpace::register_method_handler()` and `MethodId::PublishSubscribe_GetSecurityKeys`.
+///
+/// Keys are only handed out over a session whose secure channel is encrypted.
+#[cfg(feature = "server")]
+pub struct GetSecurityKeysMethod {
+    security_key_service: Arc<RwLock<SecurityKeyService>>,
+}
+
+#[cfg(feature = "server")]
+impl GetSecurityKeysMethod {
+    pub fn new(security_key_service: Arc<RwLock<SecurityKeyService>>) -> Self {
+        GetSecurityKeysMethod {
+            security_key_service,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Method for GetSecurityKeysMethod {
+    fn call(
+        &mut self,
+        session_id: &NodeId,
+        session_manager: Arc<RwLock<SessionManager>>,
+        request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+        debug!("Method handler for GetSecurityKeys");
+
+        // OPC UA part 14 - GetSecurityKeys([in] String SecurityGroupId, [in] IntegerId StartingTokenId,
+        //   [in] UInt32 RequestedKeyCount, [out] String SecurityPolicyUri, [out] IntegerId FirstTokenId,
+        //   [out] ByteString[] Keys, [out] Duration TimeToNextKey, [out] Duration KeyLifetime);
+        //
+        // Return codes
+        //
+        // BadNotFound
+        // BadSecurityModeInsufficient
+        {
+            let session_manager = trace_read_lock!(session_manager);
+            let Some(session) = session_manager.find_session_by_id(session_id) else {
+                return Err(StatusCode::BadSessionIdInvalid);
+            };
+            let security_mode = trace_read_lock!(session).security_mode();
+            if security_mode != MessageSecurityMode::SignAndEncrypt {
+                debug!(
+                    "Method handler for GetSecurityKeys rejects security mode {:?}",
+                    security_mode
+                );
+                return Err(StatusCode::BadSecurityModeInsufficient);
+            }
+        }
+
+        let input_arguments = request.input_arguments.as_deref().unwrap_or_default();
+        let (security_group_id, starting_token_id, requested_key_count) = match input_arguments {
+            [Variant::String(security_group_id), Variant::UInt32(starting_token_id), Variant::UInt32(requested_key_count)] => {
+                (security_group_id, *starting_token_id, *requested_key_count)
+            }
+            [_, _, _] => return Err(StatusCode::BadInvalidArgument),
+            arguments if arguments.len() < 3 => return Err(StatusCode::BadArgumentsMissing),
+            _ => return Err(StatusCode::BadTooManyArguments),
+        };
+
+        let result = trace_write_lock!(self.security_key_service).get_security_keys(
+            security_group_id.as_ref(),
+            starting_token_id,
+            requested_key_count,
+        )?;
+        Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: Some(vec![StatusCode::Good; 3]),
+            input_argument_diagnostic_infos: None,
+            output_arguments: Some(result.output_arguments()),
+        })
+    }
+}
+
+/// Calls GetSecurityKeys on a security key service, e.g. to get the keys of a security group
+/// for a publisher or subscriber, or newer keys when `SecurityKeys::needs_keys()` says so. The
+/// session must be on an encrypted secure channel.
+#[cfg(feature = "client")]
+pub async fn get_security_keys(
+    session: &crate::client::Session,
+    security_group_id: &str,
+    starting_token_id: u32,
+    requested_key_count: u32,
+) -> Result<GetSecurityKeysResult, StatusCode> {
+    let object_id: NodeId = ObjectId::PublishSubscribe.into();
+    let method_id: NodeId = MethodId::PublishSubscribe_GetSecurityKeys.into();
+    let args = Some(vec![
+        Variant::from(security_group_id),
+        Variant::from(starting_token_id),
+        Variant::from(requested_key_count),
+    ]);
+    let result: CallMethodResult = session.call((object_id, method_id, args)).await?;
+    if result.status_code.is_bad() {
+        error!("GetSecurityKeys failed - {}", result.status_code);
+        return Err(result.status_code);
+    }
+    GetSecurityKeysResult::from_output_arguments(
+        result.output_arguments.as_deref().unwrap_or_default(),
+    )
+}
@@ -0,0 +1,545 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UADP subscriber. A subscriber listens to an `opc.udp://` address, usually a
+//! multicast group, and hands the data set messages it receives to the data set readers that
+//! match their publisher, writer group and data set writer. Each reader decodes the messages
+//! with the metadata of its data set and passes the fields to a callback.
+
+use std::{
+    collections::BTreeMap,
+    io::Cursor,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::{net::UdpSocket, time::timeout};
+
+use crate::sync::*;
+use crate::types::{status_code::StatusCode, *};
+
+use super::{
+    data_set_decoder::*,
+    data_set_message::DataSetMessage,
+    discovery::*,
+    json_message::{JsonDataSetMessage, JsonMessage},
+    network_message::*,
+    security::SecurityKeys,
+    udp::{self, MAX_DATAGRAM_SIZE},
+};
+
+#[cfg(feature = "pubsub-mqtt")]
+use super::mqtt::{self, MqttSettings};
+
+/// How long a subscriber waits for metadata it has asked for before asking again
+const META_DATA_REQUEST_INTERVAL_MS: u64 = 5000;
+
+/// How often the subscriber checks if it has aborted while it waits for messages
+const ABORT_POLL_INTERVAL_MS: u64 = 500;
+
+/// This trait is implemented by something that receives the fields of the data set messages read
+/// by a data set reader
+pub trait OnDataSet {
+    /// Called with the decoded fields of each data set message that the reader receives
+    fn on_data_set(&mut self, fields: DataSetFields, reader: &DataSetReader);
+}
+
+type DataSetFn = dyn FnMut(DataSetFields, &DataSetReader) + Send + Sync;
+
+/// A wrapper around a callback that implements [OnDataSet]
+pub struct DataSetCallback {
+    data_set: Box<DataSetFn>,
+}
+
+impl DataSetCallback {
+    /// Create a new data set callback wrapper.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_set` - Called with the fields of each received data set message.
+    pub fn new(
+        data_set: impl FnMut(DataSetFields, &DataSetReader) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            data_set: Box::new(data_set) as Box<DataSetFn>,
+        }
+    }
+}
+
+impl OnDataSet for DataSetCallback {
+    fn on_data_set(&mut self, fields: DataSetFields, reader: &DataSetReader) {
+        (self.data_set)(fields, reader);
+    }
+}
+
+/// A data set reader, which reads the data set messages of one data set writer
+#[derive(Debug, Clone)]
+pub struct DataSetReader {
+    pub name: UAString,
+    /// The publisher the messages must come from, or any publisher when it is not set
+    pub publisher_id: Option<PublisherId>,
+    /// The writer group the messages must come from, or any writer group when it is not set
+    pub writer_group_id: Option<u16>,
+    pub data_set_writer_id: u16,
+    /// The least security that the messages must have. Messages are secured with the keys
+    /// given to the subscriber with `Subscriber::set_security_keys()`.
+    pub security_mode: MessageSecurityMode,
+    decoder: Option<DataSetDecoder>,
+    /// Set when the reader has no metadata or the metadata is out of date
+    needs_meta_data: bool,
+    meta_data_requested: Option<Instant>,
+}
+
+impl DataSetReader {
+    /// Creates a reader of the data set writer with no metadata. The subscriber asks publishers
+    /// for the metadata with discovery requests.
+    pub fn new<T>(name: T, data_set_writer_id: u16) -> Self
+    where
+        T: Into<UAString>,
+    {
+        DataSetReader {
+            name: name.into(),
+            publisher_id: None,
+            writer_group_id: None,
+            data_set_writer_id,
+            security_mode: MessageSecurityMode::None,
+            decoder: None,
+            needs_meta_data: true,
+            meta_data_requested: None,
+        }
+    }
+
+    /// Only reads messages from the publisher
+    pub fn publisher_id(mut self, publisher_id: PublisherId) -> Self {
+        self.publisher_id = Some(publisher_id);
+        self
+    }
+
+    /// Only reads messages from the writer group
+    pub fn writer_group_id(mut self, writer_group_id: u16) -> Self {
+        self.writer_group_id = Some(writer_group_id);
+        self
+    }
+
+    /// Only reads messages that are signed, or signed and encrypted
+    pub fn security_mode(mut self, security_mode: MessageSecurityMode) -> Self {
+        self.security_mode = security_mode;
+        self
+    }
+
+    /// Sets the metadata of the data set, e.g. when it is configured rather than discovered
+    pub fn meta_data(mut self, meta_data: DataSetMetaDataType) -> Self {
+        self.set_meta_data(meta_data);
+        self
+    }
+
+    /// The decoder made from the metadata of the data set, if the reader has the metadata
+    pub fn decoder(&self) -> Option<&DataSetDecoder> {
+        self.decoder.as_ref()
+    }
+
+    /// Tests if the reader has no metadata, or metadata that doesn't match the messages it
+    /// receives
+    pub fn needs_meta_data(&self) -> bool {
+        self.needs_meta_data
+    }
+
+    fn set_meta_data(&mut self, meta_data: DataSetMetaDataType) {
+        match self.decoder {
+            Some(ref mut decoder) => decoder.set_meta_data(meta_data),
+            None => self.decoder = Some(DataSetDecoder::new(meta_data)),
+        }
+        self.needs_meta_data = false;
+        self.meta_data_requested = None;
+    }
+
+    /// Tests if a message from the publisher and writer group in the header, written by the
+    /// data set writer, is for this reader. The message must be secured at least as much as the
+    /// reader requires.
+    fn matches(&self, header: &NetworkMessageHeader, data_set_writer_id: u16) -> bool {
+        let security_mode = header
+            .security
+            .as_ref()
+            .map_or(MessageSecurityMode::None, |security| {
+                security.security_mode()
+            });
+        data_set_writer_id == self.data_set_writer_id
+            && security_mode as u32 >= self.security_mode as u32
+            && self
+                .publisher_id
+                .as_ref()
+                .is_none_or(|id| header.publisher_id.as_ref() == Some(id))
+            && self.writer_group_id.is_none_or(|id| {
+                header.group_header.as_ref().and_then(|v| v.writer_group_id) == Some(id)
+            })
+    }
+
+    /// Tests if a JSON message from the publisher, written by the data set writer, is for this
+    /// reader. JSON messages have the publisher id as a string.
+    fn matches_json(&self, publisher_id: Option<&UAString>, data_set_writer_id: u16) -> bool {
+        data_set_writer_id == self.data_set_writer_id
+            && self.publisher_id.as_ref().is_none_or(|id| {
+                publisher_id.is_some_and(|publisher_id| publisher_id.as_ref() == id.to_string())
+            })
+    }
+
+    /// Decodes a JSON data set message. Without metadata the fields are passed on as they were
+    /// received.
+    fn decode_json(&mut self, message: &JsonDataSetMessage) -> Result<DataSetFields, StatusCode> {
+        let result = match self.decoder {
+            Some(ref decoder) => decoder.decode_json(message),
+            None => Ok(DataSetFields::from(message.clone())),
+        };
+        if result == Err(StatusCode::BadConfigurationError) {
+            self.needs_meta_data = true;
+        }
+        result
+    }
+
+    /// Decodes a data set message. Messages that need other metadata fail with
+    /// `BadConfigurationError`.
+    fn decode(&mut self, message: &DataSetMessage) -> Result<DataSetFields, StatusCode> {
+        let result = match self.decoder {
+            Some(ref decoder) => decoder.decode(message),
+            None => Err(StatusCode::BadConfigurationError),
+        };
+        if result == Err(StatusCode::BadConfigurationError) {
+            self.needs_meta_data = true;
+        }
+        result
+    }
+
+    /// Tests if it is time to ask for the metadata of the reader, and notes that it has been
+    /// asked for
+    fn request_meta_data(&mut self, now: Instant) -> bool {
+        let due = self.needs_meta_data
+            && self.meta_data_requested.is_none_or(|requested| {
+                now.duration_since(requested)
+                    >= Duration::from_millis(META_DATA_REQUEST_INTERVAL_MS)
+            });
+        if due {
+            self.meta_data_requested = Some(now);
+        }
+        due
+    }
+}
+
+struct ReaderEntry {
+    reader: DataSetReader,
+    callback: Box<dyn OnDataSet + Send + Sync>,
+}
+
+/// A subscriber that receives the network messages sent to an `opc.udp://` address, or the JSON
+/// messages of an MQTT broker
+pub struct Subscriber {
+    /// The id the subscriber puts in the header of its discovery requests
+    subscriber_id: PublisherId,
+    url: String,
+    readers: Vec<ReaderEntry>,
+    /// The keys of each security group, by the security group id
+    security_keys: BTreeMap<String, SecurityKeys>,
+    #[cfg(feature = "pubsub-mqtt")]
+    mqtt_settings: MqttSettings,
+    abort: bool,
+}
+
+impl Subscriber {
+    /// Creates a subscriber that listens to the url
+    pub fn new<T>(subscriber_id: PublisherId, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Subscriber {
+            subscriber_id,
+            url: url.into(),
+            readers: Vec::new(),
+            security_keys: BTreeMap::new(),
+            #[cfg(feature = "pubsub-mqtt")]
+            mqtt_settings: MqttSettings::default(),
+            abort: false,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sets the settings used when the url is an `mqtt://` or `mqtts://` broker
+    #[cfg(feature = "pubsub-mqtt")]
+    pub fn set_mqtt_settings(&mut self, mqtt_settings: MqttSettings) {
+        self.mqtt_settings = mqtt_settings;
+    }
+
+    #[cfg(feature = "pubsub-mqtt")]
+    pub fn mqtt_settings(&self) -> &MqttSettings {
+        &self.mqtt_settings
+    }
+
+    /// Sets the keys of a security group, e.g. from a call to GetSecurityKeys. Secured messages
+    /// are decoded with the keys of any security group.
+    pub fn set_security_keys<T>(&mut self, security_group_id: T, security_keys: SecurityKeys)
+    where
+        T: Into<String>,
+    {
+        self.security_keys
+            .insert(security_group_id.into(), security_keys);
+    }
+
+    pub fn security_keys(&self, security_group_id: &str) -> Option<&SecurityKeys> {
+        self.security_keys.get(security_group_id)
+    }
+
+    pub fn security_keys_mut(&mut self, security_group_id: &str) -> Option<&mut SecurityKeys> {
+        self.security_keys.get_mut(security_group_id)
+    }
+
+    /// Adds a data set reader and the callback that receives the fields it reads
+    pub fn add_data_set_reader<CB>(&mut self, reader: DataSetReader, callback: CB)
+    where
+        CB: OnDataSet + Send + Sync + 'static,
+    {
+        self.readers.push(ReaderEntry {
+            reader,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// Finds a data set reader by the id of the data set writer it reads
+    pub fn find_data_set_reader(&self, data_set_writer_id: u16) -> Option<&DataSetReader> {
+        self.readers
+            .iter()
+            .map(|entry| &entry.reader)
+            .find(|reader| reader.data_set_writer_id == data_set_writer_id)
+    }
+
+    /// Decodes a network message that was received. A secured message is checked and decrypted
+    /// with the keys of the security group that has its token id, and fails with
+    /// `BadSecurityChecksFailed` if there are none.
+    pub fn decode(
+        &self,
+        data: &[u8],
+        decoding_options: &DecodingOptions,
+    ) -> Result<NetworkMessage, StatusCode> {
+        let (header, _) = NetworkMessageHeader::decode(&mut Cursor::new(data), decoding_options)?;
+        let Some(security) = header.security else {
+            return NetworkMessage::decode(&mut Cursor::new(data), decoding_options);
+        };
+        // Token ids of different security groups may be the same, so each group that has the
+        // token id is tried
+        self.security_keys
+            .values()
+            .filter(|keys| keys.find_key(security.security_token_id).is_some())
+            .find_map(|keys| keys.decode(data, decoding_options).ok())
+            .ok_or_else(|| {
+                debug!(
+                    "No security keys can decode a message secured with key {}",
+                    security.security_token_id
+                );
+                StatusCode::BadSecurityChecksFailed
+            })
+    }
+
+    /// Handles a network message that was received. The data set messages in it are decoded by
+    /// the readers that match them and passed to their callbacks, and metadata announced by
+    /// publishers is given to the readers of that data set.
+    ///
+    /// The result is the discovery requests to send for readers that need metadata.
+    pub fn handle_message(&mut self, message: &NetworkMessage) -> Vec<NetworkMessage> {
+        match message.payload {
+            NetworkMessagePayload::DataSetMessages(ref messages) => {
+                let Some(ref data_set_writer_ids) = message.header.data_set_writer_ids else {
+                    debug!("Ignoring data set messages without data set writer ids");
+                    return Vec::new();
+                };
+                for (data_set_writer_id, data_set_message) in
+                    data_set_writer_ids.iter().zip(messages)
+                {
+                    for entry in self
+                        .readers
+                        .iter_mut()
+                        .filter(|entry| entry.reader.matches(&message.header, *data_set_writer_id))
+                    {
+                        match entry.reader.decode(data_set_message) {
+                            Ok(fields) => entry.callback.on_data_set(fields, &entry.reader),
+                            Err(err) => debug!(
+                                "Data set reader {} cannot decode a message - {}",
+                                entry.reader.name, err
+                            ),
+                        }
+                    }
+                }
+                self.meta_data_requests(Instant::now())
+            }
+            NetworkMessagePayload::DiscoveryResponse(ref response) => {
+                if let Announcement::DataSetMetaData {
+                    data_set_writer_id,
+                    ref meta_data,
+                    status_code,
+                } = response.announcement
+                {
+                    if status_code.is_good() {
+                        self.readers
+                            .iter_mut()
+                            .filter(|entry| {
+                                entry.reader.matches(&message.header, data_set_writer_id)
+                            })
+                            .for_each(|entry| {
+                                debug!("Data set reader {} has new metadata", entry.reader.name);
+                                entry.reader.set_meta_data(meta_data.clone());
+                            });
+                    }
+                }
+                Vec::new()
+            }
+            // Requests are for publishers
+            NetworkMessagePayload::DiscoveryRequest(_) => Vec::new(),
+        }
+    }
+
+    /// Handles a JSON message that was received from a broker. Data set messages are decoded by
+    /// the readers of their publisher and data set writer and passed to their callbacks. Because
+    /// JSON fields are named, a reader without metadata passes the fields as they were received.
+    /// Metadata messages are given to the readers of that data set.
+    ///
+    /// JSON messages name their writer group rather than giving its id, so the writer group of a
+    /// reader is not compared with them.
+    pub fn handle_json_message(&mut self, message: &JsonMessage) {
+        match message {
+            JsonMessage::Data(message) => {
+                for data_set_message in message.messages.iter() {
+                    for entry in self.readers.iter_mut().filter(|entry| {
+                        entry.reader.matches_json(
+                            message.publisher_id.as_ref(),
+                            data_set_message.data_set_writer_id,
+                        )
+                    }) {
+                        match entry.reader.decode_json(data_set_message) {
+                            Ok(fields) => entry.callback.on_data_set(fields, &entry.reader),
+                            Err(err) => debug!(
+                                "Data set reader {} cannot decode a message - {}",
+                                entry.reader.name, err
+                            ),
+                        }
+                    }
+                }
+            }
+            JsonMessage::MetaData(message) => self
+                .readers
+                .iter_mut()
+                .filter(|entry| {
+                    entry
+                        .reader
+                        .matches_json(Some(&message.publisher_id), message.data_set_writer_id)
+                })
+                .for_each(|entry| {
+                    debug!("Data set reader {} has new metadata", entry.reader.name);
+                    entry.reader.set_meta_data(message.meta_data.clone());
+                }),
+        }
+    }
+
+    /// Makes a discovery request for the metadata of the readers that need it, unless it was
+    /// asked for recently
+    pub fn meta_data_requests(&mut self, now: Instant) -> Vec<NetworkMessage> {
+        let data_set_writer_ids = self
+            .readers
+            .iter_mut()
+            .filter_map(|entry| {
+                let reader = &mut entry.reader;
+                reader
+                    .request_meta_data(now)
+                    .then_some(reader.data_set_writer_id)
+            })
+            .collect::<Vec<_>>();
+        if data_set_writer_ids.is_empty() {
+            Vec::new()
+        } else {
+            vec![NetworkMessage::discovery_request(
+                NetworkMessageHeader::new(self.subscriber_id.clone()),
+                DiscoveryRequest::new(InformationType::DataSetMetaData, &data_set_writer_ids),
+            )]
+        }
+    }
+
+    /// Tells the subscriber to stop
+    pub fn abort(&mut self) {
+        info!("Subscriber has been told to abort");
+        self.abort = true;
+    }
+
+    pub fn is_abort(&self) -> bool {
+        self.abort
+    }
+
+    /// Runs the subscriber until it is aborted, receiving the messages sent to the url and
+    /// sending discovery requests to it when readers need metadata
+    ///
+    /// When the url is an `mqtt://` or `mqtts://` broker, JSON messages are received from it
+    /// instead.
+    pub async fn run(subscriber: Arc<RwLock<Subscriber>>) -> Result<(), StatusCode> {
+        #[cfg(feature = "pubsub-mqtt")]
+        if mqtt::is_mqtt_url(trace_read_lock!(subscriber).url()) {
+            return mqtt::run_subscriber(subscriber).await;
+        }
+        let address = {
+            let subscriber = trace_read_lock!(subscriber);
+            udp::udp_address(&subscriber.url)?
+        };
+        let receiver = udp::bind_receiver(&address)?;
+        let sender = udp::bind_sender(&address).await?;
+        info!("Subscriber is listening to {}", address);
+
+        let decoding_options = DecodingOptions::default();
+        let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+        loop {
+            let requests = {
+                let mut subscriber = trace_write_lock!(subscriber);
+                if subscriber.is_abort() {
+                    break;
+                }
+                subscriber.meta_data_requests(Instant::now())
+            };
+            Self::send(&sender, &address, requests).await;
+
+            let received = timeout(
+                Duration::from_millis(ABORT_POLL_INTERVAL_MS),
+                receiver.recv_from(&mut buffer),
+            )
+            .await;
+            let size = match received {
+                Ok(Ok((size, _))) => size,
+                Ok(Err(err)) => {
+                    error!("Cannot receive from {} - {}", address, err);
+                    return Err(StatusCode::BadCommunicationError);
+                }
+                // Timed out, check for abort again
+                Err(_) => continue,
+            };
+            let message =
+                match trace_read_lock!(subscriber).decode(&buffer[..size], &decoding_options) {
+                    Ok(message) => message,
+                    Err(err) => {
+                        debug!("Ignoring a datagram that cannot be decoded - {}", err);
+                        continue;
+                    }
+                };
+            let requests = trace_write_lock!(subscriber).handle_message(&message);
+            Self::send(&sender, &address, requests).await;
+        }
+        info!("Subscriber has stopped");
+        Ok(())
+    }
+
+    async fn send(sender: &UdpSocket, address: &SocketAddr, messages: Vec<NetworkMessage>) {
+        for message in messages {
+            let mut stream = Cursor::new(Vec::with_capacity(message.byte_len()));
+            if let Err(err) = message.encode(&mut stream) {
+                error!("Cannot encode a network message - {}", err);
+            } else if let Err(err) = sender.send_to(&stream.into_inner(), address).await {
+                error!("Cannot send a network message to {} - {}", address, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::Cursor;
+
+use crate::pubsub::prelude::*;
+
+fn field(name: &str, built_in_type: DataTypeId, value_rank: i32) -> FieldMetaData {
+    FieldMetaData {
+        name: name.into(),
+        description: LocalizedText::null(),
+        field_flags: DataSetFieldFlags::empty(),
+        built_in_type: built_in_type as u8,
+        data_type: built_in_type.into(),
+        value_rank,
+        array_dimensions: None,
+        max_string_length: 0,
+        data_set_field_id: Guid::new(),
+        properties: None,
+    }
+}
+
+fn meta_data() -> DataSetMetaDataType {
+    DataSetMetaDataType {
+        namespaces: None,
+        structure_data_types: None,
+        enum_data_types: None,
+        simple_data_types: None,
+        name: "Sensors".into(),
+        description: LocalizedText::null(),
+        fields: Some(vec![
+            field("Temperature", DataTypeId::Double, -1),
+            field("Name", DataTypeId::String, -1),
+            field("Counts", DataTypeId::UInt16, 1),
+            field("Anything", DataTypeId::BaseDataType, -2),
+        ]),
+        data_set_class_id: Guid::null(),
+        configuration_version: ConfigurationVersionDataType {
+            major_version: 10,
+            minor_version: 20,
+        },
+    }
+}
+
+fn message(field_encoding: FieldEncoding, payload: DataSetMessagePayload) -> DataSetMessage {
+    let mut message = DataSetMessage::new(field_encoding, payload);
+    message.sequence_number = Some(5);
+    message.major_version = Some(10);
+    message.minor_version = Some(20);
+    message
+}
+
+fn values() -> Vec<DataValue> {
+    vec![
+        DataValue::value_only(21.5f64),
+        DataValue::value_only("Sensor 1"),
+        DataValue::value_only(vec![1u16, 2u16]),
+        DataValue::value_only(true),
+    ]
+}
+
+/// Sends the message through an encode and decode, like a subscriber receiving it
+fn receive(message: &DataSetMessage) -> DataSetMessage {
+    DataSetMessage::decode(
+        &mut Cursor::new(message.encode_to_vec()),
+        &DecodingOptions::test(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn decode_key_frame() {
+    let decoder = DataSetDecoder::new(meta_data());
+    let message = receive(&message(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(values()),
+    ));
+    let fields = decoder.decode(&message).unwrap();
+    assert!(fields.key_frame);
+    assert_eq!(fields.sequence_number, Some(5));
+    assert_eq!(fields.fields.len(), 4);
+    assert_eq!(
+        fields.get("Temperature").unwrap().value,
+        Some(Variant::from(21.5f64))
+    );
+    assert_eq!(
+        fields.get("Name").unwrap().value,
+        Some(Variant::from("Sensor 1"))
+    );
+    assert_eq!(
+        fields.get("Counts").unwrap().value,
+        Some(Variant::from(vec![1u16, 2u16]))
+    );
+    assert_eq!(
+        fields.get("Anything").unwrap().value,
+        Some(Variant::from(true))
+    );
+}
+
+#[test]
+fn decode_raw_key_frame() {
+    let decoder = DataSetDecoder::new(meta_data());
+    let mut values = values();
+    // A raw data field whose type is not a built in type has to be written as a variant
+    values[3] = DataValue::value_only(Variant::Variant(Box::new(Variant::from(true))));
+    let message = receive(&message(
+        FieldEncoding::RawData,
+        DataSetMessagePayload::KeyFrame(values),
+    ));
+    assert!(matches!(
+        message.payload,
+        DataSetMessagePayload::RawKeyFrame { field_count: 4, .. }
+    ));
+    let fields = decoder.decode(&message).unwrap();
+    assert_eq!(
+        fields.get("Temperature").unwrap().value,
+        Some(Variant::from(21.5f64))
+    );
+    assert_eq!(
+        fields.get("Counts").unwrap().value,
+        Some(Variant::from(vec![1u16, 2u16]))
+    );
+    assert_eq!(
+        fields.get("Anything").unwrap().value,
+        Some(Variant::from(true))
+    );
+
+    // Raw data that is too short for the fields
+    let message = DataSetMessage {
+        payload: DataSetMessagePayload::RawKeyFrame {
+            field_count: 4,
+            data: vec![0, 1, 2],
+        },
+        ..message
+    };
+    assert_eq!(
+        decoder.decode(&message).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn decode_delta_frame_and_keep_alive() {
+    let decoder = DataSetDecoder::new(meta_data());
+    let message = message(
+        FieldEncoding::DataValue,
+        DataSetMessagePayload::DeltaFrame(vec![(1, DataValue::value_only("Sensor 2"))]),
+    );
+    let fields = decoder.decode(&receive(&message)).unwrap();
+    assert!(!fields.key_frame);
+    assert_eq!(fields.fields.len(), 1);
+    assert_eq!(
+        fields.get("Name").unwrap().value,
+        Some(Variant::from("Sensor 2"))
+    );
+
+    // An index beyond the fields of the data set
+    let message = DataSetMessage {
+        payload: DataSetMessagePayload::DeltaFrame(vec![(4, DataValue::value_only(1i32))]),
+        ..message
+    };
+    assert_eq!(
+        decoder.decode(&message).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    let message = DataSetMessage {
+        payload: DataSetMessagePayload::KeepAlive,
+        ..message
+    };
+    let fields = decoder.decode(&message).unwrap();
+    assert!(!fields.key_frame);
+    assert!(fields.fields.is_empty());
+}
+
+#[test]
+fn decode_converts_types() {
+    let decoder = DataSetDecoder::new(meta_data());
+    let message = message(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(vec![
+            // Converted implicitly
+            DataValue::value_only(21i32),
+            // No implicit conversion
+            DataValue::value_only(Guid::null()),
+            // Scalar for an array
+            DataValue::value_only(1u16),
+            // Null values are left alone
+            DataValue::null(),
+        ]),
+    );
+    let fields = decoder.decode(&message).unwrap();
+    assert_eq!(
+        fields.get("Temperature").unwrap().value,
+        Some(Variant::from(21f64))
+    );
+    let name = fields.get("Name").unwrap();
+    assert_eq!(name.value, None);
+    assert_eq!(name.status, Some(StatusCode::BadTypeMismatch));
+    assert_eq!(
+        fields.get("Counts").unwrap().status,
+        Some(StatusCode::BadTypeMismatch)
+    );
+    assert_eq!(fields.get("Anything").unwrap(), &DataValue::null());
+}
+
+#[test]
+fn decode_version_mismatch() {
+    let mut decoder = DataSetDecoder::new(meta_data());
+
+    // A different major version needs new metadata
+    let mut message = message(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(values()),
+    );
+    message.major_version = Some(11);
+    assert_eq!(
+        decoder.decode(&message).unwrap_err(),
+        StatusCode::BadConfigurationError
+    );
+    let mut meta_data = meta_data();
+    meta_data.configuration_version.major_version = 11;
+    decoder.set_meta_data(meta_data);
+    assert!(decoder.decode(&message).is_ok());
+
+    // The wrong number of fields for the same version
+    let mut short_message = message.clone();
+    short_message.payload = DataSetMessagePayload::KeyFrame(values()[..3].to_vec());
+    assert_eq!(
+        decoder.decode(&short_message).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    // A newer minor version can have fields that aren't in the metadata yet
+    let mut values = values();
+    values.push(DataValue::value_only(1i32));
+    message.minor_version = Some(21);
+    message.payload = DataSetMessagePayload::KeyFrame(values);
+    let fields = decoder.decode(&message).unwrap();
+    assert_eq!(fields.fields.len(), 4);
+    message.payload = DataSetMessagePayload::DeltaFrame(vec![(4, DataValue::value_only(1i32))]);
+    assert!(decoder.decode(&message).unwrap().fields.is_empty());
+}
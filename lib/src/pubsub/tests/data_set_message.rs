@@ -0,0 +1,162 @@
+use std::io::Cursor;
+
+use crate::pubsub::prelude::*;
+
+use super::*;
+
+fn header(data_set_writer_ids: &[u16]) -> NetworkMessageHeader {
+    let mut header = NetworkMessageHeader::new(PublisherId::UInt16(10));
+    header.group_header = Some(GroupHeader {
+        writer_group_id: Some(1),
+        group_version: None,
+        network_message_number: Some(1),
+        sequence_number: Some(20),
+    });
+    header.data_set_writer_ids = Some(data_set_writer_ids.to_vec());
+    header
+}
+
+#[test]
+fn field_encoding_from_content_mask() {
+    assert_eq!(
+        FieldEncoding::from(DataSetFieldContentMask::None),
+        FieldEncoding::Variant
+    );
+    assert_eq!(
+        FieldEncoding::from(DataSetFieldContentMask::RawData),
+        FieldEncoding::RawData
+    );
+    assert_eq!(
+        FieldEncoding::from(
+            DataSetFieldContentMask::StatusCode | DataSetFieldContentMask::SourceTimestamp
+        ),
+        FieldEncoding::DataValue
+    );
+}
+
+#[test]
+fn key_frame() {
+    let values = vec![
+        DataValue::value_only(1i32),
+        DataValue::value_only("Hello"),
+        DataValue::value_only(vec![1.5f64, 2.5f64]),
+    ];
+    serialize_test(DataSetMessage::new(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(values.clone()),
+    ));
+
+    let mut message = DataSetMessage::new(
+        FieldEncoding::DataValue,
+        DataSetMessagePayload::KeyFrame(
+            values
+                .into_iter()
+                .map(|v| DataValue {
+                    status: Some(StatusCode::Good),
+                    source_timestamp: Some(DateTime::ymd_hms(2024, 1, 2, 3, 4, 5)),
+                    ..v
+                })
+                .collect(),
+        ),
+    );
+    message.sequence_number = Some(100);
+    message.timestamp = Some(DateTime::ymd_hms(2024, 1, 2, 3, 4, 5));
+    message.pico_seconds = Some(7);
+    message.status = Some(StatusCode::UncertainSubstituteValue);
+    message.major_version = Some(1000);
+    message.minor_version = Some(1001);
+    serialize_test(message);
+}
+
+#[test]
+fn delta_frame_and_keep_alive() {
+    let mut message = DataSetMessage::new(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::DeltaFrame(vec![
+            (0, DataValue::value_only(true)),
+            (3, DataValue::value_only(3u64)),
+        ]),
+    );
+    message.sequence_number = Some(u16::MAX);
+    serialize_test(message);
+
+    let mut message = DataSetMessage::new(FieldEncoding::Variant, DataSetMessagePayload::KeepAlive);
+    message.sequence_number = Some(2);
+    serialize_test(message);
+}
+
+#[test]
+fn raw_key_frame() {
+    // Raw data fields have no type information so they are decoded as bytes
+    let message = DataSetMessage::new(
+        FieldEncoding::RawData,
+        DataSetMessagePayload::KeyFrame(vec![
+            DataValue::value_only(1i32),
+            DataValue::value_only(vec![2u16, 3u16]),
+        ]),
+    );
+    let decoded = serialize_encode_and_decode(&message);
+    assert_eq!(decoded.field_encoding, FieldEncoding::RawData);
+    assert_eq!(
+        decoded.payload,
+        DataSetMessagePayload::RawKeyFrame {
+            field_count: 2,
+            data: vec![1, 0, 0, 0, 2, 0, 0, 0, 2, 0, 3, 0],
+        }
+    );
+    // The raw bytes encode to the same message
+    assert_eq!(decoded.encode_to_vec(), message.encode_to_vec());
+}
+
+#[test]
+fn raw_data_field_is_not_decoded_in_delta_frame() {
+    let message = DataSetMessage::new(
+        FieldEncoding::RawData,
+        DataSetMessagePayload::DeltaFrame(vec![(0, DataValue::value_only(1i32))]),
+    );
+    let data = message.encode_to_vec();
+    assert_eq!(
+        DataSetMessage::decode(&mut Cursor::new(data), &DecodingOptions::test()).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn network_message_with_data_set_messages() {
+    let first = DataSetMessage::new(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(vec![DataValue::value_only(1i32)]),
+    );
+    let second = DataSetMessage::new(FieldEncoding::Variant, DataSetMessagePayload::KeepAlive);
+
+    // One message, with and without writer ids in the header
+    serialize_test(NetworkMessage::data_set_messages(
+        header(&[1]),
+        vec![first.clone()],
+    ));
+    serialize_test(NetworkMessage::data_set_messages(
+        NetworkMessageHeader::new(PublisherId::Byte(1)),
+        vec![first.clone()],
+    ));
+
+    // Several messages are preceded by their sizes
+    let message = NetworkMessage::data_set_messages(header(&[1, 2]), vec![first, second]);
+    serialize_test(message.clone());
+
+    // The number of messages has to match the number of writer ids
+    let message = NetworkMessage {
+        header: header(&[1]),
+        ..message
+    };
+    let mut stream = Cursor::new(Vec::new());
+    assert_eq!(
+        message.encode(&mut stream).unwrap_err(),
+        StatusCode::BadEncodingError
+    );
+}
+
+fn serialize_encode_and_decode(message: &DataSetMessage) -> DataSetMessage {
+    let data = message.encode_to_vec();
+    assert_eq!(data.len(), message.byte_len());
+    DataSetMessage::decode(&mut Cursor::new(data), &DecodingOptions::test()).unwrap()
+}
@@ -0,0 +1,317 @@
+use std::io::Cursor;
+
+use crate::pubsub::prelude::*;
+
+use super::*;
+
+fn meta_data(name: &str) -> DataSetMetaDataType {
+    DataSetMetaDataType {
+        namespaces: None,
+        structure_data_types: None,
+        enum_data_types: None,
+        simple_data_types: None,
+        name: name.into(),
+        description: LocalizedText {
+            locale: UAString::null(),
+            text: name.into(),
+        },
+        fields: Some(vec![FieldMetaData {
+            name: "Temperature".into(),
+            description: LocalizedText::null(),
+            field_flags: DataSetFieldFlags::empty(),
+            built_in_type: DataTypeId::Double as u8,
+            data_type: DataTypeId::Double.into(),
+            value_rank: -1,
+            array_dimensions: None,
+            max_string_length: 0,
+            data_set_field_id: Guid::new(),
+            properties: None,
+        }]),
+        data_set_class_id: Guid::null(),
+        configuration_version: ConfigurationVersionDataType {
+            major_version: 1,
+            minor_version: 2,
+        },
+    }
+}
+
+fn data_set_writer(data_set_writer_id: u16) -> DataSetWriterDataType {
+    DataSetWriterDataType {
+        name: format!("Writer{}", data_set_writer_id).into(),
+        enabled: true,
+        data_set_writer_id,
+        data_set_field_content_mask: DataSetFieldContentMask::empty(),
+        key_frame_count: 1,
+        data_set_name: "DataSet".into(),
+        data_set_writer_properties: None,
+        transport_settings: ExtensionObject::null(),
+        message_settings: ExtensionObject::null(),
+    }
+}
+
+fn writer_group(writer_group_id: u16, data_set_writer_ids: &[u16]) -> WriterGroupDataType {
+    WriterGroupDataType {
+        name: format!("Group{}", writer_group_id).into(),
+        enabled: true,
+        security_mode: MessageSecurityMode::None,
+        security_group_id: UAString::null(),
+        security_key_services: None,
+        max_network_message_size: 1400,
+        group_properties: None,
+        writer_group_id,
+        publishing_interval: 1000f64,
+        keep_alive_time: 5000f64,
+        priority: 0,
+        locale_ids: None,
+        header_layout_uri: UAString::null(),
+        transport_settings: ExtensionObject::null(),
+        message_settings: ExtensionObject::null(),
+        data_set_writers: Some(
+            data_set_writer_ids
+                .iter()
+                .map(|id| data_set_writer(*id))
+                .collect(),
+        ),
+    }
+}
+
+fn publisher() -> PublisherInformation {
+    let mut publisher = PublisherInformation::default();
+    publisher.endpoints = vec![EndpointDescription::from("opc.tcp://localhost:4855/")];
+    publisher.meta_data.insert(1, meta_data("DataSet1"));
+    publisher.meta_data.insert(2, meta_data("DataSet2"));
+    publisher.writer_groups = vec![writer_group(10, &[1, 2]), writer_group(20, &[3])];
+    publisher
+}
+
+#[test]
+fn discovery_request_encoding() {
+    let message = NetworkMessage::discovery_request(
+        NetworkMessageHeader::new(PublisherId::UInt16(0x1234)),
+        DiscoveryRequest::new(InformationType::DataSetMetaData, &[5, 6]),
+    );
+    let mut stream = Cursor::new(Vec::new());
+    message.encode(&mut stream).unwrap();
+    assert_eq!(
+        stream.into_inner(),
+        vec![
+            // Flags with the version, publisher id and extended flags 1
+            0x91, // Extended flags 1 with a UInt16 publisher id and extended flags 2
+            0x81, // Extended flags 2 with the discovery request type
+            0x04, // Publisher id
+            0x34, 0x12, // Request type and information type
+            0x01, 0x02, // Data set writer ids
+            0x02, 0x00, 0x00, 0x00, 0x05, 0x00, 0x06, 0x00,
+        ]
+    );
+    serialize_test(message);
+}
+
+#[test]
+fn network_message_header() {
+    // A header with every optional field
+    let header = NetworkMessageHeader {
+        publisher_id: Some(PublisherId::String("Publisher".into())),
+        data_set_class_id: Some(Guid::new()),
+        group_header: Some(GroupHeader {
+            writer_group_id: Some(10),
+            group_version: Some(1),
+            network_message_number: Some(1),
+            sequence_number: Some(100),
+        }),
+        data_set_writer_ids: None,
+        timestamp: Some(DateTime::now()),
+        pico_seconds: Some(500),
+        security: None,
+    };
+    serialize_test(NetworkMessage::discovery_request(
+        header,
+        DiscoveryRequest::new(InformationType::PublisherEndpoints, &[]),
+    ));
+
+    [
+        PublisherId::Byte(1),
+        PublisherId::UInt16(2),
+        PublisherId::UInt32(3),
+        PublisherId::UInt64(4),
+    ]
+    .into_iter()
+    .for_each(|publisher_id| {
+        serialize_test(NetworkMessage::discovery_request(
+            NetworkMessageHeader::new(publisher_id),
+            DiscoveryRequest {
+                information_type: InformationType::DataSetWriterConfiguration,
+                data_set_writer_ids: None,
+            },
+        ));
+    });
+}
+
+#[test]
+fn network_message_unsupported() {
+    let decoding_options = DecodingOptions::test();
+    let decode = |bytes: &[u8]| NetworkMessage::decode(&mut Cursor::new(bytes), &decoding_options);
+    // Another version of UADP
+    assert_eq!(
+        decode(&[0x92, 0x81, 0x04, 0x34, 0x12]).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+    // Security, which needs the keys to decode
+    assert_eq!(
+        decode(&[0x91, 0x91, 0x04, 0x34, 0x12, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00]).unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+    // Chunks
+    assert_eq!(
+        decode(&[0x91, 0x81, 0x05, 0x34, 0x12]).unwrap_err(),
+        StatusCode::BadNotSupported
+    );
+    // An unknown discovery request type
+    assert_eq!(
+        decode(&[0x91, 0x81, 0x04, 0x34, 0x12, 0x02, 0x01]).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn discovery_responses() {
+    let mut publisher = publisher();
+    let header = NetworkMessageHeader::new(PublisherId::UInt32(100));
+    [
+        InformationType::PublisherEndpoints,
+        InformationType::DataSetMetaData,
+        InformationType::DataSetWriterConfiguration,
+    ]
+    .into_iter()
+    .for_each(|information_type| {
+        let request = DiscoveryRequest::new(information_type, &[1, 3, 4]);
+        let responses = publisher.respond(&request);
+        assert!(!responses.is_empty());
+        responses.into_iter().for_each(|response| {
+            assert_eq!(response.announcement.information_type(), information_type);
+            serialize_test(NetworkMessage::discovery_response(header.clone(), response));
+        });
+    });
+}
+
+#[test]
+fn publisher_responds() {
+    let mut publisher = publisher();
+
+    // Endpoints
+    let responses = publisher.respond(&DiscoveryRequest::new(
+        InformationType::PublisherEndpoints,
+        &[],
+    ));
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].sequence_number, 1);
+
+    // Metadata of the requested writers, with bad status for ones that are not found
+    let responses = publisher.respond(&DiscoveryRequest::new(
+        InformationType::DataSetMetaData,
+        &[2, 9],
+    ));
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].sequence_number, 2);
+    assert_eq!(responses[1].sequence_number, 3);
+    let Announcement::DataSetMetaData {
+        data_set_writer_id,
+        ref meta_data,
+        status_code,
+    } = responses[0].announcement
+    else {
+        panic!("Expected data set metadata");
+    };
+    assert_eq!(data_set_writer_id, 2);
+    assert_eq!(meta_data.name.as_ref(), "DataSet2");
+    assert_eq!(status_code, StatusCode::Good);
+    let Announcement::DataSetMetaData {
+        data_set_writer_id,
+        status_code,
+        ..
+    } = responses[1].announcement
+    else {
+        panic!("Expected data set metadata");
+    };
+    assert_eq!(data_set_writer_id, 9);
+    assert_eq!(status_code, StatusCode::BadNotFound);
+
+    // All metadata
+    let responses = publisher.respond(&DiscoveryRequest::new(
+        InformationType::DataSetMetaData,
+        &[],
+    ));
+    assert_eq!(responses.len(), 2);
+
+    // The writer groups only hold the requested writers
+    let responses = publisher.respond(&DiscoveryRequest::new(
+        InformationType::DataSetWriterConfiguration,
+        &[2, 3, 9],
+    ));
+    assert_eq!(responses.len(), 3);
+    let writers = responses
+        .iter()
+        .map(|response| match response.announcement {
+            Announcement::DataSetWriterConfiguration {
+                ref data_set_writer_ids,
+                ref writer_group,
+                ref status_codes,
+            } => (
+                writer_group.writer_group_id,
+                data_set_writer_ids.clone().unwrap(),
+                status_codes.clone().unwrap(),
+            ),
+            _ => panic!("Expected data set writer configuration"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        writers,
+        vec![
+            (10, vec![2], vec![StatusCode::Good]),
+            (20, vec![3], vec![StatusCode::Good]),
+            (0, vec![9], vec![StatusCode::BadNotFound]),
+        ]
+    );
+}
+
+#[test]
+fn subscriber_bootstraps_from_responses() {
+    let mut publisher = publisher();
+    let mut subscriber = PublisherInformation::default();
+    [
+        InformationType::PublisherEndpoints,
+        InformationType::DataSetMetaData,
+        InformationType::DataSetWriterConfiguration,
+    ]
+    .into_iter()
+    .for_each(|information_type| {
+        publisher
+            .respond(&DiscoveryRequest::new(information_type, &[1, 2, 3, 9]))
+            .iter()
+            .for_each(|response| subscriber.update(response));
+    });
+    assert_eq!(subscriber.endpoints, publisher.endpoints);
+    assert_eq!(subscriber.meta_data, publisher.meta_data);
+    assert_eq!(subscriber.writer_groups, publisher.writer_groups);
+
+    let (group, writer) = subscriber.find_data_set_writer(3).unwrap();
+    assert_eq!(group.writer_group_id, 20);
+    assert_eq!(writer.data_set_writer_id, 3);
+    assert!(subscriber.find_data_set_writer(9).is_none());
+
+    // An announcement that a writer has moved to another group
+    let mut moved = writer_group(20, &[2]);
+    moved.name = "Moved".into();
+    let response = publisher.announce(Announcement::DataSetWriterConfiguration {
+        data_set_writer_ids: Some(vec![2]),
+        writer_group: moved,
+        status_codes: Some(vec![StatusCode::Good]),
+    });
+    subscriber.update(&response);
+    let (group, _) = subscriber.find_data_set_writer(2).unwrap();
+    assert_eq!(group.writer_group_id, 20);
+    assert_eq!(group.name.as_ref(), "Moved");
+    let (group, _) = subscriber.find_data_set_writer(1).unwrap();
+    assert_eq!(group.writer_group_id, 10);
+    assert_eq!(group.data_set_writers.as_ref().unwrap().len(), 1);
+}
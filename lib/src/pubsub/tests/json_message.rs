@@ -0,0 +1,219 @@
+use crate::pubsub::prelude::*;
+
+fn field(name: &str, built_in_type: DataTypeId) -> FieldMetaData {
+    FieldMetaData {
+        name: name.into(),
+        description: LocalizedText::null(),
+        field_flags: DataSetFieldFlags::empty(),
+        built_in_type: built_in_type as u8,
+        data_type: built_in_type.into(),
+        value_rank: -1,
+        array_dimensions: None,
+        max_string_length: 0,
+        data_set_field_id: Guid::new(),
+        properties: None,
+    }
+}
+
+fn meta_data() -> DataSetMetaDataType {
+    DataSetMetaDataType {
+        namespaces: None,
+        structure_data_types: None,
+        enum_data_types: None,
+        simple_data_types: None,
+        name: "Sensors".into(),
+        description: LocalizedText::null(),
+        fields: Some(vec![
+            field("Count", DataTypeId::Int32),
+            field("Temperature", DataTypeId::Double),
+        ]),
+        data_set_class_id: Guid::null(),
+        configuration_version: ConfigurationVersionDataType {
+            major_version: 10,
+            minor_version: 20,
+        },
+    }
+}
+
+fn data_set_message(field_encoding: FieldEncoding) -> JsonDataSetMessage {
+    JsonDataSetMessage {
+        data_set_writer_id: 5,
+        sequence_number: Some(7),
+        meta_data_version: Some(ConfigurationVersionDataType {
+            major_version: 10,
+            minor_version: 20,
+        }),
+        timestamp: Some(DateTime::ymd_hms(2024, 1, 2, 3, 4, 5)),
+        status: None,
+        message_type: JsonDataSetMessageType::KeyFrame,
+        field_encoding,
+        // Decoded fields are in the order of their names
+        payload: vec![
+            ("Count".to_string(), DataValue::value_only(3i32)),
+            ("Temperature".to_string(), DataValue::value_only(21.5f64)),
+        ],
+    }
+}
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: JsonEncoder,
+{
+    T::from_json(
+        &value.to_json(JsonEncoding::Reversible),
+        &DecodingOptions::test(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn network_message() {
+    let message = JsonNetworkMessage {
+        message_id: "abc".into(),
+        publisher_id: Some("100".into()),
+        writer_group_name: Some("Group".into()),
+        data_set_class_id: None,
+        messages: vec![
+            data_set_message(FieldEncoding::Variant),
+            data_set_message(FieldEncoding::DataValue),
+        ],
+    };
+    assert_eq!(round_trip(&message), message);
+
+    let json = message.encode_json(JsonEncoding::Reversible);
+    assert_eq!(json["MessageType"], "ua-data");
+    assert_eq!(json["Messages"][0]["MessageType"], "ua-keyframe");
+    assert_eq!(json["Messages"][0]["Payload"]["Count"]["Type"], 6);
+    assert_eq!(json["Messages"][1]["Payload"]["Count"]["Value"]["Type"], 6);
+}
+
+#[test]
+fn raw_data_message() {
+    // Raw data fields are written as the value alone, so their types are guessed when read
+    let message = data_set_message(FieldEncoding::RawData);
+    let json = message.encode_json(JsonEncoding::Reversible);
+    assert_eq!(json["Payload"]["Count"], 3);
+    assert_eq!(json["Payload"]["Temperature"], 21.5);
+
+    let message = round_trip(&message);
+    assert_eq!(message.field_encoding, FieldEncoding::RawData);
+    assert_eq!(
+        message.payload,
+        vec![
+            ("Count".to_string(), DataValue::value_only(3i64)),
+            ("Temperature".to_string(), DataValue::value_only(21.5f64)),
+        ]
+    );
+}
+
+#[test]
+fn single_data_set_message() {
+    // A data set message may be sent without a network message around it
+    let json = data_set_message(FieldEncoding::Variant).to_json(JsonEncoding::Reversible);
+    let message = JsonMessage::from_json(&json, &DecodingOptions::test()).unwrap();
+    let JsonMessage::Data(message) = message else {
+        panic!("Unexpected message {:?}", message);
+    };
+    assert_eq!(message.publisher_id, None);
+    assert_eq!(
+        message.messages,
+        vec![data_set_message(FieldEncoding::Variant)]
+    );
+}
+
+#[test]
+fn meta_data_message() {
+    let message = JsonMetaDataMessage {
+        message_id: "abc".into(),
+        publisher_id: "100".into(),
+        data_set_writer_id: 5,
+        meta_data: meta_data(),
+        timestamp: Some(DateTime::ymd_hms(2024, 1, 2, 3, 4, 5)),
+    };
+    assert_eq!(round_trip(&message), message);
+    assert_eq!(
+        round_trip(&JsonMessage::MetaData(message.clone())),
+        JsonMessage::MetaData(message)
+    );
+}
+
+#[test]
+fn unknown_message_type() {
+    assert_eq!(
+        JsonMessage::from_json(
+            r#"{"MessageType":"ua-something","Messages":[]}"#,
+            &DecodingOptions::test()
+        )
+        .unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+    assert_eq!(
+        JsonDataSetMessage::from_json(
+            r#"{"DataSetWriterId":5,"MessageType":"ua-something"}"#,
+            &DecodingOptions::test()
+        )
+        .unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn from_data_set_message() {
+    let mut message = DataSetMessage::new(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::DeltaFrame(vec![(1, DataValue::value_only(21.5f64))]),
+    );
+    message.sequence_number = Some(7);
+    message.major_version = Some(10);
+    message.minor_version = Some(20);
+    let message = JsonDataSetMessage::from_data_set_message(5, message, &["Count", "Temperature"]);
+    assert_eq!(message.data_set_writer_id, 5);
+    assert_eq!(message.sequence_number, Some(7));
+    assert_eq!(message.message_type, JsonDataSetMessageType::DeltaFrame);
+    assert_eq!(
+        message
+            .meta_data_version
+            .map(|v| (v.major_version, v.minor_version)),
+        Some((10, 20))
+    );
+    assert_eq!(
+        message.payload,
+        vec![("Temperature".to_string(), DataValue::value_only(21.5f64))]
+    );
+}
+
+#[test]
+fn decode_with_meta_data() {
+    let mut decoder = DataSetDecoder::new(meta_data());
+
+    // Raw data integers are read as Int64 and converted to the type of the field
+    let message = round_trip(&data_set_message(FieldEncoding::RawData));
+    let fields = decoder.decode_json(&message).unwrap();
+    assert!(fields.key_frame);
+    assert_eq!(fields.sequence_number, Some(7));
+    assert_eq!(
+        fields.get("Count").unwrap().value,
+        Some(Variant::from(3i32))
+    );
+    assert_eq!(
+        fields.get("Temperature").unwrap().value,
+        Some(Variant::from(21.5f64))
+    );
+
+    // A field that isn't in the data set
+    let mut unknown = message.clone();
+    unknown.payload[0].0 = "Pressure".to_string();
+    assert_eq!(
+        decoder.decode_json(&unknown).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    // A different major version needs new metadata
+    let mut meta_data = meta_data();
+    meta_data.configuration_version.major_version = 11;
+    decoder.set_meta_data(meta_data);
+    assert_eq!(
+        decoder.decode_json(&message).unwrap_err(),
+        StatusCode::BadConfigurationError
+    );
+}
@@ -0,0 +1,42 @@
+use std::fmt::Debug;
+use std::io::Cursor;
+
+use crate::types::*;
+
+mod data_set_decoder;
+mod data_set_message;
+mod discovery;
+mod json_message;
+#[cfg(feature = "pubsub-mqtt")]
+mod mqtt;
+#[cfg(feature = "server")]
+mod publisher;
+mod security;
+mod subscriber;
+
+pub fn serialize_test_and_return<T>(value: T) -> T
+where
+    T: BinaryEncoder<T> + Debug + PartialEq,
+{
+    // Ask the struct for its byte length
+    let byte_len = value.byte_len();
+    let mut stream = Cursor::new(vec![0u8; byte_len]);
+
+    // Encode to stream and check the size reported is the same as the byte length impl
+    let size = value.encode(&mut stream).unwrap();
+    assert_eq!(size, byte_len);
+    assert_eq!(stream.position() as usize, byte_len);
+
+    let mut stream = Cursor::new(stream.into_inner());
+    let decoding_options = DecodingOptions::test();
+    let new_value = T::decode(&mut stream, &decoding_options).unwrap();
+    assert_eq!(value, new_value);
+    new_value
+}
+
+pub fn serialize_test<T>(value: T)
+where
+    T: BinaryEncoder<T> + Debug + PartialEq,
+{
+    let _ = serialize_test_and_return(value);
+}
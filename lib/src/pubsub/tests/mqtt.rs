@@ -0,0 +1,63 @@
+use crate::pubsub::prelude::*;
+
+#[test]
+fn mqtt_options_from_url() {
+    let settings = MqttSettings::default().client_id("test");
+    let options = mqtt_options("mqtt://broker.example.com", &settings).unwrap();
+    assert_eq!(
+        options.broker_address(),
+        ("broker.example.com".to_string(), DEFAULT_MQTT_PORT)
+    );
+    assert_eq!(options.client_id(), "test");
+    let options = mqtt_options("mqtts://broker.example.com:1234/", &settings).unwrap();
+    assert_eq!(
+        options.broker_address(),
+        ("broker.example.com".to_string(), 1234)
+    );
+    let options = mqtt_options("mqtts://broker.example.com", &settings).unwrap();
+    assert_eq!(options.broker_address().1, DEFAULT_MQTTS_PORT);
+
+    for url in [
+        "opc.udp://239.0.0.1:4840",
+        "mqtt://",
+        "mqtt://broker.example.com:port",
+    ] {
+        assert_eq!(
+            mqtt_options(url, &settings).unwrap_err(),
+            StatusCode::BadInvalidArgument
+        );
+    }
+    assert!(is_mqtt_url("mqtt://broker.example.com"));
+    assert!(!is_mqtt_url("opc.udp://239.0.0.1"));
+}
+
+#[test]
+fn mqtt_topics() {
+    let settings = MqttSettings::default();
+    assert_eq!(
+        settings.data_topic("100", "Group"),
+        "opcua/json/data/100/Group"
+    );
+    assert_eq!(
+        settings.meta_data_topic_of("100", "Group", "Writer"),
+        "opcua/json/metadata/100/Group/Writer"
+    );
+    assert_eq!(
+        settings.subscriber_topics(),
+        [
+            "opcua/json/data/#".to_string(),
+            "opcua/json/metadata/#".to_string()
+        ]
+    );
+
+    let settings = settings.topic("data").meta_data_topic("metadata");
+    assert_eq!(settings.data_topic("100", "Group"), "data");
+    assert_eq!(
+        settings.meta_data_topic_of("100", "Group", "Writer"),
+        "metadata"
+    );
+    assert_eq!(
+        settings.subscriber_topics(),
+        ["data".to_string(), "metadata".to_string()]
+    );
+}
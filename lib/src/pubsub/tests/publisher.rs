@@ -0,0 +1,406 @@
+use std::{io::Cursor, sync::Arc};
+
+use chrono::Duration;
+
+use crate::pubsub::prelude::*;
+use crate::server::address_space::{variable::Variable, AddressSpace};
+use crate::sync::*;
+
+fn make_address_space() -> AddressSpace {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    assert_eq!(ns, 1);
+    let vars = vec![
+        Variable::new(&NodeId::new(1, "temperature"), "t", "t", 20.5f64),
+        Variable::new(&NodeId::new(1, "count"), "c", "c", 1i32),
+    ];
+    let _ = address_space.add_variables(vars, &NodeId::objects_folder_id());
+    address_space
+}
+
+fn make_publisher(data_set_writer: DataSetWriter) -> Publisher {
+    let mut publisher = Publisher::new(PublisherId::UInt16(100), "opc.udp://127.0.0.1:4840");
+    publisher.add_published_data_set(
+        PublishedDataSet::new("Sensors")
+            .add_variable("Temperature", NodeId::new(1, "temperature"))
+            .add_variable("Count", NodeId::new(1, "count")),
+    );
+    publisher.add_writer_group(
+        WriterGroup::new(1, "Group", 100f64)
+            .keep_alive_time(1000f64)
+            .add_data_set_writer(data_set_writer),
+    );
+    publisher
+}
+
+fn data_set_message(message: NetworkMessage) -> DataSetMessage {
+    assert_eq!(message.header.data_set_writer_ids, Some(vec![5]));
+    match message.payload {
+        NetworkMessagePayload::DataSetMessages(mut messages) => {
+            assert_eq!(messages.len(), 1);
+            messages.remove(0)
+        }
+        payload => panic!("Unexpected payload {:?}", payload),
+    }
+}
+
+#[test]
+fn published_data_set_meta_data() {
+    let address_space = make_address_space();
+    let data_set = PublishedDataSet::new("Sensors")
+        .add_variable("Temperature", NodeId::new(1, "temperature"))
+        .add_variable("Missing", NodeId::new(1, "missing"));
+    let meta_data = data_set.meta_data(&address_space);
+    assert_eq!(meta_data.name.as_ref(), "Sensors");
+    assert_eq!(
+        meta_data.configuration_version,
+        data_set.configuration_version
+    );
+
+    let fields = meta_data.fields.unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].name.as_ref(), "Temperature");
+    assert_eq!(fields[0].built_in_type, DataTypeId::Double as u8);
+    assert_eq!(fields[0].data_type, DataTypeId::Double.into());
+    assert_eq!(fields[0].value_rank, -1);
+    assert_eq!(
+        fields[0].data_set_field_id,
+        data_set.fields[0].data_set_field_id
+    );
+    assert_eq!(fields[1].built_in_type, DataTypeId::BaseDataType as u8);
+    assert_eq!(fields[1].data_type, DataTypeId::BaseDataType.into());
+}
+
+#[test]
+fn published_data_set_sample() {
+    let address_space = make_address_space();
+    let data_set = PublishedDataSet::new("Sensors")
+        .add_variable("Count", NodeId::new(1, "count"))
+        .add_variable("Missing", NodeId::new(1, "missing"))
+        .add_field(
+            "Substituted",
+            PublishedVariableDataType {
+                published_variable: NodeId::new(1, "missing"),
+                attribute_id: AttributeId::Value as u32,
+                sampling_interval_hint: -1f64,
+                deadband_type: 0,
+                deadband_value: 0f64,
+                index_range: UAString::null(),
+                substitute_value: Variant::from(-1i32),
+                meta_data_properties: None,
+            },
+        );
+    let values = data_set.sample(&address_space);
+    assert_eq!(values[0].value, Some(Variant::from(1i32)));
+    assert_eq!(values[1].value, None);
+    assert_eq!(values[1].status, Some(StatusCode::BadNodeIdUnknown));
+    assert_eq!(values[2].value, Some(Variant::from(-1i32)));
+    assert_eq!(values[2].status, Some(StatusCode::UncertainSubstituteValue));
+}
+
+#[test]
+fn publish_key_delta_and_keep_alive_frames() {
+    let mut address_space = make_address_space();
+    let mut publisher =
+        make_publisher(DataSetWriter::new(5, "Writer", "Sensors").key_frame_count(3));
+    let start = chrono::Utc::now();
+
+    // Unknown writer group
+    assert!(publisher.publish(2, &address_space, &start).is_none());
+
+    // The first message is a key frame
+    let message = publisher.publish(1, &address_space, &start).unwrap();
+    assert_eq!(message.header.publisher_id, Some(PublisherId::UInt16(100)));
+    let group_header = message.header.group_header.clone().unwrap();
+    assert_eq!(group_header.writer_group_id, Some(1));
+    assert_eq!(group_header.sequence_number, Some(1));
+    let message = data_set_message(message);
+    assert_eq!(message.field_encoding, FieldEncoding::Variant);
+    assert_eq!(message.sequence_number, Some(1));
+    assert_eq!(
+        message.payload,
+        DataSetMessagePayload::KeyFrame(vec![
+            DataValue::value_only(20.5f64),
+            DataValue::value_only(1i32)
+        ])
+    );
+
+    // Nothing changed and the keep alive time hasn't passed
+    let now = start + Duration::milliseconds(100);
+    assert!(publisher.publish(1, &address_space, &now).is_none());
+
+    // A change is sent in a delta frame
+    let now = start + Duration::milliseconds(200);
+    let timestamp = DateTime::from(now);
+    address_space.set_variable_value(NodeId::new(1, "count"), 2i32, &timestamp, &timestamp);
+    let message = data_set_message(publisher.publish(1, &address_space, &now).unwrap());
+    assert_eq!(message.sequence_number, Some(2));
+    assert_eq!(
+        message.payload,
+        DataSetMessagePayload::DeltaFrame(vec![(1, DataValue::value_only(2i32))])
+    );
+
+    // Every third interval is a key frame
+    let now = start + Duration::milliseconds(300);
+    let message = data_set_message(publisher.publish(1, &address_space, &now).unwrap());
+    assert!(matches!(
+        message.payload,
+        DataSetMessagePayload::KeyFrame(_)
+    ));
+
+    // A keep alive is sent when nothing changes for the keep alive time
+    let now = start + Duration::milliseconds(1300);
+    let message = data_set_message(publisher.publish(1, &address_space, &now).unwrap());
+    assert_eq!(message.payload, DataSetMessagePayload::KeepAlive);
+    assert_eq!(message.sequence_number, Some(4));
+}
+
+#[test]
+fn publish_with_field_content_mask() {
+    let address_space = make_address_space();
+    let now = chrono::Utc::now();
+
+    let mut publisher = make_publisher(
+        DataSetWriter::new(5, "Writer", "Sensors")
+            .field_content_mask(DataSetFieldContentMask::StatusCode),
+    );
+    let message = data_set_message(publisher.publish(1, &address_space, &now).unwrap());
+    assert_eq!(message.field_encoding, FieldEncoding::DataValue);
+    match message.payload {
+        DataSetMessagePayload::KeyFrame(values) => {
+            assert_eq!(values[1].value, Some(Variant::from(1i32)));
+            assert_eq!(values[1].status, Some(StatusCode::Good));
+            assert!(values[1].source_timestamp.is_none());
+            assert!(values[1].server_timestamp.is_none());
+        }
+        payload => panic!("Unexpected payload {:?}", payload),
+    }
+
+    // Raw data is always sent in key frames
+    let mut publisher = make_publisher(
+        DataSetWriter::new(5, "Writer", "Sensors")
+            .field_content_mask(DataSetFieldContentMask::RawData)
+            .key_frame_count(10),
+    );
+    for _ in 0..2 {
+        let message = data_set_message(publisher.publish(1, &address_space, &now).unwrap());
+        assert_eq!(message.field_encoding, FieldEncoding::RawData);
+        assert!(matches!(
+            message.payload,
+            DataSetMessagePayload::KeyFrame(_)
+        ));
+    }
+}
+
+#[test]
+fn publisher_answers_discovery_requests() {
+    let address_space = make_address_space();
+    let mut publisher = make_publisher(DataSetWriter::new(5, "Writer", "Sensors"));
+    publisher.update_information(&address_space);
+    assert_eq!(publisher.information().meta_data.len(), 1);
+    assert_eq!(publisher.information().writer_groups.len(), 1);
+
+    let request = NetworkMessage::discovery_request(
+        NetworkMessageHeader::new(PublisherId::UInt16(200)),
+        DiscoveryRequest::new(InformationType::DataSetMetaData, &[5]),
+    );
+    let responses = publisher.handle_discovery_request(&request);
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses[0].header.publisher_id,
+        Some(PublisherId::UInt16(100))
+    );
+    match responses[0].payload {
+        NetworkMessagePayload::DiscoveryResponse(ref response) => match response.announcement {
+            Announcement::DataSetMetaData {
+                data_set_writer_id,
+                ref meta_data,
+                status_code,
+            } => {
+                assert_eq!(data_set_writer_id, 5);
+                assert_eq!(meta_data.name.as_ref(), "Sensors");
+                assert_eq!(status_code, StatusCode::Good);
+            }
+            ref announcement => panic!("Unexpected announcement {:?}", announcement),
+        },
+        ref payload => panic!("Unexpected payload {:?}", payload),
+    }
+
+    // Data set messages are not answered
+    let message = publisher
+        .publish(1, &address_space, &chrono::Utc::now())
+        .unwrap();
+    assert!(publisher.handle_discovery_request(&message).is_empty());
+}
+
+#[tokio::test]
+async fn publisher_sends_over_udp() {
+    let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("opc.udp://{}", receiver.local_addr().unwrap());
+    assert_eq!(udp_address(&url).unwrap(), receiver.local_addr().unwrap());
+
+    let mut publisher = Publisher::new(PublisherId::UInt16(100), url);
+    publisher.add_published_data_set(
+        PublishedDataSet::new("Sensors").add_variable("Count", NodeId::new(1, "count")),
+    );
+    publisher.add_writer_group(
+        WriterGroup::new(1, "Group", 50f64)
+            .add_data_set_writer(DataSetWriter::new(5, "Writer", "Sensors")),
+    );
+    let publisher = Arc::new(RwLock::new(publisher));
+    let address_space = Arc::new(RwLock::new(make_address_space()));
+    let task = tokio::spawn(Publisher::run(publisher.clone(), address_space));
+
+    let mut buffer = vec![0u8; 1500];
+    let size = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        receiver.recv(&mut buffer),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let message =
+        NetworkMessage::decode(&mut Cursor::new(&buffer[..size]), &DecodingOptions::test())
+            .unwrap();
+    assert_eq!(
+        data_set_message(message).payload,
+        DataSetMessagePayload::KeyFrame(vec![DataValue::value_only(1i32)])
+    );
+
+    trace_write_lock!(publisher).abort();
+    assert_eq!(task.await.unwrap(), Ok(()));
+}
+
+#[test]
+fn udp_address_from_url() {
+    assert_eq!(
+        udp_address("opc.udp://239.0.0.1:4841").unwrap(),
+        "239.0.0.1:4841".parse().unwrap()
+    );
+    assert_eq!(
+        udp_address("opc.udp://239.0.0.1/").unwrap(),
+        "239.0.0.1:4840".parse().unwrap()
+    );
+    assert_eq!(
+        udp_address("opc.tcp://127.0.0.1:4840").unwrap_err(),
+        StatusCode::BadInvalidArgument
+    );
+}
+
+#[test]
+fn published_messages_decode_with_meta_data() {
+    let address_space = make_address_space();
+    let mut publisher = make_publisher(
+        DataSetWriter::new(5, "Writer", "Sensors")
+            .field_content_mask(DataSetFieldContentMask::RawData),
+    );
+    publisher.update_information(&address_space);
+    let decoder = DataSetDecoder::new(publisher.information().meta_data[&5].clone());
+
+    let message = publisher
+        .publish(1, &address_space, &chrono::Utc::now())
+        .unwrap();
+    let message = NetworkMessage::decode(
+        &mut Cursor::new(message.encode_to_vec()),
+        &DecodingOptions::test(),
+    )
+    .unwrap();
+    let fields = decoder.decode(&data_set_message(message)).unwrap();
+    assert_eq!(
+        fields.get("Temperature").unwrap().value,
+        Some(Variant::from(20.5f64))
+    );
+    assert_eq!(
+        fields.get("Count").unwrap().value,
+        Some(Variant::from(1i32))
+    );
+}
+
+#[test]
+fn publish_secured_messages() {
+    let address_space = make_address_space();
+    let mut publisher = Publisher::new(PublisherId::UInt16(100), "opc.udp://127.0.0.1:4840");
+    publisher.add_published_data_set(
+        PublishedDataSet::new("Sensors").add_variable("Count", NodeId::new(1, "count")),
+    );
+    publisher.add_writer_group(
+        WriterGroup::new(1, "Group", 100f64)
+            .security(MessageSecurityMode::SignAndEncrypt, "SecurityGroup")
+            .add_data_set_writer(DataSetWriter::new(5, "Writer", "Sensors")),
+    );
+    assert_eq!(
+        publisher.writer_groups()[0].data_type().security_mode,
+        MessageSecurityMode::SignAndEncrypt
+    );
+    let message = publisher
+        .publish(1, &address_space, &chrono::Utc::now())
+        .unwrap();
+
+    // The group cannot send without the keys of its security group
+    assert_eq!(
+        publisher.encode(1, &message).unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+
+    let keys = SecurityGroup::new("SecurityGroup", PubSubSecurityPolicy::Aes128Ctr, 60000f64)
+        .get_security_keys(0, 0, std::time::Instant::now())
+        .security_keys()
+        .unwrap();
+    publisher.set_security_keys("SecurityGroup", keys.clone());
+    let data = publisher.encode(1, &message).unwrap();
+    let decoded = keys.decode(&data, &DecodingOptions::test()).unwrap();
+    assert_eq!(
+        decoded.header.security.unwrap().security_mode(),
+        MessageSecurityMode::SignAndEncrypt
+    );
+    assert_eq!(decoded.payload, message.payload);
+}
+
+#[test]
+fn publish_json_and_meta_data_messages() {
+    let address_space = make_address_space();
+    let mut publisher = make_publisher(DataSetWriter::new(5, "Writer", "Sensors"));
+    publisher.update_information(&address_space);
+
+    let message = publisher
+        .publish_json(1, &address_space, &chrono::Utc::now())
+        .unwrap();
+    assert_eq!(message.publisher_id, Some("100".into()));
+    assert_eq!(message.writer_group_name, Some("Group".into()));
+    assert_eq!(message.messages.len(), 1);
+    let data_set_message = &message.messages[0];
+    assert_eq!(data_set_message.data_set_writer_id, 5);
+    assert_eq!(
+        data_set_message.message_type,
+        JsonDataSetMessageType::KeyFrame
+    );
+    assert_eq!(
+        data_set_message.payload,
+        vec![
+            ("Temperature".to_string(), DataValue::value_only(20.5f64)),
+            ("Count".to_string(), DataValue::value_only(1i32)),
+        ]
+    );
+
+    let meta_data_messages = publisher.meta_data_messages();
+    assert_eq!(meta_data_messages.len(), 1);
+    assert_eq!(meta_data_messages[0].publisher_id, UAString::from("100"));
+    assert_eq!(meta_data_messages[0].data_set_writer_id, 5);
+
+    // The JSON sent to a broker decodes with the metadata
+    let decoder = DataSetDecoder::new(meta_data_messages[0].meta_data.clone());
+    let message = JsonNetworkMessage::from_json(
+        &message.to_json(JsonEncoding::Reversible),
+        &DecodingOptions::test(),
+    )
+    .unwrap();
+    let fields = decoder.decode_json(&message.messages[0]).unwrap();
+    assert_eq!(
+        fields.get("Count").unwrap().value,
+        Some(Variant::from(1i32))
+    );
+
+    assert!(publisher
+        .publish_json(2, &address_space, &chrono::Utc::now())
+        .is_none());
+}
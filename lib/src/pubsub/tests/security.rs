@@ -0,0 +1,232 @@
+use std::{
+    io::Cursor,
+    time::{Duration, Instant},
+};
+
+use crate::pubsub::prelude::*;
+
+fn network_message() -> NetworkMessage {
+    let mut header = NetworkMessageHeader::new(PublisherId::UInt16(100));
+    header.group_header = Some(GroupHeader {
+        writer_group_id: Some(1),
+        group_version: None,
+        network_message_number: Some(1),
+        sequence_number: Some(1),
+    });
+    header.data_set_writer_ids = Some(vec![5]);
+    NetworkMessage::data_set_messages(
+        header,
+        vec![DataSetMessage::new(
+            FieldEncoding::Variant,
+            DataSetMessagePayload::KeyFrame(vec![DataValue::value_only(3i32)]),
+        )],
+    )
+}
+
+/// Makes keys from the current and future keys of a new security group
+fn security_keys(policy: PubSubSecurityPolicy) -> SecurityKeys {
+    SecurityGroup::new("Group", policy, 60000f64)
+        .get_security_keys(0, 0, Instant::now())
+        .security_keys()
+        .unwrap()
+}
+
+#[test]
+fn secured_message_round_trip() {
+    let message = network_message();
+    let plain = message.encode_to_vec();
+    for policy in [
+        PubSubSecurityPolicy::Aes128Ctr,
+        PubSubSecurityPolicy::Aes256Ctr,
+    ] {
+        let mut keys = security_keys(policy);
+        for security_mode in [
+            MessageSecurityMode::Sign,
+            MessageSecurityMode::SignAndEncrypt,
+        ] {
+            let data = keys.encode(&message, security_mode).unwrap();
+            // The payload is only readable when the message isn't encrypted
+            let payload = &plain[plain.len() - 8..];
+            assert_eq!(
+                data.windows(payload.len()).any(|v| v == payload),
+                security_mode == MessageSecurityMode::Sign
+            );
+
+            let decoded = keys.decode(&data, &DecodingOptions::test()).unwrap();
+            let security = decoded.header.security.as_ref().unwrap();
+            assert_eq!(security.security_mode(), security_mode);
+            assert_eq!(security.security_token_id, keys.current_key().token_id());
+            assert_eq!(decoded.payload, message.payload);
+            assert_eq!(
+                decoded.header.data_set_writer_ids,
+                message.header.data_set_writer_ids
+            );
+        }
+    }
+}
+
+#[test]
+fn secured_message_checks() {
+    let message = network_message();
+    let mut keys = security_keys(PubSubSecurityPolicy::Aes128Ctr);
+    let data = keys
+        .encode(&message, MessageSecurityMode::SignAndEncrypt)
+        .unwrap();
+
+    // A message that has been changed
+    let mut tampered = data.clone();
+    let last = tampered.len() - 40;
+    tampered[last] ^= 0xff;
+    assert_eq!(
+        keys.decode(&tampered, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+
+    // Keys of another security group, which don't have the same key material
+    let other_keys = security_keys(PubSubSecurityPolicy::Aes128Ctr);
+    assert_eq!(
+        other_keys
+            .decode(&data, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+
+    // No keys at all
+    assert_eq!(
+        NetworkMessage::decode(&mut Cursor::new(&data), &DecodingOptions::test()).unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+
+    // A message that isn't secured is decoded as it is
+    assert_eq!(
+        keys.decode(&message.encode_to_vec(), &DecodingOptions::test())
+            .unwrap(),
+        message
+    );
+    assert_eq!(
+        keys.encode(&message, MessageSecurityMode::None)
+            .unwrap_err(),
+        StatusCode::BadSecurityModeRejected
+    );
+}
+
+#[test]
+fn security_keys_from_sks() {
+    assert_eq!(
+        PubSubSecurityPolicy::from_uri(SECURITY_POLICY_AES256_CTR).unwrap(),
+        PubSubSecurityPolicy::Aes256Ctr
+    );
+    assert_eq!(
+        PubSubSecurityPolicy::from_uri("http://opcfoundation.org/UA/SecurityPolicy#None")
+            .unwrap_err(),
+        StatusCode::BadSecurityPolicyRejected
+    );
+    assert_eq!(PubSubSecurityPolicy::Aes128Ctr.key_length(), 52);
+    assert_eq!(PubSubSecurityPolicy::Aes256Ctr.key_length(), 68);
+
+    // Keys that are the wrong length for the policy
+    assert_eq!(
+        SecurityKeys::new(
+            SECURITY_POLICY_AES256_CTR,
+            1,
+            &[ByteString::from(vec![0u8; 52])],
+            1000f64,
+            1000f64
+        )
+        .unwrap_err(),
+        StatusCode::BadInvalidArgument
+    );
+    assert_eq!(
+        SecurityKeys::new(SECURITY_POLICY_AES128_CTR, 1, &[], 1000f64, 1000f64).unwrap_err(),
+        StatusCode::BadInvalidArgument
+    );
+}
+
+#[test]
+fn security_keys_rotate() {
+    let key = |token_id| {
+        SecurityKey::generate(PubSubSecurityPolicy::Aes128Ctr, token_id).to_byte_string()
+    };
+    let now = Instant::now();
+    let mut keys = SecurityKeys::new(
+        SECURITY_POLICY_AES128_CTR,
+        1,
+        &[key(1), key(2), key(3)],
+        1000f64,
+        1000f64,
+    )
+    .unwrap();
+    assert_eq!(keys.current_key().token_id(), 1);
+    assert!(!keys.needs_keys());
+
+    keys.rotate(now + Duration::from_millis(1500));
+    assert_eq!(keys.current_key().token_id(), 2);
+    keys.rotate(now + Duration::from_millis(2500));
+    assert_eq!(keys.current_key().token_id(), 3);
+    assert!(keys.needs_keys());
+    // Only the previous key is kept
+    assert!(keys.find_key(1).is_none());
+    assert!(keys.find_key(2).is_some());
+
+    // The last key stays current until there are new keys
+    keys.rotate(now + Duration::from_millis(10000));
+    assert_eq!(keys.current_key().token_id(), 3);
+
+    let new_keys = SecurityKeys::new(
+        SECURITY_POLICY_AES128_CTR,
+        4,
+        &[key(4), key(5)],
+        1000f64,
+        1000f64,
+    )
+    .unwrap();
+    keys.update(new_keys);
+    assert_eq!(keys.current_key().token_id(), 4);
+    assert!(keys.find_key(3).is_some());
+    assert!(!keys.needs_keys());
+}
+
+#[test]
+fn security_group_keys() {
+    let mut security_group = SecurityGroup::new("Group", PubSubSecurityPolicy::Aes128Ctr, 1000f64);
+    let now = Instant::now();
+    let result = security_group.get_security_keys(0, 0, now);
+    assert_eq!(
+        result.security_policy_uri,
+        UAString::from(SECURITY_POLICY_AES128_CTR)
+    );
+    assert_eq!(result.first_token_id, 1);
+    assert_eq!(result.keys.len(), 3);
+    assert_eq!(result.key_lifetime, 1000f64);
+    assert!(result.time_to_next_key <= 1000f64);
+
+    // After the key lifetime the next key is current and a new future key is made
+    let later = now + Duration::from_millis(1500);
+    let rotated = security_group.get_security_keys(0, 0, later);
+    assert_eq!(rotated.first_token_id, 2);
+    assert_eq!(rotated.keys.len(), 3);
+    assert_eq!(rotated.keys[0], result.keys[1]);
+    assert!(rotated.time_to_next_key <= 500f64);
+
+    // The past key can be asked for by its token id, and the number of keys limited
+    let past = security_group.get_security_keys(1, 2, later);
+    assert_eq!(past.first_token_id, 1);
+    assert_eq!(past.keys, result.keys[..2]);
+    // An unknown token id starts with the current key
+    assert_eq!(
+        security_group
+            .get_security_keys(100, 1, later)
+            .first_token_id,
+        2
+    );
+
+    let mut sks = SecurityKeyService::new();
+    sks.add_security_group(security_group);
+    assert!(sks.find_security_group("Group").is_some());
+    assert_eq!(sks.get_security_keys("Group", 0, 1).unwrap().keys.len(), 1);
+    assert_eq!(
+        sks.get_security_keys("Other", 0, 0).unwrap_err(),
+        StatusCode::BadNotFound
+    );
+}
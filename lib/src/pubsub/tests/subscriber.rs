@@ -0,0 +1,370 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::pubsub::prelude::*;
+
+fn meta_data(major_version: u32) -> DataSetMetaDataType {
+    DataSetMetaDataType {
+        namespaces: None,
+        structure_data_types: None,
+        enum_data_types: None,
+        simple_data_types: None,
+        name: "Sensors".into(),
+        description: LocalizedText::null(),
+        fields: Some(vec![FieldMetaData {
+            name: "Count".into(),
+            description: LocalizedText::null(),
+            field_flags: DataSetFieldFlags::empty(),
+            built_in_type: DataTypeId::Int32 as u8,
+            data_type: DataTypeId::Int32.into(),
+            value_rank: -1,
+            array_dimensions: None,
+            max_string_length: 0,
+            data_set_field_id: Guid::new(),
+            properties: None,
+        }]),
+        data_set_class_id: Guid::null(),
+        configuration_version: ConfigurationVersionDataType {
+            major_version,
+            minor_version: 1,
+        },
+    }
+}
+
+fn header(
+    publisher_id: u16,
+    writer_group_id: u16,
+    data_set_writer_id: u16,
+) -> NetworkMessageHeader {
+    let mut header = NetworkMessageHeader::new(PublisherId::UInt16(publisher_id));
+    header.group_header = Some(GroupHeader {
+        writer_group_id: Some(writer_group_id),
+        group_version: None,
+        network_message_number: Some(1),
+        sequence_number: Some(1),
+    });
+    header.data_set_writer_ids = Some(vec![data_set_writer_id]);
+    header
+}
+
+fn data_set_message(
+    header: NetworkMessageHeader,
+    major_version: u32,
+    count: i32,
+) -> NetworkMessage {
+    let mut message = DataSetMessage::new(
+        FieldEncoding::Variant,
+        DataSetMessagePayload::KeyFrame(vec![DataValue::value_only(count)]),
+    );
+    message.major_version = Some(major_version);
+    message.minor_version = Some(1);
+    NetworkMessage::data_set_messages(header, vec![message])
+}
+
+fn meta_data_response(
+    publisher_id: u16,
+    data_set_writer_id: u16,
+    major_version: u32,
+) -> NetworkMessage {
+    NetworkMessage::discovery_response(
+        NetworkMessageHeader::new(PublisherId::UInt16(publisher_id)),
+        DiscoveryResponse {
+            sequence_number: 1,
+            announcement: Announcement::DataSetMetaData {
+                data_set_writer_id,
+                meta_data: meta_data(major_version),
+                status_code: StatusCode::Good,
+            },
+        },
+    )
+}
+
+/// Makes a subscriber with one reader whose callback collects the counts it receives
+fn make_subscriber(reader: DataSetReader) -> (Subscriber, Arc<Mutex<Vec<i32>>>) {
+    let counts = Arc::new(Mutex::new(Vec::new()));
+    let mut subscriber = Subscriber::new(PublisherId::UInt16(1000), "opc.udp://239.0.0.1:4840");
+    let received = counts.clone();
+    subscriber.add_data_set_reader(
+        reader,
+        DataSetCallback::new(move |fields, reader| {
+            assert_eq!(reader.data_set_writer_id, 5);
+            if let Some(Variant::Int32(count)) = fields.get("Count").and_then(|v| v.value.clone()) {
+                received.lock().unwrap().push(count);
+            }
+        }),
+    );
+    (subscriber, counts)
+}
+
+fn requested_data_set_writer_ids(requests: &[NetworkMessage]) -> Vec<u16> {
+    requests
+        .iter()
+        .flat_map(|request| match request.payload {
+            NetworkMessagePayload::DiscoveryRequest(ref request) => {
+                assert_eq!(request.information_type, InformationType::DataSetMetaData);
+                request.data_set_writer_ids.clone().unwrap_or_default()
+            }
+            ref payload => panic!("Unexpected payload {:?}", payload),
+        })
+        .collect()
+}
+
+#[test]
+fn readers_match_publisher_and_writer_group() {
+    let (mut subscriber, counts) = make_subscriber(
+        DataSetReader::new("Reader", 5)
+            .publisher_id(PublisherId::UInt16(100))
+            .writer_group_id(1)
+            .meta_data(meta_data(1)),
+    );
+    subscriber.handle_message(&data_set_message(header(100, 1, 5), 1, 1));
+    subscriber.handle_message(&data_set_message(header(101, 1, 5), 1, 2));
+    subscriber.handle_message(&data_set_message(header(100, 2, 5), 1, 3));
+    subscriber.handle_message(&data_set_message(header(100, 1, 6), 1, 4));
+    assert_eq!(*counts.lock().unwrap(), vec![1]);
+
+    // A reader without filters reads any publisher and writer group
+    let (mut subscriber, counts) =
+        make_subscriber(DataSetReader::new("Reader", 5).meta_data(meta_data(1)));
+    subscriber.handle_message(&data_set_message(header(100, 1, 5), 1, 1));
+    subscriber.handle_message(&data_set_message(header(101, 2, 5), 1, 2));
+    assert_eq!(*counts.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn subscriber_discovers_meta_data() {
+    let (mut subscriber, counts) =
+        make_subscriber(DataSetReader::new("Reader", 5).publisher_id(PublisherId::UInt16(100)));
+    assert!(subscriber
+        .find_data_set_reader(5)
+        .unwrap()
+        .needs_meta_data());
+
+    // Messages can't be read without metadata, so it is asked for
+    let requests = subscriber.handle_message(&data_set_message(header(100, 1, 5), 1, 1));
+    assert_eq!(requested_data_set_writer_ids(&requests), vec![5]);
+    assert_eq!(
+        requests[0].header.publisher_id,
+        Some(PublisherId::UInt16(1000))
+    );
+    assert!(counts.lock().unwrap().is_empty());
+
+    // But not again until the request interval has passed
+    assert!(subscriber
+        .handle_message(&data_set_message(header(100, 1, 5), 1, 2))
+        .is_empty());
+    let later = Instant::now() + Duration::from_secs(10);
+    assert_eq!(
+        requested_data_set_writer_ids(&subscriber.meta_data_requests(later)),
+        vec![5]
+    );
+
+    // Metadata from another publisher is ignored
+    subscriber.handle_message(&meta_data_response(101, 5, 1));
+    assert!(subscriber
+        .find_data_set_reader(5)
+        .unwrap()
+        .needs_meta_data());
+
+    subscriber.handle_message(&meta_data_response(100, 5, 1));
+    let reader = subscriber.find_data_set_reader(5).unwrap();
+    assert!(!reader.needs_meta_data());
+    assert_eq!(
+        reader
+            .decoder()
+            .unwrap()
+            .meta_data()
+            .configuration_version
+            .major_version,
+        1
+    );
+    assert!(subscriber
+        .handle_message(&data_set_message(header(100, 1, 5), 1, 3))
+        .is_empty());
+    assert_eq!(*counts.lock().unwrap(), vec![3]);
+}
+
+#[test]
+fn subscriber_checks_meta_data_version() {
+    let (mut subscriber, counts) =
+        make_subscriber(DataSetReader::new("Reader", 5).meta_data(meta_data(1)));
+    assert!(subscriber.meta_data_requests(Instant::now()).is_empty());
+
+    // A new major version needs new metadata
+    let requests = subscriber.handle_message(&data_set_message(header(100, 1, 5), 2, 1));
+    assert_eq!(requested_data_set_writer_ids(&requests), vec![5]);
+    assert!(subscriber
+        .find_data_set_reader(5)
+        .unwrap()
+        .needs_meta_data());
+    assert!(counts.lock().unwrap().is_empty());
+
+    subscriber.handle_message(&meta_data_response(100, 5, 2));
+    subscriber.handle_message(&data_set_message(header(100, 1, 5), 2, 2));
+    assert_eq!(*counts.lock().unwrap(), vec![2]);
+}
+
+#[test]
+fn subscriber_decodes_secured_messages() {
+    let (mut subscriber, counts) = make_subscriber(
+        DataSetReader::new("Reader", 5)
+            .security_mode(MessageSecurityMode::SignAndEncrypt)
+            .meta_data(meta_data(1)),
+    );
+    let mut keys = SecurityGroup::new("Group", PubSubSecurityPolicy::Aes256Ctr, 60000f64)
+        .get_security_keys(0, 0, Instant::now())
+        .security_keys()
+        .unwrap();
+    let data = keys
+        .encode(
+            &data_set_message(header(100, 1, 5), 1, 1),
+            MessageSecurityMode::SignAndEncrypt,
+        )
+        .unwrap();
+
+    // Without the keys the message cannot be read
+    assert_eq!(
+        subscriber
+            .decode(&data, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadSecurityChecksFailed
+    );
+    subscriber.set_security_keys("Group", keys.clone());
+    let message = subscriber.decode(&data, &DecodingOptions::test()).unwrap();
+    subscriber.handle_message(&message);
+    assert_eq!(*counts.lock().unwrap(), vec![1]);
+
+    // Messages that are secured less than the reader requires are ignored
+    let signed = keys
+        .encode(
+            &data_set_message(header(100, 1, 5), 1, 2),
+            MessageSecurityMode::Sign,
+        )
+        .unwrap();
+    let message = subscriber
+        .decode(&signed, &DecodingOptions::test())
+        .unwrap();
+    subscriber.handle_message(&message);
+    subscriber.handle_message(&data_set_message(header(100, 1, 5), 1, 3));
+    assert_eq!(*counts.lock().unwrap(), vec![1]);
+}
+
+#[cfg(feature = "server")]
+#[test]
+fn subscriber_handles_json_messages() {
+    let (mut subscriber, counts) =
+        make_subscriber(DataSetReader::new("Reader", 5).publisher_id(PublisherId::UInt16(100)));
+    let json_message = |publisher_id: &str, major_version: u32, count: i32| {
+        JsonMessage::Data(JsonNetworkMessage {
+            message_id: "abc".into(),
+            publisher_id: Some(publisher_id.into()),
+            writer_group_name: Some("Group".into()),
+            data_set_class_id: None,
+            messages: vec![JsonDataSetMessage {
+                data_set_writer_id: 5,
+                sequence_number: Some(1),
+                meta_data_version: Some(ConfigurationVersionDataType {
+                    major_version,
+                    minor_version: 1,
+                }),
+                timestamp: None,
+                status: None,
+                message_type: JsonDataSetMessageType::KeyFrame,
+                field_encoding: FieldEncoding::Variant,
+                payload: vec![("Count".to_string(), DataValue::value_only(count))],
+            }],
+        })
+    };
+
+    // Without metadata the fields are passed on as they are, and only from the publisher
+    subscriber.handle_json_message(&json_message("100", 1, 1));
+    subscriber.handle_json_message(&json_message("101", 1, 2));
+    assert_eq!(*counts.lock().unwrap(), vec![1]);
+
+    // With metadata the versions must match
+    subscriber.handle_json_message(&JsonMessage::MetaData(JsonMetaDataMessage {
+        message_id: "def".into(),
+        publisher_id: "100".into(),
+        data_set_writer_id: 5,
+        meta_data: meta_data(2),
+        timestamp: None,
+    }));
+    assert!(subscriber
+        .find_data_set_reader(5)
+        .unwrap()
+        .decoder()
+        .is_some());
+    subscriber.handle_json_message(&json_message("100", 1, 3));
+    assert!(subscriber
+        .find_data_set_reader(5)
+        .unwrap()
+        .needs_meta_data());
+    subscriber.handle_json_message(&json_message("100", 2, 4));
+    assert_eq!(*counts.lock().unwrap(), vec![1, 4]);
+}
+
+#[tokio::test]
+async fn subscriber_receives_from_publisher() {
+    use crate::server::address_space::{variable::Variable, AddressSpace};
+    use crate::sync::*;
+
+    let port = std::net::UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let url = format!("opc.udp://127.0.0.1:{}", port);
+
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    let _ = address_space.add_variables(
+        vec![Variable::new(&NodeId::new(ns, "count"), "c", "c", 7i32)],
+        &NodeId::objects_folder_id(),
+    );
+    let mut publisher = Publisher::new(PublisherId::UInt16(100), url.clone());
+    publisher.add_published_data_set(
+        PublishedDataSet::new("Sensors").add_variable("Count", NodeId::new(ns, "count")),
+    );
+    publisher.add_writer_group(
+        WriterGroup::new(1, "Group", 50f64)
+            .add_data_set_writer(DataSetWriter::new(5, "Writer", "Sensors")),
+    );
+    publisher.update_information(&address_space);
+    let meta_data = publisher.information().meta_data[&5].clone();
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut subscriber = Subscriber::new(PublisherId::UInt16(1000), url);
+    subscriber.add_data_set_reader(
+        DataSetReader::new("Reader", 5)
+            .publisher_id(PublisherId::UInt16(100))
+            .meta_data(meta_data),
+        DataSetCallback::new(move |fields, _| {
+            let _ = tx.send(fields);
+        }),
+    );
+    let subscriber = Arc::new(RwLock::new(subscriber));
+    let subscriber_task = tokio::spawn(Subscriber::run(subscriber.clone()));
+
+    let publisher = Arc::new(RwLock::new(publisher));
+    let publisher_task = tokio::spawn(Publisher::run(
+        publisher.clone(),
+        Arc::new(RwLock::new(address_space)),
+    ));
+
+    let fields = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(fields.key_frame);
+    assert_eq!(
+        fields.get("Count").unwrap().value,
+        Some(Variant::from(7i32))
+    );
+
+    trace_write_lock!(publisher).abort();
+    trace_write_lock!(subscriber).abort();
+    assert_eq!(publisher_task.await.unwrap(), Ok(()));
+    assert_eq!(subscriber_task.await.unwrap(), Ok(()));
+}
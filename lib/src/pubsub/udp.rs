@@ -0,0 +1,99 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the UDP transport for UADP network messages described in Part 14 7.3.2. Each network
+//! message is sent as one datagram to an `opc.udp://` address, which is normally a multicast group
+//! but can also be a unicast address.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::UdpSocket;
+
+use crate::types::status_code::StatusCode;
+
+/// The scheme of UADP over UDP urls
+pub const OPC_UDP_SCHEME: &str = "opc.udp";
+
+/// The port that is used when a url doesn't have one
+pub const DEFAULT_UADP_PORT: u16 = 4840;
+
+/// The largest datagram that can be sent or received
+pub(crate) const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// Resolves an `opc.udp://host:port` url to the address that network messages are sent to
+pub fn udp_address(url: &str) -> Result<SocketAddr, StatusCode> {
+    let host_port = url
+        .strip_prefix(OPC_UDP_SCHEME)
+        .and_then(|v| v.strip_prefix("://"))
+        .map(|v| v.trim_end_matches('/'))
+        .ok_or_else(|| {
+            error!("Url {} is not an {} url", url, OPC_UDP_SCHEME);
+            StatusCode::BadInvalidArgument
+        })?;
+    let resolved = if host_port.contains(':') {
+        host_port.to_socket_addrs()
+    } else {
+        (host_port, DEFAULT_UADP_PORT).to_socket_addrs()
+    };
+    resolved
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| {
+            error!("Cannot resolve the address of url {}", url);
+            StatusCode::BadInvalidArgument
+        })
+}
+
+/// Creates a socket that receives the datagrams sent to the address. The socket joins the group
+/// of a multicast address and shares the port with other sockets on the host so several
+/// publishers and subscribers can listen to the same group.
+pub(crate) fn bind_receiver(address: &SocketAddr) -> Result<UdpSocket, StatusCode> {
+    let socket = Socket::new(
+        Domain::for_address(*address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )
+    .and_then(|socket| {
+        socket.set_reuse_address(true)?;
+        socket.set_nonblocking(true)?;
+        if address.ip().is_multicast() {
+            // The group is joined on a socket bound to the wildcard address
+            let bind_address: SocketAddr = if address.is_ipv4() {
+                (Ipv4Addr::UNSPECIFIED, address.port()).into()
+            } else {
+                (Ipv6Addr::UNSPECIFIED, address.port()).into()
+            };
+            socket.bind(&bind_address.into())?;
+            match address.ip() {
+                IpAddr::V4(ip) => socket.join_multicast_v4(&ip, &Ipv4Addr::UNSPECIFIED)?,
+                IpAddr::V6(ip) => socket.join_multicast_v6(&ip, 0)?,
+            }
+        } else {
+            socket.bind(&(*address).into())?;
+        }
+        Ok(socket)
+    })
+    .map_err(|err| {
+        error!("Cannot bind a socket to receive from {} - {}", address, err);
+        StatusCode::BadCommunicationError
+    })?;
+    UdpSocket::from_std(socket.into()).map_err(|err| {
+        error!("Cannot register the socket for {} - {}", address, err);
+        StatusCode::BadCommunicationError
+    })
+}
+
+/// Creates a socket that sends datagrams to the address
+pub(crate) async fn bind_sender(address: &SocketAddr) -> Result<UdpSocket, StatusCode> {
+    let bind_address: SocketAddr = if address.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    UdpSocket::bind(bind_address).await.map_err(|err| {
+        error!("Cannot bind a socket to send to {} - {}", address, err);
+        StatusCode::BadCommunicationError
+    })
+}
@@ -3,29 +3,40 @@
 // Copyright (C) 2017-2024 Adam Lock
 
 //! Implementation of `AddressSpace`.
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
 
 use chrono::Utc;
 
+use crate::crypto::random;
 use crate::sync::*;
 use crate::types::{
     node_ids::VariableId::*,
-    service_types::{BrowseDirection, CallMethodRequest, CallMethodResult, NodeClass},
+    service_types::{
+        BrowseDirection, CallMethodRequest, CallMethodResult, ModelChangeStructureDataType,
+        ModelChangeStructureVerbMask, NodeClass,
+    },
     status_code::StatusCode,
     *,
 };
 
 use crate::server::{
     address_space::{
-        node::{HasNodeId, NodeType},
+        node::{HasNodeId, NodeBase, NodeType},
+        nodeset,
         object::{Object, ObjectBuilder},
-        references::{Reference, ReferenceDirection, References},
-        variable::Variable,
-        AttrFnGetter,
+        references::{Reference, ReferenceDirection, References, RemoteReference},
+        relative_path::find_node_from_browse_path,
+        variable::{Variable, VariableBuilder},
+        view::ViewBuilder,
+        AttrFnGetter, EventNotifier,
     },
-    callbacks, constants,
+    callbacks,
+    config::OperationalLimits,
+    constants,
     diagnostics::ServerDiagnostics,
+    events::{event::Event, model_change_event::GeneralModelChangeEventType},
     historical::HistoryServerCapabilities,
     session::SessionManager,
     state::ServerState,
@@ -123,6 +134,23 @@ macro_rules! server_diagnostics_summary {
     };
 }
 
+/// Gets a field from the live status of the server.
+macro_rules! server_status {
+    ($address_space: expr, $server_state: expr, $variable_id: expr, |$status: ident| $value: expr) => {
+        let server_state = $server_state.clone();
+        $address_space.set_variable_getter(
+            $variable_id,
+            move |_, timestamps_to_return, _, _, _, _| {
+                let $status = trace_read_lock!(server_state).server_status();
+                let mut value = DataValue::from(Variant::from($value));
+                let now = DateTime::now();
+                value.set_timestamps(timestamps_to_return, now, now);
+                Ok(Some(value))
+            },
+        );
+    };
+}
+
 pub(crate) type MethodCallback = Box<dyn callbacks::Method + Send + Sync>;
 
 const OPCUA_INTERNAL_NAMESPACE_IDX: u16 = 1;
@@ -133,6 +161,45 @@ struct MethodKey {
     method_id: NodeId,
 }
 
+/// The ids of the variables holding the call statistics of a method
+struct MethodStatistics {
+    call_count: NodeId,
+    last_call_time: NodeId,
+    last_call_status: NodeId,
+}
+
+/// The properties and the pending write of a variable whose writes must be confirmed
+struct WriteConfirmation {
+    /// How long a pending write waits to be confirmed
+    timeout: chrono::Duration,
+    pending_value: NodeId,
+    confirmation_token: NodeId,
+    confirm_write: NodeId,
+    pending_write: Option<PendingWrite>,
+}
+
+/// A value written to a variable that hasn't been confirmed yet
+struct PendingWrite {
+    /// The session that wrote the value, which is the only one that can confirm it
+    session_id: NodeId,
+    value: Variant,
+    token: u32,
+    expires_at: DateTimeUtc,
+}
+
+/// Changes to the nodes and references of the address space that haven't been reported in a
+/// model change event yet
+#[derive(Default)]
+struct ModelChanges {
+    /// The affected nodes in the order they first changed, each with the verbs of all its changes
+    changes: Vec<ModelChangeStructureDataType>,
+    /// The index of each affected node in `changes`
+    indexes: HashMap<NodeId, usize>,
+}
+
+/// How long model change events are kept in the address space after they are raised
+const MODEL_CHANGE_EVENT_LIFETIME_SECS: i64 = 60;
+
 /// The `AddressSpace` describes all of the nodes managed by the server and the references between
 /// them. Usually it will be populated with the default OPC UA node set plus any that have been
 /// added by the server.
@@ -175,6 +242,20 @@ pub struct AddressSpace {
     internal_namespace: u16,
     /// The list of all registered namespaces.
     namespaces: Vec<String>,
+    /// The server array, i.e. the uris of this server and the servers that remote references
+    /// point to. The index of a uri is the server index of an `ExpandedNodeId`.
+    servers: Vec<String>,
+    /// Call statistics of methods that have them, by method id
+    method_statistics: HashMap<NodeId, MethodStatistics>,
+    /// Variables whose writes must be confirmed, by variable id
+    write_confirmations: HashMap<NodeId, WriteConfirmation>,
+    /// Nodes that are deleted when the session that owns them closes, by session id
+    session_nodes: HashMap<NodeId, HashSet<NodeId>>,
+    /// Changes to nodes and references that haven't been reported yet, or `None` when changes
+    /// are not tracked
+    model_changes: Option<ModelChanges>,
+    /// The model change events that have been raised, oldest first, with the time of each
+    model_change_events: VecDeque<(DateTimeUtc, NodeId)>,
 }
 
 impl Default for AddressSpace {
@@ -191,6 +272,13 @@ impl Default for AddressSpace {
             // OPC UA namespace for its standard nodes. The second is the internal namespace used
             // by this implementation.
             namespaces: vec!["http://opcfoundation.org/UA/".to_string()],
+            // The first server is always this server. Its uri is set with the server state.
+            servers: vec![String::new()],
+            method_statistics: HashMap::new(),
+            write_confirmations: HashMap::new(),
+            session_nodes: HashMap::new(),
+            model_changes: None,
+            model_change_events: VecDeque::new(),
         }
     }
 }
@@ -248,15 +336,120 @@ impl AddressSpace {
             .map(|i| i as u16)
     }
 
+    /// Returns the namespace array. The index of a namespace is its position in the array.
+    pub fn namespaces(&self) -> &[String] {
+        &self.namespaces
+    }
+
+    /// Makes a node id from a namespace uri and an identifier, so a node can be addressed without
+    /// hard coding the index of its namespace. Returns `None` if the namespace is not registered.
+    pub fn namespace_node_id<T>(&self, namespace: &str, value: T) -> Option<NodeId>
+    where
+        T: 'static + Into<Identifier>,
+    {
+        self.namespace_index(namespace)
+            .map(|ns| NodeId::new(ns, value))
+    }
+
+    /// Resolves an expanded node id to a node id in this address space. A namespace uri in the
+    /// expanded node id takes the place of its namespace index. Returns `None` if the node id
+    /// refers to another server or to a namespace that is not registered.
+    pub fn resolve_expanded_node_id(&self, node_id: &ExpandedNodeId) -> Option<NodeId> {
+        node_id.to_node_id(&self.namespaces)
+    }
+
+    /// Resolves an expanded node id that may refer to a node held by another server, such as the
+    /// target of a reference sent by a client. A server uri, if it is not null, takes the place of
+    /// the server index. A node in this address space is returned with its namespace index and
+    /// server index 0, while a node held by another server is returned unchanged apart from its
+    /// server index.
+    ///
+    /// Fails with `BadServerUriInvalid` if the server uri is not in the server array,
+    /// `BadServerIndexInvalid` if the server index is not in the server array and
+    /// `BadNodeIdUnknown` if a local node id has a namespace uri that is not registered.
+    pub fn resolve_remote_node_id(
+        &self,
+        node_id: &ExpandedNodeId,
+        server_uri: &UAString,
+    ) -> Result<ExpandedNodeId, StatusCode> {
+        let server_index = if server_uri.is_null() {
+            node_id.server_index
+        } else {
+            self.server_index(server_uri.as_ref())
+                .ok_or(StatusCode::BadServerUriInvalid)?
+        };
+        if server_index == 0 {
+            let local = ExpandedNodeId {
+                server_index: 0,
+                ..node_id.clone()
+            };
+            self.resolve_expanded_node_id(&local)
+                .map(ExpandedNodeId::from)
+                .ok_or(StatusCode::BadNodeIdUnknown)
+        } else if server_index as usize >= self.servers.len() {
+            Err(StatusCode::BadServerIndexInvalid)
+        } else {
+            Ok(ExpandedNodeId {
+                server_index,
+                ..node_id.clone()
+            })
+        }
+    }
+
+    /// Registers a server described by its application uri in the server array, so that remote
+    /// references can point to nodes held by it. The return code is the server index of the
+    /// server. Registering a server that is already registered will return the previous index.
+    pub fn register_server(&mut self, server_uri: &str) -> Result<u32, StatusCode> {
+        if server_uri.is_empty() {
+            Err(StatusCode::BadServerUriInvalid)
+        } else if self.servers.len() == u32::MAX as usize {
+            Err(StatusCode::BadTooManyOperations)
+        } else if let Some(i) = self.server_index(server_uri) {
+            Ok(i)
+        } else {
+            self.servers.push(server_uri.into());
+            self.set_server_array(&DateTime::now());
+            Ok((self.servers.len() - 1) as u32)
+        }
+    }
+
+    /// Finds the server index of a given server uri
+    pub fn server_index(&self, server_uri: &str) -> Option<u32> {
+        self.servers
+            .iter()
+            .position(|s| s == server_uri)
+            .map(|i| i as u32)
+    }
+
+    /// Returns the server array. The first entry is this server.
+    pub fn servers(&self) -> &[String] {
+        &self.servers
+    }
+
     fn set_servers(&mut self, server_state: Arc<RwLock<ServerState>>, now: &DateTime) {
-        let server_state = trace_read_lock!(server_state);
+        {
+            let server_state = trace_read_lock!(server_state);
+            // The servers of the server state come first, servers registered for remote
+            // references keep their place after them
+            server_state
+                .servers
+                .iter()
+                .enumerate()
+                .for_each(|(i, server_uri)| {
+                    if i == 0 {
+                        self.servers[0] = server_uri.clone();
+                    } else if !self.servers.contains(server_uri) {
+                        self.servers.push(server_uri.clone());
+                    }
+                });
+        }
+        self.set_server_array(now);
+    }
+
+    fn set_server_array(&mut self, now: &DateTime) {
+        let value = Variant::from(&self.servers);
         if let Some(ref mut v) = self.find_variable_mut(Server_ServerArray) {
-            let _ = v.set_value_direct(
-                Variant::from(&server_state.servers),
-                StatusCode::Good,
-                now,
-                now,
-            );
+            let _ = v.set_value_direct(value, StatusCode::Good, now, now);
         }
     }
 
@@ -272,6 +465,82 @@ impl AddressSpace {
         self.set_variable_value(Server_ServiceLevel, service_level, now, now);
     }
 
+    /// Sets the values of `Server/ServerCapabilities/OperationLimits` to the server's limits
+    pub(crate) fn set_operational_limits(&mut self, ol: &OperationalLimits, now: &DateTime) {
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerRead,
+            ol.max_nodes_per_read as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerWrite,
+            ol.max_nodes_per_write as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerMethodCall,
+            ol.max_nodes_per_method_call as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerBrowse,
+            ol.max_nodes_per_browse as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerRegisterNodes,
+            ol.max_nodes_per_register_nodes as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerTranslateBrowsePathsToNodeIds,
+            ol.max_nodes_per_translate_browse_paths_to_node_ids as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerNodeManagement,
+            ol.max_nodes_per_node_management as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxMonitoredItemsPerCall,
+            ol.max_monitored_items_per_call as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryReadData,
+            ol.max_nodes_per_history_read_data as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryReadEvents,
+            ol.max_nodes_per_history_read_events as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryUpdateData,
+            ol.max_nodes_per_history_update_data as u32,
+            now,
+            now,
+        );
+        self.set_variable_value(
+            Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryUpdateEvents,
+            ol.max_nodes_per_history_update_events as u32,
+            now,
+            now,
+        );
+    }
+
     /// Sets values for nodes representing the server.
     pub fn set_server_state(&mut self, server_state: Arc<RwLock<ServerState>>) {
         // Server state requires the generated address space, otherwise nothing
@@ -347,166 +616,65 @@ impl AddressSpace {
                     &now,
                 );
 
-                let ol = &server_state.operational_limits;
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerRead,
-                    ol.max_nodes_per_read as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerWrite,
-                    ol.max_nodes_per_write as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerMethodCall,
-                    ol.max_nodes_per_method_call as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerBrowse,
-                    ol.max_nodes_per_browse as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerRegisterNodes,
-                    ol.max_nodes_per_register_nodes as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(Server_ServerCapabilities_OperationLimits_MaxNodesPerTranslateBrowsePathsToNodeIds, ol.max_nodes_per_translate_browse_paths_to_node_ids as u32, &now, &now);
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerNodeManagement,
-                    ol.max_nodes_per_node_management as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxMonitoredItemsPerCall,
-                    ol.max_monitored_items_per_call as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryReadData,
-                    ol.max_nodes_per_history_read_data as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryReadEvents,
-                    ol.max_nodes_per_history_read_events as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryUpdateData,
-                    ol.max_nodes_per_history_update_data as u32,
-                    &now,
-                    &now,
-                );
-                self.set_variable_value(
-                    Server_ServerCapabilities_OperationLimits_MaxNodesPerHistoryUpdateEvents,
-                    ol.max_nodes_per_history_update_events as u32,
-                    &now,
-                    &now,
-                );
+                self.set_operational_limits(&server_state.operational_limits, &now);
+
+                // Subscription limits are capabilities of the server added by OPC UA 1.05, which
+                // are missing from the standard nodeset so they are added to the server namespace
+                let ns = self.default_namespace;
+                for (name, value) in [
+                    (
+                        "MaxSubscriptions",
+                        server_config.limits.max_server_subscriptions,
+                    ),
+                    (
+                        "MaxSubscriptionsPerSession",
+                        server_config.limits.max_subscriptions,
+                    ),
+                    (
+                        "MaxMonitoredItemsPerSubscription",
+                        server_config.limits.max_monitored_items_per_sub,
+                    ),
+                ] {
+                    VariableBuilder::new(
+                        &NodeId::new(ns, format!("ServerCapabilities_{}", name)),
+                        QualifiedName::new(0, name),
+                        name,
+                    )
+                    .property_of(ObjectId::Server_ServerCapabilities)
+                    .has_type_definition(VariableTypeId::PropertyType)
+                    .data_type(DataTypeId::UInt32)
+                    .value(value as u32)
+                    .insert(self);
+                }
+                VariableBuilder::new(
+                    &NodeId::new(ns, "ServerCapabilities_MinPublishingInterval"),
+                    QualifiedName::new(ns, "MinPublishingInterval"),
+                    "MinPublishingInterval",
+                )
+                .property_of(ObjectId::Server_ServerCapabilities)
+                .has_type_definition(VariableTypeId::PropertyType)
+                .data_type(DataTypeId::Duration)
+                .value(server_state.min_publishing_interval_ms)
+                .insert(self);
             }
 
             // Server_ServerCapabilities_ServerProfileArray
-            if let Some(ref mut v) =
-                self.find_variable_mut(Server_ServerCapabilities_ServerProfileArray)
             {
-                // Declares what the server implements. Subitems are implied by the profile. A subitem
-                // marked - is optional to the spec
-                let server_profiles = [
-                    // Base server behaviour
-                    //  SecurityPolicy - None
-                    //  User Token - User Name Password Server Facet
-                    //  Address Space Base
-                    //  AttributeRead
-                    //  -Attribute Write Index
-                    //  -Attribute Write Values
-                    //  Base Info Core Structure
-                    //  -Base Info OptionSet
-                    //  -Base Info Placeholder Modelling Rules
-                    //  -Base Info ValueAsText
-                    //  Discovery Find Servers Self
-                    //  Discovery Get Endpoints
-                    //  -Security - No Application Authentications
-                    //  -Security - Security Administration
-                    //   Session Base
-                    //  Session General Service Behaviour
-                    //  Session Minimum 1
-                    //  View Basic
-                    //  View Minimum Continuation Point 01
-                    //  View RegisterNodes
-                    //  View TranslateBrowsePath
-                    "http://opcfoundation.org/UA-Profile/Server/Behaviour",
-                    // Embedded UA server
-                    //   SecurityPolicy - Basic128Rsa15
-                    //     Security
-                    //       - Security Certificate Validation
-                    //       - Security Basic 128Rsa15
-                    //       - Security Encryption Required
-                    //       - Security Signing Required
-                    //   Standard DataChange Subscription Server Facet
-                    //     Base Information
-                    //       - Base Info GetMonitoredItems Method
-                    //     Monitored Item Services
-                    //       - Monitored Items Deadband Filter
-                    //       - Monitor Items 10
-                    //       - Monitor Items 100
-                    //       - Monitor MinQueueSize_02
-                    //       - Monitor Triggering
-                    //     Subscription Services
-                    //       - Subscription Minimum 02
-                    //       - Subscription Publish Min 05
-                    //     Method Services
-                    //       - Method call
-                    //   User Token - X509 Certificate Server Facet
-                    //       - Security User X509 - Server supports public / private key pair for user identity
-                    //   Micro Embedded Device Server Profile
-                    // Base Information
-                    //   - Base Info Type System - Exposes a Type system with DataTypes, ReferenceTypes, ObjectTypes and VariableTypes
-                    //     including all of OPC UA namespace (namespace 0) types that are used by the Server as defined in Part 6.
-                    //   - Base Info Placeholder Modelling Rules - The server supports defining cusom Object or Variables that include the use of OptionalPlaceholder
-                    //     or MandatoryPlaceholder modelling rules
-                    //   - Base Info Engineering Units - The server supports defining Variables that include the Engineering Units property
-                    // Security
-                    //  Security Default ApplicationInstanceCertificate - has a default ApplicationInstanceCertificate that is valid
-                    "http://opcfoundation.org/UA-Profile/Server/EmbeddedUA",
-                    // TODO server profile
-                    // Standard UA Server Profile
-                    //   Enhanced DataChange Subscription Server Facet
-                    //     Monitored Item Services
-                    //       - Monitor Items 500 - Support at least 500 MonitoredItems per Subscription
-                    //       - Monitor MinQueueSize_05 - Support at least 5 queue entries
-                    //     Subscription Services
-                    //       - Subscription Minimum 05 - Support at least 5 subscriptions per Session
-                    //       - Subscription Publish Min 10 - Support at least Publish service requests per session
-                    //   Embedded UA Server Profile
-                    // Base Information
-                    //   - Base Info Diagnostics
-                    // Discovery Services
-                    //   - Discovery Register (be able to call RegisterServer)
-                    //   - Discovery Register2 (be able to call RegisterServer2)
-                    // Session Services
-                    //   - Session Change User - Support use of ActivateSession to change the Session user
-                    //   - Session Cancel - Support the Cancel Service to cancel outstanding requests
-                    //   - Session Minimum 50 Parallel - Support minimum 50 parallel Sessions
-                    //
-                    // "http://opcfoundation.org/UA-Profile/Server/StandardUA",
-                ];
-                let _ = v.set_value_direct(
-                    Variant::from((VariantTypeId::String, &server_profiles[..])),
-                    StatusCode::Good,
-                    &now,
-                    &now,
+                // Declares what the server implements. The list is computed when it is read since
+                // providers, e.g. for historical data, may be set after the address space.
+                let server_state = server_state.clone();
+                self.set_variable_getter(
+                    Server_ServerCapabilities_ServerProfileArray,
+                    move |_, timestamps_to_return, _, _, _, _| {
+                        let server_profiles = trace_read_lock!(server_state).server_profiles();
+                        let mut value = DataValue::from(Variant::from((
+                            VariantTypeId::String,
+                            &server_profiles[..],
+                        )));
+                        let now = DateTime::now();
+                        value.set_timestamps(timestamps_to_return, now, now);
+                        Ok(Some(value))
+                    },
                 );
             }
 
@@ -517,6 +685,21 @@ impl AddressSpace {
             {
                 let server_state = trace_read_lock!(server_state);
                 self.server_diagnostics = Some(server_state.diagnostics.clone());
+                let server_diagnostics = server_state.diagnostics.clone();
+                self.set_variable_getter(
+                    Server_ServerDiagnostics_ServerDiagnosticsSummary,
+                    move |_, timestamps_to_return, _, _, _, _| {
+                        let server_diagnostics = server_diagnostics.read();
+                        let mut value =
+                            DataValue::from(Variant::from(ExtensionObject::from_encodable(
+                                ObjectId::ServerDiagnosticsSummaryDataType_Encoding_DefaultBinary,
+                                server_diagnostics.server_diagnostics_summary(),
+                            )));
+                        let now = DateTime::now();
+                        value.set_timestamps(timestamps_to_return, now, now);
+                        Ok(Some(value))
+                    },
+                );
                 server_diagnostics_summary!(
                     self,
                     Server_ServerDiagnostics_ServerDiagnosticsSummary_ServerViewCount,
@@ -579,50 +762,108 @@ impl AddressSpace {
                 );
             }
 
+            // Server_ServerDiagnostics_EnabledFlag
+            self.set_variable_value(Server_ServerDiagnostics_EnabledFlag, true, &now, &now);
+
             // ServiceLevel - 0-255 worst to best quality of service
             self.set_service_level(255u8, &now);
 
-            // Auditing - var
+            // Auditing
+            let auditing = {
+                let server_state = trace_read_lock!(server_state);
+                server_state.is_auditing()
+            };
+            self.set_variable_value(Server_Auditing, auditing, &now, &now);
+
             // ServerDiagnostics
             // VendorServiceInfo
             // ServerRedundancy
 
-            // Server_ServerStatus_StartTime
-            self.set_variable_value(Server_ServerStatus_StartTime, now, &now, &now);
-
-            // Server_ServerStatus_CurrentTime
-            self.set_variable_getter(
+            // Server_ServerStatus
+            server_status!(self, server_state, Server_ServerStatus, |status| {
+                ExtensionObject::from_encodable(
+                    ObjectId::ServerStatusDataType_Encoding_DefaultBinary,
+                    &status,
+                )
+            });
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_StartTime,
+                |status| status.start_time
+            );
+            server_status!(
+                self,
+                server_state,
                 Server_ServerStatus_CurrentTime,
-                move |_, timestamps_to_return, _, _, _, _| {
-                    let now = DateTime::now();
-                    let mut value = DataValue::from(now);
-                    value.set_timestamps(timestamps_to_return, now, now);
-                    Ok(Some(value))
-                },
+                |status| status.current_time
             );
-
-            // State OPC UA Part 5 12.6, Valid states are
-            //     State (Server_ServerStatus_State)
-            self.set_variable_getter(
+            // State OPC UA Part 5 12.6
+            server_status!(
+                self,
+                server_state,
                 Server_ServerStatus_State,
-                move |_, timestamps_to_return, _, _, _, _| {
-                    // let server_state =  trace_read_lock!(server_state);
-                    let now = DateTime::now();
-                    let mut value = DataValue::from(0i32);
-                    value.set_timestamps(timestamps_to_return, now, now);
-                    Ok(Some(value))
-                },
+                |status| status.state as i32
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_SecondsTillShutdown,
+                |status| status.seconds_till_shutdown
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_ShutdownReason,
+                |status| status.shutdown_reason
             );
 
             // ServerStatus_BuildInfo
-            {
-                //    BuildDate
-                //    BuildNumber
-                //    ManufacturerName
-                //    ProductName
-                //    ProductUri
-                //    SoftwareVersion
-            }
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo,
+                |status| ExtensionObject::from_encodable(
+                    ObjectId::BuildInfo_Encoding_DefaultBinary,
+                    &status.build_info,
+                )
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_BuildDate,
+                |status| status.build_info.build_date
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_BuildNumber,
+                |status| status.build_info.build_number
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_ManufacturerName,
+                |status| status.build_info.manufacturer_name
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_ProductName,
+                |status| status.build_info.product_name
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_ProductUri,
+                |status| status.build_info.product_uri
+            );
+            server_status!(
+                self,
+                server_state,
+                Server_ServerStatus_BuildInfo_SoftwareVersion,
+                |status| status.build_info.software_version
+            );
 
             // Server method handlers
             use crate::server::address_space::method_impls;
@@ -634,6 +875,12 @@ impl AddressSpace {
                 MethodId::Server_GetMonitoredItems,
                 Box::new(method_impls::ServerGetMonitoredItemsMethod),
             );
+            self.register_method_handler(
+                MethodId::Server_RequestServerStateChange,
+                Box::new(method_impls::ServerRequestServerStateChangeMethod::new(
+                    server_state,
+                )),
+            );
         }
     }
 
@@ -792,6 +1039,24 @@ impl AddressSpace {
             if let Some(references) = references {
                 self.references.insert(&node_id, references);
             }
+            // The references of the new node go with its addition, so only the nodes they
+            // point to are affected by them
+            self.record_model_change(&node_id, ModelChangeStructureVerbMask::NodeAdded);
+            if let Some(references) = references {
+                let has_type_definition_id: NodeId = ReferenceTypeId::HasTypeDefinition.into();
+                references
+                    .iter()
+                    .filter(|(_, reference_type_id, direction)| {
+                        matches!(direction, ReferenceDirection::Inverse)
+                            || (*reference_type_id).clone().into() != has_type_definition_id
+                    })
+                    .for_each(|(target_node_id, _, _)| {
+                        self.record_model_change(
+                            target_node_id,
+                            ModelChangeStructureVerbMask::ReferenceAdded,
+                        )
+                    });
+            }
             self.update_last_modified();
             true
         }
@@ -811,12 +1076,70 @@ impl AddressSpace {
         }
     }
 
+    /// Imports the nodes and references of a node set in the UANodeSet XML format, such as a
+    /// companion specification, into the address space. The namespaces of the node set are
+    /// registered with the address space without changing the default namespace. Nodes that
+    /// already exist are left alone. The return code is the number of nodes that were inserted.
+    pub fn import_nodeset(&mut self, nodeset: &str) -> Result<usize, StatusCode> {
+        nodeset::import(self, nodeset)
+    }
+
+    /// Reads a node set in the UANodeSet XML format from a file and imports it into the address
+    /// space. See `import_nodeset()`.
+    pub fn import_nodeset_file<P>(&mut self, path: P) -> Result<usize, StatusCode>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let nodeset = std::fs::read_to_string(path).map_err(|err| {
+            error!("Cannot read node set file {}, {}", path.display(), err);
+            StatusCode::BadResourceUnavailable
+        })?;
+        self.import_nodeset(&nodeset)
+    }
+
+    /// Exports the nodes of the address space to a file in the UANodeSet XML format, along with
+    /// their references and the values of variables that are not supplied by a getter. Only nodes
+    /// in the namespaces of the filter are exported, or every node if there is no filter. The
+    /// return code is the number of nodes that were exported.
+    pub fn export_nodeset<P>(
+        &self,
+        path: P,
+        namespace_filter: Option<&[u16]>,
+    ) -> Result<usize, StatusCode>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let (nodeset, count) = nodeset::export(self, namespace_filter);
+        std::fs::write(path, nodeset).map_err(|err| {
+            error!("Cannot write node set file {}, {}", path.display(), err);
+            StatusCode::BadResourceUnavailable
+        })?;
+        Ok(count)
+    }
+
+    /// Returns every node in the address space, in no particular order
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = &NodeType> {
+        self.node_map.values()
+    }
+
     // Inserts a bunch of references between two nodes into the address space
     pub fn insert_references<T>(&mut self, references: &[(&NodeId, &NodeId, &T)])
     where
         T: Into<NodeId> + Clone,
     {
         self.references.insert_references(references);
+        references
+            .iter()
+            .for_each(|(node_id, target_node_id, reference_type_id)| {
+                self.record_reference_change(
+                    node_id,
+                    target_node_id,
+                    &(*reference_type_id).clone().into(),
+                    ModelChangeStructureVerbMask::ReferenceAdded,
+                )
+            });
         self.update_last_modified();
     }
 
@@ -831,9 +1154,67 @@ impl AddressSpace {
     {
         self.references
             .insert_reference(node_id, target_node_id, &reference_type_id);
+        self.record_reference_change(
+            node_id,
+            target_node_id,
+            &reference_type_id.into(),
+            ModelChangeStructureVerbMask::ReferenceAdded,
+        );
         self.update_last_modified();
     }
 
+    /// Inserts a reference between a node in the address space and a node held by another
+    /// server. The server index of the target node must be that of a registered server other
+    /// than this one.
+    pub fn insert_remote_reference<T>(
+        &mut self,
+        node_id: &NodeId,
+        target_node_id: &ExpandedNodeId,
+        reference_type_id: T,
+        reference_direction: ReferenceDirection,
+    ) -> Result<(), StatusCode>
+    where
+        T: Into<NodeId> + Clone,
+    {
+        if target_node_id.server_index == 0
+            || target_node_id.server_index as usize >= self.servers.len()
+        {
+            Err(StatusCode::BadServerIndexInvalid)
+        } else if target_node_id.is_null() || !self.node_exists(node_id) {
+            Err(StatusCode::BadNodeIdUnknown)
+        } else {
+            self.references.insert_remote_reference(
+                node_id,
+                target_node_id,
+                &reference_type_id,
+                reference_direction,
+            );
+            self.record_model_change(node_id, ModelChangeStructureVerbMask::ReferenceAdded);
+            self.update_last_modified();
+            Ok(())
+        }
+    }
+
+    /// Finds the matching reference to a node held by another server and deletes it
+    pub fn delete_remote_reference<T>(
+        &mut self,
+        node_id: &NodeId,
+        target_node_id: &ExpandedNodeId,
+        reference_type_id: T,
+    ) -> bool
+    where
+        T: Into<NodeId>,
+    {
+        let deleted =
+            self.references
+                .delete_remote_reference(node_id, target_node_id, reference_type_id);
+        if deleted {
+            self.record_model_change(node_id, ModelChangeStructureVerbMask::ReferenceDeleted);
+            self.update_last_modified();
+        }
+        deleted
+    }
+
     pub fn set_node_type<T>(&mut self, node_id: &NodeId, node_type: T)
     where
         T: Into<NodeId>,
@@ -888,6 +1269,235 @@ impl AddressSpace {
         }
     }
 
+    /// Adds a view to the Views folder using a generated node id. The view organizes the nodes,
+    /// so it contains them and everything beneath them. A client that passes the id of the view
+    /// to Browse or QueryFirst only sees those nodes. Fails with `BadNodeAttributesInvalid` if the
+    /// view cannot be inserted, e.g. because its browse name is empty.
+    pub fn add_view<R, S>(
+        &mut self,
+        browse_name: R,
+        display_name: S,
+        nodes: &[NodeId],
+    ) -> Result<NodeId, StatusCode>
+    where
+        R: Into<QualifiedName>,
+        S: Into<LocalizedText>,
+    {
+        let node_id = NodeId::next_numeric(self.default_namespace);
+        self.assert_namespace(&node_id);
+        let inserted = nodes
+            .iter()
+            .fold(
+                ViewBuilder::new(&node_id, browse_name, display_name)
+                    .organized_by(NodeId::views_folder_id()),
+                |builder, organizes_id| builder.organizes(organizes_id.clone()),
+            )
+            .insert(self);
+        if inserted {
+            Ok(node_id)
+        } else {
+            Err(StatusCode::BadNodeAttributesInvalid)
+        }
+    }
+
+    /// Finds the nodes that are in a view, which are the view itself and every node that can be
+    /// reached from it by following hierarchical references forward. Fails with
+    /// `BadViewIdUnknown` if the node is not a view.
+    pub fn find_view_nodes(&self, view_id: &NodeId) -> Result<HashSet<NodeId>, StatusCode> {
+        if !matches!(self.find_node(view_id), Some(NodeType::View(_))) {
+            return Err(StatusCode::BadViewIdUnknown);
+        }
+        let mut view_nodes = HashSet::new();
+        let mut nodes_to_visit = vec![view_id.clone()];
+        while let Some(node_id) = nodes_to_visit.pop() {
+            if view_nodes.contains(&node_id) {
+                continue;
+            }
+            if let Some(children) = self.find_hierarchical_references(&node_id) {
+                nodes_to_visit.extend(children);
+            }
+            view_nodes.insert(node_id);
+        }
+        Ok(view_nodes)
+    }
+
+    /// Creates an instance of an object type under the parent node. The mandatory children of the
+    /// type and its supertypes, i.e. those with a `Mandatory` modelling rule, are copied into the
+    /// instance along with their own mandatory children. Methods are not copied, the instance
+    /// references the method of the type so that its handler is called for the instance too.
+    ///
+    /// The instance is organized by the parent if the parent is a folder, otherwise it is a
+    /// component of the parent. Node ids are allocated in the default namespace.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node id of the new instance.
+    /// * `Err(StatusCode)` - The type is not an object type or the parent does not exist.
+    pub fn instantiate<R>(
+        &mut self,
+        type_id: &NodeId,
+        browse_name: R,
+        parent_node_id: &NodeId,
+    ) -> Result<NodeId, StatusCode>
+    where
+        R: Into<QualifiedName>,
+    {
+        if !matches!(self.find_node(type_id), Some(NodeType::ObjectType(_))) {
+            return Err(StatusCode::BadTypeDefinitionInvalid);
+        }
+        if !self.node_exists(parent_node_id) {
+            return Err(StatusCode::BadParentNodeIdInvalid);
+        }
+
+        let browse_name = browse_name.into();
+        let node_id = NodeId::next_numeric(self.default_namespace);
+        let parent_is_folder =
+            self.get_type_id(parent_node_id) == Some(ObjectTypeId::FolderType.into());
+        let builder = ObjectBuilder::new(&node_id, browse_name.clone(), browse_name.name.as_ref())
+            .has_type_definition(type_id.clone());
+        let builder = if parent_is_folder {
+            builder.organized_by(parent_node_id.clone())
+        } else {
+            builder.component_of(parent_node_id.clone())
+        };
+        builder.insert(self);
+
+        self.instantiate_declarations(type_id, &node_id);
+        Ok(node_id)
+    }
+
+    /// Copies the mandatory children declared by a type or by an instance declaration, which
+    /// includes those of its type definition and supertypes, to the instance.
+    fn instantiate_declarations(&mut self, declaration_id: &NodeId, instance_id: &NodeId) {
+        for declaration in self.instance_declarations(declaration_id) {
+            let node_id = NodeId::next_numeric(self.default_namespace);
+            let node = match self.find_node(&declaration.target_node) {
+                Some(NodeType::Method(_)) => {
+                    self.insert_reference(
+                        instance_id,
+                        &declaration.target_node,
+                        declaration.reference_type,
+                    );
+                    continue;
+                }
+                Some(node) => Self::copy_instance_node(node, &node_id),
+                None => None,
+            };
+            if let Some(node) = node {
+                let mut references = vec![(
+                    instance_id.clone(),
+                    declaration.reference_type,
+                    ReferenceDirection::Inverse,
+                )];
+                if let Some(type_id) = self.get_type_id(&declaration.target_node) {
+                    references.push((
+                        type_id,
+                        ReferenceTypeId::HasTypeDefinition.into(),
+                        ReferenceDirection::Forward,
+                    ));
+                }
+                let references = references
+                    .iter()
+                    .map(|(target, reference_type, direction)| (target, reference_type, *direction))
+                    .collect::<Vec<_>>();
+                self.insert(node, Some(&references));
+                self.instantiate_declarations(&declaration.target_node, &node_id);
+            }
+        }
+    }
+
+    /// Finds the mandatory children of a type or instance declaration. The children declared
+    /// by the node itself come first, followed by those of its type definition or supertype that
+    /// are not overridden by a child of the same browse name.
+    fn instance_declarations(&self, declaration_id: &NodeId) -> Vec<Reference> {
+        let mandatory: NodeId = ObjectId::ModellingRule_Mandatory.into();
+        let mut browse_names = Vec::new();
+        let mut declarations = Vec::new();
+        let mut visited = Vec::new();
+        let mut next = Some(declaration_id.clone());
+        while let Some(node_id) = next {
+            if visited.contains(&node_id) {
+                break;
+            }
+            if let Some(references) =
+                self.find_references(&node_id, Some((ReferenceTypeId::Aggregates, true)))
+            {
+                for reference in references {
+                    let Some(node) = self.find_node(&reference.target_node) else {
+                        continue;
+                    };
+                    let browse_name = node.as_node().browse_name();
+                    let is_mandatory = self.has_reference(
+                        &reference.target_node,
+                        &mandatory,
+                        ReferenceTypeId::HasModellingRule,
+                    );
+                    if is_mandatory && !browse_names.contains(&browse_name) {
+                        browse_names.push(browse_name);
+                        declarations.push(reference);
+                    }
+                }
+            }
+            // An instance declaration continues with its type definition, a type with its supertype
+            next = self.get_type_id(&node_id).or_else(|| {
+                self.find_inverse_references(&node_id, Some((ReferenceTypeId::HasSubtype, false)))
+                    .and_then(|references| references.first().map(|r| r.target_node.clone()))
+            });
+            visited.push(node_id);
+        }
+        declarations
+    }
+
+    /// Copies the attributes of an object or variable of an instance declaration to a new node
+    fn copy_instance_node(node: &NodeType, node_id: &NodeId) -> Option<NodeType> {
+        let node = match node {
+            NodeType::Object(object) => {
+                let mut copy = Object::new(
+                    node_id,
+                    object.browse_name(),
+                    object.display_name(),
+                    object.event_notifier(),
+                );
+                if let Some(description) = object.description() {
+                    copy.set_description(description);
+                }
+                NodeType::Object(Box::new(copy))
+            }
+            NodeType::Variable(variable) => {
+                let value = variable.value(
+                    TimestampsToReturn::Neither,
+                    NumericRange::None,
+                    &QualifiedName::null(),
+                    0.0,
+                );
+                let mut copy = Variable::new_data_value(
+                    node_id,
+                    variable.browse_name(),
+                    variable.display_name(),
+                    variable.data_type(),
+                    Some(variable.value_rank()),
+                    None,
+                    value.value.unwrap_or_default(),
+                );
+                if let Some(array_dimensions) = variable.array_dimensions() {
+                    copy.set_array_dimensions(&array_dimensions);
+                }
+                if let Some(minimum_sampling_interval) = variable.minimum_sampling_interval() {
+                    copy.set_minimum_sampling_interval(minimum_sampling_interval);
+                }
+                if let Some(description) = variable.description() {
+                    copy.set_description(description);
+                }
+                copy.set_access_level(variable.access_level());
+                copy.set_user_access_level(variable.user_access_level());
+                copy.set_historizing(variable.historizing());
+                NodeType::Variable(Box::new(copy))
+            }
+            _ => return None,
+        };
+        Some(node)
+    }
+
     /// Adds a list of variables to the specified parent node
     pub fn add_variables(
         &mut self,
@@ -921,17 +1531,91 @@ impl AddressSpace {
                 let _ = self.delete(&node_id, delete_target_references);
             });
         }
+        // The type definition of the node is reported with its deletion, so it is recorded before
+        // the references go
+        if self.node_exists(node_id) {
+            self.record_model_change(node_id, ModelChangeStructureVerbMask::NodeDeleted);
+        }
         // Remove the node
         let removed_node = self.node_map.remove(node_id);
+        if removed_node.is_some() {
+            self.session_nodes.values_mut().for_each(|nodes| {
+                nodes.remove(node_id);
+            });
+        }
         // Remove references
         let removed_target_references = if delete_target_references {
-            self.references.delete_node_references(node_id)
+            let (references, inverse_ref_idx) = if self.model_changes.is_some() {
+                self.references.find_references_by_direction::<NodeId>(
+                    node_id,
+                    BrowseDirection::Both,
+                    None,
+                )
+            } else {
+                (Vec::new(), 0)
+            };
+            let removed = self.references.delete_node_references(node_id);
+            references.iter().enumerate().for_each(|(i, r)| {
+                let (source_node_id, target_node_id) = if i < inverse_ref_idx {
+                    (node_id, &r.target_node)
+                } else {
+                    (&r.target_node, node_id)
+                };
+                self.record_reference_change(
+                    source_node_id,
+                    target_node_id,
+                    &r.reference_type,
+                    ModelChangeStructureVerbMask::ReferenceDeleted,
+                )
+            });
+            removed
         } else {
             false
         };
         removed_node.is_some() || removed_target_references
     }
 
+    /// Gives a node the lifetime of a session, so that it is deleted along with references to it
+    /// when `delete_session_nodes()` is called for the session. Returns `false` if the node
+    /// does not exist.
+    pub fn set_session_lifetime(&mut self, node_id: &NodeId, session_id: &NodeId) -> bool {
+        if self.node_exists(node_id) {
+            self.session_nodes
+                .entry(session_id.clone())
+                .or_default()
+                .insert(node_id.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Finds the nodes that have the lifetime of a session
+    pub fn find_session_nodes(&self, session_id: &NodeId) -> Vec<NodeId> {
+        self.session_nodes
+            .get(session_id)
+            .map(|nodes| nodes.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Deletes the nodes that have the lifetime of a session, their children and any references
+    /// to them. This is called when the session closes. Returns the number of nodes deleted.
+    pub fn delete_session_nodes(&mut self, session_id: &NodeId) -> usize {
+        if let Some(nodes) = self.session_nodes.remove(session_id) {
+            let deleted = nodes
+                .iter()
+                .filter(|node_id| self.delete(node_id, true))
+                .count();
+            debug!(
+                "Deleted {} nodes with the lifetime of session {}",
+                deleted, session_id
+            );
+            deleted
+        } else {
+            0
+        }
+    }
+
     /// Finds the matching reference and deletes it
     pub fn delete_reference<T>(
         &mut self,
@@ -942,8 +1626,156 @@ impl AddressSpace {
     where
         T: Into<NodeId>,
     {
-        self.references
-            .delete_reference(node_id, target_node_id, reference_type_id)
+        let reference_type_id = reference_type_id.into();
+        let deleted =
+            self.references
+                .delete_reference(node_id, target_node_id, reference_type_id.clone());
+        if deleted {
+            self.record_reference_change(
+                node_id,
+                target_node_id,
+                &reference_type_id,
+                ModelChangeStructureVerbMask::ReferenceDeleted,
+            );
+        }
+        deleted
+    }
+
+    /// Turns the tracking of changes to nodes and references on or off. While it is on, the nodes
+    /// that are added or deleted, and the nodes whose references are added or deleted, are
+    /// reported by `report_model_changes()`. The server turns it on when it starts running, so
+    /// the nodes it is set up with are not reported as changes. Turning it on lets clients
+    /// subscribe to the events of the server object, which the changes are reported from.
+    ///
+    /// The NodeManagement services report the changes they make before they respond. Changes that
+    /// server code makes are reported by the server about once a second, or straight away if it
+    /// calls `report_model_changes()` itself.
+    pub fn set_model_change_tracking(&mut self, enabled: bool) {
+        if !enabled {
+            self.model_changes = None;
+        } else if self.model_changes.is_none() {
+            self.model_changes = Some(ModelChanges::default());
+            if let Some(server) = find_node_mut!(self, &ObjectId::Server.into(), Object) {
+                server.set_event_notifier(
+                    server.event_notifier() | EventNotifier::SUBSCRIBE_TO_EVENTS,
+                );
+            }
+        }
+    }
+
+    /// Tests if changes to nodes and references are tracked
+    pub fn is_tracking_model_changes(&self) -> bool {
+        self.model_changes.is_some()
+    }
+
+    /// Adds a `NodeVersion` property to a node, so clients can tell when its references change.
+    /// The version is bumped whenever a tracked change to the node is reported. The property is
+    /// in the namespace of the node, or the default namespace for nodes in the OPC UA namespace.
+    /// Returns `false` if the node does not exist or already has a version.
+    pub fn add_node_version(&mut self, node_id: &NodeId) -> bool {
+        if !self.node_exists(node_id) || self.find_node_version(node_id).is_some() {
+            return false;
+        }
+        let ns = if node_id.namespace == 0 {
+            self.default_namespace
+        } else {
+            node_id.namespace
+        };
+        VariableBuilder::new(
+            &NodeId::next_numeric(ns),
+            QualifiedName::new(0, "NodeVersion"),
+            "NodeVersion",
+        )
+        .property_of(node_id.clone())
+        .has_type_definition(VariableTypeId::PropertyType)
+        .data_type(DataTypeId::String)
+        .value(UAString::from("1"))
+        .insert(self);
+        true
+    }
+
+    /// Reports the changes to nodes and references since the last report in a single
+    /// `GeneralModelChangeEventType` event from the server object, and bumps the `NodeVersion`
+    /// of each changed node that has one. Model change events that are older than a minute are
+    /// purged. Returns the id of the event, or `None` if nothing changed or changes are not
+    /// tracked.
+    pub fn report_model_changes(&mut self, now: &DateTimeUtc) -> Option<NodeId> {
+        let changes = self
+            .model_changes
+            .as_mut()
+            .map(|model_changes| {
+                model_changes.indexes.clear();
+                std::mem::take(&mut model_changes.changes)
+            })
+            .unwrap_or_default();
+
+        let happened_before = *now - chrono::Duration::seconds(MODEL_CHANGE_EVENT_LIFETIME_SECS);
+        while let Some((_, event_id)) = self
+            .model_change_events
+            .front()
+            .filter(|(time, _)| *time < happened_before)
+            .cloned()
+        {
+            self.model_change_events.pop_front();
+            self.untracked(|address_space| address_space.delete(&event_id, true));
+        }
+        if changes.is_empty() {
+            return None;
+        }
+
+        let time = DateTime::from(*now);
+        changes
+            .iter()
+            .for_each(|change| self.bump_node_version(&change.affected, &time));
+
+        debug!("Reporting {} model changes", changes.len());
+        let node_id = NodeId::next_numeric(self.internal_namespace);
+        let mut event = GeneralModelChangeEventType::new(node_id, time, changes)
+            .source_node(ObjectId::Server)
+            .source_name("Server")
+            .message("The nodes or references of the address space changed");
+        let event_id = event.raise(self).ok()?;
+        self.model_change_events.push_back((*now, event_id.clone()));
+        Some(event_id)
+    }
+
+    /// Calls the function with changes to nodes and references left untracked, for nodes that
+    /// are not part of the model, such as events.
+    pub(crate) fn untracked<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&mut AddressSpace) -> T,
+    {
+        let model_changes = self.model_changes.take();
+        let result = f(self);
+        self.model_changes = model_changes;
+        result
+    }
+
+    /// Finds the `NodeVersion` property of a node
+    fn find_node_version(&self, node_id: &NodeId) -> Option<NodeId> {
+        find_node_from_browse_path(self, node_id, &[QualifiedName::new(0, "NodeVersion")])
+            .ok()
+            .map(|node| node.node_id())
+    }
+
+    /// Increments the `NodeVersion` of a node, if it has one
+    fn bump_node_version(&mut self, node_id: &NodeId, now: &DateTime) {
+        let Some(node_version_id) = self.find_node_version(node_id) else {
+            return;
+        };
+        let version = match self.get_variable_value(node_version_id.clone()) {
+            Ok(DataValue {
+                value: Some(Variant::String(v)),
+                ..
+            }) => v.as_ref().parse::<u64>().map_or(1, |v| v.wrapping_add(1)),
+            _ => 1,
+        };
+        self.set_variable_value_by_ref(
+            &node_version_id,
+            UAString::from(version.to_string()),
+            now,
+            now,
+        );
     }
 
     /// Find node by something that can be turned into a node id and return a reference to it.
@@ -1043,6 +1875,31 @@ impl AddressSpace {
         }
     }
 
+    /// Sets the value of a variable together with its status code and the time the value was
+    /// taken at its source, e.g. to surface a bad quality reading from a device. The server
+    /// timestamp is set to now. Monitored items report the change if the value or the status
+    /// differs from what they last reported. The function will return false if the variable does
+    /// not exist, or the node is not a variable.
+    pub fn set_value_with<N, V>(
+        &mut self,
+        node_id: N,
+        value: V,
+        status_code: StatusCode,
+        source_timestamp: &DateTime,
+    ) -> bool
+    where
+        N: Into<NodeId>,
+        V: Into<Variant>,
+    {
+        if let Some(variable) = self.find_variable_mut(node_id) {
+            let _ =
+                variable.set_value_direct(value, status_code, &DateTime::now(), source_timestamp);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Gets a variable value with the supplied NodeId. The function will return Err if the
     /// NodeId does not exist or is not a variable.
     pub fn get_variable_value<N>(&self, node_id: N) -> Result<DataValue, ()>
@@ -1061,6 +1918,17 @@ impl AddressSpace {
             .ok_or_else(|| ())
     }
 
+    /// Gets every attribute of the node with the supplied NodeId in a [`NodeSnapshot`], or `None`
+    /// if the NodeId does not exist.
+    pub fn node_snapshot<N>(&self, node_id: N) -> Option<NodeSnapshot>
+    where
+        N: Into<NodeId>,
+    {
+        let node = self.find_node(&node_id.into())?;
+        let attributes = node.as_node().get_attributes(TimestampsToReturn::Both);
+        NodeSnapshot::from_attributes(attributes, &DecodingOptions::default()).ok()
+    }
+
     /// Registers a method callback on the specified object id and method id
     pub fn register_method_handler<N>(&mut self, method_id: N, handler: MethodCallback)
     where
@@ -1145,13 +2013,296 @@ impl AddressSpace {
         }
     }
 
+    /// Adds call statistics to a method, so that operators can supervise its use. The method gets
+    /// `CallCount`, `LastCallTime` and `LastCallStatus` properties which are updated whenever a
+    /// client calls it, whether or not the call succeeds. The properties are in the namespace of
+    /// the method, or the default namespace for methods in the OPC UA namespace. Returns `false`
+    /// if the node is not a method or already has statistics.
+    pub fn add_method_statistics(&mut self, method_id: &NodeId) -> bool {
+        if !matches!(self.find_node(method_id), Some(NodeType::Method(_)))
+            || self.method_statistics.contains_key(method_id)
+        {
+            return false;
+        }
+        // Standard methods get their statistics in the default namespace so the ids cannot
+        // clash with standard nodes
+        let ns = if method_id.namespace == 0 {
+            self.default_namespace
+        } else {
+            method_id.namespace
+        };
+        let statistics = MethodStatistics {
+            call_count: NodeId::next_numeric(ns),
+            last_call_time: NodeId::next_numeric(ns),
+            last_call_status: NodeId::next_numeric(ns),
+        };
+        for (node_id, name, data_type, value) in [
+            (
+                &statistics.call_count,
+                "CallCount",
+                DataTypeId::UInt32,
+                Variant::from(0u32),
+            ),
+            (
+                &statistics.last_call_time,
+                "LastCallTime",
+                DataTypeId::UtcTime,
+                Variant::from(DateTime::null()),
+            ),
+            (
+                &statistics.last_call_status,
+                "LastCallStatus",
+                DataTypeId::StatusCode,
+                Variant::from(StatusCode::Good),
+            ),
+        ] {
+            VariableBuilder::new(node_id, QualifiedName::new(ns, name), name)
+                .property_of(method_id.clone())
+                .has_type_definition(VariableTypeId::PropertyType)
+                .data_type(data_type)
+                .value(value)
+                .insert(self);
+        }
+        self.method_statistics.insert(method_id.clone(), statistics);
+        true
+    }
+
+    /// Records a call to a method that has call statistics
+    fn update_method_statistics(&mut self, method_id: &NodeId, status_code: StatusCode) {
+        let Some(statistics) = self.method_statistics.get(method_id) else {
+            return;
+        };
+        let (call_count_id, last_call_time_id, last_call_status_id) = (
+            statistics.call_count.clone(),
+            statistics.last_call_time.clone(),
+            statistics.last_call_status.clone(),
+        );
+        let now = DateTime::now();
+        let call_count = match self.get_variable_value(call_count_id.clone()) {
+            Ok(DataValue {
+                value: Some(Variant::UInt32(v)),
+                ..
+            }) => v.wrapping_add(1),
+            _ => 1,
+        };
+        self.set_variable_value_by_ref(&call_count_id, call_count, &now, &now);
+        self.set_variable_value_by_ref(&last_call_time_id, now, &now, &now);
+        self.set_variable_value_by_ref(&last_call_status_id, status_code, &now, &now);
+    }
+
+    /// Makes writes to the value of a variable take two steps, for values such as setpoints that
+    /// must not change by mistake. A write only holds the value as pending and succeeds with
+    /// `GoodCompletesAsynchronously`. The variable gets a `PendingValue` property with the value
+    /// and a `ConfirmationToken` property with the token of the write, 0 when there is none. The
+    /// session that wrote the value confirms it by writing the token to the `ConfirmWrite`
+    /// property, which sets the value. A write that isn't confirmed within the timeout is rolled
+    /// back, i.e. the variable keeps its value. The properties are in the namespace of the
+    /// variable, or the default namespace for variables in the OPC UA namespace. Returns `false`
+    /// if the node is not a variable or its writes are already confirmed.
+    pub fn add_write_confirmation(
+        &mut self,
+        variable_id: &NodeId,
+        timeout: std::time::Duration,
+    ) -> bool {
+        let Some(data_type) = self
+            .find_variable_by_ref(variable_id)
+            .map(|variable| variable.data_type())
+        else {
+            return false;
+        };
+        if self.write_confirmations.contains_key(variable_id) {
+            return false;
+        }
+        let ns = if variable_id.namespace == 0 {
+            self.default_namespace
+        } else {
+            variable_id.namespace
+        };
+        let confirmation = WriteConfirmation {
+            timeout: chrono::Duration::from_std(timeout).unwrap_or(chrono::Duration::max_value()),
+            pending_value: NodeId::next_numeric(ns),
+            confirmation_token: NodeId::next_numeric(ns),
+            confirm_write: NodeId::next_numeric(ns),
+            pending_write: None,
+        };
+        VariableBuilder::new(
+            &confirmation.pending_value,
+            QualifiedName::new(ns, "PendingValue"),
+            "PendingValue",
+        )
+        .property_of(variable_id.clone())
+        .has_type_definition(VariableTypeId::PropertyType)
+        .data_type(data_type)
+        .value(Variant::Empty)
+        .insert(self);
+        VariableBuilder::new(
+            &confirmation.confirmation_token,
+            QualifiedName::new(ns, "ConfirmationToken"),
+            "ConfirmationToken",
+        )
+        .property_of(variable_id.clone())
+        .has_type_definition(VariableTypeId::PropertyType)
+        .data_type(DataTypeId::UInt32)
+        .value(0u32)
+        .insert(self);
+        VariableBuilder::new(
+            &confirmation.confirm_write,
+            QualifiedName::new(ns, "ConfirmWrite"),
+            "ConfirmWrite",
+        )
+        .property_of(variable_id.clone())
+        .has_type_definition(VariableTypeId::PropertyType)
+        .data_type(DataTypeId::UInt32)
+        .value(0u32)
+        .writable()
+        .insert(self);
+        self.write_confirmations
+            .insert(variable_id.clone(), confirmation);
+        true
+    }
+
+    /// Tests if the value of the node is written in two steps, either because it is a variable
+    /// whose writes must be confirmed or because it is the `ConfirmWrite` property of one.
+    pub(crate) fn is_confirmed_write(&self, node_id: &NodeId) -> bool {
+        self.write_confirmations.contains_key(node_id)
+            || self
+                .write_confirmations
+                .values()
+                .any(|confirmation| &confirmation.confirm_write == node_id)
+    }
+
+    /// Writes the value of a node for which `is_confirmed_write()` is true. A value written to the
+    /// variable becomes its pending write, replacing any earlier one. A token written to its
+    /// `ConfirmWrite` property confirms the pending write with that token, if it belongs to the
+    /// session, and sets the value of the variable.
+    pub(crate) fn write_confirmed_value(
+        &mut self,
+        session_id: &NodeId,
+        node_id: &NodeId,
+        value: &Variant,
+    ) -> StatusCode {
+        let now = Utc::now();
+        self.expire_pending_writes(&now);
+        if let Some(confirmation) = self.write_confirmations.get_mut(node_id) {
+            let mut token = [0u8; 4];
+            while token == [0u8; 4] {
+                random::bytes(&mut token);
+            }
+            let token = u32::from_le_bytes(token);
+            confirmation.pending_write = Some(PendingWrite {
+                session_id: session_id.clone(),
+                value: value.clone(),
+                token,
+                expires_at: now + confirmation.timeout,
+            });
+            let (pending_value_id, confirmation_token_id) = (
+                confirmation.pending_value.clone(),
+                confirmation.confirmation_token.clone(),
+            );
+            let now = DateTime::from(now);
+            self.set_variable_value_by_ref(&pending_value_id, value.clone(), &now, &now);
+            self.set_variable_value_by_ref(&confirmation_token_id, token, &now, &now);
+            debug!("Write to {} is pending until it is confirmed", node_id);
+            return StatusCode::GoodCompletesAsynchronously;
+        }
+
+        let Some((variable_id, confirmation)) = self
+            .write_confirmations
+            .iter_mut()
+            .find(|(_, confirmation)| &confirmation.confirm_write == node_id)
+        else {
+            return StatusCode::BadNodeIdUnknown;
+        };
+        let Variant::UInt32(token) = value else {
+            return StatusCode::BadTypeMismatch;
+        };
+        let status_code = match confirmation.pending_write {
+            None => StatusCode::BadInvalidState,
+            Some(ref pending_write) if &pending_write.session_id != session_id => {
+                StatusCode::BadUserAccessDenied
+            }
+            Some(ref pending_write) if pending_write.token != *token => {
+                StatusCode::BadInvalidArgument
+            }
+            Some(_) => StatusCode::Good,
+        };
+        if status_code.is_bad() {
+            error!(
+                "Write to {} cannot be confirmed, {}",
+                variable_id, status_code
+            );
+            return status_code;
+        }
+        let variable_id = variable_id.clone();
+        let pending_write = self.clear_pending_write(&variable_id).unwrap();
+        match self.find_variable_mut_by_ref(&variable_id) {
+            Some(variable) => match variable.set_value(NumericRange::None, pending_write.value) {
+                Ok(_) => StatusCode::Good,
+                Err(status_code) => status_code,
+            },
+            None => StatusCode::BadNodeIdUnknown,
+        }
+    }
+
+    /// Rolls back the pending writes that haven't been confirmed in time
+    pub(crate) fn expire_pending_writes(&mut self, now: &DateTimeUtc) {
+        let expired = self
+            .write_confirmations
+            .iter()
+            .filter(|(_, confirmation)| {
+                confirmation
+                    .pending_write
+                    .as_ref()
+                    .is_some_and(|pending_write| pending_write.expires_at <= *now)
+            })
+            .map(|(variable_id, _)| variable_id.clone())
+            .collect::<Vec<_>>();
+        for variable_id in expired {
+            info!(
+                "Write to {} was not confirmed in time so it is rolled back",
+                variable_id
+            );
+            self.clear_pending_write(&variable_id);
+        }
+    }
+
+    /// Removes the pending write of a variable and resets its properties
+    fn clear_pending_write(&mut self, variable_id: &NodeId) -> Option<PendingWrite> {
+        let confirmation = self.write_confirmations.get_mut(variable_id)?;
+        let pending_write = confirmation.pending_write.take()?;
+        let (pending_value_id, confirmation_token_id) = (
+            confirmation.pending_value.clone(),
+            confirmation.confirmation_token.clone(),
+        );
+        let now = DateTime::now();
+        self.set_variable_value_by_ref(&pending_value_id, Variant::Empty, &now, &now);
+        self.set_variable_value_by_ref(&confirmation_token_id, 0u32, &now, &now);
+        Some(pending_write)
+    }
+
     /// Calls a method node with the supplied request and expecting a result.
     ///
     /// Calls require a registered handler to handle the method. If there is no handler, or if
     /// the request refers to a non existent object / method, the function will return an error.
     pub fn call_method(
         &mut self,
-        _server_state: &ServerState,
+        session_id: &NodeId,
+        session_manager: Arc<RwLock<SessionManager>>,
+        request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+        let result = self.invoke_method(session_id, session_manager, request);
+        if self.method_statistics.contains_key(&request.method_id) {
+            let status_code = match result {
+                Ok(ref result) => result.status_code,
+                Err(status_code) => status_code,
+            };
+            self.update_method_statistics(&request.method_id, status_code);
+        }
+        result
+    }
+
+    fn invoke_method(
+        &mut self,
         session_id: &NodeId,
         session_manager: Arc<RwLock<SessionManager>>,
         request: &CallMethodRequest,
@@ -1230,7 +2381,7 @@ impl AddressSpace {
                         {
                             // Type definition must find the sought after type
                             type_refs.iter().any(|r| {
-                                include_subtypes && self.is_subtype(&node_type_id, &r.target_node)
+                                include_subtypes && self.is_subtype(&r.target_node, &node_type_id)
                                     || r.target_node == node_type_id
                             })
                         } else {
@@ -1353,11 +2504,126 @@ impl AddressSpace {
             .find_references_by_direction(node_id, browse_direction, reference_filter)
     }
 
+    /// Finds references between the specified node and nodes held by other servers, optionally
+    /// forwards, inverse or both. The reference filter can optionally filter results by a specific
+    /// type and subtypes.
+    pub fn find_remote_references<T>(
+        &self,
+        node_id: &NodeId,
+        browse_direction: BrowseDirection,
+        reference_filter: Option<(T, bool)>,
+    ) -> Vec<RemoteReference>
+    where
+        T: Into<NodeId> + Clone,
+    {
+        self.references
+            .find_remote_references(node_id, browse_direction, reference_filter)
+    }
+
     /// Updates the last modified timestamp to now
     fn update_last_modified(&mut self) {
         self.last_modified = Utc::now();
     }
 
+    /// Records a change to a reference between two nodes. The instances of a type come and go
+    /// without changing the type, so only the instance is affected by a `HasTypeDefinition`.
+    fn record_reference_change(
+        &mut self,
+        node_id: &NodeId,
+        target_node_id: &NodeId,
+        reference_type_id: &NodeId,
+        verb: ModelChangeStructureVerbMask,
+    ) {
+        self.record_model_change(node_id, verb);
+        if *reference_type_id != ReferenceTypeId::HasTypeDefinition.into() {
+            self.record_model_change(target_node_id, verb);
+        }
+    }
+
+    /// Records a change to a node, if changes are tracked and the node exists. The verbs of
+    /// changes to the same node are combined until they are reported.
+    fn record_model_change(&mut self, affected: &NodeId, verb: ModelChangeStructureVerbMask) {
+        if self.model_changes.is_none() || !self.node_exists(affected) {
+            return;
+        }
+        let affected_type = self
+            .references
+            .get_type_id(affected)
+            .unwrap_or_else(NodeId::null);
+        let model_changes = self.model_changes.as_mut().unwrap();
+        match model_changes.indexes.get(affected) {
+            Some(index) => {
+                let change = &mut model_changes.changes[*index];
+                change.verb |= verb as u8;
+                if change.affected_type.is_null() {
+                    change.affected_type = affected_type;
+                }
+            }
+            None => {
+                model_changes
+                    .indexes
+                    .insert(affected.clone(), model_changes.changes.len());
+                model_changes.changes.push(ModelChangeStructureDataType {
+                    affected: affected.clone(),
+                    affected_type,
+                    verb: verb as u8,
+                });
+            }
+        }
+    }
+
+    /// Sets the getters of the session and subscription diagnostics arrays, whose values are
+    /// taken from the sessions of the supplied session manager when they are read.
+    pub(crate) fn set_session_manager(&mut self, session_manager: Arc<RwLock<SessionManager>>) {
+        fn diagnostics_array<T>(
+            session_manager: &Arc<RwLock<SessionManager>>,
+            diagnostics: impl Fn(&SessionManager) -> Vec<T>,
+            encoding_id: ObjectId,
+            timestamps_to_return: TimestampsToReturn,
+        ) -> Result<Option<DataValue>, StatusCode>
+        where
+            T: BinaryEncoder<T>,
+        {
+            // The session manager may be waiting for the address space that the caller holds
+            let Some(session_manager) = session_manager.try_read_recursive() else {
+                return Err(StatusCode::BadResourceUnavailable);
+            };
+            let values = diagnostics(&session_manager)
+                .iter()
+                .map(|v| Variant::from(ExtensionObject::from_encodable(encoding_id, v)))
+                .collect::<Vec<_>>();
+            let mut value =
+                DataValue::from(Variant::from((VariantTypeId::ExtensionObject, values)));
+            let now = DateTime::now();
+            value.set_timestamps(timestamps_to_return, now, now);
+            Ok(Some(value))
+        }
+
+        let sessions = session_manager.clone();
+        self.set_variable_getter(
+            Server_ServerDiagnostics_SessionsDiagnosticsSummary_SessionDiagnosticsArray,
+            move |_, timestamps_to_return, _, _, _, _| {
+                diagnostics_array(
+                    &sessions,
+                    SessionManager::session_diagnostics,
+                    ObjectId::SessionDiagnosticsDataType_Encoding_DefaultBinary,
+                    timestamps_to_return,
+                )
+            },
+        );
+        self.set_variable_getter(
+            Server_ServerDiagnostics_SubscriptionDiagnosticsArray,
+            move |_, timestamps_to_return, _, _, _, _| {
+                diagnostics_array(
+                    &session_manager,
+                    SessionManager::subscription_diagnostics,
+                    ObjectId::SubscriptionDiagnosticsDataType_Encoding_DefaultBinary,
+                    timestamps_to_return,
+                )
+            },
+        );
+    }
+
     /// Sets the getter for a variable node
     fn set_variable_getter<N, F>(&mut self, variable_id: N, getter: F)
     where
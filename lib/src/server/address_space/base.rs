@@ -23,6 +23,8 @@ pub(crate) struct Base {
     write_mask: Option<u32>,
     /// User write mask bits (optional)
     user_write_mask: Option<u32>,
+    /// Permissions that roles have on the node (optional)
+    role_permissions: Option<Vec<RolePermissionType>>,
 }
 
 impl NodeBase for Base {
@@ -69,6 +71,14 @@ impl NodeBase for Base {
     fn set_user_write_mask(&mut self, user_write_mask: WriteMask) {
         self.user_write_mask = Some(user_write_mask.bits());
     }
+
+    fn role_permissions(&self) -> Option<&[RolePermissionType]> {
+        self.role_permissions.as_deref()
+    }
+
+    fn set_role_permissions(&mut self, role_permissions: Vec<RolePermissionType>) {
+        self.role_permissions = Some(role_permissions);
+    }
 }
 
 impl Node for Base {
@@ -88,6 +98,10 @@ impl Node for Base {
             AttributeId::Description => self.description().map(|description| description.into()),
             AttributeId::WriteMask => self.write_mask.map(|v| v.into()),
             AttributeId::UserWriteMask => self.user_write_mask.map(|v| v.into()),
+            AttributeId::RolePermissions | AttributeId::UserRolePermissions => self
+                .role_permissions
+                .as_ref()
+                .map(|v| Self::role_permissions_to_variant(v).into()),
             _ => None,
         }
     }
@@ -168,6 +182,24 @@ impl Node for Base {
                     Err(StatusCode::BadTypeMismatch)
                 }
             }
+            AttributeId::RolePermissions => {
+                let role_permissions = match value {
+                    Variant::Array(array) => array
+                        .values
+                        .iter()
+                        .map(|v| match v {
+                            Variant::ExtensionObject(v) => v
+                                .decode_inner::<RolePermissionType>(&DecodingOptions::default())
+                                .map_err(|_| StatusCode::BadTypeMismatch),
+                            _ => Err(StatusCode::BadTypeMismatch),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Variant::Empty => Vec::new(),
+                    _ => return Err(StatusCode::BadTypeMismatch),
+                };
+                self.role_permissions = Some(role_permissions);
+                Ok(())
+            }
             _ => Err(StatusCode::BadAttributeIdInvalid),
         }
     }
@@ -192,6 +224,7 @@ impl Base {
             description: None,
             write_mask: None,
             user_write_mask: None,
+            role_permissions: None,
         }
     }
 
@@ -210,4 +243,18 @@ impl Base {
     {
         self.browse_name = browse_name.into();
     }
+
+    /// Turns role permissions into the value of a RolePermissions attribute
+    pub fn role_permissions_to_variant(role_permissions: &[RolePermissionType]) -> Variant {
+        let role_permissions = role_permissions
+            .iter()
+            .map(|v| {
+                Variant::from(ExtensionObject::from_encodable(
+                    ObjectId::RolePermissionType_Encoding_DefaultBinary,
+                    v,
+                ))
+            })
+            .collect::<Vec<Variant>>();
+        Variant::from((VariantTypeId::ExtensionObject, role_permissions))
+    }
 }
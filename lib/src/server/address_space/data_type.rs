@@ -4,17 +4,37 @@
 
 //! Contains the implementation of `Method` and `MethodBuilder`.
 
-use crate::types::service_types::DataTypeAttributes;
+use crate::types::{service_types::DataTypeAttributes, DataTypeDefinition};
 
 use super::{base::Base, node::Node, node::NodeBase};
 
 node_builder_impl!(DataTypeBuilder, DataType);
 
+impl DataTypeBuilder {
+    /// Sets whether the data type is abstract.
+    pub fn is_abstract(mut self, is_abstract: bool) -> Self {
+        self.node.set_is_abstract(is_abstract);
+        self
+    }
+
+    /// Sets the definition of the data type, describing the fields of a structure or an
+    /// enumeration.
+    pub fn data_type_definition<T>(mut self, data_type_definition: T) -> Self
+    where
+        T: Into<DataTypeDefinition>,
+    {
+        self.node
+            .set_data_type_definition(Some(data_type_definition.into()));
+        self
+    }
+}
+
 /// A `DataType` is a type of node within the `AddressSpace`.
 #[derive(Debug)]
 pub struct DataType {
     base: Base,
     is_abstract: bool,
+    data_type_definition: Option<DataTypeDefinition>,
 }
 
 impl Default for DataType {
@@ -22,6 +42,7 @@ impl Default for DataType {
         Self {
             base: Base::new(NodeClass::DataType, &NodeId::null(), "", ""),
             is_abstract: false,
+            data_type_definition: None,
         }
     }
 }
@@ -39,6 +60,9 @@ impl Node for DataType {
     ) -> Option<DataValue> {
         match attribute_id {
             AttributeId::IsAbstract => Some(self.is_abstract().into()),
+            AttributeId::DataTypeDefinition => self
+                .data_type_definition()
+                .map(|v| DataValue::new_now(ExtensionObject::from(v))),
             _ => self.base.get_attribute_max_age(
                 timestamps_to_return,
                 attribute_id,
@@ -63,6 +87,17 @@ impl Node for DataType {
                     Err(StatusCode::BadTypeMismatch)
                 }
             }
+            AttributeId::DataTypeDefinition => {
+                if let Variant::ExtensionObject(v) = value {
+                    let definition =
+                        DataTypeDefinition::from_extension_object(&v, &DecodingOptions::default())
+                            .map_err(|_| StatusCode::BadTypeMismatch)?;
+                    self.set_data_type_definition(Some(definition));
+                    Ok(())
+                } else {
+                    Err(StatusCode::BadTypeMismatch)
+                }
+            }
             _ => self.base.set_attribute(attribute_id, value),
         }
     }
@@ -82,6 +117,7 @@ impl DataType {
         DataType {
             base: Base::new(NodeClass::DataType, node_id, browse_name, display_name),
             is_abstract,
+            data_type_definition: None,
         }
     }
 
@@ -128,4 +164,12 @@ impl DataType {
     pub fn set_is_abstract(&mut self, is_abstract: bool) {
         self.is_abstract = is_abstract;
     }
+
+    pub fn data_type_definition(&self) -> Option<&DataTypeDefinition> {
+        self.data_type_definition.as_ref()
+    }
+
+    pub fn set_data_type_definition(&mut self, data_type_definition: Option<DataTypeDefinition>) {
+        self.data_type_definition = data_type_definition;
+    }
 }
@@ -42,6 +42,18 @@ impl MethodBuilder {
         self
     }
 
+    /// Sets whether the method can be called.
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.node.set_executable(executable);
+        self
+    }
+
+    /// Sets whether the method can be called by the current user.
+    pub fn user_executable(mut self, user_executable: bool) -> Self {
+        self.node.set_user_executable(user_executable);
+        self
+    }
+
     fn args_to_variant(arguments: &[Argument]) -> Variant {
         let arguments = arguments
             .iter()
@@ -6,12 +6,12 @@ use std::sync::Arc;
 
 use crate::sync::*;
 use crate::types::{
-    service_types::{CallMethodRequest, CallMethodResult},
+    service_types::{CallMethodRequest, CallMethodResult, ServerState as ServerStateType},
     status_code::StatusCode,
     *,
 };
 
-use crate::server::{callbacks::Method, session::SessionManager};
+use crate::server::{callbacks::Method, session::SessionManager, state::ServerState};
 
 /// Count the number of provided input arguments, comparing them to the expected number.
 fn ensure_input_argument_count(
@@ -181,3 +181,82 @@ impl Method for ServerGetMonitoredItemsMethod {
         }
     }
 }
+
+/// This is the handler for the Server.RequestServerStateChange method call.
+pub struct ServerRequestServerStateChangeMethod {
+    server_state: Arc<RwLock<ServerState>>,
+}
+
+impl ServerRequestServerStateChangeMethod {
+    pub fn new(server_state: Arc<RwLock<ServerState>>) -> Self {
+        ServerRequestServerStateChangeMethod { server_state }
+    }
+}
+
+impl Method for ServerRequestServerStateChangeMethod {
+    fn call(
+        &mut self,
+        _session_id: &NodeId,
+        _session_manager: Arc<RwLock<SessionManager>>,
+        request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+        debug!("Method handler for RequestServerStateChange");
+
+        // OPC UA part 5 - RequestServerStateChange([in] ServerState state, [in] UtcTime estimatedReturnTime,
+        //   [in] UInt32 secondsTillShutdown, [in] LocalizedText reason, [in] Boolean restart);
+        //
+        // state - The state the server should be in
+        // estimatedReturnTime - When the server is expected to be running again
+        // secondsTillShutdown - The seconds until the server shuts down, when the state is Shutdown
+        // reason - Why the state is changing
+        // restart - Whether the server should restart after it shuts down
+        //
+        // Return codes
+        //
+        // BadInvalidArgument
+        // BadNotSupported - the server cannot restart itself
+        // BadUserAccessDenied
+
+        ensure_input_argument_count(request, 5)?;
+
+        let state = get_input_argument!(request, 0, Int32)?;
+        let _estimated_return_time = get_input_argument!(request, 1, DateTime)?;
+        let seconds_till_shutdown = get_input_argument!(request, 2, UInt32)?;
+        let reason = get_input_argument!(request, 3, LocalizedText)?;
+        let restart = get_input_argument!(request, 4, Boolean)?;
+
+        // The other states describe faults that only the server itself can be in
+        let state = match *state {
+            0 => ServerStateType::Running,
+            3 => ServerStateType::Suspended,
+            4 => ServerStateType::Shutdown,
+            5 => ServerStateType::Test,
+            state => {
+                debug!(
+                    "Method handler for RequestServerStateChange rejects state {}",
+                    state
+                );
+                return Err(StatusCode::BadInvalidArgument);
+            }
+        };
+        if *restart {
+            debug!("Method handler for RequestServerStateChange cannot restart the server");
+            return Err(StatusCode::BadNotSupported);
+        }
+
+        let mut server_state = trace_write_lock!(self.server_state);
+        if state == ServerStateType::Shutdown {
+            server_state.request_shutdown(*seconds_till_shutdown, reason.as_ref().clone());
+        } else {
+            server_state.cancel_shutdown();
+            server_state.set_state(state);
+        }
+
+        Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: Some(vec![StatusCode::Good; 5]),
+            input_argument_diagnostic_infos: None,
+            output_arguments: None,
+        })
+    }
+}
@@ -137,6 +137,20 @@ macro_rules! node_builder_impl {
             references: Vec<(NodeId, NodeId, ReferenceDirection)>,
         }
 
+        impl $node_ty {
+            /// Creates a builder for a node with the node id. The browse name must be set on the
+            /// builder before the node is built or inserted, and usually the display name too.
+            pub fn builder(node_id: &NodeId) -> $node_builder_ty {
+                trace!("Creating a node using a builder, node id {}", node_id);
+                $node_builder_ty {
+                    node: $node_ty::default(),
+                    references: Vec::with_capacity(10),
+                }
+                .node_id(node_id.clone())
+                .browse_name(QualifiedName::null())
+            }
+        }
+
         impl $node_builder_ty {
             /// Creates a builder for a node. All nodes are required to su
             pub fn new<T, S>(node_id: &NodeId, browse_name: T, display_name: S) -> Self
@@ -163,7 +177,8 @@ macro_rules! node_builder_impl {
                 self
             }
 
-            fn browse_name<V>(mut self, browse_name: V) -> Self
+            /// Sets the browse name of the node
+            pub fn browse_name<V>(mut self, browse_name: V) -> Self
             where
                 V: Into<QualifiedName>,
             {
@@ -171,7 +186,8 @@ macro_rules! node_builder_impl {
                 self
             }
 
-            fn display_name<V>(mut self, display_name: V) -> Self
+            /// Sets the display name of the node
+            pub fn display_name<V>(mut self, display_name: V) -> Self
             where
                 V: Into<LocalizedText>,
             {
@@ -193,6 +209,36 @@ macro_rules! node_builder_impl {
                 self
             }
 
+            /// Sets which attributes of the node may be written
+            pub fn write_mask(mut self, write_mask: WriteMask) -> Self {
+                self.node.set_write_mask(write_mask);
+                self
+            }
+
+            /// Sets which attributes of the node may be written by the current user
+            pub fn user_write_mask(mut self, user_write_mask: WriteMask) -> Self {
+                self.node.set_user_write_mask(user_write_mask);
+                self
+            }
+
+            /// Grants a role permissions on the node. Once any role is granted permissions,
+            /// roles that are not granted any are denied everything.
+            pub fn role_permission<T>(mut self, role_id: T, permissions: PermissionType) -> Self
+            where
+                T: Into<NodeId>,
+            {
+                let mut role_permissions = self
+                    .node
+                    .role_permissions()
+                    .map_or_else(Vec::new, |v| v.to_vec());
+                role_permissions.push(RolePermissionType {
+                    role_id: role_id.into(),
+                    permissions,
+                });
+                self.node.set_role_permissions(role_permissions);
+                self
+            }
+
             /// Adds a reference to the node
             pub fn reference<T>(
                 mut self,
@@ -434,6 +480,14 @@ macro_rules! node_base_impl {
             fn set_user_write_mask(&mut self, user_write_mask: WriteMask) {
                 self.base.set_user_write_mask(user_write_mask)
             }
+
+            fn role_permissions(&self) -> Option<&[RolePermissionType]> {
+                self.base.role_permissions()
+            }
+
+            fn set_role_permissions(&mut self, role_permissions: Vec<RolePermissionType>) {
+                self.base.set_role_permissions(role_permissions)
+            }
         }
     };
 }
@@ -457,8 +511,10 @@ pub mod view;
 mod generated;
 #[cfg(feature = "generated-address-space")]
 mod method_impls;
+mod nodeset;
 
 bitflags! {
+    #[derive(Debug, Clone, Copy)]
     pub struct AccessLevel: u8 {
         const CURRENT_READ = 1;
         const CURRENT_WRITE = 2;
@@ -472,6 +528,7 @@ bitflags! {
 }
 
 bitflags! {
+    #[derive(Debug, Clone, Copy)]
     pub struct UserAccessLevel: u8 {
         const CURRENT_READ = 1;
         const CURRENT_WRITE = 2;
@@ -3,8 +3,10 @@
 // Copyright (C) 2017-2024 Adam Lock
 
 use crate::types::{
-    service_types::NodeClass, status_code::StatusCode, AttributeId, DataValue, LocalizedText,
-    NodeId, NumericRange, QualifiedName, TimestampsToReturn, Variant, WriteMask,
+    service_types::{NodeClass, PermissionType, RolePermissionType},
+    status_code::StatusCode,
+    AttributeId, DataValue, LocalizedText, NodeId, NumericRange, QualifiedName, TimestampsToReturn,
+    Variant, WriteMask,
 };
 
 use super::types::{
@@ -105,6 +107,24 @@ pub trait NodeBase {
     fn user_write_mask(&self) -> Option<WriteMask>;
 
     fn set_user_write_mask(&mut self, write_mask: WriteMask);
+
+    fn role_permissions(&self) -> Option<&[RolePermissionType]>;
+
+    fn set_role_permissions(&mut self, role_permissions: Vec<RolePermissionType>);
+
+    /// Returns the permissions that the roles have on the node. A node without role permissions
+    /// does not restrict what any role may do.
+    fn permissions(&self, roles: &[NodeId]) -> PermissionType {
+        match self.role_permissions() {
+            Some(role_permissions) => role_permissions
+                .iter()
+                .filter(|p| roles.contains(&p.role_id))
+                .fold(PermissionType::None, |permissions, p| {
+                    permissions | p.permissions
+                }),
+            None => PermissionType::all(),
+        }
+    }
 }
 
 /// Implemented by each node type's to provide a generic way to set or get attributes, e.g.
@@ -144,6 +164,26 @@ pub trait Node: NodeBase {
         )
     }
 
+    /// Finds the value of every attribute that the node's class defines and the node has, in the
+    /// order of their ids.
+    fn get_attributes(
+        &self,
+        timestamps_to_return: TimestampsToReturn,
+    ) -> Vec<(AttributeId, DataValue)> {
+        AttributeId::for_node_class(self.node_class())
+            .iter()
+            .filter_map(|attribute_id| {
+                self.get_attribute(
+                    timestamps_to_return,
+                    *attribute_id,
+                    NumericRange::None,
+                    &QualifiedName::null(),
+                )
+                .map(|data_value| (*attribute_id, data_value))
+            })
+            .collect()
+    }
+
     /// Sets the attribute with the new value
     fn set_attribute(
         &mut self,
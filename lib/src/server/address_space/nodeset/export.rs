@@ -0,0 +1,397 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Exports the nodes of an address space as a node set in the UANodeSet XML format.
+//!
+//! The `NamespaceUris` table of the file holds every namespace of the address space after the
+//! OPC UA namespace, so namespace indices in the file are the same as in the address space.
+
+use crate::types::{service_types::Argument, service_types::EnumValueType, xml::Element, *};
+
+use super::{
+    super::{
+        address_space::AddressSpace,
+        node::{HasNodeId, NodeType},
+        AccessLevel, EventNotifier, UserAccessLevel,
+    },
+    VALUE_TYPES,
+};
+
+/// The XML encoding ids of the extension objects that are written
+const ARGUMENT_ENCODING_DEFAULT_XML: u32 = 297;
+const ENUM_VALUE_TYPE_ENCODING_DEFAULT_XML: u32 = 7616;
+
+/// Exports the nodes of the address space in the namespaces of the filter, or every node if there
+/// is no filter, returning the node set and the number of nodes in it. Nodes are written with
+/// their forward references, and inverse references from nodes that are not exported. Values
+/// are written for variables whose value is held in the address space, not those with a getter.
+pub(crate) fn export(
+    address_space: &AddressSpace,
+    namespace_filter: Option<&[u16]>,
+) -> (String, usize) {
+    let is_exported = |node_id: &NodeId| {
+        namespace_filter.is_none_or(|namespaces| namespaces.contains(&node_id.namespace))
+    };
+
+    let mut nodes = address_space
+        .nodes()
+        .filter(|node| is_exported(&node.node_id()))
+        .collect::<Vec<_>>();
+    nodes.sort_by_cached_key(|node| {
+        let node_id = node.node_id();
+        (node_id.namespace, node_id.identifier.to_string())
+    });
+
+    let namespace_uris = address_space
+        .namespaces()
+        .iter()
+        .skip(1)
+        .fold(Element::new("NamespaceUris"), |uris, uri| {
+            uris.with_child(Element::new("Uri").with_text(uri))
+        });
+    let mut root = Element::new("UANodeSet")
+        .with_attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+        .with_attribute("xmlns:xsd", "http://www.w3.org/2001/XMLSchema")
+        .with_attribute("xmlns", "http://opcfoundation.org/UA/2011/03/UANodeSet.xsd")
+        .with_attribute(
+            "LastModified",
+            DateTime::from(address_space.last_modified()),
+        )
+        .with_child(namespace_uris);
+
+    let count = nodes.len();
+    for node in nodes {
+        let element = node_element(node);
+        let references = node_references(address_space, &node.node_id(), &is_exported);
+        let element = if references.children.is_empty() {
+            element
+        } else {
+            element.with_child(references)
+        };
+        root = root.with_child(node_value(node, element));
+    }
+    (root.to_document(), count)
+}
+
+/// Writes the attributes of a node
+fn node_element(node: &NodeType) -> Element {
+    let base = node.as_node();
+    let name = match node {
+        NodeType::Object(_) => "UAObject",
+        NodeType::Variable(_) => "UAVariable",
+        NodeType::Method(_) => "UAMethod",
+        NodeType::View(_) => "UAView",
+        NodeType::ObjectType(_) => "UAObjectType",
+        NodeType::VariableType(_) => "UAVariableType",
+        NodeType::DataType(_) => "UADataType",
+        NodeType::ReferenceType(_) => "UAReferenceType",
+    };
+    let browse_name = base.browse_name();
+    let browse_name = if browse_name.namespace_index == 0 {
+        browse_name.name.to_string()
+    } else {
+        format!("{}:{}", browse_name.namespace_index, browse_name.name)
+    };
+    let mut element = Element::new(name)
+        .with_attribute("NodeId", base.node_id())
+        .with_attribute("BrowseName", browse_name);
+
+    match node {
+        NodeType::Object(object) => {
+            element = event_notifier(element, object.event_notifier());
+        }
+        NodeType::Variable(variable) => {
+            element = element
+                .with_attribute("DataType", variable.data_type())
+                .with_attribute("ValueRank", variable.value_rank());
+            if let Some(array_dimensions) = variable.array_dimensions() {
+                element = element.with_attribute("ArrayDimensions", dimensions(&array_dimensions));
+            }
+            if variable.access_level().bits() != AccessLevel::CURRENT_READ.bits() {
+                element = element.with_attribute("AccessLevel", variable.access_level().bits());
+            }
+            if variable.user_access_level().bits() != UserAccessLevel::CURRENT_READ.bits() {
+                element =
+                    element.with_attribute("UserAccessLevel", variable.user_access_level().bits());
+            }
+            if variable.historizing() {
+                element = element.with_attribute("Historizing", true);
+            }
+            if let Some(minimum_sampling_interval) = variable.minimum_sampling_interval() {
+                element =
+                    element.with_attribute("MinimumSamplingInterval", minimum_sampling_interval);
+            }
+        }
+        NodeType::Method(method) => {
+            if !method.executable() {
+                element = element.with_attribute("Executable", false);
+            }
+            if !method.user_executable() {
+                element = element.with_attribute("UserExecutable", false);
+            }
+        }
+        NodeType::View(view) => {
+            element = event_notifier(element, view.event_notifier());
+            if view.contains_no_loops() {
+                element = element.with_attribute("ContainsNoLoops", true);
+            }
+        }
+        NodeType::ObjectType(object_type) => {
+            element = is_abstract(element, object_type.is_abstract());
+        }
+        NodeType::VariableType(variable_type) => {
+            element = element
+                .with_attribute("DataType", variable_type.data_type())
+                .with_attribute("ValueRank", variable_type.value_rank());
+            if let Some(array_dimensions) = variable_type.array_dimensions() {
+                element = element.with_attribute("ArrayDimensions", dimensions(&array_dimensions));
+            }
+            element = is_abstract(element, variable_type.is_abstract());
+        }
+        NodeType::DataType(data_type) => {
+            element = is_abstract(element, data_type.is_abstract());
+        }
+        NodeType::ReferenceType(reference_type) => {
+            element = is_abstract(element, reference_type.is_abstract());
+            if reference_type.symmetric() {
+                element = element.with_attribute("Symmetric", true);
+            }
+        }
+    }
+    if let Some(write_mask) = base.write_mask().filter(|w| !w.is_empty()) {
+        element = element.with_attribute("WriteMask", write_mask.bits());
+    }
+    if let Some(user_write_mask) = base.user_write_mask().filter(|w| !w.is_empty()) {
+        element = element.with_attribute("UserWriteMask", user_write_mask.bits());
+    }
+
+    element = element.with_child(localized_text("DisplayName", &base.display_name()));
+    if let Some(description) = base.description() {
+        element = element.with_child(localized_text("Description", &description));
+    }
+    if let NodeType::ReferenceType(reference_type) = node {
+        if let Some(inverse_name) = reference_type.inverse_name() {
+            element = element.with_child(localized_text("InverseName", &inverse_name));
+        }
+    }
+    element
+}
+
+fn event_notifier(element: Element, event_notifier: EventNotifier) -> Element {
+    if event_notifier.is_empty() {
+        element
+    } else {
+        element.with_attribute("EventNotifier", event_notifier.bits())
+    }
+}
+
+fn is_abstract(element: Element, is_abstract: bool) -> Element {
+    if is_abstract {
+        element.with_attribute("IsAbstract", true)
+    } else {
+        element
+    }
+}
+
+fn dimensions(array_dimensions: &[u32]) -> String {
+    array_dimensions
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn localized_text(name: &str, text: &LocalizedText) -> Element {
+    let element = Element::new(name).with_text(text.text.as_ref());
+    if text.locale.is_empty() {
+        element
+    } else {
+        element.with_attribute("Locale", text.locale.as_ref())
+    }
+}
+
+/// Writes the forward references of the node, and the inverse references from nodes that are
+/// not exported since those nodes do not carry the reference themselves
+fn node_references<F>(address_space: &AddressSpace, node_id: &NodeId, is_exported: &F) -> Element
+where
+    F: Fn(&NodeId) -> bool,
+{
+    let reference = |reference_type: &NodeId, other_node: &NodeId, is_forward: bool| {
+        let element = Element::new("Reference")
+            .with_attribute("ReferenceType", reference_type)
+            .with_text(other_node);
+        if is_forward {
+            element
+        } else {
+            element.with_attribute("IsForward", false)
+        }
+    };
+    let mut references = Element::new("References");
+    address_space
+        .find_inverse_references(node_id, None::<(NodeId, bool)>)
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| !is_exported(&r.target_node))
+        .for_each(|r| {
+            references
+                .children
+                .push(reference(&r.reference_type, &r.target_node, false));
+        });
+    address_space
+        .find_references(node_id, None::<(NodeId, bool)>)
+        .unwrap_or_default()
+        .iter()
+        .for_each(|r| {
+            references
+                .children
+                .push(reference(&r.reference_type, &r.target_node, true));
+        });
+    references
+}
+
+/// Writes the value of a variable or variable type. Values of a type that cannot be written
+/// are left out.
+fn node_value(node: &NodeType, element: Element) -> Element {
+    let value = match node {
+        NodeType::Variable(variable) if !variable.has_value_getter() => {
+            variable
+                .value(
+                    TimestampsToReturn::Neither,
+                    NumericRange::None,
+                    &QualifiedName::null(),
+                    0.0,
+                )
+                .value
+        }
+        NodeType::VariableType(variable_type) => {
+            variable_type.value().and_then(|value| value.value)
+        }
+        _ => None,
+    };
+    match value.as_ref().and_then(value_element) {
+        Some(value) => element.with_child(Element::new("Value").with_child(value)),
+        None => element,
+    }
+}
+
+fn type_name(variant_type_id: VariantTypeId) -> Option<&'static str> {
+    VALUE_TYPES
+        .iter()
+        .find(|(_, v)| *v == variant_type_id)
+        .map(|(name, _)| *name)
+}
+
+fn value_element(value: &Variant) -> Option<Element> {
+    match value {
+        Variant::Empty => None,
+        Variant::Array(array) => {
+            let type_name = type_name(array.value_type)?;
+            let mut list = Element::new(&format!("ListOf{}", type_name))
+                .with_attribute("xmlns", XML_TYPES_NAMESPACE);
+            for value in array.values.iter() {
+                list = list.with_child(scalar_element(value)?);
+            }
+            Some(list)
+        }
+        value => Some(scalar_element(value)?.with_attribute("xmlns", XML_TYPES_NAMESPACE)),
+    }
+}
+
+/// Writes a scalar value, or `None` if its type is not supported
+fn scalar_element(value: &Variant) -> Option<Element> {
+    // Infinity is written as XML schema writes it
+    fn float<T: ToString>(v: T) -> String {
+        match v.to_string().as_str() {
+            "inf" => "INF".into(),
+            "-inf" => "-INF".into(),
+            v => v.into(),
+        }
+    }
+    let type_name = type_name(value.type_id())?;
+    let element = Element::new(type_name);
+    let element = match value {
+        Variant::Boolean(v) => element.with_text(v),
+        Variant::SByte(v) => element.with_text(v),
+        Variant::Byte(v) => element.with_text(v),
+        Variant::Int16(v) => element.with_text(v),
+        Variant::UInt16(v) => element.with_text(v),
+        Variant::Int32(v) => element.with_text(v),
+        Variant::UInt32(v) => element.with_text(v),
+        Variant::Int64(v) => element.with_text(v),
+        Variant::UInt64(v) => element.with_text(v),
+        Variant::Float(v) => element.with_text(float(*v)),
+        Variant::Double(v) => element.with_text(float(*v)),
+        Variant::String(v) => element.with_text(v.as_ref()),
+        Variant::DateTime(v) => element.with_text(v),
+        Variant::Guid(v) => element.with_child(Element::new("String").with_text(v)),
+        Variant::ByteString(v) => element.with_text(v.as_base64()),
+        Variant::StatusCode(v) => element.with_child(Element::new("Code").with_text(v.bits())),
+        Variant::LocalizedText(v) => element
+            .with_child(Element::new("Locale").with_text(v.locale.as_ref()))
+            .with_child(Element::new("Text").with_text(v.text.as_ref())),
+        Variant::QualifiedName(v) => element
+            .with_child(Element::new("NamespaceIndex").with_text(v.namespace_index))
+            .with_child(Element::new("Name").with_text(v.name.as_ref())),
+        Variant::NodeId(v) => element.with_child(Element::new("Identifier").with_text(v)),
+        Variant::ExpandedNodeId(v) if v.server_index == 0 && v.namespace_uri.is_null() => {
+            element.with_child(Element::new("Identifier").with_text(&v.node_id))
+        }
+        Variant::ExtensionObject(v) => extension_object(element, v)?,
+        _ => return None,
+    };
+    Some(element)
+}
+
+/// Writes the method arguments and enum values that node sets hold in extension objects
+fn extension_object(element: Element, extension_object: &ExtensionObject) -> Option<Element> {
+    let localized_text = |name: &str, text: &LocalizedText| {
+        Element::new(name)
+            .with_child(Element::new("Locale").with_text(text.locale.as_ref()))
+            .with_child(Element::new("Text").with_text(text.text.as_ref()))
+    };
+    let decoding_options = DecodingOptions::default();
+    let (encoding_id, body) = match extension_object.object_id().ok()? {
+        ObjectId::Argument_Encoding_DefaultBinary => {
+            let argument = extension_object
+                .decode_inner::<Argument>(&decoding_options)
+                .ok()?;
+            let array_dimensions = argument
+                .array_dimensions
+                .unwrap_or_default()
+                .iter()
+                .fold(Element::new("ArrayDimensions"), |dimensions, d| {
+                    dimensions.with_child(Element::new("UInt32").with_text(d))
+                });
+            let body = Element::new("Argument")
+                .with_child(Element::new("Name").with_text(argument.name.as_ref()))
+                .with_child(
+                    Element::new("DataType")
+                        .with_child(Element::new("Identifier").with_text(&argument.data_type)),
+                )
+                .with_child(Element::new("ValueRank").with_text(argument.value_rank))
+                .with_child(array_dimensions)
+                .with_child(localized_text("Description", &argument.description));
+            (ARGUMENT_ENCODING_DEFAULT_XML, body)
+        }
+        ObjectId::EnumValueType_Encoding_DefaultBinary => {
+            let enum_value = extension_object
+                .decode_inner::<EnumValueType>(&decoding_options)
+                .ok()?;
+            let body = Element::new("EnumValueType")
+                .with_child(Element::new("Value").with_text(enum_value.value))
+                .with_child(localized_text("DisplayName", &enum_value.display_name))
+                .with_child(localized_text("Description", &enum_value.description));
+            (ENUM_VALUE_TYPE_ENCODING_DEFAULT_XML, body)
+        }
+        _ => return None,
+    };
+    Some(
+        element
+            .with_child(
+                Element::new("TypeId")
+                    .with_child(Element::new("Identifier").with_text(NodeId::new(0, encoding_id))),
+            )
+            .with_child(Element::new("Body").with_child(body)),
+    )
+}
@@ -0,0 +1,641 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Imports node sets in the UANodeSet XML format, as published for the standard nodeset and
+//! companion specifications, into an address space, and exports an address space in the same
+//! format.
+//!
+//! Namespace indices in the file are relative to the file's own `NamespaceUris` table, so each
+//! namespace is registered with the address space and node ids are remapped to the registered
+//! indices. Aliases are resolved wherever the file refers to a node id.
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::types::{
+    service_types::Argument,
+    service_types::EnumValueType,
+    xml::{self, Element},
+    *,
+};
+
+use super::{
+    address_space::AddressSpace,
+    data_type::DataType,
+    method::Method,
+    node::{HasNodeId, NodeType},
+    object::Object,
+    object_type::ObjectType,
+    reference_type::ReferenceType,
+    variable::Variable,
+    variable_type::VariableType,
+    view::View,
+    AccessLevel, EventNotifier, UserAccessLevel,
+};
+
+pub(crate) use self::export::export;
+
+mod export;
+
+/// The built-in types of values that are read and written, by their name in a node set
+const VALUE_TYPES: &[(&str, VariantTypeId)] = &[
+    ("Boolean", VariantTypeId::Boolean),
+    ("SByte", VariantTypeId::SByte),
+    ("Byte", VariantTypeId::Byte),
+    ("Int16", VariantTypeId::Int16),
+    ("UInt16", VariantTypeId::UInt16),
+    ("Int32", VariantTypeId::Int32),
+    ("UInt32", VariantTypeId::UInt32),
+    ("Int64", VariantTypeId::Int64),
+    ("UInt64", VariantTypeId::UInt64),
+    ("Float", VariantTypeId::Float),
+    ("Double", VariantTypeId::Double),
+    ("String", VariantTypeId::String),
+    ("DateTime", VariantTypeId::DateTime),
+    ("Guid", VariantTypeId::Guid),
+    ("ByteString", VariantTypeId::ByteString),
+    ("StatusCode", VariantTypeId::StatusCode),
+    ("LocalizedText", VariantTypeId::LocalizedText),
+    ("QualifiedName", VariantTypeId::QualifiedName),
+    ("NodeId", VariantTypeId::NodeId),
+    ("ExpandedNodeId", VariantTypeId::ExpandedNodeId),
+    ("ExtensionObject", VariantTypeId::ExtensionObject),
+];
+
+/// A reference read from the node set, held until all the nodes have been inserted
+struct ImportedReference {
+    source_node: NodeId,
+    target_node: NodeId,
+    reference_type: NodeId,
+}
+
+/// Resolves the node ids, names and values of a node set against the address space
+struct Importer {
+    /// The address space namespace index of each namespace index in the file
+    namespaces: Vec<u16>,
+    /// Aliases for node ids, usually of data types and reference types
+    aliases: HashMap<String, String>,
+}
+
+/// Imports the node set into the address space, returning the number of nodes inserted. Nodes that
+/// already exist in the address space are left alone. The node set is read in full before anything
+/// is inserted so a malformed node set leaves the address space untouched, except for the
+/// registration of its namespaces.
+pub(crate) fn import(address_space: &mut AddressSpace, nodeset: &str) -> Result<usize, StatusCode> {
+    let root = xml::parse(nodeset).map_err(|err| {
+        error!("Node set is not valid XML, {}", err);
+        StatusCode::BadDecodingError
+    })?;
+    if root.name != "UANodeSet" {
+        error!("Node set has a root element {} not UANodeSet", root.name);
+        return Err(StatusCode::BadDecodingError);
+    }
+
+    // Registering namespaces must not change the namespace that new node ids are created in
+    let default_namespace = address_space.default_namespace();
+    let mut namespaces = vec![0u16];
+    if let Some(uris) = root.child("NamespaceUris") {
+        for uri in uris.children("Uri") {
+            let ns = address_space
+                .register_namespace(uri.text.trim())
+                .map_err(|_| {
+                    error!("Node set namespace \"{}\" cannot be registered", uri.text);
+                    StatusCode::BadDecodingError
+                })?;
+            namespaces.push(ns);
+        }
+    }
+    address_space.set_default_namespace(default_namespace);
+
+    let aliases = root
+        .child("Aliases")
+        .map(|aliases| {
+            aliases
+                .children("Alias")
+                .filter_map(|a| {
+                    a.attribute("Alias")
+                        .map(|alias| (alias.to_string(), a.text.trim().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let importer = Importer {
+        namespaces,
+        aliases,
+    };
+
+    let mut nodes = Vec::new();
+    let mut references = Vec::new();
+    for element in &root.children {
+        let node = match element.name.as_str() {
+            "UAObject" => importer.object(element)?,
+            "UAVariable" => importer.variable(element)?,
+            "UAMethod" => importer.method(element)?,
+            "UAView" => importer.view(element)?,
+            "UAObjectType" => importer.object_type(element)?,
+            "UAVariableType" => importer.variable_type(element)?,
+            "UADataType" => importer.data_type(element)?,
+            "UAReferenceType" => importer.reference_type(element)?,
+            _ => continue,
+        };
+        importer.references(element, &node.node_id(), &mut references)?;
+        nodes.push(node);
+    }
+
+    let mut inserted = 0;
+    nodes.into_iter().for_each(|node| {
+        let node_id = node.node_id();
+        if address_space.node_exists(&node_id) {
+            debug!("Node set node {} already exists and is skipped", node_id);
+        } else if address_space.insert::<NodeType, NodeId>(node, None) {
+            inserted += 1;
+        }
+    });
+    references.iter().for_each(|r| {
+        address_space.insert_reference(&r.source_node, &r.target_node, r.reference_type.clone());
+    });
+    Ok(inserted)
+}
+
+impl Importer {
+    /// Resolves a node id or alias in the file to a node id in the address space
+    fn node_id(&self, value: &str) -> Result<NodeId, StatusCode> {
+        let value = value.trim();
+        let value = self.aliases.get(value).map(|v| v.as_str()).unwrap_or(value);
+        let node_id = NodeId::from_str(value).map_err(|_| {
+            error!("Node set node id \"{}\" is invalid", value);
+            StatusCode::BadNodeIdInvalid
+        })?;
+        let namespace = self.namespace(node_id.namespace)?;
+        Ok(NodeId {
+            namespace,
+            identifier: node_id.identifier,
+        })
+    }
+
+    fn namespace(&self, namespace: u16) -> Result<u16, StatusCode> {
+        self.namespaces
+            .get(namespace as usize)
+            .copied()
+            .ok_or_else(|| {
+                error!("Node set namespace index {} is not in the file", namespace);
+                StatusCode::BadNodeIdInvalid
+            })
+    }
+
+    /// Resolves a browse name, written as `<namespace index>:<name>` or just `<name>`
+    fn qualified_name(&self, value: &str) -> Result<QualifiedName, StatusCode> {
+        match value.split_once(':') {
+            Some((ns, name)) if !ns.is_empty() && ns.chars().all(|c| c.is_ascii_digit()) => {
+                let ns = ns
+                    .parse::<u16>()
+                    .map_err(|_| StatusCode::BadDecodingError)?;
+                Ok(QualifiedName::new(self.namespace(ns)?, name))
+            }
+            _ => Ok(QualifiedName::new(0, value)),
+        }
+    }
+
+    fn attribute<T>(element: &Element, name: &str, default: T) -> Result<T, StatusCode>
+    where
+        T: FromStr,
+    {
+        match element.attribute(name) {
+            Some(value) => value.trim().parse::<T>().map_err(|_| {
+                error!("Node set attribute {}=\"{}\" is invalid", name, value);
+                StatusCode::BadDecodingError
+            }),
+            None => Ok(default),
+        }
+    }
+
+    fn array_dimensions(element: &Element) -> Result<Option<Vec<u32>>, StatusCode> {
+        match element.attribute("ArrayDimensions") {
+            Some(value) if !value.trim().is_empty() => value
+                .split(',')
+                .map(|d| d.trim().parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map(Some)
+                .map_err(|_| {
+                    error!("Node set array dimensions \"{}\" are invalid", value);
+                    StatusCode::BadDecodingError
+                }),
+            _ => Ok(None),
+        }
+    }
+
+    fn localized_text(element: &Element) -> LocalizedText {
+        LocalizedText::new(
+            element.attribute("Locale").unwrap_or_default(),
+            element.text.trim(),
+        )
+    }
+
+    /// Reads the attributes common to every node class, returning the node id, browse name
+    /// and display name
+    fn base(
+        &self,
+        element: &Element,
+    ) -> Result<(NodeId, QualifiedName, LocalizedText), StatusCode> {
+        let node_id = element.attribute("NodeId").ok_or_else(|| {
+            error!("Node set {} has no NodeId", element.name);
+            StatusCode::BadNodeIdInvalid
+        })?;
+        let node_id = self.node_id(node_id)?;
+        let browse_name =
+            self.qualified_name(element.attribute("BrowseName").unwrap_or_default())?;
+        let display_name = element
+            .child("DisplayName")
+            .map(Self::localized_text)
+            .unwrap_or_else(|| LocalizedText::new("", browse_name.name.as_ref()));
+        Ok((node_id, browse_name, display_name))
+    }
+
+    /// Sets the optional attributes common to every node class
+    fn set_base(element: &Element, node: &mut NodeType) -> Result<(), StatusCode> {
+        let node = node.as_mut_node();
+        if let Some(description) = element.child("Description") {
+            node.set_description(Self::localized_text(description));
+        }
+        let write_mask = Self::attribute(element, "WriteMask", 0u32)?;
+        if write_mask != 0 {
+            node.set_write_mask(WriteMask::from_bits_truncate(write_mask));
+        }
+        let user_write_mask = Self::attribute(element, "UserWriteMask", 0u32)?;
+        if user_write_mask != 0 {
+            node.set_user_write_mask(WriteMask::from_bits_truncate(user_write_mask));
+        }
+        Ok(())
+    }
+
+    fn references(
+        &self,
+        element: &Element,
+        node_id: &NodeId,
+        references: &mut Vec<ImportedReference>,
+    ) -> Result<(), StatusCode> {
+        if let Some(node_references) = element.child("References") {
+            for reference in node_references.children("Reference") {
+                let reference_type =
+                    self.node_id(reference.attribute("ReferenceType").unwrap_or_default())?;
+                let other_node = self.node_id(&reference.text)?;
+                let is_forward = Self::attribute(reference, "IsForward", true)?;
+                if other_node == *node_id {
+                    warn!("Node set node {} references itself, ignored", node_id);
+                    continue;
+                }
+                let (source_node, target_node) = if is_forward {
+                    (node_id.clone(), other_node)
+                } else {
+                    (other_node, node_id.clone())
+                };
+                references.push(ImportedReference {
+                    source_node,
+                    target_node,
+                    reference_type,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn object(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let event_notifier =
+            EventNotifier::from_bits_truncate(Self::attribute(element, "EventNotifier", 0u8)?);
+        let mut node = Object::new(&node_id, browse_name, display_name, event_notifier).into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn variable(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let data_type = match element.attribute("DataType") {
+            Some(data_type) => self.node_id(data_type)?,
+            None => DataTypeId::BaseDataType.into(),
+        };
+        let value_rank = Self::attribute(element, "ValueRank", -1i32)?;
+        let value = self.node_value(element)?;
+        let mut variable = Variable::new_data_value(
+            &node_id,
+            browse_name,
+            display_name,
+            data_type,
+            Some(value_rank),
+            None,
+            value,
+        );
+        if let Some(array_dimensions) = Self::array_dimensions(element)? {
+            variable.set_array_dimensions(&array_dimensions);
+        }
+        variable.set_access_level(AccessLevel::from_bits_truncate(Self::attribute(
+            element,
+            "AccessLevel",
+            1u8,
+        )?));
+        variable.set_user_access_level(UserAccessLevel::from_bits_truncate(Self::attribute(
+            element,
+            "UserAccessLevel",
+            1u8,
+        )?));
+        variable.set_historizing(Self::attribute(element, "Historizing", false)?);
+        variable.set_minimum_sampling_interval(Self::attribute(
+            element,
+            "MinimumSamplingInterval",
+            0f64,
+        )?);
+        let mut node = variable.into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn method(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let mut node = Method::new(
+            &node_id,
+            browse_name,
+            display_name,
+            Self::attribute(element, "Executable", true)?,
+            Self::attribute(element, "UserExecutable", true)?,
+        )
+        .into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn view(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let event_notifier =
+            EventNotifier::from_bits_truncate(Self::attribute(element, "EventNotifier", 0u8)?);
+        let mut node = View::new(
+            &node_id,
+            browse_name,
+            display_name,
+            event_notifier,
+            Self::attribute(element, "ContainsNoLoops", false)?,
+        )
+        .into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn object_type(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let mut node = ObjectType::new(
+            &node_id,
+            browse_name,
+            display_name,
+            Self::attribute(element, "IsAbstract", false)?,
+        )
+        .into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn variable_type(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let data_type = match element.attribute("DataType") {
+            Some(data_type) => self.node_id(data_type)?,
+            None => DataTypeId::BaseDataType.into(),
+        };
+        let mut variable_type = VariableType::new(
+            &node_id,
+            browse_name,
+            display_name,
+            data_type,
+            Self::attribute(element, "IsAbstract", false)?,
+            Self::attribute(element, "ValueRank", -1i32)?,
+        );
+        if let Some(array_dimensions) = Self::array_dimensions(element)? {
+            variable_type.set_array_dimensions(&array_dimensions);
+        }
+        let value = self.node_value(element)?;
+        if value != Variant::Empty {
+            variable_type.set_value(value);
+        }
+        let mut node = variable_type.into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn data_type(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let mut node = DataType::new(
+            &node_id,
+            browse_name,
+            display_name,
+            Self::attribute(element, "IsAbstract", false)?,
+        )
+        .into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    fn reference_type(&self, element: &Element) -> Result<NodeType, StatusCode> {
+        let (node_id, browse_name, display_name) = self.base(element)?;
+        let mut node = ReferenceType::new(
+            &node_id,
+            browse_name,
+            display_name,
+            element.child("InverseName").map(Self::localized_text),
+            Self::attribute(element, "Symmetric", false)?,
+            Self::attribute(element, "IsAbstract", false)?,
+        )
+        .into();
+        Self::set_base(element, &mut node)?;
+        Ok(node)
+    }
+
+    /// Reads the `Value` of a variable or variable type. Values of a type that cannot be read are
+    /// left empty.
+    fn node_value(&self, element: &Element) -> Result<Variant, StatusCode> {
+        let value = element.child("Value").and_then(|v| v.children.first());
+        let Some(value) = value else {
+            return Ok(Variant::Empty);
+        };
+        let variant = if let Some(type_name) = value.name.strip_prefix("ListOf") {
+            let mut values = Vec::with_capacity(value.children.len());
+            for v in value.children(type_name) {
+                match self.scalar_value(type_name, v)? {
+                    Some(v) => values.push(v),
+                    None => {
+                        values.clear();
+                        break;
+                    }
+                }
+            }
+            match Self::variant_type_id(type_name) {
+                Some(variant_type_id) if values.len() == value.children.len() => {
+                    Some(Variant::from((variant_type_id, values)))
+                }
+                _ => None,
+            }
+        } else {
+            self.scalar_value(&value.name, value)?
+        };
+        Ok(variant.unwrap_or_else(|| {
+            warn!(
+                "Node set value {} of {} is not supported and is left empty",
+                value.name,
+                element.attribute("NodeId").unwrap_or_default()
+            );
+            Variant::Empty
+        }))
+    }
+
+    fn variant_type_id(type_name: &str) -> Option<VariantTypeId> {
+        VALUE_TYPES
+            .iter()
+            .find(|(name, _)| *name == type_name)
+            .map(|(_, variant_type_id)| *variant_type_id)
+    }
+
+    /// Reads a scalar value of the named built-in type, or `None` if the type is not supported
+    fn scalar_value(
+        &self,
+        type_name: &str,
+        element: &Element,
+    ) -> Result<Option<Variant>, StatusCode> {
+        fn parse<T: FromStr>(element: &Element) -> Result<T, StatusCode> {
+            element.text.trim().parse::<T>().map_err(|_| {
+                error!(
+                    "Node set value {} \"{}\" is invalid",
+                    element.name, element.text
+                );
+                StatusCode::BadDecodingError
+            })
+        }
+        let text = element.text.trim();
+        let value = match type_name {
+            "Boolean" => Variant::from(text == "true" || text == "1"),
+            "SByte" => Variant::from(parse::<i8>(element)?),
+            "Byte" => Variant::from(parse::<u8>(element)?),
+            "Int16" => Variant::from(parse::<i16>(element)?),
+            "UInt16" => Variant::from(parse::<u16>(element)?),
+            "Int32" => Variant::from(parse::<i32>(element)?),
+            "UInt32" => Variant::from(parse::<u32>(element)?),
+            "Int64" => Variant::from(parse::<i64>(element)?),
+            "UInt64" => Variant::from(parse::<u64>(element)?),
+            "Float" => Variant::from(parse::<f32>(element)?),
+            "Double" => Variant::from(parse::<f64>(element)?),
+            "String" => Variant::from(UAString::from(element.text.as_str())),
+            "DateTime" => Variant::from(parse::<DateTime>(element)?),
+            "Guid" => {
+                let guid = element.child_text("String").unwrap_or(text);
+                Variant::from(Guid::from_str(guid).map_err(|_| StatusCode::BadDecodingError)?)
+            }
+            "ByteString" => {
+                let data = text.split_whitespace().collect::<String>();
+                Variant::from(ByteString::from_base64(&data).ok_or(StatusCode::BadDecodingError)?)
+            }
+            "StatusCode" => {
+                let code = element
+                    .child("Code")
+                    .map(parse::<u32>)
+                    .transpose()?
+                    .unwrap_or(0);
+                Variant::from(StatusCode::from_u32(code).ok_or(StatusCode::BadDecodingError)?)
+            }
+            "LocalizedText" => Variant::from(LocalizedText::new(
+                element.child_text("Locale").unwrap_or_default(),
+                element.child_text("Text").unwrap_or_default(),
+            )),
+            "QualifiedName" => {
+                let namespace = element
+                    .child("NamespaceIndex")
+                    .map(parse::<u16>)
+                    .transpose()?
+                    .unwrap_or(0);
+                Variant::from(QualifiedName::new(
+                    self.namespace(namespace)?,
+                    element.child_text("Name").unwrap_or_default(),
+                ))
+            }
+            "NodeId" => {
+                Variant::from(self.node_id(element.child_text("Identifier").unwrap_or(text))?)
+            }
+            "ExpandedNodeId" => Variant::from(ExpandedNodeId::new(
+                self.node_id(element.child_text("Identifier").unwrap_or(text))?,
+            )),
+            "ExtensionObject" => match self.extension_object(element)? {
+                Some(extension_object) => Variant::from(extension_object),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    /// Reads the method arguments and enum values that node sets hold in extension objects
+    fn extension_object(&self, element: &Element) -> Result<Option<ExtensionObject>, StatusCode> {
+        let Some(body) = element.child("Body").and_then(|b| b.children.first()) else {
+            return Ok(None);
+        };
+        let localized_text = |name: &str| {
+            body.child(name)
+                .map(|t| {
+                    LocalizedText::new(
+                        t.child_text("Locale").unwrap_or_default(),
+                        t.child_text("Text").unwrap_or_default(),
+                    )
+                })
+                .unwrap_or_else(LocalizedText::null)
+        };
+        let extension_object = match body.name.as_str() {
+            "Argument" => {
+                let data_type = match body
+                    .child("DataType")
+                    .and_then(|d| d.child_text("Identifier"))
+                {
+                    Some(data_type) => self.node_id(data_type)?,
+                    None => DataTypeId::BaseDataType.into(),
+                };
+                let value_rank = body
+                    .child_text("ValueRank")
+                    .map(|v| v.parse::<i32>())
+                    .transpose()
+                    .map_err(|_| StatusCode::BadDecodingError)?
+                    .unwrap_or(-1);
+                let array_dimensions = body
+                    .child("ArrayDimensions")
+                    .map(|a| {
+                        a.children("UInt32")
+                            .map(|d| d.text.trim().parse::<u32>())
+                            .collect::<Result<Vec<u32>, _>>()
+                    })
+                    .transpose()
+                    .map_err(|_| StatusCode::BadDecodingError)?
+                    .filter(|a| !a.is_empty());
+                ExtensionObject::from_encodable(
+                    ObjectId::Argument_Encoding_DefaultBinary,
+                    &Argument {
+                        name: UAString::from(body.child_text("Name").unwrap_or_default()),
+                        data_type,
+                        value_rank,
+                        array_dimensions,
+                        description: localized_text("Description"),
+                    },
+                )
+            }
+            "EnumValueType" => {
+                let value = body
+                    .child_text("Value")
+                    .map(|v| v.parse::<i64>())
+                    .transpose()
+                    .map_err(|_| StatusCode::BadDecodingError)?
+                    .unwrap_or(0);
+                ExtensionObject::from_encodable(
+                    ObjectId::EnumValueType_Encoding_DefaultBinary,
+                    &EnumValueType {
+                        value,
+                        display_name: localized_text("DisplayName"),
+                        description: localized_text("Description"),
+                    },
+                )
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(extension_object))
+    }
+}
@@ -11,6 +11,29 @@ use super::{base::Base, node::Node, node::NodeBase};
 node_builder_impl!(ReferenceTypeBuilder, ReferenceType);
 node_builder_impl_subtype!(ReferenceTypeBuilder);
 
+impl ReferenceTypeBuilder {
+    /// Sets whether the reference type means the same in both directions.
+    pub fn symmetric(mut self, symmetric: bool) -> Self {
+        self.node.set_symmetric(symmetric);
+        self
+    }
+
+    /// Sets whether the reference type is abstract.
+    pub fn is_abstract(mut self, is_abstract: bool) -> Self {
+        self.node.set_is_abstract(is_abstract);
+        self
+    }
+
+    /// Sets the name of the reference type when it is followed in the inverse direction.
+    pub fn inverse_name<V>(mut self, inverse_name: V) -> Self
+    where
+        V: Into<LocalizedText>,
+    {
+        self.node.set_inverse_name(inverse_name.into());
+        self
+    }
+}
+
 /// A `ReferenceType` is a type of node within the `AddressSpace`.
 #[derive(Debug)]
 pub struct ReferenceType {
@@ -26,6 +26,17 @@ impl Reference {
     }
 }
 
+/// A reference from a node in this address space to a node held by another server. The target
+/// is an `ExpandedNodeId` whose server index refers to an entry in the server array.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RemoteReference {
+    pub reference_type: NodeId,
+    pub target_node: ExpandedNodeId,
+    /// False if the reference is an inverse reference, i.e. it is held by the remote node and
+    /// points to the source node.
+    pub is_forward: bool,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum ReferenceDirection {
     Forward,
@@ -40,6 +51,9 @@ pub struct References {
     /// by. It is not the same as an inverse reference. A node may be referenced one or more
     /// times by the other node.
     referenced_by_map: HashMap<NodeId, HashSet<NodeId>>,
+    /// References from local nodes to nodes held by other servers. These are kept apart from
+    /// the other references since the target nodes can never be found in this address space.
+    remote_references_map: HashMap<NodeId, Vec<RemoteReference>>,
 }
 
 impl Default for References {
@@ -47,6 +61,7 @@ impl Default for References {
         Self {
             references_map: HashMap::with_capacity(2000),
             referenced_by_map: HashMap::with_capacity(2000),
+            remote_references_map: HashMap::new(),
         }
     }
 }
@@ -260,8 +275,106 @@ impl References {
         deleted
     }
 
+    /// Inserts a reference between a local node and a node held by another server. A forward
+    /// reference points from the source node to the remote node, an inverse reference points
+    /// from the remote node to the source node.
+    pub fn insert_remote_reference<T>(
+        &mut self,
+        source_node: &NodeId,
+        target_node: &ExpandedNodeId,
+        reference_type: &T,
+        reference_direction: ReferenceDirection,
+    ) where
+        T: Into<NodeId> + Clone,
+    {
+        let reference = RemoteReference {
+            reference_type: reference_type.clone().into(),
+            target_node: target_node.clone(),
+            is_forward: matches!(reference_direction, ReferenceDirection::Forward),
+        };
+        let references = self
+            .remote_references_map
+            .entry(source_node.clone())
+            .or_default();
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+
+    /// Deletes a matching reference between a local node and a node held by another server. The
+    /// function returns true if the reference was found and deleted.
+    pub fn delete_remote_reference<T>(
+        &mut self,
+        source_node: &NodeId,
+        target_node: &ExpandedNodeId,
+        reference_type: T,
+    ) -> bool
+    where
+        T: Into<NodeId>,
+    {
+        let reference_type = reference_type.into();
+        let mut deleted = false;
+        let remove_entry = if let Some(references) = self.remote_references_map.get_mut(source_node)
+        {
+            references.retain(|r| {
+                if r.reference_type == reference_type && r.target_node == *target_node {
+                    deleted = true;
+                    false
+                } else {
+                    true
+                }
+            });
+            references.is_empty()
+        } else {
+            false
+        };
+        if remove_entry {
+            self.remote_references_map.remove(source_node);
+        }
+        deleted
+    }
+
+    /// Finds the references between the node and nodes held by other servers in the browse
+    /// direction. The reference filter indicates if only references of a certain type (including
+    /// sub types) should be fetched.
+    pub fn find_remote_references<T>(
+        &self,
+        node: &NodeId,
+        browse_direction: BrowseDirection,
+        reference_filter: Option<(T, bool)>,
+    ) -> Vec<RemoteReference>
+    where
+        T: Into<NodeId> + Clone,
+    {
+        let reference_filter: Option<(NodeId, bool)> = reference_filter
+            .map(|(reference_type, include_subtypes)| (reference_type.into(), include_subtypes));
+        if let Some(references) = self.remote_references_map.get(node) {
+            references
+                .iter()
+                .filter(|r| match browse_direction {
+                    BrowseDirection::Forward => r.is_forward,
+                    BrowseDirection::Inverse => !r.is_forward,
+                    BrowseDirection::Both => true,
+                    BrowseDirection::Invalid => false,
+                })
+                .filter(|r| match reference_filter {
+                    None => true,
+                    Some((ref reference_type_id, include_subtypes)) => self.reference_type_matches(
+                        reference_type_id,
+                        &r.reference_type,
+                        include_subtypes,
+                    ),
+                })
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Deletes all references to the node.
     pub fn delete_node_references(&mut self, source_node: &NodeId) -> bool {
+        let deleted_remote_references = self.remote_references_map.remove(source_node).is_some();
         let deleted_references = if let Some(references) = self.references_map.remove(source_node) {
             // Deleted every reference from the node, and clean up the reverse lookup map
             let nodes_referenced = references
@@ -281,7 +394,7 @@ impl References {
             false
         };
 
-        deleted_references || deleted_lookups
+        deleted_references || deleted_lookups || deleted_remote_references
     }
 
     /// Test if a reference relationship exists between one node and another node
@@ -64,6 +64,22 @@ pub(crate) fn find_node_from_browse_path<'a>(
     }
 }
 
+/// Finds the node at the end of a relative path from a node, which is the node itself if the path
+/// is empty. When the path leads to more than one node, the first is returned.
+pub(crate) fn find_node_from_relative_path<'a>(
+    address_space: &'a AddressSpace,
+    node_id: &NodeId,
+    relative_path: &RelativePath,
+) -> Option<&'a NodeType> {
+    if relative_path.elements.as_ref().is_none_or(|e| e.is_empty()) {
+        address_space.find_node(node_id)
+    } else {
+        find_nodes_relative_path(address_space, node_id, relative_path)
+            .ok()
+            .and_then(|node_ids| node_ids.first().and_then(|n| address_space.find_node(n)))
+    }
+}
+
 /// Given a path as a string, find all the nodes that match against it. Note this function
 /// uses a default path resolver based on common browse names. If you need something else use
 /// `find_nodes_relative_path()` after you have created a relative path.
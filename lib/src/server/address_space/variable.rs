@@ -16,7 +16,7 @@ use crate::server::{
         node::{Node, NodeBase},
         AccessLevel, UserAccessLevel,
     },
-    callbacks::{AttributeGetter, AttributeSetter},
+    callbacks::{getter_data_value, AttributeGetter, AttributeSetter, DeferredValue},
 };
 
 // This is a builder object for constructing variable nodes programmatically.
@@ -457,21 +457,14 @@ impl Variable {
     ) -> DataValue {
         if let Some(ref value_getter) = self.value_getter {
             let mut value_getter = value_getter.lock();
-            value_getter
-                .get(
-                    &self.node_id(),
-                    timestamps_to_return,
-                    AttributeId::Value,
-                    index_range,
-                    data_encoding,
-                    max_age,
-                )
-                .unwrap_or_else(|status_code| {
-                    let mut value = DataValue::default();
-                    value.status = Some(status_code);
-                    Some(value)
-                })
-                .unwrap_or_default()
+            getter_data_value(value_getter.get(
+                &self.node_id(),
+                timestamps_to_return,
+                AttributeId::Value,
+                index_range,
+                data_encoding,
+                max_age,
+            ))
         } else {
             let data_value = &self.value;
             let mut result = DataValue {
@@ -503,6 +496,35 @@ impl Variable {
         }
     }
 
+    /// Gets the value like `value()`, except that a getter may defer it with a pending value
+    /// which is supplied later. See [`AttributeGetter::get_deferred()`].
+    pub fn value_deferred(
+        &self,
+        timestamps_to_return: TimestampsToReturn,
+        index_range: NumericRange,
+        data_encoding: &QualifiedName,
+        max_age: f64,
+    ) -> DeferredValue {
+        if let Some(ref value_getter) = self.value_getter {
+            let mut value_getter = value_getter.lock();
+            value_getter.get_deferred(
+                &self.node_id(),
+                timestamps_to_return,
+                AttributeId::Value,
+                index_range,
+                data_encoding,
+                max_age,
+            )
+        } else {
+            DeferredValue::Ready(self.value(
+                timestamps_to_return,
+                index_range,
+                data_encoding,
+                max_age,
+            ))
+        }
+    }
+
     /// Sets the variable's `Variant` value. The timestamps for the change are updated to now.
     pub fn set_value<V>(&mut self, index_range: NumericRange, value: V) -> Result<(), StatusCode>
     where
@@ -588,6 +610,12 @@ impl Variable {
         self.value_getter = Some(value_getter);
     }
 
+    /// Returns `true` if the value of the variable comes from a getter rather than being held
+    /// by the variable
+    pub fn has_value_getter(&self) -> bool {
+        self.value_getter.is_some()
+    }
+
     /// Sets a setter function that will be called to set the value of this variable.
     pub fn set_value_setter(&mut self, value_setter: Arc<Mutex<dyn AttributeSetter + Send>>) {
         self.value_setter = Some(value_setter);
@@ -15,6 +15,44 @@ node_builder_impl!(VariableTypeBuilder, VariableType);
 node_builder_impl_generates_event!(VariableTypeBuilder);
 node_builder_impl_subtype!(VariableTypeBuilder);
 
+impl VariableTypeBuilder {
+    /// Sets the data type of variables of the type.
+    pub fn data_type<T>(mut self, data_type: T) -> Self
+    where
+        T: Into<NodeId>,
+    {
+        self.node.set_data_type(data_type);
+        self
+    }
+
+    /// Sets whether the variable type is abstract.
+    pub fn is_abstract(mut self, is_abstract: bool) -> Self {
+        self.node.set_is_abstract(is_abstract);
+        self
+    }
+
+    /// Sets the value rank of variables of the type.
+    pub fn value_rank(mut self, value_rank: i32) -> Self {
+        self.node.set_value_rank(value_rank);
+        self
+    }
+
+    /// Sets the array dimensions of variables of the type.
+    pub fn array_dimensions(mut self, array_dimensions: &[u32]) -> Self {
+        self.node.set_array_dimensions(array_dimensions);
+        self
+    }
+
+    /// Sets the default value of variables of the type.
+    pub fn value<V>(mut self, value: V) -> Self
+    where
+        V: Into<Variant>,
+    {
+        self.node.set_value(value);
+        self
+    }
+}
+
 /// A `VariableType` is a type of node within the `AddressSpace`.
 #[derive(Debug)]
 pub struct VariableType {
@@ -0,0 +1,169 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Authentication of the users that activate sessions. The server checks the policy of a user
+//! identity token, decrypts its password or verifies its signature, and then asks a
+//! [`UserAuthenticator`] who the user is. By default this is a [`ConfigUserAuthenticator`] that
+//! looks the user up in the `user_tokens` of the [`ServerConfig`].
+//!
+//! [`ServerConfig`]: ../config/struct.ServerConfig.html
+
+use std::collections::BTreeMap;
+
+use crate::crypto::X509;
+use crate::types::{service_types::UserTokenType, status_code::StatusCode, NodeId};
+
+use super::config::{ServerEndpoint, ServerUserToken, ANONYMOUS_USER_TOKEN_ID};
+
+/// The credentials that a client presented to activate a session.
+pub enum UserToken<'a> {
+    /// No credentials
+    Anonymous,
+    /// A user name and its password, decrypted if the client encrypted it
+    UserName { user: &'a str, password: &'a str },
+    /// A certificate whose private key the client has proven it holds
+    X509 { certificate: &'a X509 },
+}
+
+impl UserToken<'_> {
+    /// The type of the token as it is advertised in an endpoint's user token policies.
+    pub fn token_type(&self) -> UserTokenType {
+        match self {
+            UserToken::Anonymous => UserTokenType::Anonymous,
+            UserToken::UserName { .. } => UserTokenType::UserName,
+            UserToken::X509 { .. } => UserTokenType::Certificate,
+        }
+    }
+}
+
+/// A user that has been authenticated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserIdentity {
+    /// Identifies the user, e.g. the id of the user token in the config
+    pub user_token_id: String,
+    /// The roles of the user. The server adds the well-known `AuthenticatedUser` role to these,
+    /// or uses the `Anonymous` role for an anonymous user.
+    pub roles: Vec<NodeId>,
+}
+
+impl UserIdentity {
+    pub fn new<T>(user_token_id: T, roles: Vec<NodeId>) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            user_token_id: user_token_id.into(),
+            roles,
+        }
+    }
+}
+
+/// Authenticates the users of sessions, allowing them to be held elsewhere than in the server's
+/// config, e.g. in LDAP, the accounts of the OS or a database. Set it with
+/// `ServerState::set_user_authenticator()`.
+pub trait UserAuthenticator {
+    /// Returns the types of user token that the endpoint accepts. The server advertises these in
+    /// the endpoint's user token policies and rejects tokens of any other type.
+    fn user_token_types(&self, endpoint: &ServerEndpoint) -> Vec<UserTokenType>;
+
+    /// Authenticates a user on the endpoint, returning `BadUserAccessDenied` or
+    /// `BadIdentityTokenRejected` if the credentials are not accepted.
+    fn authenticate(
+        &self,
+        token: &UserToken,
+        endpoint: &ServerEndpoint,
+    ) -> Result<UserIdentity, StatusCode>;
+}
+
+/// Authenticates users against the user tokens of the server's config that an endpoint lists in
+/// its `user_token_ids`.
+pub struct ConfigUserAuthenticator {
+    user_tokens: BTreeMap<String, ServerUserToken>,
+}
+
+impl ConfigUserAuthenticator {
+    pub fn new(user_tokens: &BTreeMap<String, ServerUserToken>) -> Self {
+        Self {
+            user_tokens: user_tokens.clone(),
+        }
+    }
+
+    /// Finds the user tokens of the endpoint
+    fn endpoint_user_tokens<'a>(
+        &'a self,
+        endpoint: &'a ServerEndpoint,
+    ) -> impl Iterator<Item = (&'a String, &'a ServerUserToken)> {
+        endpoint
+            .user_token_ids
+            .iter()
+            .filter(|id| id.as_str() != ANONYMOUS_USER_TOKEN_ID)
+            .filter_map(move |id| self.user_tokens.get(id).map(|user_token| (id, user_token)))
+    }
+
+    fn identity(user_token_id: &str, user_token: &ServerUserToken) -> UserIdentity {
+        // Roles are checked when the config is validated
+        UserIdentity::new(user_token_id, user_token.role_ids().unwrap_or_default())
+    }
+}
+
+impl UserAuthenticator for ConfigUserAuthenticator {
+    fn user_token_types(&self, endpoint: &ServerEndpoint) -> Vec<UserTokenType> {
+        let mut user_token_types = Vec::with_capacity(3);
+        if endpoint.supports_anonymous() {
+            user_token_types.push(UserTokenType::Anonymous);
+        }
+        if endpoint.supports_user_pass(&self.user_tokens) {
+            user_token_types.push(UserTokenType::UserName);
+        }
+        if endpoint.supports_x509(&self.user_tokens) {
+            user_token_types.push(UserTokenType::Certificate);
+        }
+        user_token_types
+    }
+
+    fn authenticate(
+        &self,
+        token: &UserToken,
+        endpoint: &ServerEndpoint,
+    ) -> Result<UserIdentity, StatusCode> {
+        match *token {
+            UserToken::Anonymous => {
+                if endpoint.supports_anonymous() {
+                    Ok(UserIdentity::new(ANONYMOUS_USER_TOKEN_ID, Vec::new()))
+                } else {
+                    Err(StatusCode::BadIdentityTokenRejected)
+                }
+            }
+            UserToken::UserName { user, password } => {
+                if let Some((user_token_id, user_token)) = self
+                    .endpoint_user_tokens(endpoint)
+                    .find(|(_, t)| t.is_user_pass() && t.user == user)
+                {
+                    // Password compared as UTF-8 bytes, a user without a password has an empty one
+                    let server_password = user_token.pass.as_deref().unwrap_or("");
+                    if server_password.as_bytes() == password.as_bytes() {
+                        Ok(Self::identity(user_token_id, user_token))
+                    } else {
+                        error!("Cannot authenticate \"{}\", password is invalid", user);
+                        Err(StatusCode::BadUserAccessDenied)
+                    }
+                } else {
+                    error!(
+                        "Cannot authenticate \"{}\", user not found for endpoint",
+                        user
+                    );
+                    Err(StatusCode::BadUserAccessDenied)
+                }
+            }
+            UserToken::X509 { certificate } => {
+                // The signing cert matches a user's identity, so it is valid
+                let thumbprint = certificate.thumbprint();
+                self.endpoint_user_tokens(endpoint)
+                    .find(|(_, t)| t.thumbprint.as_ref() == Some(&thumbprint))
+                    .map(|(user_token_id, user_token)| Self::identity(user_token_id, user_token))
+                    .ok_or(StatusCode::BadIdentityTokenInvalid)
+            }
+        }
+    }
+}
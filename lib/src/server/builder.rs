@@ -4,10 +4,15 @@
 
 use std::path::PathBuf;
 
-use crate::core::config::Config;
+use crate::core::config::{Config, ConfigOverrideError};
+use crate::crypto::SecurityPolicy;
+use crate::types::MessageSecurityMode;
 
 use super::{
-    config::{ServerConfig, ServerEndpoint, ServerUserToken, ANONYMOUS_USER_TOKEN_ID},
+    config::{
+        HttpsConfig, OperationalLimits, ServerConfig, ServerEndpoint, ServerUserToken,
+        WebSocketConfig, ANONYMOUS_USER_TOKEN_ID,
+    },
     constants,
     server::Server,
 };
@@ -82,6 +87,7 @@ impl ServerBuilder {
                     pass: Some("sample1pwd".to_string()),
                     x509: None,
                     thumbprint: None,
+                    roles: Vec::new(),
                 },
             )
             .user_token(
@@ -91,6 +97,7 @@ impl ServerBuilder {
                     pass: None,
                     x509: Some("./users/sample-x509.der".to_string()),
                     thumbprint: None,
+                    roles: Vec::new(),
                 },
             )
             .user_token(
@@ -100,55 +107,59 @@ impl ServerBuilder {
                     pass: Some("unused1".to_string()),
                     x509: None,
                     thumbprint: None,
+                    roles: Vec::new(),
                 },
             )
-            .endpoints(vec![
-                ("none", ServerEndpoint::new_none(path, &user_token_ids)),
-                (
-                    "basic128rsa15_sign",
-                    ServerEndpoint::new_basic128rsa15_sign(path, &user_token_ids),
-                ),
-                (
-                    "basic128rsa15_sign_encrypt",
-                    ServerEndpoint::new_basic128rsa15_sign_encrypt(path, &user_token_ids),
-                ),
-                (
-                    "aes128-sha256-rsaoaep_sign",
-                    ServerEndpoint::new_aes128_sha256_rsaoaep_sign(path, &user_token_ids),
-                ),
-                (
-                    "aes128-sha256-rsaoaep_sign_encrypt",
-                    ServerEndpoint::new_aes128_sha256_rsaoaep_sign_encrypt(path, &user_token_ids),
-                ),
-                (
-                    "aes256-sha256-rsapss_sign",
-                    ServerEndpoint::new_aes256_sha256_rsapss_sign(path, &user_token_ids),
-                ),
-                (
-                    "aes256-sha256-rsapss_sign_encrypt",
-                    ServerEndpoint::new_aes256_sha256_rsapss_sign_encrypt(path, &user_token_ids),
-                ),
-                (
-                    "basic256_sign",
-                    ServerEndpoint::new_basic256_sign(path, &user_token_ids),
-                ),
-                (
-                    "basic256_sign_encrypt",
-                    ServerEndpoint::new_basic256_sign_encrypt(path, &user_token_ids),
-                ),
-                (
-                    "basic256sha256_sign",
-                    ServerEndpoint::new_basic256sha256_sign(path, &user_token_ids),
-                ),
-                (
-                    "basic256sha256_sign_encrypt",
-                    ServerEndpoint::new_basic256sha256_sign_encrypt(path, &user_token_ids),
-                ),
-                ("no_access", ServerEndpoint::new_none("/noaccess", &[])),
-            ])
+            .endpoints_for_path(
+                path,
+                &[
+                    (SecurityPolicy::None, MessageSecurityMode::None),
+                    (SecurityPolicy::Basic128Rsa15, MessageSecurityMode::Sign),
+                    (
+                        SecurityPolicy::Basic128Rsa15,
+                        MessageSecurityMode::SignAndEncrypt,
+                    ),
+                    (
+                        SecurityPolicy::Aes128Sha256RsaOaep,
+                        MessageSecurityMode::Sign,
+                    ),
+                    (
+                        SecurityPolicy::Aes128Sha256RsaOaep,
+                        MessageSecurityMode::SignAndEncrypt,
+                    ),
+                    (
+                        SecurityPolicy::Aes256Sha256RsaPss,
+                        MessageSecurityMode::Sign,
+                    ),
+                    (
+                        SecurityPolicy::Aes256Sha256RsaPss,
+                        MessageSecurityMode::SignAndEncrypt,
+                    ),
+                    (SecurityPolicy::Basic256, MessageSecurityMode::Sign),
+                    (
+                        SecurityPolicy::Basic256,
+                        MessageSecurityMode::SignAndEncrypt,
+                    ),
+                    (SecurityPolicy::Basic256Sha256, MessageSecurityMode::Sign),
+                    (
+                        SecurityPolicy::Basic256Sha256,
+                        MessageSecurityMode::SignAndEncrypt,
+                    ),
+                ],
+                &user_token_ids,
+            )
+            .endpoint("no_access", ServerEndpoint::new_none("/noaccess", &[]))
             .discovery_urls(vec![DEFAULT_ENDPOINT_PATH.into()])
     }
 
+    /// Overrides the values set so far with those of environment variables, e.g. `OPCUA_SERVER_PORT`.
+    /// Values set on the builder afterwards override them in turn.
+    pub fn with_env_overrides(self) -> Result<Self, ConfigOverrideError> {
+        Ok(Self {
+            config: self.config.with_env_overrides()?,
+        })
+    }
+
     /// Yields a [`Server`] from the values set by the builder. If the builder is not in a valid state
     /// it will return `None`.
     ///
@@ -182,6 +193,19 @@ impl ServerBuilder {
         self
     }
 
+    /// Sets the application name for a locale, e.g. "de". Clients that ask for that locale in
+    /// `FindServers` or `GetEndpoints` are given this name.
+    pub fn localized_application_name<S, T>(mut self, locale_id: S, application_name: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.config
+            .localized_application_names
+            .insert(locale_id.into(), application_name.into());
+        self
+    }
+
     /// Sets the application uri
     pub fn application_uri<T>(mut self, application_uri: T) -> Self
     where
@@ -200,6 +224,25 @@ impl ServerBuilder {
         self
     }
 
+    /// Sets the manufacturer name reported in the server's build info.
+    pub fn manufacturer_name<T>(mut self, manufacturer_name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.config.manufacturer_name = Some(manufacturer_name.into());
+        self
+    }
+
+    /// Sets the build number reported in the server's build info. The crate version is reported
+    /// if this is not set.
+    pub fn build_number<T>(mut self, build_number: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.config.build_number = Some(build_number.into());
+        self
+    }
+
     /// Sets whether the server should generate its own key pair if there is none found in the pki
     /// directory.
     pub fn create_sample_keypair(mut self, create_sample_keypair: bool) -> Self {
@@ -239,6 +282,39 @@ impl ServerBuilder {
         self
     }
 
+    /// Sets the node set files in the UANodeSet XML format that are imported into the address
+    /// space when the server is created, e.g. companion specifications the server implements.
+    pub fn nodesets<T>(mut self, nodesets: Vec<T>) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        self.config.nodesets = nodesets.into_iter().map(|n| n.into()).collect();
+        self
+    }
+
+    /// Sets whether the server raises audit events for security related operations. Auditing
+    /// is on by default.
+    pub fn auditing(mut self, auditing: bool) -> Self {
+        self.config.auditing = auditing;
+        self
+    }
+
+    /// Sets a file that the traffic of every connection is captured to, for debugging.
+    pub fn capture_file<T>(mut self, capture_file: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        self.config.capture_file = Some(capture_file.into());
+        self
+    }
+
+    /// Sets whether the decoded messages of every connection are captured to the capture file as
+    /// well as its chunks. Off by default.
+    pub fn capture_messages(mut self, capture_messages: bool) -> Self {
+        self.config.capture_messages = capture_messages;
+        self
+    }
+
     /// Adds an endpoint to the list of endpoints the server knows of.
     pub fn endpoint<T>(mut self, endpoint_id: T, endpoint: ServerEndpoint) -> Self
     where
@@ -259,6 +335,45 @@ impl ServerBuilder {
         self
     }
 
+    /// Adds an endpoint on the path for each of the security policy and mode pairs, accepting the
+    /// same user tokens. Each endpoint's id is made from its policy and mode, e.g.
+    /// `basic256sha256_sign_encrypt`, or `none` for no security, and is prefixed with the path
+    /// when that isn't `/`, e.g. `plant_none` for `/plant`.
+    pub fn endpoints_for_path<T>(
+        mut self,
+        path: &str,
+        security: &[(SecurityPolicy, MessageSecurityMode)],
+        user_token_ids: &[T],
+    ) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let user_token_ids = user_token_ids
+            .iter()
+            .map(|id| id.as_ref().to_string())
+            .collect::<Vec<_>>();
+        for (security_policy, security_mode) in security {
+            let mut endpoint_id = match security_mode {
+                MessageSecurityMode::Sign => {
+                    format!("{}_sign", security_policy.to_str().to_lowercase())
+                }
+                MessageSecurityMode::SignAndEncrypt => {
+                    format!("{}_sign_encrypt", security_policy.to_str().to_lowercase())
+                }
+                _ => security_policy.to_str().to_lowercase(),
+            };
+            let prefix = path.trim_matches('/');
+            if !prefix.is_empty() {
+                endpoint_id = format!("{}_{}", prefix.replace('/', "_"), endpoint_id);
+            }
+            self.config.endpoints.insert(
+                endpoint_id,
+                ServerEndpoint::new(path, *security_policy, *security_mode, &user_token_ids),
+            );
+        }
+        self
+    }
+
     /// Adds a user token to the server.
     pub fn user_token<T>(mut self, user_token_id: T, user_token: ServerUserToken) -> Self
     where
@@ -286,6 +401,21 @@ impl ServerBuilder {
         self
     }
 
+    /// Sets the port the server listens on for opc.wss connections, in addition to the opc.tcp
+    /// port. The host is the same as the opc.tcp host.
+    pub fn websocket_port(mut self, port: u16) -> Self {
+        self.config.websocket_config = Some(WebSocketConfig { port });
+        self
+    }
+
+    /// Sets the port the server listens on for https connections, in addition to the opc.tcp
+    /// port. The host is the same as the opc.tcp host. Only endpoints without security are
+    /// offered over https.
+    pub fn https_port(mut self, port: u16) -> Self {
+        self.config.https_config = Some(HttpsConfig { port });
+        self
+    }
+
     /// Discovery endpoint urls - the urls of this server used by clients to get endpoints.
     /// If the url is relative, e.g. "/" then the code will make a url for you using the port/host
     /// settings as they are at the time this function is executed.
@@ -307,18 +437,64 @@ impl ServerBuilder {
         self
     }
 
+    /// Set the maximum number of sessions across all connections of the server
+    pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+        self.config.limits.max_sessions = max_sessions;
+        self
+    }
+
+    /// Set the maximum session timeout in milliseconds. Sessions that go without a service call
+    /// for longer than their timeout are closed.
+    pub fn max_session_timeout(mut self, max_session_timeout: f64) -> Self {
+        self.config.limits.max_session_timeout = max_session_timeout;
+        self
+    }
+
     /// Set the maximum number of subscriptions in a session
     pub fn max_subscriptions(mut self, max_subscriptions: usize) -> Self {
         self.config.limits.max_subscriptions = max_subscriptions;
         self
     }
 
+    /// Set the maximum number of subscriptions across all sessions of the server
+    pub fn max_server_subscriptions(mut self, max_server_subscriptions: usize) -> Self {
+        self.config.limits.max_server_subscriptions = max_server_subscriptions;
+        self
+    }
+
+    /// Set the minimum publishing interval of subscriptions in seconds. Clients that request a
+    /// shorter interval have it revised up to this value.
+    pub fn min_publishing_interval(mut self, min_publishing_interval: f64) -> Self {
+        self.config.limits.min_publishing_interval = min_publishing_interval;
+        self
+    }
+
     /// Set the maximum number of monitored items per subscription
     pub fn max_monitored_items_per_sub(mut self, max_monitored_items_per_sub: usize) -> Self {
         self.config.limits.max_monitored_items_per_sub = max_monitored_items_per_sub;
         self
     }
 
+    /// Set the maximum number of values queued by a monitored item
+    pub fn max_monitored_item_queue_size(mut self, max_monitored_item_queue_size: usize) -> Self {
+        self.config.limits.max_monitored_item_queue_size = max_monitored_item_queue_size;
+        self
+    }
+
+    /// Set the minimum sampling interval of monitored items in seconds. Clients that request a
+    /// shorter interval have it revised up to this value.
+    pub fn min_sampling_interval(mut self, min_sampling_interval: f64) -> Self {
+        self.config.limits.min_sampling_interval = min_sampling_interval;
+        self
+    }
+
+    /// Set the most operations that a client may ask for in one call of each service. These are
+    /// advertised in `Server/ServerCapabilities/OperationLimits`.
+    pub fn operational_limits(mut self, operational_limits: OperationalLimits) -> Self {
+        self.config.limits.operational = operational_limits;
+        self
+    }
+
     /// Set the max array length in elements
     pub fn max_array_length(mut self, max_array_length: usize) -> Self {
         self.config.limits.max_array_length = max_array_length;
@@ -376,6 +552,13 @@ impl ServerBuilder {
         self
     }
 
+    /// Set that nodes clients add through the node management service are deleted when the
+    /// session that added them closes. By default, they persist.
+    pub fn session_lifetime_nodes(mut self) -> Self {
+        self.config.limits.session_lifetime_nodes = true;
+        self
+    }
+
     /// Configures the server to use a single-threaded executor. The default executor uses a
     /// thread pool with a worker thread for each CPU core available on the system.
     pub fn single_threaded_executor(mut self) -> Self {
@@ -388,4 +571,27 @@ impl ServerBuilder {
         self.config.performance.single_threaded_executor = false;
         self
     }
+
+    /// Sets the name of the executor's threads. The default is `opcua-server`.
+    pub fn thread_name<T>(mut self, thread_name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.config.performance.thread_name = thread_name.into();
+        self
+    }
+
+    /// Sets the maximum number of namespaces that the nodes of a single Read request are read
+    /// from concurrently. The default of 1 reads every node in turn.
+    pub fn max_concurrent_reads(mut self, max_concurrent_reads: usize) -> Self {
+        self.config.performance.max_concurrent_reads = max_concurrent_reads;
+        self
+    }
+
+    /// Sets the maximum number of responses of a connection that may wait to be written before
+    /// the server stops reading requests from it. The default is 64.
+    pub fn max_queued_responses(mut self, max_queued_responses: usize) -> Self {
+        self.config.performance.max_queued_responses = max_queued_responses;
+        self
+    }
 }
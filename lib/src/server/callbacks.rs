@@ -6,14 +6,19 @@
 
 use std::sync::Arc;
 
+use tokio::sync::oneshot;
+
 use crate::sync::*;
 use crate::types::{
     service_types::{CallMethodRequest, CallMethodResult, TimestampsToReturn},
     status_code::StatusCode,
-    AttributeId, DataValue, NodeId, NumericRange, QualifiedName,
+    AttributeId, DataValue, NodeId, NumericRange, QualifiedName, WriteMask,
 };
 
-use super::session::{Session, SessionManager};
+use super::{
+    address_space::UserAccessLevel,
+    session::{Session, SessionManager},
+};
 
 /// An attribute getter trait is used to obtain the data value associated with the particular attribute id
 /// This allows server implementations to supply a value on demand, usually in response to a polling action
@@ -40,6 +45,93 @@ pub trait AttributeGetter {
         data_encoding: &QualifiedName,
         max_age: f64,
     ) -> Result<Option<DataValue>, StatusCode>;
+
+    /// Returns a data value of the specified attribute for a Read request, or a [`PendingValue`]
+    /// when the value is not at hand and must first be fetched, e.g. from a slow field device that
+    /// the server is a gateway to. The Read service parks a request with pending values and sends
+    /// its response once they have completed, rather than blocking the thread that serves it.
+    /// Monitored items only ever call `get()`.
+    ///
+    /// The default implementation calls `get()`.
+    fn get_deferred(
+        &mut self,
+        node_id: &NodeId,
+        timestamps_to_return: TimestampsToReturn,
+        attribute_id: AttributeId,
+        index_range: NumericRange,
+        data_encoding: &QualifiedName,
+        max_age: f64,
+    ) -> DeferredValue {
+        DeferredValue::Ready(getter_data_value(self.get(
+            node_id,
+            timestamps_to_return,
+            attribute_id,
+            index_range,
+            data_encoding,
+            max_age,
+        )))
+    }
+}
+
+/// The value of an attribute returned by [`AttributeGetter::get_deferred()`]
+pub enum DeferredValue {
+    /// The value is at hand
+    Ready(DataValue),
+    /// The value is supplied later through the completer of the pending value
+    Pending(PendingValue),
+}
+
+/// A value that a getter supplies after it has returned. It is created along with the
+/// [`ValueCompleter`] that the getter passes to whatever fetches the value. A read of a value
+/// whose completer is dropped, or that does not complete within the timeout of the request, is
+/// `BadWaitingForInitialData`.
+pub struct PendingValue(oneshot::Receiver<DataValue>);
+
+/// Completes a [`PendingValue`]
+pub struct ValueCompleter(oneshot::Sender<DataValue>);
+
+impl PendingValue {
+    pub fn channel() -> (PendingValue, ValueCompleter) {
+        let (tx, rx) = oneshot::channel();
+        (PendingValue(rx), ValueCompleter(tx))
+    }
+
+    /// Waits for the value until the deadline.
+    pub(crate) async fn wait(self, deadline: tokio::time::Instant) -> DataValue {
+        match tokio::time::timeout_at(deadline, self.0).await {
+            Ok(Ok(data_value)) => data_value,
+            _ => status_data_value(StatusCode::BadWaitingForInitialData),
+        }
+    }
+
+    /// Takes the value if it has already completed.
+    pub(crate) fn try_take(mut self) -> DataValue {
+        self.0
+            .try_recv()
+            .unwrap_or_else(|_| status_data_value(StatusCode::BadWaitingForInitialData))
+    }
+}
+
+impl ValueCompleter {
+    /// Completes the pending value with the data value. The read that waits for it is answered
+    /// once its other pending values have completed too.
+    pub fn complete(self, data_value: DataValue) {
+        let _ = self.0.send(data_value);
+    }
+}
+
+/// The data value of a getter's result, where an error is the status of a value without one.
+pub(crate) fn getter_data_value(result: Result<Option<DataValue>, StatusCode>) -> DataValue {
+    result
+        .unwrap_or_else(|status_code| Some(status_data_value(status_code)))
+        .unwrap_or_default()
+}
+
+fn status_data_value(status_code: StatusCode) -> DataValue {
+    DataValue {
+        status: Some(status_code),
+        ..Default::default()
+    }
 }
 
 // An attribute setter. Sets the value on the specified attribute
@@ -99,3 +191,59 @@ pub trait Method {
         request: &CallMethodRequest,
     ) -> Result<CallMethodResult, StatusCode>;
 }
+
+/// Decides what the user of a session may see and do with the nodes in the address space. It is
+/// consulted when the `UserAccessLevel`, `UserWriteMask` and `UserExecutable` attributes are read,
+/// when values are read or written, and when nodes are browsed.
+///
+/// Each function is given the value held by the node and returns the value that applies to the
+/// user of the session, typically the same or more restrictive. The user can be identified through
+/// `Session::client_user_id()`. The default implementations leave the node's values unchanged and
+/// allow every node to be browsed.
+pub trait AuthorizationHandler {
+    /// Returns the access level of the session's user to a variable.
+    fn user_access_level(
+        &self,
+        _session: &Session,
+        _node_id: &NodeId,
+        user_access_level: UserAccessLevel,
+    ) -> UserAccessLevel {
+        user_access_level
+    }
+
+    /// Returns which attributes of a node the session's user may write.
+    fn user_write_mask(
+        &self,
+        _session: &Session,
+        _node_id: &NodeId,
+        user_write_mask: WriteMask,
+    ) -> WriteMask {
+        user_write_mask
+    }
+
+    /// Returns whether the session's user may call a method.
+    fn user_executable(
+        &self,
+        _session: &Session,
+        _node_id: &NodeId,
+        user_executable: bool,
+    ) -> bool {
+        user_executable
+    }
+
+    /// Returns whether the session's user may see the node when browsing. Nodes that are not
+    /// browsable are left out of browse results as if they did not exist.
+    fn is_browsable(&self, _session: &Session, _node_id: &NodeId) -> bool {
+        true
+    }
+}
+
+/// Receives the audit events raised by the server so they can be forwarded elsewhere, e.g. to a
+/// separate audit log or a security monitoring system. Events are passed on whether or not they
+/// could be added to the address space, and only while auditing is enabled in the config.
+pub trait AuditSink {
+    /// Called for each audit event. `event_type_id` identifies the type of event, e.g.
+    /// `ObjectTypeId::AuditCreateSessionEventType`, and `message` holds the fields of the event as
+    /// comma separated `name=value` pairs.
+    fn audit(&self, event_type_id: &NodeId, message: &str);
+}
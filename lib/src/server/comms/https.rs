@@ -0,0 +1,213 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Accepts https connections. Each connection is a TLS session, using the application instance
+//! certificate of the server, that carries requests in HTTP POSTs. There is no secure channel over
+//! HTTPS, so the requests of every connection are passed to one transport over a channel with no
+//! security, which the server opens itself. Sessions are told apart by their authentication token
+//! so it doesn't matter which connection a request comes on.
+
+use std::{collections::HashMap, future::Future};
+
+use tokio::{
+    io::{AsyncRead, AsyncWrite, DuplexStream},
+    net::TcpStream,
+    sync::{
+        mpsc::{unbounded_channel, UnboundedSender},
+        oneshot,
+    },
+};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+
+use crate::core::{
+    comms::{https::*, secure_channel::Role, tcp_codec::Message, tcp_types::HelloMessage},
+    supported_message::SupportedMessage,
+};
+use crate::server::services::message_handler::MessageHandler;
+use crate::types::{
+    encoding::{BinaryEncoder, DecodingOptions},
+    service_types::{
+        MessageSecurityMode, OpenSecureChannelRequest, SecurityTokenRequestType, ServiceFault,
+    },
+    status_code::StatusCode,
+    ByteString, RequestHeader,
+};
+
+/// A request for the transport and where its response goes
+type Call = (SupportedMessage, oneshot::Sender<SupportedMessage>);
+
+/// The channel that carries the requests of https connections to the transport that serves them.
+/// The channel closes when the transport does.
+#[derive(Clone)]
+pub(crate) struct HttpsChannel {
+    calls: UnboundedSender<Call>,
+}
+
+impl HttpsChannel {
+    /// Opens a channel with no security to the transport at the other end of the stream, and
+    /// spawns the task that passes requests to it and responses back.
+    pub async fn open(
+        stream: DuplexStream,
+        endpoint_url: &str,
+        decoding_options: DecodingOptions,
+        max_chunk_size: usize,
+    ) -> Result<HttpsChannel, StatusCode> {
+        let mut stream = ChunkStream::new(stream, Role::Client, decoding_options.clone());
+        let hello = HelloMessage::new(
+            endpoint_url,
+            max_chunk_size,
+            max_chunk_size,
+            decoding_options.max_message_size,
+            decoding_options.max_chunk_count,
+        );
+        stream.write_raw(&hello.encode_to_vec()).await?;
+        let acknowledge = match stream.read_raw().await? {
+            Some(Message::Acknowledge(acknowledge)) => acknowledge,
+            other => {
+                error!(
+                    "Expected an acknowledge from the transport, got {:?}",
+                    other
+                );
+                return Err(StatusCode::BadCommunicationError);
+            }
+        };
+        stream.set_max_chunk_size(acknowledge.receive_buffer_size as usize);
+
+        // The channel lives as long as the transport so its token is never renewed
+        let request = OpenSecureChannelRequest {
+            request_header: RequestHeader::dummy(),
+            client_protocol_version: 0,
+            request_type: SecurityTokenRequestType::Issue,
+            security_mode: MessageSecurityMode::None,
+            client_nonce: ByteString::null(),
+            requested_lifetime: u32::MAX,
+        };
+        stream.write_message(1, &request.into()).await?;
+        match stream.read_message().await? {
+            Some((_, SupportedMessage::OpenSecureChannelResponse(response))) => {
+                stream
+                    .secure_channel_mut()
+                    .set_security_token(response.security_token);
+            }
+            other => {
+                error!("Transport did not open the HTTPS channel, got {:?}", other);
+                return Err(StatusCode::BadSecureChannelClosed);
+            }
+        }
+
+        let (calls, mut calls_rx) = unbounded_channel::<Call>();
+        tokio::spawn(async move {
+            let mut pending_calls: HashMap<u32, oneshot::Sender<SupportedMessage>> = HashMap::new();
+            let mut last_request_id = 1;
+            loop {
+                tokio::select! {
+                    message = stream.read_message() => match message {
+                        Ok(Some((request_id, response))) => {
+                            if let Some(response_tx) = pending_calls.remove(&request_id) {
+                                let _ = response_tx.send(response);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(status_code) => {
+                            error!("HTTPS channel cannot read from the transport, {}", status_code);
+                            break;
+                        }
+                    },
+                    call = calls_rx.recv() => {
+                        let Some((request, response_tx)) = call else {
+                            break;
+                        };
+                        last_request_id += 1;
+                        if stream.write_message(last_request_id, &request).await.is_err() {
+                            break;
+                        }
+                        pending_calls.insert(last_request_id, response_tx);
+                    }
+                }
+            }
+            debug!("HTTPS channel to the transport is closed");
+        });
+        Ok(HttpsChannel { calls })
+    }
+
+    /// Sends the request to the transport and waits for its response.
+    pub async fn call(&self, request: SupportedMessage) -> Result<SupportedMessage, StatusCode> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.calls
+            .send((request, response_tx))
+            .map_err(|_| StatusCode::BadSecureChannelClosed)?;
+        response_rx
+            .await
+            .map_err(|_| StatusCode::BadSecureChannelClosed)
+    }
+
+    /// Tests if the transport has closed, in which case a new channel must be opened
+    pub fn is_closed(&self) -> bool {
+        self.calls.is_closed()
+    }
+}
+
+/// Accepts the TLS session of a new connection.
+pub(crate) async fn accept(
+    acceptor: &TlsAcceptor,
+    socket: TcpStream,
+) -> Result<TlsStream<TcpStream>, StatusCode> {
+    acceptor.accept(socket).await.map_err(|err| {
+        error!("TLS handshake of https connection failed, {}", err);
+        StatusCode::BadSecureChannelClosed
+    })
+}
+
+/// Answers the requests of an https connection until the client closes it. Each request is
+/// passed to the channel that `channel` returns. Requests that belong to a secure channel, such
+/// as OpenSecureChannel, are answered with a fault since there is no secure channel to act on.
+pub(crate) async fn serve<S, F, Fut>(
+    mut stream: S,
+    decoding_options: DecodingOptions,
+    channel: F,
+) -> Result<(), StatusCode>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<HttpsChannel, StatusCode>>,
+{
+    loop {
+        let request = match read_https_request(&mut stream, decoding_options.max_message_size).await
+        {
+            Ok(request) => request,
+            Err(StatusCode::BadConnectionClosed) => return Ok(()),
+            Err(status_code) => return Err(status_code),
+        };
+        let message = match decode_https_body(&request.body, &decoding_options) {
+            Ok(message) if message.is_request() => message,
+            result => {
+                error!("HTTPS request does not hold a request, {:?}", result.err());
+                write_https_error(&mut stream, "400 Bad Request").await?;
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        let response = if MessageHandler::is_handled(&message) {
+            match channel().await {
+                Ok(channel) => channel.call(message.clone()).await,
+                Err(status_code) => Err(status_code),
+            }
+            .unwrap_or_else(|status_code| {
+                error!("HTTPS request could not be served, {}", status_code);
+                ServiceFault::new(message.request_header(), status_code).into()
+            })
+        } else {
+            error!("HTTPS request is not supported, {:?}", message);
+            ServiceFault::new(message.request_header(), StatusCode::BadServiceUnsupported).into()
+        };
+        write_https_response(
+            &mut stream,
+            &encode_https_body(&response)?,
+            request.keep_alive,
+        )
+        .await?;
+        if !request.keep_alive {
+            return Ok(());
+        }
+    }
+}
@@ -7,5 +7,11 @@
 
 mod secure_channel_service;
 
+#[cfg(feature = "https")]
+pub(crate) mod https;
 pub mod tcp_transport;
+#[cfg(any(feature = "websocket", feature = "https"))]
+pub(crate) mod tls;
 pub mod transport;
+#[cfg(feature = "websocket")]
+pub(crate) mod websocket;
@@ -186,6 +186,8 @@ impl SecureChannelService {
             },
             server_nonce: secure_channel.local_nonce_as_byte_string(),
         };
+        // Record when the token was issued and its lifetime
+        secure_channel.set_security_token(response.security_token.clone());
         Ok(response.into())
     }
 
@@ -9,17 +9,27 @@
 //! responses. i.e. the client is expected to call and wait for a response to their request.
 //! Publish requests are sent based on the number of subscriptions and the responses / handling are
 //! left to asynchronous event handlers.
+//!
+//! Each connection is served by a few tokio tasks rather than threads of its own. Responses wait
+//! in a queue for the writing task, and the reading task stops reading requests while the queue
+//! is full, so a client that doesn't keep up with its responses is held back by TCP flow control.
 use chrono::{self, Utc};
 use futures::StreamExt;
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use tokio::{
     self,
-    io::AsyncWriteExt,
-    net::{
-        tcp::{OwnedReadHalf, OwnedWriteHalf},
-        TcpStream,
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        Notify,
     },
-    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     time::{interval_at, Duration, Instant},
 };
 
@@ -28,25 +38,32 @@ use tokio_util::codec::FramedRead;
 
 use crate::core::{
     comms::{
+        capture::{Direction, MessageCapture},
         message_writer::MessageWriter,
         secure_channel::SecureChannel,
         tcp_codec::{self, TcpCodec},
     },
     prelude::*,
+    runtime,
 };
-use crate::crypto::CertificateStore;
+use crate::crypto::{CertificateStore, SecurityPolicy};
 use crate::sync::*;
 use crate::types::status_code::StatusCode;
 
 use crate::server::{
     address_space::types::AddressSpace,
     comms::{secure_channel_service::SecureChannelService, transport::*},
-    services::message_handler::MessageHandler,
-    session::SessionManager,
+    services::{audit, message_handler::MessageHandler},
+    session::{IdleSessionState, Session, SessionManager},
     state::ServerState,
     subscriptions::subscription::TickReason,
 };
 
+/// The reading half of the stream a connection runs on, a TCP socket or a WebSocket
+pub type TransportReader = Box<dyn AsyncRead + Send + Unpin>;
+/// The writing half of the stream a connection runs on
+pub type TransportWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
 /// Messages that may be sent to the writer.
 #[derive(Debug)]
 enum Message {
@@ -56,9 +73,45 @@ enum Message {
     Message(u32, SupportedMessage),
 }
 
+/// Counts the messages that wait for the writer, which takes each one off once it is written.
+struct QueueLength {
+    len: AtomicUsize,
+    max_len: usize,
+    /// Notified when a message is written while the queue is full
+    room: Notify,
+}
+
+impl QueueLength {
+    fn new(max_len: usize) -> QueueLength {
+        QueueLength {
+            len: AtomicUsize::new(0),
+            max_len,
+            room: Notify::new(),
+        }
+    }
+
+    fn push(&self) {
+        self.len.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn pop(&self) {
+        if self.len.fetch_sub(1, Ordering::SeqCst) <= self.max_len {
+            self.room.notify_one();
+        }
+    }
+
+    /// Waits until the queue has room for another response
+    async fn wait_for_room(&self) {
+        while self.len.load(Ordering::SeqCst) >= self.max_len {
+            self.room.notified().await;
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct MessageSender {
     sender: UnboundedSender<Message>,
+    queue: Arc<QueueLength>,
 }
 
 impl MessageSender {
@@ -67,7 +120,19 @@ impl MessageSender {
     }
 
     pub fn send_message(&self, request_id: u32, message: SupportedMessage) {
-        let _ = self.sender.send(Message::Message(request_id, message));
+        let _ = self.queue_message(request_id, message);
+    }
+
+    /// Queues the message for the writer, failing if the writer has gone
+    fn queue_message(&self, request_id: u32, message: SupportedMessage) -> Result<(), StatusCode> {
+        // Counted before it is sent so the writer can't take it off first
+        self.queue.push();
+        self.sender
+            .send(Message::Message(request_id, message))
+            .map_err(|_| {
+                self.queue.pop();
+                StatusCode::BadConnectionClosed
+            })
     }
 }
 
@@ -75,11 +140,13 @@ struct ReadState {
     /// The associated connection
     pub transport: Arc<RwLock<TcpTransport>>,
     /// Sender of responses
-    pub sender: UnboundedSender<Message>,
+    pub sender: MessageSender,
+    /// Write buffer, whose limits are set by the HELLO of the client
+    pub send_buffer: Arc<Mutex<MessageWriter>>,
     /// Time to wait for a HELLO from the client
     pub hello_timeout: u32,
     /// Reader from which messages will be decoded
-    pub reader: OwnedReadHalf,
+    pub reader: TransportReader,
 }
 
 struct WriteState {
@@ -88,9 +155,41 @@ struct WriteState {
     /// Secure channel state
     pub secure_channel: Arc<RwLock<SecureChannel>>,
     /// Writing portion of socket
-    pub writer: OwnedWriteHalf,
+    pub writer: TransportWriter,
     /// Write buffer (protected since it might be accessed by publish response / event activity)
     pub send_buffer: Arc<Mutex<MessageWriter>>,
+    /// Captures the messages that are written, if the server captures its traffic
+    pub capture: Option<MessageCapture>,
+}
+
+/// A snapshot of the state of a secure channel open on the server, for diagnostics and
+/// troubleshooting.
+#[derive(Debug, Clone)]
+pub struct SecureChannelInfo {
+    /// Transport id of the connection the channel is open on
+    pub transport_id: NodeId,
+    /// Secure channel id, or 0 if the client has not opened a channel yet
+    pub secure_channel_id: u32,
+    /// Address of the client (peer)
+    pub client_address: Option<SocketAddr>,
+    /// Security policy of the channel
+    pub security_policy: SecurityPolicy,
+    /// Message security mode of the channel
+    pub security_mode: MessageSecurityMode,
+    /// Id of the current security token
+    pub token_id: u32,
+    /// Time the current security token was issued
+    pub token_created_at: DateTime,
+    /// Time the current security token expires unless it is renewed
+    pub token_expires_at: DateTime,
+    /// Time the connection was accepted
+    pub connected_at: DateTime,
+    /// Number of bytes sent to the client
+    pub bytes_sent: u64,
+    /// Number of bytes received from the client
+    pub bytes_received: u64,
+    /// Number of sessions on the connection
+    pub session_count: usize,
 }
 
 /// This is the thing that handles input and output for the open connection associated with the
@@ -118,8 +217,23 @@ pub struct TcpTransport {
     last_received_sequence_number: u32,
     /// A message may consist of one or more chunks which are stored here until complete.
     pending_chunks: Vec<MessageChunk>,
+    /// Size in bytes of the pending chunks
+    pending_chunks_size: usize,
+    /// Time the first of the pending chunks was received, which the timeout hint of the request
+    /// is measured from
+    message_received_at: DateTimeUtc,
     /// Sessions associated with this connection. Normally there would be one, but potentially there could be more
     session_manager: Arc<RwLock<SessionManager>>,
+    /// Time the connection was accepted
+    connected_at: DateTime,
+    /// Number of bytes written to the socket
+    bytes_sent: u64,
+    /// Number of bytes read from the socket
+    bytes_received: u64,
+    /// Signalled to forcibly close the connection
+    close_notify: Arc<Notify>,
+    /// Captures the traffic of the connection, if the server captures its traffic
+    capture: Option<MessageCapture>,
 }
 
 impl Transport for TcpTransport {
@@ -127,7 +241,8 @@ impl Transport for TcpTransport {
         self.transport_state
     }
 
-    // Terminates the connection and the session
+    // Terminates the connection. Its sessions are kept so the client can activate them on another
+    // connection, and are closed if it does not do so before they time out.
     fn finish(&mut self, status_code: StatusCode) {
         if !self.is_finished() {
             debug!(
@@ -135,9 +250,6 @@ impl Transport for TcpTransport {
                 status_code
             );
             self.transport_state = TransportState::Finished(status_code);
-            // Clear sessions
-            let mut session_manager = trace_write_lock!(self.session_manager);
-            session_manager.clear(self.address_space.clone());
         } else {
             trace!("Transport is being placed in finished state when it is already finished, ignoring code {}", status_code);
         }
@@ -159,10 +271,15 @@ impl TcpTransport {
         address_space: Arc<RwLock<AddressSpace>>,
         session_manager: Arc<RwLock<SessionManager>>,
     ) -> TcpTransport {
-        let decoding_options = {
+        let transport_id = NodeId::next_numeric(0);
+        let (decoding_options, capture) = {
             let server_state = trace_read_lock!(server_state);
             let config = trace_read_lock!(server_state.config);
-            config.decoding_options()
+            let capture = server_state
+                .message_capture
+                .as_ref()
+                .map(|capture| capture.for_connection(transport_id.to_string()));
+            (config.decoding_options(), capture)
         };
         let secure_channel = Arc::new(RwLock::new(SecureChannel::new(
             certificate_store.clone(),
@@ -179,7 +296,6 @@ impl TcpTransport {
         );
 
         let secure_channel_service = SecureChannelService::new();
-        let transport_id = NodeId::next_numeric(0);
 
         TcpTransport {
             server_state,
@@ -193,10 +309,49 @@ impl TcpTransport {
             client_protocol_version: 0,
             last_received_sequence_number: 0,
             pending_chunks: Vec::with_capacity(2),
+            pending_chunks_size: 0,
+            message_received_at: Utc::now(),
             session_manager,
+            connected_at: DateTime::now(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            close_notify: Arc::new(Notify::new()),
+            capture,
         }
     }
 
+    /// Returns a snapshot of the secure channel open on this connection.
+    pub fn secure_channel_info(&self) -> SecureChannelInfo {
+        let secure_channel = trace_read_lock!(self.secure_channel);
+        let token_created_at = secure_channel.token_created_at();
+        let token_expires_at = DateTime::from(
+            token_created_at.as_chrono()
+                + chrono::Duration::milliseconds(secure_channel.token_lifetime() as i64),
+        );
+        let session_count = trace_read_lock!(self.session_manager).sessions.len();
+        SecureChannelInfo {
+            transport_id: self.transport_id.clone(),
+            secure_channel_id: secure_channel.secure_channel_id(),
+            client_address: self.client_address,
+            security_policy: secure_channel.security_policy(),
+            security_mode: secure_channel.security_mode(),
+            token_id: secure_channel.token_id(),
+            token_created_at,
+            token_expires_at,
+            connected_at: self.connected_at,
+            bytes_sent: self.bytes_sent,
+            bytes_received: self.bytes_received,
+            session_count,
+        }
+    }
+
+    /// Forcibly closes the connection. The connection is dropped without a response to any
+    /// outstanding request and the transport finishes with `BadSecureChannelClosed`.
+    pub fn close(&self) {
+        info!("Transport {} is being forcibly closed", self.transport_id);
+        self.close_notify.notify_one();
+    }
+
     /// This is the entry point for the session. This function is asynchronous - it spawns tokio
     /// tasks to handle the session execution loop so this function will returns immediately.
     pub fn run(connection: Arc<RwLock<TcpTransport>>, socket: TcpStream, looping_interval_ms: f64) {
@@ -217,25 +372,51 @@ impl TcpTransport {
             }
         );
 
+        let client_address = socket.peer_addr().ok();
+        let (reader, writer) = socket.into_split();
+        Self::run_stream(
+            connection,
+            Box::new(reader),
+            Box::new(writer),
+            client_address,
+            looping_interval_ms,
+        );
+    }
+
+    /// Runs the session on a stream other than a TCP socket, such as a WebSocket. Like `run`,
+    /// this spawns the tasks of the session and returns immediately.
+    pub fn run_stream(
+        connection: Arc<RwLock<TcpTransport>>,
+        reader: TransportReader,
+        writer: TransportWriter,
+        client_address: Option<SocketAddr>,
+        looping_interval_ms: f64,
+    ) {
         // Store the address of the client
-        let (send_buffer_size, receive_buffer_size) = {
+        let (send_buffer_size, receive_buffer_size, max_queued_responses) = {
             let mut connection = trace_write_lock!(connection);
-            connection.client_address = Some(socket.peer_addr().unwrap());
+            connection.client_address = client_address;
             connection.transport_state = TransportState::WaitingHello;
             let server_state = trace_read_lock!(connection.server_state);
+            let max_queued_responses = trace_read_lock!(server_state.config)
+                .performance
+                .max_queued_responses;
             (
                 server_state.send_buffer_size,
                 server_state.receive_buffer_size,
+                max_queued_responses,
             )
         };
 
         // Spawn the tasks we need to run
         tokio::spawn(Self::spawn_session_handler_task(
             connection,
-            socket,
+            reader,
+            writer,
             looping_interval_ms,
             send_buffer_size,
             receive_buffer_size,
+            max_queued_responses,
         ));
     }
 
@@ -245,27 +426,43 @@ impl TcpTransport {
             send_buffer.bytes_to_write()
         };
         let result = write_state.writer.write_all(&bytes_to_write).await;
+        if let Some(ref capture) = write_state.capture {
+            capture.chunks(Direction::Sent, &bytes_to_write);
+        }
+        let mut transport = trace_write_lock!(write_state.transport);
         if let Err(err) = result {
             error!("Write IO error {:?}", err);
-            let mut transport = trace_write_lock!(write_state.transport);
             transport.finish(StatusCode::BadCommunicationError);
+        } else {
+            transport.bytes_sent += bytes_to_write.len() as u64;
+        }
+        drop(transport);
+        // Hand the bytes back so the next write reuses their allocation
+        {
+            let mut send_buffer = trace_lock!(write_state.send_buffer);
+            send_buffer.recycle(bytes_to_write);
         }
         write_state
     }
 
     async fn spawn_session_handler_task(
         transport: Arc<RwLock<TcpTransport>>,
-        socket: TcpStream,
+        reader: TransportReader,
+        writer: TransportWriter,
         looping_interval_ms: f64,
         send_buffer_size: usize,
         receive_buffer_size: usize,
+        max_queued_responses: usize,
     ) {
         // The reader task will send responses, the writer task will receive responses
         let (tx, rx) = unbounded_channel();
+        let sender = MessageSender {
+            sender: tx,
+            queue: Arc::new(QueueLength::new(max_queued_responses)),
+        };
         let send_buffer = Arc::new(Mutex::new(MessageWriter::new(send_buffer_size, 0, 0)));
 
-        let (reader, writer) = socket.into_split();
-        let (hello_timeout, secure_channel) = {
+        let (hello_timeout, secure_channel, close_notify) = {
             let transport = trace_read_lock!(transport);
             let server_state = trace_read_lock!(transport.server_state);
             let server_config = trace_read_lock!(server_state.config);
@@ -277,6 +474,7 @@ impl TcpTransport {
             (
                 server_config.tcp_config.hello_timeout,
                 transport.secure_channel.clone(),
+                transport.close_notify.clone(),
             )
         };
 
@@ -284,25 +482,35 @@ impl TcpTransport {
             reader,
             hello_timeout,
             transport: transport.clone(),
-            sender: tx.clone(),
+            sender: sender.clone(),
+            send_buffer: send_buffer.clone(),
         };
 
         // Spawn all the tasks that monitor the session - the subscriptions, finished state,
-        // reading and writing.
-        let final_status = tokio::select! {
-            _ = Self::spawn_subscriptions_task(transport.clone(), tx.clone(), looping_interval_ms) => {
-                log::trace!("Closing connection because the subscription task failed");
-                Ok(())
-            }
-            status = Self::spawn_writing_loop_task(writer, rx, secure_channel, transport.clone(), send_buffer) => {
-                log::trace!("Closing connection after the write task ended");
-                status
-            }
-            status = Self::spawn_reading_loop_task(read_state, send_buffer_size, receive_buffer_size) => {
-                log::trace!("Closing connection after the read task ended");
-                status
-            }
-        }.err().unwrap_or(StatusCode::Good);
+        // reading and writing. A panic in any of them closes the connection with an error rather
+        // than leaving it in limbo.
+        let final_status = runtime::catch_panic("session handler", async {
+            tokio::select! {
+                status = Self::spawn_subscriptions_task(transport.clone(), sender.clone(), looping_interval_ms) => {
+                    log::trace!("Closing connection because the subscription task ended");
+                    status
+                }
+                status = Self::spawn_writing_loop_task(writer, rx, sender.queue.clone(), secure_channel, transport.clone(), send_buffer) => {
+                    log::trace!("Closing connection after the write task ended");
+                    status
+                }
+                status = Self::spawn_reading_loop_task(read_state, send_buffer_size, receive_buffer_size) => {
+                    log::trace!("Closing connection after the read task ended");
+                    status
+                }
+                _ = close_notify.notified() => {
+                    log::trace!("Closing connection because it was forcibly closed");
+                    Err(StatusCode::BadSecureChannelClosed)
+                }
+            }.err().unwrap_or(StatusCode::Good)
+        })
+        .await
+        .unwrap_or_else(|status_code| status_code);
 
         log::info!("Closing connection with status {}", final_status);
         // Both the read and write halves of the tcp stream are dropped at this point,
@@ -314,17 +522,20 @@ impl TcpTransport {
     /// Spawns the writing loop task. The writing loop takes messages to send off of a queue
     /// and sends them to the stream.
     async fn spawn_writing_loop_task(
-        writer: OwnedWriteHalf,
+        writer: TransportWriter,
         mut receiver: UnboundedReceiver<Message>,
+        queue: Arc<QueueLength>,
         secure_channel: Arc<RwLock<SecureChannel>>,
         transport: Arc<RwLock<TcpTransport>>,
         send_buffer: Arc<Mutex<MessageWriter>>,
     ) -> Result<(), StatusCode> {
+        let capture = trace_read_lock!(transport).capture.clone();
         let mut write_state = WriteState {
             transport: transport.clone(),
             writer,
             send_buffer,
             secure_channel,
+            capture,
         };
 
         // The writing task waits for messages that are to be sent
@@ -352,17 +563,47 @@ impl TcpTransport {
                         send_buffer.write_ack(&ack)?;
                     }
                     msg => {
-                        send_buffer.write(request_id, msg, &secure_channel)?;
+                        if let Some(ref capture) = write_state.capture {
+                            capture.message(Direction::Sent, request_id, &msg);
+                        }
+                        let request_handle = msg
+                            .is_response()
+                            .then(|| msg.response_header().request_handle);
+                        if let Err(status) = send_buffer.write(request_id, msg, &secure_channel) {
+                            // A response too large for the client is replaced by a fault, rather
+                            // than closing the connection
+                            let Some(request_handle) = request_handle
+                                .filter(|_| status == StatusCode::BadResponseTooLarge)
+                            else {
+                                return Err(status);
+                            };
+                            warn!(
+                                "Response to request {} exceeds the limits of the client",
+                                request_handle
+                            );
+                            let fault = ServiceFault {
+                                response_header: ResponseHeader {
+                                    timestamp: DateTime::now(),
+                                    request_handle,
+                                    service_result: status,
+                                    service_diagnostics: DiagnosticInfo::default(),
+                                    string_table: None,
+                                    additional_header: ExtensionObject::null(),
+                                },
+                            };
+                            send_buffer.write(request_id, fault.into(), &secure_channel)?;
+                        }
                     }
                 }
             }
             write_state = Self::write_bytes_task(write_state).await;
+            queue.pop();
         }
         Ok(())
     }
 
     async fn wait_for_hello(
-        reader: &mut FramedRead<OwnedReadHalf, TcpCodec>,
+        reader: &mut FramedRead<TransportReader, TcpCodec>,
         hello_timeout: u32,
     ) -> Result<HelloMessage, StatusCode> {
         let duration = Duration::from_secs(u64::from(hello_timeout));
@@ -395,7 +636,7 @@ impl TcpTransport {
         send_buffer_size: usize,
         receive_buffer_size: usize,
     ) -> Result<(), StatusCode> {
-        let (transport, mut sender) = { (read_state.transport.clone(), read_state.sender.clone()) };
+        let (transport, sender) = { (read_state.transport.clone(), read_state.sender.clone()) };
 
         let decoding_options = {
             let transport = trace_read_lock!(transport);
@@ -408,20 +649,31 @@ impl TcpTransport {
             FramedRead::new(read_state.reader, TcpCodec::new(decoding_options.clone()));
 
         let hello = Self::wait_for_hello(&mut framed_read, read_state.hello_timeout).await?;
-        trace_write_lock!(transport).process_hello(
-            hello,
-            &mut sender,
-            &decoding_options,
-            send_buffer_size,
-            receive_buffer_size,
-        )?;
+        {
+            let mut transport = trace_write_lock!(transport);
+            transport.bytes_received += hello.message_header.message_size as u64;
+            transport.process_hello(
+                hello,
+                &sender,
+                &read_state.send_buffer,
+                &decoding_options,
+                send_buffer_size,
+                receive_buffer_size,
+            )?;
+        }
 
-        while let Some(next_msg) = framed_read.next().await {
+        loop {
+            // Nothing more is read from the client until its responses have been written
+            sender.queue.wait_for_room().await;
+            let Some(next_msg) = framed_read.next().await else {
+                break;
+            };
             match next_msg {
                 Ok(tcp_codec::Message::Chunk(chunk)) => {
                     log::trace!("Received message chunk: {:?}", chunk);
                     let mut transport = trace_write_lock!(transport);
-                    transport.process_chunk(chunk, &mut sender)?
+                    transport.bytes_received += chunk.data.len() as u64;
+                    transport.process_chunk(chunk, &sender)?
                 }
                 Ok(unexpected) => {
                     log::error!("Received unexpected message: {:?}", unexpected);
@@ -439,7 +691,7 @@ impl TcpTransport {
     /// Start the subscription timer to service subscriptions
     async fn spawn_subscriptions_task(
         transport: Arc<RwLock<TcpTransport>>,
-        sender: UnboundedSender<Message>,
+        sender: MessageSender,
         looping_interval_ms: f64,
     ) -> Result<(), StatusCode> {
         // Subscription events are passed sent from the monitor task to the receiver
@@ -455,13 +707,38 @@ impl TcpTransport {
             timer.tick().await;
 
             let transport = trace_read_lock!(transport);
+            let secure_channel_id = {
+                let secure_channel = trace_read_lock!(transport.secure_channel);
+                // A channel whose token expired without being renewed is closed with its
+                // connection, e.g. because the client went away without closing it
+                if secure_channel.token_id() != 0 && secure_channel.token_has_expired() {
+                    warn!(
+                        "Secure channel {} is closed because its security token has expired",
+                        secure_channel.secure_channel_id()
+                    );
+                    return Err(StatusCode::BadSecureChannelClosed);
+                }
+                secure_channel.secure_channel_id()
+            };
             let session_manager = trace_read_lock!(transport.session_manager);
+            let mut idle_sessions = Vec::new();
 
-            for (_node_id, session) in session_manager.sessions.iter() {
-                let mut session = trace_write_lock!(session);
-                let address_space = trace_read_lock!(transport.address_space);
+            for (_node_id, session_lock) in session_manager.sessions.iter() {
+                let mut session = trace_write_lock!(session_lock);
+                // Publish responses must go out on the channel the session is bound to
+                if session.secure_channel_id() != secure_channel_id {
+                    continue;
+                }
                 let now = Utc::now();
 
+                // Sessions that have been idle for too long are warned and then closed
+                let idle_state = session.idle_state(&now);
+                if idle_state != IdleSessionState::Active {
+                    idle_sessions.push((session_lock.clone(), idle_state));
+                }
+
+                let address_space = trace_read_lock!(transport.address_space);
+
                 // Request queue might contain stale publish requests
                 session.expire_stale_publish_requests(&now);
 
@@ -480,10 +757,7 @@ impl TcpTransport {
                         );
                         // Messages will be sent by the writing task
                         sender
-                            .send(Message::Message(
-                                publish_response.request_id,
-                                publish_response.response,
-                            ))
+                            .queue_message(publish_response.request_id, publish_response.response)
                             .map_err(|e| {
                                 error!("Unable to send publish response to writer task: {}", e);
                                 StatusCode::BadUnexpectedError
@@ -491,6 +765,51 @@ impl TcpTransport {
                     }
                 }
             }
+            drop(session_manager);
+
+            for (session, idle_state) in idle_sessions {
+                Self::process_idle_session(&transport, session, idle_state);
+            }
+        }
+    }
+
+    /// Raises an event for a session that is idle, and closes it if it has expired
+    fn process_idle_session(
+        transport: &TcpTransport,
+        session: Arc<RwLock<Session>>,
+        idle_state: IdleSessionState,
+    ) {
+        let server_state = trace_read_lock!(transport.server_state);
+        let session_id = {
+            let session = trace_read_lock!(session);
+            if idle_state == IdleSessionState::Expired {
+                warn!(
+                    "Session {} is closed because it has been idle since {}",
+                    session.session_id(),
+                    session.last_activity_timestamp()
+                );
+                audit::log_idle_session_timeout(
+                    &server_state,
+                    &session,
+                    transport.address_space.clone(),
+                );
+            } else {
+                info!(
+                    "Session {} will be closed soon because it has been idle since {}",
+                    session.session_id(),
+                    session.last_activity_timestamp()
+                );
+                audit::log_idle_session_warning(
+                    &server_state,
+                    &session,
+                    transport.address_space.clone(),
+                );
+            }
+            session.session_id().clone()
+        };
+        if idle_state == IdleSessionState::Expired {
+            let mut session_manager = trace_write_lock!(transport.session_manager);
+            session_manager.terminate_session(&session_id, &transport.address_space);
         }
     }
 
@@ -503,7 +822,8 @@ impl TcpTransport {
     fn process_hello(
         &mut self,
         hello: HelloMessage,
-        sender: &mut UnboundedSender<Message>,
+        sender: &MessageSender,
+        send_buffer: &Mutex<MessageWriter>,
         decoding_options: &DecodingOptions,
         send_buffer_size: usize,
         receive_buffer_size: usize,
@@ -511,7 +831,7 @@ impl TcpTransport {
         let server_protocol_version = 0;
         let endpoints = {
             let server_state = trace_read_lock!(self.server_state);
-            server_state.endpoints(&hello.endpoint_url, &None)
+            server_state.endpoints(&hello.endpoint_url, &None, &None)
         }
         .unwrap();
 
@@ -532,12 +852,23 @@ impl TcpTransport {
 
         let client_protocol_version = hello.protocol_version;
 
+        // Chunks are no larger than what the other end can receive
+        let receive_buffer_size = (receive_buffer_size as u32).min(hello.send_buffer_size);
+        let send_buffer_size = (send_buffer_size as u32).min(hello.receive_buffer_size);
+
+        // Responses are chunked and limited according to what the client accepts
+        trace_lock!(send_buffer).set_limits(
+            send_buffer_size as usize,
+            hello.max_message_size as usize,
+            hello.max_chunk_count as usize,
+        );
+
         // Send acknowledge
         let mut acknowledge = AcknowledgeMessage {
             message_header: MessageHeader::new(MessageType::Acknowledge),
             protocol_version: server_protocol_version,
-            receive_buffer_size: receive_buffer_size as u32,
-            send_buffer_size: send_buffer_size as u32,
+            receive_buffer_size,
+            send_buffer_size,
             max_message_size: decoding_options.max_message_size as u32,
             max_chunk_count: decoding_options.max_chunk_count as u32,
         };
@@ -549,7 +880,7 @@ impl TcpTransport {
         self.client_protocol_version = client_protocol_version;
 
         debug!("Sending ACK");
-        let _ = sender.send(Message::Message(0, acknowledge));
+        sender.send_message(0, acknowledge);
         Ok(())
     }
 
@@ -571,8 +902,11 @@ impl TcpTransport {
     fn process_chunk(
         &mut self,
         chunk: MessageChunk,
-        sender: &mut UnboundedSender<Message>,
+        sender: &MessageSender,
     ) -> std::result::Result<(), StatusCode> {
+        if let Some(ref capture) = self.capture {
+            capture.chunks(Direction::Received, &chunk.data);
+        }
         let decoding_options = {
             let secure_channel = trace_read_lock!(self.secure_channel);
             secure_channel.decoding_options()
@@ -583,18 +917,41 @@ impl TcpTransport {
         if message_header.is_final == MessageIsFinalType::FinalError {
             info!("Discarding chunks as after receiving one marked as final error");
             self.pending_chunks.clear();
+            self.pending_chunks_size = 0;
             Ok(())
         } else {
+            // The client was told the limits in the acknowledge, so a message that goes over them
+            // drops the connection before its chunks pile up
+            if decoding_options.max_chunk_count > 0
+                && self.pending_chunks.len() >= decoding_options.max_chunk_count
+            {
+                error!(
+                    "Message has more than the maximum of {} chunks",
+                    decoding_options.max_chunk_count
+                );
+                return Err(StatusCode::BadTcpMessageTooLarge);
+            }
+            self.pending_chunks_size += chunk.data.len();
+            if decoding_options.max_message_size > 0
+                && self.pending_chunks_size > decoding_options.max_message_size
+            {
+                error!(
+                    "Message is larger than the maximum size of {} bytes",
+                    decoding_options.max_message_size
+                );
+                return Err(StatusCode::BadTcpMessageTooLarge);
+            }
+
             // Decrypt / verify chunk if necessary
             let chunk = {
                 let mut secure_channel = trace_write_lock!(self.secure_channel);
-                secure_channel.verify_and_remove_security(&chunk.data)?
+                secure_channel.remove_security(chunk)?
             };
 
-            // TODO check how many chunks are pending, produce error and drop connection if it exceeds
-            //  supported chunk limit
-
             // Put the chunk on the list
+            if self.pending_chunks.is_empty() {
+                self.message_received_at = Utc::now();
+            }
             self.pending_chunks.push(chunk);
 
             // The final chunk will trigger turning all pending chunks into a request
@@ -609,10 +966,11 @@ impl TcpTransport {
     fn process_final_chunk(
         &mut self,
         message_header: &MessageChunkHeader,
-        sender: &mut UnboundedSender<Message>,
+        sender: &MessageSender,
     ) -> Result<(), StatusCode> {
         // Drain pending chunks and turn them into a message
         let chunks: Vec<MessageChunk> = self.pending_chunks.drain(..).collect();
+        self.pending_chunks_size = 0;
         let chunk_info = {
             let secure_channel = trace_read_lock!(self.secure_channel);
             chunks[0].chunk_info(&secure_channel)?
@@ -621,22 +979,24 @@ impl TcpTransport {
         // Handle the request, and then send the response back to the caller
         let request = self.turn_received_chunks_into_message(&chunks)?;
         let request_id = chunk_info.sequence_header.request_id;
-
-        let sender = MessageSender {
-            sender: sender.clone(),
-        };
+        if let Some(ref capture) = self.capture {
+            capture.message(Direction::Received, request_id, &request);
+        }
 
         match message_header.message_type {
             MessageChunkType::OpenSecureChannel => self.process_open_secure_channel(
                 request_id,
                 &request,
                 &chunk_info.security_header,
-                &sender,
+                sender,
             ),
             MessageChunkType::CloseSecureChannel => {
-                self.process_close_secure_channel(request_id, &request, &sender)
+                self.process_close_secure_channel(request_id, &request, sender)
+            }
+            MessageChunkType::Message => {
+                let received_at = self.message_received_at;
+                self.process_message(request_id, &request, received_at, sender)
             }
-            MessageChunkType::Message => self.process_message(request_id, &request, &sender),
         }
     }
 
@@ -647,6 +1007,18 @@ impl TcpTransport {
         security_header: &SecurityHeader,
         sender: &MessageSender,
     ) -> Result<(), StatusCode> {
+        let request_type = match request {
+            SupportedMessage::OpenSecureChannelRequest(request) => Some(request.request_type),
+            _ => None,
+        };
+        let span = tracing::info_span!(
+            "open_secure_channel",
+            ?request_type,
+            request_id,
+            secure_channel_id = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+
         let mut secure_channel = trace_write_lock!(self.secure_channel);
         let response = self.secure_channel_service.open_secure_channel(
             &mut secure_channel,
@@ -654,6 +1026,16 @@ impl TcpTransport {
             self.client_protocol_version,
             request,
         )?;
+        if let SupportedMessage::OpenSecureChannelResponse(ref response) = response {
+            span.record("secure_channel_id", response.security_token.channel_id);
+            tracing::info!(
+                security_policy = %secure_channel.security_policy(),
+                security_mode = ?secure_channel.security_mode(),
+                token_id = response.security_token.token_id,
+                revised_lifetime = response.security_token.revised_lifetime,
+                "Secure channel opened"
+            );
+        }
         let _ = sender.send_message(request_id, response);
         Ok(())
     }
@@ -664,6 +1046,14 @@ impl TcpTransport {
         request: &SupportedMessage,
         sender: &MessageSender,
     ) -> Result<(), StatusCode> {
+        let secure_channel_id = {
+            let secure_channel = trace_read_lock!(self.secure_channel);
+            secure_channel.secure_channel_id()
+        };
+        let span = tracing::info_span!("close_secure_channel", secure_channel_id, request_id);
+        let _entered = span.enter();
+        tracing::info!("Secure channel closed by the client");
+
         let response = self.secure_channel_service.close_secure_channel(request)?;
         let _ = sender.send_message(request_id, response);
         Ok(())
@@ -673,11 +1063,66 @@ impl TcpTransport {
         &mut self,
         request_id: u32,
         request: &SupportedMessage,
+        received_at: DateTimeUtc,
         sender: &MessageSender,
     ) -> Result<(), StatusCode> {
         let _ = self
             .message_handler
-            .handle_message(request_id, request, sender)?;
+            .handle_message(request_id, request, received_at, sender)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::FutureExt;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::{Message, MessageSender, QueueLength};
+    use crate::core::supported_message::SupportedMessage;
+    use crate::types::{ResponseHeader, ServiceFault};
+
+    fn response() -> SupportedMessage {
+        ServiceFault {
+            response_header: ResponseHeader::null(),
+        }
+        .into()
+    }
+
+    /// The reader waits for room before it reads the next chunk, so a full queue stops it reading
+    /// until the writer takes a response off
+    #[tokio::test]
+    async fn full_queue_stops_reading() {
+        let (sender, mut receiver) = unbounded_channel();
+        let sender = MessageSender {
+            sender,
+            queue: Arc::new(QueueLength::new(2)),
+        };
+        sender.send_message(1, response());
+        assert!(sender.queue.wait_for_room().now_or_never().is_some());
+        sender.send_message(2, response());
+
+        let mut room = Box::pin(sender.queue.wait_for_room());
+        assert!((&mut room).now_or_never().is_none());
+
+        // The writer takes a response off
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Message::Message(1, _))
+        ));
+        sender.queue.pop();
+        tokio::time::timeout(std::time::Duration::from_secs(1), room)
+            .await
+            .unwrap();
+
+        // A message that cannot be queued because the writer has gone is not counted
+        drop(receiver);
+        assert!(sender.queue_message(3, response()).is_err());
+        assert_eq!(
+            sender.queue.len.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+}
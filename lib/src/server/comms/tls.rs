@@ -0,0 +1,44 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Accepts the TLS sessions of the opc.wss and https transports, using the application instance
+//! certificate of the server.
+
+use std::sync::Arc;
+
+use tokio_rustls::{rustls, TlsAcceptor};
+
+use crate::crypto::{PrivateKey, X509};
+use crate::types::status_code::StatusCode;
+
+/// Creates the TLS acceptor for opc.wss and https connections from the certificate and private
+/// key of the server.
+pub(crate) fn tls_acceptor(
+    certificate: Option<&X509>,
+    private_key: Option<&PrivateKey>,
+) -> Result<TlsAcceptor, StatusCode> {
+    let (Some(certificate), Some(private_key)) = (certificate, private_key) else {
+        error!("Server cannot accept TLS connections without a certificate and private key");
+        return Err(StatusCode::BadConfigurationError);
+    };
+    let certificate = certificate.to_der().map_err(|_| {
+        error!("Cannot turn the server certificate into DER");
+        StatusCode::BadCertificateInvalid
+    })?;
+    let private_key = private_key
+        .private_key_to_der()
+        .map_err(|_| StatusCode::BadConfigurationError)?;
+    let config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![rustls::Certificate(certificate)],
+            rustls::PrivateKey(private_key),
+        )
+        .map_err(|err| {
+            error!("Cannot make the TLS config of the server, {}", err);
+            StatusCode::BadConfigurationError
+        })?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
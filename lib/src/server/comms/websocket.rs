@@ -0,0 +1,28 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Accepts opc.wss connections. A connection is a TLS session, using the application instance
+//! certificate of the server, that is upgraded to a WebSocket. The WebSocket is then turned into a
+//! stream that a transport runs its session on.
+
+use tokio::{io::DuplexStream, net::TcpStream};
+use tokio_rustls::TlsAcceptor;
+
+use crate::core::comms::{secure_channel::Role, websocket::*};
+use crate::types::status_code::StatusCode;
+
+/// Accepts the TLS session and WebSocket handshake of a new connection, returning the stream that
+/// carries the chunks of the connection.
+pub(crate) async fn accept(
+    acceptor: &TlsAcceptor,
+    socket: TcpStream,
+    max_chunk_size: usize,
+) -> Result<DuplexStream, StatusCode> {
+    let mut stream = acceptor.accept(socket).await.map_err(|err| {
+        error!("TLS handshake of opc.wss connection failed, {}", err);
+        StatusCode::BadSecureChannelClosed
+    })?;
+    websocket_server_handshake(&mut stream).await?;
+    Ok(websocket_bridge(stream, Role::Server, max_chunk_size))
+}
@@ -5,14 +5,22 @@
 //! Provides configuration settings for the server including serialization and deserialization from file.
 use std::{
     collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt,
     path::{Path, PathBuf},
     str::FromStr,
 };
 
 use crate::{
-    core::{comms::url::url_matches_except_host, config::Config},
+    core::{
+        comms::url::url_matches_except_host,
+        config::{Config, ConfigOverrideError},
+    },
     crypto::{CertificateStore, SecurityPolicy, Thumbprint},
-    types::{service_types::ApplicationType, DecodingOptions, MessageSecurityMode, UAString},
+    types::{
+        node_ids::ObjectId, profiles, service_types::ApplicationType, DecodingOptions,
+        LocalizedText, MessageSecurityMode, NodeId, UAString,
+    },
 };
 
 use super::constants;
@@ -32,6 +40,20 @@ pub struct TcpConfig {
     pub port: u16,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct WebSocketConfig {
+    /// The port number the server listens on for opc.wss connections. The host is the one of the
+    /// tcp config.
+    pub port: u16,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct HttpsConfig {
+    /// The port number the server listens on for https connections. The host is the one of the
+    /// tcp config.
+    pub port: u16,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ServerUserToken {
     /// User name
@@ -44,6 +66,10 @@ pub struct ServerUserToken {
     pub x509: Option<String>,
     #[serde(skip)]
     pub thumbprint: Option<Thumbprint>,
+    /// Roles granted to the user in addition to `AuthenticatedUser`. A role is the name of a
+    /// well known role, e.g. "Operator", or the node id of a role, e.g. "ns=2;s=Maintenance".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
 }
 
 impl ServerUserToken {
@@ -57,6 +83,7 @@ impl ServerUserToken {
             pass: Some(pass.into()),
             x509: None,
             thumbprint: None,
+            roles: Vec::new(),
         }
     }
 
@@ -70,9 +97,41 @@ impl ServerUserToken {
             pass: None,
             x509: Some(cert_path.to_string_lossy().to_string()),
             thumbprint: None,
+            roles: Vec::new(),
         }
     }
 
+    /// Grants roles to the user, see `roles`.
+    pub fn roles<T>(mut self, roles: &[T]) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.roles = roles.iter().map(|r| r.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Returns the node ids of the roles granted to the user, or the first role that is neither
+    /// a well known role nor a node id.
+    pub fn role_ids(&self) -> Result<Vec<NodeId>, String> {
+        self.roles
+            .iter()
+            .map(|role| {
+                let role_id = match role.as_str() {
+                    "Anonymous" => ObjectId::WellKnownRole_Anonymous,
+                    "AuthenticatedUser" => ObjectId::WellKnownRole_AuthenticatedUser,
+                    "Observer" => ObjectId::WellKnownRole_Observer,
+                    "Operator" => ObjectId::WellKnownRole_Operator,
+                    "Engineer" => ObjectId::WellKnownRole_Engineer,
+                    "Supervisor" => ObjectId::WellKnownRole_Supervisor,
+                    "ConfigureAdmin" => ObjectId::WellKnownRole_ConfigureAdmin,
+                    "SecurityAdmin" => ObjectId::WellKnownRole_SecurityAdmin,
+                    _ => return NodeId::from_str(role).map_err(|_| role.clone()),
+                };
+                Ok(role_id.into())
+            })
+            .collect()
+    }
+
     /// Read an X509 user token's certificate from disk and then hold onto the thumbprint for it.
     pub fn read_thumbprint(&mut self) {
         if self.is_x509() && self.thumbprint.is_none() {
@@ -115,6 +174,13 @@ impl ServerUserToken {
             );
             valid = false;
         }
+        if let Err(role) = self.role_ids() {
+            error!(
+                "User token {} has a role {} that is not recognized.",
+                id, role
+            );
+            valid = false;
+        }
         valid
     }
 
@@ -127,14 +193,77 @@ impl ServerUserToken {
     }
 }
 
+/// Most operations that a client may ask for in one call of a service, advertised in
+/// `Server/ServerCapabilities/OperationLimits`. A call that exceeds a limit fails with
+/// `BadTooManyOperations`. 0 means there is no limit.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct OperationalLimits {
+    pub max_nodes_per_translate_browse_paths_to_node_ids: usize,
+    pub max_nodes_per_read: usize,
+    pub max_nodes_per_write: usize,
+    pub max_nodes_per_method_call: usize,
+    pub max_nodes_per_browse: usize,
+    pub max_nodes_per_register_nodes: usize,
+    pub max_nodes_per_node_management: usize,
+    pub max_monitored_items_per_call: usize,
+    pub max_nodes_per_history_read_data: usize,
+    pub max_nodes_per_history_read_events: usize,
+    pub max_nodes_per_history_update_data: usize,
+    pub max_nodes_per_history_update_events: usize,
+}
+
+impl Default for OperationalLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes_per_translate_browse_paths_to_node_ids:
+                constants::MAX_NODES_PER_TRANSLATE_BROWSE_PATHS_TO_NODE_IDS,
+            max_nodes_per_read: constants::MAX_NODES_PER_READ,
+            max_nodes_per_write: constants::MAX_NODES_PER_WRITE,
+            max_nodes_per_method_call: constants::MAX_NODES_PER_METHOD_CALL,
+            max_nodes_per_browse: constants::MAX_NODES_PER_BROWSE,
+            max_nodes_per_register_nodes: constants::MAX_NODES_PER_REGISTER_NODES,
+            max_nodes_per_node_management: constants::MAX_NODES_PER_NODE_MANAGEMENT,
+            max_monitored_items_per_call: constants::MAX_MONITORED_ITEMS_PER_CALL,
+            max_nodes_per_history_read_data: constants::MAX_NODES_PER_HISTORY_READ_DATA,
+            max_nodes_per_history_read_events: constants::MAX_NODES_PER_HISTORY_READ_EVENTS,
+            max_nodes_per_history_update_data: constants::MAX_NODES_PER_HISTORY_UPDATE_DATA,
+            max_nodes_per_history_update_events: constants::MAX_NODES_PER_HISTORY_UPDATE_EVENTS,
+        }
+    }
+}
+
+impl OperationalLimits {
+    /// Tests if a call with this many operations is within a limit
+    pub fn allows(limit: usize, operations: usize) -> bool {
+        limit == 0 || operations <= limit
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Limits {
     /// Indicates if clients are able to modify the address space through the node management service
     /// set. This is a very broad flag and is likely to require more fine grained per user control
     /// in a later revision. By default, this value is `false`
     pub clients_can_modify_address_space: bool,
+    /// Indicates if nodes that a client adds through the node management service only live as
+    /// long as the session that added them. When the session closes, the nodes and references
+    /// to them are deleted. By default, this value is `false` and added nodes persist.
+    #[serde(default)]
+    pub session_lifetime_nodes: bool,
+    /// Maximum number of sessions across all connections of the server, 0 for no limit
+    #[serde(default = "Limits::default_max_sessions")]
+    pub max_sessions: usize,
+    /// Maximum session timeout in milliseconds. A session that goes without a service call for
+    /// longer than its timeout is closed. A client that asks for a longer timeout, or for none,
+    /// has its timeout revised to this.
+    #[serde(default = "Limits::default_max_session_timeout")]
+    pub max_session_timeout: f64,
     /// Maximum number of subscriptions in a session, 0 for no limit
     pub max_subscriptions: usize,
+    /// Maximum number of subscriptions across all sessions of the server, 0 for no limit
+    #[serde(default)]
+    pub max_server_subscriptions: usize,
     /// Maximum number of monitored items per subscription, 0 for no limit
     pub max_monitored_items_per_sub: usize,
     /// Maximum number of values in a monitored item queue
@@ -157,6 +286,9 @@ pub struct Limits {
     pub send_buffer_size: usize,
     /// Receive buffer size in bytes
     pub receive_buffer_size: usize,
+    /// Most operations per call of each service
+    #[serde(default)]
+    pub operational: OperationalLimits,
 }
 
 impl Default for Limits {
@@ -166,20 +298,35 @@ impl Default for Limits {
             max_array_length: decoding_options.max_array_length,
             max_string_length: decoding_options.max_string_length,
             max_byte_string_length: decoding_options.max_byte_string_length,
+            max_sessions: Limits::default_max_sessions(),
+            max_session_timeout: Limits::default_max_session_timeout(),
             max_subscriptions: constants::DEFAULT_MAX_SUBSCRIPTIONS,
+            max_server_subscriptions: 0,
             max_monitored_items_per_sub: constants::DEFAULT_MAX_MONITORED_ITEMS_PER_SUB,
             max_monitored_item_queue_size: constants::MAX_DATA_CHANGE_QUEUE_SIZE,
             max_message_size: decoding_options.max_message_size,
             max_chunk_count: decoding_options.max_chunk_count,
             clients_can_modify_address_space: false,
+            session_lifetime_nodes: false,
             min_sampling_interval: constants::MIN_SAMPLING_INTERVAL,
             min_publishing_interval: constants::MIN_PUBLISHING_INTERVAL,
             send_buffer_size: SEND_BUFFER_SIZE,
             receive_buffer_size: RECEIVE_BUFFER_SIZE,
+            operational: OperationalLimits::default(),
         }
     }
 }
 
+impl Limits {
+    fn default_max_sessions() -> usize {
+        constants::DEFAULT_MAX_SESSIONS
+    }
+
+    fn default_max_session_timeout() -> f64 {
+        constants::MAX_SESSION_TIMEOUT
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct CertificateValidation {
     /// Auto trusts client certificates. For testing/samples only unless you're sure what you're
@@ -212,6 +359,14 @@ pub struct ServerEndpoint {
     pub password_security_policy: Option<String>,
     /// User tokens
     pub user_token_ids: BTreeSet<String>,
+    /// Time in seconds that a session on the endpoint may go without calling a service, other
+    /// than publishing, before the server closes it. Use 0 for no limit
+    #[serde(default)]
+    pub idle_session_timeout: u32,
+    /// Time in seconds before an idle session is closed that the server raises an event to warn
+    /// of it. Use 0 for no warning, leaving only the event that the session was closed
+    #[serde(default)]
+    pub idle_session_warning: u32,
 }
 
 /// Convenience method to make an endpoint from a tuple
@@ -224,6 +379,8 @@ impl<'a> From<(&'a str, SecurityPolicy, MessageSecurityMode, &'a [&'a str])> for
             security_level: Self::security_level(v.1, v.2),
             password_security_policy: None,
             user_token_ids: v.3.iter().map(|id| id.to_string()).collect(),
+            idle_session_timeout: 0,
+            idle_session_warning: 0,
         }
     }
 }
@@ -245,9 +402,37 @@ impl ServerEndpoint {
             security_level: Self::security_level(security_policy, security_mode),
             password_security_policy: None,
             user_token_ids: user_token_ids.iter().cloned().collect(),
+            idle_session_timeout: 0,
+            idle_session_warning: 0,
         }
     }
 
+    /// Adds the id of a user token that may be used to activate sessions on the endpoint
+    pub fn user_token_id<T>(mut self, user_token_id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.user_token_ids.insert(user_token_id.into());
+        self
+    }
+
+    /// Sets the security policy used to encrypt the password of a user name identity token
+    pub fn with_password_security_policy(
+        mut self,
+        password_security_policy: SecurityPolicy,
+    ) -> Self {
+        self.password_security_policy = Some(password_security_policy.to_string());
+        self
+    }
+
+    /// Sets the time in seconds that a session on the endpoint may be idle before it is closed,
+    /// and the time before that when a warning is raised. Use 0 for no limit or no warning.
+    pub fn idle_session(mut self, idle_session_timeout: u32, idle_session_warning: u32) -> Self {
+        self.idle_session_timeout = idle_session_timeout;
+        self.idle_session_warning = idle_session_warning;
+        self
+    }
+
     /// Recommends a security level for the supplied security policy
     fn security_level(security_policy: SecurityPolicy, security_mode: MessageSecurityMode) -> u8 {
         let security_level = match security_policy {
@@ -443,6 +628,14 @@ impl ServerEndpoint {
             error!("Endpoint {} is invalid. Security policy and security mode must both contain None or neither of them should (2).", id);
             valid = false;
         }
+
+        if self.idle_session_timeout > 0 && self.idle_session_warning >= self.idle_session_timeout {
+            error!(
+                "Endpoint {} is invalid. Idle session warning {} must be less than the idle session timeout {}",
+                id, self.idle_session_warning, self.idle_session_timeout
+            );
+            valid = false;
+        }
         valid
     }
 
@@ -523,16 +716,80 @@ pub struct Performance {
     /// Use a single-threaded executor. The default executor uses a thread pool with a worker
     /// thread for each CPU core available on the system.
     pub single_threaded_executor: bool,
+    /// The name given to the threads of the executor, e.g. to identify them in a debugger or in
+    /// thread dumps.
+    #[serde(default = "Performance::default_thread_name")]
+    pub thread_name: String,
+    /// The maximum number of namespaces that the nodes of a single Read request are read from
+    /// concurrently, each on its own thread. This cuts the latency of reads that span namespaces
+    /// whose values come from slow getters, e.g. a gateway to devices. The default of 1 reads every
+    /// node in turn.
+    #[serde(default = "Performance::default_max_concurrent_reads")]
+    pub max_concurrent_reads: usize,
+    /// The maximum number of responses of a connection that may wait to be written to the client.
+    /// While the queue is full the server stops reading requests from that connection, so a client
+    /// that sends faster than it reads is slowed down rather than growing the server's memory.
+    #[serde(default = "Performance::default_max_queued_responses")]
+    pub max_queued_responses: usize,
+}
+
+impl Performance {
+    fn default_thread_name() -> String {
+        "opcua-server".to_string()
+    }
+
+    fn default_max_concurrent_reads() -> usize {
+        1
+    }
+
+    fn default_max_queued_responses() -> usize {
+        64
+    }
+}
+
+/// The reason a config was not reloaded into a running server. Nothing of the config is applied
+/// when a reload fails.
+#[derive(Debug)]
+pub enum ReloadError {
+    /// The config file could not be read or parsed
+    Load(PathBuf),
+    /// The environment variables could not be applied to the config of the file
+    Override(ConfigOverrideError),
+    /// The config is invalid, e.g. an endpoint uses a user token that it does not define
+    Invalid,
 }
 
+impl fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Load(path) => write!(f, "Cannot load configuration {}", path.display()),
+            Self::Override(err) => write!(f, "Cannot apply configuration overrides, {}", err),
+            Self::Invalid => write!(f, "Configuration is invalid"),
+        }
+    }
+}
+
+impl Error for ReloadError {}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ServerConfig {
     /// An id for this server
     pub application_name: String,
+    /// The application name in other locales, keyed by locale id. Clients that ask for none of
+    /// these locales are given the `application_name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub localized_application_names: BTreeMap<String, String>,
     /// A description for this server
     pub application_uri: String,
     /// Product url
     pub product_uri: String,
+    /// Manufacturer name reported in the server's `BuildInfo`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manufacturer_name: Option<String>,
+    /// Build number reported in the server's `BuildInfo`. The crate version is reported when
+    /// this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_number: Option<String>,
     /// Autocreates public / private keypair if they don't exist. For testing/samples only
     /// since you do not have control of the values
     pub create_sample_keypair: bool,
@@ -549,6 +806,12 @@ pub struct ServerConfig {
     pub discovery_server_url: Option<String>,
     /// tcp configuration information
     pub tcp_config: TcpConfig,
+    /// WebSocket config, when the server also listens for opc.wss connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub websocket_config: Option<WebSocketConfig>,
+    /// HTTPS config, when the server also listens for https connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_config: Option<HttpsConfig>,
     /// Server OPA UA limits
     pub limits: Limits,
     /// Server Performance
@@ -563,9 +826,33 @@ pub struct ServerConfig {
     pub default_endpoint: Option<String>,
     /// Endpoints supported by the server
     pub endpoints: BTreeMap<String, ServerEndpoint>,
+    /// Node set files in the UANodeSet XML format, e.g. companion specifications, that are
+    /// imported into the address space when the server is created
+    #[serde(default)]
+    pub nodesets: Vec<PathBuf>,
+    /// Raise audit events for security related operations, e.g. creating and activating
+    /// sessions, rejecting certificates and writing values. The `Server.Auditing` variable
+    /// reports whether this is on.
+    #[serde(default = "ServerConfig::default_auditing")]
+    pub auditing: bool,
+    /// File that the chunks of every connection are captured to, for debugging problems with the
+    /// stacks of clients. The capture can be replayed with
+    /// `opcua::core::comms::capture::replay_capture()`. A capture is sensitive, the chunks of a
+    /// channel without security hold user names and passwords in the clear.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_file: Option<PathBuf>,
+    /// Also capture the decoded messages of every connection to the capture file, which shows
+    /// the messages of secured channels too. Identity tokens, user token signatures and nonces
+    /// are blanked out.
+    #[serde(default)]
+    pub capture_messages: bool,
 }
 
 impl Config for ServerConfig {
+    const ENV_PREFIX: &'static str = "OPCUA_SERVER_";
+    const ENV_ALIASES: &'static [(&'static str, &'static str)] =
+        &[("HOST", "TCP_CONFIG__HOST"), ("PORT", "TCP_CONFIG__PORT")];
+
     fn is_valid(&self) -> bool {
         let mut valid = true;
         if self.application_name.is_empty() {
@@ -585,6 +872,20 @@ impl Config for ServerConfig {
             if !endpoint.is_valid(id, &self.user_tokens) {
                 valid = false;
             }
+            // Only the first endpoint with the same path and security could ever be used
+            if let Some((other_id, _)) = self.endpoints.iter().find(|(other_id, other)| {
+                other_id.as_str() < id.as_str()
+                    && other.path == endpoint.path
+                    && other.security_policy() == endpoint.security_policy()
+                    && MessageSecurityMode::from(other.security_mode.as_ref())
+                        == MessageSecurityMode::from(endpoint.security_mode.as_ref())
+            }) {
+                error!(
+                    "Endpoint {} is invalid. It has the same path and security as endpoint {}",
+                    id, other_id
+                );
+                valid = false;
+            }
         }
         if let Some(ref default_endpoint) = self.default_endpoint {
             if !self.endpoints.contains_key(default_endpoint) {
@@ -608,6 +909,36 @@ impl Config for ServerConfig {
             error!("Server configuration is invalid. Max byte string length is invalid");
             valid = false;
         }
+        if self.limits.min_publishing_interval < constants::MIN_PUBLISHING_INTERVAL {
+            error!(
+                "Server configuration is invalid. Min publishing interval {} is less than {}",
+                self.limits.min_publishing_interval,
+                constants::MIN_PUBLISHING_INTERVAL
+            );
+            valid = false;
+        }
+        if self.limits.min_sampling_interval < constants::MIN_SAMPLING_INTERVAL {
+            error!(
+                "Server configuration is invalid. Min sampling interval {} is less than {}",
+                self.limits.min_sampling_interval,
+                constants::MIN_SAMPLING_INTERVAL
+            );
+            valid = false;
+        }
+        if self.limits.max_monitored_item_queue_size == 0 {
+            error!(
+                "Server configuration is invalid. Max monitored item queue size must be at least 1"
+            );
+            valid = false;
+        }
+        if self.limits.max_session_timeout <= 0.0 {
+            error!("Server configuration is invalid. Max session timeout must be greater than 0");
+            valid = false;
+        }
+        if self.performance.max_queued_responses == 0 {
+            error!("Server configuration is invalid. Max queued responses must be at least 1");
+            valid = false;
+        }
         if self.discovery_urls.is_empty() {
             error!("Server configuration is invalid. Discovery urls not set");
             valid = false;
@@ -645,6 +976,7 @@ impl Default for ServerConfig {
 
         ServerConfig {
             application_name: String::new(),
+            localized_application_names: BTreeMap::new(),
             application_uri: String::new(),
             product_uri: String::new(),
             create_sample_keypair: false,
@@ -658,14 +990,25 @@ impl Default for ServerConfig {
                 port: constants::DEFAULT_RUST_OPC_UA_SERVER_PORT,
                 hello_timeout: constants::DEFAULT_HELLO_TIMEOUT_SECONDS,
             },
+            websocket_config: None,
+            https_config: None,
             limits: Limits::default(),
             user_tokens: BTreeMap::new(),
             locale_ids: vec!["en".to_string()],
             discovery_urls: Vec::new(),
             default_endpoint: None,
             endpoints: BTreeMap::new(),
+            nodesets: Vec::new(),
+            auditing: ServerConfig::default_auditing(),
+            capture_file: None,
+            capture_messages: false,
+            manufacturer_name: None,
+            build_number: None,
             performance: Performance {
                 single_threaded_executor: false,
+                thread_name: Performance::default_thread_name(),
+                max_concurrent_reads: Performance::default_max_concurrent_reads(),
+                max_queued_responses: Performance::default_max_queued_responses(),
             },
         }
     }
@@ -675,6 +1018,10 @@ impl ServerConfig {
     /// The default PKI directory
     pub const PKI_DIR: &'static str = "pki";
 
+    fn default_auditing() -> bool {
+        true
+    }
+
     pub fn new<T>(
         application_name: T,
         user_tokens: BTreeMap<String, ServerUserToken>,
@@ -698,6 +1045,7 @@ impl ServerConfig {
 
         ServerConfig {
             application_name,
+            localized_application_names: BTreeMap::new(),
             application_uri,
             product_uri,
             create_sample_keypair: false,
@@ -714,14 +1062,25 @@ impl ServerConfig {
                 port,
                 hello_timeout: constants::DEFAULT_HELLO_TIMEOUT_SECONDS,
             },
+            websocket_config: None,
+            https_config: None,
             limits: Limits::default(),
             locale_ids,
             user_tokens,
             discovery_urls,
             default_endpoint: None,
             endpoints,
+            nodesets: Vec::new(),
+            auditing: ServerConfig::default_auditing(),
+            capture_file: None,
+            capture_messages: false,
+            manufacturer_name: None,
+            build_number: None,
             performance: Performance {
                 single_threaded_executor: false,
+                thread_name: Performance::default_thread_name(),
+                max_concurrent_reads: Performance::default_max_concurrent_reads(),
+                max_queued_responses: Performance::default_max_queued_responses(),
             },
         }
     }
@@ -738,6 +1097,28 @@ impl ServerConfig {
         }
     }
 
+    /// Returns the application name in the first of the locales that it is configured for, trying
+    /// each locale id in turn and then just the language of it, e.g. "de" for "de-AT". The
+    /// `application_name` is returned if there is no match.
+    pub fn localized_application_name(&self, locale_ids: &Option<Vec<UAString>>) -> LocalizedText {
+        let locale_ids = locale_ids
+            .iter()
+            .flatten()
+            .filter_map(|l| l.value().as_deref());
+        for locale_id in locale_ids {
+            let language = locale_id.split('-').next().unwrap_or_default();
+            for locale in [locale_id, language] {
+                if let Some(name) = self.localized_application_names.get(locale) {
+                    return LocalizedText::new(locale, name);
+                }
+            }
+        }
+        LocalizedText {
+            locale: UAString::null(),
+            text: UAString::from(&self.application_name),
+        }
+    }
+
     pub fn add_endpoint(&mut self, id: &str, endpoint: ServerEndpoint) {
         self.endpoints.insert(id.to_string(), endpoint);
     }
@@ -756,6 +1137,56 @@ impl ServerConfig {
         )
     }
 
+    /// Returns a opc.wss://server:port url that paths can be appended onto, if the server listens
+    /// for WebSocket connections
+    pub fn base_websocket_url(&self) -> Option<String> {
+        self.websocket_config.as_ref().map(|websocket_config| {
+            format!(
+                "opc.wss://{}:{}",
+                self.tcp_config.host, websocket_config.port
+            )
+        })
+    }
+
+    /// Returns a https://server:port url that paths can be appended onto, if the server listens
+    /// for HTTPS connections
+    pub fn base_https_url(&self) -> Option<String> {
+        self.https_config
+            .as_ref()
+            .map(|https_config| format!("https://{}:{}", self.tcp_config.host, https_config.port))
+    }
+
+    /// Returns the base url of each transport the server listens on, with the uri of the
+    /// transport profile
+    pub fn base_endpoint_urls(&self) -> Vec<(String, &'static str)> {
+        let mut base_endpoint_urls = vec![(
+            self.base_endpoint_url(),
+            profiles::TRANSPORT_PROFILE_URI_BINARY,
+        )];
+        if cfg!(feature = "websocket") {
+            if let Some(base_websocket_url) = self.base_websocket_url() {
+                base_endpoint_urls.push((
+                    base_websocket_url,
+                    profiles::TRANSPORT_PROFILE_URI_WSS_BINARY,
+                ));
+            }
+        }
+        if cfg!(feature = "https") {
+            if let Some(base_https_url) = self.base_https_url() {
+                base_endpoint_urls
+                    .push((base_https_url, profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY));
+            }
+        }
+        base_endpoint_urls
+    }
+
+    /// Tests if the endpoint is offered on the transport. HTTPS has no secure channel, TLS
+    /// protects the messages instead, so only endpoints without security are offered on it.
+    pub fn is_endpoint_offered(endpoint: &ServerEndpoint, transport_profile_uri: &str) -> bool {
+        transport_profile_uri != profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY
+            || endpoint.security_policy() == SecurityPolicy::None
+    }
+
     /// Find the default endpoint
     pub fn default_endpoint(&self) -> Option<&ServerEndpoint> {
         if let Some(ref default_endpoint) = self.default_endpoint {
@@ -773,10 +1204,16 @@ impl ServerConfig {
         security_policy: SecurityPolicy,
         security_mode: MessageSecurityMode,
     ) -> Option<&ServerEndpoint> {
-        let base_endpoint_url = self.base_endpoint_url();
+        let base_endpoint_urls = self.base_endpoint_urls();
         let endpoint = self.endpoints.iter().find(|&(_, e)| {
-            // Test end point's security_policy_uri and matching url
-            if url_matches_except_host(&e.endpoint_url(&base_endpoint_url), endpoint_url) {
+            // Test end point's security_policy_uri and matching url on any of the transports
+            if base_endpoint_urls
+                .iter()
+                .any(|(base_endpoint_url, transport_profile_uri)| {
+                    Self::is_endpoint_offered(e, transport_profile_uri)
+                        && url_matches_except_host(&e.endpoint_url(base_endpoint_url), endpoint_url)
+                })
+            {
                 if e.security_policy() == security_policy
                     && e.message_security_mode() == security_mode
                 {
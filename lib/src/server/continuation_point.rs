@@ -2,19 +2,24 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
-//! Provides a browse continuation point type for tracking a browse operation initiated by a client.
+//! Provides browse and query continuation point types for tracking browse and query operations
+//! initiated by a client.
 
 use std::sync::Arc;
 
 use crate::sync::*;
+#[cfg(feature = "query-services")]
+use crate::types::service_types::QueryDataSet;
 use crate::types::{service_types::ReferenceDescription, ByteString, DateTimeUtc};
 
 use crate::prelude::AddressSpace;
+use crate::server::constants;
 
 #[derive(Clone, Debug)]
 pub struct BrowseContinuationPoint {
     pub id: ByteString,
     pub address_space_last_modified: DateTimeUtc,
+    pub created: DateTimeUtc,
     pub max_references_per_node: usize,
     pub starting_index: usize,
     pub reference_descriptions: Arc<Mutex<Vec<ReferenceDescription>>>,
@@ -26,4 +31,38 @@ impl BrowseContinuationPoint {
     pub fn is_valid_browse_continuation_point(&self, address_space: &AddressSpace) -> bool {
         self.address_space_last_modified >= address_space.last_modified()
     }
+
+    /// Test if the continuation point has gone unused for longer than the timeout and should be
+    /// released.
+    pub fn is_expired(&self, now: &DateTimeUtc) -> bool {
+        now.signed_duration_since(self.created).num_milliseconds()
+            > constants::BROWSE_CONTINUATION_POINT_TIMEOUT_MS
+    }
+}
+
+/// The data sets of a query that remain to be returned by `QueryNext`.
+#[cfg(feature = "query-services")]
+#[derive(Clone, Debug)]
+pub struct QueryContinuationPoint {
+    pub id: ByteString,
+    pub address_space_last_modified: DateTimeUtc,
+    pub created: DateTimeUtc,
+    pub max_data_sets_to_return: usize,
+    pub query_data_sets: Vec<QueryDataSet>,
+}
+
+#[cfg(feature = "query-services")]
+impl QueryContinuationPoint {
+    /// Test if the continuation point valid which is only true if address space has not been
+    /// modified since the point was made.
+    pub fn is_valid_query_continuation_point(&self, address_space: &AddressSpace) -> bool {
+        self.address_space_last_modified >= address_space.last_modified()
+    }
+
+    /// Test if the continuation point has gone unused for longer than the timeout and should be
+    /// released.
+    pub fn is_expired(&self, now: &DateTimeUtc) -> bool {
+        now.signed_duration_since(self.created).num_milliseconds()
+            > constants::QUERY_CONTINUATION_POINT_TIMEOUT_MS
+    }
 }
@@ -12,7 +12,7 @@ use crate::core::RUNTIME;
 
 use super::{session::Session, subscriptions::subscription::Subscription};
 
-/// Structure that captures di                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          agnostics information for the server
+/// Structure that captures diagnostics information for the server
 #[derive(Clone, Serialize, Debug)]
 pub struct ServerDiagnostics {
     /// This is a live summary of the server diagnostics
@@ -49,6 +49,7 @@ impl ServerDiagnostics {
     pub(crate) fn on_rejected_security_session(&mut self) {
         self.server_diagnostics_summary
             .security_rejected_session_count += 1;
+        self.on_rejected_session();
     }
 
     /// Increment the number of requests that were rejected since the server was started (or restarted). The
@@ -93,6 +94,22 @@ impl ServerDiagnostics {
         self.server_diagnostics_summary.session_timeout_count += 1;
     }
 
+    /// Increment the number of requests that were rejected due to security constraints since the server was
+    /// started (or restarted). The requests include all Services defined in Part 4, also requests
+    /// to create sessions.
+    pub(crate) fn on_security_rejected_request(&mut self) {
+        self.server_diagnostics_summary
+            .security_rejected_requests_count += 1;
+        self.on_rejected_request();
+    }
+
+    /// Increment the number of requests that were rejected since the server was started (or restarted). The
+    /// requests include all Services defined in Part 4, also requests to create sessions. This
+    /// number includes the securityRejectedRequestsCount.
+    pub(crate) fn on_rejected_request(&mut self) {
+        self.server_diagnostics_summary.rejected_requests_count += 1;
+    }
+
     // --- These are not yet called by anything
 
     /*
@@ -113,21 +130,5 @@ impl ServerDiagnostics {
         self.server_diagnostics_summary.publishing_interval_count += 1;
         unimplemented!()
     }
-
-    /// Increment the number of requests that were rejected due to security constraints since the server was
-    /// started (or restarted). The requests include all Services defined in Part 4, also requests
-    /// to create sessions.
-    pub fn on_security_rejected_request(&mut self) {
-        self.server_diagnostics_summary.security_rejected_requests_count += 1;
-        unimplemented!()
-    }
-
-    /// Increment the number of requests that were rejected since the server was started (or restarted). The
-    /// requests include all Services defined in Part 4, also requests to create sessions. This
-    /// number includes the securityRejectedRequestsCount.
-    pub(crate) fn on_rejected_request(&mut self) {
-        self.server_diagnostics_summary.rejected_requests_count += 1;
-        unimplemented!()
-    }
     */
 }
@@ -13,7 +13,9 @@ use std::sync::Arc;
 use crate::sync::*;
 use crate::types::*;
 
-use crate::server::{address_space::address_space::AddressSpace, events::event::Event};
+use crate::server::{
+    address_space::address_space::AddressSpace, callbacks::AuditSink, events::event::Event,
+};
 
 pub trait AuditEvent: Event {
     fn parent_node() -> NodeId {
@@ -38,16 +40,26 @@ pub mod session_events;
 pub mod certificate_events;
 pub mod cancel_event;
 pub mod node_management_event;
+pub mod update_events;
 
 /// The audit log will be responsible for adding audit events to the address space, and potentially logging them
 /// to file. All audit events should be raised through `AuditLog` to support any future logging capability.
 pub(crate) struct AuditLog {
     address_space: Arc<RwLock<AddressSpace>>,
+    /// Receives a copy of every audit event
+    sink: Option<Box<dyn AuditSink + Send + Sync>>,
 }
 
 impl AuditLog {
     pub fn new(address_space: Arc<RwLock<AddressSpace>>) -> AuditLog {
-        AuditLog { address_space }
+        AuditLog {
+            address_space,
+            sink: None,
+        }
+    }
+
+    pub fn set_sink(&mut self, sink: Box<dyn AuditSink + Send + Sync>) {
+        self.sink = Some(sink);
     }
 
     pub fn raise_and_log<T>(&self, mut event: T) -> Result<NodeId, ()>
@@ -61,7 +73,11 @@ impl AuditLog {
         }
         // At this point audit events just go out as log events but smarter logging implementations can always hive these
         // events off to a separate file. Look at demo-server for an example of this.
-        info!("Audit Event: {}", event.log_message());
+        let message = event.log_message();
+        info!("Audit Event: {}", message);
+        if let Some(ref sink) = self.sink {
+            sink.audit(&T::event_type_id(), &message);
+        }
         result
     }
 }
@@ -107,6 +107,21 @@ impl AuditSessionEventType {
         .source_name(reason.source_name())
     }
 
+    /// Creates an event warning that a session is about to be closed because it is idle
+    pub fn new_idle_session_warning<R>(node_id: R, time: DateTime) -> Self
+    where
+        R: Into<NodeId>,
+    {
+        Self::new(
+            node_id,
+            Self::event_type_id(),
+            "AuditSessionEventType",
+            "AuditSessionEventType",
+            time,
+        )
+        .source_name("Session/IdleWarning")
+    }
+
     pub fn session_id<T>(mut self, session_id: T) -> Self
     where
         T: Into<NodeId>,
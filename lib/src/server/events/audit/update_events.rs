@@ -0,0 +1,179 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+use crate::types::*;
+
+use crate::server::{address_space::address_space::AddressSpace, events::event::Event};
+
+use super::{event::AuditEventType, AuditEvent};
+
+/// Base type for audit update events. Do not raise events of this type
+pub(super) struct AuditUpdateEventType {
+    base: AuditEventType,
+}
+
+impl AuditEvent for AuditUpdateEventType {
+    fn event_type_id() -> NodeId {
+        panic!()
+    }
+
+    fn log_message(&self) -> String {
+        self.base.log_message()
+    }
+}
+
+impl Event for AuditUpdateEventType {
+    type Err = ();
+
+    fn is_valid(&self) -> bool {
+        self.base.is_valid()
+    }
+
+    fn raise(&mut self, address_space: &mut AddressSpace) -> Result<NodeId, Self::Err> {
+        self.base.raise(address_space)
+    }
+}
+
+audit_event_impl!(AuditUpdateEventType, base);
+
+impl AuditUpdateEventType {
+    pub fn new<R, E, S, T>(
+        node_id: R,
+        event_type_id: E,
+        browse_name: S,
+        display_name: T,
+        time: DateTime,
+    ) -> Self
+    where
+        R: Into<NodeId>,
+        E: Into<NodeId>,
+        S: Into<QualifiedName>,
+        T: Into<LocalizedText>,
+    {
+        Self {
+            base: AuditEventType::new(node_id, event_type_id, browse_name, display_name, time),
+        }
+    }
+}
+
+/// Raised when a client writes to an attribute of a node
+pub struct AuditWriteUpdateEventType {
+    base: AuditUpdateEventType,
+    attribute_id: u32,
+    index_range: UAString,
+    old_value: Variant,
+    new_value: Variant,
+}
+
+impl AuditEvent for AuditWriteUpdateEventType {
+    fn event_type_id() -> NodeId {
+        ObjectTypeId::AuditWriteUpdateEventType.into()
+    }
+
+    fn log_message(&self) -> String {
+        self.base.log_message()
+    }
+}
+
+impl Event for AuditWriteUpdateEventType {
+    type Err = ();
+
+    fn is_valid(&self) -> bool {
+        self.base.is_valid()
+    }
+
+    fn raise(&mut self, address_space: &mut AddressSpace) -> Result<NodeId, Self::Err> {
+        let node_id = self.base.raise(address_space)?;
+        let ns = node_id.namespace;
+        self.add_property(
+            &node_id,
+            NodeId::next_numeric(ns),
+            "AttributeId",
+            "AttributeId",
+            DataTypeId::UInt32,
+            self.attribute_id,
+            address_space,
+        );
+        self.add_property(
+            &node_id,
+            NodeId::next_numeric(ns),
+            "IndexRange",
+            "IndexRange",
+            DataTypeId::NumericRange,
+            self.index_range.clone(),
+            address_space,
+        );
+        self.add_property(
+            &node_id,
+            NodeId::next_numeric(ns),
+            "OldValue",
+            "OldValue",
+            DataTypeId::BaseDataType,
+            self.old_value.clone(),
+            address_space,
+        );
+        self.add_property(
+            &node_id,
+            NodeId::next_numeric(ns),
+            "NewValue",
+            "NewValue",
+            DataTypeId::BaseDataType,
+            self.new_value.clone(),
+            address_space,
+        );
+        Ok(node_id)
+    }
+}
+
+audit_event_impl!(AuditWriteUpdateEventType, base);
+
+impl AuditWriteUpdateEventType {
+    pub fn new<R>(node_id: R, time: DateTime) -> Self
+    where
+        R: Into<NodeId>,
+    {
+        Self {
+            base: AuditUpdateEventType::new(
+                node_id,
+                Self::event_type_id(),
+                "AuditWriteUpdateEventType",
+                "AuditWriteUpdateEventType",
+                time,
+            ),
+            attribute_id: 0,
+            index_range: UAString::null(),
+            old_value: Variant::Empty,
+            new_value: Variant::Empty,
+        }
+    }
+
+    pub fn attribute_id(mut self, attribute_id: u32) -> Self {
+        self.attribute_id = attribute_id;
+        self
+    }
+
+    pub fn index_range<T>(mut self, index_range: T) -> Self
+    where
+        T: Into<UAString>,
+    {
+        self.index_range = index_range.into();
+        self
+    }
+
+    pub fn old_value<T>(mut self, old_value: T) -> Self
+    where
+        T: Into<Variant>,
+    {
+        self.old_value = old_value.into();
+        self
+    }
+
+    pub fn new_value<T>(mut self, new_value: T) -> Self
+    where
+        T: Into<Variant>,
+    {
+        self.new_value = new_value.into();
+        self
+    }
+}
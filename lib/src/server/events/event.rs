@@ -83,6 +83,13 @@ impl Event for BaseEventType {
     }
 
     fn raise(&mut self, address_space: &mut AddressSpace) -> Result<NodeId, Self::Err> {
+        // Events are not part of the model, so inserting them is not a model change
+        address_space.untracked(|address_space| self.raise_untracked(address_space))
+    }
+}
+
+impl BaseEventType {
+    fn raise_untracked(&mut self, address_space: &mut AddressSpace) -> Result<NodeId, ()> {
         if self.is_valid() {
             // create an event object in a folder with the
             let ns = self.node_id.namespace;
@@ -310,12 +317,14 @@ impl BaseEventType {
         U: Into<NodeId>,
         V: Into<Variant>,
     {
-        VariableBuilder::new(&property_id.into(), browse_name, display_name)
-            .property_of(event_id.clone())
-            .has_type_definition(VariableTypeId::PropertyType)
-            .data_type(data_type)
-            .value(value)
-            .insert(address_space);
+        address_space.untracked(|address_space| {
+            VariableBuilder::new(&property_id.into(), browse_name, display_name)
+                .property_of(event_id.clone())
+                .has_type_definition(VariableTypeId::PropertyType)
+                .data_type(data_type)
+                .value(value)
+                .insert(address_space)
+        });
     }
 
     pub fn message<T>(mut self, message: T) -> Self
@@ -367,6 +376,7 @@ impl BaseEventType {
 macro_rules! base_event_impl {
     ( $event:ident, $base:ident ) => {
         impl $event {
+            #[allow(clippy::too_many_arguments)]
             pub fn add_property<T, R, S, U, V>(
                 &mut self,
                 event_id: &NodeId,
@@ -552,9 +562,11 @@ where
         // Delete these events from the address space
         info!("Deleting some events from the address space");
         let len = events.len();
-        events.into_iter().for_each(|node_id| {
-            debug!("Deleting event {}", node_id);
-            address_space.delete(&node_id, true);
+        address_space.untracked(|address_space| {
+            events.into_iter().for_each(|node_id| {
+                debug!("Deleting event {}", node_id);
+                address_space.delete(&node_id, true);
+            })
         });
         len
     } else {
@@ -31,7 +31,8 @@ pub fn validate(
             .map(|clause| validate_select_clause(clause, address_space))
             .collect()
     });
-    let where_clause_result = validate_where_clause(&event_filter.where_clause, address_space)?;
+    let where_clause_result =
+        validate_where_clause(&event_filter.where_clause, address_space, false)?;
     Ok(EventFilterResult {
         select_clause_results,
         select_clause_diagnostic_infos: None,
@@ -167,9 +168,12 @@ fn validate_select_clause(
     }
 }
 
-fn validate_where_clause(
+/// Validates the where clause of an event filter, or the filter of a query when `in_query` is set.
+/// Only a query filter may use attribute operands.
+pub(crate) fn validate_where_clause(
     where_clause: &ContentFilter,
     address_space: &AddressSpace,
+    in_query: bool,
 ) -> Result<ContentFilterResult, StatusCode> {
     // The ContentFilter structure defines a collection of elements that define filtering criteria.
     // Each element in the collection describes an operator and an array of operands to be used by
@@ -209,6 +213,7 @@ fn validate_where_clause(
                     FilterOperator::Cast => filter_operands.len() < 2,
                     FilterOperator::BitwiseAnd => filter_operands.len() < 2,
                     FilterOperator::BitwiseOr => filter_operands.len() < 2,
+                    FilterOperator::OfType => filter_operands.is_empty(),
                     _ => true,
                 };
 
@@ -218,6 +223,7 @@ fn validate_where_clause(
                     match <Operand>::try_from(e) {
                         Ok(operand) => {
                             match operand {
+                                Operand::AttributeOperand(_) if in_query => StatusCode::Good,
                                 Operand::AttributeOperand(_) => {
                                     // AttributeOperand may not be used in an EventFilter where clause
                                     error!("AttributeOperand is not permitted in EventFilter where clause");
@@ -312,7 +318,7 @@ fn validate_where_clause_test() {
     {
         let where_clause = ContentFilter { elements: None };
         // check for at least one filter operand
-        let result = validate_where_clause(&where_clause, &address_space);
+        let result = validate_where_clause(&where_clause, &address_space, false);
         assert_eq!(
             result.unwrap(),
             ContentFilterResult {
@@ -361,7 +367,7 @@ fn validate_where_clause_test() {
             ]),
         };
         // Check for less than required number of operands
-        let result = validate_where_clause(&where_clause, &address_space).unwrap();
+        let result = validate_where_clause(&where_clause, &address_space, false).unwrap();
         result
             .element_results
             .unwrap()
@@ -379,7 +385,7 @@ fn validate_where_clause_test() {
                 filter_operands: Some(vec![bad_operator]),
             }]),
         };
-        let result = validate_where_clause(&where_clause, &address_space).unwrap();
+        let result = validate_where_clause(&where_clause, &address_space, false).unwrap();
         let element_results = result.element_results.unwrap();
         assert_eq!(element_results.len(), 1);
         assert_eq!(
@@ -8,3 +8,4 @@ pub(crate) mod operator;
 pub mod event;
 #[macro_use]
 pub mod audit;
+pub mod model_change_event;
@@ -0,0 +1,80 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the event that tells clients the nodes or references of the address space changed.
+
+use crate::types::{
+    service_types::{ModelChangeStructureDataType, TimeZoneDataType},
+    DataTypeId, DateTime, ExtensionObject, LocalizedText, NodeId, ObjectId, ObjectTypeId,
+    QualifiedName, UAString, Variant, VariantTypeId,
+};
+
+use crate::server::{
+    address_space::AddressSpace,
+    events::event::{BaseEventType, Event},
+};
+
+/// This corresponds to GeneralModelChangeEventType definition in OPC UA Part 5. Its `Changes`
+/// say which nodes were affected and how, so that a client can refresh what it has browsed of them.
+pub struct GeneralModelChangeEventType {
+    base: BaseEventType,
+    changes: Vec<ModelChangeStructureDataType>,
+}
+
+impl Event for GeneralModelChangeEventType {
+    type Err = ();
+
+    fn is_valid(&self) -> bool {
+        self.base.is_valid()
+    }
+
+    fn raise(&mut self, address_space: &mut AddressSpace) -> Result<NodeId, Self::Err> {
+        let node_id = self.base.raise(address_space)?;
+        let changes = self
+            .changes
+            .iter()
+            .map(|change| {
+                Variant::from(ExtensionObject::from_encodable(
+                    ObjectId::ModelChangeStructureDataType_Encoding_DefaultBinary,
+                    change,
+                ))
+            })
+            .collect::<Vec<Variant>>();
+        self.add_property(
+            &node_id,
+            NodeId::next_numeric(node_id.namespace),
+            "Changes",
+            "Changes",
+            DataTypeId::ModelChangeStructureDataType,
+            (VariantTypeId::ExtensionObject, changes),
+            address_space,
+        );
+        Ok(node_id)
+    }
+}
+
+base_event_impl!(GeneralModelChangeEventType, base);
+
+impl GeneralModelChangeEventType {
+    pub fn new<R>(node_id: R, time: DateTime, changes: Vec<ModelChangeStructureDataType>) -> Self
+    where
+        R: Into<NodeId>,
+    {
+        Self {
+            base: BaseEventType::new(
+                node_id,
+                ObjectTypeId::GeneralModelChangeEventType,
+                "GeneralModelChangeEvent",
+                "GeneralModelChangeEvent",
+                NodeId::null(),
+                time,
+            ),
+            changes,
+        }
+    }
+
+    pub fn changes(&self) -> &[ModelChangeStructureDataType] {
+        &self.changes
+    }
+}
@@ -10,15 +10,17 @@ use regex::Regex;
 
 use crate::types::{
     operand::Operand,
-    service_types::{ContentFilterElement, FilterOperator, SimpleAttributeOperand},
+    service_types::{
+        AttributeOperand, ContentFilterElement, FilterOperator, SimpleAttributeOperand,
+    },
     status_code::StatusCode,
-    AttributeId, ExtensionObject, NodeId, NumericRange, QualifiedName, TimestampsToReturn, Variant,
-    VariantTypeId,
+    AttributeId, ExtensionObject, NodeId, NumericRange, QualifiedName, ReferenceTypeId,
+    TimestampsToReturn, UAString, Variant, VariantTypeId,
 };
 
 use crate::server::address_space::{
     node::{NodeBase, NodeType},
-    relative_path::find_node_from_browse_path,
+    relative_path::{find_node_from_browse_path, find_node_from_relative_path},
     AddressSpace,
 };
 
@@ -161,6 +163,13 @@ pub(crate) fn evaluate(
                     elements,
                     address_space,
                 ),
+                FilterOperator::OfType => of_type(
+                    object_id,
+                    &operands[..],
+                    used_elements,
+                    elements,
+                    address_space,
+                ),
                 _ => Err(StatusCode::BadFilterOperatorUnsupported),
             }
         } else {
@@ -280,12 +289,60 @@ pub(crate) fn value_of(
         Operand::SimpleAttributeOperand(ref o) => {
             Ok(value_of_simple_attribute(object_id, o, address_space))
         }
-        Operand::AttributeOperand(_) => {
-            panic!();
-        }
+        Operand::AttributeOperand(ref o) => Ok(value_of_attribute(object_id, o, address_space)),
+    }
+}
+
+/// Gets the value of an attribute of the node found by following the browse path of the operand
+/// from the object. An empty browse path refers to the object itself.
+pub(crate) fn value_of_attribute(
+    object_id: &NodeId,
+    o: &AttributeOperand,
+    address_space: &AddressSpace,
+) -> Variant {
+    if let Some(node) = find_node_from_relative_path(address_space, object_id, &o.browse_path) {
+        attribute_value(node, o.attribute_id, &o.index_range)
+    } else {
+        debug!(
+            "value_of, cannot find node from browse path {:?}",
+            o.browse_path
+        );
+        Variant::Empty
     }
 }
 
+/// Reads an attribute of a node as a variant, which is empty if the node does not have the
+/// attribute or the index range is invalid.
+pub(crate) fn attribute_value(
+    node: &NodeType,
+    attribute_id: u32,
+    index_range: &UAString,
+) -> Variant {
+    let attribute_id = match AttributeId::from_u32(attribute_id) {
+        Ok(attribute_id) => attribute_id,
+        Err(_) => {
+            error!("value_of, invalid attribute id {}", attribute_id);
+            return Variant::Empty;
+        }
+    };
+    let index_range = match index_range.as_ref().parse::<NumericRange>() {
+        Ok(index_range) => index_range,
+        Err(_) => {
+            error!("value_of, invalid index range {}", index_range);
+            return Variant::Empty;
+        }
+    };
+    node.as_node()
+        .get_attribute(
+            TimestampsToReturn::Neither,
+            attribute_id,
+            index_range,
+            &QualifiedName::null(),
+        )
+        .and_then(|v| v.value)
+        .unwrap_or(Variant::Empty)
+}
+
 fn convert(v1: Variant, v2: Variant) -> (Variant, Variant) {
     // Types may have to be converted to be compared
     let dt1 = v1.type_id();
@@ -860,6 +917,36 @@ pub(crate) fn or(
     Ok(result)
 }
 
+// TRUE if the type definition of the object is the type identified by operand[0] or one of its
+// subtypes.
+pub(crate) fn of_type(
+    object_id: &NodeId,
+    operands: &[Operand],
+    used_elements: &mut HashSet<u32>,
+    elements: &[ContentFilterElement],
+    address_space: &AddressSpace,
+) -> Result<Variant, StatusCode> {
+    let type_id = match value_of(
+        object_id,
+        &operands[0],
+        used_elements,
+        elements,
+        address_space,
+    )? {
+        Variant::NodeId(node_id) => *node_id,
+        Variant::ExpandedNodeId(node_id) => node_id.node_id,
+        _ => return Err(StatusCode::BadFilterOperandInvalid),
+    };
+    let result = address_space
+        .find_references(object_id, Some((ReferenceTypeId::HasTypeDefinition, false)))
+        .is_some_and(|references| {
+            references
+                .iter()
+                .any(|r| address_space.is_subtype(&r.target_node, &type_id))
+        });
+    Ok(result.into())
+}
+
 // Converts operand[0] to a value with a data type with a NodeId identified by operand[1].
 pub(crate) fn cast(
     object_id: &NodeId,
@@ -0,0 +1,575 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Calculation of the aggregates described in OPC UA Part 13. Aggregates are computed from raw
+//! values over a series of processing intervals, and are used by HistoryRead with
+//! `ReadProcessedDetails` and by monitored items with an `AggregateFilter`.
+
+use std::result::Result;
+
+use chrono::Duration;
+
+use crate::types::{node_ids::ObjectId, status_code::StatusCode, *};
+
+/// The aggregate functions supported by the server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum AggregateFunction {
+    Interpolative,
+    Average,
+    Minimum,
+    Maximum,
+    MinimumActualTime,
+    MaximumActualTime,
+    Range,
+    Count,
+    Start,
+    End,
+    Delta,
+    DurationGood,
+    DurationBad,
+    PercentGood,
+    PercentBad,
+    WorstQuality,
+}
+
+impl AggregateFunction {
+    /// All the supported aggregate functions
+    pub const ALL: [AggregateFunction; 16] = [
+        AggregateFunction::Interpolative,
+        AggregateFunction::Average,
+        AggregateFunction::Minimum,
+        AggregateFunction::Maximum,
+        AggregateFunction::MinimumActualTime,
+        AggregateFunction::MaximumActualTime,
+        AggregateFunction::Range,
+        AggregateFunction::Count,
+        AggregateFunction::Start,
+        AggregateFunction::End,
+        AggregateFunction::Delta,
+        AggregateFunction::DurationGood,
+        AggregateFunction::DurationBad,
+        AggregateFunction::PercentGood,
+        AggregateFunction::PercentBad,
+        AggregateFunction::WorstQuality,
+    ];
+
+    /// Finds the aggregate function identified by the node id, e.g. `AggregateFunction_Average`.
+    /// Returns `BadAggregateNotSupported` if the function is unknown or unsupported.
+    pub fn from_node_id(node_id: &NodeId) -> Result<AggregateFunction, StatusCode> {
+        let object_id = node_id
+            .as_object_id()
+            .map_err(|_| StatusCode::BadAggregateNotSupported)?;
+        Self::ALL
+            .iter()
+            .find(|f| f.object_id() == object_id)
+            .copied()
+            .ok_or(StatusCode::BadAggregateNotSupported)
+    }
+
+    /// The object id of the aggregate function in the address space
+    pub fn object_id(&self) -> ObjectId {
+        match self {
+            AggregateFunction::Interpolative => ObjectId::AggregateFunction_Interpolative,
+            AggregateFunction::Average => ObjectId::AggregateFunction_Average,
+            AggregateFunction::Minimum => ObjectId::AggregateFunction_Minimum,
+            AggregateFunction::Maximum => ObjectId::AggregateFunction_Maximum,
+            AggregateFunction::MinimumActualTime => ObjectId::AggregateFunction_MinimumActualTime,
+            AggregateFunction::MaximumActualTime => ObjectId::AggregateFunction_MaximumActualTime,
+            AggregateFunction::Range => ObjectId::AggregateFunction_Range,
+            AggregateFunction::Count => ObjectId::AggregateFunction_Count,
+            AggregateFunction::Start => ObjectId::AggregateFunction_Start,
+            AggregateFunction::End => ObjectId::AggregateFunction_End,
+            AggregateFunction::Delta => ObjectId::AggregateFunction_Delta,
+            AggregateFunction::DurationGood => ObjectId::AggregateFunction_DurationGood,
+            AggregateFunction::DurationBad => ObjectId::AggregateFunction_DurationBad,
+            AggregateFunction::PercentGood => ObjectId::AggregateFunction_PercentGood,
+            AggregateFunction::PercentBad => ObjectId::AggregateFunction_PercentBad,
+            AggregateFunction::WorstQuality => ObjectId::AggregateFunction_WorstQuality,
+        }
+    }
+}
+
+/// The aggregate configuration the server uses when a client asks for the server's defaults.
+pub fn default_aggregate_configuration() -> AggregateConfiguration {
+    AggregateConfiguration {
+        use_server_capabilities_defaults: false,
+        treat_uncertain_as_bad: true,
+        percent_data_bad: 100,
+        percent_data_good: 100,
+        use_sloped_extrapolation: false,
+    }
+}
+
+/// A raw value that has a timestamp, with its status and numeric value pulled out
+struct Sample<'a> {
+    time: DateTimeUtc,
+    status: StatusCode,
+    value: Option<f64>,
+    data_value: &'a DataValue,
+}
+
+/// The portion of an interval that is covered by good and bad values
+struct Quality {
+    percent_good: f64,
+    percent_bad: f64,
+    duration_good: f64,
+    duration_bad: f64,
+}
+
+/// Computes an aggregate function over raw values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AggregateCalculator {
+    function: AggregateFunction,
+    configuration: AggregateConfiguration,
+    processing_interval: f64,
+}
+
+impl AggregateCalculator {
+    /// Creates a calculator for the aggregate type with a processing interval in milliseconds. A
+    /// processing interval of 0 means a single interval covering the whole time range.
+    ///
+    /// Returns `BadAggregateNotSupported` for an unsupported aggregate type and
+    /// `BadAggregateConfigurationRejected` if the percentages of the configuration are invalid.
+    pub fn new(
+        aggregate_type: &NodeId,
+        configuration: &AggregateConfiguration,
+        processing_interval: f64,
+    ) -> Result<AggregateCalculator, StatusCode> {
+        let function = AggregateFunction::from_node_id(aggregate_type)?;
+        if !processing_interval.is_finite() || processing_interval < 0f64 {
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let configuration = if configuration.use_server_capabilities_defaults {
+            default_aggregate_configuration()
+        } else {
+            configuration.clone()
+        };
+        // The good and bad regions must not overlap
+        if configuration.percent_data_bad > 100
+            || configuration.percent_data_good > 100
+            || (configuration.percent_data_bad as u32 + configuration.percent_data_good as u32)
+                < 100
+        {
+            return Err(StatusCode::BadAggregateConfigurationRejected);
+        }
+        Ok(AggregateCalculator {
+            function,
+            configuration,
+            processing_interval,
+        })
+    }
+
+    pub fn function(&self) -> AggregateFunction {
+        self.function
+    }
+
+    /// The configuration in effect, i.e. with server defaults substituted if they were requested
+    pub fn configuration(&self) -> &AggregateConfiguration {
+        &self.configuration
+    }
+
+    pub fn processing_interval(&self) -> f64 {
+        self.processing_interval
+    }
+
+    /// Computes the aggregate for each processing interval between the start and end time. The
+    /// raw values should include the bounding values either side of the time range if there are
+    /// any, since some aggregates interpolate or carry values across interval boundaries.
+    ///
+    /// If the end time is before the start time, the intervals are returned in reverse order.
+    pub fn process(
+        &self,
+        start_time: &DateTime,
+        end_time: &DateTime,
+        raw_values: &[DataValue],
+    ) -> Vec<DataValue> {
+        let (start, end) = (start_time.as_chrono(), end_time.as_chrono());
+        let reverse = end < start;
+        let (start, end) = if reverse { (end, start) } else { (start, end) };
+        if start == end {
+            return Vec::new();
+        }
+
+        let samples = Self::samples(raw_values);
+        let interval = if self.processing_interval > 0f64 {
+            Duration::microseconds((self.processing_interval * 1000f64) as i64)
+                .max(Duration::microseconds(1))
+        } else {
+            end - start
+        };
+
+        let mut results = Vec::new();
+        let mut interval_start = start;
+        while interval_start < end {
+            // An interval that goes beyond the representable times ends at the end time
+            let interval_end = interval_start
+                .checked_add_signed(interval)
+                .map_or(end, |interval_end| interval_end.min(end));
+            let mut result = self.calculate(interval_start, interval_end, &samples);
+            // A final interval that is shorter than the processing interval is partial
+            if interval_end - interval_start < interval {
+                if let Some(status) = result.status {
+                    if status
+                        .bitflags()
+                        .contains(StatusCode::HISTORICAL_CALCULATED)
+                    {
+                        result.status = Some(status | StatusCode::HISTORICAL_PARTIAL);
+                    }
+                }
+            }
+            results.push(result);
+            interval_start = interval_end;
+        }
+        if reverse {
+            results.reverse();
+        }
+        results
+    }
+
+    /// Computes the aggregate over a single interval from start (inclusive) to end (exclusive).
+    pub fn calculate_interval(
+        &self,
+        start_time: &DateTime,
+        end_time: &DateTime,
+        raw_values: &[DataValue],
+    ) -> DataValue {
+        let samples = Self::samples(raw_values);
+        self.calculate(start_time.as_chrono(), end_time.as_chrono(), &samples)
+    }
+
+    /// Extracts the raw values that have a timestamp in time order. The source timestamp is
+    /// preferred over the server timestamp.
+    fn samples(raw_values: &[DataValue]) -> Vec<Sample<'_>> {
+        let mut samples = raw_values
+            .iter()
+            .filter_map(|data_value| {
+                let time = data_value
+                    .source_timestamp
+                    .or(data_value.server_timestamp)?
+                    .as_chrono();
+                Some(Sample {
+                    time,
+                    status: data_value.status(),
+                    value: data_value.value.as_ref().and_then(|v| v.as_f64()),
+                    data_value,
+                })
+            })
+            .collect::<Vec<_>>();
+        samples.sort_by_key(|s| s.time);
+        samples
+    }
+
+    /// Tests if a value's status allows it to be used in a calculation
+    fn is_usable(&self, status: StatusCode) -> bool {
+        status.is_good() || (status.is_uncertain() && !self.configuration.treat_uncertain_as_bad)
+    }
+
+    /// Tests if a value's status counts as bad
+    fn is_bad(&self, status: StatusCode) -> bool {
+        status.is_bad() || (status.is_uncertain() && self.configuration.treat_uncertain_as_bad)
+    }
+
+    fn calculate(&self, start: DateTimeUtc, end: DateTimeUtc, samples: &[Sample]) -> DataValue {
+        let inside = samples
+            .iter()
+            .filter(|s| s.time >= start && s.time < end)
+            .collect::<Vec<_>>();
+        let numeric = inside
+            .iter()
+            .filter(|s| s.value.is_some() && self.is_usable(s.status))
+            .copied()
+            .collect::<Vec<_>>();
+
+        match self.function {
+            AggregateFunction::Interpolative => self.interpolate(start, samples),
+            AggregateFunction::Average => {
+                let values = numeric.iter().filter_map(|s| s.value).collect::<Vec<_>>();
+                if values.is_empty() {
+                    Self::no_data(start)
+                } else {
+                    let average = values.iter().sum::<f64>() / values.len() as f64;
+                    self.calculated(start, end, samples, start, average)
+                }
+            }
+            AggregateFunction::Minimum | AggregateFunction::MinimumActualTime => {
+                match numeric
+                    .iter()
+                    .reduce(|a, b| if b.value < a.value { b } else { a })
+                {
+                    Some(s) => self.extreme(start, end, samples, s),
+                    None => Self::no_data(start),
+                }
+            }
+            AggregateFunction::Maximum | AggregateFunction::MaximumActualTime => {
+                match numeric
+                    .iter()
+                    .reduce(|a, b| if b.value > a.value { b } else { a })
+                {
+                    Some(s) => self.extreme(start, end, samples, s),
+                    None => Self::no_data(start),
+                }
+            }
+            AggregateFunction::Range => {
+                let values = numeric.iter().filter_map(|s| s.value);
+                let min = values.clone().reduce(f64::min);
+                let max = values.reduce(f64::max);
+                match (min, max) {
+                    (Some(min), Some(max)) => {
+                        self.calculated(start, end, samples, start, max - min)
+                    }
+                    _ => Self::no_data(start),
+                }
+            }
+            AggregateFunction::Count => {
+                let count = inside.iter().filter(|s| self.is_usable(s.status)).count() as i32;
+                self.calculated(start, end, samples, start, count)
+            }
+            AggregateFunction::Start => match inside.first() {
+                Some(s) => Self::raw(s),
+                None => Self::no_data(start),
+            },
+            AggregateFunction::End => match inside.last() {
+                Some(s) => Self::raw(s),
+                None => Self::no_data(start),
+            },
+            AggregateFunction::Delta => match (numeric.first(), numeric.last()) {
+                (Some(first), Some(last)) => {
+                    let delta = last.value.unwrap() - first.value.unwrap();
+                    self.calculated(start, end, samples, start, delta)
+                }
+                _ => Self::no_data(start),
+            },
+            AggregateFunction::DurationGood => {
+                let quality = self.quality(start, end, samples);
+                Self::good(start, quality.duration_good)
+            }
+            AggregateFunction::DurationBad => {
+                let quality = self.quality(start, end, samples);
+                Self::good(start, quality.duration_bad)
+            }
+            AggregateFunction::PercentGood => {
+                let quality = self.quality(start, end, samples);
+                Self::good(start, quality.percent_good)
+            }
+            AggregateFunction::PercentBad => {
+                let quality = self.quality(start, end, samples);
+                Self::good(start, quality.percent_bad)
+            }
+            AggregateFunction::WorstQuality => {
+                let severity = |s: &StatusCode| {
+                    if s.is_bad() {
+                        2
+                    } else if s.is_uncertain() {
+                        1
+                    } else {
+                        0
+                    }
+                };
+                // The first of the worst values is the one reported
+                match inside.iter().reduce(|a, b| {
+                    if severity(&b.status) > severity(&a.status) {
+                        b
+                    } else {
+                        a
+                    }
+                }) {
+                    Some(worst) => {
+                        let worst_status = worst.status.status();
+                        let mut result = Self::good(worst.time, Variant::StatusCode(worst_status));
+                        // Flag when more than one value has the worst status
+                        if inside
+                            .iter()
+                            .filter(|s| s.status.status() == worst_status)
+                            .count()
+                            > 1
+                        {
+                            result.status = result
+                                .status
+                                .map(|status| status | StatusCode::HISTORICAL_MULTI_VALUE);
+                        }
+                        result
+                    }
+                    None => Self::no_data(start),
+                }
+            }
+        }
+    }
+
+    /// The minimum or maximum value, with the time of the raw value for the actual time variants
+    fn extreme(
+        &self,
+        start: DateTimeUtc,
+        end: DateTimeUtc,
+        samples: &[Sample],
+        sample: &Sample,
+    ) -> DataValue {
+        let time = match self.function {
+            AggregateFunction::MinimumActualTime | AggregateFunction::MaximumActualTime => {
+                sample.time
+            }
+            _ => start,
+        };
+        let value = sample.data_value.value.clone().unwrap();
+        self.calculated(start, end, samples, time, value)
+    }
+
+    /// The interpolated value at the start of the interval from the bounding values either side.
+    fn interpolate(&self, start: DateTimeUtc, samples: &[Sample]) -> DataValue {
+        let usable = samples
+            .iter()
+            .filter(|s| s.value.is_some() && self.is_usable(s.status))
+            .collect::<Vec<_>>();
+        let before = usable.iter().rposition(|s| s.time <= start);
+        let after = usable.iter().find(|s| s.time > start);
+        match (before.map(|i| usable[i]), after) {
+            // A raw value at the start of the interval is returned as is
+            (Some(b), _) if b.time == start => Self::raw(b),
+            (Some(b), Some(a)) => {
+                let (v1, v2) = (b.value.unwrap(), a.value.unwrap());
+                let fraction = Self::millis(start - b.time) / Self::millis(a.time - b.time);
+                let status = if b.status.is_good() && a.status.is_good() {
+                    StatusCode::Good
+                } else {
+                    StatusCode::UncertainDataSubNormal
+                };
+                Self::value_at(
+                    start,
+                    v1 + (v2 - v1) * fraction,
+                    status | StatusCode::HISTORICAL_INTERPOLATED,
+                )
+            }
+            (Some(b), None) => {
+                // Extrapolate from the last value, which is always uncertain
+                let previous = before.and_then(|i| i.checked_sub(1)).map(|i| usable[i]);
+                let value = match previous {
+                    Some(p) if self.configuration.use_sloped_extrapolation && p.time < b.time => {
+                        let (v1, v2) = (p.value.unwrap(), b.value.unwrap());
+                        let slope = (v2 - v1) / Self::millis(b.time - p.time);
+                        v2 + slope * Self::millis(start - b.time)
+                    }
+                    _ => b.value.unwrap(),
+                };
+                Self::value_at(
+                    start,
+                    value,
+                    StatusCode::UncertainDataSubNormal | StatusCode::HISTORICAL_INTERPOLATED,
+                )
+            }
+            _ => Self::no_data(start),
+        }
+    }
+
+    /// Works out how much of the interval is covered by good and bad values. Each value holds
+    /// until the next one, and a value before the interval holds until the first value inside it.
+    /// Time that is not covered by any value counts as bad.
+    fn quality(&self, start: DateTimeUtc, end: DateTimeUtc, samples: &[Sample]) -> Quality {
+        let mut duration_good = 0f64;
+        let mut duration_bad = 0f64;
+        let mut time = start;
+        let mut status = samples.iter().rfind(|s| s.time < start).map(|s| s.status);
+        for sample in samples.iter().filter(|s| s.time >= start && s.time < end) {
+            let duration = Self::millis(sample.time - time);
+            match status {
+                Some(s) if s.is_good() => duration_good += duration,
+                Some(s) if !self.is_bad(s) => {}
+                _ => duration_bad += duration,
+            }
+            time = sample.time;
+            status = Some(sample.status);
+        }
+        let duration = Self::millis(end - time);
+        match status {
+            Some(s) if s.is_good() => duration_good += duration,
+            Some(s) if !self.is_bad(s) => {}
+            _ => duration_bad += duration,
+        }
+        let total = Self::millis(end - start);
+        Quality {
+            percent_good: duration_good * 100f64 / total,
+            percent_bad: duration_bad * 100f64 / total,
+            duration_good,
+            duration_bad,
+        }
+    }
+
+    /// A calculated value whose status depends on how much of the interval had good data
+    fn calculated<V>(
+        &self,
+        start: DateTimeUtc,
+        end: DateTimeUtc,
+        samples: &[Sample],
+        time: DateTimeUtc,
+        value: V,
+    ) -> DataValue
+    where
+        V: Into<Variant>,
+    {
+        let quality = self.quality(start, end, samples);
+        let status = if quality.percent_bad > 0f64
+            && quality.percent_bad >= self.configuration.percent_data_bad as f64
+        {
+            StatusCode::BadNoData
+        } else if quality.percent_good >= self.configuration.percent_data_good as f64 {
+            StatusCode::Good
+        } else {
+            StatusCode::UncertainDataSubNormal
+        };
+        let mut result = Self::value_at(time, value, status | StatusCode::HISTORICAL_CALCULATED);
+        if status.is_bad() {
+            result.value = None;
+        }
+        result
+    }
+
+    fn good<V>(time: DateTimeUtc, value: V) -> DataValue
+    where
+        V: Into<Variant>,
+    {
+        Self::value_at(
+            time,
+            value,
+            StatusCode::Good | StatusCode::HISTORICAL_CALCULATED,
+        )
+    }
+
+    /// A raw value that is returned unchanged
+    fn raw(sample: &Sample) -> DataValue {
+        let mut result = sample.data_value.clone();
+        let time = DateTime::from(sample.time);
+        result.source_timestamp = Some(time);
+        result.server_timestamp = Some(time);
+        result
+    }
+
+    fn no_data(time: DateTimeUtc) -> DataValue {
+        let time = DateTime::from(time);
+        DataValue {
+            value: None,
+            status: Some(StatusCode::BadNoData),
+            source_timestamp: Some(time),
+            source_picoseconds: None,
+            server_timestamp: Some(time),
+            server_picoseconds: None,
+        }
+    }
+
+    fn value_at<V>(time: DateTimeUtc, value: V, status: StatusCode) -> DataValue
+    where
+        V: Into<Variant>,
+    {
+        let time = DateTime::from(time);
+        DataValue {
+            value: Some(value.into()),
+            status: Some(status),
+            source_timestamp: Some(time),
+            source_picoseconds: None,
+            server_timestamp: Some(time),
+            server_picoseconds: None,
+        }
+    }
+
+    fn millis(duration: Duration) -> f64 {
+        duration.num_microseconds().unwrap_or(i64::MAX) as f64 / 1000f64
+    }
+}
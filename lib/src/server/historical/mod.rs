@@ -10,6 +10,10 @@ use crate::types::*;
 
 use crate::server::address_space::AddressSpace;
 
+pub mod aggregates;
+
+use self::aggregates::AggregateCalculator;
+
 /// Values that should be set in the address space via `AddressSpace::set_history_server_capabilities()`
 /// to denote to clients what history capabilities the server has.
 pub struct HistoryServerCapabilities {
@@ -97,16 +101,95 @@ pub trait HistoricalDataProvider {
         Err(StatusCode::BadHistoryOperationUnsupported)
     }
 
+    /// The default implementation reads the raw values of each node through
+    /// `read_raw_modified_details()` and computes the requested aggregates from them. Implementors
+    /// with their own aggregation, or that can push it down into a database, may override it.
     fn read_processed_details(
         &self,
-        _address_space: Arc<RwLock<AddressSpace>>,
-        _request: ReadProcessedDetails,
-        _timestamps_to_return: TimestampsToReturn,
-        _release_continuation_points: bool,
-        _nodes_to_read: &[HistoryReadValueId],
+        address_space: Arc<RwLock<AddressSpace>>,
+        request: ReadProcessedDetails,
+        timestamps_to_return: TimestampsToReturn,
+        release_continuation_points: bool,
+        nodes_to_read: &[HistoryReadValueId],
     ) -> Result<Vec<HistoryReadResult>, StatusCode> {
-        info!("Unimplemented read_processed_details");
-        Err(StatusCode::BadHistoryOperationUnsupported)
+        // There must be one aggregate for each node
+        let aggregate_types = request.aggregate_type.as_deref().unwrap_or(&[]);
+        if aggregate_types.len() != nodes_to_read.len() {
+            return Err(StatusCode::BadAggregateListMismatch);
+        }
+        if request.start_time == request.end_time {
+            return Err(StatusCode::BadInvalidArgument);
+        }
+
+        // Read the raw values and their bounding values over the whole time range
+        let raw_request = ReadRawModifiedDetails {
+            is_read_modified: false,
+            start_time: request.start_time,
+            end_time: request.end_time,
+            num_values_per_node: 0,
+            return_bounds: true,
+        };
+        let raw_results = self.read_raw_modified_details(
+            address_space,
+            raw_request,
+            TimestampsToReturn::Both,
+            release_continuation_points,
+            nodes_to_read,
+        )?;
+        if raw_results.len() != nodes_to_read.len() {
+            return Err(StatusCode::BadUnexpectedError);
+        }
+
+        let decoding_options = DecodingOptions::default();
+        let results = raw_results
+            .into_iter()
+            .zip(aggregate_types)
+            .map(|(raw_result, aggregate_type)| {
+                if raw_result.status_code.is_bad() {
+                    return raw_result;
+                }
+                let processed = AggregateCalculator::new(
+                    aggregate_type,
+                    &request.aggregate_configuration,
+                    request.processing_interval,
+                )
+                .and_then(|calculator| {
+                    let raw_values = if raw_result.history_data.is_null() {
+                        Vec::new()
+                    } else {
+                        raw_result
+                            .history_data
+                            .decode_inner::<HistoryData>(&decoding_options)?
+                            .data_values
+                            .unwrap_or_default()
+                    };
+                    let mut data_values =
+                        calculator.process(&request.start_time, &request.end_time, &raw_values);
+                    data_values
+                        .iter_mut()
+                        .for_each(|v| v.strip_timestamps(timestamps_to_return));
+                    Ok(HistoryData {
+                        data_values: Some(data_values),
+                    })
+                });
+                match processed {
+                    Ok(history_data) => HistoryReadResult {
+                        status_code: StatusCode::Good,
+                        continuation_point: ByteString::null(),
+                        history_data: ExtensionObject::from_encodable(
+                            ObjectId::HistoryData_Encoding_DefaultBinary,
+                            &history_data,
+                        ),
+                    },
+                    Err(status_code) => HistoryReadResult {
+                        status_code,
+                        continuation_point: ByteString::null(),
+                        history_data: ExtensionObject::null(),
+                    },
+                }
+            })
+            .collect();
+        Ok(results)
     }
 
     fn read_at_time_details(
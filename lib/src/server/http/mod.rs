@@ -88,35 +88,40 @@ pub fn run_http_server(
     // code has to run on a single thread, but also async and through Tokio.
 
     let runtime_handle = runtime.handle().clone();
-    thread::spawn(move || {
-        info!(
-            "HTTP server is running on http://{}/ to provide OPC UA server metrics",
-            address
-        );
+    let spawn_result = thread::Builder::new()
+        .name("opcua-http".to_string())
+        .spawn(move || {
+            info!(
+                "HTTP server is running on http://{}/ to provide OPC UA server metrics",
+                address
+            );
 
-        let local = tokio::task::LocalSet::new();
-        local.spawn_local(async move {
-            // Spawns a new HTTP server
-            if let Ok(server) = HttpServer::new(move || {
-                App::new()
-                    .app_data(web::Data::new(AppState {
-                        server_state: server_state_http.clone(),
-                        connections: connections.clone(),
-                        server_metrics: server_metrics.clone(),
-                        base_path: Arc::new(RwLock::new(base_path.clone())),
-                    }))
-                    .route("/server/metrics", web::get().to(metrics))
-                    .route("/server/abort", web::get().to(abort))
-                    .route("/", web::get().to(index))
-            })
-            .bind(&address)
-            {
-                let _ = server.run().await;
-            } else {
-                error!("Could not start HTTP server");
-            }
+            let local = tokio::task::LocalSet::new();
+            local.spawn_local(async move {
+                // Spawns a new HTTP server
+                if let Ok(server) = HttpServer::new(move || {
+                    App::new()
+                        .app_data(web::Data::new(AppState {
+                            server_state: server_state_http.clone(),
+                            connections: connections.clone(),
+                            server_metrics: server_metrics.clone(),
+                            base_path: Arc::new(RwLock::new(base_path.clone())),
+                        }))
+                        .route("/server/metrics", web::get().to(metrics))
+                        .route("/server/abort", web::get().to(abort))
+                        .route("/", web::get().to(index))
+                })
+                .bind(&address)
+                {
+                    let _ = server.run().await;
+                } else {
+                    error!("Could not start HTTP server");
+                }
+            });
+            runtime_handle.block_on(local);
+            debug!("HTTP server has terminated");
         });
-        runtime_handle.block_on(local);
-        debug!("HTTP server has terminated");
-    });
+    if let Err(err) = spawn_result {
+        error!("Could not start HTTP server thread, error = {}", err);
+    }
 }
@@ -91,6 +91,7 @@ impl ServerMetrics {
                 pass: None,
                 x509: None,
                 thumbprint: None,
+                roles: Vec::new(),
             },
         );
         self.config = Some(config);
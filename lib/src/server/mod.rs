@@ -37,6 +37,7 @@ macro_rules! is_empty_option_vec {
 }
 
 mod identity_token;
+mod reload;
 mod services;
 
 #[cfg(feature = "discovery-server-registration")]
@@ -48,6 +49,7 @@ mod session_diagnostics;
 pub mod http;
 
 pub mod address_space;
+pub mod authenticator;
 pub mod builder;
 pub mod callbacks;
 pub mod comms;
@@ -58,6 +60,7 @@ pub mod diagnostics;
 pub mod events;
 pub mod historical;
 pub mod metrics;
+pub mod polling;
 pub mod server;
 pub mod session;
 pub mod state;
@@ -72,11 +75,15 @@ pub mod prelude {
     pub use crate::server::{
         address_space::types::*,
         address_space::{AccessLevel, EventNotifier, UserAccessLevel},
+        authenticator::*,
         builder::*,
         callbacks::*,
+        comms::tcp_transport::SecureChannelInfo,
         config::*,
         events::event::*,
+        events::model_change_event::*,
         historical::*,
+        polling::*,
         server::*,
         subscriptions::*,
         util::*,
@@ -92,6 +99,8 @@ pub mod constants {
     pub const DEFAULT_HELLO_TIMEOUT_SECONDS: u32 = 5;
     /// Default OPC UA server port for this implementation
     pub const DEFAULT_RUST_OPC_UA_SERVER_PORT: u16 = 4855;
+    /// Default maximum number of sessions across all connections of the server
+    pub const DEFAULT_MAX_SESSIONS: usize = 5;
     /// Default maximum number of subscriptions in a session
     pub const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
     /// Default maximum number of monitored items per subscription
@@ -105,7 +114,8 @@ pub mod constants {
     /// fine-grained this is, the more often subscriptions will be checked for changes. The minimum
     /// publish interval cannot be less than this.
     pub const SUBSCRIPTION_TIMER_RATE_MS: u64 = 100;
-    /// Minimum publishing interval for subscriptions
+    /// Minimum publishing interval for subscriptions in seconds. A server can be configured with
+    /// a larger minimum but not a smaller one.
     pub const MIN_PUBLISHING_INTERVAL: f64 = (SUBSCRIPTION_TIMER_RATE_MS as f64) / 1000.0;
     /// Minimum sampling interval on monitored items
     pub const MIN_SAMPLING_INTERVAL: f64 = (SUBSCRIPTION_TIMER_RATE_MS as f64) / 1000.0;
@@ -116,7 +126,10 @@ pub mod constants {
     /// Interval to check for HELLO timeout in millis. This can be fairly coarse because it's not
     /// something that requires huge accuracy.
     pub const HELLO_TIMEOUT_POLL_MS: u64 = 500;
-    /// Maximum time in MS that a session can be inactive before a timeout
+    /// Maximum publishing interval in millis. A client that asks for a longer interval has it
+    /// revised to this so that keep alives and the lifetime of the subscription remain bounded.
+    pub const MAX_PUBLISHING_INTERVAL_MS: f64 = 3_600_000f64;
+    /// Default maximum time in MS that a session can be inactive before a timeout
     pub const MAX_SESSION_TIMEOUT: f64 = 60000f64;
     /// Maximum size in bytes that a request message is allowed to be
     pub const MAX_REQUEST_MESSAGE_SIZE: u32 = 32768;
@@ -126,10 +139,17 @@ pub mod constants {
     pub const MAX_KEEP_ALIVE_COUNT: u32 = 30000;
     /// Maximum browse continuation points
     pub const MAX_BROWSE_CONTINUATION_POINTS: usize = 20;
+    /// Time in milliseconds that an unused browse continuation point is kept before it expires
+    pub const BROWSE_CONTINUATION_POINT_TIMEOUT_MS: i64 = 5 * 60 * 1000;
+    /// Time in milliseconds that a read waits for the values that getters have deferred, when the
+    /// request has no timeout hint
+    pub const PENDING_READ_TIMEOUT_MS: u64 = 10_000;
     /// Maximum history continuation points
     pub const MAX_HISTORY_CONTINUATION_POINTS: usize = 10;
     /// Maximum query continuation points
     pub const MAX_QUERY_CONTINUATION_POINTS: usize = 10;
+    /// Time in milliseconds that an unused query continuation point is kept before it expires
+    pub const QUERY_CONTINUATION_POINT_TIMEOUT_MS: i64 = 5 * 60 * 1000;
 
     /// Maximum number of nodes in a TranslateBrowsePathsToNodeIdsRequest
     pub const MAX_NODES_PER_TRANSLATE_BROWSE_PATHS_TO_NODE_IDS: usize = 10;
@@ -140,7 +160,9 @@ pub mod constants {
     pub const MAX_NODES_PER_REGISTER_NODES: usize = 10;
     /// Maximum number of nodes / references per node manaument operation
     pub const MAX_NODES_PER_NODE_MANAGEMENT: usize = 100;
-    pub const MAX_MONITORED_ITEMS_PER_CALL: usize = 10;
+    /// Maximum number of monitored items per monitored item service call, enough for the items of
+    /// a subscription to be created in one call
+    pub const MAX_MONITORED_ITEMS_PER_CALL: usize = DEFAULT_MAX_MONITORED_ITEMS_PER_SUB;
     pub const MAX_NODES_PER_HISTORY_READ_DATA: usize = 10;
     pub const MAX_NODES_PER_HISTORY_READ_EVENTS: usize = 10;
     pub const MAX_NODES_PER_HISTORY_UPDATE_DATA: usize = 10;
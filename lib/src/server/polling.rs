@@ -0,0 +1,125 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Bridges another protocol into the address space by polling it. A [`PollingProvider`] reads
+//! the tags of an external system, e.g. the registers of a Modbus device, and a [`PollingGroup`]
+//! pairs the tags with variables and writes what the provider read into them. A tag that could
+//! not be read keeps the last value of its variable but takes the bad status code the provider
+//! returned, so clients can tell a stale value from a good one.
+
+use crate::types::{
+    DateTime, NodeId, NumericRange, QualifiedName, StatusCode, TimestampsToReturn, Variant,
+};
+
+use crate::server::address_space::AddressSpace;
+
+/// A source of values from outside of the server, such as a field device spoken to over another
+/// protocol. The server calls it from a polling action, so it should not block for longer than
+/// its polling interval.
+pub trait PollingProvider: Send + 'static {
+    /// The address of a value in the external system, e.g. a register number
+    type Tag: Send + 'static;
+
+    /// Reads the values of the tags, returning one result per tag in the same order. A tag that
+    /// could not be read has the status code describing why, e.g. `BadCommunicationError` when
+    /// the device did not respond, or `BadOutOfRange` when the tag does not exist.
+    fn poll(&mut self, tags: &[Self::Tag]) -> Vec<Result<Variant, StatusCode>>;
+}
+
+/// The tags of a provider, each registered against the variable its value is written to.
+pub struct PollingGroup<P>
+where
+    P: PollingProvider,
+{
+    provider: P,
+    node_ids: Vec<NodeId>,
+    tags: Vec<P::Tag>,
+}
+
+impl<P> PollingGroup<P>
+where
+    P: PollingProvider,
+{
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            node_ids: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Registers a tag of the provider whose value is written to the variable with the node id.
+    pub fn tag<N>(mut self, node_id: N, tag: P::Tag) -> Self
+    where
+        N: Into<NodeId>,
+    {
+        self.add_tag(node_id, tag);
+        self
+    }
+
+    /// Registers a tag of the provider whose value is written to the variable with the node id.
+    pub fn add_tag<N>(&mut self, node_id: N, tag: P::Tag)
+    where
+        N: Into<NodeId>,
+    {
+        self.node_ids.push(node_id.into());
+        self.tags.push(tag);
+    }
+
+    /// The provider of the group.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// The provider of the group, e.g. to reconfigure its connection.
+    pub fn provider_mut(&mut self) -> &mut P {
+        &mut self.provider
+    }
+
+    /// Polls the provider once and writes the results to the variables. Returns the number of
+    /// tags that were read with a good status.
+    pub fn poll(&mut self, address_space: &mut AddressSpace) -> usize {
+        if self.tags.is_empty() {
+            return 0;
+        }
+        let mut results = self.provider.poll(&self.tags);
+        if results.len() != self.tags.len() {
+            error!(
+                "Polling provider returned {} results for {} tags",
+                results.len(),
+                self.tags.len()
+            );
+            results = vec![Err(StatusCode::BadInternalError); self.tags.len()];
+        }
+
+        let now = DateTime::now();
+        let mut good = 0;
+        for (node_id, result) in self.node_ids.iter().zip(results) {
+            let Some(variable) = address_space.find_variable_mut_by_ref(node_id) else {
+                debug!("Polled tag has no variable {}", node_id);
+                continue;
+            };
+            match result {
+                Ok(value) => {
+                    let _ = variable.set_value_direct(value, StatusCode::Good, &now, &now);
+                    good += 1;
+                }
+                Err(status_code) => {
+                    // The last value is kept, with the quality of the failed read
+                    let value = variable
+                        .value(
+                            TimestampsToReturn::Neither,
+                            NumericRange::None,
+                            &QualifiedName::null(),
+                            0.0,
+                        )
+                        .value
+                        .unwrap_or_default();
+                    let _ = variable.set_value_direct(value, status_code, &now, &now);
+                }
+            }
+        }
+        good
+    }
+}
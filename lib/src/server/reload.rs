@@ -0,0 +1,231 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Applies changes to the configuration and to the trust list of a server while it runs. Only
+//! the changes that are safe to make without restarting are applied, i.e. those to the user
+//! tokens, the limits and the certificate validation. Sessions that no longer satisfy the
+//! changed configuration are closed, so their clients must create and activate a new session.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use crate::core::config::Config;
+use crate::crypto::{CertificateStore, SecurityPolicy, X509};
+use crate::sync::*;
+use crate::types::{DateTime, NodeId};
+
+use crate::server::{
+    address_space::AddressSpace,
+    authenticator::ConfigUserAuthenticator,
+    config::{ReloadError, ServerConfig},
+    session::SessionManager,
+    state::ServerState,
+};
+
+/// Reloads the configuration of a running server. It holds the parts of the server that a reload
+/// changes so that it can be moved into a polling action.
+#[derive(Clone)]
+pub(crate) struct ConfigReloader {
+    pub server_state: Arc<RwLock<ServerState>>,
+    pub certificate_store: Arc<RwLock<CertificateStore>>,
+    pub session_manager: Arc<RwLock<SessionManager>>,
+    pub address_space: Arc<RwLock<AddressSpace>>,
+}
+
+impl ConfigReloader {
+    /// Applies the user tokens, limits and certificate validation of the config to the server
+    /// and closes the sessions whose user token was removed or changed. Other changes are
+    /// ignored with a warning because they only take effect when the server restarts. Returns
+    /// the ids of the sessions closed, or `ReloadError::Invalid` if the resulting config is invalid.
+    pub fn reload_config(&self, mut config: ServerConfig) -> Result<Vec<NodeId>, ReloadError> {
+        config.read_x509_thumbprints();
+
+        let changed_user_tokens = {
+            let mut server_state = trace_write_lock!(self.server_state);
+            let (reloaded, changed_user_tokens) = {
+                let current = trace_read_lock!(server_state.config);
+
+                // Anything else that differs needs a restart
+                let mut restart_config = config.clone();
+                restart_config.user_tokens = current.user_tokens.clone();
+                restart_config.limits = current.limits.clone();
+                restart_config.certificate_validation = current.certificate_validation.clone();
+                if restart_config != *current {
+                    warn!("Reloaded config has changes other than to the user tokens, limits and certificate validation, which only take effect when the server restarts");
+                }
+
+                let changed_user_tokens = current
+                    .user_tokens
+                    .iter()
+                    .filter(|(id, user_token)| config.user_tokens.get(*id) != Some(user_token))
+                    .map(|(id, _)| id.clone())
+                    .collect::<Vec<_>>();
+
+                let mut reloaded = current.clone();
+                reloaded.user_tokens = config.user_tokens;
+                reloaded.limits = config.limits;
+                reloaded.certificate_validation = config.certificate_validation;
+                (reloaded, changed_user_tokens)
+            };
+            if !reloaded.is_valid() {
+                error!("Reloaded config is invalid and has not been applied");
+                return Err(ReloadError::Invalid);
+            }
+
+            server_state.set_limits(&reloaded.limits);
+            trace_write_lock!(self.address_space)
+                .set_operational_limits(&reloaded.limits.operational, &DateTime::now());
+            if server_state.config_user_authenticator {
+                server_state.user_authenticator =
+                    Box::new(ConfigUserAuthenticator::new(&reloaded.user_tokens));
+            }
+            {
+                let mut certificate_store = trace_write_lock!(self.certificate_store);
+                certificate_store
+                    .set_trust_unknown_certs(reloaded.certificate_validation.trust_client_certs);
+                certificate_store.set_check_time(reloaded.certificate_validation.check_time);
+            }
+            let config_user_authenticator = server_state.config_user_authenticator;
+            *trace_write_lock!(server_state.config) = reloaded;
+            info!("Server config has been reloaded");
+
+            // A custom authenticator does not use the user tokens of the config
+            if config_user_authenticator {
+                changed_user_tokens
+            } else {
+                Vec::new()
+            }
+        };
+
+        let mut closed = self.close_sessions(|session_user_token_id, _| {
+            session_user_token_id.is_some_and(|id| changed_user_tokens.iter().any(|t| t == id))
+        });
+        closed.extend(self.revalidate_sessions());
+        Ok(closed)
+    }
+
+    /// Loads the config file at the path, applies the environment variables to it and reloads it
+    /// with `reload_config()`.
+    pub fn reload_file(&self, path: &Path) -> Result<Vec<NodeId>, ReloadError> {
+        let config: ServerConfig =
+            ServerConfig::load(path).map_err(|_| ReloadError::Load(path.to_path_buf()))?;
+        let config = config.with_env_overrides().map_err(ReloadError::Override)?;
+        self.reload_config(config)
+    }
+
+    /// Validates the certificate of every session's client against the trust list again, closing
+    /// the sessions whose certificate is no longer trusted, e.g. because it was moved from the
+    /// trusted to the rejected directory of the PKI. Returns the ids of the sessions closed.
+    pub fn revalidate_sessions(&self) -> Vec<NodeId> {
+        self.close_sessions(|_, client_certificate| {
+            if let Some((certificate, security_policy)) = client_certificate {
+                let certificate_store = trace_read_lock!(self.certificate_store);
+                certificate_store
+                    .validate_application_instance_cert(certificate, security_policy, None, None)
+                    .is_bad()
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Closes the sessions that the predicate is true for. The predicate is given the id of the
+    /// session's user token and its client certificate with the security policy it is used with.
+    fn close_sessions<F>(&self, should_close: F) -> Vec<NodeId>
+    where
+        F: Fn(Option<&str>, Option<(&X509, SecurityPolicy)>) -> bool,
+    {
+        // Sessions are not locked while the predicate runs
+        let sessions = {
+            let session_manager = trace_read_lock!(self.session_manager);
+            session_manager
+                .sessions
+                .iter()
+                .map(|(session_id, session)| {
+                    let session = trace_read_lock!(session);
+                    let security_policy = SecurityPolicy::from_uri(session.security_policy_uri());
+                    let client_certificate = if security_policy != SecurityPolicy::None {
+                        session.client_certificate().clone()
+                    } else {
+                        None
+                    };
+                    (
+                        session_id.clone(),
+                        session.user_token_id().map(String::from),
+                        client_certificate.map(|c| (c, security_policy)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let to_close = sessions
+            .into_iter()
+            .filter(|(_, user_token_id, client_certificate)| {
+                should_close(
+                    user_token_id.as_deref(),
+                    client_certificate.as_ref().map(|(c, p)| (c, *p)),
+                )
+            })
+            .map(|(session_id, _, _)| session_id)
+            .collect::<Vec<_>>();
+
+        let mut session_manager = trace_write_lock!(self.session_manager);
+        let mut closed = Vec::with_capacity(to_close.len());
+        for session_id in to_close {
+            if session_manager.terminate_session(&session_id, &self.address_space) {
+                warn!(
+                    "Session {} is closed because it is not valid for the reloaded config or trust list",
+                    session_id
+                );
+                closed.push(session_id);
+            }
+        }
+        closed
+    }
+
+    /// Returns a polling action that reloads the config from its file when the file is modified
+    /// and revalidates the sessions when the trusted or rejected directories of the PKI change.
+    pub fn watch(self, path: PathBuf) -> impl Fn() + Send + Sync + 'static {
+        let pki_dirs = {
+            let certificate_store = trace_read_lock!(self.certificate_store);
+            [
+                certificate_store.trusted_certs_dir(),
+                certificate_store.rejected_certs_dir(),
+            ]
+        };
+        let modified = Mutex::new((
+            Self::modified(&path),
+            pki_dirs
+                .iter()
+                .map(|d| Self::modified(d))
+                .collect::<Vec<_>>(),
+        ));
+        move || {
+            let mut modified = trace_lock!(modified);
+            let config_modified = Self::modified(&path);
+            if config_modified != modified.0 {
+                modified.0 = config_modified;
+                info!("Server config {} has changed, reloading it", path.display());
+                if let Err(err) = self.reload_file(&path) {
+                    error!("Server config has not been reloaded, {}", err);
+                }
+            }
+            let pki_modified = pki_dirs
+                .iter()
+                .map(|d| Self::modified(d))
+                .collect::<Vec<_>>();
+            if pki_modified != modified.1 {
+                modified.1 = pki_modified;
+                info!("Trust list has changed, revalidating sessions");
+                self.revalidate_sessions();
+            }
+        }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        path.metadata().and_then(|m| m.modified()).ok()
+    }
+}
@@ -4,7 +4,7 @@
 
 //! Provides the [`Server`] type and functionality related to it.
 
-use std::{marker::Sync, net::SocketAddr, sync::Arc};
+use std::{marker::Sync, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use tokio::{
     self,
@@ -13,23 +13,30 @@ use tokio::{
     time::{interval_at, Duration, Instant},
 };
 
-use crate::core::{config::Config, prelude::*};
+use crate::core::{comms::capture::MessageCapture, config::Config, prelude::*};
 use crate::crypto::*;
 use crate::sync::*;
 use crate::types::service_types::ServerState as ServerStateType;
 
+#[cfg(feature = "https")]
+use crate::server::comms::https::HttpsChannel;
+
 use crate::server::{
-    address_space::types::AddressSpace,
+    address_space::types::{AddressSpace, VariableBuilder},
+    authenticator::ConfigUserAuthenticator,
     comms::tcp_transport::*,
     comms::transport::Transport,
-    config::ServerConfig,
+    config::{ReloadError, ServerConfig},
     constants,
     diagnostics::ServerDiagnostics,
     events::audit::AuditLog,
     metrics::ServerMetrics,
+    polling::{PollingGroup, PollingProvider},
+    reload::ConfigReloader,
     session::SessionManager,
-    state::{OperationalLimits, ServerState},
-    util::PollingAction,
+    state::ServerState,
+    subscriptions::subscription::SubscriptionInfo,
+    util::{HeartbeatKind, PollingAction},
 };
 
 pub type Connections = Vec<Arc<RwLock<TcpTransport>>>;
@@ -100,12 +107,30 @@ impl Server {
         let application_uri = UAString::from(&config.application_uri);
         let product_uri = UAString::from(&config.product_uri);
         let start_time = DateTime::now();
+        let build_info = BuildInfo {
+            product_uri: product_uri.clone(),
+            manufacturer_name: config
+                .manufacturer_name
+                .as_ref()
+                .map(UAString::from)
+                .unwrap_or_default(),
+            product_name: UAString::from(&application_name),
+            software_version: UAString::from(env!("CARGO_PKG_VERSION")),
+            build_number: UAString::from(
+                config
+                    .build_number
+                    .as_deref()
+                    .unwrap_or(env!("CARGO_PKG_VERSION")),
+            ),
+            build_date: Self::build_date(),
+        };
         let servers = vec![config.application_uri.clone()];
         let base_endpoint = format!(
             "opc.tcp://{}:{}",
             config.tcp_config.host, config.tcp_config.port
         );
         let max_subscriptions = config.limits.max_subscriptions;
+        let max_server_subscriptions = config.limits.max_server_subscriptions;
         let max_monitored_items_per_sub = config.limits.max_monitored_items_per_sub;
         let max_monitored_item_queue_size = config.limits.max_monitored_item_queue_size;
 
@@ -144,6 +169,15 @@ impl Server {
         }
         certificate_store.set_check_time(config.certificate_validation.check_time);
 
+        let user_authenticator = Box::new(ConfigUserAuthenticator::new(&config.user_tokens));
+        let message_capture = config.capture_file.as_deref().and_then(|path| {
+            MessageCapture::open(path, config.capture_messages)
+                .map_err(|err| {
+                    error!("Cannot open capture file {}, {}", path.display(), err);
+                })
+                .ok()
+        });
+        let operational_limits = config.limits.operational.clone();
         let config = Arc::new(RwLock::new(config));
 
         // Set some values in the address space from the server state
@@ -160,6 +194,7 @@ impl Server {
             },
             servers,
             base_endpoint,
+            build_info,
             state: ServerStateType::Shutdown,
             start_time,
             config,
@@ -167,6 +202,7 @@ impl Server {
             server_pkey,
             last_subscription_id: 0,
             max_subscriptions,
+            max_server_subscriptions,
             max_monitored_items_per_sub,
             max_monitored_item_queue_size,
             min_publishing_interval_ms,
@@ -176,20 +212,47 @@ impl Server {
             max_lifetime_count: constants::MAX_KEEP_ALIVE_COUNT * 3,
             diagnostics,
             abort: false,
+            shutdown_at: None,
+            shutdown_reason: LocalizedText::null(),
             audit_log,
             register_nodes_callback: None,
             unregister_nodes_callback: None,
+            authorization_handler: None,
+            user_authenticator,
+            config_user_authenticator: true,
             historical_data_provider: None,
             historical_event_provider: None,
-            operational_limits: OperationalLimits::default(),
+            operational_limits,
+            message_capture,
             send_buffer_size,
             receive_buffer_size,
         };
         let server_state = Arc::new(RwLock::new(server_state));
 
+        let session_manager = Arc::new(RwLock::new(SessionManager::default()));
+
         {
             let mut address_space = trace_write_lock!(address_space);
             address_space.set_server_state(server_state.clone());
+            address_space.set_session_manager(session_manager.clone());
+
+            // Import the information models of any node sets
+            let server_state = trace_read_lock!(server_state);
+            let config = trace_read_lock!(server_state.config);
+            config.nodesets.iter().for_each(|nodeset| {
+                match address_space.import_nodeset_file(nodeset) {
+                    Ok(inserted) => info!(
+                        "Imported {} nodes from node set {}",
+                        inserted,
+                        nodeset.display()
+                    ),
+                    Err(status_code) => error!(
+                        "Cannot import node set {}, {}",
+                        nodeset.display(),
+                        status_code
+                    ),
+                }
+            });
         }
 
         // Server metrics
@@ -205,7 +268,7 @@ impl Server {
             address_space,
             certificate_store,
             connections: Arc::new(RwLock::new(Vec::new())),
-            session_manager: Arc::new(RwLock::new(SessionManager::default())),
+            session_manager,
         };
 
         let mut server_metrics = trace_write_lock!(server_metrics);
@@ -226,11 +289,14 @@ impl Server {
     /// Runs the supplied server and blocks until it completes either by aborting or
     /// by error.
     pub fn run_server(server: Arc<RwLock<Server>>) {
-        let single_threaded_executor = {
+        let (single_threaded_executor, thread_name) = {
             let server = trace_read_lock!(server);
             let server_state = trace_read_lock!(server.server_state);
             let config = trace_read_lock!(server_state.config);
-            config.performance.single_threaded_executor
+            (
+                config.performance.single_threaded_executor,
+                config.performance.thread_name.clone(),
+            )
         };
         let server_task = Self::new_server_task(server);
         // Launch
@@ -239,7 +305,11 @@ impl Server {
         } else {
             tokio::runtime::Builder::new_current_thread()
         };
-        let runtime = builder.enable_all().build().unwrap();
+        let runtime = builder
+            .thread_name(thread_name)
+            .enable_all()
+            .build()
+            .unwrap();
         Self::run_server_on_runtime(runtime, server_task, true);
     }
 
@@ -327,6 +397,9 @@ impl Server {
                 server_state.set_state(ServerStateType::Running);
             }
 
+            // Nodes and references that change from now on are reported to clients
+            trace_write_lock!(server.address_space).set_model_change_tracking(true);
+
             // Start a timer that registers the server with a discovery server
             if let Some(ref discovery_server_url) = discovery_server_url {
                 server.start_discovery_server_registration_timer(discovery_server_url);
@@ -372,6 +445,8 @@ impl Server {
                 // Help the rust type inferencer out
                 Ok::<_, tokio::io::Error>(())
             } => {}
+            _ = Self::websocket_server_task(server.clone()) => {}
+            _ = Self::https_server_task(server.clone()) => {}
             _ = rx_abort => {
                 info!("abort received");
             }
@@ -379,6 +454,270 @@ impl Server {
         info!("main server task is finished");
     }
 
+    /// Listens for opc.wss connections if the server is configured with a WebSocket port. The
+    /// task never finishes, it ends with the main server task.
+    #[cfg(feature = "websocket")]
+    async fn websocket_server_task(server: Arc<RwLock<Server>>) {
+        use crate::server::comms::{tls::tls_acceptor, websocket};
+
+        let websocket = {
+            let server = trace_read_lock!(server);
+            let server_state = trace_read_lock!(server.server_state);
+            let config = trace_read_lock!(server_state.config);
+            config.websocket_config.as_ref().map(|websocket_config| {
+                let acceptor = tls_acceptor(
+                    server_state.server_certificate.as_ref(),
+                    server_state.server_pkey.as_ref(),
+                );
+                (
+                    format!("{}:{}", config.tcp_config.host, websocket_config.port),
+                    acceptor,
+                    usize::max(
+                        server_state.send_buffer_size,
+                        server_state.receive_buffer_size,
+                    ),
+                )
+            })
+        };
+        let Some((address, acceptor, max_chunk_size)) = websocket else {
+            return futures::future::pending().await;
+        };
+        let acceptor = match acceptor {
+            Ok(acceptor) => Arc::new(acceptor),
+            Err(status_code) => {
+                error!(
+                    "Server cannot listen for opc.wss connections, {}",
+                    status_code
+                );
+                return futures::future::pending().await;
+            }
+        };
+        let listener = match TcpListener::bind(&address).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("Could not bind to opc.wss socket {}, {:?}", address, err);
+                return futures::future::pending().await;
+            }
+        };
+        info!("Waiting for opc.wss connections on {}", address);
+        loop {
+            match listener.accept().await {
+                Ok((socket, addr)) => {
+                    info!("Handling new opc.wss connection {:?}", socket);
+                    let is_abort = {
+                        let server = trace_read_lock!(server);
+                        let server_state = trace_read_lock!(server.server_state);
+                        server_state.is_abort()
+                    };
+                    if is_abort {
+                        info!("Server is aborting so it will not accept new connections");
+                        return futures::future::pending().await;
+                    }
+                    // The TLS and WebSocket handshakes happen away from the accept loop so a slow
+                    // client doesn't hold up others
+                    let server = server.clone();
+                    let acceptor = acceptor.clone();
+                    tokio::spawn(async move {
+                        match websocket::accept(&acceptor, socket, max_chunk_size).await {
+                            Ok(stream) => {
+                                let mut server = trace_write_lock!(server);
+                                server.handle_websocket_connection(stream, addr);
+                            }
+                            Err(status_code) => {
+                                error!(
+                                    "opc.wss connection from {} was rejected, {}",
+                                    addr, status_code
+                                );
+                            }
+                        }
+                    });
+                }
+                Err(e) => {
+                    error!("couldn't accept opc.wss connection to client: {:?}", e);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "websocket"))]
+    async fn websocket_server_task(server: Arc<RwLock<Server>>) {
+        let has_websocket_config = {
+            let server = trace_read_lock!(server);
+            let server_state = trace_read_lock!(server.server_state);
+            let config = trace_read_lock!(server_state.config);
+            config.websocket_config.is_some()
+        };
+        if has_websocket_config {
+            warn!("WebSocket config is ignored because the websocket feature is disabled");
+        }
+        futures::future::pending().await
+    }
+
+    /// Listens for https connections if the server is configured with an HTTPS port. The task
+    /// never finishes, it ends with the main server task.
+    #[cfg(feature = "https")]
+    async fn https_server_task(server: Arc<RwLock<Server>>) {
+        use crate::server::comms::{https, tls::tls_acceptor};
+
+        let https = {
+            let server = trace_read_lock!(server);
+            let server_state = trace_read_lock!(server.server_state);
+            let config = trace_read_lock!(server_state.config);
+            config.https_config.as_ref().map(|https_config| {
+                let acceptor = tls_acceptor(
+                    server_state.server_certificate.as_ref(),
+                    server_state.server_pkey.as_ref(),
+                );
+                // The channel to the transport is opened on the url of an endpoint that https
+                // offers, which is one without security
+                let endpoint_url = config.base_https_url().and_then(|base_https_url| {
+                    config
+                        .endpoints
+                        .values()
+                        .find(|e| {
+                            ServerConfig::is_endpoint_offered(
+                                e,
+                                profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY,
+                            )
+                        })
+                        .map(|e| e.endpoint_url(&base_https_url))
+                });
+                (
+                    format!("{}:{}", config.tcp_config.host, https_config.port),
+                    acceptor,
+                    endpoint_url,
+                    server_state.decoding_options(),
+                    usize::max(
+                        server_state.send_buffer_size,
+                        server_state.receive_buffer_size,
+                    ),
+                )
+            })
+        };
+        let Some((address, acceptor, endpoint_url, decoding_options, max_chunk_size)) = https
+        else {
+            return futures::future::pending().await;
+        };
+        let Some(endpoint_url) = endpoint_url else {
+            error!(
+                "Server cannot listen for https connections, it has no endpoint without security"
+            );
+            return futures::future::pending().await;
+        };
+        let acceptor = match acceptor {
+            Ok(acceptor) => Arc::new(acceptor),
+            Err(status_code) => {
+                error!(
+                    "Server cannot listen for https connections, {}",
+                    status_code
+                );
+                return futures::future::pending().await;
+            }
+        };
+        let listener = match TcpListener::bind(&address).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("Could not bind to https socket {}, {:?}", address, err);
+                return futures::future::pending().await;
+            }
+        };
+        info!("Waiting for https connections on {}", address);
+        // Every connection shares the channel to one transport, which is opened when the first
+        // request arrives and again if the transport closes
+        let channel = Arc::new(tokio::sync::Mutex::new(None));
+        loop {
+            match listener.accept().await {
+                Ok((socket, addr)) => {
+                    info!("Handling new https connection {:?}", socket);
+                    let is_abort = {
+                        let server = trace_read_lock!(server);
+                        let server_state = trace_read_lock!(server.server_state);
+                        server_state.is_abort()
+                    };
+                    if is_abort {
+                        info!("Server is aborting so it will not accept new connections");
+                        return futures::future::pending().await;
+                    }
+                    let server = server.clone();
+                    let acceptor = acceptor.clone();
+                    let channel = channel.clone();
+                    let endpoint_url = endpoint_url.clone();
+                    let decoding_options = decoding_options.clone();
+                    tokio::spawn(async move {
+                        let result = match https::accept(&acceptor, socket).await {
+                            Ok(stream) => {
+                                https::serve(stream, decoding_options.clone(), || {
+                                    Self::https_channel(
+                                        &server,
+                                        &channel,
+                                        &endpoint_url,
+                                        decoding_options.clone(),
+                                        max_chunk_size,
+                                    )
+                                })
+                                .await
+                            }
+                            Err(status_code) => Err(status_code),
+                        };
+                        if let Err(status_code) = result {
+                            error!("https connection from {} was closed, {}", addr, status_code);
+                        }
+                    });
+                }
+                Err(e) => {
+                    error!("couldn't accept https connection to client: {:?}", e);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "https"))]
+    async fn https_server_task(server: Arc<RwLock<Server>>) {
+        let has_https_config = {
+            let server = trace_read_lock!(server);
+            let server_state = trace_read_lock!(server.server_state);
+            let config = trace_read_lock!(server_state.config);
+            config.https_config.is_some()
+        };
+        if has_https_config {
+            warn!("HTTPS config is ignored because the https feature is disabled");
+        }
+        futures::future::pending().await
+    }
+
+    /// Returns the channel that https requests are passed to a transport on, opening a new
+    /// transport if there is none or it has closed.
+    #[cfg(feature = "https")]
+    async fn https_channel(
+        server: &Arc<RwLock<Server>>,
+        channel: &tokio::sync::Mutex<Option<HttpsChannel>>,
+        endpoint_url: &str,
+        decoding_options: DecodingOptions,
+        max_chunk_size: usize,
+    ) -> Result<HttpsChannel, StatusCode> {
+        let mut channel = channel.lock().await;
+        if let Some(channel) = channel.as_ref().filter(|channel| !channel.is_closed()) {
+            return Ok(channel.clone());
+        }
+        let (local, remote) = tokio::io::duplex(max_chunk_size);
+        {
+            let mut server = trace_write_lock!(server);
+            let (connection, looping_interval_ms) = server.add_connection();
+            let (reader, writer) = tokio::io::split(remote);
+            TcpTransport::run_stream(
+                connection,
+                Box::new(reader),
+                Box::new(writer),
+                None,
+                looping_interval_ms,
+            );
+        }
+        let new_channel =
+            HttpsChannel::open(local, endpoint_url, decoding_options, max_chunk_size).await?;
+        *channel = Some(new_channel.clone());
+        Ok(new_channel)
+    }
+
     /// Returns the current [`ServerState`] for the server.
     ///
     /// [`ServerState`]: ../state/struct.ServerState.html
@@ -405,6 +744,91 @@ impl Server {
         self.connections.clone()
     }
 
+    /// Returns a snapshot of every secure channel that is currently open on the server, i.e. the
+    /// peer address, security policy, token expiry and bytes transferred of each connection.
+    pub fn secure_channels(&self) -> Vec<SecureChannelInfo> {
+        Self::secure_channel_infos(&self.connections)
+    }
+
+    /// Returns a snapshot of every subscription on the server, in order of subscription id. The
+    /// monitored item handles are the same as the `GetMonitoredItems` method returns for each
+    /// subscription.
+    pub fn subscriptions(&self) -> Vec<SubscriptionInfo> {
+        let session_manager = trace_read_lock!(self.session_manager);
+        let mut subscriptions = session_manager
+            .sessions
+            .values()
+            .flat_map(|session| {
+                let session = trace_read_lock!(session);
+                session
+                    .subscriptions()
+                    .subscriptions()
+                    .values()
+                    .map(|subscription| subscription.info(session.session_id()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        subscriptions.sort_by_key(|subscription| subscription.subscription_id);
+        subscriptions
+    }
+
+    /// Forcibly closes the connection carrying the secure channel with the supplied id. Any
+    /// sessions on the connection are terminated. Returns `BadSecureChannelIdInvalid` if no
+    /// open connection has a secure channel with the id.
+    pub fn close_secure_channel(&self, secure_channel_id: u32) -> Result<(), StatusCode> {
+        let connections = trace_read_lock!(self.connections);
+        let transport = connections.iter().find(|transport| {
+            let transport = trace_read_lock!(transport);
+            !transport.is_finished()
+                && transport.secure_channel_info().secure_channel_id == secure_channel_id
+        });
+        if let Some(transport) = transport {
+            trace_read_lock!(transport).close();
+            Ok(())
+        } else {
+            Err(StatusCode::BadSecureChannelIdInvalid)
+        }
+    }
+
+    /// Periodically logs the secure channels open on the server at the supplied interval. This
+    /// may be useful for troubleshooting stuck connections.
+    pub fn add_secure_channel_logging(&mut self, interval_ms: u64) {
+        let connections = self.connections.clone();
+        self.add_polling_action(interval_ms, move || {
+            let secure_channels = Self::secure_channel_infos(&connections);
+            info!("Open secure channels: {}", secure_channels.len());
+            for c in secure_channels {
+                info!(
+                    "  Secure channel {} from {:?}, policy {}, mode {}, token {} expires {}, sent {} bytes, received {} bytes, {} session(s)",
+                    c.secure_channel_id,
+                    c.client_address,
+                    c.security_policy,
+                    c.security_mode,
+                    c.token_id,
+                    c.token_expires_at,
+                    c.bytes_sent,
+                    c.bytes_received,
+                    c.session_count
+                );
+            }
+        });
+    }
+
+    fn secure_channel_infos(connections: &RwLock<Connections>) -> Vec<SecureChannelInfo> {
+        let connections = trace_read_lock!(connections);
+        connections
+            .iter()
+            .filter_map(|transport| {
+                let transport = trace_read_lock!(transport);
+                if transport.is_finished() {
+                    None
+                } else {
+                    Some(transport.secure_channel_info())
+                }
+            })
+            .collect()
+    }
+
     /// Returns the [`ServerMetrics`] for the server.
     ///
     /// [`ServerMetrics`]: ../metrics/struct.ServerMetrics.html
@@ -427,19 +851,48 @@ impl Server {
         server_state.abort();
     }
 
+    /// Stops the server gracefully. The server goes into the shutdown state and counts down
+    /// `Server/ServerStatus/SecondsTillShutdown` so that clients monitoring it can disconnect. When
+    /// the countdown ends the server closes every session and aborts.
+    pub fn shutdown(&mut self, seconds_till_shutdown: u32, reason: LocalizedText) {
+        let mut server_state = trace_write_lock!(self.server_state);
+        server_state.request_shutdown(seconds_till_shutdown, reason);
+    }
+
+    /// The modification time of the executable, which is when it was built unless it has been
+    /// copied since.
+    fn build_date() -> DateTime {
+        std::env::current_exe()
+            .and_then(std::fs::metadata)
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| DateTime::from(chrono::DateTime::<chrono::Utc>::from(modified)))
+            .unwrap_or_else(|_| DateTime::null())
+    }
+
+    /// Closes every session, e.g. when a requested shutdown is due
+    fn close_sessions(&self) {
+        let mut session_manager = trace_write_lock!(self.session_manager);
+        if !session_manager.sessions.is_empty() {
+            info!(
+                "Server is shutting down, closing {} sessions",
+                session_manager.len()
+            );
+            session_manager.clear(self.address_space.clone());
+            session_manager.sessions_terminated = true;
+        }
+    }
+
     /// Strip out dead connections, i.e those which have disconnected. Returns `true` if there are
     /// still open connections after this function completes.
     fn remove_dead_connections(&self) -> bool {
         // Go through all connections, removing those that have terminated
         let mut connections = trace_write_lock!(self.connections);
         connections.retain(|transport| {
-            // Try to obtain the lock on the transport and the session and check if session is terminated
-            // if it is, then we'll use its termination status to sweep it out.
+            // Try to obtain the lock on the transport and check if it has finished, if it has,
+            // then sweep it out.
             let lock = transport.try_read();
             if let Some(ref transport) = lock {
-                let session_manager = transport.session_manager();
-                let session_manager = trace_read_lock!(session_manager);
-                !session_manager.sessions_terminated()
+                !transport.is_finished()
             } else {
                 true
             }
@@ -447,12 +900,30 @@ impl Server {
         !connections.is_empty()
     }
 
+    /// Closes the sessions that have gone without a service call for longer than their timeout
+    fn close_timed_out_sessions(&self) {
+        let mut session_manager = trace_write_lock!(self.session_manager);
+        let closed =
+            session_manager.close_timed_out_sessions(&chrono::Utc::now(), &self.address_space);
+        if !closed.is_empty() {
+            let server_state = trace_read_lock!(self.server_state);
+            let mut diagnostics = trace_write_lock!(server_state.diagnostics);
+            closed.iter().for_each(|_| diagnostics.on_session_timeout());
+        }
+    }
+
     /// Log information about the endpoints on this server
     fn log_endpoint_info(&self) {
         let server_state = trace_read_lock!(self.server_state);
         let config = trace_read_lock!(server_state.config);
         info!("OPC UA Server: {}", server_state.application_name);
         info!("Base url: {}", server_state.base_endpoint);
+        if let Some(base_websocket_url) = config.base_websocket_url() {
+            info!("WebSocket base url: {}", base_websocket_url);
+        }
+        if let Some(base_https_url) = config.base_https_url() {
+            info!("HTTPS base url: {}", base_https_url);
+        }
         info!("Supported endpoints:");
         for (id, endpoint) in &config.endpoints {
             let users: Vec<String> = endpoint.user_token_ids.iter().cloned().collect();
@@ -478,7 +949,9 @@ impl Server {
         }
     }
 
-    /// This timer will poll the server to see if it has aborted. It also cleans up dead connections.
+    /// This timer will poll the server to see if it has aborted. It also cleans up dead connections,
+    /// closes sessions that have timed out, rolls back writes that weren't confirmed in time and
+    /// reports changes to the address space in a model change event.
     /// If it determines to abort it will signal the tx_abort so that the main listener loop can
     /// be broken at its convenience.
     fn start_abort_poll(server: Arc<RwLock<Server>>, tx_abort: Sender<()>) {
@@ -489,8 +962,23 @@ impl Server {
                 // Check if there are any open sessions
                 {
                     let server = trace_read_lock!(server);
+                    server.close_timed_out_sessions();
                     let has_open_connections = server.remove_dead_connections();
-                    let server_state = trace_read_lock!(server.server_state);
+                    {
+                        let mut address_space = trace_write_lock!(server.address_space);
+                        let now = chrono::Utc::now();
+                        address_space.expire_pending_writes(&now);
+                        address_space.report_model_changes(&now);
+                    }
+                    let shutdown_due = trace_read_lock!(server.server_state)
+                        .is_shutdown_due(std::time::Instant::now());
+                    if shutdown_due {
+                        server.close_sessions();
+                    }
+                    let mut server_state = trace_write_lock!(server.server_state);
+                    if shutdown_due {
+                        server_state.abort();
+                    }
                     // Predicate breaks on abort & no open connections
                     if server_state.is_abort() {
                         if has_open_connections {
@@ -610,6 +1098,114 @@ impl Server {
         }
     }
 
+    /// Applies a changed configuration to the running server without restarting it. The user
+    /// tokens, limits and certificate validation of the config are applied, other changes only take
+    /// effect when the server restarts. Sessions whose user token was removed or changed, or whose
+    /// client certificate is no longer trusted, are closed. Returns the ids of the sessions that
+    /// were closed, or `ReloadError::Invalid` if the config is invalid, in which case nothing is
+    /// applied.
+    pub fn reload_config(&self, config: ServerConfig) -> Result<Vec<NodeId>, ReloadError> {
+        self.config_reloader().reload_config(config)
+    }
+
+    /// Validates the client certificates of the sessions against the trust list again, e.g.
+    /// after a certificate has been moved from the trusted to the rejected directory of the PKI,
+    /// and closes the sessions whose certificate is no longer trusted. Returns the ids of the
+    /// sessions that were closed.
+    pub fn revalidate_sessions(&self) -> Vec<NodeId> {
+        self.config_reloader().revalidate_sessions()
+    }
+
+    /// Watches the config file at the path and the trusted and rejected directories of the PKI,
+    /// checking them every `interval_ms`. When the file is modified, it is loaded, overridden by
+    /// any environment variables and applied with `reload_config()`. When the directories
+    /// change, the sessions are revalidated with `revalidate_sessions()`.
+    pub fn watch_config<T>(&mut self, path: T, interval_ms: u64)
+    where
+        T: Into<PathBuf>,
+    {
+        let action = self.config_reloader().watch(path.into());
+        self.add_polling_action(interval_ms, action);
+    }
+
+    fn config_reloader(&self) -> ConfigReloader {
+        ConfigReloader {
+            server_state: self.server_state.clone(),
+            certificate_store: self.certificate_store.clone(),
+            session_manager: self.session_manager.clone(),
+            address_space: self.address_space.clone(),
+        }
+    }
+
+    /// Adds a heartbeat variable to the address space that changes every `interval_ms` for as long
+    /// as the server runs. Clients can watch the variable to tell that the server is alive. The
+    /// variable is organized by the parent node. An existing variable with the node id is reused
+    /// as the heartbeat.
+    ///
+    /// Returns `false` if the node id belongs to a node that is not a variable, or the variable
+    /// could not be inserted.
+    pub fn add_heartbeat(
+        &mut self,
+        node_id: &NodeId,
+        browse_name: &str,
+        parent_node_id: &NodeId,
+        kind: HeartbeatKind,
+        interval_ms: u64,
+    ) -> bool {
+        {
+            let mut address_space = trace_write_lock!(self.address_space);
+            if address_space.find_variable_by_ref(node_id).is_none() {
+                if address_space.node_exists(node_id) {
+                    error!("Heartbeat node {} is not a variable", node_id);
+                    return false;
+                }
+                let inserted = VariableBuilder::new(node_id, browse_name, browse_name)
+                    .data_type(kind.data_type())
+                    .value(kind.initial_value())
+                    .organized_by(parent_node_id.clone())
+                    .insert(&mut address_space);
+                if !inserted {
+                    return false;
+                }
+            }
+        }
+        let address_space = self.address_space.clone();
+        let node_id = node_id.clone();
+        self.add_polling_action(interval_ms, move || {
+            let mut address_space = trace_write_lock!(address_space);
+            let value = address_space
+                .get_variable_value(node_id.clone())
+                .ok()
+                .and_then(|v| v.value)
+                .unwrap_or_default();
+            let now = DateTime::now();
+            let _ = address_space.set_variable_value(
+                node_id.clone(),
+                kind.next_value(&value),
+                &now,
+                &now,
+            );
+        });
+        true
+    }
+
+    /// Polls the provider of the group every `interval_ms` for as long as the server runs,
+    /// writing the values of its tags to their variables. The variables must already be in the
+    /// address space. A tag that could not be read keeps its last value with the bad status
+    /// code returned by the provider.
+    pub fn add_polling_provider<P>(&mut self, interval_ms: u64, group: PollingGroup<P>)
+    where
+        P: PollingProvider,
+    {
+        let address_space = self.address_space.clone();
+        let group = Mutex::new(group);
+        self.add_polling_action(interval_ms, move || {
+            let mut group = group.lock();
+            let mut address_space = trace_write_lock!(address_space);
+            group.poll(&mut address_space);
+        });
+    }
+
     /// Starts any polling actions which were queued ready to start but not yet
     fn start_pending_polling_actions(&mut self) {
         let server_state = self.server_state.clone();
@@ -640,7 +1236,32 @@ impl Server {
     /// Handles the incoming request
     fn handle_connection(&mut self, socket: TcpStream) {
         trace!("Connection thread spawning");
+        let (connection, looping_interval_ms) = self.add_connection();
+        // Run adds a session task to the tokio session
+        TcpTransport::run(connection, socket, looping_interval_ms);
+    }
 
+    /// Handles an incoming opc.wss connection once its handshakes are done
+    #[cfg(feature = "websocket")]
+    fn handle_websocket_connection(
+        &mut self,
+        stream: tokio::io::DuplexStream,
+        client_address: SocketAddr,
+    ) {
+        let (connection, looping_interval_ms) = self.add_connection();
+        let (reader, writer) = tokio::io::split(stream);
+        TcpTransport::run_stream(
+            connection,
+            Box::new(reader),
+            Box::new(writer),
+            Some(client_address),
+            looping_interval_ms,
+        );
+    }
+
+    /// Creates the transport of a new connection, returning it with the interval its session
+    /// loops at
+    fn add_connection(&mut self) -> (Arc<RwLock<TcpTransport>>, f64) {
         // Spawn a task for the connection
         let connection = Arc::new(RwLock::new(self.new_transport()));
         {
@@ -657,8 +1278,6 @@ impl Server {
                 server_state.min_sampling_interval_ms,
             )
         };
-
-        // Run adds a session task to the tokio session
-        TcpTransport::run(connection, socket, looping_interval_ms);
+        (connection, looping_interval_ms)
     }
 }
@@ -2,7 +2,16 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
-use std::{result::Result, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    result::Result,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::{
     core::supported_message::SupportedMessage,
@@ -12,11 +21,16 @@ use crate::{
 
 use crate::server::{
     address_space::{
+        base::Base,
         node::{HasNodeId, NodeBase, NodeType},
         variable::Variable,
         AddressSpace, UserAccessLevel,
     },
-    services::Service,
+    callbacks::DeferredValue,
+    comms::tcp_transport::MessageSender,
+    config::OperationalLimits,
+    constants,
+    services::{audit, Service},
     session::Session,
     state::ServerState,
 };
@@ -57,6 +71,9 @@ impl AttributeService {
     /// elements or to read ranges of elements of the composite. Servers may make historical
     /// values available to Clients using this Service, although the historical values themselves
     /// are not visible in the AddressSpace.
+    ///
+    /// Values that are deferred by their getters are not waited for.
+    #[cfg(test)]
     pub fn read(
         &self,
         server_state: Arc<RwLock<ServerState>>,
@@ -64,71 +81,183 @@ impl AttributeService {
         address_space: Arc<RwLock<AddressSpace>>,
         request: &ReadRequest,
     ) -> SupportedMessage {
+        match self.read_deferred(server_state, session, address_space, request) {
+            Ok(results) => {
+                let results = results
+                    .into_iter()
+                    .map(|result| Self::take_value(result, request.timestamps_to_return))
+                    .collect();
+                Self::read_response(&request.request_header, results)
+            }
+            Err(response) => response,
+        }
+    }
+
+    /// Reads the attributes of one or more nodes. A request with values that their getters have
+    /// deferred is parked until they complete, or until the timeout hint of the request passes. The
+    /// response to a parked request is sent later with the sender, so nothing is returned for it.
+    pub fn async_read(
+        &self,
+        server_state: Arc<RwLock<ServerState>>,
+        session: Arc<RwLock<Session>>,
+        address_space: Arc<RwLock<AddressSpace>>,
+        request_id: u32,
+        request: &ReadRequest,
+        sender: &MessageSender,
+    ) -> Option<SupportedMessage> {
+        let results = match self.read_deferred(server_state, session, address_space, request) {
+            Ok(results) => results,
+            Err(response) => return Some(response),
+        };
+        if results
+            .iter()
+            .all(|result| matches!(result, DeferredValue::Ready(_)))
+        {
+            let results = results
+                .into_iter()
+                .map(|result| Self::take_value(result, request.timestamps_to_return))
+                .collect();
+            return Some(Self::read_response(&request.request_header, results));
+        }
+
+        let timeout_ms = match request.request_header.timeout_hint {
+            0 => constants::PENDING_READ_TIMEOUT_MS,
+            timeout_hint => timeout_hint as u64,
+        };
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+        debug!(
+            "Read request {} is parked until its pending values complete",
+            request.request_header.request_handle
+        );
+        let request_header = request.request_header.clone();
+        let timestamps_to_return = request.timestamps_to_return;
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            // Every value was requested when the getters were called, so they complete in any
+            // order while this waits on each in turn
+            let mut data_values = Vec::with_capacity(results.len());
+            for result in results {
+                data_values.push(match result {
+                    DeferredValue::Ready(data_value) => data_value,
+                    DeferredValue::Pending(pending_value) => {
+                        let mut data_value = pending_value.wait(deadline).await;
+                        data_value.strip_timestamps(timestamps_to_return);
+                        data_value
+                    }
+                });
+            }
+            sender.send_message(
+                request_id,
+                Self::read_response(&request_header, data_values),
+            );
+        });
+        None
+    }
+
+    /// Reads the nodes of the request, whose values may be deferred, or fails with a service fault.
+    fn read_deferred(
+        &self,
+        server_state: Arc<RwLock<ServerState>>,
+        session: Arc<RwLock<Session>>,
+        address_space: Arc<RwLock<AddressSpace>>,
+        request: &ReadRequest,
+    ) -> Result<Vec<DeferredValue>, SupportedMessage> {
         if is_empty_option_vec!(request.nodes_to_read) {
-            self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+            Err(self.service_fault(&request.request_header, StatusCode::BadNothingToDo))
         } else if request.max_age < 0f64 {
             // Negative values are invalid for max_age
             warn!("ReadRequest max age is invalid");
-            self.service_fault(&request.request_header, StatusCode::BadMaxAgeInvalid)
+            Err(self.service_fault(&request.request_header, StatusCode::BadMaxAgeInvalid))
         } else if request.timestamps_to_return == TimestampsToReturn::Invalid {
             warn!("ReadRequest invalid timestamps to return");
-            self.service_fault(
+            Err(self.service_fault(
                 &request.request_header,
                 StatusCode::BadTimestampsToReturnInvalid,
-            )
+            ))
         } else {
             let server_state = trace_read_lock!(server_state);
             let nodes_to_read = request.nodes_to_read.as_ref().unwrap();
-            if nodes_to_read.len() <= server_state.operational_limits.max_nodes_per_read {
+            if OperationalLimits::allows(
+                server_state.operational_limits.max_nodes_per_read,
+                nodes_to_read.len(),
+            ) {
+                let max_concurrent_reads = {
+                    let config = trace_read_lock!(server_state.config);
+                    config.performance.max_concurrent_reads
+                };
                 // Read nodes and their attributes
                 let session = trace_read_lock!(session);
                 let address_space = trace_read_lock!(address_space);
-                let timestamps_to_return = request.timestamps_to_return;
-                let results = nodes_to_read
-                    .iter()
-                    .map(|node_to_read| {
-                        Self::read_node_value(
-                            &session,
-                            &address_space,
-                            node_to_read,
-                            request.max_age,
-                            timestamps_to_return,
-                        )
-                    })
-                    .collect();
-
-                let diagnostic_infos = None;
-                let response = ReadResponse {
-                    response_header: ResponseHeader::new_good(&request.request_header),
-                    results: Some(results),
-                    diagnostic_infos,
-                };
-                response.into()
+                Ok(Self::read_node_values(
+                    &session,
+                    &address_space,
+                    nodes_to_read,
+                    request.max_age,
+                    request.timestamps_to_return,
+                    max_concurrent_reads,
+                ))
             } else {
                 warn!("ReadRequest too many nodes to read {}", nodes_to_read.len());
-                self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
+                Err(self.service_fault(&request.request_header, StatusCode::BadTooManyOperations))
             }
         }
     }
 
+    /// Takes the value of a read, which is `BadWaitingForInitialData` if it is still pending.
+    fn take_value(result: DeferredValue, timestamps_to_return: TimestampsToReturn) -> DataValue {
+        match result {
+            DeferredValue::Ready(data_value) => data_value,
+            DeferredValue::Pending(pending_value) => {
+                let mut data_value = pending_value.try_take();
+                data_value.strip_timestamps(timestamps_to_return);
+                data_value
+            }
+        }
+    }
+
+    fn read_response(request_header: &RequestHeader, results: Vec<DataValue>) -> SupportedMessage {
+        let diagnostic_infos = None;
+        let response = ReadResponse {
+            response_header: ResponseHeader::new_good(request_header),
+            results: Some(results),
+            diagnostic_infos,
+        };
+        response.into()
+    }
+
     /// Used to read historical values
     pub fn history_read(
         &self,
         server_state: Arc<RwLock<ServerState>>,
-        _session: Arc<RwLock<Session>>,
+        session: Arc<RwLock<Session>>,
         address_space: Arc<RwLock<AddressSpace>>,
         request: &HistoryReadRequest,
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.nodes_to_read) {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
         } else {
-            let decoding_options = {
+            let (decoding_options, max_nodes_per_history_read) = {
                 let server_state = trace_read_lock!(server_state);
-                server_state.decoding_options()
+                let limits = &server_state.operational_limits;
+                let read_events =
+                    Self::node_id_to_historical_read_action(&request.history_read_details.node_id)
+                        == Ok(ObjectId::ReadEventDetails_Encoding_DefaultBinary);
+                let max_nodes_per_history_read = if read_events {
+                    limits.max_nodes_per_history_read_events
+                } else {
+                    limits.max_nodes_per_history_read_data
+                };
+                (server_state.decoding_options(), max_nodes_per_history_read)
             };
+            let nodes_to_read = request.nodes_to_read.as_ref().unwrap();
+            if !OperationalLimits::allows(max_nodes_per_history_read, nodes_to_read.len()) {
+                return self
+                    .service_fault(&request.request_header, StatusCode::BadTooManyOperations);
+            }
             match Self::do_history_read_details(
                 &decoding_options,
                 server_state,
+                session,
                 address_space,
                 request,
             ) {
@@ -161,18 +290,53 @@ impl AttributeService {
             debug!("Empty list passed to write {:?}", request);
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
         } else {
-            // TODO audit - generate AuditWriteUpdateEventType event
             let server_state = trace_read_lock!(server_state);
             let session = trace_read_lock!(session);
-            let mut address_space = trace_write_lock!(address_space);
 
             let nodes_to_write = request.nodes_to_write.as_ref().unwrap();
-            if nodes_to_write.len() <= server_state.operational_limits.max_nodes_per_write {
-                let results = nodes_to_write
-                    .iter()
-                    .map(|node_to_write| {
-                        Self::write_node_value(&session, &mut address_space, node_to_write)
-                    })
+            if OperationalLimits::allows(
+                server_state.operational_limits.max_nodes_per_write,
+                nodes_to_write.len(),
+            ) {
+                let auditing = server_state.is_auditing();
+                let writes = {
+                    let mut address_space = trace_write_lock!(address_space);
+                    nodes_to_write
+                        .iter()
+                        .map(|node_to_write| {
+                            // The value being replaced is only needed for auditing
+                            let old_value = if auditing {
+                                Self::value_before_write(&address_space, node_to_write)
+                            } else {
+                                Variant::Empty
+                            };
+                            let status_code =
+                                Self::write_node_value(&session, &mut address_space, node_to_write);
+                            (status_code, old_value)
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                // Audit events are added to the address space so it must be unlocked first
+                if auditing {
+                    for (node_to_write, (status_code, old_value)) in
+                        nodes_to_write.iter().zip(writes.iter())
+                    {
+                        audit::log_write_update(
+                            &server_state,
+                            &session,
+                            address_space.clone(),
+                            &request.request_header,
+                            node_to_write,
+                            old_value.clone(),
+                            *status_code,
+                        );
+                    }
+                }
+
+                let results = writes
+                    .into_iter()
+                    .map(|(status_code, _)| status_code)
                     .collect();
 
                 let diagnostic_infos = None;
@@ -204,11 +368,36 @@ impl AttributeService {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
         } else {
             // TODO audit - generate AuditHistoryUpdateEventType event
+            let history_update_details = request.history_update_details.as_ref().unwrap();
             let decoding_options = {
                 let server_state = trace_read_lock!(server_state);
+                // Updates of events and of data have separate limits
+                let event_updates = history_update_details
+                    .iter()
+                    .filter(|u| {
+                        Self::node_id_to_action(
+                            &u.node_id,
+                            &[
+                                ObjectId::UpdateEventDetails_Encoding_DefaultBinary,
+                                ObjectId::DeleteEventDetails_Encoding_DefaultBinary,
+                            ],
+                        )
+                        .is_ok()
+                    })
+                    .count();
+                let limits = &server_state.operational_limits;
+                if !OperationalLimits::allows(
+                    limits.max_nodes_per_history_update_events,
+                    event_updates,
+                ) || !OperationalLimits::allows(
+                    limits.max_nodes_per_history_update_data,
+                    history_update_details.len() - event_updates,
+                ) {
+                    return self
+                        .service_fault(&request.request_header, StatusCode::BadTooManyOperations);
+                }
                 server_state.decoding_options()
             };
-            let history_update_details = request.history_update_details.as_ref().unwrap();
             let results = history_update_details
                 .iter()
                 .map(|u| {
@@ -419,18 +608,57 @@ impl AttributeService {
     fn do_history_read_details(
         decoding_options: &DecodingOptions,
         server_state: Arc<RwLock<ServerState>>,
+        session: Arc<RwLock<Session>>,
         address_space: Arc<RwLock<AddressSpace>>,
         request: &HistoryReadRequest,
     ) -> Result<Vec<HistoryReadResult>, StatusCode> {
-        // TODO enforce operation limits
-
         // Validate the action being performed
-        let nodes_to_read = &request.nodes_to_read.as_ref().unwrap();
         let timestamps_to_return = request.timestamps_to_return;
         let release_continuation_points = request.release_continuation_points;
         let read_details =
             Self::decode_history_read_details(&request.history_read_details, decoding_options)?;
 
+        // The history of a variable can only be read if its access levels allow it. Only the
+        // permitted nodes are passed to the provider.
+        let denied = {
+            let session = trace_read_lock!(session);
+            let address_space = trace_read_lock!(address_space);
+            request
+                .nodes_to_read
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|node_to_read| {
+                    address_space
+                        .find_node(&node_to_read.node_id)
+                        .and_then(|node| {
+                            Self::value_access_denied(
+                                &session,
+                                node,
+                                AttributeId::Value,
+                                UserAccessLevel::HISTORY_READ,
+                            )
+                        })
+                })
+                .collect::<Vec<_>>()
+        };
+        let nodes_to_read = request
+            .nodes_to_read
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(&denied)
+            .filter(|(_, denied)| denied.is_none())
+            .map(|(node_to_read, _)| node_to_read.clone())
+            .collect::<Vec<_>>();
+        if nodes_to_read.is_empty() {
+            return Ok(denied
+                .into_iter()
+                .map(|status_code| Self::history_read_error(status_code.unwrap()))
+                .collect());
+        }
+        let nodes_to_read = &nodes_to_read;
+
         let server_state = trace_read_lock!(server_state);
         let results = match read_details {
             ReadDetails::ReadEventDetails(details) => {
@@ -486,7 +714,26 @@ impl AttributeService {
                 )?
             }
         };
-        Ok(results)
+
+        // Put the results of the denied nodes back in their place
+        let mut results = results.into_iter();
+        Ok(denied
+            .into_iter()
+            .map(|denied| match denied {
+                Some(status_code) => Self::history_read_error(status_code),
+                None => results
+                    .next()
+                    .unwrap_or_else(|| Self::history_read_error(StatusCode::BadInternalError)),
+            })
+            .collect())
+    }
+
+    fn history_read_error(status_code: StatusCode) -> HistoryReadResult {
+        HistoryReadResult {
+            status_code,
+            continuation_point: ByteString::null(),
+            history_data: ExtensionObject::null(),
+        }
     }
 
     fn is_supported_data_encoding(data_encoding: &QualifiedName) -> bool {
@@ -497,13 +744,81 @@ impl AttributeService {
         }
     }
 
+    /// Reads the nodes, returning their values in request order. If concurrent reads are allowed
+    /// and the nodes are in more than one namespace, the namespaces are read on separate threads
+    /// so that a namespace whose values are slow to get does not hold up the others. No more than
+    /// `max_concurrent_reads` namespaces are read at once.
+    fn read_node_values(
+        session: &Session,
+        address_space: &AddressSpace,
+        nodes_to_read: &[ReadValueId],
+        max_age: f64,
+        timestamps_to_return: TimestampsToReturn,
+        max_concurrent_reads: usize,
+    ) -> Vec<DeferredValue> {
+        let read = |node_to_read: &ReadValueId| {
+            Self::read_node_value(
+                session,
+                address_space,
+                node_to_read,
+                max_age,
+                timestamps_to_return,
+            )
+        };
+
+        // The indices of the nodes to read in each namespace
+        let mut namespaces: BTreeMap<u16, Vec<usize>> = BTreeMap::new();
+        nodes_to_read
+            .iter()
+            .enumerate()
+            .for_each(|(i, node_to_read)| {
+                namespaces
+                    .entry(node_to_read.node_id.namespace)
+                    .or_default()
+                    .push(i);
+            });
+        if max_concurrent_reads <= 1 || namespaces.len() <= 1 {
+            return nodes_to_read.iter().map(read).collect();
+        }
+
+        // Each worker takes the next namespace that nobody is reading until there are none left
+        let namespaces = namespaces.into_values().collect::<Vec<_>>();
+        let next_namespace = AtomicUsize::new(0);
+        let mut results = Vec::new();
+        results.resize_with(nodes_to_read.len(), || {
+            DeferredValue::Ready(DataValue::default())
+        });
+        thread::scope(|scope| {
+            let workers = (0..max_concurrent_reads.min(namespaces.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut values = Vec::new();
+                        while let Some(indices) =
+                            namespaces.get(next_namespace.fetch_add(1, Ordering::Relaxed))
+                        {
+                            values.extend(indices.iter().map(|i| (*i, read(&nodes_to_read[*i]))));
+                        }
+                        values
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers.into_iter().for_each(|worker| {
+                let values = worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                values.into_iter().for_each(|(i, value)| results[i] = value);
+            });
+        });
+        results
+    }
+
     fn read_node_value(
         session: &Session,
         address_space: &AddressSpace,
         node_to_read: &ReadValueId,
         max_age: f64,
         timestamps_to_return: TimestampsToReturn,
-    ) -> DataValue {
+    ) -> DeferredValue {
         // Node node found
         // debug!("read_node_value asked to read node id {}, attribute {}", node_to_read.node_id, node_to_read.attribute_id);
         let mut result_value = DataValue::null();
@@ -517,24 +832,39 @@ impl AttributeService {
                 {
                     Ok(index_range) => index_range,
                     Err(err) => {
-                        return DataValue {
+                        return DeferredValue::Ready(DataValue {
                             value: None,
                             status: Some(err),
                             source_timestamp: None,
                             source_picoseconds: None,
                             server_timestamp: None,
                             server_picoseconds: None,
-                        };
+                        });
                     }
                 };
 
-                if !Self::is_readable(session, node, attribute_id) {
+                if !session
+                    .permissions(node.as_node())
+                    .contains(Self::read_permission(attribute_id))
+                {
+                    // The roles of the user are not permitted to read this attribute
+                    debug!(
+                        "read_node_value result for read node id {}, attribute {} is denied",
+                        node_to_read.node_id, node_to_read.attribute_id
+                    );
+                    result_value.status = Some(StatusCode::BadUserAccessDenied);
+                } else if let Some(status_code) = Self::value_access_denied(
+                    session,
+                    node,
+                    attribute_id,
+                    UserAccessLevel::CURRENT_READ,
+                ) {
                     // Can't read this node
                     debug!(
                         "read_node_value result for read node id {}, attribute {} is unreadable",
                         node_to_read.node_id, node_to_read.attribute_id
                     );
-                    result_value.status = Some(StatusCode::BadNotReadable);
+                    result_value.status = Some(status_code);
                 } else if attribute_id != AttributeId::Value && index_range != NumericRange::None {
                     // Can't supply an index range on a non-Value attribute
                     debug!(
@@ -546,34 +876,71 @@ impl AttributeService {
                     // Caller must request binary
                     debug!("read_node_value result for read node id {}, attribute {} is invalid data encoding", node_to_read.node_id, node_to_read.attribute_id);
                     result_value.status = Some(StatusCode::BadDataEncodingInvalid);
-                } else if let Some(attribute) = node.as_node().get_attribute_max_age(
-                    timestamps_to_return,
-                    attribute_id,
-                    index_range,
-                    &node_to_read.data_encoding,
-                    max_age,
-                ) {
-                    // If caller was reading the user access level, this needs to be modified to
-                    // take account of the effective level based on who is logged in.
-                    let value = if attribute_id == AttributeId::UserAccessLevel {
-                        if let Some(value) = attribute.value {
-                            if let Variant::Byte(value) = value {
-                                // The bits from the node are further modified by the session
-                                let user_access_level = UserAccessLevel::from_bits_truncate(value);
-                                let user_access_level = session.effective_user_access_level(
-                                    user_access_level,
-                                    &node.node_id(),
-                                    attribute_id,
-                                );
-                                Some(Variant::from(user_access_level.bits()))
-                            } else {
-                                Some(value)
+                } else if let Some(attribute) = match (node, attribute_id) {
+                    // The getter of a variable's value may defer it
+                    (NodeType::Variable(variable), AttributeId::Value) => {
+                        match variable.value_deferred(
+                            timestamps_to_return,
+                            index_range,
+                            &node_to_read.data_encoding,
+                            max_age,
+                        ) {
+                            DeferredValue::Ready(data_value) => Some(data_value),
+                            DeferredValue::Pending(pending_value) => {
+                                return DeferredValue::Pending(pending_value)
                             }
-                        } else {
-                            None
                         }
-                    } else {
-                        attribute.value.clone()
+                    }
+                    _ => node.as_node().get_attribute_max_age(
+                        timestamps_to_return,
+                        attribute_id,
+                        index_range,
+                        &node_to_read.data_encoding,
+                        max_age,
+                    ),
+                } {
+                    // If caller was reading one of the user attributes, this needs to be modified
+                    // to take account of the effective value based on who is logged in.
+                    let node_id = node.node_id();
+                    let value = match (attribute_id, attribute.value.clone()) {
+                        (AttributeId::UserAccessLevel, Some(Variant::Byte(value))) => {
+                            // The bits from the node are further modified by the session
+                            let user_access_level = UserAccessLevel::from_bits_truncate(value);
+                            let user_access_level = session.effective_user_access_level(
+                                user_access_level,
+                                &node_id,
+                                attribute_id,
+                            );
+                            let user_access_level =
+                                Self::permitted_access_level(session, node, user_access_level);
+                            Some(Variant::from(user_access_level.bits()))
+                        }
+                        (AttributeId::UserWriteMask, Some(Variant::UInt32(value))) => {
+                            let user_write_mask = WriteMask::from_bits_truncate(value);
+                            let user_write_mask =
+                                session.effective_user_write_mask(user_write_mask, &node_id);
+                            Some(Variant::from(user_write_mask.bits()))
+                        }
+                        (AttributeId::UserExecutable, Some(Variant::Boolean(value))) => {
+                            Some(Variant::from(
+                                session.effective_user_executable(value, &node_id)
+                                    && session
+                                        .permissions(node.as_node())
+                                        .contains(PermissionType::Call),
+                            ))
+                        }
+                        (AttributeId::UserRolePermissions, Some(_)) => {
+                            // Only the permissions of the user's own roles
+                            node.as_node().role_permissions().map(|role_permissions| {
+                                let role_permissions = role_permissions
+                                    .iter()
+                                    .filter(|p| session.roles().contains(&p.role_id))
+                                    .cloned()
+                                    .collect::<Vec<_>>();
+                                Base::role_permissions_to_variant(&role_permissions)
+                            })
+                        }
+                        (_, value) => value,
                     };
 
                     // Result value is clone from the attribute
@@ -589,25 +956,11 @@ impl AttributeService {
                     // Timestamps to return only applies to variable value
                     if let NodeType::Variable(_) = node {
                         if attribute_id == AttributeId::Value {
-                            match timestamps_to_return {
-                                TimestampsToReturn::Source => {
-                                    result_value.source_timestamp = attribute.source_timestamp;
-                                    result_value.source_picoseconds = attribute.source_picoseconds;
-                                }
-                                TimestampsToReturn::Server => {
-                                    result_value.server_timestamp = attribute.server_timestamp;
-                                    result_value.server_picoseconds = attribute.server_picoseconds;
-                                }
-                                TimestampsToReturn::Both => {
-                                    result_value.source_timestamp = attribute.source_timestamp;
-                                    result_value.source_picoseconds = attribute.source_picoseconds;
-                                    result_value.server_timestamp = attribute.server_timestamp;
-                                    result_value.server_picoseconds = attribute.server_picoseconds;
-                                }
-                                TimestampsToReturn::Neither | TimestampsToReturn::Invalid => {
-                                    // Nothing needs to change
-                                }
-                            }
+                            result_value.source_timestamp = attribute.source_timestamp;
+                            result_value.source_picoseconds = attribute.source_picoseconds;
+                            result_value.server_timestamp = attribute.server_timestamp;
+                            result_value.server_picoseconds = attribute.server_picoseconds;
+                            result_value.strip_timestamps(timestamps_to_return);
                         }
                     }
                 } else {
@@ -631,7 +984,7 @@ impl AttributeService {
             );
             result_value.status = Some(StatusCode::BadNodeIdUnknown);
         }
-        result_value
+        DeferredValue::Ready(result_value)
     }
 
     fn user_access_level(
@@ -647,69 +1000,184 @@ impl AttributeService {
         session.effective_user_access_level(user_access_level, &node.node_id(), attribute_id)
     }
 
+    /// Removes the access that the roles of the session's user are not permitted from the access
+    /// level
+    fn permitted_access_level(
+        session: &Session,
+        node: &NodeType,
+        mut user_access_level: UserAccessLevel,
+    ) -> UserAccessLevel {
+        let permissions = session.permissions(node.as_node());
+        if !permissions.contains(PermissionType::Read) {
+            user_access_level.remove(UserAccessLevel::CURRENT_READ);
+        }
+        if !permissions.contains(PermissionType::Write) {
+            user_access_level.remove(UserAccessLevel::CURRENT_WRITE);
+        }
+        if !permissions.contains(PermissionType::ReadHistory) {
+            user_access_level.remove(UserAccessLevel::HISTORY_READ);
+        }
+        if !permissions.intersects(
+            PermissionType::InsertHistory
+                | PermissionType::ModifyHistory
+                | PermissionType::DeleteHistory,
+        ) {
+            user_access_level.remove(UserAccessLevel::HISTORY_WRITE);
+        }
+        user_access_level
+    }
+
+    /// The permission a role needs to read the attribute
+    fn read_permission(attribute_id: AttributeId) -> PermissionType {
+        match attribute_id {
+            AttributeId::Value => PermissionType::Read,
+            AttributeId::RolePermissions => PermissionType::ReadRolePermissions,
+            _ => PermissionType::Browse,
+        }
+    }
+
+    /// The permission a role needs to write the attribute
+    fn write_permission(attribute_id: AttributeId) -> PermissionType {
+        match attribute_id {
+            AttributeId::Value => PermissionType::Write,
+            AttributeId::RolePermissions => PermissionType::WriteRolePermissions,
+            AttributeId::Historizing => PermissionType::WriteHistorizing,
+            _ => PermissionType::WriteAttribute,
+        }
+    }
+
+    /// Tests if the user of the session may read the attribute of the node, which other services
+    /// that return attribute values must check too
+    #[cfg(feature = "query-services")]
+    pub(crate) fn is_permitted_read(
+        session: &Session,
+        node: &NodeType,
+        attribute_id: AttributeId,
+    ) -> bool {
+        session
+            .permissions(node.as_node())
+            .contains(Self::read_permission(attribute_id))
+            && Self::is_readable(session, node, attribute_id)
+    }
+
+    #[cfg(feature = "query-services")]
     fn is_readable(session: &Session, node: &NodeType, attribute_id: AttributeId) -> bool {
-        // TODO session for current user
-        // Check for access level, user access level
-        Self::user_access_level(session, node, attribute_id).contains(UserAccessLevel::CURRENT_READ)
+        Self::value_access_denied(session, node, attribute_id, UserAccessLevel::CURRENT_READ)
+            .is_none()
     }
 
-    fn is_writable(session: &Session, node: &NodeType, attribute_id: AttributeId) -> bool {
-        // TODO session for current user
-        // For a variable, the access level controls access to the variable
+    /// Checks an access to the value of a variable against the `AccessLevel` of the variable and
+    /// the `UserAccessLevel` of the session's user. An access the variable does not allow at all
+    /// is `BadNotReadable` or `BadNotWritable`, one the user is not given is `BadUserAccessDenied`.
+    /// Other attributes and nodes are not governed by the access levels.
+    fn value_access_denied(
+        session: &Session,
+        node: &NodeType,
+        attribute_id: AttributeId,
+        access: UserAccessLevel,
+    ) -> Option<StatusCode> {
+        let NodeType::Variable(variable) = node else {
+            return None;
+        };
+        if attribute_id != AttributeId::Value {
+            None
+        } else if !UserAccessLevel::from_bits_truncate(variable.access_level().bits())
+            .contains(access)
+        {
+            if access.intersects(UserAccessLevel::CURRENT_READ | UserAccessLevel::HISTORY_READ) {
+                Some(StatusCode::BadNotReadable)
+            } else {
+                Some(StatusCode::BadNotWritable)
+            }
+        } else if !Self::user_access_level(session, node, attribute_id).contains(access) {
+            Some(StatusCode::BadUserAccessDenied)
+        } else {
+            None
+        }
+    }
+
+    /// Checks a write of an attribute. The value of a variable is governed by its access levels,
+    /// any other attribute by the `WriteMask` of the node, which is further narrowed for the
+    /// session's user by the `UserWriteMask` of the node. An attribute the node does not allow
+    /// to be written at all is `BadNotWritable`, one the user is not given is `BadUserAccessDenied`.
+    fn write_denied(
+        session: &Session,
+        node: &NodeType,
+        attribute_id: AttributeId,
+    ) -> Option<StatusCode> {
         if let NodeType::Variable(_) = node {
             if attribute_id == AttributeId::Value {
-                return Self::user_access_level(session, node, attribute_id)
-                    .contains(UserAccessLevel::CURRENT_WRITE);
+                return Self::value_access_denied(
+                    session,
+                    node,
+                    attribute_id,
+                    UserAccessLevel::CURRENT_WRITE,
+                );
             }
         }
 
-        if let Some(write_mask) = node.as_node().write_mask() {
-            match attribute_id {
-                AttributeId::Value => {
-                    if let NodeType::VariableType(_) = node {
-                        write_mask.contains(WriteMask::VALUE_FOR_VARIABLE_TYPE)
-                    } else {
-                        false
-                    }
-                }
-                AttributeId::NodeId => write_mask.contains(WriteMask::NODE_ID),
-                AttributeId::NodeClass => write_mask.contains(WriteMask::NODE_CLASS),
-                AttributeId::BrowseName => write_mask.contains(WriteMask::BROWSE_NAME),
-                AttributeId::DisplayName => write_mask.contains(WriteMask::DISPLAY_NAME),
-                AttributeId::Description => write_mask.contains(WriteMask::DESCRIPTION),
-                AttributeId::WriteMask => write_mask.contains(WriteMask::WRITE_MASK),
-                AttributeId::UserWriteMask => write_mask.contains(WriteMask::USER_WRITE_MASK),
-                AttributeId::IsAbstract => write_mask.contains(WriteMask::IS_ABSTRACT),
-                AttributeId::Symmetric => write_mask.contains(WriteMask::SYMMETRIC),
-                AttributeId::InverseName => write_mask.contains(WriteMask::INVERSE_NAME),
-                AttributeId::ContainsNoLoops => write_mask.contains(WriteMask::CONTAINS_NO_LOOPS),
-                AttributeId::EventNotifier => write_mask.contains(WriteMask::EVENT_NOTIFIER),
-                AttributeId::DataType => write_mask.contains(WriteMask::DATA_TYPE),
-                AttributeId::ValueRank => write_mask.contains(WriteMask::VALUE_RANK),
-                AttributeId::ArrayDimensions => write_mask.contains(WriteMask::ARRAY_DIMENSIONS),
-                AttributeId::AccessLevel => write_mask.contains(WriteMask::ACCESS_LEVEL),
-                AttributeId::UserAccessLevel => write_mask.contains(WriteMask::USER_ACCESS_LEVEL),
-                AttributeId::MinimumSamplingInterval => {
-                    write_mask.contains(WriteMask::MINIMUM_SAMPLING_INTERVAL)
-                }
-                AttributeId::Historizing => write_mask.contains(WriteMask::HISTORIZING),
-                AttributeId::Executable => write_mask.contains(WriteMask::EXECUTABLE),
-                AttributeId::UserExecutable => write_mask.contains(WriteMask::USER_EXECUTABLE),
-                AttributeId::DataTypeDefinition => {
-                    write_mask.contains(WriteMask::DATA_TYPE_DEFINITION)
-                }
-                AttributeId::RolePermissions => write_mask.contains(WriteMask::ROLE_PERMISSIONS),
-                AttributeId::AccessRestrictions => {
-                    write_mask.contains(WriteMask::ACCESS_RESTRICTIONS)
-                }
-                AttributeId::AccessLevelEx => write_mask.contains(WriteMask::ACCESS_LEVEL_EX),
-                AttributeId::UserRolePermissions => false, // Reserved
-            }
+        let Some(write_mask) = node.as_node().write_mask() else {
+            return Some(StatusCode::BadNotWritable);
+        };
+        let Some(required) = Self::write_mask_bit(node, attribute_id) else {
+            return Some(StatusCode::BadNotWritable);
+        };
+        if !write_mask.contains(required) {
+            return Some(StatusCode::BadNotWritable);
+        }
+        // A node without a UserWriteMask gives every user its WriteMask
+        let user_write_mask = node
+            .as_node()
+            .user_write_mask()
+            .map_or(write_mask, |user_write_mask| user_write_mask & write_mask);
+        let user_write_mask = session.effective_user_write_mask(user_write_mask, &node.node_id());
+        if !user_write_mask.contains(required) {
+            Some(StatusCode::BadUserAccessDenied)
         } else {
-            false
+            None
         }
     }
 
+    /// The bit of the write mask that allows the attribute of the node to be written, if any.
+    fn write_mask_bit(node: &NodeType, attribute_id: AttributeId) -> Option<WriteMask> {
+        let bit = match attribute_id {
+            AttributeId::Value => {
+                if let NodeType::VariableType(_) = node {
+                    WriteMask::VALUE_FOR_VARIABLE_TYPE
+                } else {
+                    return None;
+                }
+            }
+            AttributeId::NodeId => WriteMask::NODE_ID,
+            AttributeId::NodeClass => WriteMask::NODE_CLASS,
+            AttributeId::BrowseName => WriteMask::BROWSE_NAME,
+            AttributeId::DisplayName => WriteMask::DISPLAY_NAME,
+            AttributeId::Description => WriteMask::DESCRIPTION,
+            AttributeId::WriteMask => WriteMask::WRITE_MASK,
+            AttributeId::UserWriteMask => WriteMask::USER_WRITE_MASK,
+            AttributeId::IsAbstract => WriteMask::IS_ABSTRACT,
+            AttributeId::Symmetric => WriteMask::SYMMETRIC,
+            AttributeId::InverseName => WriteMask::INVERSE_NAME,
+            AttributeId::ContainsNoLoops => WriteMask::CONTAINS_NO_LOOPS,
+            AttributeId::EventNotifier => WriteMask::EVENT_NOTIFIER,
+            AttributeId::DataType => WriteMask::DATA_TYPE,
+            AttributeId::ValueRank => WriteMask::VALUE_RANK,
+            AttributeId::ArrayDimensions => WriteMask::ARRAY_DIMENSIONS,
+            AttributeId::AccessLevel => WriteMask::ACCESS_LEVEL,
+            AttributeId::UserAccessLevel => WriteMask::USER_ACCESS_LEVEL,
+            AttributeId::MinimumSamplingInterval => WriteMask::MINIMUM_SAMPLING_INTERVAL,
+            AttributeId::Historizing => WriteMask::HISTORIZING,
+            AttributeId::Executable => WriteMask::EXECUTABLE,
+            AttributeId::UserExecutable => WriteMask::USER_EXECUTABLE,
+            AttributeId::DataTypeDefinition => WriteMask::DATA_TYPE_DEFINITION,
+            AttributeId::RolePermissions => WriteMask::ROLE_PERMISSIONS,
+            AttributeId::AccessRestrictions => WriteMask::ACCESS_RESTRICTIONS,
+            AttributeId::AccessLevelEx => WriteMask::ACCESS_LEVEL_EX,
+            AttributeId::UserRolePermissions => return None, // Reserved
+        };
+        Some(bit)
+    }
+
     /*
     fn is_history_readable(session: &Session, node: &NodeType) -> bool {
         Self::user_access_level(session, node, AttributeId::Value).contains(UserAccessLevel::HISTORY_READ)
@@ -769,6 +1237,64 @@ impl AttributeService {
         valid
     }
 
+    /// Determine if the value matches the value rank and array dimensions of a Variable node
+    fn validate_value_rank_to_write(variable: &Variable, value: &Variant) -> bool {
+        let value_rank = match value {
+            Variant::Empty => return true,
+            value if !value.is_valid() => return false,
+            // A byte string may be written to a byte array
+            Variant::ByteString(_) if variable.data_type() == DataTypeId::Byte.into() => 1,
+            value => value.value_rank(),
+        };
+        let valid = match variable.value_rank() {
+            -3 => value_rank == -1 || value_rank == 1,
+            -2 => true,
+            -1 => value_rank == -1,
+            0 => value_rank >= 1,
+            n => value_rank == n,
+        };
+        // Array dimensions are the maximum length of each dimension, or 0 for any length
+        let valid = valid
+            && match (variable.array_dimensions(), value.array_dimensions()) {
+                (Some(max_dimensions), Some(dimensions))
+                    if max_dimensions.len() == dimensions.len() =>
+                {
+                    max_dimensions
+                        .iter()
+                        .zip(dimensions.iter())
+                        .all(|(max, d)| *max == 0 || d <= max)
+                }
+                _ => true,
+            };
+        if !valid {
+            debug!("Variable value validation did not pass, check value {:?} against var {} value rank of {} and array dimensions {:?}", value, variable.node_id(), variable.value_rank(), variable.array_dimensions());
+        }
+        valid
+    }
+
+    /// Reads the value of the attribute that a write is about to replace, or an empty value if
+    /// there is none
+    fn value_before_write(address_space: &AddressSpace, node_to_write: &WriteValue) -> Variant {
+        let index_range = node_to_write
+            .index_range
+            .as_ref()
+            .parse::<NumericRange>()
+            .unwrap_or(NumericRange::None);
+        address_space
+            .find_node(&node_to_write.node_id)
+            .zip(AttributeId::from_u32(node_to_write.attribute_id).ok())
+            .and_then(|(node, attribute_id)| {
+                node.as_node().get_attribute(
+                    TimestampsToReturn::Neither,
+                    attribute_id,
+                    index_range,
+                    &QualifiedName::null(),
+                )
+            })
+            .and_then(|data_value| data_value.value)
+            .unwrap_or(Variant::Empty)
+    }
+
     fn write_node_value(
         session: &Session,
         address_space: &mut AddressSpace,
@@ -778,8 +1304,13 @@ impl AttributeService {
             if let Ok(attribute_id) = AttributeId::from_u32(node_to_write.attribute_id) {
                 let index_range = node_to_write.index_range.as_ref().parse::<NumericRange>();
 
-                if !Self::is_writable(session, node, attribute_id) {
-                    StatusCode::BadNotWritable
+                if !session
+                    .permissions(node.as_node())
+                    .contains(Self::write_permission(attribute_id))
+                {
+                    StatusCode::BadUserAccessDenied
+                } else if let Some(status_code) = Self::write_denied(session, node, attribute_id) {
+                    status_code
                 } else if attribute_id != AttributeId::Value && !node_to_write.index_range.is_null()
                 {
                     // Index ranges are not supported on anything other than a value attribute
@@ -802,7 +1333,11 @@ impl AttributeService {
                     let data_type_valid = if attribute_id == AttributeId::Value {
                         match node {
                             NodeType::Variable(ref variable) => {
+                                // A range is written into the existing value so it is only the
+                                // whole value that must match the value rank
                                 Self::validate_value_to_write(address_space, variable, value)
+                                    && (index_range.has_range()
+                                        || Self::validate_value_rank_to_write(variable, value))
                             }
                             _ => true, // Other types don't have this attr but they will reject later during set
                         }
@@ -812,6 +1347,19 @@ impl AttributeService {
                     if !data_type_valid {
                         error!("Data type of value is invalid for writing to attribute");
                         StatusCode::BadTypeMismatch
+                    } else if attribute_id == AttributeId::Value
+                        && address_space.is_confirmed_write(&node_to_write.node_id)
+                    {
+                        if index_range.has_range() {
+                            error!("Writes that must be confirmed cannot have an index range");
+                            StatusCode::BadWriteNotSupported
+                        } else {
+                            address_space.write_confirmed_value(
+                                session.session_id(),
+                                &node_to_write.node_id,
+                                value,
+                            )
+                        }
                     } else {
                         let node = address_space.find_node_mut(&node_to_write.node_id).unwrap();
                         let result = if attribute_id == AttributeId::Value {
@@ -10,7 +10,7 @@ use crate::types::{status_code::StatusCode, *};
 use crate::server::prelude::SecureChannel;
 use crate::server::{
     address_space::address_space::AddressSpace,
-    events::audit::{certificate_events::*, session_events::*},
+    events::audit::{certificate_events::*, session_events::*, update_events::*},
     session::Session,
     state::ServerState,
 };
@@ -125,26 +125,115 @@ pub fn log_close_session(
     let _ = server_state.raise_and_log(event);
 }
 
+/// Raises an event warning that a session will soon be closed because it is idle
+pub fn log_idle_session_warning(
+    server_state: &ServerState,
+    session: &Session,
+    address_space: Arc<RwLock<AddressSpace>>,
+) {
+    let node_id = next_node_id(address_space);
+    let now = DateTime::now();
+
+    let event = AuditSessionEventType::new_idle_session_warning(node_id, now)
+        .status(true)
+        .client_user_id(session.client_user_id())
+        .session_id(session.session_id().clone());
+
+    let _ = server_state.raise_and_log(event);
+}
+
+/// Raises an event that the server closed a session because it was idle
+pub fn log_idle_session_timeout(
+    server_state: &ServerState,
+    session: &Session,
+    address_space: Arc<RwLock<AddressSpace>>,
+) {
+    let node_id = next_node_id(address_space);
+    let now = DateTime::now();
+
+    let event =
+        AuditSessionEventType::new_close_session(node_id, now, AuditCloseSessionReason::Timeout)
+            .status(true)
+            .client_user_id(session.client_user_id())
+            .session_id(session.session_id().clone());
+
+    let _ = server_state.raise_and_log(event);
+}
+
 pub fn log_certificate_error(
     server_state: &ServerState,
     address_space: Arc<RwLock<AddressSpace>>,
     status_code: StatusCode,
+    certificate: &ByteString,
     request_header: &RequestHeader,
 ) {
     let node_id = next_node_id(address_space);
     let now = DateTime::now();
 
     match status_code.status() {
-        StatusCode::BadCertificateTimeInvalid => {
+        StatusCode::BadCertificateTimeInvalid | StatusCode::BadCertificateIssuerTimeInvalid => {
             let event = AuditCertificateExpiredEventType::new(node_id, now)
+                .certificate(certificate.clone())
+                .client_audit_entry_id(request_header.audit_entry_id.clone());
+            let _ = server_state.raise_and_log(event);
+        }
+        StatusCode::BadCertificateUntrusted => {
+            let event = AuditCertificateUntrustedEventType::new(node_id, now)
+                .certificate(certificate.clone())
+                .client_audit_entry_id(request_header.audit_entry_id.clone());
+            let _ = server_state.raise_and_log(event);
+        }
+        StatusCode::BadCertificateRevoked
+        | StatusCode::BadCertificateIssuerRevoked
+        | StatusCode::BadCertificateRevocationUnknown
+        | StatusCode::BadCertificateIssuerRevocationUnknown => {
+            let event = AuditCertificateRevokedEventType::new(node_id, now)
+                .certificate(certificate.clone())
+                .client_audit_entry_id(request_header.audit_entry_id.clone());
+            let _ = server_state.raise_and_log(event);
+        }
+        StatusCode::BadCertificateUriInvalid
+        | StatusCode::BadCertificateHostNameInvalid
+        | StatusCode::BadCertificateUseNotAllowed
+        | StatusCode::BadCertificateIssuerUseNotAllowed => {
+            let event = AuditCertificateMismatchEventType::new(node_id, now)
+                .certificate(certificate.clone())
                 .client_audit_entry_id(request_header.audit_entry_id.clone());
             let _ = server_state.raise_and_log(event);
         }
         _ => {
             // TODO client_id
             let event = AuditCertificateInvalidEventType::new(node_id, now)
+                .certificate(certificate.clone())
                 .client_audit_entry_id(request_header.audit_entry_id.clone());
             let _ = server_state.raise_and_log(event);
         }
     };
 }
+
+/// Raises an event that a client wrote to an attribute of a node, successfully or not
+pub fn log_write_update(
+    server_state: &ServerState,
+    session: &Session,
+    address_space: Arc<RwLock<AddressSpace>>,
+    request_header: &RequestHeader,
+    node_to_write: &WriteValue,
+    old_value: Variant,
+    status_code: StatusCode,
+) {
+    let node_id = next_node_id(address_space);
+    let now = DateTime::now();
+
+    let event = AuditWriteUpdateEventType::new(node_id, now)
+        .status(status_code.is_good())
+        .client_user_id(session.client_user_id())
+        .client_audit_entry_id(request_header.audit_entry_id.clone())
+        .attribute_id(node_to_write.attribute_id)
+        .index_range(node_to_write.index_range.clone())
+        .old_value(old_value)
+        .new_value(node_to_write.value.value.clone().unwrap_or(Variant::Empty))
+        .source_node(node_to_write.node_id.clone())
+        .source_name("Attribute/Write");
+
+    let _ = server_state.raise_and_log(event);
+}
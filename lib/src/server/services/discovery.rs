@@ -33,12 +33,13 @@ impl DiscoveryService {
     ) -> SupportedMessage {
         let server_state = trace_read_lock!(server_state);
 
-        // TODO some of the arguments in the request are ignored
-        //  localeIds - list of locales to use for human readable strings (in the endpoint descriptions)
-
         // TODO audit - generate event for failed service invocation
 
-        let endpoints = server_state.endpoints(&request.endpoint_url, &request.profile_uris);
+        let endpoints = server_state.endpoints(
+            &request.endpoint_url,
+            &request.profile_uris,
+            &request.locale_ids,
+        );
         GetEndpointsResponse {
             response_header: ResponseHeader::new_good(&request.request_header),
             endpoints,
@@ -71,7 +72,10 @@ impl DiscoveryService {
 
         let application_description = {
             let config = trace_read_lock!(server_state.config);
-            config.application_description()
+            ApplicationDescription {
+                application_name: config.localized_application_name(&request.locale_ids),
+                ..config.application_description()
+            }
         };
 
         // Fields within the request
@@ -80,8 +84,6 @@ impl DiscoveryService {
 
         // TODO endpoint URL
 
-        // TODO localeids, filter out servers that do not support locale ids
-
         // Filter servers that do not have a matching application uri
         if let Some(ref server_uris) = request.server_uris {
             if !server_uris.is_empty() {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use chrono::Utc;
 
@@ -12,14 +12,17 @@ use crate::crypto::CertificateStore;
 use crate::sync::*;
 use crate::types::{status_code::StatusCode, *};
 
+#[cfg(feature = "node-management-services")]
+use crate::server::services::node_management::NodeManagementService;
+#[cfg(feature = "query-services")]
+use crate::server::services::query::QueryService;
 use crate::server::{
     address_space::AddressSpace,
     comms::tcp_transport::MessageSender,
     services::{
         attribute::AttributeService, discovery::DiscoveryService, method::MethodService,
-        monitored_item::MonitoredItemService, node_management::NodeManagementService,
-        query::QueryService, session::SessionService, subscription::SubscriptionService,
-        view::ViewService,
+        monitored_item::MonitoredItemService, session::SessionService,
+        subscription::SubscriptionService, view::ViewService,
     },
     session::{Session, SessionManager},
     session_diagnostics::*,
@@ -43,12 +46,14 @@ pub(crate) struct MessageHandler {
     /// Discovery service
     discovery_service: DiscoveryService,
     /// Node Management service
+    #[cfg(feature = "node-management-services")]
     node_management_service: NodeManagementService,
     /// Method service
     method_service: MethodService,
     /// MonitoredItem service
     monitored_item_service: MonitoredItemService,
     /// Query service
+    #[cfg(feature = "query-services")]
     query_service: QueryService,
     /// Session service
     session_service: SessionService,
@@ -56,6 +61,8 @@ pub(crate) struct MessageHandler {
     subscription_service: SubscriptionService,
     /// View service
     view_service: ViewService,
+    /// Time the message being handled was received
+    received_at: DateTimeUtc,
 }
 
 impl MessageHandler {
@@ -76,20 +83,27 @@ impl MessageHandler {
             discovery_service: DiscoveryService::new(),
             method_service: MethodService::new(),
             monitored_item_service: MonitoredItemService::new(),
+            #[cfg(feature = "node-management-services")]
             node_management_service: NodeManagementService::new(),
+            #[cfg(feature = "query-services")]
             query_service: QueryService::new(),
             session_service: SessionService::new(),
             view_service: ViewService::new(),
             subscription_service: SubscriptionService::new(),
+            received_at: Utc::now(),
         }
     }
 
+    /// Handles the message, which the server received at `received_at`, and sends its response.
     pub fn handle_message(
         &mut self,
         request_id: u32,
         message: &SupportedMessage,
+        received_at: DateTimeUtc,
         sender: &MessageSender,
     ) -> Result<(), StatusCode> {
+        self.received_at = received_at;
+
         // Note the order of arguments for all these services is the order that they must be locked in,
         //
         // 1. ServerState
@@ -99,6 +113,17 @@ impl MessageHandler {
         let server_state = self.server_state.clone();
         let address_space = self.address_space.clone();
 
+        // The session id is recorded once the request's session is found
+        let span = tracing::info_span!(
+            "service_request",
+            service = message.name(),
+            request_handle = message.request_handle(),
+            request_id,
+            session_id = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+        let started = Instant::now();
+
         let response = match message {
             // Discovery Service Set, OPC UA Part 4, Section 5.4
             SupportedMessage::GetEndpointsRequest(request) => {
@@ -123,10 +148,22 @@ impl MessageHandler {
             SupportedMessage::CreateSessionRequest(request) => {
                 let mut session_manager = trace_write_lock!(self.session_manager);
 
-                // TODO this is completely arbitrary - 5 sessions total in a single connection
-                pub(crate) const MAX_SESSIONS_PER_TRANSPORT: usize = 5;
+                let max_sessions = {
+                    let server_state = trace_read_lock!(server_state);
+                    let config = trace_read_lock!(server_state.config);
+                    config.limits.max_sessions
+                };
 
-                let response = if session_manager.len() >= MAX_SESSIONS_PER_TRANSPORT {
+                let response = if max_sessions > 0 && session_manager.len() >= max_sessions {
+                    warn!(
+                        "Create session rejected, the server already has {} sessions",
+                        session_manager.len()
+                    );
+                    {
+                        let server_state = trace_read_lock!(server_state);
+                        let mut diagnostics = trace_write_lock!(server_state.diagnostics);
+                        diagnostics.on_rejected_session();
+                    }
                     ServiceFault::new(&request.request_header, StatusCode::BadTooManySessions)
                         .into()
                 } else {
@@ -177,6 +214,7 @@ impl MessageHandler {
             }
 
             // NodeManagement Service Set, OPC UA Part 4, Section 5.7
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddNodesRequest(request) => {
                 self.validate_service_request(message, ADD_NODES_COUNT, |session, _| {
                     Some(self.node_management_service.add_nodes(
@@ -188,6 +226,7 @@ impl MessageHandler {
                 })
             }
 
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::AddReferencesRequest(request) => {
                 self.validate_service_request(message, ADD_REFERENCES_COUNT, |session, _| {
                     Some(self.node_management_service.add_references(
@@ -199,6 +238,7 @@ impl MessageHandler {
                 })
             }
 
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteNodesRequest(request) => {
                 self.validate_service_request(message, DELETE_NODES_COUNT, |session, _| {
                     Some(self.node_management_service.delete_nodes(
@@ -210,6 +250,7 @@ impl MessageHandler {
                 })
             }
 
+            #[cfg(feature = "node-management-services")]
             SupportedMessage::DeleteReferencesRequest(request) => {
                 self.validate_service_request(message, DELETE_REFERENCES_COUNT, |session, _| {
                     Some(self.node_management_service.delete_references(
@@ -268,6 +309,7 @@ impl MessageHandler {
             }
 
             // Query Service Set, OPC UA Part 4, Section 5.9
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryFirstRequest(request) => {
                 self.validate_service_request(message, READ_COUNT, |session, _| {
                     Some(self.query_service.query_first(
@@ -279,6 +321,7 @@ impl MessageHandler {
                 })
             }
 
+            #[cfg(feature = "query-services")]
             SupportedMessage::QueryNextRequest(request) => {
                 self.validate_service_request(message, READ_COUNT, |session, _| {
                     Some(self.query_service.query_next(
@@ -293,9 +336,14 @@ impl MessageHandler {
             // Attribute Service Set, OPC UA Part 4, Section 5.10
             SupportedMessage::ReadRequest(request) => {
                 self.validate_service_request(message, READ_COUNT, |session, _| {
-                    Some(
-                        self.attribute_service
-                            .read(server_state, session, address_space, request),
+                    // A read of values that are deferred by their getters is answered later
+                    self.attribute_service.async_read(
+                        server_state,
+                        session,
+                        address_space,
+                        request_id,
+                        request,
+                        sender,
                     )
                 })
             }
@@ -368,23 +416,28 @@ impl MessageHandler {
                 }),
             SupportedMessage::SetMonitoringModeRequest(request) => {
                 self.validate_service_request(message, SET_MONITORING_MODE_COUNT, |session, _| {
-                    Some(
-                        self.monitored_item_service
-                            .set_monitoring_mode(session, request),
-                    )
+                    Some(self.monitored_item_service.set_monitoring_mode(
+                        server_state,
+                        session,
+                        request,
+                    ))
                 })
             }
             SupportedMessage::SetTriggeringRequest(request) => {
                 self.validate_service_request(message, SET_TRIGGERING_COUNT, |session, _| {
-                    Some(self.monitored_item_service.set_triggering(session, request))
+                    Some(
+                        self.monitored_item_service
+                            .set_triggering(server_state, session, request),
+                    )
                 })
             }
             SupportedMessage::DeleteMonitoredItemsRequest(request) => self
                 .validate_service_request(message, DELETE_MONITORED_ITEMS_COUNT, |session, _| {
-                    Some(
-                        self.monitored_item_service
-                            .delete_monitored_items(session, request),
-                    )
+                    Some(self.monitored_item_service.delete_monitored_items(
+                        server_state,
+                        session,
+                        request,
+                    ))
                 }),
 
             // Subscription Service Set, OPC UA Part 4, Section 5.13
@@ -423,12 +476,18 @@ impl MessageHandler {
                 })
             }
             SupportedMessage::TransferSubscriptionsRequest(request) => self
-                .validate_service_request(message, TRANSFER_SUBSCRIPTIONS_COUNT, |session, _| {
-                    Some(
-                        self.subscription_service
-                            .transfer_subscriptions(session, request),
-                    )
-                }),
+                .validate_service_request(
+                    message,
+                    TRANSFER_SUBSCRIPTIONS_COUNT,
+                    |session, session_manager| {
+                        Some(self.subscription_service.transfer_subscriptions(
+                            &Utc::now(),
+                            session,
+                            session_manager,
+                            request,
+                        ))
+                    },
+                ),
             SupportedMessage::PublishRequest(request) => {
                 self.validate_service_request(message, "", |session, _| {
                     // TODO publish request diagnostics have to be done asynchronously too
@@ -462,32 +521,134 @@ impl MessageHandler {
         };
 
         if let Some(response) = response {
+            tracing::debug!(
+                response = response.name(),
+                service_result = %response.response_header().service_result,
+                duration_us = started.elapsed().as_micros() as u64,
+                "Service request handled"
+            );
             let _ = sender.send_message(request_id, response);
+        } else {
+            tracing::debug!(
+                duration_us = started.elapsed().as_micros() as u64,
+                "Service request handled without an immediate response"
+            );
         }
 
         Ok(())
     }
 
+    /// Tests if the message is a request that the handler answers. Any other message is an
+    /// error that ends the connection it came on.
+    #[cfg(feature = "https")]
+    pub fn is_handled(message: &SupportedMessage) -> bool {
+        match message {
+            #[cfg(feature = "node-management-services")]
+            SupportedMessage::AddNodesRequest(_)
+            | SupportedMessage::AddReferencesRequest(_)
+            | SupportedMessage::DeleteNodesRequest(_)
+            | SupportedMessage::DeleteReferencesRequest(_) => true,
+            #[cfg(feature = "query-services")]
+            SupportedMessage::QueryFirstRequest(_) | SupportedMessage::QueryNextRequest(_) => true,
+            message => matches!(
+                message,
+                SupportedMessage::GetEndpointsRequest(_)
+                    | SupportedMessage::RegisterServerRequest(_)
+                    | SupportedMessage::RegisterServer2Request(_)
+                    | SupportedMessage::FindServersRequest(_)
+                    | SupportedMessage::CreateSessionRequest(_)
+                    | SupportedMessage::CloseSessionRequest(_)
+                    | SupportedMessage::ActivateSessionRequest(_)
+                    | SupportedMessage::CancelRequest(_)
+                    | SupportedMessage::BrowseRequest(_)
+                    | SupportedMessage::BrowseNextRequest(_)
+                    | SupportedMessage::TranslateBrowsePathsToNodeIdsRequest(_)
+                    | SupportedMessage::RegisterNodesRequest(_)
+                    | SupportedMessage::UnregisterNodesRequest(_)
+                    | SupportedMessage::ReadRequest(_)
+                    | SupportedMessage::HistoryReadRequest(_)
+                    | SupportedMessage::WriteRequest(_)
+                    | SupportedMessage::HistoryUpdateRequest(_)
+                    | SupportedMessage::CallRequest(_)
+                    | SupportedMessage::CreateMonitoredItemsRequest(_)
+                    | SupportedMessage::ModifyMonitoredItemsRequest(_)
+                    | SupportedMessage::SetMonitoringModeRequest(_)
+                    | SupportedMessage::SetTriggeringRequest(_)
+                    | SupportedMessage::DeleteMonitoredItemsRequest(_)
+                    | SupportedMessage::CreateSubscriptionRequest(_)
+                    | SupportedMessage::ModifySubscriptionRequest(_)
+                    | SupportedMessage::SetPublishingModeRequest(_)
+                    | SupportedMessage::DeleteSubscriptionsRequest(_)
+                    | SupportedMessage::TransferSubscriptionsRequest(_)
+                    | SupportedMessage::PublishRequest(_)
+                    | SupportedMessage::RepublishRequest(_)
+            ),
+        }
+    }
+
+    /// Records the id of the request's session on the current service request span
+    fn record_session_id(session: &Arc<RwLock<Session>>) {
+        let session = trace_read_lock!(session);
+        tracing::Span::current()
+            .record("session_id", tracing::field::display(session.session_id()));
+    }
+
     /// Tests if this request should be rejected because of a session timeout
     fn is_session_timed_out(
+        &self,
         session: Arc<RwLock<Session>>,
         request_header: &RequestHeader,
         now: DateTimeUtc,
     ) -> Result<(), SupportedMessage> {
-        let mut session = trace_write_lock!(session);
-        let last_service_request_timestamp = session.last_service_request_timestamp();
-        let elapsed = now - last_service_request_timestamp;
-        if elapsed.num_milliseconds() as f64 > session.session_timeout()
-            && session.session_timeout() > 0.0
-        {
-            session.terminate_session();
+        let session_id = {
+            let session = trace_read_lock!(session);
+            if !session.is_timed_out(&now) {
+                return Ok(());
+            }
+            let elapsed = now - session.last_service_request_timestamp();
             error!("Session has timed out because too much time has elapsed between service calls - elapsed time = {}ms", elapsed.num_milliseconds());
-            Err(ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into())
+            session.session_id().clone()
+        };
+        let mut session_manager = trace_write_lock!(self.session_manager);
+        session_manager.terminate_session(&session_id, &self.address_space);
+        Err(ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into())
+    }
+
+    /// Tests if the timeout hint of the request has expired, in which case the client has given up
+    /// waiting for the response and the request is abandoned with `BadTimeout`. The expiry is
+    /// measured from when the server received the request rather than from the timestamp of the
+    /// request, so a client whose clock differs from the server's is not timed out.
+    pub(crate) fn is_request_expired(
+        request_header: &RequestHeader,
+        received_at: DateTimeUtc,
+        now: DateTimeUtc,
+    ) -> Result<(), SupportedMessage> {
+        if request_header.timeout_hint == 0 {
+            return Ok(());
+        }
+        let elapsed = now - received_at;
+        if elapsed.num_milliseconds() > request_header.timeout_hint as i64 {
+            warn!(
+                "Request {} has expired and is abandoned - elapsed time = {}ms, timeout hint = {}ms",
+                request_header.request_handle,
+                elapsed.num_milliseconds(),
+                request_header.timeout_hint
+            );
+            Err(ServiceFault::new(request_header, StatusCode::BadTimeout).into())
         } else {
             Ok(())
         }
     }
 
+    /// Tests if the request shows that the session is in use. Publishing is not counted because
+    /// a client that has been abandoned with subscriptions still publishes.
+    fn is_session_activity(request: &SupportedMessage) -> bool {
+        !matches!(
+            request,
+            SupportedMessage::PublishRequest(_) | SupportedMessage::RepublishRequest(_)
+        )
+    }
+
     /// Test if the session is activated
     fn is_session_activated(
         &self,
@@ -534,24 +695,32 @@ impl MessageHandler {
             session_manager.find_session_by_token(&request_header.authentication_token)
         };
         if let Some(session) = session {
+            Self::record_session_id(&session);
             let (response, authorized) = if let Err(response) =
-                Self::is_session_timed_out(session.clone(), request_header, now)
+                self.is_session_timed_out(session.clone(), request_header, now)
             {
                 (response, false)
             } else {
                 let response = action(session.clone());
                 let mut session = trace_write_lock!(session);
                 session.set_last_service_request_timestamp(now);
+                if Self::is_session_activity(request) {
+                    session.set_last_activity_timestamp(now);
+                }
                 (response, true)
             };
             Self::diag_service_response(session, authorized, &response, diagnostic_key);
+            self.diag_rejected_request(&response);
             Some(response)
         } else {
             warn!(
                 "validate_activate_service_request, session not found for token {}",
                 &request_header.authentication_token
             );
-            Some(ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into())
+            let response =
+                ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into();
+            self.diag_rejected_request(&response);
+            Some(response)
         }
     }
 
@@ -574,33 +743,66 @@ impl MessageHandler {
             session_manager.find_session_by_token(&request_header.authentication_token)
         };
         if let Some(session) = session {
+            Self::record_session_id(&session);
             let (response, authorized) =
                 if let Err(response) = self.is_session_activated(session.clone(), request_header) {
                     (Some(response), false)
                 } else if let Err(response) =
-                    Self::is_session_timed_out(session.clone(), request_header, now)
+                    self.is_session_timed_out(session.clone(), request_header, now)
                 {
                     (Some(response), false)
+                } else if let Err(response) =
+                    Self::is_request_expired(request_header, self.received_at, now)
+                {
+                    (Some(response), true)
                 } else {
                     let response = action(session.clone(), session_manager);
                     let mut session = trace_write_lock!(session);
                     session.set_last_service_request_timestamp(now);
+                    if Self::is_session_activity(request) {
+                        session.set_last_activity_timestamp(now);
+                    }
                     (response, true)
                 };
             // Async calls may not return a response here
             response.map(|response| {
                 Self::diag_service_response(session, authorized, &response, diagnostic_key);
+                self.diag_rejected_request(&response);
                 response
             })
         } else {
-            Some(ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into())
+            let response =
+                ServiceFault::new(request_header, StatusCode::BadSessionIdInvalid).into();
+            self.diag_rejected_request(&response);
+            Some(response)
+        }
+    }
+
+    /// Increment count of rejected requests in server diagnostics if the response is a fault
+    fn diag_rejected_request(&self, response: &SupportedMessage) {
+        if let SupportedMessage::ServiceFault(fault) = response {
+            let server_state = trace_read_lock!(self.server_state);
+            let mut diagnostics = trace_write_lock!(server_state.diagnostics);
+            match fault.response_header.service_result {
+                StatusCode::BadUserAccessDenied
+                | StatusCode::BadIdentityTokenInvalid
+                | StatusCode::BadIdentityTokenRejected
+                | StatusCode::BadSecurityChecksFailed
+                | StatusCode::BadApplicationSignatureInvalid
+                | StatusCode::BadUserSignatureInvalid => diagnostics.on_security_rejected_request(),
+                _ => diagnostics.on_rejected_request(),
+            }
         }
     }
 
     /// Increment count of request in session diagnostics
-    fn diag_authorized_request(session_diagnostics: &mut SessionDiagnostics, authorized: bool) {
+    fn diag_authorized_request(
+        session_diagnostics: &mut SessionDiagnostics,
+        authorized: bool,
+        service_success: bool,
+    ) {
         if authorized {
-            session_diagnostics.request();
+            session_diagnostics.request(service_success);
         } else {
             session_diagnostics.unauthorized_request();
         }
@@ -616,13 +818,9 @@ impl MessageHandler {
         let session = trace_read_lock!(session);
         let session_diagnostics = session.session_diagnostics();
         let mut session_diagnostics = trace_write_lock!(session_diagnostics);
-        Self::diag_authorized_request(&mut session_diagnostics, authorized);
+        let service_success = !matches!(response, SupportedMessage::ServiceFault(_));
+        Self::diag_authorized_request(&mut session_diagnostics, authorized, service_success);
         if !diagnostic_key.is_empty() {
-            let service_success = if let SupportedMessage::ServiceFault(_response) = response {
-                false
-            } else {
-                true
-            };
             if service_success {
                 session_diagnostics.service_success(diagnostic_key);
             } else {
@@ -9,7 +9,8 @@ use crate::sync::*;
 use crate::types::{status_code::StatusCode, *};
 
 use crate::server::{
-    address_space::AddressSpace, services::Service, session::SessionManager, state::ServerState,
+    address_space::AddressSpace, config::OperationalLimits, services::Service,
+    session::SessionManager, state::ServerState,
 };
 
 /// The method service. Allows a client to call a method on the server.
@@ -35,8 +36,16 @@ impl MethodService {
         request: &CallRequest,
     ) -> SupportedMessage {
         if let Some(ref calls) = request.methods_to_call {
-            let server_state = trace_read_lock!(server_state);
-            if calls.len() <= server_state.operational_limits.max_nodes_per_method_call {
+            // The server state isn't locked while methods are called, since some change it
+            let max_nodes_per_method_call = trace_read_lock!(server_state)
+                .operational_limits
+                .max_nodes_per_method_call;
+            if OperationalLimits::allows(max_nodes_per_method_call, calls.len()) {
+                // The roles of the user decide which methods may be called
+                let roles = trace_read_lock!(session_manager)
+                    .find_session_by_id(session_id)
+                    .map(|session| trace_read_lock!(session).roles().to_vec())
+                    .unwrap_or_default();
                 let mut address_space = trace_write_lock!(address_space);
 
                 let results: Vec<CallMethodResult> = calls
@@ -52,9 +61,30 @@ impl MethodService {
                         // state of the system (acknowledge, batch sequencing or other system changes) must
                         // generate an AuditUpdateMethodEventType or a subtype of it.
 
+                        let permitted =
+                            address_space
+                                .find_node(&request.method_id)
+                                .is_none_or(|method| {
+                                    method
+                                        .as_node()
+                                        .permissions(&roles)
+                                        .contains(PermissionType::Call)
+                                });
+                        if !permitted {
+                            error!(
+                                "Call to {:?} on {:?} is denied to the roles of the user",
+                                request.method_id, request.object_id
+                            );
+                            return CallMethodResult {
+                                status_code: StatusCode::BadUserAccessDenied,
+                                input_argument_results: None,
+                                input_argument_diagnostic_infos: None,
+                                output_arguments: None,
+                            };
+                        }
+
                         // Call the method via whatever is registered in the address space
                         match address_space.call_method(
-                            &server_state,
                             session_id,
                             session_manager.clone(),
                             request,
@@ -30,10 +30,12 @@ pub mod attribute;
 pub mod discovery;
 pub mod method;
 pub mod monitored_item;
+#[cfg(feature = "node-management-services")]
 pub mod node_management;
+#[cfg(feature = "query-services")]
 pub mod query;
 pub mod session;
 pub mod subscription;
 pub mod view;
 
-mod audit;
+pub(crate) mod audit;
@@ -9,7 +9,8 @@ use crate::sync::*;
 use crate::types::{status_code::StatusCode, *};
 
 use crate::server::{
-    address_space::AddressSpace, services::Service, session::Session, state::ServerState,
+    address_space::AddressSpace, config::OperationalLimits, services::Service, session::Session,
+    state::ServerState,
 };
 
 /// The monitored item service. Allows client to create, modify and delete monitored items on a subscription.
@@ -36,6 +37,11 @@ impl MonitoredItemService {
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.items_to_create) {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+        } else if !Self::allows_items(
+            &server_state,
+            request.items_to_create.as_ref().unwrap().len(),
+        ) {
+            self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
         } else {
             let server_state = trace_read_lock!(server_state);
             let mut session = trace_write_lock!(session);
@@ -79,6 +85,11 @@ impl MonitoredItemService {
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.items_to_modify) {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+        } else if !Self::allows_items(
+            &server_state,
+            request.items_to_modify.as_ref().unwrap().len(),
+        ) {
+            self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
         } else {
             let server_state = trace_read_lock!(server_state);
             let mut session = trace_write_lock!(session);
@@ -112,11 +123,17 @@ impl MonitoredItemService {
     /// Implementation of SetMonitoringMode service. See OPC Unified Architecture, Part 4 5.12.4
     pub fn set_monitoring_mode(
         &self,
+        server_state: Arc<RwLock<ServerState>>,
         session: Arc<RwLock<Session>>,
         request: &SetMonitoringModeRequest,
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.monitored_item_ids) {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+        } else if !Self::allows_items(
+            &server_state,
+            request.monitored_item_ids.as_ref().unwrap().len(),
+        ) {
+            self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
         } else {
             let mut session = trace_write_lock!(session);
             let monitored_item_ids = request.monitored_item_ids.as_ref().unwrap();
@@ -145,13 +162,18 @@ impl MonitoredItemService {
     /// Implementation of SetTriggering service. See OPC Unified Architecture, Part 4 5.12.5
     pub fn set_triggering(
         &self,
+        server_state: Arc<RwLock<ServerState>>,
         session: Arc<RwLock<Session>>,
         request: &SetTriggeringRequest,
     ) -> SupportedMessage {
+        let links = request.links_to_add.as_ref().map_or(0, |l| l.len())
+            + request.links_to_remove.as_ref().map_or(0, |l| l.len());
         if is_empty_option_vec!(request.links_to_add)
             && is_empty_option_vec!(request.links_to_remove)
         {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+        } else if !Self::allows_items(&server_state, links) {
+            self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
         } else {
             let mut session = trace_write_lock!(session);
             let links_to_add = match request.links_to_add {
@@ -203,11 +225,17 @@ impl MonitoredItemService {
     /// Implementation of DeleteMonitoredItems service. See OPC Unified Architecture, Part 4 5.12.6
     pub fn delete_monitored_items(
         &self,
+        server_state: Arc<RwLock<ServerState>>,
         session: Arc<RwLock<Session>>,
         request: &DeleteMonitoredItemsRequest,
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.monitored_item_ids) {
             self.service_fault(&request.request_header, StatusCode::BadNothingToDo)
+        } else if !Self::allows_items(
+            &server_state,
+            request.monitored_item_ids.as_ref().unwrap().len(),
+        ) {
+            self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
         } else {
             let mut session = trace_write_lock!(session);
             let monitored_item_ids = request.monitored_item_ids.as_ref().unwrap();
@@ -231,4 +259,14 @@ impl MonitoredItemService {
             }
         }
     }
+
+    /// Tests if a call on this many monitored items is within the `MaxMonitoredItemsPerCall`
+    /// operation limit of the server
+    fn allows_items(server_state: &Arc<RwLock<ServerState>>, items: usize) -> bool {
+        let server_state = trace_read_lock!(server_state);
+        OperationalLimits::allows(
+            server_state.operational_limits.max_monitored_items_per_call,
+            items,
+        )
+    }
 }
@@ -10,6 +10,7 @@ use crate::types::{node_ids::ObjectId, status_code::StatusCode, *};
 
 use crate::server::{
     address_space::{relative_path, types::*, AddressSpace},
+    config::OperationalLimits,
     services::Service,
     session::Session,
     state::ServerState,
@@ -40,11 +41,12 @@ impl NodeManagementService {
         if let Some(ref nodes_to_add) = request.nodes_to_add {
             if !nodes_to_add.is_empty() {
                 let server_state = trace_read_lock!(server_state);
-                if nodes_to_add.len()
-                    <= server_state
+                if OperationalLimits::allows(
+                    server_state
                         .operational_limits
-                        .max_nodes_per_node_management
-                {
+                        .max_nodes_per_node_management,
+                    nodes_to_add.len(),
+                ) {
                     let session = trace_read_lock!(session);
                     let mut address_space = trace_write_lock!(address_space);
 
@@ -64,6 +66,8 @@ impl NodeManagementService {
                             }
                         })
                         .collect();
+                    // Clients see the changes as soon as the service returns
+                    address_space.report_model_changes(&chrono::Utc::now());
                     let response = AddNodesResponse {
                         response_header: ResponseHeader::new_good(&request.request_header),
                         results: Some(results),
@@ -93,17 +97,19 @@ impl NodeManagementService {
         if let Some(ref references_to_add) = request.references_to_add {
             if !references_to_add.is_empty() {
                 let server_state = trace_read_lock!(server_state);
-                if references_to_add.len()
-                    <= server_state
+                if OperationalLimits::allows(
+                    server_state
                         .operational_limits
-                        .max_nodes_per_node_management
-                {
+                        .max_nodes_per_node_management,
+                    references_to_add.len(),
+                ) {
                     let session = trace_read_lock!(session);
                     let mut address_space = trace_write_lock!(address_space);
                     let results = references_to_add
                         .iter()
                         .map(|r| Self::add_reference(&session, &mut address_space, r))
                         .collect();
+                    address_space.report_model_changes(&chrono::Utc::now());
                     AddReferencesResponse {
                         response_header: ResponseHeader::new_good(&request.request_header),
                         results: Some(results),
@@ -133,11 +139,12 @@ impl NodeManagementService {
         if let Some(ref nodes_to_delete) = request.nodes_to_delete {
             if !nodes_to_delete.is_empty() {
                 let server_state = trace_read_lock!(server_state);
-                if nodes_to_delete.len()
-                    <= server_state
+                if OperationalLimits::allows(
+                    server_state
                         .operational_limits
-                        .max_nodes_per_node_management
-                {
+                        .max_nodes_per_node_management,
+                    nodes_to_delete.len(),
+                ) {
                     let session = trace_read_lock!(session);
                     let mut address_space = trace_write_lock!(address_space);
                     let results = nodes_to_delete
@@ -146,6 +153,7 @@ impl NodeManagementService {
                             Self::delete_node(&session, &mut address_space, node_to_delete)
                         })
                         .collect();
+                    address_space.report_model_changes(&chrono::Utc::now());
                     let response = DeleteNodesResponse {
                         response_header: ResponseHeader::new_good(&request.request_header),
                         results: Some(results),
@@ -175,17 +183,19 @@ impl NodeManagementService {
         if let Some(ref references_to_delete) = request.references_to_delete {
             if !references_to_delete.is_empty() {
                 let server_state = trace_read_lock!(server_state);
-                if references_to_delete.len()
-                    <= server_state
+                if OperationalLimits::allows(
+                    server_state
                         .operational_limits
-                        .max_nodes_per_node_management
-                {
+                        .max_nodes_per_node_management,
+                    references_to_delete.len(),
+                ) {
                     let session = trace_read_lock!(session);
                     let mut address_space = trace_write_lock!(address_space);
                     let results = references_to_delete
                         .iter()
                         .map(|r| Self::delete_reference(&session, &mut address_space, r))
                         .collect();
+                    address_space.report_model_changes(&chrono::Utc::now());
                     DeleteReferencesResponse {
                         response_header: ResponseHeader::new_good(&request.request_header),
                         results: Some(results),
@@ -308,20 +318,27 @@ impl NodeManagementService {
             return (StatusCode::BadUserAccessDenied, NodeId::null());
         }
 
-        let requested_new_node_id = &item.requested_new_node_id;
-        if requested_new_node_id.server_index != 0 {
-            // Server index is supposed to be 0
-            error!("node cannot be created because server index is not 0");
+        // A namespace uri in the requested node id takes the place of its namespace index
+        let requested_new_node_id = if item.requested_new_node_id.is_null() {
+            None
+        } else if let Some(node_id) =
+            address_space.resolve_expanded_node_id(&item.requested_new_node_id)
+        {
+            Some(node_id)
+        } else {
+            // Server index is supposed to be 0 and the namespace must be registered
+            error!("node cannot be created because server index is not 0 or namespace is unknown");
             return (StatusCode::BadNodeIdRejected, NodeId::null());
-        }
+        };
 
         if item.node_class == NodeClass::Unspecified {
             error!("node cannot be created because node class is unspecified");
             return (StatusCode::BadNodeClassInvalid, NodeId::null());
         }
 
-        if !requested_new_node_id.is_null()
-            && address_space.node_exists(&requested_new_node_id.node_id)
+        if requested_new_node_id
+            .as_ref()
+            .is_some_and(|node_id| address_space.node_exists(node_id))
         {
             // If a node id is supplied, it should not already exist
             error!("node cannot be created because node id already exists");
@@ -347,11 +364,10 @@ impl NodeManagementService {
         let relative_path =
             RelativePath::from_str(&browse_name, &RelativePathElement::default_node_resolver)
                 .unwrap();
-        if let Ok(nodes) = relative_path::find_nodes_relative_path(
-            address_space,
-            &item.parent_node_id.node_id,
-            &relative_path,
-        ) {
+        let parent_node_id = address_space.resolve_expanded_node_id(&item.parent_node_id);
+        if let Some(Ok(nodes)) = parent_node_id.as_ref().map(|parent_node_id| {
+            relative_path::find_nodes_relative_path(address_space, parent_node_id, &relative_path)
+        }) {
             if !nodes.is_empty() {
                 error!("node cannot be created because the browse name is a duplicate");
                 return (StatusCode::BadBrowseNameDuplicated, NodeId::null());
@@ -360,33 +376,40 @@ impl NodeManagementService {
 
         if let Ok(reference_type_id) = item.reference_type_id.as_reference_type_id() {
             // Node Id was either supplied or will be generated
-            let new_node_id = if requested_new_node_id.is_null() {
-                NodeId::next_numeric(address_space.internal_namespace())
-            } else {
-                requested_new_node_id.node_id.clone()
-            };
+            let new_node_id = requested_new_node_id
+                .unwrap_or_else(|| NodeId::next_numeric(address_space.internal_namespace()));
+            let type_definition = address_space.resolve_expanded_node_id(&item.type_definition);
 
             // TODO test data model constraint
             // BadReferenceNotAllowed
 
             // Check the type definition is valid
-            if !address_space
-                .is_valid_type_definition(item.node_class, &item.type_definition.node_id)
-            {
-                // Type definition was either invalid or supplied when it should not have been supplied
-                error!("node cannot be created because type definition is not valid");
-                return (StatusCode::BadTypeDefinitionInvalid, NodeId::null());
-            }
+            let type_definition = match type_definition {
+                Some(type_definition)
+                    if address_space
+                        .is_valid_type_definition(item.node_class, &type_definition) =>
+                {
+                    type_definition
+                }
+                _ => {
+                    // Type definition was either invalid or supplied when it should not have been supplied
+                    error!("node cannot be created because type definition is not valid");
+                    return (StatusCode::BadTypeDefinitionInvalid, NodeId::null());
+                }
+            };
 
             // Check that the parent node exists
-            if !item.parent_node_id.server_index == 0
-                || !address_space.node_exists(&item.parent_node_id.node_id)
-            {
-                error!(
-                    "node cannot be created because parent node id is invalid or does not exist"
-                );
-                return (StatusCode::BadParentNodeIdInvalid, NodeId::null());
-            }
+            let parent_node_id = match parent_node_id {
+                Some(parent_node_id) if address_space.node_exists(&parent_node_id) => {
+                    parent_node_id
+                }
+                _ => {
+                    error!(
+                        "node cannot be created because parent node id is invalid or does not exist"
+                    );
+                    return (StatusCode::BadParentNodeIdInvalid, NodeId::null());
+                }
+            };
 
             // Create a node
             if let Ok(node) = Self::create_node(
@@ -400,14 +423,18 @@ impl NodeManagementService {
                 address_space.insert(
                     node,
                     Some(&[(
-                        &item.parent_node_id.node_id,
+                        &parent_node_id,
                         &reference_type_id,
                         ReferenceDirection::Forward,
                     )]),
                 );
                 // Object / Variable types must add a reference to the type
                 if item.node_class == NodeClass::Object || item.node_class == NodeClass::Variable {
-                    address_space.set_node_type(&new_node_id, item.type_definition.node_id.clone());
+                    address_space.set_node_type(&new_node_id, type_definition);
+                }
+                // The node is deleted when the session closes
+                if session.session_lifetime_nodes() {
+                    address_space.set_session_lifetime(&new_node_id, session.session_id());
                 }
                 (StatusCode::Good, new_node_id)
             } else {
@@ -428,19 +455,28 @@ impl NodeManagementService {
     ) -> StatusCode {
         if !session.can_modify_address_space() {
             // No permission to modify address space
-            StatusCode::BadUserAccessDenied
-        } else if !item.target_server_uri.is_null() {
-            StatusCode::BadServerUriInvalid
-        } else if item.target_node_id.server_index != 0 {
-            StatusCode::BadReferenceLocalOnly
+            return StatusCode::BadUserAccessDenied;
+        }
+        // The target may be named by namespace uri or held by a server in the server array
+        let target_node_id = match address_space
+            .resolve_remote_node_id(&item.target_node_id, &item.target_server_uri)
+        {
+            Ok(target_node_id) => target_node_id,
+            Err(StatusCode::BadServerIndexInvalid) => return StatusCode::BadReferenceLocalOnly,
+            Err(StatusCode::BadNodeIdUnknown) => return StatusCode::BadTargetNodeIdInvalid,
+            Err(status_code) => return status_code,
+        };
+        if target_node_id.server_index != 0 {
+            Self::add_remote_reference(address_space, item, &target_node_id)
         } else if !address_space.node_exists(&item.source_node_id) {
             StatusCode::BadSourceNodeIdInvalid
-        } else if !address_space.node_exists(&item.target_node_id.node_id) {
+        } else if !address_space.node_exists(&target_node_id.node_id) {
             StatusCode::BadTargetNodeIdInvalid
         } else if item.target_node_class == NodeClass::Unspecified {
             StatusCode::BadNodeClassInvalid
         } else {
-            if let Some(node_type) = address_space.find_node(&item.target_node_id.node_id) {
+            let target_node_id = &target_node_id.node_id;
+            if let Some(node_type) = address_space.find_node(target_node_id) {
                 // If the target node exists the class can be compared to the one supplied
                 if item.target_node_class != node_type.node_class() {
                     return StatusCode::BadNodeClassInvalid;
@@ -449,7 +485,7 @@ impl NodeManagementService {
             if let Ok(reference_type_id) = item.reference_type_id.as_reference_type_id() {
                 if !address_space.has_reference(
                     &item.source_node_id,
-                    &item.target_node_id.node_id,
+                    target_node_id,
                     reference_type_id,
                 ) {
                     // TODO test data model constraint
@@ -457,12 +493,12 @@ impl NodeManagementService {
                     if item.is_forward {
                         address_space.insert_reference(
                             &item.source_node_id,
-                            &item.target_node_id.node_id,
+                            target_node_id,
                             reference_type_id,
                         );
                     } else {
                         address_space.insert_reference(
-                            &item.target_node_id.node_id,
+                            target_node_id,
                             &item.source_node_id,
                             reference_type_id,
                         );
@@ -479,6 +515,54 @@ impl NodeManagementService {
         }
     }
 
+    /// Adds a reference to a node held by another server. The class of the target node can't be
+    /// checked because the node is not in this address space.
+    fn add_remote_reference(
+        address_space: &mut AddressSpace,
+        item: &AddReferencesItem,
+        target_node_id: &ExpandedNodeId,
+    ) -> StatusCode {
+        if item.target_node_class == NodeClass::Unspecified {
+            return StatusCode::BadNodeClassInvalid;
+        }
+        let Ok(reference_type_id) = item.reference_type_id.as_reference_type_id() else {
+            error!("reference cannot be added because reference type id is invalid");
+            return StatusCode::BadReferenceTypeIdInvalid;
+        };
+        let browse_direction = if item.is_forward {
+            BrowseDirection::Forward
+        } else {
+            BrowseDirection::Inverse
+        };
+        let is_duplicate = address_space
+            .find_remote_references(
+                &item.source_node_id,
+                browse_direction,
+                Some((reference_type_id, false)),
+            )
+            .iter()
+            .any(|r| r.target_node == *target_node_id);
+        if is_duplicate {
+            error!("reference cannot be added because reference is a duplicate");
+            return StatusCode::BadDuplicateReferenceNotAllowed;
+        }
+        let reference_direction = if item.is_forward {
+            ReferenceDirection::Forward
+        } else {
+            ReferenceDirection::Inverse
+        };
+        match address_space.insert_remote_reference(
+            &item.source_node_id,
+            target_node_id,
+            reference_type_id,
+            reference_direction,
+        ) {
+            Ok(_) => StatusCode::Good,
+            Err(StatusCode::BadNodeIdUnknown) => StatusCode::BadSourceNodeIdInvalid,
+            Err(status_code) => status_code,
+        }
+    }
+
     fn delete_node(
         session: &Session,
         address_space: &mut AddressSpace,
@@ -501,15 +585,47 @@ impl NodeManagementService {
         item: &DeleteReferencesItem,
     ) -> StatusCode {
         let node_id = &item.source_node_id;
-        let target_node_id = &item.target_node_id.node_id;
 
         if !session.can_modify_address_space() {
             // No permission to modify address space
-            StatusCode::BadUserAccessDenied
-        } else if item.target_node_id.server_index != 0 {
-            error!("reference cannot be added because only local references are supported");
-            StatusCode::BadReferenceLocalOnly
-        } else if node_id.is_null() || !address_space.node_exists(node_id) {
+            return StatusCode::BadUserAccessDenied;
+        }
+        let target_node_id =
+            match address_space.resolve_remote_node_id(&item.target_node_id, &UAString::null()) {
+                Ok(target_node_id) => target_node_id,
+                Err(StatusCode::BadServerIndexInvalid) => {
+                    error!("reference cannot be deleted because the server index is unknown");
+                    return StatusCode::BadReferenceLocalOnly;
+                }
+                Err(_) => {
+                    error!("reference cannot be deleted because target node id is invalid");
+                    return StatusCode::BadTargetNodeIdInvalid;
+                }
+            };
+        if target_node_id.server_index != 0 {
+            return match item.reference_type_id.as_reference_type_id() {
+                Ok(reference_type_id)
+                    if address_space.delete_remote_reference(
+                        node_id,
+                        &target_node_id,
+                        reference_type_id,
+                    ) =>
+                {
+                    StatusCode::Good
+                }
+                Ok(_) => {
+                    error!("reference cannot be deleted because it does not exist");
+                    StatusCode::BadNotFound
+                }
+                Err(_) => {
+                    error!("reference cannot be deleted because reference type id is invalid");
+                    StatusCode::BadReferenceTypeIdInvalid
+                }
+            };
+        }
+        let target_node_id = &target_node_id.node_id;
+
+        if node_id.is_null() || !address_space.node_exists(node_id) {
             error!("reference cannot be added because source node id is invalid");
             StatusCode::BadSourceNodeIdInvalid
         } else if target_node_id.is_null() || !address_space.node_exists(target_node_id) {
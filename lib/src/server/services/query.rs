@@ -2,17 +2,31 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::Utc;
+
 use crate::core::supported_message::SupportedMessage;
+use crate::crypto::random;
 use crate::sync::*;
-use crate::types::{status_code::StatusCode, *};
+use crate::types::{node_ids::ReferenceTypeId, operand::Operand, status_code::StatusCode, *};
 
 use crate::server::{
-    address_space::AddressSpace, services::Service, session::Session, state::ServerState,
+    address_space::{node::HasNodeId, relative_path::find_node_from_relative_path, AddressSpace},
+    continuation_point::QueryContinuationPoint,
+    events::{event_filter, operator},
+    services::{attribute::AttributeService, view::ViewService, Service},
+    session::Session,
+    state::ServerState,
 };
 
-/// The view service. Allows the client to browse the address space of the server.
+/// The query service. Allows the client to find nodes of specified types that match a filter.
+///
+/// The data to return for a node is read from the node at the end of a relative path, and a
+/// filter may test the type definition of a node with `OfType` and compare the attributes of
+/// nodes with attribute operands. References are not returned, so `max_references_to_return` is
+/// ignored.
 pub(crate) struct QueryService;
 
 impl Service for QueryService {
@@ -29,20 +43,366 @@ impl QueryService {
     pub fn query_first(
         &self,
         _server_state: Arc<RwLock<ServerState>>,
-        _session: Arc<RwLock<Session>>,
-        _address_space: Arc<RwLock<AddressSpace>>,
+        session: Arc<RwLock<Session>>,
+        address_space: Arc<RwLock<AddressSpace>>,
         request: &QueryFirstRequest,
     ) -> SupportedMessage {
-        self.service_fault(&request.request_header, StatusCode::BadNotSupported)
+        if is_empty_option_vec!(request.node_types) {
+            return self.service_fault(&request.request_header, StatusCode::BadNothingToDo);
+        }
+
+        let mut session = trace_write_lock!(session);
+        let address_space = trace_read_lock!(address_space);
+        session.remove_expired_query_continuation_points(&address_space);
+
+        let view_nodes = match ViewService::find_view_nodes(&address_space, &request.view) {
+            Ok(view_nodes) => view_nodes,
+            Err(status_code) => {
+                info!(
+                    "Query request ignored because its view is invalid, {}",
+                    status_code
+                );
+                return self.service_fault(&request.request_header, status_code);
+            }
+        };
+
+        let node_types = request.node_types.as_ref().unwrap();
+        let parsing_results = node_types
+            .iter()
+            .map(|node_type| Self::parse_node_type(&address_space, node_type))
+            .collect::<Vec<ParsingResult>>();
+        if parsing_results.iter().any(|r| r.status_code.is_bad()) {
+            error!("Query request has invalid node types");
+            return QueryFirstResponse {
+                response_header: ResponseHeader::new_service_result(
+                    &request.request_header,
+                    StatusCode::BadInvalidArgument,
+                ),
+                query_data_sets: None,
+                continuation_point: ByteString::null(),
+                parsing_results: Some(parsing_results),
+                diagnostic_infos: None,
+                filter_result: ContentFilterResult {
+                    element_results: None,
+                    element_diagnostic_infos: None,
+                },
+            }
+            .into();
+        }
+
+        let filter_result =
+            match event_filter::validate_where_clause(&request.filter, &address_space, true) {
+                Ok(filter_result) => filter_result,
+                Err(status_code) => {
+                    return self.service_fault(&request.request_header, status_code);
+                }
+            };
+        let filter_is_valid = filter_result
+            .element_results
+            .iter()
+            .flatten()
+            .all(|r| r.status_code.is_good());
+        if !filter_is_valid {
+            error!("Query request has an invalid filter");
+            return QueryFirstResponse {
+                response_header: ResponseHeader::new_service_result(
+                    &request.request_header,
+                    StatusCode::BadContentFilterInvalid,
+                ),
+                query_data_sets: None,
+                continuation_point: ByteString::null(),
+                parsing_results: None,
+                diagnostic_infos: None,
+                filter_result,
+            }
+            .into();
+        }
+
+        let query_data_sets = Self::query_nodes(
+            &session,
+            &address_space,
+            view_nodes.as_ref(),
+            node_types,
+            request,
+        );
+        match Self::next_data_sets(
+            &mut session,
+            &address_space,
+            query_data_sets,
+            request.max_data_sets_to_return as usize,
+        ) {
+            Ok((query_data_sets, continuation_point)) => QueryFirstResponse {
+                response_header: ResponseHeader::new_good(&request.request_header),
+                query_data_sets: Some(query_data_sets),
+                continuation_point,
+                parsing_results: None,
+                diagnostic_infos: None,
+                filter_result,
+            }
+            .into(),
+            Err(status_code) => self.service_fault(&request.request_header, status_code),
+        }
     }
 
     pub fn query_next(
         &self,
         _server_state: Arc<RwLock<ServerState>>,
-        _session: Arc<RwLock<Session>>,
-        _address_space: Arc<RwLock<AddressSpace>>,
+        session: Arc<RwLock<Session>>,
+        address_space: Arc<RwLock<AddressSpace>>,
         request: &QueryNextRequest,
     ) -> SupportedMessage {
-        self.service_fault(&request.request_header, StatusCode::BadNotSupported)
+        let mut session = trace_write_lock!(session);
+        let address_space = trace_read_lock!(address_space);
+        session.remove_expired_query_continuation_points(&address_space);
+
+        let continuation_point =
+            match session.find_query_continuation_point(&request.continuation_point) {
+                Some(continuation_point) => continuation_point,
+                None => {
+                    error!(
+                        "Query continuation point {} was invalid",
+                        request.continuation_point.as_base64()
+                    );
+                    return self.service_fault(
+                        &request.request_header,
+                        StatusCode::BadContinuationPointInvalid,
+                    );
+                }
+            };
+
+        if request.release_continuation_point {
+            // The point has been removed from the session, nothing more is returned
+            QueryNextResponse {
+                response_header: ResponseHeader::new_good(&request.request_header),
+                query_data_sets: None,
+                revised_continuation_point: ByteString::null(),
+            }
+            .into()
+        } else {
+            match Self::next_data_sets(
+                &mut session,
+                &address_space,
+                continuation_point.query_data_sets,
+                continuation_point.max_data_sets_to_return,
+            ) {
+                Ok((query_data_sets, revised_continuation_point)) => QueryNextResponse {
+                    response_header: ResponseHeader::new_good(&request.request_header),
+                    query_data_sets: Some(query_data_sets),
+                    revised_continuation_point,
+                }
+                .into(),
+                Err(status_code) => self.service_fault(&request.request_header, status_code),
+            }
+        }
+    }
+
+    /// Checks that a node type description refers to a type and that the data it asks for can be
+    /// returned.
+    fn parse_node_type(
+        address_space: &AddressSpace,
+        node_type: &NodeTypeDescription,
+    ) -> ParsingResult {
+        let data_status_codes = node_type.data_to_return.as_ref().map(|data_to_return| {
+            data_to_return
+                .iter()
+                .map(|data| {
+                    if AttributeId::from_u32(data.attribute_id).is_err() {
+                        StatusCode::BadAttributeIdInvalid
+                    } else if data.index_range.as_ref().parse::<NumericRange>().is_err() {
+                        StatusCode::BadIndexRangeInvalid
+                    } else {
+                        StatusCode::Good
+                    }
+                })
+                .collect::<Vec<StatusCode>>()
+        });
+        let status_code = match address_space
+            .resolve_expanded_node_id(&node_type.type_definition_node)
+            .and_then(|type_id| address_space.find_node(&type_id))
+        {
+            None => StatusCode::BadNodeIdUnknown,
+            Some(node) => match node.node_class() {
+                NodeClass::ObjectType | NodeClass::VariableType => data_status_codes
+                    .iter()
+                    .flatten()
+                    .find(|status_code| status_code.is_bad())
+                    .copied()
+                    .unwrap_or(StatusCode::Good),
+                _ => StatusCode::BadNotTypeDefinition,
+            },
+        };
+        ParsingResult {
+            status_code,
+            data_status_codes,
+            data_diagnostic_infos: None,
+        }
+    }
+
+    /// Finds the nodes of the requested types that match the filter and reads the data to return
+    /// for each of them. Only nodes in the view are found if the request has one.
+    fn query_nodes(
+        session: &Session,
+        address_space: &AddressSpace,
+        view_nodes: Option<&HashSet<NodeId>>,
+        node_types: &[NodeTypeDescription],
+        request: &QueryFirstRequest,
+    ) -> Vec<QueryDataSet> {
+        // The type definitions that each node type description matches
+        let type_ids = node_types
+            .iter()
+            .map(|node_type| {
+                let type_id = address_space
+                    .resolve_expanded_node_id(&node_type.type_definition_node)
+                    .unwrap();
+                let mut type_ids = HashSet::new();
+                Self::add_type_ids(
+                    address_space,
+                    type_id,
+                    node_type.include_sub_types,
+                    &mut type_ids,
+                );
+                type_ids
+            })
+            .collect::<Vec<HashSet<NodeId>>>();
+
+        address_space
+            .nodes()
+            .filter(|node| matches!(node.node_class(), NodeClass::Object | NodeClass::Variable))
+            .filter(|node| view_nodes.is_none_or(|v| v.contains(&node.node_id())))
+            .filter_map(|node| {
+                let node_id = node.node_id();
+                let type_definition = address_space
+                    .find_references(&node_id, Some((ReferenceTypeId::HasTypeDefinition, false)))?
+                    .first()?
+                    .target_node
+                    .clone();
+                let node_type = type_ids
+                    .iter()
+                    .position(|type_ids| type_ids.contains(&type_definition))
+                    .map(|idx| &node_types[idx])?;
+                // The user must be allowed to browse the node, as with Browse, and to read each
+                // attribute the filter tests, as with Read
+                if !session.is_browsable(&node_id)
+                    || !session
+                        .permissions(node.as_node())
+                        .contains(PermissionType::Browse)
+                    || !Self::is_filter_readable(session, address_space, &node_id, &request.filter)
+                {
+                    return None;
+                }
+                let matches =
+                    event_filter::evaluate_where_clause(&node_id, &request.filter, address_space)
+                        .is_ok_and(|result| result == Variant::Boolean(true));
+                if !matches {
+                    return None;
+                }
+                let values = node_type.data_to_return.as_ref().map(|data_to_return| {
+                    data_to_return
+                        .iter()
+                        .map(|data| Self::data_value(session, address_space, &node_id, data))
+                        .collect()
+                });
+                Some(QueryDataSet {
+                    node_id: node_id.into(),
+                    type_definition_node: type_definition.into(),
+                    values,
+                })
+            })
+            .collect()
+    }
+
+    /// Tests if the user of the session may read every attribute that the attribute operands of
+    /// the filter refer to from the node. An attribute of a node that does not exist is empty.
+    fn is_filter_readable(
+        session: &Session,
+        address_space: &AddressSpace,
+        node_id: &NodeId,
+        filter: &ContentFilter,
+    ) -> bool {
+        filter
+            .elements
+            .iter()
+            .flatten()
+            .flat_map(|element| element.filter_operands.iter().flatten())
+            .all(|operand| match Operand::try_from(operand) {
+                Ok(Operand::AttributeOperand(o)) => {
+                    match find_node_from_relative_path(address_space, node_id, &o.browse_path) {
+                        Some(node) => {
+                            AttributeId::from_u32(o.attribute_id).is_ok_and(|attribute_id| {
+                                AttributeService::is_permitted_read(session, node, attribute_id)
+                            })
+                        }
+                        None => true,
+                    }
+                }
+                _ => true,
+            })
+    }
+
+    /// Adds the type and, if requested, its subtypes to the set
+    fn add_type_ids(
+        address_space: &AddressSpace,
+        type_id: NodeId,
+        include_sub_types: bool,
+        type_ids: &mut HashSet<NodeId>,
+    ) {
+        if include_sub_types {
+            if let Some(references) =
+                address_space.find_references(&type_id, Some((ReferenceTypeId::HasSubtype, false)))
+            {
+                references.into_iter().for_each(|r| {
+                    Self::add_type_ids(address_space, r.target_node, true, type_ids);
+                });
+            }
+        }
+        type_ids.insert(type_id);
+    }
+
+    /// Reads the data to return for a node, which is empty if the data does not exist or the user
+    /// may not read it
+    fn data_value(
+        session: &Session,
+        address_space: &AddressSpace,
+        node_id: &NodeId,
+        data: &QueryDataDescription,
+    ) -> Variant {
+        match find_node_from_relative_path(address_space, node_id, &data.relative_path) {
+            Some(node)
+                if AttributeId::from_u32(data.attribute_id).is_ok_and(|attribute_id| {
+                    AttributeService::is_permitted_read(session, node, attribute_id)
+                }) =>
+            {
+                operator::attribute_value(node, data.attribute_id, &data.index_range)
+            }
+            _ => Variant::Empty,
+        }
+    }
+
+    /// Splits off the data sets to return now and stores the rest in a continuation point
+    fn next_data_sets(
+        session: &mut Session,
+        address_space: &AddressSpace,
+        mut query_data_sets: Vec<QueryDataSet>,
+        max_data_sets_to_return: usize,
+    ) -> Result<(Vec<QueryDataSet>, ByteString), StatusCode> {
+        if max_data_sets_to_return == 0 || query_data_sets.len() <= max_data_sets_to_return {
+            Ok((query_data_sets, ByteString::null()))
+        } else {
+            let remaining = query_data_sets.split_off(max_data_sets_to_return);
+            let continuation_point = random::byte_string(6);
+            debug!(
+                "Query has {} more data sets, creating continuation point {}",
+                remaining.len(),
+                continuation_point.as_base64()
+            );
+            session.add_query_continuation_point(QueryContinuationPoint {
+                id: continuation_point.clone(),
+                address_space_last_modified: address_space.last_modified(),
+                created: Utc::now(),
+                max_data_sets_to_return,
+                query_data_sets: remaining,
+            })?;
+            Ok((query_data_sets, continuation_point))
+        }
     }
 }
@@ -104,6 +104,7 @@ impl SessionService {
                         &server_state,
                         address_space.clone(),
                         result,
+                        &request.client_certificate,
                         &request.request_header,
                     );
 
@@ -132,12 +133,18 @@ impl SessionService {
                     self.service_fault(&request.request_header, service_result),
                 )
             } else {
-                let session_timeout =
-                    if request.requested_session_timeout > constants::MAX_SESSION_TIMEOUT {
-                        constants::MAX_SESSION_TIMEOUT
+                // A session without a timeout would never be closed if its client goes away
+                let session_timeout = {
+                    let config = trace_read_lock!(server_state.config);
+                    let max_session_timeout = config.limits.max_session_timeout;
+                    if request.requested_session_timeout <= 0.0
+                        || request.requested_session_timeout > max_session_timeout
+                    {
+                        max_session_timeout
                     } else {
                         request.requested_session_timeout
-                    };
+                    }
+                };
 
                 let max_request_message_size = constants::MAX_REQUEST_MESSAGE_SIZE;
 
@@ -164,10 +171,28 @@ impl SessionService {
                 session.set_max_response_message_size(request.max_response_message_size);
                 session.set_endpoint_url(request.endpoint_url.clone());
                 session.set_security_policy_uri(security_policy.to_uri());
+                session.set_security_mode(secure_channel.security_mode());
                 session.set_user_identity(IdentityToken::None);
                 session.set_client_certificate(client_certificate);
                 session.set_session_nonce(server_nonce.clone());
                 session.set_session_name(request.session_name.clone());
+                session.set_client_description(request.client_description.clone());
+                session.set_server_uri(request.server_uri.clone());
+
+                // Sessions on the endpoint may be closed when they are idle
+                {
+                    let config = trace_read_lock!(server_state.config);
+                    if let Some(endpoint) = config.find_endpoint(
+                        request.endpoint_url.as_ref(),
+                        security_policy,
+                        secure_channel.security_mode(),
+                    ) {
+                        session.set_idle_session_timeout(
+                            endpoint.idle_session_timeout as i64 * 1000,
+                            endpoint.idle_session_warning as i64 * 1000,
+                        );
+                    }
+                }
 
                 audit::log_create_session(
                     &server_state,
@@ -248,8 +273,9 @@ impl SessionService {
             StatusCode::Good
         };
 
+        let mut identity = None;
         if service_result.is_good() {
-            if let Err(err) = server_state.authenticate_endpoint(
+            match server_state.authenticate_endpoint(
                 request,
                 endpoint_url,
                 security_policy,
@@ -257,8 +283,11 @@ impl SessionService {
                 &request.user_identity_token,
                 session.session_nonce(),
             ) {
-                error!("activate_session, invalid endpoint");
-                service_result = err;
+                Ok(user_identity) => identity = Some(user_identity),
+                Err(err) => {
+                    error!("activate_session, invalid endpoint");
+                    service_result = err;
+                }
             }
         }
 
@@ -291,11 +320,15 @@ impl SessionService {
 
             session.set_activated(true);
             session.set_secure_channel_id(secure_channel_id);
+            session.set_security_mode(security_mode);
             session.set_session_nonce(server_nonce);
             session.set_user_identity(IdentityToken::new(
                 &request.user_identity_token,
                 &server_state.decoding_options(),
             ));
+            let identity = identity.unwrap();
+            session.set_user_token_id(Some(identity.user_token_id));
+            session.set_roles(identity.roles);
             session.set_locale_ids(request.locale_ids.clone());
 
             let diagnostic_infos = None;
@@ -368,7 +401,15 @@ impl SessionService {
 
                 session.set_authentication_token(NodeId::null());
                 session.set_user_identity(IdentityToken::None);
+                session.set_user_token_id(None);
+                session.set_roles(Vec::new());
                 session.set_activated(false);
+
+                // Nodes added by the session do not outlive it
+                {
+                    let mut address_space = trace_write_lock!(address_space);
+                    address_space.delete_session_nodes(session.session_id());
+                }
                 audit::log_close_session(&server_state, &session, address_space, true, request);
             }
 
@@ -390,16 +431,25 @@ impl SessionService {
         }
     }
 
+    /// Cancels the outstanding requests with the request handle in the request. Other services
+    /// complete before the next request is processed, so only the publish requests queued on the
+    /// session can still be outstanding. Each one cancelled is answered with
+    /// `BadRequestCancelledByClient`.
     pub fn cancel(
         &self,
         _server_state: Arc<RwLock<ServerState>>,
-        _session: Arc<RwLock<Session>>,
+        session: Arc<RwLock<Session>>,
         request: &CancelRequest,
     ) -> SupportedMessage {
-        // This service call currently does nothing
+        let cancel_count = {
+            let mut session = trace_write_lock!(session);
+            session
+                .subscriptions_mut()
+                .cancel_publish_requests(request.request_handle)
+        };
         CancelResponse {
             response_header: ResponseHeader::new_good(&request.request_header),
-            cancel_count: 0,
+            cancel_count,
         }
         .into()
     }
@@ -9,10 +9,57 @@ use crate::sync::*;
 use crate::types::{status_code::StatusCode, *};
 
 use crate::server::{
-    address_space::AddressSpace, services::Service, session::Session, state::ServerState,
+    address_space::AddressSpace,
+    constants,
+    services::Service,
+    session::{Session, SessionManager},
+    state::ServerState,
     subscriptions::subscription::Subscription,
 };
 
+/// Who is asking to take over a subscription, captured so the owning session can be checked
+/// without holding both session locks
+struct TransferClient {
+    client_user_id: UAString,
+    client_certificate: Option<ByteString>,
+    application_uri: UAString,
+}
+
+impl From<&Session> for TransferClient {
+    fn from(session: &Session) -> Self {
+        TransferClient {
+            client_user_id: session.client_user_id(),
+            client_certificate: session
+                .client_certificate()
+                .as_ref()
+                .map(|cert| cert.as_byte_string()),
+            application_uri: session.client_description().application_uri.clone(),
+        }
+    }
+}
+
+impl TransferClient {
+    /// Tests if the session that owns a subscription belongs to this client. Anonymous sessions
+    /// have no user so the client must instead have the same certificate or, without one, the
+    /// same ApplicationUri as the owner. See OPC UA Part 4 5.13.7
+    fn owns(&self, owner: &Session) -> bool {
+        if owner.client_user_id() != self.client_user_id {
+            false
+        } else if !self.client_user_id.is_null() {
+            true
+        } else if let Some(ref client_certificate) = self.client_certificate {
+            owner
+                .client_certificate()
+                .as_ref()
+                .is_some_and(|cert| cert.as_byte_string() == *client_certificate)
+        } else {
+            owner.client_certificate().is_none()
+                && !self.application_uri.is_empty()
+                && owner.client_description().application_uri == self.application_uri
+        }
+    }
+}
+
 /// The subscription service. Allows the client to create, modify and delete subscriptions of monitored items
 /// on the server and to request publish of notifications.
 pub(crate) struct SubscriptionService;
@@ -44,6 +91,11 @@ impl SubscriptionService {
             && subscriptions.len() >= server_state.max_subscriptions
         {
             self.service_fault(&request.request_header, StatusCode::BadTooManySubscriptions)
+        } else if server_state.max_server_subscriptions > 0
+            && server_state.subscription_count() >= server_state.max_server_subscriptions
+        {
+            // The limit across all sessions of the server
+            self.service_fault(&request.request_header, StatusCode::BadTooManySubscriptions)
         } else {
             let subscription_id = server_state.create_subscription_id();
 
@@ -116,6 +168,7 @@ impl SubscriptionService {
             subscription.set_priority(request.priority);
             subscription.reset_lifetime_counter();
             subscription.reset_keep_alive_counter();
+            subscription.on_modify();
             // ...max_notifications_per_publish??
 
             ModifySubscriptionResponse {
@@ -164,10 +217,14 @@ impl SubscriptionService {
         }
     }
 
-    /// Handles a TransferSubscriptionsRequest
+    /// Handles a TransferSubscriptionsRequest. Each subscription is looked up in the other
+    /// sessions on the server and moved to this session, along with the notifications it has
+    /// that are still waiting to be acknowledged. See OPC UA Part 4 5.13.7
     pub fn transfer_subscriptions(
         &self,
-        _session: Arc<RwLock<Session>>,
+        now: &DateTimeUtc,
+        session: Arc<RwLock<Session>>,
+        session_manager: Arc<RwLock<SessionManager>>,
         request: &TransferSubscriptionsRequest,
     ) -> SupportedMessage {
         if is_empty_option_vec!(request.subscription_ids) {
@@ -175,13 +232,17 @@ impl SubscriptionService {
         } else {
             let subscription_ids = request.subscription_ids.as_ref().unwrap();
             let results = {
-                // TODO this is a stub. The real thing should look up subscriptions belonging to
-                //  other sessions and transfer them across to this one.
+                let session_manager = trace_read_lock!(session_manager);
                 let results = subscription_ids
                     .iter()
-                    .map(|_subscription_id| TransferResult {
-                        status_code: StatusCode::BadSubscriptionIdInvalid,
-                        available_sequence_numbers: None,
+                    .map(|subscription_id| {
+                        Self::transfer_subscription(
+                            now,
+                            &session,
+                            &session_manager,
+                            *subscription_id,
+                            request.send_initial_values,
+                        )
                     })
                     .collect::<Vec<TransferResult>>();
                 Some(results)
@@ -196,6 +257,84 @@ impl SubscriptionService {
         }
     }
 
+    /// Transfers a single subscription from whichever session owns it to `session`.
+    fn transfer_subscription(
+        now: &DateTimeUtc,
+        session: &Arc<RwLock<Session>>,
+        session_manager: &SessionManager,
+        subscription_id: u32,
+        send_initial_values: bool,
+    ) -> TransferResult {
+        let client = {
+            let mut session = trace_write_lock!(session);
+            // Transferring a subscription the session already owns does nothing
+            if session.subscriptions().contains(subscription_id) {
+                if send_initial_values {
+                    if let Some(subscription) = session.subscriptions_mut().get_mut(subscription_id)
+                    {
+                        subscription.set_resend_data();
+                    }
+                }
+                return TransferResult {
+                    status_code: StatusCode::Good,
+                    available_sequence_numbers: session
+                        .subscriptions()
+                        .available_sequence_numbers(subscription_id),
+                };
+            }
+            TransferClient::from(&*session)
+        };
+
+        let owner = session_manager.sessions.values().find(|s| {
+            !Arc::ptr_eq(s, session)
+                && trace_read_lock!(s)
+                    .subscriptions()
+                    .contains(subscription_id)
+        });
+        let Some(owner) = owner else {
+            return TransferResult {
+                status_code: StatusCode::BadSubscriptionIdInvalid,
+                available_sequence_numbers: None,
+            };
+        };
+
+        let transferred = {
+            let mut owner = trace_write_lock!(owner);
+            // Only the user that owns the subscription may take it over
+            if !client.owns(&owner) {
+                return TransferResult {
+                    status_code: StatusCode::BadUserAccessDenied,
+                    available_sequence_numbers: None,
+                };
+            }
+            owner.subscriptions_mut().transfer_out(subscription_id, now)
+        };
+        let Some((mut subscription, notifications)) = transferred else {
+            return TransferResult {
+                status_code: StatusCode::BadSubscriptionIdInvalid,
+                available_sequence_numbers: None,
+            };
+        };
+
+        debug!(
+            "Transferring subscription {} with {} unacknowledged notifications",
+            subscription_id,
+            notifications.len()
+        );
+        subscription.reset_lifetime_counter();
+        if send_initial_values {
+            subscription.set_resend_data();
+        }
+
+        let mut session = trace_write_lock!(session);
+        let subscriptions = session.subscriptions_mut();
+        subscriptions.transfer_in(subscription, notifications);
+        TransferResult {
+            status_code: StatusCode::Good,
+            available_sequence_numbers: subscriptions.available_sequence_numbers(subscription_id),
+        }
+    }
+
     /// Handles a DeleteSubscriptionsRequest
     pub fn delete_subscriptions(
         &self,
@@ -291,10 +430,15 @@ impl SubscriptionService {
         requested_max_keep_alive_count: u32,
         requested_lifetime_count: u32,
     ) -> (Duration, u32, u32) {
+        // NaN is revised to the minimum by f64::max
         let revised_publishing_interval = f64::max(
             requested_publishing_interval,
             server_state.min_publishing_interval_ms,
-        );
+        )
+        .min(f64::max(
+            constants::MAX_PUBLISHING_INTERVAL_MS,
+            server_state.min_publishing_interval_ms,
+        ));
         let revised_max_keep_alive_count =
             if requested_max_keep_alive_count > server_state.max_keep_alive_count {
                 server_state.max_keep_alive_count
@@ -304,7 +448,7 @@ impl SubscriptionService {
                 requested_max_keep_alive_count
             };
         // Lifetime count must exceed keep alive count by at least a multiple of
-        let min_lifetime_count = revised_max_keep_alive_count * 3;
+        let min_lifetime_count = revised_max_keep_alive_count.saturating_mul(3);
         let revised_lifetime_count = if requested_lifetime_count < min_lifetime_count {
             min_lifetime_count
         } else if requested_lifetime_count > server_state.max_lifetime_count {
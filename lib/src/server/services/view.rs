@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2024 Adam Lock
 
+use std::collections::HashSet;
 use std::result::Result;
 use std::sync::Arc;
 
+use chrono::Utc;
+
 use crate::core::supported_message::SupportedMessage;
 use crate::crypto::random;
 use crate::sync::*;
@@ -12,6 +15,7 @@ use crate::types::{node_ids::ReferenceTypeId, status_code::StatusCode, *};
 
 use crate::server::{
     address_space::{relative_path, AddressSpace},
+    config::OperationalLimits,
     continuation_point::BrowseContinuationPoint,
     services::Service,
     session::Session,
@@ -45,55 +49,92 @@ impl ViewService {
             let mut session = trace_write_lock!(session);
             let address_space = trace_read_lock!(address_space);
 
-            let view = &request.view;
-            if !view.view_id.is_null() || !view.timestamp.is_null() {
-                // Views are not supported
-                info!("Browse request ignored because view was specified (views not supported)");
-                self.service_fault(&request.request_header, StatusCode::BadViewIdUnknown)
-            } else {
-                // debug!("Browse request = {:#?}", request);
-                let nodes_to_browse = request.nodes_to_browse.as_ref().unwrap();
-                if nodes_to_browse.len() <= server_state.operational_limits.max_nodes_per_browse {
-                    // Max references per node. This should be server configurable but the constant
-                    // is generous. TODO this value needs to adapt for the max message size
-                    const DEFAULT_MAX_REFERENCES_PER_NODE: u32 = 255;
-                    let max_references_per_node = if request.requested_max_references_per_node == 0
-                    {
-                        // Client imposes no limit
-                        DEFAULT_MAX_REFERENCES_PER_NODE
-                    } else if request.requested_max_references_per_node
-                        > DEFAULT_MAX_REFERENCES_PER_NODE
-                    {
-                        // Client limit exceeds default
-                        DEFAULT_MAX_REFERENCES_PER_NODE
+            match Self::find_view_nodes(&address_space, &request.view) {
+                Err(status_code) => {
+                    info!(
+                        "Browse request ignored because its view is invalid, {}",
+                        status_code
+                    );
+                    self.service_fault(&request.request_header, status_code)
+                }
+                Ok(view_nodes) => {
+                    // debug!("Browse request = {:#?}", request);
+                    let nodes_to_browse = request.nodes_to_browse.as_ref().unwrap();
+                    if OperationalLimits::allows(
+                        server_state.operational_limits.max_nodes_per_browse,
+                        nodes_to_browse.len(),
+                    ) {
+                        // Max references per node. This should be server configurable but the constant
+                        // is generous. TODO this value needs to adapt for the max message size
+                        const DEFAULT_MAX_REFERENCES_PER_NODE: u32 = 255;
+                        let max_references_per_node =
+                            if request.requested_max_references_per_node == 0 {
+                                // Client imposes no limit
+                                DEFAULT_MAX_REFERENCES_PER_NODE
+                            } else if request.requested_max_references_per_node
+                                > DEFAULT_MAX_REFERENCES_PER_NODE
+                            {
+                                // Client limit exceeds default
+                                DEFAULT_MAX_REFERENCES_PER_NODE
+                            } else {
+                                request.requested_max_references_per_node
+                            };
+                        // Browse the nodes
+                        session.remove_expired_browse_continuation_points(&address_space);
+                        let results = Some(Self::browse_nodes(
+                            &mut session,
+                            &address_space,
+                            view_nodes.as_ref(),
+                            nodes_to_browse,
+                            max_references_per_node as usize,
+                        ));
+                        let diagnostic_infos = None;
+                        BrowseResponse {
+                            response_header: ResponseHeader::new_good(&request.request_header),
+                            results,
+                            diagnostic_infos,
+                        }
+                        .into()
                     } else {
-                        request.requested_max_references_per_node
-                    };
-                    // Browse the nodes
-                    let results = Some(Self::browse_nodes(
-                        &mut session,
-                        &address_space,
-                        nodes_to_browse,
-                        max_references_per_node as usize,
-                    ));
-                    let diagnostic_infos = None;
-                    BrowseResponse {
-                        response_header: ResponseHeader::new_good(&request.request_header),
-                        results,
-                        diagnostic_infos,
+                        error!(
+                            "Browse request too many nodes to browse {}",
+                            nodes_to_browse.len()
+                        );
+                        self.service_fault(
+                            &request.request_header,
+                            StatusCode::BadTooManyOperations,
+                        )
                     }
-                    .into()
-                } else {
-                    error!(
-                        "Browse request too many nodes to browse {}",
-                        nodes_to_browse.len()
-                    );
-                    self.service_fault(&request.request_header, StatusCode::BadTooManyOperations)
                 }
             }
         }
     }
 
+    /// Finds the nodes in the view of a Browse or QueryFirst request, or `None` if the request
+    /// has no view so every node may be returned. Views are not versioned or historical, so a
+    /// view version or timestamp is rejected.
+    pub(crate) fn find_view_nodes(
+        address_space: &AddressSpace,
+        view: &ViewDescription,
+    ) -> Result<Option<HashSet<NodeId>>, StatusCode> {
+        if view.view_id.is_null() {
+            if view.timestamp.is_null() && view.view_version == 0 {
+                Ok(None)
+            } else {
+                Err(StatusCode::BadViewIdUnknown)
+            }
+        } else {
+            let view_nodes = address_space.find_view_nodes(&view.view_id)?;
+            if !view.timestamp.is_null() {
+                Err(StatusCode::BadViewTimestampInvalid)
+            } else if view.view_version != 0 {
+                Err(StatusCode::BadViewVersionInvalid)
+            } else {
+                Ok(Some(view_nodes))
+            }
+        }
+    }
+
     pub fn browse_next(
         &self,
         session: Arc<RwLock<Session>>,
@@ -151,7 +192,7 @@ impl ViewService {
             let max_browse_paths_per_translate = server_state
                 .operational_limits
                 .max_nodes_per_translate_browse_paths_to_node_ids;
-            if browse_paths.len() <= max_browse_paths_per_translate {
+            if OperationalLimits::allows(max_browse_paths_per_translate, browse_paths.len()) {
                 let results = browse_paths
                     .iter()
                     .enumerate()
@@ -230,9 +271,10 @@ impl ViewService {
         } else {
             let mut server_state = trace_write_lock!(server_state);
             let nodes_to_register = request.nodes_to_register.as_ref().unwrap();
-            if nodes_to_register.len()
-                <= server_state.operational_limits.max_nodes_per_register_nodes
-            {
+            if OperationalLimits::allows(
+                server_state.operational_limits.max_nodes_per_register_nodes,
+                nodes_to_register.len(),
+            ) {
                 if let Some(ref mut callback) = server_state.register_nodes_callback {
                     match callback.register_nodes(session, &nodes_to_register[..]) {
                         Ok(registered_node_ids) => RegisterNodesResponse {
@@ -272,9 +314,10 @@ impl ViewService {
         } else {
             let mut server_state = trace_write_lock!(server_state);
             let nodes_to_unregister = request.nodes_to_unregister.as_ref().unwrap();
-            if nodes_to_unregister.len()
-                <= server_state.operational_limits.max_nodes_per_register_nodes
-            {
+            if OperationalLimits::allows(
+                server_state.operational_limits.max_nodes_per_register_nodes,
+                nodes_to_unregister.len(),
+            ) {
                 if let Some(ref mut callback) = server_state.unregister_nodes_callback {
                     match callback.unregister_nodes(session, &nodes_to_unregister[..]) {
                         Ok(_) => UnregisterNodesResponse {
@@ -303,6 +346,7 @@ impl ViewService {
     fn browse_nodes(
         session: &mut Session,
         address_space: &AddressSpace,
+        view_nodes: Option<&HashSet<NodeId>>,
         nodes_to_browse: &[BrowseDescription],
         max_references_per_node: usize,
     ) -> Vec<BrowseResult> {
@@ -312,6 +356,7 @@ impl ViewService {
                 match Self::browse_node(
                     session,
                     address_space,
+                    view_nodes,
                     0,
                     node_to_browse,
                     max_references_per_node,
@@ -330,13 +375,25 @@ impl ViewService {
     fn browse_node(
         session: &mut Session,
         address_space: &AddressSpace,
+        view_nodes: Option<&HashSet<NodeId>>,
         starting_index: usize,
         node_to_browse: &BrowseDescription,
         max_references_per_node: usize,
     ) -> Result<BrowseResult, StatusCode> {
         // Node must exist or there will be no references
-        if node_to_browse.node_id.is_null() || !address_space.node_exists(&node_to_browse.node_id) {
-            return Err(StatusCode::BadNodeIdUnknown);
+        let node = match address_space.find_node(&node_to_browse.node_id) {
+            Some(node) if session.is_browsable(&node_to_browse.node_id) => node,
+            _ => return Err(StatusCode::BadNodeIdUnknown),
+        };
+        let in_view = |node_id: &NodeId| view_nodes.is_none_or(|v| v.contains(node_id));
+        if !in_view(&node_to_browse.node_id) {
+            return Err(StatusCode::BadNodeNotInView);
+        }
+        if !session
+            .permissions(node.as_node())
+            .contains(PermissionType::Browse)
+        {
+            return Err(StatusCode::BadUserAccessDenied);
         }
 
         //debug!("Node to browse = {:?}", node_to_browse);
@@ -359,6 +416,16 @@ impl ViewService {
             node_to_browse.browse_direction,
             reference_type_id,
         );
+        // A view only contains nodes of this server
+        let remote_references = if view_nodes.is_none() {
+            address_space.find_remote_references(
+                &node_to_browse.node_id,
+                node_to_browse.browse_direction,
+                reference_type_id,
+            )
+        } else {
+            Vec::new()
+        };
 
         let result_mask =
             BrowseDescriptionResultMask::from_bits_truncate(node_to_browse.result_mask);
@@ -372,7 +439,10 @@ impl ViewService {
                 continue;
             }
             let target_node_id = reference.target_node.clone();
-            if target_node_id.is_null() {
+            if target_node_id.is_null()
+                || !in_view(&target_node_id)
+                || !session.is_browsable(&target_node_id)
+            {
                 continue;
             }
             let target_node = address_space.find_node(&target_node_id);
@@ -381,6 +451,12 @@ impl ViewService {
             }
 
             let target_node = target_node.unwrap().as_node();
+            if !session
+                .permissions(target_node)
+                .contains(PermissionType::Browse)
+            {
+                continue;
+            }
             let target_node_class = target_node.node_class();
 
             // Skip target nodes not required by the mask
@@ -458,10 +534,37 @@ impl ViewService {
             reference_descriptions.push(reference_description);
         }
 
+        // References to nodes held by other servers. Nothing is known about the target node
+        // except its id, so the node class and names are left unspecified and the node class
+        // mask does not exclude them.
+        remote_references.into_iter().for_each(|reference| {
+            let reference_type_id =
+                if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_REFERENCE_TYPE) {
+                    reference.reference_type
+                } else {
+                    NodeId::null()
+                };
+            let is_forward =
+                if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_IS_FORWARD) {
+                    reference.is_forward
+                } else {
+                    true
+                };
+            reference_descriptions.push(ReferenceDescription {
+                node_id: reference.target_node,
+                reference_type_id,
+                is_forward,
+                node_class: NodeClass::Unspecified,
+                browse_name: QualifiedName::null(),
+                display_name: LocalizedText::null(),
+                type_definition: ExpandedNodeId::null(),
+            });
+        });
+
         Ok(Self::reference_description_to_browse_result(
             session,
             address_space,
-            &reference_descriptions,
+            &Arc::new(Mutex::new(reference_descriptions)),
             0,
             max_references_per_node,
         ))
@@ -479,12 +582,11 @@ impl ViewService {
                 "Browsing from continuation point {}",
                 continuation_point.id.as_base64()
             );
-            let reference_descriptions = continuation_point.reference_descriptions.lock();
             // Use the existing result. This may result in another continuation point being created
             Self::reference_description_to_browse_result(
                 session,
                 address_space,
-                &reference_descriptions,
+                &continuation_point.reference_descriptions,
                 continuation_point.starting_index,
                 continuation_point.max_references_per_node,
             )
@@ -505,37 +607,43 @@ impl ViewService {
     fn reference_description_to_browse_result(
         session: &mut Session,
         address_space: &AddressSpace,
-        reference_descriptions: &[ReferenceDescription],
+        all_reference_descriptions: &Arc<Mutex<Vec<ReferenceDescription>>>,
         starting_index: usize,
         max_references_per_node: usize,
     ) -> BrowseResult {
+        let reference_descriptions = all_reference_descriptions.lock();
         let references_remaining = reference_descriptions.len() - starting_index;
-        let (reference_descriptions, continuation_point) = if max_references_per_node > 0
-            && references_remaining > max_references_per_node
-        {
+        if max_references_per_node > 0 && references_remaining > max_references_per_node {
             // There is too many results for a single browse result, so only a result will be used
             let next_starting_index = starting_index + max_references_per_node;
             let reference_descriptions_slice =
                 reference_descriptions[starting_index..next_starting_index].to_vec();
 
-            // TODO it is wasteful to create a new reference_descriptions vec if the caller to this fn
-            //  already has a ref counted reference_descriptions. We could clone the Arc if the fn could
-            //  be factored to allow for that
-
-            // Create a continuation point for the remainder of the result. The point will hold the entire result
+            // Create a continuation point for the remainder of the result. The point shares the
+            // entire result with any point it was continued from.
             let continuation_point = random::byte_string(6);
 
             debug!("References remaining {} exceeds max references {}, returning range {}..{} and creating new continuation point {}", references_remaining, max_references_per_node, starting_index, next_starting_index, continuation_point.as_base64());
 
-            session.add_browse_continuation_point(BrowseContinuationPoint {
+            match session.add_browse_continuation_point(BrowseContinuationPoint {
                 id: continuation_point.clone(),
                 address_space_last_modified: address_space.last_modified(),
+                created: Utc::now(),
                 max_references_per_node,
                 starting_index: next_starting_index,
-                reference_descriptions: Arc::new(Mutex::new(reference_descriptions.to_vec())),
-            });
-
-            (reference_descriptions_slice, continuation_point)
+                reference_descriptions: all_reference_descriptions.clone(),
+            }) {
+                Ok(_) => BrowseResult {
+                    status_code: StatusCode::Good,
+                    continuation_point,
+                    references: Some(reference_descriptions_slice),
+                },
+                Err(status_code) => BrowseResult {
+                    status_code,
+                    continuation_point: ByteString::null(),
+                    references: None,
+                },
+            }
         } else {
             // Returns the remainder of the results
             let reference_descriptions_slice = reference_descriptions[starting_index..].to_vec();
@@ -544,12 +652,11 @@ impl ViewService {
                 starting_index,
                 reference_descriptions.len()
             );
-            (reference_descriptions_slice, ByteString::null())
-        };
-        BrowseResult {
-            status_code: StatusCode::Good,
-            continuation_point,
-            references: Some(reference_descriptions),
+            BrowseResult {
+                status_code: StatusCode::Good,
+                continuation_point: ByteString::null(),
+                references: Some(reference_descriptions_slice),
+            }
         }
     }
 }
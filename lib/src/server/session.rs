@@ -16,16 +16,20 @@ use crate::sync::*;
 use crate::types::{service_types::PublishRequest, status_code::StatusCode, *};
 
 use crate::server::{
-    address_space::{AddressSpace, UserAccessLevel},
+    address_space::{node::Node, AddressSpace, UserAccessLevel},
+    callbacks::AuthorizationHandler,
     continuation_point::BrowseContinuationPoint,
     diagnostics::ServerDiagnostics,
     identity_token::IdentityToken,
-    session_diagnostics::SessionDiagnostics,
+    session_diagnostics::*,
     state::ServerState,
     subscriptions::subscription::TickReason,
     subscriptions::subscriptions::Subscriptions,
 };
 
+#[cfg(feature = "query-services")]
+use crate::server::continuation_point::QueryContinuationPoint;
+
 /// Session info holds information about a session created by CreateSession service
 #[derive(Clone)]
 pub struct SessionInfo {}
@@ -73,14 +77,75 @@ impl SessionManager {
     /// Puts all sessions into a terminated state, deregisters them, and clears the map
     pub fn clear(&mut self, address_space: Arc<RwLock<AddressSpace>>) {
         for (_nodeid, session) in self.sessions.drain() {
-            let mut session = trace_write_lock!(session);
-            session.set_terminated();
-            let mut space = trace_write_lock!(address_space);
-            let diagnostics = trace_write_lock!(session.session_diagnostics);
-            diagnostics.deregister_session(&session, &mut space);
+            Self::terminate(&session, &address_space);
+        }
+    }
+
+    /// Puts the session into a terminated state and removes it, e.g. when the server closes a
+    /// session that has been idle for too long. Returns `false` if there is no such session.
+    pub(crate) fn terminate_session(
+        &mut self,
+        session_id: &NodeId,
+        address_space: &Arc<RwLock<AddressSpace>>,
+    ) -> bool {
+        if let Some(session) = self.sessions.remove(session_id) {
+            Self::terminate(&session, address_space);
+            self.sessions_terminated = self.sessions.is_empty();
+            true
+        } else {
+            false
         }
     }
 
+    /// Closes the sessions that have gone without a service call for longer than their session
+    /// timeout, e.g. because their client went away. Returns the ids of the sessions closed.
+    pub(crate) fn close_timed_out_sessions(
+        &mut self,
+        now: &DateTimeUtc,
+        address_space: &Arc<RwLock<AddressSpace>>,
+    ) -> Vec<NodeId> {
+        let timed_out = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| trace_read_lock!(session).is_timed_out(now))
+            .map(|(session_id, _)| session_id.clone())
+            .collect::<Vec<_>>();
+        for session_id in &timed_out {
+            warn!("Session {} is closed because it has timed out", session_id);
+            self.terminate_session(session_id, address_space);
+        }
+        timed_out
+    }
+
+    /// Returns the diagnostics of every session. A session that is locked for writing is left
+    /// out rather than waited for, because the caller may hold a lock that its holder needs.
+    pub(crate) fn session_diagnostics(&self) -> Vec<SessionDiagnosticsDataType> {
+        self.sessions
+            .values()
+            .filter_map(|session| session.try_read_recursive())
+            .map(|session| session.diagnostics_data())
+            .collect()
+    }
+
+    /// Returns the diagnostics of every subscription of every session. As with
+    /// `session_diagnostics()`, a session that is locked for writing is left out.
+    pub(crate) fn subscription_diagnostics(&self) -> Vec<SubscriptionDiagnosticsDataType> {
+        self.sessions
+            .values()
+            .filter_map(|session| session.try_read_recursive())
+            .flat_map(|session| session.subscriptions().diagnostics(session.session_id()))
+            .collect()
+    }
+
+    fn terminate(session: &Arc<RwLock<Session>>, address_space: &Arc<RwLock<AddressSpace>>) {
+        let mut session = trace_write_lock!(session);
+        session.set_terminated();
+        let mut space = trace_write_lock!(address_space);
+        let diagnostics = trace_write_lock!(session.session_diagnostics);
+        diagnostics.deregister_session(&session, &mut space);
+        space.delete_session_nodes(session.session_id());
+    }
+
     /// Find a session by its session id and return it.
     pub fn find_session_by_id(&self, session_id: &NodeId) -> Option<Arc<RwLock<Session>>> {
         self.sessions
@@ -140,12 +205,25 @@ impl SessionManager {
     }
 }
 
+/// How long a session has been idle, relative to the idle session timeout of its endpoint
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum IdleSessionState {
+    /// The session is in use or is not due to be closed yet
+    Active,
+    /// The session will soon be closed unless the client calls a service
+    Warning,
+    /// The session has been idle for longer than the timeout and should be closed
+    Expired,
+}
+
 /// The Session is any state maintained between the client and server
 pub struct Session {
     /// The session identifier
     session_id: NodeId,
     /// Security policy
     security_policy_uri: String,
+    /// Security mode of the secure channel the session was created on
+    security_mode: MessageSecurityMode,
     /// Secure channel id
     secure_channel_id: u32,
     /// Client's certificate
@@ -156,10 +234,21 @@ pub struct Session {
     session_nonce: ByteString,
     /// Session name (supplied by client)
     session_name: UAString,
+    /// Description of the client application (supplied by client)
+    client_description: ApplicationDescription,
+    /// Server uri that the client asked for (supplied by client)
+    server_uri: UAString,
+    /// Time that the session was created
+    client_connection_time: DateTime,
     /// Session timeout
     session_timeout: f64,
     /// User identity token
     user_identity: IdentityToken,
+    /// Id of the user token that the user was authenticated with, if the session is activated
+    user_token_id: Option<String>,
+    /// Roles of the user, which decide what the session may do with nodes that have role
+    /// permissions
+    roles: Vec<NodeId>,
     /// Session's preferred locale ids
     locale_ids: Option<Vec<UAString>>,
     /// Negotiated max request message size
@@ -172,6 +261,9 @@ pub struct Session {
     max_browse_continuation_points: usize,
     /// Browse continuation points (oldest to newest)
     browse_continuation_points: VecDeque<BrowseContinuationPoint>,
+    /// Query continuation points (oldest to newest)
+    #[cfg(feature = "query-services")]
+    query_continuation_points: VecDeque<QueryContinuationPoint>,
     /// Diagnostics associated with the server
     diagnostics: Arc<RwLock<ServerDiagnostics>>,
     /// Diagnostics associated with the session
@@ -187,10 +279,22 @@ pub struct Session {
     /// Flag indicating broadly if this session may modify the address space by adding or removing
     /// nodes or references to nodes.
     can_modify_address_space: bool,
+    /// Flag indicating if nodes added by this session are deleted when the session closes
+    session_lifetime_nodes: bool,
     /// Timestamp of the last service request to have happened (only counts service requests while there is a session)
     last_service_request_timestamp: DateTimeUtc,
+    /// Timestamp of the last service request other than publishing, used to detect idle sessions
+    last_activity_timestamp: DateTimeUtc,
+    /// Time in millis that the session may be idle before it is closed, 0 for no limit
+    idle_session_timeout: i64,
+    /// Time in millis before an idle session is closed that a warning is raised
+    idle_session_warning: i64,
+    /// Flag indicating the warning was raised for the current idle period
+    idle_session_warned: bool,
     /// Subscriptions associated with the session
     subscriptions: Subscriptions,
+    /// Decides what the user of the session may access
+    authorization_handler: Option<Arc<dyn AuthorizationHandler + Send + Sync>>,
 }
 
 impl Drop for Session {
@@ -205,6 +309,7 @@ impl Session {
     #[cfg(test)]
     pub fn new_no_certificate_store() -> Session {
         let max_browse_continuation_points = super::constants::MAX_BROWSE_CONTINUATION_POINTS;
+        let authorization_handler = None;
         let session = Session {
             subscriptions: Subscriptions::new(100, PUBLISH_REQUEST_TIMEOUT),
             session_id: next_session_id(),
@@ -215,21 +320,35 @@ impl Session {
             terminated_at: chrono::Utc::now(),
             client_certificate: None,
             security_policy_uri: String::new(),
+            security_mode: MessageSecurityMode::None,
             authentication_token: NodeId::null(),
             session_nonce: ByteString::null(),
             session_name: UAString::null(),
+            client_description: ApplicationDescription::default(),
+            server_uri: UAString::null(),
+            client_connection_time: DateTime::now(),
             session_timeout: 0f64,
             user_identity: IdentityToken::None,
+            user_token_id: None,
+            roles: Vec::new(),
             locale_ids: None,
             max_request_message_size: 0,
             max_response_message_size: 0,
             endpoint_url: UAString::null(),
             max_browse_continuation_points,
             browse_continuation_points: VecDeque::with_capacity(max_browse_continuation_points),
+            #[cfg(feature = "query-services")]
+            query_continuation_points: VecDeque::new(),
             can_modify_address_space: true,
+            session_lifetime_nodes: false,
             diagnostics: Arc::new(RwLock::new(ServerDiagnostics::default())),
             session_diagnostics: Arc::new(RwLock::new(SessionDiagnostics::default())),
             last_service_request_timestamp: Utc::now(),
+            last_activity_timestamp: Utc::now(),
+            idle_session_timeout: 0,
+            idle_session_warning: 0,
+            idle_session_warned: false,
+            authorization_handler,
         };
 
         {
@@ -246,9 +365,13 @@ impl Session {
         let server_state = trace_read_lock!(server_state);
         let max_subscriptions = server_state.max_subscriptions;
         let diagnostics = server_state.diagnostics.clone();
-        let can_modify_address_space = {
+        let authorization_handler = server_state.authorization_handler.clone();
+        let (can_modify_address_space, session_lifetime_nodes) = {
             let config = trace_read_lock!(server_state.config);
-            config.limits.clients_can_modify_address_space
+            (
+                config.limits.clients_can_modify_address_space,
+                config.limits.session_lifetime_nodes,
+            )
         };
 
         let session = Session {
@@ -261,21 +384,35 @@ impl Session {
             terminated_at: chrono::Utc::now(),
             client_certificate: None,
             security_policy_uri: String::new(),
+            security_mode: MessageSecurityMode::None,
             authentication_token: NodeId::null(),
             session_nonce: ByteString::null(),
             session_name: UAString::null(),
+            client_description: ApplicationDescription::default(),
+            server_uri: UAString::null(),
+            client_connection_time: DateTime::now(),
             session_timeout: 0f64,
             user_identity: IdentityToken::None,
+            user_token_id: None,
+            roles: Vec::new(),
             locale_ids: None,
             max_request_message_size: 0,
             max_response_message_size: 0,
             endpoint_url: UAString::null(),
             max_browse_continuation_points,
             browse_continuation_points: VecDeque::with_capacity(max_browse_continuation_points),
+            #[cfg(feature = "query-services")]
+            query_continuation_points: VecDeque::new(),
             can_modify_address_space,
+            session_lifetime_nodes,
             diagnostics,
             session_diagnostics: Arc::new(RwLock::new(SessionDiagnostics::default())),
             last_service_request_timestamp: Utc::now(),
+            last_activity_timestamp: Utc::now(),
+            idle_session_timeout: 0,
+            idle_session_warning: 0,
+            idle_session_warned: false,
+            authorization_handler,
         };
         {
             let mut diagnostics = trace_write_lock!(session.diagnostics);
@@ -350,14 +487,44 @@ impl Session {
         self.endpoint_url = endpoint_url;
     }
 
+    pub fn security_policy_uri(&self) -> &str {
+        &self.security_policy_uri
+    }
+
     pub fn set_security_policy_uri(&mut self, security_policy_uri: &str) {
         self.security_policy_uri = security_policy_uri.to_string();
     }
 
+    pub fn security_mode(&self) -> MessageSecurityMode {
+        self.security_mode
+    }
+
+    pub fn set_security_mode(&mut self, security_mode: MessageSecurityMode) {
+        self.security_mode = security_mode;
+    }
+
     pub fn set_user_identity(&mut self, user_identity: IdentityToken) {
         self.user_identity = user_identity;
     }
 
+    /// Returns the id of the user token that the user of the session was authenticated with
+    pub fn user_token_id(&self) -> Option<&str> {
+        self.user_token_id.as_deref()
+    }
+
+    pub fn set_user_token_id(&mut self, user_token_id: Option<String>) {
+        self.user_token_id = user_token_id;
+    }
+
+    /// Returns the node ids of the roles granted to the user of the session
+    pub fn roles(&self) -> &[NodeId] {
+        &self.roles
+    }
+
+    pub fn set_roles(&mut self, roles: Vec<NodeId>) {
+        self.roles = roles;
+    }
+
     pub fn last_service_request_timestamp(&self) -> DateTimeUtc {
         self.last_service_request_timestamp
     }
@@ -369,6 +536,52 @@ impl Session {
         self.last_service_request_timestamp = last_service_request_timestamp;
     }
 
+    /// Tests if the session has gone without a service call for longer than its session timeout
+    pub fn is_timed_out(&self, now: &DateTimeUtc) -> bool {
+        let elapsed = now.signed_duration_since(self.last_service_request_timestamp);
+        self.session_timeout > 0.0 && elapsed.num_milliseconds() as f64 > self.session_timeout
+    }
+
+    /// Sets the time in millis that the session may go without calling a service other than
+    /// publishing before it is closed, and the time before that when a warning is raised.
+    pub fn set_idle_session_timeout(
+        &mut self,
+        idle_session_timeout: i64,
+        idle_session_warning: i64,
+    ) {
+        self.idle_session_timeout = idle_session_timeout;
+        self.idle_session_warning = idle_session_warning;
+    }
+
+    pub fn last_activity_timestamp(&self) -> DateTimeUtc {
+        self.last_activity_timestamp
+    }
+
+    /// Records a service call that shows the session is in use, which ends any idle period
+    pub fn set_last_activity_timestamp(&mut self, last_activity_timestamp: DateTimeUtc) {
+        self.last_activity_timestamp = last_activity_timestamp;
+        self.idle_session_warned = false;
+    }
+
+    /// Tests how long the session has been idle for. A warning is returned once per idle period.
+    pub(crate) fn idle_state(&mut self, now: &DateTimeUtc) -> IdleSessionState {
+        if self.idle_session_timeout <= 0 {
+            return IdleSessionState::Active;
+        }
+        let idle = now.signed_duration_since(self.last_activity_timestamp);
+        let idle = idle.num_milliseconds();
+        if idle >= self.idle_session_timeout {
+            IdleSessionState::Expired
+        } else if !self.idle_session_warned
+            && idle >= self.idle_session_timeout - self.idle_session_warning
+        {
+            self.idle_session_warned = true;
+            IdleSessionState::Warning
+        } else {
+            IdleSessionState::Active
+        }
+    }
+
     pub fn locale_ids(&self) -> &Option<Vec<UAString>> {
         &self.locale_ids
     }
@@ -401,10 +614,81 @@ impl Session {
         self.session_name = session_name;
     }
 
+    pub fn client_description(&self) -> &ApplicationDescription {
+        &self.client_description
+    }
+
+    pub fn set_client_description(&mut self, client_description: ApplicationDescription) {
+        self.client_description = client_description;
+    }
+
+    pub fn set_server_uri(&mut self, server_uri: UAString) {
+        self.server_uri = server_uri;
+    }
+
     pub(crate) fn session_diagnostics(&self) -> Arc<RwLock<SessionDiagnostics>> {
         self.session_diagnostics.clone()
     }
 
+    /// Returns the diagnostics of the session, as exposed in the SessionDiagnosticsArray of the
+    /// server's address space.
+    pub(crate) fn diagnostics_data(&self) -> SessionDiagnosticsDataType {
+        let session_diagnostics = trace_read_lock!(self.session_diagnostics);
+        let counter = |key| session_diagnostics.service_counter(key);
+        let subscriptions = self.subscriptions.subscriptions();
+        SessionDiagnosticsDataType {
+            session_id: self.session_id.clone(),
+            session_name: self.session_name.clone(),
+            client_description: self.client_description.clone(),
+            server_uri: self.server_uri.clone(),
+            endpoint_url: self.endpoint_url.clone(),
+            locale_ids: self.locale_ids.clone(),
+            actual_session_timeout: self.session_timeout,
+            max_response_message_size: self.max_response_message_size,
+            client_connection_time: self.client_connection_time,
+            client_last_contact_time: DateTime::from(self.last_service_request_timestamp),
+            current_subscriptions_count: subscriptions.len() as u32,
+            current_monitored_items_count: subscriptions
+                .values()
+                .map(|subscription| subscription.monitored_items_len() as u32)
+                .sum(),
+            current_publish_requests_in_queue: self.subscriptions.publish_request_queue_len()
+                as u32,
+            total_request_count: session_diagnostics.total_request_count(),
+            unauthorized_request_count: session_diagnostics.unauthorized_request_count(),
+            read_count: counter(READ_COUNT),
+            history_read_count: counter(HISTORY_READ_COUNT),
+            write_count: counter(WRITE_COUNT),
+            history_update_count: counter(HISTORY_UPDATE_COUNT),
+            call_count: counter(CALL_COUNT),
+            create_monitored_items_count: counter(CREATE_MONITORED_ITEMS_COUNT),
+            modify_monitored_items_count: counter(MODIFY_MONITORED_ITEMS_COUNT),
+            set_monitoring_mode_count: counter(SET_MONITORING_MODE_COUNT),
+            set_triggering_count: counter(SET_TRIGGERING_COUNT),
+            delete_monitored_items_count: counter(DELETE_MONITORED_ITEMS_COUNT),
+            create_subscription_count: counter(CREATE_SUBSCRIPTION_COUNT),
+            modify_subscription_count: counter(MODIFY_SUBSCRIPTION_COUNT),
+            set_publishing_mode_count: counter(SET_PUBLISHING_MODE_COUNT),
+            publish_count: ServiceCounterDataType::default(),
+            republish_count: counter(REPUBLISH_COUNT),
+            transfer_subscriptions_count: counter(TRANSFER_SUBSCRIPTIONS_COUNT),
+            delete_subscriptions_count: counter(DELETE_SUBSCRIPTIONS_COUNT),
+            add_nodes_count: counter(ADD_NODES_COUNT),
+            add_references_count: counter(ADD_REFERENCES_COUNT),
+            delete_nodes_count: counter(DELETE_NODES_COUNT),
+            delete_references_count: counter(DELETE_REFERENCES_COUNT),
+            browse_count: counter(BROWSE_COUNT),
+            browse_next_count: counter(BROWSE_NEXT_COUNT),
+            translate_browse_paths_to_node_ids_count: counter(
+                TRANSLATE_BROWSE_PATHS_TO_NODE_IDS_COUNT,
+            ),
+            query_first_count: ServiceCounterDataType::default(),
+            query_next_count: ServiceCounterDataType::default(),
+            register_nodes_count: counter(REGISTER_NODES_COUNT),
+            unregister_nodes_count: counter(UNREGISTER_NODES_COUNT),
+        }
+    }
+
     pub(crate) fn subscriptions(&self) -> &Subscriptions {
         &self.subscriptions
     }
@@ -430,7 +714,27 @@ impl Session {
         address_space: &AddressSpace,
         reason: TickReason,
     ) -> Result<(), StatusCode> {
-        self.subscriptions.tick(now, address_space, reason)
+        let span = tracing::debug_span!(
+            "publish_cycle",
+            session_id = %self.session_id,
+            ?reason,
+            subscriptions = self.subscriptions.len(),
+        );
+        let _entered = span.enter();
+        let queued = self.subscriptions.publish_response_queue_len();
+        let result = self.subscriptions.tick(now, address_space, reason);
+        let publish_responses = self
+            .subscriptions
+            .publish_response_queue_len()
+            .saturating_sub(queued);
+        if publish_responses > 0 {
+            tracing::debug!(
+                publish_responses,
+                publish_requests = self.subscriptions.publish_request_queue_len(),
+                "Publish responses queued"
+            );
+        }
+        result
     }
 
     /// Reset the lifetime counter on the subscription, e.g. because a service references the
@@ -447,20 +751,23 @@ impl Session {
         self.subscriptions.expire_stale_publish_requests(now);
     }
 
+    /// Stores a continuation point in the session, failing with `BadNoContinuationPoints` if the
+    /// session already holds as many as it is allowed.
     pub(crate) fn add_browse_continuation_point(
         &mut self,
         continuation_point: BrowseContinuationPoint,
-    ) {
-        // Remove excess browse continuation points
-        while self.browse_continuation_points.len() >= self.max_browse_continuation_points {
-            let continuation_point = self.browse_continuation_points.pop_front();
+    ) -> Result<(), StatusCode> {
+        if self.browse_continuation_points.len() >= self.max_browse_continuation_points {
             debug!(
-                "Removing old continuation point {} to make way for new one",
-                continuation_point.unwrap().id.as_base64()
+                "Session has no free continuation points for {}",
+                continuation_point.id.as_base64()
             );
+            Err(StatusCode::BadNoContinuationPoints)
+        } else {
+            self.browse_continuation_points
+                .push_back(continuation_point);
+            Ok(())
         }
-        self.browse_continuation_points
-            .push_back(continuation_point);
     }
 
     /// Finds and REMOVES a continuation point by id.
@@ -479,14 +786,23 @@ impl Session {
         }
     }
 
+    /// Removes continuation points that are invalidated by a change to the address space or that
+    /// have gone unused for too long.
     pub(crate) fn remove_expired_browse_continuation_points(
         &mut self,
         address_space: &AddressSpace,
     ) {
+        let now = Utc::now();
         self.browse_continuation_points.retain(|continuation_point| {
             let valid = continuation_point.is_valid_browse_continuation_point(address_space);
             if !valid {
                 debug!("Continuation point {:?} is no longer valid and will be removed, address space last modified = {}", continuation_point, address_space.last_modified());
+            } else if continuation_point.is_expired(&now) {
+                debug!(
+                    "Continuation point {} has expired and will be removed",
+                    continuation_point.id.as_base64()
+                );
+                return false;
             }
             valid
         });
@@ -502,6 +818,59 @@ impl Session {
             .retain(|continuation_point| !continuation_points_set.contains(&continuation_point.id));
     }
 
+    /// Stores a query continuation point in the session, failing with `BadNoContinuationPoints` if
+    /// the session already holds as many as it is allowed.
+    #[cfg(feature = "query-services")]
+    pub(crate) fn add_query_continuation_point(
+        &mut self,
+        continuation_point: QueryContinuationPoint,
+    ) -> Result<(), StatusCode> {
+        if self.query_continuation_points.len() >= super::constants::MAX_QUERY_CONTINUATION_POINTS {
+            debug!(
+                "Session has no free query continuation points for {}",
+                continuation_point.id.as_base64()
+            );
+            Err(StatusCode::BadNoContinuationPoints)
+        } else {
+            self.query_continuation_points.push_back(continuation_point);
+            Ok(())
+        }
+    }
+
+    /// Finds and REMOVES a query continuation point by id.
+    #[cfg(feature = "query-services")]
+    pub(crate) fn find_query_continuation_point(
+        &mut self,
+        id: &ByteString,
+    ) -> Option<QueryContinuationPoint> {
+        let idx = self
+            .query_continuation_points
+            .iter()
+            .position(|continuation_point| continuation_point.id == *id)?;
+        self.query_continuation_points.remove(idx)
+    }
+
+    /// Removes query continuation points that are invalidated by a change to the address space or
+    /// that have gone unused for too long.
+    #[cfg(feature = "query-services")]
+    pub(crate) fn remove_expired_query_continuation_points(
+        &mut self,
+        address_space: &AddressSpace,
+    ) {
+        let now = Utc::now();
+        self.query_continuation_points.retain(|continuation_point| {
+            let keep = continuation_point.is_valid_query_continuation_point(address_space)
+                && !continuation_point.is_expired(&now);
+            if !keep {
+                debug!(
+                    "Query continuation point {} is no longer valid and will be removed",
+                    continuation_point.id.as_base64()
+                );
+            }
+            keep
+        });
+    }
+
     pub(crate) fn can_modify_address_space(&self) -> bool {
         self.can_modify_address_space
     }
@@ -511,14 +880,68 @@ impl Session {
         self.can_modify_address_space = can_modify_address_space;
     }
 
+    pub(crate) fn session_lifetime_nodes(&self) -> bool {
+        self.session_lifetime_nodes
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_session_lifetime_nodes(&mut self, session_lifetime_nodes: bool) {
+        self.session_lifetime_nodes = session_lifetime_nodes;
+    }
+
     pub(crate) fn effective_user_access_level(
         &self,
         user_access_level: UserAccessLevel,
-        _node_id: &NodeId,
+        node_id: &NodeId,
         _attribute_id: AttributeId,
     ) -> UserAccessLevel {
-        // TODO session could modify the user_access_level further here via user / groups
-        user_access_level
+        match self.authorization_handler {
+            Some(ref handler) => handler.user_access_level(self, node_id, user_access_level),
+            None => user_access_level,
+        }
+    }
+
+    pub(crate) fn effective_user_write_mask(
+        &self,
+        user_write_mask: WriteMask,
+        node_id: &NodeId,
+    ) -> WriteMask {
+        match self.authorization_handler {
+            Some(ref handler) => handler.user_write_mask(self, node_id, user_write_mask),
+            None => user_write_mask,
+        }
+    }
+
+    pub(crate) fn effective_user_executable(
+        &self,
+        user_executable: bool,
+        node_id: &NodeId,
+    ) -> bool {
+        match self.authorization_handler {
+            Some(ref handler) => handler.user_executable(self, node_id, user_executable),
+            None => user_executable,
+        }
+    }
+
+    /// Returns the permissions that the roles of the session's user have on the node
+    pub(crate) fn permissions(&self, node: &dyn Node) -> PermissionType {
+        node.permissions(&self.roles)
+    }
+
+    /// Tests if the user of the session may see the node when browsing
+    pub(crate) fn is_browsable(&self, node_id: &NodeId) -> bool {
+        match self.authorization_handler {
+            Some(ref handler) => handler.is_browsable(self, node_id),
+            None => true,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_authorization_handler(
+        &mut self,
+        authorization_handler: Arc<dyn AuthorizationHandler + Send + Sync>,
+    ) {
+        self.authorization_handler = Some(authorization_handler);
     }
 
     /// Helper function to return the client user id from the identity token or None of there is no user id
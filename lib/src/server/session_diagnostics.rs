@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::types::{node_ids::ObjectTypeId, service_types::ServiceCounterDataType};
+use crate::types::{
+    node_ids::{ObjectId, ObjectTypeId},
+    service_types::ServiceCounterDataType,
+};
 
 use super::{
     address_space::{address_space::AddressSpace, object::ObjectBuilder},
@@ -11,7 +14,7 @@ use super::{
 
 #[derive(Default)]
 pub(crate) struct SessionDiagnostics {
-    total_request_count: u32,
+    total_request_count: ServiceCounterDataType,
     unauthorized_request_count: u32,
     service_counters: HashMap<&'static str, ServiceCounterDataType>,
 }
@@ -30,6 +33,7 @@ impl SessionDiagnostics {
             format!("{}", session_id),
             format!("{}", session_id),
         )
+        .component_of(ObjectId::Server_ServerDiagnostics_SessionsDiagnosticsSummary)
         .has_type_definition(ObjectTypeId::SessionDiagnosticsObjectType)
         .insert(address_space);
 
@@ -115,27 +119,37 @@ impl SessionDiagnostics {
         address_space.delete(session.session_id(), true);
     }
 
-    /// Called on every request
-    pub(crate) fn request(&mut self) {
-        self.total_request_count += 1;
+    /// Called on every authorized request
+    pub(crate) fn request(&mut self, success: bool) {
+        if success {
+            self.total_request_count.success();
+        } else {
+            self.total_request_count.error();
+        }
     }
 
-    /// Called on an authorized request
+    /// Called on an unauthorized request
     pub(crate) fn unauthorized_request(&mut self) {
         self.unauthorized_request_count += 1;
-        self.total_request_count += 1;
+        self.total_request_count.error();
+    }
+
+    /// Fetches a snapshot of the count of all requests
+    pub(crate) fn total_request_count(&self) -> ServiceCounterDataType {
+        self.total_request_count.clone()
+    }
+
+    /// Fetches the count of requests that were not authorized
+    pub(crate) fn unauthorized_request_count(&self) -> u32 {
+        self.unauthorized_request_count
     }
 
     /// Fetches a snapshot of the current service counter value
-    pub(crate) fn service_counter(
-        &mut self,
-        diagnostic_key: &'static str,
-    ) -> ServiceCounterDataType {
-        if let Some(counter) = self.service_counters.get_mut(diagnostic_key) {
-            counter.clone()
-        } else {
-            ServiceCounterDataType::default()
-        }
+    pub(crate) fn service_counter(&self, diagnostic_key: &'static str) -> ServiceCounterDataType {
+        self.service_counters
+            .get(diagnostic_key)
+            .cloned()
+            .unwrap_or_default()
     }
 
     /// Increments the service counter for a successful service call
@@ -4,25 +4,25 @@
 
 //! Provides server state information, such as status, configuration, running servers and so on.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
-use crate::core::prelude::*;
+use crate::core::{comms::capture::MessageCapture, prelude::*};
 use crate::crypto::{user_identity, PrivateKey, SecurityPolicy, X509};
 use crate::sync::*;
 use crate::types::{
-    profiles,
     service_types::{
         ActivateSessionRequest, AnonymousIdentityToken, ApplicationDescription, ApplicationType,
-        EndpointDescription, RegisteredServer, ServerState as ServerStateType, SignatureData,
-        UserNameIdentityToken, UserTokenPolicy, UserTokenType, X509IdentityToken,
+        BuildInfo, EndpointDescription, RegisteredServer, ServerState as ServerStateType,
+        ServerStatusDataType, SignatureData, UserNameIdentityToken, UserTokenPolicy, UserTokenType,
+        X509IdentityToken,
     },
     status_code::StatusCode,
 };
 
 use crate::server::{
-    callbacks::{RegisterNodes, UnregisterNodes},
-    config::{ServerConfig, ServerEndpoint},
-    constants,
+    authenticator::{UserAuthenticator, UserIdentity, UserToken},
+    callbacks::{AuditSink, AuthorizationHandler, RegisterNodes, UnregisterNodes},
+    config::{Limits, OperationalLimits, ServerConfig, ServerEndpoint},
     diagnostics::ServerDiagnostics,
     events::{
         audit::{AuditEvent, AuditLog},
@@ -35,41 +35,6 @@ use crate::server::{
     },
 };
 
-pub(crate) struct OperationalLimits {
-    pub max_nodes_per_translate_browse_paths_to_node_ids: usize,
-    pub max_nodes_per_read: usize,
-    pub max_nodes_per_write: usize,
-    pub max_nodes_per_method_call: usize,
-    pub max_nodes_per_browse: usize,
-    pub max_nodes_per_register_nodes: usize,
-    pub max_nodes_per_node_management: usize,
-    pub max_monitored_items_per_call: usize,
-    pub max_nodes_per_history_read_data: usize,
-    pub max_nodes_per_history_read_events: usize,
-    pub max_nodes_per_history_update_data: usize,
-    pub max_nodes_per_history_update_events: usize,
-}
-
-impl Default for OperationalLimits {
-    fn default() -> Self {
-        Self {
-            max_nodes_per_translate_browse_paths_to_node_ids:
-                constants::MAX_NODES_PER_TRANSLATE_BROWSE_PATHS_TO_NODE_IDS,
-            max_nodes_per_read: constants::MAX_NODES_PER_READ,
-            max_nodes_per_write: constants::MAX_NODES_PER_WRITE,
-            max_nodes_per_method_call: constants::MAX_NODES_PER_METHOD_CALL,
-            max_nodes_per_browse: constants::MAX_NODES_PER_BROWSE,
-            max_nodes_per_register_nodes: constants::MAX_NODES_PER_REGISTER_NODES,
-            max_nodes_per_node_management: constants::MAX_NODES_PER_NODE_MANAGEMENT,
-            max_monitored_items_per_call: constants::MAX_MONITORED_ITEMS_PER_CALL,
-            max_nodes_per_history_read_data: constants::MAX_NODES_PER_HISTORY_READ_DATA,
-            max_nodes_per_history_read_events: constants::MAX_NODES_PER_HISTORY_READ_EVENTS,
-            max_nodes_per_history_update_data: constants::MAX_NODES_PER_HISTORY_UPDATE_DATA,
-            max_nodes_per_history_update_events: constants::MAX_NODES_PER_HISTORY_UPDATE_EVENTS,
-        }
-    }
-}
-
 /// Server state is any state associated with the server as a whole that individual sessions might
 /// be interested in. That includes configuration info etc.
 pub struct ServerState {
@@ -96,6 +61,8 @@ pub struct ServerState {
     pub last_subscription_id: u32,
     /// Maximum number of subscriptions per session, 0 means no limit (danger)
     pub max_subscriptions: usize,
+    /// Maximum number of subscriptions across all sessions, 0 means no limit
+    pub max_server_subscriptions: usize,
     /// Maximum number of monitored items per subscription, 0 means no limit (danger)
     pub max_monitored_items_per_sub: usize,
     /// Maximum number of queued values in a monitored item, 0 means no limit (danger)
@@ -112,10 +79,16 @@ pub struct ServerState {
     pub max_lifetime_count: u32,
     /// Operational limits
     pub(crate) operational_limits: OperationalLimits,
+    /// Build information of the server, reported in `Server/ServerStatus/BuildInfo`
+    pub build_info: BuildInfo,
     /// Current state
     pub state: ServerStateType,
     /// Sets the abort flag that terminates the associated server
     pub abort: bool,
+    /// The time that a requested shutdown happens
+    pub(crate) shutdown_at: Option<Instant>,
+    /// The reason given for a requested shutdown
+    pub(crate) shutdown_reason: LocalizedText,
     /// Audit log
     pub(crate) audit_log: Arc<RwLock<AuditLog>>,
     /// Diagnostic information
@@ -124,10 +97,19 @@ pub struct ServerState {
     pub(crate) register_nodes_callback: Option<Box<dyn RegisterNodes + Send + Sync>>,
     /// Callback for unregister nodes
     pub(crate) unregister_nodes_callback: Option<Box<dyn UnregisterNodes + Send + Sync>>,
+    /// Decides what the user of each session may access
+    pub(crate) authorization_handler: Option<Arc<dyn AuthorizationHandler + Send + Sync>>,
+    /// Authenticates the users of sessions
+    pub(crate) user_authenticator: Box<dyn UserAuthenticator + Send + Sync>,
+    /// Indicates if the user authenticator is the default one, which authenticates users against
+    /// the user tokens of the config and is replaced when the config is reloaded
+    pub(crate) config_user_authenticator: bool,
     /// Callback for historical data
     pub(crate) historical_data_provider: Option<Box<dyn HistoricalDataProvider + Send + Sync>>,
     /// Callback for historical events
     pub(crate) historical_event_provider: Option<Box<dyn HistoricalEventProvider + Send + Sync>>,
+    /// Captures the traffic of the server's connections, if the config has a capture file
+    pub(crate) message_capture: Option<MessageCapture>,
     /// Size of the send buffer in bytes
     pub send_buffer_size: usize,
     /// Size of the receive buffer in bytes
@@ -135,26 +117,34 @@ pub struct ServerState {
 }
 
 impl ServerState {
+    /// Returns the endpoints for the url, with the application name in the first of the locales
+    /// that it is configured for.
     pub fn endpoints(
         &self,
         endpoint_url: &UAString,
         transport_profile_uris: &Option<Vec<UAString>>,
+        locale_ids: &Option<Vec<UAString>>,
     ) -> Option<Vec<EndpointDescription>> {
         // Filter endpoints based on profile_uris
         debug!(
             "Endpoints requested, transport profile uris {:?}",
             transport_profile_uris
         );
+        let config = trace_read_lock!(self.config);
+        let mut base_endpoint_urls = config.base_endpoint_urls();
         if let Some(ref transport_profile_uris) = *transport_profile_uris {
             // Note - some clients pass an empty array
             if !transport_profile_uris.is_empty() {
-                // As we only support binary transport, the result is None if the supplied profile_uris does not contain that profile
-                let found_binary_transport = transport_profile_uris.iter().any(|profile_uri| {
-                    profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_BINARY
+                // Only the transports the client asks for are returned, and the result is None if
+                // the server has none of them
+                base_endpoint_urls.retain(|(_, transport_profile_uri)| {
+                    transport_profile_uris
+                        .iter()
+                        .any(|profile_uri| profile_uri.as_ref() == *transport_profile_uri)
                 });
-                if !found_binary_transport {
+                if base_endpoint_urls.is_empty() {
                     error!(
-                        "Client wants to connect with a non binary transport {:#?}",
+                        "Client wants to connect with an unsupported transport {:#?}",
                         transport_profile_uris
                     );
                     return None;
@@ -162,15 +152,28 @@ impl ServerState {
             }
         }
 
-        let config = trace_read_lock!(self.config);
         if let Ok(hostname) = hostname_from_url(endpoint_url.as_ref()) {
             if !hostname.eq_ignore_ascii_case(&config.tcp_config.host) {
                 debug!("Endpoint url \"{}\" hostname supplied by caller does not match server's hostname \"{}\"", endpoint_url, &config.tcp_config.host);
             }
-            let endpoints = config
-                .endpoints
+            let endpoints = base_endpoint_urls
                 .iter()
-                .map(|(_, e)| self.new_endpoint_description(&config, e, true))
+                .flat_map(|(base_endpoint_url, transport_profile_uri)| {
+                    config
+                        .endpoints
+                        .values()
+                        .filter(|e| ServerConfig::is_endpoint_offered(e, transport_profile_uri))
+                        .map(|e| {
+                            self.new_endpoint_description(
+                                &config,
+                                e,
+                                base_endpoint_url,
+                                transport_profile_uri,
+                                true,
+                                locale_ids,
+                            )
+                        })
+                })
                 .collect();
             Some(endpoints)
         } else {
@@ -179,7 +182,23 @@ impl ServerState {
                 endpoint_url
             );
             if let Some(e) = config.default_endpoint() {
-                Some(vec![self.new_endpoint_description(&config, e, true)])
+                let endpoints = base_endpoint_urls
+                    .iter()
+                    .filter(|(_, transport_profile_uri)| {
+                        ServerConfig::is_endpoint_offered(e, transport_profile_uri)
+                    })
+                    .map(|(base_endpoint_url, transport_profile_uri)| {
+                        self.new_endpoint_description(
+                            &config,
+                            e,
+                            base_endpoint_url,
+                            transport_profile_uri,
+                            true,
+                            locale_ids,
+                        )
+                    })
+                    .collect();
+                Some(endpoints)
             } else {
                 Some(vec![])
             }
@@ -207,15 +226,33 @@ impl ServerState {
     ) -> Option<Vec<EndpointDescription>> {
         debug!("find_endpoint, url = {}", endpoint_url);
         let config = trace_read_lock!(self.config);
-        let base_endpoint_url = config.base_endpoint_url();
         let endpoints: Vec<EndpointDescription> = config
-            .endpoints
+            .base_endpoint_urls()
             .iter()
-            .filter(|&(_, e)| {
-                // Test end point's security_policy_uri and matching url
-                url_matches_except_host(&e.endpoint_url(&base_endpoint_url), endpoint_url)
+            .flat_map(|(base_endpoint_url, transport_profile_uri)| {
+                config
+                    .endpoints
+                    .values()
+                    .filter(|e| {
+                        // Test end point's security_policy_uri and matching url
+                        ServerConfig::is_endpoint_offered(e, transport_profile_uri)
+                            && url_matches_except_host(
+                                &e.endpoint_url(base_endpoint_url),
+                                endpoint_url,
+                            )
+                    })
+                    .map(|e| {
+                        self.new_endpoint_description(
+                            &config,
+                            e,
+                            base_endpoint_url,
+                            transport_profile_uri,
+                            false,
+                            &None,
+                        )
+                    })
+                    .collect::<Vec<_>>()
             })
-            .map(|(_, e)| self.new_endpoint_description(&config, e, false))
             .collect();
         if endpoints.is_empty() {
             None
@@ -249,15 +286,12 @@ impl ServerState {
         UAString::null()
     }
 
-    fn user_identity_tokens(
-        &self,
-        config: &ServerConfig,
-        endpoint: &ServerEndpoint,
-    ) -> Vec<UserTokenPolicy> {
+    fn user_identity_tokens(&self, endpoint: &ServerEndpoint) -> Vec<UserTokenPolicy> {
         let mut user_identity_tokens = Vec::with_capacity(3);
+        let user_token_types = self.user_authenticator.user_token_types(endpoint);
 
         // Anonymous policy
-        if endpoint.supports_anonymous() {
+        if user_token_types.contains(&UserTokenType::Anonymous) {
             user_identity_tokens.push(UserTokenPolicy {
                 policy_id: UAString::from(POLICY_ID_ANONYMOUS),
                 token_type: UserTokenType::Anonymous,
@@ -267,7 +301,7 @@ impl ServerState {
             });
         }
         // User pass policy
-        if endpoint.supports_user_pass(&config.user_tokens) {
+        if user_token_types.contains(&UserTokenType::UserName) {
             // The endpoint may set a password security policy
             user_identity_tokens.push(UserTokenPolicy {
                 policy_id: Self::user_pass_security_policy_id(endpoint),
@@ -278,7 +312,7 @@ impl ServerState {
             });
         }
         // X509 policy
-        if endpoint.supports_x509(&config.user_tokens) {
+        if user_token_types.contains(&UserTokenType::Certificate) {
             user_identity_tokens.push(UserTokenPolicy {
                 policy_id: UAString::from(POLICY_ID_X509),
                 token_type: UserTokenType::Certificate,
@@ -298,16 +332,18 @@ impl ServerState {
         user_identity_tokens
     }
 
-    /// Constructs a new endpoint description using the server's info and that in an Endpoint
+    /// Constructs a new endpoint description using the server's info and that in an Endpoint, with
+    /// the application name in the first of the `locale_ids` that it is configured for
     fn new_endpoint_description(
         &self,
         config: &ServerConfig,
         endpoint: &ServerEndpoint,
+        base_endpoint_url: &str,
+        transport_profile_uri: &str,
         all_fields: bool,
+        locale_ids: &Option<Vec<UAString>>,
     ) -> EndpointDescription {
-        let base_endpoint_url = config.base_endpoint_url();
-
-        let user_identity_tokens = self.user_identity_tokens(config, endpoint);
+        let user_identity_tokens = self.user_identity_tokens(endpoint);
 
         // CreateSession doesn't need all the endpoint description
         // and docs say not to bother sending the server and server
@@ -317,7 +353,7 @@ impl ServerState {
                 ApplicationDescription {
                     application_uri: self.application_uri.clone(),
                     product_uri: self.product_uri.clone(),
-                    application_name: self.application_name.clone(),
+                    application_name: config.localized_application_name(locale_ids),
                     application_type: self.application_type(),
                     gateway_server_uri: self.gateway_server_uri(),
                     discovery_profile_uri: UAString::null(),
@@ -341,13 +377,13 @@ impl ServerState {
         };
 
         EndpointDescription {
-            endpoint_url: endpoint.endpoint_url(&base_endpoint_url).into(),
+            endpoint_url: endpoint.endpoint_url(base_endpoint_url).into(),
             server,
             server_certificate,
             security_mode: endpoint.message_security_mode(),
             security_policy_uri: UAString::from(endpoint.security_policy().to_uri()),
             user_identity_tokens: Some(user_identity_tokens),
-            transport_profile_uri: UAString::from(profiles::TRANSPORT_PROFILE_URI_BINARY),
+            transport_profile_uri: UAString::from(transport_profile_uri),
             security_level: endpoint.security_level,
         }
     }
@@ -387,6 +423,58 @@ impl ServerState {
         self.abort
     }
 
+    /// Puts the server into the shutdown state and aborts it after the number of seconds, e.g.
+    /// when a client calls the Server.RequestServerStateChange method
+    pub fn request_shutdown(&mut self, seconds_till_shutdown: u32, reason: LocalizedText) {
+        info!(
+            "Server will shut down in {} seconds, reason {}",
+            seconds_till_shutdown, reason
+        );
+        self.state = ServerStateType::Shutdown;
+        self.shutdown_at =
+            Some(Instant::now() + std::time::Duration::from_secs(u64::from(seconds_till_shutdown)));
+        self.shutdown_reason = reason;
+    }
+
+    /// Stops a requested shutdown from happening
+    pub fn cancel_shutdown(&mut self) {
+        if self.shutdown_at.take().is_some() {
+            info!("Requested shutdown of the server is cancelled");
+            self.shutdown_reason = LocalizedText::null();
+        }
+    }
+
+    /// The seconds until a requested shutdown happens, if there is one
+    pub fn seconds_till_shutdown(&self) -> Option<u32> {
+        self.shutdown_at.map(|shutdown_at| {
+            shutdown_at
+                .saturating_duration_since(Instant::now())
+                .as_secs() as u32
+        })
+    }
+
+    pub fn shutdown_reason(&self) -> &LocalizedText {
+        &self.shutdown_reason
+    }
+
+    /// The status of the server, as reported in `Server/ServerStatus`
+    pub fn server_status(&self) -> ServerStatusDataType {
+        ServerStatusDataType {
+            start_time: self.start_time,
+            current_time: DateTime::now(),
+            state: self.state,
+            build_info: self.build_info.clone(),
+            seconds_till_shutdown: self.seconds_till_shutdown().unwrap_or(0),
+            shutdown_reason: self.shutdown_reason.clone(),
+        }
+    }
+
+    /// Tests if the time of a requested shutdown has come
+    pub(crate) fn is_shutdown_due(&self, now: Instant) -> bool {
+        self.shutdown_at
+            .is_some_and(|shutdown_at| now >= shutdown_at)
+    }
+
     pub fn is_running(&self) -> bool {
         self.state == ServerStateType::Running
     }
@@ -406,8 +494,18 @@ impl ServerState {
         let discovery_urls = self.discovery_urls();
         let server_type = self.application_type();
         let is_online = self.is_running();
-        let server_names = Some(vec![self.application_name.clone()]);
-        // Server names
+        // Server names, in every locale so the discovery server can answer in any of them
+        let mut server_names = vec![self.application_name.clone()];
+        {
+            let config = trace_read_lock!(self.config);
+            server_names.extend(
+                config
+                    .localized_application_names
+                    .iter()
+                    .map(|(locale, name)| LocalizedText::new(locale, name)),
+            );
+        }
+        let server_names = Some(server_names);
         RegisteredServer {
             server_uri,
             product_uri,
@@ -425,12 +523,36 @@ impl ServerState {
         self.last_subscription_id
     }
 
+    /// Returns the number of subscriptions that currently exist across all sessions
+    pub(crate) fn subscription_count(&self) -> usize {
+        let diagnostics = trace_read_lock!(self.diagnostics);
+        diagnostics
+            .server_diagnostics_summary()
+            .current_subscription_count as usize
+    }
+
+    /// Updates the limits that the server state holds from the limits of a reloaded config.
+    /// Subscriptions and monitored items that exist keep their revised values, the limits apply
+    /// to those that are created or modified later.
+    pub(crate) fn set_limits(&mut self, limits: &Limits) {
+        self.max_subscriptions = limits.max_subscriptions;
+        self.max_server_subscriptions = limits.max_server_subscriptions;
+        self.max_monitored_items_per_sub = limits.max_monitored_items_per_sub;
+        self.max_monitored_item_queue_size = limits.max_monitored_item_queue_size;
+        self.min_publishing_interval_ms = limits.min_publishing_interval * 1000.0;
+        self.min_sampling_interval_ms = limits.min_sampling_interval * 1000.0;
+        self.send_buffer_size = limits.send_buffer_size;
+        self.receive_buffer_size = limits.receive_buffer_size;
+        self.operational_limits = limits.operational.clone();
+    }
+
     /// Authenticates access to an endpoint. The endpoint is described by its path, policy, mode and
     /// the token is supplied in an extension object that must be extracted and authenticated.
     ///
     /// It is possible that the endpoint does not exist, or that the token is invalid / unsupported
     /// or that the token cannot be used with the end point. The return codes reflect the responses
-    /// that ActivateSession would expect from a service call.
+    /// that ActivateSession would expect from a service call. Otherwise the identity of the user
+    /// is returned with the roles that its session has.
     pub fn authenticate_endpoint(
         &self,
         request: &ActivateSessionRequest,
@@ -439,11 +561,16 @@ impl ServerState {
         security_mode: MessageSecurityMode,
         user_identity_token: &ExtensionObject,
         server_nonce: &ByteString,
-    ) -> Result<String, StatusCode> {
+    ) -> Result<UserIdentity, StatusCode> {
         // Get security from endpoint url
-        let config = trace_read_lock!(self.config);
+        let endpoint = {
+            let config = trace_read_lock!(self.config);
+            config
+                .find_endpoint(endpoint_url, security_policy, security_mode)
+                .cloned()
+        };
 
-        if let Some(endpoint) = config.find_endpoint(endpoint_url, security_policy, security_mode) {
+        if let Some(endpoint) = endpoint {
             // Now validate the user identity token
             match IdentityToken::new(user_identity_token, &self.decoding_options()) {
                 IdentityToken::None => {
@@ -451,19 +578,17 @@ impl ServerState {
                     Err(StatusCode::BadIdentityTokenInvalid)
                 }
                 IdentityToken::AnonymousIdentityToken(token) => {
-                    Self::authenticate_anonymous_token(endpoint, &token)
+                    self.authenticate_anonymous_token(&endpoint, &token)
                 }
                 IdentityToken::UserNameIdentityToken(token) => self
                     .authenticate_username_identity_token(
-                        &config,
-                        endpoint,
+                        &endpoint,
                         &token,
                         &self.server_pkey,
                         server_nonce,
                     ),
                 IdentityToken::X509IdentityToken(token) => self.authenticate_x509_identity_token(
-                    &config,
-                    endpoint,
+                    &endpoint,
                     &token,
                     &request.user_token_signature,
                     &self.server_certificate,
@@ -480,6 +605,126 @@ impl ServerState {
         }
     }
 
+    /// Sets what authenticates the users of sessions. By default users are authenticated against
+    /// the user tokens of the config.
+    pub fn set_user_authenticator(
+        &mut self,
+        user_authenticator: Box<dyn UserAuthenticator + Send + Sync>,
+    ) {
+        self.user_authenticator = user_authenticator;
+        self.config_user_authenticator = false;
+    }
+
+    /// Asks the user authenticator for the identity of a token's user and gives it the well-known
+    /// role that applies, i.e. `Anonymous` for an anonymous user, otherwise `AuthenticatedUser`.
+    fn authenticate_user(
+        &self,
+        endpoint: &ServerEndpoint,
+        token: UserToken,
+    ) -> Result<UserIdentity, StatusCode> {
+        if !self
+            .user_authenticator
+            .user_token_types(endpoint)
+            .contains(&token.token_type())
+        {
+            error!(
+                "Endpoint \"{}\" does not support {:?} tokens",
+                endpoint.path,
+                token.token_type()
+            );
+            return Err(StatusCode::BadIdentityTokenRejected);
+        }
+        let mut identity = self.user_authenticator.authenticate(&token, endpoint)?;
+        let well_known_role = if let UserToken::Anonymous = token {
+            identity.roles.clear();
+            ObjectId::WellKnownRole_Anonymous
+        } else {
+            ObjectId::WellKnownRole_AuthenticatedUser
+        };
+        identity.roles.insert(0, well_known_role.into());
+        Ok(identity)
+    }
+
+    /// Returns the URIs of the profiles and facets that the server implements, derived from its
+    /// configuration, the enabled features and the providers that have been set. This is the
+    /// value of `Server.ServerCapabilities.ServerProfileArray`.
+    pub fn server_profiles(&self) -> Vec<&'static str> {
+        let config = trace_read_lock!(self.config);
+
+        // Base server behaviour
+        //  SecurityPolicy - None
+        //  Address Space Base
+        //  AttributeRead
+        //  Base Info Core Structure
+        //  Discovery Find Servers Self
+        //  Discovery Get Endpoints
+        //  Session Base
+        //  Session General Service Behaviour
+        //  Session Minimum 1
+        //  View Basic
+        //  View Minimum Continuation Point 01
+        //  View RegisterNodes
+        //  View TranslateBrowsePath
+        let mut server_profiles = vec![profiles::SERVER_PROFILE_URI_BEHAVIOUR];
+
+        let secure = config
+            .endpoints
+            .values()
+            .any(|e| e.security_policy() != SecurityPolicy::None);
+        let subscriptions = self.max_subscriptions > 0;
+
+        // Embedded UA server requires a secure endpoint and the Standard DataChange Subscription
+        // Server Facet, as well as the Method Server Facet that is always supported.
+        if secure && subscriptions {
+            server_profiles.push(profiles::SERVER_PROFILE_URI_EMBEDDED_UA);
+        }
+        if subscriptions {
+            server_profiles.push(profiles::SERVER_FACET_URI_STANDARD_DATA_CHANGE_SUBSCRIPTION);
+        }
+        server_profiles.push(profiles::SERVER_FACET_URI_METHODS);
+        if self.historical_data_provider.is_some() {
+            server_profiles.push(profiles::SERVER_FACET_URI_HISTORICAL_RAW_DATA);
+        }
+        if self.historical_event_provider.is_some() {
+            server_profiles.push(profiles::SERVER_FACET_URI_HISTORICAL_EVENTS);
+        }
+
+        // Transports
+        server_profiles.push(profiles::TRANSPORT_PROFILE_URI_BINARY);
+        if cfg!(feature = "websocket") && config.websocket_config.is_some() {
+            server_profiles.push(profiles::TRANSPORT_PROFILE_URI_WSS_BINARY);
+        }
+        if cfg!(feature = "https") && config.https_config.is_some() {
+            server_profiles.push(profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY);
+        }
+
+        // Security policies and user tokens offered by the endpoints
+        let mut security_policies = config
+            .endpoints
+            .values()
+            .map(|e| e.security_policy().to_uri())
+            .collect::<Vec<_>>();
+        security_policies.sort_unstable();
+        security_policies.dedup();
+        server_profiles.extend(security_policies);
+        let user_token_types = config
+            .endpoints
+            .values()
+            .flat_map(|e| self.user_authenticator.user_token_types(e))
+            .collect::<Vec<_>>();
+        if user_token_types.contains(&UserTokenType::Anonymous) {
+            server_profiles.push(profiles::SECURITY_USER_TOKEN_POLICY_ANONYMOUS);
+        }
+        if user_token_types.contains(&UserTokenType::UserName) {
+            server_profiles.push(profiles::SECURITY_USER_TOKEN_POLICY_USERPASS);
+        }
+        if user_token_types.contains(&UserTokenType::Certificate) {
+            server_profiles.push(profiles::SECURITY_USER_TOKEN_POLICY_X509);
+        }
+
+        server_profiles
+    }
+
     pub fn set_register_nodes_callbacks(
         &mut self,
         register_nodes_callback: Box<dyn RegisterNodes + Send + Sync>,
@@ -489,6 +734,15 @@ impl ServerState {
         self.unregister_nodes_callback = Some(unregister_nodes_callback);
     }
 
+    /// Sets the handler that decides what the user of a session may access in the address space.
+    /// It applies to sessions created after it is set.
+    pub fn set_authorization_handler(
+        &mut self,
+        authorization_handler: Box<dyn AuthorizationHandler + Send + Sync>,
+    ) {
+        self.authorization_handler = Some(Arc::from(authorization_handler));
+    }
+
     /// Returns the decoding options of the server
     pub fn decoding_options(&self) -> DecodingOptions {
         let config = trace_read_lock!(self.config);
@@ -497,35 +751,34 @@ impl ServerState {
 
     /// Authenticates an anonymous token, i.e. does the endpoint support anonymous access or not
     fn authenticate_anonymous_token(
+        &self,
         endpoint: &ServerEndpoint,
         token: &AnonymousIdentityToken,
-    ) -> Result<String, StatusCode> {
+    ) -> Result<UserIdentity, StatusCode> {
         if token.policy_id.as_ref() != POLICY_ID_ANONYMOUS {
             error!("Token doesn't possess the correct policy id");
             Err(StatusCode::BadIdentityTokenInvalid)
-        } else if !endpoint.supports_anonymous() {
-            error!(
-                "Endpoint \"{}\" does not support anonymous authentication",
-                endpoint.path
-            );
-            Err(StatusCode::BadIdentityTokenRejected)
         } else {
+            let identity = self.authenticate_user(endpoint, UserToken::Anonymous)?;
             debug!("Anonymous identity is authenticated");
-            Ok(String::from(crate::server::config::ANONYMOUS_USER_TOKEN_ID))
+            Ok(identity)
         }
     }
 
-    /// Authenticates the username identity token with the supplied endpoint. The function returns the user token identifier
-    /// that matches the identity token.
+    /// Authenticates the username identity token with the supplied endpoint. The function returns the
+    /// identity of the user that the user authenticator finds for the name and password.
     fn authenticate_username_identity_token(
         &self,
-        config: &ServerConfig,
         endpoint: &ServerEndpoint,
         token: &UserNameIdentityToken,
         server_key: &Option<PrivateKey>,
         server_nonce: &ByteString,
-    ) -> Result<String, StatusCode> {
-        if !endpoint.supports_user_pass(&config.user_tokens) {
+    ) -> Result<UserIdentity, StatusCode> {
+        if !self
+            .user_authenticator
+            .user_token_types(endpoint)
+            .contains(&UserTokenType::UserName)
+        {
             error!("Endpoint doesn't support username password tokens");
             Err(StatusCode::BadIdentityTokenRejected)
         } else if token.policy_id != Self::user_pass_security_policy_id(endpoint) {
@@ -554,55 +807,31 @@ impl ServerState {
             } else {
                 token.plaintext_password()?
             };
-
-            // Iterate ids in endpoint
-            for user_token_id in &endpoint.user_token_ids {
-                if let Some(server_user_token) = config.user_tokens.get(user_token_id) {
-                    if server_user_token.is_user_pass()
-                        && server_user_token.user == token.user_name.as_ref()
-                    {
-                        // test for empty password
-                        let valid = if server_user_token.pass.is_none() {
-                            // Empty password for user
-                            token_password.is_empty()
-                        } else {
-                            // Password compared as UTF-8 bytes
-                            let server_password =
-                                server_user_token.pass.as_ref().unwrap().as_bytes();
-                            server_password == token_password.as_bytes()
-                        };
-                        if !valid {
-                            error!(
-                                "Cannot authenticate \"{}\", password is invalid",
-                                server_user_token.user
-                            );
-                            return Err(StatusCode::BadUserAccessDenied);
-                        } else {
-                            return Ok(user_token_id.clone());
-                        }
-                    }
-                }
-            }
-            error!(
-                "Cannot authenticate \"{}\", user not found for endpoint",
-                token.user_name
-            );
-            Err(StatusCode::BadUserAccessDenied)
+            self.authenticate_user(
+                endpoint,
+                UserToken::UserName {
+                    user: token.user_name.as_ref(),
+                    password: &token_password,
+                },
+            )
         }
     }
 
-    /// Authenticate the x509 token against the endpoint. The function returns the user token identifier
-    /// that matches the identity token.
+    /// Authenticate the x509 token against the endpoint. The function returns the identity of the
+    /// user that the user authenticator finds for the certificate.
     fn authenticate_x509_identity_token(
         &self,
-        config: &ServerConfig,
         endpoint: &ServerEndpoint,
         token: &X509IdentityToken,
         user_token_signature: &SignatureData,
         server_certificate: &Option<X509>,
         server_nonce: &ByteString,
-    ) -> Result<String, StatusCode> {
-        if !endpoint.supports_x509(&config.user_tokens) {
+    ) -> Result<UserIdentity, StatusCode> {
+        if !self
+            .user_authenticator
+            .user_token_types(endpoint)
+            .contains(&UserTokenType::Certificate)
+        {
             error!("Endpoint doesn't support x509 tokens");
             Err(StatusCode::BadIdentityTokenRejected)
         } else if token.policy_id.as_ref() != POLICY_ID_X509 {
@@ -612,7 +841,7 @@ impl ServerState {
             let result = match server_certificate {
                 Some(ref server_certificate) => {
                     // Find the security policy used for verifying tokens
-                    let user_identity_tokens = self.user_identity_tokens(config, endpoint);
+                    let user_identity_tokens = self.user_identity_tokens(endpoint);
                     let security_policy = user_identity_tokens
                         .iter()
                         .find(|t| t.token_type == UserTokenType::Certificate)
@@ -639,20 +868,13 @@ impl ServerState {
                 None => Err(StatusCode::BadIdentityTokenInvalid),
             };
             result.and_then(|_| {
-                // Check the endpoint to see if this token is supported
                 let signing_cert = X509::from_byte_string(&token.certificate_data)?;
-                let signing_thumbprint = signing_cert.thumbprint();
-                for user_token_id in &endpoint.user_token_ids {
-                    if let Some(server_user_token) = config.user_tokens.get(user_token_id) {
-                        if let Some(ref user_thumbprint) = server_user_token.thumbprint {
-                            // The signing cert matches a user's identity, so it is valid
-                            if *user_thumbprint == signing_thumbprint {
-                                return Ok(user_token_id.clone());
-                            }
-                        }
-                    }
-                }
-                Err(StatusCode::BadIdentityTokenInvalid)
+                self.authenticate_user(
+                    endpoint,
+                    UserToken::X509 {
+                        certificate: &signing_cert,
+                    },
+                )
             })
         }
     }
@@ -671,10 +893,25 @@ impl ServerState {
         self.historical_event_provider = Some(historical_event_provider);
     }
 
+    /// Sets the sink that receives a copy of every audit event raised by the server.
+    pub fn set_audit_sink(&mut self, audit_sink: Box<dyn AuditSink + Send + Sync>) {
+        let mut audit_log = trace_write_lock!(self.audit_log);
+        audit_log.set_sink(audit_sink);
+    }
+
+    /// Tests if the server raises audit events
+    pub fn is_auditing(&self) -> bool {
+        let config = trace_read_lock!(self.config);
+        config.auditing
+    }
+
     pub(crate) fn raise_and_log<T>(&self, event: T) -> Result<NodeId, ()>
     where
         T: AuditEvent + Event,
     {
+        if !self.is_auditing() {
+            return Err(());
+        }
         let audit_log = trace_write_lock!(self.audit_log);
         audit_log.raise_and_log(event)
     }
@@ -5,7 +5,7 @@
 use std::time::Duration;
 
 use crate::core::supported_message::SupportedMessage;
-use crate::types::{service_types::PublishRequest, status_code::StatusCode};
+use crate::types::{service_types::PublishRequest, status_code::StatusCode, DateTimeUtc};
 
 /// The publish request entry preserves the request_id which is part of the chunk layer but clients
 /// are fickle about receiving responses from the same as the request. Normally this is easy because
@@ -27,13 +27,28 @@ pub struct PublishResponseEntry {
     pub response: SupportedMessage,
 }
 
-/// This converts an OPC UA Duration into a time duration used for testing for interval elapsed
+/// This converts an OPC UA Duration into a time duration used for testing for interval elapsed.
+/// Negative and NaN durations are zero and durations too large to represent are saturated.
 fn duration_from_ms(d: f64) -> Duration {
     // Duration is a floating point number in millis so turn to microseconds for greater accuracy
-    // 1 millisecond = 1000 microsecond
+    // 1 millisecond = 1000 microsecond. The cast saturates and turns NaN into 0.
     Duration::from_micros((d * 1000f64) as u64)
 }
 
+/// This converts an OPC UA Duration into a signed time duration for date time arithmetic, in the
+/// same way as `duration_from_ms`
+pub(crate) fn time_delta_from_ms(d: f64) -> chrono::Duration {
+    chrono::Duration::from_std(duration_from_ms(d)).unwrap_or(chrono::Duration::max_value())
+}
+
+/// Adds a duration to a time, saturating to the latest representable time rather than
+/// overflowing when a client asks for a very long interval
+pub(crate) fn saturating_add(time: &DateTimeUtc, duration: chrono::Duration) -> DateTimeUtc {
+    time.checked_add_signed(duration)
+        .unwrap_or(DateTimeUtc::MAX_UTC)
+}
+
 pub mod monitored_item;
+pub mod sampling;
 pub mod subscription;
 pub mod subscriptions;
@@ -8,16 +8,21 @@ use std::result::Result;
 use crate::types::{
     node_ids::ObjectId,
     service_types::{
-        DataChangeFilter, EventFieldList, EventFilter, MonitoredItemCreateRequest,
-        MonitoredItemModifyRequest, MonitoredItemNotification, ReadValueId, TimestampsToReturn,
+        AggregateFilter, AggregateFilterResult, DataChangeFilter, EventFieldList, EventFilter,
+        MonitoredItemCreateRequest, MonitoredItemModifyRequest, MonitoredItemNotification,
+        ReadValueId, TimestampsToReturn,
     },
     status_code::StatusCode,
     *,
 };
 
 use crate::server::{
-    address_space::{node::Node, AddressSpace, EventNotifier},
+    address_space::{
+        node::{Node, NodeBase, NodeType},
+        AddressSpace, EventNotifier,
+    },
     events::event_filter,
+    historical::aggregates::AggregateCalculator,
     state::ServerState,
 };
 
@@ -44,6 +49,7 @@ pub(crate) enum FilterType {
     None,
     DataChangeFilter(DataChangeFilter),
     EventFilter(EventFilter),
+    AggregateFilter(AggregateFilter),
 }
 
 impl FilterType {
@@ -66,6 +72,11 @@ impl FilterType {
                 ObjectId::EventFilter_Encoding_DefaultBinary => Ok(FilterType::EventFilter(
                     filter.decode_inner::<EventFilter>(decoding_options)?,
                 )),
+                ObjectId::AggregateFilter_Encoding_DefaultBinary => {
+                    Ok(FilterType::AggregateFilter(
+                        filter.decode_inner::<AggregateFilter>(decoding_options)?,
+                    ))
+                }
                 _ => {
                     error!(
                         "Requested data filter type is not supported, {:?}",
@@ -88,6 +99,9 @@ impl FilterType {
 pub(crate) struct MonitoredItem {
     monitored_item_id: u32,
     item_to_monitor: ReadValueId,
+    /// The index range of the item to monitor, so only that part of an array value is reported
+    #[serde(skip)]
+    index_range: NumericRange,
     monitoring_mode: MonitoringMode,
     // Triggered items are other monitored items in the same subscription which are reported if this
     // monitored item changes.
@@ -101,9 +115,20 @@ pub(crate) struct MonitoredItem {
     /// message, pop back gets the most recent.
     notification_queue: VecDeque<Notification>,
     queue_overflow: bool,
+    /// The number of times the notification queue has overflowed
+    queue_overflow_count: u32,
     timestamps_to_return: TimestampsToReturn,
     last_sample_time: DateTimeUtc,
     last_data_value: Option<DataValue>,
+    /// The (low, high) engineering unit range of the monitored variable, used by a percent deadband
+    eu_range: Option<(f64, f64)>,
+    /// The calculator of an aggregate filter
+    aggregate: Option<AggregateCalculator>,
+    /// Values sampled for the current processing interval of an aggregate filter, preceded by the
+    /// last value of the previous interval.
+    aggregate_samples: Vec<DataValue>,
+    /// The start of the current processing interval of an aggregate filter
+    aggregate_interval_start: Option<DateTimeUtc>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -124,6 +149,12 @@ impl MonitoredItem {
         server_state: &ServerState,
         request: &MonitoredItemCreateRequest,
     ) -> Result<MonitoredItem, StatusCode> {
+        let index_range = request
+            .item_to_monitor
+            .index_range
+            .as_ref()
+            .parse::<NumericRange>()
+            .map_err(|_| StatusCode::BadIndexRangeInvalid)?;
         let filter = FilterType::from_filter(
             &request.requested_parameters.filter,
             &server_state.decoding_options(),
@@ -134,11 +165,13 @@ impl MonitoredItem {
         );
         let queue_size = Self::sanitize_queue_size(
             server_state,
-            request.requested_parameters.queue_size as usize,
+            &request.item_to_monitor,
+            request.requested_parameters.queue_size,
         );
         Ok(MonitoredItem {
             monitored_item_id,
             item_to_monitor: request.item_to_monitor.clone(),
+            index_range,
             monitoring_mode: request.monitoring_mode,
             triggered_items: BTreeSet::new(),
             client_handle: request.requested_parameters.client_handle,
@@ -148,9 +181,14 @@ impl MonitoredItem {
             timestamps_to_return,
             last_sample_time: *now,
             last_data_value: None,
+            eu_range: None,
+            aggregate: None,
+            aggregate_samples: Vec::new(),
+            aggregate_interval_start: None,
             queue_size,
             notification_queue: VecDeque::with_capacity(queue_size),
             queue_overflow: false,
+            queue_overflow_count: 0,
         })
     }
 
@@ -174,17 +212,32 @@ impl MonitoredItem {
         );
         self.queue_size = Self::sanitize_queue_size(
             server_state,
-            request.requested_parameters.queue_size as usize,
+            &self.item_to_monitor,
+            request.requested_parameters.queue_size,
         );
         self.client_handle = request.requested_parameters.client_handle;
         self.discard_oldest = request.requested_parameters.discard_oldest;
+        self.reset_aggregate();
 
         // Shrink / grow the notification queue to the new threshold
         if self.notification_queue.len() > self.queue_size {
-            // Discard old notifications
-            let discard = self.queue_size - self.notification_queue.len();
-            let _ = self.notification_queue.drain(0..discard);
-            // TODO potential edge case with discard oldest behaviour
+            // Discard the oldest or newest notifications the same way as an overflow does
+            let discard = self.notification_queue.len() - self.queue_size;
+            let next_to_gap = if self.discard_oldest {
+                let _ = self.notification_queue.drain(0..discard);
+                self.notification_queue.front_mut()
+            } else {
+                self.notification_queue.truncate(self.queue_size);
+                self.notification_queue.back_mut()
+            };
+            // Overflow only affects queues > 1 element
+            if self.queue_size > 1 {
+                if let Some(notification) = next_to_gap {
+                    Self::set_overflow_bit(notification);
+                }
+                self.queue_overflow = true;
+                self.queue_overflow_count = self.queue_overflow_count.wrapping_add(1);
+            }
             // Shrink the queue
             self.notification_queue.shrink_to_fit();
         } else if self.notification_queue.capacity() < self.queue_size {
@@ -210,24 +263,150 @@ impl MonitoredItem {
     /// Validates the filter associated with the monitored item and returns the filter result
     /// encoded in an extension object.
     pub fn validate_filter(
-        &self,
+        &mut self,
         address_space: &AddressSpace,
     ) -> Result<ExtensionObject, StatusCode> {
-        // Event filter must be validated
-        let filter_result = if let FilterType::EventFilter(ref event_filter) = self.filter {
-            let filter_result = event_filter::validate(event_filter, address_space)?;
-            ExtensionObject::from_encodable(
-                ObjectId::EventFilterResult_Encoding_DefaultBinary,
-                &filter_result,
-            )
-        } else {
-            // DataChangeFilter has no result
-            ExtensionObject::null()
+        let filter_result = match self.filter {
+            // Event filter must be validated
+            FilterType::EventFilter(ref event_filter) => {
+                let filter_result = event_filter::validate(event_filter, address_space)?;
+                ExtensionObject::from_encodable(
+                    ObjectId::EventFilterResult_Encoding_DefaultBinary,
+                    &filter_result,
+                )
+            }
+            FilterType::DataChangeFilter(ref filter) => {
+                self.eu_range = self.validate_data_change_filter(filter, address_space)?;
+                // DataChangeFilter has no result
+                ExtensionObject::null()
+            }
+            FilterType::AggregateFilter(ref filter) => {
+                let (calculator, filter_result) = self.validate_aggregate_filter(filter)?;
+                // Hold the revised parameters so they are used from now on
+                self.filter = FilterType::AggregateFilter(AggregateFilter {
+                    start_time: filter_result.revised_start_time,
+                    aggregate_type: filter.aggregate_type.clone(),
+                    processing_interval: filter_result.revised_processing_interval,
+                    aggregate_configuration: filter_result.revised_aggregate_configuration.clone(),
+                });
+                self.aggregate = Some(calculator);
+                ExtensionObject::from_encodable(
+                    ObjectId::AggregateFilterResult_Encoding_DefaultBinary,
+                    &filter_result,
+                )
+            }
+            FilterType::None => ExtensionObject::null(),
         };
         Ok(filter_result)
     }
 
-    /// Called repeatedly on the monitored item.
+    /// Validates a data change filter against the monitored item, returning the engineering unit
+    /// range of the variable if the filter has a percent deadband.
+    fn validate_data_change_filter(
+        &self,
+        filter: &DataChangeFilter,
+        address_space: &AddressSpace,
+    ) -> Result<Option<(f64, f64)>, StatusCode> {
+        if self.item_to_monitor.attribute_id != AttributeId::Value as u32 {
+            return Err(StatusCode::BadFilterNotAllowed);
+        }
+        let deadband_type = filter.deadband_type;
+        if deadband_type == DeadbandType::None as u32 {
+            return Ok(None);
+        }
+        if (deadband_type != DeadbandType::Absolute as u32
+            && deadband_type != DeadbandType::Percent as u32)
+            || filter.deadband_value < 0f64
+        {
+            return Err(StatusCode::BadDeadbandFilterInvalid);
+        }
+
+        // A deadband only applies to numeric variables
+        let node_id = &self.item_to_monitor.node_id;
+        let Some(NodeType::Variable(variable)) = address_space.find_node(node_id) else {
+            return Err(StatusCode::BadFilterNotAllowed);
+        };
+        if !address_space.is_subtype(&variable.data_type(), &DataTypeId::Number.into()) {
+            return Err(StatusCode::BadFilterNotAllowed);
+        }
+
+        if deadband_type == DeadbandType::Percent as u32 {
+            // A percent deadband is a percentage of the EURange property of an analog item
+            if filter.deadband_value > 100f64 {
+                return Err(StatusCode::BadDeadbandFilterInvalid);
+            }
+            match Self::find_eu_range(address_space, node_id) {
+                Some((low, high)) if low < high => Ok(Some((low, high))),
+                Some(_) => Err(StatusCode::BadDeadbandFilterInvalid),
+                None => Err(StatusCode::BadMonitoredItemFilterUnsupported),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Validates an aggregate filter against the monitored item, returning the calculator for the
+    /// aggregate and the revised filter parameters. The processing interval cannot be shorter than
+    /// the sampling interval and the start time defaults to now.
+    fn validate_aggregate_filter(
+        &self,
+        filter: &AggregateFilter,
+    ) -> Result<(AggregateCalculator, AggregateFilterResult), StatusCode> {
+        if self.item_to_monitor.attribute_id != AttributeId::Value as u32 {
+            return Err(StatusCode::BadFilterNotAllowed);
+        }
+        let processing_interval = filter.processing_interval.max(self.sampling_interval);
+        if processing_interval <= 0f64 {
+            return Err(StatusCode::BadMonitoredItemFilterInvalid);
+        }
+        let calculator = AggregateCalculator::new(
+            &filter.aggregate_type,
+            &filter.aggregate_configuration,
+            processing_interval,
+        )?;
+        let revised_start_time = if filter.start_time.is_null() {
+            DateTime::now()
+        } else {
+            filter.start_time
+        };
+        let filter_result = AggregateFilterResult {
+            revised_start_time,
+            revised_processing_interval: processing_interval,
+            revised_aggregate_configuration: calculator.configuration().clone(),
+        };
+        Ok((calculator, filter_result))
+    }
+
+    /// Finds the (low, high) value of the EURange property of a variable
+    fn find_eu_range(address_space: &AddressSpace, node_id: &NodeId) -> Option<(f64, f64)> {
+        let references =
+            address_space.find_references(node_id, Some((ReferenceTypeId::HasProperty, false)))?;
+        references.iter().find_map(|r| {
+            let Some(NodeType::Variable(property)) = address_space.find_node(&r.target_node) else {
+                return None;
+            };
+            if property.browse_name().name.as_ref() != "EURange" {
+                return None;
+            }
+            let value = property.value(
+                TimestampsToReturn::Neither,
+                NumericRange::None,
+                &QualifiedName::null(),
+                0.0,
+            );
+            match value.value {
+                Some(Variant::ExtensionObject(ref range)) => range
+                    .decode_inner::<Range>(&DecodingOptions::default())
+                    .ok()
+                    .map(|range| (range.low, range.high)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Called repeatedly on a monitored item outside of a subscription, which leaves the item to
+    /// enforce its own sampling interval. A subscription uses its sampling scheduler to decide
+    /// which items to `sample()` instead.
     ///
     /// If the monitored item has a negative interval and subscription interval has elapsed,
     /// the value is tested immediately. Otherwise, the monitored items sampling interval is enforced
@@ -235,6 +414,7 @@ impl MonitoredItem {
     ///
     /// Function returns a `TickResult` denoting if the value changed or not, and whether it should
     /// be reported.
+    #[cfg(test)]
     pub fn tick(
         &mut self,
         now: &DateTimeUtc,
@@ -242,47 +422,69 @@ impl MonitoredItem {
         publishing_interval_elapsed: bool,
         resend_data: bool,
     ) -> TickResult {
-        if self.monitoring_mode == MonitoringMode::Disabled {
-            TickResult::NoChange
+        let check_value = if self.monitoring_mode == MonitoringMode::Disabled {
+            false
+        } else if resend_data {
+            // Always check for resend_data flag
+            true
+        } else if self.sampling_interval < 0f64 {
+            // -1 means use the subscription publishing interval so if the publishing interval elapsed,
+            // then this monitored item is evaluated otherwise it won't be.
+            publishing_interval_elapsed
+        } else if self.sampling_interval == 0f64 {
+            // 0 means fastest practical rate, i.e. the tick quantum itself
+            // 0 is also used for clients subscribing for events.
+            true
         } else {
-            let check_value = if resend_data {
-                // Always check for resend_data flag
-                true
-            } else if self.sampling_interval < 0f64 {
-                // -1 means use the subscription publishing interval so if the publishing interval elapsed,
-                // then this monitored item is evaluated otherwise it won't be.
-                publishing_interval_elapsed
-            } else if self.sampling_interval == 0f64 {
-                // 0 means fastest practical rate, i.e. the tick quantum itself
-                // 0 is also used for clients subscribing for events.
-                true
-            } else {
-                // Compare sample interval to the time elapsed
-                let sampling_interval = super::duration_from_ms(self.sampling_interval);
-                let elapsed = now
-                    .signed_duration_since(self.last_sample_time)
-                    .to_std()
-                    .unwrap();
-                elapsed >= sampling_interval
-            };
+            // Compare sample interval to the time elapsed
+            let sampling_interval = super::duration_from_ms(self.sampling_interval);
+            let elapsed = now
+                .signed_duration_since(self.last_sample_time)
+                .to_std()
+                .unwrap_or_default();
+            elapsed >= sampling_interval
+        };
+        if check_value {
+            self.sample(now, address_space, resend_data)
+        } else {
+            TickResult::NoChange
+        }
+    }
 
-            // Test the value (or don't)
-            let value_changed = check_value && {
-                // Indicate a change if reporting is enabled
-                let first_tick = !self.is_event_filter() && self.last_data_value.is_none();
-                let value_changed = self.check_value(address_space, now, resend_data);
-                first_tick || value_changed || !self.notification_queue.is_empty()
-            };
+    /// Samples the monitored item now, regardless of its sampling interval. The subscription calls
+    /// this for the items its sampling scheduler says are due.
+    ///
+    /// Function returns a `TickResult` denoting if the value changed or not, and whether it should
+    /// be reported.
+    pub fn sample(
+        &mut self,
+        now: &DateTimeUtc,
+        address_space: &AddressSpace,
+        resend_data: bool,
+    ) -> TickResult {
+        if self.monitoring_mode == MonitoringMode::Disabled {
+            return TickResult::NoChange;
+        }
 
-            if value_changed {
-                if self.monitoring_mode == MonitoringMode::Reporting {
-                    TickResult::ReportValueChanged
-                } else {
-                    TickResult::ValueChanged
-                }
+        // Indicate a change if reporting is enabled
+        let first_tick = matches!(
+            self.filter,
+            FilterType::None | FilterType::DataChangeFilter(_)
+        ) && self.last_data_value.is_none();
+        let value_changed = self.check_value(address_space, now, resend_data);
+        // Queued samples are only pending a report while the item is reporting. In sampling
+        // mode they wait in the queue until the item is triggered or starts reporting.
+        let pending = self.monitoring_mode == MonitoringMode::Reporting
+            && !self.notification_queue.is_empty();
+
+        if first_tick || value_changed || pending {
+            if self.monitoring_mode == MonitoringMode::Reporting {
+                TickResult::ReportValueChanged
             } else {
-                TickResult::NoChange
+                TickResult::ValueChanged
             }
+        } else {
+            TickResult::NoChange
         }
     }
 
@@ -345,10 +547,11 @@ impl MonitoredItem {
         attribute_id: AttributeId,
         node: &dyn Node,
     ) -> bool {
+        // Both timestamps are sampled so that a filter can compare them, and stripped later
         let data_value = node.get_attribute(
-            TimestampsToReturn::Neither,
+            TimestampsToReturn::Both,
             attribute_id,
-            NumericRange::None,
+            self.index_range.clone(),
             &QualifiedName::null(),
         );
         if let Some(mut data_value) = data_value {
@@ -358,11 +561,14 @@ impl MonitoredItem {
             } else if let Some(ref last_data_value) = self.last_data_value {
                 // If there is a filter on the monitored item then the filter determines
                 // if the value is considered to have changed, otherwise it is a straight
-                // equality test.
+                // equality test of the status and value, the default trigger of the spec.
                 match self.filter {
-                    FilterType::None => data_value.value != last_data_value.value,
+                    FilterType::None => {
+                        data_value.status() != last_data_value.status()
+                            || data_value.value != last_data_value.value
+                    }
                     FilterType::DataChangeFilter(ref filter) => {
-                        !filter.compare(&data_value, last_data_value, None)
+                        !filter.compare(&data_value, last_data_value, self.eu_range)
                     }
                     _ => {
                         // Unrecognized filter
@@ -388,25 +594,7 @@ impl MonitoredItem {
                 self.last_data_value = Some(data_value.clone());
 
                 // Strip out timestamps that subscriber is not interested in
-                match self.timestamps_to_return {
-                    TimestampsToReturn::Neither | TimestampsToReturn::Invalid => {
-                        data_value.source_timestamp = None;
-                        data_value.source_picoseconds = None;
-                        data_value.server_timestamp = None;
-                        data_value.server_picoseconds = None
-                    }
-                    TimestampsToReturn::Server => {
-                        data_value.source_timestamp = None;
-                        data_value.source_picoseconds = None;
-                    }
-                    TimestampsToReturn::Source => {
-                        data_value.server_timestamp = None;
-                        data_value.server_picoseconds = None
-                    }
-                    TimestampsToReturn::Both => {
-                        // DO NOTHING
-                    }
-                }
+                data_value.strip_timestamps(self.timestamps_to_return);
 
                 // Enqueue notification message
                 let client_handle = self.client_handle;
@@ -428,8 +616,88 @@ impl MonitoredItem {
         }
     }
 
-    fn is_event_filter(&self) -> bool {
-        matches!(self.filter, FilterType::EventFilter(_))
+    /// Samples the value for an aggregate filter and enqueues the aggregate of each processing
+    /// interval that has ended by now. Returns true if any aggregate was enqueued.
+    fn check_for_aggregate(
+        &mut self,
+        now: &DateTimeUtc,
+        attribute_id: AttributeId,
+        node: &dyn Node,
+    ) -> bool {
+        let (FilterType::AggregateFilter(filter), Some(calculator)) =
+            (&self.filter, self.aggregate.clone())
+        else {
+            return false;
+        };
+        // Nothing is sampled before the start time of the filter
+        let start_time = filter.start_time.as_chrono();
+        if *now < start_time {
+            return false;
+        }
+        let processing_interval = super::time_delta_from_ms(calculator.processing_interval());
+
+        // Intervals are aligned to the start time of the filter
+        let mut interval_start = *self.aggregate_interval_start.get_or_insert_with(|| {
+            let elapsed = (*now - start_time).num_microseconds().unwrap_or(0);
+            let interval = processing_interval
+                .num_microseconds()
+                .unwrap_or(i64::MAX)
+                .max(1);
+            start_time + chrono::Duration::microseconds(elapsed - elapsed % interval)
+        });
+
+        // The value is stamped with the time it was sampled
+        if let Some(mut data_value) = node.get_attribute(
+            TimestampsToReturn::Neither,
+            attribute_id,
+            self.index_range.clone(),
+            &QualifiedName::null(),
+        ) {
+            let sample_time = DateTime::from(*now);
+            data_value.source_timestamp = Some(sample_time);
+            data_value.server_timestamp = Some(sample_time);
+            self.aggregate_samples.push(data_value);
+        }
+
+        let mut enqueued = false;
+        while let Some(interval_end) = interval_start
+            .checked_add_signed(processing_interval)
+            .filter(|interval_end| *interval_end <= *now)
+        {
+            let mut data_value = calculator.calculate_interval(
+                &interval_start.into(),
+                &interval_end.into(),
+                &self.aggregate_samples,
+            );
+            data_value.server_timestamp = Some(DateTime::from(*now));
+            data_value.strip_timestamps(self.timestamps_to_return);
+            let client_handle = self.client_handle;
+            self.enqueue_notification_message(MonitoredItemNotification {
+                client_handle,
+                value: data_value,
+            });
+            enqueued = true;
+            interval_start = interval_end;
+        }
+        self.aggregate_interval_start = Some(interval_start);
+
+        // Only the last sample before the current interval is needed to bound it
+        let interval_start = DateTime::from(interval_start);
+        if let Some(bound) = self
+            .aggregate_samples
+            .iter()
+            .rposition(|v| v.source_timestamp.is_some_and(|t| t < interval_start))
+        {
+            let _ = self.aggregate_samples.drain(..bound);
+        }
+        enqueued
+    }
+
+    /// Discards the samples and interval of an aggregate filter
+    fn reset_aggregate(&mut self) {
+        self.aggregate = None;
+        self.aggregate_samples.clear();
+        self.aggregate_interval_start = None;
     }
 
     /// Fetches the most recent value of the monitored item from the source and compares
@@ -460,6 +728,9 @@ impl MonitoredItem {
                                 false
                             }
                         }
+                        FilterType::AggregateFilter(_) => {
+                            self.check_for_aggregate(now, attribute_id, node)
+                        }
                         _ => self.check_for_data_change(
                             address_space,
                             resend_data,
@@ -514,16 +785,30 @@ impl MonitoredItem {
         };
         let mut notification = notification.into();
         if overflow {
-            if let Notification::MonitoredItemNotification(ref mut notification) = notification {
-                // Set the overflow bit on the data value's status
-                notification.value.status =
-                    Some(notification.value.status() | StatusCode::OVERFLOW);
+            // The overflow bit goes on the value next to the gap, i.e. the oldest value left
+            // when the oldest are discarded, or the new value when it replaces the newest
+            if self.discard_oldest {
+                if let Some(oldest) = self.notification_queue.front_mut() {
+                    Self::set_overflow_bit(oldest);
+                }
+            } else {
+                Self::set_overflow_bit(&mut notification);
             }
             self.queue_overflow = true;
+            self.queue_overflow_count = self.queue_overflow_count.wrapping_add(1);
         }
         self.notification_queue.push_back(notification);
     }
 
+    /// Sets the overflow bit on the status of a data change notification
+    fn set_overflow_bit(notification: &mut Notification) {
+        if let Notification::MonitoredItemNotification(ref mut notification) = notification {
+            let mut status = notification.value.status();
+            status.set_overflow(true);
+            notification.value.status = Some(status);
+        }
+    }
+
     /// Gets the oldest notification message from the notification queue
     #[cfg(test)]
     pub fn oldest_notification_message(&mut self) -> Option<Notification> {
@@ -556,7 +841,8 @@ impl MonitoredItem {
             // From spec "any negative number is interpreted as -1"
             // -1 means monitored item's sampling interval defaults to the subscription's publishing interval
             -1.0
-        } else if requested_sampling_interval == 0.0
+        } else if requested_sampling_interval.is_nan()
+            || requested_sampling_interval == 0.0
             || requested_sampling_interval < server_state.min_sampling_interval_ms
         {
             server_state.min_sampling_interval_ms
@@ -565,19 +851,56 @@ impl MonitoredItem {
         }
     }
 
+    /// Raises the sampling interval of an item monitoring the value of a variable to the
+    /// `MinimumSamplingInterval` of the variable, when the variable cannot be sampled as fast as
+    /// asked. An item that samples at the publishing interval is given the minimum if the
+    /// publishing interval is shorter.
+    pub(crate) fn apply_minimum_sampling_interval(
+        &mut self,
+        address_space: &AddressSpace,
+        publishing_interval: Duration,
+    ) {
+        if self.item_to_monitor.attribute_id != AttributeId::Value as u32 {
+            return;
+        }
+        let Some(minimum_sampling_interval) = address_space
+            .find_variable_by_ref(&self.item_to_monitor.node_id)
+            .and_then(|variable| variable.minimum_sampling_interval())
+        else {
+            return;
+        };
+        // 0 means the value is sampled continuously and -1 that the rate is indeterminate
+        let sampling_interval = if self.sampling_interval < 0.0 {
+            publishing_interval
+        } else {
+            self.sampling_interval
+        };
+        if minimum_sampling_interval > 0.0 && sampling_interval < minimum_sampling_interval {
+            self.sampling_interval = minimum_sampling_interval;
+        }
+    }
+
     /// Takes the requested queue size and ensures it is within the range supported by the server
-    fn sanitize_queue_size(server_state: &ServerState, requested_queue_size: usize) -> usize {
-        if requested_queue_size == 0 || requested_queue_size == 1 {
+    fn sanitize_queue_size(
+        server_state: &ServerState,
+        item_to_monitor: &ReadValueId,
+        requested_queue_size: u32,
+    ) -> usize {
+        let max_queue_size = server_state.max_monitored_item_queue_size.max(1);
+        let requested_queue_size = requested_queue_size as usize;
+        if item_to_monitor.attribute_id == AttributeId::EventNotifier as u32 {
+            // For event monitored items 0 means the default queue size for events, which is the
+            // largest the server supports so that events between publishes aren't lost
+            if requested_queue_size == 0 {
+                max_queue_size
+            } else {
+                requested_queue_size.min(max_queue_size)
+            }
+        } else if requested_queue_size <= 1 {
             // For data monitored items 0 -> 1
-            // Future - for event monitored items, queue size should be the default queue size for event notifications
             1
-        // Future - for event monitored items, the minimum queue size the server requires for event notifications
-        } else if requested_queue_size > server_state.max_monitored_item_queue_size {
-            server_state.max_monitored_item_queue_size
-        // Future - for event monitored items MaxUInt32 returns the maximum queue size the server support
-        // for event notifications
         } else {
-            requested_queue_size
+            requested_queue_size.min(max_queue_size)
         }
     }
 
@@ -597,7 +920,18 @@ impl MonitoredItem {
         &self.triggered_items
     }
 
+    /// Sets the monitoring mode of the item. Samples queued while the item is `Sampling` are kept
+    /// so they are reported if the item is switched to `Reporting` or triggered. Disabling the
+    /// item discards its queue and last value, so it reports its current value when it is enabled
+    /// again.
     pub fn set_monitoring_mode(&mut self, monitoring_mode: MonitoringMode) {
+        if monitoring_mode == MonitoringMode::Disabled {
+            self.notification_queue.clear();
+            self.queue_overflow = false;
+            self.last_data_value = None;
+            self.aggregate_samples.clear();
+            self.aggregate_interval_start = None;
+        }
         self.monitoring_mode = monitoring_mode;
     }
 
@@ -609,11 +943,20 @@ impl MonitoredItem {
         self.queue_size
     }
 
+    pub fn notification_queue_is_empty(&self) -> bool {
+        self.notification_queue.is_empty()
+    }
+
     #[cfg(test)]
     pub fn queue_overflow(&self) -> bool {
         self.queue_overflow
     }
 
+    /// Returns the number of times the notification queue has overflowed and discarded a value
+    pub fn queue_overflow_count(&self) -> u32 {
+        self.queue_overflow_count
+    }
+
     #[cfg(test)]
     pub fn notification_queue(&self) -> &VecDeque<Notification> {
         &self.notification_queue
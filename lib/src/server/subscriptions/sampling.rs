@@ -0,0 +1,169 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::types::*;
+
+/// How often a monitored item is sampled
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum SamplingRate {
+    /// Sampled whenever the publishing interval of the subscription elapses
+    PublishingInterval,
+    /// Sampled on every tick of the subscription timer
+    EveryTick,
+    /// Sampled every so many microseconds
+    Interval(u64),
+}
+
+impl SamplingRate {
+    fn from_sampling_interval(sampling_interval: Duration) -> SamplingRate {
+        if sampling_interval < 0f64 {
+            SamplingRate::PublishingInterval
+        } else if sampling_interval == 0f64 {
+            SamplingRate::EveryTick
+        } else {
+            SamplingRate::Interval(super::duration_from_ms(sampling_interval).as_micros() as u64)
+        }
+    }
+}
+
+/// Monitored items sharing the same sampling interval, sampled together
+#[derive(Debug, Clone)]
+struct SamplingGroup {
+    /// The next time the items are due to be sampled
+    next_sample_time: DateTimeUtc,
+    /// The ids of the monitored items
+    monitored_item_ids: BTreeSet<u32>,
+}
+
+/// Decides which monitored items of a subscription are due to be sampled on a tick. Items are
+/// coalesced into one group per sampling interval so a tick only visits the items that are due,
+/// no matter how many items there are at other rates.
+///
+/// The subscription timer is the resolution of the scheduler, i.e. an item is sampled on the first
+/// tick at or after the time it is due.
+#[derive(Debug, Clone, Default)]
+pub struct SamplingScheduler {
+    /// Items sampled on a fixed interval, keyed by the interval in microseconds
+    groups: BTreeMap<u64, SamplingGroup>,
+    /// Items sampled on every tick
+    every_tick: BTreeSet<u32>,
+    /// Items sampled when the publishing interval elapses
+    publishing_interval: BTreeSet<u32>,
+    /// The rate each item is scheduled at
+    rates: HashMap<u32, SamplingRate>,
+}
+
+impl SamplingScheduler {
+    pub fn new() -> SamplingScheduler {
+        SamplingScheduler::default()
+    }
+
+    /// Schedules a monitored item with the revised sampling interval in milliseconds, replacing
+    /// any previous schedule of the item. A negative interval means the item is sampled at the
+    /// publishing interval and zero means it is sampled on every tick. An item that joins an
+    /// existing group is first sampled when the group is next due, otherwise one interval from
+    /// `now`.
+    pub fn schedule(
+        &mut self,
+        monitored_item_id: u32,
+        sampling_interval: Duration,
+        now: &DateTimeUtc,
+    ) {
+        self.unschedule(monitored_item_id);
+        let rate = SamplingRate::from_sampling_interval(sampling_interval);
+        match rate {
+            SamplingRate::PublishingInterval => {
+                self.publishing_interval.insert(monitored_item_id);
+            }
+            SamplingRate::EveryTick => {
+                self.every_tick.insert(monitored_item_id);
+            }
+            SamplingRate::Interval(interval) => {
+                self.groups
+                    .entry(interval)
+                    .or_insert_with(|| SamplingGroup {
+                        next_sample_time: super::saturating_add(
+                            now,
+                            Self::interval_duration(interval),
+                        ),
+                        monitored_item_ids: BTreeSet::new(),
+                    })
+                    .monitored_item_ids
+                    .insert(monitored_item_id);
+            }
+        }
+        self.rates.insert(monitored_item_id, rate);
+    }
+
+    /// Removes a monitored item from the schedule. Groups left empty are discarded.
+    pub fn unschedule(&mut self, monitored_item_id: u32) {
+        match self.rates.remove(&monitored_item_id) {
+            Some(SamplingRate::PublishingInterval) => {
+                self.publishing_interval.remove(&monitored_item_id);
+            }
+            Some(SamplingRate::EveryTick) => {
+                self.every_tick.remove(&monitored_item_id);
+            }
+            Some(SamplingRate::Interval(interval)) => {
+                if let Some(group) = self.groups.get_mut(&interval) {
+                    group.monitored_item_ids.remove(&monitored_item_id);
+                    if group.monitored_item_ids.is_empty() {
+                        self.groups.remove(&interval);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Removes every monitored item from the schedule
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        self.every_tick.clear();
+        self.publishing_interval.clear();
+        self.rates.clear();
+    }
+
+    /// Returns the ids of the monitored items that are due to be sampled at `now` and moves the
+    /// groups that were due on to their next sample time. A group that has fallen more than an
+    /// interval behind skips the samples it missed rather than sampling repeatedly to catch up.
+    pub fn take_due(
+        &mut self,
+        now: &DateTimeUtc,
+        publishing_interval_elapsed: bool,
+    ) -> BTreeSet<u32> {
+        let mut due = self.every_tick.clone();
+        if publishing_interval_elapsed {
+            due.extend(self.publishing_interval.iter());
+        }
+        self.groups
+            .iter_mut()
+            .filter(|(_, group)| group.next_sample_time <= *now)
+            .for_each(|(interval, group)| {
+                due.extend(group.monitored_item_ids.iter());
+                let interval = Self::interval_duration(*interval);
+                group.next_sample_time = super::saturating_add(&group.next_sample_time, interval);
+                if group.next_sample_time <= *now {
+                    group.next_sample_time = super::saturating_add(now, interval);
+                }
+            });
+        due
+    }
+
+    /// Returns `true` if the monitored item is scheduled
+    pub fn is_scheduled(&self, monitored_item_id: u32) -> bool {
+        self.rates.contains_key(&monitored_item_id)
+    }
+
+    /// Returns the number of distinct sampling intervals that items are scheduled at
+    pub fn interval_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    fn interval_duration(interval: u64) -> chrono::Duration {
+        chrono::Duration::microseconds(i64::try_from(interval).unwrap_or(i64::MAX))
+    }
+}
@@ -9,7 +9,8 @@ use crate::sync::*;
 use crate::types::{
     service_types::{
         MonitoredItemCreateRequest, MonitoredItemCreateResult, MonitoredItemModifyRequest,
-        MonitoredItemModifyResult, NotificationMessage, TimestampsToReturn,
+        MonitoredItemModifyResult, NotificationMessage, ReadValueId,
+        SubscriptionDiagnosticsDataType, TimestampsToReturn,
     },
     status_code::StatusCode,
     *,
@@ -22,7 +23,10 @@ use crate::server::{
     constants,
     diagnostics::ServerDiagnostics,
     state::ServerState,
-    subscriptions::monitored_item::{MonitoredItem, Notification, TickResult},
+    subscriptions::{
+        monitored_item::{MonitoredItem, Notification, TickResult},
+        sampling::SamplingScheduler,
+    },
 };
 
 /// The state of the subscription
@@ -106,6 +110,24 @@ pub(crate) enum TickReason {
     TickTimerFired,
 }
 
+/// A snapshot of a subscription on the server, for diagnostics and for reconciling the monitored
+/// items that a client thinks it has with those the server has.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionInfo {
+    /// Id of the session the subscription belongs to
+    pub session_id: NodeId,
+    /// Subscription id
+    pub subscription_id: u32,
+    /// Publishing interval in milliseconds
+    pub publishing_interval: Duration,
+    /// Priority of the subscription
+    pub priority: u8,
+    /// Whether publishing is enabled
+    pub publishing_enabled: bool,
+    /// Server and client handle of each monitored item, in order of server handle
+    pub monitored_items: Vec<(u32, u32)>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Subscription {
     /// Subscription id
@@ -122,6 +144,9 @@ pub struct Subscription {
     priority: u8,
     /// Map of monitored items
     monitored_items: HashMap<u32, MonitoredItem>,
+    /// Decides which monitored items are sampled on each tick
+    #[serde(skip)]
+    sampling_scheduler: SamplingScheduler,
     /// State of the subscription
     state: SubscriptionState,
     /// A value that contains the number of consecutive publishing timer expirations without Client
@@ -153,6 +178,17 @@ pub struct Subscription {
     // Currently outstanding notifications to send
     #[serde(skip)]
     notifications: VecDeque<NotificationMessage>,
+    /// Number of times the subscription has been modified
+    modify_count: u32,
+    /// Number of times publishing has been enabled or disabled on the subscription
+    enable_count: u32,
+    disable_count: u32,
+    /// Number of publish requests that have been answered with a message from the subscription
+    publish_request_count: u32,
+    /// Number of data change and event notifications, and of all notifications, queued for the client
+    data_change_notifications_count: u32,
+    event_notifications_count: u32,
+    notifications_count: u32,
     /// Server diagnostics to track creation / destruction / modification of the subscription
     #[serde(skip)]
     diagnostics: Arc<RwLock<ServerDiagnostics>>,
@@ -185,6 +221,7 @@ impl Subscription {
             publishing_interval,
             priority,
             monitored_items: HashMap::with_capacity(constants::DEFAULT_MONITORED_ITEM_CAPACITY),
+            sampling_scheduler: SamplingScheduler::new(),
             max_lifetime_counter: lifetime_counter,
             max_keep_alive_counter: keep_alive_counter,
             // State variables
@@ -200,6 +237,13 @@ impl Subscription {
             next_monitored_item_id: 1,
             last_time_publishing_interval_elapsed: chrono::Utc::now(),
             notifications: VecDeque::with_capacity(100),
+            modify_count: 0,
+            enable_count: 0,
+            disable_count: 0,
+            publish_request_count: 0,
+            data_change_notifications_count: 0,
+            event_notifications_count: 0,
+            notifications_count: 0,
             diagnostics,
             diagnostics_on_drop: true,
         };
@@ -246,6 +290,8 @@ impl Subscription {
             .map(|item_to_create| {
                 if !address_space.node_exists(&item_to_create.item_to_monitor.node_id) {
                     Self::monitored_item_create_error(StatusCode::BadNodeIdUnknown)
+                } else if !Self::is_value_readable(address_space, &item_to_create.item_to_monitor) {
+                    Self::monitored_item_create_error(StatusCode::BadNotReadable)
                 } else {
                     // TODO validate the attribute id for the type of node
                     // TODO validate the index range for the node
@@ -259,17 +305,29 @@ impl Subscription {
                         server_state,
                         item_to_create,
                     ) {
-                        Ok(monitored_item) => {
+                        Ok(mut monitored_item) => {
                             if server_state.max_monitored_items_per_sub == 0
                                 || self.monitored_items.len()
-                                    <= server_state.max_monitored_items_per_sub
+                                    < server_state.max_monitored_items_per_sub
                             {
-                                let revised_sampling_interval = monitored_item.sampling_interval();
+                                monitored_item.apply_minimum_sampling_interval(
+                                    address_space,
+                                    self.publishing_interval,
+                                );
+                                let revised_sampling_interval = Self::revised_sampling_interval(
+                                    self.publishing_interval,
+                                    &monitored_item,
+                                );
                                 let revised_queue_size = monitored_item.queue_size() as u32;
                                 // Validate the filter before registering the item
                                 match monitored_item.validate_filter(address_space) {
                                     Ok(filter_result) => {
                                         // Register the item with the subscription
+                                        self.sampling_scheduler.schedule(
+                                            monitored_item_id,
+                                            monitored_item.sampling_interval(),
+                                            now,
+                                        );
                                         self.monitored_items
                                             .insert(monitored_item_id, monitored_item);
                                         self.next_monitored_item_id += 1;
@@ -308,13 +366,12 @@ impl Subscription {
         items_to_modify: &[MonitoredItemModifyRequest],
     ) -> Vec<MonitoredItemModifyResult> {
         self.reset_lifetime_counter();
+        let now = chrono::Utc::now();
         items_to_modify
             .iter()
             .map(|item_to_modify| {
-                match self
-                    .monitored_items
-                    .get_mut(&item_to_modify.monitored_item_id)
-                {
+                let monitored_item_id = item_to_modify.monitored_item_id;
+                match self.monitored_items.get_mut(&monitored_item_id) {
                     Some(monitored_item) => {
                         // Try to change the monitored item according to the modify request
                         let modify_result = monitored_item.modify(
@@ -324,12 +381,28 @@ impl Subscription {
                             item_to_modify,
                         );
                         match modify_result {
-                            Ok(filter_result) => MonitoredItemModifyResult {
-                                status_code: StatusCode::Good,
-                                revised_sampling_interval: monitored_item.sampling_interval(),
-                                revised_queue_size: monitored_item.queue_size() as u32,
-                                filter_result,
-                            },
+                            Ok(filter_result) => {
+                                monitored_item.apply_minimum_sampling_interval(
+                                    address_space,
+                                    self.publishing_interval,
+                                );
+                                let revised_sampling_interval = Self::revised_sampling_interval(
+                                    self.publishing_interval,
+                                    monitored_item,
+                                );
+                                let revised_queue_size = monitored_item.queue_size() as u32;
+                                self.sampling_scheduler.schedule(
+                                    monitored_item_id,
+                                    monitored_item.sampling_interval(),
+                                    &now,
+                                );
+                                MonitoredItemModifyResult {
+                                    status_code: StatusCode::Good,
+                                    revised_sampling_interval,
+                                    revised_queue_size,
+                                    filter_result,
+                                }
+                            }
                             Err(err) => MonitoredItemModifyResult {
                                 status_code: err,
                                 revised_sampling_interval: 0f64,
@@ -350,6 +423,28 @@ impl Subscription {
             .collect()
     }
 
+    /// Tests if the item monitors a value that the access level of its variable allows to be read
+    fn is_value_readable(address_space: &AddressSpace, item_to_monitor: &ReadValueId) -> bool {
+        item_to_monitor.attribute_id != AttributeId::Value as u32
+            || address_space
+                .find_variable_by_ref(&item_to_monitor.node_id)
+                .is_none_or(|variable| variable.is_readable())
+    }
+
+    /// The sampling interval the server actually uses for a monitored item, as returned to the
+    /// client. Items that sample at the publishing interval report the publishing interval.
+    fn revised_sampling_interval(
+        publishing_interval: Duration,
+        monitored_item: &MonitoredItem,
+    ) -> Duration {
+        let sampling_interval = monitored_item.sampling_interval();
+        if sampling_interval < 0f64 {
+            publishing_interval
+        } else {
+            sampling_interval
+        }
+    }
+
     /// Sets the monitoring mode on one monitored item
     pub fn set_monitoring_mode(
         &mut self,
@@ -371,27 +466,93 @@ impl Subscription {
             .iter()
             .map(
                 |item_to_delete| match self.monitored_items.remove(item_to_delete) {
-                    Some(_) => StatusCode::Good,
+                    Some(_) => {
+                        self.sampling_scheduler.unschedule(*item_to_delete);
+                        StatusCode::Good
+                    }
                     None => StatusCode::BadMonitoredItemIdInvalid,
                 },
             )
             .collect()
     }
 
-    // Returns two vecs representing the server and client handles for each monitored item.
-    // Called from the GetMonitoredItems impl
-    pub fn get_handles(&self) -> (Vec<u32>, Vec<u32>) {
-        let server_handles = self
-            .monitored_items
-            .values()
-            .map(|i| i.monitored_item_id())
-            .collect();
-        let client_handles = self
+    /// Returns the server and client handle of each monitored item, in order of server handle.
+    pub fn monitored_item_handles(&self) -> Vec<(u32, u32)> {
+        let mut handles = self
             .monitored_items
             .values()
-            .map(|i| i.client_handle())
-            .collect();
-        (server_handles, client_handles)
+            .map(|i| (i.monitored_item_id(), i.client_handle()))
+            .collect::<Vec<_>>();
+        handles.sort_unstable();
+        handles
+    }
+
+    // Returns two vecs representing the server and client handles for each monitored item, where
+    // the handles at the same index belong to the same item. Called from the GetMonitoredItems impl
+    pub fn get_handles(&self) -> (Vec<u32>, Vec<u32>) {
+        self.monitored_item_handles().into_iter().unzip()
+    }
+
+    /// Returns a snapshot of the subscription, which belongs to the session with the supplied id.
+    pub fn info(&self, session_id: &NodeId) -> SubscriptionInfo {
+        SubscriptionInfo {
+            session_id: session_id.clone(),
+            subscription_id: self.subscription_id,
+            publishing_interval: self.publishing_interval,
+            priority: self.priority,
+            publishing_enabled: self.publishing_enabled,
+            monitored_items: self.monitored_item_handles(),
+        }
+    }
+
+    /// Returns the diagnostics of the subscription, which belongs to the session with the supplied
+    /// id. Counts that are kept outside of the subscription are left at 0.
+    pub fn diagnostics(&self, session_id: &NodeId) -> SubscriptionDiagnosticsDataType {
+        SubscriptionDiagnosticsDataType {
+            session_id: session_id.clone(),
+            subscription_id: self.subscription_id,
+            priority: self.priority,
+            publishing_interval: self.publishing_interval,
+            max_keep_alive_count: self.max_keep_alive_counter,
+            max_lifetime_count: self.max_lifetime_counter,
+            max_notifications_per_publish: 0,
+            publishing_enabled: self.publishing_enabled,
+            modify_count: self.modify_count,
+            enable_count: self.enable_count,
+            disable_count: self.disable_count,
+            republish_request_count: 0,
+            republish_message_request_count: 0,
+            republish_message_count: 0,
+            transfer_request_count: 0,
+            transferred_to_alt_client_count: 0,
+            transferred_to_same_client_count: 0,
+            publish_request_count: self.publish_request_count,
+            data_change_notifications_count: self.data_change_notifications_count,
+            event_notifications_count: self.event_notifications_count,
+            notifications_count: self.notifications_count,
+            late_publish_request_count: 0,
+            current_keep_alive_count: self.keep_alive_counter,
+            current_lifetime_count: self.lifetime_counter,
+            unacknowledged_message_count: 0,
+            discarded_message_count: 0,
+            monitored_item_count: self.monitored_items.len() as u32,
+            disabled_monitored_item_count: self
+                .monitored_items
+                .values()
+                .filter(|item| item.monitoring_mode() == MonitoringMode::Disabled)
+                .count() as u32,
+            monitoring_queue_overflow_count: self
+                .monitored_items
+                .values()
+                .map(|item| item.queue_overflow_count())
+                .fold(0u32, |count, item_count| count.wrapping_add(item_count)),
+            next_sequence_number: if self.last_sequence_number == u32::MAX {
+                1
+            } else {
+                self.last_sequence_number + 1
+            },
+            event_queue_over_flow_count: 0,
+        }
     }
 
     /// Sets the resend data flag which means the next publish request will receive the latest value
@@ -406,11 +567,11 @@ impl Subscription {
         // Look at the last expiration time compared to now and see if it matches
         // or exceeds the publishing interval
         let publishing_interval = super::duration_from_ms(self.publishing_interval);
-        // TODO unwrap logic needs to change
+        // A time before the last expiration, e.g. if the clock went back, counts as no time elapsed
         let elapsed = now
             .signed_duration_since(self.last_time_publishing_interval_elapsed)
             .to_std()
-            .unwrap();
+            .unwrap_or_default();
         if elapsed >= publishing_interval {
             self.last_time_publishing_interval_elapsed = *now;
             true
@@ -499,6 +660,18 @@ impl Subscription {
         }
         // debug!("Enqueuing notification {:?}", notification);
         self.last_sequence_number = notification.sequence_number;
+        for notification_data in notification.notification_data.iter().flatten() {
+            match notification_data.object_id() {
+                Ok(ObjectId::DataChangeNotification_Encoding_DefaultBinary) => {
+                    self.data_change_notifications_count += 1
+                }
+                Ok(ObjectId::EventNotificationList_Encoding_DefaultBinary) => {
+                    self.event_notifications_count += 1
+                }
+                _ => {}
+            }
+            self.notifications_count += 1;
+        }
         self.notifications.push_back(notification);
     }
 
@@ -511,11 +684,17 @@ impl Subscription {
         // Now act on the state's action
         match update_state_result.update_state_action {
             UpdateStateAction::None => {
-                if let Some(ref notification) = notification {
-                    // Reset the next sequence number to the discarded notification
-                    let notification_sequence_number = notification.sequence_number;
-                    self.sequence_number.set_next(notification_sequence_number);
-                    debug!("Notification message nr {} was being ignored for a do-nothing, update state was {:?}", notification_sequence_number, update_state_result);
+                if let Some(notification) = notification {
+                    if self.state == SubscriptionState::Late {
+                        // The client has no publish request waiting, so the notification is kept
+                        // until it sends one rather than lose the values taken from the items
+                        self.enqueue_notification(notification);
+                    } else {
+                        // Reset the next sequence number to the discarded notification
+                        let notification_sequence_number = notification.sequence_number;
+                        self.sequence_number.set_next(notification_sequence_number);
+                        debug!("Notification message nr {} was being ignored for a do-nothing, update state was {:?}", notification_sequence_number, update_state_result);
+                    }
                 }
                 // Send nothing
             }
@@ -555,6 +734,7 @@ impl Subscription {
                 // Delete the monitored items, issue a status change for the subscription
                 debug!("Subscription status change to closed / timeout");
                 self.monitored_items.clear();
+                self.sampling_scheduler.clear();
                 let notification = NotificationMessage::status_change(
                     self.sequence_number.next(),
                     DateTime::from(*now),
@@ -565,8 +745,13 @@ impl Subscription {
         }
     }
 
+    /// Takes the oldest queued notification, to answer a publish request with.
     pub(crate) fn take_notification(&mut self) -> Option<NotificationMessage> {
-        self.notifications.pop_front()
+        let notification = self.notifications.pop_front();
+        if notification.is_some() {
+            self.publish_request_count += 1;
+        }
+        notification
     }
 
     // See OPC UA Part 4 5.13.1.2 State Table
@@ -636,7 +821,7 @@ impl Subscription {
         // more state tests that match on more than one state
         match self.state {
             SubscriptionState::Normal | SubscriptionState::Late | SubscriptionState::KeepAlive => {
-                if self.lifetime_counter == 1 {
+                if self.lifetime_counter <= 1 {
                     // State #27
                     self.state = SubscriptionState::Closed;
                     return UpdateStateResult::new(
@@ -853,13 +1038,29 @@ impl Subscription {
         let mut triggered_items: BTreeSet<u32> = BTreeSet::new();
         let mut monitored_item_notifications = Vec::with_capacity(self.monitored_items.len() * 2);
 
-        for monitored_item in self.monitored_items.values_mut() {
+        // While publishing is disabled the monitored items keep sampling into their own queues
+        // and nothing is taken from them until publishing is enabled again.
+        let report = publishing_interval_elapsed && self.publishing_enabled;
+
+        // Only the items that are due are sampled, unless everything is being resent
+        let due = self
+            .sampling_scheduler
+            .take_due(now, publishing_interval_elapsed);
+        let items_to_sample: Vec<u32> = if resend_data {
+            self.monitored_items.keys().copied().collect()
+        } else {
+            due.into_iter().collect()
+        };
+
+        for monitored_item_id in items_to_sample.iter() {
+            let Some(monitored_item) = self.monitored_items.get_mut(monitored_item_id) else {
+                continue;
+            };
             // If this returns true then the monitored item wants to report its notification
             let monitoring_mode = monitored_item.monitoring_mode();
-            match monitored_item.tick(now, address_space, publishing_interval_elapsed, resend_data)
-            {
+            match monitored_item.sample(now, address_space, resend_data) {
                 TickResult::ReportValueChanged => {
-                    if publishing_interval_elapsed {
+                    if report {
                         // If this monitored item has triggered items, then they need to be handled
                         match monitoring_mode {
                             MonitoringMode::Reporting => {
@@ -886,7 +1087,7 @@ impl Subscription {
                 TickResult::ValueChanged => {
                     // The monitored item doesn't have changes to report but its value did change so it
                     // is still necessary to check its triggered items.
-                    if publishing_interval_elapsed {
+                    if report {
                         match monitoring_mode {
                             MonitoringMode::Sampling => {
                                 // If the monitoring mode of the triggering item is SAMPLING, then it is not reported when the
@@ -908,6 +1109,24 @@ impl Subscription {
             }
         }
 
+        // Items that are not due on this tick may still hold samples taken on earlier ticks, which
+        // are reported now that the publishing interval has elapsed
+        if report {
+            self.monitored_items
+                .iter_mut()
+                .filter(|(id, item)| {
+                    item.monitoring_mode() == MonitoringMode::Reporting
+                        && !item.notification_queue_is_empty()
+                        && !items_to_sample.contains(id)
+                })
+                .for_each(|(_, monitored_item)| {
+                    triggered_items.extend(monitored_item.triggered_items().iter());
+                    if let Some(mut notifications) = monitored_item.all_notifications() {
+                        monitored_item_notifications.append(&mut notifications);
+                    }
+                });
+        }
+
         // Are there any triggered items to force a change on?
         triggered_items.iter().for_each(|i| {
             if let Some(ref mut monitored_item) = self.monitored_items.get_mut(i) {
@@ -915,10 +1134,13 @@ impl Subscription {
                 match monitored_item.monitoring_mode() {
                     MonitoringMode::Sampling => {
                         // If the monitoring mode of the item to report is SAMPLING, then it is reported when the
-                        // triggering item triggers the i tems to report.
+                        // triggering item triggers the items to report.
                         //
-                        // Call with the resend_data flag as true to force the monitored item to
-                        monitored_item.check_value(address_space, now, true);
+                        // The samples it has queued are reported. If there are none, call with the resend_data
+                        // flag as true to force the monitored item to queue its current value.
+                        if monitored_item.notification_queue_is_empty() {
+                            monitored_item.check_value(address_space, now, true);
+                        }
                         if let Some(mut notifications) = monitored_item.all_notifications() {
                             monitored_item_notifications.append(&mut notifications);
                         }
@@ -1004,7 +1226,7 @@ impl Subscription {
 
     /// Start or restart the publishing timer and decrement the LifetimeCounter Variable.
     pub fn start_publishing_timer(&mut self) {
-        self.lifetime_counter -= 1;
+        self.lifetime_counter = self.lifetime_counter.saturating_sub(1);
         trace!("Decrementing life time counter {}", self.lifetime_counter);
     }
 
@@ -1082,11 +1304,25 @@ impl Subscription {
         self.priority = priority;
     }
 
+    pub fn publishing_enabled(&self) -> bool {
+        self.publishing_enabled
+    }
+
     pub(crate) fn set_publishing_enabled(&mut self, publishing_enabled: bool) {
+        if publishing_enabled {
+            self.enable_count += 1;
+        } else {
+            self.disable_count += 1;
+        }
         self.publishing_enabled = publishing_enabled;
         self.reset_lifetime_counter();
     }
 
+    /// Counts a modification of the subscription's parameters by the client.
+    pub(crate) fn on_modify(&mut self) {
+        self.modify_count += 1;
+    }
+
     pub(crate) fn set_diagnostics_on_drop(&mut self, diagnostics_on_drop: bool) {
         self.diagnostics_on_drop = diagnostics_on_drop;
     }
@@ -8,7 +8,10 @@ use std::{
 };
 
 use crate::types::{
-    service_types::{NotificationMessage, PublishRequest, PublishResponse, ServiceFault},
+    service_types::{
+        NotificationMessage, PublishRequest, PublishResponse, ServiceFault,
+        SubscriptionDiagnosticsDataType,
+    },
     status_code::StatusCode,
     *,
 };
@@ -122,6 +125,33 @@ impl Subscriptions {
         }
     }
 
+    /// Returns the diagnostics of each subscription, which belong to the session with the supplied id.
+    pub(crate) fn diagnostics(&self, session_id: &NodeId) -> Vec<SubscriptionDiagnosticsDataType> {
+        self.subscriptions
+            .values()
+            .map(|subscription| {
+                let subscription_id = subscription.subscription_id();
+                let mut diagnostics = subscription.diagnostics(session_id);
+                diagnostics.unacknowledged_message_count = self
+                    .retransmission_queue
+                    .keys()
+                    .filter(|(id, _)| *id == subscription_id)
+                    .count() as u32;
+                diagnostics
+            })
+            .collect()
+    }
+
+    /// Returns the number of publish requests waiting for a notification to answer them with.
+    pub(crate) fn publish_request_queue_len(&self) -> usize {
+        self.publish_request_queue.len()
+    }
+
+    /// Returns the number of publish responses waiting to be sent to the client.
+    pub(crate) fn publish_response_queue_len(&self) -> usize {
+        self.publish_response_queue.len()
+    }
+
     /// Returns the number of maxmimum publish requests allowable for the current number of subscriptions
     pub fn max_publish_requests(&self) -> usize {
         // Allow for two requests per subscription
@@ -139,6 +169,10 @@ impl Subscriptions {
         request: PublishRequest,
         address_space: &AddressSpace,
     ) -> Result<(), StatusCode> {
+        // Acknowledgements are processed first so notifications are released even if the request
+        // itself cannot be queued
+        let results = self.process_subscription_acknowledgements(&request);
+
         // Check if we have too  requests waiting already
         let max_publish_requests = self.max_publish_requests();
         if self.publish_request_queue.len() >= max_publish_requests {
@@ -156,7 +190,6 @@ impl Subscriptions {
             Err(StatusCode::BadTooManyPublishRequests)
         } else {
             // Add to the front of the queue - older items are popped from the back
-            let results = self.process_subscription_acknowledgements(&request);
             self.publish_request_queue.push_front(PublishRequestEntry {
                 request_id,
                 request,
@@ -199,6 +232,66 @@ impl Subscriptions {
         self.subscriptions.get_mut(&subscription_id)
     }
 
+    /// Removes a subscription so that it can be transferred to another session, returning it
+    /// together with its notifications that are still waiting to be acknowledged. If the client has
+    /// a publish request queued on this session, it is used to send a status change telling the
+    /// client that the subscription was transferred.
+    pub(crate) fn transfer_out(
+        &mut self,
+        subscription_id: u32,
+        now: &DateTimeUtc,
+    ) -> Option<(Subscription, Vec<NotificationMessage>)> {
+        let subscription = self.subscriptions.remove(&subscription_id)?;
+        let sequence_nrs = self
+            .retransmission_queue
+            .keys()
+            .filter(|k| k.0 == subscription_id)
+            .copied()
+            .collect::<Vec<_>>();
+        let notifications = sequence_nrs
+            .iter()
+            .filter_map(|k| self.retransmission_queue.remove(k))
+            .collect();
+
+        if let Some(publish_request) = self.publish_request_queue.pop_front() {
+            // The status change is not part of the subscription's sequence so it does not
+            // consume a sequence number
+            let notification_message = NotificationMessage::status_change(
+                0,
+                DateTime::from(*now),
+                StatusCode::GoodSubscriptionTransferred,
+            );
+            let response = self.make_publish_response(
+                publish_request,
+                subscription_id,
+                now,
+                notification_message,
+                false,
+                None,
+            );
+            self.publish_response_queue.push_back(response);
+        }
+
+        Some((subscription, notifications))
+    }
+
+    /// Adds a subscription transferred from another session along with its unacknowledged
+    /// notifications, so they can be acknowledged or republished on this session.
+    pub(crate) fn transfer_in(
+        &mut self,
+        subscription: Subscription,
+        notifications: Vec<NotificationMessage>,
+    ) {
+        let subscription_id = subscription.subscription_id();
+        self.subscriptions.insert(subscription_id, subscription);
+        for notification in notifications {
+            self.retransmission_queue.insert(
+                (subscription_id, notification.sequence_number),
+                notification,
+            );
+        }
+    }
+
     /// The tick causes the subscription manager to iterate through individual subscriptions calling tick
     /// on each in order of priority. In each case this could generate data change notifications. Data change
     /// notifications will be attached to the next available publish response and queued for sending
@@ -271,14 +364,18 @@ impl Subscriptions {
             // Search the transmission queue for more notifications from this same subscription
             let more_notifications = self.more_notifications(subscription_id);
 
-            // Get a list of available sequence numbers
-            let available_sequence_numbers = self.available_sequence_numbers(subscription_id);
+            // The notification to be sent is now put into the retransmission queue. Keep-alive
+            // messages carry the next sequence number rather than their own, so they are not
+            // retained and cannot be acknowledged or republished.
+            if notification_message.notification_data.is_some() {
+                self.retransmission_queue.insert(
+                    (subscription_id, notification_message.sequence_number),
+                    notification_message.clone(),
+                );
+            }
 
-            // The notification to be sent is now put into the retransmission queue
-            self.retransmission_queue.insert(
-                (subscription_id, notification_message.sequence_number),
-                notification_message.clone(),
-            );
+            // Get a list of available sequence numbers, including the one being sent
+            let available_sequence_numbers = self.available_sequence_numbers(subscription_id);
 
             // Enqueue a publish response
             let response = self.make_publish_response(
@@ -298,6 +395,33 @@ impl Subscriptions {
         Ok(())
     }
 
+    /// Removes the queued publish requests with the supplied request handle, answering each with
+    /// `BadRequestCancelledByClient`. Returns the number of requests cancelled.
+    pub fn cancel_publish_requests(&mut self, request_handle: IntegerId) -> u32 {
+        let mut cancelled_publish_responses = VecDeque::new();
+        self.publish_request_queue.retain(|request| {
+            let request_header = &request.request.request_header;
+            if request_header.request_handle == request_handle {
+                debug!("Publish request {} has been cancelled", request_handle);
+                cancelled_publish_responses.push_front(PublishResponseEntry {
+                    request_id: request.request_id,
+                    response: ServiceFault::new(
+                        request_header,
+                        StatusCode::BadRequestCancelledByClient,
+                    )
+                    .into(),
+                });
+                false
+            } else {
+                true
+            }
+        });
+        let cancel_count = cancelled_publish_responses.len() as u32;
+        self.publish_response_queue
+            .append(&mut cancelled_publish_responses);
+        cancel_count
+    }
+
     /// Iterates through the existing queued publish requests and creates a timeout
     /// publish response any that have expired.
     pub fn expire_stale_publish_requests(&mut self, now: &DateTimeUtc) {
@@ -390,7 +514,7 @@ impl Subscriptions {
     }
 
     /// Returns the array of available sequence numbers in the retransmission queue for the specified subscription
-    fn available_sequence_numbers(&self, subscription_id: u32) -> Option<Vec<u32>> {
+    pub(crate) fn available_sequence_numbers(&self, subscription_id: u32) -> Option<Vec<u32>> {
         if self.retransmission_queue.is_empty() {
             None
         } else {
@@ -486,15 +610,21 @@ impl Subscriptions {
         self.remove_notifications(&sequence_nrs_to_remove);
 
         // Compare number of items in retransmission queue to max permissible and remove the older
-        // notifications.
+        // notifications. The queue is keyed by subscription and sequence number so the oldest are
+        // found by their publish time.
         let max_retransmission_queue = self.max_publish_requests() * 2;
         if self.retransmission_queue.len() > max_retransmission_queue {
             let remove_count = self.retransmission_queue.len() - max_retransmission_queue;
-            let sequence_nrs_to_remove = self
+            let mut by_publish_time = self
                 .retransmission_queue
                 .iter()
+                .map(|(k, v)| (v.publish_time.checked_ticks(), *k))
+                .collect::<Vec<_>>();
+            by_publish_time.sort();
+            let sequence_nrs_to_remove = by_publish_time
+                .into_iter()
                 .take(remove_count)
-                .map(|(k, _)| *k)
+                .map(|(_, k)| k)
                 .collect::<Vec<_>>();
             self.remove_notifications(&sequence_nrs_to_remove);
         }
@@ -48,6 +48,191 @@ fn namespaces() {
     assert_eq!(address_space.register_namespace("foo").unwrap(), 2u16);
 }
 
+#[test]
+fn namespace_array_and_uri_node_ids() {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+
+    // The namespace array follows the registered namespaces
+    let namespace_array = address_space
+        .get_variable_value(VariableId::Server_NamespaceArray)
+        .unwrap()
+        .value
+        .unwrap();
+    assert_eq!(
+        namespace_array,
+        Variant::from(address_space.namespaces().to_vec())
+    );
+    assert_eq!(address_space.namespaces()[ns as usize], "urn:test");
+
+    // Nodes can be addressed by namespace uri
+    assert_eq!(
+        address_space.namespace_node_id("urn:test", "v1"),
+        Some(NodeId::new(ns, "v1"))
+    );
+    assert_eq!(address_space.namespace_node_id("urn:unknown", "v1"), None);
+
+    let mut node_id = ExpandedNodeId::new(NodeId::new(0, "v1"));
+    node_id.namespace_uri = "urn:test".into();
+    assert_eq!(
+        address_space.resolve_expanded_node_id(&node_id),
+        Some(NodeId::new(ns, "v1"))
+    );
+    node_id.namespace_uri = "urn:unknown".into();
+    assert_eq!(address_space.resolve_expanded_node_id(&node_id), None);
+    let mut node_id = ExpandedNodeId::new(NodeId::new(ns, "v1"));
+    assert_eq!(
+        address_space.resolve_expanded_node_id(&node_id),
+        Some(NodeId::new(ns, "v1"))
+    );
+    // Nodes on other servers are not in this address space
+    node_id.server_index = 1;
+    assert_eq!(address_space.resolve_expanded_node_id(&node_id), None);
+}
+
+#[test]
+fn resolve_remote_node_id() {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    address_space.register_server("urn:remote").unwrap();
+
+    // A local node has its namespace uri replaced by its index
+    let mut node_id = ExpandedNodeId::new(NodeId::new(0, "v1"));
+    node_id.namespace_uri = "urn:test".into();
+    assert_eq!(
+        address_space.resolve_remote_node_id(&node_id, &UAString::null()),
+        Ok(ExpandedNodeId::new(NodeId::new(ns, "v1")))
+    );
+    let mut unknown = node_id.clone();
+    unknown.namespace_uri = "urn:unknown".into();
+    assert_eq!(
+        address_space.resolve_remote_node_id(&unknown, &UAString::null()),
+        Err(StatusCode::BadNodeIdUnknown)
+    );
+
+    // A server uri takes the place of the server index
+    let mut remote = node_id.clone();
+    remote.server_index = 1;
+    assert_eq!(
+        address_space.resolve_remote_node_id(&node_id, &"urn:remote".into()),
+        Ok(remote.clone())
+    );
+    assert_eq!(
+        address_space.resolve_remote_node_id(&remote, &UAString::null()),
+        Ok(remote.clone())
+    );
+    assert_eq!(
+        address_space.resolve_remote_node_id(&node_id, &"urn:unknown".into()),
+        Err(StatusCode::BadServerUriInvalid)
+    );
+    remote.server_index = 2;
+    assert_eq!(
+        address_space.resolve_remote_node_id(&remote, &UAString::null()),
+        Err(StatusCode::BadServerIndexInvalid)
+    );
+}
+
+#[test]
+fn servers_and_remote_references() {
+    let mut address_space = AddressSpace::new();
+
+    // The first server is this server, others are registered for remote references
+    assert_eq!(address_space.servers().len(), 1);
+    assert_eq!(
+        address_space.register_server(""),
+        Err(StatusCode::BadServerUriInvalid)
+    );
+    assert_eq!(address_space.register_server("urn:remote").unwrap(), 1);
+    assert_eq!(address_space.register_server("urn:other").unwrap(), 2);
+    assert_eq!(address_space.register_server("urn:remote").unwrap(), 1);
+    assert_eq!(address_space.server_index("urn:other"), Some(2));
+    assert_eq!(address_space.server_index("urn:unknown"), None);
+    let server_array = address_space
+        .get_variable_value(VariableId::Server_ServerArray)
+        .unwrap()
+        .value
+        .unwrap();
+    assert_eq!(
+        server_array,
+        Variant::from(vec![String::new(), "urn:remote".into(), "urn:other".into()])
+    );
+
+    let source: NodeId = ObjectId::ObjectsFolder.into();
+    let mut remote = ExpandedNodeId::new(NodeId::new(2, "Remote"));
+    remote.namespace_uri = "urn:remote:ns".into();
+
+    // Only registered servers other than this one can be referenced
+    assert_eq!(
+        address_space.insert_remote_reference(
+            &source,
+            &remote,
+            ReferenceTypeId::Organizes,
+            ReferenceDirection::Forward
+        ),
+        Err(StatusCode::BadServerIndexInvalid)
+    );
+    remote.server_index = 3;
+    assert_eq!(
+        address_space.insert_remote_reference(
+            &source,
+            &remote,
+            ReferenceTypeId::Organizes,
+            ReferenceDirection::Forward
+        ),
+        Err(StatusCode::BadServerIndexInvalid)
+    );
+    remote.server_index = 1;
+    assert_eq!(
+        address_space.insert_remote_reference(
+            &NodeId::new(1, "Missing"),
+            &remote,
+            ReferenceTypeId::Organizes,
+            ReferenceDirection::Forward
+        ),
+        Err(StatusCode::BadNodeIdUnknown)
+    );
+    assert!(address_space
+        .insert_remote_reference(
+            &source,
+            &remote,
+            ReferenceTypeId::Organizes,
+            ReferenceDirection::Forward
+        )
+        .is_ok());
+
+    // The reference is found by direction and by type, including subtypes
+    let references = address_space.find_remote_references(
+        &source,
+        BrowseDirection::Forward,
+        Some((ReferenceTypeId::HierarchicalReferences, true)),
+    );
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0].target_node, remote);
+    assert!(references[0].is_forward);
+    assert!(address_space
+        .find_remote_references(&source, BrowseDirection::Inverse, None::<(NodeId, bool)>)
+        .is_empty());
+    assert!(address_space
+        .find_remote_references(
+            &source,
+            BrowseDirection::Both,
+            Some((ReferenceTypeId::HasComponent, true))
+        )
+        .is_empty());
+    // Remote references are not mixed up with local ones
+    assert!(address_space
+        .find_references(&source, Some((ReferenceTypeId::Organizes, false)))
+        .unwrap()
+        .iter()
+        .all(|r| r.target_node != remote.node_id));
+
+    assert!(address_space.delete_remote_reference(&source, &remote, ReferenceTypeId::Organizes));
+    assert!(!address_space.delete_remote_reference(&source, &remote, ReferenceTypeId::Organizes));
+    assert!(address_space
+        .find_remote_references(&source, BrowseDirection::Both, None::<(NodeId, bool)>)
+        .is_empty());
+}
+
 #[test]
 fn find_root_folder() {
     let address_space = AddressSpace::new();
@@ -656,6 +841,89 @@ fn variable_builder() {
     ));
 }
 
+#[test]
+fn builder_from_node_id() {
+    let mut address_space = AddressSpace::new();
+
+    // All the attributes of a variable can be set fluently, starting from the node id
+    let node_id = NodeId::new(1, "Fluent");
+    let inserted = Variable::builder(&node_id)
+        .browse_name("Fluent")
+        .display_name("Fluent variable")
+        .write_mask(WriteMask::DESCRIPTION)
+        .user_write_mask(WriteMask::DESCRIPTION)
+        .data_type(DataTypeId::Double)
+        .value(1.5f64)
+        .writable()
+        .historizing(true)
+        .organized_by(ObjectId::ObjectsFolder)
+        .insert(&mut address_space);
+    assert!(inserted);
+    let v = address_space.find_variable_by_ref(&node_id).unwrap();
+    assert_eq!(v.browse_name(), QualifiedName::new(0, "Fluent"));
+    assert_eq!(v.display_name(), LocalizedText::new("", "Fluent variable"));
+    assert_eq!(
+        v.write_mask().unwrap().bits(),
+        WriteMask::DESCRIPTION.bits()
+    );
+    assert_eq!(
+        v.user_write_mask().unwrap().bits(),
+        WriteMask::DESCRIPTION.bits()
+    );
+    assert!(v.access_level().contains(AccessLevel::CURRENT_WRITE));
+    assert!(v.historizing());
+    assert!(address_space.has_reference(
+        &ObjectId::ObjectsFolder.into(),
+        &node_id,
+        ReferenceTypeId::Organizes
+    ));
+
+    // A node without a browse name is not valid
+    assert!(!Object::builder(&NodeId::new(1, "Nameless")).is_valid());
+
+    let m = crate::server::address_space::types::Method::builder(&NodeId::new(1, "Method"))
+        .browse_name("Method")
+        .executable(true)
+        .user_executable(false)
+        .callback(Box::new(HelloWorld))
+        .build();
+    assert!(m.executable());
+    assert!(!m.user_executable());
+
+    let vt = VariableType::builder(&NodeId::new(1, "VariableType"))
+        .browse_name("VariableType")
+        .data_type(DataTypeId::Int32)
+        .is_abstract(true)
+        .value_rank(1)
+        .array_dimensions(&[2])
+        .value(vec![1i32, 2i32])
+        .build();
+    assert_eq!(vt.data_type(), DataTypeId::Int32.into());
+    assert!(vt.is_abstract());
+    assert_eq!(vt.value_rank(), 1);
+    assert_eq!(vt.array_dimensions(), Some(vec![2]));
+    assert!(vt.value().is_some());
+
+    let dt = DataType::builder(&NodeId::new(1, "DataType"))
+        .browse_name("DataType")
+        .is_abstract(true)
+        .build();
+    assert!(dt.is_abstract());
+
+    let rt = ReferenceType::builder(&NodeId::new(1, "ReferenceType"))
+        .browse_name("ReferenceType")
+        .symmetric(false)
+        .is_abstract(false)
+        .inverse_name("InverseReferenceType")
+        .build();
+    assert!(!rt.symmetric());
+    assert!(!rt.is_abstract());
+    assert_eq!(
+        rt.inverse_name(),
+        Some(LocalizedText::new("", "InverseReferenceType"))
+    );
+}
+
 #[test]
 fn method_builder() {
     let mut address_space = AddressSpace::new();
@@ -915,6 +1183,56 @@ fn is_subtype() {
     });
 }
 
+#[test]
+fn find_objects_by_type() {
+    let mut address_space = AddressSpace::new();
+
+    let node_type_id = NodeId::new(1, "HelloType");
+    let _ot = ObjectTypeBuilder::new(&node_type_id, "HelloType", "HelloType")
+        .subtype_of(ObjectTypeId::BaseObjectType)
+        .insert(&mut address_space);
+
+    let node_id = NodeId::new(1, "Hello");
+    let _o = ObjectBuilder::new(&node_id, "Hello", "Hello")
+        .organized_by(ObjectId::ObjectsFolder)
+        .has_type_definition(node_type_id.clone())
+        .insert(&mut address_space);
+
+    // The object is found by its own type with or without subtypes
+    let found = address_space
+        .find_objects_by_type(node_type_id.clone(), false)
+        .unwrap();
+    assert_eq!(found, vec![node_id.clone()]);
+    let found = address_space
+        .find_objects_by_type(node_type_id.clone(), true)
+        .unwrap();
+    assert_eq!(found, vec![node_id.clone()]);
+
+    // The object is found by a base type of its type only when subtypes are included
+    let found = address_space
+        .find_objects_by_type(ObjectTypeId::BaseObjectType, true)
+        .unwrap();
+    assert!(found.contains(&node_id));
+    let found = address_space
+        .find_objects_by_type(ObjectTypeId::BaseObjectType, false)
+        .unwrap();
+    assert!(!found.contains(&node_id));
+
+    // The object is not found by a subtype of its type
+    let subtype_id = NodeId::new(1, "HelloSubType");
+    let _ot = ObjectTypeBuilder::new(&subtype_id, "HelloSubType", "HelloSubType")
+        .subtype_of(node_type_id.clone())
+        .insert(&mut address_space);
+    assert!(address_space
+        .find_objects_by_type(subtype_id, true)
+        .is_none());
+
+    // A node that is not an object type finds nothing
+    assert!(address_space
+        .find_objects_by_type(node_id.clone(), true)
+        .is_none());
+}
+
 #[test]
 fn hierarchical_references() {
     let address_space = AddressSpace::new();
@@ -952,3 +1270,349 @@ fn hierarchical_references() {
     assert!(refs.contains(&ObjectId::Server_ServerCapabilities_AggregateFunctions.into()));
     assert!(refs.contains(&ObjectId::HistoryServerCapabilities.into()));
 }
+
+#[test]
+fn instantiate_object_type() {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    let mandatory = ObjectId::ModellingRule_Mandatory;
+    let optional = ObjectId::ModellingRule_Optional;
+
+    // DeviceType has a mandatory serial number, and a mandatory status which is overridden
+    // by the subtype
+    let device_type_id = NodeId::new(ns, "DeviceType");
+    ObjectTypeBuilder::new(&device_type_id, "DeviceType", "DeviceType")
+        .subtype_of(ObjectTypeId::BaseObjectType)
+        .insert(&mut address_space);
+    VariableBuilder::new(&NodeId::new(ns, "DeviceType.Serial"), "Serial", "Serial")
+        .data_type(DataTypeId::String)
+        .value("unknown")
+        .has_type_definition(VariableTypeId::PropertyType)
+        .has_modelling_rule(mandatory)
+        .property_of(device_type_id.clone())
+        .insert(&mut address_space);
+    VariableBuilder::new(&NodeId::new(ns, "DeviceType.Status"), "Status", "Status")
+        .data_type(DataTypeId::Int32)
+        .value(0)
+        .has_modelling_rule(mandatory)
+        .component_of(device_type_id.clone())
+        .insert(&mut address_space);
+
+    // PumpType has a mandatory speed with a mandatory property, an optional flow, a mandatory
+    // status and a mandatory method
+    let pump_type_id = NodeId::new(ns, "PumpType");
+    ObjectTypeBuilder::new(&pump_type_id, "PumpType", "PumpType")
+        .subtype_of(device_type_id.clone())
+        .insert(&mut address_space);
+    let speed_id = NodeId::new(ns, "PumpType.Speed");
+    VariableBuilder::new(&speed_id, "Speed", "Speed")
+        .data_type(DataTypeId::Double)
+        .value(1.5f64)
+        .writable()
+        .has_type_definition(VariableTypeId::BaseDataVariableType)
+        .has_modelling_rule(mandatory)
+        .component_of(pump_type_id.clone())
+        .insert(&mut address_space);
+    VariableBuilder::new(&NodeId::new(ns, "PumpType.Speed.Unit"), "Unit", "Unit")
+        .data_type(DataTypeId::String)
+        .value("rpm")
+        .has_modelling_rule(mandatory)
+        .property_of(speed_id.clone())
+        .insert(&mut address_space);
+    VariableBuilder::new(&NodeId::new(ns, "PumpType.Flow"), "Flow", "Flow")
+        .data_type(DataTypeId::Double)
+        .value(0f64)
+        .has_modelling_rule(optional)
+        .component_of(pump_type_id.clone())
+        .insert(&mut address_space);
+    VariableBuilder::new(&NodeId::new(ns, "PumpType.Status"), "Status", "Status")
+        .data_type(DataTypeId::Int32)
+        .value(1)
+        .has_modelling_rule(mandatory)
+        .component_of(pump_type_id.clone())
+        .insert(&mut address_space);
+    let start_id = NodeId::new(ns, "PumpType.Start");
+    MethodBuilder::new(&start_id, "Start", "Start")
+        .component_of(pump_type_id.clone())
+        .reference(
+            mandatory,
+            ReferenceTypeId::HasModellingRule,
+            ReferenceDirection::Forward,
+        )
+        .callback(Box::new(HelloWorld))
+        .insert(&mut address_space);
+
+    // Bad type or parent
+    let objects_folder_id = NodeId::objects_folder_id();
+    assert_eq!(
+        address_space.instantiate(&speed_id, "Pump1", &objects_folder_id),
+        Err(StatusCode::BadTypeDefinitionInvalid)
+    );
+    assert_eq!(
+        address_space.instantiate(&pump_type_id, "Pump1", &NodeId::new(ns, "Missing")),
+        Err(StatusCode::BadParentNodeIdInvalid)
+    );
+
+    let pump_id = address_space
+        .instantiate(&pump_type_id, "Pump1", &objects_folder_id)
+        .unwrap();
+    assert!(address_space.has_reference(&objects_folder_id, &pump_id, ReferenceTypeId::Organizes));
+    assert!(address_space.has_reference(
+        &pump_id,
+        &pump_type_id,
+        ReferenceTypeId::HasTypeDefinition
+    ));
+
+    let find_child = |address_space: &AddressSpace, parent_id: &NodeId, name: &str| {
+        find_node_from_browse_path(address_space, parent_id, &[QualifiedName::new(0, name)])
+            .ok()
+            .map(|node| node.as_node().node_id())
+    };
+
+    // Mandatory children are copied with their values and attributes
+    let speed = find_child(&address_space, &pump_id, "Speed").unwrap();
+    assert_ne!(speed, speed_id);
+    let variable = address_space.find_variable(speed.clone()).unwrap();
+    assert_eq!(variable.data_type(), DataTypeId::Double.into());
+    assert!(variable.is_writable());
+    assert_eq!(
+        address_space
+            .get_variable_value(speed.clone())
+            .unwrap()
+            .value,
+        Some(Variant::from(1.5f64))
+    );
+    assert!(address_space.has_reference(
+        &speed,
+        &VariableTypeId::BaseDataVariableType.into(),
+        ReferenceTypeId::HasTypeDefinition
+    ));
+    assert!(address_space
+        .find_references(&speed, Some((ReferenceTypeId::HasModellingRule, false)))
+        .is_none_or(|r| r.is_empty()));
+    let unit = find_child(&address_space, &speed, "Unit").unwrap();
+    assert_eq!(
+        address_space.get_variable_value(unit).unwrap().value,
+        Some(Variant::from("rpm"))
+    );
+
+    // Supertype children are inherited unless overridden
+    let serial = find_child(&address_space, &pump_id, "Serial").unwrap();
+    assert!(address_space.has_reference(&pump_id, &serial, ReferenceTypeId::HasProperty));
+    let status = find_child(&address_space, &pump_id, "Status").unwrap();
+    assert_eq!(
+        address_space.get_variable_value(status).unwrap().value,
+        Some(Variant::from(1))
+    );
+    let statuses = address_space
+        .find_aggregates_of(&pump_id)
+        .unwrap()
+        .iter()
+        .filter(|n| {
+            address_space
+                .find_node(n)
+                .is_some_and(|n| n.as_node().browse_name() == QualifiedName::new(0, "Status"))
+        })
+        .count();
+    assert_eq!(statuses, 1);
+
+    // Optional children are not copied, methods are shared with the type
+    assert!(find_child(&address_space, &pump_id, "Flow").is_none());
+    assert!(address_space.has_reference(&pump_id, &start_id, ReferenceTypeId::HasComponent));
+
+    // A second instance gets its own children
+    let folder_id = address_space
+        .add_folder("Pumps", "Pumps", &objects_folder_id)
+        .unwrap();
+    let pump2_id = address_space
+        .instantiate(&pump_type_id, "Pump2", &folder_id)
+        .unwrap();
+    assert_ne!(
+        find_child(&address_space, &pump2_id, "Speed").unwrap(),
+        speed
+    );
+}
+
+#[test]
+fn node_snapshot() {
+    let mut address_space = AddressSpace::new();
+    let node_id = NodeId::new(1, "Speed");
+    VariableBuilder::new(&node_id, "Speed", "Speed")
+        .description("Speed of the motor")
+        .data_type(DataTypeId::Double)
+        .value(1.5f64)
+        .writable()
+        .minimum_sampling_interval(100.0)
+        .organized_by(ObjectId::ObjectsFolder)
+        .insert(&mut address_space);
+
+    let snapshot = address_space.node_snapshot(node_id.clone()).unwrap();
+    assert_eq!(snapshot.node_id, node_id);
+    assert_eq!(snapshot.node_class, NodeClass::Variable);
+    assert_eq!(snapshot.browse_name, QualifiedName::from("Speed"));
+    assert_eq!(
+        snapshot.description,
+        Some(LocalizedText::from("Speed of the motor"))
+    );
+    assert_eq!(snapshot.data_type, Some(DataTypeId::Double.into()));
+    assert_eq!(
+        snapshot.value.and_then(|v| v.value),
+        Some(Variant::Double(1.5))
+    );
+    assert_eq!(
+        snapshot.access_level,
+        Some((AccessLevel::CURRENT_READ | AccessLevel::CURRENT_WRITE).bits())
+    );
+    assert_eq!(snapshot.minimum_sampling_interval, Some(100.0));
+    assert_eq!(snapshot.historizing, Some(false));
+    // Attributes of other node classes are not there
+    assert!(snapshot.event_notifier.is_none());
+    assert!(snapshot.is_abstract.is_none());
+
+    let snapshot = address_space
+        .node_snapshot(ObjectId::ObjectsFolder)
+        .unwrap();
+    assert_eq!(snapshot.node_class, NodeClass::Object);
+    assert_eq!(snapshot.event_notifier, Some(0));
+    assert!(snapshot.value.is_none());
+
+    assert!(address_space
+        .node_snapshot(NodeId::new(1, "Missing"))
+        .is_none());
+}
+
+#[test]
+fn model_changes() {
+    let mut address_space = AddressSpace::new();
+    let folder_id = address_space
+        .add_folder("Devices", "Devices", &NodeId::objects_folder_id())
+        .unwrap();
+    assert!(address_space.add_node_version(&folder_id));
+    assert!(!address_space.add_node_version(&folder_id));
+
+    let node_version = |address_space: &AddressSpace| {
+        let node_version_id =
+            find_node_from_browse_path(address_space, &folder_id, &["NodeVersion".into()])
+                .unwrap()
+                .as_node()
+                .node_id();
+        address_space
+            .get_variable_value(node_version_id)
+            .unwrap()
+            .value
+            .unwrap()
+    };
+    let changes = |address_space: &AddressSpace, event_id: &NodeId| {
+        let changes_id = find_node_from_browse_path(address_space, event_id, &["Changes".into()])
+            .unwrap()
+            .as_node()
+            .node_id();
+        match address_space.get_variable_value(changes_id).unwrap().value {
+            Some(Variant::Array(array)) => array
+                .values
+                .iter()
+                .map(|v| match v {
+                    Variant::ExtensionObject(v) => v
+                        .decode_inner::<ModelChangeStructureDataType>(&DecodingOptions::test())
+                        .unwrap(),
+                    _ => panic!(),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!(),
+        }
+    };
+
+    // Nothing is reported until changes are tracked
+    let now = chrono::Utc::now();
+    assert!(address_space.report_model_changes(&now).is_none());
+    address_space.set_model_change_tracking(true);
+    assert!(address_space.report_model_changes(&now).is_none());
+    // Clients can subscribe to the events of the server
+    assert!(address_space
+        .find_node(&ObjectId::Server.into())
+        .unwrap()
+        .as_node()
+        .get_attribute(
+            TimestampsToReturn::Neither,
+            AttributeId::EventNotifier,
+            NumericRange::None,
+            &QualifiedName::null(),
+        )
+        .is_some_and(|v| v.value == Some(Variant::Byte(1))));
+
+    let node_id = NodeId::new(1, "Pump");
+    VariableBuilder::new(&node_id, "Pump", "Pump")
+        .data_type(DataTypeId::Boolean)
+        .value(false)
+        .has_type_definition(VariableTypeId::BaseDataVariableType)
+        .organized_by(folder_id.clone())
+        .insert(&mut address_space);
+    // Events are not model changes
+    let mut event = BaseEventType::new_now(
+        NodeId::new(1, "Event"),
+        ObjectTypeId::BaseEventType,
+        "Event",
+        "Event",
+        folder_id.clone(),
+    )
+    .source_node(ObjectId::Server);
+    assert!(event.raise(&mut address_space).is_ok());
+
+    let first_event_id = address_space.report_model_changes(&now).unwrap();
+    // It is an event of the server, along with the one raised before
+    let events = events_for_object(
+        ObjectId::Server,
+        &address_space,
+        &(now - chrono::Duration::seconds(1)),
+    )
+    .unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.contains(&first_event_id));
+    assert_eq!(
+        changes(&address_space, &first_event_id),
+        vec![
+            ModelChangeStructureDataType {
+                affected: node_id.clone(),
+                affected_type: VariableTypeId::BaseDataVariableType.into(),
+                verb: ModelChangeStructureVerbMask::NodeAdded as u8,
+            },
+            ModelChangeStructureDataType {
+                affected: folder_id.clone(),
+                affected_type: ObjectTypeId::FolderType.into(),
+                verb: ModelChangeStructureVerbMask::ReferenceAdded as u8,
+            },
+        ]
+    );
+    assert_eq!(node_version(&address_space), Variant::from("2"));
+    assert!(address_space.report_model_changes(&now).is_none());
+
+    // Changes to the same node are combined
+    address_space.insert_reference(&folder_id, &node_id, ReferenceTypeId::HasComponent);
+    address_space.delete(&node_id, true);
+    let now = now + chrono::Duration::seconds(30);
+    let event_id = address_space.report_model_changes(&now).unwrap();
+    assert_eq!(
+        changes(&address_space, &event_id),
+        vec![
+            ModelChangeStructureDataType {
+                affected: folder_id.clone(),
+                affected_type: ObjectTypeId::FolderType.into(),
+                verb: ModelChangeStructureVerbMask::ReferenceAdded as u8
+                    | ModelChangeStructureVerbMask::ReferenceDeleted as u8,
+            },
+            ModelChangeStructureDataType {
+                affected: node_id.clone(),
+                affected_type: VariableTypeId::BaseDataVariableType.into(),
+                verb: ModelChangeStructureVerbMask::ReferenceAdded as u8
+                    | ModelChangeStructureVerbMask::NodeDeleted as u8,
+            },
+        ]
+    );
+    assert_eq!(node_version(&address_space), Variant::from("3"));
+
+    // Events are purged after a minute
+    let now = now + chrono::Duration::seconds(45);
+    assert!(address_space.report_model_changes(&now).is_none());
+    assert!(address_space.find_node(&first_event_id).is_none());
+    assert!(address_space.find_node(&event_id).is_some());
+}
@@ -0,0 +1,327 @@
+use chrono::Duration;
+
+use crate::server::historical::aggregates::*;
+
+use super::*;
+
+fn t0() -> DateTime {
+    DateTime::ymd_hms(2024, 1, 1, 0, 0, 0)
+}
+
+fn at(millis: i64) -> DateTime {
+    t0() + Duration::milliseconds(millis)
+}
+
+fn raw<V>(millis: i64, value: V, status: StatusCode) -> DataValue
+where
+    V: Into<Variant>,
+{
+    DataValue {
+        value: Some(value.into()),
+        status: Some(status),
+        source_timestamp: Some(at(millis)),
+        source_picoseconds: None,
+        server_timestamp: Some(at(millis)),
+        server_picoseconds: None,
+    }
+}
+
+fn config(
+    treat_uncertain_as_bad: bool,
+    percent_data_bad: u8,
+    percent_data_good: u8,
+) -> AggregateConfiguration {
+    AggregateConfiguration {
+        use_server_capabilities_defaults: false,
+        treat_uncertain_as_bad,
+        percent_data_bad,
+        percent_data_good,
+        use_sloped_extrapolation: false,
+    }
+}
+
+fn calculator(
+    aggregate: ObjectId,
+    configuration: &AggregateConfiguration,
+    processing_interval: f64,
+) -> AggregateCalculator {
+    AggregateCalculator::new(&aggregate.into(), configuration, processing_interval).unwrap()
+}
+
+/// Values 1 to 6, one every second, all good
+fn ramp() -> Vec<DataValue> {
+    (0..6)
+        .map(|i| raw(i * 1000, (i + 1) as f64, StatusCode::Good))
+        .collect()
+}
+
+fn values(results: &[DataValue]) -> Vec<Option<Variant>> {
+    results.iter().map(|v| v.value.clone()).collect()
+}
+
+fn calculated() -> StatusCode {
+    StatusCode::Good | StatusCode::HISTORICAL_CALCULATED
+}
+
+#[test]
+fn aggregate_function_from_node_id() {
+    assert_eq!(
+        AggregateFunction::from_node_id(&ObjectId::AggregateFunction_Average.into()).unwrap(),
+        AggregateFunction::Average
+    );
+    AggregateFunction::ALL.iter().for_each(|f| {
+        assert_eq!(
+            AggregateFunction::from_node_id(&f.object_id().into()).unwrap(),
+            *f
+        );
+    });
+    // Not supported, or not an aggregate at all
+    assert_eq!(
+        AggregateFunction::from_node_id(&ObjectId::AggregateFunction_Total.into()).unwrap_err(),
+        StatusCode::BadAggregateNotSupported
+    );
+    assert_eq!(
+        AggregateFunction::from_node_id(&NodeId::new(2, "Average")).unwrap_err(),
+        StatusCode::BadAggregateNotSupported
+    );
+}
+
+#[test]
+fn aggregate_configuration() {
+    let node_id: NodeId = ObjectId::AggregateFunction_Average.into();
+
+    // Server defaults replace whatever else was asked for
+    let mut requested = config(false, 10, 10);
+    requested.use_server_capabilities_defaults = true;
+    let calculator = AggregateCalculator::new(&node_id, &requested, 1000f64).unwrap();
+    assert_eq!(
+        *calculator.configuration(),
+        default_aggregate_configuration()
+    );
+
+    // The good and bad percentages must not leave a gap between them
+    assert_eq!(
+        AggregateCalculator::new(&node_id, &config(true, 20, 50), 1000f64).unwrap_err(),
+        StatusCode::BadAggregateConfigurationRejected
+    );
+    assert_eq!(
+        AggregateCalculator::new(&node_id, &config(true, 101, 100), 1000f64).unwrap_err(),
+        StatusCode::BadAggregateConfigurationRejected
+    );
+    assert!(AggregateCalculator::new(&node_id, &config(true, 50, 50), 1000f64).is_ok());
+
+    assert_eq!(
+        AggregateCalculator::new(&node_id, &config(true, 100, 100), -1f64).unwrap_err(),
+        StatusCode::BadInvalidArgument
+    );
+}
+
+#[test]
+fn aggregate_average_min_max() {
+    let configuration = default_aggregate_configuration();
+    let process = |aggregate: ObjectId| {
+        calculator(aggregate, &configuration, 2000f64).process(&t0(), &at(6000), &ramp())
+    };
+
+    let results = process(ObjectId::AggregateFunction_Average);
+    assert_eq!(
+        values(&results),
+        vec![
+            Some(1.5f64.into()),
+            Some(3.5f64.into()),
+            Some(5.5f64.into())
+        ]
+    );
+    results.iter().enumerate().for_each(|(i, v)| {
+        assert_eq!(v.status, Some(calculated()));
+        assert_eq!(v.source_timestamp, Some(at(i as i64 * 2000)));
+    });
+
+    assert_eq!(
+        values(&process(ObjectId::AggregateFunction_Minimum)),
+        vec![Some(1f64.into()), Some(3f64.into()), Some(5f64.into())]
+    );
+    assert_eq!(
+        values(&process(ObjectId::AggregateFunction_Maximum)),
+        vec![Some(2f64.into()), Some(4f64.into()), Some(6f64.into())]
+    );
+    assert_eq!(
+        values(&process(ObjectId::AggregateFunction_Range)),
+        vec![Some(1f64.into()); 3]
+    );
+    assert_eq!(
+        values(&process(ObjectId::AggregateFunction_Delta)),
+        vec![Some(1f64.into()); 3]
+    );
+    assert_eq!(
+        values(&process(ObjectId::AggregateFunction_Count)),
+        vec![Some(2i32.into()); 3]
+    );
+
+    // The actual time variants are stamped with the time of the raw value
+    let results = process(ObjectId::AggregateFunction_MaximumActualTime);
+    assert_eq!(results[0].value, Some(2f64.into()));
+    assert_eq!(results[0].source_timestamp, Some(at(1000)));
+
+    // Start and end return the raw values
+    let results = process(ObjectId::AggregateFunction_End);
+    assert_eq!(results[1], ramp()[3]);
+}
+
+#[test]
+fn aggregate_quality() {
+    // A good value, then a bad one for the second half of the interval
+    let raw_values = vec![
+        raw(0, 1f64, StatusCode::Good),
+        raw(1000, 100f64, StatusCode::BadSensorFailure),
+    ];
+
+    // The bad value is left out, and half the interval being bad makes the result uncertain
+    let results = calculator(
+        ObjectId::AggregateFunction_Average,
+        &default_aggregate_configuration(),
+        0f64,
+    )
+    .process(&t0(), &at(2000), &raw_values);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].value, Some(1f64.into()));
+    assert_eq!(
+        results[0].status,
+        Some(StatusCode::UncertainDataSubNormal | StatusCode::HISTORICAL_CALCULATED)
+    );
+
+    // Half bad is bad enough when the configuration says so
+    let results = calculator(
+        ObjectId::AggregateFunction_Average,
+        &config(true, 50, 50),
+        0f64,
+    )
+    .process(&t0(), &at(2000), &raw_values);
+    assert_eq!(
+        results[0].status,
+        Some(StatusCode::BadNoData | StatusCode::HISTORICAL_CALCULATED)
+    );
+    assert!(results[0].value.is_none());
+
+    // The quality aggregates report the split
+    let quality = |aggregate: ObjectId| {
+        calculator(aggregate, &default_aggregate_configuration(), 0f64).process(
+            &t0(),
+            &at(2000),
+            &raw_values,
+        )[0]
+        .value
+        .clone()
+    };
+    assert_eq!(
+        quality(ObjectId::AggregateFunction_PercentGood),
+        Some(50f64.into())
+    );
+    assert_eq!(
+        quality(ObjectId::AggregateFunction_DurationBad),
+        Some(1000f64.into())
+    );
+    assert_eq!(
+        quality(ObjectId::AggregateFunction_WorstQuality),
+        Some(Variant::StatusCode(StatusCode::BadSensorFailure))
+    );
+
+    // Uncertain values are only used if they are not treated as bad
+    let raw_values = vec![
+        raw(0, 1f64, StatusCode::Good),
+        raw(1000, 3f64, StatusCode::UncertainLastUsableValue),
+    ];
+    let average = |configuration: &AggregateConfiguration| {
+        calculator(ObjectId::AggregateFunction_Average, configuration, 0f64).process(
+            &t0(),
+            &at(2000),
+            &raw_values,
+        )[0]
+        .value
+        .clone()
+    };
+    assert_eq!(
+        average(&default_aggregate_configuration()),
+        Some(1f64.into())
+    );
+    assert_eq!(average(&config(false, 100, 100)), Some(2f64.into()));
+
+    // No data at all in an interval
+    let results = calculator(
+        ObjectId::AggregateFunction_Average,
+        &default_aggregate_configuration(),
+        1000f64,
+    )
+    .process(&at(10000), &at(11000), &raw_values);
+    assert_eq!(results[0].status, Some(StatusCode::BadNoData));
+}
+
+#[test]
+fn aggregate_interpolative() {
+    let raw_values = vec![
+        raw(0, 0f64, StatusCode::Good),
+        raw(4000, 40f64, StatusCode::Good),
+    ];
+    let mut configuration = default_aggregate_configuration();
+
+    let results = calculator(
+        ObjectId::AggregateFunction_Interpolative,
+        &configuration,
+        1000f64,
+    )
+    .process(&t0(), &at(6000), &raw_values);
+    assert_eq!(
+        values(&results),
+        vec![
+            Some(0f64.into()),
+            Some(10f64.into()),
+            Some(20f64.into()),
+            Some(30f64.into()),
+            Some(40f64.into()),
+            Some(40f64.into())
+        ]
+    );
+    // Raw values are passed through, values between them are interpolated and values after the
+    // last one are extrapolated
+    assert_eq!(results[0].status, Some(StatusCode::Good));
+    assert_eq!(
+        results[1].status,
+        Some(StatusCode::Good | StatusCode::HISTORICAL_INTERPOLATED)
+    );
+    assert_eq!(
+        results[5].status,
+        Some(StatusCode::UncertainDataSubNormal | StatusCode::HISTORICAL_INTERPOLATED)
+    );
+
+    configuration.use_sloped_extrapolation = true;
+    let results = calculator(
+        ObjectId::AggregateFunction_Interpolative,
+        &configuration,
+        1000f64,
+    )
+    .process(&at(5000), &at(6000), &raw_values);
+    assert_eq!(results[0].value, Some(50f64.into()));
+}
+
+#[test]
+fn aggregate_reverse_partial() {
+    // The end time before the start time returns the intervals newest first
+    let results = calculator(
+        ObjectId::AggregateFunction_Count,
+        &default_aggregate_configuration(),
+        2000f64,
+    )
+    .process(&at(5000), &t0(), &ramp());
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].source_timestamp, Some(at(4000)));
+    assert_eq!(results[0].value, Some(1i32.into()));
+    assert_eq!(results[2].source_timestamp, Some(t0()));
+    assert_eq!(results[2].value, Some(2i32.into()));
+
+    // The last interval is shorter than the processing interval
+    assert_eq!(
+        results[0].status,
+        Some(calculated() | StatusCode::HISTORICAL_PARTIAL)
+    );
+    assert_eq!(results[2].status, Some(calculated()));
+}
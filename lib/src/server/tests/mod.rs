@@ -1,13 +1,23 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use chrono;
 
 use crate::{
-    core::{config::Config, supported_message::SupportedMessage},
+    core::{
+        config::{Config, ConfigOverrideError},
+        supported_message::SupportedMessage,
+    },
     server::{
         address_space::{address_space::*, variable::*},
         builder::ServerBuilder,
-        config::ServerConfig,
+        config::{
+            OperationalLimits, ReloadError, ServerConfig, ServerEndpoint, ServerUserToken,
+            ANONYMOUS_USER_TOKEN_ID,
+        },
+        server::Server,
         session::*,
         subscriptions::*,
     },
@@ -16,7 +26,10 @@ use crate::{
 };
 
 mod address_space;
+mod aggregates;
 mod events;
+mod nodeset;
+mod polling;
 mod services;
 mod subscriptions;
 
@@ -98,6 +111,424 @@ pub fn server_config_invalid() {
         .user_token_ids
         .insert("hello".to_string());
     assert_eq!(config.is_valid(), false);
+
+    // An idle session warning that is not before the timeout
+    config = ServerBuilder::new_anonymous("foo").config();
+    let endpoint = config.endpoints.get_mut("none").unwrap();
+    endpoint.idle_session_timeout = 60;
+    endpoint.idle_session_warning = 60;
+    assert!(!config.is_valid());
+
+    // A queue that can't hold a single response
+    config = ServerBuilder::new_anonymous("foo")
+        .max_queued_responses(0)
+        .config();
+    assert!(!config.is_valid());
+
+    // Two endpoints with the same path and security
+    config = ServerBuilder::new_anonymous("foo")
+        .endpoint(
+            "none2",
+            ServerEndpoint::new_none("/", &[ANONYMOUS_USER_TOKEN_ID.to_string()]),
+        )
+        .config();
+    assert!(!config.is_valid());
+
+    // Monitored items that can't queue a value
+    config = ServerBuilder::new_anonymous("foo")
+        .max_monitored_item_queue_size(0)
+        .config();
+    assert!(!config.is_valid());
+
+    // A user with a role that is not recognized
+    config = ServerBuilder::new_anonymous("foo")
+        .user_token(
+            "op",
+            ServerUserToken::user_pass("op", "pwd").roles(&["Operater"]),
+        )
+        .config();
+    assert!(!config.is_valid());
+}
+
+#[test]
+pub fn server_config_overrides() {
+    let config = ServerBuilder::new_anonymous("foo").config();
+    let config = config
+        .with_overrides(vec![
+            ("OPCUA_SERVER_HOST", "10.0.0.1"),
+            ("OPCUA_SERVER_PORT", "4841"),
+            ("OPCUA_SERVER_LIMITS__MAX_SESSIONS", "20"),
+            ("OPCUA_SERVER_ENDPOINTS__NONE__PATH", "/plant"),
+            ("OPCUA_SERVER_BUILD_NUMBER", "\"1.0\""),
+            ("OPCUA_SERVER_WEBSOCKET_CONFIG__PORT", "4843"),
+            // The server's own variable wins over the shared one
+            ("OPCUA_SERVER_PKI_DIR", "/srv/pki"),
+            ("OPCUA_PKI_DIR", "/var/pki"),
+            ("OPCUA_CERT_PATH", "own/server.der"),
+            // Not for the server
+            ("OPCUA_CLIENT_SESSION_NAME", "bar"),
+            ("PATH", "/usr/bin"),
+        ])
+        .unwrap();
+    assert_eq!(config.tcp_config.host, "10.0.0.1");
+    assert_eq!(config.tcp_config.port, 4841);
+    assert_eq!(config.limits.max_sessions, 20);
+    assert_eq!(config.endpoints["none"].path, "/plant");
+    assert_eq!(config.build_number.as_deref(), Some("1.0"));
+    assert_eq!(config.websocket_config.unwrap().port, 4843);
+    assert_eq!(config.pki_dir, PathBuf::from("/srv/pki"));
+    assert_eq!(
+        config.certificate_path,
+        Some(PathBuf::from("own/server.der"))
+    );
+
+    // A value of the wrong type
+    let config = ServerBuilder::new_anonymous("foo").config();
+    match config.with_overrides(vec![("OPCUA_SERVER_PORT", "any")]) {
+        Err(ConfigOverrideError::InvalidValue { name, value, .. }) => {
+            assert_eq!(name, "OPCUA_SERVER_PORT");
+            assert_eq!(value, "any");
+        }
+        result => panic!("Unexpected result {:?}", result.map(|_| ())),
+    }
+
+    // The bad value is blamed on its variable among good ones
+    let config = ServerBuilder::new_anonymous("foo").config();
+    match config.with_overrides(vec![
+        ("OPCUA_SERVER_HOST", "10.0.0.1"),
+        ("OPCUA_SERVER_LIMITS__MAX_SESSIONS", "many"),
+        ("OPCUA_SERVER_PORT", "4841"),
+    ]) {
+        Err(ConfigOverrideError::InvalidValue { name, value, .. }) => {
+            assert_eq!(name, "OPCUA_SERVER_LIMITS__MAX_SESSIONS");
+            assert_eq!(value, "many");
+        }
+        result => panic!("Unexpected result {:?}", result.map(|_| ())),
+    }
+
+    // Variables that only make a valid value together
+    let config = ServerBuilder::new_anonymous("foo")
+        .config()
+        .with_overrides(vec![
+            ("OPCUA_SERVER_USER_TOKENS__OPERATOR__PASS", "secret"),
+            ("OPCUA_SERVER_USER_TOKENS__OPERATOR__USER", "operator"),
+        ])
+        .unwrap();
+    assert_eq!(config.user_tokens["operator"].user, "operator");
+    assert_eq!(
+        config.user_tokens["operator"].pass.as_deref(),
+        Some("secret")
+    );
+
+    // Values set on the builder afterwards win over overrides
+    let config = ServerBuilder::new_anonymous("foo")
+        .with_env_overrides()
+        .unwrap()
+        .max_sessions(3)
+        .config();
+    assert_eq!(config.limits.max_sessions, 3);
+}
+
+#[test]
+pub fn server_builder_endpoints() {
+    use crate::crypto::SecurityPolicy;
+
+    let config = ServerBuilder::new()
+        .application_name("Plant")
+        .application_uri("urn:Plant")
+        .host_and_port("plant-server", 4840)
+        .user_token("operator", ServerUserToken::user_pass("operator", "op1"))
+        .endpoints_for_path(
+            "/plant",
+            &[
+                (SecurityPolicy::None, MessageSecurityMode::None),
+                (
+                    SecurityPolicy::Basic256Sha256,
+                    MessageSecurityMode::SignAndEncrypt,
+                ),
+            ],
+            &[ANONYMOUS_USER_TOKEN_ID],
+        )
+        .endpoint(
+            "operator",
+            ServerEndpoint::new(
+                "/plant/operator",
+                SecurityPolicy::Aes256Sha256RsaPss,
+                MessageSecurityMode::Sign,
+                &[],
+            )
+            .user_token_id("operator")
+            .with_password_security_policy(SecurityPolicy::Basic256Sha256)
+            .idle_session(600, 60),
+        )
+        .max_monitored_item_queue_size(50)
+        .min_sampling_interval(0.5)
+        .discovery_urls(vec!["opc.tcp://plant-server:4840/plant".into()])
+        .config();
+    assert!(config.is_valid());
+
+    assert_eq!(
+        config.endpoints.keys().collect::<Vec<_>>(),
+        vec![
+            "operator",
+            "plant_basic256sha256_sign_encrypt",
+            "plant_none"
+        ]
+    );
+    let endpoint = &config.endpoints["plant_basic256sha256_sign_encrypt"];
+    assert_eq!(endpoint.path, "/plant");
+    assert_eq!(endpoint.security_policy(), SecurityPolicy::Basic256Sha256);
+    assert_eq!(endpoint.security_mode, "SignAndEncrypt");
+    assert!(endpoint.supports_anonymous());
+    let endpoint = &config.endpoints["operator"];
+    assert!(endpoint.supports_user_token_id("operator"));
+    assert_eq!(
+        endpoint.password_security_policy.as_deref(),
+        Some("Basic256Sha256")
+    );
+    assert_eq!(endpoint.idle_session_timeout, 600);
+    assert_eq!(endpoint.idle_session_warning, 60);
+    assert_eq!(config.limits.max_monitored_item_queue_size, 50);
+    assert_eq!(config.limits.min_sampling_interval, 0.5);
+
+    // The config is the same as one loaded from a file
+    let path = make_test_file("server_builder_endpoints.yaml");
+    assert!(config.save(&path).is_ok());
+    let loaded: ServerConfig = ServerConfig::load(&path).unwrap();
+    assert_eq!(loaded, config);
+}
+
+#[test]
+pub fn config_user_authenticator() {
+    use crate::server::authenticator::{ConfigUserAuthenticator, UserAuthenticator, UserToken};
+
+    let config = ServerBuilder::new_anonymous("foo")
+        .user_token(
+            "op",
+            ServerUserToken::user_pass("op", "pwd").roles(&["Operator", "ns=2;s=Maintenance"]),
+        )
+        .config();
+    let authenticator = ConfigUserAuthenticator::new(&config.user_tokens);
+
+    let endpoint = ServerEndpoint::new_none("/", &["op".to_string()]);
+    assert_eq!(
+        authenticator.user_token_types(&endpoint),
+        vec![UserTokenType::UserName]
+    );
+    let identity = authenticator
+        .authenticate(
+            &UserToken::UserName {
+                user: "op",
+                password: "pwd",
+            },
+            &endpoint,
+        )
+        .unwrap();
+    let operator: NodeId = ObjectId::WellKnownRole_Operator.into();
+    assert_eq!(identity.user_token_id, "op");
+    assert_eq!(
+        identity.roles,
+        vec![operator, NodeId::new(2, "Maintenance")]
+    );
+    let result = authenticator.authenticate(
+        &UserToken::UserName {
+            user: "op",
+            password: "wrong",
+        },
+        &endpoint,
+    );
+    assert_eq!(result.unwrap_err(), StatusCode::BadUserAccessDenied);
+    let result = authenticator.authenticate(&UserToken::Anonymous, &endpoint);
+    assert_eq!(result.unwrap_err(), StatusCode::BadIdentityTokenRejected);
+
+    // The op user can't use an endpoint that doesn't list it
+    let endpoint = ServerEndpoint::new_none("/", &[ANONYMOUS_USER_TOKEN_ID.to_string()]);
+    assert_eq!(
+        authenticator.user_token_types(&endpoint),
+        vec![UserTokenType::Anonymous]
+    );
+    let result = authenticator.authenticate(
+        &UserToken::UserName {
+            user: "op",
+            password: "pwd",
+        },
+        &endpoint,
+    );
+    assert_eq!(result.unwrap_err(), StatusCode::BadUserAccessDenied);
+    let identity = authenticator
+        .authenticate(&UserToken::Anonymous, &endpoint)
+        .unwrap();
+    assert_eq!(identity.user_token_id, ANONYMOUS_USER_TOKEN_ID);
+    assert!(identity.roles.is_empty());
+}
+
+#[test]
+pub fn build_info() {
+    let server = ServerBuilder::new_anonymous("foo").server().unwrap();
+    let server_state = server.server_state();
+    let server_state = trace_read_lock!(server_state);
+    assert!(server_state.build_info.manufacturer_name.is_null());
+    assert_eq!(
+        server_state.build_info.build_number.as_ref(),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let server = ServerBuilder::new_anonymous("foo")
+        .manufacturer_name("Acme")
+        .build_number("1234")
+        .server()
+        .unwrap();
+    let server_state = server.server_state();
+    let server_state = trace_read_lock!(server_state);
+    assert_eq!(server_state.build_info.manufacturer_name.as_ref(), "Acme");
+    assert_eq!(server_state.build_info.build_number.as_ref(), "1234");
+}
+
+#[test]
+pub fn server_profiles() {
+    use crate::types::profiles;
+
+    // Anonymous access over an insecure endpoint
+    let server = ServerBuilder::new_anonymous("foo").server().unwrap();
+    let server_state = server.server_state();
+    let profiles_of_anonymous = trace_read_lock!(server_state).server_profiles();
+    assert_eq!(
+        profiles_of_anonymous,
+        vec![
+            profiles::SERVER_PROFILE_URI_BEHAVIOUR,
+            profiles::SERVER_FACET_URI_STANDARD_DATA_CHANGE_SUBSCRIPTION,
+            profiles::SERVER_FACET_URI_METHODS,
+            profiles::TRANSPORT_PROFILE_URI_BINARY,
+            crate::crypto::SecurityPolicy::None.to_uri(),
+            profiles::SECURITY_USER_TOKEN_POLICY_ANONYMOUS,
+        ]
+    );
+
+    // The value is exposed through the address space
+    let address_space = server.address_space();
+    let address_space = trace_read_lock!(address_space);
+    let v = address_space
+        .find_variable(VariableId::Server_ServerCapabilities_ServerProfileArray)
+        .unwrap();
+    let value = v.value(
+        TimestampsToReturn::Neither,
+        NumericRange::None,
+        &QualifiedName::null(),
+        0.0,
+    );
+    assert_eq!(
+        value.value.unwrap(),
+        Variant::from((VariantTypeId::String, &profiles_of_anonymous[..]))
+    );
+
+    // Secure endpoints with user name and x509 users, no subscriptions
+    let server = ServerBuilder::new_sample()
+        .max_subscriptions(0)
+        .server()
+        .unwrap();
+    let server_state = server.server_state();
+    let server_profiles = trace_read_lock!(server_state).server_profiles();
+    assert!(!server_profiles.contains(&profiles::SERVER_PROFILE_URI_EMBEDDED_UA));
+    assert!(
+        !server_profiles.contains(&profiles::SERVER_FACET_URI_STANDARD_DATA_CHANGE_SUBSCRIPTION)
+    );
+    assert!(server_profiles.contains(&profiles::SECURITY_USER_TOKEN_POLICY_USERPASS));
+    assert!(server_profiles.contains(&profiles::SECURITY_USER_TOKEN_POLICY_X509));
+    assert!(server_profiles.contains(&crate::crypto::SecurityPolicy::Basic256Sha256.to_uri()));
+
+    let server = ServerBuilder::new_sample().server().unwrap();
+    let server_state = server.server_state();
+    let server_profiles = trace_read_lock!(server_state).server_profiles();
+    assert!(server_profiles.contains(&profiles::SERVER_PROFILE_URI_EMBEDDED_UA));
+}
+
+#[test]
+pub fn operational_limits() {
+    let max_nodes_per_read = |server: &Server| {
+        let address_space = server.address_space();
+        let address_space = trace_read_lock!(address_space);
+        address_space
+            .find_variable(VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerRead)
+            .unwrap()
+            .value(
+                TimestampsToReturn::Neither,
+                NumericRange::None,
+                &QualifiedName::null(),
+                0.0,
+            )
+            .value
+            .unwrap()
+    };
+
+    // The limits of the config are enforced and advertised
+    let server = ServerBuilder::new_sample()
+        .operational_limits(OperationalLimits {
+            max_nodes_per_read: 7,
+            ..Default::default()
+        })
+        .server()
+        .unwrap();
+    let server_state = server.server_state();
+    assert_eq!(
+        trace_read_lock!(server_state)
+            .operational_limits
+            .max_nodes_per_read,
+        7
+    );
+    assert_eq!(max_nodes_per_read(&server), Variant::UInt32(7));
+
+    // Reloading the config changes them
+    let reloader = crate::server::reload::ConfigReloader {
+        server_state: server_state.clone(),
+        certificate_store: server.certificate_store(),
+        session_manager: Arc::new(RwLock::new(SessionManager::default())),
+        address_space: server.address_space(),
+    };
+    let mut config = {
+        let server_state = trace_read_lock!(server_state);
+        let config = trace_read_lock!(server_state.config);
+        config.clone()
+    };
+    config.limits.operational.max_nodes_per_read = 0;
+    reloader.reload_config(config).unwrap();
+    assert_eq!(
+        trace_read_lock!(server_state)
+            .operational_limits
+            .max_nodes_per_read,
+        0
+    );
+    assert_eq!(max_nodes_per_read(&server), Variant::UInt32(0));
+
+    assert!(OperationalLimits::allows(0, 1000));
+    assert!(OperationalLimits::allows(10, 10));
+    assert!(!OperationalLimits::allows(10, 11));
+}
+
+#[test]
+pub fn expired_requests() {
+    use crate::server::services::message_handler::MessageHandler;
+
+    // The client's clock is an hour behind the server's
+    let now = chrono::Utc::now();
+    let client_now = DateTime::from(now - chrono::Duration::hours(1));
+    let mut request_header = RequestHeader::new(&NodeId::null(), &client_now, 1);
+    request_header.timeout_hint = 1000;
+
+    // The timeout hint runs from when the server received the request, not the request timestamp
+    assert!(MessageHandler::is_request_expired(&request_header, now, now).is_ok());
+    let received_at = now - chrono::Duration::milliseconds(500);
+    assert!(MessageHandler::is_request_expired(&request_header, received_at, now).is_ok());
+    let received_at = now - chrono::Duration::seconds(2);
+    let Err(SupportedMessage::ServiceFault(fault)) =
+        MessageHandler::is_request_expired(&request_header, received_at, now)
+    else {
+        panic!("Expected a service fault for the expired request");
+    };
+    assert_eq!(fault.response_header.service_result, StatusCode::BadTimeout);
+
+    // Without a timeout hint a request never expires
+    request_header.timeout_hint = 0;
+    assert!(MessageHandler::is_request_expired(&request_header, received_at, now).is_ok());
 }
 
 #[test]
@@ -174,3 +605,85 @@ pub fn expired_publish_requests() {
         }
     }
 }
+
+#[test]
+pub fn reload_config() {
+    use crate::server::reload::ConfigReloader;
+
+    let server = ServerBuilder::new_sample().server().unwrap();
+    let server_state = server.server_state();
+    let session_manager = Arc::new(RwLock::new(SessionManager::default()));
+    let reloader = ConfigReloader {
+        server_state: server_state.clone(),
+        certificate_store: server.certificate_store(),
+        session_manager: session_manager.clone(),
+        address_space: server.address_space(),
+    };
+
+    // Sessions of the password user, the unused user and an anonymous user
+    let session_ids = [
+        "sample_password_user",
+        "unused_user",
+        ANONYMOUS_USER_TOKEN_ID,
+    ]
+    .iter()
+    .map(|user_token_id| {
+        let mut session = Session::new(server_state.clone());
+        session.set_user_token_id(Some(user_token_id.to_string()));
+        let session_id = session.session_id().clone();
+        trace_write_lock!(session_manager)
+            .sessions
+            .insert(session_id.clone(), Arc::new(RwLock::new(session)));
+        session_id
+    })
+    .collect::<Vec<_>>();
+
+    let mut config = {
+        let server_state = trace_read_lock!(server_state);
+        let config = trace_read_lock!(server_state.config);
+        config.clone()
+    };
+    let host = config.tcp_config.host.clone();
+
+    // A config whose endpoints use a removed user token is not applied
+    let mut invalid_config = config.clone();
+    invalid_config.user_tokens.remove("sample_password_user");
+    invalid_config.limits.max_subscriptions = 5;
+    assert!(matches!(
+        reloader.reload_config(invalid_config),
+        Err(ReloadError::Invalid)
+    ));
+    assert!(matches!(
+        reloader.reload_file(Path::new("missing.conf")),
+        Err(ReloadError::Load(_))
+    ));
+    assert_ne!(trace_read_lock!(server_state).max_subscriptions, 5);
+    assert_eq!(trace_read_lock!(session_manager).len(), 3);
+
+    // Limits and user tokens are applied, the host needs a restart
+    config.limits.max_subscriptions = 5;
+    config.limits.min_publishing_interval = 0.5;
+    config
+        .user_tokens
+        .get_mut("unused_user")
+        .unwrap()
+        .pass
+        .replace("changed".to_string());
+    config.tcp_config.host = "otherhost".to_string();
+    let closed = reloader.reload_config(config).unwrap();
+
+    // Only the session of the user whose password changed is closed
+    assert_eq!(closed, vec![session_ids[1].clone()]);
+    assert_eq!(trace_read_lock!(session_manager).len(), 2);
+
+    let server_state = trace_read_lock!(server_state);
+    assert_eq!(server_state.max_subscriptions, 5);
+    assert_eq!(server_state.min_publishing_interval_ms, 500.0);
+    let config = trace_read_lock!(server_state.config);
+    assert_eq!(config.limits.max_subscriptions, 5);
+    assert_eq!(
+        config.user_tokens["unused_user"].pass.as_deref(),
+        Some("changed")
+    );
+    assert_eq!(config.tcp_config.host, host);
+}
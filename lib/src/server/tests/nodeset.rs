@@ -0,0 +1,390 @@
+use crate::server::address_space::{
+    node::{NodeBase, NodeType},
+    AccessLevel, AttrFnGetter,
+};
+
+use super::*;
+
+const NODESET: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- A small companion specification -->
+<UANodeSet xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns="http://opcfoundation.org/UA/2011/03/UANodeSet.xsd">
+  <NamespaceUris>
+    <Uri>urn:machines</Uri>
+  </NamespaceUris>
+  <Aliases>
+    <Alias Alias="Double">i=11</Alias>
+    <Alias Alias="Int32">i=6</Alias>
+    <Alias Alias="Organizes">i=35</Alias>
+    <Alias Alias="HasComponent">i=47</Alias>
+    <Alias Alias="HasProperty">i=46</Alias>
+    <Alias Alias="HasSubtype">i=45</Alias>
+    <Alias Alias="HasTypeDefinition">i=40</Alias>
+  </Aliases>
+  <UAObjectType NodeId="ns=1;i=1000" BrowseName="1:MachineType">
+    <DisplayName>MachineType</DisplayName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=58</Reference>
+    </References>
+  </UAObjectType>
+  <UAObject NodeId="ns=1;i=2000" BrowseName="1:Press" EventNotifier="1">
+    <DisplayName Locale="en">Press &amp; Stamp</DisplayName>
+    <Description>A <![CDATA[<hydraulic>]]> press</Description>
+    <References>
+      <Reference ReferenceType="Organizes" IsForward="false">i=85</Reference>
+      <Reference ReferenceType="HasTypeDefinition">ns=1;i=1000</Reference>
+      <Reference ReferenceType="HasComponent">ns=1;i=2001</Reference>
+    </References>
+  </UAObject>
+  <UAVariable NodeId="ns=1;i=2001" BrowseName="1:Pressure" DataType="Double" AccessLevel="3" Historizing="true">
+    <DisplayName>Pressure</DisplayName>
+    <References>
+      <Reference ReferenceType="HasComponent" IsForward="false">ns=1;i=2000</Reference>
+      <Reference ReferenceType="HasTypeDefinition">i=63</Reference>
+    </References>
+    <Value>
+      <uax:Double xmlns:uax="http://opcfoundation.org/UA/2008/02/Types.xsd">12.5</uax:Double>
+    </Value>
+  </UAVariable>
+  <UAVariable NodeId="ns=1;s=Limits" BrowseName="1:Limits" DataType="Int32" ValueRank="1" ArrayDimensions="3">
+    <DisplayName>Limits</DisplayName>
+    <References>
+      <Reference ReferenceType="HasProperty" IsForward="false">ns=1;i=2000</Reference>
+    </References>
+    <Value>
+      <ListOfInt32 xmlns="http://opcfoundation.org/UA/2008/02/Types.xsd">
+        <Int32>1</Int32>
+        <Int32>2</Int32>
+        <Int32>3</Int32>
+      </ListOfInt32>
+    </Value>
+  </UAVariable>
+  <UAMethod NodeId="ns=1;i=3000" BrowseName="1:Start" UserExecutable="false">
+    <DisplayName>Start</DisplayName>
+    <References>
+      <Reference ReferenceType="HasComponent" IsForward="false">ns=1;i=2000</Reference>
+      <Reference ReferenceType="HasProperty">ns=1;i=3001</Reference>
+    </References>
+  </UAMethod>
+  <UAVariable NodeId="ns=1;i=3001" BrowseName="InputArguments" DataType="i=296" ValueRank="1">
+    <DisplayName>InputArguments</DisplayName>
+    <Value>
+      <ListOfExtensionObject xmlns="http://opcfoundation.org/UA/2008/02/Types.xsd">
+        <ExtensionObject>
+          <TypeId><Identifier>i=297</Identifier></TypeId>
+          <Body>
+            <Argument>
+              <Name>Speed</Name>
+              <DataType><Identifier>i=11</Identifier></DataType>
+              <ValueRank>-1</ValueRank>
+              <ArrayDimensions />
+            </Argument>
+          </Body>
+        </ExtensionObject>
+      </ListOfExtensionObject>
+    </Value>
+  </UAVariable>
+  <UAReferenceType NodeId="ns=1;i=4000" BrowseName="1:Feeds">
+    <DisplayName>Feeds</DisplayName>
+    <InverseName>FedBy</InverseName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=32</Reference>
+    </References>
+  </UAReferenceType>
+</UANodeSet>
+"#;
+
+#[test]
+fn import_nodeset() {
+    let mut address_space = AddressSpace::new();
+    let default_namespace = address_space.default_namespace();
+
+    assert_eq!(address_space.import_nodeset(NODESET).unwrap(), 7);
+
+    // The namespace of the node set is registered and node ids are remapped to it
+    let ns = address_space.namespace_index("urn:machines").unwrap();
+    assert_eq!(address_space.default_namespace(), default_namespace);
+
+    let press_id = NodeId::new(ns, 2000);
+    let NodeType::Object(press) = address_space.find_node(&press_id).unwrap() else {
+        panic!("Press is not an object");
+    };
+    assert_eq!(press.browse_name(), QualifiedName::new(ns, "Press"));
+    assert_eq!(
+        press.display_name(),
+        LocalizedText::new("en", "Press & Stamp")
+    );
+    assert_eq!(
+        press.description(),
+        Some(LocalizedText::new("", "A <hydraulic> press"))
+    );
+    assert!(address_space.has_reference(
+        &ObjectId::ObjectsFolder.into(),
+        &press_id,
+        ReferenceTypeId::Organizes
+    ));
+    assert!(address_space.has_reference(
+        &press_id,
+        &NodeId::new(ns, 1000),
+        ReferenceTypeId::HasTypeDefinition
+    ));
+    assert!(address_space.is_subtype(&NodeId::new(ns, 1000), &ObjectTypeId::BaseObjectType.into()));
+
+    // Variable values, data types and attributes
+    let pressure = address_space
+        .find_variable_by_ref(&NodeId::new(ns, 2001))
+        .unwrap();
+    assert_eq!(pressure.data_type(), DataTypeId::Double.into());
+    assert!(pressure.access_level().contains(AccessLevel::CURRENT_WRITE));
+    assert!(pressure.historizing());
+    assert!(address_space.has_reference(
+        &press_id,
+        &NodeId::new(ns, 2001),
+        ReferenceTypeId::HasComponent
+    ));
+    assert_eq!(
+        address_space
+            .get_variable_value(NodeId::new(ns, 2001))
+            .unwrap()
+            .value,
+        Some(Variant::from(12.5f64))
+    );
+    let limits = address_space
+        .find_variable_by_ref(&NodeId::new(ns, "Limits"))
+        .unwrap();
+    assert_eq!(limits.value_rank(), 1);
+    assert_eq!(limits.array_dimensions(), Some(vec![3]));
+    assert_eq!(
+        address_space
+            .get_variable_value(NodeId::new(ns, "Limits"))
+            .unwrap()
+            .value,
+        Some(Variant::from(vec![1i32, 2i32, 3i32]))
+    );
+
+    // Method arguments are decoded from their extension objects
+    let NodeType::Method(start) = address_space.find_node(&NodeId::new(ns, 3000)).unwrap() else {
+        panic!("Start is not a method");
+    };
+    assert!(start.executable());
+    assert!(!start.user_executable());
+    let arguments = address_space
+        .get_variable_value(NodeId::new(ns, 3001))
+        .unwrap()
+        .value
+        .unwrap();
+    let Variant::Array(arguments) = arguments else {
+        panic!("Arguments are not an array");
+    };
+    let Variant::ExtensionObject(argument) = &arguments.values[0] else {
+        panic!("Argument is not an extension object");
+    };
+    let argument = argument
+        .decode_inner::<service_types::Argument>(&DecodingOptions::test())
+        .unwrap();
+    assert_eq!(argument.name, UAString::from("Speed"));
+    assert_eq!(argument.data_type, DataTypeId::Double.into());
+
+    let NodeType::ReferenceType(feeds) = address_space.find_node(&NodeId::new(ns, 4000)).unwrap()
+    else {
+        panic!("Feeds is not a reference type");
+    };
+    assert_eq!(feeds.inverse_name(), Some(LocalizedText::new("", "FedBy")));
+
+    // Importing again leaves the existing nodes alone
+    assert_eq!(address_space.import_nodeset(NODESET).unwrap(), 0);
+    assert_eq!(address_space.namespace_index("urn:machines").unwrap(), ns);
+}
+
+#[test]
+fn import_nodeset_invalid() {
+    let mut address_space = AddressSpace::new();
+
+    // Malformed XML, or not a node set at all
+    assert_eq!(
+        address_space.import_nodeset("<UANodeSet><UAObject></UANodeSet>"),
+        Err(StatusCode::BadDecodingError)
+    );
+    assert_eq!(
+        address_space.import_nodeset("<Nodes/>"),
+        Err(StatusCode::BadDecodingError)
+    );
+
+    // A namespace index that is not in the file, nothing is inserted
+    let nodeset = r#"<UANodeSet>
+  <NamespaceUris><Uri>urn:ok</Uri></NamespaceUris>
+  <UAObject NodeId="ns=1;i=1" BrowseName="1:Ok"><DisplayName>Ok</DisplayName></UAObject>
+  <UAObject NodeId="ns=2;i=1" BrowseName="Bad"><DisplayName>Bad</DisplayName></UAObject>
+</UANodeSet>"#;
+    assert_eq!(
+        address_space.import_nodeset(nodeset),
+        Err(StatusCode::BadNodeIdInvalid)
+    );
+    let ns = address_space.namespace_index("urn:ok").unwrap();
+    assert!(!address_space.node_exists(&NodeId::new(ns, 1)));
+
+    // An attribute that is not a number
+    let nodeset = r#"<UANodeSet>
+  <UAObject NodeId="i=90000" BrowseName="Bad" EventNotifier="yes"/>
+</UANodeSet>"#;
+    assert_eq!(
+        address_space.import_nodeset(nodeset),
+        Err(StatusCode::BadDecodingError)
+    );
+    assert!(!address_space.node_exists(&NodeId::new(0, 90000)));
+
+    assert_eq!(
+        address_space.import_nodeset_file(make_test_file("missing.NodeSet2.xml")),
+        Err(StatusCode::BadResourceUnavailable)
+    );
+}
+
+#[test]
+fn import_nodeset_at_startup() {
+    let path = make_test_file("import_nodeset_at_startup.NodeSet2.xml");
+    std::fs::write(&path, NODESET).unwrap();
+
+    let server = ServerBuilder::new_sample()
+        .nodesets(vec![&path])
+        .server()
+        .unwrap();
+    let address_space = server.address_space();
+    let address_space = trace_read_lock!(address_space);
+    let ns = address_space.namespace_index("urn:machines").unwrap();
+    assert!(address_space.has_reference(
+        &ObjectId::ObjectsFolder.into(),
+        &NodeId::new(ns, 2000),
+        ReferenceTypeId::Organizes
+    ));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn export_nodeset() {
+    let mut address_space = AddressSpace::new();
+    address_space.import_nodeset(NODESET).unwrap();
+    let ns = address_space.namespace_index("urn:machines").unwrap();
+
+    // A variable whose value comes from a getter has no value to export
+    VariableBuilder::new(&NodeId::new(ns, "Speed"), "Speed", "Speed <rpm>")
+        .data_type(DataTypeId::Double)
+        .component_of(NodeId::new(ns, 2000))
+        .value_getter(AttrFnGetter::new_boxed(|_, _, _, _, _, _| {
+            Ok(Some(DataValue::new_now(1500f64)))
+        }))
+        .insert(&mut address_space);
+
+    let path = make_test_file("export_nodeset.NodeSet2.xml");
+    assert_eq!(address_space.export_nodeset(&path, Some(&[ns])).unwrap(), 8);
+
+    // Only the nodes in the namespace are exported
+    let nodeset = std::fs::read_to_string(&path).unwrap();
+    assert!(nodeset.contains("<Uri>urn:machines</Uri>"));
+    assert!(!nodeset.contains("NodeId=\"i=85\""));
+
+    // Importing the export into another address space recreates the nodes
+    let mut imported = AddressSpace::new();
+    assert_eq!(imported.import_nodeset_file(&path).unwrap(), 8);
+    let ns = imported.namespace_index("urn:machines").unwrap();
+
+    let press_id = NodeId::new(ns, 2000);
+    let NodeType::Object(press) = imported.find_node(&press_id).unwrap() else {
+        panic!("Press is not an object");
+    };
+    assert_eq!(
+        press.display_name(),
+        LocalizedText::new("en", "Press & Stamp")
+    );
+    assert_eq!(
+        press.description(),
+        Some(LocalizedText::new("", "A <hydraulic> press"))
+    );
+    assert!(imported.has_reference(
+        &ObjectId::ObjectsFolder.into(),
+        &press_id,
+        ReferenceTypeId::Organizes
+    ));
+    assert!(imported.has_reference(
+        &press_id,
+        &NodeId::new(ns, 1000),
+        ReferenceTypeId::HasTypeDefinition
+    ));
+    assert!(imported.is_subtype(&NodeId::new(ns, 1000), &ObjectTypeId::BaseObjectType.into()));
+
+    let pressure = imported
+        .find_variable_by_ref(&NodeId::new(ns, 2001))
+        .unwrap();
+    assert!(pressure.access_level().contains(AccessLevel::CURRENT_WRITE));
+    assert!(pressure.historizing());
+    assert_eq!(
+        imported
+            .get_variable_value(NodeId::new(ns, 2001))
+            .unwrap()
+            .value,
+        Some(Variant::from(12.5f64))
+    );
+    let limits = imported
+        .find_variable_by_ref(&NodeId::new(ns, "Limits"))
+        .unwrap();
+    assert_eq!(limits.array_dimensions(), Some(vec![3]));
+    assert_eq!(
+        imported
+            .get_variable_value(NodeId::new(ns, "Limits"))
+            .unwrap()
+            .value,
+        Some(Variant::from(vec![1i32, 2i32, 3i32]))
+    );
+
+    let NodeType::Method(start) = imported.find_node(&NodeId::new(ns, 3000)).unwrap() else {
+        panic!("Start is not a method");
+    };
+    assert!(!start.user_executable());
+    let arguments = imported
+        .get_variable_value(NodeId::new(ns, 3001))
+        .unwrap()
+        .value
+        .unwrap();
+    let Variant::Array(arguments) = arguments else {
+        panic!("Arguments are not an array");
+    };
+    let Variant::ExtensionObject(argument) = &arguments.values[0] else {
+        panic!("Argument is not an extension object");
+    };
+    let argument = argument
+        .decode_inner::<service_types::Argument>(&DecodingOptions::test())
+        .unwrap();
+    assert_eq!(argument.name, UAString::from("Speed"));
+    assert_eq!(argument.data_type, DataTypeId::Double.into());
+
+    let NodeType::ReferenceType(feeds) = imported.find_node(&NodeId::new(ns, 4000)).unwrap() else {
+        panic!("Feeds is not a reference type");
+    };
+    assert_eq!(feeds.inverse_name(), Some(LocalizedText::new("", "FedBy")));
+
+    let speed = imported
+        .find_variable_by_ref(&NodeId::new(ns, "Speed"))
+        .unwrap();
+    assert_eq!(speed.display_name(), LocalizedText::new("", "Speed <rpm>"));
+    assert_eq!(
+        imported
+            .get_variable_value(NodeId::new(ns, "Speed"))
+            .unwrap()
+            .value
+            .unwrap_or_default(),
+        Variant::Empty
+    );
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn export_nodeset_invalid_path() {
+    let address_space = AddressSpace::new();
+    assert_eq!(
+        address_space.export_nodeset(
+            make_test_file("export_nodeset_missing_dir/export.NodeSet2.xml"),
+            None
+        ),
+        Err(StatusCode::BadResourceUnavailable)
+    );
+}
@@ -0,0 +1,97 @@
+use crate::server::polling::{PollingGroup, PollingProvider};
+
+use super::*;
+
+/// Reads registers from a bank, where a register past the end of the bank can't be read and
+/// an offline bank can't be read at all.
+struct RegisterBank {
+    registers: Vec<u16>,
+    online: bool,
+}
+
+impl PollingProvider for RegisterBank {
+    type Tag = usize;
+
+    fn poll(&mut self, tags: &[usize]) -> Vec<Result<Variant, StatusCode>> {
+        tags.iter()
+            .map(|tag| {
+                if !self.online {
+                    Err(StatusCode::BadCommunicationError)
+                } else {
+                    self.registers
+                        .get(*tag)
+                        .map(|v| Variant::UInt16(*v))
+                        .ok_or(StatusCode::BadOutOfRange)
+                }
+            })
+            .collect()
+    }
+}
+
+fn make_register_variables(address_space: &mut AddressSpace) -> (NodeId, NodeId) {
+    let r0 = NodeId::new(1, "r0");
+    let r9 = NodeId::new(1, "r9");
+    let _ = address_space.add_variables(
+        vec![
+            Variable::new(&r0, "r0", "r0", 0u16),
+            Variable::new(&r9, "r9", "r9", 0u16),
+        ],
+        &NodeId::objects_folder_id(),
+    );
+    (r0, r9)
+}
+
+#[test]
+fn polling_group_writes_values_and_quality() {
+    let mut address_space = AddressSpace::new();
+    let (r0, r9) = make_register_variables(&mut address_space);
+    let mut group = PollingGroup::new(RegisterBank {
+        registers: vec![42, 7],
+        online: true,
+    })
+    .tag(r0.clone(), 0)
+    .tag(r9.clone(), 9)
+    // A tag without a variable is skipped
+    .tag(NodeId::new(1, "missing"), 1);
+
+    assert_eq!(group.poll(&mut address_space), 1);
+
+    let value = address_space.get_variable_value(r0.clone()).unwrap();
+    assert_eq!(value.value, Some(Variant::UInt16(42)));
+    assert_eq!(value.status, Some(StatusCode::Good));
+    assert!(value.source_timestamp.is_some());
+
+    let value = address_space.get_variable_value(r9).unwrap();
+    assert_eq!(value.value, Some(Variant::UInt16(0)));
+    assert_eq!(value.status, Some(StatusCode::BadOutOfRange));
+
+    // A device that goes offline leaves the last value with a bad status
+    group.provider_mut().online = false;
+    assert_eq!(group.poll(&mut address_space), 0);
+    let value = address_space.get_variable_value(r0).unwrap();
+    assert_eq!(value.value, Some(Variant::UInt16(42)));
+    assert_eq!(value.status, Some(StatusCode::BadCommunicationError));
+}
+
+#[test]
+fn polling_group_rejects_short_results() {
+    struct ShortProvider;
+
+    impl PollingProvider for ShortProvider {
+        type Tag = ();
+
+        fn poll(&mut self, _tags: &[()]) -> Vec<Result<Variant, StatusCode>> {
+            vec![Ok(Variant::UInt16(1))]
+        }
+    }
+
+    let mut address_space = AddressSpace::new();
+    let (r0, r9) = make_register_variables(&mut address_space);
+    let mut group = PollingGroup::new(ShortProvider)
+        .tag(r0.clone(), ())
+        .tag(r9, ());
+
+    assert_eq!(group.poll(&mut address_space), 0);
+    let value = address_space.get_variable_value(r0).unwrap();
+    assert_eq!(value.status, Some(StatusCode::BadInternalError));
+}
@@ -1,6 +1,9 @@
 use chrono::Duration;
 
-use crate::server::{address_space::AccessLevel, services::attribute::AttributeService};
+use crate::server::{
+    address_space::{base::Base, relative_path::find_node_from_browse_path, AccessLevel},
+    services::attribute::AttributeService,
+};
 use crate::supported_message_as;
 use crate::sync::*;
 use crate::types::{Variant, WriteMask};
@@ -129,10 +132,10 @@ fn read() {
             assert!(results[2].source_timestamp.is_none());
             assert!(results[2].server_timestamp.is_none());
 
-            // 4. a variable with no read access
+            // 4. a variable with no read access for the user
             assert_eq!(
                 results[3].status.as_ref().unwrap(),
-                &StatusCode::BadNotReadable
+                &StatusCode::BadUserAccessDenied
             );
             assert!(results[3].source_timestamp.is_none());
             assert!(results[3].server_timestamp.is_none());
@@ -400,6 +403,573 @@ fn write() {
     });
 }
 
+#[test]
+fn write_attributes() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let (_, node_ids) = add_many_vars_to_address_space(address_space.clone(), 1);
+        let node_id = &node_ids[0];
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let node = address_space.find_node_mut(node_id).unwrap().as_mut_node();
+            // Anyone may write the display name, only some users may write the description
+            node.set_write_mask(WriteMask::DISPLAY_NAME | WriteMask::DESCRIPTION);
+            node.set_user_write_mask(WriteMask::DISPLAY_NAME);
+        }
+
+        let nodes_to_write = vec![
+            write_value(
+                node_id,
+                AttributeId::DisplayName,
+                DataValue::new_now(LocalizedText::new("", "Renamed")),
+            ),
+            write_value(
+                node_id,
+                AttributeId::Description,
+                DataValue::new_now(LocalizedText::new("", "Described")),
+            ),
+            write_value(
+                node_id,
+                AttributeId::BrowseName,
+                DataValue::new_now(QualifiedName::new(1, "Renamed")),
+            ),
+            write_value(
+                node_id,
+                AttributeId::DisplayName,
+                DataValue::new_now(UAString::from("Renamed")),
+            ),
+        ];
+        let response = write_request(
+            server_state,
+            session,
+            address_space.clone(),
+            ats,
+            nodes_to_write,
+        );
+        assert_eq!(
+            response.results.unwrap(),
+            vec![
+                StatusCode::Good,
+                StatusCode::BadUserAccessDenied,
+                StatusCode::BadNotWritable,
+                StatusCode::BadTypeMismatch,
+            ]
+        );
+
+        let address_space = trace_read_lock!(address_space);
+        let node = address_space.find_node(node_id).unwrap().as_node();
+        assert_eq!(node.display_name(), LocalizedText::new("", "Renamed"));
+        assert!(node.description().is_none());
+    });
+}
+
+/// Takes write access to one node away from every user
+struct WriteDenied(NodeId);
+
+impl AuthorizationHandler for WriteDenied {
+    fn user_access_level(
+        &self,
+        _session: &Session,
+        node_id: &NodeId,
+        user_access_level: UserAccessLevel,
+    ) -> UserAccessLevel {
+        if *node_id == self.0 {
+            user_access_level - UserAccessLevel::CURRENT_WRITE
+        } else {
+            user_access_level
+        }
+    }
+}
+
+#[test]
+fn authorization_handler_user_access_level() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let (_, node_ids) = add_many_vars_to_address_space(address_space.clone(), 2);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            for node_id in &node_ids {
+                let node = address_space.find_node_mut(node_id).unwrap();
+                let access_level = UserAccessLevel::CURRENT_READ | UserAccessLevel::CURRENT_WRITE;
+                node.as_mut_node()
+                    .set_attribute(
+                        AttributeId::UserAccessLevel,
+                        Variant::from(access_level.bits()),
+                    )
+                    .unwrap();
+                node.as_mut_node()
+                    .set_attribute(AttributeId::AccessLevel, Variant::from(access_level.bits()))
+                    .unwrap();
+            }
+        }
+        {
+            let mut session = trace_write_lock!(session);
+            session.set_authorization_handler(Arc::new(WriteDenied(node_ids[0].clone())));
+        }
+
+        // The user access level read back is narrowed by the handler for the first node only
+        let nodes_to_read = node_ids
+            .iter()
+            .map(|n| read_value(n, AttributeId::UserAccessLevel))
+            .collect();
+        let request = ReadRequest {
+            request_header: make_request_header(),
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Both,
+            nodes_to_read: Some(nodes_to_read),
+        };
+        let response = ats.read(
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &request,
+        );
+        let response: ReadResponse = supported_message_as!(response, ReadResponse);
+        let results = response.results.unwrap();
+        assert_eq!(
+            results[0].value.as_ref().unwrap(),
+            &Variant::Byte(UserAccessLevel::CURRENT_READ.bits())
+        );
+        assert_eq!(
+            results[1].value.as_ref().unwrap(),
+            &Variant::Byte((UserAccessLevel::CURRENT_READ | UserAccessLevel::CURRENT_WRITE).bits())
+        );
+
+        // And the value can only be written to the second node
+        let nodes_to_write = node_ids
+            .iter()
+            .map(|n| write_value(n, AttributeId::Value, DataValue::new_now(100i32)))
+            .collect();
+        let response = write_request(server_state, session, address_space, ats, nodes_to_write);
+        let results = response.results.unwrap();
+        assert_eq!(results[0], StatusCode::BadUserAccessDenied);
+        assert_eq!(results[1], StatusCode::Good);
+    });
+}
+
+#[test]
+fn role_permissions() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let node_id = NodeId::next_numeric(2);
+        let observer: NodeId = ObjectId::WellKnownRole_Observer.into();
+        let operator: NodeId = ObjectId::WellKnownRole_Operator.into();
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = VariableBuilder::new(&node_id, var_name(0), "")
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .role_permission(
+                    observer.clone(),
+                    PermissionType::Browse | PermissionType::Read,
+                )
+                .role_permission(
+                    operator.clone(),
+                    PermissionType::Browse | PermissionType::Read | PermissionType::Write,
+                )
+                .insert(&mut address_space);
+        }
+        let read = |attribute_id| {
+            let request = ReadRequest {
+                request_header: make_request_header(),
+                max_age: 0f64,
+                timestamps_to_return: TimestampsToReturn::Both,
+                nodes_to_read: Some(vec![read_value(&node_id, attribute_id)]),
+            };
+            let response = ats.read(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request,
+            );
+            let response: ReadResponse = supported_message_as!(response, ReadResponse);
+            response.results.unwrap().remove(0)
+        };
+        let write = || {
+            let nodes_to_write = vec![write_value(
+                &node_id,
+                AttributeId::Value,
+                DataValue::new_now(100i32),
+            )];
+            let response = write_request(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                ats,
+                nodes_to_write,
+            );
+            response.results.unwrap()[0]
+        };
+
+        // A user without any of the roles may do nothing with the node
+        assert_eq!(
+            read(AttributeId::Value).status,
+            Some(StatusCode::BadUserAccessDenied)
+        );
+        assert_eq!(
+            read(AttributeId::DisplayName).status,
+            Some(StatusCode::BadUserAccessDenied)
+        );
+        assert_eq!(write(), StatusCode::BadUserAccessDenied);
+
+        // An observer may read but not write
+        trace_write_lock!(session).set_roles(vec![observer.clone()]);
+        assert_eq!(read(AttributeId::Value).status, Some(StatusCode::Good));
+        assert_eq!(
+            read(AttributeId::UserAccessLevel).value,
+            Some(Variant::Byte(UserAccessLevel::CURRENT_READ.bits()))
+        );
+        assert_eq!(write(), StatusCode::BadUserAccessDenied);
+
+        // The role permissions themselves need permission to read, but the user's own are visible
+        assert_eq!(
+            read(AttributeId::RolePermissions).status,
+            Some(StatusCode::BadUserAccessDenied)
+        );
+        let user_role_permissions = read(AttributeId::UserRolePermissions).value.unwrap();
+        let expected = Base::role_permissions_to_variant(&[RolePermissionType {
+            role_id: observer,
+            permissions: PermissionType::Browse | PermissionType::Read,
+        }]);
+        assert_eq!(user_role_permissions, expected);
+
+        // An operator may write too
+        trace_write_lock!(session).set_roles(vec![operator]);
+        assert_eq!(write(), StatusCode::Good);
+        assert_eq!(read(AttributeId::Value).value, Some(Variant::Int32(100)));
+    });
+}
+
+/// Keeps the audit events passed to it
+struct AuditRecorder(Arc<Mutex<Vec<(NodeId, String)>>>);
+
+impl AuditSink for AuditRecorder {
+    fn audit(&self, event_type_id: &NodeId, message: &str) {
+        self.0
+            .lock()
+            .push((event_type_id.clone(), message.to_string()));
+    }
+}
+
+#[test]
+fn write_audit_event() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let (_, node_ids) = add_many_vars_to_address_space(address_space.clone(), 1);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let node = address_space.find_node_mut(&node_ids[0]).unwrap();
+            let access_level = AccessLevel::CURRENT_READ | AccessLevel::CURRENT_WRITE;
+            for attribute_id in [AttributeId::AccessLevel, AttributeId::UserAccessLevel] {
+                node.as_mut_node()
+                    .set_attribute(attribute_id, Variant::from(access_level.bits()))
+                    .unwrap();
+            }
+        }
+        let audits = Arc::new(Mutex::new(Vec::new()));
+        {
+            let mut server_state = trace_write_lock!(server_state);
+            server_state.set_audit_sink(Box::new(AuditRecorder(audits.clone())));
+        }
+        let nodes_to_write = vec![write_value(
+            &node_ids[0],
+            AttributeId::Value,
+            DataValue::new_now(100i32),
+        )];
+
+        // A write is audited with the value it replaced and the value written
+        let response = write_request(
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            ats,
+            nodes_to_write.clone(),
+        );
+        assert_eq!(response.results.unwrap()[0], StatusCode::Good);
+        {
+            let audits = audits.lock();
+            assert_eq!(audits.len(), 1);
+            let (event_type_id, message) = &audits[0];
+            let write_update_event_type_id: NodeId = ObjectTypeId::AuditWriteUpdateEventType.into();
+            assert_eq!(*event_type_id, write_update_event_type_id);
+            assert!(message.contains("SourceName=Attribute/Write"));
+            assert!(message.contains("Status=true"));
+            assert!(message.contains("OldValue=0"));
+            assert!(message.contains("NewValue=100"));
+        }
+
+        // Nothing is audited once auditing is turned off
+        {
+            let server_state = trace_read_lock!(server_state);
+            let mut config = trace_write_lock!(server_state.config);
+            config.auditing = false;
+        }
+        let response = write_request(server_state, session, address_space, ats, nodes_to_write);
+        assert_eq!(response.results.unwrap()[0], StatusCode::Good);
+        assert_eq!(audits.lock().len(), 1);
+    });
+}
+
+#[test]
+fn read_write_value_getter_setter() {
+    // Values of a variable backed by a getter and setter come from and go to application code
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let node_id = NodeId::next_numeric(2);
+        let fail_node_id = NodeId::next_numeric(2);
+        let application_value = Arc::new(Mutex::new(10i32));
+        let requested_max_age = Arc::new(Mutex::new(None));
+        {
+            let getter_value = application_value.clone();
+            let getter_max_age = requested_max_age.clone();
+            let setter_value = application_value.clone();
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = VariableBuilder::new(&node_id, var_name(0), "")
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .value_getter(AttrFnGetter::new_boxed(
+                    move |_, _, _, _, _, max_age| -> Result<Option<DataValue>, StatusCode> {
+                        *getter_max_age.lock() = Some(max_age);
+                        Ok(Some(DataValue::new_now(*getter_value.lock())))
+                    },
+                ))
+                .value_setter(AttrFnSetter::new_boxed(
+                    move |_, attribute_id, _, data_value| -> Result<(), StatusCode> {
+                        assert_eq!(attribute_id, AttributeId::Value);
+                        match data_value.value {
+                            Some(Variant::Int32(v)) => {
+                                *setter_value.lock() = v;
+                                Ok(())
+                            }
+                            _ => Err(StatusCode::BadTypeMismatch),
+                        }
+                    },
+                ))
+                .insert(&mut address_space);
+            let _ = VariableBuilder::new(&fail_node_id, var_name(1), "")
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .value_getter(AttrFnGetter::new_boxed(
+                    |_, _, _, _, _, _| -> Result<Option<DataValue>, StatusCode> {
+                        Err(StatusCode::BadDeviceFailure)
+                    },
+                ))
+                .value_setter(AttrFnSetter::new_boxed(
+                    |_, _, _, _| -> Result<(), StatusCode> { Err(StatusCode::BadDeviceFailure) },
+                ))
+                .insert(&mut address_space);
+        }
+
+        let read = |max_age: f64| {
+            let request = ReadRequest {
+                request_header: make_request_header(),
+                max_age,
+                timestamps_to_return: TimestampsToReturn::Both,
+                nodes_to_read: Some(vec![
+                    read_value(&node_id, AttributeId::Value),
+                    read_value(&fail_node_id, AttributeId::Value),
+                ]),
+            };
+            let response = ats.read(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request,
+            );
+            supported_message_as!(response, ReadResponse)
+                .results
+                .unwrap()
+        };
+
+        // The value is computed on read, and an error from the getter becomes the status
+        let results = read(500f64);
+        assert_eq!(results[0].value, Some(Variant::Int32(10)));
+        assert_eq!(*requested_max_age.lock(), Some(500f64));
+        assert_eq!(results[1].status, Some(StatusCode::BadDeviceFailure));
+
+        // Writes are forwarded to the setter, and an error from it is the result of the write
+        let response = write_request(
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            ats,
+            vec![
+                write_value(&node_id, AttributeId::Value, DataValue::new_now(20i32)),
+                write_value(&fail_node_id, AttributeId::Value, DataValue::new_now(20i32)),
+            ],
+        );
+        assert_eq!(
+            response.results.unwrap(),
+            vec![StatusCode::Good, StatusCode::BadDeviceFailure]
+        );
+        assert_eq!(*application_value.lock(), 20);
+        assert_eq!(read(0f64)[0].value, Some(Variant::Int32(20)));
+    });
+}
+
+/// Defers the value, which it completes straight away unless it is offline
+struct DeferringGetter {
+    online: bool,
+}
+
+impl AttributeGetter for DeferringGetter {
+    fn get(
+        &mut self,
+        _node_id: &NodeId,
+        _timestamps_to_return: TimestampsToReturn,
+        _attribute_id: AttributeId,
+        _index_range: NumericRange,
+        _data_encoding: &QualifiedName,
+        _max_age: f64,
+    ) -> Result<Option<DataValue>, StatusCode> {
+        Err(StatusCode::BadWaitingForInitialData)
+    }
+
+    fn get_deferred(
+        &mut self,
+        _node_id: &NodeId,
+        _timestamps_to_return: TimestampsToReturn,
+        _attribute_id: AttributeId,
+        _index_range: NumericRange,
+        _data_encoding: &QualifiedName,
+        _max_age: f64,
+    ) -> DeferredValue {
+        let (pending_value, completer) = PendingValue::channel();
+        if self.online {
+            completer.complete(DataValue::new_now(42i32));
+        }
+        DeferredValue::Pending(pending_value)
+    }
+}
+
+#[test]
+fn read_deferred_value() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let node_id = NodeId::next_numeric(2);
+        let offline_node_id = NodeId::next_numeric(2);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            for (node_id, online) in [(&node_id, true), (&offline_node_id, false)] {
+                VariableBuilder::new(node_id, var_name(0), "")
+                    .data_type(DataTypeId::Int32)
+                    .value(0i32)
+                    .organized_by(ObjectId::RootFolder)
+                    .value_getter(Arc::new(Mutex::new(DeferringGetter { online })))
+                    .insert(&mut address_space);
+            }
+        }
+
+        let request = ReadRequest {
+            request_header: make_request_header(),
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Neither,
+            nodes_to_read: Some(vec![
+                read_value(&node_id, AttributeId::Value),
+                read_value(&offline_node_id, AttributeId::Value),
+            ]),
+        };
+        let response = ats.read(server_state, session, address_space.clone(), &request);
+        let results = supported_message_as!(response, ReadResponse)
+            .results
+            .unwrap();
+
+        // A value that has completed is read, one that hasn't is still waited for
+        assert_eq!(results[0].value, Some(Variant::Int32(42)));
+        assert!(results[0].source_timestamp.is_none());
+        assert_eq!(
+            results[1].status,
+            Some(StatusCode::BadWaitingForInitialData)
+        );
+
+        // Monitored items only call get()
+        let address_space = trace_read_lock!(address_space);
+        assert_eq!(
+            address_space.get_variable_value(node_id).unwrap().status,
+            Some(StatusCode::BadWaitingForInitialData)
+        );
+    });
+}
+
+#[test]
+fn read_namespaces_concurrently() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        {
+            let server_state = trace_read_lock!(server_state);
+            let mut config = trace_write_lock!(server_state.config);
+            config.performance.max_concurrent_reads = 2;
+        }
+
+        // The getter of each namespace waits for the other, so the read only succeeds if the
+        // namespaces are read at the same time
+        let (tx_a, rx_a) = std::sync::mpsc::channel::<()>();
+        let (tx_b, rx_b) = std::sync::mpsc::channel::<()>();
+        let waiting_getter =
+            |namespace: u16, tx: std::sync::mpsc::Sender<()>, rx: std::sync::mpsc::Receiver<()>| {
+                let rx = Mutex::new(rx);
+                AttrFnGetter::new_boxed(
+                    move |_, _, _, _, _, _| -> Result<Option<DataValue>, StatusCode> {
+                        let _ = tx.send(());
+                        rx.lock()
+                            .recv_timeout(std::time::Duration::from_secs(5))
+                            .map_err(|_| StatusCode::BadTimeout)?;
+                        Ok(Some(DataValue::new_now(namespace as i32)))
+                    },
+                )
+            };
+        let (namespace_a, namespace_b) = {
+            let mut address_space = trace_write_lock!(address_space);
+            (
+                address_space.register_namespace("urn:a").unwrap(),
+                address_space.register_namespace("urn:b").unwrap(),
+            )
+        };
+        let slow_a = NodeId::new(namespace_a, "slow_a");
+        let slow_b = NodeId::new(namespace_b, "slow_b");
+        let plain = NodeId::new(namespace_b, "plain");
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = VariableBuilder::new(&slow_a, "slow_a", "")
+                .data_type(DataTypeId::Int32)
+                .organized_by(ObjectId::RootFolder)
+                .value_getter(waiting_getter(namespace_a, tx_b, rx_a))
+                .insert(&mut address_space);
+            let _ = VariableBuilder::new(&slow_b, "slow_b", "")
+                .data_type(DataTypeId::Int32)
+                .organized_by(ObjectId::RootFolder)
+                .value_getter(waiting_getter(namespace_b, tx_a, rx_b))
+                .insert(&mut address_space);
+            let _ = VariableBuilder::new(&plain, "plain", "")
+                .data_type(DataTypeId::Int32)
+                .value(100i32)
+                .organized_by(ObjectId::RootFolder)
+                .insert(&mut address_space);
+        }
+
+        let request = ReadRequest {
+            request_header: make_request_header(),
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Both,
+            nodes_to_read: Some(vec![
+                read_value(&plain, AttributeId::Value),
+                read_value(&slow_a, AttributeId::Value),
+                read_value(&NodeId::new(namespace_a, "missing"), AttributeId::Value),
+                read_value(&slow_b, AttributeId::Value),
+            ]),
+        };
+        let response = ats.read(server_state, session, address_space, &request);
+        let results = supported_message_as!(response, ReadResponse)
+            .results
+            .unwrap();
+
+        // Results are in request order
+        assert_eq!(results[0].value, Some(Variant::Int32(100)));
+        assert_eq!(results[1].value, Some(Variant::Int32(namespace_a as i32)));
+        assert_eq!(results[2].status, Some(StatusCode::BadNodeIdUnknown));
+        assert_eq!(results[3].value, Some(Variant::Int32(namespace_b as i32)));
+    });
+}
+
 #[test]
 fn write_bytestring_to_byte_array() {
     // This test checks that writing a byte string to a byte array variable works
@@ -614,6 +1184,236 @@ fn read_raw_modified_details() -> ReadRawModifiedDetails {
     }
 }
 
+#[test]
+fn write_value_rank_and_array_dimensions() {
+    // Test that written values must match the value rank and array dimensions of the variable
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let scalar_id = NodeId::next_numeric(2);
+        let array_id = NodeId::next_numeric(2);
+        let matrix_id = NodeId::next_numeric(2);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = VariableBuilder::new(&scalar_id, var_name(0), "")
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .insert(&mut address_space);
+            let _ = VariableBuilder::new(&array_id, var_name(1), "")
+                .data_type(DataTypeId::Int32)
+                .value_rank(1)
+                .array_dimensions(&[4])
+                .value(vec![0i32; 4])
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .insert(&mut address_space);
+            let _ = VariableBuilder::new(&matrix_id, var_name(2), "")
+                .data_type(DataTypeId::Int32)
+                .value_rank(2)
+                .array_dimensions(&[2, 0])
+                .value(vec![0i32; 4])
+                .organized_by(ObjectId::RootFolder)
+                .writable()
+                .insert(&mut address_space);
+        }
+
+        let matrix = |rows: u32, columns: u32| {
+            let values: Vec<Variant> = (0..(rows * columns) as i32).map(Variant::from).collect();
+            Variant::from((VariantTypeId::Int32, values, vec![rows, columns]))
+        };
+
+        let nodes_to_write = vec![
+            // Array to a scalar
+            write_value(
+                &scalar_id,
+                AttributeId::Value,
+                DataValue::new_now(vec![1i32, 2i32]),
+            ),
+            // Scalar to an array
+            write_value(&array_id, AttributeId::Value, DataValue::new_now(1i32)),
+            // Array longer than its array dimensions
+            write_value(
+                &array_id,
+                AttributeId::Value,
+                DataValue::new_now(vec![1i32; 5]),
+            ),
+            // Array within its array dimensions
+            write_value(
+                &array_id,
+                AttributeId::Value,
+                DataValue::new_now(vec![1i32; 3]),
+            ),
+            // One dimension array to a two dimension variable
+            write_value(
+                &matrix_id,
+                AttributeId::Value,
+                DataValue::new_now(vec![1i32; 4]),
+            ),
+            // Too many rows
+            write_value(
+                &matrix_id,
+                AttributeId::Value,
+                DataValue::new_now(matrix(3, 2)),
+            ),
+            // Any number of columns
+            write_value(
+                &matrix_id,
+                AttributeId::Value,
+                DataValue::new_now(matrix(2, 5)),
+            ),
+        ];
+
+        let response = write_request(
+            server_state,
+            session,
+            address_space.clone(),
+            ats,
+            nodes_to_write,
+        );
+        let results = response.results.unwrap();
+
+        assert_eq!(results[0], StatusCode::BadTypeMismatch);
+        assert_eq!(results[1], StatusCode::BadTypeMismatch);
+        assert_eq!(results[2], StatusCode::BadTypeMismatch);
+        assert_eq!(results[3], StatusCode::Good);
+        assert_eq!(results[4], StatusCode::BadTypeMismatch);
+        assert_eq!(results[5], StatusCode::BadTypeMismatch);
+        assert_eq!(results[6], StatusCode::Good);
+
+        validate_variable_value(address_space, &matrix_id, |value| {
+            assert_eq!(value.array_dimensions(), Some(vec![2, 5]));
+            match value {
+                Variant::Array(array) => {
+                    assert_eq!(array.value_at(&[1, 4]), Some(&Variant::Int32(9)));
+                }
+                _ => panic!(),
+            }
+        });
+    });
+}
+
+#[test]
+fn write_confirmation() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let node_id = NodeId::next_numeric(2);
+        let short_node_id = NodeId::next_numeric(2);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            for node_id in [&node_id, &short_node_id] {
+                VariableBuilder::new(node_id, var_name(0), "")
+                    .data_type(DataTypeId::Int32)
+                    .value(10i32)
+                    .organized_by(ObjectId::RootFolder)
+                    .writable()
+                    .insert(&mut address_space);
+            }
+            assert!(!address_space
+                .add_write_confirmation(&ObjectId::RootFolder.into(), std::time::Duration::ZERO));
+            assert!(address_space
+                .add_write_confirmation(&node_id, std::time::Duration::from_secs(3600)));
+            assert!(!address_space
+                .add_write_confirmation(&node_id, std::time::Duration::from_secs(3600)));
+            assert!(address_space.add_write_confirmation(&short_node_id, std::time::Duration::ZERO));
+        }
+        let property = |node_id: &NodeId, name: &str| {
+            let address_space = trace_read_lock!(address_space);
+            find_node_from_browse_path(&address_space, node_id, &[QualifiedName::new(2, name)])
+                .unwrap()
+                .as_node()
+                .node_id()
+        };
+        let value = |node_id: &NodeId| {
+            let address_space = trace_read_lock!(address_space);
+            address_space.get_variable_value(node_id).unwrap().value
+        };
+        let write = |session: &Arc<RwLock<Session>>, node_id: &NodeId, value: Variant| {
+            let response = write_request(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                ats,
+                vec![write_value(
+                    node_id,
+                    AttributeId::Value,
+                    DataValue::new_now(value),
+                )],
+            );
+            response.results.unwrap()[0]
+        };
+        let confirm_write = property(&node_id, "ConfirmWrite");
+
+        // Nothing to confirm yet
+        assert_eq!(
+            write(&session, &confirm_write, 1u32.into()),
+            StatusCode::BadInvalidState
+        );
+
+        // The write is held until it is confirmed
+        assert_eq!(
+            write(&session, &node_id, 20i32.into()),
+            StatusCode::GoodCompletesAsynchronously
+        );
+        assert_eq!(value(&node_id), Some(Variant::from(10i32)));
+        assert_eq!(
+            value(&property(&node_id, "PendingValue")),
+            Some(Variant::from(20i32))
+        );
+        let Some(Variant::UInt32(token)) = value(&property(&node_id, "ConfirmationToken")) else {
+            panic!();
+        };
+        assert_ne!(token, 0);
+
+        // Only the right token from the same session confirms it
+        assert_eq!(
+            write(&session, &confirm_write, token.wrapping_add(1).into()),
+            StatusCode::BadInvalidArgument
+        );
+        let other_session = Arc::new(RwLock::new(Session::new(server_state.clone())));
+        assert_eq!(
+            write(&other_session, &confirm_write, token.into()),
+            StatusCode::BadUserAccessDenied
+        );
+        assert_eq!(value(&node_id), Some(Variant::from(10i32)));
+        assert_eq!(
+            write(&session, &confirm_write, token.into()),
+            StatusCode::Good
+        );
+        assert_eq!(value(&node_id), Some(Variant::from(20i32)));
+        assert_eq!(
+            value(&property(&node_id, "ConfirmationToken")),
+            Some(Variant::from(0u32))
+        );
+        assert_eq!(
+            write(&session, &confirm_write, token.into()),
+            StatusCode::BadInvalidState
+        );
+
+        // A write that isn't confirmed in time is rolled back
+        assert_eq!(
+            write(&session, &short_node_id, 30i32.into()),
+            StatusCode::GoodCompletesAsynchronously
+        );
+        let Some(Variant::UInt32(token)) = value(&property(&short_node_id, "ConfirmationToken"))
+        else {
+            panic!();
+        };
+        trace_write_lock!(address_space).expire_pending_writes(&chrono::Utc::now());
+        assert_eq!(
+            value(&property(&short_node_id, "PendingValue")),
+            Some(Variant::Empty)
+        );
+        assert_eq!(
+            write(
+                &session,
+                &property(&short_node_id, "ConfirmWrite"),
+                token.into()
+            ),
+            StatusCode::BadInvalidState
+        );
+        assert_eq!(value(&short_node_id), Some(Variant::from(10i32)));
+    });
+}
+
 #[test]
 fn history_read_nothing_to_do_1() {
     do_attribute_service_test(|server_state, session, address_space, ats| {
@@ -695,6 +1495,253 @@ fn history_read_nothing_data_provider() {
     });
 }
 
+/// Provides raw values 1 to 4, one every second from the start time, for every node
+struct RawDataProvider;
+
+impl HistoricalDataProvider for RawDataProvider {
+    fn read_raw_modified_details(
+        &self,
+        _address_space: Arc<RwLock<AddressSpace>>,
+        request: ReadRawModifiedDetails,
+        _timestamps_to_return: TimestampsToReturn,
+        _release_continuation_points: bool,
+        nodes_to_read: &[HistoryReadValueId],
+    ) -> Result<Vec<HistoryReadResult>, StatusCode> {
+        let data_values = (0..4)
+            .map(|i| {
+                let mut data_value = DataValue::value_only((i + 1) as f64);
+                data_value.source_timestamp = Some(request.start_time + Duration::seconds(i));
+                data_value
+            })
+            .collect();
+        let history_data = HistoryData {
+            data_values: Some(data_values),
+        };
+        Ok(nodes_to_read
+            .iter()
+            .map(|_| HistoryReadResult {
+                status_code: StatusCode::Good,
+                continuation_point: ByteString::null(),
+                history_data: ExtensionObject::from_encodable(
+                    ObjectId::HistoryData_Encoding_DefaultBinary,
+                    &history_data,
+                ),
+            })
+            .collect())
+    }
+}
+
+fn history_read_processed_request(aggregate_type: Option<Vec<NodeId>>) -> HistoryReadRequest {
+    let start_time = DateTime::ymd_hms(2024, 1, 1, 0, 0, 0);
+    let read_processed_details = ReadProcessedDetails {
+        start_time,
+        end_time: start_time + Duration::seconds(4),
+        processing_interval: 2000f64,
+        aggregate_type,
+        aggregate_configuration: AggregateConfiguration {
+            use_server_capabilities_defaults: true,
+            treat_uncertain_as_bad: true,
+            percent_data_bad: 100,
+            percent_data_good: 100,
+            use_sloped_extrapolation: false,
+        },
+    };
+    HistoryReadRequest {
+        request_header: make_request_header(),
+        history_read_details: ExtensionObject::from_encodable(
+            ObjectId::ReadProcessedDetails_Encoding_DefaultBinary,
+            &read_processed_details,
+        ),
+        timestamps_to_return: TimestampsToReturn::Source,
+        release_continuation_points: true,
+        nodes_to_read: Some(nodes_to_read()),
+    }
+}
+
+#[test]
+fn history_read_processed() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        {
+            let mut server_state = server_state.write();
+            server_state.set_historical_data_provider(Box::new(RawDataProvider));
+        }
+
+        // The aggregates are computed from the raw values of the provider
+        let request =
+            history_read_processed_request(Some(vec![ObjectId::AggregateFunction_Average.into()]));
+        let response: HistoryReadResponse = supported_message_as!(
+            ats.history_read(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request
+            ),
+            HistoryReadResponse
+        );
+        let results = response.results.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status_code, StatusCode::Good);
+        let data_values = results[0]
+            .history_data
+            .decode_inner::<HistoryData>(&DecodingOptions::test())
+            .unwrap()
+            .data_values
+            .unwrap();
+        assert_eq!(data_values.len(), 2);
+        assert_eq!(data_values[0].value, Some(Variant::Double(1.5)));
+        assert_eq!(data_values[1].value, Some(Variant::Double(3.5)));
+        assert!(data_values[0].source_timestamp.is_some());
+        assert!(data_values[0].server_timestamp.is_none());
+
+        // An unsupported aggregate is reported against the node
+        let request =
+            history_read_processed_request(Some(vec![ObjectId::AggregateFunction_Total.into()]));
+        let response: HistoryReadResponse = supported_message_as!(
+            ats.history_read(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request
+            ),
+            HistoryReadResponse
+        );
+        assert_eq!(
+            response.results.unwrap()[0].status_code,
+            StatusCode::BadAggregateNotSupported
+        );
+
+        // There must be an aggregate for every node
+        let request = history_read_processed_request(None);
+        let response: ServiceFault = supported_message_as!(
+            ats.history_read(server_state, session, address_space, &request),
+            ServiceFault
+        );
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadAggregateListMismatch
+        );
+    });
+}
+
+#[test]
+fn access_levels_enforced() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let (_, node_ids) = add_many_vars_to_address_space(address_space.clone(), 4);
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let mut set_access_levels = |node_id: &NodeId, access_level, user_access_level| {
+                let variable = address_space.find_variable_mut(node_id.clone()).unwrap();
+                variable.set_access_level(access_level);
+                variable.set_user_access_level(user_access_level);
+            };
+            // The variable can't be read by anyone, even though the user may
+            set_access_levels(
+                &node_ids[0],
+                AccessLevel::empty(),
+                UserAccessLevel::CURRENT_READ | UserAccessLevel::HISTORY_READ,
+            );
+            // The variable can be written, but not by the user
+            set_access_levels(
+                &node_ids[1],
+                AccessLevel::CURRENT_READ | AccessLevel::CURRENT_WRITE,
+                UserAccessLevel::CURRENT_READ,
+            );
+            // The variable can't be written, even though the user may
+            set_access_levels(
+                &node_ids[2],
+                AccessLevel::CURRENT_READ,
+                UserAccessLevel::CURRENT_READ | UserAccessLevel::CURRENT_WRITE,
+            );
+            // The variable has history
+            set_access_levels(
+                &node_ids[3],
+                AccessLevel::CURRENT_READ | AccessLevel::HISTORY_READ,
+                UserAccessLevel::CURRENT_READ | UserAccessLevel::HISTORY_READ,
+            );
+        }
+
+        // The access levels only govern the value
+        let request = ReadRequest {
+            request_header: make_request_header(),
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Both,
+            nodes_to_read: Some(vec![
+                read_value(&node_ids[0], AttributeId::Value),
+                read_value(&node_ids[0], AttributeId::DisplayName),
+            ]),
+        };
+        let response: ReadResponse = supported_message_as!(
+            ats.read(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request
+            ),
+            ReadResponse
+        );
+        let results = response.results.unwrap();
+        assert_eq!(results[0].status, Some(StatusCode::BadNotReadable));
+        assert!(results[1].value.is_some());
+
+        let nodes_to_write = node_ids[1..=2]
+            .iter()
+            .map(|n| write_value(n, AttributeId::Value, DataValue::new_now(100i32)))
+            .collect();
+        let response = write_request(
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            ats,
+            nodes_to_write,
+        );
+        assert_eq!(
+            response.results.unwrap(),
+            vec![StatusCode::BadUserAccessDenied, StatusCode::BadNotWritable]
+        );
+
+        // History is only read for the variables that allow it, in the order they were asked for
+        {
+            let mut server_state = server_state.write();
+            server_state.set_historical_data_provider(Box::new(RawDataProvider));
+        }
+        let history_read_details = ExtensionObject::from_encodable(
+            ObjectId::ReadRawModifiedDetails_Encoding_DefaultBinary,
+            &read_raw_modified_details(),
+        );
+        let nodes_to_read = [&node_ids[0], &node_ids[3], &node_ids[1]]
+            .into_iter()
+            .map(|node_id| HistoryReadValueId {
+                node_id: node_id.clone(),
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+                continuation_point: ByteString::null(),
+            })
+            .collect();
+        let request = HistoryReadRequest {
+            request_header: make_request_header(),
+            history_read_details,
+            timestamps_to_return: TimestampsToReturn::Both,
+            release_continuation_points: true,
+            nodes_to_read: Some(nodes_to_read),
+        };
+        let response: HistoryReadResponse = supported_message_as!(
+            ats.history_read(server_state, session, address_space, &request),
+            HistoryReadResponse
+        );
+        let results = response.results.unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.status_code).collect::<Vec<_>>(),
+            vec![
+                StatusCode::BadNotReadable,
+                StatusCode::Good,
+                StatusCode::BadNotReadable
+            ]
+        );
+        assert!(results[0].history_data.is_null());
+        assert!(!results[1].history_data.is_null());
+    });
+}
+
 fn delete_raw_modified_details() -> DeleteRawModifiedDetails {
     let now = chrono::Utc::now();
     let start_time = (now - Duration::days(5)).into();
@@ -827,3 +1874,61 @@ fn history_update_data_provider() {
         assert_eq!(result1.status_code, StatusCode::Good);
     });
 }
+
+#[test]
+fn read_data_type_definition() {
+    do_attribute_service_test(|server_state, session, address_space, ats| {
+        let definition = StructureDefinition {
+            default_encoding_id: NodeId::new(1, "PointEncoding"),
+            base_data_type: DataTypeId::Structure.into(),
+            structure_type: StructureType::Structure,
+            fields: Some(vec![StructureField {
+                name: "X".into(),
+                description: LocalizedText::null(),
+                data_type: DataTypeId::Double.into(),
+                value_rank: -1,
+                array_dimensions: None,
+                max_string_length: 0,
+                is_optional: false,
+            }]),
+        };
+        let point_id = NodeId::new(1, "Point");
+        let empty_id = NodeId::new(1, "Empty");
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            DataTypeBuilder::new(&point_id, "Point", "Point")
+                .data_type_definition(definition.clone())
+                .insert(&mut address_space);
+            DataTypeBuilder::new(&empty_id, "Empty", "Empty").insert(&mut address_space);
+        }
+
+        let request = ReadRequest {
+            request_header: make_request_header(),
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Neither,
+            nodes_to_read: Some(vec![
+                read_value(&point_id, AttributeId::DataTypeDefinition),
+                read_value(&empty_id, AttributeId::DataTypeDefinition),
+            ]),
+        };
+        let response = ats.read(server_state, session, address_space, &request);
+        let response: ReadResponse = supported_message_as!(response, ReadResponse);
+        let results = response.results.unwrap();
+
+        // The definition is returned as an extension object
+        let value = match results[0].value {
+            Some(Variant::ExtensionObject(ref v)) => v,
+            ref v => panic!("Unexpected value {:?}", v),
+        };
+        assert_eq!(
+            DataTypeDefinition::from_extension_object(value, &DecodingOptions::test()).unwrap(),
+            DataTypeDefinition::Structure(definition)
+        );
+
+        // A data type without a definition
+        assert_eq!(
+            results[1].status.as_ref().unwrap(),
+            &StatusCode::BadAttributeIdInvalid
+        );
+    });
+}
@@ -38,6 +38,99 @@ fn get_endpoints() {
     });
 }
 
+#[cfg(feature = "websocket")]
+#[test]
+fn get_endpoints_websocket() {
+    let st = ServiceTest::new_with_server(ServerBuilder::new_sample().websocket_port(4843));
+    let (server_state, _session) = st.get_server_state_and_session();
+    let ds = DiscoveryService::new();
+    let get_endpoints = |profile_uris: Option<Vec<UAString>>| {
+        let request = GetEndpointsRequest {
+            request_header: make_request_header(),
+            endpoint_url: UAString::from("opc.wss://localhost:4843/"),
+            locale_ids: None,
+            profile_uris,
+        };
+        let result = ds.get_endpoints(server_state.clone(), &request);
+        supported_message_as!(result, GetEndpointsResponse).endpoints
+    };
+
+    // Every endpoint is offered on both transports
+    let endpoints = get_endpoints(None).unwrap();
+    let wss_endpoints = endpoints
+        .iter()
+        .filter(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_WSS_BINARY)
+        .collect::<Vec<_>>();
+    assert_eq!(wss_endpoints.len() * 2, endpoints.len());
+    assert!(wss_endpoints
+        .iter()
+        .all(|e| e.endpoint_url.as_ref().starts_with("opc.wss://")));
+
+    // Only the transport that is asked for
+    let endpoints = get_endpoints(Some(vec![UAString::from(
+        profiles::TRANSPORT_PROFILE_URI_WSS_BINARY,
+    )]))
+    .unwrap();
+    assert_eq!(endpoints.len(), wss_endpoints.len());
+    assert!(get_endpoints(Some(vec![UAString::from(
+        "http://opcfoundation.org/UA-Profile/Transport/https-uabinary"
+    )]))
+    .is_none());
+
+    // Sessions can be created on the opc.wss endpoints
+    let server_state = trace_read_lock!(server_state);
+    assert!(server_state.endpoint_exists(
+        "opc.wss://localhost:4843/",
+        SecurityPolicy::None,
+        MessageSecurityMode::None
+    ));
+    assert!(!server_state.endpoint_exists(
+        "opc.wss://localhost:4844/",
+        SecurityPolicy::None,
+        MessageSecurityMode::None
+    ));
+}
+
+#[test]
+#[cfg(feature = "https")]
+fn get_endpoints_https() {
+    let st = ServiceTest::new_with_server(ServerBuilder::new_sample().https_port(4443));
+    let (server_state, _session) = st.get_server_state_and_session();
+    let ds = DiscoveryService::new();
+    let request = GetEndpointsRequest {
+        request_header: make_request_header(),
+        endpoint_url: UAString::from("https://localhost:4443/"),
+        locale_ids: None,
+        profile_uris: Some(vec![UAString::from(
+            profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY,
+        )]),
+    };
+    let result = ds.get_endpoints(server_state.clone(), &request);
+    let endpoints = supported_message_as!(result, GetEndpointsResponse)
+        .endpoints
+        .unwrap();
+
+    // Only the endpoints without security are offered over https
+    assert!(!endpoints.is_empty());
+    assert!(endpoints.iter().all(|e| {
+        e.endpoint_url.as_ref().starts_with("https://")
+            && e.security_policy_uri.as_ref() == SecurityPolicy::None.to_uri()
+            && e.security_mode == MessageSecurityMode::None
+    }));
+
+    let server_state = trace_read_lock!(server_state);
+    assert!(server_state.endpoint_exists(
+        "https://localhost:4443/",
+        SecurityPolicy::None,
+        MessageSecurityMode::None
+    ));
+    assert!(!server_state.endpoint_exists(
+        "https://localhost:4443/",
+        SecurityPolicy::Basic256Sha256,
+        MessageSecurityMode::SignAndEncrypt
+    ));
+}
+
 #[test]
 fn find_servers() {
     do_discovery_service_test(|server_state, _session, ds| {
@@ -120,3 +213,63 @@ fn discovery_test() {
         }
     });
 }
+
+#[test]
+fn localized_application_name() {
+    let st = ServiceTest::new_with_server(
+        ServerBuilder::new_sample()
+            .localized_application_name("de", "OPC UA Beispielserver")
+            .localized_application_name("fr-CA", "Serveur OPC UA"),
+    );
+    let (server_state, _session) = st.get_server_state_and_session();
+    let ds = DiscoveryService::new();
+
+    let locale_ids =
+        |locale_ids: &[&str]| Some(locale_ids.iter().map(|l| UAString::from(*l)).collect());
+    let find_servers = |locale_ids: Option<Vec<UAString>>| {
+        let request = FindServersRequest {
+            request_header: make_request_header(),
+            endpoint_url: Default::default(),
+            locale_ids,
+            server_uris: None,
+        };
+        let result = ds.find_servers(server_state.clone(), &request);
+        let mut servers = supported_message_as!(result, FindServersResponse)
+            .servers
+            .unwrap();
+        servers.remove(0).application_name
+    };
+
+    // Without a matching locale, the application name is the default one
+    assert_eq!(find_servers(None).text.as_ref(), "OPC UA Sample Server");
+    assert_eq!(
+        find_servers(locale_ids(&["fr"])).text.as_ref(),
+        "OPC UA Sample Server"
+    );
+
+    // The first locale that matches wins, by locale id or by its language
+    let name = find_servers(locale_ids(&["es", "de-AT", "fr-CA"]));
+    assert_eq!(name, LocalizedText::new("de", "OPC UA Beispielserver"));
+    let name = find_servers(locale_ids(&["fr-CA", "de"]));
+    assert_eq!(name, LocalizedText::new("fr-CA", "Serveur OPC UA"));
+
+    // Endpoints carry the application name in the same locale
+    let request = GetEndpointsRequest {
+        request_header: make_request_header(),
+        endpoint_url: UAString::from("opc.tcp://localhost:4855/"),
+        locale_ids: locale_ids(&["de"]),
+        profile_uris: None,
+    };
+    let result = ds.get_endpoints(server_state.clone(), &request);
+    let endpoints = supported_message_as!(result, GetEndpointsResponse)
+        .endpoints
+        .unwrap();
+    assert!(!endpoints.is_empty());
+    assert!(endpoints
+        .iter()
+        .all(|e| e.server.application_name == LocalizedText::new("de", "OPC UA Beispielserver")));
+
+    // A discovery server is told the name in every locale
+    let registered_server = trace_read_lock!(server_state).registered_server();
+    assert_eq!(registered_server.server_names.unwrap().len(), 3);
+}
@@ -1,8 +1,12 @@
+use crate::server::address_space::relative_path::find_node_from_browse_path;
 use crate::supported_message_as;
 use crate::sync::*;
 use crate::types::{
     node_ids::{MethodId, ObjectId},
-    service_types::{CallMethodRequest, CallMethodResult, CallRequest, CallResponse},
+    service_types::{
+        CallMethodRequest, CallMethodResult, CallRequest, CallResponse,
+        ServerState as ServerStateType,
+    },
     status_code::StatusCode,
 };
 
@@ -322,6 +326,100 @@ fn call_getmonitoreditems() {
     });
 }
 
+#[test]
+fn call_getmonitoreditems_pairs_handles() {
+    do_method_service_test(|server_state, session_manager, session, address_space, s| {
+        let ss = SubscriptionService::new();
+        let mis = MonitoredItemService::new();
+
+        let subscription_id = {
+            let request = create_subscription_request();
+            let response: CreateSubscriptionResponse = supported_message_as!(
+                ss.create_subscription(server_state.clone(), session.clone(), &request),
+                CreateSubscriptionResponse
+            );
+            response.subscription_id
+        };
+
+        // Client handles that don't follow the order of the server handles
+        let mut expected = [300u32, 100, 200]
+            .iter()
+            .map(|client_handle| {
+                let request = create_monitored_items_request(
+                    subscription_id,
+                    *client_handle,
+                    VariableId::Server_ServerStatus_CurrentTime,
+                );
+                let response: CreateMonitoredItemsResponse = supported_message_as!(
+                    mis.create_monitored_items(
+                        server_state.clone(),
+                        session.clone(),
+                        address_space.clone(),
+                        &request
+                    ),
+                    CreateMonitoredItemsResponse
+                );
+                (
+                    response.results.unwrap()[0].monitored_item_id,
+                    *client_handle,
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort();
+
+        let args: Vec<Variant> = vec![subscription_id.into()];
+        let request = new_call_method_request(
+            ObjectId::Server,
+            MethodId::Server_GetMonitoredItems,
+            Some(args),
+        );
+        let response = call_single(
+            s,
+            server_state,
+            session_manager,
+            session.clone(),
+            address_space,
+            request,
+        )
+        .unwrap();
+        assert_eq!(response.status_code, StatusCode::Good);
+
+        // The handles at each index belong to the same item, in order of server handle
+        let handles = |v: &Variant| match v {
+            Variant::Array(array) => array
+                .values
+                .iter()
+                .map(|v| match v {
+                    Variant::UInt32(v) => *v,
+                    _ => panic!("Expected a u32"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected an array"),
+        };
+        let result = response.output_arguments.unwrap();
+        let server_handles = handles(&result[0]);
+        let client_handles = handles(&result[1]);
+        let actual = server_handles
+            .into_iter()
+            .zip(client_handles)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        // The snapshot of the subscription has the same handles
+        let session = trace_read_lock!(session);
+        let info = session
+            .subscriptions()
+            .subscriptions()
+            .get(&subscription_id)
+            .unwrap()
+            .info(session.session_id());
+        assert_eq!(info.subscription_id, subscription_id);
+        assert_eq!(&info.session_id, session.session_id());
+        assert!(info.publishing_enabled);
+        assert_eq!(info.monitored_items, expected);
+    });
+}
+
 #[test]
 fn call_resend_data() {
     do_method_service_test(|server_state, session_manager, session, address_space, s| {
@@ -390,3 +488,289 @@ fn call_resend_data() {
         }
     });
 }
+
+#[test]
+fn call_method_statistics() {
+    do_method_service_test(|server_state, session_manager, session, address_space, s| {
+        let method_id: NodeId = MethodId::Server_GetMonitoredItems.into();
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            assert!(!address_space.add_method_statistics(&ObjectId::Server.into()));
+            assert!(address_space.add_method_statistics(&method_id));
+            assert!(!address_space.add_method_statistics(&method_id));
+        }
+        let statistic = |name: &str| {
+            let address_space = trace_read_lock!(address_space);
+            let node_id = find_node_from_browse_path(
+                &address_space,
+                &method_id,
+                &[QualifiedName::new(address_space.default_namespace(), name)],
+            )
+            .unwrap()
+            .as_node()
+            .node_id();
+            address_space
+                .get_variable_value(node_id)
+                .unwrap()
+                .value
+                .unwrap()
+        };
+        assert_eq!(statistic("CallCount"), Variant::from(0u32));
+        assert_eq!(statistic("LastCallTime"), Variant::from(DateTime::null()));
+
+        // Failed calls are counted too
+        let request =
+            new_call_method_request(ObjectId::Server, MethodId::Server_GetMonitoredItems, None);
+        let response = call_single(
+            s,
+            server_state.clone(),
+            session_manager.clone(),
+            session.clone(),
+            address_space.clone(),
+            request,
+        )
+        .unwrap();
+        assert_eq!(response.status_code, StatusCode::BadArgumentsMissing);
+        assert_eq!(statistic("CallCount"), Variant::from(1u32));
+        assert_eq!(
+            statistic("LastCallStatus"),
+            Variant::from(StatusCode::BadArgumentsMissing)
+        );
+
+        let request = new_call_method_request(
+            ObjectId::Server,
+            MethodId::Server_GetMonitoredItems,
+            Some(vec![Variant::from(999u32)]),
+        );
+        let response = call_single(
+            s,
+            server_state,
+            session_manager,
+            session,
+            address_space.clone(),
+            request,
+        )
+        .unwrap();
+        assert_eq!(statistic("CallCount"), Variant::from(2u32));
+        assert_eq!(
+            statistic("LastCallStatus"),
+            Variant::from(response.status_code)
+        );
+        assert_ne!(statistic("LastCallTime"), Variant::from(DateTime::null()));
+    });
+}
+
+#[test]
+fn call_role_permissions() {
+    do_method_service_test(|server_state, session_manager, session, address_space, s| {
+        let operator: NodeId = ObjectId::WellKnownRole_Operator.into();
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let method = address_space
+                .find_node_mut(&MethodId::Server_GetMonitoredItems.into())
+                .unwrap();
+            method
+                .as_mut_node()
+                .set_role_permissions(vec![RolePermissionType {
+                    role_id: operator.clone(),
+                    permissions: PermissionType::Browse | PermissionType::Call,
+                }]);
+        }
+        let call = || {
+            let request =
+                new_call_method_request(ObjectId::Server, MethodId::Server_GetMonitoredItems, None);
+            call_single(
+                s,
+                server_state.clone(),
+                session_manager.clone(),
+                session.clone(),
+                address_space.clone(),
+                request,
+            )
+            .unwrap()
+            .status_code
+        };
+
+        // Only an operator may call the method
+        assert_eq!(call(), StatusCode::BadUserAccessDenied);
+        trace_write_lock!(session).set_roles(vec![operator]);
+        assert_eq!(call(), StatusCode::BadArgumentsMissing);
+    });
+}
+
+#[test]
+fn call_request_server_state_change() {
+    do_method_service_test(|server_state, session_manager, session, address_space, s| {
+        let call = |state: ServerStateType, seconds_till_shutdown: u32, restart: bool| {
+            let args: Vec<Variant> = vec![
+                (state as i32).into(),
+                DateTime::now().into(),
+                seconds_till_shutdown.into(),
+                LocalizedText::new("", "Maintenance").into(),
+                restart.into(),
+            ];
+            let request = new_call_method_request(
+                ObjectId::Server,
+                MethodId::Server_RequestServerStateChange,
+                Some(args),
+            );
+            call_single(
+                s,
+                server_state.clone(),
+                session_manager.clone(),
+                session.clone(),
+                address_space.clone(),
+                request,
+            )
+            .unwrap()
+        };
+
+        let response = call(ServerStateType::Suspended, 0, false);
+        assert_eq!(response.status_code, StatusCode::Good);
+        assert_eq!(
+            trace_read_lock!(server_state).state(),
+            ServerStateType::Suspended
+        );
+
+        // The server reports the states that are faults itself, and cannot restart itself
+        let response = call(ServerStateType::Failed, 0, false);
+        assert_eq!(response.status_code, StatusCode::BadInvalidArgument);
+        let response = call(ServerStateType::Shutdown, 0, true);
+        assert_eq!(response.status_code, StatusCode::BadNotSupported);
+
+        let response = call(ServerStateType::Shutdown, 60, false);
+        assert_eq!(response.status_code, StatusCode::Good);
+        {
+            let server_state = trace_read_lock!(server_state);
+            assert_eq!(server_state.state(), ServerStateType::Shutdown);
+            assert!(server_state.seconds_till_shutdown().unwrap() <= 60);
+            assert_eq!(
+                server_state.shutdown_reason().text,
+                UAString::from("Maintenance")
+            );
+            assert!(!server_state.is_abort());
+        }
+
+        // The countdown is reported in the server status
+        let read = |variable_id: VariableId| {
+            let address_space = trace_read_lock!(address_space);
+            address_space
+                .find_variable(variable_id)
+                .unwrap()
+                .value(
+                    TimestampsToReturn::Neither,
+                    NumericRange::None,
+                    &QualifiedName::null(),
+                    0.0,
+                )
+                .value
+                .unwrap()
+        };
+        assert_eq!(
+            read(VariableId::Server_ServerStatus_State),
+            Variant::from(ServerStateType::Shutdown as i32)
+        );
+        let Variant::UInt32(seconds_till_shutdown) =
+            read(VariableId::Server_ServerStatus_SecondsTillShutdown)
+        else {
+            panic!("Expected seconds till shutdown");
+        };
+        assert!(seconds_till_shutdown > 0 && seconds_till_shutdown <= 60);
+        assert_eq!(
+            read(VariableId::Server_ServerStatus_ShutdownReason),
+            Variant::from(LocalizedText::new("", "Maintenance"))
+        );
+        let Variant::ExtensionObject(status) = read(VariableId::Server_ServerStatus) else {
+            panic!("Expected an extension object");
+        };
+        let status = status
+            .decode_inner::<ServerStatusDataType>(&DecodingOptions::test())
+            .unwrap();
+        assert_eq!(status.state, ServerStateType::Shutdown);
+        assert_eq!(status.start_time, trace_read_lock!(server_state).start_time);
+        assert_eq!(
+            status.build_info.software_version,
+            UAString::from(env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(
+            read(VariableId::Server_ServerStatus_BuildInfo_SoftwareVersion),
+            Variant::from(env!("CARGO_PKG_VERSION"))
+        );
+
+        // Going back to running cancels the shutdown
+        let response = call(ServerStateType::Running, 0, false);
+        assert_eq!(response.status_code, StatusCode::Good);
+        let server_state = trace_read_lock!(server_state);
+        assert!(server_state.is_running());
+        assert_eq!(server_state.seconds_till_shutdown(), None);
+    });
+}
+
+#[cfg(feature = "pubsub")]
+#[test]
+fn call_get_security_keys() {
+    use crate::pubsub::prelude::{
+        GetSecurityKeysMethod, PubSubSecurityPolicy, SecurityGroup, SecurityKeyService,
+    };
+
+    do_method_service_test(|server_state, session_manager, session, address_space, s| {
+        let mut sks = SecurityKeyService::new();
+        sks.add_security_group(SecurityGroup::new(
+            "Group",
+            PubSubSecurityPolicy::Aes256Ctr,
+            60000f64,
+        ));
+        trace_write_lock!(address_space).register_method_handler(
+            MethodId::PublishSubscribe_GetSecurityKeys,
+            Box::new(GetSecurityKeysMethod::new(Arc::new(RwLock::new(sks)))),
+        );
+        let call = |args: Vec<Variant>| {
+            let request = new_call_method_request(
+                ObjectId::PublishSubscribe,
+                MethodId::PublishSubscribe_GetSecurityKeys,
+                Some(args),
+            );
+            call_single(
+                s,
+                server_state.clone(),
+                session_manager.clone(),
+                session.clone(),
+                address_space.clone(),
+                request,
+            )
+            .unwrap()
+        };
+        let args = |security_group_id: &str| -> Vec<Variant> {
+            vec![security_group_id.into(), 0u32.into(), 2u32.into()]
+        };
+
+        // Keys are only handed out over an encrypted channel
+        let response = call(args("Group"));
+        assert_eq!(
+            response.status_code,
+            StatusCode::BadSecurityModeInsufficient
+        );
+
+        trace_write_lock!(session).set_security_mode(MessageSecurityMode::SignAndEncrypt);
+        let response = call(args("Group"));
+        assert_eq!(response.status_code, StatusCode::Good);
+        let output_arguments = response.output_arguments.unwrap();
+        assert_eq!(output_arguments.len(), 5);
+        assert_eq!(
+            output_arguments[0],
+            Variant::from(PubSubSecurityPolicy::Aes256Ctr.to_uri())
+        );
+        assert_eq!(output_arguments[1], Variant::from(1u32));
+        match output_arguments[2] {
+            Variant::Array(ref keys) => assert_eq!(keys.values.len(), 2),
+            ref keys => panic!("Unexpected keys {:?}", keys),
+        }
+
+        let response = call(args("Other"));
+        assert_eq!(response.status_code, StatusCode::BadNotFound);
+        let response = call(vec!["Group".into(), 0i32.into(), 2u32.into()]);
+        assert_eq!(response.status_code, StatusCode::BadInvalidArgument);
+        let response = call(vec!["Group".into()]);
+        assert_eq!(response.status_code, StatusCode::BadArgumentsMissing);
+    });
+}
@@ -6,10 +6,12 @@ use chrono::{self, Utc};
 use super::*;
 use crate::{
     server::{
+        diagnostics::ServerDiagnostics,
+        historical::aggregates::default_aggregate_configuration,
         services::{monitored_item::MonitoredItemService, subscription::SubscriptionService},
         subscriptions::{
             monitored_item::*,
-            subscription::{SubscriptionState, TickReason},
+            subscription::{Subscription, SubscriptionState, TickReason},
         },
     },
     supported_message_as,
@@ -125,6 +127,7 @@ fn make_create_request_event_filter(
 }
 
 fn set_monitoring_mode(
+    server_state: Arc<RwLock<ServerState>>,
     session: Arc<RwLock<Session>>,
     subscription_id: u32,
     monitored_item_id: u32,
@@ -138,7 +141,7 @@ fn set_monitoring_mode(
         monitored_item_ids: Some(vec![monitored_item_id]),
     };
     let response: SetMonitoringModeResponse = supported_message_as!(
-        mis.set_monitoring_mode(session, &request),
+        mis.set_monitoring_mode(server_state, session, &request),
         SetMonitoringModeResponse
     );
     let results = response.results.unwrap();
@@ -147,6 +150,7 @@ fn set_monitoring_mode(
 }
 
 fn set_triggering(
+    server_state: Arc<RwLock<ServerState>>,
     session: Arc<RwLock<Session>>,
     subscription_id: u32,
     monitored_item_id: u32,
@@ -169,8 +173,10 @@ fn set_triggering(
             Some(links_to_remove.to_vec())
         },
     };
-    let response: SetTriggeringResponse =
-        supported_message_as!(mis.set_triggering(session, &request), SetTriggeringResponse);
+    let response: SetTriggeringResponse = supported_message_as!(
+        mis.set_triggering(server_state, session, &request),
+        SetTriggeringResponse
+    );
     (response.add_results, response.remove_results)
 }
 
@@ -355,9 +361,13 @@ fn data_change_filter_test() {
     filter.trigger = DataChangeTrigger::StatusValueTimestamp;
     assert_eq!(filter.compare(&v1, &v2, None), true);
 
-    // Change timestamps to differ
+    // Server timestamps are not compared
     let now = DateTime::now();
     v1.server_timestamp = Some(now.clone());
+    assert!(filter.compare(&v1, &v2, None));
+
+    // Change source timestamps to differ
+    v1.source_timestamp = Some(now);
     assert_eq!(filter.compare(&v1, &v2, None), false);
 }
 
@@ -404,6 +414,295 @@ fn data_change_deadband_abs_test() {
     assert_eq!(filter.compare(&v1, &v2, None), false);
 }
 
+#[test]
+fn data_change_deadband_array_test() {
+    let filter = DataChangeFilter {
+        trigger: DataChangeTrigger::StatusValue,
+        deadband_type: DeadbandType::Absolute as u32,
+        deadband_value: 1f64,
+    };
+
+    let v1 = Variant::from(vec![10f64, 20f64]);
+
+    // Every element within the deadband
+    let v2 = Variant::from(vec![10.5f64, 19f64]);
+    assert_eq!(filter.compare_value(&v1, &v2, None), Ok(true));
+
+    // One element outside the deadband
+    let v2 = Variant::from(vec![10.5f64, 21.5f64]);
+    assert_eq!(filter.compare_value(&v1, &v2, None), Ok(false));
+
+    // Different lengths
+    let v2 = Variant::from(vec![10f64]);
+    assert_eq!(filter.compare_value(&v1, &v2, None), Ok(false));
+}
+
+fn make_create_request_deadband(
+    node_id: NodeId,
+    deadband_type: DeadbandType,
+    deadband_value: f64,
+) -> MonitoredItemCreateRequest {
+    let filter = ExtensionObject::from_encodable(
+        ObjectId::DataChangeFilter_Encoding_DefaultBinary,
+        &DataChangeFilter {
+            trigger: DataChangeTrigger::StatusValue,
+            deadband_type: deadband_type as u32,
+            deadband_value,
+        },
+    );
+    make_create_request(-1f64, 5, node_id, AttributeId::Value, filter)
+}
+
+#[test]
+fn monitored_item_deadband() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            // An analog item with an engineering unit range of 0 to 200
+            let analog_id = NodeId::new(1, "analog");
+            VariableBuilder::new(&analog_id, "analog", "analog")
+                .data_type(DataTypeId::Double)
+                .value(0f64)
+                .organized_by(ObjectId::ObjectsFolder)
+                .insert(&mut address_space);
+            VariableBuilder::new(&NodeId::new(1, "analog.EURange"), "EURange", "EURange")
+                .data_type(DataTypeId::Range)
+                .value(ExtensionObject::from_encodable(
+                    ObjectId::Range_Encoding_DefaultBinary,
+                    &Range {
+                        low: 0f64,
+                        high: 200f64,
+                    },
+                ))
+                .property_of(analog_id.clone())
+                .insert(&mut address_space);
+
+            let create = |request: &MonitoredItemCreateRequest| {
+                let mut monitored_item = MonitoredItem::new(
+                    &chrono::Utc::now(),
+                    1,
+                    TimestampsToReturn::Both,
+                    &server_state,
+                    request,
+                )
+                .unwrap();
+                monitored_item
+                    .validate_filter(&address_space)
+                    .map(|_| monitored_item)
+            };
+
+            // A percent deadband requires an EURange
+            assert_eq!(
+                create(&make_create_request_deadband(
+                    test_var_node_id(),
+                    DeadbandType::Percent,
+                    10f64
+                ))
+                .unwrap_err(),
+                StatusCode::BadMonitoredItemFilterUnsupported
+            );
+            // A deadband requires a numeric variable
+            assert_eq!(
+                create(&make_create_request_deadband(
+                    test_object_node_id(),
+                    DeadbandType::Absolute,
+                    10f64
+                ))
+                .unwrap_err(),
+                StatusCode::BadFilterNotAllowed
+            );
+            // The deadband may not be negative
+            assert_eq!(
+                create(&make_create_request_deadband(
+                    analog_id.clone(),
+                    DeadbandType::Absolute,
+                    -1f64
+                ))
+                .unwrap_err(),
+                StatusCode::BadDeadbandFilterInvalid
+            );
+            // A percent deadband may not exceed 100
+            assert_eq!(
+                create(&make_create_request_deadband(
+                    analog_id.clone(),
+                    DeadbandType::Percent,
+                    100.5f64
+                ))
+                .unwrap_err(),
+                StatusCode::BadDeadbandFilterInvalid
+            );
+
+            // 10% of the range is 20
+            let mut monitored_item = create(&make_create_request_deadband(
+                analog_id.clone(),
+                DeadbandType::Percent,
+                10f64,
+            ))
+            .unwrap();
+
+            let mut now = Utc::now();
+            let mut tick = |address_space: &mut AddressSpace, value: f64| {
+                now += chrono::Duration::milliseconds(100);
+                let ts = DateTime::from(now);
+                let _ = address_space.set_variable_value(analog_id.clone(), value, &ts, &ts);
+                let result = monitored_item.tick(&now, address_space, true, false);
+                let _ = monitored_item.all_notifications();
+                result
+            };
+
+            assert_eq!(
+                tick(&mut address_space, 0f64),
+                TickResult::ReportValueChanged
+            );
+            assert_eq!(tick(&mut address_space, 15f64), TickResult::NoChange);
+            assert_eq!(tick(&mut address_space, 20f64), TickResult::NoChange);
+            assert_eq!(
+                tick(&mut address_space, 25f64),
+                TickResult::ReportValueChanged
+            );
+            assert_eq!(tick(&mut address_space, 10f64), TickResult::NoChange);
+            assert_eq!(
+                tick(&mut address_space, 4f64),
+                TickResult::ReportValueChanged
+            );
+        },
+    )
+}
+
+fn make_create_request_aggregate_filter(
+    node_id: NodeId,
+    attribute_id: AttributeId,
+    aggregate_type: ObjectId,
+    start_time: DateTime,
+) -> MonitoredItemCreateRequest {
+    let filter = ExtensionObject::from_encodable(
+        ObjectId::AggregateFilter_Encoding_DefaultBinary,
+        &AggregateFilter {
+            start_time,
+            aggregate_type: aggregate_type.into(),
+            processing_interval: 1000f64,
+            aggregate_configuration: AggregateConfiguration {
+                use_server_capabilities_defaults: true,
+                treat_uncertain_as_bad: false,
+                percent_data_bad: 0,
+                percent_data_good: 0,
+                use_sloped_extrapolation: false,
+            },
+        },
+    );
+    make_create_request(0f64, 5, node_id, attribute_id, filter)
+}
+
+#[test]
+fn monitored_item_aggregate_filter() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            // The item is created one sampling interval before the aggregate starts, so its first
+            // sample is at the start of the first interval
+            let start = Utc::now();
+            let created = start - chrono::Duration::milliseconds(100);
+            let create = |request: &MonitoredItemCreateRequest| {
+                let mut monitored_item = MonitoredItem::new(
+                    &created,
+                    1,
+                    TimestampsToReturn::Both,
+                    &server_state,
+                    request,
+                )
+                .unwrap();
+                monitored_item
+                    .validate_filter(&address_space)
+                    .map(|filter_result| (monitored_item, filter_result))
+            };
+
+            // Aggregates only apply to values and must be supported
+            assert_eq!(
+                create(&make_create_request_aggregate_filter(
+                    test_var_node_id(),
+                    AttributeId::DisplayName,
+                    ObjectId::AggregateFunction_Average,
+                    start.into(),
+                ))
+                .unwrap_err(),
+                StatusCode::BadFilterNotAllowed
+            );
+            assert_eq!(
+                create(&make_create_request_aggregate_filter(
+                    test_var_node_id(),
+                    AttributeId::Value,
+                    ObjectId::AggregateFunction_Total,
+                    start.into(),
+                ))
+                .unwrap_err(),
+                StatusCode::BadAggregateNotSupported
+            );
+
+            // The filter result holds the server's defaults for the configuration
+            let (mut monitored_item, filter_result) =
+                create(&make_create_request_aggregate_filter(
+                    test_var_node_id(),
+                    AttributeId::Value,
+                    ObjectId::AggregateFunction_Average,
+                    start.into(),
+                ))
+                .unwrap();
+            let filter_result = filter_result
+                .decode_inner::<AggregateFilterResult>(&DecodingOptions::test())
+                .unwrap();
+            assert_eq!(filter_result.revised_start_time, DateTime::from(start));
+            assert_eq!(filter_result.revised_processing_interval, 1000f64);
+            assert_eq!(
+                filter_result.revised_aggregate_configuration,
+                default_aggregate_configuration()
+            );
+
+            // Sample 0 to 9 every 100ms, nothing is reported until the interval ends
+            (0..10).for_each(|i| {
+                let now = start + chrono::Duration::milliseconds(i * 100);
+                let ts = DateTime::from(now);
+                let _ = address_space.set_variable_value(test_var_node_id(), i as u32, &ts, &ts);
+                assert_eq!(
+                    monitored_item.tick(&now, &address_space, true, false),
+                    TickResult::NoChange
+                );
+            });
+            assert!(monitored_item.notification_queue_is_empty());
+
+            let now = start + chrono::Duration::milliseconds(1000);
+            let ts = DateTime::from(now);
+            let _ = address_space.set_variable_value(test_var_node_id(), 10u32, &ts, &ts);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
+            );
+            let notifications = monitored_item.all_notifications().unwrap();
+            assert_eq!(notifications.len(), 1);
+            let Notification::MonitoredItemNotification(ref notification) = notifications[0] else {
+                panic!()
+            };
+            assert_eq!(notification.value.value, Some(Variant::Double(4.5)));
+            assert_eq!(
+                notification.value.status,
+                Some(StatusCode::Good | StatusCode::HISTORICAL_CALCULATED)
+            );
+            assert_eq!(notification.value.source_timestamp, Some(start.into()));
+        },
+    )
+}
+
 // Straight tests of abs function
 #[test]
 fn deadband_abs() {
@@ -522,8 +821,7 @@ fn monitored_item_data_change_filter() {
 }
 
 #[test]
-fn monitored_item_event_filter() {
-    // create an address space
+fn monitored_item_status_change() {
     do_subscription_service_test(
         |server_state,
          _session,
@@ -533,152 +831,910 @@ fn monitored_item_event_filter() {
             let mut address_space = make_address_space();
             let server_state = trace_read_lock!(server_state);
 
-            let ns = address_space.register_namespace("urn:test").unwrap();
-
-            // Create request should monitor attribute of variable, e.g. value
-            // Sample interval is negative so it will always test on repeated calls
+            // No filter, so the default trigger of status and value applies
             let mut monitored_item = MonitoredItem::new(
                 &chrono::Utc::now(),
                 1,
                 TimestampsToReturn::Both,
                 &server_state,
-                &make_create_request_event_filter(-1f64, 5),
+                &make_create_request(
+                    -1f64,
+                    5,
+                    test_var_node_id(),
+                    AttributeId::Value,
+                    ExtensionObject::null(),
+                ),
             )
             .unwrap();
-
-            let mut now = Utc::now();
-
-            // Verify tick does nothing
+            let now = Utc::now();
             assert_eq!(
-                monitored_item.tick(&now, &address_space, false, false),
-                TickResult::NoChange
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
             );
 
-            now = now + chrono::Duration::milliseconds(100);
-
-            // Raise an event
-            let event_id = NodeId::new(ns, "Event1");
-            let event_type_id = ObjectTypeId::BaseEventType;
-            let mut event = BaseEventType::new(
-                &event_id,
-                event_type_id,
-                "Event1",
-                "",
-                NodeId::objects_folder_id(),
-                DateTime::from(now),
-            )
-            .source_node(test_object_node_id());
-            assert!(event.raise(&mut address_space).is_ok());
-
-            // Verify that event comes back
+            // The same value with a bad status is a change
+            let source_timestamp = DateTime::ymd(2020, 1, 1);
+            assert!(address_space.set_value_with(
+                test_var_node_id(),
+                0u32,
+                StatusCode::BadCommunicationError,
+                &source_timestamp
+            ));
             assert_eq!(
                 monitored_item.tick(&now, &address_space, true, false),
                 TickResult::ReportValueChanged
             );
-
-            // Look at monitored item queue
-            assert_eq!(monitored_item.notification_queue().len(), 1);
-            let event = match monitored_item.oldest_notification_message().unwrap() {
-                Notification::Event(event) => event,
-                _ => panic!(),
-            };
-
-            // Verify EventFieldList
-            assert_eq!(event.client_handle, 999);
-            let mut event_fields = event.event_fields.unwrap();
-            assert_eq!(event_fields.len(), 2);
-
-            // EventId should be a ByteString, contents of which should be 16 bytes
-            let event_id = event_fields.remove(0);
-            match event_id {
-                Variant::ByteString(value) => assert_eq!(value.value.unwrap().len(), 16),
-                _ => panic!(),
-            }
-
-            // Source node should point to the originating object
-            let event_source_node = event_fields.remove(0);
-            match event_source_node {
-                Variant::NodeId(source_node) => assert_eq!(*source_node, test_object_node_id()),
+            let _ = monitored_item.oldest_notification_message();
+            match monitored_item.oldest_notification_message().unwrap() {
+                Notification::MonitoredItemNotification(notification) => {
+                    let value = notification.value;
+                    assert_eq!(value.value, Some(Variant::UInt32(0)));
+                    assert_eq!(value.status, Some(StatusCode::BadCommunicationError));
+                    assert_eq!(value.source_timestamp, Some(source_timestamp));
+                    assert!(value.server_timestamp.is_some());
+                }
                 _ => panic!(),
             }
 
-            // Tick again (nothing expected)
-            now = now + chrono::Duration::milliseconds(100);
+            // Only a newer source timestamp is not a change
+            assert!(address_space.set_value_with(
+                test_var_node_id(),
+                0u32,
+                StatusCode::BadCommunicationError,
+                &DateTime::ymd(2020, 1, 2)
+            ));
             assert_eq!(
-                monitored_item.tick(&now, &address_space, false, false),
+                monitored_item.tick(&now, &address_space, true, false),
                 TickResult::NoChange
             );
 
-            // Raise an event on another object, expect nothing in the tick about it
-            let event_id = NodeId::new(ns, "Event2");
-            let event_type_id = ObjectTypeId::BaseEventType;
-            let mut event = BaseEventType::new(
-                &event_id,
-                event_type_id,
-                "Event2",
-                "",
-                NodeId::objects_folder_id(),
-                DateTime::from(now),
-            )
-            .source_node(ObjectId::Server);
-            assert!(event.raise(&mut address_space).is_ok());
-            now = now + chrono::Duration::milliseconds(100);
+            // The reading recovers
+            assert!(address_space.set_value_with(
+                test_var_node_id(),
+                0u32,
+                StatusCode::Good,
+                &DateTime::ymd(2020, 1, 3)
+            ));
             assert_eq!(
-                monitored_item.tick(&now, &address_space, false, false),
-                TickResult::NoChange
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
             );
+
+            // Not a variable
+            assert!(!address_space.set_value_with(
+                test_object_node_id(),
+                0u32,
+                StatusCode::Good,
+                &DateTime::now()
+            ));
         },
-    );
+    )
 }
 
-/// Test to ensure create monitored items returns an error for an unknown node id
 #[test]
-fn unknown_node_id() {
+fn monitored_item_index_range() {
     do_subscription_service_test(
         |server_state,
-         session,
-         address_space,
-         ss: SubscriptionService,
-         mis: MonitoredItemService| {
-            // Create subscription
-            let subscription_id = {
-                let request = create_subscription_request(0, 0);
-                let response: CreateSubscriptionResponse = supported_message_as!(
-                    ss.create_subscription(server_state.clone(), session.clone(), &request),
-                    CreateSubscriptionResponse
-                );
-                response.subscription_id
-            };
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
 
-            let request = create_monitored_items_request(
-                subscription_id,
-                vec![
-                    NodeId::new(1, var_name(1)),
-                    NodeId::new(99, "Doesn't exist"),
-                ],
+            let node_id = NodeId::new(1, 100);
+            VariableBuilder::new(&node_id, "array", "array")
+                .data_type(DataTypeId::UInt32)
+                .value_rank(1)
+                .value(vec![0u32; 4])
+                .organized_by(ObjectId::ObjectsFolder)
+                .insert(&mut address_space);
+
+            let mut request = make_create_request(
+                -1f64,
+                5,
+                node_id.clone(),
+                AttributeId::Value,
+                ExtensionObject::null(),
             );
 
-            let response: CreateMonitoredItemsResponse = supported_message_as!(
-                mis.create_monitored_items(
-                    server_state.clone(),
-                    session.clone(),
-                    address_space.clone(),
-                    &request
-                ),
-                CreateMonitoredItemsResponse
+            // An index range which cannot be parsed is rejected
+            request.item_to_monitor.index_range = UAString::from("1:x");
+            let result = MonitoredItem::new(
+                &chrono::Utc::now(),
+                1,
+                TimestampsToReturn::Both,
+                &server_state,
+                &request,
             );
-            let results = response.results.unwrap();
-            assert_eq!(results.len(), 2);
+            assert_eq!(result.unwrap_err(), StatusCode::BadIndexRangeInvalid);
+
+            // Monitor the middle two elements
+            request.item_to_monitor.index_range = UAString::from("1:2");
+            let mut monitored_item = MonitoredItem::new(
+                &chrono::Utc::now(),
+                1,
+                TimestampsToReturn::Both,
+                &server_state,
+                &request,
+            )
+            .unwrap();
+
+            let set_value = |address_space: &mut AddressSpace, index: u32, value: u32| {
+                if let NodeType::Variable(node) = address_space.find_node_mut(&node_id).unwrap() {
+                    node.set_value(NumericRange::Index(index), vec![value])
+                        .unwrap();
+                } else {
+                    panic!("Expected a variable, didn't get one!!");
+                }
+            };
+            let assert_notification = |monitored_item: &mut MonitoredItem, expected: Vec<u32>| {
+                let notification = monitored_item.oldest_notification_message().unwrap();
+                if let Notification::MonitoredItemNotification(notification) = notification {
+                    assert_eq!(notification.value.value.unwrap(), Variant::from(expected));
+                } else {
+                    panic!();
+                }
+            };
+
+            let now = Utc::now();
             assert_eq!(
-                results.get(0).as_ref().unwrap().status_code,
-                StatusCode::Good
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
             );
+            assert_notification(&mut monitored_item, vec![0, 0]);
+
+            // A change outside of the range is not reported
+            set_value(&mut address_space, 3, 7);
             assert_eq!(
-                results.get(1).as_ref().unwrap().status_code,
-                StatusCode::BadNodeIdUnknown
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::NoChange
             );
-        },
-    );
+
+            // A change inside of the range is reported with just the range
+            set_value(&mut address_space, 1, 5);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
+            );
+            assert_notification(&mut monitored_item, vec![5, 0]);
+        },
+    )
+}
+
+#[test]
+fn monitored_item_timestamps_to_return() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            // A getter only supplies the timestamps it is asked for, so the monitored item
+            // has to ask for both and strip the ones the subscriber doesn't want
+            let source_timestamp = DateTime::ymd(2020, 1, 1);
+            let server_timestamp = DateTime::ymd(2021, 1, 1);
+            let node_id = NodeId::new(1, 100);
+            VariableBuilder::new(&node_id, "getter", "getter")
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::ObjectsFolder)
+                .value_getter(AttrFnGetter::new_boxed(
+                    move |_,
+                          timestamps_to_return,
+                          _,
+                          _,
+                          _,
+                          _|
+                          -> Result<Option<DataValue>, StatusCode> {
+                        let mut value = DataValue::value_only(1i32);
+                        value.set_timestamps(
+                            timestamps_to_return,
+                            source_timestamp,
+                            server_timestamp,
+                        );
+                        value.source_picoseconds = Some(10);
+                        value.server_picoseconds = Some(20);
+                        Ok(Some(value))
+                    },
+                ))
+                .insert(&mut address_space);
+
+            let sample = |timestamps_to_return| {
+                let mut monitored_item = MonitoredItem::new(
+                    &chrono::Utc::now(),
+                    1,
+                    timestamps_to_return,
+                    &server_state,
+                    &make_create_request(
+                        -1f64,
+                        5,
+                        node_id.clone(),
+                        AttributeId::Value,
+                        ExtensionObject::null(),
+                    ),
+                )
+                .unwrap();
+                assert_eq!(
+                    monitored_item.tick(&Utc::now(), &address_space, true, false),
+                    TickResult::ReportValueChanged
+                );
+                match monitored_item.oldest_notification_message().unwrap() {
+                    Notification::MonitoredItemNotification(notification) => notification.value,
+                    _ => panic!(),
+                }
+            };
+
+            let value = sample(TimestampsToReturn::Both);
+            assert_eq!(value.source_timestamp, Some(source_timestamp));
+            assert_eq!(value.source_picoseconds, Some(10));
+            assert_eq!(value.server_timestamp, Some(server_timestamp));
+            assert_eq!(value.server_picoseconds, Some(20));
+
+            let value = sample(TimestampsToReturn::Source);
+            assert_eq!(value.source_timestamp, Some(source_timestamp));
+            assert!(value.server_timestamp.is_none() && value.server_picoseconds.is_none());
+
+            let value = sample(TimestampsToReturn::Server);
+            assert!(value.source_timestamp.is_none() && value.source_picoseconds.is_none());
+            assert_eq!(value.server_timestamp, Some(server_timestamp));
+
+            let value = sample(TimestampsToReturn::Neither);
+            assert_eq!(value.value, Some(Variant::Int32(1)));
+            assert!(value.source_timestamp.is_none() && value.source_picoseconds.is_none());
+            assert!(value.server_timestamp.is_none() && value.server_picoseconds.is_none());
+        },
+    )
+}
+
+#[test]
+fn monitored_item_monitoring_mode() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            let mut monitored_item = MonitoredItem::new(
+                &chrono::Utc::now(),
+                1,
+                TimestampsToReturn::Both,
+                &server_state,
+                &make_create_request_data_change_filter(-1f64, 5),
+            )
+            .unwrap();
+            monitored_item.set_monitoring_mode(MonitoringMode::Sampling);
+
+            let set_value = |address_space: &mut AddressSpace, value: u32| {
+                let now = DateTime::now();
+                let _ = address_space.set_variable_value(test_var_node_id(), value, &now, &now);
+            };
+
+            let now = Utc::now();
+
+            // A sampling item queues its samples without reporting them
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ValueChanged
+            );
+            assert_eq!(monitored_item.notification_queue().len(), 1);
+
+            // Queued samples alone are not a change while sampling
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::NoChange
+            );
+
+            set_value(&mut address_space, 1);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ValueChanged
+            );
+            assert_eq!(monitored_item.notification_queue().len(), 2);
+
+            // Once reporting, the queued samples are reported
+            monitored_item.set_monitoring_mode(MonitoringMode::Reporting);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
+            );
+            assert_eq!(monitored_item.all_notifications().unwrap().len(), 2);
+
+            // Disabling the item discards anything queued
+            set_value(&mut address_space, 2);
+            monitored_item.set_monitoring_mode(MonitoringMode::Sampling);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ValueChanged
+            );
+            monitored_item.set_monitoring_mode(MonitoringMode::Disabled);
+            assert!(monitored_item.notification_queue().is_empty());
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::NoChange
+            );
+
+            // And it reports its current value when enabled again
+            monitored_item.set_monitoring_mode(MonitoringMode::Reporting);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
+            );
+            assert_eq!(monitored_item.notification_queue().len(), 1);
+        },
+    )
+}
+
+#[test]
+fn monitored_item_event_filter() {
+    // create an address space
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            // The event's properties take ids from a shared counter, so they go in a namespace
+            // of their own to not clash with the test variables in namespace 1
+            let _ = address_space.register_namespace("urn:test").unwrap();
+            let ns = address_space.register_namespace("urn:test:events").unwrap();
+
+            // Create request should monitor attribute of variable, e.g. value
+            // Sample interval is negative so it will always test on repeated calls
+            let mut monitored_item = MonitoredItem::new(
+                &chrono::Utc::now(),
+                1,
+                TimestampsToReturn::Both,
+                &server_state,
+                &make_create_request_event_filter(-1f64, 5),
+            )
+            .unwrap();
+
+            let mut now = Utc::now();
+
+            // Verify tick does nothing
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, false, false),
+                TickResult::NoChange
+            );
+
+            now = now + chrono::Duration::milliseconds(100);
+
+            // Raise an event
+            let event_id = NodeId::new(ns, "Event1");
+            let event_type_id = ObjectTypeId::BaseEventType;
+            let mut event = BaseEventType::new(
+                &event_id,
+                event_type_id,
+                "Event1",
+                "",
+                NodeId::objects_folder_id(),
+                DateTime::from(now),
+            )
+            .source_node(test_object_node_id());
+            assert!(event.raise(&mut address_space).is_ok());
+
+            // Verify that event comes back
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, true, false),
+                TickResult::ReportValueChanged
+            );
+
+            // Look at monitored item queue
+            assert_eq!(monitored_item.notification_queue().len(), 1);
+            let event = match monitored_item.oldest_notification_message().unwrap() {
+                Notification::Event(event) => event,
+                _ => panic!(),
+            };
+
+            // Verify EventFieldList
+            assert_eq!(event.client_handle, 999);
+            let mut event_fields = event.event_fields.unwrap();
+            assert_eq!(event_fields.len(), 2);
+
+            // EventId should be a ByteString, contents of which should be 16 bytes
+            let event_id = event_fields.remove(0);
+            match event_id {
+                Variant::ByteString(value) => assert_eq!(value.value.unwrap().len(), 16),
+                _ => panic!(),
+            }
+
+            // Source node should point to the originating object
+            let event_source_node = event_fields.remove(0);
+            match event_source_node {
+                Variant::NodeId(source_node) => assert_eq!(*source_node, test_object_node_id()),
+                _ => panic!(),
+            }
+
+            // Tick again (nothing expected)
+            now = now + chrono::Duration::milliseconds(100);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, false, false),
+                TickResult::NoChange
+            );
+
+            // Raise an event on another object, expect nothing in the tick about it
+            let event_id = NodeId::new(ns, "Event2");
+            let event_type_id = ObjectTypeId::BaseEventType;
+            let mut event = BaseEventType::new(
+                &event_id,
+                event_type_id,
+                "Event2",
+                "",
+                NodeId::objects_folder_id(),
+                DateTime::from(now),
+            )
+            .source_node(ObjectId::Server);
+            assert!(event.raise(&mut address_space).is_ok());
+            now = now + chrono::Duration::milliseconds(100);
+            assert_eq!(
+                monitored_item.tick(&now, &address_space, false, false),
+                TickResult::NoChange
+            );
+        },
+    );
+}
+
+/// Test to ensure create monitored items returns an error for an unknown node id
+/// A call on more monitored items than the `MaxMonitoredItemsPerCall` operation limit fails,
+/// and a limit of 0 means there is no limit
+#[test]
+fn max_monitored_items_per_call() {
+    do_subscription_service_test(
+        |server_state,
+         session,
+         address_space,
+         ss: SubscriptionService,
+         mis: MonitoredItemService| {
+            let subscription_id = {
+                let request = create_subscription_request(0, 0);
+                let response: CreateSubscriptionResponse = supported_message_as!(
+                    ss.create_subscription(server_state.clone(), session.clone(), &request),
+                    CreateSubscriptionResponse
+                );
+                response.subscription_id
+            };
+            let request = create_monitored_items_request(
+                subscription_id,
+                (1..=3).map(|i| NodeId::new(1, var_name(i))).collect(),
+            );
+
+            trace_write_lock!(server_state)
+                .operational_limits
+                .max_monitored_items_per_call = 2;
+            let response: ServiceFault = supported_message_as!(
+                mis.create_monitored_items(
+                    server_state.clone(),
+                    session.clone(),
+                    address_space.clone(),
+                    &request
+                ),
+                ServiceFault
+            );
+            assert_eq!(
+                response.response_header.service_result,
+                StatusCode::BadTooManyOperations
+            );
+
+            trace_write_lock!(server_state)
+                .operational_limits
+                .max_monitored_items_per_call = 0;
+            let response: CreateMonitoredItemsResponse = supported_message_as!(
+                mis.create_monitored_items(
+                    server_state.clone(),
+                    session.clone(),
+                    address_space.clone(),
+                    &request
+                ),
+                CreateMonitoredItemsResponse
+            );
+            let monitored_item_ids = response
+                .results
+                .unwrap()
+                .iter()
+                .map(|r| r.monitored_item_id)
+                .collect::<Vec<_>>();
+            assert_eq!(monitored_item_ids.len(), 3);
+
+            trace_write_lock!(server_state)
+                .operational_limits
+                .max_monitored_items_per_call = 2;
+            let request = DeleteMonitoredItemsRequest {
+                request_header: RequestHeader::dummy(),
+                subscription_id,
+                monitored_item_ids: Some(monitored_item_ids),
+            };
+            let response: ServiceFault = supported_message_as!(
+                mis.delete_monitored_items(server_state.clone(), session.clone(), &request),
+                ServiceFault
+            );
+            assert_eq!(
+                response.response_header.service_result,
+                StatusCode::BadTooManyOperations
+            );
+        },
+    );
+}
+
+#[test]
+fn unknown_node_id() {
+    do_subscription_service_test(
+        |server_state,
+         session,
+         address_space,
+         ss: SubscriptionService,
+         mis: MonitoredItemService| {
+            // Create subscription
+            let subscription_id = {
+                let request = create_subscription_request(0, 0);
+                let response: CreateSubscriptionResponse = supported_message_as!(
+                    ss.create_subscription(server_state.clone(), session.clone(), &request),
+                    CreateSubscriptionResponse
+                );
+                response.subscription_id
+            };
+
+            let request = create_monitored_items_request(
+                subscription_id,
+                vec![
+                    NodeId::new(1, var_name(1)),
+                    NodeId::new(99, "Doesn't exist"),
+                ],
+            );
+
+            let response: CreateMonitoredItemsResponse = supported_message_as!(
+                mis.create_monitored_items(
+                    server_state.clone(),
+                    session.clone(),
+                    address_space.clone(),
+                    &request
+                ),
+                CreateMonitoredItemsResponse
+            );
+            let results = response.results.unwrap();
+            assert_eq!(results.len(), 2);
+            assert_eq!(
+                results.get(0).as_ref().unwrap().status_code,
+                StatusCode::Good
+            );
+            assert_eq!(
+                results.get(1).as_ref().unwrap().status_code,
+                StatusCode::BadNodeIdUnknown
+            );
+        },
+    );
+}
+
+#[test]
+fn monitored_item_revised_sampling_interval() {
+    do_subscription_service_test(
+        |server_state,
+         session,
+         address_space,
+         ss: SubscriptionService,
+         mis: MonitoredItemService| {
+            let (subscription_id, revised_publishing_interval) = {
+                let request = create_subscription_request(0, 0);
+                let response: CreateSubscriptionResponse = supported_message_as!(
+                    ss.create_subscription(server_state.clone(), session.clone(), &request),
+                    CreateSubscriptionResponse
+                );
+                (
+                    response.subscription_id,
+                    response.revised_publishing_interval,
+                )
+            };
+
+            let mut request = create_monitored_items_request(
+                subscription_id,
+                vec![
+                    NodeId::new(1, var_name(1)),
+                    NodeId::new(1, var_name(2)),
+                    NodeId::new(1, var_name(3)),
+                ],
+            );
+            let items_to_create = request.items_to_create.as_mut().unwrap();
+            items_to_create[0].requested_parameters.sampling_interval = -1f64;
+            items_to_create[1].requested_parameters.sampling_interval = 0f64;
+            items_to_create[2].requested_parameters.sampling_interval = 2500f64;
+
+            let response: CreateMonitoredItemsResponse = supported_message_as!(
+                mis.create_monitored_items(
+                    server_state.clone(),
+                    session.clone(),
+                    address_space.clone(),
+                    &request
+                ),
+                CreateMonitoredItemsResponse
+            );
+            let min_sampling_interval_ms = trace_read_lock!(server_state).min_sampling_interval_ms;
+            let revised_sampling_intervals: Vec<f64> = response
+                .results
+                .unwrap()
+                .iter()
+                .map(|r| r.revised_sampling_interval)
+                .collect();
+            // The client is told the interval the item is actually sampled at
+            assert_eq!(
+                revised_sampling_intervals,
+                vec![
+                    revised_publishing_interval,
+                    min_sampling_interval_ms,
+                    2500f64
+                ]
+            );
+        },
+    );
+}
+
+#[test]
+fn monitored_item_minimum_sampling_interval() {
+    do_subscription_service_test(
+        |server_state,
+         session,
+         address_space,
+         ss: SubscriptionService,
+         mis: MonitoredItemService| {
+            let (subscription_id, revised_publishing_interval) = {
+                let request = create_subscription_request(0, 0);
+                let response: CreateSubscriptionResponse = supported_message_as!(
+                    ss.create_subscription(server_state.clone(), session.clone(), &request),
+                    CreateSubscriptionResponse
+                );
+                (
+                    response.subscription_id,
+                    response.revised_publishing_interval,
+                )
+            };
+            let minimum_sampling_interval = revised_publishing_interval + 1000f64;
+            {
+                let mut address_space = trace_write_lock!(address_space);
+                (1..=3).for_each(|i| {
+                    let variable = address_space
+                        .find_variable_mut(NodeId::new(1, var_name(i)))
+                        .unwrap();
+                    variable.set_minimum_sampling_interval(minimum_sampling_interval);
+                });
+                // A variable that can't be read can't be monitored
+                address_space
+                    .find_variable_mut(NodeId::new(1, var_name(4)))
+                    .unwrap()
+                    .set_access_level(AccessLevel::empty());
+            }
+
+            let mut request = create_monitored_items_request(
+                subscription_id,
+                vec![
+                    NodeId::new(1, var_name(1)),
+                    NodeId::new(1, var_name(2)),
+                    NodeId::new(1, var_name(3)),
+                    NodeId::new(1, var_name(4)),
+                ],
+            );
+            let items_to_create = request.items_to_create.as_mut().unwrap();
+            items_to_create[0].requested_parameters.sampling_interval = -1f64;
+            items_to_create[1].requested_parameters.sampling_interval = 100f64;
+            items_to_create[2].requested_parameters.sampling_interval =
+                minimum_sampling_interval + 500f64;
+
+            let response: CreateMonitoredItemsResponse = supported_message_as!(
+                mis.create_monitored_items(
+                    server_state.clone(),
+                    session.clone(),
+                    address_space.clone(),
+                    &request
+                ),
+                CreateMonitoredItemsResponse
+            );
+            let results = response.results.unwrap();
+            // Items are not sampled faster than the variable allows
+            assert_eq!(
+                results[..3]
+                    .iter()
+                    .map(|r| r.revised_sampling_interval)
+                    .collect::<Vec<_>>(),
+                vec![
+                    minimum_sampling_interval,
+                    minimum_sampling_interval,
+                    minimum_sampling_interval + 500f64
+                ]
+            );
+            assert_eq!(results[3].status_code, StatusCode::BadNotReadable);
+
+            // The minimum applies to a modified item too
+            let request = ModifyMonitoredItemsRequest {
+                request_header: RequestHeader::dummy(),
+                subscription_id,
+                timestamps_to_return: TimestampsToReturn::Both,
+                items_to_modify: Some(vec![MonitoredItemModifyRequest {
+                    monitored_item_id: results[2].monitored_item_id,
+                    requested_parameters: MonitoringParameters {
+                        client_handle: 1,
+                        sampling_interval: 10f64,
+                        filter: ExtensionObject::null(),
+                        queue_size: 1,
+                        discard_oldest: true,
+                    },
+                }]),
+            };
+            let response: ModifyMonitoredItemsResponse = supported_message_as!(
+                mis.modify_monitored_items(server_state, session, address_space, &request),
+                ModifyMonitoredItemsResponse
+            );
+            assert_eq!(
+                response.results.unwrap()[0].revised_sampling_interval,
+                minimum_sampling_interval
+            );
+        },
+    );
+}
+
+#[test]
+fn monitored_item_reports_samples_between_publishing_intervals() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let mut address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            let start = Utc::now();
+            let after = |millis: i64| start.add(chrono::Duration::milliseconds(millis));
+            let mut subscription = Subscription::new(
+                Arc::new(RwLock::new(ServerDiagnostics::default())),
+                1,
+                true,
+                100f64,
+                300,
+                100,
+                0,
+            );
+            subscription.set_state(SubscriptionState::Normal);
+
+            // The item is sampled at 250ms, 500ms, 750ms... from the start
+            let results = subscription.create_monitored_items(
+                &server_state,
+                &address_space,
+                &start,
+                TimestampsToReturn::Both,
+                &[make_create_request(
+                    250f64,
+                    5,
+                    test_var_node_id(),
+                    AttributeId::Value,
+                    ExtensionObject::null(),
+                )],
+            );
+            assert!(results[0].status_code.is_good());
+
+            // The first sample is reported when the publishing interval elapses
+            subscription.tick(
+                &after(260),
+                &address_space,
+                TickReason::TickTimerFired,
+                true,
+            );
+            let notification = subscription.take_notification().unwrap();
+            assert!(notification.notification_data.is_some());
+
+            if let &mut NodeType::Variable(ref mut node) =
+                address_space.find_node_mut(&test_var_node_id()).unwrap()
+            {
+                node.set_value(NumericRange::None, Variant::UInt32(1))
+                    .unwrap();
+            } else {
+                panic!("Expected a variable, didn't get one!!");
+            }
+
+            // The publishing interval elapses before the change is sampled, so nothing is reported
+            subscription.tick(
+                &after(450),
+                &address_space,
+                TickReason::TickTimerFired,
+                true,
+            );
+            assert!(subscription.take_notification().is_none());
+
+            // The change is sampled between publishing intervals
+            subscription.tick(
+                &after(500),
+                &address_space,
+                TickReason::TickTimerFired,
+                true,
+            );
+            assert!(subscription.take_notification().is_none());
+
+            // and reported when the publishing interval next elapses, although the item is not
+            // due to be sampled again yet
+            subscription.tick(
+                &after(560),
+                &address_space,
+                TickReason::TickTimerFired,
+                true,
+            );
+            let notification = subscription.take_notification().unwrap();
+            assert!(notification.notification_data.is_some());
+        },
+    );
+}
+
+#[test]
+fn monitored_item_reported_while_late() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+
+            let start = Utc::now();
+            let after = |millis: i64| start.add(chrono::Duration::milliseconds(millis));
+            let mut subscription = Subscription::new(
+                Arc::new(RwLock::new(ServerDiagnostics::default())),
+                1,
+                true,
+                100f64,
+                300,
+                100,
+                0,
+            );
+            subscription.set_state(SubscriptionState::Normal);
+
+            let results = subscription.create_monitored_items(
+                &server_state,
+                &address_space,
+                &start,
+                TimestampsToReturn::Both,
+                &[make_create_request(
+                    100f64,
+                    5,
+                    test_var_node_id(),
+                    AttributeId::Value,
+                    ExtensionObject::null(),
+                )],
+            );
+            assert!(results[0].status_code.is_good());
+
+            // The first sample is taken when no publish request is waiting, so the subscription
+            // is late and keeps the notification for the next publish request
+            subscription.tick(
+                &after(110),
+                &address_space,
+                TickReason::TickTimerFired,
+                false,
+            );
+            assert_eq!(subscription.state(), SubscriptionState::Late);
+            let notification = subscription.take_notification().unwrap();
+            assert!(notification.notification_data.is_some());
+
+            // Values that are resent while the subscription is still late are kept as well
+            subscription.set_resend_data();
+            subscription.tick(
+                &after(210),
+                &address_space,
+                TickReason::TickTimerFired,
+                false,
+            );
+            assert_eq!(subscription.state(), SubscriptionState::Late);
+            let resent = subscription.take_notification().unwrap();
+            assert!(resent.notification_data.is_some());
+            assert_eq!(resent.sequence_number, notification.sequence_number + 1);
+            assert!(subscription.take_notification().is_none());
+        },
+    );
 }
 
 #[test]
@@ -748,6 +1804,7 @@ fn monitored_item_triggers() {
 
             // set 3 monitored items to be reporting, sampling, disabled respectively
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[0],
@@ -755,6 +1812,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[1],
@@ -762,6 +1820,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[2],
@@ -771,6 +1830,7 @@ fn monitored_item_triggers() {
 
             // set 1 monitored item to trigger other 3 plus itself
             let (add_results, remove_results) = set_triggering(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 monitored_item_ids[0],
@@ -834,6 +1894,7 @@ fn monitored_item_triggers() {
 
             // set monitoring mode of all 3 to reporting.
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[0],
@@ -841,6 +1902,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[1],
@@ -848,6 +1910,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[2],
@@ -894,6 +1957,7 @@ fn monitored_item_triggers() {
 
             // revert to 3 items to be reporting, sampling, disabled
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[0],
@@ -901,6 +1965,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[1],
@@ -908,6 +1973,7 @@ fn monitored_item_triggers() {
                 &mis,
             );
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggered_item_ids[2],
@@ -917,6 +1983,7 @@ fn monitored_item_triggers() {
 
             // change monitoring mode of triggering item to sampling and change value
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggering_item_id,
@@ -960,6 +2027,7 @@ fn monitored_item_triggers() {
 
             // change monitoring mode of triggering item to disable
             set_monitoring_mode(
+                server_state.clone(),
                 session.clone(),
                 subscription_id,
                 triggering_item_id,
@@ -988,6 +2056,151 @@ fn monitored_item_triggers() {
     );
 }
 
+#[test]
+fn monitored_item_queue_overflow_bit() {
+    // The overflow bit is set on the value next to the values that were discarded
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let server_state = trace_read_lock!(server_state);
+            let overflows = |monitored_item: &mut MonitoredItem| {
+                monitored_item
+                    .all_notifications()
+                    .unwrap()
+                    .into_iter()
+                    .map(|notification| match notification {
+                        Notification::MonitoredItemNotification(notification) => {
+                            notification.value.status().overflow()
+                        }
+                        _ => panic!(),
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // The oldest value left after discarding the oldest
+            let mut monitored_item = populate_monitored_item(&server_state, true);
+            assert_eq!(monitored_item.queue_overflow_count(), 1);
+            assert_eq!(
+                overflows(&mut monitored_item),
+                vec![true, false, false, false, false]
+            );
+
+            // The new value that replaced the newest
+            let mut monitored_item = populate_monitored_item(&server_state, false);
+            assert_eq!(monitored_item.queue_overflow_count(), 1);
+            assert_eq!(
+                overflows(&mut monitored_item),
+                vec![false, false, false, false, true]
+            );
+        },
+    );
+}
+
+#[test]
+fn monitored_item_revised_queue_size() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let server_state = trace_read_lock!(server_state);
+            let max_queue_size = server_state.max_monitored_item_queue_size;
+            let revised = |request: MonitoredItemCreateRequest| {
+                MonitoredItem::new(
+                    &chrono::Utc::now(),
+                    1,
+                    TimestampsToReturn::Both,
+                    &server_state,
+                    &request,
+                )
+                .unwrap()
+                .queue_size()
+            };
+
+            // A data item queues at least one value and no more than the server's limit
+            assert_eq!(revised(make_create_request_data_change_filter(-1f64, 0)), 1);
+            assert_eq!(revised(make_create_request_data_change_filter(-1f64, 1)), 1);
+            assert_eq!(revised(make_create_request_data_change_filter(-1f64, 3)), 3);
+            assert_eq!(
+                revised(make_create_request_data_change_filter(-1f64, u32::MAX)),
+                max_queue_size
+            );
+
+            // An event item asking for 0 gets the default for events, which is the limit
+            assert_eq!(
+                revised(make_create_request_event_filter(-1f64, 0)),
+                max_queue_size
+            );
+            assert_eq!(revised(make_create_request_event_filter(-1f64, 3)), 3);
+            assert_eq!(
+                revised(make_create_request_event_filter(-1f64, u32::MAX)),
+                max_queue_size
+            );
+        },
+    );
+}
+
+#[test]
+fn monitored_item_modify_shrinks_queue() {
+    do_subscription_service_test(
+        |server_state,
+         _session,
+         _address_space,
+         _ss: SubscriptionService,
+         _mis: MonitoredItemService| {
+            let address_space = make_address_space();
+            let server_state = trace_read_lock!(server_state);
+            let shrink = |discard_oldest: bool| {
+                let mut monitored_item = populate_monitored_item(&server_state, true);
+                let _ = monitored_item.modify(
+                    &server_state,
+                    &address_space,
+                    TimestampsToReturn::Both,
+                    &MonitoredItemModifyRequest {
+                        monitored_item_id: 1,
+                        requested_parameters: MonitoringParameters {
+                            client_handle: 999,
+                            sampling_interval: -1f64,
+                            filter: ExtensionObject::null(),
+                            queue_size: 2,
+                            discard_oldest,
+                        },
+                    },
+                );
+                assert_eq!(monitored_item.queue_size(), 2);
+                assert_eq!(monitored_item.queue_overflow_count(), 2);
+                monitored_item
+                    .all_notifications()
+                    .unwrap()
+                    .into_iter()
+                    .map(|notification| match notification {
+                        Notification::MonitoredItemNotification(notification) => (
+                            notification.value.value.clone().unwrap(),
+                            notification.value.status().overflow(),
+                        ),
+                        _ => panic!(),
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // The queue holds 1 (with the overflow bit), 2, 3, 4, 10 after 0 was discarded to
+            // queue 10. Shrinking it keeps the newest or the oldest values.
+            assert_eq!(
+                shrink(true),
+                vec![(Variant::Int32(4), true), (Variant::Int32(10), false)]
+            );
+            assert_eq!(
+                shrink(false),
+                vec![(Variant::Int32(1), true), (Variant::Int32(2), true)]
+            );
+        },
+    );
+}
+
 #[test]
 fn monitored_item_queue_discard_oldest() {
     // The purpose of this test is to monitor the discard oldest behaviour. Depending on true/false
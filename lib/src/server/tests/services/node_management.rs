@@ -394,6 +394,56 @@ fn add_nodes_type_definition_invalid() {
     );
 }
 
+#[test]
+fn add_nodes_namespace_uri() {
+    // The parent and type definition are named by namespace uri
+    do_node_management_service_test(true, |server_state, session, address_space, nms| {
+        let parent_node_id = {
+            let mut address_space = trace_write_lock!(address_space);
+            let ns = address_space.register_namespace("urn:test").unwrap();
+            let parent_node_id = NodeId::new(ns, "Parent");
+            ObjectBuilder::new(&parent_node_id, "Parent", "Parent")
+                .organized_by(ObjectId::ObjectsFolder)
+                .insert(&mut address_space);
+            parent_node_id
+        };
+        let mut parent = ExpandedNodeId::new(NodeId::new(0, parent_node_id.identifier.clone()));
+        parent.namespace_uri = "urn:test".into();
+        let mut requested_new_node_id = ExpandedNodeId::new(NodeId::new(0, "Child"));
+        requested_new_node_id.namespace_uri = "urn:test".into();
+        let response = nms.add_nodes(
+            server_state,
+            session,
+            address_space.clone(),
+            &AddNodesRequest {
+                request_header: RequestHeader::dummy(),
+                nodes_to_add: Some(vec![AddNodesItem {
+                    parent_node_id: parent,
+                    reference_type_id: ReferenceTypeId::Organizes.into(),
+                    requested_new_node_id,
+                    browse_name: QualifiedName::from("Child"),
+                    node_class: NodeClass::Object,
+                    node_attributes: object_attributes("Child"),
+                    type_definition: ObjectTypeId::BaseObjectType.into(),
+                }]),
+            },
+        );
+        let results = supported_message_as!(response, AddNodesResponse)
+            .results
+            .unwrap();
+        assert_eq!(results[0].status_code, StatusCode::Good);
+        let child = NodeId::new(parent_node_id.namespace, "Child");
+        assert_eq!(results[0].added_node_id, child);
+        let address_space = trace_read_lock!(address_space);
+        assert!(address_space.find_node(&child).is_some());
+        assert!(address_space.has_reference(
+            &child,
+            &ObjectTypeId::BaseObjectType.into(),
+            ReferenceTypeId::HasTypeDefinition
+        ));
+    });
+}
+
 #[test]
 fn add_nodes_node_id_exists() {
     // Add a node where node id already exists
@@ -466,6 +516,78 @@ fn add_nodes_valid() {
     );
 }
 
+#[test]
+fn add_nodes_session_lifetime() {
+    // Nodes added by a session with session lifetime nodes are deleted when it closes
+    crate::console_logging::init();
+
+    let st = ServiceTest::new();
+    let nms = NodeManagementService::new();
+
+    let add_node = |session_lifetime_nodes: bool, browse_name: &str| {
+        {
+            let mut session = trace_write_lock!(st.session);
+            session.set_can_modify_address_space(true);
+            session.set_session_lifetime_nodes(session_lifetime_nodes);
+        }
+        let response = nms.add_nodes(
+            st.server_state.clone(),
+            st.session.clone(),
+            st.address_space.clone(),
+            &AddNodesRequest {
+                request_header: RequestHeader::dummy(),
+                nodes_to_add: Some(vec![AddNodesItem {
+                    parent_node_id: ObjectId::ObjectsFolder.into(),
+                    reference_type_id: ReferenceTypeId::Organizes.into(),
+                    requested_new_node_id: ExpandedNodeId::null(),
+                    browse_name: QualifiedName::from(browse_name),
+                    node_class: NodeClass::Object,
+                    node_attributes: object_attributes(browse_name),
+                    type_definition: ObjectTypeId::BaseObjectType.into(),
+                }]),
+            },
+        );
+        let response: AddNodesResponse = supported_message_as!(response, AddNodesResponse);
+        let result = &response.results.unwrap()[0];
+        assert_eq!(result.status_code, StatusCode::Good);
+        result.added_node_id.clone()
+    };
+
+    let session_node_id = add_node(true, "session");
+    let persistent_node_id = add_node(false, "persistent");
+
+    let session_id = {
+        let session = trace_read_lock!(st.session);
+        session.session_id().clone()
+    };
+    {
+        let address_space = trace_read_lock!(st.address_space);
+        assert_eq!(
+            address_space.find_session_nodes(&session_id),
+            vec![session_node_id.clone()]
+        );
+    }
+
+    // Closing the session deletes its nodes and the references to them
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.clear(st.address_space.clone());
+    }
+
+    let address_space = trace_read_lock!(st.address_space);
+    assert!(address_space.find_node(&session_node_id).is_none());
+    assert!(address_space
+        .find_references(
+            &ObjectId::ObjectsFolder.into(),
+            Some((ReferenceTypeId::Organizes, false)),
+        )
+        .unwrap()
+        .iter()
+        .all(|r| r.target_node != session_node_id));
+    assert!(address_space.find_session_nodes(&session_id).is_empty());
+    assert!(address_space.find_node(&persistent_node_id).is_some());
+}
+
 #[test]
 fn add_nodes_user_access_denied() {
     // Add a node without permission
@@ -606,6 +728,96 @@ fn add_references_reference_local_only() {
     );
 }
 
+#[test]
+fn add_and_delete_remote_references() {
+    do_node_management_service_test(true, |server_state, session, address_space, nms| {
+        let ns_uri = {
+            let mut address_space = trace_write_lock!(address_space);
+            address_space.register_server("urn:remote").unwrap();
+            address_space.namespaces()[1].clone()
+        };
+        let add_reference = |target_server_uri: UAString, target_node_id: ExpandedNodeId| {
+            let response = nms.add_references(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &AddReferencesRequest {
+                    request_header: RequestHeader::dummy(),
+                    references_to_add: Some(vec![AddReferencesItem {
+                        source_node_id: ObjectId::ObjectsFolder.into(),
+                        reference_type_id: ReferenceTypeId::Organizes.into(),
+                        is_forward: true,
+                        target_server_uri,
+                        target_node_id,
+                        target_node_class: NodeClass::Variable,
+                    }]),
+                },
+            );
+            supported_message_as!(response, AddReferencesResponse)
+                .results
+                .unwrap()[0]
+        };
+
+        // A local target named by its namespace uri
+        let mut local = ExpandedNodeId::new(NodeId::new(0, var_name(1)));
+        local.namespace_uri = ns_uri.as_str().into();
+        assert_eq!(add_reference(UAString::null(), local), StatusCode::Good);
+        assert!(trace_read_lock!(address_space).has_reference(
+            &ObjectId::ObjectsFolder.into(),
+            &var_node_id(1),
+            ReferenceTypeId::Organizes
+        ));
+
+        // A target on a registered server named by its uri
+        let mut remote = ExpandedNodeId::new(NodeId::new(0, "Remote"));
+        remote.namespace_uri = "urn:remote:ns".into();
+        assert_eq!(
+            add_reference("urn:remote".into(), remote.clone()),
+            StatusCode::Good
+        );
+        assert_eq!(
+            add_reference("urn:remote".into(), remote.clone()),
+            StatusCode::BadDuplicateReferenceNotAllowed
+        );
+        assert_eq!(
+            add_reference("urn:unknown".into(), remote.clone()),
+            StatusCode::BadServerUriInvalid
+        );
+        remote.server_index = 1;
+        let references = trace_read_lock!(address_space).find_remote_references(
+            &ObjectId::ObjectsFolder.into(),
+            BrowseDirection::Forward,
+            None::<(NodeId, bool)>,
+        );
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].target_node, remote);
+
+        // Delete the remote reference
+        let delete_reference = || {
+            let response = nms.delete_references(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &DeleteReferencesRequest {
+                    request_header: RequestHeader::dummy(),
+                    references_to_delete: Some(vec![DeleteReferencesItem {
+                        source_node_id: ObjectId::ObjectsFolder.into(),
+                        reference_type_id: ReferenceTypeId::Organizes.into(),
+                        is_forward: true,
+                        target_node_id: remote.clone(),
+                        delete_bidirectional: false,
+                    }]),
+                },
+            );
+            supported_message_as!(response, DeleteReferencesResponse)
+                .results
+                .unwrap()[0]
+        };
+        assert_eq!(delete_reference(), StatusCode::Good);
+        assert_eq!(delete_reference(), StatusCode::BadNotFound);
+    });
+}
+
 #[test]
 fn add_references_duplicate_reference_not_allowed() {
     // Add a reference that is a duplicate
@@ -0,0 +1,503 @@
+use crate::server::services::query::QueryService;
+use crate::supported_message_as;
+use crate::sync::*;
+use crate::types::{
+    node_ids::{DataTypeId, ObjectTypeId},
+    operand::{ContentFilterBuilder, Operand},
+};
+
+use super::*;
+
+// Query service tests
+
+fn pump_type_id() -> NodeId {
+    NodeId::new(1, "PumpType")
+}
+
+fn big_pump_type_id() -> NodeId {
+    NodeId::new(1, "BigPumpType")
+}
+
+fn pump_id(idx: usize) -> NodeId {
+    NodeId::new(1, format!("Pump{}", idx))
+}
+
+fn speed() -> QualifiedName {
+    QualifiedName::new(1, "Speed")
+}
+
+/// Adds ten pumps with speeds 0, 10, .. 90, a big pump with a speed of 500 whose type is a
+/// subtype of the pump type and a folder that isn't a pump
+fn add_pumps_to_address_space(address_space: Arc<RwLock<AddressSpace>>) {
+    let mut address_space = trace_write_lock!(address_space);
+    ObjectTypeBuilder::new(&pump_type_id(), "PumpType", "PumpType")
+        .subtype_of(ObjectTypeId::BaseObjectType)
+        .insert(&mut address_space);
+    ObjectTypeBuilder::new(&big_pump_type_id(), "BigPumpType", "BigPumpType")
+        .subtype_of(pump_type_id())
+        .insert(&mut address_space);
+
+    let add_pump = |address_space: &mut AddressSpace, node_id: NodeId, type_id, value: i32| {
+        ObjectBuilder::new(&node_id, node_id.to_string(), node_id.to_string())
+            .organized_by(ObjectId::ObjectsFolder)
+            .has_type_definition(type_id)
+            .insert(address_space);
+        VariableBuilder::new(&NodeId::next_numeric(1), speed(), "Speed")
+            .data_type(DataTypeId::Int32)
+            .value(value)
+            .component_of(node_id)
+            .insert(address_space);
+    };
+    (0..10).for_each(|i| {
+        add_pump(
+            &mut address_space,
+            pump_id(i),
+            pump_type_id(),
+            i as i32 * 10,
+        )
+    });
+    add_pump(
+        &mut address_space,
+        NodeId::new(1, "BigPump"),
+        big_pump_type_id(),
+        500,
+    );
+    address_space
+        .add_folder("Pumps", "Pumps", &NodeId::objects_folder_id())
+        .unwrap();
+}
+
+fn do_query_service_test<F>(f: F)
+where
+    F: FnOnce(
+        Arc<RwLock<ServerState>>,
+        Arc<RwLock<Session>>,
+        Arc<RwLock<AddressSpace>>,
+        &QueryService,
+    ),
+{
+    crate::console_logging::init();
+    let st = ServiceTest::new();
+    add_pumps_to_address_space(st.address_space.clone());
+    f(
+        st.server_state.clone(),
+        st.session.clone(),
+        st.address_space.clone(),
+        &QueryService::new(),
+    );
+}
+
+fn pump_node_types(include_sub_types: bool) -> Vec<NodeTypeDescription> {
+    vec![NodeTypeDescription::new(pump_type_id(), include_sub_types)
+        .data_to_return(QueryDataDescription::value(&[speed()]))]
+}
+
+fn do_query_first(
+    qs: &QueryService,
+    server_state: Arc<RwLock<ServerState>>,
+    session: Arc<RwLock<Session>>,
+    address_space: Arc<RwLock<AddressSpace>>,
+    node_types: Vec<NodeTypeDescription>,
+    filter: ContentFilter,
+    max_data_sets_to_return: u32,
+) -> SupportedMessage {
+    let request = QueryFirstRequest {
+        request_header: make_request_header(),
+        view: ViewDescription {
+            view_id: NodeId::null(),
+            timestamp: DateTime::null(),
+            view_version: 0,
+        },
+        node_types: Some(node_types),
+        filter,
+        max_data_sets_to_return,
+        max_references_to_return: 0,
+    };
+    qs.query_first(server_state, session, address_space, &request)
+}
+
+fn do_query_next(
+    qs: &QueryService,
+    server_state: Arc<RwLock<ServerState>>,
+    session: Arc<RwLock<Session>>,
+    address_space: Arc<RwLock<AddressSpace>>,
+    continuation_point: &ByteString,
+    release_continuation_point: bool,
+) -> SupportedMessage {
+    let request = QueryNextRequest {
+        request_header: make_request_header(),
+        release_continuation_point,
+        continuation_point: continuation_point.clone(),
+    };
+    qs.query_next(server_state, session, address_space, &request)
+}
+
+/// Returns the speeds of the data sets, sorted
+fn speeds(query_data_sets: &[QueryDataSet]) -> Vec<i32> {
+    let mut speeds = query_data_sets
+        .iter()
+        .map(|data_set| match data_set.values.as_ref().unwrap()[0] {
+            Variant::Int32(speed) => speed,
+            ref value => panic!("Unexpected speed {:?}", value),
+        })
+        .collect::<Vec<i32>>();
+    speeds.sort();
+    speeds
+}
+
+#[test]
+fn query_first_by_type() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        // Only the pumps of the exact type
+        let response = do_query_first(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            pump_node_types(false),
+            ContentFilter { elements: None },
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        assert!(response.continuation_point.is_null());
+        let query_data_sets = response.query_data_sets.unwrap();
+        assert_eq!(
+            speeds(&query_data_sets),
+            (0..10).map(|i| i * 10).collect::<Vec<_>>()
+        );
+        assert!(query_data_sets
+            .iter()
+            .all(|data_set| data_set.type_definition_node == pump_type_id().into()));
+        assert!(query_data_sets
+            .iter()
+            .any(|data_set| data_set.node_id == pump_id(3).into()));
+
+        // The big pump is a subtype
+        let response = do_query_first(
+            qs,
+            server_state,
+            session,
+            address_space,
+            pump_node_types(true),
+            ContentFilter { elements: None },
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        let query_data_sets = response.query_data_sets.unwrap();
+        assert_eq!(query_data_sets.len(), 11);
+        assert_eq!(*speeds(&query_data_sets).last().unwrap(), 500);
+    });
+}
+
+#[test]
+fn query_first_filter() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        // Pumps faster than 50
+        let filter = ContentFilterBuilder::new()
+            .gt(
+                Operand::attribute(
+                    pump_type_id(),
+                    &[speed()][..],
+                    AttributeId::Value,
+                    UAString::null(),
+                ),
+                Operand::literal(50),
+            )
+            .build();
+        let response = do_query_first(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            pump_node_types(true),
+            filter,
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        assert_eq!(
+            speeds(&response.query_data_sets.unwrap()),
+            vec![60, 70, 80, 90, 500]
+        );
+
+        // Objects of the big pump type, found from all objects
+        let filter = ContentFilterBuilder::new()
+            .of_type(Operand::literal(Variant::from(big_pump_type_id())))
+            .build();
+        let response = do_query_first(
+            qs,
+            server_state,
+            session,
+            address_space,
+            vec![NodeTypeDescription::new(ObjectTypeId::BaseObjectType, true)],
+            filter,
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        let query_data_sets = response.query_data_sets.unwrap();
+        assert_eq!(query_data_sets.len(), 1);
+        assert_eq!(query_data_sets[0].node_id, NodeId::new(1, "BigPump").into());
+    });
+}
+
+#[test]
+fn query_first_role_permissions() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        let observer: NodeId = ObjectId::WellKnownRole_Observer.into();
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            // Pump 3 may only be browsed by an observer
+            address_space
+                .find_node_mut(&pump_id(3))
+                .unwrap()
+                .as_mut_node()
+                .set_role_permissions(vec![RolePermissionType {
+                    role_id: observer.clone(),
+                    permissions: PermissionType::Browse,
+                }]);
+            // The speed of pump 5 may be browsed but not read by anyone
+            let speed_id = address_space
+                .find_references(&pump_id(5), Some((ReferenceTypeId::HasComponent, false)))
+                .unwrap()[0]
+                .target_node
+                .clone();
+            address_space
+                .find_node_mut(&speed_id)
+                .unwrap()
+                .as_mut_node()
+                .set_role_permissions(vec![RolePermissionType {
+                    role_id: observer.clone(),
+                    permissions: PermissionType::Browse,
+                }]);
+        }
+        // Pumps faster than 40
+        let query = |filter: ContentFilter| {
+            let response = do_query_first(
+                qs,
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                pump_node_types(false),
+                filter,
+                0,
+            );
+            let response = supported_message_as!(response, QueryFirstResponse);
+            response.query_data_sets.unwrap()
+        };
+        let filter = ContentFilterBuilder::new()
+            .gt(
+                Operand::attribute(
+                    pump_type_id(),
+                    &[speed()][..],
+                    AttributeId::Value,
+                    UAString::null(),
+                ),
+                Operand::literal(40),
+            )
+            .build();
+
+        // Pump 3 cannot be browsed without a role that may browse it
+        let query_data_sets = query(ContentFilter { elements: None });
+        assert_eq!(query_data_sets.len(), 9);
+        assert!(query_data_sets
+            .iter()
+            .all(|data_set| data_set.node_id != pump_id(3).into()));
+
+        // An observer finds it, but not pump 5 whose speed the filter cannot read
+        trace_write_lock!(session).set_roles(vec![observer]);
+        let query_data_sets = query(ContentFilter { elements: None });
+        assert_eq!(query_data_sets.len(), 10);
+        assert_eq!(speeds(&query(filter)), vec![60, 70, 80, 90]);
+    });
+}
+
+#[test]
+fn query_first_invalid() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        // A node type that is not a type
+        let node_types = vec![
+            NodeTypeDescription::new(pump_type_id(), false),
+            NodeTypeDescription::new(pump_id(0), false),
+        ];
+        let response = do_query_first(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            node_types,
+            ContentFilter { elements: None },
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadInvalidArgument
+        );
+        let parsing_results = response.parsing_results.unwrap();
+        assert_eq!(parsing_results[0].status_code, StatusCode::Good);
+        assert_eq!(
+            parsing_results[1].status_code,
+            StatusCode::BadNotTypeDefinition
+        );
+
+        // A filter element that is missing an operand
+        let filter = ContentFilter {
+            elements: Some(vec![ContentFilterElement {
+                filter_operator: FilterOperator::Equals,
+                filter_operands: Some(vec![(&Operand::literal(1)).into()]),
+            }]),
+        };
+        let response = do_query_first(
+            qs,
+            server_state,
+            session,
+            address_space,
+            pump_node_types(false),
+            filter,
+            0,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadContentFilterInvalid
+        );
+        assert_eq!(
+            response.filter_result.element_results.unwrap()[0].status_code,
+            StatusCode::BadFilterOperandCountMismatch
+        );
+    });
+}
+
+#[test]
+fn query_next() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        let response = do_query_first(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            pump_node_types(false),
+            ContentFilter { elements: None },
+            4,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        let mut query_data_sets = response.query_data_sets.unwrap();
+        assert_eq!(query_data_sets.len(), 4);
+        let cp1 = response.continuation_point;
+        assert!(!cp1.is_null());
+
+        let response = do_query_next(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &cp1,
+            false,
+        );
+        let response = supported_message_as!(response, QueryNextResponse);
+        let cp2 = response.revised_continuation_point;
+        assert!(!cp2.is_null());
+        query_data_sets.extend(response.query_data_sets.unwrap());
+        assert_eq!(query_data_sets.len(), 8);
+
+        let response = do_query_next(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &cp2,
+            false,
+        );
+        let response = supported_message_as!(response, QueryNextResponse);
+        assert!(response.revised_continuation_point.is_null());
+        query_data_sets.extend(response.query_data_sets.unwrap());
+        assert_eq!(
+            speeds(&query_data_sets),
+            (0..10).map(|i| i * 10).collect::<Vec<_>>()
+        );
+
+        // A continuation point can only be used once
+        let response = do_query_next(qs, server_state, session, address_space, &cp1, false);
+        let response = supported_message_as!(response, ServiceFault);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadContinuationPointInvalid
+        );
+    });
+}
+
+#[test]
+fn query_next_release() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        let response = do_query_first(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            pump_node_types(false),
+            ContentFilter { elements: None },
+            4,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        let cp1 = response.continuation_point;
+
+        let response = do_query_next(
+            qs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &cp1,
+            true,
+        );
+        let response = supported_message_as!(response, QueryNextResponse);
+        assert!(response.query_data_sets.is_none());
+        assert!(response.revised_continuation_point.is_null());
+
+        let response = do_query_next(qs, server_state, session, address_space, &cp1, false);
+        let response = supported_message_as!(response, ServiceFault);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadContinuationPointInvalid
+        );
+    });
+}
+
+#[test]
+fn query_first_view() {
+    do_query_service_test(|server_state, session, address_space, qs| {
+        let view_id = trace_write_lock!(address_space)
+            .add_view("Pumps", "Pumps", &[pump_id(2), pump_id(5), pump_id(7)])
+            .unwrap();
+
+        let mut request = QueryFirstRequest {
+            request_header: make_request_header(),
+            view: ViewDescription {
+                view_id,
+                timestamp: DateTime::null(),
+                view_version: 0,
+            },
+            node_types: Some(pump_node_types(true)),
+            filter: ContentFilter { elements: None },
+            max_data_sets_to_return: 0,
+            max_references_to_return: 0,
+        };
+
+        // Only the pumps in the view are found
+        let response = qs.query_first(
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &request,
+        );
+        let response = supported_message_as!(response, QueryFirstResponse);
+        assert_eq!(speeds(&response.query_data_sets.unwrap()), vec![20, 50, 70]);
+
+        // A node that is not a view is rejected
+        request.view.view_id = pump_id(2);
+        let response = qs.query_first(server_state, session, address_space, &request);
+        let response = supported_message_as!(response, ServiceFault);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadViewIdUnknown
+        );
+    });
+}
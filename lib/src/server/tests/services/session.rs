@@ -1,8 +1,16 @@
-use crate::crypto::{random, user_identity::make_user_name_identity_token, SecurityPolicy};
-use crate::types::{ActivateSessionRequest, RequestHeader, SignatureData};
+use std::path::Path;
+
+use crate::core::comms::secure_channel::SecureChannel;
+use crate::crypto::{
+    random, user_identity::make_user_name_identity_token, CertificateStore, SecurityPolicy,
+};
+use crate::supported_message_as;
+use crate::types::{ActivateSessionRequest, CreateSessionRequest, RequestHeader, SignatureData};
 
 use crate::server::{
+    authenticator::{UserAuthenticator, UserIdentity, UserToken},
     builder::ServerBuilder,
+    config::ServerEndpoint,
     identity_token::{
         POLICY_ID_USER_PASS_NONE, POLICY_ID_USER_PASS_RSA_15, POLICY_ID_USER_PASS_RSA_OAEP,
     },
@@ -320,3 +328,186 @@ fn user_name_pass_token() {
         },
     );
 }
+
+/// Accepts a single user name and password on any endpoint
+struct SingleUserAuthenticator;
+
+impl UserAuthenticator for SingleUserAuthenticator {
+    fn user_token_types(&self, _endpoint: &ServerEndpoint) -> Vec<UserTokenType> {
+        vec![UserTokenType::UserName]
+    }
+
+    fn authenticate(
+        &self,
+        token: &UserToken,
+        _endpoint: &ServerEndpoint,
+    ) -> Result<UserIdentity, StatusCode> {
+        match *token {
+            UserToken::UserName {
+                user: "ldap",
+                password: "secret",
+            } => Ok(UserIdentity::new(
+                "ldap",
+                vec![ObjectId::WellKnownRole_Operator.into()],
+            )),
+            _ => Err(StatusCode::BadUserAccessDenied),
+        }
+    }
+}
+
+#[test]
+fn custom_user_authenticator() {
+    do_session_service_test(None, |server_state, _session_service| {
+        let mut server_state = server_state.write();
+        server_state.set_user_authenticator(Box::new(SingleUserAuthenticator));
+
+        const ENDPOINT_URL: &str = "opc.tcp://localhost:4855/";
+        let server_nonce = random::byte_string(20);
+        let request = dummy_activate_session_request();
+        let authenticate = |token: &ExtensionObject| {
+            server_state.authenticate_endpoint(
+                &request,
+                ENDPOINT_URL,
+                SecurityPolicy::None,
+                MessageSecurityMode::None,
+                token,
+                &server_nonce,
+            )
+        };
+
+        // The authenticator's user is authenticated with its roles and AuthenticatedUser
+        let token = make_unencrypted_user_name_identity_token("ldap", "secret");
+        let identity = authenticate(&token).unwrap();
+        assert_eq!(identity.user_token_id, "ldap");
+        let authenticated_user: NodeId = ObjectId::WellKnownRole_AuthenticatedUser.into();
+        let operator: NodeId = ObjectId::WellKnownRole_Operator.into();
+        assert_eq!(identity.roles, vec![authenticated_user, operator]);
+
+        // The users of the config are not
+        let token = make_unencrypted_user_name_identity_token("sample1", "sample1pwd");
+        assert_eq!(
+            authenticate(&token).unwrap_err(),
+            StatusCode::BadUserAccessDenied
+        );
+
+        // Nor are anonymous users, which the authenticator doesn't accept
+        let token = ExtensionObject::null();
+        assert_eq!(
+            authenticate(&token).unwrap_err(),
+            StatusCode::BadIdentityTokenRejected
+        );
+
+        // Endpoints only offer the user name token
+        let endpoints = server_state
+            .endpoints(&UAString::from(ENDPOINT_URL), &None, &None)
+            .unwrap();
+        assert!(endpoints.iter().all(|e| {
+            let tokens = e.user_identity_tokens.as_ref().unwrap();
+            tokens.len() == 1 && tokens[0].token_type == UserTokenType::UserName
+        }));
+    });
+}
+
+#[test]
+fn idle_session() {
+    let st = ServiceTest::new();
+    let session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    let now = chrono::Utc::now();
+    {
+        let mut session = trace_write_lock!(session);
+
+        // Sessions are not closed for being idle by default
+        session.set_last_activity_timestamp(now - chrono::Duration::hours(1));
+        assert_eq!(session.idle_state(&now), IdleSessionState::Active);
+
+        session.set_idle_session_timeout(10000, 2000);
+        session.set_last_activity_timestamp(now - chrono::Duration::seconds(5));
+        assert_eq!(session.idle_state(&now), IdleSessionState::Active);
+
+        // The warning is raised once per idle period
+        session.set_last_activity_timestamp(now - chrono::Duration::milliseconds(8500));
+        assert_eq!(session.idle_state(&now), IdleSessionState::Warning);
+        assert_eq!(session.idle_state(&now), IdleSessionState::Active);
+        assert_eq!(
+            session.idle_state(&(now + chrono::Duration::seconds(2))),
+            IdleSessionState::Expired
+        );
+
+        // Activity starts a new idle period
+        session.set_last_activity_timestamp(now);
+        assert_eq!(session.idle_state(&now), IdleSessionState::Active);
+        assert_eq!(
+            session.idle_state(&(now + chrono::Duration::seconds(9))),
+            IdleSessionState::Warning
+        );
+    }
+
+    // The server closes an expired session
+    let session_id = trace_read_lock!(session).session_id().clone();
+    let mut session_manager = SessionManager::default();
+    session_manager.register_session(session.clone());
+    assert!(session_manager.terminate_session(&session_id, &st.address_space));
+    assert!(session_manager.find_session_by_id(&session_id).is_none());
+    assert!(trace_read_lock!(session).is_terminated());
+    assert!(!session_manager.terminate_session(&session_id, &st.address_space));
+}
+
+#[test]
+fn session_timeout() {
+    let st = ServiceTest::new_with_server(ServerBuilder::new_sample().max_session_timeout(30000.0));
+    let ss = SessionService::new();
+    let endpoint_url = UAString::from(trace_read_lock!(st.server_state).base_endpoint.as_str());
+    let create_session = |requested_session_timeout| {
+        let request = CreateSessionRequest {
+            request_header: RequestHeader::dummy(),
+            client_description: ApplicationDescription::default(),
+            server_uri: UAString::null(),
+            endpoint_url: endpoint_url.clone(),
+            session_name: UAString::from("test"),
+            client_nonce: ByteString::null(),
+            client_certificate: ByteString::null(),
+            requested_session_timeout,
+            max_response_message_size: 0,
+        };
+        let (session, response) = ss.create_session(
+            Arc::new(RwLock::new(SecureChannel::new_no_certificate_store())),
+            Arc::new(RwLock::new(CertificateStore::new(Path::new("./pki")))),
+            st.server_state.clone(),
+            st.address_space.clone(),
+            &request,
+        );
+        let response: CreateSessionResponse =
+            supported_message_as!(response, CreateSessionResponse);
+        assert_eq!(
+            session.as_ref().unwrap().session_timeout(),
+            response.revised_session_timeout
+        );
+        (session.unwrap(), response.revised_session_timeout)
+    };
+
+    // Timeouts that are too long, or missing, are revised to the maximum
+    assert_eq!(create_session(10000.0).1, 10000.0);
+    assert_eq!(create_session(0.0).1, 30000.0);
+    assert_eq!(create_session(100000.0).1, 30000.0);
+
+    // A session is closed once it goes without a service call for longer than its timeout
+    let (session, _) = create_session(10000.0);
+    let session_id = session.session_id().clone();
+    let session = Arc::new(RwLock::new(session));
+    let mut session_manager = SessionManager::default();
+    session_manager.register_session(session.clone());
+    let now = chrono::Utc::now();
+    trace_write_lock!(session)
+        .set_last_service_request_timestamp(now - chrono::Duration::seconds(5));
+    assert!(session_manager
+        .close_timed_out_sessions(&now, &st.address_space)
+        .is_empty());
+    trace_write_lock!(session)
+        .set_last_service_request_timestamp(now - chrono::Duration::seconds(15));
+    assert_eq!(
+        session_manager.close_timed_out_sessions(&now, &st.address_space),
+        vec![session_id.clone()]
+    );
+    assert!(session_manager.find_session_by_id(&session_id).is_none());
+    assert!(trace_read_lock!(session).is_terminated());
+}
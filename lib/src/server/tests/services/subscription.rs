@@ -3,8 +3,12 @@ use std::ops::Add;
 use chrono::Utc;
 
 use crate::server::{
+    identity_token::IdentityToken,
     prelude::*,
-    services::{monitored_item::MonitoredItemService, subscription::SubscriptionService},
+    services::{
+        monitored_item::MonitoredItemService, session::SessionService,
+        subscription::SubscriptionService,
+    },
     state::ServerState,
     subscriptions::subscription::*,
 };
@@ -142,6 +146,34 @@ fn test_revised_keep_alive_lifetime_counts() {
     );
 }
 
+#[test]
+fn test_revised_publishing_interval() {
+    use crate::server::constants::MAX_PUBLISHING_INTERVAL_MS;
+
+    do_subscription_service_test(|server_state, session, _, ss, _| {
+        let min_publishing_interval = trace_read_lock!(server_state).min_publishing_interval_ms;
+        [
+            (f64::NAN, min_publishing_interval),
+            (-1f64, min_publishing_interval),
+            (f64::INFINITY, MAX_PUBLISHING_INTERVAL_MS),
+            (f64::MAX, MAX_PUBLISHING_INTERVAL_MS),
+            (1000f64, 1000f64),
+        ]
+        .into_iter()
+        .for_each(|(requested, expected)| {
+            let request = CreateSubscriptionRequest {
+                requested_publishing_interval: requested,
+                ..create_subscription_request(0, 0)
+            };
+            let response: CreateSubscriptionResponse = supported_message_as!(
+                ss.create_subscription(server_state.clone(), session.clone(), &request),
+                CreateSubscriptionResponse
+            );
+            assert_eq!(response.revised_publishing_interval, expected);
+        });
+    })
+}
+
 #[test]
 fn publish_with_no_subscriptions() {
     do_subscription_service_test(|_, session, address_space, ss, _| {
@@ -226,7 +258,8 @@ fn publish_response_subscription() {
             // We expect the response to contain a non-empty notification
             assert_eq!(response.more_notifications, false);
             assert_eq!(response.subscription_id, subscription_id);
-            assert!(response.available_sequence_numbers.is_none());
+            // The notification is held for retransmission until it is acknowledged
+            assert_eq!(response.available_sequence_numbers, Some(vec![1]));
 
             response.notification_message
         };
@@ -345,6 +378,12 @@ fn publish_keep_alive() {
             assert_eq!(response.more_notifications, false);
             assert_eq!(response.subscription_id, subscription_id);
             assert!(response.available_sequence_numbers.is_none());
+
+            // Keep-alive messages are not held for retransmission
+            assert!(session
+                .subscriptions_mut()
+                .retransmission_queue()
+                .is_empty());
             response.notification_message
         };
 
@@ -354,6 +393,67 @@ fn publish_keep_alive() {
     })
 }
 
+#[test]
+fn cancel_publish_requests() {
+    do_subscription_service_test(|server_state, session, address_space, ss, _| {
+        let subscription_id = create_subscription(server_state.clone(), session.clone(), &ss);
+        {
+            let mut session = trace_write_lock!(session);
+            let subscription = session
+                .subscriptions_mut()
+                .get_mut(subscription_id)
+                .unwrap();
+            subscription.set_state(SubscriptionState::Normal);
+            subscription.set_publishing_enabled(false);
+        }
+
+        // Queue two publish requests with different request handles
+        let now = Utc::now();
+        for (request_id, request_handle) in [(1001, 42), (1002, 43)] {
+            let mut request = publish_request(None);
+            request.request_header.request_handle = request_handle;
+            let response = ss.async_publish(
+                &now,
+                session.clone(),
+                address_space.clone(),
+                request_id,
+                &request,
+            );
+            assert!(response.is_none());
+        }
+
+        let cancel = |request_handle| {
+            let request = CancelRequest {
+                request_header: RequestHeader::dummy(),
+                request_handle,
+            };
+            let response: CancelResponse = supported_message_as!(
+                SessionService::new().cancel(server_state.clone(), session.clone(), &request),
+                CancelResponse
+            );
+            response.cancel_count
+        };
+        assert_eq!(cancel(42), 1);
+        assert_eq!(cancel(42), 0);
+
+        // The cancelled request is answered and the other one is still queued
+        let mut session = trace_write_lock!(session);
+        assert_eq!(session.subscriptions_mut().publish_request_queue().len(), 1);
+        let response = session
+            .subscriptions_mut()
+            .publish_response_queue()
+            .pop_back()
+            .unwrap();
+        assert_eq!(response.request_id, 1001);
+        let response: ServiceFault = supported_message_as!(response.response, ServiceFault);
+        assert_eq!(response.response_header.request_handle, 42);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadRequestCancelledByClient
+        );
+    })
+}
+
 #[test]
 fn multiple_publish_response_subscription() {
     do_subscription_service_test(|server_state, session, address_space, ss, _mis| {
@@ -384,25 +484,80 @@ fn acknowledge_unknown_sequence_nr() {
     do_subscription_service_test(|server_state, session, address_space, ss, _mis| {
         let subscription_id = create_subscription(server_state, session.clone(), &ss);
 
+        // Two notifications are waiting to be acknowledged
+        {
+            let mut session = trace_write_lock!(session);
+            for sequence_number in [1, 2] {
+                let notification = NotificationMessage::data_change(
+                    sequence_number,
+                    DateTime::now(),
+                    vec![MonitoredItemNotification {
+                        client_handle: 1,
+                        value: Variant::Empty.into(),
+                    }],
+                    vec![],
+                );
+                session
+                    .subscriptions_mut()
+                    .retransmission_queue()
+                    .insert((subscription_id, sequence_number), notification);
+            }
+        }
+
         let now = Utc::now();
         let request_id = 1001;
 
-        // Acknowledge an unknown seqid, test the response
-        let ack = SubscriptionAcknowledgement {
-            subscription_id,
-            sequence_number: 10001,
-        };
-        let request = publish_request(Some(vec![ack]));
-        let _response = ss.async_publish(
+        // Acknowledge a known seqid, an unknown seqid and a seqid of an unknown subscription
+        let acks = vec![
+            SubscriptionAcknowledgement {
+                subscription_id,
+                sequence_number: 1,
+            },
+            SubscriptionAcknowledgement {
+                subscription_id,
+                sequence_number: 10001,
+            },
+            SubscriptionAcknowledgement {
+                subscription_id: subscription_id + 1,
+                sequence_number: 2,
+            },
+        ];
+        let request = publish_request(Some(acks));
+        let response = ss.async_publish(
             &now,
             session.clone(),
             address_space.clone(),
             request_id,
             &request,
         );
+        assert!(response.is_none());
 
-        // TODO
-        //unimplemented!();
+        // Only the acknowledged notification is purged
+        let mut session = trace_write_lock!(session);
+        let subscriptions = session.subscriptions_mut();
+        assert_eq!(
+            subscriptions
+                .retransmission_queue()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![(subscription_id, 2)]
+        );
+        assert_eq!(
+            subscriptions.available_sequence_numbers(subscription_id),
+            Some(vec![2])
+        );
+
+        // The results for the acknowledgements go with the queued request
+        let results = subscriptions.publish_request_queue()[0].results.clone();
+        assert_eq!(
+            results,
+            Some(vec![
+                StatusCode::Good,
+                StatusCode::BadSequenceNumberUnknown,
+                StatusCode::BadSubscriptionIdInvalid
+            ])
+        );
     })
 }
 
@@ -459,3 +614,461 @@ fn republish() {
         );
     })
 }
+
+#[test]
+fn transfer_subscriptions() {
+    let st = ServiceTest::new();
+    let ss = SubscriptionService::new();
+
+    // A second session on the server, from the same anonymous client application
+    let new_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    for session in [&st.session, &new_session] {
+        let mut session = trace_write_lock!(session);
+        session.set_client_description(ApplicationDescription {
+            application_uri: UAString::from("urn:SampleClient"),
+            ..Default::default()
+        });
+    }
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.register_session(new_session.clone());
+    }
+
+    // Create a subscription on the old session with a notification awaiting acknowledgement
+    let subscription_id = create_subscription(st.server_state.clone(), st.session.clone(), &ss);
+    {
+        let notification = NotificationMessage::data_change(
+            1,
+            DateTime::now(),
+            vec![MonitoredItemNotification {
+                client_handle: 1,
+                value: Variant::Empty.into(),
+            }],
+            vec![],
+        );
+        let mut session = trace_write_lock!(st.session);
+        session
+            .subscriptions_mut()
+            .retransmission_queue()
+            .insert((subscription_id, 1), notification);
+    }
+
+    let request = TransferSubscriptionsRequest {
+        request_header: RequestHeader::dummy(),
+        subscription_ids: Some(vec![subscription_id, subscription_id + 1]),
+        send_initial_values: true,
+    };
+    let response: TransferSubscriptionsResponse = supported_message_as!(
+        ss.transfer_subscriptions(
+            &Utc::now(),
+            new_session.clone(),
+            st.session_manager.clone(),
+            &request
+        ),
+        TransferSubscriptionsResponse
+    );
+    let results = response.results.unwrap();
+    assert_eq!(results[0].status_code, StatusCode::Good);
+    assert_eq!(results[0].available_sequence_numbers, Some(vec![1]));
+    assert_eq!(results[1].status_code, StatusCode::BadSubscriptionIdInvalid);
+
+    // The subscription and its notification now belong to the new session
+    {
+        let session = trace_read_lock!(st.session);
+        assert!(!session.subscriptions().contains(subscription_id));
+    }
+    {
+        let new_session = trace_read_lock!(new_session);
+        assert!(new_session.subscriptions().contains(subscription_id));
+        assert!(new_session
+            .subscriptions()
+            .find_notification_message(subscription_id, 1)
+            .is_ok());
+    }
+
+    // A session for a different user cannot take the subscription over
+    let other_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    {
+        let mut other_session = trace_write_lock!(other_session);
+        other_session.set_user_identity(IdentityToken::UserNameIdentityToken(
+            UserNameIdentityToken {
+                policy_id: UAString::null(),
+                user_name: UAString::from("sample1"),
+                password: ByteString::null(),
+                encryption_algorithm: UAString::null(),
+            },
+        ));
+    }
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.register_session(other_session.clone());
+    }
+    let request = TransferSubscriptionsRequest {
+        request_header: RequestHeader::dummy(),
+        subscription_ids: Some(vec![subscription_id]),
+        send_initial_values: false,
+    };
+    let response: TransferSubscriptionsResponse = supported_message_as!(
+        ss.transfer_subscriptions(
+            &Utc::now(),
+            other_session,
+            st.session_manager.clone(),
+            &request
+        ),
+        TransferSubscriptionsResponse
+    );
+    assert_eq!(
+        response.results.unwrap()[0].status_code,
+        StatusCode::BadUserAccessDenied
+    );
+}
+
+#[test]
+fn transfer_subscriptions_anonymous() {
+    let st = ServiceTest::new();
+    let ss = SubscriptionService::new();
+
+    let (client_certificate, _) = X509::cert_and_pkey(&X509Data::sample_cert()).unwrap();
+    {
+        let mut session = trace_write_lock!(st.session);
+        session.set_client_certificate(Some(client_certificate.clone()));
+    }
+    let subscription_id = create_subscription(st.server_state.clone(), st.session.clone(), &ss);
+
+    let transfer = |session: Arc<RwLock<Session>>| {
+        let request = TransferSubscriptionsRequest {
+            request_header: RequestHeader::dummy(),
+            subscription_ids: Some(vec![subscription_id]),
+            send_initial_values: false,
+        };
+        let response: TransferSubscriptionsResponse = supported_message_as!(
+            ss.transfer_subscriptions(&Utc::now(), session, st.session_manager.clone(), &request),
+            TransferSubscriptionsResponse
+        );
+        response.results.unwrap()[0].status_code
+    };
+
+    // An anonymous session of a client with a different certificate cannot take the subscription
+    // over, even with the same ApplicationUri
+    let other_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    {
+        let (other_certificate, _) = X509::cert_and_pkey(&X509Data::sample_cert()).unwrap();
+        let client_description = trace_read_lock!(st.session).client_description().clone();
+        let mut other_session = trace_write_lock!(other_session);
+        other_session.set_client_certificate(Some(other_certificate));
+        other_session.set_client_description(client_description);
+    }
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.register_session(other_session.clone());
+    }
+    assert_eq!(transfer(other_session), StatusCode::BadUserAccessDenied);
+
+    // Nor can one without a certificate
+    let no_certificate_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.register_session(no_certificate_session.clone());
+    }
+    assert_eq!(
+        transfer(no_certificate_session),
+        StatusCode::BadUserAccessDenied
+    );
+
+    // An anonymous session of the same client can
+    let new_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+    {
+        let mut new_session = trace_write_lock!(new_session);
+        new_session.set_client_certificate(Some(client_certificate));
+    }
+    {
+        let mut session_manager = trace_write_lock!(st.session_manager);
+        session_manager.register_session(new_session.clone());
+    }
+    assert_eq!(transfer(new_session.clone()), StatusCode::Good);
+    assert!(trace_read_lock!(new_session)
+        .subscriptions()
+        .contains(subscription_id));
+}
+
+#[test]
+fn publishing_disabled_buffers_notifications() {
+    do_subscription_service_test(|server_state, session, address_space, ss, mis| {
+        let subscription_id = create_subscription(server_state.clone(), session.clone(), &ss);
+        create_monitored_item(
+            subscription_id,
+            VariableId::Server_ServerStatus_StartTime,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &mis,
+        );
+        {
+            let mut session = trace_write_lock!(session);
+            session
+                .subscriptions_mut()
+                .get_mut(subscription_id)
+                .unwrap()
+                .set_state(SubscriptionState::Normal);
+        }
+
+        let set_publishing_mode = |publishing_enabled| {
+            let request = SetPublishingModeRequest {
+                request_header: RequestHeader::dummy(),
+                publishing_enabled,
+                subscription_ids: Some(vec![subscription_id]),
+            };
+            let response: SetPublishingModeResponse = supported_message_as!(
+                ss.set_publishing_mode(session.clone(), &request),
+                SetPublishingModeResponse
+            );
+            assert_eq!(response.results.unwrap(), vec![StatusCode::Good]);
+        };
+
+        // Publishing is disabled, so the value change is held back
+        set_publishing_mode(false);
+        let now = Utc::now();
+        let _ = ss.async_publish(
+            &now,
+            session.clone(),
+            address_space.clone(),
+            1001,
+            &publish_request(None),
+        );
+        {
+            let now = now.add(chrono::Duration::seconds(2));
+            let mut session = trace_write_lock!(session);
+            let address_space = trace_read_lock!(address_space);
+            let _ = session.tick_subscriptions(&now, &address_space, TickReason::TickTimerFired);
+            while let Some(response) = session
+                .subscriptions_mut()
+                .publish_response_queue()
+                .pop_back()
+            {
+                let response: PublishResponse =
+                    supported_message_as!(response.response, PublishResponse);
+                assert!(response.notification_message.notification_data.is_none());
+            }
+        }
+
+        // Once enabled, the buffered value change is delivered
+        set_publishing_mode(true);
+        let _ = ss.async_publish(
+            &now.add(chrono::Duration::seconds(3)),
+            session.clone(),
+            address_space.clone(),
+            1002,
+            &publish_request(None),
+        );
+        let now = now.add(chrono::Duration::seconds(4));
+        let mut session = trace_write_lock!(session);
+        let address_space = trace_read_lock!(address_space);
+        let _ = session.tick_subscriptions(&now, &address_space, TickReason::TickTimerFired);
+        let response = session
+            .subscriptions_mut()
+            .publish_response_queue()
+            .pop_front()
+            .unwrap()
+            .response;
+        let response: PublishResponse = supported_message_as!(response, PublishResponse);
+        let notification_data = response.notification_message.notification_data.unwrap();
+        let data_change = notification_data[0]
+            .decode_inner::<DataChangeNotification>(&DecodingOptions::test())
+            .unwrap();
+        assert_eq!(data_change.monitored_items.unwrap().len(), 1);
+    })
+}
+
+#[test]
+fn subscription_limits() {
+    let st = ServiceTest::new_with_server(
+        ServerBuilder::new_sample()
+            .max_subscriptions(2)
+            .max_server_subscriptions(3)
+            .max_monitored_items_per_sub(2)
+            .min_publishing_interval(0.5),
+    );
+    let ss = SubscriptionService::new();
+    let mis = MonitoredItemService::new();
+    let other_session = Arc::new(RwLock::new(Session::new(st.server_state.clone())));
+
+    let create = |session: &Arc<RwLock<Session>>| {
+        let request = create_subscription_request(0, 0);
+        ss.create_subscription(st.server_state.clone(), session.clone(), &request)
+    };
+
+    // The publishing interval is revised up to the minimum
+    let response: CreateSubscriptionResponse =
+        supported_message_as!(create(&st.session), CreateSubscriptionResponse);
+    assert_eq!(response.revised_publishing_interval, 500f64);
+    let subscription_id = response.subscription_id;
+
+    // The limit per session
+    let _: CreateSubscriptionResponse =
+        supported_message_as!(create(&st.session), CreateSubscriptionResponse);
+    let response: ServiceFault = supported_message_as!(create(&st.session), ServiceFault);
+    assert_eq!(
+        response.response_header.service_result,
+        StatusCode::BadTooManySubscriptions
+    );
+
+    // The limit across sessions
+    let _: CreateSubscriptionResponse =
+        supported_message_as!(create(&other_session), CreateSubscriptionResponse);
+    let response: ServiceFault = supported_message_as!(create(&other_session), ServiceFault);
+    assert_eq!(
+        response.response_header.service_result,
+        StatusCode::BadTooManySubscriptions
+    );
+
+    // The limit of monitored items in a subscription
+    let request = create_monitored_items_request(
+        subscription_id,
+        vec![
+            VariableId::Server_ServerStatus_CurrentTime,
+            VariableId::Server_ServerStatus_StartTime,
+            VariableId::Server_ServerStatus_State,
+        ],
+    );
+    let response: CreateMonitoredItemsResponse = supported_message_as!(
+        mis.create_monitored_items(
+            st.server_state.clone(),
+            st.session.clone(),
+            st.address_space.clone(),
+            &request
+        ),
+        CreateMonitoredItemsResponse
+    );
+    let results = response.results.unwrap();
+    assert!(results[0].status_code.is_good());
+    assert!(results[1].status_code.is_good());
+    assert_eq!(results[2].status_code, StatusCode::BadTooManyMonitoredItems);
+
+    // The limits are published as server capabilities
+    let address_space = trace_read_lock!(st.address_space);
+    let ns = address_space.default_namespace();
+    for (name, value) in [
+        ("MaxSubscriptions", 3u32),
+        ("MaxSubscriptionsPerSession", 2u32),
+        ("MaxMonitoredItemsPerSubscription", 2u32),
+    ] {
+        let node_id = NodeId::new(ns, format!("ServerCapabilities_{}", name));
+        let v = address_space.find_variable(&node_id).unwrap();
+        assert_eq!(
+            v.value(
+                TimestampsToReturn::Neither,
+                NumericRange::None,
+                &QualifiedName::null(),
+                0.0
+            )
+            .value,
+            Some(Variant::from(value))
+        );
+    }
+}
+
+#[test]
+fn diagnostics() {
+    let st = ServiceTest::new();
+    add_many_vars_to_address_space(st.address_space.clone(), 10);
+    {
+        let mut address_space = trace_write_lock!(st.address_space);
+        address_space.set_session_manager(st.session_manager.clone());
+    }
+    let ss = SubscriptionService::new();
+    let mis = MonitoredItemService::new();
+
+    let subscription_id = create_subscription(st.server_state.clone(), st.session.clone(), &ss);
+    create_monitored_item(
+        subscription_id,
+        var_node_id(1),
+        st.server_state.clone(),
+        st.session.clone(),
+        st.address_space.clone(),
+        &mis,
+    );
+
+    let read = |variable_id: VariableId| {
+        let address_space = trace_read_lock!(st.address_space);
+        address_space
+            .find_variable(variable_id)
+            .unwrap()
+            .value(
+                TimestampsToReturn::Neither,
+                NumericRange::None,
+                &QualifiedName::null(),
+                0.0,
+            )
+            .value
+            .unwrap()
+    };
+    let decoding_options = DecodingOptions::test();
+    let decode_array = |value: Variant| {
+        let Variant::Array(array) = value else {
+            panic!("Expected an array, got {:?}", value);
+        };
+        array
+            .values
+            .into_iter()
+            .map(|v| match v {
+                Variant::ExtensionObject(v) => v,
+                v => panic!("Expected an extension object, got {:?}", v),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The summary counts the session and subscription
+    let Variant::ExtensionObject(summary) =
+        read(VariableId::Server_ServerDiagnostics_ServerDiagnosticsSummary)
+    else {
+        panic!("Expected an extension object");
+    };
+    let summary = summary
+        .decode_inner::<ServerDiagnosticsSummaryDataType>(&decoding_options)
+        .unwrap();
+    assert_eq!(summary.current_session_count, 1);
+    assert_eq!(summary.current_subscription_count, 1);
+    assert_eq!(summary.cumulated_subscription_count, 1);
+
+    // Each session and subscription is described in the diagnostics arrays
+    let session_id = trace_read_lock!(st.session).session_id().clone();
+    let sessions = decode_array(read(
+        VariableId::Server_ServerDiagnostics_SessionsDiagnosticsSummary_SessionDiagnosticsArray,
+    ));
+    assert_eq!(sessions.len(), 1);
+    let session = sessions[0]
+        .decode_inner::<SessionDiagnosticsDataType>(&decoding_options)
+        .unwrap();
+    assert_eq!(session.session_id, session_id);
+    assert_eq!(session.current_subscriptions_count, 1);
+    assert_eq!(session.current_monitored_items_count, 1);
+
+    let subscriptions = decode_array(read(
+        VariableId::Server_ServerDiagnostics_SubscriptionDiagnosticsArray,
+    ));
+    assert_eq!(subscriptions.len(), 1);
+    let subscription = subscriptions[0]
+        .decode_inner::<SubscriptionDiagnosticsDataType>(&decoding_options)
+        .unwrap();
+    assert_eq!(subscription.session_id, session_id);
+    assert_eq!(subscription.subscription_id, subscription_id);
+    assert_eq!(subscription.monitored_item_count, 1);
+    assert!(subscription.publishing_enabled);
+
+    // Changing the publishing mode is counted
+    {
+        let mut session = trace_write_lock!(st.session);
+        let subscription = session
+            .subscriptions_mut()
+            .get_mut(subscription_id)
+            .unwrap();
+        subscription.set_publishing_enabled(false);
+    }
+    let subscription = decode_array(read(
+        VariableId::Server_ServerDiagnostics_SubscriptionDiagnosticsArray,
+    ))[0]
+        .decode_inner::<SubscriptionDiagnosticsDataType>(&decoding_options)
+        .unwrap();
+    assert!(!subscription.publishing_enabled);
+    assert_eq!(subscription.disable_count, 1);
+}
@@ -1,6 +1,6 @@
 use std::sync::Weak;
 
-use crate::server::services::view::ViewService;
+use crate::server::{constants, services::view::ViewService};
 use crate::supported_message_as;
 use crate::sync::*;
 
@@ -159,9 +159,162 @@ fn browse() {
     });
 }
 
-// Test the response of supplying an unsupported view to the browse request
 #[test]
-fn browse_non_null_view() {
+fn browse_remote_references() {
+    do_view_service_test(|server_state, session, address_space, vs| {
+        let folder_id = NodeId::new(1, "Federated");
+        let mut remote = ExpandedNodeId::new(NodeId::new(3, "Remote"));
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = address_space.add_folder_with_id(
+                &folder_id,
+                "Federated",
+                "Federated",
+                &ObjectId::ObjectsFolder.into(),
+            );
+            remote.server_index = address_space.register_server("urn:remote").unwrap();
+            address_space
+                .insert_remote_reference(
+                    &folder_id,
+                    &remote,
+                    ReferenceTypeId::Organizes,
+                    ReferenceDirection::Forward,
+                )
+                .unwrap();
+            // The server array holds this server followed by the remote server
+            let servers = address_space.servers();
+            assert_eq!(servers.len(), 2);
+            assert!(!servers[0].is_empty());
+            assert_eq!(servers[1], "urn:remote");
+        }
+
+        let response = do_browse(
+            vs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            std::slice::from_ref(&folder_id),
+            1000,
+            BrowseDirection::Forward,
+        );
+        let results = response.results.unwrap();
+        let references = results[0].references.as_ref().unwrap();
+        assert_eq!(references.len(), 1);
+        let r = &references[0];
+        assert_eq!(r.node_id, remote);
+        assert_eq!(r.node_id.server_index, 1);
+        assert_eq!(r.reference_type_id, ReferenceTypeId::Organizes.into());
+        assert!(r.is_forward);
+        assert_eq!(r.node_class, NodeClass::Unspecified);
+        assert!(r.browse_name.is_null());
+
+        // The remote reference is not an inverse reference
+        let response = do_browse(
+            vs,
+            server_state,
+            session,
+            address_space,
+            &[folder_id],
+            1000,
+            BrowseDirection::Inverse,
+        );
+        let results = response.results.unwrap();
+        let references = results[0].references.as_ref().unwrap();
+        assert!(references.iter().all(|r| r.node_id.server_index == 0));
+    });
+}
+
+/// Hides one node from every user
+struct Hidden(NodeId);
+
+impl AuthorizationHandler for Hidden {
+    fn is_browsable(&self, _session: &Session, node_id: &NodeId) -> bool {
+        *node_id != self.0
+    }
+}
+
+#[test]
+fn browse_hides_nodes() {
+    do_view_service_test(|server_state, session, address_space, vs| {
+        {
+            let mut session = trace_write_lock!(session);
+            session.set_authorization_handler(Arc::new(Hidden(ObjectId::TypesFolder.into())));
+        }
+
+        let nodes: Vec<NodeId> = vec![ObjectId::RootFolder.into(), ObjectId::TypesFolder.into()];
+        let response = do_browse(
+            vs,
+            server_state,
+            session.clone(),
+            address_space.clone(),
+            &nodes,
+            1000,
+            BrowseDirection::Forward,
+        );
+        let results = response.results.unwrap();
+        assert_eq!(results.len(), 2);
+
+        // Types/ is left out of the references from the root
+        let references = results[0].references.as_ref().unwrap();
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].browse_name, QualifiedName::new(0, "Objects"));
+        assert_eq!(references[1].browse_name, QualifiedName::new(0, "Views"));
+
+        // Browsing Types/ directly behaves as if it does not exist
+        assert_eq!(results[1].status_code, StatusCode::BadNodeIdUnknown);
+    });
+}
+
+#[test]
+fn browse_role_permissions() {
+    do_view_service_test(|server_state, session, address_space, vs| {
+        let observer: NodeId = ObjectId::WellKnownRole_Observer.into();
+        {
+            let mut address_space = trace_write_lock!(address_space);
+            let types_folder = address_space
+                .find_node_mut(&ObjectId::TypesFolder.into())
+                .unwrap();
+            types_folder
+                .as_mut_node()
+                .set_role_permissions(vec![RolePermissionType {
+                    role_id: observer.clone(),
+                    permissions: PermissionType::Browse,
+                }]);
+        }
+        let browse = || {
+            let nodes: Vec<NodeId> =
+                vec![ObjectId::RootFolder.into(), ObjectId::TypesFolder.into()];
+            let response = do_browse(
+                vs,
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &nodes,
+                1000,
+                BrowseDirection::Forward,
+            );
+            response.results.unwrap()
+        };
+
+        // Types/ is left out of the references from the root and cannot be browsed without a
+        // role that may browse it
+        let results = browse();
+        let references = results[0].references.as_ref().unwrap();
+        assert_eq!(references.len(), 2);
+        assert_eq!(results[1].status_code, StatusCode::BadUserAccessDenied);
+
+        // An observer sees it
+        trace_write_lock!(session).set_roles(vec![observer]);
+        let results = browse();
+        let references = results[0].references.as_ref().unwrap();
+        assert_eq!(references.len(), 3);
+        assert_eq!(results[1].status_code, StatusCode::Good);
+    });
+}
+
+// Test the response of supplying an unknown view to the browse request
+#[test]
+fn browse_unknown_view() {
     do_view_service_test(|server_state, session, address_space, vs| {
         let nodes: Vec<NodeId> = vec![ObjectId::RootFolder.into()];
 
@@ -198,6 +351,100 @@ fn browse_non_null_view() {
     });
 }
 
+// Test that browsing in a view only finds the nodes in it
+#[test]
+fn browse_view() {
+    do_view_service_test(|server_state, session, address_space, vs| {
+        let operator_id = NodeId::new(1, "Operator");
+        let engineering_id = NodeId::new(1, "Engineering");
+        let view_id = {
+            let mut address_space = trace_write_lock!(address_space);
+            let _ = address_space.add_folder_with_id(
+                &operator_id,
+                "Operator",
+                "Operator",
+                &ObjectId::ObjectsFolder.into(),
+            );
+            let _ = address_space.add_folder_with_id(
+                &engineering_id,
+                "Engineering",
+                "Engineering",
+                &operator_id,
+            );
+            address_space
+                .add_view(
+                    "OperatorView",
+                    "OperatorView",
+                    std::slice::from_ref(&operator_id),
+                )
+                .unwrap()
+        };
+
+        let browse = |nodes: &[NodeId], browse_direction| {
+            let mut request = make_browse_request(
+                nodes,
+                NodeClassMask::empty(),
+                1000,
+                browse_direction,
+                ReferenceTypeId::Organizes,
+            );
+            request.view.view_id = view_id.clone();
+            let response = vs.browse(
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &request,
+            );
+            supported_message_as!(response, BrowseResponse)
+                .results
+                .unwrap()
+        };
+        let browse_names = |result: &BrowseResult| {
+            result
+                .references
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|r| r.browse_name.name.as_ref().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The view organizes the folder, which organizes the other folder
+        let results = browse(
+            &[view_id.clone(), operator_id.clone()],
+            BrowseDirection::Forward,
+        );
+        assert_eq!(browse_names(&results[0]), vec!["Operator"]);
+        assert_eq!(browse_names(&results[1]), vec!["Engineering"]);
+
+        // The Objects folder is not in the view, so it is left out of the parents of the folder
+        // and cannot be browsed
+        let results = browse(
+            &[operator_id.clone(), ObjectId::ObjectsFolder.into()],
+            BrowseDirection::Inverse,
+        );
+        assert_eq!(browse_names(&results[0]), vec!["OperatorView"]);
+        assert_eq!(results[1].status_code, StatusCode::BadNodeNotInView);
+
+        // Views have no history
+        let mut request = make_browse_request(
+            &[operator_id],
+            NodeClassMask::empty(),
+            1000,
+            BrowseDirection::Forward,
+            ReferenceTypeId::Organizes,
+        );
+        request.view.view_id = view_id;
+        request.view.timestamp = DateTime::now();
+        let response = vs.browse(server_state, session, address_space, &request);
+        let response = supported_message_as!(response, ServiceFault);
+        assert_eq!(
+            response.response_header.service_result,
+            StatusCode::BadViewTimestampInvalid
+        );
+    });
+}
+
 // This test applies a class mask to the browse so only nodes of types in the mask should come back
 #[test]
 fn browse_node_class_mask() {
@@ -855,6 +1102,104 @@ fn browse_next_modify_address_space() {
     });
 }
 
+#[test]
+fn browse_no_continuation_points() {
+    // Browse until the session holds as many continuation points as it may, expecting the next
+    // browse that needs one to fail with BadNoContinuationPoints
+    do_view_service_test(|server_state, session, address_space, vs| {
+        let parent_node_id = add_many_vars_to_address_space(address_space.clone(), 100).0;
+        let nodes = vec![parent_node_id.clone()];
+        for _ in 0..constants::MAX_BROWSE_CONTINUATION_POINTS {
+            let response = do_browse(
+                &vs,
+                server_state.clone(),
+                session.clone(),
+                address_space.clone(),
+                &nodes,
+                10,
+                BrowseDirection::Forward,
+            );
+            let r1 = &response.results.unwrap()[0];
+            assert_eq!(r1.status_code, StatusCode::Good);
+            assert!(!r1.continuation_point.is_null());
+        }
+
+        let response = do_browse(
+            &vs,
+            server_state.clone(),
+            session.clone(),
+            address_space.clone(),
+            &nodes,
+            10,
+            BrowseDirection::Forward,
+        );
+        let r1 = &response.results.unwrap()[0];
+        assert_eq!(r1.status_code, StatusCode::BadNoContinuationPoints);
+        assert!(r1.continuation_point.is_null());
+        assert!(r1.references.is_none());
+
+        // A browse that fits in one result does not need a continuation point
+        let response = do_browse(
+            &vs,
+            server_state,
+            session,
+            address_space,
+            &nodes,
+            100,
+            BrowseDirection::Forward,
+        );
+        let r1 = &response.results.unwrap()[0];
+        assert_eq!(r1.status_code, StatusCode::Good);
+        verify_references_to_many_vars(r1.references.as_ref().unwrap(), 100, 0);
+    });
+}
+
+#[test]
+fn browse_next_expired_cp() {
+    // A continuation point that goes unused for longer than the timeout is released
+    do_view_service_test(|server_state, session, address_space, vs| {
+        let parent_node_id = add_many_vars_to_address_space(address_space.clone(), 100).0;
+        let nodes = vec![parent_node_id.clone()];
+        let response = do_browse(
+            &vs,
+            server_state,
+            session.clone(),
+            address_space.clone(),
+            &nodes,
+            99,
+            BrowseDirection::Forward,
+        );
+        let r1 = &response.results.unwrap()[0];
+        assert!(!r1.continuation_point.is_null());
+
+        // Age the continuation point past the timeout
+        {
+            let mut session = trace_write_lock!(session);
+            let mut continuation_point = session
+                .find_browse_continuation_point(&r1.continuation_point)
+                .unwrap();
+            continuation_point.created = chrono::Utc::now()
+                - chrono::Duration::milliseconds(
+                    constants::BROWSE_CONTINUATION_POINT_TIMEOUT_MS + 1000,
+                );
+            session
+                .add_browse_continuation_point(continuation_point)
+                .unwrap();
+        }
+
+        let response = do_browse_next(
+            &vs,
+            session.clone(),
+            address_space.clone(),
+            &r1.continuation_point,
+            false,
+        );
+        let r2 = &response.results.unwrap()[0];
+        assert_eq!(r2.status_code, StatusCode::BadContinuationPointInvalid);
+        assert!(r2.references.is_none());
+    });
+}
+
 #[test]
 fn translate_browse_paths_to_node_ids() {
     do_view_service_test(|server_state, _session, address_space, vs| {
@@ -1,2 +1,3 @@
+mod sampling;
 mod subscription;
 mod subscriptions;
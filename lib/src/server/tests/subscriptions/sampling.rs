@@ -0,0 +1,128 @@
+use chrono::{Duration, Utc};
+
+use crate::server::subscriptions::sampling::SamplingScheduler;
+use crate::types::DateTimeUtc;
+
+fn after(start: &DateTimeUtc, millis: i64) -> DateTimeUtc {
+    *start + Duration::milliseconds(millis)
+}
+
+fn ids(ids: &[u32]) -> Vec<u32> {
+    ids.to_vec()
+}
+
+#[test]
+fn sampling_scheduler_coalesces_intervals() {
+    let start = Utc::now();
+    let mut scheduler = SamplingScheduler::new();
+    scheduler.schedule(1, 100f64, &start);
+    scheduler.schedule(2, 100f64, &start);
+    scheduler.schedule(3, 250f64, &start);
+    assert_eq!(scheduler.interval_count(), 2);
+
+    // Nothing is due before the first interval elapses
+    assert!(scheduler.take_due(&after(&start, 50), false).is_empty());
+
+    // Items with the same interval are sampled together
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 100), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 2]));
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 200), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 2]));
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 250), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[3]));
+
+    // A late tick samples once and skips the samples that were missed
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 1000), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 2, 3]));
+    assert!(scheduler.take_due(&after(&start, 1050), false).is_empty());
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 1100), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 2]));
+}
+
+#[test]
+fn sampling_scheduler_special_intervals() {
+    let start = Utc::now();
+    let mut scheduler = SamplingScheduler::new();
+    // -1 samples at the publishing interval, 0 on every tick
+    scheduler.schedule(1, -1f64, &start);
+    scheduler.schedule(2, 0f64, &start);
+    assert_eq!(scheduler.interval_count(), 0);
+
+    let due: Vec<u32> = scheduler.take_due(&start, false).into_iter().collect();
+    assert_eq!(due, ids(&[2]));
+    let due: Vec<u32> = scheduler.take_due(&start, true).into_iter().collect();
+    assert_eq!(due, ids(&[1, 2]));
+}
+
+#[test]
+fn sampling_scheduler_reschedule() {
+    let start = Utc::now();
+    let mut scheduler = SamplingScheduler::new();
+    scheduler.schedule(1, 100f64, &start);
+    scheduler.schedule(2, 100f64, &start);
+
+    // Moving an item to another interval takes it out of its old group
+    scheduler.schedule(2, 500f64, &start);
+    assert_eq!(scheduler.interval_count(), 2);
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 100), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1]));
+
+    // An item joining a group is sampled when the group is next due
+    scheduler.schedule(3, 100f64, &after(&start, 150));
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 200), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 3]));
+
+    // Empty groups are discarded
+    scheduler.unschedule(1);
+    scheduler.unschedule(3);
+    assert_eq!(scheduler.interval_count(), 1);
+    assert!(!scheduler.is_scheduled(1));
+    assert!(scheduler.is_scheduled(2));
+
+    scheduler.clear();
+    assert_eq!(scheduler.interval_count(), 0);
+    assert!(scheduler.take_due(&after(&start, 10000), true).is_empty());
+}
+
+#[test]
+fn sampling_scheduler_huge_intervals() {
+    let start = Utc::now();
+    let mut scheduler = SamplingScheduler::new();
+    // Intervals too long to represent saturate instead of overflowing or wrapping negative
+    scheduler.schedule(1, f64::MAX, &start);
+    scheduler.schedule(2, f64::INFINITY, &start);
+    scheduler.schedule(3, 100f64, &start);
+    assert_eq!(scheduler.interval_count(), 2);
+
+    let due: Vec<u32> = scheduler
+        .take_due(&after(&start, 100), false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[3]));
+    let due: Vec<u32> = scheduler
+        .take_due(&DateTimeUtc::MAX_UTC, false)
+        .into_iter()
+        .collect();
+    assert_eq!(due, ids(&[1, 2, 3]));
+}
@@ -8,13 +8,14 @@ use std::sync::Arc;
 
 use tokio::time::{interval_at, Duration, Instant};
 
+use crate::core::runtime;
 use crate::sync::*;
-use crate::types::service_types::ServerState as ServerStateType;
+use crate::types::{service_types::ServerState as ServerStateType, DataTypeId, Variant};
 
 use crate::server::state::ServerState;
 
 /// This is a convenience for a polling action. This struct starts a repeating timer that calls
-/// an action repeatedly.
+/// an action repeatedly. If the action panics, the panic is logged and the timer stops.
 pub struct PollingAction {}
 
 impl PollingAction {
@@ -26,7 +27,7 @@ impl PollingAction {
     where
         F: 'static + Fn() + Send,
     {
-        tokio::spawn(async move {
+        tokio::spawn(runtime::catch_panic("polling action", async move {
             let mut timer = interval_at(Instant::now(), Duration::from_millis(interval_ms));
             loop {
                 {
@@ -57,7 +58,45 @@ impl PollingAction {
                     action();
                 }
             }
-        });
+        }));
         PollingAction {}
     }
 }
+
+/// The kind of value held by a heartbeat variable. A heartbeat changes at a fixed rate for as long
+/// as the server runs so that clients, typically machines in a handshake, can tell it is alive.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HeartbeatKind {
+    /// A `UInt32` that is incremented on every beat, wrapping around to zero
+    Counter,
+    /// A `Boolean` that is toggled on every beat
+    Toggle,
+}
+
+impl HeartbeatKind {
+    /// The data type of the heartbeat variable
+    pub fn data_type(&self) -> DataTypeId {
+        match self {
+            HeartbeatKind::Counter => DataTypeId::UInt32,
+            HeartbeatKind::Toggle => DataTypeId::Boolean,
+        }
+    }
+
+    /// The value of the heartbeat variable before the first beat
+    pub fn initial_value(&self) -> Variant {
+        match self {
+            HeartbeatKind::Counter => Variant::UInt32(0),
+            HeartbeatKind::Toggle => Variant::Boolean(false),
+        }
+    }
+
+    /// The value following the current value of the heartbeat variable. A value of the wrong
+    /// type restarts the heartbeat from its initial value.
+    pub fn next_value(&self, value: &Variant) -> Variant {
+        match (self, value) {
+            (HeartbeatKind::Counter, Variant::UInt32(v)) => Variant::UInt32(v.wrapping_add(1)),
+            (HeartbeatKind::Toggle, Variant::Boolean(v)) => Variant::Boolean(!v),
+            _ => self.initial_value(),
+        }
+    }
+}
@@ -82,6 +82,39 @@ impl Array {
         }
     }
 
+    /// Returns the length of each dimension of the array. A single dimension array has one
+    /// dimension, the number of values.
+    pub fn array_dimensions(&self) -> Vec<u32> {
+        match self.dimensions {
+            Some(ref dimensions) if !dimensions.is_empty() => dimensions.clone(),
+            _ => vec![self.values.len() as u32],
+        }
+    }
+
+    /// Returns the number of dimensions of the array, i.e. its value rank
+    pub fn value_rank(&self) -> i32 {
+        self.array_dimensions().len() as i32
+    }
+
+    /// Returns the value at an index with one entry per dimension, e.g. `[1, 0]` for the first
+    /// value of the second row of a two dimensional array. Returns `None` if the index has the
+    /// wrong number of entries or is out of bounds.
+    pub fn value_at(&self, index: &[u32]) -> Option<&Variant> {
+        let dimensions = self.array_dimensions();
+        if index.len() != dimensions.len() {
+            return None;
+        }
+        // Higher rank dimensions come first, so the last index varies fastest
+        let mut offset = 0usize;
+        for (i, d) in index.iter().zip(dimensions.iter()) {
+            if i >= d {
+                return None;
+            }
+            offset = offset * (*d as usize) + (*i as usize);
+        }
+        self.values.get(offset)
+    }
+
     pub fn is_valid(&self) -> bool {
         self.is_valid_dimensions() && Self::array_is_valid(&self.values)
     }
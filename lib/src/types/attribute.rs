@@ -8,6 +8,8 @@
 
 use std::{error::Error, fmt};
 
+use crate::types::service_types::NodeClass;
+
 #[derive(Debug)]
 pub struct AttributeIdError;
 
@@ -87,4 +89,133 @@ impl AttributeId {
         };
         Ok(attribute_id)
     }
+
+    /// The attributes that nodes of the class define, in the order of their ids. Some of them are
+    /// optional, so a particular node may not have all of them. See OPC UA Part 3, 5.
+    pub fn for_node_class(node_class: NodeClass) -> &'static [AttributeId] {
+        match node_class {
+            NodeClass::Unspecified => &[],
+            NodeClass::Object => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::EventNotifier,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::Variable => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::Value,
+                AttributeId::DataType,
+                AttributeId::ValueRank,
+                AttributeId::ArrayDimensions,
+                AttributeId::AccessLevel,
+                AttributeId::UserAccessLevel,
+                AttributeId::MinimumSamplingInterval,
+                AttributeId::Historizing,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+                AttributeId::AccessLevelEx,
+            ],
+            NodeClass::Method => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::Executable,
+                AttributeId::UserExecutable,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::ObjectType => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::IsAbstract,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::VariableType => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::IsAbstract,
+                AttributeId::Value,
+                AttributeId::DataType,
+                AttributeId::ValueRank,
+                AttributeId::ArrayDimensions,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::ReferenceType => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::IsAbstract,
+                AttributeId::Symmetric,
+                AttributeId::InverseName,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::DataType => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::IsAbstract,
+                AttributeId::DataTypeDefinition,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+            NodeClass::View => &[
+                AttributeId::NodeId,
+                AttributeId::NodeClass,
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::Description,
+                AttributeId::WriteMask,
+                AttributeId::UserWriteMask,
+                AttributeId::ContainsNoLoops,
+                AttributeId::EventNotifier,
+                AttributeId::RolePermissions,
+                AttributeId::UserRolePermissions,
+                AttributeId::AccessRestrictions,
+            ],
+        }
+    }
 }
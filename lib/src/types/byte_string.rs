@@ -18,7 +18,9 @@ use crate::types::{
         process_decode_io_result, process_encode_io_result, write_i32, BinaryEncoder,
         DecodingOptions, EncodingResult,
     },
+    json::{JsonEncoder, JsonEncoding, JsonValue},
     status_codes::StatusCode,
+    xml::{nil_element, Element, XmlEncoder},
     Guid,
 };
 
@@ -117,7 +119,7 @@ impl BinaryEncoder<ByteString> for ByteString {
     fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
         let len = i32::decode(stream, decoding_options)?;
         // Null string?
-        if len == -1 {
+        if len == -1 || (len == 0 && decoding_options.canonical) {
             Ok(ByteString::null())
         } else if len < -1 {
             error!("ByteString buf length is a negative number {}", len);
@@ -137,6 +139,79 @@ impl BinaryEncoder<ByteString> for ByteString {
     }
 }
 
+impl XmlEncoder for ByteString {
+    fn xml_type_name() -> String {
+        "ByteString".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        if self.is_null() {
+            nil_element(name)
+        } else {
+            Element::new(name).with_text(self.as_base64())
+        }
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if element.is_nil() {
+            return Ok(ByteString::null());
+        }
+        // Base64 in XML may be broken over several lines
+        let text = element
+            .text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let v = ByteString::from_base64(&text).ok_or_else(|| {
+            error!("Expected base64 encoded text in element {}", element.name);
+            StatusCode::BadDecodingError
+        })?;
+        if v.as_ref().len() > decoding_options.max_byte_string_length {
+            error!(
+                "Byte string length {} exceeds decoding limit {}",
+                v.as_ref().len(),
+                decoding_options.max_byte_string_length
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(v)
+        }
+    }
+}
+
+impl JsonEncoder for ByteString {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            JsonValue::from(self.as_base64())
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            return Ok(ByteString::null());
+        }
+        let v = value
+            .as_str()
+            .and_then(ByteString::from_base64)
+            .ok_or_else(|| {
+                error!("Expected a base64 encoded JSON string, got {}", value);
+                StatusCode::BadDecodingError
+            })?;
+        if v.as_ref().len() > decoding_options.max_byte_string_length {
+            error!(
+                "Byte string length {} exceeds decoding limit {}",
+                v.as_ref().len(),
+                decoding_options.max_byte_string_length
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(v)
+        }
+    }
+}
+
 impl<'a, T> From<&'a T> for ByteString
 where
     T: AsRef<[u8]> + ?Sized,
@@ -7,11 +7,15 @@
 use std::io::{Read, Write};
 
 use crate::types::{
-    byte_string::ByteString, date_time::*, encoding::*, guid::Guid, localized_text::LocalizedText,
-    node_id::NodeId, qualified_name::QualifiedName, service_types::TimestampsToReturn,
-    status_codes::StatusCode, string::UAString, variant::Variant,
+    byte_string::ByteString, date_time::*, encoding::*, guid::Guid, json::*,
+    localized_text::LocalizedText, node_id::NodeId, qualified_name::QualifiedName,
+    service_types::TimestampsToReturn, status_codes::StatusCode, string::UAString,
+    variant::Variant, xml::*,
 };
 
+/// The largest valid picoseconds value of a timestamp
+const MAX_PICOSECONDS: u16 = 9999;
+
 bitflags! {
     struct DataValueFlags: u8 {
         /// False if the Value is Null.
@@ -143,7 +147,7 @@ impl BinaryEncoder<DataValue> for DataValue {
             None
         };
         let source_picoseconds = if encoding_mask.contains(DataValueFlags::HAS_SOURCE_PICOSECONDS) {
-            Some(u16::decode(stream, decoding_options)?)
+            Self::valid_picoseconds(u16::decode(stream, decoding_options)?)
         } else {
             None
         };
@@ -154,27 +158,100 @@ impl BinaryEncoder<DataValue> for DataValue {
             None
         };
         let server_picoseconds = if encoding_mask.contains(DataValueFlags::HAS_SERVER_PICOSECONDS) {
-            Some(u16::decode(stream, decoding_options)?)
+            Self::valid_picoseconds(u16::decode(stream, decoding_options)?)
         } else {
             None
         };
-        // Pico second values are discarded if associated timestamp is not supplied
-        Ok(DataValue {
+        Ok(DataValue::with_picoseconds(
             value,
             status,
-            source_picoseconds: if source_timestamp.is_some() {
-                source_picoseconds
-            } else {
-                None
-            },
             source_timestamp,
-            server_picoseconds: if server_timestamp.is_some() {
-                server_picoseconds
-            } else {
-                None
-            },
+            source_picoseconds,
             server_timestamp,
-        })
+            server_picoseconds,
+        ))
+    }
+}
+
+impl XmlEncoder for DataValue {
+    fn xml_type_name() -> String {
+        "DataValue".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Value", &self.value);
+        write_xml_field(&mut element, "StatusCode", &self.status);
+        write_xml_field(&mut element, "SourceTimestamp", &self.source_timestamp);
+        write_xml_field(&mut element, "SourcePicoseconds", &self.source_picoseconds);
+        write_xml_field(&mut element, "ServerTimestamp", &self.server_timestamp);
+        write_xml_field(&mut element, "ServerPicoseconds", &self.server_picoseconds);
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let source_picoseconds: Option<u16> =
+            read_xml_field(element, "SourcePicoseconds", decoding_options)?;
+        let server_picoseconds: Option<u16> =
+            read_xml_field(element, "ServerPicoseconds", decoding_options)?;
+        Ok(DataValue::with_picoseconds(
+            read_xml_field(element, "Value", decoding_options)?,
+            read_xml_field(element, "StatusCode", decoding_options)?,
+            read_xml_field(element, "SourceTimestamp", decoding_options)?,
+            source_picoseconds.and_then(Self::valid_picoseconds),
+            read_xml_field(element, "ServerTimestamp", decoding_options)?,
+            server_picoseconds.and_then(Self::valid_picoseconds),
+        ))
+    }
+}
+
+/// DataValue encoded as an object with a field for each of its parts that is present
+impl JsonEncoder for DataValue {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        write_json_field(&mut object, "Status", &self.status, encoding);
+        write_json_field(
+            &mut object,
+            "SourceTimestamp",
+            &self.source_timestamp,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SourcePicoseconds",
+            &self.source_picoseconds,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerTimestamp",
+            &self.server_timestamp,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerPicoseconds",
+            &self.server_picoseconds,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        let source_picoseconds: Option<u16> =
+            read_json_field(value, "SourcePicoseconds", decoding_options)?;
+        let server_picoseconds: Option<u16> =
+            read_json_field(value, "ServerPicoseconds", decoding_options)?;
+        Ok(DataValue::with_picoseconds(
+            read_json_field(value, "Value", decoding_options)?,
+            read_json_field(value, "Status", decoding_options)?,
+            read_json_field(value, "SourceTimestamp", decoding_options)?,
+            source_picoseconds.and_then(Self::valid_picoseconds),
+            read_json_field(value, "ServerTimestamp", decoding_options)?,
+            server_picoseconds.and_then(Self::valid_picoseconds),
+        ))
     }
 }
 
@@ -464,6 +541,28 @@ impl DataValue {
         }
     }
 
+    /// Removes the timestamps, and their picoseconds, that a client did not ask for. `Invalid`
+    /// removes both timestamps.
+    pub fn strip_timestamps(&mut self, timestamps_to_return: TimestampsToReturn) {
+        match timestamps_to_return {
+            TimestampsToReturn::Source => {
+                self.server_timestamp = None;
+                self.server_picoseconds = None;
+            }
+            TimestampsToReturn::Server => {
+                self.source_timestamp = None;
+                self.source_picoseconds = None;
+            }
+            TimestampsToReturn::Neither | TimestampsToReturn::Invalid => {
+                self.source_timestamp = None;
+                self.source_picoseconds = None;
+                self.server_timestamp = None;
+                self.server_picoseconds = None;
+            }
+            TimestampsToReturn::Both => {}
+        }
+    }
+
     /// Returns the status code or Good if there is no code on the value
     pub fn status(&self) -> StatusCode {
         self.status.map_or(StatusCode::Good, |s| s)
@@ -475,6 +574,40 @@ impl DataValue {
         self.status().status().is_good()
     }
 
+    /// Makes a decoded data value. Pico second values are discarded if the associated timestamp
+    /// is not supplied.
+    fn with_picoseconds(
+        value: Option<Variant>,
+        status: Option<StatusCode>,
+        source_timestamp: Option<DateTime>,
+        source_picoseconds: Option<u16>,
+        server_timestamp: Option<DateTime>,
+        server_picoseconds: Option<u16>,
+    ) -> DataValue {
+        DataValue {
+            value,
+            status,
+            source_picoseconds: source_timestamp.and(source_picoseconds),
+            source_timestamp,
+            server_picoseconds: server_timestamp.and(server_picoseconds),
+            server_timestamp,
+        }
+    }
+
+    /// Picoseconds are in 10 picosecond intervals below the 100 nanosecond resolution of a
+    /// timestamp, so a value above 9999 is invalid and is discarded
+    fn valid_picoseconds(picoseconds: u16) -> Option<u16> {
+        if picoseconds <= MAX_PICOSECONDS {
+            Some(picoseconds)
+        } else {
+            warn!(
+                "Picoseconds value {} is out of range and is ignored",
+                picoseconds
+            );
+            None
+        }
+    }
+
     fn encoding_mask(&self) -> DataValueFlags {
         let mut encoding_mask = DataValueFlags::empty();
         if self.value.is_some() {
@@ -15,7 +15,7 @@ use std::{
 use chrono::{Duration, SecondsFormat, TimeDelta, TimeZone, Timelike, Utc};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::types::encoding::*;
+use crate::types::{encoding::*, json::*, status_codes::StatusCode, xml::*};
 
 const NANOS_PER_SECOND: i64 = 1_000_000_000;
 const NANOS_PER_TICK: i64 = 100;
@@ -74,17 +74,63 @@ impl BinaryEncoder<DateTime> for DateTime {
     }
 }
 
+impl XmlEncoder for DateTime {
+    fn xml_type_name() -> String {
+        "DateTime".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        if self.is_null() {
+            nil_element(name)
+        } else {
+            Element::new(name).with_text(self.to_rfc3339())
+        }
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let text = element.text.trim();
+        if element.is_nil() || text.is_empty() {
+            Ok(DateTime::null())
+        } else {
+            DateTime::from_str(text).map_err(|_| StatusCode::BadDecodingError)
+        }
+    }
+}
+
+/// DateTime encoded as an ISO 8601 string
+impl JsonEncoder for DateTime {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        serde_to_json(self)
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(DateTime::null())
+        } else {
+            serde_from_json(value)
+        }
+    }
+}
+
 impl Default for DateTime {
     fn default() -> Self {
         DateTime::epoch()
     }
 }
 
+/// Adds a duration, saturating to the endtimes, or the epoch for a negative duration, if the
+/// result cannot be represented
 impl Add<Duration> for DateTime {
     type Output = Self;
 
     fn add(self, duration: Duration) -> Self {
-        DateTime::from(self.date_time + duration)
+        self.checked_add(duration).unwrap_or_else(|| {
+            if duration < Duration::zero() {
+                Self::epoch()
+            } else {
+                Self::endtimes()
+            }
+        })
     }
 }
 
@@ -96,11 +142,19 @@ impl Sub<DateTime> for DateTime {
     }
 }
 
+/// Subtracts a duration, saturating to the epoch, or the endtimes for a negative duration, if the
+/// result cannot be represented
 impl Sub<Duration> for DateTime {
     type Output = Self;
 
     fn sub(self, duration: Duration) -> Self {
-        DateTime::from(self.date_time - duration)
+        self.checked_sub(duration).unwrap_or_else(|| {
+            if duration < Duration::zero() {
+                Self::endtimes()
+            } else {
+                Self::epoch()
+            }
+        })
     }
 }
 
@@ -171,10 +225,9 @@ impl From<i64> for DateTime {
             // Max signifies end times
             Self::endtimes()
         } else {
-            let secs = value / TICKS_PER_SECOND;
-            let nanos = (value - secs * TICKS_PER_SECOND) * NANOS_PER_TICK;
-            let duration = TimeDelta::try_seconds(secs).unwrap() + Duration::nanoseconds(nanos);
-            Self::from(Self::epoch_chrono() + duration)
+            Self::epoch()
+                .checked_add(Self::ticks_to_duration(value))
+                .unwrap_or_else(Self::epoch)
         }
     }
 }
@@ -285,6 +338,20 @@ impl DateTime {
         DateTime::from((year, month, day, hour, minute, second, nanos))
     }
 
+    /// Adds a duration, returning `None` if the result is out of the range of a date time
+    pub fn checked_add(&self, duration: Duration) -> Option<DateTime> {
+        self.date_time
+            .checked_add_signed(duration)
+            .map(DateTime::from)
+    }
+
+    /// Subtracts a duration, returning `None` if the result is out of the range of a date time
+    pub fn checked_sub(&self, duration: Duration) -> Option<DateTime> {
+        self.date_time
+            .checked_sub_signed(duration)
+            .map(DateTime::from)
+    }
+
     /// Returns an RFC 3339 and ISO 8601 date and time string such as 1996-12-19T16:39:57-08:00.
     pub fn to_rfc3339(&self) -> String {
         self.date_time.to_rfc3339_opts(SecondsFormat::Millis, true)
@@ -342,14 +409,24 @@ impl DateTime {
             .unwrap()
     }
 
-    /// Turns a duration to ticks
+    /// Turns a duration to ticks, saturating to `i64::MIN` or `i64::MAX` if there are too many
     fn duration_to_ticks(duration: Duration) -> i64 {
         // We can't directly ask for nanos because it will exceed i64,
-        // so we have to subtract the total seconds before asking for the nano portion
-        let seconds_part = TimeDelta::try_seconds(duration.num_seconds()).unwrap();
-        let seconds = seconds_part.num_seconds();
-        let nanos = (duration - seconds_part).num_nanoseconds().unwrap();
+        // so the seconds and the nano portion are converted separately
+        let seconds = duration.num_seconds();
+        let nanos = duration.subsec_nanos() as i64;
         // Put it back together in ticks
-        seconds * TICKS_PER_SECOND + nanos / NANOS_PER_TICK
+        seconds
+            .checked_mul(TICKS_PER_SECOND)
+            .and_then(|ticks| ticks.checked_add(nanos / NANOS_PER_TICK))
+            .unwrap_or(if seconds < 0 { i64::MIN } else { i64::MAX })
+    }
+
+    /// Turns ticks to a duration
+    fn ticks_to_duration(ticks: i64) -> Duration {
+        let seconds = ticks / TICKS_PER_SECOND;
+        let nanos = (ticks % TICKS_PER_SECOND) * NANOS_PER_TICK;
+        // Any number of ticks is well within the range of a duration
+        TimeDelta::seconds(seconds) + TimeDelta::nanoseconds(nanos)
     }
 }
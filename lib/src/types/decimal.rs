@@ -0,0 +1,336 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the implementation of `Decimal`.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use crate::types::{
+    encoding::*, extension_object::ExtensionObject, node_ids::DataTypeId, status_code::StatusCode,
+    variant::Variant,
+};
+
+/// A decimal number held as a 64-bit integer value and a scale, the number of digits after the
+/// decimal point, so that the number is `value * 10^-scale`. A negative scale multiplies the value
+/// by a power of ten.
+///
+/// Decimal is a standard data type (Part 3 8.54) rather than a built-in type, so a variant holds
+/// it in an [`ExtensionObject`] whose node id is the Decimal data type. The body is the scale
+/// followed by the value as a little endian two's complement integer, whose length is the rest of
+/// the body (Part 6 5.1.7). Values that need more than 64 bits are rejected when decoding.
+///
+/// Decimals compare by the number they represent, so `1.0` equals `1.00`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Decimal {
+    value: i64,
+    scale: i16,
+}
+
+impl BinaryEncoder<Decimal> for Decimal {
+    fn byte_len(&self) -> usize {
+        2 + self.value_bytes().len()
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut size = write_i16(stream, self.scale)?;
+        size += process_encode_io_result(stream.write(&self.value_bytes()))?;
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, _: &DecodingOptions) -> EncodingResult<Self> {
+        let scale = read_i16(stream)?;
+        let mut bytes = Vec::new();
+        process_decode_io_result(stream.read_to_end(&mut bytes))?;
+        let negative = bytes.last().is_some_and(|b| b & 0x80 != 0);
+        let sign_extension = if negative { 0xff } else { 0 };
+        if bytes.len() > 8 && bytes[8..].iter().any(|b| *b != sign_extension) {
+            error!(
+                "Decimal value of {} bytes is too large for a 64-bit value",
+                bytes.len()
+            );
+            return Err(StatusCode::BadDecodingError);
+        }
+        bytes.resize(8, sign_extension);
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&bytes[..8]);
+        Ok(Decimal {
+            value: i64::from_le_bytes(buf),
+            scale,
+        })
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match Self::aligned(self, other) {
+            Some((a, b)) => a.cmp(&b),
+            // Scales too far apart to align are compared approximately
+            None => self
+                .to_f64()
+                .partial_cmp(&other.to_f64())
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.value == 0 {
+            write!(f, "0")
+        } else if self.scale <= 0 {
+            write!(f, "{}", self.value)?;
+            (0..-(self.scale as i32)).try_for_each(|_| write!(f, "0"))
+        } else {
+            let digits = self.value.unsigned_abs().to_string();
+            let scale = self.scale as usize;
+            let digits = if digits.len() <= scale {
+                format!("{}{}", "0".repeat(scale - digits.len() + 1), digits)
+            } else {
+                digits
+            };
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+            let sign = if self.value < 0 { "-" } else { "" };
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ();
+
+    /// Parses a decimal number such as `-12.345`, keeping the number of digits after the point as
+    /// the scale.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let (negative, integer) = match integer.strip_prefix('-') {
+            Some(integer) => (true, integer),
+            None => (false, integer.strip_prefix('+').unwrap_or(integer)),
+        };
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty())
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            error!("Decimal cannot be parsed from string {}", s);
+            return Err(());
+        }
+        let scale = i16::try_from(fraction.len()).map_err(|_| ())?;
+        let digits = format!("{}{}", integer, fraction);
+        let value = i64::from_str(&digits).map_err(|_| {
+            error!("Decimal {} is too large for a 64-bit value", s);
+        })?;
+        Ok(Decimal {
+            value: if negative { -value } else { value },
+            scale,
+        })
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal { value, scale: 0 }
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(value: i32) -> Self {
+        Self::from(value as i64)
+    }
+}
+
+impl From<Decimal> for ExtensionObject {
+    fn from(v: Decimal) -> Self {
+        ExtensionObject::from_encodable(DataTypeId::Decimal, &v)
+    }
+}
+
+impl From<Decimal> for Variant {
+    fn from(v: Decimal) -> Self {
+        ExtensionObject::from(v).into()
+    }
+}
+
+impl TryFrom<&ExtensionObject> for Decimal {
+    type Error = StatusCode;
+
+    fn try_from(v: &ExtensionObject) -> Result<Self, Self::Error> {
+        if v.node_id != DataTypeId::Decimal.into() {
+            Err(StatusCode::BadDataTypeIdUnknown)
+        } else {
+            v.decode_inner(&DecodingOptions::minimal())
+        }
+    }
+}
+
+impl TryFrom<Variant> for Decimal {
+    type Error = ();
+
+    /// Converts a variant holding a decimal, an integer or a string with a decimal number
+    fn try_from(v: Variant) -> Result<Self, Self::Error> {
+        match v {
+            Variant::ExtensionObject(ref v) => Decimal::try_from(v.as_ref()).map_err(|_| ()),
+            Variant::String(ref v) => Decimal::from_str(v.as_ref()),
+            Variant::Float(_) | Variant::Double(_) => Err(()),
+            v => i64::try_from(v).map(Decimal::from),
+        }
+    }
+}
+
+impl Decimal {
+    /// Creates a decimal with the value `value * 10^-scale`
+    pub fn new(value: i64, scale: i16) -> Decimal {
+        Decimal { value, scale }
+    }
+
+    /// Creates a decimal with the scale from a floating point value, rounding it to the number
+    /// of digits of the scale. Returns `None` if the value is not finite or does not fit.
+    pub fn from_f64(value: f64, scale: i16) -> Option<Decimal> {
+        let scaled = (value * 10f64.powi(scale as i32)).round();
+        if scaled.is_finite() && scaled >= i64::MIN as f64 && scaled < i64::MAX as f64 {
+            Some(Decimal {
+                value: scaled as i64,
+                scale,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The unscaled value
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// The number of digits after the decimal point
+    pub fn scale(&self) -> i16 {
+        self.scale
+    }
+
+    /// Returns the nearest floating point value, which may lose precision
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /// Returns the value with the digits after the decimal point rounded half away from zero
+    pub fn round_to_i64(&self) -> Option<i64> {
+        self.rescale(0).map(|v| v.value)
+    }
+
+    /// Returns the same number with another scale. Digits that a smaller scale can't hold are
+    /// rounded half away from zero. Returns `None` if the value does not fit.
+    pub fn rescale(&self, scale: i16) -> Option<Decimal> {
+        let diff = scale as i32 - self.scale as i32;
+        let value = match diff.cmp(&0) {
+            Ordering::Equal => self.value,
+            Ordering::Greater => self.value.checked_mul(Self::power_of_ten(diff)?)?,
+            Ordering::Less => {
+                let divisor = match Self::power_of_ten(-diff) {
+                    Some(divisor) => divisor as i128,
+                    // Every digit is dropped
+                    None => return Some(Decimal { value: 0, scale }),
+                };
+                let value = self.value as i128;
+                let rounded = (value.abs() + divisor / 2) / divisor;
+                i64::try_from(rounded * value.signum()).ok()?
+            }
+        };
+        Some(Decimal { value, scale })
+    }
+
+    /// Adds two decimals, giving a result with the larger of the two scales, or `None` on overflow
+    pub fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let value = self
+            .rescale(scale)?
+            .value
+            .checked_add(other.rescale(scale)?.value)?;
+        Some(Decimal { value, scale })
+    }
+
+    /// Subtracts a decimal, giving a result with the larger of the two scales, or `None` on
+    /// overflow
+    pub fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Multiplies two decimals, giving a result whose scale is the sum of the two scales, or
+    /// `None` on overflow
+    pub fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        Some(Decimal {
+            value: self.value.checked_mul(other.value)?,
+            scale: self.scale.checked_add(other.scale)?,
+        })
+    }
+
+    /// Negates the decimal, or `None` on overflow
+    pub fn checked_neg(&self) -> Option<Decimal> {
+        Some(Decimal {
+            value: self.value.checked_neg()?,
+            scale: self.scale,
+        })
+    }
+
+    /// Returns the same number with trailing zero digits after the decimal point removed
+    pub fn normalize(&self) -> Decimal {
+        let mut result = *self;
+        while result.scale > 0 && result.value % 10 == 0 && result.value != 0 {
+            result.value /= 10;
+            result.scale -= 1;
+        }
+        if result.value == 0 {
+            result.scale = 0;
+        }
+        result
+    }
+
+    fn power_of_ten(exp: i32) -> Option<i64> {
+        10i64.checked_pow(u32::try_from(exp).ok()?)
+    }
+
+    /// Both unscaled values at the larger of the two scales
+    fn aligned(a: &Decimal, b: &Decimal) -> Option<(i128, i128)> {
+        let scale = a.scale.max(b.scale) as i32;
+        let align = |d: &Decimal| {
+            10i128
+                .checked_pow((scale - d.scale as i32) as u32)
+                .and_then(|p| (d.value as i128).checked_mul(p))
+        };
+        Some((align(a)?, align(b)?))
+    }
+
+    /// The value as the fewest little endian two's complement bytes that hold it
+    fn value_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.value.to_le_bytes().to_vec();
+        while bytes.len() > 1 {
+            let last = bytes[bytes.len() - 1];
+            let next_negative = bytes[bytes.len() - 2] & 0x80 != 0;
+            if (last == 0 && !next_negative) || (last == 0xff && next_negative) {
+                bytes.pop();
+            } else {
+                break;
+            }
+        }
+        bytes
+    }
+}
@@ -6,7 +6,7 @@
 
 use std::io::{Read, Write};
 
-use crate::types::{encoding::*, status_codes::StatusCode, string::UAString};
+use crate::types::{encoding::*, json::*, status_codes::StatusCode, string::UAString, xml::*};
 
 bitflags! {
     #[derive(Copy, Clone, Debug, PartialEq)]
@@ -181,6 +181,121 @@ impl BinaryEncoder<DiagnosticInfo> for DiagnosticInfo {
     }
 }
 
+impl XmlEncoder for DiagnosticInfo {
+    fn xml_type_name() -> String {
+        "DiagnosticInfo".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SymbolicId", &self.symbolic_id);
+        write_xml_field(&mut element, "NamespaceUri", &self.namespace_uri);
+        write_xml_field(&mut element, "Locale", &self.locale);
+        write_xml_field(&mut element, "LocalizedText", &self.localized_text);
+        write_xml_field(&mut element, "AdditionalInfo", &self.additional_info);
+        write_xml_field(&mut element, "InnerStatusCode", &self.inner_status_code);
+        write_xml_field(
+            &mut element,
+            "InnerDiagnosticInfo",
+            &self.inner_diagnostic_info,
+        );
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        // Diagnostic info is depth checked to prevent deep recursion
+        let _depth_lock = decoding_options.depth_lock()?;
+        Ok(DiagnosticInfo {
+            symbolic_id: read_xml_field(element, "SymbolicId", decoding_options)?,
+            namespace_uri: read_xml_field(element, "NamespaceUri", decoding_options)?,
+            locale: read_xml_field(element, "Locale", decoding_options)?,
+            localized_text: read_xml_field(element, "LocalizedText", decoding_options)?,
+            additional_info: read_xml_field(element, "AdditionalInfo", decoding_options)?,
+            inner_status_code: read_xml_field(element, "InnerStatusCode", decoding_options)?,
+            inner_diagnostic_info: read_xml_field(
+                element,
+                "InnerDiagnosticInfo",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl XmlEncoder for DiagnosticBits {
+    fn xml_type_name() -> String {
+        "UInt32".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DiagnosticBits::from_bits_truncate(u32::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+/// DiagnosticInfo encoded as an object with a field for each of its parts that is present
+impl JsonEncoder for DiagnosticInfo {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SymbolicId", &self.symbolic_id, encoding);
+        write_json_field(&mut object, "NamespaceUri", &self.namespace_uri, encoding);
+        write_json_field(&mut object, "Locale", &self.locale, encoding);
+        write_json_field(&mut object, "LocalizedText", &self.localized_text, encoding);
+        write_json_field(
+            &mut object,
+            "AdditionalInfo",
+            &self.additional_info,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "InnerStatusCode",
+            &self.inner_status_code,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "InnerDiagnosticInfo",
+            &self.inner_diagnostic_info,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        // Diagnostic info is depth checked to prevent deep recursion
+        let _depth_lock = decoding_options.depth_lock()?;
+        check_json_object(value)?;
+        Ok(DiagnosticInfo {
+            symbolic_id: read_json_field(value, "SymbolicId", decoding_options)?,
+            namespace_uri: read_json_field(value, "NamespaceUri", decoding_options)?,
+            locale: read_json_field(value, "Locale", decoding_options)?,
+            localized_text: read_json_field(value, "LocalizedText", decoding_options)?,
+            additional_info: read_json_field(value, "AdditionalInfo", decoding_options)?,
+            inner_status_code: read_json_field(value, "InnerStatusCode", decoding_options)?,
+            inner_diagnostic_info: read_json_field(value, "InnerDiagnosticInfo", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DiagnosticBits {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DiagnosticBits::from_bits_truncate(u32::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 impl Default for DiagnosticInfo {
     fn default() -> Self {
         DiagnosticInfo::null()
@@ -112,6 +112,9 @@ pub struct DecodingOptions {
     pub max_array_length: usize,
     /// Decoding depth gauge is used to check for recursion
     pub decoding_depth_gauge: Arc<DepthGauge>,
+    /// Decodes empty arrays, strings and byte strings as null so that values which only differ
+    /// in how they express "nothing" decode to the same thing. Used for canonical encoding.
+    pub canonical: bool,
 }
 
 impl Default for DecodingOptions {
@@ -124,6 +127,7 @@ impl Default for DecodingOptions {
             max_byte_string_length: constants::MAX_BYTE_STRING_LENGTH,
             max_array_length: constants::MAX_ARRAY_LENGTH,
             decoding_depth_gauge: Arc::new(DepthGauge::default()),
+            canonical: false,
         }
     }
 }
@@ -141,6 +145,15 @@ impl DecodingOptions {
         }
     }
 
+    /// Options for decoding a value into its canonical form. Empty arrays, strings and byte
+    /// strings are decoded as null.
+    pub fn canonical() -> Self {
+        DecodingOptions {
+            canonical: true,
+            ..Default::default()
+        }
+    }
+
     /// For test only. Having a separate function makes it easier to control calls to DecodingOptions::default().
     #[cfg(test)]
     pub fn test() -> Self {
@@ -226,7 +239,7 @@ pub fn read_array<S: Read, T: BinaryEncoder<T>>(
     decoding_options: &DecodingOptions,
 ) -> EncodingResult<Option<Vec<T>>> {
     let len = read_i32(stream)?;
-    if len == -1 {
+    if len == -1 || (len == 0 && decoding_options.canonical) {
         Ok(None)
     } else if len < -1 {
         error!("Array length is negative value and invalid");
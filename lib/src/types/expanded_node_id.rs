@@ -17,9 +17,11 @@ use crate::types::{
     byte_string::ByteString,
     encoding::*,
     guid::Guid,
+    json::*,
     node_id::{Identifier, NodeId},
     status_codes::StatusCode,
     string::*,
+    xml::*,
 };
 
 /// A NodeId that allows the namespace URI to be specified instead of an index.
@@ -315,6 +317,49 @@ impl BinaryEncoder<ExpandedNodeId> for ExpandedNodeId {
     }
 }
 
+/// A node id is encoded as its string form in an `Identifier` element
+impl XmlEncoder for ExpandedNodeId {
+    fn xml_type_name() -> String {
+        "ExpandedNodeId".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        if self.is_null() {
+            nil_element(name)
+        } else {
+            Element::new(name).with_child(Element::new("Identifier").with_text(self))
+        }
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        match element.child_text("Identifier") {
+            None | Some("") => Ok(ExpandedNodeId::null()),
+            Some(v) => ExpandedNodeId::from_str(v).map_err(|_| {
+                error!("Invalid ExpandedNodeId value \"{}\"", v);
+                StatusCode::BadDecodingError
+            }),
+        }
+    }
+}
+
+impl JsonEncoder for ExpandedNodeId {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            serde_to_json(self)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(ExpandedNodeId::null())
+        } else {
+            serde_from_json(value)
+        }
+    }
+}
+
 impl<'a> Into<ExpandedNodeId> for &'a NodeId {
     fn into(self) -> ExpandedNodeId {
         self.clone().into()
@@ -353,21 +398,21 @@ impl From<NodeId> for ExpandedNodeId {
 
 impl fmt::Display for ExpandedNodeId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The server index is omitted when it is 0, i.e. the local server
+        if self.server_index != 0 {
+            write!(f, "svr={};", self.server_index)?;
+        }
         // Formatted depending on the namespace uri being empty or not.
         if self.namespace_uri.is_empty() {
             // svr=<serverindex>;ns=<namespaceindex>;<type>=<value>
-            write!(f, "svr={};{}", self.server_index, self.node_id)
+            write!(f, "{}", self.node_id)
         } else {
             // The % and ; chars have to be escaped out in the uri
             let namespace_uri = String::from(self.namespace_uri.as_ref())
                 .replace('%', "%25")
                 .replace(';', "%3b");
             // svr=<serverindex>;nsu=<uri>;<type>=<value>
-            write!(
-                f,
-                "svr={};nsu={};{}",
-                self.server_index, namespace_uri, self.node_id.identifier
-            )
+            write!(f, "nsu={};{}", namespace_uri, self.node_id.identifier)
         }
     }
 }
@@ -382,30 +427,31 @@ impl FromStr for ExpandedNodeId {
         // svr=<serverindex>;ns=<namespaceindex>;<type>=<value>
         // or
         // svr=<serverindex>;nsu=<uri>;<type>=<value>
+        //
+        // If server index == 0, the svr=0; will be omitted, as will ns=0;
 
         lazy_static! {
-            // Contains capture groups "svr", either "ns" or "nsu" and then "t" for type
-            static ref RE: Regex = Regex::new(r"^svr=(?P<svr>[0-9]+);(ns=(?P<ns>[0-9]+)|nsu=(?P<nsu>[^;]+));(?P<t>[isgb]=.+)$").unwrap();
+            // Contains capture groups "svr", "ns" or "nsu" and then "t" for type
+            static ref RE: Regex = Regex::new(r"^(svr=(?P<svr>[0-9]+);)?(ns=(?P<ns>[0-9]+);|nsu=(?P<nsu>[^;]+);)?(?P<t>[isgb]=.+)$").unwrap();
         }
 
         let captures = RE.captures(s).ok_or(StatusCode::BadNodeIdInvalid)?;
 
-        // Server index
-        let server_index = captures
-            .name("svr")
-            .ok_or(StatusCode::BadNodeIdInvalid)
-            .and_then(|server_index| {
-                server_index
-                    .as_str()
-                    .parse::<u32>()
-                    .map_err(|_| StatusCode::BadNodeIdInvalid)
-            })?;
+        // Server index (optional)
+        let server_index = if let Some(svr) = captures.name("svr") {
+            svr.as_str()
+                .parse::<u32>()
+                .map_err(|_| StatusCode::BadNodeIdInvalid)?
+        } else {
+            0
+        };
 
         // Check for namespace uri
         let namespace_uri = if let Some(nsu) = captures.name("nsu") {
-            // The % and ; chars need to be unescaped
+            // The % and ; chars need to be unescaped, in either case
             let nsu = String::from(nsu.as_str())
                 .replace("%3b", ";")
+                .replace("%3B", ";")
                 .replace("%25", "%");
             UAString::from(nsu)
         } else {
@@ -433,6 +479,27 @@ impl FromStr for ExpandedNodeId {
 }
 
 impl ExpandedNodeId {
+    /// Returns the node id on the local server, finding the namespace index of a namespace uri
+    /// in the namespace array, e.g. the value of the server's `NamespaceArray` variable. Returns
+    /// `None` for a node on another server or a namespace uri that is not in the array.
+    pub fn to_node_id<T>(&self, namespaces: &[T]) -> Option<NodeId>
+    where
+        T: AsRef<str>,
+    {
+        if self.server_index != 0 {
+            None
+        } else if self.namespace_uri.is_empty() {
+            Some(self.node_id.clone())
+        } else {
+            let namespace_uri = self.namespace_uri.as_ref();
+            namespaces
+                .iter()
+                .position(|ns| ns.as_ref() == namespace_uri)
+                .and_then(|namespace| u16::try_from(namespace).ok())
+                .map(|namespace| NodeId::new(namespace, self.node_id.identifier.clone()))
+        }
+    }
+
     /// Creates an expanded node id from a node id
     pub fn new<T>(value: T) -> ExpandedNodeId
     where
@@ -11,8 +11,8 @@ use std::{
 };
 
 use super::{
-    byte_string::ByteString, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    status_codes::StatusCode, string::XmlElement,
+    byte_string::ByteString, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    status_codes::StatusCode, string::XmlElement, xml::*,
 };
 
 #[derive(Debug)]
@@ -37,6 +37,17 @@ pub enum ExtensionObjectEncoding {
     XmlElement(XmlElement),
 }
 
+/// A structure that is defined by an application rather than by OPC UA, with node ids for its
+/// data type and the default binary encoding that identifies it in an [`ExtensionObject`].
+/// Implement it with `#[derive(UaStructure)]`, which also implements [`BinaryEncoder`].
+pub trait UaStructure: BinaryEncoder<Self> + Sized {
+    /// The node id of the data type of the structure
+    fn data_type_id() -> NodeId;
+
+    /// The node id of the default binary encoding of the structure
+    fn encoding_id() -> NodeId;
+}
+
 /// An extension object holds a serialized object identified by its node id.
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -112,6 +123,103 @@ impl BinaryEncoder<ExtensionObject> for ExtensionObject {
     }
 }
 
+/// The body of an extension object is either a `ByteString` element with the binary encoding of
+/// the value, or the XML encoding of the value itself
+impl XmlEncoder for ExtensionObject {
+    fn xml_type_name() -> String {
+        "ExtensionObject".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        if self.is_null() {
+            return nil_element(name);
+        }
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "TypeId", &self.node_id);
+        match self.body {
+            ExtensionObjectEncoding::None => {}
+            ExtensionObjectEncoding::ByteString(ref value) => {
+                element = element
+                    .with_child(Element::new("Body").with_child(value.encode_xml("ByteString")));
+            }
+            ExtensionObjectEncoding::XmlElement(ref value) => match parse(value.as_ref()) {
+                Ok(body) => element = element.with_child(Element::new("Body").with_child(body)),
+                Err(err) => error!("Cannot write the XML body of an extension object - {}", err),
+            },
+        }
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if element.is_nil() {
+            return Ok(ExtensionObject::null());
+        }
+        let node_id = read_xml_field(element, "TypeId", decoding_options)?;
+        let body = match element.child("Body").and_then(|body| body.children.first()) {
+            None => ExtensionObjectEncoding::None,
+            Some(body) if body.name == "ByteString" => {
+                ExtensionObjectEncoding::ByteString(ByteString::decode_xml(body, decoding_options)?)
+            }
+            Some(body) => ExtensionObjectEncoding::XmlElement(XmlElement::from(body.to_fragment())),
+        };
+        Ok(ExtensionObject { node_id, body })
+    }
+}
+
+/// ExtensionObject encoded as an object with its type id, encoding and body for the reversible
+/// encoding, and as just its body for the non-reversible encoding. Binary bodies are written as
+/// base64 strings, and XML bodies as strings.
+impl JsonEncoder for ExtensionObject {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let (encoding_type, body) = match self.body {
+            ExtensionObjectEncoding::None => (0, JsonValue::Null),
+            ExtensionObjectEncoding::ByteString(ref value) => (1, value.encode_json(encoding)),
+            ExtensionObjectEncoding::XmlElement(ref value) => (2, value.encode_json(encoding)),
+        };
+        match encoding {
+            JsonEncoding::Reversible => {
+                if self.is_null() {
+                    return JsonValue::Null;
+                }
+                let mut object = JsonObject::new();
+                write_json_field(&mut object, "TypeId", &self.node_id, encoding);
+                if encoding_type != 0 {
+                    write_json_field(&mut object, "Encoding", &encoding_type, encoding);
+                    if !body.is_null() {
+                        object.insert("Body".to_string(), body);
+                    }
+                }
+                JsonValue::Object(object)
+            }
+            JsonEncoding::NonReversible => body,
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        let node_id = read_json_field(value, "TypeId", decoding_options)?;
+        let encoding_type: u8 = read_json_field(value, "Encoding", decoding_options)?;
+        let body = value.get("Body").unwrap_or(&JsonValue::Null);
+        let body = match encoding_type {
+            0 if body.is_null() => ExtensionObjectEncoding::None,
+            1 => ExtensionObjectEncoding::ByteString(ByteString::decode_json(
+                body,
+                decoding_options,
+            )?),
+            2 => ExtensionObjectEncoding::XmlElement(XmlElement::decode_json(
+                body,
+                decoding_options,
+            )?),
+            _ => {
+                // A body that is a JSON object would need the type to be known to decode it
+                error!("Unsupported extension object encoding {}", encoding_type);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(ExtensionObject { node_id, body })
+    }
+}
+
 impl ExtensionObject {
     /// Creates a null extension object, i.e. one with no value or payload
     pub fn null() -> ExtensionObject {
@@ -178,4 +286,61 @@ impl ExtensionObject {
             }
         }
     }
+
+    /// Creates an extension object with the XML encoding of a value as its body. The node id
+    /// should be the id of the XML encoding of the type, e.g. `i=297` for `Argument`.
+    pub fn from_xml_encodable<N, T>(node_id: N, encodable: &T) -> ExtensionObject
+    where
+        N: Into<NodeId>,
+        T: XmlEncoder,
+    {
+        let body = encodable.encode_xml(&T::xml_type_name()).to_fragment();
+        ExtensionObject {
+            node_id: node_id.into(),
+            body: ExtensionObjectEncoding::XmlElement(XmlElement::from(body)),
+        }
+    }
+
+    /// Decodes the XML body of the extension object and returns it. The node id is ignored for
+    /// decoding. Errors, including a body that is not XML, result in a decoding error.
+    pub fn decode_inner_xml<T>(&self, decoding_options: &DecodingOptions) -> EncodingResult<T>
+    where
+        T: XmlEncoder,
+    {
+        match self.body {
+            ExtensionObjectEncoding::XmlElement(ref xml) => {
+                T::from_xml(xml.as_ref(), decoding_options)
+            }
+            _ => {
+                error!("decode_inner_xml called on an ExtensionObject without an XML body");
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    /// Creates an extension object holding a structure, identified by its encoding id
+    pub fn from_structure<T>(structure: &T) -> ExtensionObject
+    where
+        T: UaStructure,
+    {
+        Self::from_encodable(T::encoding_id(), structure)
+    }
+
+    /// Decodes a structure from the extension object, providing the extension object holds
+    /// the default binary encoding of the structure.
+    pub fn decode_structure<T>(&self, decoding_options: &DecodingOptions) -> EncodingResult<T>
+    where
+        T: UaStructure,
+    {
+        if self.node_id != T::encoding_id() {
+            error!(
+                "Extension object {} does not hold a structure with encoding {}",
+                self.node_id,
+                T::encoding_id()
+            );
+            Err(StatusCode::BadDataTypeIdUnknown)
+        } else {
+            self.decode_inner(decoding_options)
+        }
+    }
 }
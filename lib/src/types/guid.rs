@@ -13,7 +13,7 @@ use std::{
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
-use crate::types::encoding::*;
+use crate::types::{encoding::*, json::*, status_codes::StatusCode, xml::*};
 
 /// A Guid is a 16 byte Globally Unique Identifier.
 #[derive(Eq, PartialEq, Clone, Hash)]
@@ -73,6 +73,41 @@ impl BinaryEncoder<Guid> for Guid {
     }
 }
 
+/// A guid is encoded as its string form in a `String` element
+impl XmlEncoder for Guid {
+    fn xml_type_name() -> String {
+        "Guid".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        Element::new(name).with_child(Element::new("String").with_text(self))
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        match element.child_text("String") {
+            None | Some("") => Ok(Guid::null()),
+            Some(v) => Guid::from_str(v).map_err(|_| {
+                error!("Invalid Guid value \"{}\"", v);
+                StatusCode::BadDecodingError
+            }),
+        }
+    }
+}
+
+impl JsonEncoder for Guid {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        serde_to_json(self)
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(Guid::null())
+        } else {
+            serde_from_json(value)
+        }
+    }
+}
+
 impl FromStr for Guid {
     type Err = ();
 
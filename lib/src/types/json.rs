@@ -0,0 +1,351 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the `JsonEncoder` trait for the OPC UA JSON encoding described in Part 6 5.4, and
+//! implementations of it for primitives and arrays.
+//!
+//! There are two forms of the encoding:
+//!
+//! * Reversible, which keeps the type information that is needed to decode a value back into the
+//!   same type, e.g. the built-in type of a `Variant` or the type id of an `ExtensionObject`.
+//! * Non-reversible, which is meant for consumers that only read the values, e.g. a dashboard. It
+//!   writes a `Variant` as its value alone, a `LocalizedText` as its text, a `StatusCode` with its
+//!   symbolic name and an enumeration as its name and value.
+//!
+//! Only the reversible encoding can be decoded. Namespace and server indexes are always written
+//! as numbers since the encoder does not know the namespace and server arrays to look up their
+//! URIs.
+//!
+//! Structures are encoded as JSON objects with a field per structure field, named as it is in the
+//! OPC UA schema. Fields that are null are omitted and a missing field is decoded as its default
+//! value.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub use serde_json::Value as JsonValue;
+
+use crate::types::{encoding::*, status_codes::StatusCode};
+
+/// The fields of a JSON object
+pub type JsonObject = serde_json::Map<String, JsonValue>;
+
+/// The form of the JSON encoding
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum JsonEncoding {
+    /// The encoding that preserves enough information to decode the value back
+    Reversible,
+    /// The encoding that is simpler to consume but which cannot be decoded back
+    NonReversible,
+}
+
+/// OPC UA JSON encoding interface. Anything that encodes to JSON must implement this. Values are
+/// encoded to and decoded from a `serde_json::Value`, and the `to_json` and `from_json` functions
+/// convert them to and from a string.
+pub trait JsonEncoder: Sized {
+    /// Encodes the value to JSON in the requested form
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue;
+
+    /// Decodes a value from its reversible JSON encoding. The decoding options restrict the
+    /// length of strings, arrays and the depth of recursive values in the same way as for binary.
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self>;
+
+    /// Encodes the value to a JSON string in the requested form
+    fn to_json(&self, encoding: JsonEncoding) -> String {
+        self.encode_json(encoding).to_string()
+    }
+
+    /// Decodes a value from a string holding its reversible JSON encoding
+    fn from_json(json: &str, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = serde_json::from_str::<JsonValue>(json).map_err(|err| {
+            error!("Cannot parse JSON - {}", err);
+            StatusCode::BadDecodingError
+        })?;
+        Self::decode_json(&value, decoding_options)
+    }
+}
+
+/// Writes a field of a structure to the JSON object, unless the value is null
+pub fn write_json_field<T>(object: &mut JsonObject, name: &str, value: &T, encoding: JsonEncoding)
+where
+    T: JsonEncoder,
+{
+    let value = value.encode_json(encoding);
+    if !value.is_null() {
+        object.insert(name.to_string(), value);
+    }
+}
+
+/// Reads a field of a structure from the JSON object. A missing field is decoded from null, i.e.
+/// as the default value of its type.
+pub fn read_json_field<T>(
+    value: &JsonValue,
+    name: &str,
+    decoding_options: &DecodingOptions,
+) -> EncodingResult<T>
+where
+    T: JsonEncoder,
+{
+    T::decode_json(
+        value.get(name).unwrap_or(&JsonValue::Null),
+        decoding_options,
+    )
+}
+
+/// Tests that the JSON value of a structure is an object, or null for a structure whose fields
+/// all have default values.
+pub fn check_json_object(value: &JsonValue) -> EncodingResult<()> {
+    if value.is_object() || value.is_null() {
+        Ok(())
+    } else {
+        error!("Expected a JSON object, got {}", value);
+        Err(StatusCode::BadDecodingError)
+    }
+}
+
+/// Writes an enumeration, as a number for the reversible encoding and as `<name>_<value>` for the
+/// non-reversible encoding.
+pub fn write_json_enum(name: &str, value: i64, encoding: JsonEncoding) -> JsonValue {
+    match encoding {
+        JsonEncoding::Reversible => JsonValue::from(value),
+        JsonEncoding::NonReversible => JsonValue::from(format!("{}_{}", name, value)),
+    }
+}
+
+/// Reads the value of an enumeration
+pub fn read_json_enum(
+    value: &JsonValue,
+    decoding_options: &DecodingOptions,
+) -> EncodingResult<i64> {
+    i64::decode_json(value, decoding_options)
+}
+
+/// Converts a value that serializes to its reversible JSON encoding through serde
+pub(crate) fn serde_to_json<T>(value: &T) -> JsonValue
+where
+    T: Serialize,
+{
+    serde_json::to_value(value).unwrap_or(JsonValue::Null)
+}
+
+/// Converts a value that deserializes from its reversible JSON encoding through serde
+pub(crate) fn serde_from_json<T>(value: &JsonValue) -> EncodingResult<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value).map_err(|err| {
+        error!("Cannot decode JSON value {} - {}", value, err);
+        StatusCode::BadDecodingError
+    })
+}
+
+/// Reads a string out of the JSON value, checking it against the maximum string length
+pub(crate) fn json_str(value: &JsonValue, max_length: usize) -> EncodingResult<&str> {
+    let v = value.as_str().ok_or_else(|| {
+        error!("Expected a JSON string, got {}", value);
+        StatusCode::BadDecodingError
+    })?;
+    if v.len() > max_length {
+        error!(
+            "String length {} exceeds decoding limit {}",
+            v.len(),
+            max_length
+        );
+        Err(StatusCode::BadDecodingError)
+    } else {
+        Ok(v)
+    }
+}
+
+impl JsonEncoder for bool {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        JsonValue::Bool(*self)
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+        match value {
+            JsonValue::Null => Ok(false),
+            JsonValue::Bool(v) => Ok(*v),
+            v => {
+                error!("Expected a JSON boolean, got {}", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Integers up to 32-bits are encoded as JSON numbers
+macro_rules! json_integer {
+    ($t: ty) => {
+        impl JsonEncoder for $t {
+            fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+                JsonValue::from(*self)
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+                match value {
+                    JsonValue::Null => Ok(0),
+                    JsonValue::Number(v) => v
+                        .as_i64()
+                        .and_then(|v| <$t>::try_from(v).ok())
+                        .ok_or_else(|| {
+                            error!("Value {} is out of range for {}", v, stringify!($t));
+                            StatusCode::BadDecodingError
+                        }),
+                    v => {
+                        error!("Expected a JSON number, got {}", v);
+                        Err(StatusCode::BadDecodingError)
+                    }
+                }
+            }
+        }
+    };
+}
+
+json_integer!(i8);
+json_integer!(u8);
+json_integer!(i16);
+json_integer!(u16);
+json_integer!(i32);
+json_integer!(u32);
+
+/// 64-bit integers are encoded as JSON strings because they cannot all be represented as a JSON
+/// number, which is a double. A number is accepted when decoding.
+macro_rules! json_integer_64 {
+    ($t: ty) => {
+        impl JsonEncoder for $t {
+            fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+                JsonValue::from(self.to_string())
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+                let v = match value {
+                    JsonValue::Null => Some(0),
+                    JsonValue::String(v) => v.parse::<$t>().ok(),
+                    JsonValue::Number(v) => v.to_string().parse::<$t>().ok(),
+                    _ => None,
+                };
+                v.ok_or_else(|| {
+                    error!("Expected a {} value, got {}", stringify!($t), value);
+                    StatusCode::BadDecodingError
+                })
+            }
+        }
+    };
+}
+
+json_integer_64!(i64);
+json_integer_64!(u64);
+
+const VALUE_INFINITY: &str = "Infinity";
+const VALUE_NEG_INFINITY: &str = "-Infinity";
+const VALUE_NAN: &str = "NaN";
+
+/// Floats and doubles are encoded as JSON numbers, except for the special values which are
+/// encoded as strings
+macro_rules! json_float {
+    ($t: ty) => {
+        impl JsonEncoder for $t {
+            fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+                if self.is_nan() {
+                    JsonValue::from(VALUE_NAN)
+                } else if *self == <$t>::INFINITY {
+                    JsonValue::from(VALUE_INFINITY)
+                } else if *self == <$t>::NEG_INFINITY {
+                    JsonValue::from(VALUE_NEG_INFINITY)
+                } else {
+                    JsonValue::from(*self)
+                }
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+                let v = match value {
+                    JsonValue::Null => Some(0.0),
+                    JsonValue::Number(v) => v.as_f64().map(|v| v as $t),
+                    JsonValue::String(v) => match v.as_str() {
+                        VALUE_NAN => Some(<$t>::NAN),
+                        VALUE_INFINITY => Some(<$t>::INFINITY),
+                        VALUE_NEG_INFINITY => Some(<$t>::NEG_INFINITY),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                v.ok_or_else(|| {
+                    error!("Expected a {} value, got {}", stringify!($t), value);
+                    StatusCode::BadDecodingError
+                })
+            }
+        }
+    };
+}
+
+json_float!(f32);
+json_float!(f64);
+
+/// Arrays are encoded as JSON arrays
+impl<T> JsonEncoder for Vec<T>
+where
+    T: JsonEncoder,
+{
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        JsonValue::Array(self.iter().map(|v| v.encode_json(encoding)).collect())
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        match value {
+            JsonValue::Null => Ok(Vec::new()),
+            JsonValue::Array(values) => {
+                if values.len() > decoding_options.max_array_length {
+                    error!(
+                        "Array length {} exceeds decoding limit {}",
+                        values.len(),
+                        decoding_options.max_array_length
+                    );
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    values
+                        .iter()
+                        .map(|v| T::decode_json(v, decoding_options))
+                        .collect()
+                }
+            }
+            v => {
+                error!("Expected a JSON array, got {}", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Optional values, including null arrays, are encoded as null when they are `None`
+impl<T> JsonEncoder for Option<T>
+where
+    T: JsonEncoder,
+{
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.as_ref()
+            .map(|v| v.encode_json(encoding))
+            .unwrap_or(JsonValue::Null)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            T::decode_json(value, decoding_options).map(Some)
+        }
+    }
+}
+
+impl<T> JsonEncoder for Box<T>
+where
+    T: JsonEncoder,
+{
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.as_ref().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        T::decode_json(value, decoding_options).map(Box::new)
+    }
+}
@@ -8,7 +8,7 @@ use std::{
     io::{Read, Write},
 };
 
-use crate::types::{encoding::*, string::*};
+use crate::types::{encoding::*, json::*, string::*, xml::*};
 
 /// JSON encoding
 ///  Locale    The Localeportion of LocalizedTextvalues shall be encoded as a JSON string
@@ -109,6 +109,53 @@ impl BinaryEncoder<LocalizedText> for LocalizedText {
     }
 }
 
+impl XmlEncoder for LocalizedText {
+    fn xml_type_name() -> String {
+        "LocalizedText".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Locale", &self.locale);
+        write_xml_field(&mut element, "Text", &self.text);
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(LocalizedText {
+            locale: read_xml_field(element, "Locale", decoding_options)?,
+            text: read_xml_field(element, "Text", decoding_options)?,
+        })
+    }
+}
+
+/// LocalizedText encoded as an object with its locale and text for the reversible encoding, and
+/// as just the text for the non-reversible encoding
+impl JsonEncoder for LocalizedText {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        match encoding {
+            JsonEncoding::Reversible => {
+                if self.locale.is_null() && self.text.is_null() {
+                    return JsonValue::Null;
+                }
+                let mut object = JsonObject::new();
+                write_json_field(&mut object, "Locale", &self.locale, encoding);
+                write_json_field(&mut object, "Text", &self.text, encoding);
+                JsonValue::Object(object)
+            }
+            JsonEncoding::NonReversible => self.text.encode_json(encoding),
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(LocalizedText {
+            locale: read_json_field(value, "Locale", decoding_options)?,
+            text: read_json_field(value, "Text", decoding_options)?,
+        })
+    }
+}
+
 impl LocalizedText {
     pub fn new(locale: &str, text: &str) -> LocalizedText {
         LocalizedText {
@@ -17,10 +17,27 @@
 pub mod profiles {
     pub const TRANSPORT_PROFILE_URI_BINARY: &str =
         "http://opcfoundation.org/UA-Profile/Transport/uatcp-uasc-uabinary";
+    pub const TRANSPORT_PROFILE_URI_WSS_BINARY: &str =
+        "http://opcfoundation.org/UA-Profile/Transport/wss-uasc-uabinary";
+    pub const TRANSPORT_PROFILE_URI_HTTPS_BINARY: &str =
+        "http://opcfoundation.org/UA-Profile/Transport/https-uabinary";
     pub const SECURITY_USER_TOKEN_POLICY_ANONYMOUS: &str =
         "http://opcfoundation.org/UA-Profile/Security/UserToken/Anonymous";
     pub const SECURITY_USER_TOKEN_POLICY_USERPASS: &str =
-        "http://opcfoundation.org/UA-Profile/ Security/UserToken-Server/UserNamePassword";
+        "http://opcfoundation.org/UA-Profile/Security/UserToken/Server/UserNamePassword";
+    pub const SECURITY_USER_TOKEN_POLICY_X509: &str =
+        "http://opcfoundation.org/UA-Profile/Security/UserToken/Server/X509Certificate";
+    pub const SERVER_PROFILE_URI_BEHAVIOUR: &str =
+        "http://opcfoundation.org/UA-Profile/Server/Behaviour";
+    pub const SERVER_PROFILE_URI_EMBEDDED_UA: &str =
+        "http://opcfoundation.org/UA-Profile/Server/EmbeddedUA";
+    pub const SERVER_FACET_URI_STANDARD_DATA_CHANGE_SUBSCRIPTION: &str =
+        "http://opcfoundation.org/UA-Profile/Server/StandardDataChangeSubscription";
+    pub const SERVER_FACET_URI_METHODS: &str = "http://opcfoundation.org/UA-Profile/Server/Methods";
+    pub const SERVER_FACET_URI_HISTORICAL_RAW_DATA: &str =
+        "http://opcfoundation.org/UA-Profile/Server/HistoricalRawData";
+    pub const SERVER_FACET_URI_HISTORICAL_EVENTS: &str =
+        "http://opcfoundation.org/UA-Profile/Server/HistoricalEvents";
 }
 
 pub mod constants {
@@ -99,6 +116,7 @@ bitflags! {
 //
 // See Part 3, Table 43
 bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub struct WriteMask: u32 {
         /// Indicates if the AccessLevel Attribute is writable.
         const ACCESS_LEVEL = 1;
@@ -201,13 +219,16 @@ pub mod byte_string;
 pub mod data_types;
 pub mod data_value;
 pub mod date_time;
+pub mod decimal;
 pub mod diagnostic_info;
 pub mod encoding;
 pub mod expanded_node_id;
 pub mod extension_object;
 pub mod guid;
+pub mod json;
 pub mod localized_text;
 pub mod node_id;
+pub mod node_snapshot;
 pub mod notification_message;
 pub mod numeric_range;
 pub mod operand;
@@ -217,17 +238,25 @@ pub mod request_header;
 pub mod response_header;
 pub mod status_code;
 pub mod string;
+pub mod struct_value;
+pub mod testing;
 pub mod variant;
 pub mod variant_json;
 pub mod variant_type_id;
+pub mod variant_xml;
+pub mod xml;
 
 pub use crate::types::{
     argument::*, array::*, attribute::*, basic_types::*, byte_string::*, data_types::*,
-    data_value::*, date_time::*, diagnostic_info::*, encoding::*, expanded_node_id::*,
-    extension_object::*, guid::*, localized_text::*, node_id::*, node_ids::*, numeric_range::*,
-    operand::*, qualified_name::*, request_header::*, response_header::*, service_types::*,
-    status_code::*, string::*, variant::*, variant_type_id::*,
+    data_value::*, date_time::*, decimal::*, diagnostic_info::*, encoding::*, expanded_node_id::*,
+    extension_object::*, guid::*, json::*, localized_text::*, node_id::*, node_ids::*,
+    node_snapshot::*, numeric_range::*, operand::*, qualified_name::*, request_header::*,
+    response_header::*, service_types::*, status_code::*, string::*, struct_value::*, variant::*,
+    variant_type_id::*, xml::*,
 };
 
+/// Derives [`BinaryEncoder`] and [`UaStructure`] for a user defined structure
+pub use opcua_macros::UaStructure;
+
 #[cfg(test)]
 mod tests;
@@ -20,9 +20,11 @@ use crate::types::{
     byte_string::ByteString,
     encoding::*,
     guid::Guid,
-    node_ids::{ObjectId, ReferenceTypeId},
+    json::*,
+    node_ids::{DataTypeId, ObjectId, ReferenceTypeId},
     status_codes::StatusCode,
     string::*,
+    xml::*,
 };
 
 /// The kind of identifier, numeric, string, guid or byte
@@ -375,6 +377,49 @@ impl BinaryEncoder<NodeId> for NodeId {
     }
 }
 
+/// A node id is encoded as its string form in an `Identifier` element
+impl XmlEncoder for NodeId {
+    fn xml_type_name() -> String {
+        "NodeId".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        if self.is_null() {
+            nil_element(name)
+        } else {
+            Element::new(name).with_child(Element::new("Identifier").with_text(self))
+        }
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        match element.child_text("Identifier") {
+            None | Some("") => Ok(NodeId::null()),
+            Some(v) => NodeId::from_str(v).map_err(|_| {
+                error!("Invalid NodeId value \"{}\"", v);
+                StatusCode::BadDecodingError
+            }),
+        }
+    }
+}
+
+impl JsonEncoder for NodeId {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            serde_to_json(self)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(NodeId::null())
+        } else {
+            serde_from_json(value)
+        }
+    }
+}
+
 impl FromStr for NodeId {
     type Err = StatusCode;
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -527,6 +572,16 @@ impl NodeId {
         }
     }
 
+    /// Extracts a DataTypeId from a node id, providing the node id holds a data type id
+    pub fn as_data_type_id(&self) -> std::result::Result<DataTypeId, NodeIdError> {
+        match self.identifier {
+            Identifier::Numeric(id) if self.namespace == 0 => {
+                DataTypeId::try_from(id).map_err(|_| NodeIdError)
+            }
+            _ => Err(NodeIdError),
+        }
+    }
+
     pub fn as_reference_type_id(&self) -> std::result::Result<ReferenceTypeId, NodeIdError> {
         // TODO this function should not exist - filter code should work with non ns 0 reference
         // types
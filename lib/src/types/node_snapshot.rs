@@ -0,0 +1,232 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains `NodeSnapshot`, the attributes of a node gathered into one typed struct.
+//!
+//! A client that wants to show a node, e.g. in a browser, otherwise has to read each attribute
+//! its class defines and convert every value from a `Variant` itself.
+
+use crate::types::{
+    attribute::AttributeId,
+    data_value::DataValue,
+    encoding::DecodingOptions,
+    localized_text::LocalizedText,
+    node_id::NodeId,
+    qualified_name::QualifiedName,
+    service_types::{NodeClass, RolePermissionType},
+    status_code::StatusCode,
+    struct_value::DataTypeDefinition,
+    variant::Variant,
+    WriteMask,
+};
+
+/// The attributes of a node at the time they were read. An attribute that the class of the node
+/// does not define, or that is optional and the node does not have, is `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSnapshot {
+    pub node_id: NodeId,
+    pub node_class: NodeClass,
+    pub browse_name: QualifiedName,
+    pub display_name: LocalizedText,
+    pub description: Option<LocalizedText>,
+    pub write_mask: Option<WriteMask>,
+    pub user_write_mask: Option<WriteMask>,
+    pub is_abstract: Option<bool>,
+    pub symmetric: Option<bool>,
+    pub inverse_name: Option<LocalizedText>,
+    pub contains_no_loops: Option<bool>,
+    pub event_notifier: Option<u8>,
+    /// The value of a variable or variable type, with the status and timestamps it was read with.
+    /// A value that could not be read, e.g. because it is not readable, has a bad status.
+    pub value: Option<DataValue>,
+    pub data_type: Option<NodeId>,
+    pub value_rank: Option<i32>,
+    pub array_dimensions: Option<Vec<u32>>,
+    pub access_level: Option<u8>,
+    pub user_access_level: Option<u8>,
+    pub minimum_sampling_interval: Option<f64>,
+    pub historizing: Option<bool>,
+    pub executable: Option<bool>,
+    pub user_executable: Option<bool>,
+    pub data_type_definition: Option<DataTypeDefinition>,
+    pub role_permissions: Option<Vec<RolePermissionType>>,
+    pub user_role_permissions: Option<Vec<RolePermissionType>>,
+    pub access_restrictions: Option<u16>,
+    pub access_level_ex: Option<u32>,
+}
+
+impl NodeSnapshot {
+    /// Makes a snapshot from the values of the attributes of a node, e.g. the results of reading
+    /// them. Attributes with a bad status are left out, apart from the value. Fails with
+    /// `BadNodeClassInvalid` if the node class is missing, or the status it was read with.
+    pub fn from_attributes<I>(
+        attributes: I,
+        decoding_options: &DecodingOptions,
+    ) -> Result<NodeSnapshot, StatusCode>
+    where
+        I: IntoIterator<Item = (AttributeId, DataValue)>,
+    {
+        let attributes = attributes.into_iter().collect::<Vec<_>>();
+        let node_class = attributes
+            .iter()
+            .find(|(attribute_id, _)| *attribute_id == AttributeId::NodeClass)
+            .ok_or(StatusCode::BadNodeClassInvalid)
+            .and_then(|(_, data_value)| {
+                let status_code = data_value.status();
+                if status_code.is_bad() {
+                    Err(status_code)
+                } else {
+                    match data_value.value {
+                        Some(Variant::Int32(v)) => node_class_from_i32(v),
+                        _ => None,
+                    }
+                    .ok_or(StatusCode::BadNodeClassInvalid)
+                }
+            })?;
+
+        let mut snapshot = NodeSnapshot {
+            node_id: NodeId::null(),
+            node_class,
+            browse_name: QualifiedName::null(),
+            display_name: LocalizedText::null(),
+            description: None,
+            write_mask: None,
+            user_write_mask: None,
+            is_abstract: None,
+            symmetric: None,
+            inverse_name: None,
+            contains_no_loops: None,
+            event_notifier: None,
+            value: None,
+            data_type: None,
+            value_rank: None,
+            array_dimensions: None,
+            access_level: None,
+            user_access_level: None,
+            minimum_sampling_interval: None,
+            historizing: None,
+            executable: None,
+            user_executable: None,
+            data_type_definition: None,
+            role_permissions: None,
+            user_role_permissions: None,
+            access_restrictions: None,
+            access_level_ex: None,
+        };
+
+        let defined = AttributeId::for_node_class(node_class);
+        for (attribute_id, data_value) in attributes {
+            if !defined.contains(&attribute_id) {
+                continue;
+            }
+            if attribute_id == AttributeId::Value {
+                // The value is kept whatever its status, unless the node doesn't have one
+                if data_value.status() != StatusCode::BadAttributeIdInvalid {
+                    snapshot.value = Some(data_value);
+                }
+                continue;
+            }
+            if data_value.status().is_bad() {
+                continue;
+            }
+            let Some(value) = data_value.value else {
+                continue;
+            };
+            match attribute_id {
+                AttributeId::NodeId => {
+                    snapshot.node_id = NodeId::try_from(value).unwrap_or_else(|_| NodeId::null())
+                }
+                AttributeId::BrowseName => {
+                    snapshot.browse_name =
+                        QualifiedName::try_from(value).unwrap_or_else(|_| QualifiedName::null())
+                }
+                AttributeId::DisplayName => {
+                    snapshot.display_name =
+                        LocalizedText::try_from(value).unwrap_or_else(|_| LocalizedText::null())
+                }
+                AttributeId::Description => snapshot.description = value.try_into().ok(),
+                AttributeId::WriteMask => {
+                    snapshot.write_mask =
+                        u32::try_from(value).ok().map(WriteMask::from_bits_truncate)
+                }
+                AttributeId::UserWriteMask => {
+                    snapshot.user_write_mask =
+                        u32::try_from(value).ok().map(WriteMask::from_bits_truncate)
+                }
+                AttributeId::IsAbstract => snapshot.is_abstract = value.try_into().ok(),
+                AttributeId::Symmetric => snapshot.symmetric = value.try_into().ok(),
+                AttributeId::InverseName => snapshot.inverse_name = value.try_into().ok(),
+                AttributeId::ContainsNoLoops => snapshot.contains_no_loops = value.try_into().ok(),
+                AttributeId::EventNotifier => snapshot.event_notifier = value.try_into().ok(),
+                AttributeId::DataType => snapshot.data_type = value.try_into().ok(),
+                AttributeId::ValueRank => snapshot.value_rank = value.try_into().ok(),
+                AttributeId::ArrayDimensions => {
+                    snapshot.array_dimensions = Vec::<u32>::try_from(&value).ok()
+                }
+                AttributeId::AccessLevel => snapshot.access_level = value.try_into().ok(),
+                AttributeId::UserAccessLevel => snapshot.user_access_level = value.try_into().ok(),
+                AttributeId::MinimumSamplingInterval => {
+                    snapshot.minimum_sampling_interval = value.try_into().ok()
+                }
+                AttributeId::Historizing => snapshot.historizing = value.try_into().ok(),
+                AttributeId::Executable => snapshot.executable = value.try_into().ok(),
+                AttributeId::UserExecutable => snapshot.user_executable = value.try_into().ok(),
+                AttributeId::DataTypeDefinition => {
+                    snapshot.data_type_definition = match value {
+                        Variant::ExtensionObject(v) => {
+                            DataTypeDefinition::from_extension_object(&v, decoding_options).ok()
+                        }
+                        _ => None,
+                    }
+                }
+                AttributeId::RolePermissions => {
+                    snapshot.role_permissions = role_permissions(value, decoding_options)
+                }
+                AttributeId::UserRolePermissions => {
+                    snapshot.user_role_permissions = role_permissions(value, decoding_options)
+                }
+                AttributeId::AccessRestrictions => {
+                    snapshot.access_restrictions = value.try_into().ok()
+                }
+                AttributeId::AccessLevelEx => snapshot.access_level_ex = value.try_into().ok(),
+                AttributeId::NodeClass | AttributeId::Value => {}
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+fn node_class_from_i32(v: i32) -> Option<NodeClass> {
+    match v {
+        1 => Some(NodeClass::Object),
+        2 => Some(NodeClass::Variable),
+        4 => Some(NodeClass::Method),
+        8 => Some(NodeClass::ObjectType),
+        16 => Some(NodeClass::VariableType),
+        32 => Some(NodeClass::ReferenceType),
+        64 => Some(NodeClass::DataType),
+        128 => Some(NodeClass::View),
+        _ => None,
+    }
+}
+
+/// Decodes an array of `RolePermissionType` extension objects.
+fn role_permissions(
+    value: Variant,
+    decoding_options: &DecodingOptions,
+) -> Option<Vec<RolePermissionType>> {
+    let Variant::Array(array) = value else {
+        return None;
+    };
+    array
+        .values
+        .iter()
+        .map(|v| match v {
+            Variant::ExtensionObject(v) => {
+                v.decode_inner::<RolePermissionType>(decoding_options).ok()
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -9,7 +9,7 @@ use crate::types::{
     node_ids::ObjectId,
     service_types::{
         AttributeOperand, ContentFilter, ContentFilterElement, ElementOperand, FilterOperator,
-        LiteralOperand, SimpleAttributeOperand,
+        LiteralOperand, RelativePath, SimpleAttributeOperand,
     },
     status_code::StatusCode,
     DecodingOptions, ExtensionObject, NodeId, QualifiedName, UAString, Variant,
@@ -187,6 +187,12 @@ impl From<SimpleAttributeOperand> for Operand {
     }
 }
 
+impl From<AttributeOperand> for Operand {
+    fn from(v: AttributeOperand) -> Self {
+        Operand::AttributeOperand(v)
+    }
+}
+
 impl Operand {
     pub fn element(index: u32) -> Operand {
         ElementOperand { index }.into()
@@ -213,6 +219,29 @@ impl Operand {
             .into()
     }
 
+    /// Creates an attribute operand for the attribute of the node at the browse path from the node
+    /// being filtered. An empty path is the node itself. Attribute operands are used in query
+    /// filters.
+    pub fn attribute<T, R>(
+        node_id: T,
+        browse_path: R,
+        attribute_id: AttributeId,
+        index_range: UAString,
+    ) -> Operand
+    where
+        T: Into<NodeId>,
+        R: Into<RelativePath>,
+    {
+        AttributeOperand {
+            node_id: node_id.into(),
+            alias: UAString::null(),
+            browse_path: browse_path.into(),
+            attribute_id: attribute_id as u32,
+            index_range,
+        }
+        .into()
+    }
+
     pub fn operand_type(&self) -> OperandType {
         match self {
             Operand::ElementOperand(_) => OperandType::ElementOperand,
@@ -407,6 +436,13 @@ impl ContentFilterBuilder {
         self.add_element(FilterOperator::BitwiseOr, vec![o1.into(), o2.into()])
     }
 
+    pub fn of_type<T>(self, o1: T) -> Self
+    where
+        T: Into<Operand>,
+    {
+        self.add_element(FilterOperator::OfType, vec![o1.into()])
+    }
+
     pub fn build(self) -> ContentFilter {
         ContentFilter {
             elements: Some(self.elements),
@@ -5,7 +5,7 @@
 //! Contains the definition of `QualifiedName`.
 use std::io::{Read, Write};
 
-use crate::types::{encoding::*, string::*};
+use crate::types::{encoding::*, json::*, string::*, xml::*};
 
 /// An identifier for a error or condition that is associated with a value or an operation.
 ///
@@ -91,6 +91,50 @@ impl BinaryEncoder<QualifiedName> for QualifiedName {
     }
 }
 
+impl XmlEncoder for QualifiedName {
+    fn xml_type_name() -> String {
+        "QualifiedName".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NamespaceIndex", &self.namespace_index);
+        write_xml_field(&mut element, "Name", &self.name);
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QualifiedName {
+            namespace_index: read_xml_field(element, "NamespaceIndex", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+        })
+    }
+}
+
+/// QualifiedName encoded as an object with its name and namespace index, which is omitted if it
+/// is 0
+impl JsonEncoder for QualifiedName {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        if self.is_null() {
+            return JsonValue::Null;
+        }
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        if self.namespace_index != 0 {
+            write_json_field(&mut object, "Uri", &self.namespace_index, encoding);
+        }
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QualifiedName {
+            namespace_index: read_json_field(value, "Uri", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+        })
+    }
+}
+
 impl QualifiedName {
     pub fn new<T>(namespace_index: u16, name: T) -> QualifiedName
     where
@@ -362,6 +362,25 @@ impl<'a> From<&'a RelativePath> for String {
     }
 }
 
+impl<'a> From<&'a [QualifiedName]> for RelativePath {
+    /// Makes a relative path which follows forward hierarchical references through each of the
+    /// browse names in turn. An empty list of browse names is the starting node itself.
+    fn from(browse_names: &'a [QualifiedName]) -> Self {
+        let elements = browse_names
+            .iter()
+            .map(|target_name| RelativePathElement {
+                reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+                is_inverse: false,
+                include_subtypes: true,
+                target_name: target_name.clone(),
+            })
+            .collect();
+        RelativePath {
+            elements: Some(elements),
+        }
+    }
+}
+
 /// Reserved characters in the browse name which must be escaped with a &
 const BROWSE_NAME_RESERVED_CHARS: &str = "&/.<>:#!";
 
@@ -640,3 +659,15 @@ fn test_relative_path() {
         assert_eq!(relative_path, actual);
     });
 }
+
+#[test]
+fn test_relative_path_from_browse_names() {
+    let browse_names = [
+        QualifiedName::new(2, "Block"),
+        QualifiedName::new(0, "Output"),
+    ];
+    let path = RelativePath::from(&browse_names[..]);
+    assert_eq!(String::from(&path), "/2:Block/0:Output");
+    let path = RelativePath::from(&[][..]);
+    assert_eq!(path.elements, Some(Vec::new()));
+}
@@ -9,7 +9,7 @@ use std::{
 
 use crate::types::{
     data_types::*, date_time::DateTime, diagnostic_info::DiagnosticBits, encoding::*,
-    extension_object::ExtensionObject, node_id::NodeId, string::UAString,
+    extension_object::ExtensionObject, json::*, node_id::NodeId, string::UAString, xml::*,
 };
 
 /// The `RequestHeader` contains information common to every request from a client to the server.
@@ -146,6 +146,82 @@ impl BinaryEncoder<RequestHeader> for RequestHeader {
     }
 }
 
+impl XmlEncoder for RequestHeader {
+    fn xml_type_name() -> String {
+        "RequestHeader".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "AuthenticationToken",
+            &self.authentication_token,
+        );
+        write_xml_field(&mut element, "Timestamp", &self.timestamp);
+        write_xml_field(&mut element, "RequestHandle", &self.request_handle);
+        write_xml_field(&mut element, "ReturnDiagnostics", &self.return_diagnostics);
+        write_xml_field(&mut element, "AuditEntryId", &self.audit_entry_id);
+        write_xml_field(&mut element, "TimeoutHint", &self.timeout_hint);
+        write_xml_field(&mut element, "AdditionalHeader", &self.additional_header);
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RequestHeader {
+            authentication_token: read_xml_field(element, "AuthenticationToken", decoding_options)?,
+            timestamp: read_xml_field(element, "Timestamp", decoding_options)?,
+            request_handle: read_xml_field(element, "RequestHandle", decoding_options)?,
+            return_diagnostics: read_xml_field(element, "ReturnDiagnostics", decoding_options)?,
+            audit_entry_id: read_xml_field(element, "AuditEntryId", decoding_options)?,
+            timeout_hint: read_xml_field(element, "TimeoutHint", decoding_options)?,
+            additional_header: read_xml_field(element, "AdditionalHeader", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RequestHeader {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "AuthenticationToken",
+            &self.authentication_token,
+            encoding,
+        );
+        write_json_field(&mut object, "Timestamp", &self.timestamp, encoding);
+        write_json_field(&mut object, "RequestHandle", &self.request_handle, encoding);
+        write_json_field(
+            &mut object,
+            "ReturnDiagnostics",
+            &self.return_diagnostics,
+            encoding,
+        );
+        write_json_field(&mut object, "AuditEntryId", &self.audit_entry_id, encoding);
+        write_json_field(&mut object, "TimeoutHint", &self.timeout_hint, encoding);
+        write_json_field(
+            &mut object,
+            "AdditionalHeader",
+            &self.additional_header,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RequestHeader {
+            authentication_token: read_json_field(value, "AuthenticationToken", decoding_options)?,
+            timestamp: read_json_field(value, "Timestamp", decoding_options)?,
+            request_handle: read_json_field(value, "RequestHandle", decoding_options)?,
+            return_diagnostics: read_json_field(value, "ReturnDiagnostics", decoding_options)?,
+            audit_entry_id: read_json_field(value, "AuditEntryId", decoding_options)?,
+            timeout_hint: read_json_field(value, "TimeoutHint", decoding_options)?,
+            additional_header: read_json_field(value, "AdditionalHeader", decoding_options)?,
+        })
+    }
+}
+
 impl RequestHeader {
     pub fn new(
         authentication_token: &NodeId,
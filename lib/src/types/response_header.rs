@@ -9,8 +9,8 @@ use std::{
 
 use crate::types::{
     data_types::*, date_time::DateTime, diagnostic_info::DiagnosticInfo, encoding::*,
-    extension_object::ExtensionObject, request_header::RequestHeader, status_codes::StatusCode,
-    string::UAString,
+    extension_object::ExtensionObject, json::*, request_header::RequestHeader,
+    status_codes::StatusCode, string::UAString, xml::*,
 };
 
 /// The `ResponseHeader` contains information common to every response from server to client.
@@ -66,6 +66,73 @@ impl BinaryEncoder<ResponseHeader> for ResponseHeader {
     }
 }
 
+impl XmlEncoder for ResponseHeader {
+    fn xml_type_name() -> String {
+        "ResponseHeader".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Timestamp", &self.timestamp);
+        write_xml_field(&mut element, "RequestHandle", &self.request_handle);
+        write_xml_field(&mut element, "ServiceResult", &self.service_result);
+        write_xml_field(
+            &mut element,
+            "ServiceDiagnostics",
+            &self.service_diagnostics,
+        );
+        write_xml_field(&mut element, "StringTable", &self.string_table);
+        write_xml_field(&mut element, "AdditionalHeader", &self.additional_header);
+        element
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ResponseHeader {
+            timestamp: read_xml_field(element, "Timestamp", decoding_options)?,
+            request_handle: read_xml_field(element, "RequestHandle", decoding_options)?,
+            service_result: read_xml_field(element, "ServiceResult", decoding_options)?,
+            service_diagnostics: read_xml_field(element, "ServiceDiagnostics", decoding_options)?,
+            string_table: read_xml_field(element, "StringTable", decoding_options)?,
+            additional_header: read_xml_field(element, "AdditionalHeader", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ResponseHeader {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Timestamp", &self.timestamp, encoding);
+        write_json_field(&mut object, "RequestHandle", &self.request_handle, encoding);
+        write_json_field(&mut object, "ServiceResult", &self.service_result, encoding);
+        write_json_field(
+            &mut object,
+            "ServiceDiagnostics",
+            &self.service_diagnostics,
+            encoding,
+        );
+        write_json_field(&mut object, "StringTable", &self.string_table, encoding);
+        write_json_field(
+            &mut object,
+            "AdditionalHeader",
+            &self.additional_header,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ResponseHeader {
+            timestamp: read_json_field(value, "Timestamp", decoding_options)?,
+            request_handle: read_json_field(value, "RequestHandle", decoding_options)?,
+            service_result: read_json_field(value, "ServiceResult", decoding_options)?,
+            service_diagnostics: read_json_field(value, "ServiceDiagnostics", decoding_options)?,
+            string_table: read_json_field(value, "StringTable", decoding_options)?,
+            additional_header: read_json_field(value, "AdditionalHeader", decoding_options)?,
+        })
+    }
+}
+
 impl ResponseHeader {
     pub fn new_good(request_header: &RequestHeader) -> ResponseHeader {
         ResponseHeader::new_service_result(request_header, StatusCode::Good)
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     request_header::RequestHeader, service_types::impls::MessageInfo, service_types::SignatureData,
-    service_types::SignedSoftwareCertificate, string::UAString,
+    service_types::SignedSoftwareCertificate, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -73,3 +73,94 @@ impl BinaryEncoder<ActivateSessionRequest> for ActivateSessionRequest {
         })
     }
 }
+
+impl XmlEncoder for ActivateSessionRequest {
+    fn xml_type_name() -> String {
+        "ActivateSessionRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "ClientSignature", &self.client_signature);
+        write_xml_field(
+            &mut element,
+            "ClientSoftwareCertificates",
+            &self.client_software_certificates,
+        );
+        write_xml_field(&mut element, "LocaleIds", &self.locale_ids);
+        write_xml_field(&mut element, "UserIdentityToken", &self.user_identity_token);
+        write_xml_field(
+            &mut element,
+            "UserTokenSignature",
+            &self.user_token_signature,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ActivateSessionRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            client_signature: read_xml_field(element, "ClientSignature", decoding_options)?,
+            client_software_certificates: read_xml_field(
+                element,
+                "ClientSoftwareCertificates",
+                decoding_options,
+            )?,
+            locale_ids: read_xml_field(element, "LocaleIds", decoding_options)?,
+            user_identity_token: read_xml_field(element, "UserIdentityToken", decoding_options)?,
+            user_token_signature: read_xml_field(element, "UserTokenSignature", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ActivateSessionRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ClientSignature",
+            &self.client_signature,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ClientSoftwareCertificates",
+            &self.client_software_certificates,
+            encoding,
+        );
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, encoding);
+        write_json_field(
+            &mut object,
+            "UserIdentityToken",
+            &self.user_identity_token,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UserTokenSignature",
+            &self.user_token_signature,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ActivateSessionRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            client_signature: read_json_field(value, "ClientSignature", decoding_options)?,
+            client_software_certificates: read_json_field(
+                value,
+                "ClientSoftwareCertificates",
+                decoding_options,
+            )?,
+            locale_ids: read_json_field(value, "LocaleIds", decoding_options)?,
+            user_identity_token: read_json_field(value, "UserIdentityToken", decoding_options)?,
+            user_token_signature: read_json_field(value, "UserTokenSignature", decoding_options)?,
+        })
+    }
+}
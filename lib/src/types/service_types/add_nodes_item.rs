@@ -9,9 +9,9 @@
 #[allow(unused_imports)]
 use crate::types::{
     basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId,
-    extension_object::ExtensionObject, node_id::NodeId, node_ids::ObjectId,
+    extension_object::ExtensionObject, json::*, node_id::NodeId, node_ids::ObjectId,
     qualified_name::QualifiedName, service_types::enums::NodeClass,
-    service_types::impls::MessageInfo,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -78,3 +78,86 @@ impl BinaryEncoder<AddNodesItem> for AddNodesItem {
         })
     }
 }
+
+impl XmlEncoder for AddNodesItem {
+    fn xml_type_name() -> String {
+        "AddNodesItem".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ParentNodeId", &self.parent_node_id);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(
+            &mut element,
+            "RequestedNewNodeId",
+            &self.requested_new_node_id,
+        );
+        write_xml_field(&mut element, "BrowseName", &self.browse_name);
+        write_xml_field(&mut element, "NodeClass", &self.node_class);
+        write_xml_field(&mut element, "NodeAttributes", &self.node_attributes);
+        write_xml_field(&mut element, "TypeDefinition", &self.type_definition);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AddNodesItem {
+            parent_node_id: read_xml_field(element, "ParentNodeId", decoding_options)?,
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            requested_new_node_id: read_xml_field(element, "RequestedNewNodeId", decoding_options)?,
+            browse_name: read_xml_field(element, "BrowseName", decoding_options)?,
+            node_class: read_xml_field(element, "NodeClass", decoding_options)?,
+            node_attributes: read_xml_field(element, "NodeAttributes", decoding_options)?,
+            type_definition: read_xml_field(element, "TypeDefinition", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AddNodesItem {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ParentNodeId", &self.parent_node_id, encoding);
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedNewNodeId",
+            &self.requested_new_node_id,
+            encoding,
+        );
+        write_json_field(&mut object, "BrowseName", &self.browse_name, encoding);
+        write_json_field(&mut object, "NodeClass", &self.node_class, encoding);
+        write_json_field(
+            &mut object,
+            "NodeAttributes",
+            &self.node_attributes,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TypeDefinition",
+            &self.type_definition,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AddNodesItem {
+            parent_node_id: read_json_field(value, "ParentNodeId", decoding_options)?,
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            requested_new_node_id: read_json_field(value, "RequestedNewNodeId", decoding_options)?,
+            browse_name: read_json_field(value, "BrowseName", decoding_options)?,
+            node_class: read_json_field(value, "NodeClass", decoding_options)?,
+            node_attributes: read_json_field(value, "NodeAttributes", decoding_options)?,
+            type_definition: read_json_field(value, "TypeDefinition", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::AddNodesItem,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::AddNodesItem, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<AddNodesRequest> for AddNodesRequest {
         })
     }
 }
+
+impl XmlEncoder for AddNodesRequest {
+    fn xml_type_name() -> String {
+        "AddNodesRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "NodesToAdd", &self.nodes_to_add);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AddNodesRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            nodes_to_add: read_xml_field(element, "NodesToAdd", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AddNodesRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "NodesToAdd", &self.nodes_to_add, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AddNodesRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            nodes_to_add: read_json_field(value, "NodesToAdd", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo,
-    service_types::AddNodesResult,
+    service_types::AddNodesResult, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,56 @@ impl BinaryEncoder<AddNodesResponse> for AddNodesResponse {
         })
     }
 }
+
+impl XmlEncoder for AddNodesResponse {
+    fn xml_type_name() -> String {
+        "AddNodesResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "Results", &self.results);
+        write_xml_field(&mut element, "DiagnosticInfos", &self.diagnostic_infos);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AddNodesResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            results: read_xml_field(element, "Results", decoding_options)?,
+            diagnostic_infos: read_xml_field(element, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AddNodesResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(&mut object, "Results", &self.results, encoding);
+        write_json_field(
+            &mut object,
+            "DiagnosticInfos",
+            &self.diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AddNodesResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            results: read_json_field(value, "Results", decoding_options)?,
+            diagnostic_infos: read_json_field(value, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
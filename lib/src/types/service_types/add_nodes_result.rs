@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<AddNodesResult> for AddNodesResult {
         })
     }
 }
+
+impl XmlEncoder for AddNodesResult {
+    fn xml_type_name() -> String {
+        "AddNodesResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "AddedNodeId", &self.added_node_id);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AddNodesResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            added_node_id: read_xml_field(element, "AddedNodeId", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AddNodesResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(&mut object, "AddedNodeId", &self.added_node_id, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AddNodesResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            added_node_id: read_json_field(value, "AddedNodeId", decoding_options)?,
+        })
+    }
+}
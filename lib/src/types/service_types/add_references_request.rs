@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::AddReferencesItem,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::AddReferencesItem, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,47 @@ impl BinaryEncoder<AddReferencesRequest> for AddReferencesRequest {
         })
     }
 }
+
+impl XmlEncoder for AddReferencesRequest {
+    fn xml_type_name() -> String {
+        "AddReferencesRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "ReferencesToAdd", &self.references_to_add);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AddReferencesRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            references_to_add: read_xml_field(element, "ReferencesToAdd", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AddReferencesRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ReferencesToAdd",
+            &self.references_to_add,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AddReferencesRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            references_to_add: read_json_field(value, "ReferencesToAdd", decoding_options)?,
+        })
+    }
+}
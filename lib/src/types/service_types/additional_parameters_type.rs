@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::KeyValuePair,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::KeyValuePair, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,38 @@ impl BinaryEncoder<AdditionalParametersType> for AdditionalParametersType {
         Ok(AdditionalParametersType { parameters })
     }
 }
+
+impl XmlEncoder for AdditionalParametersType {
+    fn xml_type_name() -> String {
+        "AdditionalParametersType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Parameters", &self.parameters);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AdditionalParametersType {
+            parameters: read_xml_field(element, "Parameters", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AdditionalParametersType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Parameters", &self.parameters, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AdditionalParametersType {
+            parameters: read_json_field(value, "Parameters", decoding_options)?,
+        })
+    }
+}
@@ -8,11 +8,13 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 pub struct AggregateConfiguration {
     pub use_server_capabilities_defaults: bool,
     pub treat_uncertain_as_bad: bool,
@@ -65,3 +67,115 @@ impl BinaryEncoder<AggregateConfiguration> for AggregateConfiguration {
         })
     }
 }
+
+impl XmlEncoder for AggregateConfiguration {
+    fn xml_type_name() -> String {
+        "AggregateConfiguration".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "UseServerCapabilitiesDefaults",
+            &self.use_server_capabilities_defaults,
+        );
+        write_xml_field(
+            &mut element,
+            "TreatUncertainAsBad",
+            &self.treat_uncertain_as_bad,
+        );
+        write_xml_field(&mut element, "PercentDataBad", &self.percent_data_bad);
+        write_xml_field(&mut element, "PercentDataGood", &self.percent_data_good);
+        write_xml_field(
+            &mut element,
+            "UseSlopedExtrapolation",
+            &self.use_sloped_extrapolation,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AggregateConfiguration {
+            use_server_capabilities_defaults: read_xml_field(
+                element,
+                "UseServerCapabilitiesDefaults",
+                decoding_options,
+            )?,
+            treat_uncertain_as_bad: read_xml_field(
+                element,
+                "TreatUncertainAsBad",
+                decoding_options,
+            )?,
+            percent_data_bad: read_xml_field(element, "PercentDataBad", decoding_options)?,
+            percent_data_good: read_xml_field(element, "PercentDataGood", decoding_options)?,
+            use_sloped_extrapolation: read_xml_field(
+                element,
+                "UseSlopedExtrapolation",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for AggregateConfiguration {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "UseServerCapabilitiesDefaults",
+            &self.use_server_capabilities_defaults,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TreatUncertainAsBad",
+            &self.treat_uncertain_as_bad,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PercentDataBad",
+            &self.percent_data_bad,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PercentDataGood",
+            &self.percent_data_good,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UseSlopedExtrapolation",
+            &self.use_sloped_extrapolation,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AggregateConfiguration {
+            use_server_capabilities_defaults: read_json_field(
+                value,
+                "UseServerCapabilitiesDefaults",
+                decoding_options,
+            )?,
+            treat_uncertain_as_bad: read_json_field(
+                value,
+                "TreatUncertainAsBad",
+                decoding_options,
+            )?,
+            percent_data_bad: read_json_field(value, "PercentDataBad", decoding_options)?,
+            percent_data_good: read_json_field(value, "PercentDataGood", decoding_options)?,
+            use_sloped_extrapolation: read_json_field(
+                value,
+                "UseSlopedExtrapolation",
+                decoding_options,
+            )?,
+        })
+    }
+}
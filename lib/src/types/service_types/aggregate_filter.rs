@@ -8,12 +8,13 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId,
-    service_types::AggregateConfiguration,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId,
+    service_types::AggregateConfiguration, xml::*,
 };
 use std::io::{Read, Write};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 pub struct AggregateFilter {
     pub start_time: DateTime,
     pub aggregate_type: NodeId,
@@ -55,3 +56,76 @@ impl BinaryEncoder<AggregateFilter> for AggregateFilter {
         })
     }
 }
+
+impl XmlEncoder for AggregateFilter {
+    fn xml_type_name() -> String {
+        "AggregateFilter".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "AggregateType", &self.aggregate_type);
+        write_xml_field(
+            &mut element,
+            "ProcessingInterval",
+            &self.processing_interval,
+        );
+        write_xml_field(
+            &mut element,
+            "AggregateConfiguration",
+            &self.aggregate_configuration,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AggregateFilter {
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            aggregate_type: read_xml_field(element, "AggregateType", decoding_options)?,
+            processing_interval: read_xml_field(element, "ProcessingInterval", decoding_options)?,
+            aggregate_configuration: read_xml_field(
+                element,
+                "AggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for AggregateFilter {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "AggregateType", &self.aggregate_type, encoding);
+        write_json_field(
+            &mut object,
+            "ProcessingInterval",
+            &self.processing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AggregateConfiguration",
+            &self.aggregate_configuration,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AggregateFilter {
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            aggregate_type: read_json_field(value, "AggregateType", decoding_options)?,
+            processing_interval: read_json_field(value, "ProcessingInterval", decoding_options)?,
+            aggregate_configuration: read_json_field(
+                value,
+                "AggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, service_types::AggregateConfiguration,
+    basic_types::*, date_time::DateTime, encoding::*, json::*,
+    service_types::AggregateConfiguration, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +51,85 @@ impl BinaryEncoder<AggregateFilterResult> for AggregateFilterResult {
         })
     }
 }
+
+impl XmlEncoder for AggregateFilterResult {
+    fn xml_type_name() -> String {
+        "AggregateFilterResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RevisedStartTime", &self.revised_start_time);
+        write_xml_field(
+            &mut element,
+            "RevisedProcessingInterval",
+            &self.revised_processing_interval,
+        );
+        write_xml_field(
+            &mut element,
+            "RevisedAggregateConfiguration",
+            &self.revised_aggregate_configuration,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AggregateFilterResult {
+            revised_start_time: read_xml_field(element, "RevisedStartTime", decoding_options)?,
+            revised_processing_interval: read_xml_field(
+                element,
+                "RevisedProcessingInterval",
+                decoding_options,
+            )?,
+            revised_aggregate_configuration: read_xml_field(
+                element,
+                "RevisedAggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for AggregateFilterResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "RevisedStartTime",
+            &self.revised_start_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedProcessingInterval",
+            &self.revised_processing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedAggregateConfiguration",
+            &self.revised_aggregate_configuration,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AggregateFilterResult {
+            revised_start_time: read_json_field(value, "RevisedStartTime", decoding_options)?,
+            revised_processing_interval: read_json_field(
+                value,
+                "RevisedProcessingInterval",
+                decoding_options,
+            )?,
+            revised_aggregate_configuration: read_json_field(
+                value,
+                "RevisedAggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, node_ids::ObjectId,
-    qualified_name::QualifiedName, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*, node_ids::ObjectId,
+    qualified_name::QualifiedName, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<AliasNameDataType> for AliasNameDataType {
         })
     }
 }
+
+impl XmlEncoder for AliasNameDataType {
+    fn xml_type_name() -> String {
+        "AliasNameDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "AliasName", &self.alias_name);
+        write_xml_field(&mut element, "ReferencedNodes", &self.referenced_nodes);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AliasNameDataType {
+            alias_name: read_xml_field(element, "AliasName", decoding_options)?,
+            referenced_nodes: read_xml_field(element, "ReferencedNodes", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AliasNameDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "AliasName", &self.alias_name, encoding);
+        write_json_field(
+            &mut object,
+            "ReferencedNodes",
+            &self.referenced_nodes,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AliasNameDataType {
+            alias_name: read_json_field(value, "AliasName", decoding_options)?,
+            referenced_nodes: read_json_field(value, "ReferencedNodes", decoding_options)?,
+        })
+    }
+}
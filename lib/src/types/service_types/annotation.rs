@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,51 @@ impl BinaryEncoder<Annotation> for Annotation {
         })
     }
 }
+
+impl XmlEncoder for Annotation {
+    fn xml_type_name() -> String {
+        "Annotation".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Message", &self.message);
+        write_xml_field(&mut element, "UserName", &self.user_name);
+        write_xml_field(&mut element, "AnnotationTime", &self.annotation_time);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(Annotation {
+            message: read_xml_field(element, "Message", decoding_options)?,
+            user_name: read_xml_field(element, "UserName", decoding_options)?,
+            annotation_time: read_xml_field(element, "AnnotationTime", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for Annotation {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Message", &self.message, encoding);
+        write_json_field(&mut object, "UserName", &self.user_name, encoding);
+        write_json_field(
+            &mut object,
+            "AnnotationTime",
+            &self.annotation_time,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(Annotation {
+            message: read_json_field(value, "Message", decoding_options)?,
+            user_name: read_json_field(value, "UserName", decoding_options)?,
+            annotation_time: read_json_field(value, "AnnotationTime", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, string::UAString};
+use crate::types::{basic_types::*, encoding::*, json::*, string::UAString, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +35,38 @@ impl BinaryEncoder<AnonymousIdentityToken> for AnonymousIdentityToken {
         Ok(AnonymousIdentityToken { policy_id })
     }
 }
+
+impl XmlEncoder for AnonymousIdentityToken {
+    fn xml_type_name() -> String {
+        "AnonymousIdentityToken".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PolicyId", &self.policy_id);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AnonymousIdentityToken {
+            policy_id: read_xml_field(element, "PolicyId", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AnonymousIdentityToken {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AnonymousIdentityToken {
+            policy_id: read_json_field(value, "PolicyId", decoding_options)?,
+        })
+    }
+}
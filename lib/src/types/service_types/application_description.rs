@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
     service_types::enums::ApplicationType, service_types::impls::MessageInfo, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -76,3 +77,95 @@ impl BinaryEncoder<ApplicationDescription> for ApplicationDescription {
         })
     }
 }
+
+impl XmlEncoder for ApplicationDescription {
+    fn xml_type_name() -> String {
+        "ApplicationDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ApplicationUri", &self.application_uri);
+        write_xml_field(&mut element, "ProductUri", &self.product_uri);
+        write_xml_field(&mut element, "ApplicationName", &self.application_name);
+        write_xml_field(&mut element, "ApplicationType", &self.application_type);
+        write_xml_field(&mut element, "GatewayServerUri", &self.gateway_server_uri);
+        write_xml_field(
+            &mut element,
+            "DiscoveryProfileUri",
+            &self.discovery_profile_uri,
+        );
+        write_xml_field(&mut element, "DiscoveryUrls", &self.discovery_urls);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ApplicationDescription {
+            application_uri: read_xml_field(element, "ApplicationUri", decoding_options)?,
+            product_uri: read_xml_field(element, "ProductUri", decoding_options)?,
+            application_name: read_xml_field(element, "ApplicationName", decoding_options)?,
+            application_type: read_xml_field(element, "ApplicationType", decoding_options)?,
+            gateway_server_uri: read_xml_field(element, "GatewayServerUri", decoding_options)?,
+            discovery_profile_uri: read_xml_field(
+                element,
+                "DiscoveryProfileUri",
+                decoding_options,
+            )?,
+            discovery_urls: read_xml_field(element, "DiscoveryUrls", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ApplicationDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ApplicationUri",
+            &self.application_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "ProductUri", &self.product_uri, encoding);
+        write_json_field(
+            &mut object,
+            "ApplicationName",
+            &self.application_name,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ApplicationType",
+            &self.application_type,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "GatewayServerUri",
+            &self.gateway_server_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DiscoveryProfileUri",
+            &self.discovery_profile_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "DiscoveryUrls", &self.discovery_urls, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ApplicationDescription {
+            application_uri: read_json_field(value, "ApplicationUri", decoding_options)?,
+            product_uri: read_json_field(value, "ProductUri", decoding_options)?,
+            application_name: read_json_field(value, "ApplicationName", decoding_options)?,
+            application_type: read_json_field(value, "ApplicationType", decoding_options)?,
+            gateway_server_uri: read_json_field(value, "GatewayServerUri", decoding_options)?,
+            discovery_profile_uri: read_json_field(value, "DiscoveryProfileUri", decoding_options)?,
+            discovery_urls: read_json_field(value, "DiscoveryUrls", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, service_types::RelativePath, string::UAString,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, service_types::RelativePath,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -59,3 +60,54 @@ impl BinaryEncoder<AttributeOperand> for AttributeOperand {
         })
     }
 }
+
+impl XmlEncoder for AttributeOperand {
+    fn xml_type_name() -> String {
+        "AttributeOperand".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "Alias", &self.alias);
+        write_xml_field(&mut element, "BrowsePath", &self.browse_path);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AttributeOperand {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            alias: read_xml_field(element, "Alias", decoding_options)?,
+            browse_path: read_xml_field(element, "BrowsePath", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AttributeOperand {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "Alias", &self.alias, encoding);
+        write_json_field(&mut object, "BrowsePath", &self.browse_path, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AttributeOperand {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            alias: read_json_field(value, "Alias", decoding_options)?,
+            browse_path: read_json_field(value, "BrowsePath", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
     service_types::enums::AxisScaleEnumeration, service_types::impls::MessageInfo,
-    service_types::EUInformation, service_types::Range,
+    service_types::EUInformation, service_types::Range, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -67,3 +67,64 @@ impl BinaryEncoder<AxisInformation> for AxisInformation {
         })
     }
 }
+
+impl XmlEncoder for AxisInformation {
+    fn xml_type_name() -> String {
+        "AxisInformation".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EngineeringUnits", &self.engineering_units);
+        write_xml_field(&mut element, "EURange", &self.eu_range);
+        write_xml_field(&mut element, "Title", &self.title);
+        write_xml_field(&mut element, "AxisScaleType", &self.axis_scale_type);
+        write_xml_field(&mut element, "AxisSteps", &self.axis_steps);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AxisInformation {
+            engineering_units: read_xml_field(element, "EngineeringUnits", decoding_options)?,
+            eu_range: read_xml_field(element, "EURange", decoding_options)?,
+            title: read_xml_field(element, "Title", decoding_options)?,
+            axis_scale_type: read_xml_field(element, "AxisScaleType", decoding_options)?,
+            axis_steps: read_xml_field(element, "AxisSteps", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for AxisInformation {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "EngineeringUnits",
+            &self.engineering_units,
+            encoding,
+        );
+        write_json_field(&mut object, "EURange", &self.eu_range, encoding);
+        write_json_field(&mut object, "Title", &self.title, encoding);
+        write_json_field(
+            &mut object,
+            "AxisScaleType",
+            &self.axis_scale_type,
+            encoding,
+        );
+        write_json_field(&mut object, "AxisSteps", &self.axis_steps, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(AxisInformation {
+            engineering_units: read_json_field(value, "EngineeringUnits", decoding_options)?,
+            eu_range: read_json_field(value, "EURange", decoding_options)?,
+            title: read_json_field(value, "Title", decoding_options)?,
+            axis_scale_type: read_json_field(value, "AxisScaleType", decoding_options)?,
+            axis_steps: read_json_field(value, "AxisSteps", decoding_options)?,
+        })
+    }
+}
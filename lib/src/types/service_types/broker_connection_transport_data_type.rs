@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, string::UAString};
+use crate::types::{basic_types::*, encoding::*, json::*, string::UAString, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,59 @@ impl BinaryEncoder<BrokerConnectionTransportDataType> for BrokerConnectionTransp
         })
     }
 }
+
+impl XmlEncoder for BrokerConnectionTransportDataType {
+    fn xml_type_name() -> String {
+        "BrokerConnectionTransportDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResourceUri", &self.resource_uri);
+        write_xml_field(
+            &mut element,
+            "AuthenticationProfileUri",
+            &self.authentication_profile_uri,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrokerConnectionTransportDataType {
+            resource_uri: read_xml_field(element, "ResourceUri", decoding_options)?,
+            authentication_profile_uri: read_xml_field(
+                element,
+                "AuthenticationProfileUri",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for BrokerConnectionTransportDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResourceUri", &self.resource_uri, encoding);
+        write_json_field(
+            &mut object,
+            "AuthenticationProfileUri",
+            &self.authentication_profile_uri,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrokerConnectionTransportDataType {
+            resource_uri: read_json_field(value, "ResourceUri", decoding_options)?,
+            authentication_profile_uri: read_json_field(
+                value,
+                "AuthenticationProfileUri",
+                decoding_options,
+            )?,
+        })
+    }
+}
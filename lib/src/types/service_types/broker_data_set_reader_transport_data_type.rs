@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::enums::BrokerTransportQualityOfService,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, service_types::enums::BrokerTransportQualityOfService,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,97 @@ impl BinaryEncoder<BrokerDataSetReaderTransportDataType> for BrokerDataSetReader
         })
     }
 }
+
+impl XmlEncoder for BrokerDataSetReaderTransportDataType {
+    fn xml_type_name() -> String {
+        "BrokerDataSetReaderTransportDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "QueueName", &self.queue_name);
+        write_xml_field(&mut element, "ResourceUri", &self.resource_uri);
+        write_xml_field(
+            &mut element,
+            "AuthenticationProfileUri",
+            &self.authentication_profile_uri,
+        );
+        write_xml_field(
+            &mut element,
+            "RequestedDeliveryGuarantee",
+            &self.requested_delivery_guarantee,
+        );
+        write_xml_field(
+            &mut element,
+            "MetaDataQueueName",
+            &self.meta_data_queue_name,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrokerDataSetReaderTransportDataType {
+            queue_name: read_xml_field(element, "QueueName", decoding_options)?,
+            resource_uri: read_xml_field(element, "ResourceUri", decoding_options)?,
+            authentication_profile_uri: read_xml_field(
+                element,
+                "AuthenticationProfileUri",
+                decoding_options,
+            )?,
+            requested_delivery_guarantee: read_xml_field(
+                element,
+                "RequestedDeliveryGuarantee",
+                decoding_options,
+            )?,
+            meta_data_queue_name: read_xml_field(element, "MetaDataQueueName", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrokerDataSetReaderTransportDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "QueueName", &self.queue_name, encoding);
+        write_json_field(&mut object, "ResourceUri", &self.resource_uri, encoding);
+        write_json_field(
+            &mut object,
+            "AuthenticationProfileUri",
+            &self.authentication_profile_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedDeliveryGuarantee",
+            &self.requested_delivery_guarantee,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MetaDataQueueName",
+            &self.meta_data_queue_name,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrokerDataSetReaderTransportDataType {
+            queue_name: read_json_field(value, "QueueName", decoding_options)?,
+            resource_uri: read_json_field(value, "ResourceUri", decoding_options)?,
+            authentication_profile_uri: read_json_field(
+                value,
+                "AuthenticationProfileUri",
+                decoding_options,
+            )?,
+            requested_delivery_guarantee: read_json_field(
+                value,
+                "RequestedDeliveryGuarantee",
+                decoding_options,
+            )?,
+            meta_data_queue_name: read_json_field(value, "MetaDataQueueName", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::enums::BrowseDirection, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::enums::BrowseDirection, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -71,3 +71,78 @@ impl BinaryEncoder<BrowseDescription> for BrowseDescription {
         })
     }
 }
+
+impl XmlEncoder for BrowseDescription {
+    fn xml_type_name() -> String {
+        "BrowseDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "BrowseDirection", &self.browse_direction);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(&mut element, "IncludeSubtypes", &self.include_subtypes);
+        write_xml_field(&mut element, "NodeClassMask", &self.node_class_mask);
+        write_xml_field(&mut element, "ResultMask", &self.result_mask);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowseDescription {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            browse_direction: read_xml_field(element, "BrowseDirection", decoding_options)?,
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            include_subtypes: read_xml_field(element, "IncludeSubtypes", decoding_options)?,
+            node_class_mask: read_xml_field(element, "NodeClassMask", decoding_options)?,
+            result_mask: read_xml_field(element, "ResultMask", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowseDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "BrowseDirection",
+            &self.browse_direction,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "IncludeSubtypes",
+            &self.include_subtypes,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NodeClassMask",
+            &self.node_class_mask,
+            encoding,
+        );
+        write_json_field(&mut object, "ResultMask", &self.result_mask, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowseDescription {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            browse_direction: read_json_field(value, "BrowseDirection", decoding_options)?,
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            include_subtypes: read_json_field(value, "IncludeSubtypes", decoding_options)?,
+            node_class_mask: read_json_field(value, "NodeClassMask", decoding_options)?,
+            result_mask: read_json_field(value, "ResultMask", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    request_header::RequestHeader, service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    request_header::RequestHeader, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,72 @@ impl BinaryEncoder<BrowseNextRequest> for BrowseNextRequest {
         })
     }
 }
+
+impl XmlEncoder for BrowseNextRequest {
+    fn xml_type_name() -> String {
+        "BrowseNextRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "ReleaseContinuationPoints",
+            &self.release_continuation_points,
+        );
+        write_xml_field(
+            &mut element,
+            "ContinuationPoints",
+            &self.continuation_points,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowseNextRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            release_continuation_points: read_xml_field(
+                element,
+                "ReleaseContinuationPoints",
+                decoding_options,
+            )?,
+            continuation_points: read_xml_field(element, "ContinuationPoints", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowseNextRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ReleaseContinuationPoints",
+            &self.release_continuation_points,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ContinuationPoints",
+            &self.continuation_points,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowseNextRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            release_continuation_points: read_json_field(
+                value,
+                "ReleaseContinuationPoints",
+                decoding_options,
+            )?,
+            continuation_points: read_json_field(value, "ContinuationPoints", decoding_options)?,
+        })
+    }
+}
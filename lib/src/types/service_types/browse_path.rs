@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, service_types::RelativePath,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, service_types::RelativePath, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<BrowsePath> for BrowsePath {
         })
     }
 }
+
+impl XmlEncoder for BrowsePath {
+    fn xml_type_name() -> String {
+        "BrowsePath".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StartingNode", &self.starting_node);
+        write_xml_field(&mut element, "RelativePath", &self.relative_path);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowsePath {
+            starting_node: read_xml_field(element, "StartingNode", decoding_options)?,
+            relative_path: read_xml_field(element, "RelativePath", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowsePath {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartingNode", &self.starting_node, encoding);
+        write_json_field(&mut object, "RelativePath", &self.relative_path, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowsePath {
+            starting_node: read_json_field(value, "StartingNode", decoding_options)?,
+            relative_path: read_json_field(value, "RelativePath", decoding_options)?,
+        })
+    }
+}
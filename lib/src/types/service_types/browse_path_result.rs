@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::BrowsePathTarget, status_codes::StatusCode,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::BrowsePathTarget, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<BrowsePathResult> for BrowsePathResult {
         })
     }
 }
+
+impl XmlEncoder for BrowsePathResult {
+    fn xml_type_name() -> String {
+        "BrowsePathResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "Targets", &self.targets);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowsePathResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            targets: read_xml_field(element, "Targets", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowsePathResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(&mut object, "Targets", &self.targets, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowsePathResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            targets: read_json_field(value, "Targets", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,51 @@ impl BinaryEncoder<BrowsePathTarget> for BrowsePathTarget {
         })
     }
 }
+
+impl XmlEncoder for BrowsePathTarget {
+    fn xml_type_name() -> String {
+        "BrowsePathTarget".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "TargetId", &self.target_id);
+        write_xml_field(
+            &mut element,
+            "RemainingPathIndex",
+            &self.remaining_path_index,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowsePathTarget {
+            target_id: read_xml_field(element, "TargetId", decoding_options)?,
+            remaining_path_index: read_xml_field(element, "RemainingPathIndex", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowsePathTarget {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TargetId", &self.target_id, encoding);
+        write_json_field(
+            &mut object,
+            "RemainingPathIndex",
+            &self.remaining_path_index,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowsePathTarget {
+            target_id: read_json_field(value, "TargetId", decoding_options)?,
+            remaining_path_index: read_json_field(value, "RemainingPathIndex", decoding_options)?,
+        })
+    }
+}
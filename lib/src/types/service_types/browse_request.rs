@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
     service_types::impls::MessageInfo, service_types::BrowseDescription,
-    service_types::ViewDescription,
+    service_types::ViewDescription, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -62,3 +62,72 @@ impl BinaryEncoder<BrowseRequest> for BrowseRequest {
         })
     }
 }
+
+impl XmlEncoder for BrowseRequest {
+    fn xml_type_name() -> String {
+        "BrowseRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "View", &self.view);
+        write_xml_field(
+            &mut element,
+            "RequestedMaxReferencesPerNode",
+            &self.requested_max_references_per_node,
+        );
+        write_xml_field(&mut element, "NodesToBrowse", &self.nodes_to_browse);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowseRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            view: read_xml_field(element, "View", decoding_options)?,
+            requested_max_references_per_node: read_xml_field(
+                element,
+                "RequestedMaxReferencesPerNode",
+                decoding_options,
+            )?,
+            nodes_to_browse: read_xml_field(element, "NodesToBrowse", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowseRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "View", &self.view, encoding);
+        write_json_field(
+            &mut object,
+            "RequestedMaxReferencesPerNode",
+            &self.requested_max_references_per_node,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NodesToBrowse",
+            &self.nodes_to_browse,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowseRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            view: read_json_field(value, "View", decoding_options)?,
+            requested_max_references_per_node: read_json_field(
+                value,
+                "RequestedMaxReferencesPerNode",
+                decoding_options,
+            )?,
+            nodes_to_browse: read_json_field(value, "NodesToBrowse", decoding_options)?,
+        })
+    }
+}
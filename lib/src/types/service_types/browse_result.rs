@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     service_types::impls::MessageInfo, service_types::ReferenceDescription,
-    status_codes::StatusCode,
+    status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,51 @@ impl BinaryEncoder<BrowseResult> for BrowseResult {
         })
     }
 }
+
+impl XmlEncoder for BrowseResult {
+    fn xml_type_name() -> String {
+        "BrowseResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "ContinuationPoint", &self.continuation_point);
+        write_xml_field(&mut element, "References", &self.references);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BrowseResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            continuation_point: read_xml_field(element, "ContinuationPoint", decoding_options)?,
+            references: read_xml_field(element, "References", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BrowseResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "ContinuationPoint",
+            &self.continuation_point,
+            encoding,
+        );
+        write_json_field(&mut object, "References", &self.references, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BrowseResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            continuation_point: read_json_field(value, "ContinuationPoint", decoding_options)?,
+            references: read_json_field(value, "References", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -71,3 +71,68 @@ impl BinaryEncoder<BuildInfo> for BuildInfo {
         })
     }
 }
+
+impl XmlEncoder for BuildInfo {
+    fn xml_type_name() -> String {
+        "BuildInfo".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ProductUri", &self.product_uri);
+        write_xml_field(&mut element, "ManufacturerName", &self.manufacturer_name);
+        write_xml_field(&mut element, "ProductName", &self.product_name);
+        write_xml_field(&mut element, "SoftwareVersion", &self.software_version);
+        write_xml_field(&mut element, "BuildNumber", &self.build_number);
+        write_xml_field(&mut element, "BuildDate", &self.build_date);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(BuildInfo {
+            product_uri: read_xml_field(element, "ProductUri", decoding_options)?,
+            manufacturer_name: read_xml_field(element, "ManufacturerName", decoding_options)?,
+            product_name: read_xml_field(element, "ProductName", decoding_options)?,
+            software_version: read_xml_field(element, "SoftwareVersion", decoding_options)?,
+            build_number: read_xml_field(element, "BuildNumber", decoding_options)?,
+            build_date: read_xml_field(element, "BuildDate", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for BuildInfo {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ProductUri", &self.product_uri, encoding);
+        write_json_field(
+            &mut object,
+            "ManufacturerName",
+            &self.manufacturer_name,
+            encoding,
+        );
+        write_json_field(&mut object, "ProductName", &self.product_name, encoding);
+        write_json_field(
+            &mut object,
+            "SoftwareVersion",
+            &self.software_version,
+            encoding,
+        );
+        write_json_field(&mut object, "BuildNumber", &self.build_number, encoding);
+        write_json_field(&mut object, "BuildDate", &self.build_date, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(BuildInfo {
+            product_uri: read_json_field(value, "ProductUri", decoding_options)?,
+            manufacturer_name: read_json_field(value, "ManufacturerName", decoding_options)?,
+            product_name: read_json_field(value, "ProductName", decoding_options)?,
+            software_version: read_json_field(value, "SoftwareVersion", decoding_options)?,
+            build_number: read_json_field(value, "BuildNumber", decoding_options)?,
+            build_date: read_json_field(value, "BuildDate", decoding_options)?,
+        })
+    }
+}
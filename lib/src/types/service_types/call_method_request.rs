@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, variant::Variant,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,51 @@ impl BinaryEncoder<CallMethodRequest> for CallMethodRequest {
         })
     }
 }
+
+impl XmlEncoder for CallMethodRequest {
+    fn xml_type_name() -> String {
+        "CallMethodRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ObjectId", &self.object_id);
+        write_xml_field(&mut element, "MethodId", &self.method_id);
+        write_xml_field(&mut element, "InputArguments", &self.input_arguments);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CallMethodRequest {
+            object_id: read_xml_field(element, "ObjectId", decoding_options)?,
+            method_id: read_xml_field(element, "MethodId", decoding_options)?,
+            input_arguments: read_xml_field(element, "InputArguments", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CallMethodRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ObjectId", &self.object_id, encoding);
+        write_json_field(&mut object, "MethodId", &self.method_id, encoding);
+        write_json_field(
+            &mut object,
+            "InputArguments",
+            &self.input_arguments,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CallMethodRequest {
+            object_id: read_json_field(value, "ObjectId", decoding_options)?,
+            method_id: read_json_field(value, "MethodId", decoding_options)?,
+            input_arguments: read_json_field(value, "InputArguments", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode, variant::Variant,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -62,3 +62,89 @@ impl BinaryEncoder<CallMethodResult> for CallMethodResult {
         })
     }
 }
+
+impl XmlEncoder for CallMethodResult {
+    fn xml_type_name() -> String {
+        "CallMethodResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(
+            &mut element,
+            "InputArgumentResults",
+            &self.input_argument_results,
+        );
+        write_xml_field(
+            &mut element,
+            "InputArgumentDiagnosticInfos",
+            &self.input_argument_diagnostic_infos,
+        );
+        write_xml_field(&mut element, "OutputArguments", &self.output_arguments);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CallMethodResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            input_argument_results: read_xml_field(
+                element,
+                "InputArgumentResults",
+                decoding_options,
+            )?,
+            input_argument_diagnostic_infos: read_xml_field(
+                element,
+                "InputArgumentDiagnosticInfos",
+                decoding_options,
+            )?,
+            output_arguments: read_xml_field(element, "OutputArguments", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CallMethodResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "InputArgumentResults",
+            &self.input_argument_results,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "InputArgumentDiagnosticInfos",
+            &self.input_argument_diagnostic_infos,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "OutputArguments",
+            &self.output_arguments,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CallMethodResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            input_argument_results: read_json_field(
+                value,
+                "InputArgumentResults",
+                decoding_options,
+            )?,
+            input_argument_diagnostic_infos: read_json_field(
+                value,
+                "InputArgumentDiagnosticInfos",
+                decoding_options,
+            )?,
+            output_arguments: read_json_field(value, "OutputArguments", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::CallMethodRequest,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::CallMethodRequest, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<CallRequest> for CallRequest {
         })
     }
 }
+
+impl XmlEncoder for CallRequest {
+    fn xml_type_name() -> String {
+        "CallRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "MethodsToCall", &self.methods_to_call);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CallRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            methods_to_call: read_xml_field(element, "MethodsToCall", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CallRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "MethodsToCall",
+            &self.methods_to_call,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CallRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            methods_to_call: read_json_field(value, "MethodsToCall", decoding_options)?,
+        })
+    }
+}
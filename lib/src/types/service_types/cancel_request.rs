@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<CancelRequest> for CancelRequest {
         })
     }
 }
+
+impl XmlEncoder for CancelRequest {
+    fn xml_type_name() -> String {
+        "CancelRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "RequestHandle", &self.request_handle);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CancelRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            request_handle: read_xml_field(element, "RequestHandle", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CancelRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "RequestHandle", &self.request_handle, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CancelRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            request_handle: read_json_field(value, "RequestHandle", decoding_options)?,
+        })
+    }
+}
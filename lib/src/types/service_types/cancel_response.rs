@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, response_header::ResponseHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, response_header::ResponseHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<CancelResponse> for CancelResponse {
         })
     }
 }
+
+impl XmlEncoder for CancelResponse {
+    fn xml_type_name() -> String {
+        "CancelResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "CancelCount", &self.cancel_count);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CancelResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            cancel_count: read_xml_field(element, "CancelCount", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CancelResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(&mut object, "CancelCount", &self.cancel_count, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CancelResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            cancel_count: read_json_field(value, "CancelCount", decoding_options)?,
+        })
+    }
+}
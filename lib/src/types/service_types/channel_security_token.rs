@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,55 @@ impl BinaryEncoder<ChannelSecurityToken> for ChannelSecurityToken {
         })
     }
 }
+
+impl XmlEncoder for ChannelSecurityToken {
+    fn xml_type_name() -> String {
+        "ChannelSecurityToken".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ChannelId", &self.channel_id);
+        write_xml_field(&mut element, "TokenId", &self.token_id);
+        write_xml_field(&mut element, "CreatedAt", &self.created_at);
+        write_xml_field(&mut element, "RevisedLifetime", &self.revised_lifetime);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ChannelSecurityToken {
+            channel_id: read_xml_field(element, "ChannelId", decoding_options)?,
+            token_id: read_xml_field(element, "TokenId", decoding_options)?,
+            created_at: read_xml_field(element, "CreatedAt", decoding_options)?,
+            revised_lifetime: read_xml_field(element, "RevisedLifetime", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ChannelSecurityToken {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ChannelId", &self.channel_id, encoding);
+        write_json_field(&mut object, "TokenId", &self.token_id, encoding);
+        write_json_field(&mut object, "CreatedAt", &self.created_at, encoding);
+        write_json_field(
+            &mut object,
+            "RevisedLifetime",
+            &self.revised_lifetime,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ChannelSecurityToken {
+            channel_id: read_json_field(value, "ChannelId", decoding_options)?,
+            token_id: read_json_field(value, "TokenId", decoding_options)?,
+            created_at: read_json_field(value, "CreatedAt", decoding_options)?,
+            revised_lifetime: read_json_field(value, "RevisedLifetime", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,38 @@ impl BinaryEncoder<CloseSecureChannelRequest> for CloseSecureChannelRequest {
         Ok(CloseSecureChannelRequest { request_header })
     }
 }
+
+impl XmlEncoder for CloseSecureChannelRequest {
+    fn xml_type_name() -> String {
+        "CloseSecureChannelRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CloseSecureChannelRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CloseSecureChannelRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CloseSecureChannelRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,51 @@ impl BinaryEncoder<CloseSessionRequest> for CloseSessionRequest {
         })
     }
 }
+
+impl XmlEncoder for CloseSessionRequest {
+    fn xml_type_name() -> String {
+        "CloseSessionRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "DeleteSubscriptions",
+            &self.delete_subscriptions,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CloseSessionRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            delete_subscriptions: read_xml_field(element, "DeleteSubscriptions", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CloseSessionRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "DeleteSubscriptions",
+            &self.delete_subscriptions,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CloseSessionRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            delete_subscriptions: read_json_field(value, "DeleteSubscriptions", decoding_options)?,
+        })
+    }
+}
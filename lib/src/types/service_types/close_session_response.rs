@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, response_header::ResponseHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, response_header::ResponseHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,43 @@ impl BinaryEncoder<CloseSessionResponse> for CloseSessionResponse {
         Ok(CloseSessionResponse { response_header })
     }
 }
+
+impl XmlEncoder for CloseSessionResponse {
+    fn xml_type_name() -> String {
+        "CloseSessionResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CloseSessionResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CloseSessionResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CloseSessionResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +52,42 @@ impl BinaryEncoder<ConfigurationVersionDataType> for ConfigurationVersionDataTyp
         })
     }
 }
+
+impl XmlEncoder for ConfigurationVersionDataType {
+    fn xml_type_name() -> String {
+        "ConfigurationVersionDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "MajorVersion", &self.major_version);
+        write_xml_field(&mut element, "MinorVersion", &self.minor_version);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ConfigurationVersionDataType {
+            major_version: read_xml_field(element, "MajorVersion", decoding_options)?,
+            minor_version: read_xml_field(element, "MinorVersion", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ConfigurationVersionDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MajorVersion", &self.major_version, encoding);
+        write_json_field(&mut object, "MinorVersion", &self.minor_version, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ConfigurationVersionDataType {
+            major_version: read_json_field(value, "MajorVersion", decoding_options)?,
+            minor_version: read_json_field(value, "MinorVersion", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -36,3 +37,30 @@ impl BinaryEncoder<ConnectionTransportDataType> for ConnectionTransportDataType
         Ok(ConnectionTransportDataType {})
     }
 }
+
+impl XmlEncoder for ConnectionTransportDataType {
+    fn xml_type_name() -> String {
+        "ConnectionTransportDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        Element::new(name)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ConnectionTransportDataType {})
+    }
+}
+
+impl JsonEncoder for ConnectionTransportDataType {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        JsonValue::Object(JsonObject::new())
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ConnectionTransportDataType {})
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::ContentFilterElement,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::ContentFilterElement, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -45,3 +45,38 @@ impl BinaryEncoder<ContentFilter> for ContentFilter {
         Ok(ContentFilter { elements })
     }
 }
+
+impl XmlEncoder for ContentFilter {
+    fn xml_type_name() -> String {
+        "ContentFilter".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Elements", &self.elements);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ContentFilter {
+            elements: read_xml_field(element, "Elements", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ContentFilter {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Elements", &self.elements, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ContentFilter {
+            elements: read_json_field(value, "Elements", decoding_options)?,
+        })
+    }
+}
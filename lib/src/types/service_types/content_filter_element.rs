@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
-    service_types::enums::FilterOperator, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
+    service_types::enums::FilterOperator, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,52 @@ impl BinaryEncoder<ContentFilterElement> for ContentFilterElement {
         })
     }
 }
+
+impl XmlEncoder for ContentFilterElement {
+    fn xml_type_name() -> String {
+        "ContentFilterElement".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "FilterOperator", &self.filter_operator);
+        write_xml_field(&mut element, "FilterOperands", &self.filter_operands);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ContentFilterElement {
+            filter_operator: read_xml_field(element, "FilterOperator", decoding_options)?,
+            filter_operands: read_xml_field(element, "FilterOperands", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ContentFilterElement {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "FilterOperator",
+            &self.filter_operator,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "FilterOperands",
+            &self.filter_operands,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ContentFilterElement {
+            filter_operator: read_json_field(value, "FilterOperator", decoding_options)?,
+            filter_operands: read_json_field(value, "FilterOperands", decoding_options)?,
+        })
+    }
+}
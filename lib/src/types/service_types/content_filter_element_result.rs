@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,72 @@ impl BinaryEncoder<ContentFilterElementResult> for ContentFilterElementResult {
         })
     }
 }
+
+impl XmlEncoder for ContentFilterElementResult {
+    fn xml_type_name() -> String {
+        "ContentFilterElementResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(
+            &mut element,
+            "OperandStatusCodes",
+            &self.operand_status_codes,
+        );
+        write_xml_field(
+            &mut element,
+            "OperandDiagnosticInfos",
+            &self.operand_diagnostic_infos,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ContentFilterElementResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            operand_status_codes: read_xml_field(element, "OperandStatusCodes", decoding_options)?,
+            operand_diagnostic_infos: read_xml_field(
+                element,
+                "OperandDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ContentFilterElementResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "OperandStatusCodes",
+            &self.operand_status_codes,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "OperandDiagnosticInfos",
+            &self.operand_diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ContentFilterElementResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            operand_status_codes: read_json_field(value, "OperandStatusCodes", decoding_options)?,
+            operand_diagnostic_infos: read_json_field(
+                value,
+                "OperandDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
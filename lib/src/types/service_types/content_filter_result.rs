@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, service_types::ContentFilterElementResult,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, service_types::ContentFilterElementResult, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -53,3 +53,64 @@ impl BinaryEncoder<ContentFilterResult> for ContentFilterResult {
         })
     }
 }
+
+impl XmlEncoder for ContentFilterResult {
+    fn xml_type_name() -> String {
+        "ContentFilterResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ElementResults", &self.element_results);
+        write_xml_field(
+            &mut element,
+            "ElementDiagnosticInfos",
+            &self.element_diagnostic_infos,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ContentFilterResult {
+            element_results: read_xml_field(element, "ElementResults", decoding_options)?,
+            element_diagnostic_infos: read_xml_field(
+                element,
+                "ElementDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ContentFilterResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ElementResults",
+            &self.element_results,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ElementDiagnosticInfos",
+            &self.element_diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ContentFilterResult {
+            element_results: read_json_field(value, "ElementResults", decoding_options)?,
+            element_diagnostic_infos: read_json_field(
+                value,
+                "ElementDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
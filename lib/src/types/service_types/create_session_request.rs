@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     request_header::RequestHeader, service_types::impls::MessageInfo,
-    service_types::ApplicationDescription, string::UAString,
+    service_types::ApplicationDescription, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -87,3 +87,114 @@ impl BinaryEncoder<CreateSessionRequest> for CreateSessionRequest {
         })
     }
 }
+
+impl XmlEncoder for CreateSessionRequest {
+    fn xml_type_name() -> String {
+        "CreateSessionRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "ClientDescription", &self.client_description);
+        write_xml_field(&mut element, "ServerUri", &self.server_uri);
+        write_xml_field(&mut element, "EndpointUrl", &self.endpoint_url);
+        write_xml_field(&mut element, "SessionName", &self.session_name);
+        write_xml_field(&mut element, "ClientNonce", &self.client_nonce);
+        write_xml_field(&mut element, "ClientCertificate", &self.client_certificate);
+        write_xml_field(
+            &mut element,
+            "RequestedSessionTimeout",
+            &self.requested_session_timeout,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxResponseMessageSize",
+            &self.max_response_message_size,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CreateSessionRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            client_description: read_xml_field(element, "ClientDescription", decoding_options)?,
+            server_uri: read_xml_field(element, "ServerUri", decoding_options)?,
+            endpoint_url: read_xml_field(element, "EndpointUrl", decoding_options)?,
+            session_name: read_xml_field(element, "SessionName", decoding_options)?,
+            client_nonce: read_xml_field(element, "ClientNonce", decoding_options)?,
+            client_certificate: read_xml_field(element, "ClientCertificate", decoding_options)?,
+            requested_session_timeout: read_xml_field(
+                element,
+                "RequestedSessionTimeout",
+                decoding_options,
+            )?,
+            max_response_message_size: read_xml_field(
+                element,
+                "MaxResponseMessageSize",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for CreateSessionRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ClientDescription",
+            &self.client_description,
+            encoding,
+        );
+        write_json_field(&mut object, "ServerUri", &self.server_uri, encoding);
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, encoding);
+        write_json_field(&mut object, "SessionName", &self.session_name, encoding);
+        write_json_field(&mut object, "ClientNonce", &self.client_nonce, encoding);
+        write_json_field(
+            &mut object,
+            "ClientCertificate",
+            &self.client_certificate,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedSessionTimeout",
+            &self.requested_session_timeout,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxResponseMessageSize",
+            &self.max_response_message_size,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CreateSessionRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            client_description: read_json_field(value, "ClientDescription", decoding_options)?,
+            server_uri: read_json_field(value, "ServerUri", decoding_options)?,
+            endpoint_url: read_json_field(value, "EndpointUrl", decoding_options)?,
+            session_name: read_json_field(value, "SessionName", decoding_options)?,
+            client_nonce: read_json_field(value, "ClientNonce", decoding_options)?,
+            client_certificate: read_json_field(value, "ClientCertificate", decoding_options)?,
+            requested_session_timeout: read_json_field(
+                value,
+                "RequestedSessionTimeout",
+                decoding_options,
+            )?,
+            max_response_message_size: read_json_field(
+                value,
+                "MaxResponseMessageSize",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,10 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    response_header::ResponseHeader, service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_id::NodeId,
+    node_ids::ObjectId, response_header::ResponseHeader, service_types::impls::MessageInfo,
     service_types::EndpointDescription, service_types::SignatureData,
-    service_types::SignedSoftwareCertificate,
+    service_types::SignedSoftwareCertificate, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -95,3 +95,154 @@ impl BinaryEncoder<CreateSessionResponse> for CreateSessionResponse {
         })
     }
 }
+
+impl XmlEncoder for CreateSessionResponse {
+    fn xml_type_name() -> String {
+        "CreateSessionResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "SessionId", &self.session_id);
+        write_xml_field(
+            &mut element,
+            "AuthenticationToken",
+            &self.authentication_token,
+        );
+        write_xml_field(
+            &mut element,
+            "RevisedSessionTimeout",
+            &self.revised_session_timeout,
+        );
+        write_xml_field(&mut element, "ServerNonce", &self.server_nonce);
+        write_xml_field(&mut element, "ServerCertificate", &self.server_certificate);
+        write_xml_field(&mut element, "ServerEndpoints", &self.server_endpoints);
+        write_xml_field(
+            &mut element,
+            "ServerSoftwareCertificates",
+            &self.server_software_certificates,
+        );
+        write_xml_field(&mut element, "ServerSignature", &self.server_signature);
+        write_xml_field(
+            &mut element,
+            "MaxRequestMessageSize",
+            &self.max_request_message_size,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CreateSessionResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            session_id: read_xml_field(element, "SessionId", decoding_options)?,
+            authentication_token: read_xml_field(element, "AuthenticationToken", decoding_options)?,
+            revised_session_timeout: read_xml_field(
+                element,
+                "RevisedSessionTimeout",
+                decoding_options,
+            )?,
+            server_nonce: read_xml_field(element, "ServerNonce", decoding_options)?,
+            server_certificate: read_xml_field(element, "ServerCertificate", decoding_options)?,
+            server_endpoints: read_xml_field(element, "ServerEndpoints", decoding_options)?,
+            server_software_certificates: read_xml_field(
+                element,
+                "ServerSoftwareCertificates",
+                decoding_options,
+            )?,
+            server_signature: read_xml_field(element, "ServerSignature", decoding_options)?,
+            max_request_message_size: read_xml_field(
+                element,
+                "MaxRequestMessageSize",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for CreateSessionResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(&mut object, "SessionId", &self.session_id, encoding);
+        write_json_field(
+            &mut object,
+            "AuthenticationToken",
+            &self.authentication_token,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedSessionTimeout",
+            &self.revised_session_timeout,
+            encoding,
+        );
+        write_json_field(&mut object, "ServerNonce", &self.server_nonce, encoding);
+        write_json_field(
+            &mut object,
+            "ServerCertificate",
+            &self.server_certificate,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerEndpoints",
+            &self.server_endpoints,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerSoftwareCertificates",
+            &self.server_software_certificates,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerSignature",
+            &self.server_signature,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxRequestMessageSize",
+            &self.max_request_message_size,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CreateSessionResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            session_id: read_json_field(value, "SessionId", decoding_options)?,
+            authentication_token: read_json_field(value, "AuthenticationToken", decoding_options)?,
+            revised_session_timeout: read_json_field(
+                value,
+                "RevisedSessionTimeout",
+                decoding_options,
+            )?,
+            server_nonce: read_json_field(value, "ServerNonce", decoding_options)?,
+            server_certificate: read_json_field(value, "ServerCertificate", decoding_options)?,
+            server_endpoints: read_json_field(value, "ServerEndpoints", decoding_options)?,
+            server_software_certificates: read_json_field(
+                value,
+                "ServerSoftwareCertificates",
+                decoding_options,
+            )?,
+            server_signature: read_json_field(value, "ServerSignature", decoding_options)?,
+            max_request_message_size: read_json_field(
+                value,
+                "MaxRequestMessageSize",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -76,3 +76,135 @@ impl BinaryEncoder<CreateSubscriptionRequest> for CreateSubscriptionRequest {
         })
     }
 }
+
+impl XmlEncoder for CreateSubscriptionRequest {
+    fn xml_type_name() -> String {
+        "CreateSubscriptionRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "RequestedPublishingInterval",
+            &self.requested_publishing_interval,
+        );
+        write_xml_field(
+            &mut element,
+            "RequestedLifetimeCount",
+            &self.requested_lifetime_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RequestedMaxKeepAliveCount",
+            &self.requested_max_keep_alive_count,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxNotificationsPerPublish",
+            &self.max_notifications_per_publish,
+        );
+        write_xml_field(&mut element, "PublishingEnabled", &self.publishing_enabled);
+        write_xml_field(&mut element, "Priority", &self.priority);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CreateSubscriptionRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            requested_publishing_interval: read_xml_field(
+                element,
+                "RequestedPublishingInterval",
+                decoding_options,
+            )?,
+            requested_lifetime_count: read_xml_field(
+                element,
+                "RequestedLifetimeCount",
+                decoding_options,
+            )?,
+            requested_max_keep_alive_count: read_xml_field(
+                element,
+                "RequestedMaxKeepAliveCount",
+                decoding_options,
+            )?,
+            max_notifications_per_publish: read_xml_field(
+                element,
+                "MaxNotificationsPerPublish",
+                decoding_options,
+            )?,
+            publishing_enabled: read_xml_field(element, "PublishingEnabled", decoding_options)?,
+            priority: read_xml_field(element, "Priority", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CreateSubscriptionRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "RequestedPublishingInterval",
+            &self.requested_publishing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedLifetimeCount",
+            &self.requested_lifetime_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedMaxKeepAliveCount",
+            &self.requested_max_keep_alive_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxNotificationsPerPublish",
+            &self.max_notifications_per_publish,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingEnabled",
+            &self.publishing_enabled,
+            encoding,
+        );
+        write_json_field(&mut object, "Priority", &self.priority, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CreateSubscriptionRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            requested_publishing_interval: read_json_field(
+                value,
+                "RequestedPublishingInterval",
+                decoding_options,
+            )?,
+            requested_lifetime_count: read_json_field(
+                value,
+                "RequestedLifetimeCount",
+                decoding_options,
+            )?,
+            requested_max_keep_alive_count: read_json_field(
+                value,
+                "RequestedMaxKeepAliveCount",
+                decoding_options,
+            )?,
+            max_notifications_per_publish: read_json_field(
+                value,
+                "MaxNotificationsPerPublish",
+                decoding_options,
+            )?,
+            publishing_enabled: read_json_field(value, "PublishingEnabled", decoding_options)?,
+            priority: read_json_field(value, "Priority", decoding_options)?,
+        })
+    }
+}
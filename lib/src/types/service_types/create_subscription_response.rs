@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, response_header::ResponseHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, response_header::ResponseHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,115 @@ impl BinaryEncoder<CreateSubscriptionResponse> for CreateSubscriptionResponse {
         })
     }
 }
+
+impl XmlEncoder for CreateSubscriptionResponse {
+    fn xml_type_name() -> String {
+        "CreateSubscriptionResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(
+            &mut element,
+            "RevisedPublishingInterval",
+            &self.revised_publishing_interval,
+        );
+        write_xml_field(
+            &mut element,
+            "RevisedLifetimeCount",
+            &self.revised_lifetime_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RevisedMaxKeepAliveCount",
+            &self.revised_max_keep_alive_count,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CreateSubscriptionResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            revised_publishing_interval: read_xml_field(
+                element,
+                "RevisedPublishingInterval",
+                decoding_options,
+            )?,
+            revised_lifetime_count: read_xml_field(
+                element,
+                "RevisedLifetimeCount",
+                decoding_options,
+            )?,
+            revised_max_keep_alive_count: read_xml_field(
+                element,
+                "RevisedMaxKeepAliveCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for CreateSubscriptionResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedPublishingInterval",
+            &self.revised_publishing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedLifetimeCount",
+            &self.revised_lifetime_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedMaxKeepAliveCount",
+            &self.revised_max_keep_alive_count,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CreateSubscriptionResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            revised_publishing_interval: read_json_field(
+                value,
+                "RevisedPublishingInterval",
+                decoding_options,
+            )?,
+            revised_lifetime_count: read_json_field(
+                value,
+                "RevisedLifetimeCount",
+                decoding_options,
+            )?,
+            revised_max_keep_alive_count: read_json_field(
+                value,
+                "RevisedMaxKeepAliveCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,55 @@ impl BinaryEncoder<CurrencyUnitType> for CurrencyUnitType {
         })
     }
 }
+
+impl XmlEncoder for CurrencyUnitType {
+    fn xml_type_name() -> String {
+        "CurrencyUnitType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NumericCode", &self.numeric_code);
+        write_xml_field(&mut element, "Exponent", &self.exponent);
+        write_xml_field(&mut element, "AlphabeticCode", &self.alphabetic_code);
+        write_xml_field(&mut element, "Currency", &self.currency);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(CurrencyUnitType {
+            numeric_code: read_xml_field(element, "NumericCode", decoding_options)?,
+            exponent: read_xml_field(element, "Exponent", decoding_options)?,
+            alphabetic_code: read_xml_field(element, "AlphabeticCode", decoding_options)?,
+            currency: read_xml_field(element, "Currency", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for CurrencyUnitType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NumericCode", &self.numeric_code, encoding);
+        write_json_field(&mut object, "Exponent", &self.exponent, encoding);
+        write_json_field(
+            &mut object,
+            "AlphabeticCode",
+            &self.alphabetic_code,
+            encoding,
+        );
+        write_json_field(&mut object, "Currency", &self.currency, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(CurrencyUnitType {
+            numeric_code: read_json_field(value, "NumericCode", decoding_options)?,
+            exponent: read_json_field(value, "Exponent", decoding_options)?,
+            alphabetic_code: read_json_field(value, "AlphabeticCode", decoding_options)?,
+            currency: read_json_field(value, "Currency", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, service_types::enums::DataChangeTrigger};
+use crate::types::{
+    basic_types::*, encoding::*, json::*, service_types::enums::DataChangeTrigger, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -48,3 +50,46 @@ impl BinaryEncoder<DataChangeFilter> for DataChangeFilter {
         })
     }
 }
+
+impl XmlEncoder for DataChangeFilter {
+    fn xml_type_name() -> String {
+        "DataChangeFilter".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Trigger", &self.trigger);
+        write_xml_field(&mut element, "DeadbandType", &self.deadband_type);
+        write_xml_field(&mut element, "DeadbandValue", &self.deadband_value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataChangeFilter {
+            trigger: read_xml_field(element, "Trigger", decoding_options)?,
+            deadband_type: read_xml_field(element, "DeadbandType", decoding_options)?,
+            deadband_value: read_xml_field(element, "DeadbandValue", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataChangeFilter {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Trigger", &self.trigger, encoding);
+        write_json_field(&mut object, "DeadbandType", &self.deadband_type, encoding);
+        write_json_field(&mut object, "DeadbandValue", &self.deadband_value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataChangeFilter {
+            trigger: read_json_field(value, "Trigger", decoding_options)?,
+            deadband_type: read_json_field(value, "DeadbandType", decoding_options)?,
+            deadband_value: read_json_field(value, "DeadbandValue", decoding_options)?,
+        })
+    }
+}
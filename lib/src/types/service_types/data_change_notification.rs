@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*,
-    service_types::MonitoredItemNotification,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*,
+    service_types::MonitoredItemNotification, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -46,3 +46,52 @@ impl BinaryEncoder<DataChangeNotification> for DataChangeNotification {
         })
     }
 }
+
+impl XmlEncoder for DataChangeNotification {
+    fn xml_type_name() -> String {
+        "DataChangeNotification".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "MonitoredItems", &self.monitored_items);
+        write_xml_field(&mut element, "DiagnosticInfos", &self.diagnostic_infos);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataChangeNotification {
+            monitored_items: read_xml_field(element, "MonitoredItems", decoding_options)?,
+            diagnostic_infos: read_xml_field(element, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataChangeNotification {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "MonitoredItems",
+            &self.monitored_items,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DiagnosticInfos",
+            &self.diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataChangeNotification {
+            monitored_items: read_json_field(value, "MonitoredItems", decoding_options)?,
+            diagnostic_infos: read_json_field(value, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
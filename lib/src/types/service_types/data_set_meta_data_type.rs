@@ -8,10 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, guid::Guid, localized_text::LocalizedText,
+    basic_types::*, encoding::*, guid::Guid, json::*, localized_text::LocalizedText,
     service_types::ConfigurationVersionDataType, service_types::EnumDescription,
     service_types::FieldMetaData, service_types::SimpleTypeDescription,
-    service_types::StructureDescription, string::UAString,
+    service_types::StructureDescription, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -85,3 +85,111 @@ impl BinaryEncoder<DataSetMetaDataType> for DataSetMetaDataType {
         })
     }
 }
+
+impl XmlEncoder for DataSetMetaDataType {
+    fn xml_type_name() -> String {
+        "DataSetMetaDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Namespaces", &self.namespaces);
+        write_xml_field(
+            &mut element,
+            "StructureDataTypes",
+            &self.structure_data_types,
+        );
+        write_xml_field(&mut element, "EnumDataTypes", &self.enum_data_types);
+        write_xml_field(&mut element, "SimpleDataTypes", &self.simple_data_types);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "Fields", &self.fields);
+        write_xml_field(&mut element, "DataSetClassId", &self.data_set_class_id);
+        write_xml_field(
+            &mut element,
+            "ConfigurationVersion",
+            &self.configuration_version,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetMetaDataType {
+            namespaces: read_xml_field(element, "Namespaces", decoding_options)?,
+            structure_data_types: read_xml_field(element, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_xml_field(element, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_xml_field(element, "SimpleDataTypes", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            fields: read_xml_field(element, "Fields", decoding_options)?,
+            data_set_class_id: read_xml_field(element, "DataSetClassId", decoding_options)?,
+            configuration_version: read_xml_field(
+                element,
+                "ConfigurationVersion",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for DataSetMetaDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, encoding);
+        write_json_field(
+            &mut object,
+            "StructureDataTypes",
+            &self.structure_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "EnumDataTypes",
+            &self.enum_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SimpleDataTypes",
+            &self.simple_data_types,
+            encoding,
+        );
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "Fields", &self.fields, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetClassId",
+            &self.data_set_class_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ConfigurationVersion",
+            &self.configuration_version,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataSetMetaDataType {
+            namespaces: read_json_field(value, "Namespaces", decoding_options)?,
+            structure_data_types: read_json_field(value, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_json_field(value, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_json_field(value, "SimpleDataTypes", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            fields: read_json_field(value, "Fields", decoding_options)?,
+            data_set_class_id: read_json_field(value, "DataSetClassId", decoding_options)?,
+            configuration_version: read_json_field(
+                value,
+                "ConfigurationVersion",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,11 +8,11 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     service_types::enums::DataSetFieldContentMask, service_types::enums::MessageSecurityMode,
     service_types::impls::MessageInfo, service_types::DataSetMetaDataType,
     service_types::EndpointDescription, service_types::KeyValuePair, string::UAString,
-    variant::Variant,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -132,3 +132,211 @@ impl BinaryEncoder<DataSetReaderDataType> for DataSetReaderDataType {
         })
     }
 }
+
+impl XmlEncoder for DataSetReaderDataType {
+    fn xml_type_name() -> String {
+        "DataSetReaderDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        write_xml_field(&mut element, "PublisherId", &self.publisher_id);
+        write_xml_field(&mut element, "WriterGroupId", &self.writer_group_id);
+        write_xml_field(&mut element, "DataSetWriterId", &self.data_set_writer_id);
+        write_xml_field(&mut element, "DataSetMetaData", &self.data_set_meta_data);
+        write_xml_field(
+            &mut element,
+            "DataSetFieldContentMask",
+            &self.data_set_field_content_mask,
+        );
+        write_xml_field(
+            &mut element,
+            "MessageReceiveTimeout",
+            &self.message_receive_timeout,
+        );
+        write_xml_field(&mut element, "KeyFrameCount", &self.key_frame_count);
+        write_xml_field(&mut element, "HeaderLayoutUri", &self.header_layout_uri);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityGroupId", &self.security_group_id);
+        write_xml_field(
+            &mut element,
+            "SecurityKeyServices",
+            &self.security_key_services,
+        );
+        write_xml_field(
+            &mut element,
+            "DataSetReaderProperties",
+            &self.data_set_reader_properties,
+        );
+        write_xml_field(&mut element, "TransportSettings", &self.transport_settings);
+        write_xml_field(&mut element, "MessageSettings", &self.message_settings);
+        write_xml_field(&mut element, "SubscribedDataSet", &self.subscribed_data_set);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetReaderDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+            publisher_id: read_xml_field(element, "PublisherId", decoding_options)?,
+            writer_group_id: read_xml_field(element, "WriterGroupId", decoding_options)?,
+            data_set_writer_id: read_xml_field(element, "DataSetWriterId", decoding_options)?,
+            data_set_meta_data: read_xml_field(element, "DataSetMetaData", decoding_options)?,
+            data_set_field_content_mask: read_xml_field(
+                element,
+                "DataSetFieldContentMask",
+                decoding_options,
+            )?,
+            message_receive_timeout: read_xml_field(
+                element,
+                "MessageReceiveTimeout",
+                decoding_options,
+            )?,
+            key_frame_count: read_xml_field(element, "KeyFrameCount", decoding_options)?,
+            header_layout_uri: read_xml_field(element, "HeaderLayoutUri", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_group_id: read_xml_field(element, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_xml_field(
+                element,
+                "SecurityKeyServices",
+                decoding_options,
+            )?,
+            data_set_reader_properties: read_xml_field(
+                element,
+                "DataSetReaderProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_xml_field(element, "TransportSettings", decoding_options)?,
+            message_settings: read_xml_field(element, "MessageSettings", decoding_options)?,
+            subscribed_data_set: read_xml_field(element, "SubscribedDataSet", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataSetReaderDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, encoding);
+        write_json_field(
+            &mut object,
+            "WriterGroupId",
+            &self.writer_group_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetWriterId",
+            &self.data_set_writer_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetMetaData",
+            &self.data_set_meta_data,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetFieldContentMask",
+            &self.data_set_field_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageReceiveTimeout",
+            &self.message_receive_timeout,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "KeyFrameCount",
+            &self.key_frame_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "HeaderLayoutUri",
+            &self.header_layout_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityGroupId",
+            &self.security_group_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityKeyServices",
+            &self.security_key_services,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetReaderProperties",
+            &self.data_set_reader_properties,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportSettings",
+            &self.transport_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageSettings",
+            &self.message_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SubscribedDataSet",
+            &self.subscribed_data_set,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataSetReaderDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+            publisher_id: read_json_field(value, "PublisherId", decoding_options)?,
+            writer_group_id: read_json_field(value, "WriterGroupId", decoding_options)?,
+            data_set_writer_id: read_json_field(value, "DataSetWriterId", decoding_options)?,
+            data_set_meta_data: read_json_field(value, "DataSetMetaData", decoding_options)?,
+            data_set_field_content_mask: read_json_field(
+                value,
+                "DataSetFieldContentMask",
+                decoding_options,
+            )?,
+            message_receive_timeout: read_json_field(
+                value,
+                "MessageReceiveTimeout",
+                decoding_options,
+            )?,
+            key_frame_count: read_json_field(value, "KeyFrameCount", decoding_options)?,
+            header_layout_uri: read_json_field(value, "HeaderLayoutUri", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_group_id: read_json_field(value, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_json_field(value, "SecurityKeyServices", decoding_options)?,
+            data_set_reader_properties: read_json_field(
+                value,
+                "DataSetReaderProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_json_field(value, "TransportSettings", decoding_options)?,
+            message_settings: read_json_field(value, "MessageSettings", decoding_options)?,
+            subscribed_data_set: read_json_field(value, "SubscribedDataSet", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     service_types::enums::DataSetFieldContentMask, service_types::impls::MessageInfo,
-    service_types::KeyValuePair, string::UAString,
+    service_types::KeyValuePair, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -89,3 +89,124 @@ impl BinaryEncoder<DataSetWriterDataType> for DataSetWriterDataType {
         })
     }
 }
+
+impl XmlEncoder for DataSetWriterDataType {
+    fn xml_type_name() -> String {
+        "DataSetWriterDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        write_xml_field(&mut element, "DataSetWriterId", &self.data_set_writer_id);
+        write_xml_field(
+            &mut element,
+            "DataSetFieldContentMask",
+            &self.data_set_field_content_mask,
+        );
+        write_xml_field(&mut element, "KeyFrameCount", &self.key_frame_count);
+        write_xml_field(&mut element, "DataSetName", &self.data_set_name);
+        write_xml_field(
+            &mut element,
+            "DataSetWriterProperties",
+            &self.data_set_writer_properties,
+        );
+        write_xml_field(&mut element, "TransportSettings", &self.transport_settings);
+        write_xml_field(&mut element, "MessageSettings", &self.message_settings);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetWriterDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+            data_set_writer_id: read_xml_field(element, "DataSetWriterId", decoding_options)?,
+            data_set_field_content_mask: read_xml_field(
+                element,
+                "DataSetFieldContentMask",
+                decoding_options,
+            )?,
+            key_frame_count: read_xml_field(element, "KeyFrameCount", decoding_options)?,
+            data_set_name: read_xml_field(element, "DataSetName", decoding_options)?,
+            data_set_writer_properties: read_xml_field(
+                element,
+                "DataSetWriterProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_xml_field(element, "TransportSettings", decoding_options)?,
+            message_settings: read_xml_field(element, "MessageSettings", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataSetWriterDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetWriterId",
+            &self.data_set_writer_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetFieldContentMask",
+            &self.data_set_field_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "KeyFrameCount",
+            &self.key_frame_count,
+            encoding,
+        );
+        write_json_field(&mut object, "DataSetName", &self.data_set_name, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetWriterProperties",
+            &self.data_set_writer_properties,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportSettings",
+            &self.transport_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageSettings",
+            &self.message_settings,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataSetWriterDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+            data_set_writer_id: read_json_field(value, "DataSetWriterId", decoding_options)?,
+            data_set_field_content_mask: read_json_field(
+                value,
+                "DataSetFieldContentMask",
+                decoding_options,
+            )?,
+            key_frame_count: read_json_field(value, "KeyFrameCount", decoding_options)?,
+            data_set_name: read_json_field(value, "DataSetName", decoding_options)?,
+            data_set_writer_properties: read_json_field(
+                value,
+                "DataSetWriterProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_json_field(value, "TransportSettings", decoding_options)?,
+            message_settings: read_json_field(value, "MessageSettings", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    qualified_name::QualifiedName, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    qualified_name::QualifiedName, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -48,3 +48,42 @@ impl BinaryEncoder<DataTypeDescription> for DataTypeDescription {
         Ok(DataTypeDescription { data_type_id, name })
     }
 }
+
+impl XmlEncoder for DataTypeDescription {
+    fn xml_type_name() -> String {
+        "DataTypeDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataTypeId", &self.data_type_id);
+        write_xml_field(&mut element, "Name", &self.name);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataTypeDescription {
+            data_type_id: read_xml_field(element, "DataTypeId", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataTypeDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, encoding);
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataTypeDescription {
+            data_type_id: read_json_field(value, "DataTypeId", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
     service_types::EnumDescription, service_types::SimpleTypeDescription,
-    service_types::StructureDescription, string::UAString,
+    service_types::StructureDescription, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -64,3 +64,69 @@ impl BinaryEncoder<DataTypeSchemaHeader> for DataTypeSchemaHeader {
         })
     }
 }
+
+impl XmlEncoder for DataTypeSchemaHeader {
+    fn xml_type_name() -> String {
+        "DataTypeSchemaHeader".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Namespaces", &self.namespaces);
+        write_xml_field(
+            &mut element,
+            "StructureDataTypes",
+            &self.structure_data_types,
+        );
+        write_xml_field(&mut element, "EnumDataTypes", &self.enum_data_types);
+        write_xml_field(&mut element, "SimpleDataTypes", &self.simple_data_types);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataTypeSchemaHeader {
+            namespaces: read_xml_field(element, "Namespaces", decoding_options)?,
+            structure_data_types: read_xml_field(element, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_xml_field(element, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_xml_field(element, "SimpleDataTypes", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DataTypeSchemaHeader {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, encoding);
+        write_json_field(
+            &mut object,
+            "StructureDataTypes",
+            &self.structure_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "EnumDataTypes",
+            &self.enum_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SimpleDataTypes",
+            &self.simple_data_types,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DataTypeSchemaHeader {
+            namespaces: read_json_field(value, "Namespaces", decoding_options)?,
+            structure_data_types: read_json_field(value, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_json_field(value, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_json_field(value, "SimpleDataTypes", decoding_options)?,
+        })
+    }
+}
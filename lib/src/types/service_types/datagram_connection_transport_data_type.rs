@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, extension_object::ExtensionObject};
+use crate::types::{
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +37,43 @@ impl BinaryEncoder<DatagramConnectionTransportDataType> for DatagramConnectionTr
         Ok(DatagramConnectionTransportDataType { discovery_address })
     }
 }
+
+impl XmlEncoder for DatagramConnectionTransportDataType {
+    fn xml_type_name() -> String {
+        "DatagramConnectionTransportDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DiscoveryAddress", &self.discovery_address);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DatagramConnectionTransportDataType {
+            discovery_address: read_xml_field(element, "DiscoveryAddress", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DatagramConnectionTransportDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "DiscoveryAddress",
+            &self.discovery_address,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DatagramConnectionTransportDataType {
+            discovery_address: read_json_field(value, "DiscoveryAddress", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*};
+use crate::types::{basic_types::*, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,60 @@ impl BinaryEncoder<DatagramWriterGroupTransportDataType> for DatagramWriterGroup
         })
     }
 }
+
+impl XmlEncoder for DatagramWriterGroupTransportDataType {
+    fn xml_type_name() -> String {
+        "DatagramWriterGroupTransportDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "MessageRepeatCount",
+            &self.message_repeat_count,
+        );
+        write_xml_field(
+            &mut element,
+            "MessageRepeatDelay",
+            &self.message_repeat_delay,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DatagramWriterGroupTransportDataType {
+            message_repeat_count: read_xml_field(element, "MessageRepeatCount", decoding_options)?,
+            message_repeat_delay: read_xml_field(element, "MessageRepeatDelay", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DatagramWriterGroupTransportDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "MessageRepeatCount",
+            &self.message_repeat_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageRepeatDelay",
+            &self.message_repeat_delay,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DatagramWriterGroupTransportDataType {
+            message_repeat_count: read_json_field(value, "MessageRepeatCount", decoding_options)?,
+            message_repeat_delay: read_json_field(value, "MessageRepeatDelay", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -48,3 +48,42 @@ impl BinaryEncoder<DecimalDataType> for DecimalDataType {
         Ok(DecimalDataType { scale, value })
     }
 }
+
+impl XmlEncoder for DecimalDataType {
+    fn xml_type_name() -> String {
+        "DecimalDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Scale", &self.scale);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DecimalDataType {
+            scale: read_xml_field(element, "Scale", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DecimalDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Scale", &self.scale, encoding);
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DecimalDataType {
+            scale: read_json_field(value, "Scale", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId};
+use crate::types::{
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,3 +41,42 @@ impl BinaryEncoder<DeleteAtTimeDetails> for DeleteAtTimeDetails {
         Ok(DeleteAtTimeDetails { node_id, req_times })
     }
 }
+
+impl XmlEncoder for DeleteAtTimeDetails {
+    fn xml_type_name() -> String {
+        "DeleteAtTimeDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "ReqTimes", &self.req_times);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteAtTimeDetails {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            req_times: read_xml_field(element, "ReqTimes", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteAtTimeDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "ReqTimes", &self.req_times, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteAtTimeDetails {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            req_times: read_json_field(value, "ReqTimes", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, byte_string::ByteString, encoding::*, node_id::NodeId};
+use crate::types::{
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_id::NodeId, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,3 +41,42 @@ impl BinaryEncoder<DeleteEventDetails> for DeleteEventDetails {
         Ok(DeleteEventDetails { node_id, event_ids })
     }
 }
+
+impl XmlEncoder for DeleteEventDetails {
+    fn xml_type_name() -> String {
+        "DeleteEventDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "EventIds", &self.event_ids);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteEventDetails {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            event_ids: read_xml_field(element, "EventIds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteEventDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "EventIds", &self.event_ids, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteEventDetails {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            event_ids: read_json_field(value, "EventIds", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,59 @@ impl BinaryEncoder<DeleteNodesItem> for DeleteNodesItem {
         })
     }
 }
+
+impl XmlEncoder for DeleteNodesItem {
+    fn xml_type_name() -> String {
+        "DeleteNodesItem".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(
+            &mut element,
+            "DeleteTargetReferences",
+            &self.delete_target_references,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteNodesItem {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            delete_target_references: read_xml_field(
+                element,
+                "DeleteTargetReferences",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteNodesItem {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "DeleteTargetReferences",
+            &self.delete_target_references,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteNodesItem {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            delete_target_references: read_json_field(
+                value,
+                "DeleteTargetReferences",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId};
+use crate::types::{
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,3 +54,55 @@ impl BinaryEncoder<DeleteRawModifiedDetails> for DeleteRawModifiedDetails {
         })
     }
 }
+
+impl XmlEncoder for DeleteRawModifiedDetails {
+    fn xml_type_name() -> String {
+        "DeleteRawModifiedDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "IsDeleteModified", &self.is_delete_modified);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "EndTime", &self.end_time);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteRawModifiedDetails {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            is_delete_modified: read_xml_field(element, "IsDeleteModified", decoding_options)?,
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            end_time: read_xml_field(element, "EndTime", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteRawModifiedDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "IsDeleteModified",
+            &self.is_delete_modified,
+            encoding,
+        );
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "EndTime", &self.end_time, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteRawModifiedDetails {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            is_delete_modified: read_json_field(value, "IsDeleteModified", decoding_options)?,
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            end_time: read_json_field(value, "EndTime", decoding_options)?,
+        })
+    }
+}
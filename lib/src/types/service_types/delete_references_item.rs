@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, node_id::NodeId,
-    node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*, node_id::NodeId,
+    node_ids::ObjectId, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,68 @@ impl BinaryEncoder<DeleteReferencesItem> for DeleteReferencesItem {
         })
     }
 }
+
+impl XmlEncoder for DeleteReferencesItem {
+    fn xml_type_name() -> String {
+        "DeleteReferencesItem".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SourceNodeId", &self.source_node_id);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(&mut element, "IsForward", &self.is_forward);
+        write_xml_field(&mut element, "TargetNodeId", &self.target_node_id);
+        write_xml_field(
+            &mut element,
+            "DeleteBidirectional",
+            &self.delete_bidirectional,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteReferencesItem {
+            source_node_id: read_xml_field(element, "SourceNodeId", decoding_options)?,
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_xml_field(element, "IsForward", decoding_options)?,
+            target_node_id: read_xml_field(element, "TargetNodeId", decoding_options)?,
+            delete_bidirectional: read_xml_field(element, "DeleteBidirectional", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteReferencesItem {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SourceNodeId", &self.source_node_id, encoding);
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(&mut object, "IsForward", &self.is_forward, encoding);
+        write_json_field(&mut object, "TargetNodeId", &self.target_node_id, encoding);
+        write_json_field(
+            &mut object,
+            "DeleteBidirectional",
+            &self.delete_bidirectional,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteReferencesItem {
+            source_node_id: read_json_field(value, "SourceNodeId", decoding_options)?,
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_json_field(value, "IsForward", decoding_options)?,
+            target_node_id: read_json_field(value, "TargetNodeId", decoding_options)?,
+            delete_bidirectional: read_json_field(value, "DeleteBidirectional", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::DeleteReferencesItem,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::DeleteReferencesItem, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,51 @@ impl BinaryEncoder<DeleteReferencesRequest> for DeleteReferencesRequest {
         })
     }
 }
+
+impl XmlEncoder for DeleteReferencesRequest {
+    fn xml_type_name() -> String {
+        "DeleteReferencesRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "ReferencesToDelete",
+            &self.references_to_delete,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteReferencesRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            references_to_delete: read_xml_field(element, "ReferencesToDelete", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteReferencesRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ReferencesToDelete",
+            &self.references_to_delete,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteReferencesRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            references_to_delete: read_json_field(value, "ReferencesToDelete", decoding_options)?,
+        })
+    }
+}
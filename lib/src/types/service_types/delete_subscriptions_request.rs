@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<DeleteSubscriptionsRequest> for DeleteSubscriptionsRequest {
         })
     }
 }
+
+impl XmlEncoder for DeleteSubscriptionsRequest {
+    fn xml_type_name() -> String {
+        "DeleteSubscriptionsRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionIds", &self.subscription_ids);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DeleteSubscriptionsRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_ids: read_xml_field(element, "SubscriptionIds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DeleteSubscriptionsRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionIds",
+            &self.subscription_ids,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DeleteSubscriptionsRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_ids: read_json_field(value, "SubscriptionIds", decoding_options)?,
+        })
+    }
+}
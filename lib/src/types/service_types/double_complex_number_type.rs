@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -47,3 +48,42 @@ impl BinaryEncoder<DoubleComplexNumberType> for DoubleComplexNumberType {
         Ok(DoubleComplexNumberType { real, imaginary })
     }
 }
+
+impl XmlEncoder for DoubleComplexNumberType {
+    fn xml_type_name() -> String {
+        "DoubleComplexNumberType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Real", &self.real);
+        write_xml_field(&mut element, "Imaginary", &self.imaginary);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DoubleComplexNumberType {
+            real: read_xml_field(element, "Real", decoding_options)?,
+            imaginary: read_xml_field(element, "Imaginary", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for DoubleComplexNumberType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Real", &self.real, encoding);
+        write_json_field(&mut object, "Imaginary", &self.imaginary, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(DoubleComplexNumberType {
+            real: read_json_field(value, "Real", decoding_options)?,
+            imaginary: read_json_field(value, "Imaginary", decoding_options)?,
+        })
+    }
+}
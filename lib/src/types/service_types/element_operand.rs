@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*};
+use crate::types::{basic_types::*, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +35,38 @@ impl BinaryEncoder<ElementOperand> for ElementOperand {
         Ok(ElementOperand { index })
     }
 }
+
+impl XmlEncoder for ElementOperand {
+    fn xml_type_name() -> String {
+        "ElementOperand".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Index", &self.index);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ElementOperand {
+            index: read_xml_field(element, "Index", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ElementOperand {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Index", &self.index, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ElementOperand {
+            index: read_json_field(value, "Index", decoding_options)?,
+        })
+    }
+}
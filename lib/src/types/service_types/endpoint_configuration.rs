@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -85,3 +86,139 @@ impl BinaryEncoder<EndpointConfiguration> for EndpointConfiguration {
         })
     }
 }
+
+impl XmlEncoder for EndpointConfiguration {
+    fn xml_type_name() -> String {
+        "EndpointConfiguration".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "OperationTimeout", &self.operation_timeout);
+        write_xml_field(&mut element, "UseBinaryEncoding", &self.use_binary_encoding);
+        write_xml_field(&mut element, "MaxStringLength", &self.max_string_length);
+        write_xml_field(
+            &mut element,
+            "MaxByteStringLength",
+            &self.max_byte_string_length,
+        );
+        write_xml_field(&mut element, "MaxArrayLength", &self.max_array_length);
+        write_xml_field(&mut element, "MaxMessageSize", &self.max_message_size);
+        write_xml_field(&mut element, "MaxBufferSize", &self.max_buffer_size);
+        write_xml_field(&mut element, "ChannelLifetime", &self.channel_lifetime);
+        write_xml_field(
+            &mut element,
+            "SecurityTokenLifetime",
+            &self.security_token_lifetime,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EndpointConfiguration {
+            operation_timeout: read_xml_field(element, "OperationTimeout", decoding_options)?,
+            use_binary_encoding: read_xml_field(element, "UseBinaryEncoding", decoding_options)?,
+            max_string_length: read_xml_field(element, "MaxStringLength", decoding_options)?,
+            max_byte_string_length: read_xml_field(
+                element,
+                "MaxByteStringLength",
+                decoding_options,
+            )?,
+            max_array_length: read_xml_field(element, "MaxArrayLength", decoding_options)?,
+            max_message_size: read_xml_field(element, "MaxMessageSize", decoding_options)?,
+            max_buffer_size: read_xml_field(element, "MaxBufferSize", decoding_options)?,
+            channel_lifetime: read_xml_field(element, "ChannelLifetime", decoding_options)?,
+            security_token_lifetime: read_xml_field(
+                element,
+                "SecurityTokenLifetime",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for EndpointConfiguration {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "OperationTimeout",
+            &self.operation_timeout,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UseBinaryEncoding",
+            &self.use_binary_encoding,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxStringLength",
+            &self.max_string_length,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxByteStringLength",
+            &self.max_byte_string_length,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxArrayLength",
+            &self.max_array_length,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxMessageSize",
+            &self.max_message_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxBufferSize",
+            &self.max_buffer_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ChannelLifetime",
+            &self.channel_lifetime,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityTokenLifetime",
+            &self.security_token_lifetime,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EndpointConfiguration {
+            operation_timeout: read_json_field(value, "OperationTimeout", decoding_options)?,
+            use_binary_encoding: read_json_field(value, "UseBinaryEncoding", decoding_options)?,
+            max_string_length: read_json_field(value, "MaxStringLength", decoding_options)?,
+            max_byte_string_length: read_json_field(
+                value,
+                "MaxByteStringLength",
+                decoding_options,
+            )?,
+            max_array_length: read_json_field(value, "MaxArrayLength", decoding_options)?,
+            max_message_size: read_json_field(value, "MaxMessageSize", decoding_options)?,
+            max_buffer_size: read_json_field(value, "MaxBufferSize", decoding_options)?,
+            channel_lifetime: read_json_field(value, "ChannelLifetime", decoding_options)?,
+            security_token_lifetime: read_json_field(
+                value,
+                "SecurityTokenLifetime",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,9 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     service_types::enums::MessageSecurityMode, service_types::impls::MessageInfo,
     service_types::ApplicationDescription, service_types::UserTokenPolicy, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -83,3 +84,98 @@ impl BinaryEncoder<EndpointDescription> for EndpointDescription {
         })
     }
 }
+
+impl XmlEncoder for EndpointDescription {
+    fn xml_type_name() -> String {
+        "EndpointDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EndpointUrl", &self.endpoint_url);
+        write_xml_field(&mut element, "Server", &self.server);
+        write_xml_field(&mut element, "ServerCertificate", &self.server_certificate);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityPolicyUri", &self.security_policy_uri);
+        write_xml_field(
+            &mut element,
+            "UserIdentityTokens",
+            &self.user_identity_tokens,
+        );
+        write_xml_field(
+            &mut element,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+        );
+        write_xml_field(&mut element, "SecurityLevel", &self.security_level);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EndpointDescription {
+            endpoint_url: read_xml_field(element, "EndpointUrl", decoding_options)?,
+            server: read_xml_field(element, "Server", decoding_options)?,
+            server_certificate: read_xml_field(element, "ServerCertificate", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_xml_field(element, "SecurityPolicyUri", decoding_options)?,
+            user_identity_tokens: read_xml_field(element, "UserIdentityTokens", decoding_options)?,
+            transport_profile_uri: read_xml_field(
+                element,
+                "TransportProfileUri",
+                decoding_options,
+            )?,
+            security_level: read_xml_field(element, "SecurityLevel", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EndpointDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, encoding);
+        write_json_field(&mut object, "Server", &self.server, encoding);
+        write_json_field(
+            &mut object,
+            "ServerCertificate",
+            &self.server_certificate,
+            encoding,
+        );
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityPolicyUri",
+            &self.security_policy_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UserIdentityTokens",
+            &self.user_identity_tokens,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "SecurityLevel", &self.security_level, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EndpointDescription {
+            endpoint_url: read_json_field(value, "EndpointUrl", decoding_options)?,
+            server: read_json_field(value, "Server", decoding_options)?,
+            server_certificate: read_json_field(value, "ServerCertificate", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_json_field(value, "SecurityPolicyUri", decoding_options)?,
+            user_identity_tokens: read_json_field(value, "UserIdentityTokens", decoding_options)?,
+            transport_profile_uri: read_json_field(value, "TransportProfileUri", decoding_options)?,
+            security_level: read_json_field(value, "SecurityLevel", decoding_options)?,
+        })
+    }
+}
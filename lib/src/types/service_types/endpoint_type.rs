@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::enums::MessageSecurityMode,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId,
+    service_types::enums::MessageSecurityMode, service_types::impls::MessageInfo, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +62,68 @@ impl BinaryEncoder<EndpointType> for EndpointType {
         })
     }
 }
+
+impl XmlEncoder for EndpointType {
+    fn xml_type_name() -> String {
+        "EndpointType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EndpointUrl", &self.endpoint_url);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityPolicyUri", &self.security_policy_uri);
+        write_xml_field(
+            &mut element,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EndpointType {
+            endpoint_url: read_xml_field(element, "EndpointUrl", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_xml_field(element, "SecurityPolicyUri", decoding_options)?,
+            transport_profile_uri: read_xml_field(
+                element,
+                "TransportProfileUri",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for EndpointType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, encoding);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityPolicyUri",
+            &self.security_policy_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EndpointType {
+            endpoint_url: read_json_field(value, "EndpointUrl", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_json_field(value, "SecurityPolicyUri", decoding_options)?,
+            transport_profile_uri: read_json_field(value, "TransportProfileUri", decoding_options)?,
+        })
+    }
+}
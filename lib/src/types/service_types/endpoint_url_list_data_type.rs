@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,43 @@ impl BinaryEncoder<EndpointUrlListDataType> for EndpointUrlListDataType {
         Ok(EndpointUrlListDataType { endpoint_url_list })
     }
 }
+
+impl XmlEncoder for EndpointUrlListDataType {
+    fn xml_type_name() -> String {
+        "EndpointUrlListDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EndpointUrlList", &self.endpoint_url_list);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EndpointUrlListDataType {
+            endpoint_url_list: read_xml_field(element, "EndpointUrlList", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EndpointUrlListDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "EndpointUrlList",
+            &self.endpoint_url_list,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EndpointUrlListDataType {
+            endpoint_url_list: read_json_field(value, "EndpointUrlList", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, service_types::EnumField};
+use crate::types::{basic_types::*, encoding::*, json::*, service_types::EnumField, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -36,3 +36,38 @@ impl BinaryEncoder<EnumDefinition> for EnumDefinition {
         Ok(EnumDefinition { fields })
     }
 }
+
+impl XmlEncoder for EnumDefinition {
+    fn xml_type_name() -> String {
+        "EnumDefinition".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Fields", &self.fields);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EnumDefinition {
+            fields: read_xml_field(element, "Fields", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EnumDefinition {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Fields", &self.fields, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EnumDefinition {
+            fields: read_json_field(value, "Fields", decoding_options)?,
+        })
+    }
+}
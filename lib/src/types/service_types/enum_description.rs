@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, qualified_name::QualifiedName,
-    service_types::EnumDefinition,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, qualified_name::QualifiedName,
+    service_types::EnumDefinition, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,55 @@ impl BinaryEncoder<EnumDescription> for EnumDescription {
         })
     }
 }
+
+impl XmlEncoder for EnumDescription {
+    fn xml_type_name() -> String {
+        "EnumDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataTypeId", &self.data_type_id);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "EnumDefinition", &self.enum_definition);
+        write_xml_field(&mut element, "BuiltInType", &self.built_in_type);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EnumDescription {
+            data_type_id: read_xml_field(element, "DataTypeId", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enum_definition: read_xml_field(element, "EnumDefinition", decoding_options)?,
+            built_in_type: read_xml_field(element, "BuiltInType", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EnumDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, encoding);
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(
+            &mut object,
+            "EnumDefinition",
+            &self.enum_definition,
+            encoding,
+        );
+        write_json_field(&mut object, "BuiltInType", &self.built_in_type, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EnumDescription {
+            data_type_id: read_json_field(value, "DataTypeId", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+            enum_definition: read_json_field(value, "EnumDefinition", decoding_options)?,
+            built_in_type: read_json_field(value, "BuiltInType", decoding_options)?,
+        })
+    }
+}
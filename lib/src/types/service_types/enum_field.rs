@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, localized_text::LocalizedText, string::UAString};
+use crate::types::{
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, string::UAString, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -53,3 +55,50 @@ impl BinaryEncoder<EnumField> for EnumField {
         })
     }
 }
+
+impl XmlEncoder for EnumField {
+    fn xml_type_name() -> String {
+        "EnumField".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Value", &self.value);
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "Name", &self.name);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EnumField {
+            value: read_xml_field(element, "Value", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EnumField {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EnumField {
+            value: read_json_field(value, "Value", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<EnumValueType> for EnumValueType {
         })
     }
 }
+
+impl XmlEncoder for EnumValueType {
+    fn xml_type_name() -> String {
+        "EnumValueType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Value", &self.value);
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EnumValueType {
+            value: read_xml_field(element, "Value", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EnumValueType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EnumValueType {
+            value: read_json_field(value, "Value", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #![allow(non_upper_case_globals)]
-use crate::types::{encoding::*, status_codes::StatusCode};
+use crate::types::{encoding::*, json::*, status_codes::StatusCode, xml::*};
 use bitflags;
 use std::io::{Read, Write};
 
@@ -48,6 +48,70 @@ impl BinaryEncoder<NodeIdType> for NodeIdType {
     }
 }
 
+impl XmlEncoder for NodeIdType {
+    fn xml_type_name() -> String {
+        "NodeIdType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::TwoByte => "TwoByte",
+            Self::FourByte => "FourByte",
+            Self::Numeric => "Numeric",
+            Self::String => "String",
+            Self::Guid => "Guid",
+            Self::ByteString => "ByteString",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::TwoByte),
+            1 => Ok(Self::FourByte),
+            2 => Ok(Self::Numeric),
+            3 => Ok(Self::String),
+            4 => Ok(Self::Guid),
+            5 => Ok(Self::ByteString),
+            v => {
+                error!("Invalid value {} for enum NodeIdType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for NodeIdType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::TwoByte => "TwoByte",
+            Self::FourByte => "FourByte",
+            Self::Numeric => "Numeric",
+            Self::String => "String",
+            Self::Guid => "Guid",
+            Self::ByteString => "ByteString",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::TwoByte),
+            1 => Ok(Self::FourByte),
+            2 => Ok(Self::Numeric),
+            3 => Ok(Self::String),
+            4 => Ok(Self::Guid),
+            5 => Ok(Self::ByteString),
+            v => {
+                error!("Invalid value {} for enum NodeIdType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NamingRuleType {
     Mandatory = 1,
@@ -78,6 +142,58 @@ impl BinaryEncoder<NamingRuleType> for NamingRuleType {
     }
 }
 
+impl XmlEncoder for NamingRuleType {
+    fn xml_type_name() -> String {
+        "NamingRuleType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Mandatory => "Mandatory",
+            Self::Optional => "Optional",
+            Self::Constraint => "Constraint",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::Mandatory),
+            2 => Ok(Self::Optional),
+            3 => Ok(Self::Constraint),
+            v => {
+                error!("Invalid value {} for enum NamingRuleType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for NamingRuleType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Mandatory => "Mandatory",
+            Self::Optional => "Optional",
+            Self::Constraint => "Constraint",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::Mandatory),
+            2 => Ok(Self::Optional),
+            3 => Ok(Self::Constraint),
+            v => {
+                error!("Invalid value {} for enum NamingRuleType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum OpenFileMode {
     Read = 1,
@@ -110,6 +226,62 @@ impl BinaryEncoder<OpenFileMode> for OpenFileMode {
     }
 }
 
+impl XmlEncoder for OpenFileMode {
+    fn xml_type_name() -> String {
+        "OpenFileMode".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Read => "Read",
+            Self::Write => "Write",
+            Self::EraseExisting => "EraseExisting",
+            Self::Append => "Append",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::Read),
+            2 => Ok(Self::Write),
+            4 => Ok(Self::EraseExisting),
+            8 => Ok(Self::Append),
+            v => {
+                error!("Invalid value {} for enum OpenFileMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for OpenFileMode {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Read => "Read",
+            Self::Write => "Write",
+            Self::EraseExisting => "EraseExisting",
+            Self::Append => "Append",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::Read),
+            2 => Ok(Self::Write),
+            4 => Ok(Self::EraseExisting),
+            8 => Ok(Self::Append),
+            v => {
+                error!("Invalid value {} for enum OpenFileMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum IdentityCriteriaType {
     UserName = 1,
@@ -146,6 +318,70 @@ impl BinaryEncoder<IdentityCriteriaType> for IdentityCriteriaType {
     }
 }
 
+impl XmlEncoder for IdentityCriteriaType {
+    fn xml_type_name() -> String {
+        "IdentityCriteriaType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::UserName => "UserName",
+            Self::Thumbprint => "Thumbprint",
+            Self::Role => "Role",
+            Self::GroupId => "GroupId",
+            Self::Anonymous => "Anonymous",
+            Self::AuthenticatedUser => "AuthenticatedUser",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Thumbprint),
+            3 => Ok(Self::Role),
+            4 => Ok(Self::GroupId),
+            5 => Ok(Self::Anonymous),
+            6 => Ok(Self::AuthenticatedUser),
+            v => {
+                error!("Invalid value {} for enum IdentityCriteriaType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for IdentityCriteriaType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::UserName => "UserName",
+            Self::Thumbprint => "Thumbprint",
+            Self::Role => "Role",
+            Self::GroupId => "GroupId",
+            Self::Anonymous => "Anonymous",
+            Self::AuthenticatedUser => "AuthenticatedUser",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Thumbprint),
+            3 => Ok(Self::Role),
+            4 => Ok(Self::GroupId),
+            5 => Ok(Self::Anonymous),
+            6 => Ok(Self::AuthenticatedUser),
+            v => {
+                error!("Invalid value {} for enum IdentityCriteriaType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TrustListMasks {
     None = 0,
@@ -182,6 +418,70 @@ impl BinaryEncoder<TrustListMasks> for TrustListMasks {
     }
 }
 
+impl XmlEncoder for TrustListMasks {
+    fn xml_type_name() -> String {
+        "TrustListMasks".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::None => "None",
+            Self::TrustedCertificates => "TrustedCertificates",
+            Self::TrustedCrls => "TrustedCrls",
+            Self::IssuerCertificates => "IssuerCertificates",
+            Self::IssuerCrls => "IssuerCrls",
+            Self::All => "All",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::TrustedCertificates),
+            2 => Ok(Self::TrustedCrls),
+            4 => Ok(Self::IssuerCertificates),
+            8 => Ok(Self::IssuerCrls),
+            15 => Ok(Self::All),
+            v => {
+                error!("Invalid value {} for enum TrustListMasks", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for TrustListMasks {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::None => "None",
+            Self::TrustedCertificates => "TrustedCertificates",
+            Self::TrustedCrls => "TrustedCrls",
+            Self::IssuerCertificates => "IssuerCertificates",
+            Self::IssuerCrls => "IssuerCrls",
+            Self::All => "All",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::TrustedCertificates),
+            2 => Ok(Self::TrustedCrls),
+            4 => Ok(Self::IssuerCertificates),
+            8 => Ok(Self::IssuerCrls),
+            15 => Ok(Self::All),
+            v => {
+                error!("Invalid value {} for enum TrustListMasks", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PubSubState {
     Disabled = 0,
@@ -214,6 +514,62 @@ impl BinaryEncoder<PubSubState> for PubSubState {
     }
 }
 
+impl XmlEncoder for PubSubState {
+    fn xml_type_name() -> String {
+        "PubSubState".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Disabled => "Disabled",
+            Self::Paused => "Paused",
+            Self::Operational => "Operational",
+            Self::Error => "Error",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Paused),
+            2 => Ok(Self::Operational),
+            3 => Ok(Self::Error),
+            v => {
+                error!("Invalid value {} for enum PubSubState", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for PubSubState {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Disabled => "Disabled",
+            Self::Paused => "Paused",
+            Self::Operational => "Operational",
+            Self::Error => "Error",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Paused),
+            2 => Ok(Self::Operational),
+            3 => Ok(Self::Error),
+            v => {
+                error!("Invalid value {} for enum PubSubState", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct DataSetFieldFlags: i16 {
@@ -239,6 +595,36 @@ impl BinaryEncoder<DataSetFieldFlags> for DataSetFieldFlags {
     }
 }
 
+impl XmlEncoder for DataSetFieldFlags {
+    fn xml_type_name() -> String {
+        "DataSetFieldFlags".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetFieldFlags::from_bits_truncate(i16::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for DataSetFieldFlags {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetFieldFlags::from_bits_truncate(i16::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct DataSetFieldContentMask: i32 {
@@ -269,6 +655,34 @@ impl BinaryEncoder<DataSetFieldContentMask> for DataSetFieldContentMask {
     }
 }
 
+impl XmlEncoder for DataSetFieldContentMask {
+    fn xml_type_name() -> String {
+        "DataSetFieldContentMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetFieldContentMask::from_bits_truncate(
+            i32::decode_xml(element, decoding_options)?,
+        ))
+    }
+}
+
+impl JsonEncoder for DataSetFieldContentMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(DataSetFieldContentMask::from_bits_truncate(
+            i32::decode_json(value, decoding_options)?,
+        ))
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum OverrideValueHandling {
     Disabled = 0,
@@ -299,38 +713,142 @@ impl BinaryEncoder<OverrideValueHandling> for OverrideValueHandling {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum DataSetOrderingType {
-    Undefined = 0,
-    AscendingWriterId = 1,
-    AscendingWriterIdSingle = 2,
-}
-
-impl BinaryEncoder<DataSetOrderingType> for DataSetOrderingType {
-    fn byte_len(&self) -> usize {
-        4
+impl XmlEncoder for OverrideValueHandling {
+    fn xml_type_name() -> String {
+        "OverrideValueHandling".into()
     }
 
-    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
-        write_i32(stream, *self as i32)
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Disabled => "Disabled",
+            Self::LastUsableValue => "LastUsableValue",
+            Self::OverrideValue => "OverrideValue",
+        };
+        write_xml_enum(name, value_name, *self as i64)
     }
 
-    fn decode<S: Read>(stream: &mut S, _: &DecodingOptions) -> EncodingResult<Self> {
-        let value = read_i32(stream)?;
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
         match value {
-            0 => Ok(Self::Undefined),
-            1 => Ok(Self::AscendingWriterId),
-            2 => Ok(Self::AscendingWriterIdSingle),
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::LastUsableValue),
+            2 => Ok(Self::OverrideValue),
             v => {
-                error!("Invalid value {} for enum DataSetOrderingType", v);
+                error!("Invalid value {} for enum OverrideValueHandling", v);
                 Err(StatusCode::BadUnexpectedError)
             }
         }
     }
 }
 
-bitflags! {
-    #[derive(Debug, Copy, Clone, PartialEq)]
+impl JsonEncoder for OverrideValueHandling {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Disabled => "Disabled",
+            Self::LastUsableValue => "LastUsableValue",
+            Self::OverrideValue => "OverrideValue",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::LastUsableValue),
+            2 => Ok(Self::OverrideValue),
+            v => {
+                error!("Invalid value {} for enum OverrideValueHandling", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DataSetOrderingType {
+    Undefined = 0,
+    AscendingWriterId = 1,
+    AscendingWriterIdSingle = 2,
+}
+
+impl BinaryEncoder<DataSetOrderingType> for DataSetOrderingType {
+    fn byte_len(&self) -> usize {
+        4
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        write_i32(stream, *self as i32)
+    }
+
+    fn decode<S: Read>(stream: &mut S, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_i32(stream)?;
+        match value {
+            0 => Ok(Self::Undefined),
+            1 => Ok(Self::AscendingWriterId),
+            2 => Ok(Self::AscendingWriterIdSingle),
+            v => {
+                error!("Invalid value {} for enum DataSetOrderingType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl XmlEncoder for DataSetOrderingType {
+    fn xml_type_name() -> String {
+        "DataSetOrderingType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Undefined => "Undefined",
+            Self::AscendingWriterId => "AscendingWriterId",
+            Self::AscendingWriterIdSingle => "AscendingWriterIdSingle",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Undefined),
+            1 => Ok(Self::AscendingWriterId),
+            2 => Ok(Self::AscendingWriterIdSingle),
+            v => {
+                error!("Invalid value {} for enum DataSetOrderingType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for DataSetOrderingType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Undefined => "Undefined",
+            Self::AscendingWriterId => "AscendingWriterId",
+            Self::AscendingWriterIdSingle => "AscendingWriterIdSingle",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Undefined),
+            1 => Ok(Self::AscendingWriterId),
+            2 => Ok(Self::AscendingWriterIdSingle),
+            v => {
+                error!("Invalid value {} for enum DataSetOrderingType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct UadpNetworkMessageContentMask: i32 {
         const None = 0;
         const PublisherId = 1;
@@ -363,6 +881,34 @@ impl BinaryEncoder<UadpNetworkMessageContentMask> for UadpNetworkMessageContentM
     }
 }
 
+impl XmlEncoder for UadpNetworkMessageContentMask {
+    fn xml_type_name() -> String {
+        "UadpNetworkMessageContentMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpNetworkMessageContentMask::from_bits_truncate(
+            i32::decode_xml(element, decoding_options)?,
+        ))
+    }
+}
+
+impl JsonEncoder for UadpNetworkMessageContentMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpNetworkMessageContentMask::from_bits_truncate(
+            i32::decode_json(value, decoding_options)?,
+        ))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct UadpDataSetMessageContentMask: i32 {
@@ -392,6 +938,34 @@ impl BinaryEncoder<UadpDataSetMessageContentMask> for UadpDataSetMessageContentM
     }
 }
 
+impl XmlEncoder for UadpDataSetMessageContentMask {
+    fn xml_type_name() -> String {
+        "UadpDataSetMessageContentMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpDataSetMessageContentMask::from_bits_truncate(
+            i32::decode_xml(element, decoding_options)?,
+        ))
+    }
+}
+
+impl JsonEncoder for UadpDataSetMessageContentMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpDataSetMessageContentMask::from_bits_truncate(
+            i32::decode_json(value, decoding_options)?,
+        ))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct JsonNetworkMessageContentMask: i32 {
@@ -421,6 +995,34 @@ impl BinaryEncoder<JsonNetworkMessageContentMask> for JsonNetworkMessageContentM
     }
 }
 
+impl XmlEncoder for JsonNetworkMessageContentMask {
+    fn xml_type_name() -> String {
+        "JsonNetworkMessageContentMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonNetworkMessageContentMask::from_bits_truncate(
+            i32::decode_xml(element, decoding_options)?,
+        ))
+    }
+}
+
+impl JsonEncoder for JsonNetworkMessageContentMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonNetworkMessageContentMask::from_bits_truncate(
+            i32::decode_json(value, decoding_options)?,
+        ))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct JsonDataSetMessageContentMask: i32 {
@@ -449,6 +1051,34 @@ impl BinaryEncoder<JsonDataSetMessageContentMask> for JsonDataSetMessageContentM
     }
 }
 
+impl XmlEncoder for JsonDataSetMessageContentMask {
+    fn xml_type_name() -> String {
+        "JsonDataSetMessageContentMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonDataSetMessageContentMask::from_bits_truncate(
+            i32::decode_xml(element, decoding_options)?,
+        ))
+    }
+}
+
+impl JsonEncoder for JsonDataSetMessageContentMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonDataSetMessageContentMask::from_bits_truncate(
+            i32::decode_json(value, decoding_options)?,
+        ))
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BrokerTransportQualityOfService {
     NotSpecified = 0,
@@ -486,6 +1116,72 @@ impl BinaryEncoder<BrokerTransportQualityOfService> for BrokerTransportQualityOf
     }
 }
 
+impl XmlEncoder for BrokerTransportQualityOfService {
+    fn xml_type_name() -> String {
+        "BrokerTransportQualityOfService".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::NotSpecified => "NotSpecified",
+            Self::BestEffort => "BestEffort",
+            Self::AtLeastOnce => "AtLeastOnce",
+            Self::AtMostOnce => "AtMostOnce",
+            Self::ExactlyOnce => "ExactlyOnce",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::NotSpecified),
+            1 => Ok(Self::BestEffort),
+            2 => Ok(Self::AtLeastOnce),
+            3 => Ok(Self::AtMostOnce),
+            4 => Ok(Self::ExactlyOnce),
+            v => {
+                error!(
+                    "Invalid value {} for enum BrokerTransportQualityOfService",
+                    v
+                );
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for BrokerTransportQualityOfService {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::NotSpecified => "NotSpecified",
+            Self::BestEffort => "BestEffort",
+            Self::AtLeastOnce => "AtLeastOnce",
+            Self::AtMostOnce => "AtMostOnce",
+            Self::ExactlyOnce => "ExactlyOnce",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::NotSpecified),
+            1 => Ok(Self::BestEffort),
+            2 => Ok(Self::AtLeastOnce),
+            3 => Ok(Self::AtMostOnce),
+            4 => Ok(Self::ExactlyOnce),
+            v => {
+                error!(
+                    "Invalid value {} for enum BrokerTransportQualityOfService",
+                    v
+                );
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DiagnosticsLevel {
     Basic = 0,
@@ -520,6 +1216,66 @@ impl BinaryEncoder<DiagnosticsLevel> for DiagnosticsLevel {
     }
 }
 
+impl XmlEncoder for DiagnosticsLevel {
+    fn xml_type_name() -> String {
+        "DiagnosticsLevel".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Basic => "Basic",
+            Self::Advanced => "Advanced",
+            Self::Info => "Info",
+            Self::Log => "Log",
+            Self::Debug => "Debug",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Basic),
+            1 => Ok(Self::Advanced),
+            2 => Ok(Self::Info),
+            3 => Ok(Self::Log),
+            4 => Ok(Self::Debug),
+            v => {
+                error!("Invalid value {} for enum DiagnosticsLevel", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for DiagnosticsLevel {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Basic => "Basic",
+            Self::Advanced => "Advanced",
+            Self::Info => "Info",
+            Self::Log => "Log",
+            Self::Debug => "Debug",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Basic),
+            1 => Ok(Self::Advanced),
+            2 => Ok(Self::Info),
+            3 => Ok(Self::Log),
+            4 => Ok(Self::Debug),
+            v => {
+                error!("Invalid value {} for enum DiagnosticsLevel", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PubSubDiagnosticsCounterClassification {
     Information = 0,
@@ -553,6 +1309,60 @@ impl BinaryEncoder<PubSubDiagnosticsCounterClassification>
     }
 }
 
+impl XmlEncoder for PubSubDiagnosticsCounterClassification {
+    fn xml_type_name() -> String {
+        "PubSubDiagnosticsCounterClassification".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Information => "Information",
+            Self::Error => "Error",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Information),
+            1 => Ok(Self::Error),
+            v => {
+                error!(
+                    "Invalid value {} for enum PubSubDiagnosticsCounterClassification",
+                    v
+                );
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for PubSubDiagnosticsCounterClassification {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Information => "Information",
+            Self::Error => "Error",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Information),
+            1 => Ok(Self::Error),
+            v => {
+                error!(
+                    "Invalid value {} for enum PubSubDiagnosticsCounterClassification",
+                    v
+                );
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum IdType {
     Numeric = 0,
@@ -585,14 +1395,70 @@ impl BinaryEncoder<IdType> for IdType {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum NodeClass {
-    Unspecified = 0,
-    Object = 1,
-    Variable = 2,
-    Method = 4,
-    ObjectType = 8,
-    VariableType = 16,
+impl XmlEncoder for IdType {
+    fn xml_type_name() -> String {
+        "IdType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Numeric => "Numeric",
+            Self::String => "String",
+            Self::Guid => "Guid",
+            Self::Opaque => "Opaque",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Numeric),
+            1 => Ok(Self::String),
+            2 => Ok(Self::Guid),
+            3 => Ok(Self::Opaque),
+            v => {
+                error!("Invalid value {} for enum IdType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for IdType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Numeric => "Numeric",
+            Self::String => "String",
+            Self::Guid => "Guid",
+            Self::Opaque => "Opaque",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Numeric),
+            1 => Ok(Self::String),
+            2 => Ok(Self::Guid),
+            3 => Ok(Self::Opaque),
+            v => {
+                error!("Invalid value {} for enum IdType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NodeClass {
+    Unspecified = 0,
+    Object = 1,
+    Variable = 2,
+    Method = 4,
+    ObjectType = 8,
+    VariableType = 16,
     ReferenceType = 32,
     DataType = 64,
     View = 128,
@@ -627,6 +1493,82 @@ impl BinaryEncoder<NodeClass> for NodeClass {
     }
 }
 
+impl XmlEncoder for NodeClass {
+    fn xml_type_name() -> String {
+        "NodeClass".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Unspecified => "Unspecified",
+            Self::Object => "Object",
+            Self::Variable => "Variable",
+            Self::Method => "Method",
+            Self::ObjectType => "ObjectType",
+            Self::VariableType => "VariableType",
+            Self::ReferenceType => "ReferenceType",
+            Self::DataType => "DataType",
+            Self::View => "View",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Unspecified),
+            1 => Ok(Self::Object),
+            2 => Ok(Self::Variable),
+            4 => Ok(Self::Method),
+            8 => Ok(Self::ObjectType),
+            16 => Ok(Self::VariableType),
+            32 => Ok(Self::ReferenceType),
+            64 => Ok(Self::DataType),
+            128 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeClass", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for NodeClass {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Unspecified => "Unspecified",
+            Self::Object => "Object",
+            Self::Variable => "Variable",
+            Self::Method => "Method",
+            Self::ObjectType => "ObjectType",
+            Self::VariableType => "VariableType",
+            Self::ReferenceType => "ReferenceType",
+            Self::DataType => "DataType",
+            Self::View => "View",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Unspecified),
+            1 => Ok(Self::Object),
+            2 => Ok(Self::Variable),
+            4 => Ok(Self::Method),
+            8 => Ok(Self::ObjectType),
+            16 => Ok(Self::VariableType),
+            32 => Ok(Self::ReferenceType),
+            64 => Ok(Self::DataType),
+            128 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeClass", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct PermissionType: i32 {
@@ -668,6 +1610,36 @@ impl BinaryEncoder<PermissionType> for PermissionType {
     }
 }
 
+impl XmlEncoder for PermissionType {
+    fn xml_type_name() -> String {
+        "PermissionType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PermissionType::from_bits_truncate(i32::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for PermissionType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PermissionType::from_bits_truncate(i32::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct AccessLevelType: u8 {
@@ -699,6 +1671,36 @@ impl BinaryEncoder<AccessLevelType> for AccessLevelType {
     }
 }
 
+impl XmlEncoder for AccessLevelType {
+    fn xml_type_name() -> String {
+        "AccessLevelType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessLevelType::from_bits_truncate(u8::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for AccessLevelType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessLevelType::from_bits_truncate(u8::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct AccessLevelExType: i32 {
@@ -734,6 +1736,36 @@ impl BinaryEncoder<AccessLevelExType> for AccessLevelExType {
     }
 }
 
+impl XmlEncoder for AccessLevelExType {
+    fn xml_type_name() -> String {
+        "AccessLevelExType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessLevelExType::from_bits_truncate(i32::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for AccessLevelExType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessLevelExType::from_bits_truncate(i32::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct EventNotifierType: u8 {
@@ -761,6 +1793,36 @@ impl BinaryEncoder<EventNotifierType> for EventNotifierType {
     }
 }
 
+impl XmlEncoder for EventNotifierType {
+    fn xml_type_name() -> String {
+        "EventNotifierType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventNotifierType::from_bits_truncate(u8::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for EventNotifierType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventNotifierType::from_bits_truncate(u8::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct AccessRestrictionType: i16 {
@@ -789,6 +1851,36 @@ impl BinaryEncoder<AccessRestrictionType> for AccessRestrictionType {
     }
 }
 
+impl XmlEncoder for AccessRestrictionType {
+    fn xml_type_name() -> String {
+        "AccessRestrictionType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessRestrictionType::from_bits_truncate(i16::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for AccessRestrictionType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AccessRestrictionType::from_bits_truncate(i16::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum StructureType {
@@ -820,6 +1912,58 @@ impl BinaryEncoder<StructureType> for StructureType {
     }
 }
 
+impl XmlEncoder for StructureType {
+    fn xml_type_name() -> String {
+        "StructureType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Structure => "Structure",
+            Self::StructureWithOptionalFields => "StructureWithOptionalFields",
+            Self::Union => "Union",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Structure),
+            1 => Ok(Self::StructureWithOptionalFields),
+            2 => Ok(Self::Union),
+            v => {
+                error!("Invalid value {} for enum StructureType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for StructureType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Structure => "Structure",
+            Self::StructureWithOptionalFields => "StructureWithOptionalFields",
+            Self::Union => "Union",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Structure),
+            1 => Ok(Self::StructureWithOptionalFields),
+            2 => Ok(Self::Union),
+            v => {
+                error!("Invalid value {} for enum StructureType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ApplicationType {
     Server = 0,
@@ -852,6 +1996,62 @@ impl BinaryEncoder<ApplicationType> for ApplicationType {
     }
 }
 
+impl XmlEncoder for ApplicationType {
+    fn xml_type_name() -> String {
+        "ApplicationType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Server => "Server",
+            Self::Client => "Client",
+            Self::ClientAndServer => "ClientAndServer",
+            Self::DiscoveryServer => "DiscoveryServer",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Server),
+            1 => Ok(Self::Client),
+            2 => Ok(Self::ClientAndServer),
+            3 => Ok(Self::DiscoveryServer),
+            v => {
+                error!("Invalid value {} for enum ApplicationType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for ApplicationType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Server => "Server",
+            Self::Client => "Client",
+            Self::ClientAndServer => "ClientAndServer",
+            Self::DiscoveryServer => "DiscoveryServer",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Server),
+            1 => Ok(Self::Client),
+            2 => Ok(Self::ClientAndServer),
+            3 => Ok(Self::DiscoveryServer),
+            v => {
+                error!("Invalid value {} for enum ApplicationType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum MessageSecurityMode {
     Invalid = 0,
@@ -884,6 +2084,62 @@ impl BinaryEncoder<MessageSecurityMode> for MessageSecurityMode {
     }
 }
 
+impl XmlEncoder for MessageSecurityMode {
+    fn xml_type_name() -> String {
+        "MessageSecurityMode".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Invalid => "Invalid",
+            Self::None => "None",
+            Self::Sign => "Sign",
+            Self::SignAndEncrypt => "SignAndEncrypt",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Invalid),
+            1 => Ok(Self::None),
+            2 => Ok(Self::Sign),
+            3 => Ok(Self::SignAndEncrypt),
+            v => {
+                error!("Invalid value {} for enum MessageSecurityMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for MessageSecurityMode {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Invalid => "Invalid",
+            Self::None => "None",
+            Self::Sign => "Sign",
+            Self::SignAndEncrypt => "SignAndEncrypt",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Invalid),
+            1 => Ok(Self::None),
+            2 => Ok(Self::Sign),
+            3 => Ok(Self::SignAndEncrypt),
+            v => {
+                error!("Invalid value {} for enum MessageSecurityMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum UserTokenType {
     Anonymous = 0,
@@ -916,6 +2172,62 @@ impl BinaryEncoder<UserTokenType> for UserTokenType {
     }
 }
 
+impl XmlEncoder for UserTokenType {
+    fn xml_type_name() -> String {
+        "UserTokenType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Anonymous => "Anonymous",
+            Self::UserName => "UserName",
+            Self::Certificate => "Certificate",
+            Self::IssuedToken => "IssuedToken",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Anonymous),
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Certificate),
+            3 => Ok(Self::IssuedToken),
+            v => {
+                error!("Invalid value {} for enum UserTokenType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for UserTokenType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Anonymous => "Anonymous",
+            Self::UserName => "UserName",
+            Self::Certificate => "Certificate",
+            Self::IssuedToken => "IssuedToken",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Anonymous),
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Certificate),
+            3 => Ok(Self::IssuedToken),
+            v => {
+                error!("Invalid value {} for enum UserTokenType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SecurityTokenRequestType {
     Issue = 0,
@@ -944,6 +2256,54 @@ impl BinaryEncoder<SecurityTokenRequestType> for SecurityTokenRequestType {
     }
 }
 
+impl XmlEncoder for SecurityTokenRequestType {
+    fn xml_type_name() -> String {
+        "SecurityTokenRequestType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Issue => "Issue",
+            Self::Renew => "Renew",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Issue),
+            1 => Ok(Self::Renew),
+            v => {
+                error!("Invalid value {} for enum SecurityTokenRequestType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for SecurityTokenRequestType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Issue => "Issue",
+            Self::Renew => "Renew",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Issue),
+            1 => Ok(Self::Renew),
+            v => {
+                error!("Invalid value {} for enum SecurityTokenRequestType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NodeAttributesMask {
     None = 0,
@@ -1038,6 +2398,186 @@ impl BinaryEncoder<NodeAttributesMask> for NodeAttributesMask {
     }
 }
 
+impl XmlEncoder for NodeAttributesMask {
+    fn xml_type_name() -> String {
+        "NodeAttributesMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::None => "None",
+            Self::AccessLevel => "AccessLevel",
+            Self::ArrayDimensions => "ArrayDimensions",
+            Self::BrowseName => "BrowseName",
+            Self::ContainsNoLoops => "ContainsNoLoops",
+            Self::DataType => "DataType",
+            Self::Description => "Description",
+            Self::DisplayName => "DisplayName",
+            Self::EventNotifier => "EventNotifier",
+            Self::Executable => "Executable",
+            Self::Historizing => "Historizing",
+            Self::InverseName => "InverseName",
+            Self::IsAbstract => "IsAbstract",
+            Self::MinimumSamplingInterval => "MinimumSamplingInterval",
+            Self::NodeClass => "NodeClass",
+            Self::NodeId => "NodeId",
+            Self::Symmetric => "Symmetric",
+            Self::UserAccessLevel => "UserAccessLevel",
+            Self::UserExecutable => "UserExecutable",
+            Self::UserWriteMask => "UserWriteMask",
+            Self::ValueRank => "ValueRank",
+            Self::WriteMask => "WriteMask",
+            Self::Value => "Value",
+            Self::DataTypeDefinition => "DataTypeDefinition",
+            Self::RolePermissions => "RolePermissions",
+            Self::AccessRestrictions => "AccessRestrictions",
+            Self::All => "All",
+            Self::BaseNode => "BaseNode",
+            Self::Object => "Object",
+            Self::ObjectType => "ObjectType",
+            Self::Variable => "Variable",
+            Self::VariableType => "VariableType",
+            Self::Method => "Method",
+            Self::ReferenceType => "ReferenceType",
+            Self::View => "View",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::AccessLevel),
+            2 => Ok(Self::ArrayDimensions),
+            4 => Ok(Self::BrowseName),
+            8 => Ok(Self::ContainsNoLoops),
+            16 => Ok(Self::DataType),
+            32 => Ok(Self::Description),
+            64 => Ok(Self::DisplayName),
+            128 => Ok(Self::EventNotifier),
+            256 => Ok(Self::Executable),
+            512 => Ok(Self::Historizing),
+            1024 => Ok(Self::InverseName),
+            2048 => Ok(Self::IsAbstract),
+            4096 => Ok(Self::MinimumSamplingInterval),
+            8192 => Ok(Self::NodeClass),
+            16384 => Ok(Self::NodeId),
+            32768 => Ok(Self::Symmetric),
+            65536 => Ok(Self::UserAccessLevel),
+            131072 => Ok(Self::UserExecutable),
+            262144 => Ok(Self::UserWriteMask),
+            524288 => Ok(Self::ValueRank),
+            1048576 => Ok(Self::WriteMask),
+            2097152 => Ok(Self::Value),
+            4194304 => Ok(Self::DataTypeDefinition),
+            8388608 => Ok(Self::RolePermissions),
+            16777216 => Ok(Self::AccessRestrictions),
+            33554431 => Ok(Self::All),
+            26501220 => Ok(Self::BaseNode),
+            26501348 => Ok(Self::Object),
+            26503268 => Ok(Self::ObjectType),
+            26571383 => Ok(Self::Variable),
+            28600438 => Ok(Self::VariableType),
+            26632548 => Ok(Self::Method),
+            26537060 => Ok(Self::ReferenceType),
+            26501356 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeAttributesMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for NodeAttributesMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::None => "None",
+            Self::AccessLevel => "AccessLevel",
+            Self::ArrayDimensions => "ArrayDimensions",
+            Self::BrowseName => "BrowseName",
+            Self::ContainsNoLoops => "ContainsNoLoops",
+            Self::DataType => "DataType",
+            Self::Description => "Description",
+            Self::DisplayName => "DisplayName",
+            Self::EventNotifier => "EventNotifier",
+            Self::Executable => "Executable",
+            Self::Historizing => "Historizing",
+            Self::InverseName => "InverseName",
+            Self::IsAbstract => "IsAbstract",
+            Self::MinimumSamplingInterval => "MinimumSamplingInterval",
+            Self::NodeClass => "NodeClass",
+            Self::NodeId => "NodeId",
+            Self::Symmetric => "Symmetric",
+            Self::UserAccessLevel => "UserAccessLevel",
+            Self::UserExecutable => "UserExecutable",
+            Self::UserWriteMask => "UserWriteMask",
+            Self::ValueRank => "ValueRank",
+            Self::WriteMask => "WriteMask",
+            Self::Value => "Value",
+            Self::DataTypeDefinition => "DataTypeDefinition",
+            Self::RolePermissions => "RolePermissions",
+            Self::AccessRestrictions => "AccessRestrictions",
+            Self::All => "All",
+            Self::BaseNode => "BaseNode",
+            Self::Object => "Object",
+            Self::ObjectType => "ObjectType",
+            Self::Variable => "Variable",
+            Self::VariableType => "VariableType",
+            Self::Method => "Method",
+            Self::ReferenceType => "ReferenceType",
+            Self::View => "View",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::AccessLevel),
+            2 => Ok(Self::ArrayDimensions),
+            4 => Ok(Self::BrowseName),
+            8 => Ok(Self::ContainsNoLoops),
+            16 => Ok(Self::DataType),
+            32 => Ok(Self::Description),
+            64 => Ok(Self::DisplayName),
+            128 => Ok(Self::EventNotifier),
+            256 => Ok(Self::Executable),
+            512 => Ok(Self::Historizing),
+            1024 => Ok(Self::InverseName),
+            2048 => Ok(Self::IsAbstract),
+            4096 => Ok(Self::MinimumSamplingInterval),
+            8192 => Ok(Self::NodeClass),
+            16384 => Ok(Self::NodeId),
+            32768 => Ok(Self::Symmetric),
+            65536 => Ok(Self::UserAccessLevel),
+            131072 => Ok(Self::UserExecutable),
+            262144 => Ok(Self::UserWriteMask),
+            524288 => Ok(Self::ValueRank),
+            1048576 => Ok(Self::WriteMask),
+            2097152 => Ok(Self::Value),
+            4194304 => Ok(Self::DataTypeDefinition),
+            8388608 => Ok(Self::RolePermissions),
+            16777216 => Ok(Self::AccessRestrictions),
+            33554431 => Ok(Self::All),
+            26501220 => Ok(Self::BaseNode),
+            26501348 => Ok(Self::Object),
+            26503268 => Ok(Self::ObjectType),
+            26571383 => Ok(Self::Variable),
+            28600438 => Ok(Self::VariableType),
+            26632548 => Ok(Self::Method),
+            26537060 => Ok(Self::ReferenceType),
+            26501356 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeAttributesMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 bitflags! {
     #[derive(Debug, Copy, Clone, PartialEq)]
     pub struct AttributeWriteMask: i32 {
@@ -1088,6 +2628,36 @@ impl BinaryEncoder<AttributeWriteMask> for AttributeWriteMask {
     }
 }
 
+impl XmlEncoder for AttributeWriteMask {
+    fn xml_type_name() -> String {
+        "AttributeWriteMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AttributeWriteMask::from_bits_truncate(i32::decode_xml(
+            element,
+            decoding_options,
+        )?))
+    }
+}
+
+impl JsonEncoder for AttributeWriteMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(AttributeWriteMask::from_bits_truncate(i32::decode_json(
+            value,
+            decoding_options,
+        )?))
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BrowseDirection {
     Forward = 0,
@@ -1120,6 +2690,62 @@ impl BinaryEncoder<BrowseDirection> for BrowseDirection {
     }
 }
 
+impl XmlEncoder for BrowseDirection {
+    fn xml_type_name() -> String {
+        "BrowseDirection".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Forward => "Forward",
+            Self::Inverse => "Inverse",
+            Self::Both => "Both",
+            Self::Invalid => "Invalid",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Forward),
+            1 => Ok(Self::Inverse),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum BrowseDirection", v);
+                Ok(Self::Invalid)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for BrowseDirection {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Forward => "Forward",
+            Self::Inverse => "Inverse",
+            Self::Both => "Both",
+            Self::Invalid => "Invalid",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Forward),
+            1 => Ok(Self::Inverse),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum BrowseDirection", v);
+                Ok(Self::Invalid)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BrowseResultMask {
     None = 0,
@@ -1164,6 +2790,86 @@ impl BinaryEncoder<BrowseResultMask> for BrowseResultMask {
     }
 }
 
+impl XmlEncoder for BrowseResultMask {
+    fn xml_type_name() -> String {
+        "BrowseResultMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::None => "None",
+            Self::ReferenceTypeId => "ReferenceTypeId",
+            Self::IsForward => "IsForward",
+            Self::NodeClass => "NodeClass",
+            Self::BrowseName => "BrowseName",
+            Self::DisplayName => "DisplayName",
+            Self::TypeDefinition => "TypeDefinition",
+            Self::All => "All",
+            Self::ReferenceTypeInfo => "ReferenceTypeInfo",
+            Self::TargetInfo => "TargetInfo",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::ReferenceTypeId),
+            2 => Ok(Self::IsForward),
+            4 => Ok(Self::NodeClass),
+            8 => Ok(Self::BrowseName),
+            16 => Ok(Self::DisplayName),
+            32 => Ok(Self::TypeDefinition),
+            63 => Ok(Self::All),
+            3 => Ok(Self::ReferenceTypeInfo),
+            60 => Ok(Self::TargetInfo),
+            v => {
+                error!("Invalid value {} for enum BrowseResultMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for BrowseResultMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::None => "None",
+            Self::ReferenceTypeId => "ReferenceTypeId",
+            Self::IsForward => "IsForward",
+            Self::NodeClass => "NodeClass",
+            Self::BrowseName => "BrowseName",
+            Self::DisplayName => "DisplayName",
+            Self::TypeDefinition => "TypeDefinition",
+            Self::All => "All",
+            Self::ReferenceTypeInfo => "ReferenceTypeInfo",
+            Self::TargetInfo => "TargetInfo",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::ReferenceTypeId),
+            2 => Ok(Self::IsForward),
+            4 => Ok(Self::NodeClass),
+            8 => Ok(Self::BrowseName),
+            16 => Ok(Self::DisplayName),
+            32 => Ok(Self::TypeDefinition),
+            63 => Ok(Self::All),
+            3 => Ok(Self::ReferenceTypeInfo),
+            60 => Ok(Self::TargetInfo),
+            v => {
+                error!("Invalid value {} for enum BrowseResultMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum FilterOperator {
@@ -1225,22 +2931,134 @@ impl BinaryEncoder<FilterOperator> for FilterOperator {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub enum TimestampsToReturn {
-    Source = 0,
-    Server = 1,
-    Both = 2,
-    Neither = 3,
-    Invalid = 4,
-}
-
-impl BinaryEncoder<TimestampsToReturn> for TimestampsToReturn {
-    fn byte_len(&self) -> usize {
-        4
-    }
-
-    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+impl XmlEncoder for FilterOperator {
+    fn xml_type_name() -> String {
+        "FilterOperator".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Equals => "Equals",
+            Self::IsNull => "IsNull",
+            Self::GreaterThan => "GreaterThan",
+            Self::LessThan => "LessThan",
+            Self::GreaterThanOrEqual => "GreaterThanOrEqual",
+            Self::LessThanOrEqual => "LessThanOrEqual",
+            Self::Like => "Like",
+            Self::Not => "Not",
+            Self::Between => "Between",
+            Self::InList => "InList",
+            Self::And => "And",
+            Self::Or => "Or",
+            Self::Cast => "Cast",
+            Self::InView => "InView",
+            Self::OfType => "OfType",
+            Self::RelatedTo => "RelatedTo",
+            Self::BitwiseAnd => "BitwiseAnd",
+            Self::BitwiseOr => "BitwiseOr",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Equals),
+            1 => Ok(Self::IsNull),
+            2 => Ok(Self::GreaterThan),
+            3 => Ok(Self::LessThan),
+            4 => Ok(Self::GreaterThanOrEqual),
+            5 => Ok(Self::LessThanOrEqual),
+            6 => Ok(Self::Like),
+            7 => Ok(Self::Not),
+            8 => Ok(Self::Between),
+            9 => Ok(Self::InList),
+            10 => Ok(Self::And),
+            11 => Ok(Self::Or),
+            12 => Ok(Self::Cast),
+            13 => Ok(Self::InView),
+            14 => Ok(Self::OfType),
+            15 => Ok(Self::RelatedTo),
+            16 => Ok(Self::BitwiseAnd),
+            17 => Ok(Self::BitwiseOr),
+            v => {
+                error!("Invalid value {} for enum FilterOperator", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for FilterOperator {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Equals => "Equals",
+            Self::IsNull => "IsNull",
+            Self::GreaterThan => "GreaterThan",
+            Self::LessThan => "LessThan",
+            Self::GreaterThanOrEqual => "GreaterThanOrEqual",
+            Self::LessThanOrEqual => "LessThanOrEqual",
+            Self::Like => "Like",
+            Self::Not => "Not",
+            Self::Between => "Between",
+            Self::InList => "InList",
+            Self::And => "And",
+            Self::Or => "Or",
+            Self::Cast => "Cast",
+            Self::InView => "InView",
+            Self::OfType => "OfType",
+            Self::RelatedTo => "RelatedTo",
+            Self::BitwiseAnd => "BitwiseAnd",
+            Self::BitwiseOr => "BitwiseOr",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Equals),
+            1 => Ok(Self::IsNull),
+            2 => Ok(Self::GreaterThan),
+            3 => Ok(Self::LessThan),
+            4 => Ok(Self::GreaterThanOrEqual),
+            5 => Ok(Self::LessThanOrEqual),
+            6 => Ok(Self::Like),
+            7 => Ok(Self::Not),
+            8 => Ok(Self::Between),
+            9 => Ok(Self::InList),
+            10 => Ok(Self::And),
+            11 => Ok(Self::Or),
+            12 => Ok(Self::Cast),
+            13 => Ok(Self::InView),
+            14 => Ok(Self::OfType),
+            15 => Ok(Self::RelatedTo),
+            16 => Ok(Self::BitwiseAnd),
+            17 => Ok(Self::BitwiseOr),
+            v => {
+                error!("Invalid value {} for enum FilterOperator", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum TimestampsToReturn {
+    Source = 0,
+    Server = 1,
+    Both = 2,
+    Neither = 3,
+    Invalid = 4,
+}
+
+impl BinaryEncoder<TimestampsToReturn> for TimestampsToReturn {
+    fn byte_len(&self) -> usize {
+        4
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
         write_i32(stream, *self as i32)
     }
 
@@ -1260,6 +3078,66 @@ impl BinaryEncoder<TimestampsToReturn> for TimestampsToReturn {
     }
 }
 
+impl XmlEncoder for TimestampsToReturn {
+    fn xml_type_name() -> String {
+        "TimestampsToReturn".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Source => "Source",
+            Self::Server => "Server",
+            Self::Both => "Both",
+            Self::Neither => "Neither",
+            Self::Invalid => "Invalid",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Source),
+            1 => Ok(Self::Server),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Neither),
+            4 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum TimestampsToReturn", v);
+                Ok(Self::Invalid)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for TimestampsToReturn {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Source => "Source",
+            Self::Server => "Server",
+            Self::Both => "Both",
+            Self::Neither => "Neither",
+            Self::Invalid => "Invalid",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Source),
+            1 => Ok(Self::Server),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Neither),
+            4 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum TimestampsToReturn", v);
+                Ok(Self::Invalid)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum HistoryUpdateType {
     Insert = 1,
@@ -1292,6 +3170,62 @@ impl BinaryEncoder<HistoryUpdateType> for HistoryUpdateType {
     }
 }
 
+impl XmlEncoder for HistoryUpdateType {
+    fn xml_type_name() -> String {
+        "HistoryUpdateType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Insert => "Insert",
+            Self::Replace => "Replace",
+            Self::Update => "Update",
+            Self::Delete => "Delete",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Delete),
+            v => {
+                error!("Invalid value {} for enum HistoryUpdateType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for HistoryUpdateType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Insert => "Insert",
+            Self::Replace => "Replace",
+            Self::Update => "Update",
+            Self::Delete => "Delete",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Delete),
+            v => {
+                error!("Invalid value {} for enum HistoryUpdateType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PerformUpdateType {
     Insert = 1,
@@ -1324,6 +3258,62 @@ impl BinaryEncoder<PerformUpdateType> for PerformUpdateType {
     }
 }
 
+impl XmlEncoder for PerformUpdateType {
+    fn xml_type_name() -> String {
+        "PerformUpdateType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Insert => "Insert",
+            Self::Replace => "Replace",
+            Self::Update => "Update",
+            Self::Remove => "Remove",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Remove),
+            v => {
+                error!("Invalid value {} for enum PerformUpdateType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for PerformUpdateType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Insert => "Insert",
+            Self::Replace => "Replace",
+            Self::Update => "Update",
+            Self::Remove => "Remove",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Remove),
+            v => {
+                error!("Invalid value {} for enum PerformUpdateType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum MonitoringMode {
@@ -1355,6 +3345,58 @@ impl BinaryEncoder<MonitoringMode> for MonitoringMode {
     }
 }
 
+impl XmlEncoder for MonitoringMode {
+    fn xml_type_name() -> String {
+        "MonitoringMode".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Disabled => "Disabled",
+            Self::Sampling => "Sampling",
+            Self::Reporting => "Reporting",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Sampling),
+            2 => Ok(Self::Reporting),
+            v => {
+                error!("Invalid value {} for enum MonitoringMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for MonitoringMode {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Disabled => "Disabled",
+            Self::Sampling => "Sampling",
+            Self::Reporting => "Reporting",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Sampling),
+            2 => Ok(Self::Reporting),
+            v => {
+                error!("Invalid value {} for enum MonitoringMode", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum DataChangeTrigger {
@@ -1386,6 +3428,58 @@ impl BinaryEncoder<DataChangeTrigger> for DataChangeTrigger {
     }
 }
 
+impl XmlEncoder for DataChangeTrigger {
+    fn xml_type_name() -> String {
+        "DataChangeTrigger".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Status => "Status",
+            Self::StatusValue => "StatusValue",
+            Self::StatusValueTimestamp => "StatusValueTimestamp",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Status),
+            1 => Ok(Self::StatusValue),
+            2 => Ok(Self::StatusValueTimestamp),
+            v => {
+                error!("Invalid value {} for enum DataChangeTrigger", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for DataChangeTrigger {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Status => "Status",
+            Self::StatusValue => "StatusValue",
+            Self::StatusValueTimestamp => "StatusValueTimestamp",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Status),
+            1 => Ok(Self::StatusValue),
+            2 => Ok(Self::StatusValueTimestamp),
+            v => {
+                error!("Invalid value {} for enum DataChangeTrigger", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DeadbandType {
     None = 0,
@@ -1416,6 +3510,58 @@ impl BinaryEncoder<DeadbandType> for DeadbandType {
     }
 }
 
+impl XmlEncoder for DeadbandType {
+    fn xml_type_name() -> String {
+        "DeadbandType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::None => "None",
+            Self::Absolute => "Absolute",
+            Self::Percent => "Percent",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Absolute),
+            2 => Ok(Self::Percent),
+            v => {
+                error!("Invalid value {} for enum DeadbandType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for DeadbandType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::None => "None",
+            Self::Absolute => "Absolute",
+            Self::Percent => "Percent",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Absolute),
+            2 => Ok(Self::Percent),
+            v => {
+                error!("Invalid value {} for enum DeadbandType", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum RedundancySupport {
     None = 0,
@@ -1452,6 +3598,70 @@ impl BinaryEncoder<RedundancySupport> for RedundancySupport {
     }
 }
 
+impl XmlEncoder for RedundancySupport {
+    fn xml_type_name() -> String {
+        "RedundancySupport".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::None => "None",
+            Self::Cold => "Cold",
+            Self::Warm => "Warm",
+            Self::Hot => "Hot",
+            Self::Transparent => "Transparent",
+            Self::HotAndMirrored => "HotAndMirrored",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Cold),
+            2 => Ok(Self::Warm),
+            3 => Ok(Self::Hot),
+            4 => Ok(Self::Transparent),
+            5 => Ok(Self::HotAndMirrored),
+            v => {
+                error!("Invalid value {} for enum RedundancySupport", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for RedundancySupport {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::None => "None",
+            Self::Cold => "Cold",
+            Self::Warm => "Warm",
+            Self::Hot => "Hot",
+            Self::Transparent => "Transparent",
+            Self::HotAndMirrored => "HotAndMirrored",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Cold),
+            2 => Ok(Self::Warm),
+            3 => Ok(Self::Hot),
+            4 => Ok(Self::Transparent),
+            5 => Ok(Self::HotAndMirrored),
+            v => {
+                error!("Invalid value {} for enum RedundancySupport", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ServerState {
     Running = 0,
@@ -1492,6 +3702,78 @@ impl BinaryEncoder<ServerState> for ServerState {
     }
 }
 
+impl XmlEncoder for ServerState {
+    fn xml_type_name() -> String {
+        "ServerState".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Running => "Running",
+            Self::Failed => "Failed",
+            Self::NoConfiguration => "NoConfiguration",
+            Self::Suspended => "Suspended",
+            Self::Shutdown => "Shutdown",
+            Self::Test => "Test",
+            Self::CommunicationFault => "CommunicationFault",
+            Self::Unknown => "Unknown",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Running),
+            1 => Ok(Self::Failed),
+            2 => Ok(Self::NoConfiguration),
+            3 => Ok(Self::Suspended),
+            4 => Ok(Self::Shutdown),
+            5 => Ok(Self::Test),
+            6 => Ok(Self::CommunicationFault),
+            7 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ServerState", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for ServerState {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Running => "Running",
+            Self::Failed => "Failed",
+            Self::NoConfiguration => "NoConfiguration",
+            Self::Suspended => "Suspended",
+            Self::Shutdown => "Shutdown",
+            Self::Test => "Test",
+            Self::CommunicationFault => "CommunicationFault",
+            Self::Unknown => "Unknown",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Running),
+            1 => Ok(Self::Failed),
+            2 => Ok(Self::NoConfiguration),
+            3 => Ok(Self::Suspended),
+            4 => Ok(Self::Shutdown),
+            5 => Ok(Self::Test),
+            6 => Ok(Self::CommunicationFault),
+            7 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ServerState", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ModelChangeStructureVerbMask {
     NodeAdded = 1,
@@ -1526,6 +3808,66 @@ impl BinaryEncoder<ModelChangeStructureVerbMask> for ModelChangeStructureVerbMas
     }
 }
 
+impl XmlEncoder for ModelChangeStructureVerbMask {
+    fn xml_type_name() -> String {
+        "ModelChangeStructureVerbMask".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::NodeAdded => "NodeAdded",
+            Self::NodeDeleted => "NodeDeleted",
+            Self::ReferenceAdded => "ReferenceAdded",
+            Self::ReferenceDeleted => "ReferenceDeleted",
+            Self::DataTypeChanged => "DataTypeChanged",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            1 => Ok(Self::NodeAdded),
+            2 => Ok(Self::NodeDeleted),
+            4 => Ok(Self::ReferenceAdded),
+            8 => Ok(Self::ReferenceDeleted),
+            16 => Ok(Self::DataTypeChanged),
+            v => {
+                error!("Invalid value {} for enum ModelChangeStructureVerbMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for ModelChangeStructureVerbMask {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::NodeAdded => "NodeAdded",
+            Self::NodeDeleted => "NodeDeleted",
+            Self::ReferenceAdded => "ReferenceAdded",
+            Self::ReferenceDeleted => "ReferenceDeleted",
+            Self::DataTypeChanged => "DataTypeChanged",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            1 => Ok(Self::NodeAdded),
+            2 => Ok(Self::NodeDeleted),
+            4 => Ok(Self::ReferenceAdded),
+            8 => Ok(Self::ReferenceDeleted),
+            16 => Ok(Self::DataTypeChanged),
+            v => {
+                error!("Invalid value {} for enum ModelChangeStructureVerbMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AxisScaleEnumeration {
     Linear = 0,
@@ -1556,6 +3898,58 @@ impl BinaryEncoder<AxisScaleEnumeration> for AxisScaleEnumeration {
     }
 }
 
+impl XmlEncoder for AxisScaleEnumeration {
+    fn xml_type_name() -> String {
+        "AxisScaleEnumeration".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::Linear => "Linear",
+            Self::Log => "Log",
+            Self::Ln => "Ln",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::Linear),
+            1 => Ok(Self::Log),
+            2 => Ok(Self::Ln),
+            v => {
+                error!("Invalid value {} for enum AxisScaleEnumeration", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for AxisScaleEnumeration {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::Linear => "Linear",
+            Self::Log => "Log",
+            Self::Ln => "Ln",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::Linear),
+            1 => Ok(Self::Log),
+            2 => Ok(Self::Ln),
+            v => {
+                error!("Invalid value {} for enum AxisScaleEnumeration", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ExceptionDeviationFormat {
     AbsoluteValue = 0,
@@ -1589,3 +3983,63 @@ impl BinaryEncoder<ExceptionDeviationFormat> for ExceptionDeviationFormat {
         }
     }
 }
+
+impl XmlEncoder for ExceptionDeviationFormat {
+    fn xml_type_name() -> String {
+        "ExceptionDeviationFormat".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {
+            Self::AbsoluteValue => "AbsoluteValue",
+            Self::PercentOfValue => "PercentOfValue",
+            Self::PercentOfRange => "PercentOfRange",
+            Self::PercentOfEURange => "PercentOfEURange",
+            Self::Unknown => "Unknown",
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {
+            0 => Ok(Self::AbsoluteValue),
+            1 => Ok(Self::PercentOfValue),
+            2 => Ok(Self::PercentOfRange),
+            3 => Ok(Self::PercentOfEURange),
+            4 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ExceptionDeviationFormat", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder for ExceptionDeviationFormat {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {
+            Self::AbsoluteValue => "AbsoluteValue",
+            Self::PercentOfValue => "PercentOfValue",
+            Self::PercentOfRange => "PercentOfRange",
+            Self::PercentOfEURange => "PercentOfEURange",
+            Self::Unknown => "Unknown",
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {
+            0 => Ok(Self::AbsoluteValue),
+            1 => Ok(Self::PercentOfValue),
+            2 => Ok(Self::PercentOfRange),
+            3 => Ok(Self::PercentOfEURange),
+            4 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ExceptionDeviationFormat", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
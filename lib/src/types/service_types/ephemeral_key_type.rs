@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<EphemeralKeyType> for EphemeralKeyType {
         })
     }
 }
+
+impl XmlEncoder for EphemeralKeyType {
+    fn xml_type_name() -> String {
+        "EphemeralKeyType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PublicKey", &self.public_key);
+        write_xml_field(&mut element, "Signature", &self.signature);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EphemeralKeyType {
+            public_key: read_xml_field(element, "PublicKey", decoding_options)?,
+            signature: read_xml_field(element, "Signature", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EphemeralKeyType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PublicKey", &self.public_key, encoding);
+        write_json_field(&mut object, "Signature", &self.signature, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EphemeralKeyType {
+            public_key: read_json_field(value, "PublicKey", decoding_options)?,
+            signature: read_json_field(value, "Signature", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,50 @@ impl BinaryEncoder<EUInformation> for EUInformation {
         })
     }
 }
+
+impl XmlEncoder for EUInformation {
+    fn xml_type_name() -> String {
+        "EUInformation".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NamespaceUri", &self.namespace_uri);
+        write_xml_field(&mut element, "UnitId", &self.unit_id);
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EUInformation {
+            namespace_uri: read_xml_field(element, "NamespaceUri", decoding_options)?,
+            unit_id: read_xml_field(element, "UnitId", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EUInformation {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NamespaceUri", &self.namespace_uri, encoding);
+        write_json_field(&mut object, "UnitId", &self.unit_id, encoding);
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EUInformation {
+            namespace_uri: read_json_field(value, "NamespaceUri", decoding_options)?,
+            unit_id: read_json_field(value, "UnitId", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    variant::Variant,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,42 @@ impl BinaryEncoder<EventFieldList> for EventFieldList {
         })
     }
 }
+
+impl XmlEncoder for EventFieldList {
+    fn xml_type_name() -> String {
+        "EventFieldList".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ClientHandle", &self.client_handle);
+        write_xml_field(&mut element, "EventFields", &self.event_fields);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventFieldList {
+            client_handle: read_xml_field(element, "ClientHandle", decoding_options)?,
+            event_fields: read_xml_field(element, "EventFields", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EventFieldList {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ClientHandle", &self.client_handle, encoding);
+        write_json_field(&mut object, "EventFields", &self.event_fields, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EventFieldList {
+            client_handle: read_json_field(value, "ClientHandle", decoding_options)?,
+            event_fields: read_json_field(value, "EventFields", decoding_options)?,
+        })
+    }
+}
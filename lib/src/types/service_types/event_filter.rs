@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::ContentFilter,
-    service_types::SimpleAttributeOperand,
+    basic_types::*, encoding::*, json::*, service_types::ContentFilter,
+    service_types::SimpleAttributeOperand, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -47,3 +47,42 @@ impl BinaryEncoder<EventFilter> for EventFilter {
         })
     }
 }
+
+impl XmlEncoder for EventFilter {
+    fn xml_type_name() -> String {
+        "EventFilter".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SelectClauses", &self.select_clauses);
+        write_xml_field(&mut element, "WhereClause", &self.where_clause);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventFilter {
+            select_clauses: read_xml_field(element, "SelectClauses", decoding_options)?,
+            where_clause: read_xml_field(element, "WhereClause", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EventFilter {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SelectClauses", &self.select_clauses, encoding);
+        write_json_field(&mut object, "WhereClause", &self.where_clause, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EventFilter {
+            select_clauses: read_json_field(value, "SelectClauses", decoding_options)?,
+            where_clause: read_json_field(value, "WhereClause", decoding_options)?,
+        })
+    }
+}
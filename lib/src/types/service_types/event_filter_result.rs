@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*,
-    service_types::ContentFilterResult, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*,
+    service_types::ContentFilterResult, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,81 @@ impl BinaryEncoder<EventFilterResult> for EventFilterResult {
         })
     }
 }
+
+impl XmlEncoder for EventFilterResult {
+    fn xml_type_name() -> String {
+        "EventFilterResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "SelectClauseResults",
+            &self.select_clause_results,
+        );
+        write_xml_field(
+            &mut element,
+            "SelectClauseDiagnosticInfos",
+            &self.select_clause_diagnostic_infos,
+        );
+        write_xml_field(&mut element, "WhereClauseResult", &self.where_clause_result);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventFilterResult {
+            select_clause_results: read_xml_field(
+                element,
+                "SelectClauseResults",
+                decoding_options,
+            )?,
+            select_clause_diagnostic_infos: read_xml_field(
+                element,
+                "SelectClauseDiagnosticInfos",
+                decoding_options,
+            )?,
+            where_clause_result: read_xml_field(element, "WhereClauseResult", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EventFilterResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SelectClauseResults",
+            &self.select_clause_results,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SelectClauseDiagnosticInfos",
+            &self.select_clause_diagnostic_infos,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "WhereClauseResult",
+            &self.where_clause_result,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EventFilterResult {
+            select_clause_results: read_json_field(value, "SelectClauseResults", decoding_options)?,
+            select_clause_diagnostic_infos: read_json_field(
+                value,
+                "SelectClauseDiagnosticInfos",
+                decoding_options,
+            )?,
+            where_clause_result: read_json_field(value, "WhereClauseResult", decoding_options)?,
+        })
+    }
+}
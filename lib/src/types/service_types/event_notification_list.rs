@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, service_types::EventFieldList};
+use crate::types::{basic_types::*, encoding::*, json::*, service_types::EventFieldList, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +35,38 @@ impl BinaryEncoder<EventNotificationList> for EventNotificationList {
         Ok(EventNotificationList { events })
     }
 }
+
+impl XmlEncoder for EventNotificationList {
+    fn xml_type_name() -> String {
+        "EventNotificationList".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Events", &self.events);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(EventNotificationList {
+            events: read_xml_field(element, "Events", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for EventNotificationList {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Events", &self.events, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(EventNotificationList {
+            events: read_json_field(value, "Events", decoding_options)?,
+        })
+    }
+}
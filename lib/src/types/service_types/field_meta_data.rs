@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, guid::Guid, localized_text::LocalizedText, node_id::NodeId,
-    node_ids::ObjectId, service_types::enums::DataSetFieldFlags, service_types::impls::MessageInfo,
-    service_types::KeyValuePair, string::UAString,
+    basic_types::*, encoding::*, guid::Guid, json::*, localized_text::LocalizedText,
+    node_id::NodeId, node_ids::ObjectId, service_types::enums::DataSetFieldFlags,
+    service_types::impls::MessageInfo, service_types::KeyValuePair, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -93,3 +93,89 @@ impl BinaryEncoder<FieldMetaData> for FieldMetaData {
         })
     }
 }
+
+impl XmlEncoder for FieldMetaData {
+    fn xml_type_name() -> String {
+        "FieldMetaData".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "FieldFlags", &self.field_flags);
+        write_xml_field(&mut element, "BuiltInType", &self.built_in_type);
+        write_xml_field(&mut element, "DataType", &self.data_type);
+        write_xml_field(&mut element, "ValueRank", &self.value_rank);
+        write_xml_field(&mut element, "ArrayDimensions", &self.array_dimensions);
+        write_xml_field(&mut element, "MaxStringLength", &self.max_string_length);
+        write_xml_field(&mut element, "DataSetFieldId", &self.data_set_field_id);
+        write_xml_field(&mut element, "Properties", &self.properties);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(FieldMetaData {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            field_flags: read_xml_field(element, "FieldFlags", decoding_options)?,
+            built_in_type: read_xml_field(element, "BuiltInType", decoding_options)?,
+            data_type: read_xml_field(element, "DataType", decoding_options)?,
+            value_rank: read_xml_field(element, "ValueRank", decoding_options)?,
+            array_dimensions: read_xml_field(element, "ArrayDimensions", decoding_options)?,
+            max_string_length: read_xml_field(element, "MaxStringLength", decoding_options)?,
+            data_set_field_id: read_xml_field(element, "DataSetFieldId", decoding_options)?,
+            properties: read_xml_field(element, "Properties", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for FieldMetaData {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "FieldFlags", &self.field_flags, encoding);
+        write_json_field(&mut object, "BuiltInType", &self.built_in_type, encoding);
+        write_json_field(&mut object, "DataType", &self.data_type, encoding);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, encoding);
+        write_json_field(
+            &mut object,
+            "ArrayDimensions",
+            &self.array_dimensions,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxStringLength",
+            &self.max_string_length,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetFieldId",
+            &self.data_set_field_id,
+            encoding,
+        );
+        write_json_field(&mut object, "Properties", &self.properties, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(FieldMetaData {
+            name: read_json_field(value, "Name", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            field_flags: read_json_field(value, "FieldFlags", decoding_options)?,
+            built_in_type: read_json_field(value, "BuiltInType", decoding_options)?,
+            data_type: read_json_field(value, "DataType", decoding_options)?,
+            value_rank: read_json_field(value, "ValueRank", decoding_options)?,
+            array_dimensions: read_json_field(value, "ArrayDimensions", decoding_options)?,
+            max_string_length: read_json_field(value, "MaxStringLength", decoding_options)?,
+            data_set_field_id: read_json_field(value, "DataSetFieldId", decoding_options)?,
+            properties: read_json_field(value, "Properties", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, guid::Guid, node_id::NodeId, node_ids::ObjectId,
+    basic_types::*, encoding::*, guid::Guid, json::*, node_id::NodeId, node_ids::ObjectId,
     service_types::enums::OverrideValueHandling, service_types::impls::MessageInfo,
-    string::UAString, variant::Variant,
+    string::UAString, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -77,3 +77,98 @@ impl BinaryEncoder<FieldTargetDataType> for FieldTargetDataType {
         })
     }
 }
+
+impl XmlEncoder for FieldTargetDataType {
+    fn xml_type_name() -> String {
+        "FieldTargetDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataSetFieldId", &self.data_set_field_id);
+        write_xml_field(
+            &mut element,
+            "ReceiverIndexRange",
+            &self.receiver_index_range,
+        );
+        write_xml_field(&mut element, "TargetNodeId", &self.target_node_id);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "WriteIndexRange", &self.write_index_range);
+        write_xml_field(
+            &mut element,
+            "OverrideValueHandling",
+            &self.override_value_handling,
+        );
+        write_xml_field(&mut element, "OverrideValue", &self.override_value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(FieldTargetDataType {
+            data_set_field_id: read_xml_field(element, "DataSetFieldId", decoding_options)?,
+            receiver_index_range: read_xml_field(element, "ReceiverIndexRange", decoding_options)?,
+            target_node_id: read_xml_field(element, "TargetNodeId", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            write_index_range: read_xml_field(element, "WriteIndexRange", decoding_options)?,
+            override_value_handling: read_xml_field(
+                element,
+                "OverrideValueHandling",
+                decoding_options,
+            )?,
+            override_value: read_xml_field(element, "OverrideValue", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for FieldTargetDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "DataSetFieldId",
+            &self.data_set_field_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ReceiverIndexRange",
+            &self.receiver_index_range,
+            encoding,
+        );
+        write_json_field(&mut object, "TargetNodeId", &self.target_node_id, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(
+            &mut object,
+            "WriteIndexRange",
+            &self.write_index_range,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "OverrideValueHandling",
+            &self.override_value_handling,
+            encoding,
+        );
+        write_json_field(&mut object, "OverrideValue", &self.override_value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(FieldTargetDataType {
+            data_set_field_id: read_json_field(value, "DataSetFieldId", decoding_options)?,
+            receiver_index_range: read_json_field(value, "ReceiverIndexRange", decoding_options)?,
+            target_node_id: read_json_field(value, "TargetNodeId", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            write_index_range: read_json_field(value, "WriteIndexRange", decoding_options)?,
+            override_value_handling: read_json_field(
+                value,
+                "OverrideValueHandling",
+                decoding_options,
+            )?,
+            override_value: read_json_field(value, "OverrideValue", decoding_options)?,
+        })
+    }
+}
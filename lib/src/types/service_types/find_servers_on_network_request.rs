@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,81 @@ impl BinaryEncoder<FindServersOnNetworkRequest> for FindServersOnNetworkRequest
         })
     }
 }
+
+impl XmlEncoder for FindServersOnNetworkRequest {
+    fn xml_type_name() -> String {
+        "FindServersOnNetworkRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "StartingRecordId", &self.starting_record_id);
+        write_xml_field(
+            &mut element,
+            "MaxRecordsToReturn",
+            &self.max_records_to_return,
+        );
+        write_xml_field(
+            &mut element,
+            "ServerCapabilityFilter",
+            &self.server_capability_filter,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(FindServersOnNetworkRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            starting_record_id: read_xml_field(element, "StartingRecordId", decoding_options)?,
+            max_records_to_return: read_xml_field(element, "MaxRecordsToReturn", decoding_options)?,
+            server_capability_filter: read_xml_field(
+                element,
+                "ServerCapabilityFilter",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for FindServersOnNetworkRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "StartingRecordId",
+            &self.starting_record_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxRecordsToReturn",
+            &self.max_records_to_return,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerCapabilityFilter",
+            &self.server_capability_filter,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(FindServersOnNetworkRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            starting_record_id: read_json_field(value, "StartingRecordId", decoding_options)?,
+            max_records_to_return: read_json_field(value, "MaxRecordsToReturn", decoding_options)?,
+            server_capability_filter: read_json_field(
+                value,
+                "ServerCapabilityFilter",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_ids::ObjectId,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo,
-    service_types::ServerOnNetwork,
+    service_types::ServerOnNetwork, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,68 @@ impl BinaryEncoder<FindServersOnNetworkResponse> for FindServersOnNetworkRespons
         })
     }
 }
+
+impl XmlEncoder for FindServersOnNetworkResponse {
+    fn xml_type_name() -> String {
+        "FindServersOnNetworkResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(
+            &mut element,
+            "LastCounterResetTime",
+            &self.last_counter_reset_time,
+        );
+        write_xml_field(&mut element, "Servers", &self.servers);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(FindServersOnNetworkResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            last_counter_reset_time: read_xml_field(
+                element,
+                "LastCounterResetTime",
+                decoding_options,
+            )?,
+            servers: read_xml_field(element, "Servers", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for FindServersOnNetworkResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastCounterResetTime",
+            &self.last_counter_reset_time,
+            encoding,
+        );
+        write_json_field(&mut object, "Servers", &self.servers, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(FindServersOnNetworkResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            last_counter_reset_time: read_json_field(
+                value,
+                "LastCounterResetTime",
+                decoding_options,
+            )?,
+            servers: read_json_field(value, "Servers", decoding_options)?,
+        })
+    }
+}
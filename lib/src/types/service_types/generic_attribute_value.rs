@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    variant::Variant,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<GenericAttributeValue> for GenericAttributeValue {
         })
     }
 }
+
+impl XmlEncoder for GenericAttributeValue {
+    fn xml_type_name() -> String {
+        "GenericAttributeValue".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(GenericAttributeValue {
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for GenericAttributeValue {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(GenericAttributeValue {
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
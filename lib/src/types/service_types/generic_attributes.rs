@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText,
-    service_types::GenericAttributeValue,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText,
+    service_types::GenericAttributeValue, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,77 @@ impl BinaryEncoder<GenericAttributes> for GenericAttributes {
         })
     }
 }
+
+impl XmlEncoder for GenericAttributes {
+    fn xml_type_name() -> String {
+        "GenericAttributes".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+        );
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "WriteMask", &self.write_mask);
+        write_xml_field(&mut element, "UserWriteMask", &self.user_write_mask);
+        write_xml_field(&mut element, "AttributeValues", &self.attribute_values);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(GenericAttributes {
+            specified_attributes: read_xml_field(element, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            write_mask: read_xml_field(element, "WriteMask", decoding_options)?,
+            user_write_mask: read_xml_field(element, "UserWriteMask", decoding_options)?,
+            attribute_values: read_xml_field(element, "AttributeValues", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for GenericAttributes {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+            encoding,
+        );
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, encoding);
+        write_json_field(
+            &mut object,
+            "UserWriteMask",
+            &self.user_write_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AttributeValues",
+            &self.attribute_values,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(GenericAttributes {
+            specified_attributes: read_json_field(value, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            write_mask: read_json_field(value, "WriteMask", decoding_options)?,
+            user_write_mask: read_json_field(value, "UserWriteMask", decoding_options)?,
+            attribute_values: read_json_field(value, "AttributeValues", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,50 @@ impl BinaryEncoder<GetEndpointsRequest> for GetEndpointsRequest {
         })
     }
 }
+
+impl XmlEncoder for GetEndpointsRequest {
+    fn xml_type_name() -> String {
+        "GetEndpointsRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "EndpointUrl", &self.endpoint_url);
+        write_xml_field(&mut element, "LocaleIds", &self.locale_ids);
+        write_xml_field(&mut element, "ProfileUris", &self.profile_uris);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(GetEndpointsRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            endpoint_url: read_xml_field(element, "EndpointUrl", decoding_options)?,
+            locale_ids: read_xml_field(element, "LocaleIds", decoding_options)?,
+            profile_uris: read_xml_field(element, "ProfileUris", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for GetEndpointsRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, encoding);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, encoding);
+        write_json_field(&mut object, "ProfileUris", &self.profile_uris, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(GetEndpointsRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            endpoint_url: read_json_field(value, "EndpointUrl", decoding_options)?,
+            locale_ids: read_json_field(value, "LocaleIds", decoding_options)?,
+            profile_uris: read_json_field(value, "ProfileUris", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, data_value::DataValue, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, data_value::DataValue, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,38 @@ impl BinaryEncoder<HistoryData> for HistoryData {
         Ok(HistoryData { data_values })
     }
 }
+
+impl XmlEncoder for HistoryData {
+    fn xml_type_name() -> String {
+        "HistoryData".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataValues", &self.data_values);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryData {
+            data_values: read_xml_field(element, "DataValues", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryData {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataValues", &self.data_values, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryData {
+            data_values: read_json_field(value, "DataValues", decoding_options)?,
+        })
+    }
+}
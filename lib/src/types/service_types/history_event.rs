@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::HistoryEventFieldList,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::HistoryEventFieldList, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,38 @@ impl BinaryEncoder<HistoryEvent> for HistoryEvent {
         Ok(HistoryEvent { events })
     }
 }
+
+impl XmlEncoder for HistoryEvent {
+    fn xml_type_name() -> String {
+        "HistoryEvent".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Events", &self.events);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryEvent {
+            events: read_xml_field(element, "Events", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryEvent {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Events", &self.events, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryEvent {
+            events: read_json_field(value, "Events", decoding_options)?,
+        })
+    }
+}
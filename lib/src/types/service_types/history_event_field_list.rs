@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    variant::Variant,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,38 @@ impl BinaryEncoder<HistoryEventFieldList> for HistoryEventFieldList {
         Ok(HistoryEventFieldList { event_fields })
     }
 }
+
+impl XmlEncoder for HistoryEventFieldList {
+    fn xml_type_name() -> String {
+        "HistoryEventFieldList".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EventFields", &self.event_fields);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryEventFieldList {
+            event_fields: read_xml_field(element, "EventFields", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryEventFieldList {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EventFields", &self.event_fields, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryEventFieldList {
+            event_fields: read_json_field(value, "EventFields", decoding_options)?,
+        })
+    }
+}
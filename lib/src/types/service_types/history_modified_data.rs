@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, data_value::DataValue, encoding::*, service_types::ModificationInfo,
+    basic_types::*, data_value::DataValue, encoding::*, json::*, service_types::ModificationInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -45,3 +46,47 @@ impl BinaryEncoder<HistoryModifiedData> for HistoryModifiedData {
         })
     }
 }
+
+impl XmlEncoder for HistoryModifiedData {
+    fn xml_type_name() -> String {
+        "HistoryModifiedData".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataValues", &self.data_values);
+        write_xml_field(&mut element, "ModificationInfos", &self.modification_infos);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryModifiedData {
+            data_values: read_xml_field(element, "DataValues", decoding_options)?,
+            modification_infos: read_xml_field(element, "ModificationInfos", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryModifiedData {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataValues", &self.data_values, encoding);
+        write_json_field(
+            &mut object,
+            "ModificationInfos",
+            &self.modification_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryModifiedData {
+            data_values: read_json_field(value, "DataValues", decoding_options)?,
+            modification_infos: read_json_field(value, "ModificationInfos", decoding_options)?,
+        })
+    }
+}
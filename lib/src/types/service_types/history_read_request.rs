@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     request_header::RequestHeader, service_types::enums::TimestampsToReturn,
-    service_types::impls::MessageInfo, service_types::HistoryReadValueId,
+    service_types::impls::MessageInfo, service_types::HistoryReadValueId, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -67,3 +67,89 @@ impl BinaryEncoder<HistoryReadRequest> for HistoryReadRequest {
         })
     }
 }
+
+impl XmlEncoder for HistoryReadRequest {
+    fn xml_type_name() -> String {
+        "HistoryReadRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "HistoryReadDetails",
+            &self.history_read_details,
+        );
+        write_xml_field(
+            &mut element,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+        );
+        write_xml_field(
+            &mut element,
+            "ReleaseContinuationPoints",
+            &self.release_continuation_points,
+        );
+        write_xml_field(&mut element, "NodesToRead", &self.nodes_to_read);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryReadRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            history_read_details: read_xml_field(element, "HistoryReadDetails", decoding_options)?,
+            timestamps_to_return: read_xml_field(element, "TimestampsToReturn", decoding_options)?,
+            release_continuation_points: read_xml_field(
+                element,
+                "ReleaseContinuationPoints",
+                decoding_options,
+            )?,
+            nodes_to_read: read_xml_field(element, "NodesToRead", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryReadRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "HistoryReadDetails",
+            &self.history_read_details,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ReleaseContinuationPoints",
+            &self.release_continuation_points,
+            encoding,
+        );
+        write_json_field(&mut object, "NodesToRead", &self.nodes_to_read, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryReadRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            history_read_details: read_json_field(value, "HistoryReadDetails", decoding_options)?,
+            timestamps_to_return: read_json_field(value, "TimestampsToReturn", decoding_options)?,
+            release_continuation_points: read_json_field(
+                value,
+                "ReleaseContinuationPoints",
+                decoding_options,
+            )?,
+            nodes_to_read: read_json_field(value, "NodesToRead", decoding_options)?,
+        })
+    }
+}
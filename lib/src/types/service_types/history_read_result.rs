@@ -9,7 +9,8 @@
 #[allow(unused_imports)]
 use crate::types::{
     basic_types::*, byte_string::ByteString, encoding::*, extension_object::ExtensionObject,
-    node_ids::ObjectId, service_types::impls::MessageInfo, status_codes::StatusCode,
+    json::*, node_ids::ObjectId, service_types::impls::MessageInfo, status_codes::StatusCode,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +57,51 @@ impl BinaryEncoder<HistoryReadResult> for HistoryReadResult {
         })
     }
 }
+
+impl XmlEncoder for HistoryReadResult {
+    fn xml_type_name() -> String {
+        "HistoryReadResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "ContinuationPoint", &self.continuation_point);
+        write_xml_field(&mut element, "HistoryData", &self.history_data);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryReadResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            continuation_point: read_xml_field(element, "ContinuationPoint", decoding_options)?,
+            history_data: read_xml_field(element, "HistoryData", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryReadResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "ContinuationPoint",
+            &self.continuation_point,
+            encoding,
+        );
+        write_json_field(&mut object, "HistoryData", &self.history_data, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryReadResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            continuation_point: read_json_field(value, "ContinuationPoint", decoding_options)?,
+            history_data: read_json_field(value, "HistoryData", decoding_options)?,
+        })
+    }
+}
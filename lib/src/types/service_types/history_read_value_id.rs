@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    qualified_name::QualifiedName, service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_id::NodeId,
+    node_ids::ObjectId, qualified_name::QualifiedName, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +62,55 @@ impl BinaryEncoder<HistoryReadValueId> for HistoryReadValueId {
         })
     }
 }
+
+impl XmlEncoder for HistoryReadValueId {
+    fn xml_type_name() -> String {
+        "HistoryReadValueId".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        write_xml_field(&mut element, "DataEncoding", &self.data_encoding);
+        write_xml_field(&mut element, "ContinuationPoint", &self.continuation_point);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryReadValueId {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+            data_encoding: read_xml_field(element, "DataEncoding", decoding_options)?,
+            continuation_point: read_xml_field(element, "ContinuationPoint", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryReadValueId {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        write_json_field(&mut object, "DataEncoding", &self.data_encoding, encoding);
+        write_json_field(
+            &mut object,
+            "ContinuationPoint",
+            &self.continuation_point,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryReadValueId {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+            data_encoding: read_json_field(value, "DataEncoding", decoding_options)?,
+            continuation_point: read_json_field(value, "ContinuationPoint", decoding_options)?,
+        })
+    }
+}
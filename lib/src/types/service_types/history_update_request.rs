@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
-    request_header::RequestHeader, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
+    request_header::RequestHeader, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,59 @@ impl BinaryEncoder<HistoryUpdateRequest> for HistoryUpdateRequest {
         })
     }
 }
+
+impl XmlEncoder for HistoryUpdateRequest {
+    fn xml_type_name() -> String {
+        "HistoryUpdateRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "HistoryUpdateDetails",
+            &self.history_update_details,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryUpdateRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            history_update_details: read_xml_field(
+                element,
+                "HistoryUpdateDetails",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryUpdateRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "HistoryUpdateDetails",
+            &self.history_update_details,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryUpdateRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            history_update_details: read_json_field(
+                value,
+                "HistoryUpdateDetails",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,56 @@ impl BinaryEncoder<HistoryUpdateResult> for HistoryUpdateResult {
         })
     }
 }
+
+impl XmlEncoder for HistoryUpdateResult {
+    fn xml_type_name() -> String {
+        "HistoryUpdateResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "OperationResults", &self.operation_results);
+        write_xml_field(&mut element, "DiagnosticInfos", &self.diagnostic_infos);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(HistoryUpdateResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            operation_results: read_xml_field(element, "OperationResults", decoding_options)?,
+            diagnostic_infos: read_xml_field(element, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for HistoryUpdateResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "OperationResults",
+            &self.operation_results,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DiagnosticInfos",
+            &self.diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(HistoryUpdateResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            operation_results: read_json_field(value, "OperationResults", decoding_options)?,
+            diagnostic_infos: read_json_field(value, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
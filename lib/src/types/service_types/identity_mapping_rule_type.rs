@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::enums::IdentityCriteriaType,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId,
+    service_types::enums::IdentityCriteriaType, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +52,42 @@ impl BinaryEncoder<IdentityMappingRuleType> for IdentityMappingRuleType {
         })
     }
 }
+
+impl XmlEncoder for IdentityMappingRuleType {
+    fn xml_type_name() -> String {
+        "IdentityMappingRuleType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "CriteriaType", &self.criteria_type);
+        write_xml_field(&mut element, "Criteria", &self.criteria);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(IdentityMappingRuleType {
+            criteria_type: read_xml_field(element, "CriteriaType", decoding_options)?,
+            criteria: read_xml_field(element, "Criteria", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for IdentityMappingRuleType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "CriteriaType", &self.criteria_type, encoding);
+        write_json_field(&mut object, "Criteria", &self.criteria, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(IdentityMappingRuleType {
+            criteria_type: read_json_field(value, "CriteriaType", decoding_options)?,
+            criteria: read_json_field(value, "Criteria", decoding_options)?,
+        })
+    }
+}
@@ -7,6 +7,7 @@ use crate::types::{
     byte_string::ByteString,
     constants,
     data_value::DataValue,
+    expanded_node_id::ExpandedNodeId,
     extension_object::ExtensionObject,
     localized_text::LocalizedText,
     node_id::NodeId,
@@ -19,14 +20,17 @@ use crate::types::{
         enums::DeadbandType, AnonymousIdentityToken, ApplicationDescription, ApplicationType,
         Argument, CallMethodRequest, DataChangeFilter, DataChangeTrigger, DataSetFieldFlags,
         EndpointDescription, MessageSecurityMode, MonitoredItemCreateRequest, MonitoringMode,
-        MonitoringParameters, ReadValueId, ServiceCounterDataType, ServiceFault, SignatureData,
-        UserNameIdentityToken, UserTokenPolicy, UserTokenType,
+        MonitoringParameters, ReadValueId, RelativePath, ServiceCounterDataType, ServiceFault,
+        SignatureData, UserNameIdentityToken, UserTokenPolicy, UserTokenType,
     },
     status_codes::StatusCode,
     string::UAString,
     variant::Variant,
 };
 
+#[cfg(feature = "query-services")]
+use crate::types::service_types::{NodeTypeDescription, QueryDataDescription, QueryDataSet};
+
 /// Implemented by messages
 pub trait MessageInfo {
     /// The object id associated with the message
@@ -54,8 +58,10 @@ impl UserTokenPolicy {
 }
 
 impl DataChangeFilter {
-    /// Compares one data value to another and returns true if they differ, according to their trigger
-    /// type of status, status/value or status/value/timestamp
+    /// Compares one data value to another and returns true if they are considered the same,
+    /// according to their trigger type of status, status/value or status/value/timestamp. The
+    /// timestamp compared is the source timestamp, as Part 4 defines for the
+    /// StatusValueTimestamp trigger. Earlier versions compared the server timestamp.
     pub fn compare(&self, v1: &DataValue, v2: &DataValue, eu_range: Option<(f64, f64)>) -> bool {
         match self.trigger {
             DataChangeTrigger::Status => v1.status == v2.status,
@@ -65,7 +71,7 @@ impl DataChangeFilter {
             DataChangeTrigger::StatusValueTimestamp => {
                 v1.status == v2.status
                     && self.compare_value_option(&v1.value, &v2.value, eu_range)
-                    && v1.server_timestamp == v2.server_timestamp
+                    && v1.source_timestamp == v2.source_timestamp
             }
         }
     }
@@ -93,7 +99,8 @@ impl DataChangeFilter {
     /// Compares two values, either a straight value compare or a numeric comparison against the
     /// deadband settings. If deadband is asked for and the values are not convertible into a numeric
     /// value, the result is false. The value is true if the values are the same within the limits
-    /// set.
+    /// set. Arrays are the same if they have the same length and every element is the same within
+    /// the limits set.
     ///
     /// The eu_range is the engineering unit range and represents the range that the value should
     /// typically operate between. It's used for percentage change operations and ignored otherwise.
@@ -108,42 +115,61 @@ impl DataChangeFilter {
         v2: &Variant,
         eu_range: Option<(f64, f64)>,
     ) -> std::result::Result<bool, StatusCode> {
-        // TODO be able to compare arrays of numbers
         if self.deadband_type == DeadbandType::None as u32 {
             // Straight comparison of values
             Ok(v1 == v2)
         } else {
-            // Absolute
-            match (v1.as_f64(), v2.as_f64()) {
-                (None, _) | (_, None) => Ok(false),
-                (Some(v1), Some(v2)) => {
-                    if self.deadband_value < 0f64 {
-                        Err(StatusCode::BadDeadbandFilterInvalid)
-                    } else if self.deadband_type == DeadbandType::Absolute as u32 {
-                        Ok(DataChangeFilter::abs_compare(v1, v2, self.deadband_value))
-                    } else if self.deadband_type == DeadbandType::Percent as u32 {
-                        match eu_range {
-                            None => Err(StatusCode::BadDeadbandFilterInvalid),
-                            Some((low, high)) => {
-                                if low >= high {
-                                    Err(StatusCode::BadDeadbandFilterInvalid)
-                                } else {
-                                    Ok(DataChangeFilter::pct_compare(
-                                        v1,
-                                        v2,
-                                        low,
-                                        high,
-                                        self.deadband_value,
-                                    ))
-                                }
-                            }
+            match (v1, v2) {
+                (Variant::Array(a1), Variant::Array(a2)) => {
+                    if a1.values.len() != a2.values.len() {
+                        return Ok(false);
+                    }
+                    for (v1, v2) in a1.values.iter().zip(a2.values.iter()) {
+                        if !self.compare_value(v1, v2, eu_range)? {
+                            return Ok(false);
                         }
-                    } else {
-                        // Type is not recognized
+                    }
+                    Ok(true)
+                }
+                (v1, v2) => match (v1.as_f64(), v2.as_f64()) {
+                    (None, _) | (_, None) => Ok(false),
+                    (Some(v1), Some(v2)) => self.compare_deadband(v1, v2, eu_range),
+                },
+            }
+        }
+    }
+
+    /// Compares two numbers against the deadband settings.
+    fn compare_deadband(
+        &self,
+        v1: f64,
+        v2: f64,
+        eu_range: Option<(f64, f64)>,
+    ) -> std::result::Result<bool, StatusCode> {
+        if self.deadband_value < 0f64 {
+            Err(StatusCode::BadDeadbandFilterInvalid)
+        } else if self.deadband_type == DeadbandType::Absolute as u32 {
+            Ok(DataChangeFilter::abs_compare(v1, v2, self.deadband_value))
+        } else if self.deadband_type == DeadbandType::Percent as u32 {
+            match eu_range {
+                None => Err(StatusCode::BadDeadbandFilterInvalid),
+                Some((low, high)) => {
+                    if low >= high {
                         Err(StatusCode::BadDeadbandFilterInvalid)
+                    } else {
+                        Ok(DataChangeFilter::pct_compare(
+                            v1,
+                            v2,
+                            low,
+                            high,
+                            self.deadband_value,
+                        ))
                     }
                 }
             }
+        } else {
+            // Type is not recognized
+            Err(StatusCode::BadDeadbandFilterInvalid)
         }
     }
 
@@ -309,6 +335,114 @@ impl MonitoredItemCreateRequest {
     }
 }
 
+#[cfg(feature = "query-services")]
+impl NodeTypeDescription {
+    /// Describes the nodes of a type, and optionally its subtypes, for a query. Add the data to
+    /// return for each node with `data_to_return()`.
+    pub fn new<T>(type_definition_node: T, include_sub_types: bool) -> NodeTypeDescription
+    where
+        T: Into<ExpandedNodeId>,
+    {
+        NodeTypeDescription {
+            type_definition_node: type_definition_node.into(),
+            include_sub_types,
+            data_to_return: None,
+        }
+    }
+
+    /// Adds data to return for each node. The values of a `QueryDataSet` are in the order that
+    /// data is added.
+    pub fn data_to_return(mut self, data_to_return: QueryDataDescription) -> Self {
+        self.data_to_return
+            .get_or_insert_with(Vec::new)
+            .push(data_to_return);
+        self
+    }
+}
+
+#[cfg(feature = "query-services")]
+impl QueryDataDescription {
+    /// Describes an attribute to return for a node found by a query, or for a node at the relative
+    /// path from it. An empty path is the node itself.
+    pub fn new<T>(relative_path: T, attribute_id: AttributeId) -> QueryDataDescription
+    where
+        T: Into<RelativePath>,
+    {
+        QueryDataDescription {
+            relative_path: relative_path.into(),
+            attribute_id: attribute_id as u32,
+            index_range: UAString::null(),
+        }
+    }
+
+    /// Describes the value of the variable at the browse path from a node found by a query
+    pub fn value(browse_path: &[QualifiedName]) -> QueryDataDescription {
+        Self::new(browse_path, AttributeId::Value)
+    }
+
+    /// Sets the index range of an array value to return
+    pub fn index_range<T>(mut self, index_range: T) -> Self
+    where
+        T: Into<UAString>,
+    {
+        self.index_range = index_range.into();
+        self
+    }
+}
+
+#[cfg(feature = "query-services")]
+impl QueryDataSet {
+    /// Finds the node type description that this data set is a result of. A description of the
+    /// exact type definition of the node is preferred, otherwise the first description including
+    /// subtypes that returns as many values as the data set holds.
+    pub fn node_type<'a>(
+        &self,
+        node_types: &'a [NodeTypeDescription],
+    ) -> Option<&'a NodeTypeDescription> {
+        let values_len = self.values.as_ref().map_or(0, |v| v.len());
+        node_types
+            .iter()
+            .find(|n| n.type_definition_node == self.type_definition_node)
+            .or_else(|| {
+                node_types.iter().find(|n| {
+                    n.include_sub_types
+                        && n.data_to_return.as_ref().map_or(0, |d| d.len()) == values_len
+                })
+            })
+    }
+
+    /// Pairs the values of the data set with the data descriptions they were returned for
+    pub fn data<'a>(
+        &'a self,
+        node_types: &'a [NodeTypeDescription],
+    ) -> Vec<(&'a QueryDataDescription, &'a Variant)> {
+        match (self.node_type(node_types), &self.values) {
+            (Some(node_type), Some(values)) => node_type
+                .data_to_return
+                .iter()
+                .flatten()
+                .zip(values.iter())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Finds the value returned for a data description
+    pub fn value(
+        &self,
+        node_types: &[NodeTypeDescription],
+        data_description: &QueryDataDescription,
+    ) -> Option<&Variant> {
+        let index = self
+            .node_type(node_types)?
+            .data_to_return
+            .as_ref()?
+            .iter()
+            .position(|d| d == data_description)?;
+        self.values.as_ref()?.get(index)
+    }
+}
+
 impl Default for ApplicationDescription {
     fn default() -> Self {
         Self {
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, byte_string::ByteString, encoding::*, string::UAString};
+use crate::types::{
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, string::UAString, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -47,3 +49,55 @@ impl BinaryEncoder<IssuedIdentityToken> for IssuedIdentityToken {
         })
     }
 }
+
+impl XmlEncoder for IssuedIdentityToken {
+    fn xml_type_name() -> String {
+        "IssuedIdentityToken".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PolicyId", &self.policy_id);
+        write_xml_field(&mut element, "TokenData", &self.token_data);
+        write_xml_field(
+            &mut element,
+            "EncryptionAlgorithm",
+            &self.encryption_algorithm,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(IssuedIdentityToken {
+            policy_id: read_xml_field(element, "PolicyId", decoding_options)?,
+            token_data: read_xml_field(element, "TokenData", decoding_options)?,
+            encryption_algorithm: read_xml_field(element, "EncryptionAlgorithm", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for IssuedIdentityToken {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, encoding);
+        write_json_field(&mut object, "TokenData", &self.token_data, encoding);
+        write_json_field(
+            &mut object,
+            "EncryptionAlgorithm",
+            &self.encryption_algorithm,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(IssuedIdentityToken {
+            policy_id: read_json_field(value, "PolicyId", decoding_options)?,
+            token_data: read_json_field(value, "TokenData", decoding_options)?,
+            encryption_algorithm: read_json_field(value, "EncryptionAlgorithm", decoding_options)?,
+        })
+    }
+}
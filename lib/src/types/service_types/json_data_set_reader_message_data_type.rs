@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::enums::JsonDataSetMessageContentMask,
-    service_types::enums::JsonNetworkMessageContentMask,
+    basic_types::*, encoding::*, json::*, service_types::enums::JsonDataSetMessageContentMask,
+    service_types::enums::JsonNetworkMessageContentMask, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -47,3 +47,76 @@ impl BinaryEncoder<JsonDataSetReaderMessageDataType> for JsonDataSetReaderMessag
         })
     }
 }
+
+impl XmlEncoder for JsonDataSetReaderMessageDataType {
+    fn xml_type_name() -> String {
+        "JsonDataSetReaderMessageDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+        );
+        write_xml_field(
+            &mut element,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonDataSetReaderMessageDataType {
+            network_message_content_mask: read_xml_field(
+                element,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            data_set_message_content_mask: read_xml_field(
+                element,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for JsonDataSetReaderMessageDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(JsonDataSetReaderMessageDataType {
+            network_message_content_mask: read_json_field(
+                value,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            data_set_message_content_mask: read_json_field(
+                value,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::enums::JsonNetworkMessageContentMask,
+    basic_types::*, encoding::*, json::*, service_types::enums::JsonNetworkMessageContentMask,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -40,3 +41,55 @@ impl BinaryEncoder<JsonWriterGroupMessageDataType> for JsonWriterGroupMessageDat
         })
     }
 }
+
+impl XmlEncoder for JsonWriterGroupMessageDataType {
+    fn xml_type_name() -> String {
+        "JsonWriterGroupMessageDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(JsonWriterGroupMessageDataType {
+            network_message_content_mask: read_xml_field(
+                element,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for JsonWriterGroupMessageDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(JsonWriterGroupMessageDataType {
+            network_message_content_mask: read_json_field(
+                value,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, qualified_name::QualifiedName,
-    service_types::impls::MessageInfo, variant::Variant,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, qualified_name::QualifiedName,
+    service_types::impls::MessageInfo, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -49,3 +49,42 @@ impl BinaryEncoder<KeyValuePair> for KeyValuePair {
         Ok(KeyValuePair { key, value })
     }
 }
+
+impl XmlEncoder for KeyValuePair {
+    fn xml_type_name() -> String {
+        "KeyValuePair".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Key", &self.key);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(KeyValuePair {
+            key: read_xml_field(element, "Key", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for KeyValuePair {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Key", &self.key, encoding);
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(KeyValuePair {
+            key: read_json_field(value, "Key", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
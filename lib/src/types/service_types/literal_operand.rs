@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, variant::Variant};
+use crate::types::{basic_types::*, encoding::*, json::*, variant::Variant, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +35,38 @@ impl BinaryEncoder<LiteralOperand> for LiteralOperand {
         Ok(LiteralOperand { value })
     }
 }
+
+impl XmlEncoder for LiteralOperand {
+    fn xml_type_name() -> String {
+        "LiteralOperand".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(LiteralOperand {
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for LiteralOperand {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(LiteralOperand {
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
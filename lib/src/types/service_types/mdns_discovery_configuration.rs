@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, string::UAString};
+use crate::types::{basic_types::*, encoding::*, json::*, string::UAString, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,56 @@ impl BinaryEncoder<MdnsDiscoveryConfiguration> for MdnsDiscoveryConfiguration {
         })
     }
 }
+
+impl XmlEncoder for MdnsDiscoveryConfiguration {
+    fn xml_type_name() -> String {
+        "MdnsDiscoveryConfiguration".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "MdnsServerName", &self.mdns_server_name);
+        write_xml_field(
+            &mut element,
+            "ServerCapabilities",
+            &self.server_capabilities,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MdnsDiscoveryConfiguration {
+            mdns_server_name: read_xml_field(element, "MdnsServerName", decoding_options)?,
+            server_capabilities: read_xml_field(element, "ServerCapabilities", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MdnsDiscoveryConfiguration {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "MdnsServerName",
+            &self.mdns_server_name,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerCapabilities",
+            &self.server_capabilities,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MdnsDiscoveryConfiguration {
+            mdns_server_name: read_json_field(value, "MdnsServerName", decoding_options)?,
+            server_capabilities: read_json_field(value, "ServerCapabilities", decoding_options)?,
+        })
+    }
+}
@@ -14,12 +14,19 @@ pub use self::impls::*;
 // All of the remaining are generated by script
 mod activate_session_request;
 mod activate_session_response;
+#[cfg(feature = "node-management-services")]
 mod add_nodes_item;
+#[cfg(feature = "node-management-services")]
 mod add_nodes_request;
+#[cfg(feature = "node-management-services")]
 mod add_nodes_response;
+#[cfg(feature = "node-management-services")]
 mod add_nodes_result;
+#[cfg(feature = "node-management-services")]
 mod add_references_item;
+#[cfg(feature = "node-management-services")]
 mod add_references_request;
+#[cfg(feature = "node-management-services")]
 mod add_references_response;
 mod additional_parameters_type;
 mod aggregate_configuration;
@@ -91,12 +98,18 @@ mod delete_at_time_details;
 mod delete_event_details;
 mod delete_monitored_items_request;
 mod delete_monitored_items_response;
+#[cfg(feature = "node-management-services")]
 mod delete_nodes_item;
+#[cfg(feature = "node-management-services")]
 mod delete_nodes_request;
+#[cfg(feature = "node-management-services")]
 mod delete_nodes_response;
 mod delete_raw_modified_details;
+#[cfg(feature = "node-management-services")]
 mod delete_references_item;
+#[cfg(feature = "node-management-services")]
 mod delete_references_request;
+#[cfg(feature = "node-management-services")]
 mod delete_references_response;
 mod delete_subscriptions_request;
 mod delete_subscriptions_response;
@@ -170,6 +183,7 @@ mod network_address_url_data_type;
 mod network_group_data_type;
 mod node_attributes;
 mod node_reference;
+#[cfg(feature = "query-services")]
 mod node_type_description;
 mod notification_data;
 mod notification_message;
@@ -179,6 +193,7 @@ mod open_secure_channel_request;
 mod open_secure_channel_response;
 mod option_set;
 mod orientation;
+#[cfg(feature = "query-services")]
 mod parsing_result;
 mod program_diagnostic_2_data_type;
 mod program_diagnostic_data_type;
@@ -192,11 +207,17 @@ mod published_data_set_data_type;
 mod published_data_set_source_data_type;
 mod published_events_data_type;
 mod published_variable_data_type;
+#[cfg(feature = "query-services")]
 mod query_data_description;
+#[cfg(feature = "query-services")]
 mod query_data_set;
+#[cfg(feature = "query-services")]
 mod query_first_request;
+#[cfg(feature = "query-services")]
 mod query_first_response;
+#[cfg(feature = "query-services")]
 mod query_next_request;
+#[cfg(feature = "query-services")]
 mod query_next_response;
 mod range;
 mod rational_number;
@@ -267,6 +288,7 @@ mod transfer_subscriptions_request;
 mod transfer_subscriptions_response;
 mod translate_browse_paths_to_node_ids_request;
 mod translate_browse_paths_to_node_ids_response;
+#[cfg(feature = "gds-types")]
 mod trust_list_data_type;
 mod ua_binary_file_data_type;
 mod uadp_data_set_reader_message_data_type;
@@ -296,12 +318,19 @@ mod xv_type;
 
 pub use self::activate_session_request::*;
 pub use self::activate_session_response::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_nodes_item::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_nodes_request::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_nodes_response::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_nodes_result::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_references_item::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_references_request::*;
+#[cfg(feature = "node-management-services")]
 pub use self::add_references_response::*;
 pub use self::additional_parameters_type::*;
 pub use self::aggregate_configuration::*;
@@ -373,12 +402,18 @@ pub use self::delete_at_time_details::*;
 pub use self::delete_event_details::*;
 pub use self::delete_monitored_items_request::*;
 pub use self::delete_monitored_items_response::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_nodes_item::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_nodes_request::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_nodes_response::*;
 pub use self::delete_raw_modified_details::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_references_item::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_references_request::*;
+#[cfg(feature = "node-management-services")]
 pub use self::delete_references_response::*;
 pub use self::delete_subscriptions_request::*;
 pub use self::delete_subscriptions_response::*;
@@ -452,6 +487,7 @@ pub use self::network_address_url_data_type::*;
 pub use self::network_group_data_type::*;
 pub use self::node_attributes::*;
 pub use self::node_reference::*;
+#[cfg(feature = "query-services")]
 pub use self::node_type_description::*;
 pub use self::notification_data::*;
 pub use self::notification_message::*;
@@ -461,6 +497,7 @@ pub use self::open_secure_channel_request::*;
 pub use self::open_secure_channel_response::*;
 pub use self::option_set::*;
 pub use self::orientation::*;
+#[cfg(feature = "query-services")]
 pub use self::parsing_result::*;
 pub use self::program_diagnostic_2_data_type::*;
 pub use self::program_diagnostic_data_type::*;
@@ -474,11 +511,17 @@ pub use self::published_data_set_data_type::*;
 pub use self::published_data_set_source_data_type::*;
 pub use self::published_events_data_type::*;
 pub use self::published_variable_data_type::*;
+#[cfg(feature = "query-services")]
 pub use self::query_data_description::*;
+#[cfg(feature = "query-services")]
 pub use self::query_data_set::*;
+#[cfg(feature = "query-services")]
 pub use self::query_first_request::*;
+#[cfg(feature = "query-services")]
 pub use self::query_first_response::*;
+#[cfg(feature = "query-services")]
 pub use self::query_next_request::*;
+#[cfg(feature = "query-services")]
 pub use self::query_next_response::*;
 pub use self::range::*;
 pub use self::rational_number::*;
@@ -549,6 +592,7 @@ pub use self::transfer_subscriptions_request::*;
 pub use self::transfer_subscriptions_response::*;
 pub use self::translate_browse_paths_to_node_ids_request::*;
 pub use self::translate_browse_paths_to_node_ids_response::*;
+#[cfg(feature = "gds-types")]
 pub use self::trust_list_data_type::*;
 pub use self::ua_binary_file_data_type::*;
 pub use self::uadp_data_set_reader_message_data_type::*;
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<ModelChangeStructureDataType> for ModelChangeStructureDataTyp
         })
     }
 }
+
+impl XmlEncoder for ModelChangeStructureDataType {
+    fn xml_type_name() -> String {
+        "ModelChangeStructureDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Affected", &self.affected);
+        write_xml_field(&mut element, "AffectedType", &self.affected_type);
+        write_xml_field(&mut element, "Verb", &self.verb);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ModelChangeStructureDataType {
+            affected: read_xml_field(element, "Affected", decoding_options)?,
+            affected_type: read_xml_field(element, "AffectedType", decoding_options)?,
+            verb: read_xml_field(element, "Verb", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ModelChangeStructureDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Affected", &self.affected, encoding);
+        write_json_field(&mut object, "AffectedType", &self.affected_type, encoding);
+        write_json_field(&mut object, "Verb", &self.verb, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ModelChangeStructureDataType {
+            affected: read_json_field(value, "Affected", decoding_options)?,
+            affected_type: read_json_field(value, "AffectedType", decoding_options)?,
+            verb: read_json_field(value, "Verb", decoding_options)?,
+        })
+    }
+}
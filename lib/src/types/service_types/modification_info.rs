@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_ids::ObjectId,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_ids::ObjectId,
     service_types::enums::HistoryUpdateType, service_types::impls::MessageInfo, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +57,51 @@ impl BinaryEncoder<ModificationInfo> for ModificationInfo {
         })
     }
 }
+
+impl XmlEncoder for ModificationInfo {
+    fn xml_type_name() -> String {
+        "ModificationInfo".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ModificationTime", &self.modification_time);
+        write_xml_field(&mut element, "UpdateType", &self.update_type);
+        write_xml_field(&mut element, "UserName", &self.user_name);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ModificationInfo {
+            modification_time: read_xml_field(element, "ModificationTime", decoding_options)?,
+            update_type: read_xml_field(element, "UpdateType", decoding_options)?,
+            user_name: read_xml_field(element, "UserName", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ModificationInfo {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ModificationTime",
+            &self.modification_time,
+            encoding,
+        );
+        write_json_field(&mut object, "UpdateType", &self.update_type, encoding);
+        write_json_field(&mut object, "UserName", &self.user_name, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ModificationInfo {
+            modification_time: read_json_field(value, "ModificationTime", decoding_options)?,
+            update_type: read_json_field(value, "UpdateType", decoding_options)?,
+            user_name: read_json_field(value, "UserName", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
     service_types::enums::TimestampsToReturn, service_types::impls::MessageInfo,
-    service_types::MonitoredItemModifyRequest,
+    service_types::MonitoredItemModifyRequest, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -63,3 +63,69 @@ impl BinaryEncoder<ModifyMonitoredItemsRequest> for ModifyMonitoredItemsRequest
         })
     }
 }
+
+impl XmlEncoder for ModifyMonitoredItemsRequest {
+    fn xml_type_name() -> String {
+        "ModifyMonitoredItemsRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(
+            &mut element,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+        );
+        write_xml_field(&mut element, "ItemsToModify", &self.items_to_modify);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ModifyMonitoredItemsRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            timestamps_to_return: read_xml_field(element, "TimestampsToReturn", decoding_options)?,
+            items_to_modify: read_xml_field(element, "ItemsToModify", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ModifyMonitoredItemsRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ItemsToModify",
+            &self.items_to_modify,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ModifyMonitoredItemsRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            timestamps_to_return: read_json_field(value, "TimestampsToReturn", decoding_options)?,
+            items_to_modify: read_json_field(value, "ItemsToModify", decoding_options)?,
+        })
+    }
+}
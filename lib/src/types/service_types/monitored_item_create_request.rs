@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::enums::MonitoringMode,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::enums::MonitoringMode,
     service_types::impls::MessageInfo, service_types::MonitoringParameters,
-    service_types::ReadValueId,
+    service_types::ReadValueId, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,65 @@ impl BinaryEncoder<MonitoredItemCreateRequest> for MonitoredItemCreateRequest {
         })
     }
 }
+
+impl XmlEncoder for MonitoredItemCreateRequest {
+    fn xml_type_name() -> String {
+        "MonitoredItemCreateRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ItemToMonitor", &self.item_to_monitor);
+        write_xml_field(&mut element, "MonitoringMode", &self.monitoring_mode);
+        write_xml_field(
+            &mut element,
+            "RequestedParameters",
+            &self.requested_parameters,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MonitoredItemCreateRequest {
+            item_to_monitor: read_xml_field(element, "ItemToMonitor", decoding_options)?,
+            monitoring_mode: read_xml_field(element, "MonitoringMode", decoding_options)?,
+            requested_parameters: read_xml_field(element, "RequestedParameters", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MonitoredItemCreateRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ItemToMonitor",
+            &self.item_to_monitor,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoringMode",
+            &self.monitoring_mode,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedParameters",
+            &self.requested_parameters,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MonitoredItemCreateRequest {
+            item_to_monitor: read_json_field(value, "ItemToMonitor", decoding_options)?,
+            monitoring_mode: read_json_field(value, "MonitoringMode", decoding_options)?,
+            requested_parameters: read_json_field(value, "RequestedParameters", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::MonitoringParameters,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::MonitoringParameters, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,56 @@ impl BinaryEncoder<MonitoredItemModifyRequest> for MonitoredItemModifyRequest {
         })
     }
 }
+
+impl XmlEncoder for MonitoredItemModifyRequest {
+    fn xml_type_name() -> String {
+        "MonitoredItemModifyRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "MonitoredItemId", &self.monitored_item_id);
+        write_xml_field(
+            &mut element,
+            "RequestedParameters",
+            &self.requested_parameters,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MonitoredItemModifyRequest {
+            monitored_item_id: read_xml_field(element, "MonitoredItemId", decoding_options)?,
+            requested_parameters: read_xml_field(element, "RequestedParameters", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MonitoredItemModifyRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "MonitoredItemId",
+            &self.monitored_item_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RequestedParameters",
+            &self.requested_parameters,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MonitoredItemModifyRequest {
+            monitored_item_id: read_json_field(value, "MonitoredItemId", decoding_options)?,
+            requested_parameters: read_json_field(value, "RequestedParameters", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,72 @@ impl BinaryEncoder<MonitoredItemModifyResult> for MonitoredItemModifyResult {
         })
     }
 }
+
+impl XmlEncoder for MonitoredItemModifyResult {
+    fn xml_type_name() -> String {
+        "MonitoredItemModifyResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(
+            &mut element,
+            "RevisedSamplingInterval",
+            &self.revised_sampling_interval,
+        );
+        write_xml_field(&mut element, "RevisedQueueSize", &self.revised_queue_size);
+        write_xml_field(&mut element, "FilterResult", &self.filter_result);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MonitoredItemModifyResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            revised_sampling_interval: read_xml_field(
+                element,
+                "RevisedSamplingInterval",
+                decoding_options,
+            )?,
+            revised_queue_size: read_xml_field(element, "RevisedQueueSize", decoding_options)?,
+            filter_result: read_xml_field(element, "FilterResult", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MonitoredItemModifyResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "RevisedSamplingInterval",
+            &self.revised_sampling_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedQueueSize",
+            &self.revised_queue_size,
+            encoding,
+        );
+        write_json_field(&mut object, "FilterResult", &self.filter_result, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MonitoredItemModifyResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            revised_sampling_interval: read_json_field(
+                value,
+                "RevisedSamplingInterval",
+                decoding_options,
+            )?,
+            revised_queue_size: read_json_field(value, "RevisedQueueSize", decoding_options)?,
+            filter_result: read_json_field(value, "FilterResult", decoding_options)?,
+        })
+    }
+}
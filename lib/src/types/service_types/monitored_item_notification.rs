@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, data_value::DataValue, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, data_value::DataValue, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,42 @@ impl BinaryEncoder<MonitoredItemNotification> for MonitoredItemNotification {
         })
     }
 }
+
+impl XmlEncoder for MonitoredItemNotification {
+    fn xml_type_name() -> String {
+        "MonitoredItemNotification".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ClientHandle", &self.client_handle);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MonitoredItemNotification {
+            client_handle: read_xml_field(element, "ClientHandle", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MonitoredItemNotification {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ClientHandle", &self.client_handle, encoding);
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MonitoredItemNotification {
+            client_handle: read_json_field(value, "ClientHandle", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
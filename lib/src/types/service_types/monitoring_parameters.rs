@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,59 @@ impl BinaryEncoder<MonitoringParameters> for MonitoringParameters {
         })
     }
 }
+
+impl XmlEncoder for MonitoringParameters {
+    fn xml_type_name() -> String {
+        "MonitoringParameters".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ClientHandle", &self.client_handle);
+        write_xml_field(&mut element, "SamplingInterval", &self.sampling_interval);
+        write_xml_field(&mut element, "Filter", &self.filter);
+        write_xml_field(&mut element, "QueueSize", &self.queue_size);
+        write_xml_field(&mut element, "DiscardOldest", &self.discard_oldest);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(MonitoringParameters {
+            client_handle: read_xml_field(element, "ClientHandle", decoding_options)?,
+            sampling_interval: read_xml_field(element, "SamplingInterval", decoding_options)?,
+            filter: read_xml_field(element, "Filter", decoding_options)?,
+            queue_size: read_xml_field(element, "QueueSize", decoding_options)?,
+            discard_oldest: read_xml_field(element, "DiscardOldest", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for MonitoringParameters {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ClientHandle", &self.client_handle, encoding);
+        write_json_field(
+            &mut object,
+            "SamplingInterval",
+            &self.sampling_interval,
+            encoding,
+        );
+        write_json_field(&mut object, "Filter", &self.filter, encoding);
+        write_json_field(&mut object, "QueueSize", &self.queue_size, encoding);
+        write_json_field(&mut object, "DiscardOldest", &self.discard_oldest, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(MonitoringParameters {
+            client_handle: read_json_field(value, "ClientHandle", decoding_options)?,
+            sampling_interval: read_json_field(value, "SamplingInterval", decoding_options)?,
+            filter: read_json_field(value, "Filter", decoding_options)?,
+            queue_size: read_json_field(value, "QueueSize", decoding_options)?,
+            discard_oldest: read_json_field(value, "DiscardOldest", decoding_options)?,
+        })
+    }
+}
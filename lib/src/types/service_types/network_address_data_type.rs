@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +44,43 @@ impl BinaryEncoder<NetworkAddressDataType> for NetworkAddressDataType {
         Ok(NetworkAddressDataType { network_interface })
     }
 }
+
+impl XmlEncoder for NetworkAddressDataType {
+    fn xml_type_name() -> String {
+        "NetworkAddressDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NetworkInterface", &self.network_interface);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NetworkAddressDataType {
+            network_interface: read_xml_field(element, "NetworkInterface", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NetworkAddressDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "NetworkInterface",
+            &self.network_interface,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NetworkAddressDataType {
+            network_interface: read_json_field(value, "NetworkInterface", decoding_options)?,
+        })
+    }
+}
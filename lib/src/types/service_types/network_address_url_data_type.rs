@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, string::UAString};
+use crate::types::{basic_types::*, encoding::*, json::*, string::UAString, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,47 @@ impl BinaryEncoder<NetworkAddressUrlDataType> for NetworkAddressUrlDataType {
         })
     }
 }
+
+impl XmlEncoder for NetworkAddressUrlDataType {
+    fn xml_type_name() -> String {
+        "NetworkAddressUrlDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NetworkInterface", &self.network_interface);
+        write_xml_field(&mut element, "Url", &self.url);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NetworkAddressUrlDataType {
+            network_interface: read_xml_field(element, "NetworkInterface", decoding_options)?,
+            url: read_xml_field(element, "Url", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NetworkAddressUrlDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "NetworkInterface",
+            &self.network_interface,
+            encoding,
+        );
+        write_json_field(&mut object, "Url", &self.url, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NetworkAddressUrlDataType {
+            network_interface: read_json_field(value, "NetworkInterface", decoding_options)?,
+            url: read_json_field(value, "Url", decoding_options)?,
+        })
+    }
+}
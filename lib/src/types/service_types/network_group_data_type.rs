@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::EndpointUrlListDataType, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::EndpointUrlListDataType, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,42 @@ impl BinaryEncoder<NetworkGroupDataType> for NetworkGroupDataType {
         })
     }
 }
+
+impl XmlEncoder for NetworkGroupDataType {
+    fn xml_type_name() -> String {
+        "NetworkGroupDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ServerUri", &self.server_uri);
+        write_xml_field(&mut element, "NetworkPaths", &self.network_paths);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NetworkGroupDataType {
+            server_uri: read_xml_field(element, "ServerUri", decoding_options)?,
+            network_paths: read_xml_field(element, "NetworkPaths", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NetworkGroupDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerUri", &self.server_uri, encoding);
+        write_json_field(&mut object, "NetworkPaths", &self.network_paths, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NetworkGroupDataType {
+            server_uri: read_json_field(value, "ServerUri", decoding_options)?,
+            network_paths: read_json_field(value, "NetworkPaths", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,60 @@ impl BinaryEncoder<NodeReference> for NodeReference {
         })
     }
 }
+
+impl XmlEncoder for NodeReference {
+    fn xml_type_name() -> String {
+        "NodeReference".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(&mut element, "IsForward", &self.is_forward);
+        write_xml_field(&mut element, "ReferencedNodeIds", &self.referenced_node_ids);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NodeReference {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_xml_field(element, "IsForward", decoding_options)?,
+            referenced_node_ids: read_xml_field(element, "ReferencedNodeIds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NodeReference {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(&mut object, "IsForward", &self.is_forward, encoding);
+        write_json_field(
+            &mut object,
+            "ReferencedNodeIds",
+            &self.referenced_node_ids,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NodeReference {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_json_field(value, "IsForward", decoding_options)?,
+            referenced_node_ids: read_json_field(value, "ReferencedNodeIds", decoding_options)?,
+        })
+    }
+}
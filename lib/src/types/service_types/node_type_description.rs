@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, service_types::QueryDataDescription,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, service_types::QueryDataDescription, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,60 @@ impl BinaryEncoder<NodeTypeDescription> for NodeTypeDescription {
         })
     }
 }
+
+impl XmlEncoder for NodeTypeDescription {
+    fn xml_type_name() -> String {
+        "NodeTypeDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "TypeDefinitionNode",
+            &self.type_definition_node,
+        );
+        write_xml_field(&mut element, "IncludeSubTypes", &self.include_sub_types);
+        write_xml_field(&mut element, "DataToReturn", &self.data_to_return);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NodeTypeDescription {
+            type_definition_node: read_xml_field(element, "TypeDefinitionNode", decoding_options)?,
+            include_sub_types: read_xml_field(element, "IncludeSubTypes", decoding_options)?,
+            data_to_return: read_xml_field(element, "DataToReturn", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NodeTypeDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "TypeDefinitionNode",
+            &self.type_definition_node,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "IncludeSubTypes",
+            &self.include_sub_types,
+            encoding,
+        );
+        write_json_field(&mut object, "DataToReturn", &self.data_to_return, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NodeTypeDescription {
+            type_definition_node: read_json_field(value, "TypeDefinitionNode", decoding_options)?,
+            include_sub_types: read_json_field(value, "IncludeSubTypes", decoding_options)?,
+            data_to_return: read_json_field(value, "DataToReturn", decoding_options)?,
+        })
+    }
+}
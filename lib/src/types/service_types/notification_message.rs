@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, extension_object::ExtensionObject,
-    node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, date_time::DateTime, encoding::*, extension_object::ExtensionObject, json::*,
+    node_ids::ObjectId, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,56 @@ impl BinaryEncoder<NotificationMessage> for NotificationMessage {
         })
     }
 }
+
+impl XmlEncoder for NotificationMessage {
+    fn xml_type_name() -> String {
+        "NotificationMessage".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SequenceNumber", &self.sequence_number);
+        write_xml_field(&mut element, "PublishTime", &self.publish_time);
+        write_xml_field(&mut element, "NotificationData", &self.notification_data);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(NotificationMessage {
+            sequence_number: read_xml_field(element, "SequenceNumber", decoding_options)?,
+            publish_time: read_xml_field(element, "PublishTime", decoding_options)?,
+            notification_data: read_xml_field(element, "NotificationData", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for NotificationMessage {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SequenceNumber",
+            &self.sequence_number,
+            encoding,
+        );
+        write_json_field(&mut object, "PublishTime", &self.publish_time, encoding);
+        write_json_field(
+            &mut object,
+            "NotificationData",
+            &self.notification_data,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(NotificationMessage {
+            sequence_number: read_json_field(value, "SequenceNumber", decoding_options)?,
+            publish_time: read_json_field(value, "PublishTime", decoding_options)?,
+            notification_data: read_json_field(value, "NotificationData", decoding_options)?,
+        })
+    }
+}
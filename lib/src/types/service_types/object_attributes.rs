@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, localized_text::LocalizedText};
+use crate::types::{basic_types::*, encoding::*, json::*, localized_text::LocalizedText, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -62,3 +62,72 @@ impl BinaryEncoder<ObjectAttributes> for ObjectAttributes {
         })
     }
 }
+
+impl XmlEncoder for ObjectAttributes {
+    fn xml_type_name() -> String {
+        "ObjectAttributes".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+        );
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "WriteMask", &self.write_mask);
+        write_xml_field(&mut element, "UserWriteMask", &self.user_write_mask);
+        write_xml_field(&mut element, "EventNotifier", &self.event_notifier);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ObjectAttributes {
+            specified_attributes: read_xml_field(element, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            write_mask: read_xml_field(element, "WriteMask", decoding_options)?,
+            user_write_mask: read_xml_field(element, "UserWriteMask", decoding_options)?,
+            event_notifier: read_xml_field(element, "EventNotifier", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ObjectAttributes {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+            encoding,
+        );
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, encoding);
+        write_json_field(
+            &mut object,
+            "UserWriteMask",
+            &self.user_write_mask,
+            encoding,
+        );
+        write_json_field(&mut object, "EventNotifier", &self.event_notifier, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ObjectAttributes {
+            specified_attributes: read_json_field(value, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            write_mask: read_json_field(value, "WriteMask", decoding_options)?,
+            user_write_mask: read_json_field(value, "UserWriteMask", decoding_options)?,
+            event_notifier: read_json_field(value, "EventNotifier", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     request_header::RequestHeader, service_types::enums::MessageSecurityMode,
-    service_types::enums::SecurityTokenRequestType, service_types::impls::MessageInfo,
+    service_types::enums::SecurityTokenRequestType, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -72,3 +72,80 @@ impl BinaryEncoder<OpenSecureChannelRequest> for OpenSecureChannelRequest {
         })
     }
 }
+
+impl XmlEncoder for OpenSecureChannelRequest {
+    fn xml_type_name() -> String {
+        "OpenSecureChannelRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "ClientProtocolVersion",
+            &self.client_protocol_version,
+        );
+        write_xml_field(&mut element, "RequestType", &self.request_type);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "ClientNonce", &self.client_nonce);
+        write_xml_field(&mut element, "RequestedLifetime", &self.requested_lifetime);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(OpenSecureChannelRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            client_protocol_version: read_xml_field(
+                element,
+                "ClientProtocolVersion",
+                decoding_options,
+            )?,
+            request_type: read_xml_field(element, "RequestType", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            client_nonce: read_xml_field(element, "ClientNonce", decoding_options)?,
+            requested_lifetime: read_xml_field(element, "RequestedLifetime", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for OpenSecureChannelRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ClientProtocolVersion",
+            &self.client_protocol_version,
+            encoding,
+        );
+        write_json_field(&mut object, "RequestType", &self.request_type, encoding);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(&mut object, "ClientNonce", &self.client_nonce, encoding);
+        write_json_field(
+            &mut object,
+            "RequestedLifetime",
+            &self.requested_lifetime,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(OpenSecureChannelRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            client_protocol_version: read_json_field(
+                value,
+                "ClientProtocolVersion",
+                decoding_options,
+            )?,
+            request_type: read_json_field(value, "RequestType", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            client_nonce: read_json_field(value, "ClientNonce", decoding_options)?,
+            requested_lifetime: read_json_field(value, "RequestedLifetime", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo,
-    service_types::ChannelSecurityToken,
+    service_types::ChannelSecurityToken, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -62,3 +62,72 @@ impl BinaryEncoder<OpenSecureChannelResponse> for OpenSecureChannelResponse {
         })
     }
 }
+
+impl XmlEncoder for OpenSecureChannelResponse {
+    fn xml_type_name() -> String {
+        "OpenSecureChannelResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(
+            &mut element,
+            "ServerProtocolVersion",
+            &self.server_protocol_version,
+        );
+        write_xml_field(&mut element, "SecurityToken", &self.security_token);
+        write_xml_field(&mut element, "ServerNonce", &self.server_nonce);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(OpenSecureChannelResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            server_protocol_version: read_xml_field(
+                element,
+                "ServerProtocolVersion",
+                decoding_options,
+            )?,
+            security_token: read_xml_field(element, "SecurityToken", decoding_options)?,
+            server_nonce: read_xml_field(element, "ServerNonce", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for OpenSecureChannelResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ServerProtocolVersion",
+            &self.server_protocol_version,
+            encoding,
+        );
+        write_json_field(&mut object, "SecurityToken", &self.security_token, encoding);
+        write_json_field(&mut object, "ServerNonce", &self.server_nonce, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(OpenSecureChannelResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            server_protocol_version: read_json_field(
+                value,
+                "ServerProtocolVersion",
+                decoding_options,
+            )?,
+            security_token: read_json_field(value, "SecurityToken", decoding_options)?,
+            server_nonce: read_json_field(value, "ServerNonce", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -48,3 +48,42 @@ impl BinaryEncoder<OptionSet> for OptionSet {
         Ok(OptionSet { value, valid_bits })
     }
 }
+
+impl XmlEncoder for OptionSet {
+    fn xml_type_name() -> String {
+        "OptionSet".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Value", &self.value);
+        write_xml_field(&mut element, "ValidBits", &self.valid_bits);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(OptionSet {
+            value: read_xml_field(element, "Value", decoding_options)?,
+            valid_bits: read_xml_field(element, "ValidBits", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for OptionSet {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        write_json_field(&mut object, "ValidBits", &self.valid_bits, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(OptionSet {
+            value: read_json_field(value, "Value", decoding_options)?,
+            valid_bits: read_json_field(value, "ValidBits", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,64 @@ impl BinaryEncoder<ParsingResult> for ParsingResult {
         })
     }
 }
+
+impl XmlEncoder for ParsingResult {
+    fn xml_type_name() -> String {
+        "ParsingResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "DataStatusCodes", &self.data_status_codes);
+        write_xml_field(
+            &mut element,
+            "DataDiagnosticInfos",
+            &self.data_diagnostic_infos,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ParsingResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            data_status_codes: read_xml_field(element, "DataStatusCodes", decoding_options)?,
+            data_diagnostic_infos: read_xml_field(
+                element,
+                "DataDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ParsingResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "DataStatusCodes",
+            &self.data_status_codes,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataDiagnosticInfos",
+            &self.data_diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ParsingResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            data_status_codes: read_json_field(value, "DataStatusCodes", decoding_options)?,
+            data_diagnostic_infos: read_json_field(value, "DataDiagnosticInfos", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId, node_ids::ObjectId,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
     service_types::impls::MessageInfo, service_types::Argument, service_types::StatusResult,
-    string::UAString,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -94,3 +94,192 @@ impl BinaryEncoder<ProgramDiagnosticDataType> for ProgramDiagnosticDataType {
         })
     }
 }
+
+impl XmlEncoder for ProgramDiagnosticDataType {
+    fn xml_type_name() -> String {
+        "ProgramDiagnosticDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "CreateSessionId", &self.create_session_id);
+        write_xml_field(&mut element, "CreateClientName", &self.create_client_name);
+        write_xml_field(
+            &mut element,
+            "InvocationCreationTime",
+            &self.invocation_creation_time,
+        );
+        write_xml_field(
+            &mut element,
+            "LastTransitionTime",
+            &self.last_transition_time,
+        );
+        write_xml_field(&mut element, "LastMethodCall", &self.last_method_call);
+        write_xml_field(
+            &mut element,
+            "LastMethodSessionId",
+            &self.last_method_session_id,
+        );
+        write_xml_field(
+            &mut element,
+            "LastMethodInputArguments",
+            &self.last_method_input_arguments,
+        );
+        write_xml_field(
+            &mut element,
+            "LastMethodOutputArguments",
+            &self.last_method_output_arguments,
+        );
+        write_xml_field(
+            &mut element,
+            "LastMethodCallTime",
+            &self.last_method_call_time,
+        );
+        write_xml_field(
+            &mut element,
+            "LastMethodReturnStatus",
+            &self.last_method_return_status,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ProgramDiagnosticDataType {
+            create_session_id: read_xml_field(element, "CreateSessionId", decoding_options)?,
+            create_client_name: read_xml_field(element, "CreateClientName", decoding_options)?,
+            invocation_creation_time: read_xml_field(
+                element,
+                "InvocationCreationTime",
+                decoding_options,
+            )?,
+            last_transition_time: read_xml_field(element, "LastTransitionTime", decoding_options)?,
+            last_method_call: read_xml_field(element, "LastMethodCall", decoding_options)?,
+            last_method_session_id: read_xml_field(
+                element,
+                "LastMethodSessionId",
+                decoding_options,
+            )?,
+            last_method_input_arguments: read_xml_field(
+                element,
+                "LastMethodInputArguments",
+                decoding_options,
+            )?,
+            last_method_output_arguments: read_xml_field(
+                element,
+                "LastMethodOutputArguments",
+                decoding_options,
+            )?,
+            last_method_call_time: read_xml_field(element, "LastMethodCallTime", decoding_options)?,
+            last_method_return_status: read_xml_field(
+                element,
+                "LastMethodReturnStatus",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ProgramDiagnosticDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "CreateSessionId",
+            &self.create_session_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CreateClientName",
+            &self.create_client_name,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "InvocationCreationTime",
+            &self.invocation_creation_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastTransitionTime",
+            &self.last_transition_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodCall",
+            &self.last_method_call,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodSessionId",
+            &self.last_method_session_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodInputArguments",
+            &self.last_method_input_arguments,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodOutputArguments",
+            &self.last_method_output_arguments,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodCallTime",
+            &self.last_method_call_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LastMethodReturnStatus",
+            &self.last_method_return_status,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ProgramDiagnosticDataType {
+            create_session_id: read_json_field(value, "CreateSessionId", decoding_options)?,
+            create_client_name: read_json_field(value, "CreateClientName", decoding_options)?,
+            invocation_creation_time: read_json_field(
+                value,
+                "InvocationCreationTime",
+                decoding_options,
+            )?,
+            last_transition_time: read_json_field(value, "LastTransitionTime", decoding_options)?,
+            last_method_call: read_json_field(value, "LastMethodCall", decoding_options)?,
+            last_method_session_id: read_json_field(
+                value,
+                "LastMethodSessionId",
+                decoding_options,
+            )?,
+            last_method_input_arguments: read_json_field(
+                value,
+                "LastMethodInputArguments",
+                decoding_options,
+            )?,
+            last_method_output_arguments: read_json_field(
+                value,
+                "LastMethodOutputArguments",
+                decoding_options,
+            )?,
+            last_method_call_time: read_json_field(value, "LastMethodCallTime", decoding_options)?,
+            last_method_return_status: read_json_field(
+                value,
+                "LastMethodReturnStatus",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::PubSubConnectionDataType, service_types::PublishedDataSetDataType,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::PubSubConnectionDataType, service_types::PublishedDataSetDataType, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -58,3 +58,51 @@ impl BinaryEncoder<PubSubConfigurationDataType> for PubSubConfigurationDataType
         })
     }
 }
+
+impl XmlEncoder for PubSubConfigurationDataType {
+    fn xml_type_name() -> String {
+        "PubSubConfigurationDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PublishedDataSets", &self.published_data_sets);
+        write_xml_field(&mut element, "Connections", &self.connections);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PubSubConfigurationDataType {
+            published_data_sets: read_xml_field(element, "PublishedDataSets", decoding_options)?,
+            connections: read_xml_field(element, "Connections", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PubSubConfigurationDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "PublishedDataSets",
+            &self.published_data_sets,
+            encoding,
+        );
+        write_json_field(&mut object, "Connections", &self.connections, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PubSubConfigurationDataType {
+            published_data_sets: read_json_field(value, "PublishedDataSets", decoding_options)?,
+            connections: read_json_field(value, "Connections", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+        })
+    }
+}
@@ -8,10 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     service_types::impls::MessageInfo, service_types::KeyValuePair,
     service_types::ReaderGroupDataType, service_types::WriterGroupDataType, string::UAString,
-    variant::Variant,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -89,3 +89,105 @@ impl BinaryEncoder<PubSubConnectionDataType> for PubSubConnectionDataType {
         })
     }
 }
+
+impl XmlEncoder for PubSubConnectionDataType {
+    fn xml_type_name() -> String {
+        "PubSubConnectionDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        write_xml_field(&mut element, "PublisherId", &self.publisher_id);
+        write_xml_field(
+            &mut element,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+        );
+        write_xml_field(&mut element, "Address", &self.address);
+        write_xml_field(
+            &mut element,
+            "ConnectionProperties",
+            &self.connection_properties,
+        );
+        write_xml_field(&mut element, "TransportSettings", &self.transport_settings);
+        write_xml_field(&mut element, "WriterGroups", &self.writer_groups);
+        write_xml_field(&mut element, "ReaderGroups", &self.reader_groups);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PubSubConnectionDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+            publisher_id: read_xml_field(element, "PublisherId", decoding_options)?,
+            transport_profile_uri: read_xml_field(
+                element,
+                "TransportProfileUri",
+                decoding_options,
+            )?,
+            address: read_xml_field(element, "Address", decoding_options)?,
+            connection_properties: read_xml_field(
+                element,
+                "ConnectionProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_xml_field(element, "TransportSettings", decoding_options)?,
+            writer_groups: read_xml_field(element, "WriterGroups", decoding_options)?,
+            reader_groups: read_xml_field(element, "ReaderGroups", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PubSubConnectionDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, encoding);
+        write_json_field(
+            &mut object,
+            "TransportProfileUri",
+            &self.transport_profile_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "Address", &self.address, encoding);
+        write_json_field(
+            &mut object,
+            "ConnectionProperties",
+            &self.connection_properties,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportSettings",
+            &self.transport_settings,
+            encoding,
+        );
+        write_json_field(&mut object, "WriterGroups", &self.writer_groups, encoding);
+        write_json_field(&mut object, "ReaderGroups", &self.reader_groups, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PubSubConnectionDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+            publisher_id: read_json_field(value, "PublisherId", decoding_options)?,
+            transport_profile_uri: read_json_field(value, "TransportProfileUri", decoding_options)?,
+            address: read_json_field(value, "Address", decoding_options)?,
+            connection_properties: read_json_field(
+                value,
+                "ConnectionProperties",
+                decoding_options,
+            )?,
+            transport_settings: read_json_field(value, "TransportSettings", decoding_options)?,
+            writer_groups: read_json_field(value, "WriterGroups", decoding_options)?,
+            reader_groups: read_json_field(value, "ReaderGroups", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::SubscriptionAcknowledgement,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::SubscriptionAcknowledgement, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -52,3 +52,59 @@ impl BinaryEncoder<PublishRequest> for PublishRequest {
         })
     }
 }
+
+impl XmlEncoder for PublishRequest {
+    fn xml_type_name() -> String {
+        "PublishRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "SubscriptionAcknowledgements",
+            &self.subscription_acknowledgements,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_acknowledgements: read_xml_field(
+                element,
+                "SubscriptionAcknowledgements",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionAcknowledgements",
+            &self.subscription_acknowledgements,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_acknowledgements: read_json_field(
+                value,
+                "SubscriptionAcknowledgements",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo,
-    service_types::NotificationMessage, status_codes::StatusCode,
+    service_types::NotificationMessage, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -77,3 +77,108 @@ impl BinaryEncoder<PublishResponse> for PublishResponse {
         })
     }
 }
+
+impl XmlEncoder for PublishResponse {
+    fn xml_type_name() -> String {
+        "PublishResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(
+            &mut element,
+            "AvailableSequenceNumbers",
+            &self.available_sequence_numbers,
+        );
+        write_xml_field(&mut element, "MoreNotifications", &self.more_notifications);
+        write_xml_field(
+            &mut element,
+            "NotificationMessage",
+            &self.notification_message,
+        );
+        write_xml_field(&mut element, "Results", &self.results);
+        write_xml_field(&mut element, "DiagnosticInfos", &self.diagnostic_infos);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            available_sequence_numbers: read_xml_field(
+                element,
+                "AvailableSequenceNumbers",
+                decoding_options,
+            )?,
+            more_notifications: read_xml_field(element, "MoreNotifications", decoding_options)?,
+            notification_message: read_xml_field(element, "NotificationMessage", decoding_options)?,
+            results: read_xml_field(element, "Results", decoding_options)?,
+            diagnostic_infos: read_xml_field(element, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AvailableSequenceNumbers",
+            &self.available_sequence_numbers,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MoreNotifications",
+            &self.more_notifications,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NotificationMessage",
+            &self.notification_message,
+            encoding,
+        );
+        write_json_field(&mut object, "Results", &self.results, encoding);
+        write_json_field(
+            &mut object,
+            "DiagnosticInfos",
+            &self.diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            available_sequence_numbers: read_json_field(
+                value,
+                "AvailableSequenceNumbers",
+                decoding_options,
+            )?,
+            more_notifications: read_json_field(value, "MoreNotifications", decoding_options)?,
+            notification_message: read_json_field(value, "NotificationMessage", decoding_options)?,
+            results: read_json_field(value, "Results", decoding_options)?,
+            diagnostic_infos: read_json_field(value, "DiagnosticInfos", decoding_options)?,
+        })
+    }
+}
@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, service_types::PublishedVariableDataType};
+use crate::types::{
+    basic_types::*, encoding::*, json::*, service_types::PublishedVariableDataType, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,3 +38,38 @@ impl BinaryEncoder<PublishedDataItemsDataType> for PublishedDataItemsDataType {
         Ok(PublishedDataItemsDataType { published_data })
     }
 }
+
+impl XmlEncoder for PublishedDataItemsDataType {
+    fn xml_type_name() -> String {
+        "PublishedDataItemsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PublishedData", &self.published_data);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishedDataItemsDataType {
+            published_data: read_xml_field(element, "PublishedData", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishedDataItemsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PublishedData", &self.published_data, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishedDataItemsDataType {
+            published_data: read_json_field(value, "PublishedData", decoding_options)?,
+        })
+    }
+}
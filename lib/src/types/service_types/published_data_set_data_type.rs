@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     service_types::impls::MessageInfo, service_types::DataSetMetaDataType,
-    service_types::KeyValuePair, string::UAString,
+    service_types::KeyValuePair, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -67,3 +67,74 @@ impl BinaryEncoder<PublishedDataSetDataType> for PublishedDataSetDataType {
         })
     }
 }
+
+impl XmlEncoder for PublishedDataSetDataType {
+    fn xml_type_name() -> String {
+        "PublishedDataSetDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "DataSetFolder", &self.data_set_folder);
+        write_xml_field(&mut element, "DataSetMetaData", &self.data_set_meta_data);
+        write_xml_field(&mut element, "ExtensionFields", &self.extension_fields);
+        write_xml_field(&mut element, "DataSetSource", &self.data_set_source);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishedDataSetDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            data_set_folder: read_xml_field(element, "DataSetFolder", decoding_options)?,
+            data_set_meta_data: read_xml_field(element, "DataSetMetaData", decoding_options)?,
+            extension_fields: read_xml_field(element, "ExtensionFields", decoding_options)?,
+            data_set_source: read_xml_field(element, "DataSetSource", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishedDataSetDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetFolder",
+            &self.data_set_folder,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetMetaData",
+            &self.data_set_meta_data,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ExtensionFields",
+            &self.extension_fields,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetSource",
+            &self.data_set_source,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishedDataSetDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            data_set_folder: read_json_field(value, "DataSetFolder", decoding_options)?,
+            data_set_meta_data: read_json_field(value, "DataSetMetaData", decoding_options)?,
+            extension_fields: read_json_field(value, "ExtensionFields", decoding_options)?,
+            data_set_source: read_json_field(value, "DataSetSource", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, service_types::ContentFilter,
-    service_types::SimpleAttributeOperand,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, service_types::ContentFilter,
+    service_types::SimpleAttributeOperand, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,51 @@ impl BinaryEncoder<PublishedEventsDataType> for PublishedEventsDataType {
         })
     }
 }
+
+impl XmlEncoder for PublishedEventsDataType {
+    fn xml_type_name() -> String {
+        "PublishedEventsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "EventNotifier", &self.event_notifier);
+        write_xml_field(&mut element, "SelectedFields", &self.selected_fields);
+        write_xml_field(&mut element, "Filter", &self.filter);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishedEventsDataType {
+            event_notifier: read_xml_field(element, "EventNotifier", decoding_options)?,
+            selected_fields: read_xml_field(element, "SelectedFields", decoding_options)?,
+            filter: read_xml_field(element, "Filter", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishedEventsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EventNotifier", &self.event_notifier, encoding);
+        write_json_field(
+            &mut object,
+            "SelectedFields",
+            &self.selected_fields,
+            encoding,
+        );
+        write_json_field(&mut object, "Filter", &self.filter, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishedEventsDataType {
+            event_notifier: read_json_field(value, "EventNotifier", decoding_options)?,
+            selected_fields: read_json_field(value, "SelectedFields", decoding_options)?,
+            filter: read_json_field(value, "Filter", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
     qualified_name::QualifiedName, service_types::impls::MessageInfo, string::UAString,
-    variant::Variant,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -83,3 +83,102 @@ impl BinaryEncoder<PublishedVariableDataType> for PublishedVariableDataType {
         })
     }
 }
+
+impl XmlEncoder for PublishedVariableDataType {
+    fn xml_type_name() -> String {
+        "PublishedVariableDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PublishedVariable", &self.published_variable);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(
+            &mut element,
+            "SamplingIntervalHint",
+            &self.sampling_interval_hint,
+        );
+        write_xml_field(&mut element, "DeadbandType", &self.deadband_type);
+        write_xml_field(&mut element, "DeadbandValue", &self.deadband_value);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        write_xml_field(&mut element, "SubstituteValue", &self.substitute_value);
+        write_xml_field(
+            &mut element,
+            "MetaDataProperties",
+            &self.meta_data_properties,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(PublishedVariableDataType {
+            published_variable: read_xml_field(element, "PublishedVariable", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            sampling_interval_hint: read_xml_field(
+                element,
+                "SamplingIntervalHint",
+                decoding_options,
+            )?,
+            deadband_type: read_xml_field(element, "DeadbandType", decoding_options)?,
+            deadband_value: read_xml_field(element, "DeadbandValue", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+            substitute_value: read_xml_field(element, "SubstituteValue", decoding_options)?,
+            meta_data_properties: read_xml_field(element, "MetaDataProperties", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for PublishedVariableDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "PublishedVariable",
+            &self.published_variable,
+            encoding,
+        );
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(
+            &mut object,
+            "SamplingIntervalHint",
+            &self.sampling_interval_hint,
+            encoding,
+        );
+        write_json_field(&mut object, "DeadbandType", &self.deadband_type, encoding);
+        write_json_field(&mut object, "DeadbandValue", &self.deadband_value, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        write_json_field(
+            &mut object,
+            "SubstituteValue",
+            &self.substitute_value,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MetaDataProperties",
+            &self.meta_data_properties,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(PublishedVariableDataType {
+            published_variable: read_json_field(value, "PublishedVariable", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            sampling_interval_hint: read_json_field(
+                value,
+                "SamplingIntervalHint",
+                decoding_options,
+            )?,
+            deadband_type: read_json_field(value, "DeadbandType", decoding_options)?,
+            deadband_value: read_json_field(value, "DeadbandValue", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+            substitute_value: read_json_field(value, "SubstituteValue", decoding_options)?,
+            meta_data_properties: read_json_field(value, "MetaDataProperties", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    service_types::RelativePath, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    service_types::RelativePath, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<QueryDataDescription> for QueryDataDescription {
         })
     }
 }
+
+impl XmlEncoder for QueryDataDescription {
+    fn xml_type_name() -> String {
+        "QueryDataDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RelativePath", &self.relative_path);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryDataDescription {
+            relative_path: read_xml_field(element, "RelativePath", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryDataDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RelativePath", &self.relative_path, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryDataDescription {
+            relative_path: read_json_field(value, "RelativePath", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+        })
+    }
+}
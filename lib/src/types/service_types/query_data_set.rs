@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, variant::Variant,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,55 @@ impl BinaryEncoder<QueryDataSet> for QueryDataSet {
         })
     }
 }
+
+impl XmlEncoder for QueryDataSet {
+    fn xml_type_name() -> String {
+        "QueryDataSet".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(
+            &mut element,
+            "TypeDefinitionNode",
+            &self.type_definition_node,
+        );
+        write_xml_field(&mut element, "Values", &self.values);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryDataSet {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            type_definition_node: read_xml_field(element, "TypeDefinitionNode", decoding_options)?,
+            values: read_xml_field(element, "Values", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryDataSet {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "TypeDefinitionNode",
+            &self.type_definition_node,
+            encoding,
+        );
+        write_json_field(&mut object, "Values", &self.values, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryDataSet {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            type_definition_node: read_json_field(value, "TypeDefinitionNode", decoding_options)?,
+            values: read_json_field(value, "Values", decoding_options)?,
+        })
+    }
+}
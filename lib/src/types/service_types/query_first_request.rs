@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
     service_types::impls::MessageInfo, service_types::ContentFilter,
-    service_types::NodeTypeDescription, service_types::ViewDescription,
+    service_types::NodeTypeDescription, service_types::ViewDescription, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -72,3 +72,92 @@ impl BinaryEncoder<QueryFirstRequest> for QueryFirstRequest {
         })
     }
 }
+
+impl XmlEncoder for QueryFirstRequest {
+    fn xml_type_name() -> String {
+        "QueryFirstRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "View", &self.view);
+        write_xml_field(&mut element, "NodeTypes", &self.node_types);
+        write_xml_field(&mut element, "Filter", &self.filter);
+        write_xml_field(
+            &mut element,
+            "MaxDataSetsToReturn",
+            &self.max_data_sets_to_return,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxReferencesToReturn",
+            &self.max_references_to_return,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryFirstRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            view: read_xml_field(element, "View", decoding_options)?,
+            node_types: read_xml_field(element, "NodeTypes", decoding_options)?,
+            filter: read_xml_field(element, "Filter", decoding_options)?,
+            max_data_sets_to_return: read_xml_field(
+                element,
+                "MaxDataSetsToReturn",
+                decoding_options,
+            )?,
+            max_references_to_return: read_xml_field(
+                element,
+                "MaxReferencesToReturn",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryFirstRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "View", &self.view, encoding);
+        write_json_field(&mut object, "NodeTypes", &self.node_types, encoding);
+        write_json_field(&mut object, "Filter", &self.filter, encoding);
+        write_json_field(
+            &mut object,
+            "MaxDataSetsToReturn",
+            &self.max_data_sets_to_return,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxReferencesToReturn",
+            &self.max_references_to_return,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryFirstRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            view: read_json_field(value, "View", decoding_options)?,
+            node_types: read_json_field(value, "NodeTypes", decoding_options)?,
+            filter: read_json_field(value, "Filter", decoding_options)?,
+            max_data_sets_to_return: read_json_field(
+                value,
+                "MaxDataSetsToReturn",
+                decoding_options,
+            )?,
+            max_references_to_return: read_json_field(
+                value,
+                "MaxReferencesToReturn",
+                decoding_options,
+            )?,
+        })
+    }
+}
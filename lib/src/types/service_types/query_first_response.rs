@@ -8,9 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, diagnostic_info::DiagnosticInfo, encoding::*,
+    basic_types::*, byte_string::ByteString, diagnostic_info::DiagnosticInfo, encoding::*, json::*,
     node_ids::ObjectId, response_header::ResponseHeader, service_types::impls::MessageInfo,
     service_types::ContentFilterResult, service_types::ParsingResult, service_types::QueryDataSet,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -72,3 +73,83 @@ impl BinaryEncoder<QueryFirstResponse> for QueryFirstResponse {
         })
     }
 }
+
+impl XmlEncoder for QueryFirstResponse {
+    fn xml_type_name() -> String {
+        "QueryFirstResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "QueryDataSets", &self.query_data_sets);
+        write_xml_field(&mut element, "ContinuationPoint", &self.continuation_point);
+        write_xml_field(&mut element, "ParsingResults", &self.parsing_results);
+        write_xml_field(&mut element, "DiagnosticInfos", &self.diagnostic_infos);
+        write_xml_field(&mut element, "FilterResult", &self.filter_result);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryFirstResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            query_data_sets: read_xml_field(element, "QueryDataSets", decoding_options)?,
+            continuation_point: read_xml_field(element, "ContinuationPoint", decoding_options)?,
+            parsing_results: read_xml_field(element, "ParsingResults", decoding_options)?,
+            diagnostic_infos: read_xml_field(element, "DiagnosticInfos", decoding_options)?,
+            filter_result: read_xml_field(element, "FilterResult", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryFirstResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "QueryDataSets",
+            &self.query_data_sets,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ContinuationPoint",
+            &self.continuation_point,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ParsingResults",
+            &self.parsing_results,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DiagnosticInfos",
+            &self.diagnostic_infos,
+            encoding,
+        );
+        write_json_field(&mut object, "FilterResult", &self.filter_result, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryFirstResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            query_data_sets: read_json_field(value, "QueryDataSets", decoding_options)?,
+            continuation_point: read_json_field(value, "ContinuationPoint", decoding_options)?,
+            parsing_results: read_json_field(value, "ParsingResults", decoding_options)?,
+            diagnostic_infos: read_json_field(value, "DiagnosticInfos", decoding_options)?,
+            filter_result: read_json_field(value, "FilterResult", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    request_header::RequestHeader, service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    request_header::RequestHeader, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,68 @@ impl BinaryEncoder<QueryNextRequest> for QueryNextRequest {
         })
     }
 }
+
+impl XmlEncoder for QueryNextRequest {
+    fn xml_type_name() -> String {
+        "QueryNextRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(
+            &mut element,
+            "ReleaseContinuationPoint",
+            &self.release_continuation_point,
+        );
+        write_xml_field(&mut element, "ContinuationPoint", &self.continuation_point);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryNextRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            release_continuation_point: read_xml_field(
+                element,
+                "ReleaseContinuationPoint",
+                decoding_options,
+            )?,
+            continuation_point: read_xml_field(element, "ContinuationPoint", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryNextRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "ReleaseContinuationPoint",
+            &self.release_continuation_point,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ContinuationPoint",
+            &self.continuation_point,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryNextRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            release_continuation_point: read_json_field(
+                value,
+                "ReleaseContinuationPoint",
+                decoding_options,
+            )?,
+            continuation_point: read_json_field(value, "ContinuationPoint", decoding_options)?,
+        })
+    }
+}
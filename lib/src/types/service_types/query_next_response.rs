@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo,
-    service_types::QueryDataSet,
+    service_types::QueryDataSet, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -57,3 +57,73 @@ impl BinaryEncoder<QueryNextResponse> for QueryNextResponse {
         })
     }
 }
+
+impl XmlEncoder for QueryNextResponse {
+    fn xml_type_name() -> String {
+        "QueryNextResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "QueryDataSets", &self.query_data_sets);
+        write_xml_field(
+            &mut element,
+            "RevisedContinuationPoint",
+            &self.revised_continuation_point,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(QueryNextResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            query_data_sets: read_xml_field(element, "QueryDataSets", decoding_options)?,
+            revised_continuation_point: read_xml_field(
+                element,
+                "RevisedContinuationPoint",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for QueryNextResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "QueryDataSets",
+            &self.query_data_sets,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RevisedContinuationPoint",
+            &self.revised_continuation_point,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(QueryNextResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            query_data_sets: read_json_field(value, "QueryDataSets", decoding_options)?,
+            revised_continuation_point: read_json_field(
+                value,
+                "RevisedContinuationPoint",
+                decoding_options,
+            )?,
+        })
+    }
+}
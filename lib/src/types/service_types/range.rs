@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -47,3 +48,42 @@ impl BinaryEncoder<Range> for Range {
         Ok(Range { low, high })
     }
 }
+
+impl XmlEncoder for Range {
+    fn xml_type_name() -> String {
+        "Range".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Low", &self.low);
+        write_xml_field(&mut element, "High", &self.high);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(Range {
+            low: read_xml_field(element, "Low", decoding_options)?,
+            high: read_xml_field(element, "High", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for Range {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Low", &self.low, encoding);
+        write_json_field(&mut object, "High", &self.high, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(Range {
+            low: read_json_field(value, "Low", decoding_options)?,
+            high: read_json_field(value, "High", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +51,42 @@ impl BinaryEncoder<RationalNumber> for RationalNumber {
         })
     }
 }
+
+impl XmlEncoder for RationalNumber {
+    fn xml_type_name() -> String {
+        "RationalNumber".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Numerator", &self.numerator);
+        write_xml_field(&mut element, "Denominator", &self.denominator);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RationalNumber {
+            numerator: read_xml_field(element, "Numerator", decoding_options)?,
+            denominator: read_xml_field(element, "Denominator", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RationalNumber {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Numerator", &self.numerator, encoding);
+        write_json_field(&mut object, "Denominator", &self.denominator, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RationalNumber {
+            numerator: read_json_field(value, "Numerator", decoding_options)?,
+            denominator: read_json_field(value, "Denominator", decoding_options)?,
+        })
+    }
+}
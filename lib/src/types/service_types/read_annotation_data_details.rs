@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*};
+use crate::types::{basic_types::*, date_time::DateTime, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,3 +35,38 @@ impl BinaryEncoder<ReadAnnotationDataDetails> for ReadAnnotationDataDetails {
         Ok(ReadAnnotationDataDetails { req_times })
     }
 }
+
+impl XmlEncoder for ReadAnnotationDataDetails {
+    fn xml_type_name() -> String {
+        "ReadAnnotationDataDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ReqTimes", &self.req_times);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadAnnotationDataDetails {
+            req_times: read_xml_field(element, "ReqTimes", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadAnnotationDataDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ReqTimes", &self.req_times, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadAnnotationDataDetails {
+            req_times: read_json_field(value, "ReqTimes", decoding_options)?,
+        })
+    }
+}
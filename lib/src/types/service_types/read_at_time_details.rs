@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*};
+use crate::types::{basic_types::*, date_time::DateTime, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,47 @@ impl BinaryEncoder<ReadAtTimeDetails> for ReadAtTimeDetails {
         })
     }
 }
+
+impl XmlEncoder for ReadAtTimeDetails {
+    fn xml_type_name() -> String {
+        "ReadAtTimeDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ReqTimes", &self.req_times);
+        write_xml_field(&mut element, "UseSimpleBounds", &self.use_simple_bounds);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadAtTimeDetails {
+            req_times: read_xml_field(element, "ReqTimes", decoding_options)?,
+            use_simple_bounds: read_xml_field(element, "UseSimpleBounds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadAtTimeDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ReqTimes", &self.req_times, encoding);
+        write_json_field(
+            &mut object,
+            "UseSimpleBounds",
+            &self.use_simple_bounds,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadAtTimeDetails {
+            req_times: read_json_field(value, "ReqTimes", decoding_options)?,
+            use_simple_bounds: read_json_field(value, "UseSimpleBounds", decoding_options)?,
+        })
+    }
+}
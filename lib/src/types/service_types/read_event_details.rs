@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*, service_types::EventFilter};
+use crate::types::{
+    basic_types::*, date_time::DateTime, encoding::*, json::*, service_types::EventFilter, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,3 +54,55 @@ impl BinaryEncoder<ReadEventDetails> for ReadEventDetails {
         })
     }
 }
+
+impl XmlEncoder for ReadEventDetails {
+    fn xml_type_name() -> String {
+        "ReadEventDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NumValuesPerNode", &self.num_values_per_node);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "EndTime", &self.end_time);
+        write_xml_field(&mut element, "Filter", &self.filter);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadEventDetails {
+            num_values_per_node: read_xml_field(element, "NumValuesPerNode", decoding_options)?,
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            end_time: read_xml_field(element, "EndTime", decoding_options)?,
+            filter: read_xml_field(element, "Filter", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadEventDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "NumValuesPerNode",
+            &self.num_values_per_node,
+            encoding,
+        );
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "EndTime", &self.end_time, encoding);
+        write_json_field(&mut object, "Filter", &self.filter, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadEventDetails {
+            num_values_per_node: read_json_field(value, "NumValuesPerNode", decoding_options)?,
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            end_time: read_json_field(value, "EndTime", decoding_options)?,
+            filter: read_json_field(value, "Filter", decoding_options)?,
+        })
+    }
+}
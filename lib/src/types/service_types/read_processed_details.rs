@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId,
-    service_types::AggregateConfiguration,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId,
+    service_types::AggregateConfiguration, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -60,3 +60,80 @@ impl BinaryEncoder<ReadProcessedDetails> for ReadProcessedDetails {
         })
     }
 }
+
+impl XmlEncoder for ReadProcessedDetails {
+    fn xml_type_name() -> String {
+        "ReadProcessedDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "EndTime", &self.end_time);
+        write_xml_field(
+            &mut element,
+            "ProcessingInterval",
+            &self.processing_interval,
+        );
+        write_xml_field(&mut element, "AggregateType", &self.aggregate_type);
+        write_xml_field(
+            &mut element,
+            "AggregateConfiguration",
+            &self.aggregate_configuration,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadProcessedDetails {
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            end_time: read_xml_field(element, "EndTime", decoding_options)?,
+            processing_interval: read_xml_field(element, "ProcessingInterval", decoding_options)?,
+            aggregate_type: read_xml_field(element, "AggregateType", decoding_options)?,
+            aggregate_configuration: read_xml_field(
+                element,
+                "AggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadProcessedDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "EndTime", &self.end_time, encoding);
+        write_json_field(
+            &mut object,
+            "ProcessingInterval",
+            &self.processing_interval,
+            encoding,
+        );
+        write_json_field(&mut object, "AggregateType", &self.aggregate_type, encoding);
+        write_json_field(
+            &mut object,
+            "AggregateConfiguration",
+            &self.aggregate_configuration,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadProcessedDetails {
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            end_time: read_json_field(value, "EndTime", decoding_options)?,
+            processing_interval: read_json_field(value, "ProcessingInterval", decoding_options)?,
+            aggregate_type: read_json_field(value, "AggregateType", decoding_options)?,
+            aggregate_configuration: read_json_field(
+                value,
+                "AggregateConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, date_time::DateTime, encoding::*};
+use crate::types::{basic_types::*, date_time::DateTime, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,3 +57,64 @@ impl BinaryEncoder<ReadRawModifiedDetails> for ReadRawModifiedDetails {
         })
     }
 }
+
+impl XmlEncoder for ReadRawModifiedDetails {
+    fn xml_type_name() -> String {
+        "ReadRawModifiedDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "IsReadModified", &self.is_read_modified);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "EndTime", &self.end_time);
+        write_xml_field(&mut element, "NumValuesPerNode", &self.num_values_per_node);
+        write_xml_field(&mut element, "ReturnBounds", &self.return_bounds);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadRawModifiedDetails {
+            is_read_modified: read_xml_field(element, "IsReadModified", decoding_options)?,
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            end_time: read_xml_field(element, "EndTime", decoding_options)?,
+            num_values_per_node: read_xml_field(element, "NumValuesPerNode", decoding_options)?,
+            return_bounds: read_xml_field(element, "ReturnBounds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadRawModifiedDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "IsReadModified",
+            &self.is_read_modified,
+            encoding,
+        );
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "EndTime", &self.end_time, encoding);
+        write_json_field(
+            &mut object,
+            "NumValuesPerNode",
+            &self.num_values_per_node,
+            encoding,
+        );
+        write_json_field(&mut object, "ReturnBounds", &self.return_bounds, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadRawModifiedDetails {
+            is_read_modified: read_json_field(value, "IsReadModified", decoding_options)?,
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            end_time: read_json_field(value, "EndTime", decoding_options)?,
+            num_values_per_node: read_json_field(value, "NumValuesPerNode", decoding_options)?,
+            return_bounds: read_json_field(value, "ReturnBounds", decoding_options)?,
+        })
+    }
+}
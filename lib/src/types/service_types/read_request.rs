@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
     service_types::enums::TimestampsToReturn, service_types::impls::MessageInfo,
-    service_types::ReadValueId,
+    service_types::ReadValueId, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -62,3 +62,59 @@ impl BinaryEncoder<ReadRequest> for ReadRequest {
         })
     }
 }
+
+impl XmlEncoder for ReadRequest {
+    fn xml_type_name() -> String {
+        "ReadRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "MaxAge", &self.max_age);
+        write_xml_field(
+            &mut element,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+        );
+        write_xml_field(&mut element, "NodesToRead", &self.nodes_to_read);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            max_age: read_xml_field(element, "MaxAge", decoding_options)?,
+            timestamps_to_return: read_xml_field(element, "TimestampsToReturn", decoding_options)?,
+            nodes_to_read: read_xml_field(element, "NodesToRead", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "MaxAge", &self.max_age, encoding);
+        write_json_field(
+            &mut object,
+            "TimestampsToReturn",
+            &self.timestamps_to_return,
+            encoding,
+        );
+        write_json_field(&mut object, "NodesToRead", &self.nodes_to_read, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            max_age: read_json_field(value, "MaxAge", decoding_options)?,
+            timestamps_to_return: read_json_field(value, "TimestampsToReturn", decoding_options)?,
+            nodes_to_read: read_json_field(value, "NodesToRead", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    qualified_name::QualifiedName, service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    qualified_name::QualifiedName, service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -62,3 +62,50 @@ impl BinaryEncoder<ReadValueId> for ReadValueId {
         })
     }
 }
+
+impl XmlEncoder for ReadValueId {
+    fn xml_type_name() -> String {
+        "ReadValueId".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        write_xml_field(&mut element, "DataEncoding", &self.data_encoding);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReadValueId {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+            data_encoding: read_xml_field(element, "DataEncoding", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReadValueId {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        write_json_field(&mut object, "DataEncoding", &self.data_encoding, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReadValueId {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+            data_encoding: read_json_field(value, "DataEncoding", decoding_options)?,
+        })
+    }
+}
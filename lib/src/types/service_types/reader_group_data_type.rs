@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*,
     service_types::enums::MessageSecurityMode, service_types::DataSetReaderDataType,
-    service_types::EndpointDescription, service_types::KeyValuePair, string::UAString,
+    service_types::EndpointDescription, service_types::KeyValuePair, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -88,3 +88,129 @@ impl BinaryEncoder<ReaderGroupDataType> for ReaderGroupDataType {
         })
     }
 }
+
+impl XmlEncoder for ReaderGroupDataType {
+    fn xml_type_name() -> String {
+        "ReaderGroupDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityGroupId", &self.security_group_id);
+        write_xml_field(
+            &mut element,
+            "SecurityKeyServices",
+            &self.security_key_services,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxNetworkMessageSize",
+            &self.max_network_message_size,
+        );
+        write_xml_field(&mut element, "GroupProperties", &self.group_properties);
+        write_xml_field(&mut element, "TransportSettings", &self.transport_settings);
+        write_xml_field(&mut element, "MessageSettings", &self.message_settings);
+        write_xml_field(&mut element, "DataSetReaders", &self.data_set_readers);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReaderGroupDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_group_id: read_xml_field(element, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_xml_field(
+                element,
+                "SecurityKeyServices",
+                decoding_options,
+            )?,
+            max_network_message_size: read_xml_field(
+                element,
+                "MaxNetworkMessageSize",
+                decoding_options,
+            )?,
+            group_properties: read_xml_field(element, "GroupProperties", decoding_options)?,
+            transport_settings: read_xml_field(element, "TransportSettings", decoding_options)?,
+            message_settings: read_xml_field(element, "MessageSettings", decoding_options)?,
+            data_set_readers: read_xml_field(element, "DataSetReaders", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReaderGroupDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityGroupId",
+            &self.security_group_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityKeyServices",
+            &self.security_key_services,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxNetworkMessageSize",
+            &self.max_network_message_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "GroupProperties",
+            &self.group_properties,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportSettings",
+            &self.transport_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageSettings",
+            &self.message_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetReaders",
+            &self.data_set_readers,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReaderGroupDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_group_id: read_json_field(value, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_json_field(value, "SecurityKeyServices", decoding_options)?,
+            max_network_message_size: read_json_field(
+                value,
+                "MaxNetworkMessageSize",
+                decoding_options,
+            )?,
+            group_properties: read_json_field(value, "GroupProperties", decoding_options)?,
+            transport_settings: read_json_field(value, "TransportSettings", decoding_options)?,
+            message_settings: read_json_field(value, "MessageSettings", decoding_options)?,
+            data_set_readers: read_json_field(value, "DataSetReaders", decoding_options)?,
+        })
+    }
+}
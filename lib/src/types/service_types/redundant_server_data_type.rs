@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::enums::ServerState,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::enums::ServerState,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<RedundantServerDataType> for RedundantServerDataType {
         })
     }
 }
+
+impl XmlEncoder for RedundantServerDataType {
+    fn xml_type_name() -> String {
+        "RedundantServerDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ServerId", &self.server_id);
+        write_xml_field(&mut element, "ServiceLevel", &self.service_level);
+        write_xml_field(&mut element, "ServerState", &self.server_state);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RedundantServerDataType {
+            server_id: read_xml_field(element, "ServerId", decoding_options)?,
+            service_level: read_xml_field(element, "ServiceLevel", decoding_options)?,
+            server_state: read_xml_field(element, "ServerState", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RedundantServerDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerId", &self.server_id, encoding);
+        write_json_field(&mut object, "ServiceLevel", &self.service_level, encoding);
+        write_json_field(&mut object, "ServerState", &self.server_state, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RedundantServerDataType {
+            server_id: read_json_field(value, "ServerId", decoding_options)?,
+            service_level: read_json_field(value, "ServiceLevel", decoding_options)?,
+            server_state: read_json_field(value, "ServerState", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,10 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, localized_text::LocalizedText,
-    node_id::NodeId, node_ids::ObjectId, qualified_name::QualifiedName,
-    service_types::enums::NodeClass, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, expanded_node_id::ExpandedNodeId, json::*,
+    localized_text::LocalizedText, node_id::NodeId, node_ids::ObjectId,
+    qualified_name::QualifiedName, service_types::enums::NodeClass,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -77,3 +78,72 @@ impl BinaryEncoder<ReferenceDescription> for ReferenceDescription {
         })
     }
 }
+
+impl XmlEncoder for ReferenceDescription {
+    fn xml_type_name() -> String {
+        "ReferenceDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(&mut element, "IsForward", &self.is_forward);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "BrowseName", &self.browse_name);
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "NodeClass", &self.node_class);
+        write_xml_field(&mut element, "TypeDefinition", &self.type_definition);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ReferenceDescription {
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_xml_field(element, "IsForward", decoding_options)?,
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            browse_name: read_xml_field(element, "BrowseName", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            node_class: read_xml_field(element, "NodeClass", decoding_options)?,
+            type_definition: read_xml_field(element, "TypeDefinition", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ReferenceDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(&mut object, "IsForward", &self.is_forward, encoding);
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "BrowseName", &self.browse_name, encoding);
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "NodeClass", &self.node_class, encoding);
+        write_json_field(
+            &mut object,
+            "TypeDefinition",
+            &self.type_definition,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ReferenceDescription {
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            is_forward: read_json_field(value, "IsForward", decoding_options)?,
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            browse_name: read_json_field(value, "BrowseName", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            node_class: read_json_field(value, "NodeClass", decoding_options)?,
+            type_definition: read_json_field(value, "TypeDefinition", decoding_options)?,
+        })
+    }
+}
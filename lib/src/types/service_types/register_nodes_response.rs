@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    response_header::ResponseHeader, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    response_header::ResponseHeader, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,52 @@ impl BinaryEncoder<RegisterNodesResponse> for RegisterNodesResponse {
         })
     }
 }
+
+impl XmlEncoder for RegisterNodesResponse {
+    fn xml_type_name() -> String {
+        "RegisterNodesResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "RegisteredNodeIds", &self.registered_node_ids);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RegisterNodesResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            registered_node_ids: read_xml_field(element, "RegisteredNodeIds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RegisterNodesResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RegisteredNodeIds",
+            &self.registered_node_ids,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RegisterNodesResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            registered_node_ids: read_json_field(value, "RegisteredNodeIds", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject, node_ids::ObjectId,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*, node_ids::ObjectId,
     request_header::RequestHeader, service_types::impls::MessageInfo,
-    service_types::RegisteredServer,
+    service_types::RegisteredServer, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -58,3 +58,63 @@ impl BinaryEncoder<RegisterServer2Request> for RegisterServer2Request {
         })
     }
 }
+
+impl XmlEncoder for RegisterServer2Request {
+    fn xml_type_name() -> String {
+        "RegisterServer2Request".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "Server", &self.server);
+        write_xml_field(
+            &mut element,
+            "DiscoveryConfiguration",
+            &self.discovery_configuration,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RegisterServer2Request {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            server: read_xml_field(element, "Server", decoding_options)?,
+            discovery_configuration: read_xml_field(
+                element,
+                "DiscoveryConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for RegisterServer2Request {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "Server", &self.server, encoding);
+        write_json_field(
+            &mut object,
+            "DiscoveryConfiguration",
+            &self.discovery_configuration,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RegisterServer2Request {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            server: read_json_field(value, "Server", decoding_options)?,
+            discovery_configuration: read_json_field(
+                value,
+                "DiscoveryConfiguration",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::RegisteredServer,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::RegisteredServer, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<RegisterServerRequest> for RegisterServerRequest {
         })
     }
 }
+
+impl XmlEncoder for RegisterServerRequest {
+    fn xml_type_name() -> String {
+        "RegisterServerRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "Server", &self.server);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RegisterServerRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            server: read_xml_field(element, "Server", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RegisterServerRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "Server", &self.server, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RegisterServerRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            server: read_json_field(value, "Server", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_ids::ObjectId,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_ids::ObjectId,
     service_types::enums::ApplicationType, service_types::impls::MessageInfo, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -81,3 +82,76 @@ impl BinaryEncoder<RegisteredServer> for RegisteredServer {
         })
     }
 }
+
+impl XmlEncoder for RegisteredServer {
+    fn xml_type_name() -> String {
+        "RegisteredServer".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ServerUri", &self.server_uri);
+        write_xml_field(&mut element, "ProductUri", &self.product_uri);
+        write_xml_field(&mut element, "ServerNames", &self.server_names);
+        write_xml_field(&mut element, "ServerType", &self.server_type);
+        write_xml_field(&mut element, "GatewayServerUri", &self.gateway_server_uri);
+        write_xml_field(&mut element, "DiscoveryUrls", &self.discovery_urls);
+        write_xml_field(&mut element, "SemaphoreFilePath", &self.semaphore_file_path);
+        write_xml_field(&mut element, "IsOnline", &self.is_online);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RegisteredServer {
+            server_uri: read_xml_field(element, "ServerUri", decoding_options)?,
+            product_uri: read_xml_field(element, "ProductUri", decoding_options)?,
+            server_names: read_xml_field(element, "ServerNames", decoding_options)?,
+            server_type: read_xml_field(element, "ServerType", decoding_options)?,
+            gateway_server_uri: read_xml_field(element, "GatewayServerUri", decoding_options)?,
+            discovery_urls: read_xml_field(element, "DiscoveryUrls", decoding_options)?,
+            semaphore_file_path: read_xml_field(element, "SemaphoreFilePath", decoding_options)?,
+            is_online: read_xml_field(element, "IsOnline", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RegisteredServer {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerUri", &self.server_uri, encoding);
+        write_json_field(&mut object, "ProductUri", &self.product_uri, encoding);
+        write_json_field(&mut object, "ServerNames", &self.server_names, encoding);
+        write_json_field(&mut object, "ServerType", &self.server_type, encoding);
+        write_json_field(
+            &mut object,
+            "GatewayServerUri",
+            &self.gateway_server_uri,
+            encoding,
+        );
+        write_json_field(&mut object, "DiscoveryUrls", &self.discovery_urls, encoding);
+        write_json_field(
+            &mut object,
+            "SemaphoreFilePath",
+            &self.semaphore_file_path,
+            encoding,
+        );
+        write_json_field(&mut object, "IsOnline", &self.is_online, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RegisteredServer {
+            server_uri: read_json_field(value, "ServerUri", decoding_options)?,
+            product_uri: read_json_field(value, "ProductUri", decoding_options)?,
+            server_names: read_json_field(value, "ServerNames", decoding_options)?,
+            server_type: read_json_field(value, "ServerType", decoding_options)?,
+            gateway_server_uri: read_json_field(value, "GatewayServerUri", decoding_options)?,
+            discovery_urls: read_json_field(value, "DiscoveryUrls", decoding_options)?,
+            semaphore_file_path: read_json_field(value, "SemaphoreFilePath", decoding_options)?,
+            is_online: read_json_field(value, "IsOnline", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    qualified_name::QualifiedName, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    qualified_name::QualifiedName, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,60 @@ impl BinaryEncoder<RelativePathElement> for RelativePathElement {
         })
     }
 }
+
+impl XmlEncoder for RelativePathElement {
+    fn xml_type_name() -> String {
+        "RelativePathElement".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ReferenceTypeId", &self.reference_type_id);
+        write_xml_field(&mut element, "IsInverse", &self.is_inverse);
+        write_xml_field(&mut element, "IncludeSubtypes", &self.include_subtypes);
+        write_xml_field(&mut element, "TargetName", &self.target_name);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RelativePathElement {
+            reference_type_id: read_xml_field(element, "ReferenceTypeId", decoding_options)?,
+            is_inverse: read_xml_field(element, "IsInverse", decoding_options)?,
+            include_subtypes: read_xml_field(element, "IncludeSubtypes", decoding_options)?,
+            target_name: read_xml_field(element, "TargetName", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RelativePathElement {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ReferenceTypeId",
+            &self.reference_type_id,
+            encoding,
+        );
+        write_json_field(&mut object, "IsInverse", &self.is_inverse, encoding);
+        write_json_field(
+            &mut object,
+            "IncludeSubtypes",
+            &self.include_subtypes,
+            encoding,
+        );
+        write_json_field(&mut object, "TargetName", &self.target_name, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RelativePathElement {
+            reference_type_id: read_json_field(value, "ReferenceTypeId", decoding_options)?,
+            is_inverse: read_json_field(value, "IsInverse", decoding_options)?,
+            include_subtypes: read_json_field(value, "IncludeSubtypes", decoding_options)?,
+            target_name: read_json_field(value, "TargetName", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,68 @@ impl BinaryEncoder<RepublishRequest> for RepublishRequest {
         })
     }
 }
+
+impl XmlEncoder for RepublishRequest {
+    fn xml_type_name() -> String {
+        "RepublishRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(
+            &mut element,
+            "RetransmitSequenceNumber",
+            &self.retransmit_sequence_number,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RepublishRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            retransmit_sequence_number: read_xml_field(
+                element,
+                "RetransmitSequenceNumber",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for RepublishRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RetransmitSequenceNumber",
+            &self.retransmit_sequence_number,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RepublishRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            retransmit_sequence_number: read_json_field(
+                value,
+                "RetransmitSequenceNumber",
+                decoding_options,
+            )?,
+        })
+    }
+}
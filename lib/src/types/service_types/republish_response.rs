@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, response_header::ResponseHeader,
-    service_types::impls::MessageInfo, service_types::NotificationMessage,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, response_header::ResponseHeader,
+    service_types::impls::MessageInfo, service_types::NotificationMessage, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,56 @@ impl BinaryEncoder<RepublishResponse> for RepublishResponse {
         })
     }
 }
+
+impl XmlEncoder for RepublishResponse {
+    fn xml_type_name() -> String {
+        "RepublishResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(
+            &mut element,
+            "NotificationMessage",
+            &self.notification_message,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RepublishResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            notification_message: read_xml_field(element, "NotificationMessage", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RepublishResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NotificationMessage",
+            &self.notification_message,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RepublishResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            notification_message: read_json_field(value, "NotificationMessage", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::enums::PermissionType, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::enums::PermissionType, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<RolePermissionType> for RolePermissionType {
         })
     }
 }
+
+impl XmlEncoder for RolePermissionType {
+    fn xml_type_name() -> String {
+        "RolePermissionType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RoleId", &self.role_id);
+        write_xml_field(&mut element, "Permissions", &self.permissions);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(RolePermissionType {
+            role_id: read_xml_field(element, "RoleId", decoding_options)?,
+            permissions: read_xml_field(element, "Permissions", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for RolePermissionType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RoleId", &self.role_id, encoding);
+        write_json_field(&mut object, "Permissions", &self.permissions, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(RolePermissionType {
+            role_id: read_json_field(value, "RoleId", decoding_options)?,
+            permissions: read_json_field(value, "Permissions", decoding_options)?,
+        })
+    }
+}
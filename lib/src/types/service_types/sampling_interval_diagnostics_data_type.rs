@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -60,3 +61,98 @@ impl BinaryEncoder<SamplingIntervalDiagnosticsDataType> for SamplingIntervalDiag
         })
     }
 }
+
+impl XmlEncoder for SamplingIntervalDiagnosticsDataType {
+    fn xml_type_name() -> String {
+        "SamplingIntervalDiagnosticsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SamplingInterval", &self.sampling_interval);
+        write_xml_field(
+            &mut element,
+            "MonitoredItemCount",
+            &self.monitored_item_count,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxMonitoredItemCount",
+            &self.max_monitored_item_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DisabledMonitoredItemCount",
+            &self.disabled_monitored_item_count,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SamplingIntervalDiagnosticsDataType {
+            sampling_interval: read_xml_field(element, "SamplingInterval", decoding_options)?,
+            monitored_item_count: read_xml_field(element, "MonitoredItemCount", decoding_options)?,
+            max_monitored_item_count: read_xml_field(
+                element,
+                "MaxMonitoredItemCount",
+                decoding_options,
+            )?,
+            disabled_monitored_item_count: read_xml_field(
+                element,
+                "DisabledMonitoredItemCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for SamplingIntervalDiagnosticsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SamplingInterval",
+            &self.sampling_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoredItemCount",
+            &self.monitored_item_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxMonitoredItemCount",
+            &self.max_monitored_item_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DisabledMonitoredItemCount",
+            &self.disabled_monitored_item_count,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SamplingIntervalDiagnosticsDataType {
+            sampling_interval: read_json_field(value, "SamplingInterval", decoding_options)?,
+            monitored_item_count: read_json_field(value, "MonitoredItemCount", decoding_options)?,
+            max_monitored_item_count: read_json_field(
+                value,
+                "MaxMonitoredItemCount",
+                decoding_options,
+            )?,
+            disabled_monitored_item_count: read_json_field(
+                value,
+                "DisabledMonitoredItemCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<SemanticChangeStructureDataType> for SemanticChangeStructureD
         })
     }
 }
+
+impl XmlEncoder for SemanticChangeStructureDataType {
+    fn xml_type_name() -> String {
+        "SemanticChangeStructureDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Affected", &self.affected);
+        write_xml_field(&mut element, "AffectedType", &self.affected_type);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SemanticChangeStructureDataType {
+            affected: read_xml_field(element, "Affected", decoding_options)?,
+            affected_type: read_xml_field(element, "AffectedType", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SemanticChangeStructureDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Affected", &self.affected, encoding);
+        write_json_field(&mut object, "AffectedType", &self.affected_type, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SemanticChangeStructureDataType {
+            affected: read_json_field(value, "Affected", decoding_options)?,
+            affected_type: read_json_field(value, "AffectedType", decoding_options)?,
+        })
+    }
+}
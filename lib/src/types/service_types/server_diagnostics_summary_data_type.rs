@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -101,3 +102,254 @@ impl BinaryEncoder<ServerDiagnosticsSummaryDataType> for ServerDiagnosticsSummar
         })
     }
 }
+
+impl XmlEncoder for ServerDiagnosticsSummaryDataType {
+    fn xml_type_name() -> String {
+        "ServerDiagnosticsSummaryDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ServerViewCount", &self.server_view_count);
+        write_xml_field(
+            &mut element,
+            "CurrentSessionCount",
+            &self.current_session_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CumulatedSessionCount",
+            &self.cumulated_session_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SecurityRejectedSessionCount",
+            &self.security_rejected_session_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RejectedSessionCount",
+            &self.rejected_session_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SessionTimeoutCount",
+            &self.session_timeout_count,
+        );
+        write_xml_field(&mut element, "SessionAbortCount", &self.session_abort_count);
+        write_xml_field(
+            &mut element,
+            "CurrentSubscriptionCount",
+            &self.current_subscription_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CumulatedSubscriptionCount",
+            &self.cumulated_subscription_count,
+        );
+        write_xml_field(
+            &mut element,
+            "PublishingIntervalCount",
+            &self.publishing_interval_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SecurityRejectedRequestsCount",
+            &self.security_rejected_requests_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RejectedRequestsCount",
+            &self.rejected_requests_count,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ServerDiagnosticsSummaryDataType {
+            server_view_count: read_xml_field(element, "ServerViewCount", decoding_options)?,
+            current_session_count: read_xml_field(
+                element,
+                "CurrentSessionCount",
+                decoding_options,
+            )?,
+            cumulated_session_count: read_xml_field(
+                element,
+                "CumulatedSessionCount",
+                decoding_options,
+            )?,
+            security_rejected_session_count: read_xml_field(
+                element,
+                "SecurityRejectedSessionCount",
+                decoding_options,
+            )?,
+            rejected_session_count: read_xml_field(
+                element,
+                "RejectedSessionCount",
+                decoding_options,
+            )?,
+            session_timeout_count: read_xml_field(
+                element,
+                "SessionTimeoutCount",
+                decoding_options,
+            )?,
+            session_abort_count: read_xml_field(element, "SessionAbortCount", decoding_options)?,
+            current_subscription_count: read_xml_field(
+                element,
+                "CurrentSubscriptionCount",
+                decoding_options,
+            )?,
+            cumulated_subscription_count: read_xml_field(
+                element,
+                "CumulatedSubscriptionCount",
+                decoding_options,
+            )?,
+            publishing_interval_count: read_xml_field(
+                element,
+                "PublishingIntervalCount",
+                decoding_options,
+            )?,
+            security_rejected_requests_count: read_xml_field(
+                element,
+                "SecurityRejectedRequestsCount",
+                decoding_options,
+            )?,
+            rejected_requests_count: read_xml_field(
+                element,
+                "RejectedRequestsCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for ServerDiagnosticsSummaryDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ServerViewCount",
+            &self.server_view_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentSessionCount",
+            &self.current_session_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CumulatedSessionCount",
+            &self.cumulated_session_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityRejectedSessionCount",
+            &self.security_rejected_session_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RejectedSessionCount",
+            &self.rejected_session_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SessionTimeoutCount",
+            &self.session_timeout_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SessionAbortCount",
+            &self.session_abort_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentSubscriptionCount",
+            &self.current_subscription_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CumulatedSubscriptionCount",
+            &self.cumulated_subscription_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingIntervalCount",
+            &self.publishing_interval_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityRejectedRequestsCount",
+            &self.security_rejected_requests_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RejectedRequestsCount",
+            &self.rejected_requests_count,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ServerDiagnosticsSummaryDataType {
+            server_view_count: read_json_field(value, "ServerViewCount", decoding_options)?,
+            current_session_count: read_json_field(value, "CurrentSessionCount", decoding_options)?,
+            cumulated_session_count: read_json_field(
+                value,
+                "CumulatedSessionCount",
+                decoding_options,
+            )?,
+            security_rejected_session_count: read_json_field(
+                value,
+                "SecurityRejectedSessionCount",
+                decoding_options,
+            )?,
+            rejected_session_count: read_json_field(
+                value,
+                "RejectedSessionCount",
+                decoding_options,
+            )?,
+            session_timeout_count: read_json_field(value, "SessionTimeoutCount", decoding_options)?,
+            session_abort_count: read_json_field(value, "SessionAbortCount", decoding_options)?,
+            current_subscription_count: read_json_field(
+                value,
+                "CurrentSubscriptionCount",
+                decoding_options,
+            )?,
+            cumulated_subscription_count: read_json_field(
+                value,
+                "CumulatedSubscriptionCount",
+                decoding_options,
+            )?,
+            publishing_interval_count: read_json_field(
+                value,
+                "PublishingIntervalCount",
+                decoding_options,
+            )?,
+            security_rejected_requests_count: read_json_field(
+                value,
+                "SecurityRejectedRequestsCount",
+                decoding_options,
+            )?,
+            rejected_requests_count: read_json_field(
+                value,
+                "RejectedRequestsCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
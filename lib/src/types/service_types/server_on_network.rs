@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,59 @@ impl BinaryEncoder<ServerOnNetwork> for ServerOnNetwork {
         })
     }
 }
+
+impl XmlEncoder for ServerOnNetwork {
+    fn xml_type_name() -> String {
+        "ServerOnNetwork".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RecordId", &self.record_id);
+        write_xml_field(&mut element, "ServerName", &self.server_name);
+        write_xml_field(&mut element, "DiscoveryUrl", &self.discovery_url);
+        write_xml_field(
+            &mut element,
+            "ServerCapabilities",
+            &self.server_capabilities,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ServerOnNetwork {
+            record_id: read_xml_field(element, "RecordId", decoding_options)?,
+            server_name: read_xml_field(element, "ServerName", decoding_options)?,
+            discovery_url: read_xml_field(element, "DiscoveryUrl", decoding_options)?,
+            server_capabilities: read_xml_field(element, "ServerCapabilities", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ServerOnNetwork {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RecordId", &self.record_id, encoding);
+        write_json_field(&mut object, "ServerName", &self.server_name, encoding);
+        write_json_field(&mut object, "DiscoveryUrl", &self.discovery_url, encoding);
+        write_json_field(
+            &mut object,
+            "ServerCapabilities",
+            &self.server_capabilities,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ServerOnNetwork {
+            record_id: read_json_field(value, "RecordId", decoding_options)?,
+            server_name: read_json_field(value, "ServerName", decoding_options)?,
+            discovery_url: read_json_field(value, "DiscoveryUrl", decoding_options)?,
+            server_capabilities: read_json_field(value, "ServerCapabilities", decoding_options)?,
+        })
+    }
+}
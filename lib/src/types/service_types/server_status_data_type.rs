@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, localized_text::LocalizedText,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, localized_text::LocalizedText,
     node_ids::ObjectId, service_types::enums::ServerState, service_types::impls::MessageInfo,
-    service_types::BuildInfo,
+    service_types::BuildInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -72,3 +72,76 @@ impl BinaryEncoder<ServerStatusDataType> for ServerStatusDataType {
         })
     }
 }
+
+impl XmlEncoder for ServerStatusDataType {
+    fn xml_type_name() -> String {
+        "ServerStatusDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StartTime", &self.start_time);
+        write_xml_field(&mut element, "CurrentTime", &self.current_time);
+        write_xml_field(&mut element, "State", &self.state);
+        write_xml_field(&mut element, "BuildInfo", &self.build_info);
+        write_xml_field(
+            &mut element,
+            "SecondsTillShutdown",
+            &self.seconds_till_shutdown,
+        );
+        write_xml_field(&mut element, "ShutdownReason", &self.shutdown_reason);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ServerStatusDataType {
+            start_time: read_xml_field(element, "StartTime", decoding_options)?,
+            current_time: read_xml_field(element, "CurrentTime", decoding_options)?,
+            state: read_xml_field(element, "State", decoding_options)?,
+            build_info: read_xml_field(element, "BuildInfo", decoding_options)?,
+            seconds_till_shutdown: read_xml_field(
+                element,
+                "SecondsTillShutdown",
+                decoding_options,
+            )?,
+            shutdown_reason: read_xml_field(element, "ShutdownReason", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ServerStatusDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartTime", &self.start_time, encoding);
+        write_json_field(&mut object, "CurrentTime", &self.current_time, encoding);
+        write_json_field(&mut object, "State", &self.state, encoding);
+        write_json_field(&mut object, "BuildInfo", &self.build_info, encoding);
+        write_json_field(
+            &mut object,
+            "SecondsTillShutdown",
+            &self.seconds_till_shutdown,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ShutdownReason",
+            &self.shutdown_reason,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ServerStatusDataType {
+            start_time: read_json_field(value, "StartTime", decoding_options)?,
+            current_time: read_json_field(value, "CurrentTime", decoding_options)?,
+            state: read_json_field(value, "State", decoding_options)?,
+            build_info: read_json_field(value, "BuildInfo", decoding_options)?,
+            seconds_till_shutdown: read_json_field(value, "SecondsTillShutdown", decoding_options)?,
+            shutdown_reason: read_json_field(value, "ShutdownReason", decoding_options)?,
+        })
+    }
+}
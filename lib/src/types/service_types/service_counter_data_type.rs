@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +51,42 @@ impl BinaryEncoder<ServiceCounterDataType> for ServiceCounterDataType {
         })
     }
 }
+
+impl XmlEncoder for ServiceCounterDataType {
+    fn xml_type_name() -> String {
+        "ServiceCounterDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "TotalCount", &self.total_count);
+        write_xml_field(&mut element, "ErrorCount", &self.error_count);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ServiceCounterDataType {
+            total_count: read_xml_field(element, "TotalCount", decoding_options)?,
+            error_count: read_xml_field(element, "ErrorCount", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ServiceCounterDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TotalCount", &self.total_count, encoding);
+        write_json_field(&mut object, "ErrorCount", &self.error_count, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ServiceCounterDataType {
+            total_count: read_json_field(value, "TotalCount", decoding_options)?,
+            error_count: read_json_field(value, "ErrorCount", decoding_options)?,
+        })
+    }
+}
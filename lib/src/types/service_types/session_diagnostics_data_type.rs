@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId, node_ids::ObjectId,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
     service_types::impls::MessageInfo, service_types::ApplicationDescription,
-    service_types::ServiceCounterDataType, string::UAString,
+    service_types::ServiceCounterDataType, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -264,3 +264,627 @@ impl BinaryEncoder<SessionDiagnosticsDataType> for SessionDiagnosticsDataType {
         })
     }
 }
+
+impl XmlEncoder for SessionDiagnosticsDataType {
+    fn xml_type_name() -> String {
+        "SessionDiagnosticsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SessionId", &self.session_id);
+        write_xml_field(&mut element, "SessionName", &self.session_name);
+        write_xml_field(&mut element, "ClientDescription", &self.client_description);
+        write_xml_field(&mut element, "ServerUri", &self.server_uri);
+        write_xml_field(&mut element, "EndpointUrl", &self.endpoint_url);
+        write_xml_field(&mut element, "LocaleIds", &self.locale_ids);
+        write_xml_field(
+            &mut element,
+            "ActualSessionTimeout",
+            &self.actual_session_timeout,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxResponseMessageSize",
+            &self.max_response_message_size,
+        );
+        write_xml_field(
+            &mut element,
+            "ClientConnectionTime",
+            &self.client_connection_time,
+        );
+        write_xml_field(
+            &mut element,
+            "ClientLastContactTime",
+            &self.client_last_contact_time,
+        );
+        write_xml_field(
+            &mut element,
+            "CurrentSubscriptionsCount",
+            &self.current_subscriptions_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CurrentMonitoredItemsCount",
+            &self.current_monitored_items_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CurrentPublishRequestsInQueue",
+            &self.current_publish_requests_in_queue,
+        );
+        write_xml_field(&mut element, "TotalRequestCount", &self.total_request_count);
+        write_xml_field(
+            &mut element,
+            "UnauthorizedRequestCount",
+            &self.unauthorized_request_count,
+        );
+        write_xml_field(&mut element, "ReadCount", &self.read_count);
+        write_xml_field(&mut element, "HistoryReadCount", &self.history_read_count);
+        write_xml_field(&mut element, "WriteCount", &self.write_count);
+        write_xml_field(
+            &mut element,
+            "HistoryUpdateCount",
+            &self.history_update_count,
+        );
+        write_xml_field(&mut element, "CallCount", &self.call_count);
+        write_xml_field(
+            &mut element,
+            "CreateMonitoredItemsCount",
+            &self.create_monitored_items_count,
+        );
+        write_xml_field(
+            &mut element,
+            "ModifyMonitoredItemsCount",
+            &self.modify_monitored_items_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SetMonitoringModeCount",
+            &self.set_monitoring_mode_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SetTriggeringCount",
+            &self.set_triggering_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DeleteMonitoredItemsCount",
+            &self.delete_monitored_items_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CreateSubscriptionCount",
+            &self.create_subscription_count,
+        );
+        write_xml_field(
+            &mut element,
+            "ModifySubscriptionCount",
+            &self.modify_subscription_count,
+        );
+        write_xml_field(
+            &mut element,
+            "SetPublishingModeCount",
+            &self.set_publishing_mode_count,
+        );
+        write_xml_field(&mut element, "PublishCount", &self.publish_count);
+        write_xml_field(&mut element, "RepublishCount", &self.republish_count);
+        write_xml_field(
+            &mut element,
+            "TransferSubscriptionsCount",
+            &self.transfer_subscriptions_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DeleteSubscriptionsCount",
+            &self.delete_subscriptions_count,
+        );
+        write_xml_field(&mut element, "AddNodesCount", &self.add_nodes_count);
+        write_xml_field(
+            &mut element,
+            "AddReferencesCount",
+            &self.add_references_count,
+        );
+        write_xml_field(&mut element, "DeleteNodesCount", &self.delete_nodes_count);
+        write_xml_field(
+            &mut element,
+            "DeleteReferencesCount",
+            &self.delete_references_count,
+        );
+        write_xml_field(&mut element, "BrowseCount", &self.browse_count);
+        write_xml_field(&mut element, "BrowseNextCount", &self.browse_next_count);
+        write_xml_field(
+            &mut element,
+            "TranslateBrowsePathsToNodeIdsCount",
+            &self.translate_browse_paths_to_node_ids_count,
+        );
+        write_xml_field(&mut element, "QueryFirstCount", &self.query_first_count);
+        write_xml_field(&mut element, "QueryNextCount", &self.query_next_count);
+        write_xml_field(
+            &mut element,
+            "RegisterNodesCount",
+            &self.register_nodes_count,
+        );
+        write_xml_field(
+            &mut element,
+            "UnregisterNodesCount",
+            &self.unregister_nodes_count,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SessionDiagnosticsDataType {
+            session_id: read_xml_field(element, "SessionId", decoding_options)?,
+            session_name: read_xml_field(element, "SessionName", decoding_options)?,
+            client_description: read_xml_field(element, "ClientDescription", decoding_options)?,
+            server_uri: read_xml_field(element, "ServerUri", decoding_options)?,
+            endpoint_url: read_xml_field(element, "EndpointUrl", decoding_options)?,
+            locale_ids: read_xml_field(element, "LocaleIds", decoding_options)?,
+            actual_session_timeout: read_xml_field(
+                element,
+                "ActualSessionTimeout",
+                decoding_options,
+            )?,
+            max_response_message_size: read_xml_field(
+                element,
+                "MaxResponseMessageSize",
+                decoding_options,
+            )?,
+            client_connection_time: read_xml_field(
+                element,
+                "ClientConnectionTime",
+                decoding_options,
+            )?,
+            client_last_contact_time: read_xml_field(
+                element,
+                "ClientLastContactTime",
+                decoding_options,
+            )?,
+            current_subscriptions_count: read_xml_field(
+                element,
+                "CurrentSubscriptionsCount",
+                decoding_options,
+            )?,
+            current_monitored_items_count: read_xml_field(
+                element,
+                "CurrentMonitoredItemsCount",
+                decoding_options,
+            )?,
+            current_publish_requests_in_queue: read_xml_field(
+                element,
+                "CurrentPublishRequestsInQueue",
+                decoding_options,
+            )?,
+            total_request_count: read_xml_field(element, "TotalRequestCount", decoding_options)?,
+            unauthorized_request_count: read_xml_field(
+                element,
+                "UnauthorizedRequestCount",
+                decoding_options,
+            )?,
+            read_count: read_xml_field(element, "ReadCount", decoding_options)?,
+            history_read_count: read_xml_field(element, "HistoryReadCount", decoding_options)?,
+            write_count: read_xml_field(element, "WriteCount", decoding_options)?,
+            history_update_count: read_xml_field(element, "HistoryUpdateCount", decoding_options)?,
+            call_count: read_xml_field(element, "CallCount", decoding_options)?,
+            create_monitored_items_count: read_xml_field(
+                element,
+                "CreateMonitoredItemsCount",
+                decoding_options,
+            )?,
+            modify_monitored_items_count: read_xml_field(
+                element,
+                "ModifyMonitoredItemsCount",
+                decoding_options,
+            )?,
+            set_monitoring_mode_count: read_xml_field(
+                element,
+                "SetMonitoringModeCount",
+                decoding_options,
+            )?,
+            set_triggering_count: read_xml_field(element, "SetTriggeringCount", decoding_options)?,
+            delete_monitored_items_count: read_xml_field(
+                element,
+                "DeleteMonitoredItemsCount",
+                decoding_options,
+            )?,
+            create_subscription_count: read_xml_field(
+                element,
+                "CreateSubscriptionCount",
+                decoding_options,
+            )?,
+            modify_subscription_count: read_xml_field(
+                element,
+                "ModifySubscriptionCount",
+                decoding_options,
+            )?,
+            set_publishing_mode_count: read_xml_field(
+                element,
+                "SetPublishingModeCount",
+                decoding_options,
+            )?,
+            publish_count: read_xml_field(element, "PublishCount", decoding_options)?,
+            republish_count: read_xml_field(element, "RepublishCount", decoding_options)?,
+            transfer_subscriptions_count: read_xml_field(
+                element,
+                "TransferSubscriptionsCount",
+                decoding_options,
+            )?,
+            delete_subscriptions_count: read_xml_field(
+                element,
+                "DeleteSubscriptionsCount",
+                decoding_options,
+            )?,
+            add_nodes_count: read_xml_field(element, "AddNodesCount", decoding_options)?,
+            add_references_count: read_xml_field(element, "AddReferencesCount", decoding_options)?,
+            delete_nodes_count: read_xml_field(element, "DeleteNodesCount", decoding_options)?,
+            delete_references_count: read_xml_field(
+                element,
+                "DeleteReferencesCount",
+                decoding_options,
+            )?,
+            browse_count: read_xml_field(element, "BrowseCount", decoding_options)?,
+            browse_next_count: read_xml_field(element, "BrowseNextCount", decoding_options)?,
+            translate_browse_paths_to_node_ids_count: read_xml_field(
+                element,
+                "TranslateBrowsePathsToNodeIdsCount",
+                decoding_options,
+            )?,
+            query_first_count: read_xml_field(element, "QueryFirstCount", decoding_options)?,
+            query_next_count: read_xml_field(element, "QueryNextCount", decoding_options)?,
+            register_nodes_count: read_xml_field(element, "RegisterNodesCount", decoding_options)?,
+            unregister_nodes_count: read_xml_field(
+                element,
+                "UnregisterNodesCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for SessionDiagnosticsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SessionId", &self.session_id, encoding);
+        write_json_field(&mut object, "SessionName", &self.session_name, encoding);
+        write_json_field(
+            &mut object,
+            "ClientDescription",
+            &self.client_description,
+            encoding,
+        );
+        write_json_field(&mut object, "ServerUri", &self.server_uri, encoding);
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, encoding);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, encoding);
+        write_json_field(
+            &mut object,
+            "ActualSessionTimeout",
+            &self.actual_session_timeout,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxResponseMessageSize",
+            &self.max_response_message_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ClientConnectionTime",
+            &self.client_connection_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ClientLastContactTime",
+            &self.client_last_contact_time,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentSubscriptionsCount",
+            &self.current_subscriptions_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentMonitoredItemsCount",
+            &self.current_monitored_items_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentPublishRequestsInQueue",
+            &self.current_publish_requests_in_queue,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TotalRequestCount",
+            &self.total_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UnauthorizedRequestCount",
+            &self.unauthorized_request_count,
+            encoding,
+        );
+        write_json_field(&mut object, "ReadCount", &self.read_count, encoding);
+        write_json_field(
+            &mut object,
+            "HistoryReadCount",
+            &self.history_read_count,
+            encoding,
+        );
+        write_json_field(&mut object, "WriteCount", &self.write_count, encoding);
+        write_json_field(
+            &mut object,
+            "HistoryUpdateCount",
+            &self.history_update_count,
+            encoding,
+        );
+        write_json_field(&mut object, "CallCount", &self.call_count, encoding);
+        write_json_field(
+            &mut object,
+            "CreateMonitoredItemsCount",
+            &self.create_monitored_items_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ModifyMonitoredItemsCount",
+            &self.modify_monitored_items_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SetMonitoringModeCount",
+            &self.set_monitoring_mode_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SetTriggeringCount",
+            &self.set_triggering_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DeleteMonitoredItemsCount",
+            &self.delete_monitored_items_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CreateSubscriptionCount",
+            &self.create_subscription_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ModifySubscriptionCount",
+            &self.modify_subscription_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SetPublishingModeCount",
+            &self.set_publishing_mode_count,
+            encoding,
+        );
+        write_json_field(&mut object, "PublishCount", &self.publish_count, encoding);
+        write_json_field(
+            &mut object,
+            "RepublishCount",
+            &self.republish_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransferSubscriptionsCount",
+            &self.transfer_subscriptions_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DeleteSubscriptionsCount",
+            &self.delete_subscriptions_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AddNodesCount",
+            &self.add_nodes_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AddReferencesCount",
+            &self.add_references_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DeleteNodesCount",
+            &self.delete_nodes_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DeleteReferencesCount",
+            &self.delete_references_count,
+            encoding,
+        );
+        write_json_field(&mut object, "BrowseCount", &self.browse_count, encoding);
+        write_json_field(
+            &mut object,
+            "BrowseNextCount",
+            &self.browse_next_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TranslateBrowsePathsToNodeIdsCount",
+            &self.translate_browse_paths_to_node_ids_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "QueryFirstCount",
+            &self.query_first_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "QueryNextCount",
+            &self.query_next_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RegisterNodesCount",
+            &self.register_nodes_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UnregisterNodesCount",
+            &self.unregister_nodes_count,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SessionDiagnosticsDataType {
+            session_id: read_json_field(value, "SessionId", decoding_options)?,
+            session_name: read_json_field(value, "SessionName", decoding_options)?,
+            client_description: read_json_field(value, "ClientDescription", decoding_options)?,
+            server_uri: read_json_field(value, "ServerUri", decoding_options)?,
+            endpoint_url: read_json_field(value, "EndpointUrl", decoding_options)?,
+            locale_ids: read_json_field(value, "LocaleIds", decoding_options)?,
+            actual_session_timeout: read_json_field(
+                value,
+                "ActualSessionTimeout",
+                decoding_options,
+            )?,
+            max_response_message_size: read_json_field(
+                value,
+                "MaxResponseMessageSize",
+                decoding_options,
+            )?,
+            client_connection_time: read_json_field(
+                value,
+                "ClientConnectionTime",
+                decoding_options,
+            )?,
+            client_last_contact_time: read_json_field(
+                value,
+                "ClientLastContactTime",
+                decoding_options,
+            )?,
+            current_subscriptions_count: read_json_field(
+                value,
+                "CurrentSubscriptionsCount",
+                decoding_options,
+            )?,
+            current_monitored_items_count: read_json_field(
+                value,
+                "CurrentMonitoredItemsCount",
+                decoding_options,
+            )?,
+            current_publish_requests_in_queue: read_json_field(
+                value,
+                "CurrentPublishRequestsInQueue",
+                decoding_options,
+            )?,
+            total_request_count: read_json_field(value, "TotalRequestCount", decoding_options)?,
+            unauthorized_request_count: read_json_field(
+                value,
+                "UnauthorizedRequestCount",
+                decoding_options,
+            )?,
+            read_count: read_json_field(value, "ReadCount", decoding_options)?,
+            history_read_count: read_json_field(value, "HistoryReadCount", decoding_options)?,
+            write_count: read_json_field(value, "WriteCount", decoding_options)?,
+            history_update_count: read_json_field(value, "HistoryUpdateCount", decoding_options)?,
+            call_count: read_json_field(value, "CallCount", decoding_options)?,
+            create_monitored_items_count: read_json_field(
+                value,
+                "CreateMonitoredItemsCount",
+                decoding_options,
+            )?,
+            modify_monitored_items_count: read_json_field(
+                value,
+                "ModifyMonitoredItemsCount",
+                decoding_options,
+            )?,
+            set_monitoring_mode_count: read_json_field(
+                value,
+                "SetMonitoringModeCount",
+                decoding_options,
+            )?,
+            set_triggering_count: read_json_field(value, "SetTriggeringCount", decoding_options)?,
+            delete_monitored_items_count: read_json_field(
+                value,
+                "DeleteMonitoredItemsCount",
+                decoding_options,
+            )?,
+            create_subscription_count: read_json_field(
+                value,
+                "CreateSubscriptionCount",
+                decoding_options,
+            )?,
+            modify_subscription_count: read_json_field(
+                value,
+                "ModifySubscriptionCount",
+                decoding_options,
+            )?,
+            set_publishing_mode_count: read_json_field(
+                value,
+                "SetPublishingModeCount",
+                decoding_options,
+            )?,
+            publish_count: read_json_field(value, "PublishCount", decoding_options)?,
+            republish_count: read_json_field(value, "RepublishCount", decoding_options)?,
+            transfer_subscriptions_count: read_json_field(
+                value,
+                "TransferSubscriptionsCount",
+                decoding_options,
+            )?,
+            delete_subscriptions_count: read_json_field(
+                value,
+                "DeleteSubscriptionsCount",
+                decoding_options,
+            )?,
+            add_nodes_count: read_json_field(value, "AddNodesCount", decoding_options)?,
+            add_references_count: read_json_field(value, "AddReferencesCount", decoding_options)?,
+            delete_nodes_count: read_json_field(value, "DeleteNodesCount", decoding_options)?,
+            delete_references_count: read_json_field(
+                value,
+                "DeleteReferencesCount",
+                decoding_options,
+            )?,
+            browse_count: read_json_field(value, "BrowseCount", decoding_options)?,
+            browse_next_count: read_json_field(value, "BrowseNextCount", decoding_options)?,
+            translate_browse_paths_to_node_ids_count: read_json_field(
+                value,
+                "TranslateBrowsePathsToNodeIdsCount",
+                decoding_options,
+            )?,
+            query_first_count: read_json_field(value, "QueryFirstCount", decoding_options)?,
+            query_next_count: read_json_field(value, "QueryNextCount", decoding_options)?,
+            register_nodes_count: read_json_field(value, "RegisterNodesCount", decoding_options)?,
+            unregister_nodes_count: read_json_field(
+                value,
+                "UnregisterNodesCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
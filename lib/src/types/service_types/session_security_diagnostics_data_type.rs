@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::enums::MessageSecurityMode, service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_id::NodeId,
+    node_ids::ObjectId, service_types::enums::MessageSecurityMode,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -86,3 +87,136 @@ impl BinaryEncoder<SessionSecurityDiagnosticsDataType> for SessionSecurityDiagno
         })
     }
 }
+
+impl XmlEncoder for SessionSecurityDiagnosticsDataType {
+    fn xml_type_name() -> String {
+        "SessionSecurityDiagnosticsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SessionId", &self.session_id);
+        write_xml_field(
+            &mut element,
+            "ClientUserIdOfSession",
+            &self.client_user_id_of_session,
+        );
+        write_xml_field(
+            &mut element,
+            "ClientUserIdHistory",
+            &self.client_user_id_history,
+        );
+        write_xml_field(
+            &mut element,
+            "AuthenticationMechanism",
+            &self.authentication_mechanism,
+        );
+        write_xml_field(&mut element, "Encoding", &self.encoding);
+        write_xml_field(&mut element, "TransportProtocol", &self.transport_protocol);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityPolicyUri", &self.security_policy_uri);
+        write_xml_field(&mut element, "ClientCertificate", &self.client_certificate);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SessionSecurityDiagnosticsDataType {
+            session_id: read_xml_field(element, "SessionId", decoding_options)?,
+            client_user_id_of_session: read_xml_field(
+                element,
+                "ClientUserIdOfSession",
+                decoding_options,
+            )?,
+            client_user_id_history: read_xml_field(
+                element,
+                "ClientUserIdHistory",
+                decoding_options,
+            )?,
+            authentication_mechanism: read_xml_field(
+                element,
+                "AuthenticationMechanism",
+                decoding_options,
+            )?,
+            encoding: read_xml_field(element, "Encoding", decoding_options)?,
+            transport_protocol: read_xml_field(element, "TransportProtocol", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_xml_field(element, "SecurityPolicyUri", decoding_options)?,
+            client_certificate: read_xml_field(element, "ClientCertificate", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SessionSecurityDiagnosticsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SessionId", &self.session_id, encoding);
+        write_json_field(
+            &mut object,
+            "ClientUserIdOfSession",
+            &self.client_user_id_of_session,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ClientUserIdHistory",
+            &self.client_user_id_history,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "AuthenticationMechanism",
+            &self.authentication_mechanism,
+            encoding,
+        );
+        write_json_field(&mut object, "Encoding", &self.encoding, encoding);
+        write_json_field(
+            &mut object,
+            "TransportProtocol",
+            &self.transport_protocol,
+            encoding,
+        );
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityPolicyUri",
+            &self.security_policy_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ClientCertificate",
+            &self.client_certificate,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SessionSecurityDiagnosticsDataType {
+            session_id: read_json_field(value, "SessionId", decoding_options)?,
+            client_user_id_of_session: read_json_field(
+                value,
+                "ClientUserIdOfSession",
+                decoding_options,
+            )?,
+            client_user_id_history: read_json_field(
+                value,
+                "ClientUserIdHistory",
+                decoding_options,
+            )?,
+            authentication_mechanism: read_json_field(
+                value,
+                "AuthenticationMechanism",
+                decoding_options,
+            )?,
+            encoding: read_json_field(value, "Encoding", decoding_options)?,
+            transport_protocol: read_json_field(value, "TransportProtocol", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_policy_uri: read_json_field(value, "SecurityPolicyUri", decoding_options)?,
+            client_certificate: read_json_field(value, "ClientCertificate", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,54 @@ impl BinaryEncoder<SessionlessInvokeRequestType> for SessionlessInvokeRequestTyp
         })
     }
 }
+
+impl XmlEncoder for SessionlessInvokeRequestType {
+    fn xml_type_name() -> String {
+        "SessionlessInvokeRequestType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "UrisVersion", &self.uris_version);
+        write_xml_field(&mut element, "NamespaceUris", &self.namespace_uris);
+        write_xml_field(&mut element, "ServerUris", &self.server_uris);
+        write_xml_field(&mut element, "LocaleIds", &self.locale_ids);
+        write_xml_field(&mut element, "ServiceId", &self.service_id);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SessionlessInvokeRequestType {
+            uris_version: read_xml_field(element, "UrisVersion", decoding_options)?,
+            namespace_uris: read_xml_field(element, "NamespaceUris", decoding_options)?,
+            server_uris: read_xml_field(element, "ServerUris", decoding_options)?,
+            locale_ids: read_xml_field(element, "LocaleIds", decoding_options)?,
+            service_id: read_xml_field(element, "ServiceId", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SessionlessInvokeRequestType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "UrisVersion", &self.uris_version, encoding);
+        write_json_field(&mut object, "NamespaceUris", &self.namespace_uris, encoding);
+        write_json_field(&mut object, "ServerUris", &self.server_uris, encoding);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, encoding);
+        write_json_field(&mut object, "ServiceId", &self.service_id, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SessionlessInvokeRequestType {
+            uris_version: read_json_field(value, "UrisVersion", decoding_options)?,
+            namespace_uris: read_json_field(value, "NamespaceUris", decoding_options)?,
+            server_uris: read_json_field(value, "ServerUris", decoding_options)?,
+            locale_ids: read_json_field(value, "LocaleIds", decoding_options)?,
+            service_id: read_json_field(value, "ServiceId", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<SessionlessInvokeResponseType> for SessionlessInvokeResponseT
         })
     }
 }
+
+impl XmlEncoder for SessionlessInvokeResponseType {
+    fn xml_type_name() -> String {
+        "SessionlessInvokeResponseType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NamespaceUris", &self.namespace_uris);
+        write_xml_field(&mut element, "ServerUris", &self.server_uris);
+        write_xml_field(&mut element, "ServiceId", &self.service_id);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SessionlessInvokeResponseType {
+            namespace_uris: read_xml_field(element, "NamespaceUris", decoding_options)?,
+            server_uris: read_xml_field(element, "ServerUris", decoding_options)?,
+            service_id: read_xml_field(element, "ServiceId", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SessionlessInvokeResponseType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NamespaceUris", &self.namespace_uris, encoding);
+        write_json_field(&mut object, "ServerUris", &self.server_uris, encoding);
+        write_json_field(&mut object, "ServiceId", &self.service_id, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SessionlessInvokeResponseType {
+            namespace_uris: read_json_field(value, "NamespaceUris", decoding_options)?,
+            server_uris: read_json_field(value, "ServerUris", decoding_options)?,
+            service_id: read_json_field(value, "ServiceId", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::enums::MonitoringMode, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::enums::MonitoringMode, service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,65 @@ impl BinaryEncoder<SetMonitoringModeRequest> for SetMonitoringModeRequest {
         })
     }
 }
+
+impl XmlEncoder for SetMonitoringModeRequest {
+    fn xml_type_name() -> String {
+        "SetMonitoringModeRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(&mut element, "MonitoringMode", &self.monitoring_mode);
+        write_xml_field(&mut element, "MonitoredItemIds", &self.monitored_item_ids);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SetMonitoringModeRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            monitoring_mode: read_xml_field(element, "MonitoringMode", decoding_options)?,
+            monitored_item_ids: read_xml_field(element, "MonitoredItemIds", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SetMonitoringModeRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoringMode",
+            &self.monitoring_mode,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoredItemIds",
+            &self.monitored_item_ids,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SetMonitoringModeRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            monitoring_mode: read_json_field(value, "MonitoringMode", decoding_options)?,
+            monitored_item_ids: read_json_field(value, "MonitoredItemIds", decoding_options)?,
+        })
+    }
+}
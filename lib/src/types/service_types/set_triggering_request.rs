@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,69 @@ impl BinaryEncoder<SetTriggeringRequest> for SetTriggeringRequest {
         })
     }
 }
+
+impl XmlEncoder for SetTriggeringRequest {
+    fn xml_type_name() -> String {
+        "SetTriggeringRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(&mut element, "TriggeringItemId", &self.triggering_item_id);
+        write_xml_field(&mut element, "LinksToAdd", &self.links_to_add);
+        write_xml_field(&mut element, "LinksToRemove", &self.links_to_remove);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SetTriggeringRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            triggering_item_id: read_xml_field(element, "TriggeringItemId", decoding_options)?,
+            links_to_add: read_xml_field(element, "LinksToAdd", decoding_options)?,
+            links_to_remove: read_xml_field(element, "LinksToRemove", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SetTriggeringRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TriggeringItemId",
+            &self.triggering_item_id,
+            encoding,
+        );
+        write_json_field(&mut object, "LinksToAdd", &self.links_to_add, encoding);
+        write_json_field(
+            &mut object,
+            "LinksToRemove",
+            &self.links_to_remove,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SetTriggeringRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            triggering_item_id: read_json_field(value, "TriggeringItemId", decoding_options)?,
+            links_to_add: read_json_field(value, "LinksToAdd", decoding_options)?,
+            links_to_remove: read_json_field(value, "LinksToRemove", decoding_options)?,
+        })
+    }
+}
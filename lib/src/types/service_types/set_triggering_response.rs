@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
     response_header::ResponseHeader, service_types::impls::MessageInfo, status_codes::StatusCode,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -68,3 +69,85 @@ impl BinaryEncoder<SetTriggeringResponse> for SetTriggeringResponse {
         })
     }
 }
+
+impl XmlEncoder for SetTriggeringResponse {
+    fn xml_type_name() -> String {
+        "SetTriggeringResponse".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ResponseHeader", &self.response_header);
+        write_xml_field(&mut element, "AddResults", &self.add_results);
+        write_xml_field(
+            &mut element,
+            "AddDiagnosticInfos",
+            &self.add_diagnostic_infos,
+        );
+        write_xml_field(&mut element, "RemoveResults", &self.remove_results);
+        write_xml_field(
+            &mut element,
+            "RemoveDiagnosticInfos",
+            &self.remove_diagnostic_infos,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SetTriggeringResponse {
+            response_header: read_xml_field(element, "ResponseHeader", decoding_options)?,
+            add_results: read_xml_field(element, "AddResults", decoding_options)?,
+            add_diagnostic_infos: read_xml_field(element, "AddDiagnosticInfos", decoding_options)?,
+            remove_results: read_xml_field(element, "RemoveResults", decoding_options)?,
+            remove_diagnostic_infos: read_xml_field(
+                element,
+                "RemoveDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for SetTriggeringResponse {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ResponseHeader",
+            &self.response_header,
+            encoding,
+        );
+        write_json_field(&mut object, "AddResults", &self.add_results, encoding);
+        write_json_field(
+            &mut object,
+            "AddDiagnosticInfos",
+            &self.add_diagnostic_infos,
+            encoding,
+        );
+        write_json_field(&mut object, "RemoveResults", &self.remove_results, encoding);
+        write_json_field(
+            &mut object,
+            "RemoveDiagnosticInfos",
+            &self.remove_diagnostic_infos,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SetTriggeringResponse {
+            response_header: read_json_field(value, "ResponseHeader", decoding_options)?,
+            add_results: read_json_field(value, "AddResults", decoding_options)?,
+            add_diagnostic_infos: read_json_field(value, "AddDiagnosticInfos", decoding_options)?,
+            remove_results: read_json_field(value, "RemoveResults", decoding_options)?,
+            remove_diagnostic_infos: read_json_field(
+                value,
+                "RemoveDiagnosticInfos",
+                decoding_options,
+            )?,
+        })
+    }
+}
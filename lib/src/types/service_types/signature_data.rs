@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<SignatureData> for SignatureData {
         })
     }
 }
+
+impl XmlEncoder for SignatureData {
+    fn xml_type_name() -> String {
+        "SignatureData".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Algorithm", &self.algorithm);
+        write_xml_field(&mut element, "Signature", &self.signature);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SignatureData {
+            algorithm: read_xml_field(element, "Algorithm", decoding_options)?,
+            signature: read_xml_field(element, "Signature", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SignatureData {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Algorithm", &self.algorithm, encoding);
+        write_json_field(&mut object, "Signature", &self.signature, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SignatureData {
+            algorithm: read_json_field(value, "Algorithm", decoding_options)?,
+            signature: read_json_field(value, "Signature", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<SignedSoftwareCertificate> for SignedSoftwareCertificate {
         })
     }
 }
+
+impl XmlEncoder for SignedSoftwareCertificate {
+    fn xml_type_name() -> String {
+        "SignedSoftwareCertificate".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "CertificateData", &self.certificate_data);
+        write_xml_field(&mut element, "Signature", &self.signature);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SignedSoftwareCertificate {
+            certificate_data: read_xml_field(element, "CertificateData", decoding_options)?,
+            signature: read_xml_field(element, "Signature", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SignedSoftwareCertificate {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "CertificateData",
+            &self.certificate_data,
+            encoding,
+        );
+        write_json_field(&mut object, "Signature", &self.signature, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SignedSoftwareCertificate {
+            certificate_data: read_json_field(value, "CertificateData", decoding_options)?,
+            signature: read_json_field(value, "Signature", decoding_options)?,
+        })
+    }
+}
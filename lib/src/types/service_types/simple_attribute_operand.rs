@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, qualified_name::QualifiedName, string::UAString,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, qualified_name::QualifiedName,
+    string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -55,3 +56,55 @@ impl BinaryEncoder<SimpleAttributeOperand> for SimpleAttributeOperand {
         })
     }
 }
+
+impl XmlEncoder for SimpleAttributeOperand {
+    fn xml_type_name() -> String {
+        "SimpleAttributeOperand".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "TypeDefinitionId", &self.type_definition_id);
+        write_xml_field(&mut element, "BrowsePath", &self.browse_path);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SimpleAttributeOperand {
+            type_definition_id: read_xml_field(element, "TypeDefinitionId", decoding_options)?,
+            browse_path: read_xml_field(element, "BrowsePath", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SimpleAttributeOperand {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "TypeDefinitionId",
+            &self.type_definition_id,
+            encoding,
+        );
+        write_json_field(&mut object, "BrowsePath", &self.browse_path, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SimpleAttributeOperand {
+            type_definition_id: read_json_field(value, "TypeDefinitionId", decoding_options)?,
+            browse_path: read_json_field(value, "BrowsePath", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+        })
+    }
+}
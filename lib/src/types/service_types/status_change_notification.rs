@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*,
+    status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -44,3 +45,47 @@ impl BinaryEncoder<StatusChangeNotification> for StatusChangeNotification {
         })
     }
 }
+
+impl XmlEncoder for StatusChangeNotification {
+    fn xml_type_name() -> String {
+        "StatusChangeNotification".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Status", &self.status);
+        write_xml_field(&mut element, "DiagnosticInfo", &self.diagnostic_info);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(StatusChangeNotification {
+            status: read_xml_field(element, "Status", decoding_options)?,
+            diagnostic_info: read_xml_field(element, "DiagnosticInfo", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for StatusChangeNotification {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Status", &self.status, encoding);
+        write_json_field(
+            &mut object,
+            "DiagnosticInfo",
+            &self.diagnostic_info,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(StatusChangeNotification {
+            status: read_json_field(value, "Status", decoding_options)?,
+            diagnostic_info: read_json_field(value, "DiagnosticInfo", decoding_options)?,
+        })
+    }
+}
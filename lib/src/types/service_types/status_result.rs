@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo, status_codes::StatusCode,
+    basic_types::*, diagnostic_info::DiagnosticInfo, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,47 @@ impl BinaryEncoder<StatusResult> for StatusResult {
         })
     }
 }
+
+impl XmlEncoder for StatusResult {
+    fn xml_type_name() -> String {
+        "StatusResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(&mut element, "DiagnosticInfo", &self.diagnostic_info);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(StatusResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            diagnostic_info: read_xml_field(element, "DiagnosticInfo", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for StatusResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "DiagnosticInfo",
+            &self.diagnostic_info,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(StatusResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            diagnostic_info: read_json_field(value, "DiagnosticInfo", decoding_options)?,
+        })
+    }
+}
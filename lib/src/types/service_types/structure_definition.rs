@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, service_types::enums::StructureType,
-    service_types::StructureField,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, service_types::enums::StructureType,
+    service_types::StructureField, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,55 @@ impl BinaryEncoder<StructureDefinition> for StructureDefinition {
         })
     }
 }
+
+impl XmlEncoder for StructureDefinition {
+    fn xml_type_name() -> String {
+        "StructureDefinition".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DefaultEncodingId", &self.default_encoding_id);
+        write_xml_field(&mut element, "BaseDataType", &self.base_data_type);
+        write_xml_field(&mut element, "StructureType", &self.structure_type);
+        write_xml_field(&mut element, "Fields", &self.fields);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(StructureDefinition {
+            default_encoding_id: read_xml_field(element, "DefaultEncodingId", decoding_options)?,
+            base_data_type: read_xml_field(element, "BaseDataType", decoding_options)?,
+            structure_type: read_xml_field(element, "StructureType", decoding_options)?,
+            fields: read_xml_field(element, "Fields", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for StructureDefinition {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "DefaultEncodingId",
+            &self.default_encoding_id,
+            encoding,
+        );
+        write_json_field(&mut object, "BaseDataType", &self.base_data_type, encoding);
+        write_json_field(&mut object, "StructureType", &self.structure_type, encoding);
+        write_json_field(&mut object, "Fields", &self.fields, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(StructureDefinition {
+            default_encoding_id: read_json_field(value, "DefaultEncodingId", decoding_options)?,
+            base_data_type: read_json_field(value, "BaseDataType", decoding_options)?,
+            structure_type: read_json_field(value, "StructureType", decoding_options)?,
+            fields: read_json_field(value, "Fields", decoding_options)?,
+        })
+    }
+}
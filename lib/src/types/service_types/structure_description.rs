@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, qualified_name::QualifiedName,
-    service_types::StructureDefinition,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, qualified_name::QualifiedName,
+    service_types::StructureDefinition, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,55 @@ impl BinaryEncoder<StructureDescription> for StructureDescription {
         })
     }
 }
+
+impl XmlEncoder for StructureDescription {
+    fn xml_type_name() -> String {
+        "StructureDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "DataTypeId", &self.data_type_id);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(
+            &mut element,
+            "StructureDefinition",
+            &self.structure_definition,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(StructureDescription {
+            data_type_id: read_xml_field(element, "DataTypeId", decoding_options)?,
+            name: read_xml_field(element, "Name", decoding_options)?,
+            structure_definition: read_xml_field(element, "StructureDefinition", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for StructureDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, encoding);
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(
+            &mut object,
+            "StructureDefinition",
+            &self.structure_definition,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(StructureDescription {
+            data_type_id: read_json_field(value, "DataTypeId", decoding_options)?,
+            name: read_json_field(value, "Name", decoding_options)?,
+            structure_definition: read_json_field(value, "StructureDefinition", decoding_options)?,
+        })
+    }
+}
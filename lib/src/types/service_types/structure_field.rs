@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_id::NodeId,
-    node_ids::ObjectId, service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_id::NodeId,
+    node_ids::ObjectId, service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -77,3 +77,72 @@ impl BinaryEncoder<StructureField> for StructureField {
         })
     }
 }
+
+impl XmlEncoder for StructureField {
+    fn xml_type_name() -> String {
+        "StructureField".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "DataType", &self.data_type);
+        write_xml_field(&mut element, "ValueRank", &self.value_rank);
+        write_xml_field(&mut element, "ArrayDimensions", &self.array_dimensions);
+        write_xml_field(&mut element, "MaxStringLength", &self.max_string_length);
+        write_xml_field(&mut element, "IsOptional", &self.is_optional);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(StructureField {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            data_type: read_xml_field(element, "DataType", decoding_options)?,
+            value_rank: read_xml_field(element, "ValueRank", decoding_options)?,
+            array_dimensions: read_xml_field(element, "ArrayDimensions", decoding_options)?,
+            max_string_length: read_xml_field(element, "MaxStringLength", decoding_options)?,
+            is_optional: read_xml_field(element, "IsOptional", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for StructureField {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "DataType", &self.data_type, encoding);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, encoding);
+        write_json_field(
+            &mut object,
+            "ArrayDimensions",
+            &self.array_dimensions,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxStringLength",
+            &self.max_string_length,
+            encoding,
+        );
+        write_json_field(&mut object, "IsOptional", &self.is_optional, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(StructureField {
+            name: read_json_field(value, "Name", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            data_type: read_json_field(value, "DataType", decoding_options)?,
+            value_rank: read_json_field(value, "ValueRank", decoding_options)?,
+            array_dimensions: read_json_field(value, "ArrayDimensions", decoding_options)?,
+            max_string_length: read_json_field(value, "MaxStringLength", decoding_options)?,
+            is_optional: read_json_field(value, "IsOptional", decoding_options)?,
+        })
+    }
+}
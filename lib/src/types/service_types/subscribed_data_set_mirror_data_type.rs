@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::RolePermissionType, string::UAString,
+    basic_types::*, encoding::*, json::*, service_types::RolePermissionType, string::UAString,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -45,3 +46,52 @@ impl BinaryEncoder<SubscribedDataSetMirrorDataType> for SubscribedDataSetMirrorD
         })
     }
 }
+
+impl XmlEncoder for SubscribedDataSetMirrorDataType {
+    fn xml_type_name() -> String {
+        "SubscribedDataSetMirrorDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ParentNodeName", &self.parent_node_name);
+        write_xml_field(&mut element, "RolePermissions", &self.role_permissions);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SubscribedDataSetMirrorDataType {
+            parent_node_name: read_xml_field(element, "ParentNodeName", decoding_options)?,
+            role_permissions: read_xml_field(element, "RolePermissions", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SubscribedDataSetMirrorDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "ParentNodeName",
+            &self.parent_node_name,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RolePermissions",
+            &self.role_permissions,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SubscribedDataSetMirrorDataType {
+            parent_node_name: read_json_field(value, "ParentNodeName", decoding_options)?,
+            role_permissions: read_json_field(value, "RolePermissions", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +51,52 @@ impl BinaryEncoder<SubscriptionAcknowledgement> for SubscriptionAcknowledgement
         })
     }
 }
+
+impl XmlEncoder for SubscriptionAcknowledgement {
+    fn xml_type_name() -> String {
+        "SubscriptionAcknowledgement".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(&mut element, "SequenceNumber", &self.sequence_number);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SubscriptionAcknowledgement {
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            sequence_number: read_xml_field(element, "SequenceNumber", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for SubscriptionAcknowledgement {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SequenceNumber",
+            &self.sequence_number,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SubscriptionAcknowledgement {
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            sequence_number: read_json_field(value, "SequenceNumber", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -196,3 +196,520 @@ impl BinaryEncoder<SubscriptionDiagnosticsDataType> for SubscriptionDiagnosticsD
         })
     }
 }
+
+impl XmlEncoder for SubscriptionDiagnosticsDataType {
+    fn xml_type_name() -> String {
+        "SubscriptionDiagnosticsDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SessionId", &self.session_id);
+        write_xml_field(&mut element, "SubscriptionId", &self.subscription_id);
+        write_xml_field(&mut element, "Priority", &self.priority);
+        write_xml_field(
+            &mut element,
+            "PublishingInterval",
+            &self.publishing_interval,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxKeepAliveCount",
+            &self.max_keep_alive_count,
+        );
+        write_xml_field(&mut element, "MaxLifetimeCount", &self.max_lifetime_count);
+        write_xml_field(
+            &mut element,
+            "MaxNotificationsPerPublish",
+            &self.max_notifications_per_publish,
+        );
+        write_xml_field(&mut element, "PublishingEnabled", &self.publishing_enabled);
+        write_xml_field(&mut element, "ModifyCount", &self.modify_count);
+        write_xml_field(&mut element, "EnableCount", &self.enable_count);
+        write_xml_field(&mut element, "DisableCount", &self.disable_count);
+        write_xml_field(
+            &mut element,
+            "RepublishRequestCount",
+            &self.republish_request_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RepublishMessageRequestCount",
+            &self.republish_message_request_count,
+        );
+        write_xml_field(
+            &mut element,
+            "RepublishMessageCount",
+            &self.republish_message_count,
+        );
+        write_xml_field(
+            &mut element,
+            "TransferRequestCount",
+            &self.transfer_request_count,
+        );
+        write_xml_field(
+            &mut element,
+            "TransferredToAltClientCount",
+            &self.transferred_to_alt_client_count,
+        );
+        write_xml_field(
+            &mut element,
+            "TransferredToSameClientCount",
+            &self.transferred_to_same_client_count,
+        );
+        write_xml_field(
+            &mut element,
+            "PublishRequestCount",
+            &self.publish_request_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DataChangeNotificationsCount",
+            &self.data_change_notifications_count,
+        );
+        write_xml_field(
+            &mut element,
+            "EventNotificationsCount",
+            &self.event_notifications_count,
+        );
+        write_xml_field(
+            &mut element,
+            "NotificationsCount",
+            &self.notifications_count,
+        );
+        write_xml_field(
+            &mut element,
+            "LatePublishRequestCount",
+            &self.late_publish_request_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CurrentKeepAliveCount",
+            &self.current_keep_alive_count,
+        );
+        write_xml_field(
+            &mut element,
+            "CurrentLifetimeCount",
+            &self.current_lifetime_count,
+        );
+        write_xml_field(
+            &mut element,
+            "UnacknowledgedMessageCount",
+            &self.unacknowledged_message_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DiscardedMessageCount",
+            &self.discarded_message_count,
+        );
+        write_xml_field(
+            &mut element,
+            "MonitoredItemCount",
+            &self.monitored_item_count,
+        );
+        write_xml_field(
+            &mut element,
+            "DisabledMonitoredItemCount",
+            &self.disabled_monitored_item_count,
+        );
+        write_xml_field(
+            &mut element,
+            "MonitoringQueueOverflowCount",
+            &self.monitoring_queue_overflow_count,
+        );
+        write_xml_field(
+            &mut element,
+            "NextSequenceNumber",
+            &self.next_sequence_number,
+        );
+        write_xml_field(
+            &mut element,
+            "EventQueueOverFlowCount",
+            &self.event_queue_over_flow_count,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(SubscriptionDiagnosticsDataType {
+            session_id: read_xml_field(element, "SessionId", decoding_options)?,
+            subscription_id: read_xml_field(element, "SubscriptionId", decoding_options)?,
+            priority: read_xml_field(element, "Priority", decoding_options)?,
+            publishing_interval: read_xml_field(element, "PublishingInterval", decoding_options)?,
+            max_keep_alive_count: read_xml_field(element, "MaxKeepAliveCount", decoding_options)?,
+            max_lifetime_count: read_xml_field(element, "MaxLifetimeCount", decoding_options)?,
+            max_notifications_per_publish: read_xml_field(
+                element,
+                "MaxNotificationsPerPublish",
+                decoding_options,
+            )?,
+            publishing_enabled: read_xml_field(element, "PublishingEnabled", decoding_options)?,
+            modify_count: read_xml_field(element, "ModifyCount", decoding_options)?,
+            enable_count: read_xml_field(element, "EnableCount", decoding_options)?,
+            disable_count: read_xml_field(element, "DisableCount", decoding_options)?,
+            republish_request_count: read_xml_field(
+                element,
+                "RepublishRequestCount",
+                decoding_options,
+            )?,
+            republish_message_request_count: read_xml_field(
+                element,
+                "RepublishMessageRequestCount",
+                decoding_options,
+            )?,
+            republish_message_count: read_xml_field(
+                element,
+                "RepublishMessageCount",
+                decoding_options,
+            )?,
+            transfer_request_count: read_xml_field(
+                element,
+                "TransferRequestCount",
+                decoding_options,
+            )?,
+            transferred_to_alt_client_count: read_xml_field(
+                element,
+                "TransferredToAltClientCount",
+                decoding_options,
+            )?,
+            transferred_to_same_client_count: read_xml_field(
+                element,
+                "TransferredToSameClientCount",
+                decoding_options,
+            )?,
+            publish_request_count: read_xml_field(
+                element,
+                "PublishRequestCount",
+                decoding_options,
+            )?,
+            data_change_notifications_count: read_xml_field(
+                element,
+                "DataChangeNotificationsCount",
+                decoding_options,
+            )?,
+            event_notifications_count: read_xml_field(
+                element,
+                "EventNotificationsCount",
+                decoding_options,
+            )?,
+            notifications_count: read_xml_field(element, "NotificationsCount", decoding_options)?,
+            late_publish_request_count: read_xml_field(
+                element,
+                "LatePublishRequestCount",
+                decoding_options,
+            )?,
+            current_keep_alive_count: read_xml_field(
+                element,
+                "CurrentKeepAliveCount",
+                decoding_options,
+            )?,
+            current_lifetime_count: read_xml_field(
+                element,
+                "CurrentLifetimeCount",
+                decoding_options,
+            )?,
+            unacknowledged_message_count: read_xml_field(
+                element,
+                "UnacknowledgedMessageCount",
+                decoding_options,
+            )?,
+            discarded_message_count: read_xml_field(
+                element,
+                "DiscardedMessageCount",
+                decoding_options,
+            )?,
+            monitored_item_count: read_xml_field(element, "MonitoredItemCount", decoding_options)?,
+            disabled_monitored_item_count: read_xml_field(
+                element,
+                "DisabledMonitoredItemCount",
+                decoding_options,
+            )?,
+            monitoring_queue_overflow_count: read_xml_field(
+                element,
+                "MonitoringQueueOverflowCount",
+                decoding_options,
+            )?,
+            next_sequence_number: read_xml_field(element, "NextSequenceNumber", decoding_options)?,
+            event_queue_over_flow_count: read_xml_field(
+                element,
+                "EventQueueOverFlowCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for SubscriptionDiagnosticsDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SessionId", &self.session_id, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionId",
+            &self.subscription_id,
+            encoding,
+        );
+        write_json_field(&mut object, "Priority", &self.priority, encoding);
+        write_json_field(
+            &mut object,
+            "PublishingInterval",
+            &self.publishing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxKeepAliveCount",
+            &self.max_keep_alive_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxLifetimeCount",
+            &self.max_lifetime_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxNotificationsPerPublish",
+            &self.max_notifications_per_publish,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingEnabled",
+            &self.publishing_enabled,
+            encoding,
+        );
+        write_json_field(&mut object, "ModifyCount", &self.modify_count, encoding);
+        write_json_field(&mut object, "EnableCount", &self.enable_count, encoding);
+        write_json_field(&mut object, "DisableCount", &self.disable_count, encoding);
+        write_json_field(
+            &mut object,
+            "RepublishRequestCount",
+            &self.republish_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RepublishMessageRequestCount",
+            &self.republish_message_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "RepublishMessageCount",
+            &self.republish_message_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransferRequestCount",
+            &self.transfer_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransferredToAltClientCount",
+            &self.transferred_to_alt_client_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransferredToSameClientCount",
+            &self.transferred_to_same_client_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishRequestCount",
+            &self.publish_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataChangeNotificationsCount",
+            &self.data_change_notifications_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "EventNotificationsCount",
+            &self.event_notifications_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NotificationsCount",
+            &self.notifications_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "LatePublishRequestCount",
+            &self.late_publish_request_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentKeepAliveCount",
+            &self.current_keep_alive_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "CurrentLifetimeCount",
+            &self.current_lifetime_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "UnacknowledgedMessageCount",
+            &self.unacknowledged_message_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DiscardedMessageCount",
+            &self.discarded_message_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoredItemCount",
+            &self.monitored_item_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DisabledMonitoredItemCount",
+            &self.disabled_monitored_item_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MonitoringQueueOverflowCount",
+            &self.monitoring_queue_overflow_count,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NextSequenceNumber",
+            &self.next_sequence_number,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "EventQueueOverFlowCount",
+            &self.event_queue_over_flow_count,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(SubscriptionDiagnosticsDataType {
+            session_id: read_json_field(value, "SessionId", decoding_options)?,
+            subscription_id: read_json_field(value, "SubscriptionId", decoding_options)?,
+            priority: read_json_field(value, "Priority", decoding_options)?,
+            publishing_interval: read_json_field(value, "PublishingInterval", decoding_options)?,
+            max_keep_alive_count: read_json_field(value, "MaxKeepAliveCount", decoding_options)?,
+            max_lifetime_count: read_json_field(value, "MaxLifetimeCount", decoding_options)?,
+            max_notifications_per_publish: read_json_field(
+                value,
+                "MaxNotificationsPerPublish",
+                decoding_options,
+            )?,
+            publishing_enabled: read_json_field(value, "PublishingEnabled", decoding_options)?,
+            modify_count: read_json_field(value, "ModifyCount", decoding_options)?,
+            enable_count: read_json_field(value, "EnableCount", decoding_options)?,
+            disable_count: read_json_field(value, "DisableCount", decoding_options)?,
+            republish_request_count: read_json_field(
+                value,
+                "RepublishRequestCount",
+                decoding_options,
+            )?,
+            republish_message_request_count: read_json_field(
+                value,
+                "RepublishMessageRequestCount",
+                decoding_options,
+            )?,
+            republish_message_count: read_json_field(
+                value,
+                "RepublishMessageCount",
+                decoding_options,
+            )?,
+            transfer_request_count: read_json_field(
+                value,
+                "TransferRequestCount",
+                decoding_options,
+            )?,
+            transferred_to_alt_client_count: read_json_field(
+                value,
+                "TransferredToAltClientCount",
+                decoding_options,
+            )?,
+            transferred_to_same_client_count: read_json_field(
+                value,
+                "TransferredToSameClientCount",
+                decoding_options,
+            )?,
+            publish_request_count: read_json_field(value, "PublishRequestCount", decoding_options)?,
+            data_change_notifications_count: read_json_field(
+                value,
+                "DataChangeNotificationsCount",
+                decoding_options,
+            )?,
+            event_notifications_count: read_json_field(
+                value,
+                "EventNotificationsCount",
+                decoding_options,
+            )?,
+            notifications_count: read_json_field(value, "NotificationsCount", decoding_options)?,
+            late_publish_request_count: read_json_field(
+                value,
+                "LatePublishRequestCount",
+                decoding_options,
+            )?,
+            current_keep_alive_count: read_json_field(
+                value,
+                "CurrentKeepAliveCount",
+                decoding_options,
+            )?,
+            current_lifetime_count: read_json_field(
+                value,
+                "CurrentLifetimeCount",
+                decoding_options,
+            )?,
+            unacknowledged_message_count: read_json_field(
+                value,
+                "UnacknowledgedMessageCount",
+                decoding_options,
+            )?,
+            discarded_message_count: read_json_field(
+                value,
+                "DiscardedMessageCount",
+                decoding_options,
+            )?,
+            monitored_item_count: read_json_field(value, "MonitoredItemCount", decoding_options)?,
+            disabled_monitored_item_count: read_json_field(
+                value,
+                "DisabledMonitoredItemCount",
+                decoding_options,
+            )?,
+            monitoring_queue_overflow_count: read_json_field(
+                value,
+                "MonitoringQueueOverflowCount",
+                decoding_options,
+            )?,
+            next_sequence_number: read_json_field(value, "NextSequenceNumber", decoding_options)?,
+            event_queue_over_flow_count: read_json_field(
+                value,
+                "EventQueueOverFlowCount",
+                decoding_options,
+            )?,
+        })
+    }
+}
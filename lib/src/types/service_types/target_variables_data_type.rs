@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*, service_types::FieldTargetDataType};
+use crate::types::{
+    basic_types::*, encoding::*, json::*, service_types::FieldTargetDataType, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,3 +38,43 @@ impl BinaryEncoder<TargetVariablesDataType> for TargetVariablesDataType {
         Ok(TargetVariablesDataType { target_variables })
     }
 }
+
+impl XmlEncoder for TargetVariablesDataType {
+    fn xml_type_name() -> String {
+        "TargetVariablesDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "TargetVariables", &self.target_variables);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TargetVariablesDataType {
+            target_variables: read_xml_field(element, "TargetVariables", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for TargetVariablesDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "TargetVariables",
+            &self.target_variables,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TargetVariablesDataType {
+            target_variables: read_json_field(value, "TargetVariables", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::ThreeDCartesianCoordinates,
-    service_types::ThreeDOrientation,
+    basic_types::*, encoding::*, json::*, service_types::ThreeDCartesianCoordinates,
+    service_types::ThreeDOrientation, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -45,3 +45,59 @@ impl BinaryEncoder<ThreeDFrame> for ThreeDFrame {
         })
     }
 }
+
+impl XmlEncoder for ThreeDFrame {
+    fn xml_type_name() -> String {
+        "ThreeDFrame".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "CartesianCoordinates",
+            &self.cartesian_coordinates,
+        );
+        write_xml_field(&mut element, "Orientation", &self.orientation);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ThreeDFrame {
+            cartesian_coordinates: read_xml_field(
+                element,
+                "CartesianCoordinates",
+                decoding_options,
+            )?,
+            orientation: read_xml_field(element, "Orientation", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ThreeDFrame {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "CartesianCoordinates",
+            &self.cartesian_coordinates,
+            encoding,
+        );
+        write_json_field(&mut object, "Orientation", &self.orientation, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ThreeDFrame {
+            cartesian_coordinates: read_json_field(
+                value,
+                "CartesianCoordinates",
+                decoding_options,
+            )?,
+            orientation: read_json_field(value, "Orientation", decoding_options)?,
+        })
+    }
+}
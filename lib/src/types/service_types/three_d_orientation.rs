@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*};
+use crate::types::{basic_types::*, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,3 +43,46 @@ impl BinaryEncoder<ThreeDOrientation> for ThreeDOrientation {
         Ok(ThreeDOrientation { a, b, c })
     }
 }
+
+impl XmlEncoder for ThreeDOrientation {
+    fn xml_type_name() -> String {
+        "ThreeDOrientation".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "A", &self.a);
+        write_xml_field(&mut element, "B", &self.b);
+        write_xml_field(&mut element, "C", &self.c);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ThreeDOrientation {
+            a: read_xml_field(element, "A", decoding_options)?,
+            b: read_xml_field(element, "B", decoding_options)?,
+            c: read_xml_field(element, "C", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ThreeDOrientation {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "A", &self.a, encoding);
+        write_json_field(&mut object, "B", &self.b, encoding);
+        write_json_field(&mut object, "C", &self.c, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ThreeDOrientation {
+            a: read_json_field(value, "A", decoding_options)?,
+            b: read_json_field(value, "B", decoding_options)?,
+            c: read_json_field(value, "C", decoding_options)?,
+        })
+    }
+}
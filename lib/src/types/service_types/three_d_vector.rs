@@ -7,7 +7,7 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, encoding::*};
+use crate::types::{basic_types::*, encoding::*, json::*, xml::*};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,3 +43,46 @@ impl BinaryEncoder<ThreeDVector> for ThreeDVector {
         Ok(ThreeDVector { x, y, z })
     }
 }
+
+impl XmlEncoder for ThreeDVector {
+    fn xml_type_name() -> String {
+        "ThreeDVector".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "X", &self.x);
+        write_xml_field(&mut element, "Y", &self.y);
+        write_xml_field(&mut element, "Z", &self.z);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ThreeDVector {
+            x: read_xml_field(element, "X", decoding_options)?,
+            y: read_xml_field(element, "Y", decoding_options)?,
+            z: read_xml_field(element, "Z", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ThreeDVector {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "X", &self.x, encoding);
+        write_json_field(&mut object, "Y", &self.y, encoding);
+        write_json_field(&mut object, "Z", &self.z, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ThreeDVector {
+            x: read_json_field(value, "X", decoding_options)?,
+            y: read_json_field(value, "Y", decoding_options)?,
+            z: read_json_field(value, "Z", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +51,59 @@ impl BinaryEncoder<TimeZoneDataType> for TimeZoneDataType {
         })
     }
 }
+
+impl XmlEncoder for TimeZoneDataType {
+    fn xml_type_name() -> String {
+        "TimeZoneDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Offset", &self.offset);
+        write_xml_field(
+            &mut element,
+            "DaylightSavingInOffset",
+            &self.daylight_saving_in_offset,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TimeZoneDataType {
+            offset: read_xml_field(element, "Offset", decoding_options)?,
+            daylight_saving_in_offset: read_xml_field(
+                element,
+                "DaylightSavingInOffset",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for TimeZoneDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Offset", &self.offset, encoding);
+        write_json_field(
+            &mut object,
+            "DaylightSavingInOffset",
+            &self.daylight_saving_in_offset,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TimeZoneDataType {
+            offset: read_json_field(value, "Offset", decoding_options)?,
+            daylight_saving_in_offset: read_json_field(
+                value,
+                "DaylightSavingInOffset",
+                decoding_options,
+            )?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::impls::MessageInfo,
-    status_codes::StatusCode,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::impls::MessageInfo,
+    status_codes::StatusCode, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,59 @@ impl BinaryEncoder<TransferResult> for TransferResult {
         })
     }
 }
+
+impl XmlEncoder for TransferResult {
+    fn xml_type_name() -> String {
+        "TransferResult".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "StatusCode", &self.status_code);
+        write_xml_field(
+            &mut element,
+            "AvailableSequenceNumbers",
+            &self.available_sequence_numbers,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TransferResult {
+            status_code: read_xml_field(element, "StatusCode", decoding_options)?,
+            available_sequence_numbers: read_xml_field(
+                element,
+                "AvailableSequenceNumbers",
+                decoding_options,
+            )?,
+        })
+    }
+}
+
+impl JsonEncoder for TransferResult {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, encoding);
+        write_json_field(
+            &mut object,
+            "AvailableSequenceNumbers",
+            &self.available_sequence_numbers,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TransferResult {
+            status_code: read_json_field(value, "StatusCode", decoding_options)?,
+            available_sequence_numbers: read_json_field(
+                value,
+                "AvailableSequenceNumbers",
+                decoding_options,
+            )?,
+        })
+    }
+}
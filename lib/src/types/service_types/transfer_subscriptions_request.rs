@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,56 @@ impl BinaryEncoder<TransferSubscriptionsRequest> for TransferSubscriptionsReques
         })
     }
 }
+
+impl XmlEncoder for TransferSubscriptionsRequest {
+    fn xml_type_name() -> String {
+        "TransferSubscriptionsRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "SubscriptionIds", &self.subscription_ids);
+        write_xml_field(&mut element, "SendInitialValues", &self.send_initial_values);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TransferSubscriptionsRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            subscription_ids: read_xml_field(element, "SubscriptionIds", decoding_options)?,
+            send_initial_values: read_xml_field(element, "SendInitialValues", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for TransferSubscriptionsRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(
+            &mut object,
+            "SubscriptionIds",
+            &self.subscription_ids,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SendInitialValues",
+            &self.send_initial_values,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TransferSubscriptionsRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            subscription_ids: read_json_field(value, "SubscriptionIds", decoding_options)?,
+            send_initial_values: read_json_field(value, "SendInitialValues", decoding_options)?,
+        })
+    }
+}
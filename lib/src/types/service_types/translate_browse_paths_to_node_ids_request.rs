@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::BrowsePath,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::BrowsePath, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<TranslateBrowsePathsToNodeIdsRequest> for TranslateBrowsePath
         })
     }
 }
+
+impl XmlEncoder for TranslateBrowsePathsToNodeIdsRequest {
+    fn xml_type_name() -> String {
+        "TranslateBrowsePathsToNodeIdsRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "BrowsePaths", &self.browse_paths);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TranslateBrowsePathsToNodeIdsRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            browse_paths: read_xml_field(element, "BrowsePaths", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for TranslateBrowsePathsToNodeIdsRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "BrowsePaths", &self.browse_paths, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TranslateBrowsePathsToNodeIdsRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            browse_paths: read_json_field(value, "BrowsePaths", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, byte_string::ByteString, encoding::*, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,77 @@ impl BinaryEncoder<TrustListDataType> for TrustListDataType {
         })
     }
 }
+
+impl XmlEncoder for TrustListDataType {
+    fn xml_type_name() -> String {
+        "TrustListDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "SpecifiedLists", &self.specified_lists);
+        write_xml_field(
+            &mut element,
+            "TrustedCertificates",
+            &self.trusted_certificates,
+        );
+        write_xml_field(&mut element, "TrustedCrls", &self.trusted_crls);
+        write_xml_field(
+            &mut element,
+            "IssuerCertificates",
+            &self.issuer_certificates,
+        );
+        write_xml_field(&mut element, "IssuerCrls", &self.issuer_crls);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(TrustListDataType {
+            specified_lists: read_xml_field(element, "SpecifiedLists", decoding_options)?,
+            trusted_certificates: read_xml_field(element, "TrustedCertificates", decoding_options)?,
+            trusted_crls: read_xml_field(element, "TrustedCrls", decoding_options)?,
+            issuer_certificates: read_xml_field(element, "IssuerCertificates", decoding_options)?,
+            issuer_crls: read_xml_field(element, "IssuerCrls", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for TrustListDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SpecifiedLists",
+            &self.specified_lists,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TrustedCertificates",
+            &self.trusted_certificates,
+            encoding,
+        );
+        write_json_field(&mut object, "TrustedCrls", &self.trusted_crls, encoding);
+        write_json_field(
+            &mut object,
+            "IssuerCertificates",
+            &self.issuer_certificates,
+            encoding,
+        );
+        write_json_field(&mut object, "IssuerCrls", &self.issuer_crls, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(TrustListDataType {
+            specified_lists: read_json_field(value, "SpecifiedLists", decoding_options)?,
+            trusted_certificates: read_json_field(value, "TrustedCertificates", decoding_options)?,
+            trusted_crls: read_json_field(value, "TrustedCrls", decoding_options)?,
+            issuer_certificates: read_json_field(value, "IssuerCertificates", decoding_options)?,
+            issuer_crls: read_json_field(value, "IssuerCrls", decoding_options)?,
+        })
+    }
+}
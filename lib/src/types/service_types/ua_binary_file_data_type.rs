@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::EnumDescription, service_types::KeyValuePair,
-    service_types::SimpleTypeDescription, service_types::StructureDescription, string::UAString,
-    variant::Variant,
+    basic_types::*, encoding::*, json::*, service_types::EnumDescription,
+    service_types::KeyValuePair, service_types::SimpleTypeDescription,
+    service_types::StructureDescription, string::UAString, variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -73,3 +73,86 @@ impl BinaryEncoder<UABinaryFileDataType> for UABinaryFileDataType {
         })
     }
 }
+
+impl XmlEncoder for UABinaryFileDataType {
+    fn xml_type_name() -> String {
+        "UABinaryFileDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Namespaces", &self.namespaces);
+        write_xml_field(
+            &mut element,
+            "StructureDataTypes",
+            &self.structure_data_types,
+        );
+        write_xml_field(&mut element, "EnumDataTypes", &self.enum_data_types);
+        write_xml_field(&mut element, "SimpleDataTypes", &self.simple_data_types);
+        write_xml_field(&mut element, "SchemaLocation", &self.schema_location);
+        write_xml_field(&mut element, "FileHeader", &self.file_header);
+        write_xml_field(&mut element, "Body", &self.body);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UABinaryFileDataType {
+            namespaces: read_xml_field(element, "Namespaces", decoding_options)?,
+            structure_data_types: read_xml_field(element, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_xml_field(element, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_xml_field(element, "SimpleDataTypes", decoding_options)?,
+            schema_location: read_xml_field(element, "SchemaLocation", decoding_options)?,
+            file_header: read_xml_field(element, "FileHeader", decoding_options)?,
+            body: read_xml_field(element, "Body", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UABinaryFileDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, encoding);
+        write_json_field(
+            &mut object,
+            "StructureDataTypes",
+            &self.structure_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "EnumDataTypes",
+            &self.enum_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SimpleDataTypes",
+            &self.simple_data_types,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SchemaLocation",
+            &self.schema_location,
+            encoding,
+        );
+        write_json_field(&mut object, "FileHeader", &self.file_header, encoding);
+        write_json_field(&mut object, "Body", &self.body, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UABinaryFileDataType {
+            namespaces: read_json_field(value, "Namespaces", decoding_options)?,
+            structure_data_types: read_json_field(value, "StructureDataTypes", decoding_options)?,
+            enum_data_types: read_json_field(value, "EnumDataTypes", decoding_options)?,
+            simple_data_types: read_json_field(value, "SimpleDataTypes", decoding_options)?,
+            schema_location: read_json_field(value, "SchemaLocation", decoding_options)?,
+            file_header: read_json_field(value, "FileHeader", decoding_options)?,
+            body: read_json_field(value, "Body", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, guid::Guid, service_types::enums::UadpDataSetMessageContentMask,
-    service_types::enums::UadpNetworkMessageContentMask,
+    basic_types::*, encoding::*, guid::Guid, json::*,
+    service_types::enums::UadpDataSetMessageContentMask,
+    service_types::enums::UadpNetworkMessageContentMask, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -82,3 +83,145 @@ impl BinaryEncoder<UadpDataSetReaderMessageDataType> for UadpDataSetReaderMessag
         })
     }
 }
+
+impl XmlEncoder for UadpDataSetReaderMessageDataType {
+    fn xml_type_name() -> String {
+        "UadpDataSetReaderMessageDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "GroupVersion", &self.group_version);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageNumber",
+            &self.network_message_number,
+        );
+        write_xml_field(&mut element, "DataSetOffset", &self.data_set_offset);
+        write_xml_field(&mut element, "DataSetClassId", &self.data_set_class_id);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+        );
+        write_xml_field(
+            &mut element,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+        );
+        write_xml_field(
+            &mut element,
+            "PublishingInterval",
+            &self.publishing_interval,
+        );
+        write_xml_field(&mut element, "ReceiveOffset", &self.receive_offset);
+        write_xml_field(&mut element, "ProcessingOffset", &self.processing_offset);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpDataSetReaderMessageDataType {
+            group_version: read_xml_field(element, "GroupVersion", decoding_options)?,
+            network_message_number: read_xml_field(
+                element,
+                "NetworkMessageNumber",
+                decoding_options,
+            )?,
+            data_set_offset: read_xml_field(element, "DataSetOffset", decoding_options)?,
+            data_set_class_id: read_xml_field(element, "DataSetClassId", decoding_options)?,
+            network_message_content_mask: read_xml_field(
+                element,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            data_set_message_content_mask: read_xml_field(
+                element,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+            publishing_interval: read_xml_field(element, "PublishingInterval", decoding_options)?,
+            receive_offset: read_xml_field(element, "ReceiveOffset", decoding_options)?,
+            processing_offset: read_xml_field(element, "ProcessingOffset", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UadpDataSetReaderMessageDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "GroupVersion", &self.group_version, encoding);
+        write_json_field(
+            &mut object,
+            "NetworkMessageNumber",
+            &self.network_message_number,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetOffset",
+            &self.data_set_offset,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetClassId",
+            &self.data_set_class_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingInterval",
+            &self.publishing_interval,
+            encoding,
+        );
+        write_json_field(&mut object, "ReceiveOffset", &self.receive_offset, encoding);
+        write_json_field(
+            &mut object,
+            "ProcessingOffset",
+            &self.processing_offset,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UadpDataSetReaderMessageDataType {
+            group_version: read_json_field(value, "GroupVersion", decoding_options)?,
+            network_message_number: read_json_field(
+                value,
+                "NetworkMessageNumber",
+                decoding_options,
+            )?,
+            data_set_offset: read_json_field(value, "DataSetOffset", decoding_options)?,
+            data_set_class_id: read_json_field(value, "DataSetClassId", decoding_options)?,
+            network_message_content_mask: read_json_field(
+                value,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            data_set_message_content_mask: read_json_field(
+                value,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+            publishing_interval: read_json_field(value, "PublishingInterval", decoding_options)?,
+            receive_offset: read_json_field(value, "ReceiveOffset", decoding_options)?,
+            processing_offset: read_json_field(value, "ProcessingOffset", decoding_options)?,
+        })
+    }
+}
@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::enums::UadpDataSetMessageContentMask,
+    basic_types::*, encoding::*, json::*, service_types::enums::UadpDataSetMessageContentMask,
+    xml::*,
 };
 use std::io::{Read, Write};
 
@@ -55,3 +56,94 @@ impl BinaryEncoder<UadpDataSetWriterMessageDataType> for UadpDataSetWriterMessag
         })
     }
 }
+
+impl XmlEncoder for UadpDataSetWriterMessageDataType {
+    fn xml_type_name() -> String {
+        "UadpDataSetWriterMessageDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+        );
+        write_xml_field(&mut element, "ConfiguredSize", &self.configured_size);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageNumber",
+            &self.network_message_number,
+        );
+        write_xml_field(&mut element, "DataSetOffset", &self.data_set_offset);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpDataSetWriterMessageDataType {
+            data_set_message_content_mask: read_xml_field(
+                element,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+            configured_size: read_xml_field(element, "ConfiguredSize", decoding_options)?,
+            network_message_number: read_xml_field(
+                element,
+                "NetworkMessageNumber",
+                decoding_options,
+            )?,
+            data_set_offset: read_xml_field(element, "DataSetOffset", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UadpDataSetWriterMessageDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "DataSetMessageContentMask",
+            &self.data_set_message_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "ConfiguredSize",
+            &self.configured_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NetworkMessageNumber",
+            &self.network_message_number,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetOffset",
+            &self.data_set_offset,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UadpDataSetWriterMessageDataType {
+            data_set_message_content_mask: read_json_field(
+                value,
+                "DataSetMessageContentMask",
+                decoding_options,
+            )?,
+            configured_size: read_json_field(value, "ConfiguredSize", decoding_options)?,
+            network_message_number: read_json_field(
+                value,
+                "NetworkMessageNumber",
+                decoding_options,
+            )?,
+            data_set_offset: read_json_field(value, "DataSetOffset", decoding_options)?,
+        })
+    }
+}
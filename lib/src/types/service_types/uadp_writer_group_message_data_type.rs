@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, service_types::enums::DataSetOrderingType,
-    service_types::enums::UadpNetworkMessageContentMask,
+    basic_types::*, encoding::*, json::*, service_types::enums::DataSetOrderingType,
+    service_types::enums::UadpNetworkMessageContentMask, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,86 @@ impl BinaryEncoder<UadpWriterGroupMessageDataType> for UadpWriterGroupMessageDat
         })
     }
 }
+
+impl XmlEncoder for UadpWriterGroupMessageDataType {
+    fn xml_type_name() -> String {
+        "UadpWriterGroupMessageDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "GroupVersion", &self.group_version);
+        write_xml_field(&mut element, "DataSetOrdering", &self.data_set_ordering);
+        write_xml_field(
+            &mut element,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+        );
+        write_xml_field(&mut element, "SamplingOffset", &self.sampling_offset);
+        write_xml_field(&mut element, "PublishingOffset", &self.publishing_offset);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UadpWriterGroupMessageDataType {
+            group_version: read_xml_field(element, "GroupVersion", decoding_options)?,
+            data_set_ordering: read_xml_field(element, "DataSetOrdering", decoding_options)?,
+            network_message_content_mask: read_xml_field(
+                element,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            sampling_offset: read_xml_field(element, "SamplingOffset", decoding_options)?,
+            publishing_offset: read_xml_field(element, "PublishingOffset", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UadpWriterGroupMessageDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "GroupVersion", &self.group_version, encoding);
+        write_json_field(
+            &mut object,
+            "DataSetOrdering",
+            &self.data_set_ordering,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "NetworkMessageContentMask",
+            &self.network_message_content_mask,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SamplingOffset",
+            &self.sampling_offset,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingOffset",
+            &self.publishing_offset,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UadpWriterGroupMessageDataType {
+            group_version: read_json_field(value, "GroupVersion", decoding_options)?,
+            data_set_ordering: read_json_field(value, "DataSetOrdering", decoding_options)?,
+            network_message_content_mask: read_json_field(
+                value,
+                "NetworkMessageContentMask",
+                decoding_options,
+            )?,
+            sampling_offset: read_json_field(value, "SamplingOffset", decoding_options)?,
+            publishing_offset: read_json_field(value, "PublishingOffset", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, data_value::DataValue, encoding::*, node_id::NodeId,
-    service_types::enums::PerformUpdateType,
+    basic_types::*, data_value::DataValue, encoding::*, json::*, node_id::NodeId,
+    service_types::enums::PerformUpdateType, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -50,3 +50,63 @@ impl BinaryEncoder<UpdateDataDetails> for UpdateDataDetails {
         })
     }
 }
+
+impl XmlEncoder for UpdateDataDetails {
+    fn xml_type_name() -> String {
+        "UpdateDataDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(
+            &mut element,
+            "PerformInsertReplace",
+            &self.perform_insert_replace,
+        );
+        write_xml_field(&mut element, "UpdateValues", &self.update_values);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UpdateDataDetails {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            perform_insert_replace: read_xml_field(
+                element,
+                "PerformInsertReplace",
+                decoding_options,
+            )?,
+            update_values: read_xml_field(element, "UpdateValues", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UpdateDataDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "PerformInsertReplace",
+            &self.perform_insert_replace,
+            encoding,
+        );
+        write_json_field(&mut object, "UpdateValues", &self.update_values, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UpdateDataDetails {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            perform_insert_replace: read_json_field(
+                value,
+                "PerformInsertReplace",
+                decoding_options,
+            )?,
+            update_values: read_json_field(value, "UpdateValues", decoding_options)?,
+        })
+    }
+}
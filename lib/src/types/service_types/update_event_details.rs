@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_id::NodeId, service_types::enums::PerformUpdateType,
-    service_types::EventFilter, service_types::HistoryEventFieldList,
+    basic_types::*, encoding::*, json::*, node_id::NodeId, service_types::enums::PerformUpdateType,
+    service_types::EventFilter, service_types::HistoryEventFieldList, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -55,3 +55,67 @@ impl BinaryEncoder<UpdateEventDetails> for UpdateEventDetails {
         })
     }
 }
+
+impl XmlEncoder for UpdateEventDetails {
+    fn xml_type_name() -> String {
+        "UpdateEventDetails".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(
+            &mut element,
+            "PerformInsertReplace",
+            &self.perform_insert_replace,
+        );
+        write_xml_field(&mut element, "Filter", &self.filter);
+        write_xml_field(&mut element, "EventData", &self.event_data);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UpdateEventDetails {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            perform_insert_replace: read_xml_field(
+                element,
+                "PerformInsertReplace",
+                decoding_options,
+            )?,
+            filter: read_xml_field(element, "Filter", decoding_options)?,
+            event_data: read_xml_field(element, "EventData", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UpdateEventDetails {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(
+            &mut object,
+            "PerformInsertReplace",
+            &self.perform_insert_replace,
+            encoding,
+        );
+        write_json_field(&mut object, "Filter", &self.filter, encoding);
+        write_json_field(&mut object, "EventData", &self.event_data, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UpdateEventDetails {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            perform_insert_replace: read_json_field(
+                value,
+                "PerformInsertReplace",
+                decoding_options,
+            )?,
+            filter: read_json_field(value, "Filter", decoding_options)?,
+            event_data: read_json_field(value, "EventData", decoding_options)?,
+        })
+    }
+}
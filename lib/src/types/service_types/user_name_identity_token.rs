@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, byte_string::ByteString, encoding::*, string::UAString};
+use crate::types::{
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, string::UAString, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,3 +54,59 @@ impl BinaryEncoder<UserNameIdentityToken> for UserNameIdentityToken {
         })
     }
 }
+
+impl XmlEncoder for UserNameIdentityToken {
+    fn xml_type_name() -> String {
+        "UserNameIdentityToken".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PolicyId", &self.policy_id);
+        write_xml_field(&mut element, "UserName", &self.user_name);
+        write_xml_field(&mut element, "Password", &self.password);
+        write_xml_field(
+            &mut element,
+            "EncryptionAlgorithm",
+            &self.encryption_algorithm,
+        );
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UserNameIdentityToken {
+            policy_id: read_xml_field(element, "PolicyId", decoding_options)?,
+            user_name: read_xml_field(element, "UserName", decoding_options)?,
+            password: read_xml_field(element, "Password", decoding_options)?,
+            encryption_algorithm: read_xml_field(element, "EncryptionAlgorithm", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UserNameIdentityToken {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, encoding);
+        write_json_field(&mut object, "UserName", &self.user_name, encoding);
+        write_json_field(&mut object, "Password", &self.password, encoding);
+        write_json_field(
+            &mut object,
+            "EncryptionAlgorithm",
+            &self.encryption_algorithm,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UserNameIdentityToken {
+            policy_id: read_json_field(value, "PolicyId", decoding_options)?,
+            user_name: read_json_field(value, "UserName", decoding_options)?,
+            password: read_json_field(value, "Password", decoding_options)?,
+            encryption_algorithm: read_json_field(value, "EncryptionAlgorithm", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, service_types::enums::UserTokenType,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, service_types::enums::UserTokenType,
+    service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -66,3 +66,69 @@ impl BinaryEncoder<UserTokenPolicy> for UserTokenPolicy {
         })
     }
 }
+
+impl XmlEncoder for UserTokenPolicy {
+    fn xml_type_name() -> String {
+        "UserTokenPolicy".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PolicyId", &self.policy_id);
+        write_xml_field(&mut element, "TokenType", &self.token_type);
+        write_xml_field(&mut element, "IssuedTokenType", &self.issued_token_type);
+        write_xml_field(&mut element, "IssuerEndpointUrl", &self.issuer_endpoint_url);
+        write_xml_field(&mut element, "SecurityPolicyUri", &self.security_policy_uri);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(UserTokenPolicy {
+            policy_id: read_xml_field(element, "PolicyId", decoding_options)?,
+            token_type: read_xml_field(element, "TokenType", decoding_options)?,
+            issued_token_type: read_xml_field(element, "IssuedTokenType", decoding_options)?,
+            issuer_endpoint_url: read_xml_field(element, "IssuerEndpointUrl", decoding_options)?,
+            security_policy_uri: read_xml_field(element, "SecurityPolicyUri", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for UserTokenPolicy {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, encoding);
+        write_json_field(&mut object, "TokenType", &self.token_type, encoding);
+        write_json_field(
+            &mut object,
+            "IssuedTokenType",
+            &self.issued_token_type,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "IssuerEndpointUrl",
+            &self.issuer_endpoint_url,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityPolicyUri",
+            &self.security_policy_uri,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(UserTokenPolicy {
+            policy_id: read_json_field(value, "PolicyId", decoding_options)?,
+            token_type: read_json_field(value, "TokenType", decoding_options)?,
+            issued_token_type: read_json_field(value, "IssuedTokenType", decoding_options)?,
+            issuer_endpoint_url: read_json_field(value, "IssuerEndpointUrl", decoding_options)?,
+            security_policy_uri: read_json_field(value, "SecurityPolicyUri", decoding_options)?,
+        })
+    }
+}
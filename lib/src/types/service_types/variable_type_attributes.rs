@@ -8,7 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, localized_text::LocalizedText, node_id::NodeId, variant::Variant,
+    basic_types::*, encoding::*, json::*, localized_text::LocalizedText, node_id::NodeId,
+    variant::Variant, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -84,3 +85,93 @@ impl BinaryEncoder<VariableTypeAttributes> for VariableTypeAttributes {
         })
     }
 }
+
+impl XmlEncoder for VariableTypeAttributes {
+    fn xml_type_name() -> String {
+        "VariableTypeAttributes".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(
+            &mut element,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+        );
+        write_xml_field(&mut element, "DisplayName", &self.display_name);
+        write_xml_field(&mut element, "Description", &self.description);
+        write_xml_field(&mut element, "WriteMask", &self.write_mask);
+        write_xml_field(&mut element, "UserWriteMask", &self.user_write_mask);
+        write_xml_field(&mut element, "Value", &self.value);
+        write_xml_field(&mut element, "DataType", &self.data_type);
+        write_xml_field(&mut element, "ValueRank", &self.value_rank);
+        write_xml_field(&mut element, "ArrayDimensions", &self.array_dimensions);
+        write_xml_field(&mut element, "IsAbstract", &self.is_abstract);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(VariableTypeAttributes {
+            specified_attributes: read_xml_field(element, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_xml_field(element, "DisplayName", decoding_options)?,
+            description: read_xml_field(element, "Description", decoding_options)?,
+            write_mask: read_xml_field(element, "WriteMask", decoding_options)?,
+            user_write_mask: read_xml_field(element, "UserWriteMask", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+            data_type: read_xml_field(element, "DataType", decoding_options)?,
+            value_rank: read_xml_field(element, "ValueRank", decoding_options)?,
+            array_dimensions: read_xml_field(element, "ArrayDimensions", decoding_options)?,
+            is_abstract: read_xml_field(element, "IsAbstract", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for VariableTypeAttributes {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(
+            &mut object,
+            "SpecifiedAttributes",
+            &self.specified_attributes,
+            encoding,
+        );
+        write_json_field(&mut object, "DisplayName", &self.display_name, encoding);
+        write_json_field(&mut object, "Description", &self.description, encoding);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, encoding);
+        write_json_field(
+            &mut object,
+            "UserWriteMask",
+            &self.user_write_mask,
+            encoding,
+        );
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        write_json_field(&mut object, "DataType", &self.data_type, encoding);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, encoding);
+        write_json_field(
+            &mut object,
+            "ArrayDimensions",
+            &self.array_dimensions,
+            encoding,
+        );
+        write_json_field(&mut object, "IsAbstract", &self.is_abstract, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(VariableTypeAttributes {
+            specified_attributes: read_json_field(value, "SpecifiedAttributes", decoding_options)?,
+            display_name: read_json_field(value, "DisplayName", decoding_options)?,
+            description: read_json_field(value, "Description", decoding_options)?,
+            write_mask: read_json_field(value, "WriteMask", decoding_options)?,
+            user_write_mask: read_json_field(value, "UserWriteMask", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+            data_type: read_json_field(value, "DataType", decoding_options)?,
+            value_rank: read_json_field(value, "ValueRank", decoding_options)?,
+            array_dimensions: read_json_field(value, "ArrayDimensions", decoding_options)?,
+            is_abstract: read_json_field(value, "IsAbstract", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, date_time::DateTime, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo,
+    basic_types::*, date_time::DateTime, encoding::*, json::*, node_id::NodeId, node_ids::ObjectId,
+    service_types::impls::MessageInfo, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -56,3 +56,46 @@ impl BinaryEncoder<ViewDescription> for ViewDescription {
         })
     }
 }
+
+impl XmlEncoder for ViewDescription {
+    fn xml_type_name() -> String {
+        "ViewDescription".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "ViewId", &self.view_id);
+        write_xml_field(&mut element, "Timestamp", &self.timestamp);
+        write_xml_field(&mut element, "ViewVersion", &self.view_version);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(ViewDescription {
+            view_id: read_xml_field(element, "ViewId", decoding_options)?,
+            timestamp: read_xml_field(element, "Timestamp", decoding_options)?,
+            view_version: read_xml_field(element, "ViewVersion", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for ViewDescription {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ViewId", &self.view_id, encoding);
+        write_json_field(&mut object, "Timestamp", &self.timestamp, encoding);
+        write_json_field(&mut object, "ViewVersion", &self.view_version, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(ViewDescription {
+            view_id: read_json_field(value, "ViewId", decoding_options)?,
+            timestamp: read_json_field(value, "Timestamp", decoding_options)?,
+            view_version: read_json_field(value, "ViewVersion", decoding_options)?,
+        })
+    }
+}
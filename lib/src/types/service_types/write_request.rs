@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, node_ids::ObjectId, request_header::RequestHeader,
-    service_types::impls::MessageInfo, service_types::WriteValue,
+    basic_types::*, encoding::*, json::*, node_ids::ObjectId, request_header::RequestHeader,
+    service_types::impls::MessageInfo, service_types::WriteValue, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -51,3 +51,42 @@ impl BinaryEncoder<WriteRequest> for WriteRequest {
         })
     }
 }
+
+impl XmlEncoder for WriteRequest {
+    fn xml_type_name() -> String {
+        "WriteRequest".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "RequestHeader", &self.request_header);
+        write_xml_field(&mut element, "NodesToWrite", &self.nodes_to_write);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(WriteRequest {
+            request_header: read_xml_field(element, "RequestHeader", decoding_options)?,
+            nodes_to_write: read_xml_field(element, "NodesToWrite", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for WriteRequest {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, encoding);
+        write_json_field(&mut object, "NodesToWrite", &self.nodes_to_write, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(WriteRequest {
+            request_header: read_json_field(value, "RequestHeader", decoding_options)?,
+            nodes_to_write: read_json_field(value, "NodesToWrite", decoding_options)?,
+        })
+    }
+}
@@ -8,8 +8,8 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, data_value::DataValue, encoding::*, node_id::NodeId, node_ids::ObjectId,
-    service_types::impls::MessageInfo, string::UAString,
+    basic_types::*, data_value::DataValue, encoding::*, json::*, node_id::NodeId,
+    node_ids::ObjectId, service_types::impls::MessageInfo, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -61,3 +61,50 @@ impl BinaryEncoder<WriteValue> for WriteValue {
         })
     }
 }
+
+impl XmlEncoder for WriteValue {
+    fn xml_type_name() -> String {
+        "WriteValue".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "NodeId", &self.node_id);
+        write_xml_field(&mut element, "AttributeId", &self.attribute_id);
+        write_xml_field(&mut element, "IndexRange", &self.index_range);
+        write_xml_field(&mut element, "Value", &self.value);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(WriteValue {
+            node_id: read_xml_field(element, "NodeId", decoding_options)?,
+            attribute_id: read_xml_field(element, "AttributeId", decoding_options)?,
+            index_range: read_xml_field(element, "IndexRange", decoding_options)?,
+            value: read_xml_field(element, "Value", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for WriteValue {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, encoding);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, encoding);
+        write_json_field(&mut object, "IndexRange", &self.index_range, encoding);
+        write_json_field(&mut object, "Value", &self.value, encoding);
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(WriteValue {
+            node_id: read_json_field(value, "NodeId", decoding_options)?,
+            attribute_id: read_json_field(value, "AttributeId", decoding_options)?,
+            index_range: read_json_field(value, "IndexRange", decoding_options)?,
+            value: read_json_field(value, "Value", decoding_options)?,
+        })
+    }
+}
@@ -8,9 +8,9 @@
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
 use crate::types::{
-    basic_types::*, encoding::*, extension_object::ExtensionObject,
+    basic_types::*, encoding::*, extension_object::ExtensionObject, json::*,
     service_types::enums::MessageSecurityMode, service_types::DataSetWriterDataType,
-    service_types::EndpointDescription, service_types::KeyValuePair, string::UAString,
+    service_types::EndpointDescription, service_types::KeyValuePair, string::UAString, xml::*,
 };
 use std::io::{Read, Write};
 
@@ -118,3 +118,177 @@ impl BinaryEncoder<WriterGroupDataType> for WriterGroupDataType {
         })
     }
 }
+
+impl XmlEncoder for WriterGroupDataType {
+    fn xml_type_name() -> String {
+        "WriterGroupDataType".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "Name", &self.name);
+        write_xml_field(&mut element, "Enabled", &self.enabled);
+        write_xml_field(&mut element, "SecurityMode", &self.security_mode);
+        write_xml_field(&mut element, "SecurityGroupId", &self.security_group_id);
+        write_xml_field(
+            &mut element,
+            "SecurityKeyServices",
+            &self.security_key_services,
+        );
+        write_xml_field(
+            &mut element,
+            "MaxNetworkMessageSize",
+            &self.max_network_message_size,
+        );
+        write_xml_field(&mut element, "GroupProperties", &self.group_properties);
+        write_xml_field(&mut element, "WriterGroupId", &self.writer_group_id);
+        write_xml_field(
+            &mut element,
+            "PublishingInterval",
+            &self.publishing_interval,
+        );
+        write_xml_field(&mut element, "KeepAliveTime", &self.keep_alive_time);
+        write_xml_field(&mut element, "Priority", &self.priority);
+        write_xml_field(&mut element, "LocaleIds", &self.locale_ids);
+        write_xml_field(&mut element, "HeaderLayoutUri", &self.header_layout_uri);
+        write_xml_field(&mut element, "TransportSettings", &self.transport_settings);
+        write_xml_field(&mut element, "MessageSettings", &self.message_settings);
+        write_xml_field(&mut element, "DataSetWriters", &self.data_set_writers);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(WriterGroupDataType {
+            name: read_xml_field(element, "Name", decoding_options)?,
+            enabled: read_xml_field(element, "Enabled", decoding_options)?,
+            security_mode: read_xml_field(element, "SecurityMode", decoding_options)?,
+            security_group_id: read_xml_field(element, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_xml_field(
+                element,
+                "SecurityKeyServices",
+                decoding_options,
+            )?,
+            max_network_message_size: read_xml_field(
+                element,
+                "MaxNetworkMessageSize",
+                decoding_options,
+            )?,
+            group_properties: read_xml_field(element, "GroupProperties", decoding_options)?,
+            writer_group_id: read_xml_field(element, "WriterGroupId", decoding_options)?,
+            publishing_interval: read_xml_field(element, "PublishingInterval", decoding_options)?,
+            keep_alive_time: read_xml_field(element, "KeepAliveTime", decoding_options)?,
+            priority: read_xml_field(element, "Priority", decoding_options)?,
+            locale_ids: read_xml_field(element, "LocaleIds", decoding_options)?,
+            header_layout_uri: read_xml_field(element, "HeaderLayoutUri", decoding_options)?,
+            transport_settings: read_xml_field(element, "TransportSettings", decoding_options)?,
+            message_settings: read_xml_field(element, "MessageSettings", decoding_options)?,
+            data_set_writers: read_xml_field(element, "DataSetWriters", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for WriterGroupDataType {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, encoding);
+        write_json_field(&mut object, "Enabled", &self.enabled, encoding);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, encoding);
+        write_json_field(
+            &mut object,
+            "SecurityGroupId",
+            &self.security_group_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "SecurityKeyServices",
+            &self.security_key_services,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MaxNetworkMessageSize",
+            &self.max_network_message_size,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "GroupProperties",
+            &self.group_properties,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "WriterGroupId",
+            &self.writer_group_id,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "PublishingInterval",
+            &self.publishing_interval,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "KeepAliveTime",
+            &self.keep_alive_time,
+            encoding,
+        );
+        write_json_field(&mut object, "Priority", &self.priority, encoding);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, encoding);
+        write_json_field(
+            &mut object,
+            "HeaderLayoutUri",
+            &self.header_layout_uri,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "TransportSettings",
+            &self.transport_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "MessageSettings",
+            &self.message_settings,
+            encoding,
+        );
+        write_json_field(
+            &mut object,
+            "DataSetWriters",
+            &self.data_set_writers,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(WriterGroupDataType {
+            name: read_json_field(value, "Name", decoding_options)?,
+            enabled: read_json_field(value, "Enabled", decoding_options)?,
+            security_mode: read_json_field(value, "SecurityMode", decoding_options)?,
+            security_group_id: read_json_field(value, "SecurityGroupId", decoding_options)?,
+            security_key_services: read_json_field(value, "SecurityKeyServices", decoding_options)?,
+            max_network_message_size: read_json_field(
+                value,
+                "MaxNetworkMessageSize",
+                decoding_options,
+            )?,
+            group_properties: read_json_field(value, "GroupProperties", decoding_options)?,
+            writer_group_id: read_json_field(value, "WriterGroupId", decoding_options)?,
+            publishing_interval: read_json_field(value, "PublishingInterval", decoding_options)?,
+            keep_alive_time: read_json_field(value, "KeepAliveTime", decoding_options)?,
+            priority: read_json_field(value, "Priority", decoding_options)?,
+            locale_ids: read_json_field(value, "LocaleIds", decoding_options)?,
+            header_layout_uri: read_json_field(value, "HeaderLayoutUri", decoding_options)?,
+            transport_settings: read_json_field(value, "TransportSettings", decoding_options)?,
+            message_settings: read_json_field(value, "MessageSettings", decoding_options)?,
+            data_set_writers: read_json_field(value, "DataSetWriters", decoding_options)?,
+        })
+    }
+}
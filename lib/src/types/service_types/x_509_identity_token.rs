@@ -7,7 +7,9 @@
 // DO NOT EDIT THIS FILE
 #![allow(unused_attributes)]
 #[allow(unused_imports)]
-use crate::types::{basic_types::*, byte_string::ByteString, encoding::*, string::UAString};
+use crate::types::{
+    basic_types::*, byte_string::ByteString, encoding::*, json::*, string::UAString, xml::*,
+};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +44,47 @@ impl BinaryEncoder<X509IdentityToken> for X509IdentityToken {
         })
     }
 }
+
+impl XmlEncoder for X509IdentityToken {
+    fn xml_type_name() -> String {
+        "X509IdentityToken".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+        write_xml_field(&mut element, "PolicyId", &self.policy_id);
+        write_xml_field(&mut element, "CertificateData", &self.certificate_data);
+        element
+    }
+
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(X509IdentityToken {
+            policy_id: read_xml_field(element, "PolicyId", decoding_options)?,
+            certificate_data: read_xml_field(element, "CertificateData", decoding_options)?,
+        })
+    }
+}
+
+impl JsonEncoder for X509IdentityToken {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, encoding);
+        write_json_field(
+            &mut object,
+            "CertificateData",
+            &self.certificate_data,
+            encoding,
+        );
+        JsonValue::Object(object)
+    }
+
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(X509IdentityToken {
+            policy_id: read_json_field(value, "PolicyId", decoding_options)?,
+            certificate_data: read_json_field(value, "CertificateData", decoding_options)?,
+        })
+    }
+}
@@ -19,6 +19,8 @@ use serde::{
 
 pub use crate::types::{encoding::*, status_codes::StatusCode};
 
+use crate::types::{json::*, xml::*};
+
 // The bitflags! macro implements Debug for StatusCode but it fouls the display because status
 // codes are a combination of bits and unique values.
 
@@ -48,6 +50,41 @@ impl BinaryEncoder<StatusCode> for StatusCode {
     }
 }
 
+/// A status code is encoded as its numeric value in a `Code` element
+impl XmlEncoder for StatusCode {
+    fn xml_type_name() -> String {
+        "StatusCode".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        Element::new(name).with_child(self.bits().encode_xml("Code"))
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        read_xml_field::<u32>(element, "Code", decoding_options).map(StatusCode::from_bits_truncate)
+    }
+}
+
+/// StatusCode encoded as a number for the reversible encoding, and as an object with the code and
+/// its symbolic name for the non-reversible encoding
+impl JsonEncoder for StatusCode {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        match encoding {
+            JsonEncoding::Reversible => JsonValue::from(self.bits()),
+            JsonEncoding::NonReversible => {
+                let mut object = JsonObject::new();
+                object.insert("Code".to_string(), JsonValue::from(self.bits()));
+                object.insert("Symbol".to_string(), JsonValue::from(self.name()));
+                JsonValue::Object(object)
+            }
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        u32::decode_json(value, decoding_options).map(StatusCode::from_bits_truncate)
+    }
+}
+
 impl Error for StatusCode {}
 
 impl StatusCode {
@@ -75,6 +112,152 @@ impl StatusCode {
     pub fn is_good(&self) -> bool {
         !self.is_bad() && !self.is_uncertain()
     }
+
+    /// Tests if the info type says the status has the info bits of a data value, i.e. the
+    /// limit, overflow and historian bits
+    pub fn is_data_value_info(&self) -> bool {
+        self.contains(StatusCode::INFO_TYPE_DATA_VALUE)
+    }
+
+    /// Tests if the overflow bit is set, meaning a monitored item queue discarded values
+    pub fn overflow(&self) -> bool {
+        self.contains(StatusCode::OVERFLOW)
+    }
+
+    /// Sets or clears the overflow bit
+    pub fn set_overflow(&mut self, overflow: bool) {
+        self.set_data_value_info(StatusCode::OVERFLOW, overflow);
+    }
+
+    /// Tests if the semantics changed bit is set, meaning the engineering units, range or some
+    /// other property that affects how the value is interpreted has changed
+    pub fn semantics_changed(&self) -> bool {
+        self.contains(StatusCode::SEMANTICS_CHANGED)
+    }
+
+    /// Sets or clears the semantics changed bit
+    pub fn set_semantics_changed(&mut self, semantics_changed: bool) {
+        self.set(StatusCode::SEMANTICS_CHANGED, semantics_changed);
+    }
+
+    /// Tests if the structure changed bit is set, meaning the data type of the value has changed
+    pub fn structure_changed(&self) -> bool {
+        self.contains(StatusCode::STRUCTURE_CHANGED)
+    }
+
+    /// Sets or clears the structure changed bit
+    pub fn set_structure_changed(&mut self, structure_changed: bool) {
+        self.set(StatusCode::STRUCTURE_CHANGED, structure_changed);
+    }
+
+    /// Returns the limit bits, which say if the value is at a limit of what its source can
+    /// produce
+    pub fn limit(&self) -> LimitBits {
+        match self.bits() & StatusCode::LIMIT_CONSTANT.bits() {
+            0 => LimitBits::None,
+            v if v == StatusCode::LIMIT_LOW.bits() => LimitBits::Low,
+            v if v == StatusCode::LIMIT_HIGH.bits() => LimitBits::High,
+            _ => LimitBits::Constant,
+        }
+    }
+
+    /// Sets the limit bits
+    pub fn set_limit(&mut self, limit: LimitBits) {
+        self.remove(StatusCode::LIMIT_CONSTANT);
+        let bits = match limit {
+            LimitBits::None => StatusCode::empty(),
+            LimitBits::Low => StatusCode::LIMIT_LOW,
+            LimitBits::High => StatusCode::LIMIT_HIGH,
+            LimitBits::Constant => StatusCode::LIMIT_CONSTANT,
+        };
+        self.set_data_value_info(bits, true);
+    }
+
+    /// Returns the historian bits, which say how a historical value was obtained
+    pub fn historian(&self) -> HistorianBits {
+        let source = match self.bits() & StatusCode::HISTORICAL_RESERVED.bits() {
+            0 => HistorianSource::Raw,
+            v if v == StatusCode::HISTORICAL_CALCULATED.bits() => HistorianSource::Calculated,
+            v if v == StatusCode::HISTORICAL_INTERPOLATED.bits() => HistorianSource::Interpolated,
+            _ => HistorianSource::Reserved,
+        };
+        HistorianBits {
+            source,
+            partial: self.contains(StatusCode::HISTORICAL_PARTIAL),
+            extra_data: self.contains(StatusCode::HISTORICAL_EXTRA_DATA),
+            multi_value: self.contains(StatusCode::HISTORICAL_MULTI_VALUE),
+        }
+    }
+
+    /// Sets the historian bits
+    pub fn set_historian(&mut self, historian: HistorianBits) {
+        self.remove(
+            StatusCode::HISTORICAL_RESERVED
+                | StatusCode::HISTORICAL_PARTIAL
+                | StatusCode::HISTORICAL_EXTRA_DATA
+                | StatusCode::HISTORICAL_MULTI_VALUE,
+        );
+        let mut bits = match historian.source {
+            HistorianSource::Raw => StatusCode::HISTORICAL_RAW,
+            HistorianSource::Calculated => StatusCode::HISTORICAL_CALCULATED,
+            HistorianSource::Interpolated => StatusCode::HISTORICAL_INTERPOLATED,
+            HistorianSource::Reserved => StatusCode::HISTORICAL_RESERVED,
+        };
+        bits.set(StatusCode::HISTORICAL_PARTIAL, historian.partial);
+        bits.set(StatusCode::HISTORICAL_EXTRA_DATA, historian.extra_data);
+        bits.set(StatusCode::HISTORICAL_MULTI_VALUE, historian.multi_value);
+        self.set_data_value_info(bits, true);
+    }
+
+    /// Sets or clears data value info bits, setting the info type when any of them are set
+    /// and clearing it when none are left
+    fn set_data_value_info(&mut self, bits: StatusCode, value: bool) {
+        self.set(bits, value);
+        let info_bits = self.bits() & DATA_VALUE_INFO_BITS;
+        self.set(StatusCode::INFO_TYPE_DATA_VALUE, info_bits != 0);
+    }
+}
+
+/// The info bits (0:9) that are only meaningful when the info type is a data value
+const DATA_VALUE_INFO_BITS: u32 = 0x0000_03ff;
+
+/// The limit bits of a status code, Part 4 7.39.1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LimitBits {
+    /// The value is free to change
+    None,
+    /// The value is at the lower limit of its source
+    Low,
+    /// The value is at the upper limit of its source
+    High,
+    /// The value is constant and cannot change
+    Constant,
+}
+
+/// How a historical value was obtained, Part 4 7.39.1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HistorianSource {
+    /// A raw value that was collected
+    Raw,
+    /// A value that was calculated from raw values
+    Calculated,
+    /// A value that was interpolated from raw values
+    Interpolated,
+    /// A reserved value that should not be used
+    Reserved,
+}
+
+/// The historian bits of a status code, Part 4 7.39.1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HistorianBits {
+    /// How the value was obtained
+    pub source: HistorianSource,
+    /// A calculated value from an interval that did not have complete data
+    pub partial: bool,
+    /// A raw value that hides other values at the same timestamp
+    pub extra_data: bool,
+    /// More than one value matches the aggregate, e.g. several values have the minimum
+    pub multi_value: bool,
 }
 
 // It would be very nice to be able to override the default implementation in bitflags! macro
@@ -176,3 +359,53 @@ fn status_code() {
         StatusCode::HISTORICAL_CALCULATED
     );
 }
+
+#[test]
+fn status_code_info_bits() {
+    let mut status = StatusCode::Good;
+    assert!(!status.overflow());
+    assert!(!status.is_data_value_info());
+
+    // Data value info bits set the info type
+    status.set_overflow(true);
+    assert!(status.overflow());
+    assert!(status.is_data_value_info());
+    assert_eq!(status.bits(), 0x0000_0480);
+    status.set_limit(LimitBits::High);
+    assert_eq!(status.limit(), LimitBits::High);
+    status.set_limit(LimitBits::Low);
+    assert_eq!(status.limit(), LimitBits::Low);
+    assert!(status.overflow());
+
+    // Clearing the last of them clears the info type
+    status.set_overflow(false);
+    status.set_limit(LimitBits::None);
+    assert_eq!(status, StatusCode::Good);
+
+    let historian = HistorianBits {
+        source: HistorianSource::Interpolated,
+        partial: false,
+        extra_data: true,
+        multi_value: true,
+    };
+    let mut status = StatusCode::UncertainDataSubNormal;
+    status.set_historian(historian);
+    assert_eq!(status.historian(), historian);
+    assert_eq!(status.status(), StatusCode::UncertainDataSubNormal);
+    assert!(status.is_data_value_info());
+    assert_eq!(
+        (StatusCode::Good | StatusCode::HISTORICAL_CALCULATED)
+            .historian()
+            .source,
+        HistorianSource::Calculated
+    );
+
+    // Semantics and structure changed bits don't depend on the info type
+    let mut status = StatusCode::Good;
+    status.set_semantics_changed(true);
+    status.set_structure_changed(true);
+    assert!(status.semantics_changed() && status.structure_changed());
+    assert!(!status.is_data_value_info());
+    status.set_semantics_changed(false);
+    assert!(!status.semantics_changed() && status.structure_changed());
+}
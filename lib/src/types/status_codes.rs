@@ -41,7 +41,7 @@ bitflags! {
         const LIMIT_HIGH              = 0x0000_0200;
         const LIMIT_CONSTANT          = 0x0000_0300;
         // Info type bits 10:11
-        const LIMIT_DATA_VALUE        = 0x0000_2000;
+        const INFO_TYPE_DATA_VALUE    = 0x0000_0400;
         // Semantics changed bit 14
         const SEMANTICS_CHANGED       = 0x0000_4000;
         // Semantics changed bit 15
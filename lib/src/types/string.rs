@@ -16,7 +16,9 @@ use crate::types::{
         process_decode_io_result, process_encode_io_result, write_i32, BinaryEncoder,
         DecodingOptions, EncodingResult,
     },
+    json::{json_str, JsonEncoder, JsonEncoding, JsonValue},
     status_codes::StatusCode,
+    xml::{nil_element, xml_str, Element, XmlEncoder},
 };
 
 /// To avoid naming conflict hell, the OPC UA String type is typed `UAString` so it does not collide
@@ -120,7 +122,7 @@ impl BinaryEncoder<UAString> for UAString {
     fn decode<S: Read>(stream: &mut S, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
         let len = i32::decode(stream, decoding_options)?;
         // Null string?
-        if len == -1 {
+        if len == -1 || (len == 0 && decoding_options.canonical) {
             Ok(UAString::null())
         } else if len < -1 {
             error!("String buf length is a negative number {}", len);
@@ -144,6 +146,44 @@ impl BinaryEncoder<UAString> for UAString {
     }
 }
 
+impl XmlEncoder for UAString {
+    fn xml_type_name() -> String {
+        "String".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        match self.value {
+            Some(ref v) => Element::new(name).with_text(v),
+            None => nil_element(name),
+        }
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if element.is_nil() {
+            Ok(UAString::null())
+        } else {
+            xml_str(element, decoding_options.max_string_length).map(UAString::from)
+        }
+    }
+}
+
+impl JsonEncoder for UAString {
+    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        self.value
+            .as_ref()
+            .map(|v| JsonValue::from(v.as_str()))
+            .unwrap_or(JsonValue::Null)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if value.is_null() {
+            Ok(UAString::null())
+        } else {
+            json_str(value, decoding_options.max_string_length).map(UAString::from)
+        }
+    }
+}
+
 impl From<UAString> for String {
     fn from(value: UAString) -> Self {
         value.as_ref().to_string()
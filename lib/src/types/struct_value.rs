@@ -0,0 +1,576 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains `StructValue`, a structure of a custom data type that is only known at runtime, and
+//! `DataTypeDefinitions` which decodes and encodes them from the `StructureDefinition` of their
+//! data type.
+//!
+//! Servers such as PLCs often expose vendor structures that have no generated type in this
+//! crate. A client can read the `DataTypeDefinition` attribute of such a data type, add it to a
+//! `DataTypeDefinitions` and then decode the body of any `ExtensionObject` encoded with it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Cursor, Read, Write},
+};
+
+use crate::types::{
+    byte_string::ByteString,
+    encoding::*,
+    extension_object::{ExtensionObject, ExtensionObjectEncoding},
+    node_id::NodeId,
+    node_ids::{DataTypeId, ObjectId},
+    service_types::{EnumDefinition, StructureDefinition, StructureField, StructureType},
+    status_code::StatusCode,
+    variant::Variant,
+    variant_type_id::VariantTypeId,
+};
+
+/// The value of the `DataTypeDefinition` attribute of a data type, describing the fields of a
+/// structure or an enumeration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataTypeDefinition {
+    Structure(StructureDefinition),
+    Enum(EnumDefinition),
+}
+
+impl From<StructureDefinition> for DataTypeDefinition {
+    fn from(value: StructureDefinition) -> Self {
+        DataTypeDefinition::Structure(value)
+    }
+}
+
+impl From<EnumDefinition> for DataTypeDefinition {
+    fn from(value: EnumDefinition) -> Self {
+        DataTypeDefinition::Enum(value)
+    }
+}
+
+impl From<&DataTypeDefinition> for ExtensionObject {
+    fn from(value: &DataTypeDefinition) -> Self {
+        match value {
+            DataTypeDefinition::Structure(definition) => ExtensionObject::from_encodable(
+                ObjectId::StructureDefinition_Encoding_DefaultBinary,
+                definition,
+            ),
+            DataTypeDefinition::Enum(definition) => ExtensionObject::from_encodable(
+                ObjectId::EnumDefinition_Encoding_DefaultBinary,
+                definition,
+            ),
+        }
+    }
+}
+
+impl DataTypeDefinition {
+    /// Decodes a definition from an extension object, i.e. the value of the
+    /// `DataTypeDefinition` attribute that was read from a data type.
+    pub fn from_extension_object(
+        extension_object: &ExtensionObject,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<DataTypeDefinition> {
+        match extension_object.object_id() {
+            Ok(ObjectId::StructureDefinition_Encoding_DefaultBinary) => extension_object
+                .decode_inner::<StructureDefinition>(decoding_options)
+                .map(DataTypeDefinition::Structure),
+            Ok(ObjectId::EnumDefinition_Encoding_DefaultBinary) => extension_object
+                .decode_inner::<EnumDefinition>(decoding_options)
+                .map(DataTypeDefinition::Enum),
+            _ => {
+                error!(
+                    "Extension object {} is not a data type definition",
+                    extension_object.node_id
+                );
+                Err(StatusCode::BadDataTypeIdUnknown)
+            }
+        }
+    }
+}
+
+/// The value of a field of a [`StructValue`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// A value of a built-in type. Enumerations are held as `Int32` and fields of an abstract
+    /// type such as `BaseDataType` hold whatever value was encoded.
+    Value(Variant),
+    /// A value of another structure
+    Struct(Box<StructValue>),
+    /// An array of values. A null array is decoded as an empty array.
+    Array(Vec<FieldValue>),
+}
+
+impl<T> From<T> for FieldValue
+where
+    T: Into<Variant>,
+{
+    fn from(value: T) -> Self {
+        FieldValue::Value(value.into())
+    }
+}
+
+impl From<StructValue> for FieldValue {
+    fn from(value: StructValue) -> Self {
+        FieldValue::Struct(Box::new(value))
+    }
+}
+
+/// A structure of a data type that is described by a `StructureDefinition`, holding its fields
+/// by name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructValue {
+    /// The id of the data type of the structure
+    pub data_type_id: NodeId,
+    /// The fields of the structure in the order of its definition. Optional fields that are not
+    /// present are left out, as are the fields of a union other than the one that is set.
+    pub fields: Vec<(String, FieldValue)>,
+}
+
+impl StructValue {
+    pub fn new<T>(data_type_id: T) -> StructValue
+    where
+        T: Into<NodeId>,
+    {
+        StructValue {
+            data_type_id: data_type_id.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets the value of a field, replacing any existing value
+    pub fn with_field<S, V>(mut self, name: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<FieldValue>,
+    {
+        self.set_field(name, value);
+        self
+    }
+
+    /// Sets the value of a field, replacing any existing value
+    pub fn set_field<S, V>(&mut self, name: S, value: V)
+    where
+        S: Into<String>,
+        V: Into<FieldValue>,
+    {
+        let name = name.into();
+        let value = value.into();
+        if let Some(field) = self.fields.iter_mut().find(|(n, _)| *n == name) {
+            field.1 = value;
+        } else {
+            self.fields.push((name, value));
+        }
+    }
+
+    /// Finds the value of a field by its name
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Finds the value of a field by its name, if it holds a built-in type
+    pub fn value(&self, name: &str) -> Option<&Variant> {
+        match self.field(name) {
+            Some(FieldValue::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// How the value of a field is encoded
+enum FieldType {
+    /// A built-in type, encoded without an encoding mask
+    BuiltIn(VariantTypeId),
+    /// An abstract type, encoded as a `Variant`
+    Variant,
+    /// A structure with a definition
+    Struct,
+}
+
+/// A collection of the definitions of custom data types, used to decode and encode the values
+/// of those types.
+#[derive(Debug, Clone, Default)]
+pub struct DataTypeDefinitions {
+    /// Structure definitions by data type id
+    structures: HashMap<NodeId, StructureDefinition>,
+    /// The data type ids of custom enumerations
+    enumerations: HashSet<NodeId>,
+    /// Data type ids by the id of their default binary encoding
+    encodings: HashMap<NodeId, NodeId>,
+}
+
+impl DataTypeDefinitions {
+    pub fn new() -> DataTypeDefinitions {
+        DataTypeDefinitions::default()
+    }
+
+    /// Adds the definition of a structure. Its fields may be of built-in types, or custom
+    /// structures and enumerations that are also added.
+    pub fn add_structure(&mut self, data_type_id: NodeId, definition: StructureDefinition) {
+        if !definition.default_encoding_id.is_null() {
+            self.encodings
+                .insert(definition.default_encoding_id.clone(), data_type_id.clone());
+        }
+        self.structures.insert(data_type_id, definition);
+    }
+
+    /// Adds a custom enumeration. Values of an enumeration are encoded as an `Int32`.
+    pub fn add_enumeration(&mut self, data_type_id: NodeId) {
+        self.enumerations.insert(data_type_id);
+    }
+
+    /// Finds the definition of a structure by its data type id
+    pub fn structure(&self, data_type_id: &NodeId) -> Option<&StructureDefinition> {
+        self.structures.get(data_type_id)
+    }
+
+    /// Finds the data type id of a structure from the id of its default binary encoding, i.e.
+    /// the node id of an extension object holding the structure.
+    pub fn data_type_id(&self, encoding_id: &NodeId) -> Option<&NodeId> {
+        self.encodings.get(encoding_id)
+    }
+
+    /// Adds the definition of a data type, either a structure or an enumeration
+    pub fn add_definition(&mut self, data_type_id: NodeId, definition: DataTypeDefinition) {
+        match definition {
+            DataTypeDefinition::Structure(definition) => {
+                self.add_structure(data_type_id, definition)
+            }
+            DataTypeDefinition::Enum(_) => self.add_enumeration(data_type_id),
+        }
+    }
+
+    /// Tests if the data type is defined, either as a structure or an enumeration
+    pub fn contains(&self, data_type_id: &NodeId) -> bool {
+        self.structures.contains_key(data_type_id) || self.enumerations.contains(data_type_id)
+    }
+
+    /// Decodes the body of an extension object holding a structure with a definition
+    pub fn decode(
+        &self,
+        extension_object: &ExtensionObject,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<StructValue> {
+        let data_type_id = self
+            .data_type_id(&extension_object.node_id)
+            .ok_or_else(|| {
+                error!(
+                    "Extension object encoding {} has no structure definition",
+                    extension_object.node_id
+                );
+                StatusCode::BadDataTypeIdUnknown
+            })?;
+        match extension_object.body {
+            ExtensionObjectEncoding::ByteString(ByteString {
+                value: Some(ref body),
+            }) => {
+                let mut stream = Cursor::new(body);
+                self.decode_struct(data_type_id, &mut stream, decoding_options)
+            }
+            _ => {
+                error!("Extension object does not have a binary body to decode");
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    /// Encodes a structure into the body of an extension object, with the id of the default
+    /// binary encoding of its data type.
+    pub fn encode(&self, value: &StructValue) -> EncodingResult<ExtensionObject> {
+        let definition = self.find_structure(&value.data_type_id)?;
+        let mut stream = Cursor::new(Vec::new());
+        self.encode_struct(value, &mut stream)?;
+        Ok(ExtensionObject {
+            node_id: definition.default_encoding_id.clone(),
+            body: ExtensionObjectEncoding::ByteString(ByteString::from(stream.into_inner())),
+        })
+    }
+
+    /// Decodes a structure of the data type from a stream
+    pub fn decode_struct<S: Read>(
+        &self,
+        data_type_id: &NodeId,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<StructValue> {
+        // Structures can nest so guard against a definition that recurses forever
+        let _depth_lock = decoding_options.depth_lock()?;
+        let definition = self.find_structure(data_type_id)?;
+        let fields = definition.fields.as_deref().unwrap_or(&[]);
+        let mut value = StructValue::new(data_type_id.clone());
+        match definition.structure_type {
+            StructureType::Structure => {
+                for field in fields {
+                    let field_value = self.decode_field(field, stream, decoding_options)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+            StructureType::StructureWithOptionalFields => {
+                // A mask has a bit for each optional field in order, set when it is present
+                let encoding_mask = read_u32(stream)?;
+                let mut optional_index = 0;
+                for field in fields {
+                    if field.is_optional {
+                        let present = encoding_mask & (1 << optional_index) != 0;
+                        optional_index += 1;
+                        if !present {
+                            continue;
+                        }
+                    }
+                    let field_value = self.decode_field(field, stream, decoding_options)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+            StructureType::Union => {
+                // The switch field is the 1-based index of the field that is set, or 0 for none
+                let switch_field = read_u32(stream)? as usize;
+                if switch_field > 0 {
+                    let field = fields.get(switch_field - 1).ok_or_else(|| {
+                        error!("Union switch field {} is out of range", switch_field);
+                        StatusCode::BadDecodingError
+                    })?;
+                    let field_value = self.decode_field(field, stream, decoding_options)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Encodes a structure to a stream
+    pub fn encode_struct<S: Write>(
+        &self,
+        value: &StructValue,
+        stream: &mut S,
+    ) -> EncodingResult<usize> {
+        let definition = self.find_structure(&value.data_type_id)?;
+        let fields = definition.fields.as_deref().unwrap_or(&[]);
+        let mut size = 0;
+        match definition.structure_type {
+            StructureType::Structure => {
+                for field in fields {
+                    let field_value = Self::find_field(value, field)?;
+                    size += self.encode_field(field, field_value, stream)?;
+                }
+            }
+            StructureType::StructureWithOptionalFields => {
+                let mut encoding_mask = 0u32;
+                for (i, field) in fields.iter().filter(|f| f.is_optional).enumerate() {
+                    if value.field(field.name.as_ref()).is_some() {
+                        encoding_mask |= 1 << i;
+                    }
+                }
+                size += write_u32(stream, encoding_mask)?;
+                for field in fields {
+                    if field.is_optional {
+                        if let Some(field_value) = value.field(field.name.as_ref()) {
+                            size += self.encode_field(field, field_value, stream)?;
+                        }
+                    } else {
+                        let field_value = Self::find_field(value, field)?;
+                        size += self.encode_field(field, field_value, stream)?;
+                    }
+                }
+            }
+            StructureType::Union => {
+                let switch_field = fields
+                    .iter()
+                    .position(|f| value.field(f.name.as_ref()).is_some());
+                if let Some(index) = switch_field {
+                    let field = &fields[index];
+                    size += write_u32(stream, index as u32 + 1)?;
+                    let field_value = Self::find_field(value, field)?;
+                    size += self.encode_field(field, field_value, stream)?;
+                } else {
+                    size += write_u32(stream, 0u32)?;
+                }
+            }
+        }
+        Ok(size)
+    }
+
+    fn find_structure(&self, data_type_id: &NodeId) -> EncodingResult<&StructureDefinition> {
+        self.structure(data_type_id).ok_or_else(|| {
+            error!("Data type {} has no structure definition", data_type_id);
+            StatusCode::BadDataTypeIdUnknown
+        })
+    }
+
+    fn find_field<'a>(
+        value: &'a StructValue,
+        field: &StructureField,
+    ) -> EncodingResult<&'a FieldValue> {
+        value.field(field.name.as_ref()).ok_or_else(|| {
+            error!(
+                "Structure of type {} is missing a value for field {}",
+                value.data_type_id, field.name
+            );
+            StatusCode::BadEncodingError
+        })
+    }
+
+    /// Finds how a field of the data type is encoded
+    fn field_type(&self, data_type: &NodeId) -> EncodingResult<FieldType> {
+        if self.structures.contains_key(data_type) {
+            return Ok(FieldType::Struct);
+        }
+        if self.enumerations.contains(data_type) {
+            return Ok(FieldType::BuiltIn(VariantTypeId::Int32));
+        }
+        let field_type = data_type.as_data_type_id().ok().and_then(|id| match id {
+            DataTypeId::BaseDataType
+            | DataTypeId::Number
+            | DataTypeId::Integer
+            | DataTypeId::UInteger => Some(FieldType::Variant),
+            // Subtypes of built-in types are encoded as the built-in type
+            DataTypeId::SByte => Some(FieldType::BuiltIn(VariantTypeId::SByte)),
+            DataTypeId::Structure => Some(FieldType::BuiltIn(VariantTypeId::ExtensionObject)),
+            DataTypeId::Enumeration => Some(FieldType::BuiltIn(VariantTypeId::Int32)),
+            DataTypeId::Duration => Some(FieldType::BuiltIn(VariantTypeId::Double)),
+            DataTypeId::UtcTime => Some(FieldType::BuiltIn(VariantTypeId::DateTime)),
+            DataTypeId::IntegerId
+            | DataTypeId::Counter
+            | DataTypeId::Index
+            | DataTypeId::VersionTime => Some(FieldType::BuiltIn(VariantTypeId::UInt32)),
+            DataTypeId::LocaleId
+            | DataTypeId::NumericRange
+            | DataTypeId::NormalizedString
+            | DataTypeId::DecimalString
+            | DataTypeId::DurationString
+            | DataTypeId::TimeString
+            | DataTypeId::DateString => Some(FieldType::BuiltIn(VariantTypeId::String)),
+            DataTypeId::ApplicationInstanceCertificate
+            | DataTypeId::ContinuationPoint
+            | DataTypeId::ImageBMP
+            | DataTypeId::ImageGIF
+            | DataTypeId::ImageJPG
+            | DataTypeId::ImagePNG
+            | DataTypeId::AudioDataType => Some(FieldType::BuiltIn(VariantTypeId::ByteString)),
+            DataTypeId::SessionAuthenticationToken => {
+                Some(FieldType::BuiltIn(VariantTypeId::NodeId))
+            }
+            _ => VariantTypeId::try_from(data_type)
+                .ok()
+                .map(FieldType::BuiltIn),
+        });
+        field_type.ok_or_else(|| {
+            error!("Data type {} of a structure field is not known", data_type);
+            StatusCode::BadDataTypeIdUnknown
+        })
+    }
+
+    fn decode_field<S: Read>(
+        &self,
+        field: &StructureField,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<FieldValue> {
+        let field_type = self.field_type(&field.data_type)?;
+        match field.value_rank {
+            -1 => self.decode_field_value(&field_type, &field.data_type, stream, decoding_options),
+            1 => {
+                let len = read_i32(stream)?;
+                if len == -1 {
+                    Ok(FieldValue::Array(Vec::new()))
+                } else if len < -1 {
+                    error!("Array length is negative value and invalid");
+                    Err(StatusCode::BadDecodingError)
+                } else if len as usize > decoding_options.max_array_length {
+                    error!(
+                        "Array length {} exceeds decoding limit {}",
+                        len, decoding_options.max_array_length
+                    );
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    (0..len)
+                        .map(|_| {
+                            self.decode_field_value(
+                                &field_type,
+                                &field.data_type,
+                                stream,
+                                decoding_options,
+                            )
+                        })
+                        .collect::<EncodingResult<Vec<FieldValue>>>()
+                        .map(FieldValue::Array)
+                }
+            }
+            value_rank => {
+                error!(
+                    "Field {} has a value rank of {} which is not supported",
+                    field.name, value_rank
+                );
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    fn decode_field_value<S: Read>(
+        &self,
+        field_type: &FieldType,
+        data_type: &NodeId,
+        stream: &mut S,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<FieldValue> {
+        match field_type {
+            FieldType::BuiltIn(variant_type) => Variant::decode_variant_value(
+                stream,
+                variant_type.encoding_mask(),
+                decoding_options,
+            )
+            .map(FieldValue::Value),
+            FieldType::Variant => Variant::decode(stream, decoding_options).map(FieldValue::Value),
+            FieldType::Struct => self
+                .decode_struct(data_type, stream, decoding_options)
+                .map(FieldValue::from),
+        }
+    }
+
+    fn encode_field<S: Write>(
+        &self,
+        field: &StructureField,
+        value: &FieldValue,
+        stream: &mut S,
+    ) -> EncodingResult<usize> {
+        let field_type = self.field_type(&field.data_type)?;
+        match (field.value_rank, value) {
+            (-1, value) => self.encode_field_value(&field_type, value, stream),
+            (1, FieldValue::Array(values)) => {
+                let mut size = write_i32(stream, values.len() as i32)?;
+                for value in values {
+                    size += self.encode_field_value(&field_type, value, stream)?;
+                }
+                Ok(size)
+            }
+            _ => {
+                error!(
+                    "Value of field {} does not match its value rank of {}",
+                    field.name, field.value_rank
+                );
+                Err(StatusCode::BadEncodingError)
+            }
+        }
+    }
+
+    fn encode_field_value<S: Write>(
+        &self,
+        field_type: &FieldType,
+        value: &FieldValue,
+        stream: &mut S,
+    ) -> EncodingResult<usize> {
+        match (field_type, value) {
+            (FieldType::BuiltIn(variant_type), FieldValue::Value(value))
+                if value.type_id() == *variant_type =>
+            {
+                Variant::encode_variant_value(stream, value)
+            }
+            (FieldType::Variant, FieldValue::Value(value)) => value.encode(stream),
+            (FieldType::Struct, FieldValue::Struct(value)) => self.encode_struct(value, stream),
+            _ => {
+                error!(
+                    "Field value {:?} does not match the type of its field",
+                    value
+                );
+                Err(StatusCode::BadEncodingError)
+            }
+        }
+    }
+}
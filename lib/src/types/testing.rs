@@ -0,0 +1,149 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Helpers for writing protocol tests against the binary and JSON encodings of OPC UA types.
+//!
+//! Values are compared in their canonical form so that golden files stay stable. In canonical
+//! form empty arrays, strings and byte strings are encoded as null, and JSON object keys are
+//! written in sorted order. Fields are always encoded in the order the specification defines.
+
+use std::{fmt::Debug, io::Cursor};
+
+use serde::Serialize;
+
+use crate::types::{
+    encoding::{BinaryEncoder, DecodingOptions},
+    status_code::StatusCode,
+};
+
+/// Encodes a value in its canonical binary form. The value is encoded, decoded with
+/// [`DecodingOptions::canonical`] and encoded again, so two values that only differ in how they
+/// express "nothing", e.g. an empty array versus a null array, produce the same bytes.
+pub fn encode_canonical<T>(value: &T) -> Result<Vec<u8>, StatusCode>
+where
+    T: BinaryEncoder<T>,
+{
+    let mut stream = Cursor::new(encode(value)?);
+    let canonical = T::decode(&mut stream, &DecodingOptions::canonical())?;
+    encode(&canonical)
+}
+
+/// Serializes a value to JSON with object keys in sorted order, so the output does not depend
+/// on the order fields happen to be serialized in.
+pub fn canonical_json<T>(value: &T) -> Result<String, StatusCode>
+where
+    T: Serialize,
+{
+    // A serde_json::Value holds objects in a sorted map
+    serde_json::to_value(value)
+        .and_then(|v| serde_json::to_string(&v))
+        .map_err(|_| StatusCode::BadEncodingError)
+}
+
+/// Asserts that a value encodes to bytes of the length given by `byte_len()`, decodes back to an
+/// equal value, and re-encodes to the same bytes.
+pub fn assert_roundtrip<T>(value: &T)
+where
+    T: BinaryEncoder<T> + Debug + PartialEq,
+{
+    let decoded = assert_roundtrip_bytes(value);
+    assert_eq!(&decoded, value, "value changed when decoded");
+}
+
+/// Asserts that a value encodes and decodes to the expected value, e.g. when decoding normalizes
+/// part of the value, and that the expected value re-encodes to the same bytes.
+pub fn assert_roundtrip_expected<T>(value: &T, expected: &T)
+where
+    T: BinaryEncoder<T> + Debug + PartialEq,
+{
+    let decoded = assert_roundtrip_bytes(value);
+    assert_eq!(
+        &decoded, expected,
+        "decoded value is not the expected value"
+    );
+}
+
+/// Asserts that the canonical binary form of a value matches golden bytes, e.g. bytes captured
+/// from another stack or read from a golden file.
+pub fn assert_golden<T>(value: &T, golden: &[u8])
+where
+    T: BinaryEncoder<T> + Debug,
+{
+    let actual = encode_canonical(value)
+        .unwrap_or_else(|status| panic!("{:?} failed to encode - {}", value, status));
+    assert_eq!(
+        hex(&actual),
+        hex(golden),
+        "canonical encoding of {:?} does not match golden bytes",
+        value
+    );
+}
+
+/// Asserts that the canonical JSON of a value matches golden JSON. The golden JSON is parsed and
+/// written out canonically before comparing, so its key order and whitespace do not matter.
+pub fn assert_golden_json<T>(value: &T, golden: &str)
+where
+    T: Serialize + Debug,
+{
+    let actual = canonical_json(value)
+        .unwrap_or_else(|status| panic!("{:?} failed to serialize - {}", value, status));
+    let golden: serde_json::Value = serde_json::from_str(golden).expect("golden JSON is not valid");
+    let golden = canonical_json(&golden).unwrap();
+    assert_eq!(
+        actual, golden,
+        "canonical JSON of {:?} does not match golden JSON",
+        value
+    );
+}
+
+/// Encodes, checks the length, decodes and re-encodes a value, returning the decoded value
+fn assert_roundtrip_bytes<T>(value: &T) -> T
+where
+    T: BinaryEncoder<T> + Debug,
+{
+    let bytes =
+        encode(value).unwrap_or_else(|status| panic!("{:?} failed to encode - {}", value, status));
+    assert_eq!(
+        bytes.len(),
+        value.byte_len(),
+        "byte_len() of {:?} does not match its encoded length",
+        value
+    );
+    let mut stream = Cursor::new(&bytes);
+    let decoded = T::decode(&mut stream, &DecodingOptions::default())
+        .unwrap_or_else(|status| panic!("{:?} failed to decode - {}", value, status));
+    assert_eq!(
+        stream.position() as usize,
+        bytes.len(),
+        "decoding {:?} did not consume all of its bytes",
+        value
+    );
+    let reencoded = encode(&decoded)
+        .unwrap_or_else(|status| panic!("{:?} failed to re-encode - {}", decoded, status));
+    assert_eq!(
+        hex(&reencoded),
+        hex(&bytes),
+        "{:?} did not re-encode to the same bytes",
+        value
+    );
+    decoded
+}
+
+fn encode<T>(value: &T) -> Result<Vec<u8>, StatusCode>
+where
+    T: BinaryEncoder<T>,
+{
+    let mut stream = Cursor::new(Vec::with_capacity(value.byte_len()));
+    value.encode(&mut stream)?;
+    Ok(stream.into_inner())
+}
+
+/// Bytes as hex so that a failed comparison shows where the encodings differ
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
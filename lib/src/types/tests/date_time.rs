@@ -85,3 +85,32 @@ fn iso8601() {
     let dt = DateTime::parse_from_rfc3339(lt_min_date).unwrap();
     assert_eq!(epoch, dt.to_rfc3339());
 }
+
+#[test]
+fn saturating_arithmetic() {
+    let huge = chrono::Duration::max_value();
+
+    // Arithmetic that goes beyond the range saturates to the endtimes or the epoch
+    assert!(DateTime::now().checked_add(huge).is_none());
+    assert!(DateTime::now().checked_sub(huge).is_none());
+    assert_eq!(DateTime::now() + huge, DateTime::endtimes());
+    assert_eq!(DateTime::now() - huge, DateTime::epoch());
+    assert_eq!(DateTime::now() + -huge, DateTime::epoch());
+    assert_eq!(DateTime::now() - -huge, DateTime::endtimes());
+
+    // Arithmetic within the range is unaffected
+    let dt = DateTime::ymd(2000, 1, 1);
+    let one_day = chrono::Duration::days(1);
+    assert_eq!(dt + one_day, DateTime::ymd(2000, 1, 2));
+    assert_eq!(dt - one_day, DateTime::ymd(1999, 12, 31));
+    assert_eq!(dt.checked_add(one_day), Some(DateTime::ymd(2000, 1, 2)));
+
+    // Any tick value converts without panicking
+    assert_eq!(DateTime::from(i64::MIN).checked_ticks(), 0);
+    assert_eq!(DateTime::from(i64::MAX - 1).checked_ticks(), i64::MAX);
+    assert_eq!(DateTime::from(-1i64).ticks(), -1);
+
+    // Ticks saturate for times beyond their range
+    assert_eq!(DateTime::from(DateTimeUtc::MAX_UTC).ticks(), i64::MAX);
+    assert_eq!(DateTime::from(DateTimeUtc::MIN_UTC).ticks(), i64::MIN);
+}
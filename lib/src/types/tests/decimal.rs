@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use crate::types::{tests::*, *};
+
+#[test]
+fn serialize() {
+    serialize_test(Decimal::new(0, 0));
+    serialize_test(Decimal::new(12345, 2));
+    serialize_test(Decimal::new(-12345, 2));
+    serialize_test(Decimal::new(i64::MAX, -3));
+    serialize_test(Decimal::new(i64::MIN, 10));
+
+    // The value is the fewest bytes that hold it
+    serialize_and_compare(Decimal::new(127, 2), &[2, 0, 0x7f]);
+    serialize_and_compare(Decimal::new(128, 2), &[2, 0, 0x80, 0]);
+    serialize_and_compare(Decimal::new(-1, 1), &[1, 0, 0xff]);
+    serialize_and_compare(Decimal::new(-129, 0xff), &[0xff, 0, 0x7f, 0xff]);
+}
+
+#[test]
+fn decode_too_large() {
+    // Sign extension beyond 64-bits is allowed, anything else is not
+    let mut stream = Cursor::new(vec![
+        0, 0, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ]);
+    let decimal = Decimal::decode(&mut stream, &DecodingOptions::test()).unwrap();
+    assert_eq!(decimal.value(), -2);
+
+    let mut stream = Cursor::new(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(
+        Decimal::decode(&mut stream, &DecodingOptions::test()).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn extension_object() {
+    let decimal = Decimal::new(-314159, 5);
+    let v = Variant::from(decimal);
+    let v = serialize_test_and_return(v);
+    if let Variant::ExtensionObject(ref eo) = v {
+        assert_eq!(eo.node_id, DataTypeId::Decimal.into());
+    } else {
+        panic!("Decimal is not an extension object");
+    }
+    let result = Decimal::try_from(v).unwrap();
+    assert_eq!(result.value(), -314159);
+    assert_eq!(result.scale(), 5);
+
+    // Extension objects of other types are not decimals
+    let eo = ExtensionObject::from_encodable(DataTypeId::Int64, &10i64);
+    assert_eq!(
+        Decimal::try_from(&eo).unwrap_err(),
+        StatusCode::BadDataTypeIdUnknown
+    );
+}
+
+#[test]
+fn to_from_string() {
+    let decimal = Decimal::from_str("-12.345").unwrap();
+    assert_eq!(decimal.value(), -12345);
+    assert_eq!(decimal.scale(), 3);
+    assert_eq!(decimal.to_string(), "-12.345");
+    assert_eq!(Decimal::from_str("+.5").unwrap(), Decimal::new(5, 1));
+    assert_eq!(Decimal::from_str("12").unwrap(), Decimal::new(12, 0));
+
+    assert_eq!(Decimal::new(-5, 3).to_string(), "-0.005");
+    assert_eq!(Decimal::new(5, 1).to_string(), "0.5");
+    assert_eq!(Decimal::new(12, -2).to_string(), "1200");
+    assert_eq!(Decimal::new(0, 4).to_string(), "0");
+
+    assert!(Decimal::from_str("").is_err());
+    assert!(Decimal::from_str("1.2.3").is_err());
+    assert!(Decimal::from_str("1e3").is_err());
+    assert!(Decimal::from_str("99999999999999999999").is_err());
+}
+
+#[test]
+fn compare() {
+    assert_eq!(Decimal::new(10, 1), Decimal::new(100, 2));
+    assert_eq!(Decimal::new(1, 0), Decimal::new(1000, 3));
+    assert!(Decimal::new(-1, 0) < Decimal::new(1, 3));
+    assert!(Decimal::new(11, 1) > Decimal::new(1099, 3));
+    assert!(Decimal::new(1, -2) > Decimal::new(99, 0));
+}
+
+#[test]
+fn arithmetic() {
+    let a = Decimal::new(1050, 2);
+    let b = Decimal::new(25, 1);
+    let sum = a.checked_add(&b).unwrap();
+    assert_eq!((sum.value(), sum.scale()), (1300, 2));
+    let difference = a.checked_sub(&b).unwrap();
+    assert_eq!((difference.value(), difference.scale()), (800, 2));
+    let product = a.checked_mul(&b).unwrap();
+    assert_eq!((product.value(), product.scale()), (26250, 3));
+    assert_eq!(product.normalize(), Decimal::new(2625, 2));
+    assert_eq!(product.normalize().scale(), 2);
+
+    // Exact where a double is not
+    let a = Decimal::from_str("0.1").unwrap();
+    let b = Decimal::from_str("0.2").unwrap();
+    assert_eq!(a.checked_add(&b).unwrap().to_string(), "0.3");
+
+    assert!(Decimal::new(i64::MAX, 0)
+        .checked_add(&Decimal::new(1, 0))
+        .is_none());
+    assert!(Decimal::new(i64::MAX, 0)
+        .checked_add(&Decimal::new(1, 1))
+        .is_none());
+    assert!(Decimal::new(i64::MIN, 0).checked_neg().is_none());
+}
+
+#[test]
+fn rescale() {
+    let decimal = Decimal::new(12345, 2);
+    assert_eq!(decimal.rescale(4).unwrap().value(), 1234500);
+    assert_eq!(decimal.rescale(1).unwrap().value(), 1235);
+    assert_eq!(Decimal::new(-12345, 2).rescale(1).unwrap().value(), -1235);
+    assert_eq!(decimal.rescale(-1).unwrap().value(), 12);
+    assert_eq!(decimal.round_to_i64(), Some(123));
+    assert!(decimal.rescale(30).is_none());
+    assert_eq!(decimal.rescale(-30).unwrap().value(), 0);
+}
+
+#[test]
+fn floating_point() {
+    assert_eq!(Decimal::new(12345, 2).to_f64(), 123.45);
+    assert_eq!(Decimal::from_f64(123.456, 2), Some(Decimal::new(12346, 2)));
+    assert_eq!(Decimal::from_f64(f64::NAN, 2), None);
+    assert_eq!(Decimal::from_f64(1e30, 2), None);
+}
+
+#[test]
+fn variant_cast() {
+    let v = Variant::from(Decimal::new(12345, 2));
+    assert_eq!(v.cast(VariantTypeId::Double), Variant::Double(123.45));
+    assert_eq!(v.cast(VariantTypeId::String), Variant::from("123.45"));
+    assert_eq!(v.cast(VariantTypeId::Int32), Variant::Int32(123));
+    assert_eq!(v.cast(VariantTypeId::Byte), Variant::Byte(123));
+    assert_eq!(
+        Variant::from(Decimal::new(1000, 0)).cast(VariantTypeId::Byte),
+        Variant::Empty
+    );
+    // Large integers keep their precision
+    assert_eq!(
+        Variant::from(Decimal::new(i64::MAX, 0)).cast(VariantTypeId::Int64),
+        Variant::Int64(i64::MAX)
+    );
+    assert_eq!(f64::try_from(v), Ok(123.45));
+
+    assert_eq!(
+        Decimal::try_from(Variant::from("1.5")),
+        Ok(Decimal::new(15, 1))
+    );
+    assert_eq!(
+        Decimal::try_from(Variant::from(7u16)),
+        Ok(Decimal::new(7, 0))
+    );
+    assert!(Decimal::try_from(Variant::from(1.5f64)).is_err());
+}
@@ -423,6 +423,77 @@ fn variant() {
     serialize_test(v);
 }
 
+#[test]
+fn data_value_picoseconds() {
+    let now = DateTime::now();
+    // Picoseconds without their timestamp are discarded
+    let v = DataValue {
+        value: Some(Variant::from(1i32)),
+        status: None,
+        source_timestamp: None,
+        source_picoseconds: Some(100),
+        server_timestamp: Some(now),
+        server_picoseconds: Some(200),
+    };
+    let expected = DataValue {
+        source_picoseconds: None,
+        ..v.clone()
+    };
+    serialize_test_expected(v, expected);
+
+    // Picoseconds above 9999 are discarded
+    let v = DataValue {
+        value: Some(Variant::from(1i32)),
+        status: None,
+        source_timestamp: Some(now),
+        source_picoseconds: Some(9999),
+        server_timestamp: Some(now),
+        server_picoseconds: Some(10000),
+    };
+    let expected = DataValue {
+        server_picoseconds: None,
+        ..v.clone()
+    };
+    serialize_test_expected(v, expected);
+}
+
+#[test]
+fn data_value_strip_timestamps() {
+    let now = DateTime::now();
+    let v = DataValue {
+        value: Some(Variant::from(1i32)),
+        status: None,
+        source_timestamp: Some(now),
+        source_picoseconds: Some(100),
+        server_timestamp: Some(now),
+        server_picoseconds: Some(200),
+    };
+    let strip = |timestamps_to_return| {
+        let mut v = v.clone();
+        v.strip_timestamps(timestamps_to_return);
+        v
+    };
+
+    assert_eq!(strip(TimestampsToReturn::Both), v);
+
+    let source = strip(TimestampsToReturn::Source);
+    assert_eq!(source.source_timestamp, Some(now));
+    assert_eq!(source.source_picoseconds, Some(100));
+    assert!(source.server_timestamp.is_none() && source.server_picoseconds.is_none());
+
+    let server = strip(TimestampsToReturn::Server);
+    assert!(server.source_timestamp.is_none() && server.source_picoseconds.is_none());
+    assert_eq!(server.server_timestamp, Some(now));
+    assert_eq!(server.server_picoseconds, Some(200));
+
+    for timestamps_to_return in [TimestampsToReturn::Neither, TimestampsToReturn::Invalid] {
+        let neither = strip(timestamps_to_return);
+        assert!(neither.source_timestamp.is_none() && neither.source_picoseconds.is_none());
+        assert!(neither.server_timestamp.is_none() && neither.server_picoseconds.is_none());
+        assert_eq!(neither.value, v.value);
+    }
+}
+
 #[test]
 fn variant_single_dimension_array() {
     let values = vec![
@@ -554,3 +625,88 @@ fn deep_encoding() {
     let res = Variant::decode(&mut stream, &decoding_options);
     assert_eq!(res.unwrap_err(), StatusCode::BadDecodingError);
 }
+
+#[test]
+fn canonical_encoding() {
+    use crate::types::testing::*;
+
+    // Empty and null arrays, strings and byte strings have the same canonical form
+    let read_value_id = |index_range: UAString| ReadValueId {
+        node_id: NodeId::new(2, 100),
+        attribute_id: 13,
+        index_range,
+        data_encoding: QualifiedName::null(),
+    };
+    let request_header = RequestHeader::dummy();
+    let read_request = |nodes_to_read| ReadRequest {
+        request_header: request_header.clone(),
+        max_age: 0.0,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read,
+    };
+    let empty = read_request(Some(vec![read_value_id(UAString::from(""))]));
+    let null = read_request(Some(vec![read_value_id(UAString::null())]));
+    assert_ne!(empty.encode_to_vec(), null.encode_to_vec());
+    assert_eq!(
+        encode_canonical(&empty).unwrap(),
+        encode_canonical(&null).unwrap()
+    );
+    assert_eq!(
+        encode_canonical(&read_request(Some(Vec::new()))).unwrap(),
+        encode_canonical(&read_request(None)).unwrap()
+    );
+    assert_eq!(
+        encode_canonical(&ByteString::from(Vec::new())).unwrap(),
+        ByteString::null().encode_to_vec()
+    );
+
+    // Canonical form keeps values which are not empty
+    assert_eq!(encode_canonical(&empty).unwrap().len(), null.byte_len());
+    assert_eq!(
+        encode_canonical(&UAString::from("x")).unwrap(),
+        UAString::from("x").encode_to_vec()
+    );
+
+    // Golden bytes
+    assert_golden(&UAString::from(""), &[0xff, 0xff, 0xff, 0xff]);
+    assert_golden(&NodeId::new(2, 100), &[0x01, 0x02, 0x64, 0x00]);
+}
+
+#[test]
+fn canonical_json_sorts_keys() {
+    use crate::types::testing::*;
+
+    let read_value_id = ReadValueId {
+        node_id: NodeId::new(2, 100),
+        attribute_id: 13,
+        index_range: UAString::null(),
+        data_encoding: QualifiedName::null(),
+    };
+    let json = canonical_json(&read_value_id).unwrap();
+    let attribute_id = json.find("\"AttributeId\"").unwrap();
+    let index_range = json.find("\"IndexRange\"").unwrap();
+    let node_id = json.find("\"NodeId\"").unwrap();
+    assert!(attribute_id < index_range && index_range < node_id);
+
+    // Key order and whitespace in the golden JSON do not matter
+    let golden = serde_json::to_string_pretty(&read_value_id).unwrap();
+    assert_golden_json(&read_value_id, &golden);
+}
+
+#[test]
+fn roundtrip_helpers() {
+    use crate::types::testing::*;
+
+    assert_roundtrip(&ReadValueId::from(NodeId::new(1, "Hello")));
+    assert_roundtrip(&Variant::from(vec![1u32, 2, 3]));
+    assert_roundtrip_expected(
+        &DateTime::ymd_hms_nano(1600, 1, 1, 0, 0, 0, 0),
+        &DateTime::null(),
+    );
+}
+
+#[test]
+#[should_panic]
+fn assert_golden_mismatch() {
+    crate::types::testing::assert_golden(&UAString::from("a"), &[0xff, 0xff, 0xff, 0xff]);
+}
@@ -539,3 +539,259 @@ fn serialize_variant_multi_dimension_array() {
 }
 
  */
+
+mod json_encoder {
+    use serde_json::json;
+
+    use crate::types::*;
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: JsonEncoder,
+    {
+        let json = value.to_json(JsonEncoding::Reversible);
+        T::from_json(&json, &DecodingOptions::test()).unwrap()
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(true.encode_json(JsonEncoding::Reversible), json!(true));
+        assert_eq!((-5i8).encode_json(JsonEncoding::Reversible), json!(-5));
+        // 64-bit integers are strings
+        assert_eq!(
+            i64::MIN.encode_json(JsonEncoding::Reversible),
+            json!("-9223372036854775808")
+        );
+        assert_eq!(round_trip(&u64::MAX), u64::MAX);
+        assert_eq!(
+            f64::INFINITY.encode_json(JsonEncoding::Reversible),
+            json!("Infinity")
+        );
+        assert!(round_trip(&f32::NAN).is_nan());
+        assert_eq!(round_trip(&1.5f64), 1.5);
+
+        // Null decodes as the default value
+        let options = DecodingOptions::test();
+        assert_eq!(u32::decode_json(&json!(null), &options).unwrap(), 0);
+        assert!(UAString::decode_json(&json!(null), &options)
+            .unwrap()
+            .is_null());
+
+        // Out of range and wrong types are errors
+        assert_eq!(
+            u8::decode_json(&json!(256), &options).unwrap_err(),
+            StatusCode::BadDecodingError
+        );
+        assert_eq!(
+            bool::decode_json(&json!("true"), &options).unwrap_err(),
+            StatusCode::BadDecodingError
+        );
+    }
+
+    #[test]
+    fn built_in_types() {
+        let v = ByteString::from(vec![1u8, 2, 3]);
+        assert_eq!(v.encode_json(JsonEncoding::Reversible), json!("AQID"));
+        assert_eq!(round_trip(&v), v);
+
+        let v = NodeId::new(2, "Hello");
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Type": 1, "Id": "Hello", "Namespace": 2})
+        );
+        assert_eq!(round_trip(&v), v);
+
+        let v = StatusCode::BadDecodingError;
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!(StatusCode::BadDecodingError.bits())
+        );
+        assert_eq!(
+            v.encode_json(JsonEncoding::NonReversible),
+            json!({"Code": StatusCode::BadDecodingError.bits(), "Symbol": "BadDecodingError"})
+        );
+        assert_eq!(round_trip(&v), v);
+
+        let v = LocalizedText::new("en", "Hello");
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Locale": "en", "Text": "Hello"})
+        );
+        assert_eq!(v.encode_json(JsonEncoding::NonReversible), json!("Hello"));
+        assert_eq!(round_trip(&v), v);
+
+        let v = QualifiedName::new(3, "Name");
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Name": "Name", "Uri": 3})
+        );
+        assert_eq!(round_trip(&v), v);
+
+        let v = ExtensionObject::from_encodable(NodeId::new(2, 1), &1u32);
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"TypeId": {"Id": 1, "Namespace": 2}, "Encoding": 1, "Body": "AQAAAA=="})
+        );
+        assert_eq!(
+            v.encode_json(JsonEncoding::NonReversible),
+            json!("AQAAAA==")
+        );
+        assert_eq!(round_trip(&v), v);
+        assert_eq!(
+            round_trip(&ExtensionObject::null()),
+            ExtensionObject::null()
+        );
+
+        let v = DataValue {
+            value: Some(Variant::from(10i32)),
+            status: Some(StatusCode::Good),
+            source_timestamp: Some(DateTime::rfc3339_now()),
+            source_picoseconds: Some(5),
+            server_timestamp: None,
+            server_picoseconds: None,
+        };
+        assert_eq!(round_trip(&v), v);
+
+        // Picoseconds without a timestamp, or above 9999, are discarded
+        let v = DataValue::decode_json(
+            &json!({"Value": {"Type": 6, "Body": 1}, "SourcePicoseconds": 10, "ServerTimestamp": "2020-01-01T00:00:00Z", "ServerPicoseconds": 10000}),
+            &DecodingOptions::test(),
+        )
+        .unwrap();
+        assert!(v.source_picoseconds.is_none());
+        assert!(v.server_timestamp.is_some());
+        assert!(v.server_picoseconds.is_none());
+    }
+
+    #[test]
+    fn variant() {
+        let v = Variant::from(5i32);
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Type": 6, "Body": 5})
+        );
+        assert_eq!(v.encode_json(JsonEncoding::NonReversible), json!(5));
+        assert_eq!(round_trip(&v), v);
+        assert_eq!(
+            Variant::Empty.encode_json(JsonEncoding::Reversible),
+            json!(null)
+        );
+        assert_eq!(round_trip(&Variant::Empty), Variant::Empty);
+
+        // Nested variants and other built-in types in a variant
+        let v = Variant::Variant(Box::new(Variant::from("Hello")));
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Type": 24, "Body": {"Type": 12, "Body": "Hello"}})
+        );
+        assert_eq!(round_trip(&v), v);
+        let v = Variant::from(LocalizedText::new("en", "Hello"));
+        assert_eq!(round_trip(&v), v);
+
+        // Single dimension arrays
+        let v = Variant::from(vec![1u16, 2, 3]);
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Type": 5, "Body": [1, 2, 3]})
+        );
+        assert_eq!(v.encode_json(JsonEncoding::NonReversible), json!([1, 2, 3]));
+        assert_eq!(round_trip(&v), v);
+
+        // Multi-dimensional arrays are nested for the non-reversible encoding
+        let v = Variant::from(
+            Array::new_multi(
+                VariantTypeId::Int32,
+                (1..=6).map(Variant::from).collect::<Vec<_>>(),
+                vec![2u32, 3],
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            v.encode_json(JsonEncoding::Reversible),
+            json!({"Type": 6, "Body": [1, 2, 3, 4, 5, 6], "Dimensions": [2, 3]})
+        );
+        assert_eq!(
+            v.encode_json(JsonEncoding::NonReversible),
+            json!([[1, 2, 3], [4, 5, 6]])
+        );
+        assert_eq!(round_trip(&v), v);
+
+        // Invalid types and dimensions
+        let options = DecodingOptions::test();
+        assert!(Variant::decode_json(&json!({"Type": 26, "Body": 1}), &options).is_err());
+        assert!(Variant::decode_json(&json!({"Type": 6, "Body": "x"}), &options).is_err());
+        assert!(Variant::decode_json(
+            &json!({"Type": 6, "Body": [1, 2, 3], "Dimensions": [2, 2]}),
+            &options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn service_types() {
+        // Date times are written to the millisecond
+        let mut request_header = RequestHeader::dummy();
+        request_header.timestamp = DateTime::rfc3339_now();
+        let request = ReadRequest {
+            request_header,
+            max_age: 100.0,
+            timestamps_to_return: TimestampsToReturn::Both,
+            nodes_to_read: Some(vec![ReadValueId::from(NodeId::new(2, 10))]),
+        };
+        let json = request.encode_json(JsonEncoding::Reversible);
+        assert_eq!(json["MaxAge"], json!(100.0));
+        assert_eq!(json["TimestampsToReturn"], json!(2));
+        assert_eq!(
+            json["NodesToRead"][0]["NodeId"],
+            json!({"Id": 10, "Namespace": 2})
+        );
+        assert_eq!(json["NodesToRead"][0]["AttributeId"], json!(13));
+        assert_eq!(round_trip(&request), request);
+
+        // Enumerations have their name in the non-reversible encoding
+        let json = request.encode_json(JsonEncoding::NonReversible);
+        assert_eq!(json["TimestampsToReturn"], json!("Both_2"));
+
+        // Missing fields are decoded as their defaults
+        let request =
+            ReadRequest::decode_json(&json!({"TimestampsToReturn": 1}), &DecodingOptions::test())
+                .unwrap();
+        assert_eq!(request.max_age, 0.0);
+        assert_eq!(request.timestamps_to_return, TimestampsToReturn::Server);
+        assert!(request.nodes_to_read.is_none());
+        assert!(request.request_header.authentication_token.is_null());
+
+        // Option sets are numbers
+        let v = AccessLevelExType::from_bits_truncate(3);
+        assert_eq!(v.encode_json(JsonEncoding::Reversible), json!(3));
+        assert_eq!(round_trip(&v), v);
+    }
+
+    #[test]
+    fn decoding_limits() {
+        let options = DecodingOptions {
+            max_string_length: 5,
+            max_array_length: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            UAString::decode_json(&json!("Hello World"), &options).unwrap_err(),
+            StatusCode::BadDecodingError
+        );
+        assert_eq!(
+            Vec::<u32>::decode_json(&json!([1, 2, 3]), &options).unwrap_err(),
+            StatusCode::BadDecodingError
+        );
+        assert!(Variant::decode_json(&json!({"Type": 7, "Body": [1, 2, 3]}), &options).is_err());
+
+        // Nesting variants beyond the decoding depth is an error
+        let mut json = json!({"Type": 6, "Body": 1});
+        for _ in 0..200 {
+            json = json!({"Type": 24, "Body": json});
+        }
+        assert_eq!(
+            Variant::decode_json(&json, &DecodingOptions::test()).unwrap_err(),
+            StatusCode::BadDecodingError
+        );
+    }
+}
@@ -1,8 +1,14 @@
 mod date_time;
+mod decimal;
 mod encoding;
 mod json;
 mod node_id;
+#[cfg(feature = "query-services")]
+mod query;
+mod struct_value;
+mod ua_structure;
 mod variant;
+mod xml;
 
 use std::cmp::PartialEq;
 use std::fmt::Debug;
@@ -100,7 +100,7 @@ fn expanded_node_id() {
         "svr=5;ns=5;",
         "svr=5;ns=5;x=",
         "svr=5;ns u=foo;s=Hello World",
-        "nsu=foo;s=Hello World",
+        "nsu=foo;ns=5;s=Hello World",
         "svr=5;nsu=foo;ns=5;s=Hello World",
         "svr=5;ns=5;nsu=foo;s=Hello World",
     ]
@@ -116,11 +116,41 @@ fn expanded_node_id() {
     assert!(ExpandedNodeId::from_str("svr=5;ns=22;s=Hello World").is_ok());
     assert!(ExpandedNodeId::from_str("svr=5;nsu=foo;s=Hello World").is_ok());
 
+    // The server index and namespace may be omitted when they are 0
+    let node_id = ExpandedNodeId::from_str("nsu=http://foo;s=Hello World").unwrap();
+    assert_eq!(node_id.server_index, 0);
+    assert_eq!(node_id.namespace_uri.as_ref(), "http://foo");
+    assert_eq!(format!("{}", node_id), "nsu=http://foo;s=Hello World");
+    let node_id = ExpandedNodeId::from_str("ns=3;g=72962b91-fa75-4ae6-8d28-b404dc7daf63").unwrap();
+    assert_eq!(node_id.server_index, 0);
+    assert_eq!(
+        node_id.node_id,
+        NodeId::new(
+            3,
+            Guid::from_str("72962b91-fa75-4ae6-8d28-b404dc7daf63").unwrap()
+        )
+    );
+    assert_eq!(
+        format!("{}", node_id),
+        "ns=3;g=72962b91-fa75-4ae6-8d28-b404dc7daf63"
+    );
+    let node_id = ExpandedNodeId::from_str("b=M/RbKBsRVkePCePcx24oRA==").unwrap();
+    assert_eq!(
+        node_id,
+        NodeId::new(
+            0,
+            ByteString::from_base64("M/RbKBsRVkePCePcx24oRA==").unwrap()
+        )
+        .into()
+    );
+
     // Test escaping from a string
     let node_id = ExpandedNodeId::from_str("svr=5;nsu=foo%3b%25;i=22").unwrap();
     assert_eq!(node_id.server_index, 5);
     assert_eq!(node_id.namespace_uri.as_ref(), "foo;%");
     assert_eq!(node_id.node_id, NodeId::from_str("i=22").unwrap());
+    let node_id = ExpandedNodeId::from_str("nsu=foo%3Bbar;i=22").unwrap();
+    assert_eq!(node_id.namespace_uri.as_ref(), "foo;bar");
 
     // Test escaping into a string
     let node_id = ExpandedNodeId {
@@ -145,3 +175,21 @@ fn expanded_node_id() {
         node_id
     );
 }
+
+#[test]
+fn expanded_node_id_to_node_id() {
+    let namespaces = ["http://opcfoundation.org/UA/", "urn:foo", "urn:bar"];
+    let node_id = ExpandedNodeId::from_str("nsu=urn:bar;s=Hello World").unwrap();
+    assert_eq!(
+        node_id.to_node_id(&namespaces),
+        Some(NodeId::new(2, "Hello World"))
+    );
+    let node_id = ExpandedNodeId::from_str("ns=5;i=10").unwrap();
+    assert_eq!(node_id.to_node_id(&namespaces), Some(NodeId::new(5, 10)));
+
+    // Unknown namespace uri or another server
+    let node_id = ExpandedNodeId::from_str("nsu=urn:baz;s=Hello World").unwrap();
+    assert_eq!(node_id.to_node_id(&namespaces), None);
+    let node_id = ExpandedNodeId::from_str("svr=1;ns=1;i=10").unwrap();
+    assert_eq!(node_id.to_node_id(&namespaces), None);
+}
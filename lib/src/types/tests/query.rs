@@ -0,0 +1,57 @@
+use crate::types::{tests::*, *};
+
+#[test]
+fn query_data_set_values() {
+    let speed = QueryDataDescription::value(&[QualifiedName::new(2, "Speed")]);
+    let name = QueryDataDescription::new(&[][..], AttributeId::DisplayName);
+    let pump_type = NodeTypeDescription::new(NodeId::new(2, "PumpType"), false)
+        .data_to_return(speed.clone())
+        .data_to_return(name.clone());
+    let device_type =
+        NodeTypeDescription::new(NodeId::new(2, "DeviceType"), true).data_to_return(name.clone());
+    let node_types = [pump_type, device_type];
+    assert_eq!(
+        speed.relative_path.elements.as_ref().unwrap()[0].target_name,
+        QualifiedName::new(2, "Speed")
+    );
+    assert_eq!(name.relative_path.elements, Some(Vec::new()));
+    assert_eq!(name.attribute_id, AttributeId::DisplayName as u32);
+    serialize_test(node_types[0].clone());
+
+    // Values are paired with the data to return of the node type
+    let pump = QueryDataSet {
+        node_id: NodeId::new(2, "Pump1").into(),
+        type_definition_node: NodeId::new(2, "PumpType").into(),
+        values: Some(vec![Variant::from(1.5f64), Variant::from("Pump 1")]),
+    };
+    serialize_test(pump.clone());
+    assert_eq!(pump.node_type(&node_types), Some(&node_types[0]));
+    let data = pump.data(&node_types);
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0], (&speed, &Variant::from(1.5f64)));
+    assert_eq!(
+        pump.value(&node_types, &name),
+        Some(&Variant::from("Pump 1"))
+    );
+
+    // A subtype of a type which includes subtypes
+    let valve = QueryDataSet {
+        node_id: NodeId::new(2, "Valve1").into(),
+        type_definition_node: NodeId::new(2, "ValveType").into(),
+        values: Some(vec![Variant::from("Valve 1")]),
+    };
+    assert_eq!(valve.node_type(&node_types), Some(&node_types[1]));
+    assert_eq!(
+        valve.value(&node_types, &name),
+        Some(&Variant::from("Valve 1"))
+    );
+    assert_eq!(valve.value(&node_types, &speed), None);
+
+    // An unknown type
+    let unknown = QueryDataSet {
+        node_id: NodeId::new(2, "X").into(),
+        type_definition_node: NodeId::new(2, "XType").into(),
+        values: None,
+    };
+    assert!(unknown.data(&node_types).is_empty());
+}
@@ -0,0 +1,250 @@
+use std::io::Cursor;
+
+use crate::types::{
+    status_code::StatusCode, DataTypeDefinition, DataTypeDefinitions, DataTypeId, DecodingOptions,
+    EnumDefinition, ExtensionObject, FieldValue, LocalizedText, NodeId, StructValue,
+    StructureDefinition, StructureField, StructureType, Variant,
+};
+
+fn field(name: &str, data_type: NodeId, value_rank: i32, is_optional: bool) -> StructureField {
+    StructureField {
+        name: name.into(),
+        description: LocalizedText::null(),
+        data_type,
+        value_rank,
+        array_dimensions: None,
+        max_string_length: 0,
+        is_optional,
+    }
+}
+
+fn structure(
+    encoding_id: u32,
+    structure_type: StructureType,
+    fields: Vec<StructureField>,
+) -> StructureDefinition {
+    StructureDefinition {
+        default_encoding_id: NodeId::new(2, encoding_id),
+        base_data_type: DataTypeId::Structure.into(),
+        structure_type,
+        fields: Some(fields),
+    }
+}
+
+/// Definitions of a point, a line made of points with optional colour and a union of the two,
+/// and an enumeration for the colour
+fn definitions() -> DataTypeDefinitions {
+    let mut definitions = DataTypeDefinitions::new();
+    definitions.add_structure(
+        NodeId::new(2, 1),
+        structure(
+            101,
+            StructureType::Structure,
+            vec![
+                field("X", DataTypeId::Double.into(), -1, false),
+                field("Y", DataTypeId::Double.into(), -1, false),
+                field("Label", DataTypeId::String.into(), -1, false),
+            ],
+        ),
+    );
+    definitions.add_definition(
+        NodeId::new(2, 3),
+        DataTypeDefinition::Enum(EnumDefinition { fields: None }),
+    );
+    definitions.add_structure(
+        NodeId::new(2, 2),
+        structure(
+            102,
+            StructureType::StructureWithOptionalFields,
+            vec![
+                field("Points", NodeId::new(2, 1), 1, false),
+                field("Colour", NodeId::new(2, 3), -1, true),
+                field("Width", DataTypeId::Duration.into(), -1, true),
+                field("Tag", DataTypeId::BaseDataType.into(), -1, false),
+            ],
+        ),
+    );
+    definitions.add_structure(
+        NodeId::new(2, 4),
+        structure(
+            104,
+            StructureType::Union,
+            vec![
+                field("Point", NodeId::new(2, 1), -1, false),
+                field("Count", DataTypeId::UInt32.into(), -1, false),
+            ],
+        ),
+    );
+    definitions
+}
+
+fn point(x: f64, y: f64, label: &str) -> StructValue {
+    StructValue::new(NodeId::new(2, 1))
+        .with_field("X", x)
+        .with_field("Y", y)
+        .with_field("Label", label)
+}
+
+fn round_trip(definitions: &DataTypeDefinitions, value: &StructValue) -> StructValue {
+    let extension_object = definitions.encode(value).unwrap();
+    definitions
+        .decode(&extension_object, &DecodingOptions::test())
+        .unwrap()
+}
+
+#[test]
+fn struct_value_plain() {
+    let definitions = definitions();
+    let value = point(1.5, -2.0, "origin");
+    let extension_object = definitions.encode(&value).unwrap();
+    assert_eq!(extension_object.node_id, NodeId::new(2, 101));
+    let decoded = definitions
+        .decode(&extension_object, &DecodingOptions::test())
+        .unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(decoded.value("X"), Some(&Variant::Double(1.5)));
+    assert_eq!(decoded.value("Label"), Some(&Variant::from("origin")));
+
+    // A structure is encoded as its fields in order with no masks
+    let bytes = match extension_object.body {
+        crate::types::ExtensionObjectEncoding::ByteString(ref b) => b.value.clone().unwrap(),
+        _ => panic!(),
+    };
+    assert_eq!(bytes.len(), 8 + 8 + 4 + 6);
+    assert_eq!(&bytes[0..8], &1.5f64.to_le_bytes());
+}
+
+#[test]
+fn struct_value_optional_fields_and_arrays() {
+    let definitions = definitions();
+    let line = StructValue::new(NodeId::new(2, 2))
+        .with_field(
+            "Points",
+            FieldValue::Array(vec![
+                point(0.0, 0.0, "a").into(),
+                point(1.0, 1.0, "b").into(),
+            ]),
+        )
+        .with_field("Width", 2.5f64)
+        .with_field("Tag", 7u16);
+    let decoded = round_trip(&definitions, &line);
+    assert_eq!(decoded, line);
+    assert!(decoded.field("Colour").is_none());
+
+    // Set the other optional field too, an enumeration that is encoded as Int32
+    let line = line.with_field("Colour", 3i32);
+    let decoded = round_trip(&definitions, &line);
+    assert_eq!(decoded.value("Colour"), Some(&Variant::Int32(3)));
+    assert_eq!(decoded.value("Tag"), Some(&Variant::UInt16(7)));
+    match decoded.field("Points") {
+        Some(FieldValue::Array(points)) => {
+            assert_eq!(points.len(), 2);
+            assert_eq!(points[1], FieldValue::from(point(1.0, 1.0, "b")));
+        }
+        v => panic!("Unexpected points {:?}", v),
+    }
+
+    // Missing mandatory fields and values of the wrong type cannot be encoded
+    let missing = StructValue::new(NodeId::new(2, 2)).with_field("Tag", 1u8);
+    assert_eq!(
+        definitions.encode(&missing).unwrap_err(),
+        StatusCode::BadEncodingError
+    );
+    let wrong_type = point(1.0, 2.0, "a").with_field("X", 1i32);
+    assert_eq!(
+        definitions.encode(&wrong_type).unwrap_err(),
+        StatusCode::BadEncodingError
+    );
+}
+
+#[test]
+fn struct_value_union() {
+    let definitions = definitions();
+    let value = StructValue::new(NodeId::new(2, 4)).with_field("Count", 5u32);
+    assert_eq!(round_trip(&definitions, &value), value);
+    let value = StructValue::new(NodeId::new(2, 4)).with_field("Point", point(3.0, 4.0, "p"));
+    assert_eq!(round_trip(&definitions, &value), value);
+    let value = StructValue::new(NodeId::new(2, 4));
+    assert_eq!(round_trip(&definitions, &value), value);
+
+    // A switch field beyond the fields of the union is an error
+    let mut stream = Cursor::new(10u32.to_le_bytes().to_vec());
+    assert_eq!(
+        definitions
+            .decode_struct(&NodeId::new(2, 4), &mut stream, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn struct_value_unknown_types() {
+    let mut definitions = definitions();
+    let extension_object = ExtensionObject::from_encodable(NodeId::new(2, 999), &1u32);
+    assert_eq!(
+        definitions
+            .decode(&extension_object, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadDataTypeIdUnknown
+    );
+
+    // A field of a custom type that has no definition
+    definitions.add_structure(
+        NodeId::new(2, 5),
+        structure(
+            105,
+            StructureType::Structure,
+            vec![field("Other", NodeId::new(2, 500), -1, false)],
+        ),
+    );
+    let value = StructValue::new(NodeId::new(2, 5)).with_field("Other", 1i32);
+    assert_eq!(
+        definitions.encode(&value).unwrap_err(),
+        StatusCode::BadDataTypeIdUnknown
+    );
+}
+
+#[test]
+fn struct_value_recursion_limit() {
+    // A structure that contains itself is only limited by the decoding depth
+    let mut definitions = DataTypeDefinitions::new();
+    definitions.add_structure(
+        NodeId::new(2, 6),
+        structure(
+            106,
+            StructureType::StructureWithOptionalFields,
+            vec![field("Next", NodeId::new(2, 6), -1, true)],
+        ),
+    );
+    let mut bytes = Vec::new();
+    for _ in 0..1000 {
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+    }
+    let mut stream = Cursor::new(bytes);
+    assert_eq!(
+        definitions
+            .decode_struct(&NodeId::new(2, 6), &mut stream, &DecodingOptions::test())
+            .unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
+
+#[test]
+fn data_type_definition_extension_object() {
+    let definition = DataTypeDefinition::from(structure(
+        101,
+        StructureType::Structure,
+        vec![field("X", DataTypeId::Double.into(), -1, false)],
+    ));
+    let extension_object = ExtensionObject::from(&definition);
+    assert_eq!(
+        DataTypeDefinition::from_extension_object(&extension_object, &DecodingOptions::test())
+            .unwrap(),
+        definition
+    );
+    let extension_object = ExtensionObject::from_encodable(NodeId::new(2, 1), &1u32);
+    assert!(
+        DataTypeDefinition::from_extension_object(&extension_object, &DecodingOptions::test())
+            .is_err()
+    );
+}
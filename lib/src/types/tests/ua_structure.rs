@@ -0,0 +1,92 @@
+use std::convert::TryFrom;
+
+use crate::types::{
+    status_code::StatusCode, BinaryEncoder, DecodingOptions, ExtensionObject, NodeId, UAString,
+    UaStructure, Variant,
+};
+
+use super::serialize_test;
+
+#[derive(Debug, Clone, PartialEq, UaStructure)]
+#[ua(data_type_id = "ns=2;i=3001", encoding_id = "ns=2;i=3002")]
+struct Point {
+    x: f64,
+    y: f64,
+    label: UAString,
+}
+
+#[derive(Debug, Clone, PartialEq, UaStructure)]
+#[ua(data_type_id = "ns=2;s=Line", encoding_id = "ns=2;s=Line_Encoding")]
+struct Line {
+    points: Vec<Point>,
+    tags: Option<Vec<UAString>>,
+    colour: Option<Vec<i32>>,
+    value: Variant,
+}
+
+fn line() -> Line {
+    Line {
+        points: vec![
+            Point {
+                x: 1.0,
+                y: 2.0,
+                label: "a".into(),
+            },
+            Point {
+                x: -1.0,
+                y: 0.5,
+                label: UAString::null(),
+            },
+        ],
+        tags: Some(vec!["x".into(), "y".into()]),
+        colour: None,
+        value: Variant::from(10u16),
+    }
+}
+
+#[test]
+fn ua_structure_encoding() {
+    let point = Point {
+        x: 1.5,
+        y: 3.0,
+        label: "p".into(),
+    };
+    // Fields are encoded in order like the generated structures
+    assert_eq!(point.byte_len(), 8 + 8 + 4 + 1);
+    serialize_test(point);
+    serialize_test(line());
+    serialize_test(Line {
+        points: Vec::new(),
+        tags: None,
+        colour: Some(Vec::new()),
+        value: Variant::Empty,
+    });
+}
+
+#[test]
+fn ua_structure_node_ids() {
+    assert_eq!(Point::data_type_id(), NodeId::new(2, 3001));
+    assert_eq!(Point::encoding_id(), NodeId::new(2, 3002));
+    assert_eq!(Line::data_type_id(), NodeId::new(2, "Line"));
+    assert_eq!(Line::encoding_id(), NodeId::new(2, "Line_Encoding"));
+}
+
+#[test]
+fn ua_structure_extension_object() {
+    let line = line();
+    let extension_object = ExtensionObject::from(&line);
+    assert_eq!(extension_object.node_id, Line::encoding_id());
+    assert_eq!(Line::try_from(&extension_object).unwrap(), line);
+    assert_eq!(
+        extension_object
+            .decode_structure::<Line>(&DecodingOptions::test())
+            .unwrap(),
+        line
+    );
+
+    // An extension object holding a different structure
+    assert_eq!(
+        Point::try_from(&extension_object).unwrap_err(),
+        StatusCode::BadDataTypeIdUnknown
+    );
+}
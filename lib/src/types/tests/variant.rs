@@ -199,6 +199,40 @@ fn variant_multi_dimensional_array() {
     assert!(!v.is_valid());
 }
 
+#[test]
+fn variant_value_rank_and_dimensions() {
+    let v = Variant::from(10);
+    assert_eq!(v.value_rank(), -1);
+    assert_eq!(v.array_dimensions(), None);
+
+    let v = Variant::from(vec![1, 2, 3]);
+    assert_eq!(v.value_rank(), 1);
+    assert_eq!(v.array_dimensions(), Some(vec![3]));
+
+    // 2 rows of 3 columns
+    let values: Vec<Variant> = (0..6).map(Variant::from).collect();
+    let v = Variant::from((VariantTypeId::Int32, values, vec![2u32, 3u32]));
+    assert!(v.is_valid());
+    assert_eq!(v.value_rank(), 2);
+    assert_eq!(v.array_dimensions(), Some(vec![2, 3]));
+
+    match v {
+        Variant::Array(ref array) => {
+            assert_eq!(array.value_at(&[0, 0]), Some(&Variant::Int32(0)));
+            assert_eq!(array.value_at(&[0, 2]), Some(&Variant::Int32(2)));
+            assert_eq!(array.value_at(&[1, 0]), Some(&Variant::Int32(3)));
+            assert_eq!(array.value_at(&[1, 2]), Some(&Variant::Int32(5)));
+            // Out of bounds
+            assert_eq!(array.value_at(&[2, 0]), None);
+            assert_eq!(array.value_at(&[0, 3]), None);
+            // Wrong number of dimensions
+            assert_eq!(array.value_at(&[1]), None);
+            assert_eq!(array.value_at(&[0, 0, 0]), None);
+        }
+        _ => panic!(),
+    }
+}
+
 #[test]
 fn index_of_array() {
     let vars: Vec<Variant> = [1, 2, 3].iter().map(|v| Variant::from(*v)).collect();
@@ -239,6 +273,92 @@ fn index_of_array() {
     assert_eq!(r, StatusCode::BadIndexRangeNoData);
 }
 
+#[test]
+fn index_of_multi_dimensional_array() {
+    // 3 rows of 4 columns
+    let values: Vec<Variant> = (0..12).map(Variant::from).collect();
+    let v = Variant::from((VariantTypeId::Int32, values, vec![3u32, 4u32]));
+
+    // Second row
+    let r = v.range_of(NumericRange::new("1,0:3").unwrap()).unwrap();
+    assert_eq!(r.array_dimensions(), Some(vec![1, 4]));
+    match r {
+        Variant::Array(array) => {
+            assert_eq!(array.values, (4..8).map(Variant::from).collect::<Vec<_>>());
+        }
+        _ => panic!(),
+    }
+
+    // A block of the last two rows and the middle columns, where the max is clipped
+    let r = v.range_of(NumericRange::new("1:5,1:2").unwrap()).unwrap();
+    assert_eq!(r.array_dimensions(), Some(vec![2, 2]));
+    match r {
+        Variant::Array(array) => {
+            let expected: Vec<Variant> = [5, 6, 9, 10].iter().map(|v| Variant::from(*v)).collect();
+            assert_eq!(array.values, expected);
+        }
+        _ => panic!(),
+    }
+
+    // Out of range or the wrong number of dimensions
+    let r = v.range_of(NumericRange::new("3,0").unwrap()).unwrap_err();
+    assert_eq!(r, StatusCode::BadIndexRangeNoData);
+    let r = v.range_of(NumericRange::new("0,0,0").unwrap()).unwrap_err();
+    assert_eq!(r, StatusCode::BadIndexRangeNoData);
+
+    // A second range on an array of strings is a substring of each string
+    let v = Variant::from(vec![
+        String::from("Hello"),
+        String::from("World"),
+        String::from("Rust"),
+    ]);
+    let r = v.range_of(NumericRange::new("1:2,0:2").unwrap()).unwrap();
+    assert_eq!(
+        r,
+        Variant::from(vec![String::from("Wor"), String::from("Rus")])
+    );
+}
+
+#[test]
+fn set_range_of_multi_dimensional_array() {
+    // 3 rows of 4 columns
+    let mut v = Variant::from((
+        VariantTypeId::Int32,
+        vec![Variant::from(0); 12],
+        vec![3u32, 4u32],
+    ));
+
+    let other = Variant::from((
+        VariantTypeId::Int32,
+        vec![
+            Variant::from(1),
+            Variant::from(2),
+            Variant::from(3),
+            Variant::from(4),
+        ],
+        vec![2u32, 2u32],
+    ));
+    v.set_range_of(NumericRange::new("1:2,2:3").unwrap(), &other)
+        .unwrap();
+    match v {
+        Variant::Array(ref array) => {
+            assert_eq!(array.dimensions, Some(vec![3, 4]));
+            let expected: Vec<Variant> = [0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 3, 4]
+                .iter()
+                .map(|v| Variant::from(*v))
+                .collect();
+            assert_eq!(array.values, expected);
+        }
+        _ => panic!(),
+    }
+
+    // The other array must fill the range
+    let r = v
+        .set_range_of(NumericRange::new("0:2,0").unwrap(), &other)
+        .unwrap_err();
+    assert_eq!(r, StatusCode::BadIndexRangeNoData);
+}
+
 #[test]
 fn index_of_string() {
     let v: Variant = "Hello World".into();
@@ -1442,6 +1562,123 @@ fn variant_cast_expanded_node_id() {
     );
 }
 
+#[test]
+fn variant_convert_out_of_range() {
+    // Implicit conversions fail when the value does not fit in the target type
+    assert_eq!(
+        Variant::from(200u8).convert(VariantTypeId::SByte),
+        Variant::Empty
+    );
+    assert_eq!(
+        Variant::from(100u8).convert(VariantTypeId::SByte),
+        Variant::SByte(100)
+    );
+    assert_eq!(
+        Variant::from(40000u16).convert(VariantTypeId::Int16),
+        Variant::Empty
+    );
+    assert_eq!(
+        Variant::from(u32::MAX).convert(VariantTypeId::Int32),
+        Variant::Empty
+    );
+    assert_eq!(
+        Variant::from(u64::MAX).convert(VariantTypeId::Int64),
+        Variant::Empty
+    );
+    assert_eq!(
+        Variant::from(5u64).convert(VariantTypeId::Int64),
+        Variant::Int64(5)
+    );
+}
+
+#[test]
+fn variant_convert_string_to_boolean() {
+    // Strings are case-insensitive
+    assert_eq!(
+        Variant::from("TRUE").convert(VariantTypeId::Boolean),
+        Variant::Boolean(true)
+    );
+    assert_eq!(
+        Variant::from("False").convert(VariantTypeId::Boolean),
+        Variant::Boolean(false)
+    );
+    assert_eq!(
+        Variant::from("yes").convert(VariantTypeId::Boolean),
+        Variant::Empty
+    );
+}
+
+#[test]
+fn variant_convert_array() {
+    let v = Variant::from(vec![1i32, 2, 3]);
+    let expected = Variant::from(vec![1i64, 2, 3]);
+    assert_eq!(v.convert(VariantTypeId::Int64), expected);
+
+    // Narrowing needs an explicit cast
+    assert_eq!(v.convert(VariantTypeId::Byte), Variant::Empty);
+    assert_eq!(v.cast(VariantTypeId::Byte), Variant::from(vec![1u8, 2, 3]));
+
+    // Every element has to convert
+    let v = Variant::from(vec![1i32, 300]);
+    assert_eq!(v.cast(VariantTypeId::Byte), Variant::Empty);
+}
+
+#[test]
+fn variant_try_from() {
+    assert_eq!(u8::try_from(Variant::from("12")), Ok(12u8));
+    assert_eq!(f64::try_from(Variant::from(12i32)), Ok(12f64));
+    assert_eq!(i32::try_from(Variant::from(12.6f64)), Ok(13i32));
+    assert_eq!(bool::try_from(Variant::from(1i32)), Ok(true));
+    assert_eq!(u8::try_from(Variant::from(-1i32)), Err(()));
+
+    assert_eq!(String::try_from(Variant::from(12i32)), Ok("12".to_string()));
+    assert_eq!(String::try_from(Variant::from(UAString::null())), Err(()));
+    assert_eq!(
+        UAString::try_from(Variant::from(true)),
+        Ok(UAString::from("true"))
+    );
+
+    let now = DateTime::now();
+    assert_eq!(DateTime::try_from(Variant::from(now)), Ok(now));
+    assert_eq!(
+        DateTime::try_from(Variant::from("2024-01-02T03:04:05Z")),
+        DateTime::from_str("2024-01-02T03:04:05Z")
+    );
+    assert_eq!(DateTime::try_from(Variant::from(10i64)), Err(()));
+
+    let guid = Guid::new();
+    assert_eq!(
+        Guid::try_from(Variant::from(guid.to_string())),
+        Ok(guid.clone())
+    );
+    assert_eq!(
+        ByteString::try_from(Variant::from(guid.clone())),
+        Ok(ByteString::from(guid.as_bytes().to_vec()))
+    );
+
+    assert_eq!(
+        NodeId::try_from(Variant::from("ns=2;s=Hello")),
+        Ok(NodeId::new(2, "Hello"))
+    );
+    assert_eq!(
+        ExpandedNodeId::try_from(Variant::from(NodeId::new(2, 10))),
+        Ok(ExpandedNodeId::from(NodeId::new(2, 10)))
+    );
+    assert_eq!(
+        QualifiedName::try_from(Variant::from("Name")),
+        Ok(QualifiedName::new(0, "Name"))
+    );
+    assert_eq!(
+        LocalizedText::try_from(Variant::from("Text")),
+        Ok(LocalizedText::new("", "Text"))
+    );
+    assert_eq!(
+        StatusCode::try_from(Variant::from(StatusCode::BadNoMatch.bits())),
+        Ok(StatusCode::BadNoMatch)
+    );
+    assert_eq!(NodeId::try_from(Variant::from(1.5f64)), Err(()));
+}
+
 #[test]
 fn variant_bytestring_to_bytearray() {
     let v = ByteString::from(&[0x1, 0x2, 0x3, 0x4]);
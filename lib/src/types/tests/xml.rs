@@ -0,0 +1,320 @@
+use crate::types::{service_types::Argument, *};
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: XmlEncoder,
+{
+    let xml = value.to_xml();
+    T::from_xml(&xml, &DecodingOptions::test()).unwrap()
+}
+
+fn decode<T>(xml: &str) -> EncodingResult<T>
+where
+    T: XmlEncoder,
+{
+    T::from_xml(xml, &DecodingOptions::test())
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(true.encode_xml("Boolean").text, "true");
+    assert_eq!((-5i8).encode_xml("SByte").text, "-5");
+    assert_eq!(u64::MAX.encode_xml("UInt64").text, "18446744073709551615");
+    assert_eq!(f64::INFINITY.encode_xml("Double").text, "INF");
+    assert_eq!(f32::NEG_INFINITY.encode_xml("Float").text, "-INF");
+    assert!(round_trip(&f64::NAN).is_nan());
+    assert_eq!(round_trip(&1.5f32), 1.5);
+    assert_eq!(round_trip(&i64::MIN), i64::MIN);
+
+    assert!(decode::<bool>("<Boolean>1</Boolean>").unwrap());
+    assert_eq!(decode::<u32>("<UInt32> 7 </UInt32>").unwrap(), 7);
+    assert_eq!(
+        decode::<u8>("<Byte>256</Byte>").unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+    assert_eq!(
+        decode::<bool>("<Boolean>yes</Boolean>").unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    // Arrays have an element per value, named after the type
+    let v = vec![1u16, 2, 3];
+    let element = v.encode_xml("ListOfUInt16");
+    assert_eq!(element.children.len(), 3);
+    assert_eq!(element.children[0].name, "UInt16");
+    assert_eq!(round_trip(&v), v);
+}
+
+#[test]
+fn built_in_types() {
+    // Strings are escaped
+    let v = UAString::from("<a> & \"b\"");
+    assert_eq!(
+        v.encode_xml("String").to_fragment(),
+        "<String>&lt;a&gt; &amp; &quot;b&quot;</String>"
+    );
+    assert_eq!(round_trip(&v), v);
+    assert!(round_trip(&UAString::null()).is_null());
+    assert_eq!(round_trip(&UAString::from("")), UAString::from(""));
+
+    let v = ByteString::from(vec![1u8, 2, 3]);
+    assert_eq!(v.encode_xml("ByteString").text, "AQID");
+    assert_eq!(round_trip(&v), v);
+    // Base64 may be broken over lines
+    assert_eq!(
+        decode::<ByteString>("<ByteString>AQ\n  ID</ByteString>").unwrap(),
+        v
+    );
+
+    let v = Guid::new();
+    assert_eq!(round_trip(&v), v);
+    assert_eq!(
+        decode::<Guid>("<Guid><String>f9e561f3-351c-47a2-b969-b8d6d7226fee</String></Guid>")
+            .unwrap()
+            .to_string(),
+        "f9e561f3-351c-47a2-b969-b8d6d7226fee"
+    );
+
+    let v = DateTime::rfc3339_now();
+    assert_eq!(round_trip(&v), v);
+    assert!(round_trip(&DateTime::null()).is_null());
+
+    let v = StatusCode::BadNodeIdUnknown;
+    assert_eq!(
+        v.encode_xml("StatusCode").child_text("Code"),
+        Some(StatusCode::BadNodeIdUnknown.bits().to_string().as_str())
+    );
+    assert_eq!(round_trip(&v), v);
+
+    let v = NodeId::new(2, "Hello");
+    assert_eq!(
+        v.encode_xml("NodeId").child_text("Identifier"),
+        Some("ns=2;s=Hello")
+    );
+    assert_eq!(round_trip(&v), v);
+    assert!(round_trip(&NodeId::null()).is_null());
+
+    let mut v = ExpandedNodeId::new(NodeId::new(0, 10));
+    v.namespace_uri = UAString::from("urn:test");
+    v.server_index = 3;
+    assert_eq!(round_trip(&v), v);
+
+    let v = QualifiedName::new(1, "Name");
+    assert_eq!(round_trip(&v), v);
+
+    let v = LocalizedText::new("en", "Text");
+    assert_eq!(round_trip(&v), v);
+    assert_eq!(round_trip(&LocalizedText::null()), LocalizedText::null());
+
+    let v = DataValue {
+        value: Some(Variant::from(5i32)),
+        status: Some(StatusCode::UncertainInitialValue),
+        source_timestamp: Some(DateTime::rfc3339_now()),
+        source_picoseconds: Some(10),
+        server_timestamp: None,
+        server_picoseconds: None,
+    };
+    let element = v.encode_xml("DataValue");
+    assert!(element.child("ServerTimestamp").is_none());
+    assert_eq!(round_trip(&v), v);
+
+    let v = DiagnosticInfo {
+        symbolic_id: Some(1),
+        localized_text: Some(2),
+        inner_status_code: Some(StatusCode::BadTimeout),
+        inner_diagnostic_info: Some(Box::new(DiagnosticInfo {
+            additional_info: Some(UAString::from("inner")),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    assert_eq!(round_trip(&v), v);
+}
+
+#[test]
+fn variant() {
+    assert_eq!(round_trip(&Variant::Empty), Variant::Empty);
+
+    // A scalar is held by the value element, named after its type
+    let v = Variant::from(5i32);
+    let element = v.encode_xml("Variant");
+    let value = element.child("Value").unwrap();
+    assert_eq!(value.children[0].name, "Int32");
+    assert_eq!(value.children[0].text, "5");
+    assert_eq!(
+        decode::<Variant>("<Variant><Value><Int32>5</Int32></Value></Variant>").unwrap(),
+        v
+    );
+
+    for v in [
+        Variant::from(true),
+        Variant::from(-1.5f64),
+        Variant::from("Hello"),
+        Variant::from(DateTime::rfc3339_now()),
+        Variant::from(Guid::new()),
+        Variant::from(ByteString::from(vec![1u8, 2])),
+        Variant::from(NodeId::new(1, 100)),
+        Variant::from(ExpandedNodeId::new(NodeId::new(1, 100))),
+        Variant::from(StatusCode::BadTimeout),
+        Variant::from(QualifiedName::new(1, "Name")),
+        Variant::from(LocalizedText::new("en", "Text")),
+        Variant::from(DataValue::value_only(10u16)),
+        Variant::Variant(Box::new(Variant::from(10u8))),
+        Variant::XmlElement(XmlElement::from("<Test a=\"1\">Text</Test>")),
+    ] {
+        assert_eq!(round_trip(&v), v);
+    }
+
+    // Single dimension arrays are lists
+    let v = Variant::from(vec![1u16, 2, 3]);
+    let element = v.encode_xml("Variant");
+    assert_eq!(
+        element.child("Value").unwrap().children[0].name,
+        "ListOfUInt16"
+    );
+    assert_eq!(round_trip(&v), v);
+
+    // Multi-dimensional arrays are matrices
+    let v = Variant::from(
+        Array::new_multi(
+            VariantTypeId::Int32,
+            (1..=6).map(Variant::from).collect::<Vec<_>>(),
+            vec![2u32, 3],
+        )
+        .unwrap(),
+    );
+    let element = v.encode_xml("Variant");
+    let matrix = &element.child("Value").unwrap().children[0];
+    assert_eq!(matrix.name, "Matrix");
+    assert_eq!(matrix.child("Dimensions").unwrap().children.len(), 2);
+    assert_eq!(matrix.child("Elements").unwrap().children.len(), 6);
+    assert_eq!(round_trip(&v), v);
+
+    // Invalid types and dimensions
+    assert!(decode::<Variant>("<Variant><Value><Foo>1</Foo></Value></Variant>").is_err());
+    assert!(decode::<Variant>(
+        "<Variant><Value><ListOfInt32><Byte>1</Byte></ListOfInt32></Value></Variant>"
+    )
+    .is_err());
+    assert!(decode::<Variant>(
+        "<Variant><Value><Matrix><Dimensions><Int32>2</Int32><Int32>2</Int32></Dimensions>\
+         <Elements><Int32>1</Int32></Elements></Matrix></Value></Variant>"
+    )
+    .is_err());
+}
+
+#[test]
+fn service_types() {
+    let mut request_header = RequestHeader::dummy();
+    request_header.timestamp = DateTime::rfc3339_now();
+    let request = ReadRequest {
+        request_header,
+        max_age: 100.0,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: Some(vec![ReadValueId::from(NodeId::new(2, 10))]),
+    };
+    let element = request.encode_xml("ReadRequest");
+    assert_eq!(element.child_text("MaxAge"), Some("100"));
+    // Enumerations are written with their name
+    assert_eq!(element.child_text("TimestampsToReturn"), Some("Both_2"));
+    let read_value_id = element
+        .child("NodesToRead")
+        .and_then(|v| v.child("ReadValueId"))
+        .unwrap();
+    assert_eq!(read_value_id.child_text("AttributeId"), Some("13"));
+    assert_eq!(round_trip(&request), request);
+
+    // Missing fields are decoded as their defaults
+    let request = decode::<ReadRequest>(
+        "<ReadRequest xmlns=\"http://opcfoundation.org/UA/2008/02/Types.xsd\">\
+         <TimestampsToReturn>Server_1</TimestampsToReturn></ReadRequest>",
+    )
+    .unwrap();
+    assert_eq!(request.max_age, 0.0);
+    assert_eq!(request.timestamps_to_return, TimestampsToReturn::Server);
+    assert!(request.nodes_to_read.is_none());
+    assert!(request.request_header.authentication_token.is_null());
+
+    // Option sets are numbers
+    let v = AccessLevelExType::from_bits_truncate(3);
+    assert_eq!(v.encode_xml("AccessLevelExType").text, "3");
+    assert_eq!(round_trip(&v), v);
+}
+
+#[test]
+fn extension_object() {
+    let argument = Argument {
+        name: UAString::from("Arg"),
+        data_type: DataTypeId::Double.into(),
+        value_rank: -1,
+        array_dimensions: None,
+        description: LocalizedText::new("", "An argument"),
+    };
+
+    // An XML body holds the element of the value
+    let v = ExtensionObject::from_xml_encodable(NodeId::new(0, 297), &argument);
+    assert_eq!(
+        v.decode_inner_xml::<Argument>(&DecodingOptions::test())
+            .unwrap(),
+        argument
+    );
+    let element = v.encode_xml("ExtensionObject");
+    let body = element.child("Body").unwrap();
+    assert_eq!(body.children[0].name, "Argument");
+    assert_eq!(body.children[0].child_text("Name"), Some("Arg"));
+    assert_eq!(round_trip(&v), v);
+
+    // A binary body is a byte string
+    let v = ExtensionObject::from_encodable(ObjectId::Argument_Encoding_DefaultBinary, &argument);
+    assert_eq!(
+        v.encode_xml("ExtensionObject")
+            .child("Body")
+            .unwrap()
+            .children[0]
+            .name,
+        "ByteString"
+    );
+    assert_eq!(round_trip(&v), v);
+    assert!(v
+        .decode_inner_xml::<Argument>(&DecodingOptions::test())
+        .is_err());
+
+    assert!(round_trip(&ExtensionObject::null()).is_null());
+}
+
+#[test]
+fn decoding_limits() {
+    let options = DecodingOptions {
+        max_string_length: 5,
+        max_array_length: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        UAString::from_xml("<String>Hello World</String>", &options).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+    assert_eq!(
+        Vec::<u32>::from_xml(
+            "<ListOfUInt32><UInt32>1</UInt32><UInt32>2</UInt32><UInt32>3</UInt32></ListOfUInt32>",
+            &options
+        )
+        .unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    // Nesting variants beyond the decoding depth is an error
+    let mut xml = "<Int32>1</Int32>".to_string();
+    for _ in 0..200 {
+        xml = format!("<Variant><Value>{}</Value></Variant>", xml);
+    }
+    assert_eq!(
+        decode::<Variant>(&xml).unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+
+    // Malformed documents
+    assert_eq!(
+        decode::<u32>("<UInt32>1</Int32>").unwrap_err(),
+        StatusCode::BadDecodingError
+    );
+}
@@ -16,6 +16,7 @@ use crate::types::{
     array::*,
     byte_string::ByteString,
     date_time::DateTime,
+    decimal::Decimal,
     encoding::*,
     expanded_node_id::ExpandedNodeId,
     extension_object::ExtensionObject,
@@ -112,6 +113,22 @@ macro_rules! impl_from_variant_for {
     };
 }
 
+macro_rules! impl_from_variant_for_boxed {
+    ($tp: ty, $vt: expr, $venum: path) => {
+        impl TryFrom<Variant> for $tp {
+            type Error = ();
+            fn try_from(v: Variant) -> Result<Self, Self::Error> {
+                let casted = v.cast($vt);
+                if let $venum(x) = casted {
+                    Ok(*x)
+                } else {
+                    Err(())
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_from_variant_for_array {
     ($tp: ty, $vt: expr, $venum: path) => {
         impl<const N: usize> TryFrom<Variant> for [$tp; N] {
@@ -150,6 +167,35 @@ impl_from_variant_for!(u32, VariantTypeId::UInt32, Variant::UInt32);
 impl_from_variant_for!(u64, VariantTypeId::UInt64, Variant::UInt64);
 impl_from_variant_for!(f32, VariantTypeId::Float, Variant::Float);
 impl_from_variant_for!(f64, VariantTypeId::Double, Variant::Double);
+impl_from_variant_for!(UAString, VariantTypeId::String, Variant::String);
+impl_from_variant_for!(ByteString, VariantTypeId::ByteString, Variant::ByteString);
+impl_from_variant_for!(StatusCode, VariantTypeId::StatusCode, Variant::StatusCode);
+impl_from_variant_for_boxed!(DateTime, VariantTypeId::DateTime, Variant::DateTime);
+impl_from_variant_for_boxed!(Guid, VariantTypeId::Guid, Variant::Guid);
+impl_from_variant_for_boxed!(NodeId, VariantTypeId::NodeId, Variant::NodeId);
+impl_from_variant_for_boxed!(
+    ExpandedNodeId,
+    VariantTypeId::ExpandedNodeId,
+    Variant::ExpandedNodeId
+);
+impl_from_variant_for_boxed!(
+    QualifiedName,
+    VariantTypeId::QualifiedName,
+    Variant::QualifiedName
+);
+impl_from_variant_for_boxed!(
+    LocalizedText,
+    VariantTypeId::LocalizedText,
+    Variant::LocalizedText
+);
+
+impl TryFrom<Variant> for String {
+    type Error = ();
+    fn try_from(v: Variant) -> Result<Self, Self::Error> {
+        // A null string has no native value
+        UAString::try_from(v)?.value().clone().ok_or(())
+    }
+}
 
 impl_from_variant_for_array!(bool, VariantTypeId::Boolean, Variant::Boolean);
 impl_from_variant_for_array!(u8, VariantTypeId::Byte, Variant::Byte);
@@ -704,7 +750,7 @@ impl Variant {
     }
 
     /// Returns the length of just the value, not the encoding flag
-    fn byte_len_variant_value(value: &Variant) -> usize {
+    pub(crate) fn byte_len_variant_value(value: &Variant) -> usize {
         match value {
             Variant::Empty => 0,
             Variant::Boolean(value) => value.byte_len(),
@@ -740,7 +786,10 @@ impl Variant {
     }
 
     /// Encodes just the value, not the encoding flag
-    fn encode_variant_value<S: Write>(stream: &mut S, value: &Variant) -> EncodingResult<usize> {
+    pub(crate) fn encode_variant_value<S: Write>(
+        stream: &mut S,
+        value: &Variant,
+    ) -> EncodingResult<usize> {
         match value {
             Variant::Empty => Ok(0),
             Variant::Boolean(value) => value.encode(stream),
@@ -776,7 +825,7 @@ impl Variant {
     }
 
     /// Reads just the variant value from the stream
-    fn decode_variant_value<S: Read>(
+    pub(crate) fn decode_variant_value<S: Read>(
         stream: &mut S,
         encoding_mask: u8,
         decoding_options: &DecodingOptions,
@@ -1040,6 +1089,24 @@ impl Variant {
                     _ => Variant::Empty,
                 },
 
+                Variant::Array(ref array) => {
+                    Self::convert_array(array, target_type, |v| v.cast(target_type))
+                }
+                Variant::ExtensionObject(ref v) => match Decimal::try_from(v.as_ref()) {
+                    // A decimal casts like a double, except that integers keep their precision
+                    Ok(v) => match target_type {
+                        VariantTypeId::Double => v.to_f64().into(),
+                        VariantTypeId::Float => (v.to_f64() as f32).into(),
+                        VariantTypeId::String => v.to_string().into(),
+                        VariantTypeId::Boolean => cast_to_bool!(v.to_f64() as i64),
+                        _ => v
+                            .round_to_i64()
+                            .map(|v| Variant::from(v).cast(target_type))
+                            .unwrap_or(Variant::Empty),
+                    },
+                    Err(_) => Variant::Empty,
+                },
+
                 // NodeId, LocalizedText, QualifiedName, XmlElement have no explicit cast
                 _ => Variant::Empty,
             }
@@ -1048,6 +1115,30 @@ impl Variant {
         }
     }
 
+    /// Converts an array element by element. The conversion fails if any element fails to convert.
+    fn convert_array<F>(array: &Array, target_type: VariantTypeId, f: F) -> Variant
+    where
+        F: Fn(&Variant) -> Variant,
+    {
+        let values = array
+            .values
+            .iter()
+            .map(|v| match f(v) {
+                Variant::Empty => None,
+                v => Some(v),
+            })
+            .collect::<Option<Vec<Variant>>>();
+        match values {
+            Some(values) => Array {
+                value_type: target_type,
+                values,
+                dimensions: array.dimensions.clone(),
+            }
+            .into(),
+            None => Variant::Empty,
+        }
+    }
+
     /// Performs an IMPLICIT conversion from one type to another
     pub fn convert(&self, target_type: VariantTypeId) -> Variant {
         if self.type_id() == target_type {
@@ -1078,7 +1169,7 @@ impl Variant {
                 VariantTypeId::Int16 => (v as i16).into(),
                 VariantTypeId::Int32 => (v as i32).into(),
                 VariantTypeId::Int64 => (v as i64).into(),
-                VariantTypeId::SByte => (v as i8).into(),
+                VariantTypeId::SByte => cast_to_integer!(v, u8, i8),
                 VariantTypeId::UInt16 => (v as u16).into(),
                 VariantTypeId::UInt32 => (v as u32).into(),
                 VariantTypeId::UInt64 => (v as u64).into(),
@@ -1197,9 +1288,9 @@ impl Variant {
                             // String values containing “true”, “false”, “1” or “0” can be converted
                             // to Boolean values. Other string values cause a conversion error. In
                             // this case Strings are case-insensitive.
-                            if v == "true" || v == "1" {
+                            if v.eq_ignore_ascii_case("true") || v == "1" {
                                 true.into()
-                            } else if v == "false" || v == "0" {
+                            } else if v.eq_ignore_ascii_case("false") || v == "0" {
                                 false.into()
                             } else {
                                 Variant::Empty
@@ -1274,7 +1365,7 @@ impl Variant {
                 match target_type {
                     VariantTypeId::Double => (v as f64).into(),
                     VariantTypeId::Float => (v as f32).into(),
-                    VariantTypeId::Int16 => (v as i16).into(),
+                    VariantTypeId::Int16 => cast_to_integer!(v, u16, i16),
                     VariantTypeId::Int32 => (v as i32).into(),
                     VariantTypeId::Int64 => (v as i64).into(),
                     VariantTypeId::StatusCode => {
@@ -1289,7 +1380,7 @@ impl Variant {
             Variant::UInt32(v) => match target_type {
                 VariantTypeId::Double => (v as f64).into(),
                 VariantTypeId::Float => (v as f32).into(),
-                VariantTypeId::Int32 => (v as i32).into(),
+                VariantTypeId::Int32 => cast_to_integer!(v, u32, i32),
                 VariantTypeId::Int64 => (v as i64).into(),
                 VariantTypeId::UInt64 => (v as u64).into(),
                 _ => Variant::Empty,
@@ -1297,12 +1388,11 @@ impl Variant {
             Variant::UInt64(v) => match target_type {
                 VariantTypeId::Double => (v as f64).into(),
                 VariantTypeId::Float => (v as f32).into(),
-                VariantTypeId::Int64 => (v as i64).into(),
+                VariantTypeId::Int64 => cast_to_integer!(v, u64, i64),
                 _ => Variant::Empty,
             },
-            Variant::Array(_) => {
-                // TODO Arrays including converting array of length 1 to scalar of same type
-                Variant::Empty
+            Variant::Array(ref array) => {
+                Self::convert_array(array, target_type, |v| v.convert(target_type))
             }
             // XmlElement everything is X
             _ => Variant::Empty,
@@ -1363,6 +1453,23 @@ impl Variant {
         matches!(self, Variant::Array(_))
     }
 
+    /// Returns the value rank of the variant, -1 for a scalar or the number of dimensions of an
+    /// array
+    pub fn value_rank(&self) -> i32 {
+        match self {
+            Variant::Array(array) => array.value_rank(),
+            _ => -1,
+        }
+    }
+
+    /// Returns the length of each dimension of an array, or `None` for a scalar
+    pub fn array_dimensions(&self) -> Option<Vec<u32>> {
+        match self {
+            Variant::Array(array) => Some(array.array_dimensions()),
+            _ => None,
+        }
+    }
+
     pub fn is_array_of_type(&self, variant_type: VariantTypeId) -> bool {
         // A non-numeric value in the array means it is not numeric
         match self {
@@ -1552,6 +1659,7 @@ impl Variant {
         // Check value is same type as our array
         match self {
             Variant::Array(ref mut array) => {
+                let dimensions = array.array_dimensions();
                 let values = &mut array.values;
                 match range {
                     NumericRange::None => Err(StatusCode::BadIndexRangeNoData),
@@ -1580,10 +1688,23 @@ impl Variant {
                             Ok(())
                         }
                     }
-                    NumericRange::MultipleRanges(_ranges) => {
-                        // Not yet supported
-                        error!("Multiple ranges not supported");
-                        Err(StatusCode::BadIndexRangeNoData)
+                    NumericRange::MultipleRanges(ranges) => {
+                        // One range per dimension, and the other array must fill the ranges
+                        if ranges.len() != dimensions.len() {
+                            return Err(StatusCode::BadIndexRangeNoData);
+                        }
+                        let bounds = range_bounds(&ranges, &dimensions)?;
+                        let offsets = array_offsets(&dimensions, &bounds);
+                        if offsets.len() != other_values.len()
+                            || offsets.iter().any(|offset| *offset >= values.len())
+                        {
+                            return Err(StatusCode::BadIndexRangeNoData);
+                        }
+                        offsets
+                            .into_iter()
+                            .zip(other_values.iter())
+                            .for_each(|(offset, v)| values[offset] = v.clone());
+                        Ok(())
                     }
                 }
             }
@@ -1639,11 +1760,84 @@ impl Variant {
                     _ => Err(StatusCode::BadIndexRangeNoData),
                 }
             }
-            NumericRange::MultipleRanges(_ranges) => {
-                // Not yet supported
-                error!("Multiple ranges not supported");
-                Err(StatusCode::BadIndexRangeNoData)
-            }
+            NumericRange::MultipleRanges(ranges) => match self {
+                Variant::Array(array) => {
+                    // A range for each dimension of the array, and any remaining ranges apply to
+                    // the values, e.g. a substring of each string in the array
+                    let dimensions = array.array_dimensions();
+                    if ranges.len() < dimensions.len() {
+                        return Err(StatusCode::BadIndexRangeNoData);
+                    }
+                    let (array_ranges, value_ranges) = ranges.split_at(dimensions.len());
+                    let value_range = match value_ranges {
+                        [] => NumericRange::None,
+                        [range] => range.clone(),
+                        ranges => NumericRange::MultipleRanges(ranges.to_vec()),
+                    };
+                    let bounds = range_bounds(array_ranges, &dimensions)?;
+                    let values = array_offsets(&dimensions, &bounds)
+                        .into_iter()
+                        .map(|offset| {
+                            array
+                                .values
+                                .get(offset)
+                                .ok_or(StatusCode::BadIndexRangeNoData)
+                                .and_then(|v| v.range_of(value_range.clone()))
+                        })
+                        .collect::<Result<Vec<Variant>, StatusCode>>()?;
+                    if bounds.len() == 1 {
+                        Ok(Variant::from((array.value_type, values)))
+                    } else {
+                        let dimensions = bounds
+                            .iter()
+                            .map(|(min, max)| (max - min + 1) as u32)
+                            .collect::<Vec<u32>>();
+                        Ok(Variant::from((array.value_type, values, dimensions)))
+                    }
+                }
+                _ => Err(StatusCode::BadIndexRangeNoData),
+            },
         }
     }
 }
+
+/// Finds the (min, max) index of each range into the dimension with the same position. A max
+/// beyond the end of its dimension is clipped, but a min beyond the end means there is no data.
+fn range_bounds(
+    ranges: &[NumericRange],
+    dimensions: &[u32],
+) -> Result<Vec<(usize, usize)>, StatusCode> {
+    ranges
+        .iter()
+        .zip(dimensions.iter())
+        .map(|(range, dimension)| {
+            let (min, max) = match *range {
+                NumericRange::Index(idx) => (idx as usize, idx as usize),
+                NumericRange::Range(min, max) => (min as usize, max as usize),
+                _ => return Err(StatusCode::BadIndexRangeInvalid),
+            };
+            let dimension = *dimension as usize;
+            if min >= dimension {
+                Err(StatusCode::BadIndexRangeNoData)
+            } else {
+                Ok((min, max.min(dimension - 1)))
+            }
+        })
+        .collect()
+}
+
+/// Finds the offsets of values within the bounds of a multi-dimensional array, in the same
+/// row-major order the values are held in.
+fn array_offsets(dimensions: &[u32], bounds: &[(usize, usize)]) -> Vec<usize> {
+    bounds
+        .iter()
+        .zip(dimensions.iter())
+        .fold(vec![0usize], |offsets, ((min, max), dimension)| {
+            offsets
+                .iter()
+                .flat_map(|offset| {
+                    (*min..=*max).map(move |idx| offset * (*dimension as usize) + idx)
+                })
+                .collect()
+        })
+}
@@ -14,6 +14,8 @@
 //!
 //! To distinguish between binary and JSON, the built-in types implement Serde's `Serialize`/`Deserialize`
 //! traits for JSON serialization.
+//!
+//! Variant also implements `JsonEncoder`, which supports arrays and the non-reversible encoding.
 
 use std::fmt;
 
@@ -562,3 +564,236 @@ impl Variant {
         }
     }
 }
+
+/// Variant encoded as an object with its built-in type id, body and the dimensions of a
+/// multi-dimensional array for the reversible encoding, and as just its body for the
+/// non-reversible encoding. Multi-dimensional arrays are written as nested arrays in the
+/// non-reversible encoding.
+impl JsonEncoder for Variant {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let (type_id, body, dimensions) = match self {
+            Variant::Empty => return JsonValue::Null,
+            Variant::Array(array) => {
+                let values = array
+                    .values
+                    .iter()
+                    .map(|v| v.json_body(encoding))
+                    .collect::<Vec<_>>();
+                let dimensions = array.array_dimensions();
+                if encoding == JsonEncoding::NonReversible {
+                    return nested_json_array(&values, &dimensions);
+                }
+                let dimensions = if dimensions.len() > 1 {
+                    Some(dimensions)
+                } else {
+                    None
+                };
+                (array.value_type, JsonValue::Array(values), dimensions)
+            }
+            v => (v.type_id(), v.json_body(encoding), None),
+        };
+        match encoding {
+            JsonEncoding::Reversible => {
+                let mut object = JsonObject::new();
+                write_json_field(&mut object, "Type", &type_id.encoding_mask(), encoding);
+                if !body.is_null() {
+                    object.insert("Body".to_string(), body);
+                }
+                write_json_field(&mut object, "Dimensions", &dimensions, encoding);
+                JsonValue::Object(object)
+            }
+            JsonEncoding::NonReversible => body,
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        // Variant is depth checked to prevent deep recursion
+        let _depth_lock = decoding_options.depth_lock()?;
+        if value.is_null() {
+            return Ok(Variant::Empty);
+        }
+        check_json_object(value)?;
+        let type_id: u8 = read_json_field(value, "Type", decoding_options)?;
+        if type_id > VariantJsonId::DiagnosticInfo as u8 {
+            error!("Invalid variant type {}", type_id);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let type_id = VariantTypeId::from_encoding_mask(type_id)?;
+        let body = value.get("Body").unwrap_or(&JsonValue::Null);
+        if let JsonValue::Array(values) = body {
+            if values.len() > decoding_options.max_array_length {
+                error!(
+                    "Array length {} exceeds decoding limit {}",
+                    values.len(),
+                    decoding_options.max_array_length
+                );
+                return Err(StatusCode::BadDecodingError);
+            }
+            let values = values
+                .iter()
+                .map(|v| Variant::decode_json_body(type_id, v, decoding_options))
+                .collect::<EncodingResult<Vec<_>>>()?;
+            let dimensions: Option<Vec<u32>> =
+                read_json_field(value, "Dimensions", decoding_options)?;
+            if let Some(dimensions) = dimensions {
+                // Check the dimensions without overflowing on malicious values
+                let length = dimensions
+                    .iter()
+                    .try_fold(1u32, |length, d| length.checked_mul(*d));
+                if dimensions.contains(&0) || length != Some(values.len() as u32) {
+                    error!(
+                        "Array dimensions do not match array length {}",
+                        values.len()
+                    );
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    Array::new_multi(type_id, values, dimensions).map(Variant::from)
+                }
+            } else {
+                Array::new(type_id, values).map(Variant::from)
+            }
+        } else {
+            Variant::decode_json_body(type_id, body, decoding_options)
+        }
+    }
+}
+
+/// Nests the values of a multi-dimensional array into an array for each dimension
+fn nested_json_array(values: &[JsonValue], dimensions: &[u32]) -> JsonValue {
+    if dimensions.len() <= 1 || values.is_empty() {
+        JsonValue::Array(values.to_vec())
+    } else {
+        let stride = (values.len() / (dimensions[0] as usize).max(1)).max(1);
+        JsonValue::Array(
+            values
+                .chunks(stride)
+                .map(|v| nested_json_array(v, &dimensions[1..]))
+                .collect(),
+        )
+    }
+}
+
+impl Variant {
+    /// Encodes the value of a scalar variant to JSON, without its type
+    fn json_body(&self, encoding: JsonEncoding) -> JsonValue {
+        match self {
+            Variant::Empty | Variant::Array(_) => JsonValue::Null,
+            Variant::Boolean(v) => v.encode_json(encoding),
+            Variant::SByte(v) => v.encode_json(encoding),
+            Variant::Byte(v) => v.encode_json(encoding),
+            Variant::Int16(v) => v.encode_json(encoding),
+            Variant::UInt16(v) => v.encode_json(encoding),
+            Variant::Int32(v) => v.encode_json(encoding),
+            Variant::UInt32(v) => v.encode_json(encoding),
+            Variant::Int64(v) => v.encode_json(encoding),
+            Variant::UInt64(v) => v.encode_json(encoding),
+            Variant::Float(v) => v.encode_json(encoding),
+            Variant::Double(v) => v.encode_json(encoding),
+            Variant::String(v) => v.encode_json(encoding),
+            Variant::DateTime(v) => v.encode_json(encoding),
+            Variant::Guid(v) => v.encode_json(encoding),
+            Variant::StatusCode(v) => v.encode_json(encoding),
+            Variant::ByteString(v) => v.encode_json(encoding),
+            Variant::XmlElement(v) => v.encode_json(encoding),
+            Variant::QualifiedName(v) => v.encode_json(encoding),
+            Variant::LocalizedText(v) => v.encode_json(encoding),
+            Variant::NodeId(v) => v.encode_json(encoding),
+            Variant::ExpandedNodeId(v) => v.encode_json(encoding),
+            Variant::ExtensionObject(v) => v.encode_json(encoding),
+            Variant::Variant(v) => v.encode_json(encoding),
+            Variant::DataValue(v) => v.encode_json(encoding),
+            Variant::DiagnosticInfo(v) => v.encode_json(encoding),
+        }
+    }
+
+    /// Decodes the value of a scalar variant of the type from JSON
+    fn decode_json_body(
+        type_id: VariantTypeId,
+        value: &JsonValue,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Variant> {
+        let v = match type_id {
+            VariantTypeId::Empty => Variant::Empty,
+            VariantTypeId::Boolean => {
+                Variant::Boolean(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::SByte => {
+                Variant::SByte(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Byte => {
+                Variant::Byte(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Int16 => {
+                Variant::Int16(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::UInt16 => {
+                Variant::UInt16(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Int32 => {
+                Variant::Int32(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::UInt32 => {
+                Variant::UInt32(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Int64 => {
+                Variant::Int64(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::UInt64 => {
+                Variant::UInt64(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Float => {
+                Variant::Float(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Double => {
+                Variant::Double(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::String => {
+                Variant::String(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::DateTime => {
+                Variant::DateTime(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Guid => {
+                Variant::Guid(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::StatusCode => {
+                Variant::StatusCode(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::ByteString => {
+                Variant::ByteString(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::XmlElement => {
+                Variant::XmlElement(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::QualifiedName => {
+                Variant::QualifiedName(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::LocalizedText => {
+                Variant::LocalizedText(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::NodeId => {
+                Variant::NodeId(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::ExpandedNodeId => {
+                Variant::ExpandedNodeId(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::ExtensionObject => {
+                Variant::ExtensionObject(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Variant => {
+                Variant::Variant(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::DataValue => {
+                Variant::DataValue(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::DiagnosticInfo => {
+                Variant::DiagnosticInfo(JsonEncoder::decode_json(value, decoding_options)?)
+            }
+            VariantTypeId::Array => {
+                error!("Variant type cannot be an array");
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(v)
+    }
+}
@@ -0,0 +1,327 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Implements the XML encoding of Variant described by Part 6 5.3.1.17.
+//!
+//! A variant is encoded as an element holding a `Value` element, which holds a single element
+//! named after the type of the value. A single dimension array is a `ListOf<Type>` element with an
+//! element per value, and a multi-dimensional array is a `Matrix` element with its `Dimensions` and
+//! its `Elements`. An empty variant has no `Value`.
+
+use crate::types::*;
+
+/// The element names of the built-in types that a variant can hold
+const VARIANT_XML_TYPES: &[(&str, VariantTypeId)] = &[
+    ("Boolean", VariantTypeId::Boolean),
+    ("SByte", VariantTypeId::SByte),
+    ("Byte", VariantTypeId::Byte),
+    ("Int16", VariantTypeId::Int16),
+    ("UInt16", VariantTypeId::UInt16),
+    ("Int32", VariantTypeId::Int32),
+    ("UInt32", VariantTypeId::UInt32),
+    ("Int64", VariantTypeId::Int64),
+    ("UInt64", VariantTypeId::UInt64),
+    ("Float", VariantTypeId::Float),
+    ("Double", VariantTypeId::Double),
+    ("String", VariantTypeId::String),
+    ("DateTime", VariantTypeId::DateTime),
+    ("Guid", VariantTypeId::Guid),
+    ("ByteString", VariantTypeId::ByteString),
+    ("XmlElement", VariantTypeId::XmlElement),
+    ("NodeId", VariantTypeId::NodeId),
+    ("ExpandedNodeId", VariantTypeId::ExpandedNodeId),
+    ("StatusCode", VariantTypeId::StatusCode),
+    ("QualifiedName", VariantTypeId::QualifiedName),
+    ("LocalizedText", VariantTypeId::LocalizedText),
+    ("ExtensionObject", VariantTypeId::ExtensionObject),
+    ("DataValue", VariantTypeId::DataValue),
+    ("Variant", VariantTypeId::Variant),
+    ("DiagnosticInfo", VariantTypeId::DiagnosticInfo),
+];
+
+fn xml_type_name(type_id: VariantTypeId) -> Option<&'static str> {
+    VARIANT_XML_TYPES
+        .iter()
+        .find(|(_, v)| *v == type_id)
+        .map(|(name, _)| *name)
+}
+
+fn xml_type_id(name: &str) -> EncodingResult<VariantTypeId> {
+    VARIANT_XML_TYPES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| *v)
+        .ok_or_else(|| {
+            error!("Invalid variant type {}", name);
+            StatusCode::BadDecodingError
+        })
+}
+
+impl XmlEncoder for Variant {
+    fn xml_type_name() -> String {
+        "Variant".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value = match self {
+            Variant::Empty => return nil_element(name),
+            Variant::Array(array) => {
+                let Some(type_name) = xml_type_name(array.value_type) else {
+                    error!("Cannot write an array of {:?}", array.value_type);
+                    return nil_element(name);
+                };
+                let values = array
+                    .values
+                    .iter()
+                    .map(|v| v.xml_body(type_name))
+                    .collect::<Vec<_>>();
+                let dimensions = array.array_dimensions();
+                if dimensions.len() > 1 {
+                    let dimensions = dimensions
+                        .iter()
+                        .map(|d| (*d as i32).encode_xml("Int32"))
+                        .collect();
+                    Element::new("Matrix")
+                        .with_child(Element {
+                            name: "Dimensions".into(),
+                            children: dimensions,
+                            ..Default::default()
+                        })
+                        .with_child(Element {
+                            name: "Elements".into(),
+                            children: values,
+                            ..Default::default()
+                        })
+                } else {
+                    Element {
+                        name: format!("ListOf{}", type_name),
+                        children: values,
+                        ..Default::default()
+                    }
+                }
+            }
+            v => match xml_type_name(v.type_id()) {
+                Some(type_name) => v.xml_body(type_name),
+                None => return nil_element(name),
+            },
+        };
+        Element::new(name).with_child(Element::new("Value").with_child(value))
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        // Variant is depth checked to prevent deep recursion
+        let _depth_lock = decoding_options.depth_lock()?;
+        let Some(value) = element
+            .child("Value")
+            .filter(|v| !v.is_nil())
+            .and_then(|v| v.children.first())
+        else {
+            return Ok(Variant::Empty);
+        };
+        if value.name == "Matrix" {
+            let dimensions: Vec<i32> = read_xml_field(value, "Dimensions", decoding_options)?;
+            // The type of the matrix is the type of its elements
+            let Some((elements, first)) = value
+                .child("Elements")
+                .and_then(|e| e.children.first().map(|first| (e, first)))
+            else {
+                error!("Matrix has no elements");
+                return Err(StatusCode::BadDecodingError);
+            };
+            let type_id = xml_type_id(&first.name)?;
+            let values = Variant::decode_xml_array(type_id, elements, decoding_options)?;
+            // Check the dimensions without overflowing on malicious values
+            let length = dimensions.iter().try_fold(1u32, |length, d| {
+                length.checked_mul(u32::try_from(*d).ok()?)
+            });
+            if dimensions.contains(&0) || length != Some(values.len() as u32) {
+                error!(
+                    "Matrix dimensions do not match its {} elements",
+                    values.len()
+                );
+                Err(StatusCode::BadDecodingError)
+            } else {
+                let dimensions = dimensions.iter().map(|d| *d as u32).collect::<Vec<_>>();
+                Array::new_multi(type_id, values, dimensions).map(Variant::from)
+            }
+        } else if let Some(type_name) = value.name.strip_prefix("ListOf") {
+            let type_id = xml_type_id(type_name)?;
+            let values = Variant::decode_xml_array(type_id, value, decoding_options)?;
+            Array::new(type_id, values).map(Variant::from)
+        } else {
+            Variant::decode_xml_body(xml_type_id(&value.name)?, value, decoding_options)
+        }
+    }
+}
+
+impl Variant {
+    /// Encodes the value of a scalar variant to an element named after its type
+    fn xml_body(&self, name: &str) -> Element {
+        match self {
+            Variant::Empty | Variant::Array(_) => nil_element(name),
+            Variant::Boolean(v) => v.encode_xml(name),
+            Variant::SByte(v) => v.encode_xml(name),
+            Variant::Byte(v) => v.encode_xml(name),
+            Variant::Int16(v) => v.encode_xml(name),
+            Variant::UInt16(v) => v.encode_xml(name),
+            Variant::Int32(v) => v.encode_xml(name),
+            Variant::UInt32(v) => v.encode_xml(name),
+            Variant::Int64(v) => v.encode_xml(name),
+            Variant::UInt64(v) => v.encode_xml(name),
+            Variant::Float(v) => v.encode_xml(name),
+            Variant::Double(v) => v.encode_xml(name),
+            Variant::String(v) => v.encode_xml(name),
+            Variant::DateTime(v) => v.encode_xml(name),
+            Variant::Guid(v) => v.encode_xml(name),
+            Variant::StatusCode(v) => v.encode_xml(name),
+            Variant::ByteString(v) => v.encode_xml(name),
+            Variant::XmlElement(v) => {
+                // The element holds the XML itself
+                let element = Element::new(name);
+                if v.is_null() {
+                    element
+                } else {
+                    match parse(v.as_ref()) {
+                        Ok(xml) => element.with_child(xml),
+                        Err(err) => {
+                            error!("Cannot write an XmlElement variant - {}", err);
+                            element
+                        }
+                    }
+                }
+            }
+            Variant::QualifiedName(v) => v.encode_xml(name),
+            Variant::LocalizedText(v) => v.encode_xml(name),
+            Variant::NodeId(v) => v.encode_xml(name),
+            Variant::ExpandedNodeId(v) => v.encode_xml(name),
+            Variant::ExtensionObject(v) => v.encode_xml(name),
+            Variant::Variant(v) => v.encode_xml(name),
+            Variant::DataValue(v) => v.encode_xml(name),
+            Variant::DiagnosticInfo(v) => v.encode_xml(name),
+        }
+    }
+
+    /// Decodes the values of an array of the type, each of which must be an element of the type
+    fn decode_xml_array(
+        type_id: VariantTypeId,
+        element: &Element,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Vec<Variant>> {
+        if element.children.len() > decoding_options.max_array_length {
+            error!(
+                "Array length {} exceeds decoding limit {}",
+                element.children.len(),
+                decoding_options.max_array_length
+            );
+            return Err(StatusCode::BadDecodingError);
+        }
+        element
+            .children
+            .iter()
+            .map(|v| {
+                if xml_type_id(&v.name)? != type_id {
+                    error!("Array of {:?} holds a {} value", type_id, v.name);
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    Variant::decode_xml_body(type_id, v, decoding_options)
+                }
+            })
+            .collect()
+    }
+
+    /// Decodes the value of a scalar variant of the type from XML
+    fn decode_xml_body(
+        type_id: VariantTypeId,
+        element: &Element,
+        decoding_options: &DecodingOptions,
+    ) -> EncodingResult<Variant> {
+        let v = match type_id {
+            VariantTypeId::Empty => Variant::Empty,
+            VariantTypeId::Boolean => {
+                Variant::Boolean(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::SByte => {
+                Variant::SByte(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Byte => {
+                Variant::Byte(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Int16 => {
+                Variant::Int16(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::UInt16 => {
+                Variant::UInt16(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Int32 => {
+                Variant::Int32(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::UInt32 => {
+                Variant::UInt32(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Int64 => {
+                Variant::Int64(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::UInt64 => {
+                Variant::UInt64(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Float => {
+                Variant::Float(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Double => {
+                Variant::Double(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::String => {
+                Variant::String(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::DateTime => {
+                Variant::DateTime(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Guid => {
+                Variant::Guid(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::StatusCode => {
+                Variant::StatusCode(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::ByteString => {
+                Variant::ByteString(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::XmlElement => Variant::XmlElement(
+                element
+                    .children
+                    .first()
+                    .map(|xml| XmlElement::from(xml.to_fragment()))
+                    .unwrap_or_else(XmlElement::null),
+            ),
+            VariantTypeId::QualifiedName => {
+                Variant::QualifiedName(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::LocalizedText => {
+                Variant::LocalizedText(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::NodeId => {
+                Variant::NodeId(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::ExpandedNodeId => {
+                Variant::ExpandedNodeId(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::ExtensionObject => {
+                Variant::ExtensionObject(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Variant => {
+                Variant::Variant(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::DataValue => {
+                Variant::DataValue(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::DiagnosticInfo => {
+                Variant::DiagnosticInfo(XmlEncoder::decode_xml(element, decoding_options)?)
+            }
+            VariantTypeId::Array => {
+                error!("Variant type cannot be an array");
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(v)
+    }
+}
@@ -0,0 +1,617 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Contains the `XmlEncoder` trait for the OPC UA XML encoding described in Part 6 5.3,
+//! implementations of it for primitives and arrays, and the minimal XML reader and writer that it
+//! is built on.
+//!
+//! Documents are read into a tree of elements. Namespace prefixes are dropped from element and
+//! attribute names since OPC UA documents only ever use one namespace for each name.
+//!
+//! Structures are encoded as an element with a child element per structure field, named as it is
+//! in the OPC UA schema. Arrays are encoded as an element with a child element per value, named
+//! after the type of the values. Null values are omitted and a missing element is decoded as the
+//! default value of its type.
+
+use std::fmt::Write;
+
+use crate::types::{encoding::*, status_codes::StatusCode};
+
+/// The namespace of the OPC UA types in XML documents
+pub const XML_TYPES_NAMESPACE: &str = "http://opcfoundation.org/UA/2008/02/Types.xsd";
+
+/// The namespace of the XML schema instance attributes, i.e. `xsi:nil`
+const XML_SCHEMA_INSTANCE_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// An element of an XML document
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Element {
+    /// The name of the element without any namespace prefix
+    pub name: String,
+    /// The attributes of the element, names without any namespace prefix
+    pub attributes: Vec<(String, String)>,
+    /// The child elements
+    pub children: Vec<Element>,
+    /// The text content of the element, with entities resolved
+    pub text: String,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Element {
+        Element {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the value of the named attribute
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first child element with the name
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Returns every child element with the name
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Returns the trimmed text of the first child element with the name
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim())
+    }
+
+    /// Adds an attribute to the element
+    pub fn with_attribute<T: ToString>(mut self, name: &str, value: T) -> Element {
+        self.attributes.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Sets the text content of the element
+    pub fn with_text<T: ToString>(mut self, text: T) -> Element {
+        self.text = text.to_string();
+        self
+    }
+
+    /// Adds a child element to the element
+    pub fn with_child(mut self, child: Element) -> Element {
+        self.children.push(child);
+        self
+    }
+
+    /// Writes the element as the root of an XML document. Child elements are indented, an
+    /// element with children is assumed to have no text of its own.
+    pub fn to_document(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        self.write(0, &mut xml);
+        xml
+    }
+
+    /// Writes the element as a fragment of XML, without the declaration of a document
+    pub fn to_fragment(&self) -> String {
+        let mut xml = String::new();
+        self.write(0, &mut xml);
+        xml.trim_end().to_string()
+    }
+
+    /// Tests if the element is nil, i.e. it stands for a null value
+    pub fn is_nil(&self) -> bool {
+        matches!(
+            self.attribute("nil").or_else(|| self.attribute("xsi:nil")),
+            Some("true") | Some("1")
+        )
+    }
+
+    fn write(&self, depth: usize, xml: &mut String) {
+        let indent = "  ".repeat(depth);
+        let _ = write!(xml, "{}<{}", indent, self.name);
+        self.attributes.iter().for_each(|(name, value)| {
+            let _ = write!(xml, " {}=\"{}\"", name, escape(value));
+        });
+        if !self.children.is_empty() {
+            xml.push_str(">\n");
+            self.children
+                .iter()
+                .for_each(|child| child.write(depth + 1, xml));
+            let _ = writeln!(xml, "{}</{}>", indent, self.name);
+        } else if !self.text.is_empty() {
+            let _ = writeln!(xml, ">{}</{}>", escape(&self.text), self.name);
+        } else {
+            xml.push_str(" />\n");
+        }
+    }
+}
+
+/// Parses an XML document and returns its root element
+pub fn parse(xml: &str) -> Result<Element, String> {
+    let mut reader = Reader { xml, pos: 0 };
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if reader.pos < xml.len() {
+        Err(reader.error("content after the root element"))
+    } else {
+        Ok(root)
+    }
+}
+
+struct Reader<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.xml[..self.pos].matches('\n').count() + 1;
+        format!("{} at line {}", message, line)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips past the terminator, returning the content before it
+    fn take_until(&mut self, terminator: &str) -> Result<&'a str, String> {
+        if let Some(i) = self.rest().find(terminator) {
+            let content = &self.rest()[..i];
+            self.pos += i + terminator.len();
+            Ok(content)
+        } else {
+            Err(self.error(&format!("missing \"{}\"", terminator)))
+        }
+    }
+
+    /// Skips whitespace, comments, processing instructions and declarations outside elements
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.take_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.take_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=')
+            .unwrap_or(rest.len());
+        if end == 0 {
+            Err(self.error("expected a name"))
+        } else {
+            self.pos += end;
+            Ok(rest[..end].to_string())
+        }
+    }
+
+    fn element(&mut self) -> Result<Element, String> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.pos += 1;
+        let qualified_name = self.name()?;
+        let mut element = Element::new(local_name(&qualified_name));
+
+        // Attributes
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected \"=\" after an attribute name"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(c) if c == '"' || c == '\'' => c,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let value = self.take_until(&quote.to_string())?;
+            let value = unescape(value).map_err(|e| self.error(&e))?;
+            // Namespace declarations are of no interest
+            if name != "xmlns" && !name.starts_with("xmlns:") {
+                element
+                    .attributes
+                    .push((local_name(&name).to_string(), value));
+            }
+        }
+
+        // Content
+        let end_tag = format!("</{}", qualified_name);
+        loop {
+            let rest = self.rest();
+            if rest.starts_with(&end_tag) {
+                self.pos += end_tag.len();
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error("expected \">\" to end the element"));
+                }
+                self.pos += 1;
+                return Ok(element);
+            } else if rest.starts_with("</") {
+                return Err(self.error(&format!("mismatched end tag for {}", qualified_name)));
+            } else if rest.starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.take_until("]]>")?;
+                element.text.push_str(text);
+            } else if rest.starts_with("<?") {
+                self.take_until("?>")?;
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("missing end tag for {}", qualified_name)));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = unescape(&rest[..end]).map_err(|e| self.error(&e))?;
+                element.text.push_str(&text);
+                self.pos += end;
+            }
+        }
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    if !s.contains('&') {
+        return Ok(s.to_string());
+    }
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        unescaped.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        let end = rest
+            .find(';')
+            .ok_or_else(|| "unterminated entity".to_string())?;
+        let entity = &rest[..end];
+        let c = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(decimal) = entity.strip_prefix('#') {
+                    decimal.parse::<u32>().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| format!("unknown entity &{};", entity))?
+            }
+        };
+        unescaped.push(c);
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    s.chars().for_each(|c| match c {
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '&' => escaped.push_str("&amp;"),
+        '"' => escaped.push_str("&quot;"),
+        _ => escaped.push(c),
+    });
+    escaped
+}
+
+/// OPC UA XML encoding interface. Anything that encodes to XML must implement this. Values are
+/// encoded to and decoded from an [`Element`], and the `to_xml` and `from_xml` functions convert
+/// them to and from a document.
+pub trait XmlEncoder: Sized {
+    /// The name of the type in the OPC UA schema, which names the element of a value of the type
+    /// in an array or at the root of a document
+    fn xml_type_name() -> String;
+
+    /// Encodes the value as an element with the name
+    fn encode_xml(&self, name: &str) -> Element;
+
+    /// Decodes a value from an element. The decoding options restrict the length of strings,
+    /// arrays and the depth of recursive values in the same way as for binary.
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self>;
+
+    /// Encodes the value to an XML document whose root element is named after the type
+    fn to_xml(&self) -> String {
+        self.encode_xml(&Self::xml_type_name())
+            .with_attribute("xmlns", XML_TYPES_NAMESPACE)
+            .with_attribute("xmlns:xsi", XML_SCHEMA_INSTANCE_NAMESPACE)
+            .to_document()
+    }
+
+    /// Decodes a value from an XML document
+    fn from_xml(xml: &str, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let element = parse(xml).map_err(|err| {
+            error!("Cannot parse XML - {}", err);
+            StatusCode::BadDecodingError
+        })?;
+        Self::decode_xml(&element, decoding_options)
+    }
+}
+
+/// Makes an element that stands for a null value
+pub fn nil_element(name: &str) -> Element {
+    Element::new(name).with_attribute("xsi:nil", true)
+}
+
+/// Writes a field of a structure as a child of the element, unless the value is null
+pub fn write_xml_field<T>(element: &mut Element, name: &str, value: &T)
+where
+    T: XmlEncoder,
+{
+    let child = value.encode_xml(name);
+    if !child.is_nil() {
+        element.children.push(child);
+    }
+}
+
+/// Reads a field of a structure from a child of the element. A missing field is decoded from a
+/// nil element, i.e. as the default value of its type.
+pub fn read_xml_field<T>(
+    element: &Element,
+    name: &str,
+    decoding_options: &DecodingOptions,
+) -> EncodingResult<T>
+where
+    T: XmlEncoder,
+{
+    match element.child(name) {
+        Some(child) => T::decode_xml(child, decoding_options),
+        None => T::decode_xml(&nil_element(name), decoding_options),
+    }
+}
+
+/// Writes an enumeration as `<name>_<value>`
+pub fn write_xml_enum(element_name: &str, name: &str, value: i64) -> Element {
+    Element::new(element_name).with_text(format!("{}_{}", name, value))
+}
+
+/// Reads the value of an enumeration written as `<name>_<value>`, or as just its value
+pub fn read_xml_enum(element: &Element) -> EncodingResult<i64> {
+    if element.is_nil() {
+        return Ok(0);
+    }
+    let text = element.text.trim();
+    text.rsplit('_')
+        .next()
+        .and_then(|v| v.parse::<i64>().ok())
+        .ok_or_else(|| {
+            error!("Invalid enumeration value \"{}\"", text);
+            StatusCode::BadDecodingError
+        })
+}
+
+/// Reads the trimmed text of an element as a value of a type that is parsed from a string. A nil
+/// or empty element is the default value of the type.
+pub(crate) fn xml_text<T>(element: &Element) -> EncodingResult<T>
+where
+    T: std::str::FromStr + Default,
+{
+    let text = element.text.trim();
+    if element.is_nil() || text.is_empty() {
+        Ok(T::default())
+    } else {
+        text.parse::<T>().map_err(|_| {
+            error!("Invalid {} value \"{}\"", element.name, text);
+            StatusCode::BadDecodingError
+        })
+    }
+}
+
+/// Reads the text of an element, checking it against the maximum string length
+pub(crate) fn xml_str(element: &Element, max_length: usize) -> EncodingResult<&str> {
+    if element.text.len() > max_length {
+        error!(
+            "String length {} exceeds decoding limit {}",
+            element.text.len(),
+            max_length
+        );
+        Err(StatusCode::BadDecodingError)
+    } else {
+        Ok(&element.text)
+    }
+}
+
+impl XmlEncoder for bool {
+    fn xml_type_name() -> String {
+        "Boolean".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        Element::new(name).with_text(self)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        match element.text.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" | "" => Ok(false),
+            v => {
+                error!("Invalid Boolean value \"{}\"", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Integers are encoded as the text of the element
+macro_rules! xml_integer {
+    ($t: ty, $name: expr) => {
+        impl XmlEncoder for $t {
+            fn xml_type_name() -> String {
+                $name.into()
+            }
+
+            fn encode_xml(&self, name: &str) -> Element {
+                Element::new(name).with_text(self)
+            }
+
+            fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+                xml_text(element)
+            }
+        }
+    };
+}
+
+xml_integer!(i8, "SByte");
+xml_integer!(u8, "Byte");
+xml_integer!(i16, "Int16");
+xml_integer!(u16, "UInt16");
+xml_integer!(i32, "Int32");
+xml_integer!(u32, "UInt32");
+xml_integer!(i64, "Int64");
+xml_integer!(u64, "UInt64");
+
+const VALUE_INFINITY: &str = "INF";
+const VALUE_NEG_INFINITY: &str = "-INF";
+const VALUE_NAN: &str = "NaN";
+
+/// Floats and doubles are encoded as the text of the element, with the special values written as
+/// XML schema writes them
+macro_rules! xml_float {
+    ($t: ty, $name: expr) => {
+        impl XmlEncoder for $t {
+            fn xml_type_name() -> String {
+                $name.into()
+            }
+
+            fn encode_xml(&self, name: &str) -> Element {
+                let element = Element::new(name);
+                if self.is_nan() {
+                    element.with_text(VALUE_NAN)
+                } else if *self == <$t>::INFINITY {
+                    element.with_text(VALUE_INFINITY)
+                } else if *self == <$t>::NEG_INFINITY {
+                    element.with_text(VALUE_NEG_INFINITY)
+                } else {
+                    element.with_text(self)
+                }
+            }
+
+            fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+                match element.text.trim() {
+                    VALUE_NAN => Ok(<$t>::NAN),
+                    VALUE_INFINITY => Ok(<$t>::INFINITY),
+                    VALUE_NEG_INFINITY => Ok(<$t>::NEG_INFINITY),
+                    _ => xml_text(element),
+                }
+            }
+        }
+    };
+}
+
+xml_float!(f32, "Float");
+xml_float!(f64, "Double");
+
+/// Arrays are encoded as an element with a child element for each value
+impl<T> XmlEncoder for Vec<T>
+where
+    T: XmlEncoder,
+{
+    fn xml_type_name() -> String {
+        format!("ListOf{}", T::xml_type_name())
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let type_name = T::xml_type_name();
+        Element {
+            name: name.into(),
+            children: self.iter().map(|v| v.encode_xml(&type_name)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if element.children.len() > decoding_options.max_array_length {
+            error!(
+                "Array length {} exceeds decoding limit {}",
+                element.children.len(),
+                decoding_options.max_array_length
+            );
+            Err(StatusCode::BadDecodingError)
+        } else {
+            element
+                .children
+                .iter()
+                .map(|v| T::decode_xml(v, decoding_options))
+                .collect()
+        }
+    }
+}
+
+/// Optional values, including null arrays, are encoded as nil when they are `None`
+impl<T> XmlEncoder for Option<T>
+where
+    T: XmlEncoder,
+{
+    fn xml_type_name() -> String {
+        T::xml_type_name()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.as_ref()
+            .map(|v| v.encode_xml(name))
+            .unwrap_or_else(|| nil_element(name))
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        if element.is_nil() {
+            Ok(None)
+        } else {
+            T::decode_xml(element, decoding_options).map(Some)
+        }
+    }
+}
+
+impl<T> XmlEncoder for Box<T>
+where
+    T: XmlEncoder,
+{
+    fn xml_type_name() -> String {
+        T::xml_type_name()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.as_ref().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        T::decode_xml(element, decoding_options).map(Box::new)
+    }
+}
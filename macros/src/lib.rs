@@ -0,0 +1,266 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Derive macros for the `opcua` crate.
+//!
+//! `#[derive(UaStructure)]` implements the binary encoding of a user defined structure so that
+//! it can be sent as the body of an `ExtensionObject`, in the same way as the structures that are
+//! generated from the OPC UA schema.
+//!
+//! ```ignore
+//! use opcua::types::*;
+//!
+//! #[derive(Debug, Clone, PartialEq, UaStructure)]
+//! #[ua(data_type_id = "ns=2;i=3001", encoding_id = "ns=2;i=3002")]
+//! pub struct Point {
+//!     pub x: f64,
+//!     pub y: f64,
+//!     pub label: UAString,
+//!     pub tags: Option<Vec<UAString>>,
+//! }
+//! ```
+//!
+//! Fields are encoded in the order they are declared. Every field type must implement
+//! `BinaryEncoder`, and `Vec<T>` or `Option<Vec<T>>` fields are encoded as arrays of `T`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Derives `BinaryEncoder` and `UaStructure` for a struct with named fields, as well as
+/// conversions to and from an `ExtensionObject`.
+///
+/// The node ids of the data type and of its default binary encoding are set with
+/// `#[ua(data_type_id = "...", encoding_id = "...")]` in the `ns=<namespace>;i=<number>` or
+/// `ns=<namespace>;s=<string>` format.
+#[proc_macro_derive(UaStructure, attributes(ua))]
+pub fn derive_ua_structure(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn derive(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "UaStructure cannot be derived for generic structs",
+        ));
+    }
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "UaStructure can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "UaStructure can only be derived for structs",
+            ))
+        }
+    };
+
+    let (data_type_id, encoding_id) = node_ids(&input)?;
+
+    let mut byte_len = Vec::new();
+    let mut encode = Vec::new();
+    let mut decode = Vec::new();
+    let mut field_names = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        match array_kind(ty) {
+            Some(ArrayKind::Optional) => {
+                byte_len.push(quote! { size += byte_len_array(&self.#ident); });
+                encode.push(quote! { size += write_array(stream, &self.#ident)?; });
+                decode.push(quote! { let #ident: #ty = read_array(stream, decoding_options)?; });
+            }
+            Some(ArrayKind::Required) => {
+                byte_len.push(quote! {
+                    size += 4 + self.#ident.iter().map(|v| v.byte_len()).sum::<usize>();
+                });
+                encode.push(quote! {
+                    size += write_i32(stream, self.#ident.len() as i32)?;
+                    for v in &self.#ident {
+                        size += v.encode(stream)?;
+                    }
+                });
+                decode.push(quote! {
+                    let #ident: #ty = read_array(stream, decoding_options)?.unwrap_or_default();
+                });
+            }
+            None => {
+                byte_len.push(quote! { size += self.#ident.byte_len(); });
+                encode.push(quote! { size += self.#ident.encode(stream)?; });
+                decode.push(quote! {
+                    let #ident = <#ty as BinaryEncoder<#ty>>::decode(stream, decoding_options)?;
+                });
+            }
+        }
+        field_names.push(ident);
+    }
+
+    Ok(quote! {
+        const _: () = {
+            use std::io::{Read, Write};
+            use ::opcua::types::{
+                byte_len_array, read_array, write_array, write_i32, BinaryEncoder,
+                DecodingOptions, EncodingResult, ExtensionObject, NodeId, StatusCode,
+                UaStructure,
+            };
+
+            impl BinaryEncoder<#name> for #name {
+                fn byte_len(&self) -> usize {
+                    let mut size = 0;
+                    #(#byte_len)*
+                    size
+                }
+
+                #[allow(unused_variables)]
+                fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+                    let mut size = 0;
+                    #(#encode)*
+                    Ok(size)
+                }
+
+                #[allow(unused_variables)]
+                fn decode<S: Read>(
+                    stream: &mut S,
+                    decoding_options: &DecodingOptions,
+                ) -> EncodingResult<Self> {
+                    #(#decode)*
+                    Ok(#name {
+                        #(#field_names),*
+                    })
+                }
+            }
+
+            impl UaStructure for #name {
+                fn data_type_id() -> NodeId {
+                    #data_type_id
+                }
+
+                fn encoding_id() -> NodeId {
+                    #encoding_id
+                }
+            }
+
+            impl From<&#name> for ExtensionObject {
+                fn from(value: &#name) -> Self {
+                    ExtensionObject::from_structure(value)
+                }
+            }
+
+            impl TryFrom<&ExtensionObject> for #name {
+                type Error = StatusCode;
+
+                fn try_from(value: &ExtensionObject) -> Result<Self, Self::Error> {
+                    value.decode_structure(&DecodingOptions::default())
+                }
+            }
+        };
+    })
+}
+
+enum ArrayKind {
+    /// `Option<Vec<T>>`, where `None` is a null array
+    Optional,
+    /// `Vec<T>`, where a null array is decoded as an empty one
+    Required,
+}
+
+/// Finds if the type is an array, by the last segment of its path
+fn array_kind(ty: &Type) -> Option<ArrayKind> {
+    match generic_argument(ty, "Option") {
+        Some(inner) if generic_argument(inner, "Vec").is_some() => Some(ArrayKind::Optional),
+        Some(_) => None,
+        None => generic_argument(ty, "Vec").map(|_| ArrayKind::Required),
+    }
+}
+
+/// Returns `T` if the type is `<wrapper><T>`
+fn generic_argument<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(ref path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+            GenericArgument::Type(ref ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Reads the data type id and encoding id from the `#[ua(...)]` attribute
+fn node_ids(input: &DeriveInput) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let mut data_type_id = None;
+    let mut encoding_id = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("ua")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("data_type_id") {
+                data_type_id = Some(node_id(&meta.value()?.parse::<LitStr>()?)?);
+                Ok(())
+            } else if meta.path.is_ident("encoding_id") {
+                encoding_id = Some(node_id(&meta.value()?.parse::<LitStr>()?)?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `data_type_id` or `encoding_id`"))
+            }
+        })?;
+    }
+    match (data_type_id, encoding_id) {
+        (Some(data_type_id), Some(encoding_id)) => Ok((data_type_id, encoding_id)),
+        _ => Err(Error::new(
+            input.ident.span(),
+            "UaStructure requires #[ua(data_type_id = \"...\", encoding_id = \"...\")]",
+        )),
+    }
+}
+
+/// Parses a node id in the `ns=<namespace>;i=<number>` or `ns=<namespace>;s=<string>` format
+/// into an expression that creates it. The namespace defaults to 0 if it is omitted.
+fn node_id(lit: &LitStr) -> syn::Result<TokenStream2> {
+    let value = lit.value();
+    let (namespace, identifier) = match value.strip_prefix("ns=") {
+        Some(rest) => {
+            let (namespace, identifier) = rest
+                .split_once(';')
+                .ok_or_else(|| Error::new(lit.span(), "invalid node id"))?;
+            let namespace = namespace
+                .parse::<u16>()
+                .map_err(|_| Error::new(lit.span(), "invalid namespace index"))?;
+            (namespace, identifier)
+        }
+        None => (0, value.as_str()),
+    };
+    if let Some(numeric) = identifier.strip_prefix("i=") {
+        let numeric = numeric
+            .parse::<u32>()
+            .map_err(|_| Error::new(lit.span(), "invalid numeric identifier"))?;
+        Ok(quote! { NodeId::new(#namespace, #numeric) })
+    } else if let Some(string) = identifier.strip_prefix("s=") {
+        Ok(quote! { NodeId::new(#namespace, #string) })
+    } else {
+        Err(Error::new(
+            lit.span(),
+            "node id must have a numeric (i=) or string (s=) identifier",
+        ))
+    }
+}
@@ -113,8 +113,15 @@ fn main() {
         // More powerful logging than a console logger
         log4rs::init_file("log4rs.yaml", Default::default()).unwrap();
 
-        // Create an OPC UA server with sample configuration and default node set
-        let mut server = Server::new(ServerConfig::load(&args.config_path).unwrap());
+        // Create an OPC UA server with sample configuration and default node set. Environment
+        // variables such as OPCUA_SERVER_PORT override values of the configuration file.
+        let config: ServerConfig = ServerConfig::load(&args.config_path).unwrap();
+        let config = config.with_env_overrides().unwrap();
+        let mut server = Server::new(config);
+
+        // Apply changes to the user tokens and limits of the configuration file, and to the
+        // trust list, without a restart
+        server.watch_config(&args.config_path, 5000);
 
         let ns = {
             let address_space = server.address_space();
@@ -0,0 +1,133 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! This is a sample gateway that makes the registers of a Modbus TCP device available as OPC UA
+//! variables. The registers are read by a polling provider, which the server polls on an interval
+//! to write their values into the address space. A register that cannot be read keeps its last
+//! value with a bad status code, e.g. `BadNotConnected` while the device is unreachable.
+//!
+//! Run it with `--simulate` to start a simulated device alongside the gateway.
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+use opcua::server::prelude::*;
+
+use crate::modbus::{ModbusTcp, Register, Table};
+
+mod modbus;
+mod simulator;
+
+struct Args {
+    help: bool,
+    config: String,
+    device: SocketAddr,
+    unit_id: u8,
+    interval: u64,
+    simulate: bool,
+}
+
+impl Args {
+    pub fn parse_args() -> Result<Args, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+        Ok(Args {
+            help: args.contains(["-h", "--help"]),
+            config: args
+                .opt_value_from_str("--config")?
+                .unwrap_or_else(|| String::from(DEFAULT_CONFIG_FILE)),
+            device: args
+                .opt_value_from_str("--device")?
+                .unwrap_or_else(|| DEFAULT_DEVICE.parse().unwrap()),
+            unit_id: args.opt_value_from_str("--unit-id")?.unwrap_or(1),
+            interval: args
+                .opt_value_from_str("--interval")?
+                .unwrap_or(DEFAULT_INTERVAL),
+            simulate: args.contains("--simulate"),
+        })
+    }
+
+    pub fn usage() {
+        println!(
+            r#"Modbus gateway
+Usage:
+  -h, --help        Show help
+  --config file     Sets the configuration file of the server (default: {})
+  --device address  Address and port of the Modbus TCP device (default: {})
+  --unit-id id      Unit id of the Modbus device (default: 1)
+  --interval ms     Interval in milliseconds between reads of the registers (default: {})
+  --simulate        Starts a simulated Modbus device at the device address"#,
+            DEFAULT_CONFIG_FILE, DEFAULT_DEVICE, DEFAULT_INTERVAL
+        );
+    }
+}
+
+const DEFAULT_CONFIG_FILE: &str = "../server.conf";
+const DEFAULT_DEVICE: &str = "127.0.0.1:5020";
+const DEFAULT_INTERVAL: u64 = 1000;
+
+/// The registers of the device and the names of the variables they map to. The last register
+/// does not exist on the simulated device, to show the status code of a failed read.
+const REGISTERS: [(&str, Table, u16); 5] = [
+    ("Counter", Table::HoldingRegister, 0),
+    ("Level", Table::HoldingRegister, 1),
+    ("Temperature", Table::InputRegister, 0),
+    ("Pressure", Table::InputRegister, 1),
+    ("Unmapped", Table::HoldingRegister, 100),
+];
+
+fn main() {
+    let args = match Args::parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            Args::usage();
+            return;
+        }
+    };
+    if args.help {
+        Args::usage();
+        return;
+    }
+
+    // This enables logging via env_logger & log crate macros. If you don't need logging or want
+    // to implement your own, omit this line.
+    opcua::console_logging::init();
+
+    if args.simulate {
+        simulator::spawn(args.device).expect("Cannot start the simulated Modbus device");
+    }
+
+    let mut server = Server::new(ServerConfig::load(&PathBuf::from(&args.config)).unwrap());
+
+    // Each register gets a variable, and is registered with the provider as the tag of that
+    // variable
+    let provider = ModbusTcp::new(args.device, args.unit_id, Duration::from_millis(500));
+    let mut group = PollingGroup::new(provider);
+    {
+        let address_space = server.address_space();
+        let mut address_space = address_space.write();
+        let ns = address_space
+            .register_namespace("urn:modbus-gateway")
+            .unwrap();
+        let folder_id = address_space
+            .add_folder("Modbus", "Modbus", &NodeId::objects_folder_id())
+            .unwrap();
+        REGISTERS.iter().for_each(|(name, table, address)| {
+            let node_id = NodeId::new(ns, *name);
+            VariableBuilder::new(&node_id, *name, *name)
+                .data_type(DataTypeId::UInt16)
+                .value(Variant::UInt16(0))
+                .organized_by(&folder_id)
+                .insert(&mut address_space);
+            group.add_tag(
+                node_id,
+                Register {
+                    table: *table,
+                    address: *address,
+                },
+            );
+        });
+    }
+    server.add_polling_provider(args.interval, group);
+
+    // Run the server. This does not ordinarily exit so you must Ctrl+C to terminate
+    server.run();
+}
@@ -0,0 +1,255 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! A minimal Modbus TCP client that reads holding and input registers, and the polling provider
+//! that makes its registers the values of variables.
+
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+use opcua::server::prelude::*;
+
+/// The most registers a single read request may ask for
+const MAX_REGISTERS_PER_READ: u16 = 125;
+
+/// The table of a register, which decides the function code used to read it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Table {
+    HoldingRegister,
+    InputRegister,
+}
+
+impl Table {
+    pub fn function_code(&self) -> u8 {
+        match self {
+            Table::HoldingRegister => 0x03,
+            Table::InputRegister => 0x04,
+        }
+    }
+}
+
+/// A register of the device, the tag of a variable of the gateway
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Register {
+    pub table: Table,
+    pub address: u16,
+}
+
+/// Reads registers of a unit over Modbus TCP. The connection is made on the first read and made
+/// again on the read after it fails.
+pub struct ModbusTcp {
+    address: SocketAddr,
+    unit_id: u8,
+    timeout: Duration,
+    stream: Option<TcpStream>,
+    transaction_id: u16,
+}
+
+impl ModbusTcp {
+    pub fn new(address: SocketAddr, unit_id: u8, timeout: Duration) -> Self {
+        Self {
+            address,
+            unit_id,
+            timeout,
+            stream: None,
+            transaction_id: 0,
+        }
+    }
+
+    /// Reads `count` registers of the table from the start address.
+    pub fn read_registers(
+        &mut self,
+        table: Table,
+        start: u16,
+        count: u16,
+    ) -> Result<Vec<u16>, StatusCode> {
+        if self.stream.is_none() {
+            let stream =
+                TcpStream::connect_timeout(&self.address, self.timeout).map_err(|err| {
+                    log::warn!("Cannot connect to Modbus device {}: {}", self.address, err);
+                    StatusCode::BadNotConnected
+                })?;
+            let _ = stream.set_read_timeout(Some(self.timeout));
+            let _ = stream.set_write_timeout(Some(self.timeout));
+            self.stream = Some(stream);
+        }
+
+        self.transaction_id = self.transaction_id.wrapping_add(1);
+        let result = self.transact(table, start, count);
+        if let Err(err) = &result {
+            log::warn!("Modbus read from {} failed: {}", self.address, err);
+            // The connection may be out of step with the device so it is made again
+            self.stream = None;
+        }
+        match result {
+            Ok(Ok(registers)) => Ok(registers),
+            Ok(Err(exception_code)) => Err(exception_status(exception_code)),
+            Err(err) if is_timeout(&err) => Err(StatusCode::BadTimeout),
+            Err(_) => Err(StatusCode::BadCommunicationError),
+        }
+    }
+
+    /// Sends a read request and receives its response, which is either the registers or the
+    /// exception code of the device.
+    fn transact(
+        &mut self,
+        table: Table,
+        start: u16,
+        count: u16,
+    ) -> io::Result<Result<Vec<u16>, u8>> {
+        let stream = self.stream.as_mut().unwrap();
+        let transaction_id = self.transaction_id;
+        let function_code = table.function_code();
+
+        let mut request = Vec::with_capacity(12);
+        request.extend_from_slice(&transaction_id.to_be_bytes());
+        request.extend_from_slice(&0u16.to_be_bytes());
+        request.extend_from_slice(&6u16.to_be_bytes());
+        request.push(self.unit_id);
+        request.push(function_code);
+        request.extend_from_slice(&start.to_be_bytes());
+        request.extend_from_slice(&count.to_be_bytes());
+        stream.write_all(&request)?;
+
+        let (header, pdu) = read_frame(stream)?;
+        if header.transaction_id != transaction_id || pdu.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected response",
+            ));
+        }
+        if pdu[0] == function_code | 0x80 {
+            return Ok(Err(pdu.get(1).copied().unwrap_or_default()));
+        }
+        if pdu[0] != function_code || pdu.len() < 2 || pdu[1] as usize != count as usize * 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "malformed response",
+            ));
+        }
+        Ok(Ok(pdu[2..]
+            .chunks_exact(2)
+            .take(count as usize)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .collect()))
+    }
+
+    /// Reads every register of the tags of one table. Registers that lie close together are
+    /// read with a single request, falling back to a request per register if that fails because
+    /// some register in between does not exist.
+    fn read_table(
+        &mut self,
+        table: Table,
+        tags: &[Register],
+        results: &mut [Result<Variant, StatusCode>],
+    ) {
+        let indexes = tags
+            .iter()
+            .enumerate()
+            .filter(|(_, tag)| tag.table == table)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let (Some(start), Some(end)) = (
+            indexes.iter().map(|i| tags[*i].address).min(),
+            indexes.iter().map(|i| tags[*i].address).max(),
+        ) else {
+            return;
+        };
+
+        if end - start < MAX_REGISTERS_PER_READ {
+            match self.read_registers(table, start, end - start + 1) {
+                Ok(registers) => {
+                    indexes.iter().for_each(|i| {
+                        let offset = (tags[*i].address - start) as usize;
+                        results[*i] = Ok(Variant::UInt16(registers[offset]));
+                    });
+                    return;
+                }
+                Err(StatusCode::BadOutOfRange) => { /* Try each register on its own */ }
+                Err(status_code) => {
+                    indexes.iter().for_each(|i| results[*i] = Err(status_code));
+                    return;
+                }
+            }
+        }
+        indexes.iter().for_each(|i| {
+            results[*i] = self
+                .read_registers(table, tags[*i].address, 1)
+                .map(|registers| Variant::UInt16(registers[0]));
+        });
+    }
+}
+
+impl PollingProvider for ModbusTcp {
+    type Tag = Register;
+
+    fn poll(&mut self, tags: &[Register]) -> Vec<Result<Variant, StatusCode>> {
+        let mut results = vec![Err(StatusCode::BadWaitingForInitialData); tags.len()];
+        self.read_table(Table::HoldingRegister, tags, &mut results);
+        self.read_table(Table::InputRegister, tags, &mut results);
+        results
+    }
+}
+
+/// The MBAP header that starts every Modbus TCP frame
+pub struct Header {
+    pub transaction_id: u16,
+    pub unit_id: u8,
+}
+
+/// Reads a frame, returning its header and its PDU, the function code followed by its data.
+pub fn read_frame(stream: &mut TcpStream) -> io::Result<(Header, Vec<u8>)> {
+    let mut header = [0u8; 7];
+    stream.read_exact(&mut header)?;
+    let length = u16::from_be_bytes([header[4], header[5]]) as usize;
+    if length < 2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame too short",
+        ));
+    }
+    let mut pdu = vec![0u8; length - 1];
+    stream.read_exact(&mut pdu)?;
+    Ok((
+        Header {
+            transaction_id: u16::from_be_bytes([header[0], header[1]]),
+            unit_id: header[6],
+        },
+        pdu,
+    ))
+}
+
+/// Writes a frame with the header and the PDU.
+pub fn write_frame(stream: &mut TcpStream, header: &Header, pdu: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(7 + pdu.len());
+    frame.extend_from_slice(&header.transaction_id.to_be_bytes());
+    frame.extend_from_slice(&0u16.to_be_bytes());
+    frame.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+    frame.push(header.unit_id);
+    frame.extend_from_slice(pdu);
+    stream.write_all(&frame)
+}
+
+/// The quality of a register that the device answered with an exception code.
+fn exception_status(exception_code: u8) -> StatusCode {
+    match exception_code {
+        // Illegal function
+        0x01 => StatusCode::BadNotSupported,
+        // Illegal data address
+        0x02 => StatusCode::BadOutOfRange,
+        // Gateway path unavailable, gateway target device failed to respond
+        0x0A | 0x0B => StatusCode::BadNotConnected,
+        _ => StatusCode::BadDeviceFailure,
+    }
+}
+
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
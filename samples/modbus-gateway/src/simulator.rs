@@ -0,0 +1,102 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! A simulated Modbus TCP device so the gateway can be tried without one. It has a few holding
+//! and input registers whose values change every second, and answers reads of any other
+//! register with an exception.
+
+use std::{
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use opcua::sync::Mutex;
+
+use crate::modbus::{read_frame, write_frame, Table};
+
+const HOLDING_REGISTERS: usize = 8;
+const INPUT_REGISTERS: usize = 8;
+
+#[derive(Default)]
+struct Registers {
+    holding: [u16; HOLDING_REGISTERS],
+    input: [u16; INPUT_REGISTERS],
+}
+
+impl Registers {
+    fn tick(&mut self) {
+        // A counter, a value rising in steps and a temperature-like input that swings around 21.0
+        self.holding[0] = self.holding[0].wrapping_add(1);
+        self.holding[1] = (self.holding[1] + 10) % 1000;
+        let angle = self.holding[0] as f64 / 10.0;
+        self.input[0] = (210.0 + angle.sin() * 25.0) as u16;
+        self.input[1] = 1013 + (self.holding[0] % 5);
+    }
+
+    fn read(&self, table: Table, start: u16, count: u16) -> Option<Vec<u16>> {
+        let registers: &[u16] = match table {
+            Table::HoldingRegister => &self.holding,
+            Table::InputRegister => &self.input,
+        };
+        let start = start as usize;
+        registers
+            .get(start..start + count as usize)
+            .map(|r| r.to_vec())
+    }
+}
+
+/// Listens for Modbus TCP connections on the address, serving each from its own thread.
+pub fn spawn(address: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    let registers = Arc::new(Mutex::new(Registers::default()));
+    {
+        let registers = registers.clone();
+        thread::spawn(move || loop {
+            registers.lock().tick();
+            thread::sleep(Duration::from_secs(1));
+        });
+    }
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let registers = registers.clone();
+            thread::spawn(move || serve(stream, registers));
+        }
+    });
+    Ok(())
+}
+
+fn serve(mut stream: TcpStream, registers: Arc<Mutex<Registers>>) {
+    while let Ok((header, pdu)) = read_frame(&mut stream) {
+        let response = respond(&pdu, &registers.lock());
+        if write_frame(&mut stream, &header, &response).is_err() {
+            break;
+        }
+    }
+}
+
+fn respond(pdu: &[u8], registers: &Registers) -> Vec<u8> {
+    let function_code = pdu[0];
+    let table = match function_code {
+        0x03 => Table::HoldingRegister,
+        0x04 => Table::InputRegister,
+        _ => return vec![function_code | 0x80, 0x01],
+    };
+    if pdu.len() != 5 {
+        return vec![function_code | 0x80, 0x03];
+    }
+    let start = u16::from_be_bytes([pdu[1], pdu[2]]);
+    let count = u16::from_be_bytes([pdu[3], pdu[4]]);
+    match registers.read(table, start, count) {
+        Some(values) => {
+            let mut response = vec![function_code, (values.len() * 2) as u8];
+            values
+                .iter()
+                .for_each(|v| response.extend_from_slice(&v.to_be_bytes()));
+            response
+        }
+        None => vec![function_code | 0x80, 0x02],
+    }
+}
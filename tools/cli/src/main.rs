@@ -0,0 +1,651 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! A command line client for OPC UA servers, to browse, read, write and subscribe to nodes, list
+//! the endpoints of a server and create the client's certificate.
+use std::{
+    ffi::OsString,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process::ExitCode,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use opcua::{
+    client::{ClientBuilder, IdentityToken, Session},
+    crypto::{CertificateStore, SecurityPolicy, X509Data},
+    types::{
+        AttributeId, BrowseDescription, BrowseDescriptionResultMask, BrowseDirection, JsonEncoder,
+        JsonEncoding, MessageSecurityMode, NodeClassMask, NodeId, ReadValueId,
+        ReferenceDescription, ReferenceTypeId, StatusCode, TimestampsToReturn, UAString,
+        UserTokenPolicy, Variant, VariantTypeId, WriteValue,
+    },
+};
+use tokio::task::JoinHandle;
+
+mod output;
+
+use output::{print_json, print_table, value_json, value_row, Format, VALUE_HEADERS};
+
+struct Args {
+    help: bool,
+    verbose: bool,
+    format: Format,
+    pki_path: PathBuf,
+    security_policy: String,
+    security_mode: String,
+    user: Option<String>,
+    trust_server_certs: bool,
+    interval: u64,
+    overwrite: bool,
+    command: Option<String>,
+    free: Vec<String>,
+}
+
+impl Args {
+    pub fn parse_args(mut args: pico_args::Arguments) -> Result<Args, Box<dyn std::error::Error>> {
+        let mut parsed = Args {
+            help: args.contains(["-h", "--help"]),
+            verbose: args.contains(["-v", "--verbose"]),
+            format: if args.contains("--json") {
+                Format::Json
+            } else {
+                Format::Table
+            },
+            pki_path: args
+                .opt_value_from_str("--pki-path")?
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_PKI_PATH)),
+            security_policy: args
+                .opt_value_from_str("--security-policy")?
+                .unwrap_or_else(|| String::from(DEFAULT_SECURITY_POLICY)),
+            security_mode: args
+                .opt_value_from_str("--security-mode")?
+                .unwrap_or_else(|| String::from(DEFAULT_SECURITY_MODE)),
+            user: args.opt_value_from_str("--user")?,
+            trust_server_certs: args.contains("--trust-server-certs"),
+            interval: args
+                .opt_value_from_str("--interval")?
+                .unwrap_or(DEFAULT_INTERVAL),
+            overwrite: args.contains(["-o", "--overwrite"]),
+            command: None,
+            free: Vec::new(),
+        };
+        parsed.free = args
+            .finish()
+            .into_iter()
+            .map(OsString::into_string)
+            .collect::<Result<_, _>>()
+            .map_err(|_| "arguments must be valid unicode")?;
+        // The command is the first argument that is not an option
+        if !parsed.free.is_empty() {
+            parsed.command = Some(parsed.free.remove(0));
+        }
+        Ok(parsed)
+    }
+
+    pub fn usage() {
+        println!(
+            r#"OPC UA Command Line Client
+
+Usage:
+  opcua-cli [options] endpoints <url>              List the endpoints of the server.
+  opcua-cli [options] browse <url> [node]          Browse the hierarchical references of a node (default: i=85).
+  opcua-cli [options] read <url> <node>...         Read the values of nodes.
+  opcua-cli [options] write <url> <node> <value>   Write a value to a node, converted to the type of its current value.
+  opcua-cli [options] subscribe <url> <node>...    Print the value of nodes as they change, until Ctrl-C.
+  opcua-cli [options] gencert                      Create the client's certificate and private key.
+
+Nodes are in the string format of a node id, e.g. ns=2;s=v1
+
+Options:
+  -h, --help                 Show help.
+  -v, --verbose              Log what the client does.
+  --json                     Print JSON instead of a table.
+  --pki-path path            Path of the client's certificates. (default: {})
+  --security-policy policy   Security policy of the endpoint, e.g. Basic256Sha256. (default: {})
+  --security-mode mode       Security mode of the endpoint - None, Sign or SignAndEncrypt. (default: {})
+  --user name                Activate the session as the user instead of anonymously. The password
+                             is taken from {} or else prompted for.
+  --trust-server-certs       Trust the server's certificate without it being in the PKI path.
+  --interval ms              Publishing interval of subscribe. (default: {})
+  -o, --overwrite            Overwrite an existing certificate in gencert."#,
+            DEFAULT_PKI_PATH,
+            DEFAULT_SECURITY_POLICY,
+            DEFAULT_SECURITY_MODE,
+            PASSWORD_ENV_VAR,
+            DEFAULT_INTERVAL
+        );
+    }
+}
+
+const DEFAULT_PKI_PATH: &str = "./pki";
+const DEFAULT_SECURITY_POLICY: &str = "None";
+const DEFAULT_SECURITY_MODE: &str = "None";
+const DEFAULT_INTERVAL: u64 = 1000;
+const DEFAULT_BROWSE_NODE: &str = "i=85";
+const APPLICATION_URI: &str = "urn:OPCUACli";
+/// The password of `--user` is read from this variable rather than an argument, so it does not
+/// show up in the process list.
+const PASSWORD_ENV_VAR: &str = "OPCUA_CLI_PASSWORD";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let Ok(args) = Args::parse_args(pico_args::Arguments::from_env()) else {
+        Args::usage();
+        return ExitCode::FAILURE;
+    };
+    let Some(command) = args.command.clone().filter(|_| !args.help) else {
+        Args::usage();
+        return if args.help {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    };
+    if args.verbose {
+        opcua::console_logging::init();
+    }
+
+    let result = match (command.as_str(), args.free.as_slice()) {
+        ("endpoints", [url]) => endpoints(&args, url).await,
+        ("browse", [url]) => browse(&args, url, DEFAULT_BROWSE_NODE).await,
+        ("browse", [url, node_id]) => browse(&args, url, node_id).await,
+        ("read", [url, node_ids @ ..]) if !node_ids.is_empty() => read(&args, url, node_ids).await,
+        ("write", [url, node_id, value]) => write(&args, url, node_id, value).await,
+        ("subscribe", [url, node_ids @ ..]) if !node_ids.is_empty() => {
+            subscribe(&args, url, node_ids).await
+        }
+        ("gencert", []) => gencert(&args),
+        _ => {
+            Args::usage();
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(status_code) => {
+            eprintln!("Error: {}", status_code);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn client(args: &Args) -> Result<opcua::client::Client, StatusCode> {
+    ClientBuilder::new()
+        .application_name("OPC UA Command Line Client")
+        .application_uri(APPLICATION_URI)
+        .product_uri(APPLICATION_URI)
+        .pki_dir(args.pki_path.clone())
+        .create_sample_keypair(true)
+        .trust_server_certs(args.trust_server_certs)
+        .session_retry_limit(0)
+        .client()
+        .ok_or(StatusCode::BadConfigurationError)
+}
+
+/// Connects to the endpoint at the url with the security and identity of the arguments. The
+/// returned handle is of the session's event loop, which ends once the session is disconnected.
+async fn connect(
+    args: &Args,
+    url: &str,
+) -> Result<(Arc<Session>, JoinHandle<StatusCode>), StatusCode> {
+    let security_policy = SecurityPolicy::from_str(&args.security_policy)
+        .ok()
+        .filter(|p| *p != SecurityPolicy::Unknown)
+        .ok_or(StatusCode::BadSecurityPolicyRejected)?;
+    let security_mode = MessageSecurityMode::from(args.security_mode.as_str());
+    if security_mode == MessageSecurityMode::Invalid {
+        return Err(StatusCode::BadSecurityModeRejected);
+    }
+    let identity_token = match &args.user {
+        Some(user) => IdentityToken::UserName(user.clone(), password(user)?),
+        None => IdentityToken::Anonymous,
+    };
+
+    let (session, event_loop) = client(args)?
+        .new_session_from_endpoint(
+            (
+                url,
+                security_policy.to_str(),
+                security_mode,
+                UserTokenPolicy::anonymous(),
+            ),
+            identity_token,
+        )
+        .await?;
+    let mut handle = event_loop.spawn();
+
+    // The event loop ends if the connection cannot be made
+    tokio::select! {
+        _ = session.wait_for_connection() => {}
+        status_code = &mut handle => {
+            let status_code = status_code.unwrap_or(StatusCode::BadUnexpectedError);
+            return Err(if status_code.is_good() {
+                StatusCode::BadNotConnected
+            } else {
+                status_code
+            });
+        }
+    }
+    Ok((session, handle))
+}
+
+/// Returns the password of the user from the environment, or prompts for it on the terminal.
+fn password(user: &str) -> Result<String, StatusCode> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+    eprint!("Password for {}: ", user);
+    let _ = io::stderr().flush();
+    let mut password = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut password)
+        .map_err(|_| StatusCode::BadIdentityTokenInvalid)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+async fn disconnect(session: Arc<Session>, handle: JoinHandle<StatusCode>) {
+    let _ = session.disconnect().await;
+    let _ = handle.await;
+}
+
+fn parse_node_id(node_id: &str) -> Result<NodeId, StatusCode> {
+    NodeId::from_str(node_id).map_err(|_| {
+        eprintln!("Cannot parse node id \"{}\"", node_id);
+        StatusCode::BadNodeIdInvalid
+    })
+}
+
+async fn endpoints(args: &Args, url: &str) -> Result<(), StatusCode> {
+    let endpoints = client(args)?.get_server_endpoints_from_url(url).await?;
+    match args.format {
+        Format::Json => print_json(&endpoints.encode_json(JsonEncoding::NonReversible)),
+        Format::Table => {
+            let rows = endpoints
+                .iter()
+                .map(|e| {
+                    let user_tokens = e
+                        .user_identity_tokens
+                        .iter()
+                        .flatten()
+                        .map(|t| format!("{:?}", t.token_type))
+                        .collect::<Vec<_>>();
+                    vec![
+                        e.endpoint_url.to_string(),
+                        SecurityPolicy::from_uri(e.security_policy_uri.as_ref())
+                            .to_str()
+                            .to_string(),
+                        e.security_mode.to_string(),
+                        user_tokens.join(", "),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            print_table(
+                &[
+                    "EndpointUrl",
+                    "SecurityPolicy",
+                    "SecurityMode",
+                    "UserTokens",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn browse(args: &Args, url: &str, node_id: &str) -> Result<(), StatusCode> {
+    let node_id = parse_node_id(node_id)?;
+    let (session, handle) = connect(args, url).await?;
+    let result = browse_references(&session, node_id).await;
+    disconnect(session, handle).await;
+    let references = result?;
+    match args.format {
+        Format::Json => print_json(&references.encode_json(JsonEncoding::NonReversible)),
+        Format::Table => {
+            let rows = references
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.node_id.to_string(),
+                        format!("{}:{}", r.browse_name.namespace_index, r.browse_name.name),
+                        r.display_name.to_string(),
+                        format!("{:?}", r.node_class),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            print_table(&["NodeId", "BrowseName", "DisplayName", "NodeClass"], &rows);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the forward hierarchical references of the node, continuing the browse for as long as
+/// the server holds some back.
+async fn browse_references(
+    session: &Session,
+    node_id: NodeId,
+) -> Result<Vec<ReferenceDescription>, StatusCode> {
+    let browse_description = BrowseDescription {
+        node_id,
+        browse_direction: BrowseDirection::Forward,
+        reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+        include_subtypes: true,
+        node_class_mask: NodeClassMask::all().bits(),
+        result_mask: BrowseDescriptionResultMask::all().bits(),
+    };
+    let result = session
+        .browse(&[browse_description])
+        .await?
+        .unwrap_or_default()
+        .pop()
+        .ok_or(StatusCode::BadUnexpectedError)?;
+    if result.status_code.is_bad() {
+        return Err(result.status_code);
+    }
+    let mut references = result.references.unwrap_or_default();
+    let mut continuation_point = result.continuation_point;
+    while !continuation_point.is_null() {
+        let Some(result) = session
+            .browse_next(false, &[continuation_point.clone()])
+            .await?
+            .and_then(|mut results| results.pop())
+        else {
+            break;
+        };
+        references.extend(result.references.unwrap_or_default());
+        continuation_point = result.continuation_point;
+    }
+    Ok(references)
+}
+
+async fn read(args: &Args, url: &str, node_ids: &[String]) -> Result<(), StatusCode> {
+    let node_ids = node_ids
+        .iter()
+        .map(|n| parse_node_id(n))
+        .collect::<Result<Vec<_>, _>>()?;
+    let nodes_to_read = node_ids
+        .iter()
+        .cloned()
+        .map(ReadValueId::from)
+        .collect::<Vec<_>>();
+    let (session, handle) = connect(args, url).await?;
+    let result = session
+        .read(&nodes_to_read, TimestampsToReturn::Both, 0.0)
+        .await;
+    disconnect(session, handle).await;
+    let values = result?;
+    match args.format {
+        Format::Json => print_json(
+            &node_ids
+                .iter()
+                .zip(&values)
+                .map(|(node_id, value)| value_json(node_id, value))
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        Format::Table => {
+            let rows = node_ids
+                .iter()
+                .zip(&values)
+                .map(|(node_id, value)| value_row(node_id, value))
+                .collect::<Vec<_>>();
+            print_table(&VALUE_HEADERS, &rows);
+        }
+    }
+    Ok(())
+}
+
+async fn write(args: &Args, url: &str, node_id: &str, value: &str) -> Result<(), StatusCode> {
+    let node_id = parse_node_id(node_id)?;
+    let (session, handle) = connect(args, url).await?;
+    let result = write_value(&session, &node_id, value).await;
+    disconnect(session, handle).await;
+    let status_code = result?;
+    match args.format {
+        Format::Json => {
+            let mut object = serde_json::Map::new();
+            object.insert("NodeId".into(), node_id.to_string().into());
+            object.insert(
+                "StatusCode".into(),
+                status_code.encode_json(JsonEncoding::NonReversible),
+            );
+            print_json(&object.into());
+        }
+        Format::Table => print_table(
+            &["NodeId", "Status"],
+            &[vec![node_id.to_string(), status_code.to_string()]],
+        ),
+    }
+    Ok(())
+}
+
+/// Writes the value to the node after converting it to the type of the node's current value.
+/// A node without a value is written a string.
+async fn write_value(
+    session: &Session,
+    node_id: &NodeId,
+    value: &str,
+) -> Result<StatusCode, StatusCode> {
+    let current = session
+        .read(
+            &[ReadValueId::from(node_id.clone())],
+            TimestampsToReturn::Neither,
+            0.0,
+        )
+        .await?
+        .pop()
+        .and_then(|v| v.value)
+        .unwrap_or_default();
+    let type_id = current.type_id();
+    let value = convert_value(value, type_id).ok_or_else(|| {
+        eprintln!("Cannot convert \"{}\" to {:?}", value, type_id);
+        StatusCode::BadTypeMismatch
+    })?;
+    let results = session
+        .write(&[WriteValue {
+            node_id: node_id.clone(),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            value: value.into(),
+        }])
+        .await?;
+    results
+        .first()
+        .copied()
+        .ok_or(StatusCode::BadUnexpectedError)
+}
+
+/// Converts the text to a value of the type, or to a string when there is no type.
+fn convert_value(value: &str, type_id: VariantTypeId) -> Option<Variant> {
+    match type_id {
+        VariantTypeId::Empty => Some(Variant::from(value)),
+        type_id => match Variant::from(value).cast(type_id) {
+            Variant::Empty => None,
+            value => Some(value),
+        },
+    }
+}
+
+async fn subscribe(args: &Args, url: &str, node_ids: &[String]) -> Result<(), StatusCode> {
+    let node_ids = node_ids
+        .iter()
+        .map(|n| parse_node_id(n))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (session, handle) = connect(args, url).await?;
+    let result = print_changes(args, &session, node_ids).await;
+    disconnect(session, handle).await;
+    result
+}
+
+/// Monitors the nodes and prints their values as they arrive, until Ctrl-C is pressed.
+async fn print_changes(
+    args: &Args,
+    session: &Arc<Session>,
+    node_ids: Vec<NodeId>,
+) -> Result<(), StatusCode> {
+    let subscription = session
+        .subscribe(Duration::from_millis(args.interval))
+        .await?;
+    let mut changes = subscription.changes();
+    for node_id in node_ids {
+        subscription.monitor_value(node_id, |_| {}).await?;
+    }
+    if args.format == Format::Table {
+        println!("{}", VALUE_HEADERS.join("  "));
+    }
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            change = changes.next() => {
+                let Some((node_id, value)) = change else {
+                    break;
+                };
+                match args.format {
+                    Format::Json => println!("{}", value_json(&node_id, &value)),
+                    Format::Table => println!("{}", value_row(&node_id, &value).join("  ")),
+                }
+            }
+        }
+    }
+    let _ = subscription.delete().await;
+    Ok(())
+}
+
+fn gencert(args: &Args) -> Result<(), StatusCode> {
+    let certificate_store = CertificateStore::new(&args.pki_path);
+    let x509_data = X509Data {
+        key_size: 2048,
+        common_name: "OPC UA Command Line Client".to_string(),
+        organization: "OPC UA for Rust".to_string(),
+        organizational_unit: "OPC UA Command Line Client".to_string(),
+        country: "IE".to_string(),
+        state: "Dublin".to_string(),
+        alt_host_names: X509Data::alt_host_names(APPLICATION_URI, None, true, true, false),
+        certificate_duration_days: 365,
+    };
+    certificate_store
+        .create_and_store_application_instance_cert(&x509_data, args.overwrite)
+        .map_err(|err| {
+            eprintln!("Certificate creation failed - {}", err);
+            StatusCode::BadCertificateInvalid
+        })?;
+    println!(
+        "Certificate and private key have been written to {} and {}",
+        certificate_store.own_certificate_path().display(),
+        certificate_store.own_private_key_path().display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::parse_args(pico_args::Arguments::from_vec(
+            args.iter().map(OsString::from).collect(),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let args = parse(&["read", "opc.tcp://localhost:4855/", "ns=2;s=v1"]);
+        assert!(!args.help);
+        assert!(!args.verbose);
+        assert_eq!(args.format, Format::Table);
+        assert_eq!(args.pki_path, PathBuf::from(DEFAULT_PKI_PATH));
+        assert_eq!(args.security_policy, DEFAULT_SECURITY_POLICY);
+        assert_eq!(args.security_mode, DEFAULT_SECURITY_MODE);
+        assert!(args.user.is_none());
+        assert_eq!(args.interval, DEFAULT_INTERVAL);
+        assert_eq!(args.command.as_deref(), Some("read"));
+        assert_eq!(args.free, ["opc.tcp://localhost:4855/", "ns=2;s=v1"]);
+    }
+
+    #[test]
+    fn parse_options() {
+        let args = parse(&[
+            "subscribe",
+            "--json",
+            "--security-policy",
+            "Basic256Sha256",
+            "--security-mode",
+            "SignAndEncrypt",
+            "--user",
+            "sample1",
+            "--interval",
+            "250",
+            "opc.tcp://localhost:4855/",
+            "-v",
+            "ns=2;s=v1",
+        ]);
+        assert!(args.verbose);
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.security_policy, "Basic256Sha256");
+        assert_eq!(args.security_mode, "SignAndEncrypt");
+        assert_eq!(args.user.as_deref(), Some("sample1"));
+        assert_eq!(args.interval, 250);
+        assert_eq!(args.command.as_deref(), Some("subscribe"));
+        assert_eq!(args.free, ["opc.tcp://localhost:4855/", "ns=2;s=v1"]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        // Not a number
+        assert!(Args::parse_args(pico_args::Arguments::from_vec(vec![
+            "subscribe".into(),
+            "--interval".into(),
+            "fast".into(),
+        ]))
+        .is_err());
+        // No command
+        let args = parse(&["--help"]);
+        assert!(args.help);
+        assert!(args.command.is_none());
+        // The password is not an option, so it is left over as an argument
+        let args = parse(&["read", "--password", "secret"]);
+        assert_eq!(args.free, ["--password", "secret"]);
+    }
+
+    #[test]
+    fn convert_values() {
+        assert_eq!(
+            convert_value("abc", VariantTypeId::Empty),
+            Some(Variant::from("abc"))
+        );
+        assert_eq!(
+            convert_value("abc", VariantTypeId::String),
+            Some(Variant::from("abc"))
+        );
+        assert_eq!(
+            convert_value("42", VariantTypeId::Int32),
+            Some(Variant::Int32(42))
+        );
+        assert_eq!(
+            convert_value("-1", VariantTypeId::SByte),
+            Some(Variant::SByte(-1))
+        );
+        assert_eq!(
+            convert_value("65535", VariantTypeId::UInt16),
+            Some(Variant::UInt16(65535))
+        );
+        assert_eq!(
+            convert_value("1.5", VariantTypeId::Double),
+            Some(Variant::Double(1.5))
+        );
+        assert_eq!(
+            convert_value("true", VariantTypeId::Boolean),
+            Some(Variant::Boolean(true))
+        );
+        assert_eq!(convert_value("abc", VariantTypeId::Int32), None);
+        assert_eq!(convert_value("-1", VariantTypeId::UInt32), None);
+        assert_eq!(convert_value("256", VariantTypeId::Byte), None);
+    }
+}
@@ -0,0 +1,82 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2024 Adam Lock
+
+//! Prints the results of the commands, either as a table for people or as JSON for scripts.
+
+use opcua::types::{DataValue, JsonEncoder, JsonEncoding, JsonValue, NodeId};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+/// Prints the rows under the headers, with each column as wide as its widest cell.
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let headers = headers.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+    print_row(&headers, &widths);
+    let rule = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    print_row(&rule, &widths);
+    rows.iter().for_each(|row| print_row(row, &widths));
+}
+
+fn print_row(cells: &[String], widths: &[usize]) {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("{}", line.trim_end());
+}
+
+/// Prints the value as indented JSON.
+pub fn print_json(value: &JsonValue) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    );
+}
+
+/// The cells of a value of a node, for the table of `read` and `subscribe`.
+pub fn value_row(node_id: &NodeId, data_value: &DataValue) -> Vec<String> {
+    vec![
+        node_id.to_string(),
+        data_value
+            .value
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+        data_value.status().to_string(),
+        data_value
+            .source_timestamp
+            .as_ref()
+            .map(|t| t.to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+pub const VALUE_HEADERS: [&str; 4] = ["NodeId", "Value", "Status", "SourceTimestamp"];
+
+/// The JSON of a value of a node, for `read` and `subscribe`.
+pub fn value_json(node_id: &NodeId, data_value: &DataValue) -> JsonValue {
+    let mut object = serde_json::Map::new();
+    object.insert("NodeId".into(), node_id.to_string().into());
+    object.insert(
+        "DataValue".into(),
+        data_value.encode_json(JsonEncoding::NonReversible),
+    );
+    object.into()
+}
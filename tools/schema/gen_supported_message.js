@@ -5,6 +5,20 @@ var xml2js = require("xml2js");
 var settings = require("./settings");
 let util = require("./util");
 
+/// Messages that are only compiled when the named cargo feature is on
+const FEATURE_GATED_MESSAGES = {
+    "query-services": ["QueryFirstRequest", "QueryFirstResponse", "QueryNextRequest", "QueryNextResponse"],
+    "node-management-services": [
+        "AddNodesRequest", "AddNodesResponse", "AddReferencesRequest", "AddReferencesResponse",
+        "DeleteNodesRequest", "DeleteNodesResponse", "DeleteReferencesRequest", "DeleteReferencesResponse",
+    ],
+};
+
+function feature_cfg(message_type, indent) {
+    let feature = _.findKey(FEATURE_GATED_MESSAGES, types => types.includes(message_type));
+    return feature ? `${indent}#[cfg(feature = "${feature}")]\n` : "";
+}
+
 function generate_supported_message(message_types) {
     var file_name = "supported_message.rs";
     var file_path = `${settings.rs_supported_message_dir}/${file_name}`;
@@ -34,8 +48,8 @@ pub use crate::core::comms::tcp_types::AcknowledgeMessage;
 /// The first form just handles the trailing comma after the last entry to save some pointless
 /// editing when new messages are added to the list.
 macro_rules! supported_messages_enum {
-    [ $( $x:ident, ) * ] => (supported_messages_enum![ $( $x ),* ];);
-    [ $( $x:ident ), * ] => {
+    [ $( $(#[$m:meta])* $x:ident, ) * ] => (supported_messages_enum![ $( $(#[$m])* $x ),* ];);
+    [ $( $(#[$m:meta])* $x:ident ), * ] => {
         #[derive(Debug, PartialEq, Clone)]
         pub enum SupportedMessage {
             /// An invalid request / response of some form
@@ -43,7 +57,7 @@ macro_rules! supported_messages_enum {
             /// Acknowledge message
             AcknowledgeMessage(Box<AcknowledgeMessage>),
             /// Other messages
-            $( $x(Box<$x>), )*
+            $( $(#[$m])* $x(Box<$x>), )*
         }
 
         impl BinaryEncoder <SupportedMessage> for SupportedMessage {
@@ -53,7 +67,7 @@ macro_rules! supported_messages_enum {
                         panic!("Unsupported message byte_len {:?}", object_id);
                     },
                     SupportedMessage::AcknowledgeMessage(value) => value.byte_len(),
-                    $( SupportedMessage::$x(value) => value.byte_len(), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.byte_len(), )*
                 }
             }
 
@@ -63,7 +77,7 @@ macro_rules! supported_messages_enum {
                         panic!("Unsupported message encode {:?}", object_id);
                     },
                     SupportedMessage::AcknowledgeMessage(value) => value.encode(stream),
-                    $( SupportedMessage::$x(value) => value.encode(stream), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.encode(stream), )*
                 }
             }
 
@@ -78,6 +92,7 @@ macro_rules! supported_messages_enum {
         }
 
         $(
+        $(#[$m])*
         impl Into<SupportedMessage> for $x {
             fn into(self) -> SupportedMessage { SupportedMessage::$x(Box::new(self)) }
         }
@@ -92,7 +107,16 @@ macro_rules! supported_messages_enum {
                     SupportedMessage::AcknowledgeMessage(value) => {
                         panic!("Unsupported message node_id {:?}", value);
                     },
-                    $( SupportedMessage::$x(value) => value.object_id().into(), )*
+                    $( $(#[$m])* SupportedMessage::$x(value) => value.object_id().into(), )*
+                }
+            }
+
+            /// The name of the message's type, e.g. `ReadRequest`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    SupportedMessage::Invalid(_) => "Invalid",
+                    SupportedMessage::AcknowledgeMessage(_) => "AcknowledgeMessage",
+                    $( $(#[$m])* SupportedMessage::$x(_) => stringify!($x), )*
                 }
             }
         }
@@ -115,7 +139,7 @@ impl SupportedMessage {
 `;
     _.each(message_types, message_type => {
         if (message_type.endsWith("Request")) {
-            contents += `            SupportedMessage::${message_type}(_) => true,
+            contents += feature_cfg(message_type, "            ") + `            SupportedMessage::${message_type}(_) => true,
 `;
         }
     });
@@ -128,7 +152,7 @@ impl SupportedMessage {
 `;
     _.each(message_types, message_type => {
         if (message_type.endsWith("Request")) {
-            contents += `            SupportedMessage::${message_type}(r) => &r.request_header,
+            contents += feature_cfg(message_type, "            ") + `            SupportedMessage::${message_type}(r) => &r.request_header,
 `;
         }
     });
@@ -141,7 +165,7 @@ impl SupportedMessage {
 `;
     _.each(message_types, message_type => {
         if (message_type.endsWith("Response") || message_type === "ServiceFault") {
-            contents += `            SupportedMessage::${message_type}(_) => true,
+            contents += feature_cfg(message_type, "            ") + `            SupportedMessage::${message_type}(_) => true,
 `;
         }
     });
@@ -154,7 +178,7 @@ impl SupportedMessage {
 `;
     _.each(message_types, message_type => {
         if (message_type.endsWith("Response") || message_type === "ServiceFault") {
-            contents += `            SupportedMessage::${message_type}(r) => &r.response_header,
+            contents += feature_cfg(message_type, "            ") + `            SupportedMessage::${message_type}(r) => &r.response_header,
 `;
         }
     });
@@ -168,7 +192,7 @@ impl SupportedMessage {
 `;
 
     _.each(message_types, message_type => {
-        contents += `            ObjectId::${message_type}_Encoding_DefaultBinary => {
+        contents += feature_cfg(message_type, "            ") + `            ObjectId::${message_type}_Encoding_DefaultBinary => {
                 ${message_type}::decode(stream, decoding_options)?.into()
             }
 `;
@@ -188,7 +212,7 @@ supported_messages_enum![
 `;
 
     _.each(message_types, message_type => {
-        contents += `    ${message_type},
+        contents += feature_cfg(message_type, "    ") + `    ${message_type},
 `;
     });
 
@@ -17,6 +17,26 @@ const IGNORED_TYPES = [
     "MethodNode", "ViewNode", "DataTypeNode", "ReferenceNode",
 ];
 
+/// Types that are only compiled when the named cargo feature is on. Minimal clients can leave them out.
+const FEATURE_GATED_TYPES = {
+    "query-services": [
+        "NodeTypeDescription", "ParsingResult", "QueryDataDescription", "QueryDataSet",
+        "QueryFirstRequest", "QueryFirstResponse", "QueryNextRequest", "QueryNextResponse",
+    ],
+    "node-management-services": [
+        "AddNodesItem", "AddNodesRequest", "AddNodesResponse", "AddNodesResult",
+        "AddReferencesItem", "AddReferencesRequest", "AddReferencesResponse",
+        "DeleteNodesItem", "DeleteNodesRequest", "DeleteNodesResponse",
+        "DeleteReferencesItem", "DeleteReferencesRequest", "DeleteReferencesResponse",
+    ],
+    "gds-types": ["TrustListDataType"],
+};
+
+function feature_cfg(type_name) {
+    let feature = _.findKey(FEATURE_GATED_TYPES, types => types.includes(type_name));
+    return feature ? `#[cfg(feature = "${feature}")]\n` : "";
+}
+
 // Modules that need to be imported for structs that reference the following types
 const BASIC_TYPES_IMPORT_MAP = {
     // "basic_types": ["Boolean", "Int32", "UInt32", "Double", "Float", "Int16", "UInt16", "Byte", "SByte"],
@@ -70,7 +90,7 @@ const JSON_SERIALIZED_TYPES = [
     "ConfigurationVersionDataType", "DataSetMetaDataType", "StructureDescription",
     "EnumDescription", "SimpleTypeDescription", "StructureDefinition", "EnumDefinition",
     "FieldMetaData", "KeyValuePair", "DataSetFieldFlags", "StructureType", "StructureField",
-    "EnumField"
+    "EnumField", "AggregateFilter", "AggregateConfiguration"
 ];
 
 // The map from OPC UA types to their corresponding Rust types.
@@ -135,8 +155,9 @@ exports.from_xml = (config) => {
                     let fields_to_add = [];
                     let fields_to_hide = [];
                     _.each(element["opc:Field"], field => {
-                        // Convert field name to snake case
-                        let field_name = convertFieldName(field["$"]["Name"]);
+                        // Convert field name to snake case. The original name is the JSON field name
+                        let json_name = field["$"]["Name"];
+                        let field_name = convertFieldName(json_name);
 
                         // Strip namespace off the type
                         let type = massageTypeName(field["$"]["TypeName"].split(":")[1]);
@@ -145,6 +166,7 @@ exports.from_xml = (config) => {
                         if (_.has(field["$"], "LengthField")) {
                             fields_to_add.push({
                                 name: field_name,
+                                json_name: json_name,
                                 type: `Option<Vec<${type}>>`,
                                 contained_type: type,
                                 inner_type: type,
@@ -154,6 +176,7 @@ exports.from_xml = (config) => {
                         } else {
                             fields_to_add.push({
                                 name: field_name,
+                                json_name: json_name,
                                 type: type,
                                 contained_type: type
                             })
@@ -405,7 +428,7 @@ pub use self::impls::*;
 `;
     _.each(structured_types, structured_type => {
         let mod_name = _.snakeCase(structured_type.name);
-        contents += `mod ${mod_name};
+        contents += `${feature_cfg(structured_type.name)}mod ${mod_name};
 `
     });
 
@@ -413,7 +436,7 @@ pub use self::impls::*;
 
     _.each(structured_types, structured_type => {
         let mod_name = _.snakeCase(structured_type.name);
-        contents += `pub use self::${mod_name}::*;
+        contents += `${feature_cfg(structured_type.name)}pub use self::${mod_name}::*;
 `
     });
 
@@ -446,6 +469,30 @@ impl BinaryEncoder<${enum_type.name}> for ${enum_type.name} {
         Ok(${enum_type.name}::from_bits_truncate(${enum_type.type}::decode(stream, decoding_options)?))
     }
 }
+
+impl XmlEncoder for ${enum_type.name} {
+    fn xml_type_name() -> String {
+        "${enum_type.name}".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        self.bits().encode_xml(name)
+    }
+
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(${enum_type.name}::from_bits_truncate(${enum_type.type}::decode_xml(element, decoding_options)?))
+    }
+}
+
+impl JsonEncoder for ${enum_type.name} {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        self.bits().encode_json(encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(${enum_type.name}::from_bits_truncate(${enum_type.type}::decode_json(value, decoding_options)?))
+    }
+}
 `
 
     return contents;
@@ -464,7 +511,9 @@ function generate_enum_types(bsd_file, enums, rs_types_dir, config) {
 use std::io::{Read, Write};
 use crate::types::{
     encoding::*,
+    json::*,
     status_codes::StatusCode,
+    xml::*,
 };
 use bitflags;
 `;
@@ -520,6 +569,74 @@ impl BinaryEncoder<${enum_type.name}> for ${enum_type.name} {
             ${value.value} => Ok(Self::${value.name}),`;
             });
 
+            contents += `
+            v => {
+                error!("Invalid value {} for enum ${enum_type.name}", v);
+                ${enum_type.error_code}
+            }
+        }
+    }
+}
+
+impl XmlEncoder for ${enum_type.name} {
+    fn xml_type_name() -> String {
+        "${enum_type.name}".into()
+    }
+
+    fn encode_xml(&self, name: &str) -> Element {
+        let value_name = match self {`;
+
+            _.each(enum_type.values, (value) => {
+                contents += `
+            Self::${value.name} => "${value.name}",`;
+            });
+
+            contents += `
+        };
+        write_xml_enum(name, value_name, *self as i64)
+    }
+
+    fn decode_xml(element: &Element, _: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_xml_enum(element)?;
+        match value {`;
+
+            _.each(enum_type.values, (value) => {
+                contents += `
+            ${value.value} => Ok(Self::${value.name}),`;
+            });
+
+            contents += `
+            v => {
+                error!("Invalid value {} for enum ${enum_type.name}", v);
+                ${enum_type.error_code}
+            }
+        }
+    }
+}
+
+impl JsonEncoder for ${enum_type.name} {
+    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let name = match self {`;
+
+            _.each(enum_type.values, (value) => {
+                contents += `
+            Self::${value.name} => "${value.name}",`;
+            });
+
+            contents += `
+        };
+        write_json_enum(name, *self as i64, encoding)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        let value = read_json_enum(value, decoding_options)?;
+        match value {`;
+
+            _.each(enum_type.values, (value) => {
+                contents += `
+            ${value.value} => Ok(Self::${value.name}),`;
+            });
+
             contents += `
             v => {
                 error!("Invalid value {} for enum ${enum_type.name}", v);
@@ -544,6 +661,8 @@ function generate_type_imports(structured_types, fields_to_add, fields_to_hide,
 use crate::types::{
     encoding::*,
     basic_types::*,
+    xml::*,
+    json::*,
 `;
 
     if (has_message_info) {
@@ -741,9 +860,93 @@ impl BinaryEncoder<${structured_type.name}> for ${structured_type.name} {
 }
 `;
 
+    contents += generate_xml_encoder(structured_type);
+    contents += generate_json_encoder(structured_type);
+
     util.write_to_file(file_path, contents);
 }
 
+function generate_xml_encoder(structured_type) {
+    const fields = _.filter(structured_type.fields_to_add, field => !_.includes(structured_type.fields_to_hide, field.name));
+
+    let contents = `
+impl XmlEncoder for ${structured_type.name} {
+    fn xml_type_name() -> String {
+        "${structured_type.name}".into()
+    }
+
+`;
+    if (fields.length > 0) {
+        contents += `    fn encode_xml(&self, name: &str) -> Element {
+        let mut element = Element::new(name);
+`;
+        _.each(fields, field => {
+            contents += `        write_xml_field(&mut element, "${field.json_name}", &self.${field.name});\n`;
+        });
+        contents += `        element
+    }
+`;
+    } else {
+        contents += `    fn encode_xml(&self, name: &str) -> Element {
+        Element::new(name)
+    }
+`;
+    }
+
+    contents += `
+    #[allow(unused_variables)]
+    fn decode_xml(element: &Element, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        Ok(${structured_type.name} {
+`;
+    _.each(fields, field => {
+        contents += `            ${field.name}: read_xml_field(element, "${field.json_name}", decoding_options)?,\n`;
+    });
+    contents += `        })
+    }
+}
+`;
+    return contents;
+}
+
+function generate_json_encoder(structured_type) {
+    const fields = _.filter(structured_type.fields_to_add, field => !_.includes(structured_type.fields_to_hide, field.name));
+
+    let contents = `
+impl JsonEncoder for ${structured_type.name} {
+`;
+    if (fields.length > 0) {
+        contents += `    fn encode_json(&self, encoding: JsonEncoding) -> JsonValue {
+        let mut object = JsonObject::new();
+`;
+        _.each(fields, field => {
+            contents += `        write_json_field(&mut object, "${field.json_name}", &self.${field.name}, encoding);\n`;
+        });
+        contents += `        JsonValue::Object(object)
+    }
+`;
+    } else {
+        contents += `    fn encode_json(&self, _: JsonEncoding) -> JsonValue {
+        JsonValue::Object(JsonObject::new())
+    }
+`;
+    }
+
+    contents += `
+    #[allow(unused_variables)]
+    fn decode_json(value: &JsonValue, decoding_options: &DecodingOptions) -> EncodingResult<Self> {
+        check_json_object(value)?;
+        Ok(${structured_type.name} {
+`;
+    _.each(fields, field => {
+        contents += `            ${field.name}: read_json_field(value, "${field.json_name}", decoding_options)?,\n`;
+    });
+    contents += `        })
+    }
+}
+`;
+    return contents;
+}
+
 function generate_union_type_file(bsd_file, structured_types, structured_type, rs_types_dir) {
     let file_name = _.snakeCase(structured_type.name) + ".rs";
     let file_path = `${rs_types_dir}/${file_name}`;